@@ -1,51 +1,70 @@
+use std::collections::BTreeMap;
 use std::time::Duration;
 
 use chrono::{DateTime, Utc};
-use reqwest::{Client, Response, Url};
+use reqwest::{
+    header::{ETAG, IF_MATCH, IF_NONE_MATCH},
+    Client, Response, Url,
+};
 use runinator_comm::{ActionCommand, ActionDispatchRecord};
 use runinator_models::json;
 use runinator_models::pipelines::PipelineBundle;
 use runinator_models::value::Value;
 use runinator_models::{
     api_routes::{
-        api_approval_command, api_artifact_download, api_replica_heartbeat, api_replica_offline,
-        api_replica_providers, api_run, api_run_artifacts, api_run_chunks,
+        api_approval_command, api_artifact_download, api_job, api_replica_heartbeat,
+        api_replica_offline, api_replica_providers, api_run, api_run_artifacts, api_run_chunks,
         api_scheduler_action_dispatch_failed, api_scheduler_action_dispatch_published,
         api_scheduler_ready_node_process, api_scheduler_workflow_run_claim_release,
-        api_scheduler_workflow_run_claim_renew, api_workflow, api_workflow_duplicate,
-        api_workflow_node_run, api_workflow_node_run_artifacts, api_workflow_node_run_chunks,
-        api_workflow_node_run_claim, api_workflow_node_run_release, api_workflow_node_transitions,
-        api_workflow_run, api_workflow_run_artifacts, api_workflow_run_command,
-        api_workflow_run_nodes, api_workflow_run_rename, api_workflow_run_replay,
-        api_workflow_run_transitions, api_workflow_runs, api_workflow_trigger,
-        api_workflow_trigger_runs, api_workflow_triggers, API_APPROVALS, API_AUTH_CONFIG,
-        API_AUTH_LOGIN, API_AUTH_LOGOUT, API_AUTH_REFRESH, API_CREDENTIALS, API_IDEMPOTENCY_KEYS,
-        API_PACKS_IMPORT, API_PROVIDERS, API_REPLICAS, API_RUNS, API_SCHEDULER_ACTION_DISPATCHES,
+        api_scheduler_workflow_run_claim_renew, api_workflow, api_workflow_backfill_cancel,
+        api_workflow_disable, api_workflow_duplicate, api_workflow_enable, api_workflow_node_run,
+        api_workflow_node_run_artifacts, api_workflow_node_run_chunks, api_workflow_node_run_claim,
+        api_workflow_node_run_release, api_workflow_node_transitions, api_workflow_pending,
+        api_workflow_pending_review, api_workflow_protection, api_workflow_retention,
+        api_workflow_revert, api_workflow_revisions, api_workflow_run, api_workflow_run_artifacts,
+        api_workflow_run_command, api_workflow_run_nodes, api_workflow_run_outputs,
+        api_workflow_run_rename, api_workflow_run_replay, api_workflow_run_transitions,
+        api_workflow_runs, api_workflow_template, api_workflow_template_instantiate,
+        api_workflow_trigger, api_workflow_trigger_backfill, api_workflow_trigger_runs,
+        api_workflow_triggers, API_APPROVALS, API_AUTH_CONFIG, API_AUTH_LOGIN, API_AUTH_LOGOUT,
+        API_AUTH_REFRESH, API_CLUSTER_PAUSE, API_CREDENTIALS, API_CRON_PREVIEW,
+        API_IDEMPOTENCY_KEYS, API_JOBS, API_PACKS_IMPORT, API_PROVIDERS, API_QUEUE_WAIT, API_READY,
+        API_REPLICAS, API_RUNS, API_SCHEDULER_ACTION_DISPATCHES,
         API_SCHEDULER_ACTION_DISPATCHES_CLAIM, API_SCHEDULER_ACTION_DISPATCHES_PENDING,
-        API_SCHEDULER_READY_NODES_CLAIM, API_SCHEDULER_WORKFLOW_RUNS_CLAIM,
-        API_SCHEDULER_WORKFLOW_TRIGGER_FIRINGS_CLAIM, API_SUPERVISOR_STATUS, API_WORKFLOWS,
-        API_WORKFLOWS_EXPORT, API_WORKFLOWS_IMPORT, API_WORKFLOWS_SIMULATE, API_WORKFLOWS_VALIDATE,
-        API_WORKFLOW_RUNS, API_WORKFLOW_TRIGGERS_DUE, WORKFLOW_JSON_IMPORT_RISK_ACK,
-        WORKFLOW_JSON_IMPORT_RISK_HEADER,
+        API_SCHEDULER_PREVIEW, API_SCHEDULER_READY_NODES_CLAIM, API_SCHEDULER_WORKFLOW_RUNS_CLAIM,
+        API_SCHEDULER_WORKFLOW_TRIGGER_FIRINGS_CLAIM, API_SCHEDULE_UPCOMING, API_STATS_PROVIDERS,
+        API_SUPERVISOR_STATUS, API_VERSION_PREFIX, API_WORKERS, API_WORKFLOWS, API_WORKFLOWS_BULK,
+        API_WORKFLOWS_EXPORT, API_WORKFLOWS_HASHES, API_WORKFLOWS_IMPORT, API_WORKFLOWS_OVERVIEW,
+        API_WORKFLOWS_SEARCH, API_WORKFLOWS_SIMULATE, API_WORKFLOWS_VALIDATE, API_WORKFLOW_RUNS,
+        API_WORKFLOW_RUN_HEALTH, API_WORKFLOW_TEMPLATES, API_WORKFLOW_TRIGGERS_DUE,
+        WORKFLOW_JSON_IMPORT_RISK_ACK, WORKFLOW_JSON_IMPORT_RISK_HEADER,
     },
     auth::{AuthConfigResponse, LoginRequest, LoginResponse, RefreshRequest},
+    backfills::WorkflowBackfill,
     billing::ScaleOrgNodesRequest,
     bundles::{Bundle, PackImportResult, ProviderBundle, SecretBundle},
+    cluster::{ClusterPause, ClusterPauseRequest},
+    jobs::{Job, JobRequest},
     orchestration::ReadyNodeRecord,
+    protection::{PendingWorkflowChange, WorkflowProtection},
     providers::ProviderMetadata,
     provisioning::{NodeBackendsResponse, ProvisionedGroup, ScaleNodesRequest, StopNodeRequest},
     replicas::{
         ReplicaHeartbeatRequest, ReplicaKind, ReplicaListResponse, ReplicaOfflineRequest,
         ReplicaProviderRegistration, ReplicaProviderRegistrationRequest, ReplicaRecord,
-        ReplicaRegistrationRequest, ReplicaStatus,
+        ReplicaRegistrationRequest, ReplicaStatus, WorkerSummary,
     },
+    retention::{UpdateWorkflowRetentionRequest, WorkflowRetention},
+    revisions::WorkflowRevision,
     runs::{RunStatus, RunSummary},
     settings::{SettingKind, SettingSummary},
+    templates::{InstantiateTemplateRequest, WorkflowTemplate},
     web::TaskResponse,
     workflows::{
-        WorkflowBundle, WorkflowDefinition, WorkflowNodeRun, WorkflowNodeRunArtifact,
-        WorkflowNodeRunChunk, WorkflowRun, WorkflowRunArtifact, WorkflowSimulateRequest,
-        WorkflowStatus, WorkflowTrigger,
+        WorkflowBundle, WorkflowDefinition, WorkflowDefinitionHash, WorkflowImportOptions,
+        WorkflowNodeRun, WorkflowNodeRunArtifact, WorkflowNodeRunChunk, WorkflowRun,
+        WorkflowRunArtifact, WorkflowRunOutput, WorkflowSearchPage, WorkflowSimulateRequest,
+        WorkflowStatus, WorkflowTrigger, WorkflowTriggerPreview,
     },
 };
 use uuid::Uuid;
@@ -53,7 +72,10 @@ use uuid::Uuid;
 use crate::{
     error::{ApiError, Result},
     locator::ServiceLocator,
-    types::{RunArtifactPayload, RunChunkPayload, RunStatusPayload, WorkflowNodeRunStatusPayload},
+    types::{
+        CachedWorkflowList, RunArtifactPayload, RunChunkPayload, RunStatusPayload,
+        WorkflowNodeRunStatusPayload,
+    },
 };
 
 /// Default cap on a single request's total wall-clock time. Bounds a hung or slow web service so a
@@ -144,6 +166,10 @@ where
         self.traced(self.client.post(url))
     }
 
+    fn http_put<U: reqwest::IntoUrl>(&self, url: U) -> reqwest::RequestBuilder {
+        self.traced(self.client.put(url))
+    }
+
     fn http_patch<U: reqwest::IntoUrl>(&self, url: U) -> reqwest::RequestBuilder {
         self.traced(self.client.patch(url))
     }
@@ -236,6 +262,14 @@ where
         Ok(response.json::<ReplicaRecord>().await?)
     }
 
+    /// probe the web service's readiness endpoint; errors when it is unreachable or not ready.
+    pub async fn check_ready(&self) -> Result<()> {
+        let url = self.build_url(API_READY).await?;
+        let response = self.http_get(url.clone()).send().await?;
+        Self::handle_response(url, response).await?;
+        Ok(())
+    }
+
     pub async fn mark_replica_offline(
         &self,
         replica_id: Uuid,
@@ -286,6 +320,17 @@ where
         Ok(response.json::<ReplicaListResponse>().await?)
     }
 
+    /// list worker replicas with their plugins, providers, and current load.
+    pub async fn fetch_workers(&self, status: Option<ReplicaStatus>) -> Result<Vec<WorkerSummary>> {
+        let mut url = self.build_url(API_WORKERS).await?;
+        if let Some(status) = status {
+            url.query_pairs_mut().append_pair("status", status.as_str());
+        }
+        let response = self.http_get(url.clone()).send().await?;
+        let response = Self::handle_response(url, response).await?;
+        Ok(response.json::<Vec<WorkerSummary>>().await?)
+    }
+
     /// list configured node-provisioning backends and the kinds they support.
     pub async fn fetch_node_backends(&self) -> Result<NodeBackendsResponse> {
         let url = self.build_url("/nodes/backends").await?;
@@ -437,6 +482,22 @@ where
         Ok(response.json::<WorkflowDefinition>().await?)
     }
 
+    /// a workflow with its `ETag`, the edit version to pass to [`Self::update_workflow_if_match`].
+    pub async fn fetch_workflow_tagged(
+        &self,
+        workflow_id: Uuid,
+    ) -> Result<(WorkflowDefinition, Option<String>)> {
+        let url = self.build_url(&api_workflow(workflow_id)).await?;
+        let response = self.http_get(url.clone()).send().await?;
+        let response = Self::handle_response(url, response).await?;
+        let etag = response
+            .headers()
+            .get(ETAG)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        Ok((response.json::<WorkflowDefinition>().await?, etag))
+    }
+
     pub async fn fetch_workflows(&self) -> Result<Vec<WorkflowDefinition>> {
         let url = self.build_url(API_WORKFLOWS).await?;
         let response = self.http_get(url.clone()).send().await?;
@@ -444,6 +505,116 @@ where
         Ok(response.json::<Vec<WorkflowDefinition>>().await?)
     }
 
+    /// the portable definition hash of every visible workflow, for drift checks against files.
+    pub async fn fetch_workflow_hashes(&self) -> Result<Vec<WorkflowDefinitionHash>> {
+        let url = self.build_url(API_WORKFLOWS_HASHES).await?;
+        let response = self.http_get(url.clone()).send().await?;
+        let response = Self::handle_response(url, response).await?;
+        Ok(response.json::<Vec<WorkflowDefinitionHash>>().await?)
+    }
+
+    /// one page of the visible workflows whose name, namespace, or definition contains `term`,
+    /// ignoring case.
+    pub async fn search_workflows(
+        &self,
+        term: &str,
+        limit: Option<usize>,
+        offset: Option<usize>,
+    ) -> Result<WorkflowSearchPage> {
+        let mut url = self.build_url(API_WORKFLOWS_SEARCH).await?;
+        url.query_pairs_mut().append_pair("q", term);
+        if let Some(limit) = limit {
+            url.query_pairs_mut()
+                .append_pair("limit", &limit.to_string());
+        }
+        if let Some(offset) = offset {
+            url.query_pairs_mut()
+                .append_pair("offset", &offset.to_string());
+        }
+        let response = self.http_get(url.clone()).send().await?;
+        let response = Self::handle_response(url, response).await?;
+        Ok(response.json::<WorkflowSearchPage>().await?)
+    }
+
+    /// the workflow templates visible to the caller.
+    pub async fn fetch_workflow_templates(&self) -> Result<Vec<WorkflowTemplate>> {
+        let url = self.build_url(API_WORKFLOW_TEMPLATES).await?;
+        let response = self.http_get(url.clone()).send().await?;
+        let response = Self::handle_response(url, response).await?;
+        Ok(response.json::<Vec<WorkflowTemplate>>().await?)
+    }
+
+    pub async fn fetch_workflow_template(&self, template_id: Uuid) -> Result<WorkflowTemplate> {
+        let url = self.build_url(&api_workflow_template(template_id)).await?;
+        let response = self.http_get(url.clone()).send().await?;
+        let response = Self::handle_response(url, response).await?;
+        Ok(response.json::<WorkflowTemplate>().await?)
+    }
+
+    /// create a template, or replace it when `template.id` is set.
+    pub async fn save_workflow_template(
+        &self,
+        template: &WorkflowTemplate,
+    ) -> Result<WorkflowTemplate> {
+        let (url, request) = match template.id {
+            Some(id) => {
+                let url = self.build_url(&api_workflow_template(id)).await?;
+                (url.clone(), self.http_patch(url))
+            }
+            None => {
+                let url = self.build_url(API_WORKFLOW_TEMPLATES).await?;
+                (url.clone(), self.http_post(url))
+            }
+        };
+        let response = request.json(template).send().await?;
+        let response = Self::handle_response(url, response).await?;
+        Ok(response.json::<WorkflowTemplate>().await?)
+    }
+
+    pub async fn delete_workflow_template(&self, template_id: Uuid) -> Result<TaskResponse> {
+        let url = self.build_url(&api_workflow_template(template_id)).await?;
+        let response = self.http_delete(url.clone()).send().await?;
+        let response = Self::handle_response(url, response).await?;
+        Ok(response.json::<TaskResponse>().await?)
+    }
+
+    /// create a workflow (and its cron trigger, if any) from a template.
+    pub async fn instantiate_workflow_template(
+        &self,
+        template_id: Uuid,
+        request: &InstantiateTemplateRequest,
+    ) -> Result<WorkflowBundle> {
+        let url = self
+            .build_url(&api_workflow_template_instantiate(template_id))
+            .await?;
+        let response = self.http_post(url.clone()).json(request).send().await?;
+        let response = Self::handle_response(url, response).await?;
+        Ok(response.json::<WorkflowBundle>().await?)
+    }
+
+    /// conditional list fetch for pollers: sends the cached etag and only downloads and decodes the
+    /// list when the server reports a change. returns whether `cache` was replaced.
+    pub async fn refresh_workflows(&self, cache: &mut CachedWorkflowList) -> Result<bool> {
+        let url = self.build_url(API_WORKFLOWS).await?;
+        let mut request = self.http_get(url.clone());
+        if let Some(etag) = &cache.etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        let response = request.send().await?;
+        if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+            return Ok(false);
+        }
+        let response = Self::handle_response(url, response).await?;
+        let etag = response
+            .headers()
+            .get(ETAG)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        cache.workflows = response.json::<Vec<WorkflowDefinition>>().await?;
+        cache.etag = etag;
+        Ok(true)
+    }
+
     pub async fn fetch_workflow_by_name(&self, name: &str) -> Result<WorkflowDefinition> {
         let mut url = self.build_url(API_WORKFLOWS).await?;
         url.query_pairs_mut().append_pair("name", name);
@@ -468,6 +639,180 @@ where
         Ok(response.json::<WorkflowDefinition>().await?)
     }
 
+    /// save an edit of a workflow fetched with `etag`. fails with a 409 [`ApiError::Http`] when
+    /// someone else saved the workflow in between.
+    pub async fn update_workflow_if_match(
+        &self,
+        workflow_id: Uuid,
+        workflow: &WorkflowDefinition,
+        etag: &str,
+    ) -> Result<WorkflowDefinition> {
+        let url = self.build_url(&api_workflow(workflow_id)).await?;
+        let response = self
+            .http_patch(url.clone())
+            .header(IF_MATCH, etag)
+            .json(workflow)
+            .send()
+            .await?;
+        let response = Self::handle_response(url, response).await?;
+        Ok(response.json::<WorkflowDefinition>().await?)
+    }
+
+    /// save `workflows` in one transaction. a rejected entry fails the whole call and nothing is
+    /// written.
+    pub async fn upsert_workflows(
+        &self,
+        workflows: &[WorkflowDefinition],
+    ) -> Result<runinator_models::workflows::WorkflowBulkResponse> {
+        let url = self.build_url(API_WORKFLOWS_BULK).await?;
+        let response = self.http_post(url.clone()).json(workflows).send().await?;
+        let response = Self::handle_response(url, response).await?;
+        Ok(response
+            .json::<runinator_models::workflows::WorkflowBulkResponse>()
+            .await?)
+    }
+
+    pub async fn delete_workflow(&self, workflow_id: Uuid) -> Result<TaskResponse> {
+        let url = self.build_url(&api_workflow(workflow_id)).await?;
+        let response = self.http_delete(url.clone()).send().await?;
+        let response = Self::handle_response(url, response).await?;
+        Ok(response.json::<TaskResponse>().await?)
+    }
+
+    /// a workflow's run-history retention override; `None` when it follows the global retention.
+    pub async fn fetch_workflow_retention(
+        &self,
+        workflow_id: Uuid,
+    ) -> Result<Option<WorkflowRetention>> {
+        let url = self.build_url(&api_workflow_retention(workflow_id)).await?;
+        let response = self.http_get(url.clone()).send().await?;
+        let response = Self::handle_response(url, response).await?;
+        Ok(response.json::<Option<WorkflowRetention>>().await?)
+    }
+
+    pub async fn set_workflow_retention(
+        &self,
+        workflow_id: Uuid,
+        retention_days: u32,
+    ) -> Result<WorkflowRetention> {
+        let url = self.build_url(&api_workflow_retention(workflow_id)).await?;
+        let response = self
+            .http_patch(url.clone())
+            .json(&UpdateWorkflowRetentionRequest { retention_days })
+            .send()
+            .await?;
+        let response = Self::handle_response(url, response).await?;
+        Ok(response.json::<WorkflowRetention>().await?)
+    }
+
+    pub async fn clear_workflow_retention(&self, workflow_id: Uuid) -> Result<TaskResponse> {
+        let url = self.build_url(&api_workflow_retention(workflow_id)).await?;
+        let response = self.http_delete(url.clone()).send().await?;
+        let response = Self::handle_response(url, response).await?;
+        Ok(response.json::<TaskResponse>().await?)
+    }
+
+    /// a workflow's saved revisions, newest first.
+    pub async fn fetch_workflow_revisions(
+        &self,
+        workflow_id: Uuid,
+    ) -> Result<Vec<WorkflowRevision>> {
+        let url = self.build_url(&api_workflow_revisions(workflow_id)).await?;
+        let response = self.http_get(url.clone()).send().await?;
+        let response = Self::handle_response(url, response).await?;
+        Ok(response.json::<Vec<WorkflowRevision>>().await?)
+    }
+
+    /// save revision `revision` of a workflow again; the revert is recorded as a new revision.
+    pub async fn revert_workflow(
+        &self,
+        workflow_id: Uuid,
+        revision: i64,
+    ) -> Result<WorkflowDefinition> {
+        let url = self
+            .build_url(&api_workflow_revert(workflow_id, revision))
+            .await?;
+        let response = self.http_post(url.clone()).send().await?;
+        let response = Self::handle_response(url, response).await?;
+        Ok(response.json::<WorkflowDefinition>().await?)
+    }
+
+    /// protect a workflow so its changes need a second approver.
+    pub async fn protect_workflow(&self, workflow_id: Uuid) -> Result<WorkflowProtection> {
+        let url = self
+            .build_url(&api_workflow_protection(workflow_id))
+            .await?;
+        let response = self.http_put(url.clone()).send().await?;
+        let response = Self::handle_response(url, response).await?;
+        Ok(response.json::<WorkflowProtection>().await?)
+    }
+
+    /// a protected workflow's changes awaiting review, oldest first.
+    pub async fn fetch_pending_workflow_changes(
+        &self,
+        workflow_id: Uuid,
+    ) -> Result<Vec<PendingWorkflowChange>> {
+        let url = self.build_url(&api_workflow_pending(workflow_id)).await?;
+        let response = self.http_get(url.clone()).send().await?;
+        let response = Self::handle_response(url, response).await?;
+        Ok(response.json::<Vec<PendingWorkflowChange>>().await?)
+    }
+
+    /// approve (and apply) or reject a pending change to a protected workflow.
+    pub async fn review_pending_workflow_change(
+        &self,
+        workflow_id: Uuid,
+        change_id: Uuid,
+        approve: bool,
+    ) -> Result<PendingWorkflowChange> {
+        let verb = if approve { "approve" } else { "reject" };
+        let url = self
+            .build_url(&api_workflow_pending_review(workflow_id, change_id, verb))
+            .await?;
+        let response = self.http_post(url.clone()).send().await?;
+        let response = Self::handle_response(url, response).await?;
+        Ok(response.json::<PendingWorkflowChange>().await?)
+    }
+
+    /// queue one action to run once without a stored workflow; poll the returned job with
+    /// [`Self::fetch_job`].
+    pub async fn submit_job(&self, request: &JobRequest) -> Result<Job> {
+        let url = self.build_url(API_JOBS).await?;
+        let response = self.http_post(url.clone()).json(request).send().await?;
+        let response = Self::handle_response(url, response).await?;
+        Ok(response.json::<Job>().await?)
+    }
+
+    pub async fn fetch_job(&self, job_id: Uuid) -> Result<Job> {
+        let url = self.build_url(&api_job(job_id)).await?;
+        let response = self.http_get(url.clone()).send().await?;
+        let response = Self::handle_response(url, response).await?;
+        Ok(response.json::<Job>().await?)
+    }
+
+    pub async fn fetch_cluster_pause(&self) -> Result<ClusterPause> {
+        let url = self.build_url(API_CLUSTER_PAUSE).await?;
+        let response = self.http_get(url.clone()).send().await?;
+        let response = Self::handle_response(url, response).await?;
+        Ok(response.json::<ClusterPause>().await?)
+    }
+
+    /// pause dispatch and/or execution across the cluster.
+    pub async fn pause_cluster(&self, request: &ClusterPauseRequest) -> Result<ClusterPause> {
+        let url = self.build_url(API_CLUSTER_PAUSE).await?;
+        let response = self.http_put(url.clone()).json(request).send().await?;
+        let response = Self::handle_response(url, response).await?;
+        Ok(response.json::<ClusterPause>().await?)
+    }
+
+    /// lift any cluster pause.
+    pub async fn resume_cluster(&self) -> Result<ClusterPause> {
+        let url = self.build_url(API_CLUSTER_PAUSE).await?;
+        let response = self.http_delete(url.clone()).send().await?;
+        let response = Self::handle_response(url, response).await?;
+        Ok(response.json::<ClusterPause>().await?)
+    }
+
     /// duplicate a workflow into a new version sharing its name, bumped by `bump`.
     pub async fn duplicate_workflow(
         &self,
@@ -481,6 +826,23 @@ where
         Ok(response.json::<WorkflowDefinition>().await?)
     }
 
+    /// flip a workflow's enabled flag without resending (and possibly clobbering) its definition.
+    pub async fn set_workflow_enabled(
+        &self,
+        workflow_id: Uuid,
+        enabled: bool,
+    ) -> Result<WorkflowDefinition> {
+        let path = if enabled {
+            api_workflow_enable(workflow_id)
+        } else {
+            api_workflow_disable(workflow_id)
+        };
+        let url = self.build_url(&path).await?;
+        let response = self.http_post(url.clone()).send().await?;
+        let response = Self::handle_response(url, response).await?;
+        Ok(response.json::<WorkflowDefinition>().await?)
+    }
+
     pub async fn validate_workflow(
         &self,
         workflow: &WorkflowDefinition,
@@ -513,8 +875,21 @@ where
     }
 
     /// POST a raw JSON workflow bundle after acknowledging that system breakage is possible.
-    pub async fn import_workflow_bundle(&self, bundle: &WorkflowBundle) -> Result<WorkflowBundle> {
-        let url = self.build_url(API_WORKFLOWS_IMPORT).await?;
+    /// `options` can import the bundle's cron schedules disabled or offset.
+    pub async fn import_workflow_bundle(
+        &self,
+        bundle: &WorkflowBundle,
+        options: &WorkflowImportOptions,
+    ) -> Result<WorkflowBundle> {
+        let mut url = self.build_url(API_WORKFLOWS_IMPORT).await?;
+        if options.disable_schedules {
+            url.query_pairs_mut()
+                .append_pair("disable_schedules", "true");
+        }
+        if let Some(offset) = options.schedule_offset_seconds {
+            url.query_pairs_mut()
+                .append_pair("schedule_offset_seconds", &offset.to_string());
+        }
         let response = self
             .http_post(url.clone())
             .header(
@@ -561,19 +936,45 @@ where
         self.import_bundle(bundle).await
     }
 
+    /// the export as a bundle, carrying up to `runs` finished runs per workflow as baselines.
     pub async fn export_workflow_bundle(
         &self,
         workflow_id: Option<Uuid>,
+        runs: Option<usize>,
     ) -> Result<WorkflowBundle> {
         let path = workflow_id
             .map(|id| format!("{}/export", api_workflow(id)))
             .unwrap_or_else(|| API_WORKFLOWS_EXPORT.into());
-        let url = self.build_url(&path).await?;
+        let mut url = self.build_url(&path).await?;
+        if let Some(runs) = runs {
+            url.query_pairs_mut().append_pair("runs", &runs.to_string());
+        }
         let response = self.http_get(url.clone()).send().await?;
         let response = Self::handle_response(url, response).await?;
         Ok(response.json::<WorkflowBundle>().await?)
     }
 
+    /// the export rendered server-side as a workflow file in `format` (`json` or `yaml`), ready to
+    /// write to disk and import again. `runs` adds baselines as in [`Self::export_workflow_bundle`].
+    pub async fn export_workflow_file(
+        &self,
+        workflow_id: Option<Uuid>,
+        format: &str,
+        runs: Option<usize>,
+    ) -> Result<String> {
+        let path = workflow_id
+            .map(|id| format!("{}/export", api_workflow(id)))
+            .unwrap_or_else(|| API_WORKFLOWS_EXPORT.into());
+        let mut url = self.build_url(&path).await?;
+        url.query_pairs_mut().append_pair("format", format);
+        if let Some(runs) = runs {
+            url.query_pairs_mut().append_pair("runs", &runs.to_string());
+        }
+        let response = self.http_get(url.clone()).send().await?;
+        let response = Self::handle_response(url, response).await?;
+        Ok(response.text().await?)
+    }
+
     pub async fn create_workflow_run(
         &self,
         workflow_id: Uuid,
@@ -650,6 +1051,32 @@ where
         Ok(response.json::<WorkflowTrigger>().await?)
     }
 
+    /// the schedule change saving `trigger` would make; nothing is written.
+    pub async fn preview_workflow_trigger_update(
+        &self,
+        trigger_id: Uuid,
+        trigger: &WorkflowTrigger,
+    ) -> Result<WorkflowTriggerPreview> {
+        let mut url = self.build_url(&api_workflow_trigger(trigger_id)).await?;
+        url.query_pairs_mut().append_pair("preview", "true");
+        let response = self.http_patch(url.clone()).json(trigger).send().await?;
+        let response = Self::handle_response(url, response).await?;
+        Ok(response.json::<WorkflowTriggerPreview>().await?)
+    }
+
+    /// validate a cron expression and list its next fire times; nothing is saved.
+    pub async fn preview_cron(
+        &self,
+        request: &runinator_models::workflows::CronPreviewRequest,
+    ) -> Result<runinator_models::workflows::CronPreview> {
+        let url = self.build_url(API_CRON_PREVIEW).await?;
+        let response = self.http_post(url.clone()).json(request).send().await?;
+        let response = Self::handle_response(url, response).await?;
+        Ok(response
+            .json::<runinator_models::workflows::CronPreview>()
+            .await?)
+    }
+
     pub async fn delete_workflow_trigger(&self, trigger_id: Uuid) -> Result<TaskResponse> {
         let url = self.build_url(&api_workflow_trigger(trigger_id)).await?;
         let response = self.http_delete(url.clone()).send().await?;
@@ -682,6 +1109,52 @@ where
         .map_err(|err| ApiError::UnexpectedResponse(err.to_string()))
     }
 
+    /// Replay the cron trigger's slots from `from` through `to`, keeping at most `concurrency`
+    /// backfilled runs open at once (server default 1).
+    pub async fn backfill_workflow_trigger(
+        &self,
+        trigger_id: Uuid,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        concurrency: Option<i64>,
+    ) -> Result<WorkflowBackfill> {
+        let mut url = self
+            .build_url(&api_workflow_trigger_backfill(trigger_id))
+            .await?;
+        {
+            let mut query = url.query_pairs_mut();
+            query.append_pair("from", &from.to_rfc3339());
+            query.append_pair("to", &to.to_rfc3339());
+            if let Some(concurrency) = concurrency {
+                query.append_pair("concurrency", &concurrency.to_string());
+            }
+        }
+        let response = self.http_post(url.clone()).send().await?;
+        let response = Self::handle_response(url, response).await?;
+        Ok(response.json::<WorkflowBackfill>().await?)
+    }
+
+    pub async fn fetch_workflow_backfills(
+        &self,
+        trigger_id: Uuid,
+    ) -> Result<Vec<WorkflowBackfill>> {
+        let url = self
+            .build_url(&api_workflow_trigger_backfill(trigger_id))
+            .await?;
+        let response = self.http_get(url.clone()).send().await?;
+        let response = Self::handle_response(url, response).await?;
+        Ok(response.json::<Vec<WorkflowBackfill>>().await?)
+    }
+
+    pub async fn cancel_workflow_backfill(&self, backfill_id: Uuid) -> Result<WorkflowBackfill> {
+        let url = self
+            .build_url(&api_workflow_backfill_cancel(backfill_id))
+            .await?;
+        let response = self.http_post(url.clone()).send().await?;
+        let response = Self::handle_response(url, response).await?;
+        Ok(response.json::<WorkflowBackfill>().await?)
+    }
+
     pub async fn create_workflow_run_with_debug(
         &self,
         workflow_id: Uuid,
@@ -803,6 +1276,106 @@ where
         Ok(response.json::<Vec<WorkflowRun>>().await?)
     }
 
+    pub async fn fetch_workflow_run_health(
+        &self,
+        period: runinator_models::run_health::SummaryPeriod,
+    ) -> Result<runinator_models::run_health::RunHealthReport> {
+        let mut url = self.build_url(API_WORKFLOW_RUN_HEALTH).await?;
+        url.query_pairs_mut().append_pair("period", period.as_str());
+        let response = self.http_get(url.clone()).send().await?;
+        let response = Self::handle_response(url, response).await?;
+        Ok(response
+            .json::<runinator_models::run_health::RunHealthReport>()
+            .await?)
+    }
+
+    pub async fn fetch_queue_wait(
+        &self,
+        hours: Option<i64>,
+    ) -> Result<runinator_models::queue_wait::QueueWaitReport> {
+        let mut url = self.build_url(API_QUEUE_WAIT).await?;
+        if let Some(hours) = hours {
+            url.query_pairs_mut()
+                .append_pair("hours", &hours.to_string());
+        }
+        let response = self.http_get(url.clone()).send().await?;
+        let response = Self::handle_response(url, response).await?;
+        Ok(response
+            .json::<runinator_models::queue_wait::QueueWaitReport>()
+            .await?)
+    }
+
+    /// running, queued, and projected cron work over the next `minutes` (server default when `None`).
+    pub async fn fetch_upcoming_schedule(
+        &self,
+        minutes: Option<i64>,
+    ) -> Result<runinator_models::upcoming::UpcomingSchedule> {
+        let mut url = self.build_url(API_SCHEDULE_UPCOMING).await?;
+        if let Some(minutes) = minutes {
+            url.query_pairs_mut()
+                .append_pair("minutes", &minutes.to_string());
+        }
+        let response = self.http_get(url.clone()).send().await?;
+        let response = Self::handle_response(url, response).await?;
+        Ok(response
+            .json::<runinator_models::upcoming::UpcomingSchedule>()
+            .await?)
+    }
+
+    /// dry-run the scheduler over the next `minutes` (server default when `None`): the cron firings
+    /// it would enqueue, blackout deferrals included, without enqueueing anything.
+    pub async fn preview_scheduler(
+        &self,
+        minutes: Option<i64>,
+    ) -> Result<runinator_models::upcoming::SchedulerPreview> {
+        let mut url = self.build_url(API_SCHEDULER_PREVIEW).await?;
+        if let Some(minutes) = minutes {
+            url.query_pairs_mut()
+                .append_pair("minutes", &minutes.to_string());
+        }
+        let response = self.http_get(url.clone()).send().await?;
+        let response = Self::handle_response(url, response).await?;
+        Ok(response
+            .json::<runinator_models::upcoming::SchedulerPreview>()
+            .await?)
+    }
+
+    /// run counts per provider action over each window in `hours` (the server defaults when empty).
+    pub async fn fetch_provider_stats(
+        &self,
+        hours: &[i64],
+    ) -> Result<runinator_models::provider_stats::ProviderStatsReport> {
+        let mut url = self.build_url(API_STATS_PROVIDERS).await?;
+        if !hours.is_empty() {
+            let windows = hours
+                .iter()
+                .map(|hours| hours.to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+            url.query_pairs_mut().append_pair("windows", &windows);
+        }
+        let response = self.http_get(url.clone()).send().await?;
+        let response = Self::handle_response(url, response).await?;
+        Ok(response
+            .json::<runinator_models::provider_stats::ProviderStatsReport>()
+            .await?)
+    }
+
+    pub async fn fetch_workflow_overview(
+        &self,
+        runs: Option<usize>,
+    ) -> Result<Vec<runinator_models::overview::WorkflowOverview>> {
+        let mut url = self.build_url(API_WORKFLOWS_OVERVIEW).await?;
+        if let Some(runs) = runs {
+            url.query_pairs_mut().append_pair("runs", &runs.to_string());
+        }
+        let response = self.http_get(url.clone()).send().await?;
+        let response = Self::handle_response(url, response).await?;
+        Ok(response
+            .json::<Vec<runinator_models::overview::WorkflowOverview>>()
+            .await?)
+    }
+
     pub async fn fetch_workflow_runs_by_name(
         &self,
         name: &str,
@@ -1225,6 +1798,35 @@ where
         Ok(response.json::<Vec<WorkflowRunArtifact>>().await?)
     }
 
+    pub async fn record_workflow_run_outputs(
+        &self,
+        workflow_run_id: Uuid,
+        outputs: &BTreeMap<String, Value>,
+    ) -> Result<Vec<WorkflowRunOutput>> {
+        let url = self
+            .build_url(&api_workflow_run_outputs(workflow_run_id))
+            .await?;
+        let response = self
+            .http_post(url.clone())
+            .json(&json!({ "outputs": outputs }))
+            .send()
+            .await?;
+        let response = Self::handle_response(url, response).await?;
+        Ok(response.json::<Vec<WorkflowRunOutput>>().await?)
+    }
+
+    pub async fn fetch_workflow_run_outputs(
+        &self,
+        workflow_run_id: Uuid,
+    ) -> Result<Vec<WorkflowRunOutput>> {
+        let url = self
+            .build_url(&api_workflow_run_outputs(workflow_run_id))
+            .await?;
+        let response = self.http_get(url.clone()).send().await?;
+        let response = Self::handle_response(url, response).await?;
+        Ok(response.json::<Vec<WorkflowRunOutput>>().await?)
+    }
+
     pub async fn fetch_workflow_run_transitions(
         &self,
         workflow_run_id: Uuid,
@@ -1468,12 +2070,16 @@ where
             url: base.clone(),
             source,
         })?;
-        let trimmed_path = path.trim_start_matches('/');
+        let trimmed_path = format!(
+            "{}/{}",
+            API_VERSION_PREFIX.trim_start_matches('/'),
+            path.trim_start_matches('/')
+        );
         base_url
-            .join(trimmed_path)
+            .join(&trimmed_path)
             .map_err(|source| ApiError::InvalidPath {
                 base: base_url.clone(),
-                path: trimmed_path.to_string(),
+                path: trimmed_path,
                 source,
             })
     }
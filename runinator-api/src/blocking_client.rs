@@ -7,11 +7,12 @@ use runinator_models::pipelines::PipelineBundle;
 use runinator_models::{
     api_routes::{
         api_workflow, api_workflow_duplicate, api_workflow_run_command, API_PACKS_IMPORT,
-        API_PROVIDERS, API_WORKFLOWS_IMPORT, API_WORKFLOWS_SIMULATE, API_WORKFLOWS_VALIDATE,
-        WORKFLOW_JSON_IMPORT_RISK_ACK, WORKFLOW_JSON_IMPORT_RISK_HEADER,
+        API_PROVIDERS, API_RUNS, API_VERSION_PREFIX, API_WORKFLOWS_IMPORT, API_WORKFLOWS_SIMULATE,
+        API_WORKFLOWS_VALIDATE, WORKFLOW_JSON_IMPORT_RISK_ACK, WORKFLOW_JSON_IMPORT_RISK_HEADER,
     },
     bundles::{Bundle, PackImportResult, ProviderBundle, SecretBundle},
     providers::ProviderMetadata,
+    runs::{RunStatus, RunSummary},
     semver::SemVerBump,
     web::TaskResponse,
     workflows::{WorkflowBundle, WorkflowDefinition, WorkflowSimulateRequest},
@@ -193,6 +194,13 @@ where
         Ok(response.json::<WorkflowBundle>()?)
     }
 
+    pub fn fetch_runs_by_status(&self, status: RunStatus) -> Result<Vec<RunSummary>> {
+        let url = self.build_url(&format!("{API_RUNS}?status={}", status.as_str()))?;
+        let response = self.http_get(url.clone()).send()?;
+        let response = Self::handle_response(url, response)?;
+        Ok(response.json::<Vec<RunSummary>>()?)
+    }
+
     pub fn pause_workflow_run(&self, workflow_run_id: Uuid) -> Result<TaskResponse> {
         self.post_workflow_run_command(workflow_run_id, "pause")
     }
@@ -225,12 +233,16 @@ where
             url: base.clone(),
             source,
         })?;
-        let trimmed_path = path.trim_start_matches('/');
+        let trimmed_path = format!(
+            "{}/{}",
+            API_VERSION_PREFIX.trim_start_matches('/'),
+            path.trim_start_matches('/')
+        );
         base_url
-            .join(trimmed_path)
+            .join(&trimmed_path)
             .map_err(|source| ApiError::InvalidPath {
                 base: base_url.clone(),
-                path: trimmed_path.to_string(),
+                path: trimmed_path,
                 source,
             })
     }
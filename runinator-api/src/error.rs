@@ -98,4 +98,13 @@ impl ApiError {
     {
         ApiError::Discovery(Box::new(error))
     }
+
+    /// the machine-readable `code` from a web service error body, e.g. `not_found` or `conflict`.
+    pub fn error_code(&self) -> Option<String> {
+        let ApiError::Http { message, .. } = self else {
+            return None;
+        };
+        let body: serde_json::Value = serde_json::from_str(message).ok()?;
+        body.get("code")?.as_str().map(str::to_string)
+    }
 }
@@ -11,8 +11,10 @@ pub use error::{ApiError, Result};
 pub use locator::{BlockingServiceLocator, ServiceLocator, StaticLocator};
 pub use replicas::{
     register_replica_provider, register_replica_session, spawn_replica_heartbeat,
-    spawn_replica_heartbeat_with_telemetry, ReplicaServiceConfig, ReplicaSession,
+    spawn_replica_heartbeat_with_live_attributes, spawn_replica_heartbeat_with_telemetry,
+    LiveAttributes, ReplicaServiceConfig, ReplicaSession,
 };
 pub use types::{
-    RunArtifactPayload, RunChunkPayload, RunStatusPayload, WorkflowNodeRunStatusPayload,
+    CachedWorkflowList, RunArtifactPayload, RunChunkPayload, RunStatusPayload,
+    WorkflowNodeRunStatusPayload,
 };
@@ -115,6 +115,10 @@ where
     })
 }
 
+/// heartbeat attributes sampled on every tick, for state that changes while the replica runs (e.g. a
+/// worker's in-flight action count). the keys of the returned object replace the static attributes'.
+pub type LiveAttributes = Arc<dyn Fn() -> Value + Send + Sync>;
+
 pub fn spawn_replica_heartbeat<L>(
     api_client: AsyncApiClient<L>,
     session: ReplicaSession,
@@ -134,6 +138,21 @@ pub fn spawn_replica_heartbeat_with_telemetry<L>(
     shutdown: Arc<Notify>,
     collector: Option<Arc<TelemetryCollector>>,
 ) -> JoinHandle<()>
+where
+    L: ServiceLocator + 'static,
+{
+    spawn_replica_heartbeat_with_live_attributes(api_client, session, shutdown, collector, None)
+}
+
+/// like [`spawn_replica_heartbeat_with_telemetry`], additionally merging `live` into the attributes
+/// of every heartbeat.
+pub fn spawn_replica_heartbeat_with_live_attributes<L>(
+    api_client: AsyncApiClient<L>,
+    session: ReplicaSession,
+    shutdown: Arc<Notify>,
+    collector: Option<Arc<TelemetryCollector>>,
+    live: Option<LiveAttributes>,
+) -> JoinHandle<()>
 where
     L: ServiceLocator + 'static,
 {
@@ -156,6 +175,15 @@ where
                         request.attributes =
                             attributes_with_telemetry(&session.config.attributes, collector);
                     }
+                    if let (Some(attributes), Some(live)) =
+                        (request.attributes.as_object_mut(), live.as_ref())
+                    {
+                        if let Value::Object(extra) = live() {
+                            for (key, value) in extra {
+                                attributes.insert(key, value);
+                            }
+                        }
+                    }
                     if let Err(err) = api_client
                         .heartbeat_replica(session.replica_id(), &request)
                         .await
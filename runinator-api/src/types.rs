@@ -1,7 +1,7 @@
 use runinator_models::value::Value;
 use runinator_models::{
     runs::{NewRunArtifact, NewRunChunk, RunStatus},
-    workflows::WorkflowStatus,
+    workflows::{WorkflowDefinition, WorkflowStatus},
 };
 use serde::{Deserialize, Serialize};
 
@@ -12,6 +12,8 @@ pub struct RunStatusPayload {
     pub output_json: Option<Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub worker_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,3 +27,11 @@ pub struct WorkflowNodeRunStatusPayload {
 
 pub type RunChunkPayload = NewRunChunk;
 pub type RunArtifactPayload = NewRunArtifact;
+
+/// the workflow list a poller last saw, keyed by the server's etag. refreshed through
+/// `AsyncApiClient::refresh_workflows`, which leaves it untouched while the list is unchanged.
+#[derive(Debug, Clone, Default)]
+pub struct CachedWorkflowList {
+    pub etag: Option<String>,
+    pub workflows: Vec<WorkflowDefinition>,
+}
@@ -318,6 +318,23 @@ async fn mark_all<T: DatabaseImpl>(db: &T, config: &Config) -> Result<(), Sendab
             info!(table = %table, count, "marked row(s) for archival");
         }
     }
+    // workflows with a retention override are skipped by the table-wide workflow-run marking
+    // above and marked on their own window, whether or not a global retention is configured.
+    for retention in db.fetch_workflow_retentions().await? {
+        let cutoff = Utc::now() - ChronoDuration::days(i64::from(retention.retention_days));
+        let count = db
+            .mark_workflow_run_archive_candidates(retention.workflow_id, cutoff, config.batch_size)
+            .await?;
+        if count > 0 {
+            info!(
+                table = %ArchiveTable::WorkflowRuns,
+                workflow_id = %retention.workflow_id,
+                retention_days = retention.retention_days,
+                count,
+                "marked row(s) for archival"
+            );
+        }
+    }
     Ok(())
 }
 
@@ -22,6 +22,8 @@ pub struct AuthOptions {
     pub enabled: bool,
     pub access_ttl_secs: i64,
     pub refresh_ttl_secs: i64,
+    /// operator-provisioned api keys accepted as admin service principals without a database row.
+    pub static_api_keys: Vec<String>,
 }
 
 /// runtime auth configuration shared across handlers and the middleware.
@@ -35,6 +37,9 @@ pub struct AuthConfig {
     pub jwt_secret_previous: Option<Vec<u8>>,
     pub access_ttl_secs: i64,
     pub refresh_ttl_secs: i64,
+    /// sha-256 hashes (see [`hash_secret`]) of the static api keys from [`AuthOptions`]; only the
+    /// hashes are held so the raw keys never sit in the long-lived config.
+    pub static_api_key_hashes: Vec<String>,
 }
 
 /// a freshly generated api key: `secret` is shown to the caller once; `key_hash` is stored.
@@ -114,6 +119,13 @@ pub fn new_refresh_token() -> (String, String) {
     (token, hash)
 }
 
+/// a webhook trigger token (shown to the caller once) and its stored hash.
+pub fn new_webhook_token() -> (String, String) {
+    let token = URL_SAFE_NO_PAD.encode(random_secret(32));
+    let hash = hash_secret(&token);
+    (token, hash)
+}
+
 // ---- jwt access tokens ----
 
 /// issue an access token for a user. `org`/`org_role` bind the token to an active organization when
@@ -211,8 +223,8 @@ pub trait CredentialStore {
     fn touch_api_key(&self, id: Uuid, last_used_at: i64) -> impl Future<Output = ()> + Send;
 }
 
-/// resolve a presented credential to a principal: try it as a jwt first, then as a
-/// `<prefix>.<secret>` api key looked up through the [`CredentialStore`].
+/// resolve a presented credential to a principal: try it as a jwt first, then as a configured static
+/// api key, then as a `<prefix>.<secret>` api key looked up through the [`CredentialStore`].
 pub async fn resolve_credential<S: CredentialStore>(
     config: &AuthConfig,
     store: &S,
@@ -227,6 +239,18 @@ pub async fn resolve_credential<S: CredentialStore>(
             org_role: claims.orl.as_deref().and_then(OrgRole::from_str_lossy),
         });
     }
+    // static keys are configured out-of-band (cli/env) for bootstrap and automation, so they have no
+    // owning user, never expire, and act as platform admins.
+    let presented_hash = hash_secret(presented);
+    if config.static_api_key_hashes.contains(&presented_hash) {
+        return Some(AuthContext {
+            principal_id: None,
+            is_admin: true,
+            kind: PrincipalKind::Service,
+            org_id: None,
+            org_role: None,
+        });
+    }
     let prefix = presented.split('.').next()?.to_string();
     let record = store.api_key_by_prefix(prefix).await?;
     if record.key.disabled {
@@ -237,7 +261,7 @@ pub async fn resolve_credential<S: CredentialStore>(
             return None;
         }
     }
-    if hash_secret(presented) != record.key_hash {
+    if presented_hash != record.key_hash {
         return None;
     }
     if let Some(id) = record.key.id {
@@ -264,6 +288,7 @@ mod tests {
             jwt_secret_previous: None,
             access_ttl_secs: 3600,
             refresh_ttl_secs: 86400,
+            static_api_key_hashes: Vec::new(),
         }
     }
 
@@ -321,6 +346,42 @@ mod tests {
         assert!(verify_access_token(&retired, &token).is_none());
     }
 
+    // a store with no database-backed keys, so only static keys can resolve.
+    struct NoKeys;
+
+    impl CredentialStore for NoKeys {
+        async fn api_key_by_prefix(&self, _prefix: String) -> Option<ApiKeyRecord> {
+            None
+        }
+
+        async fn touch_api_key(&self, _id: Uuid, _last_used_at: i64) {}
+    }
+
+    #[tokio::test]
+    async fn static_api_key_resolves_to_an_admin_service_principal() {
+        let cfg = AuthConfig {
+            static_api_key_hashes: vec![hash_secret("ops-bootstrap-key")],
+            ..config()
+        };
+        let context = resolve_credential(&cfg, &NoKeys, "ops-bootstrap-key")
+            .await
+            .expect("static key accepted");
+        assert!(context.is_admin);
+        assert_eq!(context.kind, PrincipalKind::Service);
+        assert!(context.principal_id.is_none());
+
+        assert!(
+            resolve_credential(&cfg, &NoKeys, "ops-bootstrap-key2")
+                .await
+                .is_none()
+        );
+        assert!(
+            resolve_credential(&config(), &NoKeys, "ops-bootstrap-key")
+                .await
+                .is_none()
+        );
+    }
+
     #[test]
     fn api_key_hash_matches_only_the_issued_secret() {
         let key = new_api_key();
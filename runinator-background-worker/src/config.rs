@@ -54,8 +54,17 @@ pub(crate) struct CliArgs {
     #[arg(long, default_value = "runinator-background-worker")]
     pub broker_client_id: String,
 
+    /// Shared secret used to mint bearer tokens for an auth-gated http broker
+    #[arg(long, env = "RUNINATOR_BROKER_AUTH_SECRET")]
+    pub broker_auth_secret: Option<String>,
+
     /// Stable instance id used when this worker claims trigger/action-dispatch rows. In k8s this
     /// should be the pod name; otherwise a random per-process id is generated.
     #[arg(long, env = "RUNINATOR_INSTANCE_ID")]
     pub instance_id: Option<String>,
+
+    /// Directory of JSON/YAML workflow definitions to import and keep in sync while running, for
+    /// installs without the web service. Also reads RUNINATOR_WORKFLOW_DIR.
+    #[arg(long, env = "RUNINATOR_WORKFLOW_DIR")]
+    pub workflow_dir: Option<PathBuf>,
 }
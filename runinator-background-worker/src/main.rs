@@ -10,6 +10,7 @@
 
 mod config;
 
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -18,12 +19,13 @@ use log::info;
 use runinator_broker::{
     Broker,
     adapters::{kafka::KafkaBrokerConfig, rabbitmq::RabbitMqBrokerConfig},
-    http::client::HttpBroker,
+    http::{auth::BrokerTokenSource, client::HttpBroker},
     in_memory::InMemoryBroker,
     tcp::client::TcpBroker,
 };
 use runinator_database::interfaces::DatabaseImpl;
 use runinator_db_cli::{DatabaseBackend, dispatch_database};
+use runinator_engine::workflow_source::{FileWorkflowSource, run_workflow_source_sync};
 use runinator_engine::{EnginePublisher, run_background_engine};
 use runinator_models::auth::AuthContext;
 use runinator_models::errors::SendableError;
@@ -48,6 +50,10 @@ async fn main() -> Result<(), SendableError> {
     let _telemetry = startup::startup("Runinator Background Worker")?;
 
     let args = CliArgs::parse();
+    startup::publish_report(startup::collect_report(
+        "Runinator Background Worker",
+        env!("CARGO_PKG_VERSION"),
+    ));
 
     let notify = Arc::new(Notify::new());
     let shutdown_listener = notify.clone();
@@ -70,7 +76,9 @@ async fn main() -> Result<(), SendableError> {
         broker_control_topic,
         broker_result_topic,
         broker_client_id,
+        broker_auth_secret,
         instance_id,
+        workflow_dir,
     } = args;
 
     // a stable per-process id used when claiming trigger/action-dispatch rows; k8s passes the pod name.
@@ -87,6 +95,7 @@ async fn main() -> Result<(), SendableError> {
     let broker = build_broker(
         &broker_backend,
         &broker_endpoint,
+        broker_auth_secret.as_deref(),
         KafkaBrokerConfig::new(broker_endpoint.clone())
             .with_topics(
                 broker_action_topic.clone(),
@@ -137,6 +146,7 @@ async fn main() -> Result<(), SendableError> {
                 broker.clone(),
                 instance.clone(),
                 attributes.clone(),
+                workflow_dir.clone(),
                 notify.clone(),
             )
             .await?;
@@ -153,6 +163,7 @@ async fn run_engine_with_replica<T: DatabaseImpl>(
     broker: Arc<dyn Broker>,
     instance: String,
     attributes: Value,
+    workflow_dir: Option<PathBuf>,
     shutdown: Arc<Notify>,
 ) -> Result<(), SendableError> {
     let runtime_id = Uuid::new_v4().to_string();
@@ -221,15 +232,29 @@ async fn run_engine_with_replica<T: DatabaseImpl>(
         }
     });
 
+    // optional file-backed definitions: imported into the same database the engine schedules from.
+    let workflow_sync = workflow_dir.map(|dir| {
+        info!("Syncing workflow definitions from {}", dir.display());
+        tokio::spawn(run_workflow_source_sync(
+            db.clone(),
+            FileWorkflowSource::new(dir),
+            shutdown.clone(),
+        ))
+    });
+
     let publisher = EnginePublisher::new(broker.clone());
     let result = run_background_engine(db, broker, publisher, instance, shutdown).await;
     heartbeat.abort();
+    if let Some(workflow_sync) = workflow_sync {
+        workflow_sync.abort();
+    }
     result
 }
 
 async fn build_broker(
     backend: &str,
     endpoint: &str,
+    auth_secret: Option<&str>,
     kafka_config: KafkaBrokerConfig,
     rabbitmq_config: RabbitMqBrokerConfig,
 ) -> Result<Arc<dyn Broker>, SendableError> {
@@ -249,7 +274,9 @@ async fn build_broker(
             let client = reqwest::Client::builder()
                 .build()
                 .map_err(|err| -> SendableError { err.to_string().into() })?;
-            Arc::new(HttpBroker::new(url, client))
+            Arc::new(
+                HttpBroker::new(url, client).with_auth(BrokerTokenSource::from_secret(auth_secret)),
+            )
         }
         "in-memory" => Arc::new(InMemoryBroker::new()),
         "tcp" => Arc::new(TcpBroker::new(endpoint.to_string())),
@@ -0,0 +1,110 @@
+//! broker throughput: publish/receive/ack cycles through the in-memory broker, and the same cycle
+//! over the http transport against a local server, so batching or locking changes show up as a
+//! measured regression instead of a hunch.
+//!
+//! run with `cargo bench -p runinator-broker`.
+
+use std::time::Duration;
+
+use chrono::Utc;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use runinator_broker::{
+    http::client::HttpBroker, in_memory::InMemoryBroker, Broker, BrokerMessage,
+};
+use runinator_comm::ActionCommand;
+use runinator_models::{json, workflows::WorkflowAction};
+use tokio::{net::TcpListener, runtime::Runtime};
+use uuid::Uuid;
+
+const CONSUMER: &str = "bench";
+
+fn message() -> BrokerMessage {
+    BrokerMessage {
+        command: ActionCommand {
+            command_id: Uuid::new_v4(),
+            workflow_run_id: Uuid::now_v7(),
+            workflow_node_run_id: Uuid::now_v7(),
+            node_id: "node-a".into(),
+            action: WorkflowAction {
+                provider: "bench".into(),
+                function: "execute".into(),
+                timeout_seconds: 60,
+                configuration: runinator_models::workflows::WorkflowObject::default(),
+                mcp_enabled: false,
+                tags: Vec::new(),
+                required_labels: Default::default(),
+                allowed_secrets: Vec::new(),
+                preflight: Vec::new(),
+                cache_ttl_seconds: None,
+                priority: 0,
+                queue: None,
+            },
+            attempt: 1,
+            parameters: json!({}),
+            target: Default::default(),
+            trace_id: Uuid::nil(),
+            trace_context: Default::default(),
+        },
+        // unique keys: dedupe would otherwise drop every publish after the first.
+        dedupe_key: None,
+        enqueued_at: Utc::now(),
+        priority: 0,
+        queue: None,
+    }
+}
+
+/// publish `count` messages, then drain and ack them all.
+async fn cycle<B: Broker>(broker: &B, count: usize) {
+    for _ in 0..count {
+        broker.publish(message()).await.unwrap();
+    }
+    for _ in 0..count {
+        let delivery = broker.receive(CONSUMER).await.unwrap();
+        broker.ack(CONSUMER, delivery.delivery_id).await.unwrap();
+    }
+}
+
+fn in_memory(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let broker = InMemoryBroker::new();
+    let mut group = c.benchmark_group("in_memory");
+    for count in [1, 100, 1_000] {
+        group.throughput(Throughput::Elements(count as u64));
+        group.bench_with_input(
+            BenchmarkId::new("publish_receive_ack", count),
+            &count,
+            |b, &count| b.to_async(&runtime).iter(|| cycle(&broker, count)),
+        );
+    }
+    group.finish();
+}
+
+fn http_round_trip(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let broker = runtime.block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(runinator_broker::http::server::serve(
+            listener,
+            InMemoryBroker::new(),
+        ));
+        HttpBroker::new(
+            reqwest::Url::parse(&format!("http://{addr}/")).unwrap(),
+            reqwest::Client::new(),
+        )
+    });
+    let mut group = c.benchmark_group("http");
+    group.measurement_time(Duration::from_secs(10));
+    for count in [1, 100] {
+        group.throughput(Throughput::Elements(count as u64));
+        group.bench_with_input(
+            BenchmarkId::new("publish_receive_ack", count),
+            &count,
+            |b, &count| b.to_async(&runtime).iter(|| cycle(&broker, count)),
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, in_memory, http_round_trip);
+criterion_main!(benches);
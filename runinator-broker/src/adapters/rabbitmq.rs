@@ -1,5 +1,5 @@
 #[cfg(feature = "rabbitmq")]
-use crate::{ActionTarget, ConsumerProfile};
+use crate::{ActionTarget, ConsumerProfile, DEFAULT_QUEUE};
 use crate::{
     Broker, BrokerDelivery, BrokerError, BrokerMessage, ControlCommand, ControlDelivery,
     EventDelivery, EventMessage, IngressDelivery, IngressMessage, ResultDelivery, ResultMessage,
@@ -562,9 +562,17 @@ impl Broker for RabbitMqBroker {
         // `Any` traffic (the common case) keeps using the plain shared queue unchanged; `Labels`/
         // `Replica` targets go to the second queue so general workers never see them at all. See
         // `receive_for`'s override below for how a targeted delivery is matched to the right consumer.
+        // an action bound for a named queue is targeted too: only its subscribers may take it.
         let queue = match &message.command.target {
-            ActionTarget::Any => &self.config.action_queue,
-            ActionTarget::Labels { .. } | ActionTarget::Replica { .. } => {
+            ActionTarget::Any
+                if message
+                    .queue
+                    .as_deref()
+                    .is_none_or(|queue| queue == DEFAULT_QUEUE) =>
+            {
+                &self.config.action_queue
+            }
+            ActionTarget::Any | ActionTarget::Labels { .. } | ActionTarget::Replica { .. } => {
                 &self.config.targeted_action_queue
             }
         };
@@ -590,20 +598,24 @@ impl Broker for RabbitMqBroker {
     /// org-dedicated workers) can legitimately receive both `Any` work and any `Labels`/`Replica`
     /// target they satisfy, so race both queues. exclusive profiles (e.g. the desktop worker) never
     /// match `Any` (see `ActionTarget::matches`), so only the targeted queue is worth draining.
-    /// either way, since RabbitMQ can't natively filter the targeted queue by content, every
-    /// delivery pulled from it is re-validated against `profile` and requeued if it doesn't match.
+    /// the same goes for a profile not subscribed to the default queue, since actions on a named
+    /// queue travel the targeted queue. either way, since RabbitMQ can't natively filter the
+    /// targeted queue by content, every delivery pulled from it is re-validated against `profile`
+    /// and requeued if it doesn't match.
     async fn receive_for(&self, profile: &ConsumerProfile) -> Result<BrokerDelivery, BrokerError> {
         let targeted = async {
             loop {
                 let delivery = self.receive_targeted_action(&profile.id).await?;
-                if delivery.command.target.matches(profile) {
+                if delivery.matches(profile) {
                     return Ok(delivery);
                 }
                 self.nack(&profile.id, delivery.delivery_id).await?;
                 tokio::time::sleep(std::time::Duration::from_millis(100)).await;
             }
         };
-        if profile.exclusive {
+        // a consumer off the default queue (one dedicated to a named queue) takes nothing from the
+        // shared queue either.
+        if profile.exclusive || !profile.subscribes_to(None) {
             targeted.await
         } else {
             tokio::select! {
@@ -1,15 +1,146 @@
-use runinator_broker::{http, in_memory::InMemoryBroker, tcp};
-use std::{env, net::SocketAddr};
+use chrono::Utc;
+use clap::{Parser, Subcommand};
+use runinator_broker::{
+    http, in_memory::InMemoryBroker, tcp, tcp::client::TcpBroker, Broker, PriorityAging,
+};
+use std::net::SocketAddr;
+use uuid::Uuid;
+
+#[derive(Parser)]
+#[command(name = "runinator-broker", about = "Runinator message broker")]
+struct Cli {
+    /// broker address to listen on, or to connect to for the inspection commands.
+    #[arg(
+        long,
+        env = "RUNINATOR_BROKER_ADDR",
+        default_value = "127.0.0.1:7070",
+        global = true
+    )]
+    addr: SocketAddr,
+    /// transport to serve (`tcp` or `http`). the inspection commands always speak tcp.
+    #[arg(long, env = "RUNINATOR_BROKER_TRANSPORT", default_value = "tcp")]
+    transport: String,
+    /// seconds a queued action waits to gain one point of priority; 0 turns aging off.
+    #[arg(
+        long,
+        env = "RUNINATOR_BROKER_PRIORITY_AGING_SECONDS",
+        default_value_t = 30
+    )]
+    priority_aging_seconds: u64,
+    /// most priority a queued action can gain by waiting.
+    #[arg(
+        long,
+        env = "RUNINATOR_BROKER_PRIORITY_AGING_MAX_BOOST",
+        default_value_t = 100
+    )]
+    priority_aging_max_boost: i32,
+    #[command(subcommand)]
+    command: Option<Commands>,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// serve the broker (the default when no command is given).
+    Serve,
+    /// list queued and in-flight messages on a running broker, oldest first.
+    List,
+    /// print one message, including its payload, as json.
+    Show { delivery_id: Uuid },
+    /// trace a message, by any of its delivery ids or its dedupe key, through its state changes.
+    History { key: String },
+    /// return a stuck in-flight message to the front of its queue now.
+    Requeue { delivery_id: Uuid },
+    /// drop queued messages older than an age such as `90s`, `30m`, `6h`, or `2d`.
+    Purge {
+        #[arg(long, value_parser = parse_age)]
+        older_than: chrono::Duration,
+    },
+}
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let addr: SocketAddr = env::var("RUNINATOR_BROKER_ADDR")
-        .unwrap_or_else(|_| "127.0.0.1:7070".into())
-        .parse()?;
-    let transport = env::var("RUNINATOR_BROKER_TRANSPORT").unwrap_or_else(|_| "tcp".into());
+    let cli = Cli::parse();
+    let broker = TcpBroker::new(cli.addr.to_string());
+    match cli.command.unwrap_or(Commands::Serve) {
+        Commands::Serve => {
+            let aging = PriorityAging {
+                interval: std::time::Duration::from_secs(cli.priority_aging_seconds),
+                max_boost: cli.priority_aging_max_boost,
+            };
+            serve(cli.addr, &cli.transport, aging).await?
+        }
+        Commands::List => {
+            for message in broker.list_messages().await? {
+                println!(
+                    "{}  {:<8} {:<9} {}  {}",
+                    message.delivery_id,
+                    message.channel,
+                    if message.in_flight {
+                        "in-flight"
+                    } else {
+                        "queued"
+                    },
+                    message.enqueued_at.to_rfc3339(),
+                    message.dedupe_key.as_deref().unwrap_or("-"),
+                );
+            }
+        }
+        Commands::Show { delivery_id } => {
+            let message = broker
+                .list_messages()
+                .await?
+                .into_iter()
+                .find(|message| message.delivery_id == delivery_id)
+                .ok_or_else(|| format!("no message with delivery id {delivery_id}"))?;
+            println!("{}", serde_json::to_string_pretty(&message)?);
+        }
+        Commands::History { key } => {
+            let messages = broker.message_history(&key).await?;
+            if messages.is_empty() {
+                return Err(format!("no history for '{key}'").into());
+            }
+            for message in messages {
+                println!(
+                    "{}  {:<8} {}",
+                    message.message_id,
+                    message.channel,
+                    message.dedupe_key.as_deref().unwrap_or("-"),
+                );
+                for transition in &message.transitions {
+                    println!(
+                        "  {}  {:<9} {}  {}{}",
+                        transition.at.to_rfc3339(),
+                        transition.state.as_str(),
+                        transition.delivery_id,
+                        transition.consumer.as_deref().unwrap_or("-"),
+                        transition
+                            .error
+                            .as_deref()
+                            .map(|error| format!("  {error}"))
+                            .unwrap_or_default(),
+                    );
+                }
+            }
+        }
+        Commands::Requeue { delivery_id } => {
+            broker.requeue_message(delivery_id).await?;
+            println!("Requeued {delivery_id}");
+        }
+        Commands::Purge { older_than } => {
+            let count = broker.purge_messages(Utc::now() - older_than).await?;
+            println!("Purged {count} message(s)");
+        }
+    }
+    Ok(())
+}
 
-    let broker = InMemoryBroker::new();
-    match transport.as_str() {
+async fn serve(
+    addr: SocketAddr,
+    transport: &str,
+    aging: PriorityAging,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let broker = InMemoryBroker::new().with_priority_aging(aging);
+    match transport {
         "tcp" => {
             println!("Runinator TCP broker listening on {}", addr);
             tcp::server::run_server(addr, broker).await?;
@@ -24,3 +155,21 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
     Ok(())
 }
+
+fn parse_age(value: &str) -> Result<chrono::Duration, String> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (amount, unit) = value.split_at(split);
+    let amount: i64 = amount
+        .parse()
+        .map_err(|_| format!("invalid age '{value}'"))?;
+    match unit {
+        "s" | "" => Ok(chrono::Duration::seconds(amount)),
+        "m" => Ok(chrono::Duration::minutes(amount)),
+        "h" => Ok(chrono::Duration::hours(amount)),
+        "d" => Ok(chrono::Duration::days(amount)),
+        other => Err(format!("unknown age unit '{other}' (use s, m, h, or d)")),
+    }
+}
@@ -19,7 +19,7 @@ use crate::{
 /// at the call site same as any other unsizing coercion.
 pub async fn dispatch(broker: &dyn Broker, request: TcpRequest) -> TcpResponse {
     let result = match request {
-        TcpRequest::Publish { message } => broker.publish(message).await.map(|_| TcpResponse::Ok),
+        TcpRequest::Publish { message } => broker.publish(*message).await.map(|_| TcpResponse::Ok),
         TcpRequest::PublishControl { command } => broker
             .publish_control(command)
             .await
@@ -31,10 +31,12 @@ pub async fn dispatch(broker: &dyn Broker, request: TcpRequest) -> TcpResponse {
         TcpRequest::Receive { consumer } => broker
             .receive(&consumer)
             .await
+            .map(Box::new)
             .map(|delivery| TcpResponse::Delivery { delivery }),
         TcpRequest::ReceiveFor { profile } => broker
             .receive_for(&profile)
             .await
+            .map(Box::new)
             .map(|delivery| TcpResponse::Delivery { delivery }),
         TcpRequest::ReceiveControl { consumer } => broker
             .receive_control(&consumer)
@@ -72,8 +74,9 @@ pub async fn dispatch(broker: &dyn Broker, request: TcpRequest) -> TcpResponse {
         TcpRequest::Nack {
             consumer,
             delivery_id,
+            error,
         } => broker
-            .nack(&consumer, delivery_id)
+            .nack_with_error(&consumer, delivery_id, error)
             .await
             .map(|_| TcpResponse::Ok),
         TcpRequest::NackControl {
@@ -140,6 +143,22 @@ pub async fn dispatch(broker: &dyn Broker, request: TcpRequest) -> TcpResponse {
             .receive_event(&consumer)
             .await
             .map(|delivery| TcpResponse::EventDelivery { delivery }),
+        TcpRequest::ListMessages => broker
+            .list_messages()
+            .await
+            .map(|messages| TcpResponse::Messages { messages }),
+        TcpRequest::RequeueMessage { delivery_id } => broker
+            .requeue_message(delivery_id)
+            .await
+            .map(|_| TcpResponse::Ok),
+        TcpRequest::MessageHistory { key } => broker
+            .message_history(&key)
+            .await
+            .map(|messages| TcpResponse::History { messages }),
+        TcpRequest::PurgeMessages { older_than } => broker
+            .purge_messages(older_than)
+            .await
+            .map(|count| TcpResponse::Purged { count }),
     };
     result.unwrap_or_else(|err| TcpResponse::Error {
         message: err.to_string(),
@@ -17,6 +17,8 @@ pub enum BrokerError {
     FeatureDisabled(&'static str),
     #[error("BROKER007 - consumer stream ended; connection may have dropped")]
     ConsumerStreamEnded,
+    #[error("BROKER008 - request rejected by broker: {0}")]
+    Rejected(String),
 }
 
 // numbered error dictionary for the broker engine.
@@ -46,6 +48,8 @@ pub const CONSUMER_STREAM_ENDED: ErrorDescriptor = ErrorDescriptor::new(
     "broker.consumer_stream_ended",
     "Consumer stream ended; connection may have dropped",
 );
+pub const REJECTED: ErrorDescriptor =
+    ErrorDescriptor::new("BROKER008", "broker.rejected", "Request rejected by broker");
 
 pub const DICTIONARY: &[ErrorDescriptor] = &[
     DUPLICATE,
@@ -55,6 +59,7 @@ pub const DICTIONARY: &[ErrorDescriptor] = &[
     INTERNAL,
     FEATURE_DISABLED,
     CONSUMER_STREAM_ENDED,
+    REJECTED,
 ];
 
 impl EngineErrors for BrokerError {
@@ -0,0 +1,213 @@
+//! per-message state history for operator tracing: when a message was published, which consumer
+//! each delivery went to, and how that delivery ended (acked, nacked, expired, or purged). answers
+//! "where did this command go" from the broker itself during an incident.
+//!
+//! a message keeps one identity across redeliveries, its first delivery id, and can be looked up by
+//! any of its delivery ids or by its dedupe key. the log is bounded: the oldest messages are
+//! forgotten first, and a message bouncing between consumers keeps only its latest transitions.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use uuid::Uuid;
+
+/// most messages the log remembers; publishing past it forgets the oldest.
+pub const MESSAGE_HISTORY_CAPACITY: usize = 10_000;
+
+/// most transitions kept per message. the publish is always kept; older deliveries go first.
+pub const MESSAGE_HISTORY_TRANSITIONS: usize = 64;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageState {
+    Published,
+    /// handed to a consumer under a lease.
+    Delivered,
+    Acked,
+    /// returned by its consumer (or requeued by an operator) for another delivery.
+    Nacked,
+    /// its lease ran out before an ack, or a control went stale in the queue.
+    Expired,
+    /// dropped from the queue by an operator purge.
+    Purged,
+}
+
+impl MessageState {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Published => "published",
+            Self::Delivered => "delivered",
+            Self::Acked => "acked",
+            Self::Nacked => "nacked",
+            Self::Expired => "expired",
+            Self::Purged => "purged",
+        }
+    }
+}
+
+/// one step in a message's life.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MessageTransition {
+    pub state: MessageState,
+    pub at: DateTime<Utc>,
+    /// the delivery the step applies to; a redelivery gets a new id.
+    pub delivery_id: Uuid,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub consumer: Option<String>,
+    /// the reason a nack or an expiry gave.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// the recorded transitions of one message, oldest first.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MessageHistory {
+    /// the message's first delivery id.
+    pub message_id: Uuid,
+    /// `action`, `control`, `result`, `wake`, or `ingress`.
+    pub channel: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dedupe_key: Option<String>,
+    pub transitions: Vec<MessageTransition>,
+}
+
+impl MessageHistory {
+    /// the state of the message's latest transition.
+    pub fn state(&self) -> Option<MessageState> {
+        self.transitions.last().map(|transition| transition.state)
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct HistoryLog {
+    messages: HashMap<Uuid, Entry>,
+    // message ids, oldest first, for eviction.
+    order: VecDeque<Uuid>,
+    // every delivery id of a remembered message, mapped to its message id.
+    deliveries: HashMap<Uuid, Uuid>,
+}
+
+struct Entry {
+    history: MessageHistory,
+    delivery_ids: Vec<Uuid>,
+}
+
+impl HistoryLog {
+    pub(crate) fn published(&mut self, channel: &str, delivery_id: Uuid, dedupe_key: Option<&str>) {
+        while self.order.len() >= MESSAGE_HISTORY_CAPACITY {
+            let Some(oldest) = self.order.pop_front() else {
+                break;
+            };
+            if let Some(entry) = self.messages.remove(&oldest) {
+                for delivery_id in entry.delivery_ids {
+                    self.deliveries.remove(&delivery_id);
+                }
+            }
+        }
+        self.order.push_back(delivery_id);
+        self.deliveries.insert(delivery_id, delivery_id);
+        self.messages.insert(
+            delivery_id,
+            Entry {
+                history: MessageHistory {
+                    message_id: delivery_id,
+                    channel: channel.to_string(),
+                    dedupe_key: dedupe_key.map(str::to_string),
+                    transitions: vec![transition(MessageState::Published, delivery_id, None, None)],
+                },
+                delivery_ids: vec![delivery_id],
+            },
+        );
+    }
+
+    pub(crate) fn delivered(&mut self, delivery_id: Uuid, consumer: &str) {
+        self.record(transition(
+            MessageState::Delivered,
+            delivery_id,
+            Some(consumer),
+            None,
+        ));
+    }
+
+    pub(crate) fn acked(&mut self, delivery_id: Uuid, consumer: &str) {
+        self.record(transition(
+            MessageState::Acked,
+            delivery_id,
+            Some(consumer),
+            None,
+        ));
+    }
+
+    /// `delivery_id` was nacked or expired and goes back on the queue as `redelivery_id`.
+    pub(crate) fn requeued(
+        &mut self,
+        state: MessageState,
+        delivery_id: Uuid,
+        redelivery_id: Uuid,
+        consumer: Option<&str>,
+        error: Option<String>,
+    ) {
+        if let Some(message_id) = self.deliveries.get(&delivery_id).copied() {
+            self.deliveries.insert(redelivery_id, message_id);
+            if let Some(entry) = self.messages.get_mut(&message_id) {
+                entry.delivery_ids.push(redelivery_id);
+            }
+        }
+        self.record(transition(state, delivery_id, consumer, error));
+    }
+
+    /// `delivery_id` left the queue without being delivered again.
+    pub(crate) fn dropped(&mut self, state: MessageState, delivery_id: Uuid) {
+        self.record(transition(state, delivery_id, None, None));
+    }
+
+    /// the messages one of whose delivery ids, or whose dedupe key, is `key`, oldest first.
+    pub(crate) fn find(&self, key: &str) -> Vec<MessageHistory> {
+        if let Ok(delivery_id) = key.parse::<Uuid>() {
+            if let Some(entry) = self
+                .deliveries
+                .get(&delivery_id)
+                .and_then(|message_id| self.messages.get(message_id))
+            {
+                return vec![entry.history.clone()];
+            }
+        }
+        self.order
+            .iter()
+            .filter_map(|message_id| self.messages.get(message_id))
+            .map(|entry| &entry.history)
+            .filter(|history| history.dedupe_key.as_deref() == Some(key))
+            .cloned()
+            .collect()
+    }
+
+    fn record(&mut self, transition: MessageTransition) {
+        let Some(history) = self
+            .deliveries
+            .get(&transition.delivery_id)
+            .and_then(|message_id| self.messages.get_mut(message_id))
+            .map(|entry| &mut entry.history)
+        else {
+            return;
+        };
+        if history.transitions.len() >= MESSAGE_HISTORY_TRANSITIONS {
+            history.transitions.remove(1);
+        }
+        history.transitions.push(transition);
+    }
+}
+
+fn transition(
+    state: MessageState,
+    delivery_id: Uuid,
+    consumer: Option<&str>,
+    error: Option<String>,
+) -> MessageTransition {
+    MessageTransition {
+        state,
+        at: Utc::now(),
+        delivery_id,
+        consumer: consumer.map(str::to_string),
+        error,
+    }
+}
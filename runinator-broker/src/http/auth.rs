@@ -1,11 +1,19 @@
+use crate::BrokerError;
+use chrono::Utc;
+use parking_lot::Mutex;
 use runinator_auth::AuthConfig;
 use runinator_models::auth::Claims;
+use uuid::Uuid;
 
 // env vars configuring the broker's bearer-token gate. when no secret is set the broker stays open
 // (the local/dev default); the supervisor stack is unaffected.
 const SECRET_ENV: &str = "RUNINATOR_BROKER_AUTH_SECRET";
 const SECRET_PREVIOUS_ENV: &str = "RUNINATOR_BROKER_AUTH_SECRET_PREVIOUS";
 
+// lifetime of a client-minted service token, and how close to expiry it is re-minted.
+const CLIENT_TOKEN_TTL_SECS: i64 = 300;
+const CLIENT_TOKEN_REFRESH_SECS: i64 = 60;
+
 /// verifies bearer tokens presented to the broker against a shared HS256 secret (the same secret the
 /// web service signs with). a token's `rid` claim, when present, scopes it to one worker replica so
 /// the broker can authorize targeting without a registry lookup.
@@ -37,6 +45,7 @@ impl BrokerAuth {
                 jwt_secret_previous: previous,
                 access_ttl_secs: 0,
                 refresh_ttl_secs: 0,
+                static_api_key_hashes: Vec::new(),
             },
         }
     }
@@ -52,6 +61,54 @@ impl BrokerAuth {
 #[derive(Clone)]
 pub struct AuthIdentity(pub Option<Claims>);
 
+/// client-side counterpart of [`BrokerAuth`]: holds the shared secret and mints short-lived service
+/// tokens from it, so a service configured with the same secret as the broker passes the gate without
+/// a separate token-issuing round trip. tokens are cached and re-minted shortly before they expire.
+pub struct BrokerTokenSource {
+    config: AuthConfig,
+    subject: Uuid,
+    cached: Mutex<Option<(String, i64)>>,
+}
+
+impl BrokerTokenSource {
+    pub fn new(secret: Vec<u8>) -> Self {
+        Self {
+            config: AuthConfig {
+                enabled: true,
+                jwt_secret: secret,
+                jwt_secret_previous: None,
+                access_ttl_secs: CLIENT_TOKEN_TTL_SECS,
+                refresh_ttl_secs: 0,
+                static_api_key_hashes: Vec::new(),
+            },
+            subject: Uuid::new_v4(),
+            cached: Mutex::new(None),
+        }
+    }
+
+    /// build from an optional configured secret; `None` when it is missing or blank.
+    pub fn from_secret(secret: Option<&str>) -> Option<Self> {
+        let secret = secret.map(str::trim).filter(|value| !value.is_empty())?;
+        Some(Self::new(secret.as_bytes().to_vec()))
+    }
+
+    /// the current bearer token, minting a fresh one when none is cached or it is about to expire.
+    pub fn token(&self) -> Result<String, BrokerError> {
+        let now = Utc::now().timestamp();
+        let mut cached = self.cached.lock();
+        if let Some((token, exp)) = cached.as_ref() {
+            if *exp - now > CLIENT_TOKEN_REFRESH_SECS {
+                return Ok(token.clone());
+            }
+        }
+        let (token, exp) =
+            runinator_auth::issue_access_token(&self.config, self.subject, false, None, None)
+                .map_err(BrokerError::Internal)?;
+        *cached = Some((token.clone(), exp));
+        Ok(token)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -73,12 +130,26 @@ mod tests {
             jwt_secret_previous: None,
             access_ttl_secs: 60,
             refresh_ttl_secs: 60,
+            static_api_key_hashes: Vec::new(),
         };
         let (token, _) =
             runinator_auth::issue_access_token(&config, Uuid::now_v7(), false, None, None).unwrap();
         assert!(auth.verify(&token).is_some());
     }
 
+    #[test]
+    fn token_source_mints_tokens_the_gate_accepts() {
+        let source = BrokerTokenSource::new(b"test-secret".to_vec());
+        let token = source.token().unwrap();
+        assert!(auth().verify(&token).is_some());
+        // cached until close to expiry.
+        assert_eq!(source.token().unwrap(), token);
+
+        let other = BrokerTokenSource::new(b"other-secret".to_vec());
+        assert!(auth().verify(&other.token().unwrap()).is_none());
+        assert!(BrokerTokenSource::from_secret(Some("  ")).is_none());
+    }
+
     #[test]
     fn replica_token_carries_its_replica_claim() {
         let config = AuthConfig {
@@ -87,6 +158,7 @@ mod tests {
             jwt_secret_previous: None,
             access_ttl_secs: 60,
             refresh_ttl_secs: 60,
+            static_api_key_hashes: Vec::new(),
         };
         let replica = Uuid::now_v7();
         let (token, _) =
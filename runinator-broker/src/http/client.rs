@@ -1,27 +1,53 @@
 use crate::{
+    http::auth::BrokerTokenSource,
     http::types::{
         AckRequest, PublishControlRequest, PublishEventRequest, PublishIngressRequest,
         PublishRequest, PublishWakeRequest, ReceiveControlResponse, ReceiveEventResponse,
         ReceiveIngressResponse, ReceiveRequest, ReceiveResponse, ReceiveResultResponse,
         ReceiveWakeResponse,
     },
+    http::validation::{BROKER_PROTOCOL_VERSION, BROKER_VERSION_HEADER},
     Broker, BrokerDelivery, BrokerError, BrokerMessage, ConsumerProfile, ControlCommand,
     ControlDelivery, EventDelivery, EventMessage, IngressDelivery, IngressMessage, ResultDelivery,
     ResultMessage, WakeDelivery, WakeMessage,
 };
 use async_trait::async_trait;
-use reqwest::{Client, StatusCode, Url};
+use reqwest::{Client, RequestBuilder, StatusCode, Url};
+use std::sync::Arc;
 use uuid::Uuid;
 
 #[derive(Clone)]
 pub struct HttpBroker {
     client: Client,
     base_url: Url,
+    auth: Option<Arc<BrokerTokenSource>>,
 }
 
 impl HttpBroker {
     pub fn new(base_url: Url, client: Client) -> Self {
-        Self { client, base_url }
+        Self {
+            client,
+            base_url,
+            auth: None,
+        }
+    }
+
+    /// present a bearer token minted from the broker's shared secret on every request. `None`
+    /// leaves requests unauthenticated, which an open (secret-less) broker accepts.
+    pub fn with_auth(mut self, auth: Option<BrokerTokenSource>) -> Self {
+        self.auth = auth.map(Arc::new);
+        self
+    }
+
+    fn post(&self, url: Url) -> Result<RequestBuilder, BrokerError> {
+        let request = self
+            .client
+            .post(url)
+            .header(BROKER_VERSION_HEADER, BROKER_PROTOCOL_VERSION);
+        match self.auth.as_deref() {
+            Some(auth) => Ok(request.bearer_auth(auth.token()?)),
+            None => Ok(request),
+        }
     }
 
     fn endpoint(&self, path: &str) -> Result<Url, BrokerError> {
@@ -38,17 +64,18 @@ impl HttpBroker {
     ) -> Result<(), BrokerError> {
         let url = self.endpoint(path)?;
         let response = self
-            .client
-            .post(url)
+            .post(url)?
             .json(&AckRequest {
                 consumer: consumer.to_string(),
                 delivery_id,
+                error: None,
             })
             .send()
             .await
             .map_err(|err| BrokerError::Internal(err.to_string()))?;
         match response.status() {
             StatusCode::OK => Ok(()),
+            StatusCode::BAD_REQUEST => Err(rejected(response).await),
             status => Err(BrokerError::Internal(format!(
                 "unexpected {path} status: {status}"
             ))),
@@ -61,8 +88,7 @@ impl HttpBroker {
     ) -> Result<BrokerDelivery, BrokerError> {
         let url = self.endpoint("receive")?;
         let response = self
-            .client
-            .post(url)
+            .post(url)?
             .json(&request)
             .send()
             .await
@@ -76,6 +102,7 @@ impl HttpBroker {
                     .map_err(|err| BrokerError::Internal(err.to_string()))?;
                 Ok(payload.delivery)
             }
+            StatusCode::BAD_REQUEST => Err(rejected(response).await),
             status => Err(BrokerError::Internal(format!(
                 "unexpected receive status: {status}"
             ))),
@@ -83,6 +110,20 @@ impl HttpBroker {
     }
 }
 
+// turn a structured 400 (`{ code, message }`) into an error naming what the server refused.
+async fn rejected(response: reqwest::Response) -> BrokerError {
+    let body = response
+        .json::<serde_json::Value>()
+        .await
+        .unwrap_or_default();
+    let field = |name: &str| {
+        body.get(name)
+            .and_then(|value| value.as_str())
+            .unwrap_or("")
+    };
+    BrokerError::Rejected(format!("{}: {}", field("code"), field("message")))
+}
+
 #[async_trait]
 impl Broker for HttpBroker {
     fn supports_workflow_result_channels(&self) -> bool {
@@ -93,8 +134,7 @@ impl Broker for HttpBroker {
         let url = self.endpoint("publish")?;
         let dedupe_key = message.dedupe_key_or_hash();
         let response = self
-            .client
-            .post(url)
+            .post(url)?
             .json(&PublishRequest { message })
             .send()
             .await
@@ -103,6 +143,7 @@ impl Broker for HttpBroker {
         match response.status() {
             StatusCode::OK | StatusCode::CREATED => Ok(()),
             StatusCode::CONFLICT => Err(BrokerError::Duplicate(dedupe_key)),
+            StatusCode::BAD_REQUEST => Err(rejected(response).await),
             status => Err(BrokerError::Internal(format!(
                 "unexpected publish status: {status}"
             ))),
@@ -128,17 +169,18 @@ impl Broker for HttpBroker {
     async fn ack(&self, consumer: &str, delivery_id: Uuid) -> Result<(), BrokerError> {
         let url = self.endpoint("ack")?;
         let response = self
-            .client
-            .post(url)
+            .post(url)?
             .json(&AckRequest {
                 consumer: consumer.to_string(),
                 delivery_id,
+                error: None,
             })
             .send()
             .await
             .map_err(|err| BrokerError::Internal(err.to_string()))?;
         match response.status() {
             StatusCode::OK => Ok(()),
+            StatusCode::BAD_REQUEST => Err(rejected(response).await),
             status => Err(BrokerError::Internal(format!(
                 "unexpected ack status: {status}"
             ))),
@@ -146,19 +188,29 @@ impl Broker for HttpBroker {
     }
 
     async fn nack(&self, consumer: &str, delivery_id: Uuid) -> Result<(), BrokerError> {
+        self.nack_with_error(consumer, delivery_id, None).await
+    }
+
+    async fn nack_with_error(
+        &self,
+        consumer: &str,
+        delivery_id: Uuid,
+        error: Option<String>,
+    ) -> Result<(), BrokerError> {
         let url = self.endpoint("nack")?;
         let response = self
-            .client
-            .post(url)
+            .post(url)?
             .json(&AckRequest {
                 consumer: consumer.to_string(),
                 delivery_id,
+                error,
             })
             .send()
             .await
             .map_err(|err| BrokerError::Internal(err.to_string()))?;
         match response.status() {
             StatusCode::OK => Ok(()),
+            StatusCode::BAD_REQUEST => Err(rejected(response).await),
             status => Err(BrokerError::Internal(format!(
                 "unexpected nack status: {status}"
             ))),
@@ -168,8 +220,7 @@ impl Broker for HttpBroker {
     async fn publish_control(&self, command: ControlCommand) -> Result<(), BrokerError> {
         let url = self.endpoint("control/publish")?;
         let response = self
-            .client
-            .post(url)
+            .post(url)?
             .json(&PublishControlRequest { command })
             .send()
             .await
@@ -186,8 +237,7 @@ impl Broker for HttpBroker {
     async fn receive_control(&self, consumer: &str) -> Result<ControlDelivery, BrokerError> {
         let url = self.endpoint("control/receive")?;
         let response = self
-            .client
-            .post(url)
+            .post(url)?
             .json(&ReceiveRequest {
                 consumer: consumer.to_string(),
                 profile: None,
@@ -216,8 +266,7 @@ impl Broker for HttpBroker {
     ) -> Result<ControlDelivery, BrokerError> {
         let url = self.endpoint("control/receive")?;
         let response = self
-            .client
-            .post(url)
+            .post(url)?
             .json(&ReceiveRequest {
                 consumer: profile.id.clone(),
                 profile: Some(profile.clone()),
@@ -252,8 +301,7 @@ impl Broker for HttpBroker {
         let url = self.endpoint("results/publish")?;
         let dedupe_key = message.dedupe_key_or_hash();
         let response = self
-            .client
-            .post(url)
+            .post(url)?
             .json(&crate::http::types::PublishResultRequest { message })
             .send()
             .await
@@ -271,8 +319,7 @@ impl Broker for HttpBroker {
     async fn receive_result(&self, consumer: &str) -> Result<ResultDelivery, BrokerError> {
         let url = self.endpoint("results/receive")?;
         let response = self
-            .client
-            .post(url)
+            .post(url)?
             .json(&ReceiveRequest {
                 consumer: consumer.to_string(),
                 profile: None,
@@ -307,8 +354,7 @@ impl Broker for HttpBroker {
         let url = self.endpoint("wake/publish")?;
         let dedupe_key = message.dedupe_key_or_hash();
         let response = self
-            .client
-            .post(url)
+            .post(url)?
             .json(&PublishWakeRequest { message })
             .send()
             .await
@@ -326,8 +372,7 @@ impl Broker for HttpBroker {
     async fn receive_wake(&self, consumer: &str) -> Result<WakeDelivery, BrokerError> {
         let url = self.endpoint("wake/receive")?;
         let response = self
-            .client
-            .post(url)
+            .post(url)?
             .json(&ReceiveRequest {
                 consumer: consumer.to_string(),
                 profile: None,
@@ -362,8 +407,7 @@ impl Broker for HttpBroker {
         let url = self.endpoint("ingress/publish")?;
         let dedupe_key = message.dedupe_key_or_hash();
         let response = self
-            .client
-            .post(url)
+            .post(url)?
             .json(&PublishIngressRequest { message })
             .send()
             .await
@@ -381,8 +425,7 @@ impl Broker for HttpBroker {
     async fn receive_ingress(&self, consumer: &str) -> Result<IngressDelivery, BrokerError> {
         let url = self.endpoint("ingress/receive")?;
         let response = self
-            .client
-            .post(url)
+            .post(url)?
             .json(&ReceiveRequest {
                 consumer: consumer.to_string(),
                 profile: None,
@@ -416,8 +459,7 @@ impl Broker for HttpBroker {
     async fn publish_event(&self, message: EventMessage) -> Result<(), BrokerError> {
         let url = self.endpoint("events/publish")?;
         let response = self
-            .client
-            .post(url)
+            .post(url)?
             .json(&PublishEventRequest { message })
             .send()
             .await
@@ -434,8 +476,7 @@ impl Broker for HttpBroker {
     async fn receive_event(&self, consumer: &str) -> Result<EventDelivery, BrokerError> {
         let url = self.endpoint("events/receive")?;
         let response = self
-            .client
-            .post(url)
+            .post(url)?
             .json(&ReceiveRequest {
                 consumer: consumer.to_string(),
                 profile: None,
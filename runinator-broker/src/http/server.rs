@@ -6,17 +6,21 @@ use crate::{
         ReceiveControlResponse, ReceiveEventResponse, ReceiveIngressResponse, ReceiveRequest,
         ReceiveResponse, ReceiveResultResponse, ReceiveWakeResponse,
     },
+    http::validation::{
+        check_size, negotiate_version, Rejection, Validate, BROKER_PROTOCOL_VERSION,
+        BROKER_VERSION_HEADER, MAX_MESSAGE_BYTES,
+    },
     Broker, BrokerError, ConsumerProfile,
 };
 use axum::{
-    extract::{Request, State},
-    http::StatusCode,
+    extract::{FromRequest, Request, State},
+    http::{HeaderValue, StatusCode},
     middleware::{self, Next},
     response::{IntoResponse, Response},
     routing::{get, post},
     Extension, Json, Router,
 };
-use serde::Serialize;
+use serde::{de::DeserializeOwned, Serialize};
 use std::{net::SocketAddr, sync::Arc};
 use tokio::net::TcpListener;
 
@@ -88,7 +92,8 @@ where
         .route("/ack", post(ack::<B>))
         .route("/nack", post(nack::<B>))
         .with_state(state)
-        .layer(middleware::from_fn_with_state(auth, authenticate));
+        .layer(middleware::from_fn_with_state(auth, authenticate))
+        .layer(middleware::from_fn(negotiate_protocol));
 
     axum::serve(listener, app).await
 }
@@ -115,6 +120,69 @@ async fn authenticate(
     next.run(request).await
 }
 
+// refuse clients that speak a protocol version this broker cannot, before auth or any handler
+// runs, and stamp the server's own version on every response so clients can log a mismatch.
+async fn negotiate_protocol(request: Request, next: Next) -> Response {
+    let header = request
+        .headers()
+        .get(BROKER_VERSION_HEADER)
+        .map(|value| value.to_str().unwrap_or_default());
+    let mut response = match negotiate_version(header) {
+        Ok(_) => next.run(request).await,
+        Err(rejection) => bad_request(rejection),
+    };
+    response.headers_mut().insert(
+        BROKER_VERSION_HEADER,
+        HeaderValue::from(BROKER_PROTOCOL_VERSION),
+    );
+    response
+}
+
+/// a json body that is size-capped and [`Validate`]d before the handler sees it; every failure,
+/// including undecodable json, is a structured 400.
+struct ValidJson<T>(T);
+
+impl<S, T> FromRequest<S> for ValidJson<T>
+where
+    S: Send + Sync,
+    T: DeserializeOwned + Validate,
+{
+    type Rejection = Response;
+
+    async fn from_request(request: Request, _state: &S) -> Result<Self, Self::Rejection> {
+        let declared = request
+            .headers()
+            .get(axum::http::header::CONTENT_LENGTH)
+            .and_then(|value| value.to_str().ok()?.parse::<usize>().ok());
+        if let Some(len) = declared {
+            check_size(len).map_err(bad_request)?;
+        }
+        let body = axum::body::to_bytes(request.into_body(), MAX_MESSAGE_BYTES)
+            .await
+            .map_err(|_| {
+                bad_request(Rejection::new(
+                    "message_too_large",
+                    format!("request body exceeds {MAX_MESSAGE_BYTES} bytes"),
+                ))
+            })?;
+        let value = serde_json::from_slice::<T>(&body).map_err(|err| {
+            json_response(
+                StatusCode::BAD_REQUEST,
+                ErrorResponse::new("invalid_json", err.to_string()),
+            )
+        })?;
+        value.validate().map_err(bad_request)?;
+        Ok(ValidJson(value))
+    }
+}
+
+fn bad_request(rejection: Rejection) -> Response {
+    json_response(
+        StatusCode::BAD_REQUEST,
+        ErrorResponse::new(rejection.code, rejection.message),
+    )
+}
+
 fn bearer_token(request: &Request) -> Option<String> {
     request
         .headers()
@@ -168,7 +236,7 @@ async fn health() -> Json<HealthResponse> {
 
 async fn publish<B>(
     State(state): State<AppState<B>>,
-    Json(request): Json<PublishRequest>,
+    ValidJson(request): ValidJson<PublishRequest>,
 ) -> Response
 where
     B: Broker,
@@ -194,7 +262,7 @@ where
 
 async fn receive_control<B>(
     State(state): State<AppState<B>>,
-    Json(request): Json<ReceiveRequest>,
+    ValidJson(request): ValidJson<ReceiveRequest>,
 ) -> Response
 where
     B: Broker,
@@ -211,7 +279,7 @@ where
 
 async fn nack_control<B>(
     State(state): State<AppState<B>>,
-    Json(request): Json<AckRequest>,
+    ValidJson(request): ValidJson<AckRequest>,
 ) -> Response
 where
     B: Broker,
@@ -240,7 +308,7 @@ where
 
 async fn receive_result<B>(
     State(state): State<AppState<B>>,
-    Json(request): Json<ReceiveRequest>,
+    ValidJson(request): ValidJson<ReceiveRequest>,
 ) -> Response
 where
     B: Broker,
@@ -253,7 +321,7 @@ where
 
 async fn ack_result<B>(
     State(state): State<AppState<B>>,
-    Json(request): Json<AckRequest>,
+    ValidJson(request): ValidJson<AckRequest>,
 ) -> Response
 where
     B: Broker,
@@ -269,7 +337,7 @@ where
 
 async fn nack_result<B>(
     State(state): State<AppState<B>>,
-    Json(request): Json<AckRequest>,
+    ValidJson(request): ValidJson<AckRequest>,
 ) -> Response
 where
     B: Broker,
@@ -285,7 +353,7 @@ where
 
 async fn ack_control<B>(
     State(state): State<AppState<B>>,
-    Json(request): Json<AckRequest>,
+    ValidJson(request): ValidJson<AckRequest>,
 ) -> Response
 where
     B: Broker,
@@ -314,7 +382,7 @@ where
 
 async fn receive_wake<B>(
     State(state): State<AppState<B>>,
-    Json(request): Json<ReceiveRequest>,
+    ValidJson(request): ValidJson<ReceiveRequest>,
 ) -> Response
 where
     B: Broker,
@@ -325,7 +393,10 @@ where
     }
 }
 
-async fn ack_wake<B>(State(state): State<AppState<B>>, Json(request): Json<AckRequest>) -> Response
+async fn ack_wake<B>(
+    State(state): State<AppState<B>>,
+    ValidJson(request): ValidJson<AckRequest>,
+) -> Response
 where
     B: Broker,
 {
@@ -338,7 +409,10 @@ where
     )
 }
 
-async fn nack_wake<B>(State(state): State<AppState<B>>, Json(request): Json<AckRequest>) -> Response
+async fn nack_wake<B>(
+    State(state): State<AppState<B>>,
+    ValidJson(request): ValidJson<AckRequest>,
+) -> Response
 where
     B: Broker,
 {
@@ -366,7 +440,7 @@ where
 
 async fn receive_ingress<B>(
     State(state): State<AppState<B>>,
-    Json(request): Json<ReceiveRequest>,
+    ValidJson(request): ValidJson<ReceiveRequest>,
 ) -> Response
 where
     B: Broker,
@@ -379,7 +453,7 @@ where
 
 async fn ack_ingress<B>(
     State(state): State<AppState<B>>,
-    Json(request): Json<AckRequest>,
+    ValidJson(request): ValidJson<AckRequest>,
 ) -> Response
 where
     B: Broker,
@@ -395,7 +469,7 @@ where
 
 async fn nack_ingress<B>(
     State(state): State<AppState<B>>,
-    Json(request): Json<AckRequest>,
+    ValidJson(request): ValidJson<AckRequest>,
 ) -> Response
 where
     B: Broker,
@@ -424,7 +498,7 @@ where
 
 async fn receive_event<B>(
     State(state): State<AppState<B>>,
-    Json(request): Json<ReceiveRequest>,
+    ValidJson(request): ValidJson<ReceiveRequest>,
 ) -> Response
 where
     B: Broker,
@@ -438,7 +512,7 @@ where
 async fn receive<B>(
     State(state): State<AppState<B>>,
     Extension(identity): Extension<AuthIdentity>,
-    Json(request): Json<ReceiveRequest>,
+    ValidJson(request): ValidJson<ReceiveRequest>,
 ) -> Response
 where
     B: Broker,
@@ -459,7 +533,7 @@ where
 async fn poll<B>(
     State(state): State<AppState<B>>,
     Extension(identity): Extension<AuthIdentity>,
-    Json(request): Json<PollRequest>,
+    ValidJson(request): ValidJson<PollRequest>,
 ) -> Response
 where
     B: Broker,
@@ -491,7 +565,10 @@ where
     }
 }
 
-async fn ack<B>(State(state): State<AppState<B>>, Json(request): Json<AckRequest>) -> Response
+async fn ack<B>(
+    State(state): State<AppState<B>>,
+    ValidJson(request): ValidJson<AckRequest>,
+) -> Response
 where
     B: Broker,
 {
@@ -504,14 +581,17 @@ where
     )
 }
 
-async fn nack<B>(State(state): State<AppState<B>>, Json(request): Json<AckRequest>) -> Response
+async fn nack<B>(
+    State(state): State<AppState<B>>,
+    ValidJson(request): ValidJson<AckRequest>,
+) -> Response
 where
     B: Broker,
 {
     respond(
         state
             .broker
-            .nack(&request.consumer, request.delivery_id)
+            .nack_with_error(&request.consumer, request.delivery_id, request.error)
             .await,
         StatusCode::OK,
     )
@@ -548,6 +628,10 @@ fn error_response(err: BrokerError) -> Response {
             StatusCode::NOT_IMPLEMENTED,
             ErrorResponse::new("feature_disabled", feature),
         ),
+        BrokerError::Rejected(message) => json_response(
+            StatusCode::BAD_REQUEST,
+            ErrorResponse::new("rejected", message),
+        ),
         BrokerError::ConsumerStreamEnded => json_response(
             StatusCode::SERVICE_UNAVAILABLE,
             ErrorResponse::new("consumer_stream_ended", "consumer stream ended"),
@@ -91,4 +91,7 @@ pub struct PollResponse {
 pub struct AckRequest {
     pub consumer: String,
     pub delivery_id: Uuid,
+    /// why the delivery is being returned; only meaningful on an action nack.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
 }
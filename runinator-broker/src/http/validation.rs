@@ -0,0 +1,178 @@
+//! protocol version negotiation and payload validation for the http broker. clients send
+//! [`BROKER_VERSION_HEADER`] on every request and the server rejects versions it cannot speak;
+//! request bodies are checked before they reach the backend so a misbehaving client gets a
+//! structured 400 instead of leaving malformed messages in the queue.
+
+use crate::http::types::{AckRequest, PollRequest, PublishRequest, ReceiveRequest};
+
+/// request/response header carrying the broker http protocol version.
+pub const BROKER_VERSION_HEADER: &str = "x-runinator-broker-version";
+/// the protocol version this build speaks.
+pub const BROKER_PROTOCOL_VERSION: u32 = 1;
+/// the oldest client protocol version the server still accepts. requests without the header
+/// predate negotiation and are treated as this version.
+pub const MIN_BROKER_PROTOCOL_VERSION: u32 = 1;
+
+/// largest request body the validated endpoints accept, in bytes.
+pub const MAX_MESSAGE_BYTES: usize = 1024 * 1024;
+pub const MAX_DEDUPE_KEY_LEN: usize = 256;
+pub const MAX_CONSUMER_ID_LEN: usize = 128;
+pub const MAX_QUEUE_NAME_LEN: usize = 64;
+
+/// why a request was refused; `code` is the machine-readable half of the 400 body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rejection {
+    pub code: &'static str,
+    pub message: String,
+}
+
+impl Rejection {
+    pub(crate) fn new(code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+/// check a client's version header value; `None` (no header) is accepted as the minimum version.
+pub fn negotiate_version(header: Option<&str>) -> Result<u32, Rejection> {
+    let Some(value) = header else {
+        return Ok(MIN_BROKER_PROTOCOL_VERSION);
+    };
+    let version = value.trim().parse::<u32>().map_err(|_| {
+        Rejection::new(
+            "invalid_protocol_version",
+            format!("{BROKER_VERSION_HEADER} must be an integer, got '{value}'"),
+        )
+    })?;
+    if !(MIN_BROKER_PROTOCOL_VERSION..=BROKER_PROTOCOL_VERSION).contains(&version) {
+        return Err(Rejection::new(
+            "unsupported_protocol_version",
+            format!(
+                "client speaks broker protocol {version}; this broker accepts \
+                 {MIN_BROKER_PROTOCOL_VERSION} through {BROKER_PROTOCOL_VERSION}"
+            ),
+        ));
+    }
+    Ok(version)
+}
+
+/// reject a body over [`MAX_MESSAGE_BYTES`].
+pub fn check_size(len: usize) -> Result<(), Rejection> {
+    if len > MAX_MESSAGE_BYTES {
+        return Err(Rejection::new(
+            "message_too_large",
+            format!("request body is {len} bytes; the limit is {MAX_MESSAGE_BYTES}"),
+        ));
+    }
+    Ok(())
+}
+
+/// a consumer id is 1..=[`MAX_CONSUMER_ID_LEN`] ascii letters, digits, or `-_.:@/`, which covers
+/// hostnames, replica uuids, and group names without letting whitespace or control bytes through.
+pub fn validate_consumer_id(consumer: &str) -> Result<(), Rejection> {
+    if consumer.is_empty() {
+        return Err(Rejection::new("invalid_consumer", "consumer id is empty"));
+    }
+    if consumer.len() > MAX_CONSUMER_ID_LEN {
+        return Err(Rejection::new(
+            "invalid_consumer",
+            format!("consumer id is longer than {MAX_CONSUMER_ID_LEN} bytes"),
+        ));
+    }
+    if let Some(bad) = consumer
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || "-_.:@/".contains(*c)))
+    {
+        return Err(Rejection::new(
+            "invalid_consumer",
+            format!("consumer id '{consumer}' contains {bad:?}"),
+        ));
+    }
+    Ok(())
+}
+
+pub fn validate_dedupe_key(key: &str) -> Result<(), Rejection> {
+    if key.trim().is_empty() {
+        return Err(Rejection::new("invalid_dedupe_key", "dedupe key is blank"));
+    }
+    if key.len() > MAX_DEDUPE_KEY_LEN {
+        return Err(Rejection::new(
+            "invalid_dedupe_key",
+            format!(
+                "dedupe key is {} bytes; the limit is {MAX_DEDUPE_KEY_LEN}",
+                key.len()
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// a queue name is 1..=[`MAX_QUEUE_NAME_LEN`] ascii letters, digits, or `-_.`.
+pub fn validate_queue_name(queue: &str) -> Result<(), Rejection> {
+    if queue.is_empty() {
+        return Err(Rejection::new("invalid_queue", "queue name is empty"));
+    }
+    if queue.len() > MAX_QUEUE_NAME_LEN {
+        return Err(Rejection::new(
+            "invalid_queue",
+            format!("queue name is longer than {MAX_QUEUE_NAME_LEN} bytes"),
+        ));
+    }
+    if let Some(bad) = queue
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || "-_.".contains(*c)))
+    {
+        return Err(Rejection::new(
+            "invalid_queue",
+            format!("queue name '{queue}' contains {bad:?}"),
+        ));
+    }
+    Ok(())
+}
+
+/// a request body the server checks after decoding and before touching the backend.
+pub trait Validate {
+    fn validate(&self) -> Result<(), Rejection>;
+}
+
+impl Validate for PublishRequest {
+    fn validate(&self) -> Result<(), Rejection> {
+        if let Some(queue) = &self.message.queue {
+            validate_queue_name(queue)?;
+        }
+        match &self.message.dedupe_key {
+            Some(key) => validate_dedupe_key(key),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Validate for ReceiveRequest {
+    fn validate(&self) -> Result<(), Rejection> {
+        validate_consumer_id(&self.consumer)?;
+        match &self.profile {
+            Some(profile) => {
+                validate_consumer_id(&profile.id)?;
+                profile
+                    .queues
+                    .iter()
+                    .try_for_each(|queue| validate_queue_name(queue))
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+impl Validate for PollRequest {
+    fn validate(&self) -> Result<(), Rejection> {
+        validate_consumer_id(&self.consumer)
+    }
+}
+
+impl Validate for AckRequest {
+    fn validate(&self) -> Result<(), Rejection> {
+        validate_consumer_id(&self.consumer)
+    }
+}
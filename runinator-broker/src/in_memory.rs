@@ -1,9 +1,11 @@
+use crate::history::{HistoryLog, MessageHistory, MessageState};
 use crate::{
     Broker, BrokerDelivery, BrokerError, BrokerMessage, ConsumerProfile, ControlCommand,
-    ControlDelivery, EventDelivery, EventMessage, IngressDelivery, IngressMessage, ResultDelivery,
-    ResultMessage, WakeDelivery, WakeMessage,
+    ControlDelivery, EventDelivery, EventMessage, IngressDelivery, IngressMessage, PriorityAging,
+    QueuedMessage, ResultDelivery, ResultMessage, WakeDelivery, WakeMessage,
 };
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use parking_lot::Mutex;
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::Arc;
@@ -29,6 +31,7 @@ struct BrokerState {
     ingress_queue: VecDeque<IngressDelivery>,
     ingress_inflight: HashMap<Uuid, Leased<IngressDelivery>>,
     ingress_dedupe: HashSet<String>,
+    history: HistoryLog,
 }
 
 struct Leased<T> {
@@ -50,6 +53,7 @@ pub struct InMemoryBroker {
     event_tx: broadcast::Sender<EventDelivery>,
     event_subscribers: Arc<Mutex<HashMap<String, EventReceiver>>>,
     lease_duration: Duration,
+    priority_aging: PriorityAging,
 }
 
 impl InMemoryBroker {
@@ -65,6 +69,14 @@ impl InMemoryBroker {
             ..Self::default()
         }
     }
+
+    /// set how queued actions gain priority while they wait.
+    pub fn with_priority_aging(self, priority_aging: PriorityAging) -> Self {
+        Self {
+            priority_aging,
+            ..self
+        }
+    }
 }
 
 impl Default for InMemoryBroker {
@@ -80,6 +92,7 @@ impl Default for InMemoryBroker {
             event_tx,
             event_subscribers: Arc::new(Mutex::new(HashMap::new())),
             lease_duration: Self::DEFAULT_LEASE_DURATION,
+            priority_aging: PriorityAging::default(),
         }
     }
 }
@@ -90,6 +103,7 @@ impl InMemoryBroker {
     /// control targeted at a consumer that never returns is dropped once it goes stale.
     async fn receive_control_matching(
         &self,
+        consumer: &str,
         matches: impl Fn(&ControlDelivery) -> bool,
     ) -> Result<ControlDelivery, BrokerError> {
         loop {
@@ -113,6 +127,7 @@ impl InMemoryBroker {
                                 leased_until: Instant::now() + self.lease_duration,
                             },
                         );
+                        guard.history.delivered(delivery.delivery_id, consumer);
                         Some(delivery)
                     }
                     None => None,
@@ -154,6 +169,9 @@ impl Broker for InMemoryBroker {
         }
 
         let delivery: BrokerDelivery = message.into();
+        guard
+            .history
+            .published("action", delivery.delivery_id, Some(&delivery.dedupe_key));
         guard.queue.push_back(delivery);
         drop(guard);
         // deliveries are targeted, so wake every waiter: notify_one could wake a consumer whose
@@ -179,12 +197,10 @@ impl Broker for InMemoryBroker {
             if let Some(delivery) = {
                 let mut guard = self.state.lock();
                 reclaim_expired_actions(&mut guard, Instant::now());
-                // scan for the first delivery whose target matches this consumer. a non-matching
+                // scan every delivery whose target and queue match this consumer: a non-matching
                 // head must not block matching deliveries queued behind it.
-                let index = guard
-                    .queue
-                    .iter()
-                    .position(|delivery| delivery.command.target.matches(profile));
+                let index =
+                    next_action_index(&guard.queue, profile, self.priority_aging, Utc::now());
                 match index.and_then(|index| guard.queue.remove(index)) {
                     Some(delivery) => {
                         guard.inflight.insert(
@@ -194,6 +210,7 @@ impl Broker for InMemoryBroker {
                                 leased_until: Instant::now() + self.lease_duration,
                             },
                         );
+                        guard.history.delivered(delivery.delivery_id, &profile.id);
                         Some(delivery)
                     }
                     None => None,
@@ -209,20 +226,38 @@ impl Broker for InMemoryBroker {
         }
     }
 
-    async fn ack(&self, _consumer: &str, delivery_id: Uuid) -> Result<(), BrokerError> {
+    async fn ack(&self, consumer: &str, delivery_id: Uuid) -> Result<(), BrokerError> {
         let mut guard = self.state.lock();
         if let Some(leased) = guard.inflight.remove(&delivery_id) {
             guard.dedupe.remove(&leased.delivery.dedupe_key);
+            guard.history.acked(delivery_id, consumer);
             Ok(())
         } else {
             Err(BrokerError::UnknownDelivery(delivery_id))
         }
     }
 
-    async fn nack(&self, _consumer: &str, delivery_id: Uuid) -> Result<(), BrokerError> {
+    async fn nack(&self, consumer: &str, delivery_id: Uuid) -> Result<(), BrokerError> {
+        self.nack_with_error(consumer, delivery_id, None).await
+    }
+
+    async fn nack_with_error(
+        &self,
+        consumer: &str,
+        delivery_id: Uuid,
+        error: Option<String>,
+    ) -> Result<(), BrokerError> {
         let mut guard = self.state.lock();
         if let Some(leased) = guard.inflight.remove(&delivery_id) {
-            guard.queue.push_front(redeliver_action(leased.delivery));
+            let redelivery = redeliver_action(leased.delivery, error.clone());
+            guard.history.requeued(
+                MessageState::Nacked,
+                delivery_id,
+                redelivery.delivery_id,
+                Some(consumer),
+                error,
+            );
+            guard.queue.push_front(redelivery);
             drop(guard);
             // wake sleeping consumers so a requeued delivery is not stranded until the sleep
             // fallback when the nacking consumer disconnects right after returning it.
@@ -235,7 +270,11 @@ impl Broker for InMemoryBroker {
 
     async fn publish_control(&self, command: ControlCommand) -> Result<(), BrokerError> {
         let mut guard = self.state.lock();
-        guard.control_queue.push_back(command.into());
+        let delivery: ControlDelivery = command.into();
+        guard
+            .history
+            .published("control", delivery.delivery_id, None);
+        guard.control_queue.push_back(delivery);
         drop(guard);
         // controls are targeted, so wake every waiter: notify_one could wake a consumer whose
         // profile does not match, leaving the matching consumer asleep for a full lease period.
@@ -243,34 +282,43 @@ impl Broker for InMemoryBroker {
         Ok(())
     }
 
-    async fn receive_control(&self, _consumer: &str) -> Result<ControlDelivery, BrokerError> {
+    async fn receive_control(&self, consumer: &str) -> Result<ControlDelivery, BrokerError> {
         // the legacy untargeted path: hand over the head of the queue regardless of target.
-        self.receive_control_matching(|_| true).await
+        self.receive_control_matching(consumer, |_| true).await
     }
 
     async fn receive_control_for(
         &self,
         profile: &ConsumerProfile,
     ) -> Result<ControlDelivery, BrokerError> {
-        self.receive_control_matching(|delivery| delivery.command.target.matches(profile))
-            .await
+        self.receive_control_matching(&profile.id, |delivery| {
+            delivery.command.target.matches(profile)
+        })
+        .await
     }
 
-    async fn ack_control(&self, _consumer: &str, delivery_id: Uuid) -> Result<(), BrokerError> {
+    async fn ack_control(&self, consumer: &str, delivery_id: Uuid) -> Result<(), BrokerError> {
         let mut guard = self.state.lock();
         if guard.control_inflight.remove(&delivery_id).is_some() {
+            guard.history.acked(delivery_id, consumer);
             Ok(())
         } else {
             Err(BrokerError::UnknownDelivery(delivery_id))
         }
     }
 
-    async fn nack_control(&self, _consumer: &str, delivery_id: Uuid) -> Result<(), BrokerError> {
+    async fn nack_control(&self, consumer: &str, delivery_id: Uuid) -> Result<(), BrokerError> {
         let mut guard = self.state.lock();
         if let Some(leased) = guard.control_inflight.remove(&delivery_id) {
-            guard
-                .control_queue
-                .push_front(redeliver_control(leased.delivery));
+            let redelivery = redeliver_control(leased.delivery);
+            guard.history.requeued(
+                MessageState::Nacked,
+                delivery_id,
+                redelivery.delivery_id,
+                Some(consumer),
+                None,
+            );
+            guard.control_queue.push_front(redelivery);
             drop(guard);
             self.control_notify.notify_waiters();
             Ok(())
@@ -287,13 +335,16 @@ impl Broker for InMemoryBroker {
         }
 
         let delivery: ResultDelivery = message.into();
+        guard
+            .history
+            .published("result", delivery.delivery_id, Some(&delivery.dedupe_key));
         guard.result_queue.push_back(delivery);
         drop(guard);
         self.result_notify.notify_one();
         Ok(())
     }
 
-    async fn receive_result(&self, _consumer: &str) -> Result<ResultDelivery, BrokerError> {
+    async fn receive_result(&self, consumer: &str) -> Result<ResultDelivery, BrokerError> {
         loop {
             if let Some(delivery) = {
                 let mut guard = self.state.lock();
@@ -306,6 +357,7 @@ impl Broker for InMemoryBroker {
                             leased_until: Instant::now() + self.lease_duration,
                         },
                     );
+                    guard.history.delivered(delivery.delivery_id, consumer);
                     Some(delivery)
                 } else {
                     None
@@ -321,22 +373,29 @@ impl Broker for InMemoryBroker {
         }
     }
 
-    async fn ack_result(&self, _consumer: &str, delivery_id: Uuid) -> Result<(), BrokerError> {
+    async fn ack_result(&self, consumer: &str, delivery_id: Uuid) -> Result<(), BrokerError> {
         let mut guard = self.state.lock();
         if let Some(leased) = guard.result_inflight.remove(&delivery_id) {
             guard.result_dedupe.remove(&leased.delivery.dedupe_key);
+            guard.history.acked(delivery_id, consumer);
             Ok(())
         } else {
             Err(BrokerError::UnknownDelivery(delivery_id))
         }
     }
 
-    async fn nack_result(&self, _consumer: &str, delivery_id: Uuid) -> Result<(), BrokerError> {
+    async fn nack_result(&self, consumer: &str, delivery_id: Uuid) -> Result<(), BrokerError> {
         let mut guard = self.state.lock();
         if let Some(leased) = guard.result_inflight.remove(&delivery_id) {
-            guard
-                .result_queue
-                .push_front(redeliver_result(leased.delivery));
+            let redelivery = redeliver_result(leased.delivery);
+            guard.history.requeued(
+                MessageState::Nacked,
+                delivery_id,
+                redelivery.delivery_id,
+                Some(consumer),
+                None,
+            );
+            guard.result_queue.push_front(redelivery);
             drop(guard);
             self.result_notify.notify_one();
             Ok(())
@@ -353,13 +412,16 @@ impl Broker for InMemoryBroker {
         }
 
         let delivery: WakeDelivery = message.into();
+        guard
+            .history
+            .published("wake", delivery.delivery_id, Some(&delivery.dedupe_key));
         guard.wake_queue.push_back(delivery);
         drop(guard);
         self.wake_notify.notify_one();
         Ok(())
     }
 
-    async fn receive_wake(&self, _consumer: &str) -> Result<WakeDelivery, BrokerError> {
+    async fn receive_wake(&self, consumer: &str) -> Result<WakeDelivery, BrokerError> {
         loop {
             if let Some(delivery) = {
                 let mut guard = self.state.lock();
@@ -372,6 +434,7 @@ impl Broker for InMemoryBroker {
                             leased_until: Instant::now() + self.lease_duration,
                         },
                     );
+                    guard.history.delivered(delivery.delivery_id, consumer);
                     Some(delivery)
                 } else {
                     None
@@ -387,20 +450,29 @@ impl Broker for InMemoryBroker {
         }
     }
 
-    async fn ack_wake(&self, _consumer: &str, delivery_id: Uuid) -> Result<(), BrokerError> {
+    async fn ack_wake(&self, consumer: &str, delivery_id: Uuid) -> Result<(), BrokerError> {
         let mut guard = self.state.lock();
         if let Some(leased) = guard.wake_inflight.remove(&delivery_id) {
             guard.wake_dedupe.remove(&leased.delivery.dedupe_key);
+            guard.history.acked(delivery_id, consumer);
             Ok(())
         } else {
             Err(BrokerError::UnknownDelivery(delivery_id))
         }
     }
 
-    async fn nack_wake(&self, _consumer: &str, delivery_id: Uuid) -> Result<(), BrokerError> {
+    async fn nack_wake(&self, consumer: &str, delivery_id: Uuid) -> Result<(), BrokerError> {
         let mut guard = self.state.lock();
         if let Some(leased) = guard.wake_inflight.remove(&delivery_id) {
-            guard.wake_queue.push_front(redeliver_wake(leased.delivery));
+            let redelivery = redeliver_wake(leased.delivery);
+            guard.history.requeued(
+                MessageState::Nacked,
+                delivery_id,
+                redelivery.delivery_id,
+                Some(consumer),
+                None,
+            );
+            guard.wake_queue.push_front(redelivery);
             drop(guard);
             self.wake_notify.notify_one();
             Ok(())
@@ -417,13 +489,16 @@ impl Broker for InMemoryBroker {
         }
 
         let delivery: IngressDelivery = message.into();
+        guard
+            .history
+            .published("ingress", delivery.delivery_id, Some(&delivery.dedupe_key));
         guard.ingress_queue.push_back(delivery);
         drop(guard);
         self.ingress_notify.notify_one();
         Ok(())
     }
 
-    async fn receive_ingress(&self, _consumer: &str) -> Result<IngressDelivery, BrokerError> {
+    async fn receive_ingress(&self, consumer: &str) -> Result<IngressDelivery, BrokerError> {
         loop {
             if let Some(delivery) = {
                 let mut guard = self.state.lock();
@@ -436,6 +511,7 @@ impl Broker for InMemoryBroker {
                             leased_until: Instant::now() + self.lease_duration,
                         },
                     );
+                    guard.history.delivered(delivery.delivery_id, consumer);
                     Some(delivery)
                 } else {
                     None
@@ -451,22 +527,29 @@ impl Broker for InMemoryBroker {
         }
     }
 
-    async fn ack_ingress(&self, _consumer: &str, delivery_id: Uuid) -> Result<(), BrokerError> {
+    async fn ack_ingress(&self, consumer: &str, delivery_id: Uuid) -> Result<(), BrokerError> {
         let mut guard = self.state.lock();
         if let Some(leased) = guard.ingress_inflight.remove(&delivery_id) {
             guard.ingress_dedupe.remove(&leased.delivery.dedupe_key);
+            guard.history.acked(delivery_id, consumer);
             Ok(())
         } else {
             Err(BrokerError::UnknownDelivery(delivery_id))
         }
     }
 
-    async fn nack_ingress(&self, _consumer: &str, delivery_id: Uuid) -> Result<(), BrokerError> {
+    async fn nack_ingress(&self, consumer: &str, delivery_id: Uuid) -> Result<(), BrokerError> {
         let mut guard = self.state.lock();
         if let Some(leased) = guard.ingress_inflight.remove(&delivery_id) {
-            guard
-                .ingress_queue
-                .push_front(redeliver_ingress(leased.delivery));
+            let redelivery = redeliver_ingress(leased.delivery);
+            guard.history.requeued(
+                MessageState::Nacked,
+                delivery_id,
+                redelivery.delivery_id,
+                Some(consumer),
+                None,
+            );
+            guard.ingress_queue.push_front(redelivery);
             drop(guard);
             self.ingress_notify.notify_one();
             Ok(())
@@ -495,13 +578,231 @@ impl Broker for InMemoryBroker {
             }
         }
     }
+
+    async fn list_messages(&self) -> Result<Vec<QueuedMessage>, BrokerError> {
+        let guard = self.state.lock();
+        let mut messages = Vec::new();
+        for (delivery, in_flight) in guard
+            .queue
+            .iter()
+            .map(|d| (d, false))
+            .chain(guard.inflight.values().map(|l| (&l.delivery, true)))
+        {
+            messages.push(queued_message(
+                delivery.delivery_id,
+                "action",
+                in_flight,
+                Some(&delivery.dedupe_key),
+                delivery.enqueued_at,
+                &delivery.command,
+            ));
+        }
+        for (delivery, in_flight) in guard
+            .control_queue
+            .iter()
+            .map(|d| (d, false))
+            .chain(guard.control_inflight.values().map(|l| (&l.delivery, true)))
+        {
+            messages.push(queued_message(
+                delivery.delivery_id,
+                "control",
+                in_flight,
+                None,
+                delivery.enqueued_at,
+                &delivery.command,
+            ));
+        }
+        for (delivery, in_flight) in guard
+            .result_queue
+            .iter()
+            .map(|d| (d, false))
+            .chain(guard.result_inflight.values().map(|l| (&l.delivery, true)))
+        {
+            messages.push(queued_message(
+                delivery.delivery_id,
+                "result",
+                in_flight,
+                Some(&delivery.dedupe_key),
+                delivery.enqueued_at,
+                &delivery.event,
+            ));
+        }
+        for (delivery, in_flight) in guard
+            .wake_queue
+            .iter()
+            .map(|d| (d, false))
+            .chain(guard.wake_inflight.values().map(|l| (&l.delivery, true)))
+        {
+            messages.push(queued_message(
+                delivery.delivery_id,
+                "wake",
+                in_flight,
+                Some(&delivery.dedupe_key),
+                delivery.enqueued_at,
+                &delivery.command,
+            ));
+        }
+        for (delivery, in_flight) in guard
+            .ingress_queue
+            .iter()
+            .map(|d| (d, false))
+            .chain(guard.ingress_inflight.values().map(|l| (&l.delivery, true)))
+        {
+            messages.push(queued_message(
+                delivery.delivery_id,
+                "ingress",
+                in_flight,
+                Some(&delivery.dedupe_key),
+                delivery.enqueued_at,
+                &delivery.command,
+            ));
+        }
+        drop(guard);
+        messages.sort_by_key(|message| message.enqueued_at);
+        Ok(messages)
+    }
+
+    async fn requeue_message(&self, delivery_id: Uuid) -> Result<(), BrokerError> {
+        // an operator requeue is a nack on whichever channel holds the lease.
+        const OPERATOR: &str = "operator";
+        if self.nack(OPERATOR, delivery_id).await.is_ok()
+            || self.nack_control(OPERATOR, delivery_id).await.is_ok()
+            || self.nack_result(OPERATOR, delivery_id).await.is_ok()
+            || self.nack_wake(OPERATOR, delivery_id).await.is_ok()
+            || self.nack_ingress(OPERATOR, delivery_id).await.is_ok()
+        {
+            return Ok(());
+        }
+        Err(BrokerError::UnknownDelivery(delivery_id))
+    }
+
+    async fn message_history(&self, key: &str) -> Result<Vec<MessageHistory>, BrokerError> {
+        Ok(self.state.lock().history.find(key))
+    }
+
+    async fn purge_messages(&self, older_than: DateTime<Utc>) -> Result<usize, BrokerError> {
+        let mut guard = self.state.lock();
+        let state = &mut *guard;
+        let mut purged = 0;
+        purge_queue(
+            &mut state.queue,
+            &mut state.dedupe,
+            &mut state.history,
+            older_than,
+            &mut purged,
+            |d| (d.delivery_id, d.enqueued_at, &d.dedupe_key),
+        );
+        purge_queue(
+            &mut state.result_queue,
+            &mut state.result_dedupe,
+            &mut state.history,
+            older_than,
+            &mut purged,
+            |d| (d.delivery_id, d.enqueued_at, &d.dedupe_key),
+        );
+        purge_queue(
+            &mut state.wake_queue,
+            &mut state.wake_dedupe,
+            &mut state.history,
+            older_than,
+            &mut purged,
+            |d| (d.delivery_id, d.enqueued_at, &d.dedupe_key),
+        );
+        purge_queue(
+            &mut state.ingress_queue,
+            &mut state.ingress_dedupe,
+            &mut state.history,
+            older_than,
+            &mut purged,
+            |d| (d.delivery_id, d.enqueued_at, &d.dedupe_key),
+        );
+        let controls = state.control_queue.len();
+        let history = &mut state.history;
+        state.control_queue.retain(|delivery| {
+            let keep = delivery.enqueued_at >= older_than;
+            if !keep {
+                history.dropped(MessageState::Purged, delivery.delivery_id);
+            }
+            keep
+        });
+        purged += controls - state.control_queue.len();
+        Ok(purged)
+    }
+}
+
+fn queued_message(
+    delivery_id: Uuid,
+    channel: &str,
+    in_flight: bool,
+    dedupe_key: Option<&String>,
+    enqueued_at: DateTime<Utc>,
+    payload: &impl serde::Serialize,
+) -> QueuedMessage {
+    QueuedMessage {
+        delivery_id,
+        channel: channel.to_string(),
+        in_flight,
+        dedupe_key: dedupe_key.cloned(),
+        enqueued_at,
+        payload: serde_json::to_value(payload).unwrap_or_default(),
+    }
+}
+
+// drop queued deliveries older than the cutoff, releasing their dedupe keys.
+fn purge_queue<T>(
+    queue: &mut VecDeque<T>,
+    dedupe: &mut HashSet<String>,
+    history: &mut HistoryLog,
+    older_than: DateTime<Utc>,
+    purged: &mut usize,
+    key: impl Fn(&T) -> (Uuid, DateTime<Utc>, &String),
+) {
+    queue.retain(|delivery| {
+        let (delivery_id, enqueued_at, dedupe_key) = key(delivery);
+        if enqueued_at >= older_than {
+            return true;
+        }
+        dedupe.remove(dedupe_key);
+        history.dropped(MessageState::Purged, delivery_id);
+        *purged += 1;
+        false
+    });
+}
+
+/// the matching delivery with the highest effective priority; ties go to the one nearest the head.
+fn next_action_index(
+    queue: &VecDeque<BrokerDelivery>,
+    profile: &ConsumerProfile,
+    aging: PriorityAging,
+    now: DateTime<Utc>,
+) -> Option<usize> {
+    let mut best: Option<(usize, i64)> = None;
+    for (index, delivery) in queue.iter().enumerate() {
+        if !delivery.matches(profile) {
+            continue;
+        }
+        let priority = aging.effective_priority(delivery, now);
+        if best.is_none_or(|(_, best_priority)| priority > best_priority) {
+            best = Some((index, priority));
+        }
+    }
+    best.map(|(index, _)| index)
 }
 
 fn reclaim_expired_actions(state: &mut BrokerState, now: Instant) {
     let expired = expired_ids(&state.inflight, now);
     for id in expired {
         if let Some(leased) = state.inflight.remove(&id) {
-            state.queue.push_front(redeliver_action(leased.delivery));
+            let error = Some("lease expired before the delivery was acked".to_string());
+            let redelivery = redeliver_action(leased.delivery, error.clone());
+            state.history.requeued(
+                MessageState::Expired,
+                id,
+                redelivery.delivery_id,
+                None,
+                error,
+            );
+            state.queue.push_front(redelivery);
         }
     }
 }
@@ -510,9 +811,15 @@ fn reclaim_expired_control(state: &mut BrokerState, now: Instant) {
     let expired = expired_ids(&state.control_inflight, now);
     for id in expired {
         if let Some(leased) = state.control_inflight.remove(&id) {
-            state
-                .control_queue
-                .push_front(redeliver_control(leased.delivery));
+            let redelivery = redeliver_control(leased.delivery);
+            state.history.requeued(
+                MessageState::Expired,
+                id,
+                redelivery.delivery_id,
+                None,
+                None,
+            );
+            state.control_queue.push_front(redelivery);
         }
     }
 }
@@ -521,8 +828,13 @@ fn reclaim_expired_control(state: &mut BrokerState, now: Instant) {
 /// has no consumer that can ever match it, and controls are immediate signals, so retaining one
 /// past the ttl only grows the queue (this broker also backs the long-lived http/tcp servers).
 fn drop_stale_control(state: &mut BrokerState, now: chrono::DateTime<chrono::Utc>) {
+    let history = &mut state.history;
     state.control_queue.retain(|delivery| {
-        (now - delivery.enqueued_at).num_seconds() < crate::STALE_CONTROL_TTL_SECONDS
+        let fresh = (now - delivery.enqueued_at).num_seconds() < crate::STALE_CONTROL_TTL_SECONDS;
+        if !fresh {
+            history.dropped(MessageState::Expired, delivery.delivery_id);
+        }
+        fresh
     });
 }
 
@@ -530,9 +842,15 @@ fn reclaim_expired_results(state: &mut BrokerState, now: Instant) {
     let expired = expired_ids(&state.result_inflight, now);
     for id in expired {
         if let Some(leased) = state.result_inflight.remove(&id) {
-            state
-                .result_queue
-                .push_front(redeliver_result(leased.delivery));
+            let redelivery = redeliver_result(leased.delivery);
+            state.history.requeued(
+                MessageState::Expired,
+                id,
+                redelivery.delivery_id,
+                None,
+                None,
+            );
+            state.result_queue.push_front(redelivery);
         }
     }
 }
@@ -541,7 +859,15 @@ fn reclaim_expired_wakes(state: &mut BrokerState, now: Instant) {
     let expired = expired_ids(&state.wake_inflight, now);
     for id in expired {
         if let Some(leased) = state.wake_inflight.remove(&id) {
-            state.wake_queue.push_front(redeliver_wake(leased.delivery));
+            let redelivery = redeliver_wake(leased.delivery);
+            state.history.requeued(
+                MessageState::Expired,
+                id,
+                redelivery.delivery_id,
+                None,
+                None,
+            );
+            state.wake_queue.push_front(redelivery);
         }
     }
 }
@@ -550,9 +876,15 @@ fn reclaim_expired_ingress(state: &mut BrokerState, now: Instant) {
     let expired = expired_ids(&state.ingress_inflight, now);
     for id in expired {
         if let Some(leased) = state.ingress_inflight.remove(&id) {
-            state
-                .ingress_queue
-                .push_front(redeliver_ingress(leased.delivery));
+            let redelivery = redeliver_ingress(leased.delivery);
+            state.history.requeued(
+                MessageState::Expired,
+                id,
+                redelivery.delivery_id,
+                None,
+                None,
+            );
+            state.ingress_queue.push_front(redelivery);
         }
     }
 }
@@ -564,9 +896,12 @@ fn expired_ids<T>(inflight: &HashMap<Uuid, Leased<T>>, now: Instant) -> Vec<Uuid
         .collect()
 }
 
-fn redeliver_action(delivery: BrokerDelivery) -> BrokerDelivery {
+/// a redelivered action counts as a new attempt; a nack without a reason keeps the previous one.
+fn redeliver_action(delivery: BrokerDelivery, error: Option<String>) -> BrokerDelivery {
     BrokerDelivery {
         delivery_id: Uuid::new_v4(),
+        attempt: delivery.attempt.saturating_add(1),
+        last_error: error.or(delivery.last_error),
         ..delivery
     }
 }
@@ -623,6 +958,8 @@ mod tests {
                 command: action_command(),
                 dedupe_key: Some("lease-action".into()),
                 enqueued_at: Utc::now(),
+                priority: 0,
+                queue: None,
             })
             .await
             .unwrap();
@@ -637,6 +974,42 @@ mod tests {
         broker.ack("consumer-b", second.delivery_id).await.unwrap();
     }
 
+    #[tokio::test]
+    async fn nacked_action_delivery_counts_attempts_and_keeps_the_last_error() {
+        let broker = InMemoryBroker::new();
+        let enqueued_at = Utc::now() - chrono::Duration::seconds(30);
+        broker
+            .publish(BrokerMessage {
+                command: action_command(),
+                dedupe_key: Some("attempts".into()),
+                enqueued_at,
+                priority: 0,
+                queue: None,
+            })
+            .await
+            .unwrap();
+
+        let first = broker.receive("consumer-a").await.unwrap();
+        assert_eq!(first.attempt, 1);
+        assert_eq!(first.last_error, None);
+        broker
+            .nack_with_error("consumer-a", first.delivery_id, Some("disk full".into()))
+            .await
+            .unwrap();
+
+        let second = broker.receive("consumer-a").await.unwrap();
+        assert_eq!(second.attempt, 2);
+        assert_eq!(second.last_error.as_deref(), Some("disk full"));
+        assert_eq!(second.first_enqueued_at, enqueued_at);
+        assert!(second.queue_wait(Utc::now()).num_seconds() >= 30);
+
+        // a nack without a reason still counts, and keeps the earlier reason.
+        broker.nack("consumer-a", second.delivery_id).await.unwrap();
+        let third = broker.receive("consumer-a").await.unwrap();
+        assert_eq!(third.attempt, 3);
+        assert_eq!(third.last_error.as_deref(), Some("disk full"));
+    }
+
     #[tokio::test]
     async fn in_memory_broker_redelivers_expired_result_delivery() {
         let broker = InMemoryBroker::with_lease_duration(Duration::from_millis(10));
@@ -756,8 +1129,14 @@ mod tests {
 
         let a = broker.receive_event("ws-a").await.unwrap();
         let b = broker.receive_event("ws-b").await.unwrap();
-        assert!(matches!(a.event.kind, runinator_comm::UiEventKind::WorkflowsChanged));
-        assert!(matches!(b.event.kind, runinator_comm::UiEventKind::WorkflowsChanged));
+        assert!(matches!(
+            a.event.kind,
+            runinator_comm::UiEventKind::WorkflowsChanged
+        ));
+        assert!(matches!(
+            b.event.kind,
+            runinator_comm::UiEventKind::WorkflowsChanged
+        ));
     }
 
     #[tokio::test]
@@ -873,6 +1252,8 @@ mod tests {
                 command: targeted.clone(),
                 dedupe_key: Some("targeted".into()),
                 enqueued_at: Utc::now(),
+                priority: 0,
+                queue: None,
             })
             .await
             .unwrap();
@@ -881,6 +1262,8 @@ mod tests {
                 command: any.clone(),
                 dedupe_key: Some("any".into()),
                 enqueued_at: Utc::now(),
+                priority: 0,
+                queue: None,
             })
             .await
             .unwrap();
@@ -900,6 +1283,194 @@ mod tests {
         assert_eq!(delivery.command.command_id, any.command_id);
     }
 
+    #[tokio::test]
+    async fn receive_for_hands_queued_actions_only_to_their_subscribers() {
+        use runinator_comm::{ConsumerProfile, DEFAULT_QUEUE};
+
+        let broker = InMemoryBroker::new();
+        // the export is queued first, so a consumer ignoring queues would take it.
+        for (key, queue) in [("export", Some("exports")), ("light", None)] {
+            broker
+                .publish(BrokerMessage {
+                    command: action_command(),
+                    dedupe_key: Some(key.into()),
+                    enqueued_at: Utc::now(),
+                    priority: 0,
+                    queue: queue.map(str::to_string),
+                })
+                .await
+                .unwrap();
+        }
+
+        let general = ConsumerProfile::shared("general");
+        let delivery = broker.receive_for(&general).await.unwrap();
+        assert_eq!(delivery.dedupe_key, "light");
+        broker.ack("general", delivery.delivery_id).await.unwrap();
+
+        let exporter = ConsumerProfile::shared("exporter").with_queues(["exports".into()].into());
+        assert!(!exporter.subscribes_to(None));
+        assert!(!exporter.subscribes_to(Some(DEFAULT_QUEUE)));
+        let delivery = broker.receive_for(&exporter).await.unwrap();
+        assert_eq!(delivery.dedupe_key, "export");
+        assert_eq!(delivery.queue.as_deref(), Some("exports"));
+
+        // a nacked delivery stays on its queue.
+        broker.nack("exporter", delivery.delivery_id).await.unwrap();
+        let pending = tokio::time::timeout(
+            std::time::Duration::from_millis(50),
+            broker.receive_for(&general),
+        )
+        .await;
+        assert!(
+            pending.is_err(),
+            "the general pool must not take queued work"
+        );
+        let both = ConsumerProfile::shared("both")
+            .with_queues([DEFAULT_QUEUE.to_string(), "exports".into()].into());
+        let delivery = broker.receive_for(&both).await.unwrap();
+        assert_eq!(delivery.dedupe_key, "export");
+    }
+
+    #[tokio::test]
+    async fn higher_priority_actions_are_delivered_first() {
+        let broker = InMemoryBroker::new().with_priority_aging(PriorityAging::disabled());
+        for (key, priority) in [("low", 0), ("high", 10)] {
+            broker
+                .publish(BrokerMessage {
+                    command: action_command(),
+                    dedupe_key: Some(key.into()),
+                    enqueued_at: Utc::now(),
+                    priority,
+                    queue: None,
+                })
+                .await
+                .unwrap();
+        }
+
+        let first = broker.receive("consumer").await.unwrap();
+        let second = broker.receive("consumer").await.unwrap();
+        assert_eq!(first.dedupe_key, "high");
+        assert_eq!(second.dedupe_key, "low");
+    }
+
+    #[tokio::test]
+    async fn a_long_waiting_low_priority_action_overtakes_fresh_high_priority_work() {
+        let broker = InMemoryBroker::new().with_priority_aging(PriorityAging {
+            interval: Duration::from_secs(60),
+            max_boost: 20,
+        });
+        // ten minutes in the queue earns the low-priority action ten points, enough to beat a fresh
+        // priority-5 action but not a fresh priority-15 one.
+        let published = [
+            ("stale-low", 0, Utc::now() - chrono::Duration::minutes(10)),
+            ("fresh-mid", 5, Utc::now()),
+            ("fresh-high", 15, Utc::now()),
+        ];
+        for (key, priority, enqueued_at) in published {
+            broker
+                .publish(BrokerMessage {
+                    command: action_command(),
+                    dedupe_key: Some(key.into()),
+                    enqueued_at,
+                    priority,
+                    queue: None,
+                })
+                .await
+                .unwrap();
+        }
+
+        let mut order = Vec::new();
+        for _ in 0..3 {
+            order.push(broker.receive("consumer").await.unwrap().dedupe_key);
+        }
+        assert_eq!(order, ["fresh-high", "stale-low", "fresh-mid"]);
+    }
+
+    #[test]
+    fn aging_boost_is_capped() {
+        let aging = PriorityAging {
+            interval: Duration::from_secs(1),
+            max_boost: 3,
+        };
+        let mut delivery = BrokerDelivery::from(BrokerMessage {
+            command: action_command(),
+            dedupe_key: None,
+            enqueued_at: Utc::now() - chrono::Duration::hours(1),
+            priority: 2,
+            queue: None,
+        });
+        assert_eq!(aging.effective_priority(&delivery, Utc::now()), 5);
+        assert_eq!(
+            PriorityAging::disabled().effective_priority(&delivery, Utc::now()),
+            2
+        );
+        delivery.first_enqueued_at = Utc::now();
+        assert_eq!(aging.effective_priority(&delivery, Utc::now()), 2);
+    }
+
+    #[tokio::test]
+    async fn message_history_traces_an_action_across_its_deliveries() {
+        let broker = InMemoryBroker::with_lease_duration(Duration::from_millis(10));
+        broker
+            .publish(BrokerMessage {
+                command: action_command(),
+                dedupe_key: Some("traced".into()),
+                enqueued_at: Utc::now(),
+                priority: 0,
+                queue: None,
+            })
+            .await
+            .unwrap();
+
+        let first = broker.receive("worker-a").await.unwrap();
+        broker
+            .nack_with_error("worker-a", first.delivery_id, Some("provider busy".into()))
+            .await
+            .unwrap();
+        let second = broker.receive("worker-b").await.unwrap();
+        tokio::time::sleep(Duration::from_millis(15)).await;
+        let third = broker.receive("worker-c").await.unwrap();
+        broker.ack("worker-c", third.delivery_id).await.unwrap();
+
+        // any delivery id finds the message, and so does its dedupe key once it is acked.
+        let by_key = broker.message_history("traced").await.unwrap();
+        assert_eq!(by_key.len(), 1);
+        let history = &by_key[0];
+        assert_eq!(
+            broker
+                .message_history(&third.delivery_id.to_string())
+                .await
+                .unwrap(),
+            by_key
+        );
+        assert_eq!(history.message_id, first.delivery_id);
+        assert_eq!(history.channel, "action");
+        assert_eq!(history.state(), Some(MessageState::Acked));
+        let steps: Vec<_> = history
+            .transitions
+            .iter()
+            .map(|transition| (transition.state, transition.consumer.as_deref()))
+            .collect();
+        assert_eq!(
+            steps,
+            [
+                (MessageState::Published, None),
+                (MessageState::Delivered, Some("worker-a")),
+                (MessageState::Nacked, Some("worker-a")),
+                (MessageState::Delivered, Some("worker-b")),
+                (MessageState::Expired, None),
+                (MessageState::Delivered, Some("worker-c")),
+                (MessageState::Acked, Some("worker-c")),
+            ]
+        );
+        assert_eq!(
+            history.transitions[2].error.as_deref(),
+            Some("provider busy")
+        );
+        assert_eq!(history.transitions[4].delivery_id, second.delivery_id);
+        assert!(broker.message_history("unknown").await.unwrap().is_empty());
+    }
+
     fn action_command() -> ActionCommand {
         ActionCommand {
             command_id: Uuid::new_v4(),
@@ -914,6 +1485,11 @@ mod tests {
                 mcp_enabled: false,
                 tags: Vec::new(),
                 required_labels: Default::default(),
+                allowed_secrets: Vec::new(),
+                preflight: Vec::new(),
+                cache_ttl_seconds: None,
+                priority: 0,
+                queue: None,
             },
             attempt: 1,
             parameters: json!({}),
@@ -12,9 +12,9 @@ use opentelemetry::KeyValue;
 
 use crate::types::{
     BrokerDelivery, BrokerMessage, ControlDelivery, EventDelivery, EventMessage, IngressDelivery,
-    IngressMessage, ResultDelivery, ResultMessage, WakeDelivery, WakeMessage,
+    IngressMessage, QueuedMessage, ResultDelivery, ResultMessage, WakeDelivery, WakeMessage,
 };
-use crate::{Broker, BrokerError, ConsumerProfile, ControlCommand};
+use crate::{Broker, BrokerError, ConsumerProfile, ControlCommand, MessageHistory};
 
 const METER_NAME: &str = "runinator-broker";
 const METRIC_OPERATIONS: &str = "runinator_broker_operations_total";
@@ -141,6 +141,21 @@ impl Broker for InstrumentedBroker {
         result
     }
 
+    async fn nack_with_error(
+        &self,
+        consumer: &str,
+        delivery_id: uuid::Uuid,
+        error: Option<String>,
+    ) -> Result<(), BrokerError> {
+        let start = Instant::now();
+        let result = self
+            .inner
+            .nack_with_error(consumer, delivery_id, error)
+            .await;
+        self.metrics.record(CH_ACTION, "nack", start, &result, true);
+        result
+    }
+
     async fn publish_control(&self, command: ControlCommand) -> Result<(), BrokerError> {
         let start = Instant::now();
         let result = self.inner.publish_control(command).await;
@@ -309,4 +324,24 @@ impl Broker for InstrumentedBroker {
             .record(CH_EVENT, "receive", start, &result, false);
         result
     }
+
+    // operator inspection spans every channel and is rare; it is forwarded unmetered.
+    async fn list_messages(&self) -> Result<Vec<QueuedMessage>, BrokerError> {
+        self.inner.list_messages().await
+    }
+
+    async fn requeue_message(&self, delivery_id: uuid::Uuid) -> Result<(), BrokerError> {
+        self.inner.requeue_message(delivery_id).await
+    }
+
+    async fn message_history(&self, key: &str) -> Result<Vec<MessageHistory>, BrokerError> {
+        self.inner.message_history(key).await
+    }
+
+    async fn purge_messages(
+        &self,
+        older_than: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, BrokerError> {
+        self.inner.purge_messages(older_than).await
+    }
 }
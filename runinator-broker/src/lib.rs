@@ -3,9 +3,11 @@ mod capabilities;
 pub mod dispatch;
 mod errors;
 mod factory;
+mod history;
 pub mod http;
 pub mod in_memory;
 mod instrumented;
+mod priority;
 pub mod tcp;
 #[cfg(test)]
 mod tests;
@@ -17,16 +19,23 @@ pub use capabilities::{
 };
 pub use errors::BrokerError;
 pub use factory::{build_kafka_broker, build_rabbitmq_broker};
+pub use history::{
+    MessageHistory, MessageState, MessageTransition, MESSAGE_HISTORY_CAPACITY,
+    MESSAGE_HISTORY_TRANSITIONS,
+};
 pub use instrumented::instrument;
+pub use priority::PriorityAging;
 pub use runinator_comm::{
     ActionTarget, ConsumerProfile, ControlCommand, UiEvent, WakeCommand, WsIngressCommand,
+    DEFAULT_QUEUE,
 };
 pub use types::{
     BrokerDelivery, BrokerMessage, ControlDelivery, EventDelivery, EventMessage, IngressDelivery,
-    IngressMessage, ResultDelivery, ResultMessage, WakeDelivery, WakeMessage,
+    IngressMessage, QueuedMessage, ResultDelivery, ResultMessage, WakeDelivery, WakeMessage,
 };
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 
 /// how long an undeliverable targeted control may bounce before a non-matching consumer drops it.
 /// long enough to ride out a holder's broker reconnect, short enough to bound requeue churn once
@@ -51,10 +60,11 @@ pub trait Broker: Send + Sync + 'static {
 
     /// Wait for and retrieve the next delivery whose target matches `profile`. The targeting-aware
     /// path: an exclusive consumer (e.g. the desktop worker) only receives `Replica`/`Labels`
-    /// targets it satisfies, never general-pool `Any` work.
+    /// targets it satisfies, never general-pool `Any` work, and every consumer only receives
+    /// actions published on a queue it subscribes to ([`ConsumerProfile::queues`]).
     ///
     /// Backends that do not have a smarter override (their own queue/topic routing per target) get
-    /// this safety net for free: receive, check the delivery's target against `profile`, and requeue
+    /// this safety net for free: receive, check the delivery against `profile`, and requeue
     /// (`nack`) anything that doesn't match rather than handing it to the wrong consumer. A brief
     /// sleep between mismatches avoids a hot loop if nothing currently connected matches transiently;
     /// the reducer's own pre-dispatch liveness check means a genuine, lasting mismatch should be rare
@@ -62,7 +72,7 @@ pub trait Broker: Send + Sync + 'static {
     async fn receive_for(&self, profile: &ConsumerProfile) -> Result<BrokerDelivery, BrokerError> {
         loop {
             let delivery = self.receive(&profile.id).await?;
-            if delivery.command.target.matches(profile) {
+            if delivery.matches(profile) {
                 return Ok(delivery);
             }
             self.nack(&profile.id, delivery.delivery_id).await?;
@@ -76,6 +86,17 @@ pub trait Broker: Send + Sync + 'static {
     /// Return the delivery to the queue for another attempt.
     async fn nack(&self, consumer: &str, delivery_id: uuid::Uuid) -> Result<(), BrokerError>;
 
+    /// Return the delivery to the queue, recording why it failed so the next delivery carries it
+    /// as [`BrokerDelivery::last_error`]. Backends that cannot carry the reason drop it and nack.
+    async fn nack_with_error(
+        &self,
+        consumer: &str,
+        delivery_id: uuid::Uuid,
+        _error: Option<String>,
+    ) -> Result<(), BrokerError> {
+        self.nack(consumer, delivery_id).await
+    }
+
     /// Publish a workflow control message on the control channel.
     async fn publish_control(&self, command: ControlCommand) -> Result<(), BrokerError>;
 
@@ -177,4 +198,33 @@ pub trait Broker: Send + Sync + 'static {
     /// `consumer` identifies one fan-out subscriber (use a per-replica id); each distinct consumer
     /// drains its own stream of every published event.
     async fn receive_event(&self, consumer: &str) -> Result<EventDelivery, BrokerError>;
+
+    /// List every message held on the leased channels, queued and in flight, oldest first.
+    ///
+    /// Operator inspection only; backends whose queues live in an external system (Kafka,
+    /// RabbitMQ) leave this unimplemented and are inspected with that system's own tooling.
+    async fn list_messages(&self) -> Result<Vec<QueuedMessage>, BrokerError> {
+        Err(BrokerError::NotImplemented("list_messages"))
+    }
+
+    /// Return an in-flight delivery to the front of its queue now instead of waiting for its
+    /// lease to expire, exactly as if its consumer had nacked it.
+    async fn requeue_message(&self, _delivery_id: uuid::Uuid) -> Result<(), BrokerError> {
+        Err(BrokerError::NotImplemented("requeue_message"))
+    }
+
+    /// Trace the messages matching `key`, a delivery id or a dedupe key: when each was published,
+    /// which consumer each delivery went to, and how it ended. A message keeps its history after
+    /// it is acked, until the backend's bounded log forgets it.
+    ///
+    /// Operator inspection only, like [`Broker::list_messages`].
+    async fn message_history(&self, _key: &str) -> Result<Vec<MessageHistory>, BrokerError> {
+        Err(BrokerError::NotImplemented("message_history"))
+    }
+
+    /// Drop queued (not in-flight) messages enqueued before `older_than`, returning how many were
+    /// removed. Their dedupe keys are released so the same message can be published again.
+    async fn purge_messages(&self, _older_than: DateTime<Utc>) -> Result<usize, BrokerError> {
+        Err(BrokerError::NotImplemented("purge_messages"))
+    }
 }
@@ -0,0 +1,49 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use crate::BrokerDelivery;
+
+/// how queued actions gain priority while they wait, so a steady stream of high-priority work cannot
+/// starve lower-priority messages forever.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PriorityAging {
+    /// wait that earns one point of priority; zero turns aging off.
+    pub interval: Duration,
+    /// most points a message can earn by waiting.
+    pub max_boost: i32,
+}
+
+impl PriorityAging {
+    pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(30);
+    pub const DEFAULT_MAX_BOOST: i32 = 100;
+
+    /// aging turned off: messages are delivered strictly by their own priority.
+    pub const fn disabled() -> Self {
+        Self {
+            interval: Duration::ZERO,
+            max_boost: 0,
+        }
+    }
+
+    /// the priority `delivery` competes with at `now`: its own priority plus what it has earned by
+    /// waiting since it was first published.
+    pub fn effective_priority(&self, delivery: &BrokerDelivery, now: DateTime<Utc>) -> i64 {
+        let base = i64::from(delivery.priority);
+        if self.interval.is_zero() || self.max_boost <= 0 {
+            return base;
+        }
+        let waited = delivery.queue_wait(now).to_std().unwrap_or_default();
+        let earned = waited.as_millis() / self.interval.as_millis().max(1);
+        base + earned.min(self.max_boost as u128) as i64
+    }
+}
+
+impl Default for PriorityAging {
+    fn default() -> Self {
+        Self {
+            interval: Self::DEFAULT_INTERVAL,
+            max_boost: Self::DEFAULT_MAX_BOOST,
+        }
+    }
+}
@@ -1,10 +1,11 @@
 use crate::{
     tcp::types::{TcpRequest, TcpResponse},
     Broker, BrokerDelivery, BrokerError, BrokerMessage, ConsumerProfile, ControlCommand,
-    ControlDelivery, EventDelivery, EventMessage, IngressDelivery, IngressMessage, ResultDelivery,
-    ResultMessage, WakeDelivery, WakeMessage,
+    ControlDelivery, EventDelivery, EventMessage, IngressDelivery, IngressMessage, MessageHistory,
+    QueuedMessage, ResultDelivery, ResultMessage, WakeDelivery, WakeMessage,
 };
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use std::time::Duration;
 use tokio::{
     io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
@@ -94,7 +95,11 @@ impl Broker for TcpBroker {
     }
 
     async fn publish(&self, message: BrokerMessage) -> Result<(), BrokerError> {
-        let response = self.request(TcpRequest::Publish { message }).await?;
+        let response = self
+            .request(TcpRequest::Publish {
+                message: Box::new(message),
+            })
+            .await?;
         Self::expect_ok(response)
     }
 
@@ -105,7 +110,7 @@ impl Broker for TcpBroker {
             })
             .await?
         {
-            TcpResponse::Delivery { delivery } => Ok(delivery),
+            TcpResponse::Delivery { delivery } => Ok(*delivery),
             TcpResponse::Error { message } => Err(BrokerError::Internal(message)),
             _ => Err(BrokerError::Internal(
                 "unexpected action delivery response".into(),
@@ -120,7 +125,7 @@ impl Broker for TcpBroker {
             })
             .await?
         {
-            TcpResponse::Delivery { delivery } => Ok(delivery),
+            TcpResponse::Delivery { delivery } => Ok(*delivery),
             TcpResponse::Error { message } => Err(BrokerError::Internal(message)),
             _ => Err(BrokerError::Internal(
                 "unexpected action delivery response".into(),
@@ -139,10 +144,20 @@ impl Broker for TcpBroker {
     }
 
     async fn nack(&self, consumer: &str, delivery_id: Uuid) -> Result<(), BrokerError> {
+        self.nack_with_error(consumer, delivery_id, None).await
+    }
+
+    async fn nack_with_error(
+        &self,
+        consumer: &str,
+        delivery_id: Uuid,
+        error: Option<String>,
+    ) -> Result<(), BrokerError> {
         let response = self
             .request(TcpRequest::Nack {
                 consumer: consumer.to_string(),
                 delivery_id,
+                error,
             })
             .await?;
         Self::expect_ok(response)
@@ -345,6 +360,45 @@ impl Broker for TcpBroker {
             )),
         }
     }
+
+    async fn list_messages(&self) -> Result<Vec<QueuedMessage>, BrokerError> {
+        match self.request(TcpRequest::ListMessages).await? {
+            TcpResponse::Messages { messages } => Ok(messages),
+            TcpResponse::Error { message } => Err(BrokerError::Internal(message)),
+            _ => Err(BrokerError::Internal("unexpected list response".into())),
+        }
+    }
+
+    async fn requeue_message(&self, delivery_id: Uuid) -> Result<(), BrokerError> {
+        let response = self
+            .request(TcpRequest::RequeueMessage { delivery_id })
+            .await?;
+        Self::expect_ok(response)
+    }
+
+    async fn message_history(&self, key: &str) -> Result<Vec<MessageHistory>, BrokerError> {
+        match self
+            .request(TcpRequest::MessageHistory {
+                key: key.to_string(),
+            })
+            .await?
+        {
+            TcpResponse::History { messages } => Ok(messages),
+            TcpResponse::Error { message } => Err(BrokerError::Internal(message)),
+            _ => Err(BrokerError::Internal("unexpected history response".into())),
+        }
+    }
+
+    async fn purge_messages(&self, older_than: DateTime<Utc>) -> Result<usize, BrokerError> {
+        match self
+            .request(TcpRequest::PurgeMessages { older_than })
+            .await?
+        {
+            TcpResponse::Purged { count } => Ok(count),
+            TcpResponse::Error { message } => Err(BrokerError::Internal(message)),
+            _ => Err(BrokerError::Internal("unexpected purge response".into())),
+        }
+    }
 }
 
 async fn timeout_io<T, F>(
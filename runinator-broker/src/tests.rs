@@ -144,6 +144,11 @@ fn delivery(target: ActionTarget) -> BrokerDelivery {
             mcp_enabled: false,
             tags: Vec::new(),
             required_labels: Default::default(),
+            allowed_secrets: Vec::new(),
+            preflight: Vec::new(),
+            cache_ttl_seconds: None,
+            priority: 0,
+            queue: None,
         },
         attempt: 1,
         parameters: json!({}),
@@ -155,6 +160,8 @@ fn delivery(target: ActionTarget) -> BrokerDelivery {
         command,
         dedupe_key: None,
         enqueued_at: chrono::Utc::now(),
+        priority: 0,
+        queue: None,
     })
 }
 
@@ -1,6 +1,7 @@
 use chrono::{DateTime, Utc};
 use runinator_comm::{
-    ActionCommand, ControlCommand, UiEvent, WakeCommand, WorkflowResultEvent, WsIngressCommand,
+    ActionCommand, ConsumerProfile, ControlCommand, UiEvent, WakeCommand, WorkflowResultEvent,
+    WsIngressCommand,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::DefaultHasher;
@@ -15,6 +16,13 @@ pub struct BrokerMessage {
     pub dedupe_key: Option<String>,
     #[serde(default = "utc_now")]
     pub enqueued_at: DateTime<Utc>,
+    /// higher is delivered first by backends that order their queue; 0 is the default.
+    #[serde(default)]
+    pub priority: i32,
+    /// the queue the action is published on; only consumers subscribed to it receive it. `None`
+    /// is [`crate::DEFAULT_QUEUE`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub queue: Option<String>,
 }
 
 /// Message returned when polling the broker.
@@ -24,6 +32,20 @@ pub struct BrokerDelivery {
     pub command: ActionCommand,
     pub dedupe_key: String,
     pub enqueued_at: DateTime<Utc>,
+    /// 1 on the first delivery, incremented each time the broker hands the message out again after
+    /// a nack or an expired lease. backends that cannot count redeliveries always report 1.
+    #[serde(default = "first_attempt")]
+    pub attempt: u32,
+    /// when the message was first published; unlike `enqueued_at` this survives redelivery.
+    #[serde(default = "utc_now")]
+    pub first_enqueued_at: DateTime<Utc>,
+    /// the reason given by the most recent nack, if any.
+    #[serde(default)]
+    pub last_error: Option<String>,
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub queue: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -200,10 +222,28 @@ impl From<BrokerMessage> for BrokerDelivery {
             dedupe_key: dedupe,
             enqueued_at: message.enqueued_at,
             command: message.command,
+            attempt: first_attempt(),
+            first_enqueued_at: message.enqueued_at,
+            last_error: None,
+            priority: message.priority,
+            queue: message.queue,
         }
     }
 }
 
+impl BrokerDelivery {
+    /// true when a consumer presenting `profile` may receive this delivery: it satisfies the
+    /// command's target and subscribes to the delivery's queue.
+    pub fn matches(&self, profile: &ConsumerProfile) -> bool {
+        self.command.target.matches(profile) && profile.subscribes_to(self.queue.as_deref())
+    }
+
+    /// how long the message has waited since it was first published.
+    pub fn queue_wait(&self, now: DateTime<Utc>) -> chrono::Duration {
+        (now - self.first_enqueued_at).max(chrono::Duration::zero())
+    }
+}
+
 impl From<ControlCommand> for ControlDelivery {
     fn from(command: ControlCommand) -> Self {
         Self {
@@ -226,6 +266,25 @@ impl From<ResultMessage> for ResultDelivery {
     }
 }
 
+/// One message held by a broker's queue store, as seen by operator tooling.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueuedMessage {
+    pub delivery_id: Uuid,
+    /// `action`, `control`, `result`, `wake`, or `ingress`.
+    pub channel: String,
+    /// true while a consumer holds the delivery's lease (received, not yet acked or nacked).
+    pub in_flight: bool,
+    #[serde(default)]
+    pub dedupe_key: Option<String>,
+    pub enqueued_at: DateTime<Utc>,
+    /// the channel's command or event, as it would be delivered.
+    pub payload: serde_json::Value,
+}
+
 fn utc_now() -> DateTime<Utc> {
     Utc::now()
 }
+
+fn first_attempt() -> u32 {
+    1
+}
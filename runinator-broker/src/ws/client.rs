@@ -305,7 +305,12 @@ mod imp {
 
         async fn publish(&self, message: BrokerMessage) -> Result<(), BrokerError> {
             match self
-                .request_bounded(TcpRequest::Publish { message }, ONE_SHOT_RETRY_WINDOW)
+                .request_bounded(
+                    TcpRequest::Publish {
+                        message: Box::new(message),
+                    },
+                    ONE_SHOT_RETRY_WINDOW,
+                )
                 .await?
             {
                 TcpResponse::Ok => Ok(()),
@@ -321,7 +326,7 @@ mod imp {
                 })
                 .await?
             {
-                TcpResponse::Delivery { delivery } => Ok(delivery),
+                TcpResponse::Delivery { delivery } => Ok(*delivery),
                 TcpResponse::Error { message } => Err(BrokerError::Internal(message)),
                 _ => Err(unexpected_response()),
             }
@@ -337,7 +342,7 @@ mod imp {
                 })
                 .await?
             {
-                TcpResponse::Delivery { delivery } => Ok(delivery),
+                TcpResponse::Delivery { delivery } => Ok(*delivery),
                 TcpResponse::Error { message } => Err(BrokerError::Internal(message)),
                 _ => Err(unexpected_response()),
             }
@@ -361,11 +366,21 @@ mod imp {
         }
 
         async fn nack(&self, consumer: &str, delivery_id: Uuid) -> Result<(), BrokerError> {
+            self.nack_with_error(consumer, delivery_id, None).await
+        }
+
+        async fn nack_with_error(
+            &self,
+            consumer: &str,
+            delivery_id: Uuid,
+            error: Option<String>,
+        ) -> Result<(), BrokerError> {
             match self
                 .request_bounded(
                     TcpRequest::Nack {
                         consumer: consumer.to_string(),
                         delivery_id,
+                        error,
                     },
                     ONE_SHOT_RETRY_WINDOW,
                 )
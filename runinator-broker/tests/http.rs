@@ -40,6 +40,11 @@ async fn http_broker_delivers_published_messages() {
                 mcp_enabled: false,
                 tags: Vec::new(),
                 required_labels: Default::default(),
+                allowed_secrets: Vec::new(),
+                preflight: Vec::new(),
+                cache_ttl_seconds: None,
+                priority: 0,
+                queue: None,
             },
             attempt: 1,
             parameters: json!({ "value": true }),
@@ -49,6 +54,8 @@ async fn http_broker_delivers_published_messages() {
         },
         dedupe_key: Some("http-test".into()),
         enqueued_at: Utc::now(),
+        priority: 0,
+        queue: None,
     };
 
     broker.publish(message).await.unwrap();
@@ -195,6 +202,56 @@ async fn http_broker_fans_out_events_to_every_subscriber() {
     server.abort();
 }
 
+#[tokio::test]
+async fn http_broker_routes_queued_actions_to_subscribed_consumers() {
+    use runinator_broker::{BrokerError, ConsumerProfile};
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = tokio::spawn(serve(
+        listener,
+        runinator_broker::in_memory::InMemoryBroker::new(),
+    ));
+    let broker = HttpBroker::new(
+        Url::parse(&format!("http://{addr}/")).unwrap(),
+        reqwest::Client::new(),
+    );
+
+    let export = action_command();
+    broker
+        .publish(BrokerMessage {
+            command: export.clone(),
+            dedupe_key: Some("export".into()),
+            enqueued_at: Utc::now(),
+            priority: 0,
+            queue: Some("exports".into()),
+        })
+        .await
+        .unwrap();
+
+    let exporter = ConsumerProfile::shared("exporter").with_queues(["exports".into()].into());
+    let delivery = broker.receive_for(&exporter).await.unwrap();
+    assert_eq!(delivery.command.command_id, export.command_id);
+    assert_eq!(delivery.queue.as_deref(), Some("exports"));
+    broker.ack("exporter", delivery.delivery_id).await.unwrap();
+
+    let bad_queue = broker
+        .publish(BrokerMessage {
+            command: action_command(),
+            dedupe_key: Some("bad-queue".into()),
+            enqueued_at: Utc::now(),
+            priority: 0,
+            queue: Some("heavy exports".into()),
+        })
+        .await;
+    assert!(
+        matches!(&bad_queue, Err(BrokerError::Rejected(message)) if message.starts_with("invalid_queue")),
+        "{bad_queue:?}"
+    );
+
+    server.abort();
+}
+
 fn action_command() -> ActionCommand {
     ActionCommand {
         command_id: Uuid::new_v4(),
@@ -209,6 +266,11 @@ fn action_command() -> ActionCommand {
             mcp_enabled: false,
             tags: Vec::new(),
             required_labels: Default::default(),
+            allowed_secrets: Vec::new(),
+            preflight: Vec::new(),
+            cache_ttl_seconds: None,
+            priority: 0,
+            queue: None,
         },
         attempt: 1,
         parameters: json!({ "value": true }),
@@ -260,6 +322,7 @@ async fn http_broker_auth_gates_and_scopes_by_replica() {
         jwt_secret_previous: None,
         access_ttl_secs: 60,
         refresh_ttl_secs: 60,
+        static_api_key_hashes: Vec::new(),
     };
     let (token, _) = runinator_auth::issue_replica_token(&config, Uuid::now_v7(), replica).unwrap();
     let authed = HttpBroker::new(base.clone(), bearer_client(&token));
@@ -273,6 +336,8 @@ async fn http_broker_auth_gates_and_scopes_by_replica() {
             command: command.clone(),
             dedupe_key: Some("auth-scope".into()),
             enqueued_at: Utc::now(),
+            priority: 0,
+            queue: None,
         })
         .await
         .unwrap();
@@ -292,3 +357,162 @@ async fn http_broker_auth_gates_and_scopes_by_replica() {
 
     server.abort();
 }
+
+#[tokio::test]
+async fn http_broker_mints_tokens_from_shared_secret() {
+    use runinator_broker::http::auth::{BrokerAuth, BrokerTokenSource};
+    use runinator_broker::http::server::serve_with_auth;
+
+    let secret = b"broker-shared-secret".to_vec();
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = tokio::spawn(serve_with_auth(
+        listener,
+        runinator_broker::in_memory::InMemoryBroker::new(),
+        Some(Arc::new(BrokerAuth::new(secret.clone(), None))),
+    ));
+    let base = Url::parse(&format!("http://{addr}/")).unwrap();
+
+    // a client holding the wrong secret is rejected like an anonymous one.
+    let wrong = HttpBroker::new(base.clone(), reqwest::Client::new())
+        .with_auth(Some(BrokerTokenSource::new(b"wrong".to_vec())));
+    assert!(wrong
+        .publish(BrokerMessage {
+            command: action_command(),
+            dedupe_key: Some("wrong-secret".into()),
+            enqueued_at: Utc::now(),
+            priority: 0,
+            queue: None,
+        })
+        .await
+        .is_err());
+
+    let authed = HttpBroker::new(base, reqwest::Client::new())
+        .with_auth(Some(BrokerTokenSource::new(secret)));
+    let command = action_command();
+    authed
+        .publish(BrokerMessage {
+            command: command.clone(),
+            dedupe_key: Some("shared-secret".into()),
+            enqueued_at: Utc::now(),
+            priority: 0,
+            queue: None,
+        })
+        .await
+        .unwrap();
+    let delivery = authed.receive("c").await.unwrap();
+    assert_eq!(delivery.command.command_id, command.command_id);
+    authed.ack("c", delivery.delivery_id).await.unwrap();
+
+    server.abort();
+}
+
+#[tokio::test]
+async fn http_broker_rejects_unsupported_protocol_versions() {
+    use runinator_broker::http::validation::{BROKER_PROTOCOL_VERSION, BROKER_VERSION_HEADER};
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = tokio::spawn(serve(
+        listener,
+        runinator_broker::in_memory::InMemoryBroker::new(),
+    ));
+    let client = reqwest::Client::new();
+    let receive = format!("http://{addr}/poll");
+
+    let response = client
+        .post(&receive)
+        .header(BROKER_VERSION_HEADER, BROKER_PROTOCOL_VERSION + 1)
+        .json(&json!({ "consumer": "c", "timeout_ms": 10 }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::BAD_REQUEST);
+    assert_eq!(
+        response.headers()[BROKER_VERSION_HEADER],
+        BROKER_PROTOCOL_VERSION.to_string().as_str()
+    );
+    let body = response.json::<serde_json::Value>().await.unwrap();
+    assert_eq!(body["code"], "unsupported_protocol_version");
+
+    // a pre-negotiation client without the header is still served.
+    let response = client
+        .post(&receive)
+        .json(&json!({ "consumer": "c", "timeout_ms": 10 }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::NO_CONTENT);
+
+    server.abort();
+}
+
+#[tokio::test]
+async fn http_broker_rejects_malformed_payloads_before_queueing() {
+    use runinator_broker::http::validation::MAX_MESSAGE_BYTES;
+    use runinator_broker::BrokerError;
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = tokio::spawn(serve(
+        listener,
+        runinator_broker::in_memory::InMemoryBroker::new(),
+    ));
+    let broker = HttpBroker::new(
+        Url::parse(&format!("http://{addr}/")).unwrap(),
+        reqwest::Client::new(),
+    );
+
+    let oversized_key = broker
+        .publish(BrokerMessage {
+            command: action_command(),
+            dedupe_key: Some("k".repeat(1024)),
+            enqueued_at: Utc::now(),
+            priority: 0,
+            queue: None,
+        })
+        .await;
+    assert!(
+        matches!(&oversized_key, Err(BrokerError::Rejected(message)) if message.starts_with("invalid_dedupe_key")),
+        "{oversized_key:?}"
+    );
+
+    let mut command = action_command();
+    command.parameters = json!({ "blob": "x".repeat(MAX_MESSAGE_BYTES) });
+    let oversized = broker
+        .publish(BrokerMessage {
+            command,
+            dedupe_key: Some("too-big".into()),
+            enqueued_at: Utc::now(),
+            priority: 0,
+            queue: None,
+        })
+        .await;
+    assert!(
+        matches!(&oversized, Err(BrokerError::Rejected(message)) if message.starts_with("message_too_large")),
+        "{oversized:?}"
+    );
+
+    let bad_consumer = broker.ack("bad consumer\n", Uuid::now_v7()).await;
+    assert!(
+        matches!(&bad_consumer, Err(BrokerError::Rejected(message)) if message.starts_with("invalid_consumer")),
+        "{bad_consumer:?}"
+    );
+
+    // nothing rejected reached the queue; a valid message still flows through.
+    let command = action_command();
+    broker
+        .publish(BrokerMessage {
+            command: command.clone(),
+            dedupe_key: Some("valid".into()),
+            enqueued_at: Utc::now(),
+            priority: 0,
+            queue: None,
+        })
+        .await
+        .unwrap();
+    let delivery = broker.receive("c").await.unwrap();
+    assert_eq!(delivery.command.command_id, command.command_id);
+
+    server.abort();
+}
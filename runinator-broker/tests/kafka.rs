@@ -49,6 +49,8 @@ async fn kafka_broker_delivers_published_messages() {
             command,
             dedupe_key: Some(command_id.to_string()),
             enqueued_at: Utc::now(),
+            priority: 0,
+            queue: None,
         })
         .await
         .unwrap();
@@ -153,6 +155,8 @@ async fn kafka_broker_nack_redelivers_messages() {
             command,
             dedupe_key: Some(command_id.to_string()),
             enqueued_at: Utc::now(),
+            priority: 0,
+            queue: None,
         })
         .await
         .unwrap();
@@ -191,6 +195,11 @@ fn action_command() -> ActionCommand {
             mcp_enabled: false,
             tags: Vec::new(),
             required_labels: Default::default(),
+            allowed_secrets: Vec::new(),
+            preflight: Vec::new(),
+            cache_ttl_seconds: None,
+            priority: 0,
+            queue: None,
         },
         attempt: 1,
         parameters: json!({ "value": true }),
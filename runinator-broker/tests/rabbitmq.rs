@@ -52,6 +52,8 @@ async fn rabbitmq_broker_delivers_published_messages() {
             command,
             dedupe_key: Some(command_id.to_string()),
             enqueued_at: Utc::now(),
+            priority: 0,
+            queue: None,
         })
         .await
         .unwrap();
@@ -144,6 +146,8 @@ async fn rabbitmq_broker_nack_redelivers_messages() {
             command,
             dedupe_key: Some(command_id.to_string()),
             enqueued_at: Utc::now(),
+            priority: 0,
+            queue: None,
         })
         .await
         .unwrap();
@@ -176,6 +180,8 @@ async fn rabbitmq_broker_still_delivers_any_targeted_actions_via_receive_for() {
             command,
             dedupe_key: Some(command_id.to_string()),
             enqueued_at: Utc::now(),
+            priority: 0,
+            queue: None,
         })
         .await
         .unwrap();
@@ -206,6 +212,8 @@ async fn rabbitmq_broker_routes_labels_target_to_the_matching_consumer_only() {
             command,
             dedupe_key: Some(command_id.to_string()),
             enqueued_at: Utc::now(),
+            priority: 0,
+            queue: None,
         })
         .await
         .unwrap();
@@ -259,6 +267,8 @@ async fn rabbitmq_broker_routes_replica_target_to_the_bound_replica_only() {
             command,
             dedupe_key: Some(command_id.to_string()),
             enqueued_at: Utc::now(),
+            priority: 0,
+            queue: None,
         })
         .await
         .unwrap();
@@ -288,6 +298,11 @@ fn action_command() -> ActionCommand {
             mcp_enabled: false,
             tags: Vec::new(),
             required_labels: Default::default(),
+            allowed_secrets: Vec::new(),
+            preflight: Vec::new(),
+            cache_ttl_seconds: None,
+            priority: 0,
+            queue: None,
         },
         attempt: 1,
         parameters: json!({ "value": true }),
@@ -1,7 +1,7 @@
 use chrono::Utc;
 use runinator_broker::{
     tcp::{client::TcpBroker, server::serve},
-    Broker, BrokerMessage, ControlCommand, ResultMessage,
+    Broker, BrokerMessage, ControlCommand, MessageState, ResultMessage,
 };
 use runinator_comm::{ActionCommand, ControlKind, WorkflowResultEvent, WorkflowResultEventKind};
 use runinator_models::json;
@@ -33,6 +33,11 @@ async fn tcp_broker_delivers_published_messages() {
                 mcp_enabled: false,
                 tags: Vec::new(),
                 required_labels: Default::default(),
+                allowed_secrets: Vec::new(),
+                preflight: Vec::new(),
+                cache_ttl_seconds: None,
+                priority: 0,
+                queue: None,
             },
             attempt: 1,
             parameters: json!({ "value": true }),
@@ -42,6 +47,8 @@ async fn tcp_broker_delivers_published_messages() {
         },
         dedupe_key: Some("tcp-test".into()),
         enqueued_at: Utc::now(),
+        priority: 0,
+        queue: None,
     };
 
     broker.publish(message).await.unwrap();
@@ -142,6 +149,8 @@ async fn tcp_broker_times_out_publish_response() {
             command: action_command(),
             dedupe_key: Some("tcp-timeout-test".into()),
             enqueued_at: Utc::now(),
+            priority: 0,
+            queue: None,
         })
         .await
         .expect_err("publish should time out waiting for a response");
@@ -164,6 +173,11 @@ fn action_command() -> ActionCommand {
             mcp_enabled: false,
             tags: Vec::new(),
             required_labels: Default::default(),
+            allowed_secrets: Vec::new(),
+            preflight: Vec::new(),
+            cache_ttl_seconds: None,
+            priority: 0,
+            queue: None,
         },
         attempt: 1,
         parameters: json!({ "value": true }),
@@ -172,3 +186,87 @@ fn action_command() -> ActionCommand {
         trace_context: Default::default(),
     }
 }
+
+#[tokio::test]
+async fn tcp_broker_lists_requeues_and_purges_messages() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = tokio::spawn(serve(
+        listener,
+        runinator_broker::in_memory::InMemoryBroker::new(),
+    ));
+    let broker = TcpBroker::new(addr.to_string());
+    let stale = Utc::now() - chrono::Duration::hours(2);
+    for (key, enqueued_at) in [("stale", stale), ("fresh", Utc::now())] {
+        broker
+            .publish(BrokerMessage {
+                command: action_command(),
+                dedupe_key: Some(key.into()),
+                enqueued_at,
+                priority: 0,
+                queue: None,
+            })
+            .await
+            .unwrap();
+    }
+
+    // the stale message is leased to a consumer that never acks it.
+    let stuck = broker.receive("stuck-consumer").await.unwrap();
+    let messages = broker.list_messages().await.unwrap();
+    assert_eq!(messages.len(), 2);
+    assert_eq!(messages[0].delivery_id, stuck.delivery_id);
+    assert!(messages[0].in_flight);
+    assert_eq!(messages[0].channel, "action");
+    assert!(!messages[1].in_flight);
+
+    // requeue puts it back in the queue ahead of its lease expiry.
+    broker.requeue_message(stuck.delivery_id).await.unwrap();
+    let messages = broker.list_messages().await.unwrap();
+    assert!(messages.iter().all(|message| !message.in_flight));
+    assert!(broker.requeue_message(stuck.delivery_id).await.is_err());
+
+    // purging by age removes only the stale message and frees its dedupe key.
+    let purged = broker
+        .purge_messages(Utc::now() - chrono::Duration::hours(1))
+        .await
+        .unwrap();
+    assert_eq!(purged, 1);
+    // the purged message is still traceable: leased, requeued by the operator, then purged.
+    let history = broker
+        .message_history(&stuck.delivery_id.to_string())
+        .await
+        .unwrap();
+    let states: Vec<_> = history[0]
+        .transitions
+        .iter()
+        .map(|transition| transition.state)
+        .collect();
+    assert_eq!(
+        states,
+        [
+            MessageState::Published,
+            MessageState::Delivered,
+            MessageState::Nacked,
+            MessageState::Purged,
+        ]
+    );
+    assert_eq!(
+        history[0].transitions[1].consumer.as_deref(),
+        Some("stuck-consumer")
+    );
+    let messages = broker.list_messages().await.unwrap();
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].dedupe_key.as_deref(), Some("fresh"));
+    broker
+        .publish(BrokerMessage {
+            command: action_command(),
+            dedupe_key: Some("stale".into()),
+            enqueued_at: Utc::now(),
+            priority: 0,
+            queue: None,
+        })
+        .await
+        .unwrap();
+
+    server.abort();
+}
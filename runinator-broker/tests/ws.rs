@@ -29,6 +29,8 @@ async fn ws_broker_delivers_published_messages() {
         command: action_command(),
         dedupe_key: Some("ws-test".into()),
         enqueued_at: Utc::now(),
+        priority: 0,
+        queue: None,
     };
 
     broker.publish(message).await.unwrap();
@@ -150,6 +152,8 @@ async fn ws_broker_concurrent_receive_for_does_not_block_concurrent_requests() {
                 command,
                 dedupe_key: Some("ws-concurrency-test".into()),
                 enqueued_at: Utc::now(),
+                priority: 0,
+                queue: None,
             })
             .await
             .unwrap();
@@ -219,6 +223,11 @@ fn action_command() -> ActionCommand {
             mcp_enabled: false,
             tags: Vec::new(),
             required_labels: Default::default(),
+            allowed_secrets: Vec::new(),
+            preflight: Vec::new(),
+            cache_ttl_seconds: None,
+            priority: 0,
+            queue: None,
         },
         attempt: 1,
         parameters: json!({ "value": true }),
@@ -2,9 +2,14 @@ pub mod discovery;
 pub mod errors;
 pub mod targeting;
 pub mod wire;
+pub mod worker_command;
 
-pub use targeting::{ActionTarget, ConsumerProfile};
+pub use targeting::{ActionTarget, ConsumerProfile, DEFAULT_QUEUE};
 pub use wire::{WireCodec, WireError};
+pub use worker_command::{
+    RunningActionStatus, WorkerCancelReport, WorkerCommandRequest, WorkerCommandResponse,
+    WorkerStatusReport, send_worker_command,
+};
 
 use chrono::{DateTime, Utc};
 use runinator_models::{
@@ -341,6 +346,10 @@ pub enum WorkflowResultEventKind {
         output_json: Option<Value>,
         #[serde(default, skip_serializing_if = "Option::is_none")]
         message: Option<String>,
+        /// how long the command waited in the broker before a worker started it, in milliseconds.
+        /// carried on the `running` status only.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        queue_wait_ms: Option<i64>,
     },
     Chunk {
         chunk: NewRunChunk,
@@ -395,6 +404,21 @@ impl WorkflowResultEvent {
                 status,
                 output_json,
                 message,
+                queue_wait_ms: None,
+            },
+        )
+    }
+
+    /// the `running` status a worker publishes as it starts the action, with the time the command
+    /// spent queued.
+    pub fn running(command: &ActionCommand, queue_wait_ms: i64) -> Self {
+        Self::new(
+            command,
+            WorkflowResultEventKind::Status {
+                status: WorkflowStatus::Running,
+                output_json: None,
+                message: None,
+                queue_wait_ms: Some(queue_wait_ms),
             },
         )
     }
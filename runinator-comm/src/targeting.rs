@@ -1,8 +1,12 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+/// the broker queue of an action published without one, and the only queue a consumer that names
+/// none subscribes to.
+pub const DEFAULT_QUEUE: &str = "default";
+
 /// runtime routing key stamped on an [`crate::ActionCommand`] by the reducer. selects which
 /// worker(s) may receive the action. `Any` preserves pre-targeting behavior, so existing serialized
 /// commands (which carry no target) deserialize as `Any`.
@@ -52,6 +56,11 @@ pub struct ConsumerProfile {
     pub labels: BTreeMap<String, String>,
     #[serde(default)]
     pub exclusive: bool,
+    /// broker queues this consumer takes actions from; empty subscribes to [`DEFAULT_QUEUE`] only.
+    /// a worker dedicated to heavy exports subscribes to their queue alone so light work never
+    /// waits behind them, and a general worker never picks them up.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub queues: BTreeSet<String>,
 }
 
 impl ConsumerProfile {
@@ -63,6 +72,7 @@ impl ConsumerProfile {
             replica_id: None,
             labels: BTreeMap::new(),
             exclusive: false,
+            queues: BTreeSet::new(),
         }
     }
 
@@ -78,6 +88,23 @@ impl ConsumerProfile {
         self
     }
 
+    /// subscribe to these broker queues instead of the default one. list [`DEFAULT_QUEUE`] too to
+    /// keep taking unqueued work.
+    pub fn with_queues(mut self, queues: BTreeSet<String>) -> Self {
+        self.queues = queues;
+        self
+    }
+
+    /// true when this consumer takes actions published on `queue` (`None` is the default queue).
+    pub fn subscribes_to(&self, queue: Option<&str>) -> bool {
+        let queue = queue.unwrap_or(DEFAULT_QUEUE);
+        if self.queues.is_empty() {
+            queue == DEFAULT_QUEUE
+        } else {
+            self.queues.contains(queue)
+        }
+    }
+
     /// mark this consumer exclusive: it never receives general-pool (`Any`) work, only `Replica`/
     /// `Labels` targets it satisfies. used by the desktop worker.
     pub fn exclusive(mut self) -> Self {
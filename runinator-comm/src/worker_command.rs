@@ -0,0 +1,95 @@
+// the local command protocol a worker serves on its optional command port: one newline-terminated
+// json request per connection, answered by one newline-terminated json response, the same framing
+// as the broker's tcp transport.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use runinator_models::telemetry::StartupReport;
+use serde::{Deserialize, Serialize};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::TcpStream,
+};
+use uuid::Uuid;
+
+use crate::WireCodec;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum WorkerCommandRequest {
+    /// report what the worker is running and which plugins it loaded.
+    Status,
+    /// cancel the in-flight execution of one dispatched action.
+    Cancel { command_id: Uuid },
+    /// report how the worker started: version, host, redacted config and arguments.
+    Info,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WorkerCommandResponse {
+    Status(WorkerStatusReport),
+    Cancel(WorkerCancelReport),
+    // boxed: the report dwarfs the other replies.
+    Info(Box<StartupReport>),
+    Error { message: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkerStatusReport {
+    /// the registered replica id; `None` for a worker running without one.
+    pub replica_id: Option<Uuid>,
+    pub uptime_seconds: u64,
+    pub max_concurrent_actions: usize,
+    pub running: Vec<RunningActionStatus>,
+    /// names of the plugin libraries loaded at startup, sorted.
+    pub plugins: Vec<String>,
+    /// the worker stopped polling for new actions, because the api is unreachable or execution is
+    /// paused cluster-wide.
+    #[serde(default)]
+    pub polling_paused: bool,
+    /// execution is paused cluster-wide by an operator.
+    #[serde(default)]
+    pub execution_paused: bool,
+}
+
+/// one action currently executing on the worker.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunningActionStatus {
+    pub command_id: Uuid,
+    pub workflow_run_id: Uuid,
+    pub workflow_node_run_id: Uuid,
+    pub node_id: String,
+    pub provider: String,
+    pub function: String,
+    pub attempt: i64,
+    pub started_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkerCancelReport {
+    pub command_id: Uuid,
+    /// false when no execution of that command is active on the worker.
+    pub canceled: bool,
+}
+
+/// send one command to a worker's command port and wait for its reply.
+pub async fn send_worker_command(
+    addr: &str,
+    request: &WorkerCommandRequest,
+    timeout: Duration,
+) -> std::io::Result<WorkerCommandResponse> {
+    tokio::time::timeout(timeout, async {
+        let mut stream = TcpStream::connect(addr).await?;
+        let payload = request.to_wire().map_err(std::io::Error::other)?;
+        stream.write_all(payload.as_bytes()).await?;
+        stream.write_all(b"\n").await?;
+
+        let mut line = String::new();
+        BufReader::new(stream).read_line(&mut line).await?;
+        WorkerCommandResponse::from_wire(line.trim_end()).map_err(std::io::Error::other)
+    })
+    .await
+    .map_err(|_| std::io::Error::new(std::io::ErrorKind::TimedOut, "worker command timed out"))?
+}
@@ -30,6 +30,7 @@ pub fn run() {
             crate::pack_dev::apply_dev_pack,
             crate::commands::delete_workflow,
             crate::commands::duplicate_workflow,
+            crate::commands::clone_workflow,
             crate::commands::fetch_run_chunks,
             crate::commands::fetch_run_artifacts,
             crate::commands::fetch_workflow_node_run_chunks,
@@ -38,6 +39,7 @@ pub fn run() {
             crate::commands::fetch_workflow_run_transitions,
             crate::commands::fetch_workflow_node_transitions,
             crate::commands::fetch_workflows,
+            crate::commands::fetch_workflow_page,
             crate::commands::save_workflow,
             crate::commands::simulate_workflow,
             crate::commands::fetch_workflow_triggers,
@@ -1,7 +1,7 @@
 use runinator_models::{
     api_routes::{
-        API_WORKFLOWS_IMPORT, API_WORKFLOWS_SIMULATE, WORKFLOW_JSON_IMPORT_RISK_ACK,
-        WORKFLOW_JSON_IMPORT_RISK_HEADER,
+        API_WORKFLOWS_IMPORT, API_WORKFLOWS_SEARCH, API_WORKFLOWS_SIMULATE,
+        WORKFLOW_JSON_IMPORT_RISK_ACK, WORKFLOW_JSON_IMPORT_RISK_HEADER,
     },
     orchestration::{NodeTransition, NodeTransitionStat},
     pipelines::{Pipeline, PipelineRun, PipelineRunDetail, PipelineTrigger},
@@ -11,7 +11,8 @@ use runinator_models::{
     web::TaskResponse,
     workflows::{
         WorkflowBundle, WorkflowDefinition, WorkflowNodeRunArtifact, WorkflowNodeRunChunk,
-        WorkflowRun, WorkflowRunArtifact, WorkflowSimulateRequest, WorkflowTrigger,
+        WorkflowRun, WorkflowRunArtifact, WorkflowSearchPage, WorkflowSimulateRequest,
+        WorkflowTrigger,
     },
 };
 use serde::{Deserialize, Serialize};
@@ -472,6 +473,36 @@ pub async fn fetch_workflows(
     get_json(&state, "workflows").await
 }
 
+#[tauri::command]
+pub async fn fetch_workflow_page(
+    state: State<'_, CommandCenterState>,
+    query: Option<String>,
+    offset: usize,
+    limit: usize,
+    org_id: Option<Uuid>,
+    global: Option<bool>,
+) -> CommandResult<WorkflowSearchPage> {
+    let mut url = build_state_url(&state, API_WORKFLOWS_SEARCH).await?;
+    {
+        let mut pairs = url.query_pairs_mut();
+        if let Some(query) = query.filter(|query| !query.is_empty()) {
+            pairs.append_pair("q", &query);
+        }
+        pairs
+            .append_pair("offset", &offset.to_string())
+            .append_pair("limit", &limit.to_string());
+        if let Some(org_id) = org_id {
+            pairs.append_pair("org_id", &org_id.to_string());
+        }
+        if global.unwrap_or(false) {
+            pairs.append_pair("global", "true");
+        }
+    }
+    let response = state.client.read().await.get(url.clone()).send().await?;
+    let response = handle_response(url, response).await?;
+    Ok(response.json::<WorkflowSearchPage>().await?)
+}
+
 #[tauri::command]
 pub async fn save_workflow(
     state: State<'_, CommandCenterState>,
@@ -690,6 +721,21 @@ pub async fn duplicate_workflow(
     Ok(response.json::<WorkflowDefinition>().await?)
 }
 
+#[tauri::command]
+pub async fn clone_workflow(
+    state: State<'_, CommandCenterState>,
+    workflow_id: Uuid,
+    name: Option<String>,
+) -> CommandResult<WorkflowDefinition> {
+    let mut url = build_state_url(&state, &format!("workflows/{workflow_id}/clone")).await?;
+    if let Some(name) = name {
+        url.query_pairs_mut().append_pair("name", &name);
+    }
+    let response = state.client.read().await.post(url.clone()).send().await?;
+    let response = handle_response(url, response).await?;
+    Ok(response.json::<WorkflowDefinition>().await?)
+}
+
 #[tauri::command]
 pub async fn fetch_workflow_triggers(
     state: State<'_, CommandCenterState>,
@@ -323,6 +323,7 @@ fn load_workflow_bundle(path: &Path) -> CommandResult<WorkflowBundle> {
             Ok(WorkflowBundle {
                 workflows: compile_wdl_all(path, &data, SemVer::default())?,
                 triggers: Vec::new(),
+                baselines: Vec::new(),
             })
         }
         _ => Err(command_error(format!(
@@ -454,6 +455,7 @@ fn load_wdl_directory(dir: &Path) -> CommandResult<WorkflowBundle> {
     Ok(WorkflowBundle {
         workflows,
         triggers: Vec::new(),
+        baselines: Vec::new(),
     })
 }
 
@@ -512,6 +514,7 @@ fn load_wdl_pack_manifest(path: &Path) -> CommandResult<WorkflowBundle> {
     Ok(WorkflowBundle {
         workflows,
         triggers,
+        baselines: Vec::new(),
     })
 }
 
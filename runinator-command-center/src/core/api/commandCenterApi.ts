@@ -36,9 +36,11 @@ import type {
   WdlHoverResponse,
   WorkflowBundle,
   WorkflowDefinition,
+  WorkflowPageScope,
   WorkflowRunCreated,
   WorkflowRunArtifact,
   WorkflowRunDetail,
+  WorkflowSearchPage,
   WorkflowSimulateRequest,
   WorkflowTrigger,
   SimulationRun,
@@ -285,6 +287,22 @@ export async function fetchWorkflows() {
   return command<WorkflowDefinition[]>("fetch_workflows");
 }
 
+// one page of the visible workflows, matching `query` when it is non-empty, plus the total count.
+export async function fetchWorkflowPage(
+  query: string,
+  offset: number,
+  limit: number,
+  scope: WorkflowPageScope = {},
+) {
+  return command<WorkflowSearchPage>("fetch_workflow_page", {
+    query,
+    offset,
+    limit,
+    orgId: scope.orgId ?? null,
+    global: scope.global ?? false,
+  });
+}
+
 export async function saveWorkflow(workflow: WorkflowDefinition) {
   return command<WorkflowDefinition>("save_workflow", { workflow });
 }
@@ -366,6 +384,10 @@ export async function duplicateWorkflow(
   return command<WorkflowDefinition>("duplicate_workflow", { workflowId, bump });
 }
 
+export async function cloneWorkflow(workflowId: string, name?: string) {
+  return command<WorkflowDefinition>("clone_workflow", { workflowId, name: name ?? null });
+}
+
 export async function fetchWorkflowTriggers(workflowId: string) {
   return command<WorkflowTrigger[]>("fetch_workflow_triggers", { workflowId });
 }
@@ -765,6 +787,7 @@ export interface OrgQuota {
   org_id: string;
   max_nodes_per_kind: Record<string, number>;
   max_monthly_cents: number;
+  max_concurrent_runs: number;
 }
 
 export interface OrgUsage {
@@ -772,6 +795,7 @@ export interface OrgUsage {
   since: string | null;
   node_hours: Record<string, number>;
   accrued_cents: number;
+  active_runs: number;
 }
 
 export interface RateEntry {
@@ -210,6 +210,31 @@ const REGISTRY: Record<string, HttpDescriptor> = {
     path: (args) => `api_keys/${escape(arg(args, "keyId"))}/rotate`,
   },
   fetch_workflows: { method: "GET", path: () => "workflows" },
+  fetch_workflow_page: {
+    method: "GET",
+    path: (args) => {
+      const params = new URLSearchParams();
+      const query = argOpt(args, "query");
+
+      if (typeof query === "string" && query) {
+        params.set("q", query);
+      }
+
+      params.set("offset", displayValue(arg(args, "offset")));
+      params.set("limit", displayValue(arg(args, "limit")));
+      const orgId = argOpt(args, "orgId");
+
+      if (typeof orgId === "string" && orgId) {
+        params.set("org_id", orgId);
+      }
+
+      if (argOpt(args, "global") === true) {
+        params.set("global", "true");
+      }
+
+      return `workflows/search?${params.toString()}`;
+    },
+  },
   save_workflow: {
     method: (args) => {
       const workflow = arg(args, "workflow") as { id?: string | null };
@@ -46,6 +46,7 @@ export type { WorkflowEditorNodeRecord } from "./workflow/editor-node";
 export type { WorkflowDefinition } from "./workflow/definition";
 export { workflowInputType } from "./workflow/definition";
 export type { WorkflowBundle } from "./workflow/bundle";
+export type { WorkflowPageScope, WorkflowSearchPage } from "./workflow/search-page";
 export type { WorkflowTrigger, WorkflowTriggerKind } from "./workflow/trigger";
 export type {
   Pipeline,
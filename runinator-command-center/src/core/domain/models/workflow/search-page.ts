@@ -0,0 +1,15 @@
+import type { WorkflowDefinition } from "./definition";
+
+// one page of `GET /workflows/search`, with the number of matches across every page.
+export interface WorkflowSearchPage {
+  total: number;
+  offset: number;
+  limit: number;
+  workflows: WorkflowDefinition[];
+}
+
+// narrows a workflow page request to one org's workflows (`org`) or to unowned ones (`global`).
+export interface WorkflowPageScope {
+  orgId?: string | null;
+  global?: boolean;
+}
@@ -1,6 +1,6 @@
 import type { JsonRecord } from "../../json";
 
-export type WorkflowTriggerKind = "cron" | "manual" | "chained";
+export type WorkflowTriggerKind = "cron" | "manual" | "chained" | "file_arrival" | "webhook";
 
 export interface WorkflowTrigger {
   id: string | null;
@@ -1,9 +1,17 @@
 import { createStore } from "./event-bus";
+import {
+  DEFAULT_DATE_DISPLAY,
+  isValidTimeZone,
+  setDateDisplay,
+  type DateFormatStyle,
+} from "../utils/format";
 
 export type AppTheme = "system" | "light" | "dark";
 
 const THEME_KEY = "command-center.theme";
 const DEFAULT_TAB_KEY = "command-center.defaultTab";
+const TIME_ZONE_KEY = "command-center.timeZone";
+const DATE_FORMAT_KEY = "command-center.dateFormat";
 
 export const DEFAULT_TAB_OPTIONS = [
   { value: "Workflows", label: "Workflows" },
@@ -14,12 +22,20 @@ export const DEFAULT_TAB_OPTIONS = [
   { value: "Notifications", label: "Notifications" },
 ] as const;
 
+export const DATE_FORMAT_OPTIONS: { value: DateFormatStyle; label: string }[] = [
+  { value: "locale", label: "Browser locale" },
+  { value: "iso", label: "ISO (YYYY-MM-DD HH:mm:ss)" },
+];
+
 const ALLOWED_THEMES: AppTheme[] = ["system", "light", "dark"];
+const ALLOWED_DATE_FORMATS = DATE_FORMAT_OPTIONS.map((option) => option.value);
 const ALLOWED_TABS = DEFAULT_TAB_OPTIONS.map((option) => option.value);
 
 export interface DisplayPreferencesState {
   theme: AppTheme;
   defaultTab: string;
+  timeZone: string;
+  dateFormat: DateFormatStyle;
 }
 
 function readStored<T extends string>(key: string, allowed: T[], fallback: T): T {
@@ -36,6 +52,20 @@ function readStored<T extends string>(key: string, allowed: T[], fallback: T): T
   return fallback;
 }
 
+function readStoredTimeZone(): string {
+  try {
+    const stored = localStorage.getItem(TIME_ZONE_KEY);
+
+    if (stored && isValidTimeZone(stored)) {
+      return stored;
+    }
+  } catch {
+    // storage unavailable; use fallback.
+  }
+
+  return DEFAULT_DATE_DISPLAY.timeZone;
+}
+
 function writeStored(key: string, value: string) {
   try {
     localStorage.setItem(key, value);
@@ -48,7 +78,10 @@ export function createDisplayPreferencesService() {
   const store = createStore<DisplayPreferencesState>({
     theme: readStored(THEME_KEY, ALLOWED_THEMES, "system"),
     defaultTab: readStored(DEFAULT_TAB_KEY, ALLOWED_TABS as unknown as string[], "Workflows"),
+    timeZone: readStoredTimeZone(),
+    dateFormat: readStored(DATE_FORMAT_KEY, ALLOWED_DATE_FORMATS, DEFAULT_DATE_DISPLAY.dateFormat),
   });
+  setDateDisplay({ timeZone: store.getState().timeZone, dateFormat: store.getState().dateFormat });
 
   const service = {
     ...store,
@@ -60,6 +93,24 @@ export function createDisplayPreferencesService() {
       store.setState((state) => ({ ...state, defaultTab }));
       writeStored(DEFAULT_TAB_KEY, defaultTab);
     },
+    // returns false (and keeps the current zone) when the name is not a zone the browser knows.
+    setTimeZone(timeZone: string): boolean {
+      const trimmed = timeZone.trim() || DEFAULT_DATE_DISPLAY.timeZone;
+
+      if (!isValidTimeZone(trimmed)) {
+        return false;
+      }
+
+      store.setState((state) => ({ ...state, timeZone: trimmed }));
+      setDateDisplay({ timeZone: trimmed, dateFormat: store.getState().dateFormat });
+      writeStored(TIME_ZONE_KEY, trimmed);
+      return true;
+    },
+    setDateFormat(dateFormat: DateFormatStyle) {
+      store.setState((state) => ({ ...state, dateFormat }));
+      setDateDisplay({ timeZone: store.getState().timeZone, dateFormat });
+      writeStored(DATE_FORMAT_KEY, dateFormat);
+    },
   };
 
   return service;
@@ -16,6 +16,7 @@ import { createWdlLanguageService } from "./wdl-language";
 import { createExpressionService } from "./expression";
 import { createAuditLogService } from "./audit-log";
 import { createDeadLettersService } from "./dead-letters";
+import { createWorkflowPagesService } from "./workflow-pages";
 import { createReplicaSamplesService } from "./replica-samples";
 import { createDevPackService } from "./dev-pack";
 import { createOrgAdminService } from "./org-admin";
@@ -55,6 +56,7 @@ export const wdlLanguageService = createWdlLanguageService(appService);
 export const expressionService = createExpressionService(appService);
 export const auditLogService = createAuditLogService(appService);
 export const deadLettersService = createDeadLettersService(appService);
+export const workflowPagesService = createWorkflowPagesService(appService);
 export const replicaSamplesService = createReplicaSamplesService(appService);
 export const devPackService = createDevPackService(appService);
 export const orgAdminService = createOrgAdminService(appService);
@@ -85,6 +87,7 @@ export type { WdlLanguageService } from "./wdl-language";
 export type { ExpressionService } from "./expression";
 export type { AuditLogService } from "./audit-log";
 export type { DeadLettersService } from "./dead-letters";
+export type { WorkflowPagesService } from "./workflow-pages";
 export type { ReplicaSamplesService, ReplicaSample, ReplicaSampleSeries } from "./replica-samples";
 export type { DevPackService } from "./dev-pack";
 export type { OrgAdminService, OrgMembership, OrgRole, Team, User } from "./org-admin";
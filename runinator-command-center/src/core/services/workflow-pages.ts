@@ -0,0 +1,23 @@
+import { fetchWorkflowPage } from "../api/commandCenterApi";
+import type { WorkflowPageScope, WorkflowSearchPage } from "../domain/models";
+import type { AppService } from "./app";
+
+export function createWorkflowPagesService(app: AppService) {
+  return {
+    // one page of the visible workflows. pages fetched while scrolling pass `silent` so they do not
+    // flip the global loading state on every scroll step; a failure yields null.
+    async fetchPage(
+      query: string,
+      offset: number,
+      limit: number,
+      scope: WorkflowPageScope,
+      options?: { silent?: boolean },
+    ): Promise<WorkflowSearchPage | null> {
+      return app
+        .runOperation("Loading workflows", () => fetchWorkflowPage(query, offset, limit, scope), options)
+        .catch(() => null);
+    },
+  };
+}
+
+export type WorkflowPagesService = ReturnType<typeof createWorkflowPagesService>;
@@ -1,6 +1,6 @@
 import {
   cancelWorkflowRun, closeGate, compileWdl, continueWorkflowRun, createWorkflowRun, decompileToWdl,
-  deleteWorkflow, deleteWorkflowTrigger, duplicateWorkflow, fetchGates, fetchWorkflowNodeRunArtifacts,
+  cloneWorkflow, deleteWorkflow, deleteWorkflowTrigger, duplicateWorkflow, fetchGates, fetchWorkflowNodeRunArtifacts,
   fetchWorkflowNodeRunChunks, fetchWorkflowRun, fetchWorkflowRuns, fetchWorkflowTriggers, fetchWorkflows,
   openGate, patchWorkflowRunDebug, pauseWorkflowRun, renameWorkflowRun as renameWorkflowRunApi,
   replayWorkflowRun as replayWorkflowRunApi, resumeWorkflowRun, rerunWorkflowNode, runToCursorWorkflowRun,
@@ -388,6 +388,12 @@ export function createWorkflowCatalogService(
     return typeof cron === "string" && cron.trim() ? cron : "";
   }
 
+  // set by the trigger loop when the stored cron cannot be parsed; cleared once it parses again.
+  function triggerScheduleError(trigger: WorkflowTrigger): string {
+    const error = trigger.metadata?.schedule_error;
+    return isRecord(error) && typeof error.message === "string" ? error.message : "";
+  }
+
   function triggerDateForInput(value: string | null | undefined): string {
     if (!value) {
       return "";
@@ -548,5 +554,36 @@ export function createWorkflowCatalogService(
     host.notify();
   }
 
-  return { refreshWorkflows, clearServiceState, selectWorkflow, addWorkflow, workflowNameForRun, exportWorkflowWdl, exportWorkflowPack, moveWorkflowSelection, openWorkflowSettings, closeWorkflowSettings, refreshWorkflowTriggers, clearWorkflowTriggerState, addWorkflowTrigger, editWorkflowTrigger, closeTriggerEditor, setTriggerKind, submitWorkflowTrigger, deleteSelectedWorkflowTrigger, triggerCronSummary, triggerDateForInput, workflowSaveTriggers, workflowWdlSaveRequest, saveSelectedWorkflowBundle, deleteSelectedWorkflow, duplicateSelectedWorkflow };
+  async function cloneSelectedWorkflow(name?: string) {
+    const workflow = host.getSelectedWorkflow();
+
+    if (!workflow?.id) {
+      return;
+    }
+
+    if (host.state.isDirty) {
+      host.ctx.setError("Save or discard the current changes before cloning this workflow.");
+      return;
+    }
+
+    const workflowId = workflow.id;
+    const clone = await host.ctx
+      .runOperation(`Cloning workflow ${workflow.name}`, () => cloneWorkflow(workflowId, name))
+      .catch((error: unknown) => {
+        host.ctx.setError(error instanceof Error ? error.message : "Failed to clone workflow");
+        return null;
+      });
+
+    if (!clone) {
+      return;
+    }
+
+    await refreshWorkflows();
+    host.state.selectedWorkflowId = clone.id;
+    await selectWorkflow(clone);
+    host.ctx.setStatus(`Cloned ${workflow.name} as ${clone.name} (disabled)`);
+    host.notify();
+  }
+
+  return { refreshWorkflows, clearServiceState, selectWorkflow, addWorkflow, workflowNameForRun, exportWorkflowWdl, exportWorkflowPack, moveWorkflowSelection, openWorkflowSettings, closeWorkflowSettings, refreshWorkflowTriggers, clearWorkflowTriggerState, addWorkflowTrigger, editWorkflowTrigger, closeTriggerEditor, setTriggerKind, submitWorkflowTrigger, deleteSelectedWorkflowTrigger, triggerCronSummary, triggerScheduleError, triggerDateForInput, workflowSaveTriggers, workflowWdlSaveRequest, saveSelectedWorkflowBundle, deleteSelectedWorkflow, duplicateSelectedWorkflow, cloneSelectedWorkflow };
 }
@@ -0,0 +1,26 @@
+import { describe, expect, it } from "vitest";
+import { formatDate, isValidTimeZone } from "../format";
+
+describe("formatDate", () => {
+  it("renders iso timestamps in the chosen zone", () => {
+    const value = "2026-03-01T23:30:00Z";
+
+    expect(formatDate(value, { timeZone: "UTC", dateFormat: "iso" })).toBe(
+      "2026-03-01 23:30:00 UTC",
+    );
+    expect(formatDate(value, { timeZone: "Asia/Tokyo", dateFormat: "iso" })).toBe(
+      "2026-03-02 08:30:00 GMT+9",
+    );
+  });
+
+  it("passes through missing and unparseable values", () => {
+    expect(formatDate(null, { timeZone: "UTC", dateFormat: "iso" })).toBe("-");
+    expect(formatDate("soon", { timeZone: "UTC", dateFormat: "iso" })).toBe("soon");
+  });
+
+  it("validates zone names", () => {
+    expect(isValidTimeZone("local")).toBe(true);
+    expect(isValidTimeZone("Europe/Berlin")).toBe(true);
+    expect(isValidTimeZone("Mars/Olympus")).toBe(false);
+  });
+});
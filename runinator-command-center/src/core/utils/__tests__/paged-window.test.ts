@@ -0,0 +1,53 @@
+import { describe, expect, it } from "vitest";
+import { createPagedWindow, missingPages, pagesForRange, rowAt, storePage } from "../paged-cache";
+
+function rows(page: number, size = 10): string[] {
+  return Array.from({ length: size }, (_, index) => `row-${String(page * size + index)}`);
+}
+
+describe("paged-cache", () => {
+  it("asks for the first page until the total is known", () => {
+    const cache = createPagedWindow<string>(10, 3);
+    expect(pagesForRange(cache, 0, 40)).toEqual([0]);
+
+    storePage(cache, 0, rows(0), 25, 0);
+    expect(cache.total).toBe(25);
+    expect(pagesForRange(cache, 0, 40)).toEqual([0, 1, 2]);
+    expect(missingPages(cache, 0, 40)).toEqual([1, 2]);
+  });
+
+  it("reads rows by absolute index", () => {
+    const cache = createPagedWindow<string>(10, 3);
+    storePage(cache, 0, rows(0), 25, 0);
+    storePage(cache, 2, rows(2, 5), 25, 2);
+
+    expect(rowAt(cache, 3)).toBe("row-3");
+    expect(rowAt(cache, 21)).toBe("row-21");
+    expect(rowAt(cache, 12)).toBeUndefined();
+  });
+
+  it("keeps only the pages nearest the viewport", () => {
+    const cache = createPagedWindow<string>(10, 2);
+    storePage(cache, 0, rows(0), 100, 0);
+    storePage(cache, 1, rows(1), 100, 1);
+    storePage(cache, 5, rows(5), 100, 5);
+
+    expect([...cache.pages.keys()].sort()).toEqual([1, 5]);
+  });
+
+  it("drops every cached page when the total changes", () => {
+    const cache = createPagedWindow<string>(10, 4);
+    storePage(cache, 0, rows(0), 30, 0);
+    storePage(cache, 1, rows(1), 30, 1);
+    storePage(cache, 2, rows(2), 31, 2);
+
+    expect([...cache.pages.keys()]).toEqual([2]);
+    expect(cache.total).toBe(31);
+  });
+
+  it("asks for nothing from an empty list", () => {
+    const cache = createPagedWindow<string>(10, 3);
+    storePage(cache, 0, [], 0, 0);
+    expect(pagesForRange(cache, 0, 20)).toEqual([]);
+  });
+});
@@ -1,10 +1,77 @@
-export function formatDate(value?: string | null): string {
+// how timestamps are displayed. api values stay utc; this only affects rendering.
+export type DateFormatStyle = "locale" | "iso";
+
+export interface DateDisplayOptions {
+  // an iana zone such as "Europe/Berlin", "UTC", or "local" for the browser's zone.
+  timeZone: string;
+  dateFormat: DateFormatStyle;
+}
+
+export const DEFAULT_DATE_DISPLAY: DateDisplayOptions = { timeZone: "local", dateFormat: "locale" };
+
+let dateDisplay: DateDisplayOptions = DEFAULT_DATE_DISPLAY;
+
+// set by the display preferences service; views pick it up on their next render.
+export function setDateDisplay(options: DateDisplayOptions) {
+  dateDisplay = options;
+}
+
+export function isValidTimeZone(timeZone: string): boolean {
+  if (timeZone === "local") {
+    return true;
+  }
+
+  try {
+    new Intl.DateTimeFormat(undefined, { timeZone });
+    return true;
+  } catch {
+    return false;
+  }
+}
+
+export function formatDate(
+  value?: string | null,
+  options: DateDisplayOptions = dateDisplay,
+): string {
   if (!value) {
     return "-";
   }
 
   const date = new Date(value);
-  return Number.isNaN(date.getTime()) ? value : date.toLocaleString();
+
+  if (Number.isNaN(date.getTime())) {
+    return value;
+  }
+
+  const timeZone = options.timeZone === "local" ? undefined : options.timeZone;
+
+  try {
+    return options.dateFormat === "iso"
+      ? formatIsoInZone(date, timeZone)
+      : date.toLocaleString(undefined, { timeZone, timeZoneName: timeZone ? "short" : undefined });
+  } catch {
+    // unknown zone (e.g. a stale stored preference); fall back to the browser default.
+    return date.toLocaleString();
+  }
+}
+
+// "2026-10-16 14:05:09 GMT+2": sortable and unambiguous regardless of browser locale.
+function formatIsoInZone(date: Date, timeZone?: string): string {
+  const parts = new Intl.DateTimeFormat("en-US", {
+    timeZone,
+    year: "numeric",
+    month: "2-digit",
+    day: "2-digit",
+    hour: "2-digit",
+    minute: "2-digit",
+    second: "2-digit",
+    hourCycle: "h23",
+    timeZoneName: "short",
+  }).formatToParts(date);
+  const part = (type: Intl.DateTimeFormatPartTypes) =>
+    parts.find((entry) => entry.type === type)?.value ?? "";
+
+  return `${part("year")}-${part("month")}-${part("day")} ${part("hour")}:${part("minute")}:${part("second")} ${part("timeZoneName")}`;
 }
 
 export function pretty(value: unknown): string {
@@ -0,0 +1,79 @@
+// a bounded, page-addressed cache over a long server-side list. rows are fetched a page at a time as
+// the viewport reaches them, and pages far from the viewport are dropped, so memory stays at
+// `maxPages * pageSize` rows however long the list grows.
+
+export interface PagedWindow<T> {
+  pageSize: number;
+  maxPages: number;
+  // rows across every page, as the server last reported; null until the first page lands.
+  total: number | null;
+  pages: Map<number, T[]>;
+}
+
+export function createPagedWindow<T>(pageSize: number, maxPages: number): PagedWindow<T> {
+  return {
+    pageSize: Math.max(1, pageSize),
+    maxPages: Math.max(1, maxPages),
+    total: null,
+    pages: new Map(),
+  };
+}
+
+export function pageOf(cache: PagedWindow<unknown>, index: number): number {
+  return Math.floor(Math.max(0, index) / cache.pageSize);
+}
+
+// the pages covering rows `first..=last`, clamped to the known total. before the first page lands
+// only page 0 is asked for, since the list may be shorter than the viewport.
+export function pagesForRange(cache: PagedWindow<unknown>, first: number, last: number): number[] {
+  if (cache.total === null) {
+    return [0];
+  }
+
+  if (cache.total === 0 || last < first) {
+    return [];
+  }
+
+  const lastRow = Math.min(last, cache.total - 1);
+  const pages: number[] = [];
+
+  for (let page = pageOf(cache, first); page <= pageOf(cache, lastRow); page++) {
+    pages.push(page);
+  }
+
+  return pages;
+}
+
+export function missingPages(cache: PagedWindow<unknown>, first: number, last: number): number[] {
+  return pagesForRange(cache, first, last).filter((page) => !cache.pages.has(page));
+}
+
+// record a fetched page and the server's total, then drop the pages farthest from `centerPage`
+// until at most `maxPages` remain. a total that changed means the list moved under us, so every
+// other cached page is stale and is dropped too.
+export function storePage<T>(
+  cache: PagedWindow<T>,
+  page: number,
+  rows: T[],
+  total: number,
+  centerPage: number,
+): void {
+  if (cache.total !== null && cache.total !== total) {
+    cache.pages.clear();
+  }
+
+  cache.total = total;
+  cache.pages.set(page, rows);
+
+  const byDistance = [...cache.pages.keys()].sort(
+    (left, right) => Math.abs(right - centerPage) - Math.abs(left - centerPage),
+  );
+
+  for (const stale of byDistance.slice(0, Math.max(0, cache.pages.size - cache.maxPages))) {
+    cache.pages.delete(stale);
+  }
+}
+
+export function rowAt<T>(cache: PagedWindow<T>, index: number): T | undefined {
+  return cache.pages.get(pageOf(cache, index))?.[index % cache.pageSize];
+}
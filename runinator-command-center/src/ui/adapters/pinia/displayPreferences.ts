@@ -1,16 +1,18 @@
 import { defineStore } from "pinia";
 import { computed, watch } from "vue";
 import {
+  DATE_FORMAT_OPTIONS,
   DEFAULT_TAB_OPTIONS,
   type AppTheme,
   type DisplayPreferencesState,
 } from "../../../core/services/display-preferences";
+import type { DateFormatStyle } from "../../../core/utils/format";
 import { displayPreferencesService } from "../../../core/services";
 import { applyTheme } from "../browser/theme";
 import { mirrorServiceState } from "./sync";
 
-export type { AppTheme };
-export { DEFAULT_TAB_OPTIONS };
+export type { AppTheme, DateFormatStyle };
+export { DATE_FORMAT_OPTIONS, DEFAULT_TAB_OPTIONS };
 
 export const useDisplayPreferencesStore = defineStore("displayPreferences", () => {
   const state = mirrorServiceState<DisplayPreferencesState>(displayPreferencesService);
@@ -33,7 +35,13 @@ export const useDisplayPreferencesStore = defineStore("displayPreferences", () =
       get: () => state.value.defaultTab,
       set: (defaultTab: string) => { displayPreferencesService.setDefaultTab(defaultTab); },
     }),
+    timeZone: computed(() => state.value.timeZone),
+    dateFormat: computed(() => state.value.dateFormat),
     setTheme: (theme: AppTheme) => { displayPreferencesService.setTheme(theme); },
     setDefaultTab: (defaultTab: string) => { displayPreferencesService.setDefaultTab(defaultTab); },
+    setTimeZone: (timeZone: string) => displayPreferencesService.setTimeZone(timeZone),
+    setDateFormat: (dateFormat: DateFormatStyle) => {
+      displayPreferencesService.setDateFormat(dateFormat);
+    },
   };
 });
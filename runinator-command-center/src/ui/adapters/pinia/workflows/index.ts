@@ -586,6 +586,7 @@ export const useWorkflowsStore = defineStore("workflows", () => {
     saveSelectedWorkflow: svc.catalog.saveSelectedWorkflowBundle,
     deleteSelectedWorkflow: svc.catalog.deleteSelectedWorkflow,
     duplicateSelectedWorkflow: svc.catalog.duplicateSelectedWorkflow,
+    cloneSelectedWorkflow: svc.catalog.cloneSelectedWorkflow,
     runSelectedWorkflow: svc.runs.runSelectedWorkflow,
     runSelectedWorkflowDebug: svc.runs.runSelectedWorkflowDebug,
     stepSelectedWorkflowRun: svc.runs.stepSelectedWorkflowRun,
@@ -673,6 +674,7 @@ export const useWorkflowsStore = defineStore("workflows", () => {
     submitWorkflowTrigger: svc.catalog.submitWorkflowTrigger,
     deleteSelectedWorkflowTrigger: svc.catalog.deleteSelectedWorkflowTrigger,
     triggerCronSummary: svc.catalog.triggerCronSummary,
+    triggerScheduleError: svc.catalog.triggerScheduleError,
     triggerDateForInput: svc.catalog.triggerDateForInput,
     markWorkflowDirty: svc.editor.markWorkflowDirty,
   };
@@ -1,6 +1,7 @@
 import { beforeEach, describe, expect, it, vi } from "vitest";
 import { createPinia, setActivePinia } from "pinia";
 import { useKeyboardShortcuts } from "../useKeyboardShortcuts";
+import { useAppStore } from "../../../ui/adapters/pinia/app";
 import { useWorkflowsStore } from "../../../ui/adapters/pinia/workflows";
 
 describe("useKeyboardShortcuts", () => {
@@ -39,4 +40,28 @@ describe("useKeyboardShortcuts", () => {
 
     expect(preventDefault).toHaveBeenCalledOnce();
   });
+
+  it("clones the selected workflow with c on the workflows tab only", () => {
+    const app = useAppStore();
+    const workflows = useWorkflowsStore();
+    const cloneSelectedWorkflow = vi
+      .spyOn(workflows, "cloneSelectedWorkflow")
+      .mockResolvedValue(undefined);
+    const { handleKeydown } = useKeyboardShortcuts();
+    const preventDefault = vi.fn();
+    const target = {
+      tagName: "DIV",
+      isContentEditable: false,
+      closest: vi.fn(() => null),
+    } as unknown as EventTarget;
+
+    app.activeTab = "Workflows";
+    handleKeydown({ key: "c", target, preventDefault } as unknown as KeyboardEvent);
+    handleKeydown({ key: "c", ctrlKey: true, target, preventDefault } as unknown as KeyboardEvent);
+    app.activeTab = "Runs";
+    handleKeydown({ key: "c", target, preventDefault } as unknown as KeyboardEvent);
+
+    expect(cloneSelectedWorkflow).toHaveBeenCalledOnce();
+    expect(preventDefault).toHaveBeenCalledOnce();
+  });
 });
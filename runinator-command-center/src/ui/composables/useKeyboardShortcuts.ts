@@ -75,6 +75,11 @@ export function useKeyboardShortcuts() {
       if (app.activeTab === "Workflows") {
         void workflows.runSelectedWorkflow();
       }
+    } else if (event.key === "c" && !event.ctrlKey && !event.metaKey) {
+      if (app.activeTab === "Workflows") {
+        event.preventDefault();
+        void workflows.cloneSelectedWorkflow();
+      }
     } else if (event.ctrlKey && event.key.toLowerCase() === "n") {
       event.preventDefault();
     } else if (event.key.toLowerCase() === "e") {
@@ -0,0 +1,172 @@
+import {
+  computed,
+  onBeforeUnmount,
+  onMounted,
+  ref,
+  shallowRef,
+  triggerRef,
+  watch,
+  type Ref,
+} from "vue";
+import { workflowPagesService } from "../../core/services";
+import type { WorkflowDefinition, WorkflowPageScope } from "../../core/domain/models";
+import {
+  createPagedWindow,
+  missingPages,
+  pageOf,
+  rowAt,
+  storePage,
+  type PagedWindow,
+} from "../../core/utils/paged-window";
+
+// rows are a fixed height so a scroll offset maps straight to a row index.
+export const WORKFLOW_ROW_HEIGHT = 36;
+const PAGE_SIZE = 100;
+// at most this many pages (rows = pages * PAGE_SIZE) stay in memory.
+const MAX_PAGES = 5;
+// rows rendered beyond each edge of the viewport, so a short scroll never shows blank rows.
+const OVERSCAN_ROWS = 10;
+// typing in the search box reloads once the user pauses rather than on every keystroke.
+const SEARCH_DEBOUNCE_MS = 250;
+
+export interface WorkflowListRow {
+  index: number;
+  // undefined while the row's page is still loading.
+  workflow: WorkflowDefinition | undefined;
+}
+
+// virtual scrolling over the workflow list: only the rows in (or near) the viewport are rendered,
+// and their pages are fetched from `GET /workflows/search` as the viewport reaches them. the
+// window resets whenever the search text, the scope, or `refreshKey` (the catalog reloading after
+// a change) moves.
+export function useWorkflowListWindow(
+  query: () => string,
+  scope: () => WorkflowPageScope,
+  refreshKey: () => unknown,
+) {
+  const cache = shallowRef<PagedWindow<WorkflowDefinition>>(
+    createPagedWindow(PAGE_SIZE, MAX_PAGES),
+  );
+  const viewport: Ref<HTMLElement | null> = ref(null);
+  const scrollTop = ref(0);
+  const viewportHeight = ref(0);
+  const loading = ref(false);
+  let inFlight = new Set<number>();
+  let resizeObserver: ResizeObserver | null = null;
+  let searchTimer: ReturnType<typeof setTimeout> | undefined;
+  // bumped on every reset so pages requested for an older query are discarded on arrival.
+  let generation = 0;
+
+  const total = computed(() => cache.value.total ?? 0);
+  const firstRow = computed(() =>
+    Math.max(0, Math.floor(scrollTop.value / WORKFLOW_ROW_HEIGHT) - OVERSCAN_ROWS),
+  );
+  const lastRow = computed(() =>
+    Math.min(
+      total.value - 1,
+      Math.ceil((scrollTop.value + viewportHeight.value) / WORKFLOW_ROW_HEIGHT) + OVERSCAN_ROWS,
+    ),
+  );
+  const rows = computed((): WorkflowListRow[] => {
+    const visible: WorkflowListRow[] = [];
+
+    for (let index = firstRow.value; index <= lastRow.value; index++) {
+      visible.push({ index, workflow: rowAt(cache.value, index) });
+    }
+
+    return visible;
+  });
+  const padTop = computed(() => firstRow.value * WORKFLOW_ROW_HEIGHT);
+  const padBottom = computed(
+    () => Math.max(0, total.value - lastRow.value - 1) * WORKFLOW_ROW_HEIGHT,
+  );
+  const loadedCount = computed(() =>
+    [...cache.value.pages.values()].reduce((count, page) => count + page.length, 0),
+  );
+
+  async function loadPage(page: number, silent: boolean) {
+    if (inFlight.has(page)) {
+      return;
+    }
+
+    const requested = generation;
+    const pending = inFlight;
+    pending.add(page);
+    loading.value = true;
+
+    try {
+      const fetched = await workflowPagesService.fetchPage(
+        query().trim(),
+        page * PAGE_SIZE,
+        PAGE_SIZE,
+        scope(),
+        { silent },
+      );
+
+      if (fetched && requested === generation) {
+        const center = pageOf(cache.value, Math.floor((firstRow.value + lastRow.value) / 2));
+        storePage(cache.value, page, fetched.workflows, fetched.total, center);
+        triggerRef(cache);
+      }
+    } finally {
+      pending.delete(page);
+      loading.value = inFlight.size > 0;
+    }
+  }
+
+  function loadVisible() {
+    const lastVisible = Math.max(
+      lastRow.value,
+      Math.ceil(viewportHeight.value / WORKFLOW_ROW_HEIGHT),
+    );
+
+    for (const page of missingPages(cache.value, firstRow.value, lastVisible)) {
+      void loadPage(page, true);
+    }
+  }
+
+  function measure() {
+    viewportHeight.value = viewport.value?.clientHeight ?? 0;
+    loadVisible();
+  }
+
+  function onScroll(event: Event) {
+    scrollTop.value = (event.target as HTMLElement).scrollTop;
+    measure();
+  }
+
+  function reset() {
+    generation++;
+    inFlight = new Set();
+    cache.value = createPagedWindow(PAGE_SIZE, MAX_PAGES);
+    scrollTop.value = 0;
+
+    if (viewport.value) {
+      viewport.value.scrollTop = 0;
+    }
+
+    void loadPage(0, false);
+    measure();
+  }
+
+  watch([firstRow, lastRow], loadVisible);
+  watch(query, () => {
+    clearTimeout(searchTimer);
+    searchTimer = setTimeout(reset, SEARCH_DEBOUNCE_MS);
+  });
+  watch([() => JSON.stringify(scope()), refreshKey], reset);
+  onMounted(() => {
+    if (viewport.value && typeof ResizeObserver !== "undefined") {
+      resizeObserver = new ResizeObserver(measure);
+      resizeObserver.observe(viewport.value);
+    }
+
+    reset();
+  });
+  onBeforeUnmount(() => {
+    clearTimeout(searchTimer);
+    resizeObserver?.disconnect();
+  });
+
+  return { viewport, rows, total, loadedCount, loading, padTop, padBottom, onScroll };
+}
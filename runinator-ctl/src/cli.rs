@@ -1,9 +1,11 @@
 use std::path::PathBuf;
 use uuid::Uuid;
 
+use chrono::{DateTime, Utc};
 use clap::{Parser, Subcommand, ValueEnum};
 use runinator_models::provisioning::ProvisionBackend;
-use runinator_models::replicas::ReplicaKind;
+use runinator_models::replicas::{ReplicaKind, ReplicaStatus};
+use runinator_models::run_health::SummaryPeriod;
 use runinator_models::semver::SemVerBump;
 use runinator_models::settings::SettingKind;
 use runinator_wdl::TypePolicy;
@@ -44,6 +46,23 @@ impl From<CliSettingKind> for SettingKind {
     }
 }
 
+/// cli-facing run-health window, mapped to the shared `SummaryPeriod`.
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+pub enum CliSummaryPeriod {
+    #[default]
+    Weekly,
+    Monthly,
+}
+
+impl From<CliSummaryPeriod> for SummaryPeriod {
+    fn from(period: CliSummaryPeriod) -> Self {
+        match period {
+            CliSummaryPeriod::Weekly => SummaryPeriod::Weekly,
+            CliSummaryPeriod::Monthly => SummaryPeriod::Monthly,
+        }
+    }
+}
+
 /// cli-facing WDL typing policy.
 #[derive(Debug, Clone, Copy, Default, ValueEnum)]
 pub enum CliTyping {
@@ -61,6 +80,22 @@ impl From<CliTyping> for TypePolicy {
     }
 }
 
+/// file format for `workflows export`, sent as the export endpoint's `format` query.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum CliExportFormat {
+    Json,
+    Yaml,
+}
+
+impl CliExportFormat {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CliExportFormat::Json => "json",
+            CliExportFormat::Yaml => "yaml",
+        }
+    }
+}
+
 /// cli-facing provisioning backend, mapped to the shared `ProvisionBackend`.
 #[derive(Debug, Clone, Copy, ValueEnum)]
 pub enum CliProvisionBackend {
@@ -97,6 +132,24 @@ impl From<CliNodeKind> for ReplicaKind {
     }
 }
 
+/// cli-facing replica liveness, mapped to the shared `ReplicaStatus`.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum CliReplicaStatus {
+    Live,
+    Stale,
+    Offline,
+}
+
+impl From<CliReplicaStatus> for ReplicaStatus {
+    fn from(status: CliReplicaStatus) -> Self {
+        match status {
+            CliReplicaStatus::Live => ReplicaStatus::Live,
+            CliReplicaStatus::Stale => ReplicaStatus::Stale,
+            CliReplicaStatus::Offline => ReplicaStatus::Offline,
+        }
+    }
+}
+
 #[derive(Debug, Parser)]
 #[command(
     name = "runinatorctl",
@@ -119,6 +172,15 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub json: bool,
 
+    /// Zone for displayed timestamps: `utc`, `local`, or an IANA name such as `Europe/Berlin`.
+    /// `--json` output stays UTC.
+    #[arg(long, global = true, env = "RUNINATOR_TIMEZONE", default_value = "utc")]
+    pub timezone: String,
+
+    /// strftime pattern for displayed timestamps, e.g. `%Y-%m-%d %H:%M %Z` (default: RFC 3339).
+    #[arg(long, global = true, env = "RUNINATOR_DATE_FORMAT")]
+    pub date_format: Option<String>,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -186,6 +248,39 @@ pub enum Commands {
         #[command(subcommand)]
         command: OrgCommands,
     },
+    /// List registered workers with their plugins, last heartbeat, and current load.
+    Workers {
+        /// Only show workers in this liveness state.
+        #[arg(long, value_enum)]
+        status: Option<CliReplicaStatus>,
+    },
+    /// Pause or resume dispatch and execution across the whole cluster.
+    Cluster {
+        #[command(subcommand)]
+        command: ClusterCommands,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ClusterCommands {
+    /// Show whether dispatch or execution is paused cluster-wide.
+    Status,
+    /// Pause dispatch and/or execution fleet-wide; pauses both unless one is named.
+    Pause {
+        /// Hold queued actions in the dispatch outbox.
+        #[arg(long)]
+        dispatch: bool,
+        /// Stop workers taking new actions off the broker.
+        #[arg(long)]
+        execution: bool,
+        /// Stop firing triggers and starting backfilled slots.
+        #[arg(long)]
+        scheduling: bool,
+        #[arg(long)]
+        reason: Option<String>,
+    },
+    /// Lift any cluster-wide pause.
+    Resume,
 }
 
 #[derive(Debug, Subcommand)]
@@ -339,8 +434,25 @@ pub enum WorkflowCommands {
     /// definition / import a workflow bundle from a JSON file. For a pack, an adjacent settings
     /// bundle (a `.wdlm` "settings" entry or a sibling settings.wdls/settings.json) is always
     /// imported too to seed config/secret slots. When no path is given, falls back to the
-    /// `~/.runinator/workflows` folder if it exists.
-    Apply { file: Option<PathBuf> },
+    /// `~/.runinator/workflows` folder if it exists. In a JSON file, a workflow entry with
+    /// `"state": "absent"` deletes that workflow (every version) from the server instead.
+    Apply {
+        file: Option<PathBuf>,
+        /// Disable (never delete) server workflows missing from the applied source, limited to the
+        /// namespaces the source applies to. Safer than pruning in shared environments.
+        #[arg(long)]
+        disable_missing: bool,
+        /// Import every cron trigger of a json bundle disabled, e.g. when cloning production into
+        /// staging.
+        #[arg(long)]
+        disable_schedules: bool,
+        /// Shift every cron trigger of a json bundle this many seconds after its slot.
+        #[arg(long, value_name = "SECONDS")]
+        schedule_offset: Option<i64>,
+    },
+    /// Compare the workflows a source defines with the live ones by portable definition hash,
+    /// without applying anything. Exits non-zero when any workflow drifted or is missing.
+    Drift { file: Option<PathBuf> },
     /// Dry-run a workflow pack against .wdlt test suites: simulate the state machine offline with
     /// mocked task outputs and assert on the branch taken and final outputs. No server required.
     Test {
@@ -380,7 +492,33 @@ pub enum WorkflowCommands {
         workflow_id: Option<Uuid>,
         #[arg(short, long)]
         output: Option<PathBuf>,
+        /// Write the server-rendered workflow file in this format (importable by `apply` and the
+        /// file-backed workflow source) instead of the client-formatted json bundle.
+        #[arg(long, value_enum)]
+        format: Option<CliExportFormat>,
+        /// Include each workflow's most recent finished runs (at most 100) as baselines.
+        #[arg(long)]
+        runs: Option<usize>,
     },
+    /// List the server's workflow templates.
+    Templates,
+    /// Create a workflow from a server template (plus its cron trigger, if the template has one).
+    New {
+        /// Name of the new workflow.
+        name: String,
+        /// Template id or name.
+        #[arg(long)]
+        template: String,
+        #[arg(long)]
+        namespace: Option<String>,
+        /// Enable the workflow (and its trigger) right away instead of creating it disabled.
+        #[arg(long)]
+        enable: bool,
+    },
+    /// List a workflow's saved revisions, newest first.
+    Revisions { workflow: String },
+    /// Save an earlier revision of a workflow again (recorded as a new revision).
+    Revert { workflow: String, revision: i64 },
     /// Duplicate a workflow into a new version sharing its name (default bump: minor).
     Duplicate {
         /// Workflow id or name to duplicate.
@@ -445,6 +583,15 @@ pub enum RunCommands {
     Rename { id: Uuid, name: Option<String> },
     /// List the run-level artifacts a workflow run produced.
     Artifacts { id: Uuid },
+    /// Print a run-health digest: success rate, slowest workflows, and new failures.
+    Health {
+        /// Window to summarize: weekly (last 7 days) or monthly (last 30 days).
+        #[arg(long, value_enum, default_value_t = CliSummaryPeriod::Weekly)]
+        period: CliSummaryPeriod,
+        /// Print only the digest subject line.
+        #[arg(long)]
+        subject: bool,
+    },
 }
 
 #[derive(Debug, Subcommand)]
@@ -499,6 +646,12 @@ pub enum TriggerCommands {
     List { workflow: String },
     /// List triggers due for execution.
     Due,
+    /// Dry-run the scheduler: the cron firings it would enqueue, blackout deferrals included.
+    Preview {
+        /// Look-ahead in minutes (server default 60).
+        #[arg(long)]
+        minutes: Option<i64>,
+    },
     /// Create a run from a trigger.
     Run {
         trigger_id: Uuid,
@@ -509,6 +662,23 @@ pub enum TriggerCommands {
         #[arg(long)]
         debug: bool,
     },
+    /// Replay a cron trigger's past slots; the scheduler starts them oldest first.
+    Backfill {
+        trigger_id: Uuid,
+        /// First instant of the window (RFC 3339).
+        #[arg(long)]
+        from: DateTime<Utc>,
+        /// Last instant of the window (RFC 3339); must not be in the future.
+        #[arg(long)]
+        to: DateTime<Utc>,
+        /// Backfilled runs kept open at once (server default 1).
+        #[arg(long)]
+        concurrency: Option<i64>,
+    },
+    /// List a trigger's backfills, newest first.
+    Backfills { trigger_id: Uuid },
+    /// Stop a running backfill; runs it already started carry on.
+    CancelBackfill { backfill_id: Uuid },
 }
 
 #[derive(Debug, Subcommand)]
@@ -1,4 +1,5 @@
 use std::{
+    collections::HashSet,
     error::Error,
     fs, io,
     path::{Path, PathBuf},
@@ -10,14 +11,19 @@ use runinator_api::{AsyncApiClient, StaticLocator};
 use runinator_models::json;
 use runinator_models::value::{Map, Value};
 use runinator_models::{
+    backfills::WorkflowBackfill,
     billing::ScaleOrgNodesRequest,
+    cluster::{ClusterPause, ClusterPauseRequest},
     providers::ProviderMetadata,
     provisioning::{NodeSpec, ProvisionedGroup, ScaleNodesRequest, StopNodeRequest},
-    replicas::ReplicaKind,
+    replicas::{ReplicaKind, WorkerSummary},
+    revisions::WorkflowRevision,
     settings::SettingKind,
+    templates::{InstantiateTemplateRequest, WorkflowTemplate},
+    upcoming::SchedulerPreview,
     workflows::{
-        WorkflowBundle, WorkflowDefinition, WorkflowNodeRun, WorkflowRun, WorkflowStatus,
-        WorkflowTrigger,
+        WorkflowBundle, WorkflowDefinition, WorkflowDefinitionHash, WorkflowImportOptions,
+        WorkflowNodeRun, WorkflowRun, WorkflowStatus, WorkflowTrigger,
     },
 };
 use tokio::time;
@@ -26,9 +32,9 @@ use runinator_pack::source as pack;
 
 use crate::{
     cli::{
-        ApprovalCommands, ArtifactCommands, Cli, CliTyping, Commands, NodeCommands, OrgCommands,
-        ProviderCommands, RunCommands, SettingsCommands, TriggerCommands, WdlCommands,
-        WorkflowCommands,
+        ApprovalCommands, ArtifactCommands, Cli, CliReplicaStatus, CliTyping, ClusterCommands,
+        Commands, NodeCommands, OrgCommands, ProviderCommands, RunCommands, SettingsCommands,
+        TriggerCommands, WdlCommands, WorkflowCommands,
     },
     output, params,
 };
@@ -40,6 +46,10 @@ type Client = AsyncApiClient<StaticLocator>;
 #[derive(Debug, Clone)]
 struct WorkflowApplySummary {
     message: String,
+    /// the workflows the source defines, for `--disable-missing`.
+    applied: Vec<WorkflowDefinition>,
+    /// identities of workflows the source marks `"state": "absent"`.
+    absent: Vec<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -76,9 +86,52 @@ pub async fn run(client: &Client, cli: &Cli) -> Result<()> {
         Commands::Settings { command } => settings(client, command, cli.json).await,
         Commands::Nodes { command } => nodes(client, command, cli.json).await,
         Commands::Orgs { command } => orgs(client, command, cli.json).await,
+        Commands::Workers { status } => workers(client, *status, cli.json).await,
+        Commands::Cluster { command } => cluster(client, command, cli.json).await,
     }
 }
 
+async fn workers(
+    client: &Client,
+    status: Option<CliReplicaStatus>,
+    json_output: bool,
+) -> Result<()> {
+    let workers = client.fetch_workers(status.map(Into::into)).await?;
+    if json_output {
+        return output::json(&workers);
+    }
+    print_workers(&workers);
+    Ok(())
+}
+
+async fn cluster(client: &Client, command: &ClusterCommands, json_output: bool) -> Result<()> {
+    let pause = match command {
+        ClusterCommands::Status => client.fetch_cluster_pause().await?,
+        ClusterCommands::Pause {
+            dispatch,
+            execution,
+            scheduling,
+            reason,
+        } => {
+            // naming none of them pauses all three.
+            let all = !dispatch && !execution && !scheduling;
+            let request = ClusterPauseRequest {
+                dispatch: *dispatch || all,
+                execution: *execution || all,
+                scheduling: *scheduling || all,
+                reason: reason.clone(),
+            };
+            client.pause_cluster(&request).await?
+        }
+        ClusterCommands::Resume => client.resume_cluster().await?,
+    };
+    if json_output {
+        return output::json(&pause);
+    }
+    print_cluster_pause(&pause);
+    Ok(())
+}
+
 async fn orgs(client: &Client, command: &OrgCommands, json_output: bool) -> Result<()> {
     match command {
         OrgCommands::List => {
@@ -273,6 +326,48 @@ fn parse_labels(labels: &[String]) -> Result<std::collections::BTreeMap<String,
     Ok(map)
 }
 
+fn print_cluster_pause(pause: &ClusterPause) {
+    let state = |paused: bool| if paused { "paused" } else { "running" };
+    println!("dispatch:   {}", state(pause.dispatch));
+    println!("execution:  {}", state(pause.execution));
+    println!("scheduling: {}", state(pause.scheduling));
+    if let Some(reason) = &pause.reason {
+        println!("reason:     {reason}");
+    }
+}
+
+fn print_workers(workers: &[WorkerSummary]) {
+    if workers.is_empty() {
+        println!("no workers registered");
+        return;
+    }
+    println!(
+        "{:<24} {:<8} {:<10} {:<20} {:<30}",
+        "worker", "status", "load", "last seen", "plugins"
+    );
+    for worker in workers {
+        let load = match (worker.in_flight_actions, worker.max_concurrent_actions) {
+            (Some(in_flight), Some(max)) => format!("{in_flight}/{max}"),
+            (Some(in_flight), None) => in_flight.to_string(),
+            (None, _) => "-".into(),
+        };
+        println!(
+            "{:<24} {:<8} {:<10} {:<20} {:<30}",
+            output::truncate(
+                worker
+                    .display_name
+                    .as_deref()
+                    .unwrap_or(&worker.instance_id),
+                24
+            ),
+            worker.status.as_str(),
+            load,
+            output::timestamp(worker.last_seen_at),
+            output::truncate(&worker.plugins.join(","), 30)
+        );
+    }
+}
+
 fn print_groups(groups: &[ProvisionedGroup]) {
     if groups.is_empty() {
         println!("no node groups");
@@ -325,7 +420,82 @@ async fn workflows(client: &Client, command: &WorkflowCommands, json_output: boo
             if json_output {
                 return output::json(&workflow);
             }
-            print_workflow(&workflow)?;
+            let last_change = match workflow.id {
+                Some(id) => client
+                    .fetch_workflow_revisions(id)
+                    .await?
+                    .into_iter()
+                    .next(),
+                None => None,
+            };
+            print_workflow(&workflow, last_change.as_ref())?;
+        }
+        WorkflowCommands::Templates => {
+            let templates = client.fetch_workflow_templates().await?;
+            if json_output {
+                return output::json(&templates);
+            }
+            print_templates(&templates);
+        }
+        WorkflowCommands::New {
+            name,
+            template,
+            namespace,
+            enable,
+        } => {
+            let template = fetch_template_ref(client, template).await?;
+            let template_id = template
+                .id
+                .ok_or_else(|| err("template has no persisted id"))?;
+            let bundle = client
+                .instantiate_workflow_template(
+                    template_id,
+                    &InstantiateTemplateRequest {
+                        name: name.clone(),
+                        namespace: namespace.clone(),
+                        enabled: *enable,
+                    },
+                )
+                .await?;
+            if json_output {
+                return output::json(&bundle);
+            }
+            for workflow in &bundle.workflows {
+                println!(
+                    "created {} -> id {} from template {}",
+                    workflow.name,
+                    workflow.id.unwrap_or_default(),
+                    template.name
+                );
+            }
+            for trigger in &bundle.triggers {
+                println!("created trigger {}", trigger.id.unwrap_or_default());
+            }
+        }
+        WorkflowCommands::Revisions { workflow } => {
+            let workflow = fetch_workflow_ref(client, workflow).await?;
+            let workflow_id = workflow
+                .id
+                .ok_or_else(|| err("workflow has no persisted id"))?;
+            let revisions = client.fetch_workflow_revisions(workflow_id).await?;
+            if json_output {
+                return output::json(&revisions);
+            }
+            print_revisions(&revisions);
+        }
+        WorkflowCommands::Revert { workflow, revision } => {
+            let existing = fetch_workflow_ref(client, workflow).await?;
+            let workflow_id = existing
+                .id
+                .ok_or_else(|| err("workflow has no persisted id"))?;
+            let reverted = client.revert_workflow(workflow_id, *revision).await?;
+            if json_output {
+                return output::json(&reverted);
+            }
+            println!(
+                "reverted {} to revision {} (v{})",
+                reverted.name, revision, reverted.version
+            );
         }
         WorkflowCommands::Validate { file } => {
             let workflow = read_workflow_definition(file)?;
@@ -335,13 +505,44 @@ async fn workflows(client: &Client, command: &WorkflowCommands, json_output: boo
             }
             println!("workflow {} v{} validates", workflow.name, workflow.version);
         }
-        WorkflowCommands::Apply { file } => {
+        WorkflowCommands::Apply {
+            file,
+            disable_missing,
+            disable_schedules,
+            schedule_offset,
+        } => {
             let resolved = resolve_workflow_apply_path(file.as_deref())?;
-            let summary = apply_workflow_source(client, &resolved, json_output).await?;
+            let options = WorkflowImportOptions {
+                disable_schedules: *disable_schedules,
+                schedule_offset_seconds: *schedule_offset,
+            };
+            let summary =
+                apply_workflow_source(client, &resolved, *disable_missing, &options, json_output)
+                    .await?;
             if !json_output {
                 print_apply_summary(&summary);
             }
         }
+        WorkflowCommands::Drift { file } => {
+            let resolved = resolve_workflow_apply_path(file.as_deref())?;
+            let local = load_source_workflows(client, &resolved).await?;
+            let drift = workflow_drift(&local, &client.fetch_workflow_hashes().await?);
+            if json_output {
+                output::json(&drift_json(&drift))?;
+            } else {
+                print_drift(&drift);
+            }
+            let out_of_sync = drift
+                .iter()
+                .filter(|entry| entry.state != DriftState::InSync)
+                .count();
+            if out_of_sync > 0 {
+                return Err(err(format!(
+                    "{out_of_sync} workflow(s) differ from {}",
+                    resolved.display()
+                )));
+            }
+        }
         WorkflowCommands::Test {
             file,
             tests,
@@ -379,8 +580,27 @@ async fn workflows(client: &Client, command: &WorkflowCommands, json_output: boo
         WorkflowCommands::Export {
             workflow_id,
             output: path,
+            format: Some(format),
+            runs,
+        } => {
+            let file = client
+                .export_workflow_file(*workflow_id, format.as_str(), *runs)
+                .await?;
+            match path {
+                Some(path) => {
+                    fs::write(path, &file)?;
+                    println!("wrote {}", path.display());
+                }
+                None => print!("{file}"),
+            }
+        }
+        WorkflowCommands::Export {
+            workflow_id,
+            output: path,
+            format: None,
+            runs,
         } => {
-            let bundle = client.export_workflow_bundle(*workflow_id).await?;
+            let bundle = client.export_workflow_bundle(*workflow_id, *runs).await?;
             if let Some(path) = path {
                 write_json_file(path, &bundle)?;
                 if !json_output {
@@ -453,11 +673,42 @@ fn resolve_workflow_apply_path(file: Option<&Path>) -> Result<PathBuf> {
 async fn apply_workflow_source(
     client: &Client,
     file: &Path,
+    disable_missing: bool,
+    options: &WorkflowImportOptions,
     json_output: bool,
 ) -> Result<WorkflowApplySummary> {
+    let mut summary = import_workflow_source(client, file, options, json_output).await?;
+    if !summary.absent.is_empty() {
+        let deleted = delete_absent_workflows(client, &summary.absent).await?;
+        summary.message.push_str(&format!(
+            "; deleted {deleted} workflow version(s) marked absent"
+        ));
+    }
+    if disable_missing {
+        let disabled = disable_missing_workflows(client, &summary.applied).await?;
+        summary.message.push_str(&format!(
+            "; disabled {disabled} workflow(s) missing from the source"
+        ));
+    }
+    Ok(summary)
+}
+
+async fn import_workflow_source(
+    client: &Client,
+    file: &Path,
+    options: &WorkflowImportOptions,
+    json_output: bool,
+) -> Result<WorkflowApplySummary> {
+    // schedules are only rewritten by the json bundle import, which is what `workflows export`
+    // writes when cloning an environment.
+    let bundle_only =
+        || err("--disable-schedules and --schedule-offset apply to json workflow bundles only");
     // a .wdl/.wdlm/directory is compiled client-side, zipped, and uploaded as one compiled pack;
     // json is handled below.
     if pack::is_pack_source(file) {
+        if !options.keeps_schedules() {
+            return Err(bundle_only());
+        }
         let providers = client.fetch_providers().await.unwrap_or_default();
         let bundle = pack::load_workflow_bundle_with_providers(file, &providers)?;
         // any settings (`settings.wdls`/`.json`) always ride in the same compiled pack zip.
@@ -477,6 +728,8 @@ async fn apply_workflow_source(
                 result.secrets.secrets.len(),
                 result.pipelines.len()
             ),
+            applied: bundle.workflows,
+            absent: Vec::new(),
         };
         if json_output {
             output::json(&result)?;
@@ -484,17 +737,27 @@ async fn apply_workflow_source(
         return Ok(summary);
     }
 
-    let value = params::load_json_file(file)?;
-    if value.get("workflows").is_some() {
+    let mut value = params::load_json_file(file)?;
+    if let Some(entries) = value.get_mut("workflows").and_then(Value::as_array_mut) {
+        let absent = take_absent_workflows(entries)?;
         // raw json bundles require the client to acknowledge that system breakage is possible.
         let bundle: WorkflowBundle = serde_json::from_value(value.into())?;
-        let bundle = client.import_workflow_bundle(&bundle).await?;
+        if bundle.workflows.is_empty() && bundle.triggers.is_empty() {
+            return Ok(WorkflowApplySummary {
+                message: "imported 0 workflows and 0 triggers".into(),
+                applied: Vec::new(),
+                absent,
+            });
+        }
+        let bundle = client.import_workflow_bundle(&bundle, options).await?;
         let summary = WorkflowApplySummary {
             message: format!(
                 "imported {} workflows and {} triggers",
                 bundle.workflows.len(),
                 bundle.triggers.len()
             ),
+            applied: bundle.workflows.clone(),
+            absent,
         };
         if json_output {
             output::json(&bundle)?;
@@ -502,6 +765,16 @@ async fn apply_workflow_source(
         return Ok(summary);
     }
 
+    if let Some(key) = absent_workflow_key(&value)? {
+        return Ok(WorkflowApplySummary {
+            message: format!("workflow {key} marked absent"),
+            applied: Vec::new(),
+            absent: vec![key],
+        });
+    }
+    if !options.keeps_schedules() {
+        return Err(bundle_only());
+    }
     let workflow: WorkflowDefinition = serde_json::from_value(value.into())?;
     let workflow = client.upsert_workflow(&workflow).await?;
     if json_output {
@@ -514,11 +787,215 @@ async fn apply_workflow_source(
             workflow.version,
             workflow.id.unwrap_or_default()
         ),
+        applied: vec![workflow],
+        absent: Vec::new(),
     })
 }
 
+/// a workflow's qualified identity (`namespace.name`, or just `name` when unqualified), shared by
+/// every version of it.
+fn workflow_key(namespace: Option<&str>, name: &str) -> String {
+    match namespace {
+        Some(namespace) => format!("{namespace}.{name}"),
+        None => name.to_string(),
+    }
+}
+
+/// the identity of a json workflow entry marked `"state": "absent"`; `None` for `present` (the
+/// default when `state` is omitted).
+fn absent_workflow_key(entry: &Value) -> Result<Option<String>> {
+    match entry.get("state").and_then(Value::as_str) {
+        None | Some("present") => Ok(None),
+        Some("absent") => {
+            let name = entry
+                .get("name")
+                .and_then(Value::as_str)
+                .ok_or_else(|| err("a workflow marked absent needs a name"))?;
+            let namespace = entry.get("namespace").and_then(Value::as_str);
+            Ok(Some(workflow_key(namespace, name)))
+        }
+        Some(other) => Err(err(format!(
+            "unknown workflow state '{other}' (expected present or absent)"
+        ))),
+    }
+}
+
+/// remove the entries marked absent from a bundle's `workflows` array, returning their identities.
+fn take_absent_workflows(entries: &mut Vec<Value>) -> Result<Vec<String>> {
+    let mut absent = Vec::new();
+    let mut kept = Vec::with_capacity(entries.len());
+    for entry in entries.drain(..) {
+        match absent_workflow_key(&entry)? {
+            Some(key) => absent.push(key),
+            None => kept.push(entry),
+        }
+    }
+    *entries = kept;
+    Ok(absent)
+}
+
+async fn delete_absent_workflows(client: &Client, absent: &[String]) -> Result<usize> {
+    let mut deleted = 0;
+    for workflow in client.fetch_workflows().await? {
+        let key = workflow_key(workflow.namespace.as_deref(), &workflow.name);
+        let Some(id) = workflow.id.filter(|_| absent.contains(&key)) else {
+            continue;
+        };
+        client.delete_workflow(id).await?;
+        deleted += 1;
+    }
+    Ok(deleted)
+}
+
+/// disable enabled server workflows the source does not define, only within the namespaces the
+/// source applies to so unrelated teams' workflows in a shared environment are left alone.
+async fn disable_missing_workflows(
+    client: &Client,
+    applied: &[WorkflowDefinition],
+) -> Result<usize> {
+    let keys = applied
+        .iter()
+        .map(|workflow| workflow_key(workflow.namespace.as_deref(), &workflow.name))
+        .collect::<HashSet<_>>();
+    let namespaces = applied
+        .iter()
+        .map(|workflow| workflow.namespace.clone())
+        .collect::<HashSet<_>>();
+    let mut disabled = 0;
+    for mut workflow in client.fetch_workflows().await? {
+        let key = workflow_key(workflow.namespace.as_deref(), &workflow.name);
+        if !workflow.enabled || !namespaces.contains(&workflow.namespace) || keys.contains(&key) {
+            continue;
+        }
+        workflow.enabled = false;
+        client.upsert_workflow(&workflow).await?;
+        disabled += 1;
+    }
+    Ok(disabled)
+}
+
 fn print_apply_summary(summary: &WorkflowApplySummary) {
     println!("{}", summary.message);
+    for workflow in &summary.applied {
+        println!(
+            "  {} v{} {}",
+            workflow_key(workflow.namespace.as_deref(), &workflow.name),
+            workflow.version,
+            runinator_workflows::workflow_definition_hash(workflow)
+        );
+    }
+}
+
+// the workflows a source defines, compiled or parsed the way `apply` would but not uploaded.
+async fn load_source_workflows(client: &Client, file: &Path) -> Result<Vec<WorkflowDefinition>> {
+    if pack::is_pack_source(file) {
+        let providers = client.fetch_providers().await.unwrap_or_default();
+        return Ok(pack::load_workflow_bundle_with_providers(file, &providers)?.workflows);
+    }
+    let mut value = params::load_json_file(file)?;
+    if let Some(entries) = value.get_mut("workflows").and_then(Value::as_array_mut) {
+        take_absent_workflows(entries)?;
+        let bundle: WorkflowBundle = serde_json::from_value(value.into())?;
+        return Ok(bundle.workflows);
+    }
+    if absent_workflow_key(&value)?.is_some() {
+        return Ok(Vec::new());
+    }
+    Ok(vec![serde_json::from_value(value.into())?])
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DriftState {
+    InSync,
+    /// a live workflow with the same identity and version has a different definition.
+    Drifted,
+    /// no live workflow has the identity and version.
+    Missing,
+}
+
+impl DriftState {
+    fn as_str(self) -> &'static str {
+        match self {
+            DriftState::InSync => "in-sync",
+            DriftState::Drifted => "drifted",
+            DriftState::Missing => "missing",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct WorkflowDrift {
+    key: String,
+    version: String,
+    state: DriftState,
+    local_hash: String,
+    live_hash: Option<String>,
+}
+
+// match each source workflow to the live one with the same (namespace, name, version).
+fn workflow_drift(
+    local: &[WorkflowDefinition],
+    live: &[WorkflowDefinitionHash],
+) -> Vec<WorkflowDrift> {
+    local
+        .iter()
+        .map(|workflow| {
+            let local_hash = runinator_workflows::workflow_definition_hash(workflow);
+            let live_hash = live
+                .iter()
+                .find(|entry| {
+                    entry.name == workflow.name
+                        && entry.namespace == workflow.namespace
+                        && entry.version == workflow.version
+                })
+                .map(|entry| entry.hash.clone());
+            let state = match &live_hash {
+                None => DriftState::Missing,
+                Some(hash) if *hash == local_hash => DriftState::InSync,
+                Some(_) => DriftState::Drifted,
+            };
+            WorkflowDrift {
+                key: workflow_key(workflow.namespace.as_deref(), &workflow.name),
+                version: workflow.version.to_string(),
+                state,
+                local_hash,
+                live_hash,
+            }
+        })
+        .collect()
+}
+
+fn drift_json(drift: &[WorkflowDrift]) -> Value {
+    Value::Array(
+        drift
+            .iter()
+            .map(|entry| {
+                json!({
+                    "workflow": entry.key,
+                    "version": entry.version,
+                    "state": entry.state.as_str(),
+                    "local_hash": entry.local_hash,
+                    "live_hash": entry.live_hash,
+                })
+            })
+            .collect(),
+    )
+}
+
+fn print_drift(drift: &[WorkflowDrift]) {
+    println!(
+        "{:<8} {:<36} {:>7} local_hash",
+        "state", "workflow", "version"
+    );
+    for entry in drift {
+        println!(
+            "{:<8} {:<36} {:>7} {}",
+            entry.state.as_str(),
+            output::truncate(&entry.key, 36),
+            entry.version,
+            entry.local_hash
+        );
+    }
 }
 
 // dry-run a compiled pack against .wdlt suites entirely client-side; no server or broker involved.
@@ -716,7 +1193,15 @@ async fn workflow_dev(
                 source_count,
                 if source_count == 1 { "" } else { "s" }
             );
-            match apply_workflow_source(client, file, false).await {
+            match apply_workflow_source(
+                client,
+                file,
+                false,
+                &WorkflowImportOptions::default(),
+                false,
+            )
+            .await
+            {
                 Ok(summary) => {
                     print_apply_summary(&summary);
                     if let Some(workflow) = run_workflow {
@@ -1036,6 +1521,17 @@ async fn runs(client: &Client, command: &RunCommands, json_output: bool) -> Resu
             "renamed workflow run",
             json_output,
         )?,
+        RunCommands::Health { period, subject } => {
+            let report = client.fetch_workflow_run_health((*period).into()).await?;
+            if json_output {
+                return output::json(&report);
+            }
+            if *subject {
+                println!("{}", report.subject);
+            } else {
+                print!("{}", report.text);
+            }
+        }
         RunCommands::Artifacts { id } => {
             let artifacts = client.fetch_workflow_run_artifacts(*id).await?;
             if json_output {
@@ -1164,6 +1660,13 @@ async fn triggers(client: &Client, command: &TriggerCommands, json_output: bool)
             }
             print_triggers(&triggers);
         }
+        TriggerCommands::Preview { minutes } => {
+            let preview = client.preview_scheduler(*minutes).await?;
+            if json_output {
+                return output::json(&preview);
+            }
+            print_scheduler_preview(&preview);
+        }
         TriggerCommands::Run {
             trigger_id,
             params: cli_params,
@@ -1179,6 +1682,34 @@ async fn triggers(client: &Client, command: &TriggerCommands, json_output: bool)
             }
             print_run_summary(&run);
         }
+        TriggerCommands::Backfill {
+            trigger_id,
+            from,
+            to,
+            concurrency,
+        } => {
+            let backfill = client
+                .backfill_workflow_trigger(*trigger_id, *from, *to, *concurrency)
+                .await?;
+            if json_output {
+                return output::json(&backfill);
+            }
+            print_backfills(std::slice::from_ref(&backfill));
+        }
+        TriggerCommands::Backfills { trigger_id } => {
+            let backfills = client.fetch_workflow_backfills(*trigger_id).await?;
+            if json_output {
+                return output::json(&backfills);
+            }
+            print_backfills(&backfills);
+        }
+        TriggerCommands::CancelBackfill { backfill_id } => {
+            let backfill = client.cancel_workflow_backfill(*backfill_id).await?;
+            if json_output {
+                return output::json(&backfill);
+            }
+            print_backfills(std::slice::from_ref(&backfill));
+        }
     }
     Ok(())
 }
@@ -1424,12 +1955,23 @@ fn print_workflows(workflows: &[WorkflowDefinition]) {
     }
 }
 
-fn print_workflow(workflow: &WorkflowDefinition) -> Result<()> {
+fn print_workflow(
+    workflow: &WorkflowDefinition,
+    last_change: Option<&WorkflowRevision>,
+) -> Result<()> {
     println!("id: {}", workflow.id.unwrap_or_default());
     println!("name: {}", workflow.name);
     println!("version: {}", workflow.version);
     println!("enabled: {}", workflow.enabled);
     println!("updated_at: {}", output::time(workflow.updated_at));
+    if let Some(revision) = last_change {
+        println!(
+            "last changed: {} by {} (revision {})",
+            output::timestamp(revision.created_at),
+            revision_author(revision),
+            revision.revision
+        );
+    }
     println!(
         "definition: {}",
         serde_json::to_string_pretty(&workflow.definition)?
@@ -1437,6 +1979,56 @@ fn print_workflow(workflow: &WorkflowDefinition) -> Result<()> {
     Ok(())
 }
 
+/// resolve a template by id, else by exact name.
+async fn fetch_template_ref(client: &Client, template: &str) -> Result<WorkflowTemplate> {
+    if let Ok(id) = template.parse::<Uuid>() {
+        return Ok(client.fetch_workflow_template(id).await?);
+    }
+    client
+        .fetch_workflow_templates()
+        .await?
+        .into_iter()
+        .find(|candidate| candidate.name == template)
+        .ok_or_else(|| err(format!("workflow template {template} not found")))
+}
+
+fn print_templates(templates: &[WorkflowTemplate]) {
+    println!("{:<36} {:<32} {:<16} labels", "id", "name", "cron");
+    for template in templates {
+        println!(
+            "{:<36} {:<32} {:<16} {}",
+            template.id.unwrap_or_default(),
+            output::truncate(&template.name, 32),
+            template.cron.as_deref().unwrap_or("-"),
+            template.labels.join(",")
+        );
+    }
+}
+
+fn print_revisions(revisions: &[WorkflowRevision]) {
+    println!(
+        "{:>8} {:<22} {:<36} {:>7} enabled",
+        "revision", "changed_at", "changed_by", "version"
+    );
+    for revision in revisions {
+        println!(
+            "{:>8} {:<22} {:<36} {:>7} {}",
+            revision.revision,
+            output::truncate(&output::timestamp(revision.created_at), 22),
+            revision_author(revision),
+            revision.workflow.version,
+            revision.workflow.enabled
+        );
+    }
+}
+
+fn revision_author(revision: &WorkflowRevision) -> String {
+    revision
+        .changed_by
+        .map(|id| id.to_string())
+        .unwrap_or_else(|| "system".into())
+}
+
 fn print_runs(runs: &[WorkflowRun]) {
     println!(
         "{:<6} {:<18} {:<10} {:<22} {:<18} message",
@@ -1449,7 +2041,7 @@ fn print_runs(runs: &[WorkflowRun]) {
             run.status.as_str(),
             run.workflow_id,
             output::truncate(run.active_node_id.as_deref().unwrap_or("-"), 22),
-            output::truncate(&run.created_at.to_rfc3339(), 18),
+            output::truncate(&output::timestamp(run.created_at), 18),
             output::truncate(run.message.as_deref().unwrap_or(""), 48)
         );
     }
@@ -1467,7 +2059,7 @@ fn print_run_summary(run: &WorkflowRun) {
 
 fn print_run_detail(run: &WorkflowRun, nodes: &[WorkflowNodeRun]) {
     print_run_summary(run);
-    println!("created_at: {}", run.created_at.to_rfc3339());
+    println!("created_at: {}", output::timestamp(run.created_at));
     println!("started_at: {}", output::time(run.started_at));
     println!("finished_at: {}", output::time(run.finished_at));
     if let Some(message) = &run.message {
@@ -1531,7 +2123,53 @@ fn print_triggers(triggers: &[WorkflowTrigger]) {
             trigger.workflow_id,
             trigger.enabled,
             trigger.kind.as_str(),
-            output::time(trigger.next_execution)
+            match trigger.schedule_error() {
+                Some(error) => format!("schedule error: {}", output::truncate(error, 64)),
+                None => output::time(trigger.next_execution),
+            }
+        );
+    }
+}
+
+fn print_backfills(backfills: &[WorkflowBackfill]) {
+    println!(
+        "{:<36} {:<10} {:<9} {:<11} window",
+        "id", "status", "fired", "concurrency"
+    );
+    for backfill in backfills {
+        println!(
+            "{:<36} {:<10} {:<9} {:<11} {} .. {}",
+            backfill.id,
+            backfill.status.as_str(),
+            format!("{}/{}", backfill.fired, backfill.slots.len()),
+            backfill.concurrency,
+            output::timestamp(backfill.range_start),
+            output::timestamp(backfill.range_end),
+        );
+    }
+}
+
+fn print_scheduler_preview(preview: &SchedulerPreview) {
+    println!(
+        "{:<20} {:<20} {:<9} {:<36} workflow",
+        "fires_at", "slot", "deferred", "trigger"
+    );
+    for firing in &preview.firings {
+        println!(
+            "{:<20} {:<20} {:<9} {:<36} {}",
+            output::timestamp(firing.fires_at),
+            output::timestamp(firing.slot),
+            firing.deferred,
+            firing.trigger_id,
+            firing.workflow_name
+        );
+    }
+    for trigger in &preview.invalid {
+        println!(
+            "trigger {} ({}): {}",
+            trigger.trigger_id,
+            trigger.workflow_name,
+            output::truncate(&trigger.error, 64)
         );
     }
 }
@@ -1593,3 +2231,7 @@ fn value_display(value: &Value, key: &str) -> String {
         _ => "-".into(),
     }
 }
+
+#[cfg(test)]
+#[path = "commands_tests.rs"]
+mod tests;
@@ -0,0 +1,85 @@
+use runinator_models::json;
+
+use super::{DriftState, absent_workflow_key, take_absent_workflows, workflow_drift};
+
+#[test]
+fn absent_entries_are_split_out_of_a_bundle() {
+    let mut bundle = json!({
+        "workflows": [
+            { "name": "keep" },
+            { "name": "keep-too", "state": "present" },
+            { "name": "old", "namespace": "ops", "state": "absent" },
+            { "name": "older", "state": "absent" }
+        ]
+    });
+    let entries = bundle
+        .get_mut("workflows")
+        .and_then(|value| value.as_array_mut())
+        .unwrap();
+
+    let absent = take_absent_workflows(entries).unwrap();
+
+    assert_eq!(absent, vec!["ops.old".to_string(), "older".to_string()]);
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0]["name"], "keep");
+}
+
+#[test]
+fn unknown_workflow_state_is_rejected() {
+    assert!(absent_workflow_key(&json!({ "name": "x", "state": "gone" })).is_err());
+    assert!(absent_workflow_key(&json!({ "state": "absent" })).is_err());
+    assert_eq!(absent_workflow_key(&json!({ "name": "x" })).unwrap(), None);
+}
+
+#[test]
+fn drift_matches_source_workflows_by_identity_and_version() {
+    let definition = |name: &str,
+                      timeout: i64|
+     -> runinator_models::workflows::WorkflowDefinition {
+        serde_json::from_value(serde_json::json!({
+            "name": name,
+            "namespace": "ops",
+            "version": "1.0.0",
+            "definition": {
+                "start": "start",
+                "nodes": [
+                    { "id": "start", "kind": "start", "transitions": { "next": { "$node": "build" } } },
+                    { "id": "build", "kind": "action", "action": { "provider": "console", "function": "run", "timeout_seconds": timeout, "configuration": {} }, "transitions": { "on_success": { "$node": "done" } } },
+                    { "id": "done", "kind": "end" }
+                ]
+            }
+        }))
+        .unwrap()
+    };
+    let live = |workflow: &runinator_models::workflows::WorkflowDefinition| {
+        runinator_models::workflows::WorkflowDefinitionHash {
+            id: uuid::Uuid::now_v7(),
+            name: workflow.name.clone(),
+            namespace: workflow.namespace.clone(),
+            version: workflow.version,
+            hash: runinator_workflows::workflow_definition_hash(workflow),
+        }
+    };
+    let local = vec![
+        definition("same", 1),
+        definition("edited", 1),
+        definition("new", 1),
+    ];
+    let remote = vec![live(&definition("same", 1)), live(&definition("edited", 2))];
+
+    let drift = workflow_drift(&local, &remote);
+
+    let states = drift
+        .iter()
+        .map(|entry| (entry.key.as_str(), entry.state))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        states,
+        vec![
+            ("ops.same", DriftState::InSync),
+            ("ops.edited", DriftState::Drifted),
+            ("ops.new", DriftState::Missing),
+        ]
+    );
+    assert!(drift[2].live_hash.is_none());
+}
@@ -12,6 +12,10 @@ use crate::cli::{Cli, Commands, WorkflowCommands};
 #[tokio::main]
 async fn main() -> commands::Result<()> {
     let cli = Cli::parse();
+    output::configure_time(output::TimeDisplay::parse(
+        &cli.timezone,
+        cli.date_format.as_deref(),
+    )?);
     // skip the banner in json mode to keep machine-readable output clean.
     if !cli.json {
         banner::print();
@@ -1,17 +1,92 @@
-use chrono::{DateTime, Utc};
+use std::sync::OnceLock;
+
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Local, TimeZone, Utc};
+use chrono_tz::Tz;
 use serde::Serialize;
 
 use crate::commands::Result;
 
+/// the zone timestamps are shown in. api values and `--json` output always stay utc.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DisplayZone {
+    Utc,
+    Local,
+    Named(Tz),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeDisplay {
+    pub zone: DisplayZone,
+    /// a strftime pattern; `None` prints rfc 3339.
+    pub format: Option<String>,
+}
+
+static TIME_DISPLAY: OnceLock<TimeDisplay> = OnceLock::new();
+
+impl TimeDisplay {
+    /// parse `--timezone` (`utc`, `local`, or an iana name like `Europe/Berlin`) and
+    /// `--date-format`.
+    pub fn parse(zone: &str, format: Option<&str>) -> Result<Self> {
+        let zone = match zone.trim() {
+            value if value.eq_ignore_ascii_case("utc") => DisplayZone::Utc,
+            value if value.eq_ignore_ascii_case("local") => DisplayZone::Local,
+            value => DisplayZone::Named(
+                value
+                    .parse::<Tz>()
+                    .map_err(|_| format!("unknown time zone '{value}'"))?,
+            ),
+        };
+        if let Some(format) = format
+            && StrftimeItems::new(format).any(|item| matches!(item, Item::Error))
+        {
+            return Err(format!("invalid date format '{format}'").into());
+        }
+        Ok(Self {
+            zone,
+            format: format.map(str::to_string),
+        })
+    }
+
+    pub fn render(&self, value: DateTime<Utc>) -> String {
+        match self.zone {
+            DisplayZone::Utc => self.render_in(value.with_timezone(&Utc)),
+            DisplayZone::Local => self.render_in(value.with_timezone(&Local)),
+            DisplayZone::Named(zone) => self.render_in(value.with_timezone(&zone)),
+        }
+    }
+
+    fn render_in<Z>(&self, value: DateTime<Z>) -> String
+    where
+        Z: TimeZone,
+        Z::Offset: std::fmt::Display,
+    {
+        match &self.format {
+            Some(format) => value.format(format).to_string(),
+            None => value.to_rfc3339(),
+        }
+    }
+}
+
+/// install the display settings for this process; the first call wins.
+pub fn configure_time(display: TimeDisplay) {
+    let _ = TIME_DISPLAY.set(display);
+}
+
 pub fn json<T: Serialize>(value: &T) -> Result<()> {
     println!("{}", serde_json::to_string_pretty(value)?);
     Ok(())
 }
 
 pub fn time(value: Option<DateTime<Utc>>) -> String {
-    value
-        .map(|value| value.to_rfc3339())
-        .unwrap_or_else(|| "-".into())
+    value.map(timestamp).unwrap_or_else(|| "-".into())
+}
+
+pub fn timestamp(value: DateTime<Utc>) -> String {
+    match TIME_DISPLAY.get() {
+        Some(display) => display.render(value),
+        None => value.to_rfc3339(),
+    }
 }
 
 pub fn truncate(value: &str, max_chars: usize) -> String {
@@ -26,3 +101,7 @@ pub fn truncate(value: &str, max_chars: usize) -> String {
     out.push_str("...");
     out
 }
+
+#[cfg(test)]
+#[path = "output_tests.rs"]
+mod tests;
@@ -0,0 +1,18 @@
+use chrono::{TimeZone, Utc};
+
+use super::{DisplayZone, TimeDisplay};
+
+#[test]
+fn time_display_renders_in_the_chosen_zone_and_format() {
+    let value = Utc.with_ymd_and_hms(2026, 3, 1, 23, 30, 0).unwrap();
+
+    let utc = TimeDisplay::parse("UTC", None).unwrap();
+    assert_eq!(utc.zone, DisplayZone::Utc);
+    assert_eq!(utc.render(value), "2026-03-01T23:30:00+00:00");
+
+    let tokyo = TimeDisplay::parse("Asia/Tokyo", Some("%Y-%m-%d %H:%M %Z")).unwrap();
+    assert_eq!(tokyo.render(value), "2026-03-02 08:30 JST");
+
+    assert!(TimeDisplay::parse("Mars/Olympus", None).is_err());
+    assert!(TimeDisplay::parse("utc", Some("%Q")).is_err());
+}
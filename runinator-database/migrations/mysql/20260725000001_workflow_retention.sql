@@ -0,0 +1,6 @@
+-- per-workflow run-history retention overriding the archiver's global workflow-run retention.
+CREATE TABLE IF NOT EXISTS workflow_retention (
+    workflow_id BINARY(16) PRIMARY KEY,
+    retention_days BIGINT NOT NULL,
+    updated_at BIGINT NOT NULL
+);
@@ -0,0 +1,12 @@
+-- every saved version of a workflow, numbered per workflow, so an edit can be reviewed against its
+-- predecessor and reverted. `snapshot` is the full workflow record as json.
+CREATE TABLE IF NOT EXISTS workflow_revisions (
+    id BINARY(16) PRIMARY KEY,
+    workflow_id BINARY(16) NOT NULL,
+    revision BIGINT NOT NULL,
+    snapshot LONGTEXT NOT NULL,
+    changed_by BINARY(16) NULL,
+    created_at BIGINT NOT NULL
+);
+CREATE UNIQUE INDEX idx_workflow_revisions_workflow
+    ON workflow_revisions(workflow_id, revision);
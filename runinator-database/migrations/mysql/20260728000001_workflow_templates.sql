@@ -0,0 +1,13 @@
+-- server-managed starting points for new workflows. `workflow` is the pre-filled workflow record
+-- as json and `labels` a json array of strings.
+CREATE TABLE IF NOT EXISTS workflow_templates (
+    id BINARY(16) PRIMARY KEY,
+    org_id BINARY(16) NULL,
+    name VARCHAR(255) NOT NULL,
+    description TEXT NULL,
+    cron VARCHAR(255) NULL,
+    labels TEXT NOT NULL,
+    workflow LONGTEXT NOT NULL,
+    created_at BIGINT NOT NULL,
+    updated_at BIGINT NOT NULL
+);
@@ -0,0 +1,12 @@
+-- small named values a run records for other workflows to reference as `{{output:workflow.key}}`.
+-- `value` is a json string or number.
+CREATE TABLE IF NOT EXISTS workflow_run_outputs (
+    workflow_run_id BINARY(16) NOT NULL,
+    workflow_id BINARY(16) NOT NULL,
+    name VARCHAR(128) NOT NULL,
+    value TEXT NOT NULL,
+    created_at BIGINT NOT NULL,
+    PRIMARY KEY (workflow_run_id, name)
+);
+CREATE INDEX idx_workflow_run_outputs_workflow
+    ON workflow_run_outputs(workflow_id, name);
@@ -0,0 +1,21 @@
+-- second-approver protection: a row in workflow_protection marks the workflow protected, and
+-- mutations to it wait in workflow_pending_changes until a different principal approves them.
+-- `payload` is the workflow to save for an update, as json.
+CREATE TABLE IF NOT EXISTS workflow_protection (
+    workflow_id BINARY(16) PRIMARY KEY,
+    protected_by BINARY(16) NULL,
+    created_at BIGINT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS workflow_pending_changes (
+    id BINARY(16) PRIMARY KEY,
+    workflow_id BINARY(16) NOT NULL,
+    kind VARCHAR(32) NOT NULL,
+    payload LONGTEXT NOT NULL,
+    requested_by BINARY(16) NULL,
+    status VARCHAR(32) NOT NULL,
+    reviewed_by BINARY(16) NULL,
+    created_at BIGINT NOT NULL,
+    reviewed_at BIGINT NULL
+);
+CREATE INDEX idx_workflow_pending_changes_workflow
+    ON workflow_pending_changes(workflow_id, status);
@@ -0,0 +1,15 @@
+-- one-off jobs: a single action dispatched without a stored workflow. `action` is the dispatched
+-- action as json; the executor columns hold the worker lease, as on workflow_node_runs.
+CREATE TABLE IF NOT EXISTS jobs (
+    id BINARY(16) PRIMARY KEY,
+    action LONGTEXT NOT NULL,
+    status VARCHAR(32) NOT NULL,
+    output_json LONGTEXT NULL,
+    message LONGTEXT NULL,
+    requested_by BINARY(16) NULL,
+    current_executor_replica_id BINARY(16) NULL,
+    executor_claimed_at BIGINT NULL,
+    created_at BIGINT NOT NULL,
+    started_at BIGINT NULL,
+    finished_at BIGINT NULL
+);
@@ -0,0 +1,10 @@
+-- cluster-wide pause: one row (scope `cluster`) holding whether action dispatch and/or worker
+-- execution are held fleet-wide. a missing row means nothing is paused.
+CREATE TABLE IF NOT EXISTS cluster_pause (
+    scope VARCHAR(64) PRIMARY KEY,
+    dispatch_paused TINYINT(1) NOT NULL,
+    execution_paused TINYINT(1) NOT NULL,
+    reason LONGTEXT NULL,
+    updated_by BINARY(16) NULL,
+    updated_at BIGINT NOT NULL
+);
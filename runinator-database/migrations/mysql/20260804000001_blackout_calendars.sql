@@ -0,0 +1,9 @@
+-- named holiday calendars a cron trigger's `blackouts` list can reference. `dates` is a json array of
+-- `YYYY-MM-DD` days, each blacked out from local midnight to midnight in `timezone` (utc when null).
+CREATE TABLE IF NOT EXISTS blackout_calendars (
+    name VARCHAR(255) PRIMARY KEY,
+    description LONGTEXT NULL,
+    timezone VARCHAR(64) NULL,
+    dates LONGTEXT NOT NULL,
+    updated_at BIGINT NOT NULL
+);
@@ -0,0 +1,8 @@
+-- leader leases between scheduler instances. only the `holder` of a lease does the work it guards
+-- (the trigger loop holds `scheduler`); another instance takes the row over once `expires_at` passes.
+CREATE TABLE IF NOT EXISTS scheduler_leases (
+    name VARCHAR(255) PRIMARY KEY,
+    holder VARCHAR(255) NOT NULL,
+    acquired_at BIGINT NOT NULL,
+    expires_at BIGINT NOT NULL
+);
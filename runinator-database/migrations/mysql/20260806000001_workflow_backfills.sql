@@ -0,0 +1,19 @@
+-- backfills: past slots of a cron trigger replayed on request. `slots` is the json list of slot
+-- times worked out when the backfill was requested and `fired` how many of them have started; the
+-- trigger loop starts the rest while fewer than `concurrency` backfill runs are open.
+CREATE TABLE IF NOT EXISTS workflow_backfills (
+    id BINARY(16) PRIMARY KEY,
+    workflow_id BINARY(16) NOT NULL,
+    trigger_id BINARY(16) NOT NULL,
+    range_start BIGINT NOT NULL,
+    range_end BIGINT NOT NULL,
+    concurrency BIGINT NOT NULL,
+    slots LONGTEXT NOT NULL,
+    fired BIGINT NOT NULL DEFAULT 0,
+    status VARCHAR(32) NOT NULL,
+    requested_by BINARY(16) NULL,
+    created_at BIGINT NOT NULL,
+    updated_at BIGINT NOT NULL
+);
+CREATE INDEX idx_workflow_backfills_status
+    ON workflow_backfills(status, workflow_id);
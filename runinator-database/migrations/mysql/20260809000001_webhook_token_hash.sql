@@ -0,0 +1,8 @@
+-- the sha256 of a webhook trigger's token, copied out of its configuration on every save so the
+-- public webhook route finds its trigger with one indexed lookup instead of hashing against every
+-- enabled webhook trigger. null for every other kind.
+ALTER TABLE workflow_triggers ADD COLUMN webhook_token_hash VARCHAR(64) NULL;
+UPDATE workflow_triggers
+   SET webhook_token_hash = JSON_UNQUOTE(JSON_EXTRACT(configuration, '$.token_hash'))
+ WHERE kind = 'webhook';
+CREATE INDEX idx_workflow_triggers_webhook_token ON workflow_triggers(webhook_token_hash);
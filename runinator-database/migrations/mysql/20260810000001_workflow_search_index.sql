@@ -0,0 +1,5 @@
+-- full-text index behind workflow search: one over each workflow's name, namespace, and
+-- definition, and one over each trigger's configuration. a search term is matched as a phrase
+-- against them in boolean mode, ignoring case under the default collation.
+ALTER TABLE workflows ADD FULLTEXT INDEX idx_workflows_search (name, namespace, definition);
+ALTER TABLE workflow_triggers ADD FULLTEXT INDEX idx_workflow_triggers_search (configuration);
@@ -0,0 +1,3 @@
+-- per-org cap on concurrently active workflow runs (0 = unbounded), enforced when the reducer
+-- claims a queued run so one tenant's burst cannot monopolize the workers.
+ALTER TABLE org_quotas ADD COLUMN IF NOT EXISTS max_concurrent_runs BIGINT NOT NULL DEFAULT 0;
@@ -0,0 +1,12 @@
+-- every saved version of a workflow, numbered per workflow, so an edit can be reviewed against its
+-- predecessor and reverted. `snapshot` is the full workflow record as json.
+CREATE TABLE IF NOT EXISTS workflow_revisions (
+    id UUID PRIMARY KEY,
+    workflow_id UUID NOT NULL,
+    revision BIGINT NOT NULL,
+    snapshot TEXT NOT NULL,
+    changed_by UUID NULL,
+    created_at BIGINT NOT NULL
+);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_workflow_revisions_workflow
+    ON workflow_revisions(workflow_id, revision);
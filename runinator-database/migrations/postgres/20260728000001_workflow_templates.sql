@@ -0,0 +1,13 @@
+-- server-managed starting points for new workflows. `workflow` is the pre-filled workflow record
+-- as json and `labels` a json array of strings.
+CREATE TABLE IF NOT EXISTS workflow_templates (
+    id UUID PRIMARY KEY,
+    org_id UUID NULL,
+    name TEXT NOT NULL,
+    description TEXT NULL,
+    cron TEXT NULL,
+    labels TEXT NOT NULL,
+    workflow TEXT NOT NULL,
+    created_at BIGINT NOT NULL,
+    updated_at BIGINT NOT NULL
+);
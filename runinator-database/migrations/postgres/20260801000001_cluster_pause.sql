@@ -0,0 +1,10 @@
+-- cluster-wide pause: one row (scope `cluster`) holding whether action dispatch and/or worker
+-- execution are held fleet-wide. a missing row means nothing is paused.
+CREATE TABLE IF NOT EXISTS cluster_pause (
+    scope TEXT PRIMARY KEY,
+    dispatch_paused BOOLEAN NOT NULL,
+    execution_paused BOOLEAN NOT NULL,
+    reason TEXT NULL,
+    updated_by UUID NULL,
+    updated_at BIGINT NOT NULL
+);
@@ -0,0 +1,16 @@
+-- job retries: the attempt running now and the policy that re-dispatches a failed one. each finished
+-- attempt is kept in job_attempts so a retried job's earlier failures stay visible.
+ALTER TABLE jobs ADD COLUMN attempt BIGINT NOT NULL DEFAULT 1;
+ALTER TABLE jobs ADD COLUMN max_retries BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE jobs ADD COLUMN retry_backoff_seconds BIGINT NOT NULL DEFAULT 0;
+
+CREATE TABLE IF NOT EXISTS job_attempts (
+    job_id UUID NOT NULL,
+    attempt BIGINT NOT NULL,
+    status TEXT NOT NULL,
+    output_json TEXT NULL,
+    message TEXT NULL,
+    started_at BIGINT NULL,
+    finished_at BIGINT NOT NULL,
+    PRIMARY KEY (job_id, attempt)
+);
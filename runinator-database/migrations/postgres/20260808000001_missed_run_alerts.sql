@@ -0,0 +1,8 @@
+-- missed-run alerts: one row per deadline a workflow with `expected_max_interval_seconds` missed,
+-- so each missed deadline alerts once however many instances run the checker.
+CREATE TABLE IF NOT EXISTS workflow_missed_run_alerts (
+    workflow_id UUID NOT NULL,
+    expected_by BIGINT NOT NULL,
+    created_at BIGINT NOT NULL,
+    PRIMARY KEY (workflow_id, expected_by)
+);
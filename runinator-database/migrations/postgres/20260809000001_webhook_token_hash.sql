@@ -0,0 +1,8 @@
+-- the sha256 of a webhook trigger's token, copied out of its configuration on every save so the
+-- public webhook route finds its trigger with one indexed lookup instead of hashing against every
+-- enabled webhook trigger. null for every other kind.
+ALTER TABLE workflow_triggers ADD COLUMN IF NOT EXISTS webhook_token_hash TEXT NULL;
+UPDATE workflow_triggers
+   SET webhook_token_hash = configuration::jsonb ->> 'token_hash'
+ WHERE kind = 'webhook';
+CREATE INDEX IF NOT EXISTS idx_workflow_triggers_webhook_token ON workflow_triggers(webhook_token_hash);
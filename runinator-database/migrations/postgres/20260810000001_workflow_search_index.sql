@@ -0,0 +1,13 @@
+-- full-text index behind workflow search: a tsvector over each workflow's name, namespace, and
+-- definition, and one over each trigger's configuration, both gin-indexed. the `simple`
+-- configuration neither stems nor drops stop words, so a term matches the words as written,
+-- ignoring case.
+ALTER TABLE workflows ADD COLUMN IF NOT EXISTS search_vector tsvector
+    GENERATED ALWAYS AS (
+        to_tsvector('simple', name || ' ' || COALESCE(namespace, '') || ' ' || definition)
+    ) STORED;
+CREATE INDEX IF NOT EXISTS idx_workflows_search ON workflows USING GIN (search_vector);
+ALTER TABLE workflow_triggers ADD COLUMN IF NOT EXISTS search_vector tsvector
+    GENERATED ALWAYS AS (to_tsvector('simple', configuration)) STORED;
+CREATE INDEX IF NOT EXISTS idx_workflow_triggers_search
+    ON workflow_triggers USING GIN (search_vector);
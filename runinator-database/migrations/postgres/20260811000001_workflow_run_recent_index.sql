@@ -0,0 +1,3 @@
+-- newest runs per workflow, so the overview and graphql run lists read each workflow's top rows
+-- from the index instead of sorting its whole history.
+CREATE INDEX IF NOT EXISTS idx_workflow_runs_workflow_created ON workflow_runs(workflow_id, created_at, id);
@@ -0,0 +1,3 @@
+-- how long the node's command sat in the broker before a worker started it, in milliseconds.
+-- null for runs that predate the column or never reached a worker.
+ALTER TABLE workflow_node_runs ADD COLUMN queue_wait_ms INTEGER NULL;
@@ -0,0 +1,3 @@
+-- the worker that reported the run's latest status, so a failed run can be traced to where it ran.
+-- null for runs that predate the column or whose reporter did not identify itself.
+ALTER TABLE runs ADD COLUMN worker_id TEXT NULL;
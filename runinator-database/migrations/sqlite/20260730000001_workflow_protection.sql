@@ -0,0 +1,21 @@
+-- second-approver protection: a row in workflow_protection marks the workflow protected, and
+-- mutations to it wait in workflow_pending_changes until a different principal approves them.
+-- `payload` is the workflow to save for an update, as json.
+CREATE TABLE IF NOT EXISTS workflow_protection (
+    workflow_id BLOB PRIMARY KEY,
+    protected_by BLOB NULL,
+    created_at INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS workflow_pending_changes (
+    id BLOB PRIMARY KEY,
+    workflow_id BLOB NOT NULL,
+    kind TEXT NOT NULL,
+    payload TEXT NOT NULL,
+    requested_by BLOB NULL,
+    status TEXT NOT NULL,
+    reviewed_by BLOB NULL,
+    created_at INTEGER NOT NULL,
+    reviewed_at INTEGER NULL
+);
+CREATE INDEX IF NOT EXISTS idx_workflow_pending_changes_workflow
+    ON workflow_pending_changes(workflow_id, status);
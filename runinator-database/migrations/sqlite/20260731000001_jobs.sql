@@ -0,0 +1,15 @@
+-- one-off jobs: a single action dispatched without a stored workflow. `action` is the dispatched
+-- action as json; the executor columns hold the worker lease, as on workflow_node_runs.
+CREATE TABLE IF NOT EXISTS jobs (
+    id BLOB PRIMARY KEY,
+    action TEXT NOT NULL,
+    status TEXT NOT NULL,
+    output_json TEXT NULL,
+    message TEXT NULL,
+    requested_by BLOB NULL,
+    current_executor_replica_id BLOB NULL,
+    executor_claimed_at INTEGER NULL,
+    created_at INTEGER NOT NULL,
+    started_at INTEGER NULL,
+    finished_at INTEGER NULL
+);
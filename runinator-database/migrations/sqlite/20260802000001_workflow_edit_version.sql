@@ -0,0 +1,3 @@
+-- bumped on every write to the workflow row and served as its ETag, so an edit made against a
+-- stale copy (a PATCH with an outdated If-Match) is rejected instead of overwriting a newer save.
+ALTER TABLE workflows ADD COLUMN edit_version INTEGER NOT NULL DEFAULT 1;
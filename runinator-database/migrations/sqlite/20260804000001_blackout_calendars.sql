@@ -0,0 +1,9 @@
+-- named holiday calendars a cron trigger's `blackouts` list can reference. `dates` is a json array of
+-- `YYYY-MM-DD` days, each blacked out from local midnight to midnight in `timezone` (utc when null).
+CREATE TABLE IF NOT EXISTS blackout_calendars (
+    name TEXT PRIMARY KEY,
+    description TEXT NULL,
+    timezone TEXT NULL,
+    dates TEXT NOT NULL,
+    updated_at INTEGER NOT NULL
+);
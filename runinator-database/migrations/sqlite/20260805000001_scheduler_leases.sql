@@ -0,0 +1,8 @@
+-- leader leases between scheduler instances. only the `holder` of a lease does the work it guards
+-- (the trigger loop holds `scheduler`); another instance takes the row over once `expires_at` passes.
+CREATE TABLE IF NOT EXISTS scheduler_leases (
+    name TEXT PRIMARY KEY,
+    holder TEXT NOT NULL,
+    acquired_at INTEGER NOT NULL,
+    expires_at INTEGER NOT NULL
+);
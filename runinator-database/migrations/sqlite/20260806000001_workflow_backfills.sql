@@ -0,0 +1,19 @@
+-- backfills: past slots of a cron trigger replayed on request. `slots` is the json list of slot
+-- times worked out when the backfill was requested and `fired` how many of them have started; the
+-- trigger loop starts the rest while fewer than `concurrency` backfill runs are open.
+CREATE TABLE IF NOT EXISTS workflow_backfills (
+    id BLOB PRIMARY KEY,
+    workflow_id BLOB NOT NULL,
+    trigger_id BLOB NOT NULL,
+    range_start INTEGER NOT NULL,
+    range_end INTEGER NOT NULL,
+    concurrency INTEGER NOT NULL,
+    slots TEXT NOT NULL,
+    fired INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL,
+    requested_by BLOB NULL,
+    created_at INTEGER NOT NULL,
+    updated_at INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_workflow_backfills_status
+    ON workflow_backfills(status, workflow_id);
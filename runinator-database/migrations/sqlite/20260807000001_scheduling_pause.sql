@@ -0,0 +1,2 @@
+-- cluster pause for scheduling: while set, no trigger firings or backfilled slots are claimed.
+ALTER TABLE cluster_pause ADD COLUMN scheduling_paused BOOL NOT NULL DEFAULT FALSE;
@@ -0,0 +1,58 @@
+-- full-text index behind workflow search. one fts5 row per workflow holds its name, namespace,
+-- definition, and every trigger configuration; the trigram tokenizer lets a term match anywhere in
+-- that text, ignoring case, without scanning every definition. the sql triggers below keep the row
+-- current on every workflow and trigger write.
+CREATE VIRTUAL TABLE IF NOT EXISTS workflow_search USING fts5(
+    workflow_id UNINDEXED,
+    document,
+    tokenize = 'trigram'
+);
+CREATE VIEW IF NOT EXISTS workflow_search_documents AS
+SELECT w.id AS workflow_id,
+       w.name || ' ' || COALESCE(w.namespace, '') || ' ' || w.definition || ' ' ||
+       COALESCE((SELECT group_concat(t.configuration, ' ')
+                   FROM workflow_triggers t
+                  WHERE t.workflow_id = w.id), '') AS document
+  FROM workflows w;
+INSERT INTO workflow_search (workflow_id, document)
+SELECT workflow_id, document FROM workflow_search_documents;
+
+CREATE TRIGGER IF NOT EXISTS workflow_search_after_insert AFTER INSERT ON workflows
+BEGIN
+    INSERT INTO workflow_search (workflow_id, document)
+    SELECT workflow_id, document FROM workflow_search_documents WHERE workflow_id = NEW.id;
+END;
+CREATE TRIGGER IF NOT EXISTS workflow_search_after_update
+AFTER UPDATE OF id, name, namespace, definition ON workflows
+BEGIN
+    DELETE FROM workflow_search WHERE workflow_id = OLD.id;
+    INSERT INTO workflow_search (workflow_id, document)
+    SELECT workflow_id, document FROM workflow_search_documents WHERE workflow_id = NEW.id;
+END;
+CREATE TRIGGER IF NOT EXISTS workflow_search_after_delete AFTER DELETE ON workflows
+BEGIN
+    DELETE FROM workflow_search WHERE workflow_id = OLD.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS workflow_search_after_trigger_insert AFTER INSERT ON workflow_triggers
+BEGIN
+    DELETE FROM workflow_search WHERE workflow_id = NEW.workflow_id;
+    INSERT INTO workflow_search (workflow_id, document)
+    SELECT workflow_id, document FROM workflow_search_documents
+     WHERE workflow_id = NEW.workflow_id;
+END;
+CREATE TRIGGER IF NOT EXISTS workflow_search_after_trigger_update
+AFTER UPDATE OF workflow_id, configuration ON workflow_triggers
+BEGIN
+    DELETE FROM workflow_search WHERE workflow_id IN (OLD.workflow_id, NEW.workflow_id);
+    INSERT INTO workflow_search (workflow_id, document)
+    SELECT workflow_id, document FROM workflow_search_documents
+     WHERE workflow_id IN (OLD.workflow_id, NEW.workflow_id);
+END;
+CREATE TRIGGER IF NOT EXISTS workflow_search_after_trigger_delete AFTER DELETE ON workflow_triggers
+BEGIN
+    DELETE FROM workflow_search WHERE workflow_id = OLD.workflow_id;
+    INSERT INTO workflow_search (workflow_id, document)
+    SELECT workflow_id, document FROM workflow_search_documents
+     WHERE workflow_id = OLD.workflow_id;
+END;
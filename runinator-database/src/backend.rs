@@ -1,7 +1,7 @@
 //! the per-backend seam.
 //!
-//! a backend exposes only what genuinely differs between databases: the concrete sqlx pool and the
-//! sql dialect. every `DatabaseImpl` method body is written once as a blanket impl over `SqlBackend`
+//! a backend exposes only what genuinely differs between databases: the concrete sqlx pool (plus an
+//! optional read replica) and the sql dialect. every `DatabaseImpl` method body is written once as a blanket impl over `SqlBackend`
 //! in `crate::operations`, so adding a database means implementing this trait, not re-typing queries.
 
 use std::future::Future;
@@ -11,7 +11,10 @@ use sqlx::{
     Database, Pool, mysql::MySqlQueryResult, postgres::PgQueryResult, sqlite::SqliteQueryResult,
 };
 
-use crate::queries::{self, SqlDialect};
+use crate::{
+    queries::{self, SqlDialect},
+    replica::ReadReplica,
+};
 
 /// portable access to a statement's affected-row count.
 ///
@@ -47,6 +50,20 @@ pub trait SqlBackend: Send + Sync + 'static {
     /// the pool generic operations execute against.
     fn pool(&self) -> &Pool<Self::Db>;
 
+    /// the read replica listing and stats queries may use, if one is configured.
+    fn read_replica(&self) -> Option<&ReadReplica<Self::Db>> {
+        None
+    }
+
+    /// the pool listing and stats queries execute against: the replica while it is reachable, else
+    /// the primary.
+    fn read_pool(&self) -> &Pool<Self::Db> {
+        self.read_replica()
+            .filter(|replica| replica.is_healthy())
+            .map(ReadReplica::pool)
+            .unwrap_or_else(|| self.pool())
+    }
+
     /// the sql dialect used to render queries.
     fn dialect(&self) -> SqlDialect;
 
@@ -0,0 +1,137 @@
+//! blackout windows that recur daily at local wall-clock times ("every night 1–3am"). a window is
+//! resolved to utc instants per calendar day in its zone, so it tracks the wall clock across dst
+//! shifts instead of drifting by an hour the way a fixed utc pair does. local times that do not
+//! exist (the spring-forward gap) or occur twice (the fall-back fold) are resolved explicitly.
+//!
+//! a cron trigger's `blackouts` list builds on this: weekly windows open only on their listed
+//! weekdays, and a holiday calendar blacks out each of its dates from local midnight to midnight.
+
+use chrono::{
+    DateTime, Datelike, Duration, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, Offset,
+    TimeZone, Utc,
+};
+use chrono_tz::Tz;
+use runinator_models::blackouts::{BlackoutCalendar, TRIGGER_BLACKOUTS_KEY, trigger_blackouts};
+use runinator_models::value::Value;
+use runinator_models::workflows::trigger_timezone;
+
+/// a daily window from `start` to `end` local time, both inclusive. an `end` at or before `start`
+/// wraps past midnight into the next day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LocalBlackout {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl LocalBlackout {
+    pub fn new(start: NaiveTime, end: NaiveTime) -> Self {
+        Self { start, end }
+    }
+
+    /// whether `now` falls inside the window as observed in `tz`.
+    pub fn contains(&self, tz: Tz, now: DateTime<Utc>) -> bool {
+        self.open_until(tz, now, |_| true).is_some()
+    }
+
+    /// when the window holding `now` closes, counting only windows that open on a local day
+    /// `opens_on` accepts.
+    pub fn open_until(
+        &self,
+        tz: Tz,
+        now: DateTime<Utc>,
+        opens_on: impl Fn(NaiveDate) -> bool,
+    ) -> Option<DateTime<Utc>> {
+        let today = now.with_timezone(&tz).date_naive();
+        // a window wrapping midnight that opened yesterday may still be open today.
+        [today.pred_opt(), Some(today)]
+            .into_iter()
+            .flatten()
+            .filter(|day| opens_on(*day))
+            .map(|day| self.window_on(tz, day))
+            .filter(|(start, end)| now >= *start && now <= *end)
+            .map(|(_, end)| end)
+            .max()
+    }
+
+    /// the utc instants bounding the window that opens on local `day`. a nonexistent bound moves
+    /// forward by the length of the gap; an ambiguous start takes its first occurrence and an
+    /// ambiguous end its second, so the window covers both passes through a repeated hour.
+    pub fn window_on(&self, tz: Tz, day: NaiveDate) -> (DateTime<Utc>, DateTime<Utc>) {
+        let end_day = if self.end <= self.start {
+            day.succ_opt().unwrap_or(day)
+        } else {
+            day
+        };
+        (
+            resolve_local(tz, day.and_time(self.start), true),
+            resolve_local(tz, end_day.and_time(self.end), false),
+        )
+    }
+}
+
+/// whether a cron trigger's configuration names any holiday calendar, so the trigger loop only
+/// loads calendars when a due trigger needs them.
+pub fn references_calendar(configuration: &Value) -> bool {
+    configuration
+        .get(TRIGGER_BLACKOUTS_KEY)
+        .and_then(Value::as_array)
+        .is_some_and(|entries| entries.iter().any(|entry| entry.get("calendar").is_some()))
+}
+
+/// when the latest of a trigger's `blackouts` windows holding `now` closes, or `None` when `now` is
+/// outside all of them. windows are read in their own zone, else the trigger's, else utc. a list
+/// that no longer parses, an unknown zone, or a calendar missing from `calendars` blacks out
+/// nothing; saving a trigger validates the list, so this only covers rows written around the api.
+pub fn blackout_until(
+    configuration: &Value,
+    calendars: &[BlackoutCalendar],
+    now: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    let blackouts = trigger_blackouts(configuration).ok()?;
+    let zone = |name: Option<&str>| name.map_or(Some(Tz::UTC), |name| name.parse::<Tz>().ok());
+    let default_zone = trigger_timezone(configuration);
+    let whole_day = LocalBlackout::new(NaiveTime::MIN, NaiveTime::MIN);
+    blackouts
+        .iter()
+        .filter_map(
+            |blackout| match (&blackout.calendar, blackout.start, blackout.end) {
+                (Some(name), ..) => {
+                    let calendar = calendars.iter().find(|calendar| calendar.name == *name)?;
+                    let tz = zone(calendar.timezone.as_deref())?;
+                    whole_day.open_until(tz, now, |day| calendar.dates.contains(&day))
+                }
+                (None, Some(start), Some(end)) => {
+                    let tz = zone(blackout.timezone.as_deref().or(default_zone))?;
+                    LocalBlackout::new(start, end).open_until(tz, now, |day| {
+                        blackout.days.is_empty() || blackout.days.contains(&day.weekday())
+                    })
+                }
+                _ => None,
+            },
+        )
+        .max()
+}
+
+// map a wall-clock time in `tz` to an instant. `earliest` picks between the two readings of a
+// time repeated by a fall-back fold.
+fn resolve_local(tz: Tz, local: NaiveDateTime, earliest: bool) -> DateTime<Utc> {
+    match tz.from_local_datetime(&local) {
+        LocalResult::Single(at) => at.with_timezone(&Utc),
+        LocalResult::Ambiguous(first, second) => {
+            let at = if earliest { first } else { second };
+            at.with_timezone(&Utc)
+        }
+        // skipped by a spring-forward gap: read it with the offset in force before the jump, which
+        // lands as far past the gap as the time was into it (02:30 becomes 03:30).
+        LocalResult::None => {
+            let before = tz
+                .offset_from_utc_datetime(&(local - Duration::days(1)))
+                .fix();
+            (local - Duration::seconds(before.local_minus_utc() as i64)).and_utc()
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "blackout_tests.rs"]
+mod blackout_tests;
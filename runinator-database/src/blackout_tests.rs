@@ -0,0 +1,141 @@
+use super::*;
+use chrono_tz::America::New_York;
+
+fn at(value: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(value)
+        .unwrap()
+        .with_timezone(&Utc)
+}
+
+fn window(start: (u32, u32), end: (u32, u32)) -> LocalBlackout {
+    LocalBlackout::new(
+        NaiveTime::from_hms_opt(start.0, start.1, 0).unwrap(),
+        NaiveTime::from_hms_opt(end.0, end.1, 0).unwrap(),
+    )
+}
+
+#[test]
+fn local_window_follows_the_wall_clock_across_dst() {
+    let nightly = window((1, 0), (3, 0));
+    // 02:00 local is 07:00z in winter and 06:00z in summer.
+    assert!(nightly.contains(New_York, at("2026-01-15T07:00:00Z")));
+    assert!(nightly.contains(New_York, at("2026-07-15T06:00:00Z")));
+    // 03:30 local in winter, 03:30 local in summer: both outside.
+    assert!(!nightly.contains(New_York, at("2026-01-15T08:30:00Z")));
+    assert!(!nightly.contains(New_York, at("2026-07-15T07:30:00Z")));
+    // 00:30 local in summer sits where a fixed utc pair taken in winter would have started.
+    assert!(!nightly.contains(New_York, at("2026-07-15T04:30:00Z")));
+}
+
+#[test]
+fn nonexistent_local_start_moves_past_the_gap() {
+    // 2026-03-08 02:00 est jumps to 03:00 edt, so 02:30 never happens that night.
+    let day = NaiveDate::from_ymd_opt(2026, 3, 8).unwrap();
+    let (start, end) = window((2, 30), (4, 0)).window_on(New_York, day);
+    assert_eq!(start, at("2026-03-08T07:30:00Z"));
+    assert_eq!(end, at("2026-03-08T08:00:00Z"));
+    let blackout = window((2, 30), (4, 0));
+    assert!(!blackout.contains(New_York, at("2026-03-08T07:15:00Z")));
+    assert!(blackout.contains(New_York, at("2026-03-08T07:45:00Z")));
+}
+
+#[test]
+fn ambiguous_local_times_cover_both_passes_through_the_fold() {
+    // 2026-11-01 01:00–02:00 local happens twice: first in edt, then again in est.
+    let blackout = window((0, 30), (1, 30));
+    let day = NaiveDate::from_ymd_opt(2026, 11, 1).unwrap();
+    let (start, end) = blackout.window_on(New_York, day);
+    assert_eq!(start, at("2026-11-01T04:30:00Z"));
+    assert_eq!(end, at("2026-11-01T06:30:00Z"));
+    // 01:15 edt (first pass) and 01:15 est (second pass) are both blacked out.
+    assert!(blackout.contains(New_York, at("2026-11-01T05:15:00Z")));
+    assert!(blackout.contains(New_York, at("2026-11-01T06:15:00Z")));
+    assert!(!blackout.contains(New_York, at("2026-11-01T06:45:00Z")));
+
+    // an ambiguous start opens on its first occurrence.
+    let (start, _) = window((1, 30), (3, 0)).window_on(New_York, day);
+    assert_eq!(start, at("2026-11-01T05:30:00Z"));
+}
+
+#[test]
+fn window_wrapping_midnight_spans_two_local_days() {
+    let late = window((23, 0), (1, 0));
+    assert!(!late.contains(New_York, at("2026-01-15T03:30:00Z")));
+    assert!(late.contains(New_York, at("2026-01-15T04:30:00Z")));
+    assert!(late.contains(New_York, at("2026-01-15T05:30:00Z")));
+    assert!(!late.contains(New_York, at("2026-01-15T06:30:00Z")));
+}
+
+#[test]
+fn weekly_window_opens_only_on_its_days() {
+    // every sunday 02:00–04:00 new york time.
+    let configuration = runinator_models::json!({
+        "cron": "*/15 * * * *",
+        "timezone": "America/New_York",
+        "blackouts": [{ "days": ["sun"], "start": "02:00", "end": "04:00" }],
+    });
+    // 2026-01-18 is a sunday; 03:00 est is 08:00z.
+    assert_eq!(
+        blackout_until(&configuration, &[], at("2026-01-18T08:00:00Z")),
+        Some(at("2026-01-18T09:00:00Z"))
+    );
+    // the same wall-clock time on monday is clear.
+    assert_eq!(
+        blackout_until(&configuration, &[], at("2026-01-19T08:00:00Z")),
+        None
+    );
+}
+
+#[test]
+fn calendar_dates_black_out_whole_local_days() {
+    let calendar = BlackoutCalendar {
+        name: "holidays".into(),
+        timezone: Some("America/New_York".into()),
+        dates: vec![
+            NaiveDate::from_ymd_opt(2026, 12, 25).unwrap(),
+            NaiveDate::from_ymd_opt(2026, 12, 26).unwrap(),
+        ],
+        ..BlackoutCalendar::default()
+    };
+    let configuration = runinator_models::json!({
+        "cron": "0 * * * *",
+        "blackouts": [{ "calendar": "holidays" }],
+    });
+    let calendars = [calendar];
+    // back-to-back dates run until midnight after the second one.
+    assert_eq!(
+        blackout_until(&configuration, &calendars, at("2026-12-25T12:00:00Z")),
+        Some(at("2026-12-26T05:00:00Z"))
+    );
+    assert_eq!(
+        blackout_until(&configuration, &calendars, at("2026-12-26T05:00:00Z")),
+        Some(at("2026-12-27T05:00:00Z"))
+    );
+    // 23:00 est on christmas eve is still 04:00z on the 25th, outside the calendar day.
+    assert_eq!(
+        blackout_until(&configuration, &calendars, at("2026-12-25T04:00:00Z")),
+        None
+    );
+    // a calendar that is not stored blacks out nothing.
+    assert_eq!(
+        blackout_until(&configuration, &[], at("2026-12-25T12:00:00Z")),
+        None
+    );
+    assert!(references_calendar(&configuration));
+}
+
+#[test]
+fn overlapping_windows_defer_to_the_latest_end() {
+    let configuration = runinator_models::json!({
+        "cron": "0 * * * *",
+        "blackouts": [
+            { "start": "01:00", "end": "03:00" },
+            { "start": "02:00", "end": "05:00", "timezone": "UTC" },
+        ],
+    });
+    assert_eq!(
+        blackout_until(&configuration, &[], at("2026-01-15T02:30:00Z")),
+        Some(at("2026-01-15T05:00:00Z"))
+    );
+    assert!(!references_calendar(&configuration));
+}
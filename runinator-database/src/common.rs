@@ -1,10 +1,20 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, SubsecRound, Utc};
 use croner::Cron;
 use runinator_comm::{WorkflowResultEvent, WorkflowResultEventKind};
+use runinator_models::blackouts::BlackoutCalendar;
 use runinator_models::errors::SendableError;
 use runinator_models::pipelines::PipelineTrigger;
 use runinator_models::value::Value;
-use runinator_models::workflows::{WorkflowStatus, WorkflowTrigger};
+use runinator_models::workflows::{
+    MisfirePolicy, TRIGGER_SCHEDULE_ERROR_KEY, WebhookTriggerConfig, WorkflowStatus,
+    WorkflowTrigger, WorkflowTriggerKind, trigger_jitter_seconds, trigger_misfire_policy,
+    trigger_offset_seconds, trigger_schedule_error, trigger_timezone,
+};
+use std::collections::VecDeque;
+use uuid::Uuid;
+
+use crate::blackout::blackout_until;
+use crate::schedule::parse_timezone;
 
 pub(crate) fn json_str(value: &Value, key: &str) -> String {
     value
@@ -46,17 +56,185 @@ pub(crate) fn workflow_result_event_type(event: &WorkflowResultEvent) -> &'stati
     }
 }
 
-pub(crate) fn next_execution_for_cron(
+/// the next firing of `cron_schedule` after `now`, its fields read as wall-clock times in the iana
+/// `timezone` (utc when absent). croner walks the zone's own calendar, so `0 0 9 * * *` stays at
+/// 09:00 local across dst: a time skipped by spring-forward fires once the gap ends and a time
+/// repeated by fall-back fires once.
+pub(crate) fn next_execution_in_zone(
     cron_schedule: &str,
+    timezone: Option<&str>,
     now: DateTime<Utc>,
 ) -> Result<DateTime<Utc>, SendableError> {
     let cron = cron_schedule
         .parse::<Cron>()
         .map_err(|err| -> SendableError { Box::new(err) })?;
-    cron.find_next_occurrence(&now, false)
+    let next = match timezone {
+        None => cron.find_next_occurrence(&now, false),
+        Some(timezone) => {
+            let zone = parse_timezone(timezone)?;
+            cron.find_next_occurrence(&now.with_timezone(&zone), false)
+                .map(|next| next.with_timezone(&Utc))
+        }
+    };
+    // croner carries `now`'s sub-second part into the result; cron fires on whole seconds.
+    next.map(|next| next.trunc_subsecs(0))
         .map_err(|err| -> SendableError { Box::new(err) })
 }
 
+/// the next firing of cron trigger `trigger_id` after `now`, from its `configuration` (`cron` plus
+/// optional `timezone`, `offset_seconds`, and `jitter_seconds`). a jittered trigger fires a fixed
+/// offset after each slot; see [`jitter_offset`]. an `offset_seconds` shifts the whole schedule, so
+/// the slot is looked up from `now` less the offset and a firing late in one slot's offset window
+/// still lands on the next slot rather than skipping it.
+pub(crate) fn next_execution_for_configuration(
+    configuration: &Value,
+    trigger_id: Uuid,
+    now: DateTime<Utc>,
+) -> Result<DateTime<Utc>, SendableError> {
+    let offset = Duration::seconds(trigger_offset_seconds(configuration).unwrap_or_default());
+    let slot = next_execution_in_zone(
+        configuration
+            .get("cron")
+            .and_then(Value::as_str)
+            .unwrap_or_default(),
+        trigger_timezone(configuration),
+        now - offset,
+    )?;
+    let jitter_seconds = trigger_jitter_seconds(configuration).unwrap_or_default();
+    Ok(slot + offset + Duration::seconds(jitter_offset(trigger_id, jitter_seconds)))
+}
+
+/// how many seconds after each cron slot a trigger with a `jitter_seconds` window fires, in
+/// `0..=jitter_seconds`. derived from the trigger id alone with a fixed mix rather than std's
+/// hasher, so every scheduler replica and build computes the same `next_execution` (and so the
+/// same firing key) while triggers sharing a cron still spread across the window.
+pub(crate) fn jitter_offset(trigger_id: Uuid, jitter_seconds: i64) -> i64 {
+    if jitter_seconds <= 0 {
+        return 0;
+    }
+    let bits = trigger_id.as_u128();
+    // splitmix64's finalizer over both halves of the id.
+    let mut mixed = (bits >> 64) as u64 ^ (bits as u64).rotate_left(32);
+    mixed = (mixed ^ (mixed >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    mixed = (mixed ^ (mixed >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    mixed ^= mixed >> 31;
+    (mixed % (jitter_seconds as u64 + 1)) as i64
+}
+
+/// the token hash a `webhook` trigger is looked up by, stored in its own indexed column alongside
+/// the configuration it is read from.
+pub(crate) fn webhook_token_hash(trigger: &WorkflowTrigger) -> Option<String> {
+    if trigger.kind != WorkflowTriggerKind::Webhook {
+        return None;
+    }
+    WebhookTriggerConfig::from_configuration(&trigger.configuration)
+        .ok()?
+        .token_hash
+}
+
+/// how far past its slot a `skip` trigger may still fire; later than this and the slot counts as
+/// missed. comfortably wider than the trigger loop's tick.
+pub(crate) const MISFIRE_GRACE_SECS: i64 = 60;
+
+/// most missed slots a `run_all_missed` trigger replays in one pass; older ones are dropped.
+pub(crate) const MAX_MISSED_FIRINGS: usize = 100;
+
+/// the slots a trigger due at `scheduled_at` fires at `now`, oldest first, per its misfire policy.
+/// empty when the policy skips them. an unrecognized policy falls back to `run_once`.
+pub(crate) fn misfire_slots(
+    configuration: &Value,
+    trigger_id: Uuid,
+    scheduled_at: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Result<Vec<DateTime<Utc>>, SendableError> {
+    match trigger_misfire_policy(configuration).unwrap_or_default() {
+        MisfirePolicy::RunOnce => Ok(vec![scheduled_at]),
+        MisfirePolicy::Skip if (now - scheduled_at).num_seconds() > MISFIRE_GRACE_SECS => {
+            Ok(Vec::new())
+        }
+        MisfirePolicy::Skip => Ok(vec![scheduled_at]),
+        MisfirePolicy::RunAllMissed => {
+            latest_missed_slots(configuration, trigger_id, scheduled_at, now)
+        }
+    }
+}
+
+// the latest [`MAX_MISSED_FIRINGS`] slots from `scheduled_at` through `now`. walking forward from
+// `scheduled_at` would visit every slot of a fine-grained cron left behind for weeks, so this looks
+// back from `now` over a window that doubles until it holds the cap or reaches `scheduled_at`.
+fn latest_missed_slots(
+    configuration: &Value,
+    trigger_id: Uuid,
+    scheduled_at: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Result<Vec<DateTime<Utc>>, SendableError> {
+    let mut window = Duration::minutes(1);
+    loop {
+        let from = now
+            .checked_sub_signed(window)
+            .map_or(scheduled_at, |from| from.max(scheduled_at));
+        let mut slots = VecDeque::new();
+        if from == scheduled_at {
+            slots.push_back(scheduled_at);
+        }
+        let mut cursor = from;
+        loop {
+            cursor = next_execution_for_configuration(configuration, trigger_id, cursor)?;
+            if cursor > now {
+                break;
+            }
+            if slots.len() == MAX_MISSED_FIRINGS {
+                slots.pop_front();
+            }
+            slots.push_back(cursor);
+        }
+        if from == scheduled_at || slots.len() == MAX_MISSED_FIRINGS {
+            return Ok(slots.into());
+        }
+        window = window * 2;
+    }
+}
+
+/// how long a trigger whose cron cannot be parsed is parked before the trigger loop re-checks it.
+pub(crate) const SCHEDULE_ERROR_RECHECK_SECS: i64 = 300;
+
+/// record an unparseable cron under the trigger's `schedule_error` metadata. returns `false` when the
+/// same error is already recorded, so the trigger loop only warns and notifies on first sight.
+pub(crate) fn mark_schedule_error(
+    metadata: &mut Value,
+    cron_schedule: &str,
+    message: &str,
+    now: DateTime<Utc>,
+) -> bool {
+    if trigger_schedule_error(metadata) == Some(message)
+        && metadata[TRIGGER_SCHEDULE_ERROR_KEY]["cron"] == cron_schedule
+    {
+        return false;
+    }
+    if metadata.is_null() {
+        *metadata = Value::Object(Default::default());
+    }
+    if let Some(object) = metadata.as_object_mut() {
+        object.insert(
+            TRIGGER_SCHEDULE_ERROR_KEY.to_string(),
+            runinator_models::json!({
+                "message": message,
+                "cron": cron_schedule,
+                "detected_at": now.to_rfc3339(),
+            }),
+        );
+    }
+    true
+}
+
+/// drop a recorded schedule error once the cron parses again; returns whether one was removed.
+pub(crate) fn clear_schedule_error(metadata: &mut Value) -> bool {
+    metadata
+        .as_object_mut()
+        .and_then(|object| object.remove(TRIGGER_SCHEDULE_ERROR_KEY))
+        .is_some()
+}
+
 pub(crate) fn trigger_parameters(trigger: &WorkflowTrigger) -> Value {
     trigger
         .configuration
@@ -76,11 +254,20 @@ pub(crate) fn trigger_state(trigger: &WorkflowTrigger) -> Value {
     })
 }
 
-pub(crate) fn is_trigger_in_blackout(trigger: &WorkflowTrigger, now: DateTime<Utc>) -> bool {
-    if let (Some(start), Some(end)) = (trigger.blackout_start, trigger.blackout_end) {
-        return now >= start && now <= end;
-    }
-    false
+/// when the blackout holding `now` ends, if one does: the one-off `start`/`end` pair or any of the
+/// configuration's recurring `blackouts`, whichever closes last.
+pub(crate) fn trigger_blackout_until(
+    configuration: &Value,
+    start: Option<DateTime<Utc>>,
+    end: Option<DateTime<Utc>>,
+    calendars: &[BlackoutCalendar],
+    now: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    let one_off = match (start, end) {
+        (Some(start), Some(end)) if now >= start && now <= end => Some(end),
+        _ => None,
+    };
+    one_off.max(blackout_until(configuration, calendars, now))
 }
 
 pub(crate) fn pipeline_trigger_parameters(trigger: &PipelineTrigger) -> Value {
@@ -101,16 +288,6 @@ pub(crate) fn pipeline_trigger_state(trigger: &PipelineTrigger) -> Value {
     })
 }
 
-pub(crate) fn is_pipeline_trigger_in_blackout(
-    trigger: &PipelineTrigger,
-    now: DateTime<Utc>,
-) -> bool {
-    if let (Some(start), Some(end)) = (trigger.blackout_start, trigger.blackout_end) {
-        return now >= start && now <= end;
-    }
-    false
-}
-
 pub(crate) fn status_list(statuses: &[WorkflowStatus]) -> String {
     statuses
         .iter()
@@ -32,3 +32,10 @@ impl EngineErrors for DatabaseErrors {
         DICTIONARY
     }
 }
+
+/// whether `err` is a database rejecting a write that would duplicate a unique key.
+pub fn is_unique_violation(err: &(dyn std::error::Error + 'static)) -> bool {
+    err.downcast_ref::<sqlx::Error>()
+        .and_then(sqlx::Error::as_database_error)
+        .is_some_and(|db_err| db_err.is_unique_violation())
+}
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::future::Future;
 
 use chrono::{DateTime, Utc};
@@ -7,8 +8,12 @@ use runinator_comm::{ActionCommand, ActionDispatchRecord, WorkflowResultEvent};
 use runinator_models::value::Value;
 use runinator_models::{
     auth::{ApiKey, ApiKeyRecord, AuthContext, AuthSession, Grant, LocalCredential, Team, User},
+    backfills::{BackfillStatus, WorkflowBackfill},
     billing::{OrgQuota, OrgResourceGroup, UsageSample},
+    blackouts::BlackoutCalendar,
+    cluster::{ClusterPause, SchedulerLease},
     errors::SendableError,
+    jobs::{Job, JobRetry},
     notifications::{NewNotification, Notification},
     orchestration::{
         NewOrchestrationEvent, NodeTransition, NodeTransitionStat, OrchestrationEvent,
@@ -16,21 +21,33 @@ use runinator_models::{
     },
     orgs::{OrgMembership, OrgRole, Organization},
     pipelines::{Pipeline, PipelineRun, PipelineTrigger},
+    protection::{
+        NewPendingWorkflowChange, PendingChangeStatus, PendingWorkflowChange, WorkflowProtection,
+    },
+    provider_stats::NodeRunCounts,
+    queue_wait::QueueWaitSample,
     replicas::{
         ReplicaHeartbeatRequest, ReplicaKind, ReplicaProviderRegistration,
         ReplicaProviderRegistrationRequest, ReplicaRecord, ReplicaRegistrationRequest,
         ReplicaStatus, WorkflowRunProvenance,
     },
+    retention::WorkflowRetention,
+    revisions::WorkflowRevision,
     runs::{NewRunArtifact, NewRunChunk, RunArtifact, RunChunk, RunStatus, RunSummary},
     settings::{SettingKind, SettingRecord},
     telemetry::ReplicaSample,
+    templates::WorkflowTemplate,
+    upcoming::ActiveNodeRun,
     workflows::{
-        NewWorkflowRunArtifact, WorkflowDefinition, WorkflowNodeRun, WorkflowNodeRunArtifact,
-        WorkflowNodeRunChunk, WorkflowRun, WorkflowRunArtifact, WorkflowStatus, WorkflowTrigger,
+        NewWorkflowRunArtifact, ScheduleReconcileReport, WorkflowAction, WorkflowDefinition,
+        WorkflowNodeRun, WorkflowNodeRunArtifact, WorkflowNodeRunChunk, WorkflowRun,
+        WorkflowRunArtifact, WorkflowRunOutput, WorkflowStatus, WorkflowTrigger,
+        WorkflowTriggerKind,
     },
 };
 
 use crate::archive::{ArchiveMark, ArchiveRow, ArchiveTable};
+use crate::pool::PoolStatus;
 
 /// Core persistence operations for Runinator.
 pub trait DatabaseImpl: Send + Sync + 'static {
@@ -40,6 +57,14 @@ pub trait DatabaseImpl: Send + Sync + 'static {
         paths: &[String],
     ) -> impl Future<Output = Result<(), SendableError>> + Send;
 
+    /// Close the pool, waiting for checked-out connections (and any transaction they hold) to be
+    /// returned so pending writes land before the process exits. The wait is unbounded, so a
+    /// caller with a shutdown deadline should apply it with a timeout.
+    fn close(&self) -> impl Future<Output = ()> + Send;
+
+    /// How many pooled connections are open and how many of those are idle right now.
+    fn pool_status(&self) -> PoolStatus;
+
     /// Mark old rows that are eligible for archival. Marking is idempotent.
     fn mark_archive_candidates(
         &self,
@@ -48,6 +73,16 @@ pub trait DatabaseImpl: Send + Sync + 'static {
         limit: i64,
     ) -> impl Future<Output = Result<u64, SendableError>> + Send;
 
+    /// Mark one workflow's archivable runs created before `eligible_before`; used for workflows
+    /// with a retention override, which the table-wide workflow-run marking skips. Marking is
+    /// idempotent.
+    fn mark_workflow_run_archive_candidates(
+        &self,
+        workflow_id: Uuid,
+        eligible_before: DateTime<Utc>,
+        limit: i64,
+    ) -> impl Future<Output = Result<u64, SendableError>> + Send;
+
     /// Claim archive marks for one archiver process under a short lease.
     fn claim_archive_marks(
         &self,
@@ -88,13 +123,14 @@ pub trait DatabaseImpl: Send + Sync + 'static {
         status: RunStatus,
     ) -> impl Future<Output = Result<Vec<RunSummary>, SendableError>> + Send;
 
-    /// Update the status and output of a specific run.
+    /// Update the status and output of a specific run, recording the reporting worker when given.
     fn update_run_status(
         &self,
         run_id: Uuid,
         status: RunStatus,
         output_json: Option<Value>,
         message: Option<String>,
+        worker_id: Option<String>,
     ) -> impl Future<Output = Result<(), SendableError>> + Send;
 
     /// Append a log chunk to an active run.
@@ -142,6 +178,29 @@ pub trait DatabaseImpl: Send + Sync + 'static {
         workflow: &WorkflowDefinition,
     ) -> impl Future<Output = Result<WorkflowDefinition, SendableError>> + Send;
 
+    /// Create or update a batch of workflow definitions in one transaction: either every
+    /// workflow is saved, or none is. Results come back in input order.
+    fn upsert_workflows(
+        &self,
+        workflows: &[WorkflowDefinition],
+    ) -> impl Future<Output = Result<Vec<WorkflowDefinition>, SendableError>> + Send;
+
+    /// Update a workflow only if its stored edit version still equals `edit_version`, the version
+    /// the caller's copy was read at. Returns `None` when the workflow changed since, is gone, or
+    /// `workflow.id` is unset. The check and the write share one transaction.
+    fn upsert_workflow_if_current(
+        &self,
+        workflow: &WorkflowDefinition,
+        edit_version: i64,
+    ) -> impl Future<Output = Result<Option<WorkflowDefinition>, SendableError>> + Send;
+
+    /// Fetch a workflow's edit version, bumped on every write to its row. `None` when no workflow
+    /// has the id.
+    fn fetch_workflow_edit_version(
+        &self,
+        workflow_id: Uuid,
+    ) -> impl Future<Output = Result<Option<i64>, SendableError>> + Send;
+
     /// Insert a workflow as a new row, ignoring any id and never updating an existing one.
     /// Used to duplicate a workflow into a sibling version that shares its name.
     fn insert_workflow(
@@ -154,6 +213,14 @@ pub trait DatabaseImpl: Send + Sync + 'static {
         &self,
     ) -> impl Future<Output = Result<Vec<WorkflowDefinition>, SendableError>> + Send;
 
+    /// Fetch workflow definitions matching every whitespace-separated term of `term` (a
+    /// `"quoted phrase"` is one term), ignoring case, ordered by name. A term matches when the
+    /// name, namespace, definition, or one of the workflow's trigger configurations contains it.
+    fn search_workflows(
+        &self,
+        term: String,
+    ) -> impl Future<Output = Result<Vec<WorkflowDefinition>, SendableError>> + Send;
+
     /// Fetch a workflow definition by its identifier.
     fn fetch_workflow(
         &self,
@@ -174,6 +241,14 @@ pub trait DatabaseImpl: Send + Sync + 'static {
         org_id: Option<Uuid>,
     ) -> impl Future<Output = Result<(), SendableError>> + Send;
 
+    /// Flip a workflow's enabled flag without touching its other fields. Returns `false` when no
+    /// workflow has the id.
+    fn set_workflow_enabled(
+        &self,
+        workflow_id: Uuid,
+        enabled: bool,
+    ) -> impl Future<Output = Result<bool, SendableError>> + Send;
+
     /// Fetch a workflow definition by its unique display name.
     fn fetch_workflow_by_name(
         &self,
@@ -204,6 +279,18 @@ pub trait DatabaseImpl: Send + Sync + 'static {
         trigger_id: Uuid,
     ) -> impl Future<Output = Result<Option<WorkflowTrigger>, SendableError>> + Send;
 
+    /// Fetch every enabled workflow trigger of one kind, across all workflows.
+    fn fetch_enabled_workflow_triggers_by_kind(
+        &self,
+        kind: WorkflowTriggerKind,
+    ) -> impl Future<Output = Result<Vec<WorkflowTrigger>, SendableError>> + Send;
+
+    /// Fetch the enabled webhook trigger whose token hashes to `token_hash`, by its indexed column.
+    fn fetch_enabled_webhook_trigger(
+        &self,
+        token_hash: &str,
+    ) -> impl Future<Output = Result<Option<WorkflowTrigger>, SendableError>> + Send;
+
     /// Delete a workflow trigger.
     fn delete_workflow_trigger(
         &self,
@@ -340,10 +427,12 @@ pub trait DatabaseImpl: Send + Sync + 'static {
         pipeline_run_id: Uuid,
     ) -> impl Future<Output = Result<(), SendableError>> + Send;
 
-    /// Fetch enabled triggers that should fire at or before the provided instant.
+    /// Fetch enabled triggers that should fire at or before the provided instant, in the order a
+    /// claim takes them, at most `limit` of them when given.
     fn fetch_due_workflow_triggers(
         &self,
         now: DateTime<Utc>,
+        limit: Option<i64>,
     ) -> impl Future<Output = Result<Vec<WorkflowTrigger>, SendableError>> + Send;
 
     /// Update the next execution instant for a workflow trigger.
@@ -370,6 +459,24 @@ pub trait DatabaseImpl: Send + Sync + 'static {
         fire_key: String,
     ) -> impl Future<Output = Result<bool, SendableError>> + Send;
 
+    /// Recompute `next_execution` for every enabled cron workflow and pipeline trigger from its
+    /// schedule, reporting each trigger whose stored value differs. writes nothing when `dry_run`.
+    fn reconcile_trigger_schedules(
+        &self,
+        now: DateTime<Utc>,
+        dry_run: bool,
+    ) -> impl Future<Output = Result<ScheduleReconcileReport, SendableError>> + Send;
+
+    /// Replace the cron of each `(trigger id, cron)` workflow trigger and recompute its
+    /// `next_execution`, all in one transaction: an unparseable cron fails the whole batch. Returns
+    /// each trigger's new `next_execution`; writes nothing when `dry_run`.
+    fn set_workflow_trigger_crons(
+        &self,
+        crons: Vec<(Uuid, String)>,
+        now: DateTime<Utc>,
+        dry_run: bool,
+    ) -> impl Future<Output = Result<Vec<(Uuid, DateTime<Utc>)>, SendableError>> + Send;
+
     /// Create a new instance of a workflow.
     fn create_workflow_run(
         &self,
@@ -418,12 +525,47 @@ pub trait DatabaseImpl: Send + Sync + 'static {
         limit: i64,
     ) -> impl Future<Output = Result<Vec<WorkflowRun>, SendableError>> + Send;
 
+    /// Fetch every workflow run created at or after `since`, oldest first.
+    fn fetch_workflow_runs_since(
+        &self,
+        since: DateTime<Utc>,
+    ) -> impl Future<Output = Result<Vec<WorkflowRun>, SendableError>> + Send;
+
     /// Fetch all runs for a specific workflow definition.
     fn fetch_workflow_runs_for_workflow(
         &self,
         workflow_id: Uuid,
     ) -> impl Future<Output = Result<Vec<WorkflowRun>, SendableError>> + Send;
 
+    /// Fetch the newest `per_workflow` runs of each of `workflow_ids` in one query, grouped by
+    /// workflow and newest first within each.
+    fn fetch_recent_workflow_runs_for_workflows(
+        &self,
+        workflow_ids: Vec<Uuid>,
+        per_workflow: i64,
+    ) -> impl Future<Output = Result<Vec<WorkflowRun>, SendableError>> + Send;
+
+    /// When a workflow's most recent successful run finished, if it ever succeeded.
+    fn fetch_last_workflow_success(
+        &self,
+        workflow_id: Uuid,
+    ) -> impl Future<Output = Result<Option<DateTime<Utc>>, SendableError>> + Send;
+
+    /// Record that `workflow_id` missed its `expected_by` deadline for a successful run. Returns
+    /// false when that deadline was already recorded, so its alert is raised once.
+    fn try_record_missed_run_alert(
+        &self,
+        workflow_id: Uuid,
+        expected_by: DateTime<Utc>,
+    ) -> impl Future<Output = Result<bool, SendableError>> + Send;
+
+    /// Count a workflow's open runs created before `run`, the ones a run that may not overlap
+    /// waits behind.
+    fn count_earlier_open_workflow_runs(
+        &self,
+        run: &WorkflowRun,
+    ) -> impl Future<Output = Result<i64, SendableError>> + Send;
+
     /// Fetch workflow runs by display name, optionally restricted to open runs.
     fn fetch_workflow_runs_by_name(
         &self,
@@ -485,6 +627,13 @@ pub trait DatabaseImpl: Send + Sync + 'static {
         workflow_run_id: Uuid,
     ) -> impl Future<Output = Result<Vec<WorkflowNodeRun>, SendableError>> + Send;
 
+    /// Fetch the node execution records of every run in `workflow_run_ids` in one query, each
+    /// run's in the order [`DatabaseImpl::fetch_workflow_node_runs`] returns them.
+    fn fetch_workflow_node_runs_for_runs(
+        &self,
+        workflow_run_ids: Vec<Uuid>,
+    ) -> impl Future<Output = Result<Vec<WorkflowNodeRun>, SendableError>> + Send;
+
     /// Acquire the executor lease for a node run, returning whether it was acquired. The claim only
     /// succeeds when no live executor holds the slot (unclaimed, or the prior claim predates
     /// `stale_before`), making duplicate/redelivered executions of the same node run mutually
@@ -519,6 +668,25 @@ pub trait DatabaseImpl: Send + Sync + 'static {
         status: WorkflowStatus,
     ) -> impl Future<Output = Result<Vec<WorkflowNodeRun>, SendableError>> + Send;
 
+    /// Fetch the recorded queue wait of every node run started at or after `since`, tagged with
+    /// its workflow. Node runs without a recorded wait are skipped.
+    fn fetch_queue_wait_samples(
+        &self,
+        since: DateTime<Utc>,
+    ) -> impl Future<Output = Result<Vec<QueueWaitSample>, SendableError>> + Send;
+
+    /// Fetch every queued or running node run, tagged with its workflow, oldest first.
+    fn fetch_active_node_runs(
+        &self,
+    ) -> impl Future<Output = Result<Vec<ActiveNodeRun>, SendableError>> + Send;
+
+    /// Count the node runs created at or after `since` per task (workflow and node), with their
+    /// outcomes and the summed wall time of those that finished.
+    fn fetch_node_run_counts(
+        &self,
+        since: DateTime<Utc>,
+    ) -> impl Future<Output = Result<Vec<NodeRunCounts>, SendableError>> + Send;
+
     /// Append a log chunk to a workflow node run.
     fn append_workflow_node_run_chunk(
         &self,
@@ -565,6 +733,28 @@ pub trait DatabaseImpl: Send + Sync + 'static {
         workflow_run_id: Uuid,
     ) -> impl Future<Output = Result<Vec<WorkflowRunArtifact>, SendableError>> + Send;
 
+    /// Record named outputs for a run of `workflow_id`, replacing any earlier value under the same
+    /// name. Returns every output the run has recorded.
+    fn record_workflow_run_outputs(
+        &self,
+        workflow_run_id: Uuid,
+        workflow_id: Uuid,
+        outputs: &BTreeMap<String, Value>,
+    ) -> impl Future<Output = Result<Vec<WorkflowRunOutput>, SendableError>> + Send;
+
+    /// Fetch the named outputs a workflow run recorded, by name.
+    fn fetch_workflow_run_outputs(
+        &self,
+        workflow_run_id: Uuid,
+    ) -> impl Future<Output = Result<Vec<WorkflowRunOutput>, SendableError>> + Send;
+
+    /// Fetch the value the workflow's most recently finished successful run recorded under `name`.
+    fn fetch_latest_workflow_output(
+        &self,
+        workflow_id: Uuid,
+        name: &str,
+    ) -> impl Future<Output = Result<Option<Value>, SendableError>> + Send;
+
     /// Apply a workflow result event once; returns false for duplicate events.
     fn apply_workflow_result_event(
         &self,
@@ -889,6 +1079,14 @@ pub trait DatabaseImpl: Send + Sync + 'static {
         command: ActionCommand,
     ) -> impl Future<Output = Result<ActionDispatchRecord, SendableError>> + Send;
 
+    /// Like `enqueue_action_dispatch`, but the intent stays unclaimable until `not_before`.
+    fn enqueue_action_dispatch_at(
+        &self,
+        dedupe_key: String,
+        command: ActionCommand,
+        not_before: Option<DateTime<Utc>>,
+    ) -> impl Future<Output = Result<ActionDispatchRecord, SendableError>> + Send;
+
     /// Fetch unpublished action dispatch intents.
     fn fetch_pending_action_dispatches(
         &self,
@@ -917,6 +1115,12 @@ pub trait DatabaseImpl: Send + Sync + 'static {
         error: String,
     ) -> impl Future<Output = Result<(), SendableError>> + Send;
 
+    /// Remove an action dispatch from the outbox once it has been dead-lettered.
+    fn delete_action_dispatch(
+        &self,
+        dispatch_id: Uuid,
+    ) -> impl Future<Output = Result<(), SendableError>> + Send;
+
     /// Persist a notification record.
     fn create_notification(
         &self,
@@ -1268,6 +1472,274 @@ pub trait DatabaseImpl: Send + Sync + 'static {
         user_id: Uuid,
     ) -> impl Future<Output = Result<Vec<(Organization, OrgRole)>, SendableError>> + Send;
 
+    // ---- per-workflow run-history retention ----
+
+    /// Every workflow retention override.
+    fn fetch_workflow_retentions(
+        &self,
+    ) -> impl Future<Output = Result<Vec<WorkflowRetention>, SendableError>> + Send;
+
+    /// A workflow's retention override, or `None` when it follows the global policy.
+    fn fetch_workflow_retention(
+        &self,
+        workflow_id: Uuid,
+    ) -> impl Future<Output = Result<Option<WorkflowRetention>, SendableError>> + Send;
+
+    /// Create or replace a workflow's retention override.
+    fn upsert_workflow_retention(
+        &self,
+        retention: WorkflowRetention,
+    ) -> impl Future<Output = Result<WorkflowRetention, SendableError>> + Send;
+
+    /// Remove a workflow's retention override. Returns whether one existed.
+    fn delete_workflow_retention(
+        &self,
+        workflow_id: Uuid,
+    ) -> impl Future<Output = Result<bool, SendableError>> + Send;
+
+    // ---- workflow revision history ----
+
+    /// Append a revision recording `workflow` as saved by `changed_by`. Revisions are numbered per
+    /// workflow starting at 1. The workflow must already have an id.
+    fn record_workflow_revision(
+        &self,
+        workflow: &WorkflowDefinition,
+        changed_by: Option<Uuid>,
+    ) -> impl Future<Output = Result<WorkflowRevision, SendableError>> + Send;
+
+    /// A workflow's revisions, newest first.
+    fn fetch_workflow_revisions(
+        &self,
+        workflow_id: Uuid,
+    ) -> impl Future<Output = Result<Vec<WorkflowRevision>, SendableError>> + Send;
+
+    /// One revision of a workflow by its number.
+    fn fetch_workflow_revision(
+        &self,
+        workflow_id: Uuid,
+        revision: i64,
+    ) -> impl Future<Output = Result<Option<WorkflowRevision>, SendableError>> + Send;
+
+    // ---- second-approver workflow protection ----
+
+    /// A workflow's protection flag, or `None` when it is not protected.
+    fn fetch_workflow_protection(
+        &self,
+        workflow_id: Uuid,
+    ) -> impl Future<Output = Result<Option<WorkflowProtection>, SendableError>> + Send;
+
+    /// Protect a workflow. Protecting an already protected workflow keeps the original record.
+    fn protect_workflow(
+        &self,
+        workflow_id: Uuid,
+        protected_by: Option<Uuid>,
+    ) -> impl Future<Output = Result<WorkflowProtection, SendableError>> + Send;
+
+    /// Lift a workflow's protection. Returns whether it was protected.
+    fn unprotect_workflow(
+        &self,
+        workflow_id: Uuid,
+    ) -> impl Future<Output = Result<bool, SendableError>> + Send;
+
+    /// Hold a mutation to a protected workflow until it is reviewed.
+    fn create_pending_workflow_change(
+        &self,
+        change: &NewPendingWorkflowChange,
+    ) -> impl Future<Output = Result<PendingWorkflowChange, SendableError>> + Send;
+
+    /// A workflow's changes still awaiting review, oldest first.
+    fn fetch_pending_workflow_changes(
+        &self,
+        workflow_id: Uuid,
+    ) -> impl Future<Output = Result<Vec<PendingWorkflowChange>, SendableError>> + Send;
+
+    /// One pending change by id, whatever its status.
+    fn fetch_pending_workflow_change(
+        &self,
+        change_id: Uuid,
+    ) -> impl Future<Output = Result<Option<PendingWorkflowChange>, SendableError>> + Send;
+
+    /// Move a change that is still pending to `status`. Returns false when it was already reviewed,
+    /// so two concurrent reviews cannot both apply it.
+    fn review_pending_workflow_change(
+        &self,
+        change_id: Uuid,
+        status: PendingChangeStatus,
+        reviewed_by: Option<Uuid>,
+    ) -> impl Future<Output = Result<bool, SendableError>> + Send;
+
+    // ---- cluster pause ----
+
+    /// The current cluster-wide pause; the default (nothing paused) when it was never set.
+    fn fetch_cluster_pause(
+        &self,
+    ) -> impl Future<Output = Result<ClusterPause, SendableError>> + Send;
+
+    /// Replace the cluster-wide pause.
+    fn set_cluster_pause(
+        &self,
+        pause: &ClusterPause,
+    ) -> impl Future<Output = Result<(), SendableError>> + Send;
+
+    // ---- scheduler leases ----
+
+    /// Take or renew the lease `name` for `holder` until `expires_at`, returning whether `holder`
+    /// now holds it. A lease held by another instance is only taken over once it expired before
+    /// `now`.
+    fn acquire_scheduler_lease(
+        &self,
+        name: &str,
+        holder: &str,
+        now: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) -> impl Future<Output = Result<bool, SendableError>> + Send;
+
+    /// Give up the lease `name` if `holder` holds it, so a standby can take over without waiting
+    /// for it to expire.
+    fn release_scheduler_lease(
+        &self,
+        name: &str,
+        holder: &str,
+    ) -> impl Future<Output = Result<(), SendableError>> + Send;
+
+    /// The lease `name`, expired or not; `None` when no instance ever took it.
+    fn fetch_scheduler_lease(
+        &self,
+        name: &str,
+    ) -> impl Future<Output = Result<Option<SchedulerLease>, SendableError>> + Send;
+
+    // ---- blackout calendars ----
+
+    /// Every stored holiday calendar, by name.
+    fn fetch_blackout_calendars(
+        &self,
+    ) -> impl Future<Output = Result<Vec<BlackoutCalendar>, SendableError>> + Send;
+
+    /// Create or replace the calendar named `calendar.name`.
+    fn upsert_blackout_calendar(
+        &self,
+        calendar: &BlackoutCalendar,
+    ) -> impl Future<Output = Result<(), SendableError>> + Send;
+
+    /// Delete a calendar. Returns false when no calendar had that name.
+    fn delete_blackout_calendar(
+        &self,
+        name: &str,
+    ) -> impl Future<Output = Result<bool, SendableError>> + Send;
+
+    // ---- backfills ----
+
+    /// Record a requested backfill unless its workflow already has a running one, in which case
+    /// nothing is written and false is returned.
+    fn create_workflow_backfill(
+        &self,
+        backfill: &WorkflowBackfill,
+    ) -> impl Future<Output = Result<bool, SendableError>> + Send;
+
+    /// Fetch one backfill by id.
+    fn fetch_workflow_backfill(
+        &self,
+        backfill_id: Uuid,
+    ) -> impl Future<Output = Result<Option<WorkflowBackfill>, SendableError>> + Send;
+
+    /// Every backfill of a trigger, newest first.
+    fn fetch_workflow_backfills(
+        &self,
+        trigger_id: Uuid,
+    ) -> impl Future<Output = Result<Vec<WorkflowBackfill>, SendableError>> + Send;
+
+    /// Every backfill with slots still to start, oldest first.
+    fn fetch_running_workflow_backfills(
+        &self,
+    ) -> impl Future<Output = Result<Vec<WorkflowBackfill>, SendableError>> + Send;
+
+    /// Record how many of a running backfill's slots have started and its new status. A backfill
+    /// that is no longer running is left alone, so progress never undoes a cancel.
+    fn update_workflow_backfill_progress(
+        &self,
+        backfill_id: Uuid,
+        fired: i64,
+        status: BackfillStatus,
+    ) -> impl Future<Output = Result<(), SendableError>> + Send;
+
+    /// Stop a running backfill from starting any more slots. Returns false when it was not running.
+    fn cancel_workflow_backfill(
+        &self,
+        backfill_id: Uuid,
+    ) -> impl Future<Output = Result<bool, SendableError>> + Send;
+
+    /// Count a workflow's open runs started by backfills, the ones its concurrency cap counts.
+    fn count_open_backfill_runs(
+        &self,
+        workflow_id: Uuid,
+    ) -> impl Future<Output = Result<i64, SendableError>> + Send;
+
+    // ---- one-off jobs ----
+
+    /// Record a queued job for `action` on its first attempt.
+    fn create_job(
+        &self,
+        job_id: Uuid,
+        action: &WorkflowAction,
+        retry: JobRetry,
+        requested_by: Option<Uuid>,
+    ) -> impl Future<Output = Result<Job, SendableError>> + Send;
+
+    fn fetch_job(
+        &self,
+        job_id: Uuid,
+    ) -> impl Future<Output = Result<Option<Job>, SendableError>> + Send;
+
+    /// Apply a worker result event to its job, deduplicated by event id like workflow results.
+    /// Status events update the job; a terminal status is never overwritten. A terminal status is
+    /// also recorded as a finished attempt, and a failure with retries left puts the job back in
+    /// `queued` on its next attempt instead. A status from any other attempt is ignored. Output
+    /// chunks and artifacts are not kept for jobs. Returns false for a duplicate event.
+    fn apply_job_result_event(
+        &self,
+        event: &WorkflowResultEvent,
+    ) -> impl Future<Output = Result<bool, SendableError>> + Send;
+
+    /// Compare-and-swap executor lease on a job; see `claim_workflow_node_run_executor`.
+    fn claim_job_executor(
+        &self,
+        job_id: Uuid,
+        replica_id: Uuid,
+        claimed_at: DateTime<Utc>,
+        stale_before: DateTime<Utc>,
+    ) -> impl Future<Output = Result<bool, SendableError>> + Send;
+
+    /// Free a job's executor lease if `replica_id` holds it.
+    fn release_job_executor(
+        &self,
+        job_id: Uuid,
+        replica_id: Uuid,
+    ) -> impl Future<Output = Result<(), SendableError>> + Send;
+
+    // ---- workflow templates ----
+
+    /// Create a template (when `id` is `None` or unknown) or replace an existing one.
+    fn upsert_workflow_template(
+        &self,
+        template: &WorkflowTemplate,
+    ) -> impl Future<Output = Result<WorkflowTemplate, SendableError>> + Send;
+
+    /// Every template, ordered by name.
+    fn fetch_workflow_templates(
+        &self,
+    ) -> impl Future<Output = Result<Vec<WorkflowTemplate>, SendableError>> + Send;
+
+    fn fetch_workflow_template(
+        &self,
+        template_id: Uuid,
+    ) -> impl Future<Output = Result<Option<WorkflowTemplate>, SendableError>> + Send;
+
+    /// Delete a template. Workflows created from it are unaffected. Returns whether it existed.
+    fn delete_workflow_template(
+        &self,
+        template_id: Uuid,
+    ) -> impl Future<Output = Result<bool, SendableError>> + Send;
+
     // ---- billing: per-org quotas + usage ledger ----
 
     /// An org's quota, or `None` when none is set (unbounded).
@@ -1282,6 +1754,13 @@ pub trait DatabaseImpl: Send + Sync + 'static {
         quota: OrgQuota,
     ) -> impl Future<Output = Result<OrgQuota, SendableError>> + Send;
 
+    /// Count an org's claimed, non-terminal workflow runs (queued runs excluded), the figure its
+    /// `max_concurrent_runs` quota is enforced against.
+    fn count_active_workflow_runs_for_org(
+        &self,
+        org_id: Uuid,
+    ) -> impl Future<Output = Result<i64, SendableError>> + Send;
+
     /// Append a usage sample to the ledger.
     fn insert_usage_sample(
         &self,
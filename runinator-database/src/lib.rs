@@ -11,15 +11,18 @@ use uuid::Uuid;
 
 pub mod archive;
 pub mod backend;
+pub mod blackout;
 mod common;
 pub mod errors;
 pub mod interfaces;
 mod mappers;
 pub mod mysql;
 mod operations;
-mod pool;
+pub mod pool;
 pub mod postgres;
 mod queries;
+pub mod replica;
+pub mod schedule;
 pub mod sqlite;
 
 #[derive(Debug, Clone, Default)]
@@ -6,31 +6,41 @@ use runinator_models::{
         ApiKey, ApiKeyRecord, AuthSession, Grant, LocalCredential, Permission, PrincipalType,
         ResourceType, Team, User,
     },
+    backfills::{BackfillStatus, WorkflowBackfill},
     billing::{OrgQuota, OrgResourceGroup, UsageSample},
+    blackouts::BlackoutCalendar,
+    cluster::{ClusterPause, SchedulerLease},
     errors::SendableError,
+    jobs::{Job, JobAttempt},
     notifications::Notification,
     orchestration::{OrchestrationEvent, ReadyNodeRecord},
     orgs::{OrgMembership, OrgRole, Organization},
     pipelines::{Pipeline, PipelineDefaults, PipelineRun, PipelineTrigger},
+    protection::{
+        PendingChangeKind, PendingChangeStatus, PendingWorkflowChange, WorkflowProtection,
+    },
     provisioning::ProvisionBackend,
     replicas::{
         ReplicaKind, ReplicaProviderRegistration, ReplicaRecord, ReplicaStatus, TriggerActorType,
         TriggerSourceKind,
     },
+    retention::WorkflowRetention,
+    revisions::WorkflowRevision,
     runs::{RunArtifact, RunChunk, RunStatus, RunSummary},
     settings::{SettingKind, SettingRecord},
     telemetry::ReplicaSample,
+    templates::WorkflowTemplate,
     types::RuninatorType,
     workflows::{
         WorkflowDefinition, WorkflowGraph, WorkflowNodeRun, WorkflowNodeRunArtifact,
-        WorkflowNodeRunChunk, WorkflowRun, WorkflowRunArtifact, WorkflowStatus, WorkflowTrigger,
-        WorkflowTriggerKind,
+        WorkflowNodeRunChunk, WorkflowRun, WorkflowRunArtifact, WorkflowRunOutput, WorkflowStatus,
+        WorkflowTrigger, WorkflowTriggerKind,
     },
 };
 use sqlx::{ColumnIndex, Decode, Row, Type};
 use uuid::Uuid;
 
-fn parse_json(raw: String) -> Value {
+pub(crate) fn parse_json(raw: String) -> Value {
     serde_json::from_str(&raw).unwrap_or(Value::Null)
 }
 
@@ -90,6 +100,8 @@ macro_rules! run_summary_from_row {
                 .unwrap_or_else(Utc::now),
             workflow_run_id: $row.get("workflow_run_id"),
             workflow_node_id: $row.get("workflow_node_id"),
+            workflow_name: $row.get("workflow_name"),
+            worker_id: $row.get("worker_id"),
         }
     }};
 }
@@ -215,9 +227,168 @@ row_mapper!(row_to_org_quota(row) -> OrgQuota {
         max_nodes_per_kind: serde_json::from_str(&row.get::<String, _>("max_nodes_json"))
             .unwrap_or_default(),
         max_monthly_cents: row.get::<i64, _>("max_monthly_cents") as u32,
+        max_concurrent_runs: row.get::<i64, _>("max_concurrent_runs") as u32,
+    }
+});
+
+row_mapper!(row_to_workflow_retention(row) -> WorkflowRetention {
+    WorkflowRetention {
+        workflow_id: row.get::<Uuid, _>("workflow_id"),
+        retention_days: row.get::<i64, _>("retention_days") as u32,
+        updated_at: DateTime::<Utc>::from_timestamp(row.get::<i64, _>("updated_at"), 0),
+    }
+});
+
+row_mapper!(row_to_workflow_revision(row) -> Result<WorkflowRevision, SendableError> {
+    Ok(WorkflowRevision {
+        id: row.get::<Uuid, _>("id"),
+        workflow_id: row.get::<Uuid, _>("workflow_id"),
+        revision: row.get::<i64, _>("revision"),
+        workflow: serde_json::from_str(&row.get::<String, _>("snapshot"))?,
+        changed_by: row.get::<Option<Uuid>, _>("changed_by"),
+        created_at: DateTime::<Utc>::from_timestamp(row.get::<i64, _>("created_at"), 0)
+            .unwrap_or_else(Utc::now),
+    })
+});
+
+row_mapper!(row_to_workflow_protection(row) -> WorkflowProtection {
+    WorkflowProtection {
+        workflow_id: row.get::<Uuid, _>("workflow_id"),
+        protected_by: row.get::<Option<Uuid>, _>("protected_by"),
+        created_at: DateTime::<Utc>::from_timestamp(row.get::<i64, _>("created_at"), 0),
+    }
+});
+
+row_mapper!(row_to_pending_workflow_change(row) -> Result<PendingWorkflowChange, SendableError> {
+    Ok(PendingWorkflowChange {
+        id: row.get::<Uuid, _>("id"),
+        workflow_id: row.get::<Uuid, _>("workflow_id"),
+        // an unknown kind must not be applied as some other mutation, so it fails the read.
+        kind: PendingChangeKind::try_from(row.get::<String, _>("kind").as_str())?,
+        payload: parse_json(row.get::<String, _>("payload")),
+        requested_by: row.get::<Option<Uuid>, _>("requested_by"),
+        status: PendingChangeStatus::try_from(row.get::<String, _>("status").as_str())
+            .unwrap_or(PendingChangeStatus::Rejected),
+        reviewed_by: row.get::<Option<Uuid>, _>("reviewed_by"),
+        created_at: DateTime::<Utc>::from_timestamp(row.get::<i64, _>("created_at"), 0)
+            .unwrap_or_else(Utc::now),
+        reviewed_at: row
+            .get::<Option<i64>, _>("reviewed_at")
+            .and_then(|ts| DateTime::<Utc>::from_timestamp(ts, 0)),
+    })
+});
+
+row_mapper!(row_to_cluster_pause(row) -> ClusterPause {
+    ClusterPause {
+        dispatch: row.get::<bool, _>("dispatch_paused"),
+        execution: row.get::<bool, _>("execution_paused"),
+        scheduling: row.get::<bool, _>("scheduling_paused"),
+        reason: row.get::<Option<String>, _>("reason"),
+        updated_by: row.get::<Option<Uuid>, _>("updated_by"),
+        updated_at: DateTime::<Utc>::from_timestamp(row.get::<i64, _>("updated_at"), 0),
+    }
+});
+
+row_mapper!(row_to_scheduler_lease(row) -> SchedulerLease {
+    SchedulerLease {
+        name: row.get::<String, _>("name"),
+        holder: row.get::<String, _>("holder"),
+        acquired_at: DateTime::<Utc>::from_timestamp(row.get::<i64, _>("acquired_at"), 0)
+            .unwrap_or_default(),
+        expires_at: DateTime::<Utc>::from_timestamp(row.get::<i64, _>("expires_at"), 0)
+            .unwrap_or_default(),
     }
 });
 
+row_mapper!(row_to_blackout_calendar(row) -> BlackoutCalendar {
+    BlackoutCalendar {
+        name: row.get::<String, _>("name"),
+        description: row.get::<Option<String>, _>("description"),
+        timezone: row.get::<Option<String>, _>("timezone"),
+        dates: serde_json::from_str(&row.get::<String, _>("dates")).unwrap_or_default(),
+        updated_at: DateTime::<Utc>::from_timestamp(row.get::<i64, _>("updated_at"), 0),
+    }
+});
+
+row_mapper!(row_to_workflow_backfill(row) -> WorkflowBackfill {
+    WorkflowBackfill {
+        id: row.get::<Uuid, _>("id"),
+        workflow_id: row.get::<Uuid, _>("workflow_id"),
+        trigger_id: row.get::<Uuid, _>("trigger_id"),
+        range_start: DateTime::<Utc>::from_timestamp(row.get::<i64, _>("range_start"), 0)
+            .unwrap_or_default(),
+        range_end: DateTime::<Utc>::from_timestamp(row.get::<i64, _>("range_end"), 0)
+            .unwrap_or_default(),
+        concurrency: row.get::<i64, _>("concurrency"),
+        slots: serde_json::from_str(&row.get::<String, _>("slots")).unwrap_or_default(),
+        fired: row.get::<i64, _>("fired"),
+        status: BackfillStatus::try_from(row.get::<String, _>("status").as_str())
+            .unwrap_or(BackfillStatus::Canceled),
+        requested_by: row.get::<Option<Uuid>, _>("requested_by"),
+        created_at: DateTime::<Utc>::from_timestamp(row.get::<i64, _>("created_at"), 0)
+            .unwrap_or_default(),
+        updated_at: DateTime::<Utc>::from_timestamp(row.get::<i64, _>("updated_at"), 0)
+            .unwrap_or_default(),
+    }
+});
+
+row_mapper!(row_to_job(row) -> Result<Job, SendableError> {
+    Ok(Job {
+        id: row.get::<Uuid, _>("id"),
+        action: serde_json::from_str(&row.get::<String, _>("action"))?,
+        status: WorkflowStatus::try_from(row.get::<String, _>("status").as_str())
+            .unwrap_or(WorkflowStatus::Failed),
+        output_json: row
+            .get::<Option<String>, _>("output_json")
+            .and_then(|raw| serde_json::from_str(&raw).ok()),
+        message: row.get::<Option<String>, _>("message"),
+        requested_by: row.get::<Option<Uuid>, _>("requested_by"),
+        created_at: DateTime::<Utc>::from_timestamp(row.get::<i64, _>("created_at"), 0)
+            .unwrap_or_else(Utc::now),
+        started_at: row
+            .get::<Option<i64>, _>("started_at")
+            .and_then(|ts| DateTime::<Utc>::from_timestamp(ts, 0)),
+        finished_at: row
+            .get::<Option<i64>, _>("finished_at")
+            .and_then(|ts| DateTime::<Utc>::from_timestamp(ts, 0)),
+        attempt: row.get::<i64, _>("attempt"),
+        max_retries: row.get::<i64, _>("max_retries"),
+        retry_backoff_seconds: row.get::<i64, _>("retry_backoff_seconds"),
+        attempts: Vec::new(),
+    })
+});
+
+row_mapper!(row_to_job_attempt(row) -> JobAttempt {
+    JobAttempt {
+        attempt: row.get::<i64, _>("attempt"),
+        status: WorkflowStatus::try_from(row.get::<String, _>("status").as_str())
+            .unwrap_or(WorkflowStatus::Failed),
+        output_json: row
+            .get::<Option<String>, _>("output_json")
+            .and_then(|raw| serde_json::from_str(&raw).ok()),
+        message: row.get::<Option<String>, _>("message"),
+        started_at: row
+            .get::<Option<i64>, _>("started_at")
+            .and_then(|ts| DateTime::<Utc>::from_timestamp(ts, 0)),
+        finished_at: DateTime::<Utc>::from_timestamp(row.get::<i64, _>("finished_at"), 0)
+            .unwrap_or_else(Utc::now),
+    }
+});
+
+row_mapper!(row_to_workflow_template(row) -> Result<WorkflowTemplate, SendableError> {
+    Ok(WorkflowTemplate {
+        id: Some(row.get::<Uuid, _>("id")),
+        name: row.get::<String, _>("name"),
+        description: row.get::<Option<String>, _>("description"),
+        org_id: row.get::<Option<Uuid>, _>("org_id"),
+        cron: row.get::<Option<String>, _>("cron"),
+        labels: serde_json::from_str(&row.get::<String, _>("labels")).unwrap_or_default(),
+        workflow: serde_json::from_str(&row.get::<String, _>("workflow"))?,
+        created_at: DateTime::<Utc>::from_timestamp(row.get::<i64, _>("created_at"), 0),
+        updated_at: DateTime::<Utc>::from_timestamp(row.get::<i64, _>("updated_at"), 0),
+    })
+});
+
 row_mapper!(row_to_usage_sample(row) -> UsageSample {
     UsageSample {
         org_id: row.get::<Uuid, _>("org_id"),
@@ -533,6 +704,8 @@ macro_rules! workflow_node_run_from_row {
                 .ok()
                 .flatten()
                 .and_then(|ts| DateTime::<Utc>::from_timestamp(ts, 0)),
+
+            queue_wait_ms: $row.try_get("queue_wait_ms").ok().flatten(),
         }
     }};
 }
@@ -599,6 +772,16 @@ row_mapper!(row_to_workflow_run_artifact(row) -> WorkflowRunArtifact {
     workflow_run_artifact_from_row!(row)
 });
 
+row_mapper!(row_to_workflow_run_output(row) -> WorkflowRunOutput {
+    WorkflowRunOutput {
+        workflow_run_id: row.get("workflow_run_id"),
+        name: row.get("name"),
+        value: parse_json(row.get::<String, _>("value")),
+        created_at: DateTime::<Utc>::from_timestamp(row.get("created_at"), 0)
+            .unwrap_or_else(Utc::now),
+    }
+});
+
 macro_rules! catalog_item_from_row {
     ($row:expr) => {{
         runinator_models::json!({
@@ -9,11 +9,7 @@ use sqlx::{
     mysql::{MySqlConnectOptions, MySqlPoolOptions},
 };
 
-use crate::{
-    backend::SqlBackend,
-    pool::{pool_acquire_timeout, pool_max_connections},
-    queries::SqlDialect,
-};
+use crate::{backend::SqlBackend, pool::PoolSettings, queries::SqlDialect};
 
 static MYSQL_MIGRATOR: Migrator = sqlx::migrate!("./migrations/mysql");
 
@@ -27,15 +23,22 @@ mod tests;
 
 impl MySqlDb {
     pub async fn new(connection_str: &str) -> Result<Self, SendableError> {
+        Self::with_pool_settings(connection_str, &PoolSettings::from_env()).await
+    }
+
+    pub async fn with_pool_settings(
+        connection_str: &str,
+        settings: &PoolSettings,
+    ) -> Result<Self, SendableError> {
         let options = MySqlConnectOptions::from_str(connection_str)?
+            .statement_cache_capacity(settings.statement_cache_capacity)
             .log_statements(log::LevelFilter::Debug)
             .log_slow_statements(log::LevelFilter::Warn, std::time::Duration::from_secs(1));
 
         // bound the pool and time out acquisition so a request flood cannot open unbounded
-        // connections and a saturated pool fails fast; both env-tunable (see the `pool` module).
-        let pool = MySqlPoolOptions::new()
-            .max_connections(pool_max_connections())
-            .acquire_timeout(pool_acquire_timeout())
+        // connections and a saturated pool fails fast; see the `pool` module for the knobs.
+        let pool = settings
+            .apply(MySqlPoolOptions::new())
             .connect_with(options)
             .await?;
         Ok(Self { pool })
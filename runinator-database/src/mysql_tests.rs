@@ -100,6 +100,11 @@ fn sample_action(workflow_run_id: Uuid, workflow_node_run_id: Uuid) -> ActionCom
             mcp_enabled: false,
             tags: Vec::new(),
             required_labels: Default::default(),
+            allowed_secrets: Vec::new(),
+            preflight: Vec::new(),
+            cache_ttl_seconds: None,
+            priority: 0,
+            queue: None,
         },
         attempt: 1,
         parameters: runinator_models::json!({}),
@@ -202,6 +207,7 @@ async fn mariadb_full_lifecycle() {
             status: WorkflowStatus::Succeeded,
             output_json: None,
             message: None,
+            queue_wait_ms: None,
         },
         trace_id: Uuid::nil(),
     };
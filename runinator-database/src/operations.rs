@@ -4,17 +4,22 @@
 //! genuinely divergent fragments (boolean literal, row locking, insert-or-ignore form, and the
 //! postgres no-id insert path) are the only places that branch on `self.dialect()`.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use chrono::{DateTime, Utc};
+use log::warn;
 use runinator_comm::{
     ActionCommand, ActionDispatchRecord, WorkflowResultEvent, WorkflowResultEventKind,
 };
 use runinator_models::value::Value;
 use runinator_models::{
     auth::{ApiKey, ApiKeyRecord, AuthContext, AuthSession, Grant, LocalCredential, Team, User},
+    backfills::{BackfillStatus, WorkflowBackfill},
     billing::{OrgQuota, OrgResourceGroup, UsageSample},
+    blackouts::BlackoutCalendar,
+    cluster::{ClusterPause, SchedulerLease},
     errors::SendableError,
+    jobs::{Job, JobRetry},
     notifications::{NewNotification, Notification},
     orchestration::{
         NewOrchestrationEvent, NodeTransition, NodeTransitionStat, OrchestrationEvent,
@@ -22,17 +27,29 @@ use runinator_models::{
     },
     orgs::{OrgMembership, OrgRole, Organization},
     pipelines::{Pipeline, PipelineRun, PipelineTrigger},
+    protection::{
+        NewPendingWorkflowChange, PendingChangeStatus, PendingWorkflowChange, WorkflowProtection,
+    },
+    provider_stats::NodeRunCounts,
+    queue_wait::QueueWaitSample,
     replicas::{
         ReplicaHeartbeatRequest, ReplicaKind, ReplicaProviderRegistration,
         ReplicaProviderRegistrationRequest, ReplicaRecord, ReplicaRegistrationRequest,
-        ReplicaStatus, WorkflowRunProvenance,
+        ReplicaStatus, TriggerSourceKind, WorkflowRunProvenance,
     },
+    retention::WorkflowRetention,
+    revisions::WorkflowRevision,
     runs::{NewRunArtifact, NewRunChunk, RunArtifact, RunChunk, RunStatus, RunSummary},
     settings::{SettingKind, SettingRecord},
     telemetry::ReplicaSample,
+    templates::WorkflowTemplate,
+    upcoming::ActiveNodeRun,
     workflows::{
-        NewWorkflowRunArtifact, WorkflowDefinition, WorkflowNodeRun, WorkflowNodeRunArtifact,
-        WorkflowNodeRunChunk, WorkflowRun, WorkflowRunArtifact, WorkflowStatus, WorkflowTrigger,
+        MAX_WORKFLOW_SEARCH_TERMS, NewWorkflowRunArtifact, ScheduleReconcileReport,
+        ScheduleReconciliation, WorkflowAction, WorkflowDefinition, WorkflowNodeRun,
+        WorkflowNodeRunArtifact, WorkflowNodeRunChunk, WorkflowRun, WorkflowRunArtifact,
+        WorkflowRunOutput, WorkflowStatus, WorkflowTrigger, WorkflowTriggerKind,
+        workflow_search_terms,
     },
 };
 use sqlx::{ColumnIndex, Database, Decode, Encode, Executor, IntoArguments, Row, Type};
@@ -41,24 +58,41 @@ use uuid::Uuid;
 use crate::{
     archive::{ArchiveMark, ArchiveRow, ArchiveTable},
     backend::{RowsAffected, SqlBackend},
+    blackout::references_calendar,
     common::{
-        is_pipeline_trigger_in_blackout, is_trigger_in_blackout, json_metadata, json_opt_i64,
-        json_opt_str, json_opt_uuid, json_str, next_execution_for_cron,
-        pipeline_trigger_parameters, pipeline_trigger_state, status_list, trigger_parameters,
-        trigger_state, workflow_result_event_type,
+        SCHEDULE_ERROR_RECHECK_SECS, clear_schedule_error, json_metadata, json_opt_i64,
+        json_opt_str, json_opt_uuid, json_str, mark_schedule_error, misfire_slots,
+        next_execution_for_configuration, pipeline_trigger_parameters, pipeline_trigger_state,
+        status_list, trigger_blackout_until, trigger_parameters, trigger_state, webhook_token_hash,
+        workflow_result_event_type,
     },
     interfaces::DatabaseImpl,
     mappers,
+    pool::PoolStatus,
     queries::{self, SqlDialect},
 };
 
+/// most ids bound into one `IN (...)` list; longer lists are read in batches of this size.
+const ID_LIST_BATCH: usize = 1000;
+
 const WORKFLOW_RUN_COLUMNS: &str = "id, workflow_id, workflow_snapshot, status, active_node_id, parameters, state, created_at, started_at, finished_at, message, name, pipeline_run_id, trigger_source_kind, trigger_actor_type, trigger_actor_replica_id, trigger_actor_display_name, trigger_request_host, trigger_request_ip, trigger_metadata";
-const WORKFLOW_NODE_RUN_COLUMNS: &str = "id, workflow_run_id, node_id, status, attempt, parameters, output_json, state, transition_reason, prev_node_run_id, created_at, started_at, finished_at, message, current_executor_replica_id, last_executor_replica_id, executor_claimed_at, executor_released_at";
+const WORKFLOW_NODE_RUN_COLUMNS: &str = "id, workflow_run_id, node_id, status, attempt, parameters, output_json, state, transition_reason, prev_node_run_id, created_at, started_at, finished_at, message, current_executor_replica_id, last_executor_replica_id, executor_claimed_at, executor_released_at, queue_wait_ms";
+const PENDING_CHANGE_COLUMNS: &str =
+    "id, workflow_id, kind, payload, requested_by, status, reviewed_by, created_at, reviewed_at";
+// the single cluster_pause row; a scope column leaves room for narrower pauses later.
+const CLUSTER_PAUSE_SCOPE: &str = "cluster";
+const BLACKOUT_CALENDAR_COLUMNS: &str = "name, description, timezone, dates, updated_at";
+const JOB_COLUMNS: &str = "id, action, status, output_json, message, requested_by, created_at, started_at, finished_at, attempt, max_retries, retry_backoff_seconds";
+const BACKFILL_COLUMNS: &str = "id, workflow_id, trigger_id, range_start, range_end, concurrency, slots, fired, status, requested_by, created_at, updated_at";
+const JOB_ATTEMPT_COLUMNS: &str = "attempt, status, output_json, message, started_at, finished_at";
+// the lease holder a delayed action dispatch is inserted under; no publisher ever claims as it.
+const ACTION_DISPATCH_DELAY_HOLDER: &str = "delayed";
 const REPLICA_COLUMNS: &str = "replica_id, replica_type, instance_id, runtime_id, status, display_name, host, port, base_path, observed_ip, version, attributes, first_seen_at, last_heartbeat_at, last_seen_at, offline_at, registered_by_principal_id, registered_by_kind, registered_by_org_id";
 const REPLICA_PROVIDER_COLUMNS: &str = "replica_id, provider_name, provider_json, first_registered_at, last_registered_at, last_heartbeat_at";
 const PIPELINE_COLUMNS: &str =
     "id, name, description, org_id, workflow_ids, defaults, metadata, created_at, updated_at";
 const PIPELINE_TRIGGER_COLUMNS: &str = "id, pipeline_id, kind, enabled, configuration, next_execution, blackout_start, blackout_end, metadata, created_at, updated_at";
+const SCHEDULE_ERROR_NOTIFICATION_SQL: &str = "INSERT INTO notifications (id, workflow_run_id, workflow_node_id, channel, severity, title, body, target, metadata, created_at) VALUES (?, NULL, NULL, ?, ?, ?, ?, NULL, ?, ?)";
 const PIPELINE_RUN_COLUMNS: &str = "id, pipeline_id, pipeline_snapshot, status, parameters, state, created_at, started_at, finished_at, message, trigger_source_kind, trigger_actor_type, trigger_actor_replica_id, trigger_actor_display_name, trigger_metadata";
 
 trait ArchiveSqlExt: SqlBackend {
@@ -69,6 +103,20 @@ trait ArchiveSqlExt: SqlBackend {
         limit: i64,
     ) -> Result<Vec<(Uuid, DateTime<Utc>)>, SendableError>;
 
+    async fn workflow_run_archive_candidate_ids(
+        &self,
+        workflow_id: Uuid,
+        eligible_before: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<(Uuid, DateTime<Utc>)>, SendableError>;
+
+    async fn insert_archive_marks(
+        &self,
+        table: ArchiveTable,
+        candidates: Vec<(Uuid, DateTime<Utc>)>,
+        eligible_before: DateTime<Utc>,
+    ) -> Result<u64, SendableError>;
+
     async fn fetch_archive_row(
         &self,
         mark: &ArchiveMark,
@@ -81,6 +129,7 @@ where
     for<'q> i64: Encode<'q, B::Db> + Type<B::Db>,
     for<'q> Uuid: Encode<'q, B::Db> + Type<B::Db>,
     for<'q> &'q str: Encode<'q, B::Db> + Type<B::Db>,
+    for<'q> String: Encode<'q, B::Db> + Type<B::Db>,
     for<'q> <B::Db as Database>::Arguments<'q>: IntoArguments<'q, B::Db>,
     for<'r> i64: Decode<'r, B::Db> + Type<B::Db>,
     for<'r> String: Decode<'r, B::Db> + Type<B::Db>,
@@ -91,6 +140,7 @@ where
     for<'r> Option<Uuid>: Decode<'r, B::Db> + Type<B::Db>,
     for<'c> &'c str: ColumnIndex<<B::Db as Database>::Row>,
     for<'c> &'c mut <B::Db as Database>::Connection: Executor<'c, Database = B::Db>,
+    <B::Db as Database>::QueryResult: RowsAffected,
 {
     async fn archive_candidate_ids(
         &self,
@@ -99,18 +149,65 @@ where
         limit: i64,
     ) -> Result<Vec<(Uuid, DateTime<Utc>)>, SendableError> {
         let sql = archive_candidate_sql(table);
-        let rows = sqlx::query(&self.render(sql))
+        let rows = sqlx::query(&self.render(&sql))
             .bind(eligible_before.timestamp())
             .bind(limit)
             .fetch_all(self.pool())
             .await?;
-        rows.iter()
-            .map(|row| {
-                let id: Uuid = row.get("id");
-                let created_at: i64 = row.get("created_at");
-                Ok((id, timestamp_to_utc(created_at)?))
-            })
-            .collect()
+        rows.iter().map(row_to_archive_candidate).collect()
+    }
+
+    async fn workflow_run_archive_candidate_ids(
+        &self,
+        workflow_id: Uuid,
+        eligible_before: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<(Uuid, DateTime<Utc>)>, SendableError> {
+        let sql = format!(
+            "SELECT id, created_at FROM workflow_runs
+             WHERE workflow_id = ? AND created_at <= ? AND {ARCHIVABLE_WORKFLOW_RUN}
+             ORDER BY created_at, id
+             LIMIT ?"
+        );
+        let rows = sqlx::query(&self.render(&sql))
+            .bind(workflow_id)
+            .bind(eligible_before.timestamp())
+            .bind(limit)
+            .fetch_all(self.pool())
+            .await?;
+        rows.iter().map(row_to_archive_candidate).collect()
+    }
+
+    async fn insert_archive_marks(
+        &self,
+        table: ArchiveTable,
+        candidates: Vec<(Uuid, DateTime<Utc>)>,
+        eligible_before: DateTime<Utc>,
+    ) -> Result<u64, SendableError> {
+        let now = Utc::now().timestamp();
+        let archive_day = eligible_before.format("%F").to_string();
+        let mut marked = 0;
+        for (primary_key, created_at) in candidates {
+            let insert = sqlx::query(&self.render(&queries::insert_ignore(
+                self.dialect(),
+                "archive_marks",
+                "id, table_name, primary_key, created_at, eligible_before, archive_day, status, attempts, marked_at",
+                "?, ?, ?, ?, ?, ?, 'marked', 0, ?",
+                "table_name, primary_key",
+                None,
+            )))
+            .bind(Uuid::now_v7())
+            .bind(table.as_str())
+            .bind(primary_key.to_string())
+            .bind(created_at.timestamp())
+            .bind(eligible_before.timestamp())
+            .bind(archive_day.as_str())
+            .bind(now)
+            .execute(self.pool())
+            .await?;
+            marked += insert.affected();
+        }
+        Ok(marked)
     }
 
     async fn fetch_archive_row(
@@ -135,19 +232,150 @@ where
     }
 }
 
-fn archive_candidate_sql(table: ArchiveTable) -> &'static str {
-    match table {
-        ArchiveTable::WorkflowRuns => {
-            "SELECT id, created_at FROM workflow_runs
-             WHERE created_at <= ?
-               AND status IN ('succeeded', 'failed', 'timed_out', 'canceled')
+// workflow writes scoped to a caller-supplied connection, so a single upsert and a batch share one
+// body and the batch can run every row inside one transaction.
+trait WorkflowSqlExt: SqlBackend {
+    async fn upsert_workflow_on(
+        &self,
+        conn: &mut <Self::Db as Database>::Connection,
+        workflow: &WorkflowDefinition,
+    ) -> Result<WorkflowDefinition, SendableError>;
+}
+
+impl<B> WorkflowSqlExt for B
+where
+    B: SqlBackend,
+    for<'q> i64: Encode<'q, B::Db> + Type<B::Db>,
+    for<'q> bool: Encode<'q, B::Db> + Type<B::Db>,
+    for<'q> &'q str: Encode<'q, B::Db> + Type<B::Db>,
+    for<'q> String: Encode<'q, B::Db> + Type<B::Db>,
+    for<'q> Uuid: Encode<'q, B::Db> + Type<B::Db>,
+    for<'q> Option<String>: Encode<'q, B::Db> + Type<B::Db>,
+    for<'q> Option<Uuid>: Encode<'q, B::Db> + Type<B::Db>,
+    for<'q> <B::Db as Database>::Arguments<'q>: IntoArguments<'q, B::Db>,
+    for<'r> i64: Decode<'r, B::Db> + Type<B::Db>,
+    for<'r> String: Decode<'r, B::Db> + Type<B::Db>,
+    for<'r> bool: Decode<'r, B::Db> + Type<B::Db>,
+    for<'r> Uuid: Decode<'r, B::Db> + Type<B::Db>,
+    for<'r> Option<i64>: Decode<'r, B::Db> + Type<B::Db>,
+    for<'r> Option<String>: Decode<'r, B::Db> + Type<B::Db>,
+    for<'r> Option<Uuid>: Decode<'r, B::Db> + Type<B::Db>,
+    for<'r> Vec<u8>: Decode<'r, B::Db> + Type<B::Db>,
+    for<'c> &'c str: ColumnIndex<<B::Db as Database>::Row>,
+    for<'c> &'c mut <B::Db as Database>::Connection: Executor<'c, Database = B::Db>,
+{
+    async fn upsert_workflow_on(
+        &self,
+        conn: &mut <Self::Db as Database>::Connection,
+        workflow: &WorkflowDefinition,
+    ) -> Result<WorkflowDefinition, SendableError> {
+        let now = Utc::now().timestamp();
+        // resolve an existing row by explicit id or by its (namespace, name) identity, else mint a
+        // fresh uuid. the namespace branch keeps same-named workflows in different namespaces apart.
+        let existing_id = match workflow.id {
+            Some(id) => Some(id),
+            None => {
+                let sql = self.render(match &workflow.namespace {
+                    Some(_) => "SELECT id FROM workflows WHERE name = ? AND namespace = ? ORDER BY created_at, id LIMIT 1",
+                    None => "SELECT id FROM workflows WHERE name = ? AND namespace IS NULL ORDER BY created_at, id LIMIT 1",
+                });
+                let mut query = sqlx::query(&sql).bind(workflow.name.as_str());
+                if workflow.namespace.is_some() {
+                    query = query.bind(workflow.namespace.clone());
+                }
+                query
+                    .fetch_optional(&mut *conn)
+                    .await?
+                    .map(|row| row.get::<Uuid, _>("id"))
+            }
+        };
+        let workflow_id = existing_id.unwrap_or_else(Uuid::new_v4);
+
+        // mysql has no usable RETURNING via sqlx: upsert with ON DUPLICATE KEY UPDATE, then read the
+        // row back on the same connection by the (now app-generated) id.
+        if self.dialect() == SqlDialect::MySql {
+            let columns = "id, name, namespace, org_id, version, enabled, input_schema, definition, created_at, updated_at";
+            let conflict = queries::on_conflict_update(
+                SqlDialect::MySql,
+                "id",
+                &[
+                    "name",
+                    "namespace",
+                    "org_id",
+                    "version",
+                    "enabled",
+                    "input_schema",
+                    "definition",
+                    "updated_at",
+                ],
+            );
+            let conflict = format!("{conflict}, edit_version = edit_version + 1");
+            sqlx::query(&self.render(&format!(
+                "INSERT INTO workflows (id, name, namespace, org_id, version, enabled, input_schema, definition, created_at, updated_at)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?) {conflict}",
+            )))
+            .bind(workflow_id)
+            .bind(workflow.name.as_str())
+            .bind(workflow.namespace.clone())
+            .bind(workflow.org_id)
+            .bind(workflow.version.to_string())
+            .bind(workflow.enabled)
+            .bind(serde_json::to_string(&workflow.input_type)?)
+            .bind(workflow.definition.to_string())
+            .bind(now)
+            .bind(now)
+            .execute(&mut *conn)
+            .await?;
+            let row =
+                sqlx::query(&self.render(&format!("SELECT {columns} FROM workflows WHERE id = ?")))
+                    .bind(workflow_id)
+                    .fetch_one(&mut *conn)
+                    .await?;
+            return Ok(mappers::row_to_workflow(&row));
+        }
+
+        let row = sqlx::query(&self.render(
+            "INSERT INTO workflows (id, name, namespace, org_id, version, enabled, input_schema, definition, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT(id) DO UPDATE SET name = excluded.name, namespace = excluded.namespace, org_id = excluded.org_id, version = excluded.version, enabled = excluded.enabled, input_schema = excluded.input_schema, definition = excluded.definition, updated_at = excluded.updated_at, edit_version = workflows.edit_version + 1
+             RETURNING id, name, namespace, org_id, version, enabled, input_schema, definition, created_at, updated_at",
+        ))
+        .bind(workflow_id)
+        .bind(workflow.name.as_str())
+        .bind(workflow.namespace.clone())
+        .bind(workflow.org_id)
+        .bind(workflow.version.to_string())
+        .bind(workflow.enabled)
+        .bind(serde_json::to_string(&workflow.input_type)?)
+        .bind(workflow.definition.to_string())
+        .bind(now)
+        .bind(now)
+        .fetch_one(&mut *conn)
+        .await?;
+        Ok(mappers::row_to_workflow(&row))
+    }
+}
+
+// a workflow run is archivable once it finished and no execution record still references it.
+const ARCHIVABLE_WORKFLOW_RUN: &str = "status IN ('succeeded', 'failed', 'timed_out', 'canceled')
                AND NOT EXISTS (SELECT 1 FROM workflow_node_runs WHERE workflow_node_runs.workflow_run_id = workflow_runs.id)
                AND NOT EXISTS (SELECT 1 FROM workflow_ready_nodes WHERE workflow_ready_nodes.workflow_run_id = workflow_runs.id)
                AND NOT EXISTS (SELECT 1 FROM workflow_orchestration_events WHERE workflow_orchestration_events.workflow_run_id = workflow_runs.id)
                AND NOT EXISTS (SELECT 1 FROM workflow_result_events WHERE workflow_result_events.workflow_run_id = workflow_runs.id)
-               AND NOT EXISTS (SELECT 1 FROM workflow_trigger_firings WHERE workflow_trigger_firings.workflow_run_id = workflow_runs.id)
+               AND NOT EXISTS (SELECT 1 FROM workflow_trigger_firings WHERE workflow_trigger_firings.workflow_run_id = workflow_runs.id)";
+
+fn archive_candidate_sql(table: ArchiveTable) -> String {
+    let sql = match table {
+        // workflows with a retention override are marked on their own window instead.
+        ArchiveTable::WorkflowRuns => {
+            return format!(
+                "SELECT id, created_at FROM workflow_runs
+             WHERE created_at <= ?
+               AND {ARCHIVABLE_WORKFLOW_RUN}
+               AND NOT EXISTS (SELECT 1 FROM workflow_retention WHERE workflow_retention.workflow_id = workflow_runs.workflow_id)
              ORDER BY created_at, id
              LIMIT ?"
+            );
         }
         ArchiveTable::WorkflowNodeChunks => {
             "SELECT id, created_at FROM workflow_node_chunks
@@ -197,9 +425,34 @@ fn archive_candidate_sql(table: ArchiveTable) -> &'static str {
              ORDER BY created_at, id
              LIMIT ?"
         }
+    };
+    sql.to_string()
+}
+
+// close the pool once every checked-out connection is back. sqlx's `close()` alone can return
+// early: closing an idle connection hands back a permit it already released, so the wait for
+// permits passes while a transaction is still writing. the pool size only drops as connections
+// actually close, and this waits for as long as one stays checked out; callers that must exit on
+// time put their own deadline on it.
+async fn close_pool<DB: Database>(pool: &sqlx::Pool<DB>) {
+    pool.close().await;
+    while pool.size() > 0 {
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
     }
 }
 
+fn row_to_archive_candidate<R>(row: &R) -> Result<(Uuid, DateTime<Utc>), SendableError>
+where
+    R: Row,
+    for<'r> Uuid: Decode<'r, R::Database> + Type<R::Database>,
+    for<'r> i64: Decode<'r, R::Database> + Type<R::Database>,
+    for<'c> &'c str: ColumnIndex<R>,
+{
+    let id: Uuid = row.get("id");
+    let created_at: i64 = row.get("created_at");
+    Ok((id, timestamp_to_utc(created_at)?))
+}
+
 fn archive_source_sql(dialect: SqlDialect, table: ArchiveTable) -> String {
     match table {
         ArchiveTable::WorkflowRuns => {
@@ -422,6 +675,17 @@ where
         self.init(paths).await
     }
 
+    async fn close(&self) {
+        if let Some(replica) = self.read_replica() {
+            close_pool(replica.pool()).await;
+        }
+        close_pool(self.pool()).await
+    }
+
+    fn pool_status(&self) -> PoolStatus {
+        PoolStatus::of(self.pool())
+    }
+
     async fn mark_archive_candidates(
         &self,
         table: ArchiveTable,
@@ -431,30 +695,21 @@ where
         let candidates = self
             .archive_candidate_ids(table, eligible_before, limit.max(1))
             .await?;
-        let now = Utc::now().timestamp();
-        let archive_day = eligible_before.format("%F").to_string();
-        let mut marked = 0;
-        for (primary_key, created_at) in candidates {
-            let insert = sqlx::query(&self.render(&queries::insert_ignore(
-                self.dialect(),
-                "archive_marks",
-                "id, table_name, primary_key, created_at, eligible_before, archive_day, status, attempts, marked_at",
-                "?, ?, ?, ?, ?, ?, 'marked', 0, ?",
-                "table_name, primary_key",
-                None,
-            )))
-            .bind(Uuid::now_v7())
-            .bind(table.as_str())
-            .bind(primary_key.to_string())
-            .bind(created_at.timestamp())
-            .bind(eligible_before.timestamp())
-            .bind(archive_day.as_str())
-            .bind(now)
-            .execute(self.pool())
+        self.insert_archive_marks(table, candidates, eligible_before)
+            .await
+    }
+
+    async fn mark_workflow_run_archive_candidates(
+        &self,
+        workflow_id: Uuid,
+        eligible_before: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<u64, SendableError> {
+        let candidates = self
+            .workflow_run_archive_candidate_ids(workflow_id, eligible_before, limit.max(1))
             .await?;
-            marked += insert.affected();
-        }
-        Ok(marked)
+        self.insert_archive_marks(ArchiveTable::WorkflowRuns, candidates, eligible_before)
+            .await
     }
 
     async fn claim_archive_marks(
@@ -594,7 +849,11 @@ where
         status: RunStatus,
     ) -> Result<Vec<RunSummary>, SendableError> {
         let sql = self.render(&format!(
-            "SELECT id, status, parameters, output_json, message, {trigger}, started_at, finished_at, created_at, workflow_run_id, workflow_node_id FROM runs WHERE status = ? ORDER BY created_at, id",
+            "SELECT r.id, r.status, r.parameters, r.output_json, r.message, r.{trigger}, r.started_at, r.finished_at, r.created_at, r.workflow_run_id, r.workflow_node_id, w.name AS workflow_name, r.worker_id
+             FROM runs r
+             LEFT JOIN workflow_runs wr ON wr.id = r.workflow_run_id
+             LEFT JOIN workflows w ON w.id = wr.workflow_id
+             WHERE r.status = ? ORDER BY r.created_at, r.id",
             trigger = queries::ident(self.dialect(), "trigger"),
         ));
         let rows = sqlx::query(&sql)
@@ -610,6 +869,7 @@ where
         status: RunStatus,
         output_json: Option<Value>,
         message: Option<String>,
+        worker_id: Option<String>,
     ) -> Result<(), SendableError> {
         let now = Utc::now().timestamp();
         let terminal = matches!(
@@ -619,11 +879,12 @@ where
         self.pool()
             .execute(
                 sqlx::query(&self.render(
-                    "UPDATE runs SET status = ?, output_json = COALESCE(?, output_json), message = COALESCE(?, message), started_at = CASE WHEN ? = 'running' AND started_at IS NULL THEN ? ELSE started_at END, finished_at = CASE WHEN ? THEN ? ELSE finished_at END WHERE id = ?",
+                    "UPDATE runs SET status = ?, output_json = COALESCE(?, output_json), message = COALESCE(?, message), worker_id = COALESCE(?, worker_id), started_at = CASE WHEN ? = 'running' AND started_at IS NULL THEN ? ELSE started_at END, finished_at = CASE WHEN ? THEN ? ELSE finished_at END WHERE id = ?",
                 ))
                 .bind(status.as_str())
                 .bind(output_json.map(|v| v.to_string()))
                 .bind(message)
+                .bind(worker_id)
                 .bind(status.as_str())
                 .bind(now)
                 .bind(terminal)
@@ -788,90 +1049,58 @@ where
         &self,
         workflow: &WorkflowDefinition,
     ) -> Result<WorkflowDefinition, SendableError> {
-        let now = Utc::now().timestamp();
-        // resolve an existing row by explicit id or by its (namespace, name) identity, else mint a
-        // fresh uuid. the namespace branch keeps same-named workflows in different namespaces apart.
-        let existing_id = match workflow.id {
-            Some(id) => Some(id),
-            None => {
-                let sql = self.render(match &workflow.namespace {
-                    Some(_) => "SELECT id FROM workflows WHERE name = ? AND namespace = ? ORDER BY created_at, id LIMIT 1",
-                    None => "SELECT id FROM workflows WHERE name = ? AND namespace IS NULL ORDER BY created_at, id LIMIT 1",
-                });
-                let mut query = sqlx::query(&sql).bind(workflow.name.as_str());
-                if workflow.namespace.is_some() {
-                    query = query.bind(workflow.namespace.clone());
-                }
-                query
-                    .fetch_optional(self.pool())
-                    .await?
-                    .map(|row| row.get::<Uuid, _>("id"))
-            }
-        };
-        let workflow_id = existing_id.unwrap_or_else(Uuid::new_v4);
+        let mut conn = self.pool().acquire().await?;
+        self.upsert_workflow_on(&mut *conn, workflow).await
+    }
 
-        // mysql has no usable RETURNING via sqlx: upsert with ON DUPLICATE KEY UPDATE, then read the
-        // row back on the same pinned connection by the (now app-generated) id.
-        if self.dialect() == SqlDialect::MySql {
-            let columns = "id, name, namespace, org_id, version, enabled, input_schema, definition, created_at, updated_at";
-            let conflict = queries::on_conflict_update(
-                SqlDialect::MySql,
-                "id",
-                &[
-                    "name",
-                    "namespace",
-                    "org_id",
-                    "version",
-                    "enabled",
-                    "input_schema",
-                    "definition",
-                    "updated_at",
-                ],
-            );
-            let mut conn = self.pool().acquire().await?;
-            sqlx::query(&self.render(&format!(
-                "INSERT INTO workflows (id, name, namespace, org_id, version, enabled, input_schema, definition, created_at, updated_at)
-                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?) {conflict}",
-            )))
-            .bind(workflow_id)
-            .bind(workflow.name.as_str())
-            .bind(workflow.namespace.clone())
-            .bind(workflow.org_id)
-            .bind(workflow.version.to_string())
-            .bind(workflow.enabled)
-            .bind(serde_json::to_string(&workflow.input_type)?)
-            .bind(workflow.definition.to_string())
-            .bind(now)
-            .bind(now)
-            .execute(&mut *conn)
-            .await?;
-            let row =
-                sqlx::query(&self.render(&format!("SELECT {columns} FROM workflows WHERE id = ?")))
-                    .bind(workflow_id)
-                    .fetch_one(&mut *conn)
-                    .await?;
-            return Ok(mappers::row_to_workflow(&row));
+    async fn upsert_workflows(
+        &self,
+        workflows: &[WorkflowDefinition],
+    ) -> Result<Vec<WorkflowDefinition>, SendableError> {
+        let mut tx = self.pool().begin().await?;
+        let mut saved = Vec::with_capacity(workflows.len());
+        for workflow in workflows {
+            saved.push(self.upsert_workflow_on(&mut *tx, workflow).await?);
         }
+        tx.commit().await?;
+        Ok(saved)
+    }
 
-        let row = sqlx::query(&self.render(
-            "INSERT INTO workflows (id, name, namespace, org_id, version, enabled, input_schema, definition, created_at, updated_at)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
-             ON CONFLICT(id) DO UPDATE SET name = excluded.name, namespace = excluded.namespace, org_id = excluded.org_id, version = excluded.version, enabled = excluded.enabled, input_schema = excluded.input_schema, definition = excluded.definition, updated_at = excluded.updated_at
-             RETURNING id, name, namespace, org_id, version, enabled, input_schema, definition, created_at, updated_at",
-        ))
+    async fn upsert_workflow_if_current(
+        &self,
+        workflow: &WorkflowDefinition,
+        edit_version: i64,
+    ) -> Result<Option<WorkflowDefinition>, SendableError> {
+        let Some(workflow_id) = workflow.id else {
+            return Ok(None);
+        };
+        let mut tx = self.pool().begin().await?;
+        // lock the row so a concurrent checked save waits, then sees the bumped version.
+        let current = sqlx::query(&self.render(&format!(
+            "SELECT edit_version FROM workflows WHERE id = ?{}",
+            queries::for_update(self.dialect())
+        )))
         .bind(workflow_id)
-        .bind(workflow.name.as_str())
-        .bind(workflow.namespace.clone())
-        .bind(workflow.org_id)
-        .bind(workflow.version.to_string())
-        .bind(workflow.enabled)
-        .bind(serde_json::to_string(&workflow.input_type)?)
-        .bind(workflow.definition.to_string())
-        .bind(now)
-        .bind(now)
-        .fetch_one(self.pool())
-        .await?;
-        Ok(mappers::row_to_workflow(&row))
+        .fetch_optional(&mut *tx)
+        .await?
+        .map(|row| row.get::<i64, _>("edit_version"));
+        if current != Some(edit_version) {
+            return Ok(None);
+        }
+        let saved = self.upsert_workflow_on(&mut *tx, workflow).await?;
+        tx.commit().await?;
+        Ok(Some(saved))
+    }
+
+    async fn fetch_workflow_edit_version(
+        &self,
+        workflow_id: Uuid,
+    ) -> Result<Option<i64>, SendableError> {
+        let row = sqlx::query(&self.render("SELECT edit_version FROM workflows WHERE id = ?"))
+            .bind(workflow_id)
+            .fetch_optional(self.pool())
+            .await?;
+        Ok(row.map(|row| row.get::<i64, _>("edit_version")))
     }
 
     async fn insert_workflow(
@@ -932,11 +1161,45 @@ where
 
     async fn fetch_workflows(&self) -> Result<Vec<WorkflowDefinition>, SendableError> {
         let rows = sqlx::query("SELECT id, name, namespace, org_id, version, enabled, input_schema, definition, created_at, updated_at FROM workflows ORDER BY name")
-            .fetch_all(self.pool())
+            .fetch_all(self.read_pool())
             .await?;
         Ok(rows.iter().map(mappers::row_to_workflow).collect())
     }
 
+    async fn search_workflows(
+        &self,
+        term: String,
+    ) -> Result<Vec<WorkflowDefinition>, SendableError> {
+        let mut terms = workflow_search_terms(&term);
+        if terms.is_empty() {
+            return self.fetch_workflows().await;
+        }
+        // the web service refuses longer queries; anything calling in directly is held to the
+        // same bound rather than growing the query without limit.
+        terms.truncate(MAX_WORKFLOW_SEARCH_TERMS);
+        let dialect = self.dialect();
+        // every term has to match somewhere: the name, namespace, definition (its actions, their
+        // configuration and routing labels), or one of the workflow's trigger configurations.
+        let matches: Vec<_> = terms
+            .iter()
+            .map(|term| queries::workflow_search_match(dialect, term))
+            .collect();
+        let sql = self.render(&format!(
+            "SELECT id, name, namespace, org_id, version, enabled, input_schema, definition, created_at, updated_at FROM workflows WHERE {} ORDER BY name, id",
+            matches
+                .iter()
+                .map(|(condition, _)| condition.as_str())
+                .collect::<Vec<_>>()
+                .join(" AND "),
+        ));
+        let mut query = sqlx::query(&sql);
+        for value in matches.into_iter().flat_map(|(_, binds)| binds) {
+            query = query.bind(value);
+        }
+        let rows = query.fetch_all(self.read_pool()).await?;
+        Ok(rows.iter().map(mappers::row_to_workflow).collect())
+    }
+
     async fn fetch_workflow(
         &self,
         workflow_id: Uuid,
@@ -965,14 +1228,32 @@ where
         workflow_id: Uuid,
         org_id: Option<Uuid>,
     ) -> Result<(), SendableError> {
-        sqlx::query(&self.render("UPDATE workflows SET org_id = ? WHERE id = ?"))
-            .bind(org_id)
-            .bind(workflow_id)
-            .execute(self.pool())
-            .await?;
+        sqlx::query(&self.render(
+            "UPDATE workflows SET org_id = ?, edit_version = edit_version + 1 WHERE id = ?",
+        ))
+        .bind(org_id)
+        .bind(workflow_id)
+        .execute(self.pool())
+        .await?;
         Ok(())
     }
 
+    async fn set_workflow_enabled(
+        &self,
+        workflow_id: Uuid,
+        enabled: bool,
+    ) -> Result<bool, SendableError> {
+        let result = sqlx::query(&self.render(
+            "UPDATE workflows SET enabled = ?, updated_at = ?, edit_version = edit_version + 1 WHERE id = ?",
+        ))
+        .bind(enabled)
+        .bind(Utc::now().timestamp())
+        .bind(workflow_id)
+        .execute(self.pool())
+        .await?;
+        Ok(result.affected() > 0)
+    }
+
     async fn fetch_workflow_by_name(
         &self,
         name: String,
@@ -1015,10 +1296,16 @@ where
             format!("DELETE FROM workflow_node_artifacts WHERE {node_run_filter}"),
             format!("DELETE FROM workflow_result_events WHERE {run_filter}"),
             format!("DELETE FROM workflow_trigger_firings WHERE {run_filter}"),
+            "DELETE FROM workflow_run_outputs WHERE workflow_id = ?".to_string(),
             "DELETE FROM workflow_node_runs WHERE workflow_run_id IN \
                  (SELECT id FROM workflow_runs WHERE workflow_id = ?)"
                 .to_string(),
             "DELETE FROM workflow_runs WHERE workflow_id = ?".to_string(),
+            "DELETE FROM workflow_retention WHERE workflow_id = ?".to_string(),
+            "DELETE FROM workflow_protection WHERE workflow_id = ?".to_string(),
+            "DELETE FROM workflow_pending_changes WHERE workflow_id = ?".to_string(),
+            "DELETE FROM workflow_revisions WHERE workflow_id = ?".to_string(),
+            "DELETE FROM workflow_missed_run_alerts WHERE workflow_id = ?".to_string(),
             "DELETE FROM workflows WHERE id = ?".to_string(),
         ] {
             sqlx::query(&self.render(&sql))
@@ -1053,13 +1340,14 @@ where
                     "blackout_start",
                     "blackout_end",
                     "metadata",
+                    "webhook_token_hash",
                     "updated_at",
                 ],
             );
             let mut conn = self.pool().acquire().await?;
             sqlx::query(&self.render(&format!(
-                "INSERT INTO workflow_triggers (id, workflow_id, kind, enabled, configuration, next_execution, blackout_start, blackout_end, metadata, created_at, updated_at)
-                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?) {conflict}",
+                "INSERT INTO workflow_triggers (id, workflow_id, kind, enabled, configuration, next_execution, blackout_start, blackout_end, metadata, webhook_token_hash, created_at, updated_at)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?) {conflict}",
             )))
             .bind(trigger_id)
             .bind(trigger.workflow_id)
@@ -1070,6 +1358,7 @@ where
             .bind(trigger.blackout_start.map(|dt| dt.timestamp()))
             .bind(trigger.blackout_end.map(|dt| dt.timestamp()))
             .bind(trigger.metadata.to_string())
+            .bind(webhook_token_hash(trigger))
             .bind(trigger.created_at.map(|dt| dt.timestamp()).unwrap_or(now))
             .bind(now)
             .execute(&mut *conn)
@@ -1084,9 +1373,9 @@ where
         }
 
         let row = sqlx::query(&self.render(
-            "INSERT INTO workflow_triggers (id, workflow_id, kind, enabled, configuration, next_execution, blackout_start, blackout_end, metadata, created_at, updated_at)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
-             ON CONFLICT(id) DO UPDATE SET workflow_id = excluded.workflow_id, kind = excluded.kind, enabled = excluded.enabled, configuration = excluded.configuration, next_execution = excluded.next_execution, blackout_start = excluded.blackout_start, blackout_end = excluded.blackout_end, metadata = excluded.metadata, updated_at = excluded.updated_at
+            "INSERT INTO workflow_triggers (id, workflow_id, kind, enabled, configuration, next_execution, blackout_start, blackout_end, metadata, webhook_token_hash, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT(id) DO UPDATE SET workflow_id = excluded.workflow_id, kind = excluded.kind, enabled = excluded.enabled, configuration = excluded.configuration, next_execution = excluded.next_execution, blackout_start = excluded.blackout_start, blackout_end = excluded.blackout_end, metadata = excluded.metadata, webhook_token_hash = excluded.webhook_token_hash, updated_at = excluded.updated_at
              RETURNING id, workflow_id, kind, enabled, configuration, next_execution, blackout_start, blackout_end, metadata, created_at, updated_at",
         ))
         .bind(trigger_id)
@@ -1098,6 +1387,7 @@ where
         .bind(trigger.blackout_start.map(|dt| dt.timestamp()))
         .bind(trigger.blackout_end.map(|dt| dt.timestamp()))
         .bind(trigger.metadata.to_string())
+        .bind(webhook_token_hash(trigger))
         .bind(trigger.created_at.map(|dt| dt.timestamp()).unwrap_or(now))
         .bind(now)
         .fetch_one(self.pool())
@@ -1127,6 +1417,37 @@ where
         Ok(row.map(|row| mappers::row_to_workflow_trigger(&row)))
     }
 
+    async fn fetch_enabled_workflow_triggers_by_kind(
+        &self,
+        kind: WorkflowTriggerKind,
+    ) -> Result<Vec<WorkflowTrigger>, SendableError> {
+        let sql = self.render(&format!(
+            "SELECT id, workflow_id, kind, enabled, configuration, next_execution, blackout_start, blackout_end, metadata, created_at, updated_at FROM workflow_triggers WHERE enabled = {} AND kind = ? ORDER BY created_at, id",
+            queries::bool_true(self.dialect()),
+        ));
+        let rows = sqlx::query(&sql)
+            .bind(kind.as_str())
+            .fetch_all(self.pool())
+            .await?;
+        Ok(rows.iter().map(mappers::row_to_workflow_trigger).collect())
+    }
+
+    async fn fetch_enabled_webhook_trigger(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<WorkflowTrigger>, SendableError> {
+        let sql = self.render(&format!(
+            "SELECT id, workflow_id, kind, enabled, configuration, next_execution, blackout_start, blackout_end, metadata, created_at, updated_at FROM workflow_triggers WHERE webhook_token_hash = ? AND enabled = {} AND kind = ?",
+            queries::bool_true(self.dialect()),
+        ));
+        let row = sqlx::query(&sql)
+            .bind(token_hash)
+            .bind(WorkflowTriggerKind::Webhook.as_str())
+            .fetch_optional(self.pool())
+            .await?;
+        Ok(row.map(|row| mappers::row_to_workflow_trigger(&row)))
+    }
+
     async fn delete_workflow_trigger(&self, trigger_id: Uuid) -> Result<(), SendableError> {
         self.pool()
             .execute(
@@ -1403,6 +1724,16 @@ where
         ));
         let update_next_sql = self
             .render("UPDATE pipeline_triggers SET next_execution = ?, updated_at = ? WHERE id = ?");
+        let park_sql = self.render(
+            "UPDATE pipeline_triggers SET next_execution = ?, metadata = ?, updated_at = ? WHERE id = ?",
+        );
+        let clear_error_sql =
+            self.render("UPDATE pipeline_triggers SET metadata = ?, updated_at = ? WHERE id = ?");
+        // holiday calendars load once per batch, and only when a due trigger references one.
+        let calendars_sql = self.render(&format!(
+            "SELECT {BLACKOUT_CALENDAR_COLUMNS} FROM blackout_calendars"
+        ));
+        let mut calendars: Option<Vec<BlackoutCalendar>> = None;
 
         let mut runs = Vec::new();
         for row in rows {
@@ -1416,116 +1747,186 @@ where
                 .and_then(Value::as_str)
                 .unwrap_or_default();
 
-            if trigger.next_execution.is_none() {
-                trigger.next_execution = Some(next_execution_for_cron(cron_schedule, now)?);
-                sqlx::query(&update_next_sql)
-                    .bind(trigger.next_execution.map(|dt| dt.timestamp()))
+            // corrupt crons are parked and reported as in the workflow trigger loop below.
+            let next_execution = match next_execution_for_configuration(
+                &trigger.configuration,
+                trigger_id,
+                now,
+            ) {
+                Ok(next_execution) => next_execution,
+                Err(err) => {
+                    let message = err.to_string();
+                    let first_seen =
+                        mark_schedule_error(&mut trigger.metadata, cron_schedule, &message, now);
+                    sqlx::query(&park_sql)
+                        .bind(now.timestamp() + SCHEDULE_ERROR_RECHECK_SECS)
+                        .bind(trigger.metadata.to_string())
+                        .bind(now.timestamp())
+                        .bind(trigger_id)
+                        .execute(&mut *tx)
+                        .await?;
+                    if first_seen {
+                        warn!(
+                            "pipeline trigger {trigger_id} has an invalid cron '{cron_schedule}': {message}"
+                        );
+                        sqlx::query(&self.render(SCHEDULE_ERROR_NOTIFICATION_SQL))
+                            .bind(Uuid::now_v7())
+                            .bind("ui")
+                            .bind("warning")
+                            .bind("Trigger schedule is invalid")
+                            .bind(format!(
+                                "pipeline trigger {trigger_id} cron '{cron_schedule}' cannot be parsed: {message}"
+                            ))
+                            .bind(
+                                runinator_models::json!({
+                                    "trigger_id": trigger_id,
+                                    "pipeline_id": trigger.pipeline_id,
+                                    "cron": cron_schedule,
+                                    "error": message,
+                                })
+                                .to_string(),
+                            )
+                            .bind(now.timestamp())
+                            .execute(&mut *tx)
+                            .await?;
+                    }
+                    continue;
+                }
+            };
+            if clear_schedule_error(&mut trigger.metadata) {
+                sqlx::query(&clear_error_sql)
+                    .bind(trigger.metadata.to_string())
                     .bind(now.timestamp())
                     .bind(trigger_id)
                     .execute(&mut *tx)
                     .await?;
-                continue;
             }
 
-            if is_pipeline_trigger_in_blackout(&trigger, now) {
-                if let Some(end) = trigger.blackout_end {
-                    sqlx::query(&update_next_sql)
-                        .bind(end.timestamp())
-                        .bind(now.timestamp())
-                        .bind(trigger_id)
-                        .execute(&mut *tx)
-                        .await?;
-                }
+            let Some(scheduled_at) = trigger.next_execution else {
+                sqlx::query(&update_next_sql)
+                    .bind(next_execution.timestamp())
+                    .bind(now.timestamp())
+                    .bind(trigger_id)
+                    .execute(&mut *tx)
+                    .await?;
                 continue;
-            }
+            };
 
-            let fire_key = trigger
-                .next_execution
-                .map(|dt| dt.timestamp().to_string())
-                .unwrap_or_else(|| "initial".into());
-            let insert = sqlx::query(&firing_sql)
-                .bind(Uuid::now_v7())
-                .bind(trigger_id)
-                .bind(fire_key.as_str())
-                .bind(scheduler_id.as_str())
-                .bind(now.timestamp())
-                .execute(&mut *tx)
-                .await?;
-            if insert.affected() == 0 {
+            if calendars.is_none() && references_calendar(&trigger.configuration) {
+                let calendar_rows = sqlx::query(&calendars_sql).fetch_all(&mut *tx).await?;
+                calendars = Some(
+                    calendar_rows
+                        .iter()
+                        .map(mappers::row_to_blackout_calendar)
+                        .collect(),
+                );
+            }
+            if let Some(end) = trigger_blackout_until(
+                &trigger.configuration,
+                trigger.blackout_start,
+                trigger.blackout_end,
+                calendars.as_deref().unwrap_or_default(),
+                now,
+            ) {
+                sqlx::query(&update_next_sql)
+                    .bind(end.timestamp())
+                    .bind(now.timestamp())
+                    .bind(trigger_id)
+                    .execute(&mut *tx)
+                    .await?;
                 continue;
             }
 
-            let pipeline_row = sqlx::query(&self.render(&format!(
-                "SELECT {PIPELINE_COLUMNS} FROM pipelines WHERE id = ?"
-            )))
-            .bind(trigger.pipeline_id)
-            .fetch_one(&mut *tx)
-            .await?;
-            let pipeline_snapshot = mappers::row_to_pipeline(&pipeline_row);
-            let new_run_id = Uuid::now_v7();
-            let snapshot_json = serde_json::to_string(&pipeline_snapshot)?;
-            let parameters = pipeline_trigger_parameters(&trigger).to_string();
-            let state = pipeline_trigger_state(&trigger).to_string();
-            let run_row = if self.dialect() == SqlDialect::MySql {
-                sqlx::query(&self.render(
-                    "INSERT INTO pipeline_runs (id, pipeline_id, pipeline_snapshot, status, parameters, state, created_at, trigger_source_kind, trigger_actor_type, trigger_actor_replica_id, trigger_actor_display_name, trigger_metadata) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, NULL, ?, ?)",
-                ))
-                .bind(new_run_id)
-                .bind(trigger.pipeline_id)
-                .bind(&snapshot_json)
-                .bind(WorkflowStatus::Queued.as_str())
-                .bind(&parameters)
-                .bind(&state)
-                .bind(now.timestamp())
-                .bind("cron")
-                .bind("replica")
-                .bind(scheduler_id.as_str())
-                .bind(trigger.metadata.to_string())
-                .execute(&mut *tx)
-                .await?;
-                sqlx::query(&self.render(&format!(
-                    "SELECT {PIPELINE_RUN_COLUMNS} FROM pipeline_runs WHERE id = ?"
-                )))
-                .bind(new_run_id)
-                .fetch_one(&mut *tx)
-                .await?
-            } else {
-                sqlx::query(&self.render(&format!(
-                    "INSERT INTO pipeline_runs (id, pipeline_id, pipeline_snapshot, status, parameters, state, created_at, trigger_source_kind, trigger_actor_type, trigger_actor_replica_id, trigger_actor_display_name, trigger_metadata)
-                     VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, NULL, ?, ?)
-                     RETURNING {PIPELINE_RUN_COLUMNS}",
+            // downtime can leave a trigger several slots behind; its misfire policy picks which of
+            // them fire. a skipped trigger just moves on to its next slot.
+            let slots = misfire_slots(&trigger.configuration, trigger_id, scheduled_at, now)
+                .unwrap_or_else(|_| vec![scheduled_at]);
+
+            for slot in slots {
+                let fire_key = slot.timestamp().to_string();
+                let insert = sqlx::query(&firing_sql)
+                    .bind(Uuid::now_v7())
+                    .bind(trigger_id)
+                    .bind(fire_key.as_str())
+                    .bind(scheduler_id.as_str())
+                    .bind(now.timestamp())
+                    .execute(&mut *tx)
+                    .await?;
+                if insert.affected() == 0 {
+                    continue;
+                }
+
+                let pipeline_row = sqlx::query(&self.render(&format!(
+                    "SELECT {PIPELINE_COLUMNS} FROM pipelines WHERE id = ?"
                 )))
-                .bind(new_run_id)
                 .bind(trigger.pipeline_id)
-                .bind(&snapshot_json)
-                .bind(WorkflowStatus::Queued.as_str())
-                .bind(&parameters)
-                .bind(&state)
-                .bind(now.timestamp())
-                .bind("cron")
-                .bind("replica")
-                .bind(scheduler_id.as_str())
-                .bind(trigger.metadata.to_string())
                 .fetch_one(&mut *tx)
-                .await?
-            };
-            let run = mappers::row_to_pipeline_run(&run_row);
-
-            sqlx::query(&self.render("UPDATE pipeline_trigger_firings SET pipeline_run_id = ? WHERE trigger_id = ? AND fire_key = ?"))
-                .bind(run.id)
-                .bind(trigger_id)
-                .bind(fire_key.as_str())
-                .execute(&mut *tx)
                 .await?;
+                let pipeline_snapshot = mappers::row_to_pipeline(&pipeline_row);
+                let new_run_id = Uuid::now_v7();
+                let snapshot_json = serde_json::to_string(&pipeline_snapshot)?;
+                let parameters = pipeline_trigger_parameters(&trigger).to_string();
+                let state = pipeline_trigger_state(&trigger).to_string();
+                let run_row = if self.dialect() == SqlDialect::MySql {
+                    sqlx::query(&self.render(
+                        "INSERT INTO pipeline_runs (id, pipeline_id, pipeline_snapshot, status, parameters, state, created_at, trigger_source_kind, trigger_actor_type, trigger_actor_replica_id, trigger_actor_display_name, trigger_metadata) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, NULL, ?, ?)",
+                    ))
+                    .bind(new_run_id)
+                    .bind(trigger.pipeline_id)
+                    .bind(&snapshot_json)
+                    .bind(WorkflowStatus::Queued.as_str())
+                    .bind(&parameters)
+                    .bind(&state)
+                    .bind(now.timestamp())
+                    .bind("cron")
+                    .bind("replica")
+                    .bind(scheduler_id.as_str())
+                    .bind(trigger.metadata.to_string())
+                    .execute(&mut *tx)
+                    .await?;
+                    sqlx::query(&self.render(&format!(
+                        "SELECT {PIPELINE_RUN_COLUMNS} FROM pipeline_runs WHERE id = ?"
+                    )))
+                    .bind(new_run_id)
+                    .fetch_one(&mut *tx)
+                    .await?
+                } else {
+                    sqlx::query(&self.render(&format!(
+                        "INSERT INTO pipeline_runs (id, pipeline_id, pipeline_snapshot, status, parameters, state, created_at, trigger_source_kind, trigger_actor_type, trigger_actor_replica_id, trigger_actor_display_name, trigger_metadata)
+                         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, NULL, ?, ?)
+                         RETURNING {PIPELINE_RUN_COLUMNS}",
+                    )))
+                    .bind(new_run_id)
+                    .bind(trigger.pipeline_id)
+                    .bind(&snapshot_json)
+                    .bind(WorkflowStatus::Queued.as_str())
+                    .bind(&parameters)
+                    .bind(&state)
+                    .bind(now.timestamp())
+                    .bind("cron")
+                    .bind("replica")
+                    .bind(scheduler_id.as_str())
+                    .bind(trigger.metadata.to_string())
+                    .fetch_one(&mut *tx)
+                    .await?
+                };
+                let run = mappers::row_to_pipeline_run(&run_row);
+
+                sqlx::query(&self.render("UPDATE pipeline_trigger_firings SET pipeline_run_id = ? WHERE trigger_id = ? AND fire_key = ?"))
+                    .bind(run.id)
+                    .bind(trigger_id)
+                    .bind(fire_key.as_str())
+                    .execute(&mut *tx)
+                    .await?;
+                runs.push(run);
+            }
 
-            let next_execution = next_execution_for_cron(cron_schedule, now)?;
             sqlx::query(&update_next_sql)
                 .bind(next_execution.timestamp())
                 .bind(now.timestamp())
                 .bind(trigger_id)
                 .execute(&mut *tx)
                 .await?;
-            runs.push(run);
         }
 
         tx.commit().await?;
@@ -1639,7 +2040,7 @@ where
             "SELECT {PIPELINE_RUN_COLUMNS} FROM pipeline_runs ORDER BY created_at DESC, id DESC LIMIT ?"
         )))
         .bind(limit.max(1))
-        .fetch_all(self.pool())
+        .fetch_all(self.read_pool())
         .await?;
         Ok(rows.iter().map(mappers::row_to_pipeline_run).collect())
     }
@@ -1652,7 +2053,7 @@ where
             "SELECT {PIPELINE_RUN_COLUMNS} FROM pipeline_runs WHERE pipeline_id = ? ORDER BY created_at DESC, id DESC"
         )))
         .bind(pipeline_id)
-        .fetch_all(self.pool())
+        .fetch_all(self.read_pool())
         .await?;
         Ok(rows.iter().map(mappers::row_to_pipeline_run).collect())
     }
@@ -1717,15 +2118,18 @@ where
     async fn fetch_due_workflow_triggers(
         &self,
         now: DateTime<Utc>,
+        limit: Option<i64>,
     ) -> Result<Vec<WorkflowTrigger>, SendableError> {
         let sql = self.render(&format!(
-            "SELECT id, workflow_id, kind, enabled, configuration, next_execution, blackout_start, blackout_end, metadata, created_at, updated_at FROM workflow_triggers WHERE enabled = {} AND kind = 'cron' AND (next_execution IS NULL OR next_execution <= ?) ORDER BY COALESCE(next_execution, 0), id",
+            "SELECT id, workflow_id, kind, enabled, configuration, next_execution, blackout_start, blackout_end, metadata, created_at, updated_at FROM workflow_triggers WHERE enabled = {} AND kind = 'cron' AND (next_execution IS NULL OR next_execution <= ?) ORDER BY COALESCE(next_execution, 0), id{}",
             queries::bool_true(self.dialect()),
+            if limit.is_some() { " LIMIT ?" } else { "" },
         ));
-        let rows = sqlx::query(&sql)
-            .bind(now.timestamp())
-            .fetch_all(self.pool())
-            .await?;
+        let mut query = sqlx::query(&sql).bind(now.timestamp());
+        if let Some(limit) = limit {
+            query = query.bind(limit.max(1));
+        }
+        let rows = query.fetch_all(self.pool()).await?;
         Ok(rows.iter().map(mappers::row_to_workflow_trigger).collect())
     }
 
@@ -1775,6 +2179,16 @@ where
         ));
         let update_next_sql = self
             .render("UPDATE workflow_triggers SET next_execution = ?, updated_at = ? WHERE id = ?");
+        let park_sql = self.render(
+            "UPDATE workflow_triggers SET next_execution = ?, metadata = ?, updated_at = ? WHERE id = ?",
+        );
+        let clear_error_sql =
+            self.render("UPDATE workflow_triggers SET metadata = ?, updated_at = ? WHERE id = ?");
+        // holiday calendars load once per batch, and only when a due trigger references one.
+        let calendars_sql = self.render(&format!(
+            "SELECT {BLACKOUT_CALENDAR_COLUMNS} FROM blackout_calendars"
+        ));
+        let mut calendars: Option<Vec<BlackoutCalendar>> = None;
 
         let mut runs = Vec::new();
         for row in rows {
@@ -1788,117 +2202,334 @@ where
                 .and_then(Value::as_str)
                 .unwrap_or_default();
 
-            if trigger.next_execution.is_none() {
-                trigger.next_execution = Some(next_execution_for_cron(cron_schedule, now)?);
-                sqlx::query(&update_next_sql)
-                    .bind(trigger.next_execution.map(|dt| dt.timestamp()))
+            // a corrupt cron parks the trigger instead of failing the whole batch every tick; the error
+            // is recorded once in its metadata (and notified) and cleared when the schedule parses again.
+            let next_execution = match next_execution_for_configuration(
+                &trigger.configuration,
+                trigger_id,
+                now,
+            ) {
+                Ok(next_execution) => next_execution,
+                Err(err) => {
+                    let message = err.to_string();
+                    let first_seen =
+                        mark_schedule_error(&mut trigger.metadata, cron_schedule, &message, now);
+                    sqlx::query(&park_sql)
+                        .bind(now.timestamp() + SCHEDULE_ERROR_RECHECK_SECS)
+                        .bind(trigger.metadata.to_string())
+                        .bind(now.timestamp())
+                        .bind(trigger_id)
+                        .execute(&mut *tx)
+                        .await?;
+                    if first_seen {
+                        warn!(
+                            "workflow trigger {trigger_id} has an invalid cron '{cron_schedule}': {message}"
+                        );
+                        sqlx::query(&self.render(SCHEDULE_ERROR_NOTIFICATION_SQL))
+                            .bind(Uuid::now_v7())
+                            .bind("ui")
+                            .bind("warning")
+                            .bind("Trigger schedule is invalid")
+                            .bind(format!(
+                                "workflow trigger {trigger_id} cron '{cron_schedule}' cannot be parsed: {message}"
+                            ))
+                            .bind(
+                                runinator_models::json!({
+                                    "trigger_id": trigger_id,
+                                    "workflow_id": trigger.workflow_id,
+                                    "cron": cron_schedule,
+                                    "error": message,
+                                })
+                                .to_string(),
+                            )
+                            .bind(now.timestamp())
+                            .execute(&mut *tx)
+                            .await?;
+                    }
+                    continue;
+                }
+            };
+            if clear_schedule_error(&mut trigger.metadata) {
+                sqlx::query(&clear_error_sql)
+                    .bind(trigger.metadata.to_string())
                     .bind(now.timestamp())
                     .bind(trigger_id)
                     .execute(&mut *tx)
                     .await?;
-                continue;
             }
 
-            if is_trigger_in_blackout(&trigger, now) {
-                if let Some(end) = trigger.blackout_end {
-                    sqlx::query(&update_next_sql)
-                        .bind(end.timestamp())
-                        .bind(now.timestamp())
-                        .bind(trigger_id)
-                        .execute(&mut *tx)
-                        .await?;
-                }
+            let Some(scheduled_at) = trigger.next_execution else {
+                sqlx::query(&update_next_sql)
+                    .bind(next_execution.timestamp())
+                    .bind(now.timestamp())
+                    .bind(trigger_id)
+                    .execute(&mut *tx)
+                    .await?;
                 continue;
-            }
+            };
 
-            let fire_key = trigger
-                .next_execution
-                .map(|dt| dt.timestamp().to_string())
-                .unwrap_or_else(|| "initial".into());
-            let insert = sqlx::query(&firing_sql)
-                .bind(Uuid::now_v7())
-                .bind(trigger_id)
-                .bind(fire_key.as_str())
-                .bind(scheduler_id.as_str())
-                .bind(now.timestamp())
-                .execute(&mut *tx)
-                .await?;
-            if insert.affected() == 0 {
+            if calendars.is_none() && references_calendar(&trigger.configuration) {
+                let calendar_rows = sqlx::query(&calendars_sql).fetch_all(&mut *tx).await?;
+                calendars = Some(
+                    calendar_rows
+                        .iter()
+                        .map(mappers::row_to_blackout_calendar)
+                        .collect(),
+                );
+            }
+            if let Some(end) = trigger_blackout_until(
+                &trigger.configuration,
+                trigger.blackout_start,
+                trigger.blackout_end,
+                calendars.as_deref().unwrap_or_default(),
+                now,
+            ) {
+                sqlx::query(&update_next_sql)
+                    .bind(end.timestamp())
+                    .bind(now.timestamp())
+                    .bind(trigger_id)
+                    .execute(&mut *tx)
+                    .await?;
                 continue;
             }
 
-            let workflow_row = sqlx::query(&self.render("SELECT id, name, namespace, org_id, version, enabled, input_schema, definition, created_at, updated_at FROM workflows WHERE id = ?"))
-                .bind(trigger.workflow_id)
-                .fetch_one(&mut *tx)
-                .await?;
-            let workflow_snapshot = mappers::row_to_workflow(&workflow_row);
-            let new_run_id = Uuid::now_v7();
-            let run_row = if self.dialect() == SqlDialect::MySql {
-                sqlx::query(&self.render(
-                    "INSERT INTO workflow_runs (id, workflow_id, workflow_snapshot, status, active_node_id, parameters, state, created_at, name, trigger_source_kind, trigger_actor_type, trigger_actor_replica_id, trigger_actor_display_name, trigger_request_host, trigger_request_ip, trigger_metadata) VALUES (?, ?, ?, ?, NULL, ?, ?, ?, NULL, ?, ?, NULL, ?, NULL, NULL, ?)",
-                ))
-                .bind(new_run_id)
-                .bind(trigger.workflow_id)
-                .bind(serde_json::to_string(&workflow_snapshot)?)
-                .bind(WorkflowStatus::Queued.as_str())
-                .bind(trigger_parameters(&trigger).to_string())
-                .bind(trigger_state(&trigger).to_string())
-                .bind(now.timestamp())
-                .bind("cron")
-                .bind("replica")
-                .bind(scheduler_id.as_str())
-                .bind(trigger.metadata.to_string())
-                .execute(&mut *tx)
-                .await?;
-                sqlx::query(&self.render(&format!(
-                    "SELECT {WORKFLOW_RUN_COLUMNS} FROM workflow_runs WHERE id = ?"
-                )))
-                .bind(new_run_id)
-                .fetch_one(&mut *tx)
-                .await?
-            } else {
-                sqlx::query(&self.render(&format!(
-                    "INSERT INTO workflow_runs (id, workflow_id, workflow_snapshot, status, active_node_id, parameters, state, created_at, name, trigger_source_kind, trigger_actor_type, trigger_actor_replica_id, trigger_actor_display_name, trigger_request_host, trigger_request_ip, trigger_metadata)
-                     VALUES (?, ?, ?, ?, NULL, ?, ?, ?, NULL, ?, ?, NULL, ?, NULL, NULL, ?)
-                     RETURNING {WORKFLOW_RUN_COLUMNS}",
-                )))
-                .bind(new_run_id)
-                .bind(trigger.workflow_id)
-                .bind(serde_json::to_string(&workflow_snapshot)?)
-                .bind(WorkflowStatus::Queued.as_str())
-                .bind(trigger_parameters(&trigger).to_string())
-                .bind(trigger_state(&trigger).to_string())
-                .bind(now.timestamp())
-                .bind("cron")
-                .bind("replica")
-                .bind(scheduler_id.as_str())
-                .bind(trigger.metadata.to_string())
-                .fetch_one(&mut *tx)
-                .await?
-            };
-            let run = mappers::row_to_workflow_run(&run_row);
+            // downtime can leave a trigger several slots behind; its misfire policy picks which of
+            // them fire. a skipped trigger just moves on to its next slot.
+            let slots = misfire_slots(&trigger.configuration, trigger_id, scheduled_at, now)
+                .unwrap_or_else(|_| vec![scheduled_at]);
+
+            // each firing marker (trigger id + scheduled slot, never the wall clock) commits with the
+            // run it creates, so a slot fires exactly once across schedulers and restarts.
+            for slot in slots {
+                let fire_key = slot.timestamp().to_string();
+                let insert = sqlx::query(&firing_sql)
+                    .bind(Uuid::now_v7())
+                    .bind(trigger_id)
+                    .bind(fire_key.as_str())
+                    .bind(scheduler_id.as_str())
+                    .bind(now.timestamp())
+                    .execute(&mut *tx)
+                    .await?;
+                if insert.affected() == 0 {
+                    continue;
+                }
 
-            sqlx::query(&self.render("UPDATE workflow_trigger_firings SET workflow_run_id = ? WHERE trigger_id = ? AND fire_key = ?"))
-                .bind(run.id)
-                .bind(trigger_id)
-                .bind(fire_key.as_str())
-                .execute(&mut *tx)
-                .await?;
+                let workflow_row = sqlx::query(&self.render("SELECT id, name, namespace, org_id, version, enabled, input_schema, definition, created_at, updated_at FROM workflows WHERE id = ?"))
+                    .bind(trigger.workflow_id)
+                    .fetch_one(&mut *tx)
+                    .await?;
+                let workflow_snapshot = mappers::row_to_workflow(&workflow_row);
+                let new_run_id = Uuid::now_v7();
+                let run_row = if self.dialect() == SqlDialect::MySql {
+                    sqlx::query(&self.render(
+                        "INSERT INTO workflow_runs (id, workflow_id, workflow_snapshot, status, active_node_id, parameters, state, created_at, name, trigger_source_kind, trigger_actor_type, trigger_actor_replica_id, trigger_actor_display_name, trigger_request_host, trigger_request_ip, trigger_metadata) VALUES (?, ?, ?, ?, NULL, ?, ?, ?, NULL, ?, ?, NULL, ?, NULL, NULL, ?)",
+                    ))
+                    .bind(new_run_id)
+                    .bind(trigger.workflow_id)
+                    .bind(serde_json::to_string(&workflow_snapshot)?)
+                    .bind(WorkflowStatus::Queued.as_str())
+                    .bind(trigger_parameters(&trigger).to_string())
+                    .bind(trigger_state(&trigger).to_string())
+                    .bind(now.timestamp())
+                    .bind("cron")
+                    .bind("replica")
+                    .bind(scheduler_id.as_str())
+                    .bind(trigger.metadata.to_string())
+                    .execute(&mut *tx)
+                    .await?;
+                    sqlx::query(&self.render(&format!(
+                        "SELECT {WORKFLOW_RUN_COLUMNS} FROM workflow_runs WHERE id = ?"
+                    )))
+                    .bind(new_run_id)
+                    .fetch_one(&mut *tx)
+                    .await?
+                } else {
+                    sqlx::query(&self.render(&format!(
+                        "INSERT INTO workflow_runs (id, workflow_id, workflow_snapshot, status, active_node_id, parameters, state, created_at, name, trigger_source_kind, trigger_actor_type, trigger_actor_replica_id, trigger_actor_display_name, trigger_request_host, trigger_request_ip, trigger_metadata)
+                         VALUES (?, ?, ?, ?, NULL, ?, ?, ?, NULL, ?, ?, NULL, ?, NULL, NULL, ?)
+                         RETURNING {WORKFLOW_RUN_COLUMNS}",
+                    )))
+                    .bind(new_run_id)
+                    .bind(trigger.workflow_id)
+                    .bind(serde_json::to_string(&workflow_snapshot)?)
+                    .bind(WorkflowStatus::Queued.as_str())
+                    .bind(trigger_parameters(&trigger).to_string())
+                    .bind(trigger_state(&trigger).to_string())
+                    .bind(now.timestamp())
+                    .bind("cron")
+                    .bind("replica")
+                    .bind(scheduler_id.as_str())
+                    .bind(trigger.metadata.to_string())
+                    .fetch_one(&mut *tx)
+                    .await?
+                };
+                let run = mappers::row_to_workflow_run(&run_row);
+
+                sqlx::query(&self.render("UPDATE workflow_trigger_firings SET workflow_run_id = ? WHERE trigger_id = ? AND fire_key = ?"))
+                    .bind(run.id)
+                    .bind(trigger_id)
+                    .bind(fire_key.as_str())
+                    .execute(&mut *tx)
+                    .await?;
+                runs.push(run);
+            }
 
-            let next_execution = next_execution_for_cron(cron_schedule, now)?;
             sqlx::query(&update_next_sql)
                 .bind(next_execution.timestamp())
                 .bind(now.timestamp())
                 .bind(trigger_id)
                 .execute(&mut *tx)
                 .await?;
-            runs.push(run);
         }
 
         tx.commit().await?;
         Ok(runs)
     }
 
+    async fn reconcile_trigger_schedules(
+        &self,
+        now: DateTime<Utc>,
+        dry_run: bool,
+    ) -> Result<ScheduleReconcileReport, SendableError> {
+        let workflow_rows = sqlx::query(&self.render(&format!(
+            "SELECT id, workflow_id, kind, enabled, configuration, next_execution, blackout_start, blackout_end, metadata, created_at, updated_at FROM workflow_triggers WHERE enabled = {} AND kind = 'cron' ORDER BY id",
+            queries::bool_true(self.dialect()),
+        )))
+        .fetch_all(self.pool())
+        .await?;
+        let pipeline_rows = sqlx::query(&self.render(&format!(
+            "SELECT {PIPELINE_TRIGGER_COLUMNS} FROM pipeline_triggers WHERE enabled = {} AND kind = 'cron' ORDER BY id",
+            queries::bool_true(self.dialect()),
+        )))
+        .fetch_all(self.pool())
+        .await?;
+
+        // (table, trigger type, trigger id, target id, configuration, stored next_execution)
+        let candidates = workflow_rows
+            .iter()
+            .map(mappers::row_to_workflow_trigger)
+            .map(|trigger| {
+                (
+                    "workflow_triggers",
+                    "workflow",
+                    trigger.id,
+                    trigger.workflow_id,
+                    trigger.configuration,
+                    trigger.next_execution,
+                )
+            })
+            .chain(
+                pipeline_rows
+                    .iter()
+                    .map(mappers::row_to_pipeline_trigger)
+                    .map(|trigger| {
+                        (
+                            "pipeline_triggers",
+                            "pipeline",
+                            trigger.id,
+                            trigger.pipeline_id,
+                            trigger.configuration,
+                            trigger.next_execution,
+                        )
+                    }),
+            )
+            .collect::<Vec<_>>();
+
+        let mut report = ScheduleReconcileReport {
+            dry_run,
+            checked: candidates.len(),
+            changes: Vec::new(),
+        };
+        for (table, trigger_type, trigger_id, target_id, configuration, current) in candidates {
+            let Some(trigger_id) = trigger_id else {
+                continue;
+            };
+            let cron = configuration
+                .get("cron")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string();
+            let (proposed, error) =
+                match next_execution_for_configuration(&configuration, trigger_id, now) {
+                    Ok(next) if current == Some(next) => continue,
+                    Ok(next) => (Some(next), None),
+                    Err(err) => (None, Some(err.to_string())),
+                };
+            if let (Some(next), false) = (proposed, dry_run) {
+                sqlx::query(&self.render(&format!(
+                    "UPDATE {table} SET next_execution = ?, updated_at = ? WHERE id = ?"
+                )))
+                .bind(next.timestamp())
+                .bind(now.timestamp())
+                .bind(trigger_id)
+                .execute(self.pool())
+                .await?;
+            }
+            report.changes.push(ScheduleReconciliation {
+                trigger_type: trigger_type.to_string(),
+                trigger_id,
+                target_id,
+                cron,
+                current_next_execution: current,
+                proposed_next_execution: proposed,
+                error,
+            });
+        }
+        Ok(report)
+    }
+
+    async fn set_workflow_trigger_crons(
+        &self,
+        crons: Vec<(Uuid, String)>,
+        now: DateTime<Utc>,
+        dry_run: bool,
+    ) -> Result<Vec<(Uuid, DateTime<Utc>)>, SendableError> {
+        let select_sql = self.render(
+            "SELECT id, workflow_id, kind, enabled, configuration, next_execution, blackout_start, blackout_end, metadata, created_at, updated_at FROM workflow_triggers WHERE id = ?",
+        );
+        let update_sql = self.render(
+            "UPDATE workflow_triggers SET configuration = ?, next_execution = ?, metadata = ?, updated_at = ? WHERE id = ?",
+        );
+        let mut tx = self.pool().begin().await?;
+        let mut scheduled = Vec::with_capacity(crons.len());
+        for (trigger_id, cron) in crons {
+            let Some(row) = sqlx::query(&select_sql)
+                .bind(trigger_id)
+                .fetch_optional(&mut *tx)
+                .await?
+            else {
+                return Err(Box::new(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    format!("Workflow trigger {trigger_id} not found"),
+                )));
+            };
+            let mut trigger = mappers::row_to_workflow_trigger(&row);
+            match trigger.configuration.as_object_mut() {
+                Some(configuration) => {
+                    configuration.insert("cron".into(), Value::String(cron));
+                }
+                None => trigger.configuration = runinator_models::json!({ "cron": cron }),
+            }
+            // the rewritten cron keeps the trigger's zone and jitter.
+            let next_execution =
+                next_execution_for_configuration(&trigger.configuration, trigger_id, now)?;
+            clear_schedule_error(&mut trigger.metadata);
+            if !dry_run {
+                sqlx::query(&update_sql)
+                    .bind(trigger.configuration.to_string())
+                    .bind(next_execution.timestamp())
+                    .bind(trigger.metadata.to_string())
+                    .bind(now.timestamp())
+                    .bind(trigger_id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            scheduled.push((trigger_id, next_execution));
+        }
+        tx.commit().await?;
+        Ok(scheduled)
+    }
+
     async fn try_record_trigger_firing(
         &self,
         trigger_id: Uuid,
@@ -2133,17 +2764,122 @@ where
         Ok(rows.iter().map(mappers::row_to_workflow_run).collect())
     }
 
+    async fn fetch_workflow_runs_since(
+        &self,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<WorkflowRun>, SendableError> {
+        let rows = sqlx::query(&self.render(&format!(
+            "SELECT {WORKFLOW_RUN_COLUMNS} FROM workflow_runs WHERE created_at >= ? ORDER BY created_at, id"
+        )))
+        .bind(since.timestamp())
+        .fetch_all(self.read_pool())
+        .await?;
+        Ok(rows.iter().map(mappers::row_to_workflow_run).collect())
+    }
+
     async fn fetch_workflow_runs_for_workflow(
         &self,
         workflow_id: Uuid,
     ) -> Result<Vec<WorkflowRun>, SendableError> {
         let rows = sqlx::query(&self.render(&format!("SELECT {WORKFLOW_RUN_COLUMNS} FROM workflow_runs WHERE workflow_id = ? ORDER BY created_at DESC, id DESC")))
             .bind(workflow_id)
-            .fetch_all(self.pool())
+            .fetch_all(self.read_pool())
             .await?;
         Ok(rows.iter().map(mappers::row_to_workflow_run).collect())
     }
 
+    async fn fetch_recent_workflow_runs_for_workflows(
+        &self,
+        workflow_ids: Vec<Uuid>,
+        per_workflow: i64,
+    ) -> Result<Vec<WorkflowRun>, SendableError> {
+        let mut runs = Vec::new();
+        // bounded batches keep the bind count under every dialect's parameter limit.
+        for batch in workflow_ids.chunks(ID_LIST_BATCH) {
+            let sql = self.render(&format!(
+                "SELECT {WORKFLOW_RUN_COLUMNS} FROM (
+                     SELECT {WORKFLOW_RUN_COLUMNS},
+                            ROW_NUMBER() OVER (PARTITION BY workflow_id ORDER BY created_at DESC, id DESC) AS run_rank
+                     FROM workflow_runs
+                     WHERE workflow_id IN ({ids})
+                 ) ranked
+                 WHERE run_rank <= ?
+                 ORDER BY workflow_id, created_at DESC, id DESC",
+                ids = queries::placeholders(batch.len()),
+            ));
+            let mut query = sqlx::query(&sql);
+            for workflow_id in batch {
+                query = query.bind(*workflow_id);
+            }
+            let rows = query
+                .bind(per_workflow.max(1))
+                .fetch_all(self.read_pool())
+                .await?;
+            runs.extend(rows.iter().map(mappers::row_to_workflow_run));
+        }
+        Ok(runs)
+    }
+
+    async fn fetch_last_workflow_success(
+        &self,
+        workflow_id: Uuid,
+    ) -> Result<Option<DateTime<Utc>>, SendableError> {
+        let row = sqlx::query(&self.render(
+            "SELECT finished_at FROM workflow_runs WHERE workflow_id = ? AND status = 'succeeded' AND finished_at IS NOT NULL ORDER BY finished_at DESC LIMIT 1",
+        ))
+        .bind(workflow_id)
+        .fetch_optional(self.pool())
+        .await?;
+        Ok(
+            row.and_then(|row| {
+                DateTime::<Utc>::from_timestamp(row.get::<i64, _>("finished_at"), 0)
+            }),
+        )
+    }
+
+    async fn try_record_missed_run_alert(
+        &self,
+        workflow_id: Uuid,
+        expected_by: DateTime<Utc>,
+    ) -> Result<bool, SendableError> {
+        let sql = self.render(&queries::insert_ignore(
+            self.dialect(),
+            "workflow_missed_run_alerts",
+            "workflow_id, expected_by, created_at",
+            "?, ?, ?",
+            "workflow_id, expected_by",
+            None,
+        ));
+        let insert = sqlx::query(&sql)
+            .bind(workflow_id)
+            .bind(expected_by.timestamp())
+            .bind(Utc::now().timestamp())
+            .execute(self.pool())
+            .await?;
+        Ok(insert.affected() > 0)
+    }
+
+    async fn count_earlier_open_workflow_runs(
+        &self,
+        run: &WorkflowRun,
+    ) -> Result<i64, SendableError> {
+        // runs created in the same second fall back to id order; run ids are v7 and sort by time.
+        let row = sqlx::query(&self.render(
+            "SELECT COUNT(*) AS open_count FROM workflow_runs
+             WHERE workflow_id = ? AND id <> ?
+               AND status NOT IN ('succeeded', 'failed', 'timed_out', 'canceled')
+               AND (created_at < ? OR (created_at = ? AND id < ?))",
+        ))
+        .bind(run.workflow_id)
+        .bind(run.id)
+        .bind(run.created_at.timestamp())
+        .bind(run.created_at.timestamp())
+        .bind(run.id)
+        .fetch_one(self.pool())
+        .await?;
+        Ok(row.get::<i64, _>("open_count"))
+    }
+
     async fn fetch_workflow_runs_by_name(
         &self,
         name: String,
@@ -2328,6 +3064,26 @@ where
         Ok(rows.iter().map(mappers::row_to_workflow_node_run).collect())
     }
 
+    async fn fetch_workflow_node_runs_for_runs(
+        &self,
+        workflow_run_ids: Vec<Uuid>,
+    ) -> Result<Vec<WorkflowNodeRun>, SendableError> {
+        let mut node_runs = Vec::new();
+        for batch in workflow_run_ids.chunks(ID_LIST_BATCH) {
+            let sql = self.render(&format!(
+                "SELECT {WORKFLOW_NODE_RUN_COLUMNS} FROM workflow_node_runs WHERE workflow_run_id IN ({ids}) ORDER BY workflow_run_id, created_at, id",
+                ids = queries::placeholders(batch.len()),
+            ));
+            let mut query = sqlx::query(&sql);
+            for workflow_run_id in batch {
+                query = query.bind(*workflow_run_id);
+            }
+            let rows = query.fetch_all(self.pool()).await?;
+            node_runs.extend(rows.iter().map(mappers::row_to_workflow_node_run));
+        }
+        Ok(node_runs)
+    }
+
     async fn fetch_workflow_node_run(
         &self,
         workflow_node_run_id: Uuid,
@@ -2354,6 +3110,109 @@ where
         Ok(rows.iter().map(mappers::row_to_workflow_node_run).collect())
     }
 
+    async fn fetch_queue_wait_samples(
+        &self,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<QueueWaitSample>, SendableError> {
+        let rows = sqlx::query(&self.render(
+            "SELECT r.workflow_id AS workflow_id, n.node_id AS node_id, n.queue_wait_ms AS queue_wait_ms, n.started_at AS started_at \
+             FROM workflow_node_runs n \
+             JOIN workflow_runs r ON n.workflow_run_id = r.id \
+             WHERE n.queue_wait_ms IS NOT NULL AND n.started_at >= ? \
+             ORDER BY n.started_at, n.id",
+        ))
+        .bind(since.timestamp())
+        .fetch_all(self.read_pool())
+        .await?;
+        Ok(rows
+            .iter()
+            .map(|row| QueueWaitSample {
+                workflow_id: row.get("workflow_id"),
+                node_id: row.get("node_id"),
+                queue_wait_ms: row.get("queue_wait_ms"),
+                started_at: DateTime::<Utc>::from_timestamp(row.get::<i64, _>("started_at"), 0)
+                    .unwrap_or_else(Utc::now),
+            })
+            .collect())
+    }
+
+    async fn fetch_active_node_runs(&self) -> Result<Vec<ActiveNodeRun>, SendableError> {
+        let rows = sqlx::query(&self.render(
+            "SELECT r.workflow_id AS workflow_id, n.workflow_run_id AS workflow_run_id, n.id AS id, n.node_id AS node_id, n.status AS status, n.created_at AS created_at, n.started_at AS started_at \
+             FROM workflow_node_runs n \
+             JOIN workflow_runs r ON n.workflow_run_id = r.id \
+             WHERE n.status IN ('queued', 'running') \
+             ORDER BY n.created_at, n.id",
+        ))
+        .fetch_all(self.pool())
+        .await?;
+        Ok(rows
+            .iter()
+            .map(|row| ActiveNodeRun {
+                workflow_id: row.get("workflow_id"),
+                workflow_run_id: row.get("workflow_run_id"),
+                node_run_id: row.get("id"),
+                node_id: row.get("node_id"),
+                status: WorkflowStatus::try_from(row.get::<String, _>("status").as_str())
+                    .unwrap_or(WorkflowStatus::Queued),
+                created_at: DateTime::<Utc>::from_timestamp(row.get::<i64, _>("created_at"), 0)
+                    .unwrap_or_else(Utc::now),
+                started_at: row
+                    .get::<Option<i64>, _>("started_at")
+                    .and_then(|ts| DateTime::<Utc>::from_timestamp(ts, 0)),
+            })
+            .collect())
+    }
+
+    async fn fetch_node_run_counts(
+        &self,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<NodeRunCounts>, SendableError> {
+        let dialect = self.dialect();
+        let finished = "n.started_at IS NOT NULL AND n.finished_at IS NOT NULL";
+        let rows = sqlx::query(&self.render(&format!(
+            "SELECT r.workflow_id AS workflow_id, n.node_id AS node_id, COUNT(*) AS runs, \
+             {succeeded} AS succeeded, {failed} AS failed, {timed_runs} AS timed_runs, \
+             {duration} AS duration_seconds \
+             FROM workflow_node_runs n \
+             JOIN workflow_runs r ON n.workflow_run_id = r.id \
+             WHERE n.created_at >= ? \
+             GROUP BY r.workflow_id, n.node_id",
+            succeeded = queries::sum_bigint(
+                dialect,
+                "CASE WHEN n.status = 'succeeded' THEN 1 ELSE 0 END"
+            ),
+            failed = queries::sum_bigint(
+                dialect,
+                "CASE WHEN n.status IN ('failed', 'timed_out') THEN 1 ELSE 0 END"
+            ),
+            timed_runs =
+                queries::sum_bigint(dialect, &format!("CASE WHEN {finished} THEN 1 ELSE 0 END")),
+            duration = queries::sum_bigint(
+                dialect,
+                &format!("CASE WHEN {finished} THEN n.finished_at - n.started_at ELSE 0 END")
+            ),
+        )))
+        .bind(since.timestamp())
+        .fetch_all(self.read_pool())
+        .await?;
+        let mut counts = Vec::with_capacity(rows.len());
+        for row in &rows {
+            // node run timestamps are stored in whole seconds.
+            let duration_seconds: i64 = row.try_get("duration_seconds")?;
+            counts.push(NodeRunCounts {
+                workflow_id: row.try_get("workflow_id")?,
+                node_id: row.try_get("node_id")?,
+                runs: row.try_get("runs")?,
+                succeeded: row.try_get("succeeded")?,
+                failed: row.try_get("failed")?,
+                timed_runs: row.try_get("timed_runs")?,
+                duration_ms: duration_seconds * 1000,
+            });
+        }
+        Ok(counts)
+    }
+
     async fn claim_workflow_node_run_executor(
         &self,
         node_run_id: Uuid,
@@ -2631,6 +3490,73 @@ where
             .collect())
     }
 
+    async fn record_workflow_run_outputs(
+        &self,
+        workflow_run_id: Uuid,
+        workflow_id: Uuid,
+        outputs: &BTreeMap<String, Value>,
+    ) -> Result<Vec<WorkflowRunOutput>, SendableError> {
+        let now = Utc::now().timestamp();
+        let upsert = format!(
+            "INSERT INTO workflow_run_outputs (workflow_run_id, workflow_id, name, value, created_at)
+             VALUES (?, ?, ?, ?, ?) {}",
+            queries::on_conflict_update(
+                self.dialect(),
+                "workflow_run_id, name",
+                &["value", "created_at"]
+            )
+        );
+        let mut tx = self.pool().begin().await?;
+        for (name, value) in outputs {
+            sqlx::query(&self.render(&upsert))
+                .bind(workflow_run_id)
+                .bind(workflow_id)
+                .bind(name.as_str())
+                .bind(value.to_string())
+                .bind(now)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+        self.fetch_workflow_run_outputs(workflow_run_id).await
+    }
+
+    async fn fetch_workflow_run_outputs(
+        &self,
+        workflow_run_id: Uuid,
+    ) -> Result<Vec<WorkflowRunOutput>, SendableError> {
+        let rows = sqlx::query(&self.render(
+            "SELECT workflow_run_id, name, value, created_at FROM workflow_run_outputs WHERE workflow_run_id = ? ORDER BY name ASC",
+        ))
+        .bind(workflow_run_id)
+        .fetch_all(self.pool())
+        .await?;
+        Ok(rows
+            .iter()
+            .map(mappers::row_to_workflow_run_output)
+            .collect())
+    }
+
+    async fn fetch_latest_workflow_output(
+        &self,
+        workflow_id: Uuid,
+        name: &str,
+    ) -> Result<Option<Value>, SendableError> {
+        let row = sqlx::query(&self.render(
+            "SELECT workflow_run_outputs.value FROM workflow_run_outputs
+             JOIN workflow_runs ON workflow_runs.id = workflow_run_outputs.workflow_run_id
+             WHERE workflow_run_outputs.workflow_id = ? AND workflow_run_outputs.name = ?
+               AND workflow_runs.status = 'succeeded'
+             ORDER BY workflow_runs.finished_at DESC, workflow_runs.id DESC
+             LIMIT 1",
+        ))
+        .bind(workflow_id)
+        .bind(name)
+        .fetch_optional(self.pool())
+        .await?;
+        Ok(row.map(|row| mappers::parse_json(row.get::<String, _>("value"))))
+    }
+
     async fn apply_workflow_result_event(
         &self,
         event: &WorkflowResultEvent,
@@ -2664,6 +3590,7 @@ where
                 status,
                 output_json,
                 message,
+                queue_wait_ms,
             } => {
                 let now = Utc::now().timestamp();
                 let terminal = status.is_terminal();
@@ -2672,11 +3599,12 @@ where
                 // the retry's status. attempt 0 marks an older message with no attempt: apply it
                 // unconditionally as before.
                 sqlx::query(&self.render(
-                    "UPDATE workflow_node_runs SET status = ?, output_json = COALESCE(?, output_json), message = COALESCE(?, message), started_at = CASE WHEN ? = 'running' THEN ? WHEN ? = 'queued' THEN NULL ELSE started_at END, finished_at = CASE WHEN ? THEN ? WHEN ? = 'queued' THEN NULL ELSE finished_at END WHERE id = ? AND NOT (status IN ('succeeded', 'failed', 'timed_out', 'canceled') AND ? NOT IN ('succeeded', 'failed', 'timed_out', 'canceled')) AND (? <= 0 OR attempt <= ?)",
+                    "UPDATE workflow_node_runs SET status = ?, output_json = COALESCE(?, output_json), message = COALESCE(?, message), queue_wait_ms = COALESCE(?, queue_wait_ms), started_at = CASE WHEN ? = 'running' THEN ? WHEN ? = 'queued' THEN NULL ELSE started_at END, finished_at = CASE WHEN ? THEN ? WHEN ? = 'queued' THEN NULL ELSE finished_at END WHERE id = ? AND NOT (status IN ('succeeded', 'failed', 'timed_out', 'canceled') AND ? NOT IN ('succeeded', 'failed', 'timed_out', 'canceled')) AND (? <= 0 OR attempt <= ?)",
                 ))
                 .bind(status.as_str())
                 .bind(output_json.as_ref().map(|value: &Value| value.to_string()))
                 .bind(message.clone())
+                .bind(*queue_wait_ms)
                 .bind(status.as_str())
                 .bind(now)
                 .bind(status.as_str())
@@ -2824,7 +3752,7 @@ where
         if let Some(node_id) = node_id.as_ref() {
             query = query.bind(node_id);
         }
-        let rows = query.fetch_all(self.pool()).await?;
+        let rows = query.fetch_all(self.read_pool()).await?;
         let mut stats: HashMap<(String, String), NodeTransitionStat> = HashMap::new();
         for row in &rows {
             let from_node: String = row.get("from_node");
@@ -4267,16 +5195,30 @@ where
         &self,
         dedupe_key: String,
         command: ActionCommand,
+    ) -> Result<ActionDispatchRecord, SendableError> {
+        self.enqueue_action_dispatch_at(dedupe_key, command, None)
+            .await
+    }
+
+    async fn enqueue_action_dispatch_at(
+        &self,
+        dedupe_key: String,
+        command: ActionCommand,
+        not_before: Option<DateTime<Utc>>,
     ) -> Result<ActionDispatchRecord, SendableError> {
         let now = Utc::now().timestamp();
         let id = Uuid::now_v7();
         let dispatch_columns = "id, dedupe_key, command_json, attempts, created_at, updated_at, published_at, last_error, claimed_by, claimed_until";
+        // a delayed intent is born holding a lease nobody renews, so publishers skip it until the
+        // lease lapses at `not_before`.
+        let lease_holder = not_before.map(|_| ACTION_DISPATCH_DELAY_HOLDER.to_string());
+        let lease_until = not_before.map(|at| at.timestamp());
 
         // first writer wins: keep the existing command on conflict.
         if self.dialect() == SqlDialect::MySql {
             sqlx::query(&self.render(
-                "INSERT INTO workflow_action_dispatches (id, dedupe_key, command_json, attempts, created_at, updated_at)
-                 VALUES (?, ?, ?, 0, ?, ?)
+                "INSERT INTO workflow_action_dispatches (id, dedupe_key, command_json, attempts, created_at, updated_at, claimed_by, claimed_until)
+                 VALUES (?, ?, ?, 0, ?, ?, ?, ?)
                  ON DUPLICATE KEY UPDATE command_json = command_json",
             ))
             .bind(id)
@@ -4284,6 +5226,8 @@ where
             .bind(serde_json::to_string(&command)?)
             .bind(now)
             .bind(now)
+            .bind(lease_holder)
+            .bind(lease_until)
             .execute(self.pool())
             .await?;
             let row = sqlx::query(&self.render(&format!(
@@ -4296,8 +5240,8 @@ where
         }
 
         let row = sqlx::query(&self.render(&format!(
-            "INSERT INTO workflow_action_dispatches (id, dedupe_key, command_json, attempts, created_at, updated_at)
-             VALUES (?, ?, ?, 0, ?, ?)
+            "INSERT INTO workflow_action_dispatches (id, dedupe_key, command_json, attempts, created_at, updated_at, claimed_by, claimed_until)
+             VALUES (?, ?, ?, 0, ?, ?, ?, ?)
              ON CONFLICT(dedupe_key) DO UPDATE SET command_json = workflow_action_dispatches.command_json
              RETURNING {dispatch_columns}",
         )))
@@ -4306,6 +5250,8 @@ where
         .bind(serde_json::to_string(&command)?)
         .bind(now)
         .bind(now)
+        .bind(lease_holder)
+        .bind(lease_until)
         .fetch_one(self.pool())
         .await?;
         mappers::row_to_action_dispatch(&row)
@@ -4430,6 +5376,14 @@ where
         Ok(())
     }
 
+    async fn delete_action_dispatch(&self, dispatch_id: Uuid) -> Result<(), SendableError> {
+        sqlx::query(&self.render("DELETE FROM workflow_action_dispatches WHERE id = ?"))
+            .bind(dispatch_id)
+            .execute(self.pool())
+            .await?;
+        Ok(())
+    }
+
     async fn create_notification(
         &self,
         notification: &NewNotification,
@@ -5334,14 +6288,798 @@ where
             .collect())
     }
 
-    async fn fetch_org_quota(&self, org_id: Uuid) -> Result<Option<OrgQuota>, SendableError> {
-        let row = sqlx::query(&self.render(
-            "SELECT org_id, max_nodes_json, max_monthly_cents FROM org_quotas WHERE org_id = ?",
+    async fn fetch_workflow_retentions(&self) -> Result<Vec<WorkflowRetention>, SendableError> {
+        let rows = sqlx::query(&self.render(
+            "SELECT workflow_id, retention_days, updated_at FROM workflow_retention ORDER BY workflow_id",
         ))
-        .bind(org_id)
-        .fetch_optional(self.pool())
+        .fetch_all(self.pool())
         .await?;
-        Ok(row.as_ref().map(mappers::row_to_org_quota))
+        Ok(rows
+            .iter()
+            .map(mappers::row_to_workflow_retention)
+            .collect())
+    }
+
+    async fn fetch_workflow_retention(
+        &self,
+        workflow_id: Uuid,
+    ) -> Result<Option<WorkflowRetention>, SendableError> {
+        let row = sqlx::query(&self.render(
+            "SELECT workflow_id, retention_days, updated_at FROM workflow_retention WHERE workflow_id = ?",
+        ))
+        .bind(workflow_id)
+        .fetch_optional(self.pool())
+        .await?;
+        Ok(row.as_ref().map(mappers::row_to_workflow_retention))
+    }
+
+    async fn upsert_workflow_retention(
+        &self,
+        retention: WorkflowRetention,
+    ) -> Result<WorkflowRetention, SendableError> {
+        let now = Utc::now();
+        let conflict = queries::on_conflict_update(
+            self.dialect(),
+            "workflow_id",
+            &["retention_days", "updated_at"],
+        );
+        sqlx::query(&self.render(&format!(
+            "INSERT INTO workflow_retention (workflow_id, retention_days, updated_at) VALUES (?, ?, ?) {conflict}",
+        )))
+        .bind(retention.workflow_id)
+        .bind(retention.retention_days as i64)
+        .bind(now.timestamp())
+        .execute(self.pool())
+        .await?;
+        Ok(WorkflowRetention {
+            updated_at: DateTime::from_timestamp(now.timestamp(), 0),
+            ..retention
+        })
+    }
+
+    async fn delete_workflow_retention(&self, workflow_id: Uuid) -> Result<bool, SendableError> {
+        let result =
+            sqlx::query(&self.render("DELETE FROM workflow_retention WHERE workflow_id = ?"))
+                .bind(workflow_id)
+                .execute(self.pool())
+                .await?;
+        Ok(result.affected() > 0)
+    }
+
+    async fn record_workflow_revision(
+        &self,
+        workflow: &WorkflowDefinition,
+        changed_by: Option<Uuid>,
+    ) -> Result<WorkflowRevision, SendableError> {
+        let Some(workflow_id) = workflow.id else {
+            return Err(Box::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "cannot record a revision of an unsaved workflow",
+            )));
+        };
+        let id = Uuid::now_v7();
+        let now = Utc::now().timestamp();
+        // number and insert on one transaction; the (workflow_id, revision) unique index rejects a
+        // concurrent save that read the same maximum.
+        let mut tx = self.pool().begin().await?;
+        let revision = sqlx::query(&self.render(
+            "SELECT COALESCE(MAX(revision), 0) AS latest FROM workflow_revisions WHERE workflow_id = ?",
+        ))
+        .bind(workflow_id)
+        .fetch_one(&mut *tx)
+        .await?
+        .get::<i64, _>("latest")
+            + 1;
+        sqlx::query(&self.render(
+            "INSERT INTO workflow_revisions (id, workflow_id, revision, snapshot, changed_by, created_at) VALUES (?, ?, ?, ?, ?, ?)",
+        ))
+        .bind(id)
+        .bind(workflow_id)
+        .bind(revision)
+        .bind(serde_json::to_string(workflow)?)
+        .bind(changed_by)
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(WorkflowRevision {
+            id,
+            workflow_id,
+            revision,
+            workflow: workflow.clone(),
+            changed_by,
+            created_at: DateTime::<Utc>::from_timestamp(now, 0).unwrap_or_else(Utc::now),
+        })
+    }
+
+    async fn fetch_workflow_revisions(
+        &self,
+        workflow_id: Uuid,
+    ) -> Result<Vec<WorkflowRevision>, SendableError> {
+        let rows = sqlx::query(&self.render(
+            "SELECT id, workflow_id, revision, snapshot, changed_by, created_at FROM workflow_revisions WHERE workflow_id = ? ORDER BY revision DESC",
+        ))
+        .bind(workflow_id)
+        .fetch_all(self.pool())
+        .await?;
+        rows.iter().map(mappers::row_to_workflow_revision).collect()
+    }
+
+    async fn fetch_workflow_revision(
+        &self,
+        workflow_id: Uuid,
+        revision: i64,
+    ) -> Result<Option<WorkflowRevision>, SendableError> {
+        let row = sqlx::query(&self.render(
+            "SELECT id, workflow_id, revision, snapshot, changed_by, created_at FROM workflow_revisions WHERE workflow_id = ? AND revision = ?",
+        ))
+        .bind(workflow_id)
+        .bind(revision)
+        .fetch_optional(self.pool())
+        .await?;
+        row.as_ref()
+            .map(mappers::row_to_workflow_revision)
+            .transpose()
+    }
+
+    async fn fetch_workflow_protection(
+        &self,
+        workflow_id: Uuid,
+    ) -> Result<Option<WorkflowProtection>, SendableError> {
+        let row = sqlx::query(&self.render(
+            "SELECT workflow_id, protected_by, created_at FROM workflow_protection WHERE workflow_id = ?",
+        ))
+        .bind(workflow_id)
+        .fetch_optional(self.pool())
+        .await?;
+        Ok(row.as_ref().map(mappers::row_to_workflow_protection))
+    }
+
+    async fn protect_workflow(
+        &self,
+        workflow_id: Uuid,
+        protected_by: Option<Uuid>,
+    ) -> Result<WorkflowProtection, SendableError> {
+        let conflict = queries::on_conflict_nothing(self.dialect(), "workflow_id", "workflow_id");
+        sqlx::query(&self.render(&format!(
+            "INSERT INTO workflow_protection (workflow_id, protected_by, created_at) VALUES (?, ?, ?) {conflict}",
+        )))
+        .bind(workflow_id)
+        .bind(protected_by)
+        .bind(Utc::now().timestamp())
+        .execute(self.pool())
+        .await?;
+        self.fetch_workflow_protection(workflow_id)
+            .await?
+            .ok_or_else(|| {
+                Box::new(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    format!("protection of workflow {workflow_id} vanished after save"),
+                )) as SendableError
+            })
+    }
+
+    async fn unprotect_workflow(&self, workflow_id: Uuid) -> Result<bool, SendableError> {
+        let result =
+            sqlx::query(&self.render("DELETE FROM workflow_protection WHERE workflow_id = ?"))
+                .bind(workflow_id)
+                .execute(self.pool())
+                .await?;
+        Ok(result.affected() > 0)
+    }
+
+    async fn create_pending_workflow_change(
+        &self,
+        change: &NewPendingWorkflowChange,
+    ) -> Result<PendingWorkflowChange, SendableError> {
+        let id = Uuid::now_v7();
+        sqlx::query(&self.render(
+            "INSERT INTO workflow_pending_changes (id, workflow_id, kind, payload, requested_by, status, reviewed_by, created_at, reviewed_at) VALUES (?, ?, ?, ?, ?, ?, NULL, ?, NULL)",
+        ))
+        .bind(id)
+        .bind(change.workflow_id)
+        .bind(change.kind.as_str())
+        .bind(change.payload.to_string())
+        .bind(change.requested_by)
+        .bind(PendingChangeStatus::Pending.as_str())
+        .bind(Utc::now().timestamp())
+        .execute(self.pool())
+        .await?;
+        self.fetch_pending_workflow_change(id)
+            .await?
+            .ok_or_else(|| {
+                Box::new(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    format!("pending change {id} vanished after save"),
+                )) as SendableError
+            })
+    }
+
+    async fn fetch_pending_workflow_changes(
+        &self,
+        workflow_id: Uuid,
+    ) -> Result<Vec<PendingWorkflowChange>, SendableError> {
+        let rows = sqlx::query(&self.render(&format!(
+            "SELECT {PENDING_CHANGE_COLUMNS} FROM workflow_pending_changes WHERE workflow_id = ? AND status = ? ORDER BY created_at ASC, id ASC",
+        )))
+        .bind(workflow_id)
+        .bind(PendingChangeStatus::Pending.as_str())
+        .fetch_all(self.pool())
+        .await?;
+        rows.iter()
+            .map(mappers::row_to_pending_workflow_change)
+            .collect()
+    }
+
+    async fn fetch_pending_workflow_change(
+        &self,
+        change_id: Uuid,
+    ) -> Result<Option<PendingWorkflowChange>, SendableError> {
+        let row = sqlx::query(&self.render(&format!(
+            "SELECT {PENDING_CHANGE_COLUMNS} FROM workflow_pending_changes WHERE id = ?",
+        )))
+        .bind(change_id)
+        .fetch_optional(self.pool())
+        .await?;
+        row.as_ref()
+            .map(mappers::row_to_pending_workflow_change)
+            .transpose()
+    }
+
+    async fn review_pending_workflow_change(
+        &self,
+        change_id: Uuid,
+        status: PendingChangeStatus,
+        reviewed_by: Option<Uuid>,
+    ) -> Result<bool, SendableError> {
+        let result = sqlx::query(&self.render(
+            "UPDATE workflow_pending_changes SET status = ?, reviewed_by = ?, reviewed_at = ? WHERE id = ? AND status = ?",
+        ))
+        .bind(status.as_str())
+        .bind(reviewed_by)
+        .bind(Utc::now().timestamp())
+        .bind(change_id)
+        .bind(PendingChangeStatus::Pending.as_str())
+        .execute(self.pool())
+        .await?;
+        Ok(result.affected() > 0)
+    }
+
+    async fn fetch_cluster_pause(&self) -> Result<ClusterPause, SendableError> {
+        let row = sqlx::query(&self.render(
+            "SELECT dispatch_paused, execution_paused, scheduling_paused, reason, updated_by, updated_at FROM cluster_pause WHERE scope = ?",
+        ))
+        .bind(CLUSTER_PAUSE_SCOPE)
+        .fetch_optional(self.pool())
+        .await?;
+        Ok(row
+            .map(|row| mappers::row_to_cluster_pause(&row))
+            .unwrap_or_default())
+    }
+
+    async fn set_cluster_pause(&self, pause: &ClusterPause) -> Result<(), SendableError> {
+        let conflict = queries::on_conflict_update(
+            self.dialect(),
+            "scope",
+            &[
+                "dispatch_paused",
+                "execution_paused",
+                "scheduling_paused",
+                "reason",
+                "updated_by",
+                "updated_at",
+            ],
+        );
+        sqlx::query(&self.render(&format!(
+            "INSERT INTO cluster_pause (scope, dispatch_paused, execution_paused, scheduling_paused, reason, updated_by, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?) {conflict}",
+        )))
+        .bind(CLUSTER_PAUSE_SCOPE)
+        .bind(pause.dispatch)
+        .bind(pause.execution)
+        .bind(pause.scheduling)
+        .bind(pause.reason.clone())
+        .bind(pause.updated_by)
+        .bind(pause.updated_at.unwrap_or_else(Utc::now).timestamp())
+        .execute(self.pool())
+        .await?;
+        Ok(())
+    }
+
+    async fn acquire_scheduler_lease(
+        &self,
+        name: &str,
+        holder: &str,
+        now: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) -> Result<bool, SendableError> {
+        // seed the row on first use, then take it over only when it is ours or has lapsed. the
+        // holder is read back rather than trusting affected rows, which mysql reports as zero for
+        // a renewal that changed nothing.
+        sqlx::query(&self.render(&queries::insert_ignore(
+            self.dialect(),
+            "scheduler_leases",
+            "name, holder, acquired_at, expires_at",
+            "?, ?, ?, ?",
+            "name",
+            None,
+        )))
+        .bind(name)
+        .bind(holder)
+        .bind(now.timestamp())
+        .bind(expires_at.timestamp())
+        .execute(self.pool())
+        .await?;
+        // `acquired_at` is assigned before `holder`: mysql evaluates SET left to right, so it must
+        // still see the previous holder.
+        sqlx::query(&self.render(
+            "UPDATE scheduler_leases
+             SET acquired_at = CASE WHEN holder = ? THEN acquired_at ELSE ? END, holder = ?, expires_at = ?
+             WHERE name = ? AND (holder = ? OR expires_at < ?)",
+        ))
+        .bind(holder)
+        .bind(now.timestamp())
+        .bind(holder)
+        .bind(expires_at.timestamp())
+        .bind(name)
+        .bind(holder)
+        .bind(now.timestamp())
+        .execute(self.pool())
+        .await?;
+        let row = sqlx::query(&self.render("SELECT holder FROM scheduler_leases WHERE name = ?"))
+            .bind(name)
+            .fetch_optional(self.pool())
+            .await?;
+        Ok(row.is_some_and(|row| row.get::<String, _>("holder") == holder))
+    }
+
+    async fn release_scheduler_lease(&self, name: &str, holder: &str) -> Result<(), SendableError> {
+        sqlx::query(&self.render("DELETE FROM scheduler_leases WHERE name = ? AND holder = ?"))
+            .bind(name)
+            .bind(holder)
+            .execute(self.pool())
+            .await?;
+        Ok(())
+    }
+
+    async fn fetch_scheduler_lease(
+        &self,
+        name: &str,
+    ) -> Result<Option<SchedulerLease>, SendableError> {
+        let row = sqlx::query(&self.render(
+            "SELECT name, holder, acquired_at, expires_at FROM scheduler_leases WHERE name = ?",
+        ))
+        .bind(name)
+        .fetch_optional(self.pool())
+        .await?;
+        Ok(row.map(|row| mappers::row_to_scheduler_lease(&row)))
+    }
+
+    async fn fetch_blackout_calendars(&self) -> Result<Vec<BlackoutCalendar>, SendableError> {
+        let rows = sqlx::query(&self.render(&format!(
+            "SELECT {BLACKOUT_CALENDAR_COLUMNS} FROM blackout_calendars ORDER BY name"
+        )))
+        .fetch_all(self.pool())
+        .await?;
+        Ok(rows.iter().map(mappers::row_to_blackout_calendar).collect())
+    }
+
+    async fn upsert_blackout_calendar(
+        &self,
+        calendar: &BlackoutCalendar,
+    ) -> Result<(), SendableError> {
+        let conflict = queries::on_conflict_update(
+            self.dialect(),
+            "name",
+            &["description", "timezone", "dates", "updated_at"],
+        );
+        sqlx::query(&self.render(&format!(
+            "INSERT INTO blackout_calendars ({BLACKOUT_CALENDAR_COLUMNS}) VALUES (?, ?, ?, ?, ?) {conflict}",
+        )))
+        .bind(calendar.name.as_str())
+        .bind(calendar.description.clone())
+        .bind(calendar.timezone.clone())
+        .bind(serde_json::to_string(&calendar.dates)?)
+        .bind(calendar.updated_at.unwrap_or_else(Utc::now).timestamp())
+        .execute(self.pool())
+        .await?;
+        Ok(())
+    }
+
+    async fn delete_blackout_calendar(&self, name: &str) -> Result<bool, SendableError> {
+        let result = sqlx::query(&self.render("DELETE FROM blackout_calendars WHERE name = ?"))
+            .bind(name)
+            .execute(self.pool())
+            .await?;
+        Ok(result.affected() > 0)
+    }
+
+    async fn create_workflow_backfill(
+        &self,
+        backfill: &WorkflowBackfill,
+    ) -> Result<bool, SendableError> {
+        let mut tx = self.pool().begin().await?;
+        let running = sqlx::query(&self.render(
+            "SELECT COUNT(*) AS running FROM workflow_backfills WHERE workflow_id = ? AND status = ?",
+        ))
+        .bind(backfill.workflow_id)
+        .bind(BackfillStatus::Running.as_str())
+        .fetch_one(&mut *tx)
+        .await?
+        .get::<i64, _>("running");
+        if running > 0 {
+            return Ok(false);
+        }
+        sqlx::query(&self.render(&format!(
+            "INSERT INTO workflow_backfills ({BACKFILL_COLUMNS}) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+        )))
+        .bind(backfill.id)
+        .bind(backfill.workflow_id)
+        .bind(backfill.trigger_id)
+        .bind(backfill.range_start.timestamp())
+        .bind(backfill.range_end.timestamp())
+        .bind(backfill.concurrency)
+        .bind(serde_json::to_string(&backfill.slots)?)
+        .bind(backfill.fired)
+        .bind(backfill.status.as_str())
+        .bind(backfill.requested_by)
+        .bind(backfill.created_at.timestamp())
+        .bind(backfill.updated_at.timestamp())
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    async fn fetch_workflow_backfill(
+        &self,
+        backfill_id: Uuid,
+    ) -> Result<Option<WorkflowBackfill>, SendableError> {
+        let row = sqlx::query(&self.render(&format!(
+            "SELECT {BACKFILL_COLUMNS} FROM workflow_backfills WHERE id = ?"
+        )))
+        .bind(backfill_id)
+        .fetch_optional(self.pool())
+        .await?;
+        Ok(row.as_ref().map(mappers::row_to_workflow_backfill))
+    }
+
+    async fn fetch_workflow_backfills(
+        &self,
+        trigger_id: Uuid,
+    ) -> Result<Vec<WorkflowBackfill>, SendableError> {
+        let rows = sqlx::query(&self.render(&format!(
+            "SELECT {BACKFILL_COLUMNS} FROM workflow_backfills WHERE trigger_id = ? ORDER BY created_at DESC, id DESC"
+        )))
+        .bind(trigger_id)
+        .fetch_all(self.pool())
+        .await?;
+        Ok(rows.iter().map(mappers::row_to_workflow_backfill).collect())
+    }
+
+    async fn fetch_running_workflow_backfills(
+        &self,
+    ) -> Result<Vec<WorkflowBackfill>, SendableError> {
+        let rows = sqlx::query(&self.render(&format!(
+            "SELECT {BACKFILL_COLUMNS} FROM workflow_backfills WHERE status = ? ORDER BY created_at, id"
+        )))
+        .bind(BackfillStatus::Running.as_str())
+        .fetch_all(self.pool())
+        .await?;
+        Ok(rows.iter().map(mappers::row_to_workflow_backfill).collect())
+    }
+
+    async fn update_workflow_backfill_progress(
+        &self,
+        backfill_id: Uuid,
+        fired: i64,
+        status: BackfillStatus,
+    ) -> Result<(), SendableError> {
+        sqlx::query(&self.render(
+            "UPDATE workflow_backfills SET fired = ?, status = ?, updated_at = ? WHERE id = ? AND status = ?",
+        ))
+        .bind(fired)
+        .bind(status.as_str())
+        .bind(Utc::now().timestamp())
+        .bind(backfill_id)
+        .bind(BackfillStatus::Running.as_str())
+        .execute(self.pool())
+        .await?;
+        Ok(())
+    }
+
+    async fn cancel_workflow_backfill(&self, backfill_id: Uuid) -> Result<bool, SendableError> {
+        let result = sqlx::query(&self.render(
+            "UPDATE workflow_backfills SET status = ?, updated_at = ? WHERE id = ? AND status = ?",
+        ))
+        .bind(BackfillStatus::Canceled.as_str())
+        .bind(Utc::now().timestamp())
+        .bind(backfill_id)
+        .bind(BackfillStatus::Running.as_str())
+        .execute(self.pool())
+        .await?;
+        Ok(result.affected() > 0)
+    }
+
+    async fn count_open_backfill_runs(&self, workflow_id: Uuid) -> Result<i64, SendableError> {
+        let row = sqlx::query(&self.render(
+            "SELECT COUNT(*) AS open_count FROM workflow_runs
+             WHERE workflow_id = ? AND trigger_source_kind = ?
+               AND status NOT IN ('succeeded', 'failed', 'timed_out', 'canceled')",
+        ))
+        .bind(workflow_id)
+        .bind(TriggerSourceKind::Backfill.as_str())
+        .fetch_one(self.pool())
+        .await?;
+        Ok(row.get::<i64, _>("open_count"))
+    }
+
+    async fn create_job(
+        &self,
+        job_id: Uuid,
+        action: &WorkflowAction,
+        retry: JobRetry,
+        requested_by: Option<Uuid>,
+    ) -> Result<Job, SendableError> {
+        sqlx::query(&self.render(
+            "INSERT INTO jobs (id, action, status, requested_by, created_at, attempt, max_retries, retry_backoff_seconds) VALUES (?, ?, ?, ?, ?, 1, ?, ?)",
+        ))
+        .bind(job_id)
+        .bind(serde_json::to_string(action)?)
+        .bind(WorkflowStatus::Queued.as_str())
+        .bind(requested_by)
+        .bind(Utc::now().timestamp())
+        .bind(retry.max_retries)
+        .bind(retry.backoff_seconds)
+        .execute(self.pool())
+        .await?;
+        self.fetch_job(job_id).await?.ok_or_else(|| {
+            Box::new(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("job {job_id} vanished after save"),
+            )) as SendableError
+        })
+    }
+
+    async fn fetch_job(&self, job_id: Uuid) -> Result<Option<Job>, SendableError> {
+        let row =
+            sqlx::query(&self.render(&format!("SELECT {JOB_COLUMNS} FROM jobs WHERE id = ?",)))
+                .bind(job_id)
+                .fetch_optional(self.pool())
+                .await?;
+        let Some(mut job) = row.as_ref().map(mappers::row_to_job).transpose()? else {
+            return Ok(None);
+        };
+        let attempts = sqlx::query(&self.render(&format!(
+            "SELECT {JOB_ATTEMPT_COLUMNS} FROM job_attempts WHERE job_id = ? ORDER BY attempt",
+        )))
+        .bind(job_id)
+        .fetch_all(self.pool())
+        .await?;
+        job.attempts = attempts.iter().map(mappers::row_to_job_attempt).collect();
+        Ok(Some(job))
+    }
+
+    async fn apply_job_result_event(
+        &self,
+        event: &WorkflowResultEvent,
+    ) -> Result<bool, SendableError> {
+        let mut tx = self.pool().begin().await?;
+        let insert = sqlx::query(&self.render(&queries::insert_ignore(
+            self.dialect(),
+            "workflow_result_events",
+            "event_id, workflow_run_id, workflow_node_run_id, node_id, event_type, created_at",
+            "?, ?, ?, ?, ?, ?",
+            "event_id",
+            None,
+        )))
+        .bind(event.event_id)
+        .bind(event.workflow_run_id)
+        .bind(event.workflow_node_run_id)
+        .bind(event.node_id.clone())
+        .bind(workflow_result_event_type(event))
+        .bind(event.timestamp.timestamp())
+        .execute(&mut *tx)
+        .await?;
+
+        if insert.affected() == 0 {
+            tx.commit().await?;
+            return Ok(false);
+        }
+
+        if let WorkflowResultEventKind::Status {
+            status,
+            output_json,
+            message,
+            ..
+        } = &event.kind
+        {
+            let now = Utc::now().timestamp();
+            let output_json = output_json.as_ref().map(|value: &Value| value.to_string());
+            let open = sqlx::query(&self.render(
+                "SELECT attempt, max_retries, started_at FROM jobs WHERE id = ? AND status NOT IN ('succeeded', 'failed', 'timed_out', 'canceled')",
+            ))
+            .bind(event.workflow_node_run_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+            // a late status from a superseded attempt must not touch its retry; attempt 0 is an
+            // older worker that does not say, applied as before.
+            let current = open
+                .filter(|row| event.attempt == 0 || event.attempt == row.get::<i64, _>("attempt"));
+            if let Some(row) = current {
+                let attempt = row.get::<i64, _>("attempt");
+                let max_retries = row.get::<i64, _>("max_retries");
+                if status.is_terminal() {
+                    sqlx::query(&self.render(&queries::insert_ignore(
+                        self.dialect(),
+                        "job_attempts",
+                        "job_id, attempt, status, output_json, message, started_at, finished_at",
+                        "?, ?, ?, ?, ?, ?, ?",
+                        "job_id, attempt",
+                        None,
+                    )))
+                    .bind(event.workflow_node_run_id)
+                    .bind(attempt)
+                    .bind(status.as_str())
+                    .bind(output_json.clone())
+                    .bind(message.clone())
+                    .bind(row.get::<Option<i64>, _>("started_at"))
+                    .bind(now)
+                    .execute(&mut *tx)
+                    .await?;
+                }
+                let retry = matches!(status, WorkflowStatus::Failed | WorkflowStatus::TimedOut)
+                    && attempt <= max_retries;
+                if retry {
+                    sqlx::query(&self.render(
+                        "UPDATE jobs SET status = ?, attempt = ?, output_json = NULL, message = ?, started_at = NULL, finished_at = NULL, current_executor_replica_id = NULL WHERE id = ?",
+                    ))
+                    .bind(WorkflowStatus::Queued.as_str())
+                    .bind(attempt + 1)
+                    .bind(format!(
+                        "attempt {attempt} {}: {}",
+                        status.as_str(),
+                        message.as_deref().unwrap_or("no message")
+                    ))
+                    .bind(event.workflow_node_run_id)
+                    .execute(&mut *tx)
+                    .await?;
+                } else {
+                    sqlx::query(&self.render(
+                        "UPDATE jobs SET status = ?, output_json = COALESCE(?, output_json), message = COALESCE(?, message), started_at = CASE WHEN ? = 'running' THEN ? ELSE started_at END, finished_at = CASE WHEN ? THEN ? ELSE finished_at END WHERE id = ?",
+                    ))
+                    .bind(status.as_str())
+                    .bind(output_json)
+                    .bind(message.clone())
+                    .bind(status.as_str())
+                    .bind(now)
+                    .bind(status.is_terminal())
+                    .bind(now)
+                    .bind(event.workflow_node_run_id)
+                    .execute(&mut *tx)
+                    .await?;
+                }
+            }
+        }
+
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    async fn claim_job_executor(
+        &self,
+        job_id: Uuid,
+        replica_id: Uuid,
+        claimed_at: DateTime<Utc>,
+        stale_before: DateTime<Utc>,
+    ) -> Result<bool, SendableError> {
+        let result = sqlx::query(&self.render(
+            "UPDATE jobs SET current_executor_replica_id = ?, executor_claimed_at = ? WHERE id = ? AND (current_executor_replica_id IS NULL OR executor_claimed_at < ?)",
+        ))
+        .bind(replica_id)
+        .bind(claimed_at.timestamp())
+        .bind(job_id)
+        .bind(stale_before.timestamp())
+        .execute(self.pool())
+        .await?;
+        Ok(result.affected() > 0)
+    }
+
+    async fn release_job_executor(
+        &self,
+        job_id: Uuid,
+        replica_id: Uuid,
+    ) -> Result<(), SendableError> {
+        sqlx::query(&self.render(
+            "UPDATE jobs SET current_executor_replica_id = NULL WHERE id = ? AND current_executor_replica_id = ?",
+        ))
+        .bind(job_id)
+        .bind(replica_id)
+        .execute(self.pool())
+        .await?;
+        Ok(())
+    }
+
+    async fn upsert_workflow_template(
+        &self,
+        template: &WorkflowTemplate,
+    ) -> Result<WorkflowTemplate, SendableError> {
+        let id = template.id.unwrap_or_else(Uuid::now_v7);
+        let now = Utc::now().timestamp();
+        let conflict = queries::on_conflict_update(
+            self.dialect(),
+            "id",
+            &[
+                "org_id",
+                "name",
+                "description",
+                "cron",
+                "labels",
+                "workflow",
+                "updated_at",
+            ],
+        );
+        sqlx::query(&self.render(&format!(
+            "INSERT INTO workflow_templates (id, org_id, name, description, cron, labels, workflow, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?) {conflict}",
+        )))
+        .bind(id)
+        .bind(template.org_id)
+        .bind(&template.name)
+        .bind(&template.description)
+        .bind(&template.cron)
+        .bind(serde_json::to_string(&template.labels)?)
+        .bind(serde_json::to_string(&template.workflow)?)
+        .bind(now)
+        .bind(now)
+        .execute(self.pool())
+        .await?;
+        self.fetch_workflow_template(id).await?.ok_or_else(|| {
+            Box::new(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("workflow template {id} vanished after save"),
+            )) as SendableError
+        })
+    }
+
+    async fn fetch_workflow_templates(&self) -> Result<Vec<WorkflowTemplate>, SendableError> {
+        let rows = sqlx::query(&self.render(
+            "SELECT id, org_id, name, description, cron, labels, workflow, created_at, updated_at FROM workflow_templates ORDER BY name",
+        ))
+        .fetch_all(self.pool())
+        .await?;
+        rows.iter().map(mappers::row_to_workflow_template).collect()
+    }
+
+    async fn fetch_workflow_template(
+        &self,
+        template_id: Uuid,
+    ) -> Result<Option<WorkflowTemplate>, SendableError> {
+        let row = sqlx::query(&self.render(
+            "SELECT id, org_id, name, description, cron, labels, workflow, created_at, updated_at FROM workflow_templates WHERE id = ?",
+        ))
+        .bind(template_id)
+        .fetch_optional(self.pool())
+        .await?;
+        row.as_ref()
+            .map(mappers::row_to_workflow_template)
+            .transpose()
+    }
+
+    async fn delete_workflow_template(&self, template_id: Uuid) -> Result<bool, SendableError> {
+        let result = sqlx::query(&self.render("DELETE FROM workflow_templates WHERE id = ?"))
+            .bind(template_id)
+            .execute(self.pool())
+            .await?;
+        Ok(result.affected() > 0)
+    }
+
+    async fn fetch_org_quota(&self, org_id: Uuid) -> Result<Option<OrgQuota>, SendableError> {
+        let row = sqlx::query(&self.render(
+            "SELECT org_id, max_nodes_json, max_monthly_cents, max_concurrent_runs FROM org_quotas WHERE org_id = ?",
+        ))
+        .bind(org_id)
+        .fetch_optional(self.pool())
+        .await?;
+        Ok(row.as_ref().map(mappers::row_to_org_quota))
     }
 
     async fn upsert_org_quota(&self, quota: OrgQuota) -> Result<OrgQuota, SendableError> {
@@ -5350,21 +7088,42 @@ where
         let conflict = queries::on_conflict_update(
             self.dialect(),
             "org_id",
-            &["max_nodes_json", "max_monthly_cents", "updated_at"],
+            &[
+                "max_nodes_json",
+                "max_monthly_cents",
+                "max_concurrent_runs",
+                "updated_at",
+            ],
         );
         sqlx::query(&self.render(&format!(
-            "INSERT INTO org_quotas (org_id, max_nodes_json, max_monthly_cents, updated_at) \
-             VALUES (?, ?, ?, ?) {conflict}",
+            "INSERT INTO org_quotas (org_id, max_nodes_json, max_monthly_cents, max_concurrent_runs, updated_at) \
+             VALUES (?, ?, ?, ?, ?) {conflict}",
         )))
         .bind(quota.org_id)
         .bind(&max_nodes_json)
         .bind(quota.max_monthly_cents as i64)
+        .bind(quota.max_concurrent_runs as i64)
         .bind(now)
         .execute(self.pool())
         .await?;
         Ok(quota)
     }
 
+    async fn count_active_workflow_runs_for_org(&self, org_id: Uuid) -> Result<i64, SendableError> {
+        // queued runs are excluded: they hold no claim yet, and the reducer consults this count
+        // before claiming one.
+        let row = sqlx::query(&self.render(
+            "SELECT COUNT(*) AS active_count FROM workflow_runs r
+             JOIN workflows w ON w.id = r.workflow_id
+             WHERE w.org_id = ?
+               AND r.status IN ('running', 'debug_paused', 'waiting', 'approval_required', 'input_required')",
+        ))
+        .bind(org_id)
+        .fetch_one(self.pool())
+        .await?;
+        Ok(row.get::<i64, _>("active_count"))
+    }
+
     async fn insert_usage_sample(&self, sample: UsageSample) -> Result<(), SendableError> {
         // idempotent per (org, backend, kind, sampled_at): the sampler buckets sampled_at to the
         // interval boundary, so any number of ws replicas / background workers sampling the same
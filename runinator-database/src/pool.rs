@@ -1,35 +1,125 @@
 //! connection-pool sizing shared by every sql backend. bounding the pool caps how many server
 //! connections a request flood can open, and an acquisition timeout turns a saturated pool into a
-//! fast error instead of an unbounded wait that ties up an http worker. both are env-tunable so the
-//! defaults stay sane for the local stack while production can raise them to match the database.
+//! fast error instead of an unbounded wait that ties up an http worker. every knob is env-tunable so
+//! the defaults stay sane for the local stack while production can raise them to match the database;
+//! the web service also takes them as cli flags.
 
 use std::time::Duration;
 
+use sqlx::{Database, Pool, pool::PoolOptions};
+
 /// default maximum pooled connections. sqlx's built-in default is 10; 20 gives the web service and
 /// in-process engine headroom without risking a small managed postgres's connection cap.
-const DEFAULT_MAX_CONNECTIONS: u32 = 20;
+pub const DEFAULT_MAX_CONNECTIONS: u32 = 20;
 
 /// default acquire timeout. long enough to ride out a brief burst, short enough that a genuinely
 /// saturated pool fails fast rather than parking the caller indefinitely (sqlx's default is 30s).
-const DEFAULT_ACQUIRE_TIMEOUT_SECONDS: u64 = 30;
+pub const DEFAULT_ACQUIRE_TIMEOUT_SECONDS: u64 = 30;
 
-/// maximum pooled connections, overridable via `RUNINATOR_DB_MAX_CONNECTIONS`. a missing, unparseable,
-/// or zero value falls back to the default.
-pub(crate) fn pool_max_connections() -> u32 {
-    std::env::var("RUNINATOR_DB_MAX_CONNECTIONS")
-        .ok()
-        .and_then(|raw| raw.trim().parse::<u32>().ok())
-        .filter(|value| *value > 0)
-        .unwrap_or(DEFAULT_MAX_CONNECTIONS)
+/// default idle timeout, matching sqlx's: a connection unused this long is closed so a quiet replica
+/// hands its server slots back.
+pub const DEFAULT_IDLE_TIMEOUT_SECONDS: u64 = 600;
+
+/// default prepared statements cached per connection, matching sqlx's.
+pub const DEFAULT_STATEMENT_CACHE_CAPACITY: usize = 100;
+
+/// pool tuning for one database connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolSettings {
+    pub max_connections: u32,
+    pub acquire_timeout: Duration,
+    /// `None` keeps idle connections open until the pool closes.
+    pub idle_timeout: Option<Duration>,
+    /// 0 disables the per-connection prepared-statement cache.
+    pub statement_cache_capacity: usize,
 }
 
-/// pool acquisition timeout, overridable via `RUNINATOR_DB_ACQUIRE_TIMEOUT_SECONDS`. a missing,
-/// unparseable, or zero value falls back to the default.
-pub(crate) fn pool_acquire_timeout() -> Duration {
-    let seconds = std::env::var("RUNINATOR_DB_ACQUIRE_TIMEOUT_SECONDS")
+impl Default for PoolSettings {
+    fn default() -> Self {
+        PoolSettings {
+            max_connections: DEFAULT_MAX_CONNECTIONS,
+            acquire_timeout: Duration::from_secs(DEFAULT_ACQUIRE_TIMEOUT_SECONDS),
+            idle_timeout: Some(Duration::from_secs(DEFAULT_IDLE_TIMEOUT_SECONDS)),
+            statement_cache_capacity: DEFAULT_STATEMENT_CACHE_CAPACITY,
+        }
+    }
+}
+
+impl PoolSettings {
+    /// read `RUNINATOR_DB_MAX_CONNECTIONS`, `RUNINATOR_DB_ACQUIRE_TIMEOUT_SECONDS`,
+    /// `RUNINATOR_DB_IDLE_TIMEOUT_SECONDS`, and `RUNINATOR_DB_STATEMENT_CACHE_CAPACITY`. a missing or
+    /// unparseable value falls back to the default, as does a zero connection count or acquire timeout;
+    /// a zero idle timeout keeps idle connections open.
+    pub fn from_env() -> Self {
+        PoolSettings::from_seconds(
+            env_number("RUNINATOR_DB_MAX_CONNECTIONS").unwrap_or(DEFAULT_MAX_CONNECTIONS),
+            env_number("RUNINATOR_DB_ACQUIRE_TIMEOUT_SECONDS")
+                .unwrap_or(DEFAULT_ACQUIRE_TIMEOUT_SECONDS),
+            env_number("RUNINATOR_DB_IDLE_TIMEOUT_SECONDS").unwrap_or(DEFAULT_IDLE_TIMEOUT_SECONDS),
+            env_number("RUNINATOR_DB_STATEMENT_CACHE_CAPACITY")
+                .unwrap_or(DEFAULT_STATEMENT_CACHE_CAPACITY),
+        )
+    }
+
+    /// build from whole-second values as cli flags carry them, with the same zero handling as
+    /// [`PoolSettings::from_env`].
+    pub fn from_seconds(
+        max_connections: u32,
+        acquire_timeout_seconds: u64,
+        idle_timeout_seconds: u64,
+        statement_cache_capacity: usize,
+    ) -> Self {
+        let defaults = PoolSettings::default();
+        PoolSettings {
+            max_connections: Some(max_connections)
+                .filter(|value| *value > 0)
+                .unwrap_or(defaults.max_connections),
+            acquire_timeout: Some(acquire_timeout_seconds)
+                .filter(|value| *value > 0)
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.acquire_timeout),
+            idle_timeout: Some(idle_timeout_seconds)
+                .filter(|value| *value > 0)
+                .map(Duration::from_secs),
+            statement_cache_capacity,
+        }
+    }
+
+    /// apply the connection count and timeouts to a backend's pool options.
+    pub(crate) fn apply<DB: Database>(&self, options: PoolOptions<DB>) -> PoolOptions<DB> {
+        options
+            .max_connections(self.max_connections)
+            .acquire_timeout(self.acquire_timeout)
+            .idle_timeout(self.idle_timeout)
+    }
+}
+
+fn env_number<N: std::str::FromStr>(name: &str) -> Option<N> {
+    std::env::var(name)
         .ok()
-        .and_then(|raw| raw.trim().parse::<u64>().ok())
-        .filter(|value| *value > 0)
-        .unwrap_or(DEFAULT_ACQUIRE_TIMEOUT_SECONDS);
-    Duration::from_secs(seconds)
+        .and_then(|raw| raw.trim().parse::<N>().ok())
+}
+
+/// a point-in-time view of how much of the pool is in use, exported as metrics.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PoolStatus {
+    /// open connections, idle or checked out.
+    pub size: u32,
+    pub idle: u32,
+    pub max_connections: u32,
+}
+
+impl PoolStatus {
+    pub(crate) fn of<DB: Database>(pool: &Pool<DB>) -> Self {
+        PoolStatus {
+            size: pool.size(),
+            idle: u32::try_from(pool.num_idle()).unwrap_or(u32::MAX),
+            max_connections: pool.options().get_max_connections(),
+        }
+    }
+
+    /// connections currently checked out.
+    pub fn in_use(&self) -> u32 {
+        self.size.saturating_sub(self.idle)
+    }
 }
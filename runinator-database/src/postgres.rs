@@ -9,33 +9,59 @@ use sqlx::{
     postgres::{PgConnectOptions, PgPoolOptions},
 };
 
-use crate::{
-    backend::SqlBackend,
-    pool::{pool_acquire_timeout, pool_max_connections},
-    queries::SqlDialect,
-};
+use crate::{backend::SqlBackend, pool::PoolSettings, queries::SqlDialect, replica::ReadReplica};
 
 static POSTGRES_MIGRATOR: Migrator = sqlx::migrate!("./migrations/postgres");
 
 pub struct PostgresDb {
     pub pool: PgPool,
+    replica: Option<ReadReplica<sqlx::Postgres>>,
 }
 
 impl PostgresDb {
     pub async fn new(connection_str: &str) -> Result<Self, SendableError> {
+        Self::with_pool_settings(connection_str, &PoolSettings::from_env()).await
+    }
+
+    pub async fn with_pool_settings(
+        connection_str: &str,
+        settings: &PoolSettings,
+    ) -> Result<Self, SendableError> {
         let options = PgConnectOptions::from_str(connection_str)?
+            .statement_cache_capacity(settings.statement_cache_capacity)
             .log_statements(log::LevelFilter::Debug)
             .log_slow_statements(log::LevelFilter::Warn, std::time::Duration::from_secs(1));
 
         // bound the pool so a request flood cannot open unbounded server connections, and time out
         // acquisition so a saturated pool surfaces a fast error instead of an unbounded wait that ties
-        // up the http worker. both are env-tunable; see the `pool` module.
-        let pool = PgPoolOptions::new()
-            .max_connections(pool_max_connections())
-            .acquire_timeout(pool_acquire_timeout())
+        // up the http worker. see the `pool` module for the knobs.
+        let pool = settings
+            .apply(PgPoolOptions::new())
             .connect_with(options)
             .await?;
-        Ok(Self { pool })
+        Ok(Self {
+            pool,
+            replica: None,
+        })
+    }
+
+    /// serve listing and stats queries from the read-only replica at `connection_str`, pooled with
+    /// the same `settings`. the replica connects lazily, so an unreachable one does not fail
+    /// startup; those queries read from the primary until it answers.
+    pub fn with_read_replica(
+        mut self,
+        connection_str: &str,
+        settings: &PoolSettings,
+    ) -> Result<Self, SendableError> {
+        let options = PgConnectOptions::from_str(connection_str)?
+            .statement_cache_capacity(settings.statement_cache_capacity)
+            .log_statements(log::LevelFilter::Debug)
+            .log_slow_statements(log::LevelFilter::Warn, std::time::Duration::from_secs(1));
+        let pool = settings
+            .apply(PgPoolOptions::new())
+            .connect_lazy_with(options);
+        self.replica = Some(ReadReplica::spawn(pool));
+        Ok(self)
     }
 
     pub async fn bootstrap(&self) -> Result<(), SendableError> {
@@ -80,6 +106,10 @@ impl SqlBackend for PostgresDb {
         &self.pool
     }
 
+    fn read_replica(&self) -> Option<&ReadReplica<sqlx::Postgres>> {
+        self.replica.as_ref()
+    }
+
     fn dialect(&self) -> SqlDialect {
         SqlDialect::Postgres
     }
@@ -37,6 +37,11 @@ pub(crate) fn render(dialect: SqlDialect, sql: &str) -> String {
     }
 }
 
+/// `count` comma-separated `?` placeholders for an `IN (...)` list bound value by value.
+pub(crate) fn placeholders(count: usize) -> String {
+    vec!["?"; count].join(", ")
+}
+
 /// quote an identifier that is a reserved word in some dialect (e.g. `trigger`, `key`).
 ///
 /// sqlite and postgres accept these bare as column names; mysql requires backticks.
@@ -47,6 +52,18 @@ pub(crate) fn ident(dialect: SqlDialect, name: &str) -> String {
     }
 }
 
+/// `SUM(expr)` decoded as a 64-bit integer, `0` over no rows.
+///
+/// sqlite sums integers to an integer; postgres widens a bigint sum to numeric and mysql returns
+/// decimal, so both are cast back.
+pub(crate) fn sum_bigint(dialect: SqlDialect, expr: &str) -> String {
+    match dialect {
+        SqlDialect::Sqlite => format!("COALESCE(SUM({expr}), 0)"),
+        SqlDialect::Postgres => format!("CAST(COALESCE(SUM({expr}), 0) AS BIGINT)"),
+        SqlDialect::MySql => format!("CAST(COALESCE(SUM({expr}), 0) AS SIGNED)"),
+    }
+}
+
 /// build the conflict-resolution tail of an upsert in `?`-placeholder style, without `RETURNING`.
 ///
 /// `conflict` names the unique columns; `set` is the comma-separated `col = ...` assignments using
@@ -104,6 +121,74 @@ pub(crate) fn skip_locked(dialect: SqlDialect) -> &'static str {
     }
 }
 
+/// row-locking suffix for a read-then-write check, empty on sqlite.
+pub(crate) fn for_update(dialect: SqlDialect) -> &'static str {
+    match dialect {
+        // sqlite serializes writers; a second writer fails its upgrade instead of overwriting.
+        SqlDialect::Sqlite => "",
+        SqlDialect::Postgres | SqlDialect::MySql => " FOR UPDATE",
+    }
+}
+
+/// case-insensitive match of `column` against a bound [`contains_pattern`].
+pub(crate) fn ilike(dialect: SqlDialect, column: &str) -> String {
+    match dialect {
+        SqlDialect::Postgres => format!("{column} ILIKE ? ESCAPE '!'"),
+        // sqlite only folds ascii case and mysql's folding depends on collation; lowering both
+        // sides makes them agree.
+        SqlDialect::Sqlite | SqlDialect::MySql => {
+            format!("LOWER({column}) LIKE LOWER(?) ESCAPE '!'")
+        }
+    }
+}
+
+/// a `LIKE` pattern matching `term` anywhere, with its own wildcards escaped by `!`.
+pub(crate) fn contains_pattern(term: &str) -> String {
+    let mut pattern = String::with_capacity(term.len() + 2);
+    pattern.push('%');
+    for ch in term.chars() {
+        if matches!(ch, '%' | '_' | '!') {
+            pattern.push('!');
+        }
+        pattern.push(ch);
+    }
+    pattern.push('%');
+    pattern
+}
+
+/// the condition a workflow must meet for one search term to match it, and the values to bind, in
+/// order. each dialect goes through its full-text index over the workflow's name, namespace,
+/// definition, and trigger configurations (see the `workflow_search_index` migrations).
+pub(crate) fn workflow_search_match(dialect: SqlDialect, term: &str) -> (String, Vec<String>) {
+    match dialect {
+        // the trigram index needs three characters to look a term up; a shorter one falls back to a
+        // plain scan of the indexed text.
+        SqlDialect::Sqlite if term.chars().count() < 3 => (
+            format!(
+                "id IN (SELECT workflow_id FROM workflow_search WHERE {})",
+                ilike(dialect, "document")
+            ),
+            vec![contains_pattern(term)],
+        ),
+        SqlDialect::Sqlite => (
+            "id IN (SELECT workflow_id FROM workflow_search WHERE workflow_search MATCH ?)".into(),
+            vec![format!("\"{}\"", term.replace('"', "\"\""))],
+        ),
+        SqlDialect::Postgres => (
+            "(search_vector @@ phraseto_tsquery('simple', ?) OR EXISTS (SELECT 1 FROM workflow_triggers t WHERE t.workflow_id = workflows.id AND t.search_vector @@ phraseto_tsquery('simple', ?)))".into(),
+            vec![term.to_string(), term.to_string()],
+        ),
+        SqlDialect::MySql => {
+            // a quoted phrase is matched as written, with boolean-mode operators inside it inert.
+            let phrase = format!("\"{}\"", term.replace('"', " "));
+            (
+                "(MATCH(name, namespace, definition) AGAINST (? IN BOOLEAN MODE) OR EXISTS (SELECT 1 FROM workflow_triggers t WHERE t.workflow_id = workflows.id AND MATCH(t.configuration) AGAINST (? IN BOOLEAN MODE)))".into(),
+                vec![phrase.clone(), phrase],
+            )
+        }
+    }
+}
+
 /// build an insert that ignores unique-constraint conflicts, in `?`-placeholder style.
 ///
 /// `conflict` names the conflicting columns (used by postgres `ON CONFLICT`). `returning`, when set,
@@ -0,0 +1,71 @@
+//! an optional read replica. query-heavy listings (workflow lists, run history, stats) read from it
+//! so they stop competing with the orchestration writes on the primary. a background probe pings the
+//! replica; while it is unreachable, or before its first successful ping, those reads fall back to
+//! the primary, so a replica outage only ever costs the offload.
+
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
+
+use log::{info, warn};
+use sqlx::{Connection, Database, Pool};
+
+/// environment variable naming the read replica's connection url.
+pub const READ_URL_ENV: &str = "RUNINATOR_DB_READ_URL";
+
+/// how often the probe pings the replica.
+const PROBE_INTERVAL: Duration = Duration::from_secs(5);
+/// how long one ping may take before the replica counts as unreachable.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// the replica url from [`READ_URL_ENV`], if set and non-empty.
+pub fn read_url_from_env() -> Option<String> {
+    std::env::var(READ_URL_ENV)
+        .ok()
+        .map(|raw| raw.trim().to_string())
+        .filter(|raw| !raw.is_empty())
+}
+
+pub struct ReadReplica<DB: Database> {
+    pool: Pool<DB>,
+    healthy: Arc<AtomicBool>,
+}
+
+impl<DB: Database> ReadReplica<DB> {
+    /// wrap a (lazily connecting) replica pool and start probing it. the probe stops once the pool
+    /// is closed.
+    pub(crate) fn spawn(pool: Pool<DB>) -> Self {
+        let healthy = Arc::new(AtomicBool::new(false));
+        tokio::spawn(probe(pool.clone(), healthy.clone()));
+        ReadReplica { pool, healthy }
+    }
+
+    pub fn pool(&self) -> &Pool<DB> {
+        &self.pool
+    }
+
+    /// whether the last probe reached the replica.
+    pub fn is_healthy(&self) -> bool {
+        self.healthy.load(Ordering::Relaxed)
+    }
+}
+
+async fn probe<DB: Database>(pool: Pool<DB>, healthy: Arc<AtomicBool>) {
+    while !pool.is_closed() {
+        let reached = matches!(
+            tokio::time::timeout(PROBE_TIMEOUT, async { pool.acquire().await?.ping().await }).await,
+            Ok(Ok(()))
+        );
+        let was = healthy.swap(reached, Ordering::Relaxed);
+        match (was, reached) {
+            (false, true) => info!("Read replica reachable; serving listing queries from it"),
+            (true, false) => warn!("Read replica unreachable; reading from the primary"),
+            _ => {}
+        }
+        tokio::time::sleep(PROBE_INTERVAL).await;
+    }
+}
@@ -0,0 +1,354 @@
+//! cron fire-time previews for reviewing schedule edits before (or as) they are applied, and the
+//! effective next run the trigger loop would pick for a trigger.
+
+use chrono::{DateTime, Duration, Utc};
+use chrono_tz::Tz;
+use runinator_models::blackouts::BlackoutCalendar;
+use runinator_models::errors::SendableError;
+use runinator_models::workflows::{
+    CronFireTime, CronPreview, EffectiveSchedule, MisfirePolicy, SCHEDULE_PREVIEW_COUNT,
+    ScheduleAdjustment, ScheduleAdjustmentKind, SchedulePreview, WorkflowTrigger,
+    WorkflowTriggerKind, trigger_jitter_seconds, trigger_misfire_policy, trigger_offset_seconds,
+};
+
+use crate::common::{
+    MISFIRE_GRACE_SECS, jitter_offset, misfire_slots, next_execution_for_configuration,
+    next_execution_in_zone, trigger_blackout_until,
+};
+
+/// a cron expression and the iana zone its fields are read in, `None` meaning utc.
+pub type CronSchedule<'a> = (&'a str, Option<&'a str>);
+
+/// resolve an iana zone name such as `America/New_York`.
+pub fn parse_timezone(name: &str) -> Result<Tz, SendableError> {
+    name.parse::<Tz>().map_err(|_| -> SendableError {
+        Box::new(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("unknown timezone '{name}'"),
+        ))
+    })
+}
+
+/// the next `count` fire times of `cron` strictly after `now`, read in `timezone`.
+pub fn upcoming_fire_times(
+    cron: &str,
+    timezone: Option<&str>,
+    now: DateTime<Utc>,
+    count: usize,
+) -> Result<Vec<DateTime<Utc>>, SendableError> {
+    let mut times = Vec::with_capacity(count);
+    let mut cursor = now;
+    for _ in 0..count {
+        cursor = next_execution_in_zone(cron, timezone, cursor)?;
+        times.push(cursor);
+    }
+    Ok(times)
+}
+
+/// the firings of a cron `trigger` up to `until`, oldest first and at most `max`. the stored
+/// `next_execution` is the first one, since it already accounts for blackouts and may be overdue;
+/// a trigger without one is walked from `now`.
+pub fn trigger_fire_times(
+    trigger: &WorkflowTrigger,
+    now: DateTime<Utc>,
+    until: DateTime<Utc>,
+    max: usize,
+) -> Result<Vec<DateTime<Utc>>, SendableError> {
+    let trigger_id = trigger.id.unwrap_or_default();
+    let mut cursor = match trigger.next_execution {
+        Some(next) => next,
+        None => next_execution_for_configuration(&trigger.configuration, trigger_id, now)?,
+    };
+    let mut times = Vec::new();
+    while cursor <= until && times.len() < max {
+        times.push(cursor);
+        cursor = next_execution_for_configuration(&trigger.configuration, trigger_id, cursor)?;
+    }
+    Ok(times)
+}
+
+/// the slots of a cron `trigger` from `from` through `to`, inclusive and oldest first, stopping
+/// after `max`. these are the times its firings are keyed on, offset and jitter included, so a
+/// backfill replays exactly the slots the trigger loop would have fired.
+pub fn trigger_slots_between(
+    trigger: &WorkflowTrigger,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    max: usize,
+) -> Result<Vec<DateTime<Utc>>, SendableError> {
+    let trigger_id = trigger.id.unwrap_or_default();
+    let mut cursor = next_execution_for_configuration(
+        &trigger.configuration,
+        trigger_id,
+        from - Duration::seconds(1),
+    )?;
+    let mut slots = Vec::new();
+    while cursor <= to && slots.len() < max {
+        slots.push(cursor);
+        cursor = next_execution_for_configuration(&trigger.configuration, trigger_id, cursor)?;
+    }
+    Ok(slots)
+}
+
+/// what the trigger loop would fire for a cron `trigger` through `until`: each slot paired with
+/// when it fires, oldest first and at most `max`. an overdue slot fires at `now`; a slot inside one
+/// of the trigger's blackouts fires deferred when the blackout ends, absorbing any later slots it
+/// covers, and a deferral past `until` is still listed as the last firing.
+pub fn preview_trigger_firings(
+    trigger: &WorkflowTrigger,
+    calendars: &[BlackoutCalendar],
+    now: DateTime<Utc>,
+    until: DateTime<Utc>,
+    max: usize,
+) -> Result<Vec<(DateTime<Utc>, CronFireTime)>, SendableError> {
+    let trigger_id = trigger.id.unwrap_or_default();
+    let mut cursor = match trigger.next_execution {
+        Some(next) => next,
+        None => next_execution_for_configuration(&trigger.configuration, trigger_id, now)?,
+    };
+    let mut firings = Vec::new();
+    while cursor <= until && firings.len() < max {
+        let due = cursor.max(now);
+        let fire = match trigger_blackout_until(
+            &trigger.configuration,
+            trigger.blackout_start,
+            trigger.blackout_end,
+            calendars,
+            due,
+        ) {
+            Some(end) => CronFireTime {
+                at: end,
+                deferred: true,
+            },
+            None => CronFireTime {
+                at: due,
+                deferred: false,
+            },
+        };
+        // the loop schedules the next slot from the tick that fired, not from the slot.
+        let next = next_execution_for_configuration(&trigger.configuration, trigger_id, fire.at)?;
+        firings.push((cursor, fire));
+        cursor = next;
+    }
+    Ok(firings)
+}
+
+/// when a cron `trigger` will actually fire next, as the trigger loop would decide it at `now`, and
+/// each adjustment between its cron slot and that time: the offset and jitter baked into its
+/// `next_execution`, the misfire policy for an overdue slot, and a blackout holding the firing.
+/// a disabled or non-cron trigger, or one whose schedule does not evaluate, has no effective run.
+pub fn effective_schedule(
+    trigger: &WorkflowTrigger,
+    calendars: &[BlackoutCalendar],
+    now: DateTime<Utc>,
+) -> EffectiveSchedule {
+    if !trigger.enabled
+        || trigger.kind != WorkflowTriggerKind::Cron
+        || trigger.schedule_error().is_some()
+    {
+        return EffectiveSchedule::default();
+    }
+    effective_cron_schedule(trigger, calendars, now).unwrap_or_default()
+}
+
+fn effective_cron_schedule(
+    trigger: &WorkflowTrigger,
+    calendars: &[BlackoutCalendar],
+    now: DateTime<Utc>,
+) -> Result<EffectiveSchedule, SendableError> {
+    let configuration = &trigger.configuration;
+    let trigger_id = trigger.id.unwrap_or_default();
+    let next = match trigger.next_execution {
+        Some(next) => next,
+        None => next_execution_for_configuration(configuration, trigger_id, now)?,
+    };
+    let mut adjustments = Vec::new();
+
+    // a stored blackout deferral is not a slot, so it carries no offset or jitter of its own.
+    let offset = trigger_offset_seconds(configuration).unwrap_or_default();
+    let jitter = jitter_offset(
+        trigger_id,
+        trigger_jitter_seconds(configuration).unwrap_or_default(),
+    );
+    let slot = next - Duration::seconds(offset + jitter);
+    let since = slot + Duration::seconds(offset) - Duration::seconds(1);
+    if next_execution_for_configuration(configuration, trigger_id, since)? == next {
+        if offset != 0 {
+            adjustments.push(ScheduleAdjustment {
+                kind: ScheduleAdjustmentKind::Offset,
+                from: slot,
+                to: slot + Duration::seconds(offset),
+                reason: format!("offset_seconds shifts the cron slot by {offset}s"),
+            });
+        }
+        if jitter != 0 {
+            adjustments.push(ScheduleAdjustment {
+                kind: ScheduleAdjustmentKind::Jitter,
+                from: next - Duration::seconds(jitter),
+                to: next,
+                reason: format!("jitter_seconds places this trigger {jitter}s after its slot"),
+            });
+        }
+    }
+
+    // the loop checks blackouts before the misfire policy, so an overdue slot under a blackout
+    // waits for the blackout to end and is not overdue by then.
+    let mut at = next.max(now);
+    if next < now
+        && trigger_blackout_until(
+            configuration,
+            trigger.blackout_start,
+            trigger.blackout_end,
+            calendars,
+            at,
+        )
+        .is_none()
+    {
+        let overdue = (now - next).num_seconds();
+        let policy = trigger_misfire_policy(configuration).unwrap_or_default();
+        let (to, reason) = match policy {
+            MisfirePolicy::Skip if overdue > MISFIRE_GRACE_SECS => (
+                next_execution_for_configuration(configuration, trigger_id, now)?,
+                format!(
+                    "the slot is {overdue}s overdue and misfire is skip, so it resumes at its next slot"
+                ),
+            ),
+            MisfirePolicy::RunAllMissed => {
+                let missed = misfire_slots(configuration, trigger_id, next, now)?.len();
+                (
+                    now,
+                    format!(
+                        "the slot is {overdue}s overdue and misfire is run_all_missed, so {missed} missed slot(s) fire on the next tick"
+                    ),
+                )
+            }
+            MisfirePolicy::Skip | MisfirePolicy::RunOnce => (
+                now,
+                format!("the slot is {overdue}s overdue and fires once on the next tick"),
+            ),
+        };
+        adjustments.push(ScheduleAdjustment {
+            kind: ScheduleAdjustmentKind::Misfire,
+            from: next,
+            to,
+            reason,
+        });
+        at = to;
+    }
+    if let Some(end) = trigger_blackout_until(
+        configuration,
+        trigger.blackout_start,
+        trigger.blackout_end,
+        calendars,
+        at,
+    )
+    .filter(|end| *end > at)
+    {
+        adjustments.push(ScheduleAdjustment {
+            kind: ScheduleAdjustmentKind::Blackout,
+            from: at,
+            to: end,
+            reason: "a blackout window holds the firing until it ends".to_string(),
+        });
+        at = end;
+    }
+    Ok(EffectiveSchedule {
+        effective_next_run: Some(at),
+        schedule_adjustments: adjustments,
+    })
+}
+
+/// when the blackout holding due cron `trigger` at `now` ends, if one does. the trigger loop moves
+/// such a trigger's `next_execution` there instead of firing it.
+pub fn trigger_deferred_until(
+    trigger: &WorkflowTrigger,
+    calendars: &[BlackoutCalendar],
+    now: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    trigger_blackout_until(
+        &trigger.configuration,
+        trigger.blackout_start,
+        trigger.blackout_end,
+        calendars,
+        now,
+    )
+}
+
+/// compare the next fire times of a trigger's schedule before and after an edit. either side may
+/// be absent; an unparseable side is left empty and reported in `error`.
+pub fn preview_schedule_change(
+    before: Option<CronSchedule<'_>>,
+    after: Option<CronSchedule<'_>>,
+    now: DateTime<Utc>,
+) -> SchedulePreview {
+    let mut preview = SchedulePreview {
+        before_cron: before.map(|(cron, _)| cron.to_string()),
+        after_cron: after.map(|(cron, _)| cron.to_string()),
+        before_timezone: before
+            .and_then(|(_, timezone)| timezone)
+            .map(str::to_string),
+        after_timezone: after.and_then(|(_, timezone)| timezone).map(str::to_string),
+        ..SchedulePreview::default()
+    };
+    let mut errors = Vec::new();
+    for (schedule, times, side) in [
+        (before, &mut preview.before, "before"),
+        (after, &mut preview.after, "after"),
+    ] {
+        let Some((cron, timezone)) = schedule else {
+            continue;
+        };
+        match upcoming_fire_times(cron, timezone, now, SCHEDULE_PREVIEW_COUNT) {
+            Ok(upcoming) => *times = upcoming,
+            Err(err) => errors.push(format!("{side} cron '{cron}': {err}")),
+        }
+    }
+    if !errors.is_empty() {
+        preview.error = Some(errors.join("; "));
+    }
+    preview
+}
+
+/// validate `cron` and list its next `count` firings after `from`, read in `timezone`. a slot inside
+/// the inclusive `blackout` window fires at the window's end instead, so several blacked-out slots
+/// collapse into one deferred firing, matching the trigger loop.
+pub fn preview_cron(
+    cron: &str,
+    timezone: Option<&str>,
+    from: DateTime<Utc>,
+    count: usize,
+    blackout: Option<(DateTime<Utc>, DateTime<Utc>)>,
+) -> CronPreview {
+    let mut preview = CronPreview {
+        cron: cron.to_string(),
+        valid: true,
+        fire_times: Vec::with_capacity(count),
+        error: None,
+    };
+    let mut cursor = from;
+    while preview.fire_times.len() < count {
+        let next = match next_execution_in_zone(cron, timezone, cursor) {
+            Ok(next) => next,
+            // a valid expression can still run out of occurrences (a fixed year); list what there is.
+            Err(_) if !preview.fire_times.is_empty() => break,
+            Err(err) => {
+                preview.valid = false;
+                preview.error = Some(err.to_string());
+                break;
+            }
+        };
+        let fire = match blackout {
+            Some((start, end)) if next >= start && next <= end => CronFireTime {
+                at: end,
+                deferred: true,
+            },
+            _ => CronFireTime {
+                at: next,
+                deferred: false,
+            },
+        };
+        cursor = fire.at;
+        preview.fire_times.push(fire);
+    }
+    preview
+}
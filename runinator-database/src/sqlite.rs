@@ -9,7 +9,7 @@ use sqlx::{
     sqlite::{SqliteConnectOptions, SqlitePoolOptions},
 };
 
-use crate::{backend::SqlBackend, pool::pool_acquire_timeout, queries::SqlDialect};
+use crate::{backend::SqlBackend, pool::PoolSettings, queries::SqlDialect};
 
 static SQLITE_MIGRATOR: Migrator = sqlx::migrate!("./migrations/sqlite");
 
@@ -23,9 +23,17 @@ mod tests;
 
 impl SqliteDb {
     pub async fn new(filename: &str) -> Result<Self, SendableError> {
+        Self::with_pool_settings(filename, &PoolSettings::from_env()).await
+    }
+
+    pub async fn with_pool_settings(
+        filename: &str,
+        settings: &PoolSettings,
+    ) -> Result<Self, SendableError> {
         let options = SqliteConnectOptions::new()
             .filename(filename)
-            .create_if_missing(true);
+            .create_if_missing(true)
+            .statement_cache_capacity(settings.statement_cache_capacity);
         let options_with_logs = options
             .log_statements(log::LevelFilter::Debug)
             .log_slow_statements(log::LevelFilter::Warn, std::time::Duration::from_secs(1));
@@ -34,7 +42,8 @@ impl SqliteDb {
         // connection count is left at the driver default: sqlite serializes writes, so raising it
         // trades few gains for more lock contention on the single file.
         let connection = SqlitePoolOptions::new()
-            .acquire_timeout(pool_acquire_timeout())
+            .acquire_timeout(settings.acquire_timeout)
+            .idle_timeout(settings.idle_timeout)
             .connect_with(unmutable_options)
             .await?;
         Ok(SqliteDb { pool: connection })
@@ -1,20 +1,23 @@
 use super::*;
 use crate::archive::ArchiveTable;
+use crate::common::{jitter_offset, next_execution_in_zone};
 use crate::interfaces::DatabaseImpl;
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
 use runinator_comm::{ActionCommand, WorkflowResultEvent};
 use runinator_models::value::Value;
 use runinator_models::{
     auth::{ApiKey, ApiKeyRecord, Grant, Permission, PrincipalType, ResourceType},
     notifications::NewNotification,
     orgs::OrgRole,
-    runs::NewRunChunk,
+    protection::{NewPendingWorkflowChange, PendingChangeKind, PendingChangeStatus},
+    runs::{NewRunChunk, RunStatus},
     settings::SettingKind,
     workflows::{
         WorkflowAction, WorkflowDefinition, WorkflowGraph, WorkflowNodeRun, WorkflowStatus,
         WorkflowTrigger, WorkflowTriggerKind,
     },
 };
+use std::collections::BTreeMap;
 use uuid::Uuid;
 
 #[tokio::test]
@@ -304,6 +307,60 @@ async fn scheduler_claims_open_workflow_runs_once_until_lease_expires() {
     let _ = fs::remove_file(path);
 }
 
+#[tokio::test]
+async fn due_triggers_are_limited_in_claim_order() {
+    let path = std::env::temp_dir().join(format!(
+        "runinator-due-triggers-{}.db",
+        Utc::now().timestamp_nanos_opt().unwrap()
+    ));
+    let db = SqliteDb::new(path.to_str().unwrap()).await.unwrap();
+    db.run_init_scripts(&Vec::new()).await.unwrap();
+
+    let workflow_id = db
+        .upsert_workflow(&workflow("due-test"))
+        .await
+        .unwrap()
+        .id
+        .unwrap();
+    let mut trigger_ids = Vec::new();
+    for minutes_late in [5, 30] {
+        let trigger = db
+            .upsert_workflow_trigger(&WorkflowTrigger {
+                id: None,
+                workflow_id,
+                kind: WorkflowTriggerKind::Cron,
+                enabled: true,
+                configuration: runinator_models::json!({ "cron": "0 0 * * * *" }),
+                next_execution: Some(Utc::now() - Duration::minutes(minutes_late)),
+                blackout_start: None,
+                blackout_end: None,
+                metadata: runinator_models::json!({}),
+                created_at: None,
+                updated_at: None,
+            })
+            .await
+            .unwrap();
+        trigger_ids.push(trigger.id.unwrap());
+    }
+
+    let all = db
+        .fetch_due_workflow_triggers(Utc::now(), None)
+        .await
+        .unwrap();
+    let first = db
+        .fetch_due_workflow_triggers(Utc::now(), Some(1))
+        .await
+        .unwrap();
+
+    assert_eq!(all.len(), 2);
+    assert_eq!(
+        first.iter().map(|trigger| trigger.id).collect::<Vec<_>>(),
+        vec![Some(trigger_ids[1])]
+    );
+
+    let _ = fs::remove_file(path);
+}
+
 #[tokio::test]
 async fn due_trigger_firing_is_idempotent_and_advances_next_execution() {
     let path = std::env::temp_dir().join(format!(
@@ -351,16 +408,892 @@ async fn due_trigger_firing_is_idempotent_and_advances_next_execution() {
         .claim_due_workflow_trigger_firings("scheduler-b".into(), Utc::now(), 10)
         .await
         .unwrap();
-    let refreshed = db
-        .fetch_workflow_trigger(trigger.id.unwrap())
+    let refreshed = db
+        .fetch_workflow_trigger(trigger.id.unwrap())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(first.len(), 1);
+    assert_eq!(first[0].parameters["source"], "cron");
+    assert!(duplicate.is_empty());
+    assert!(refreshed.next_execution.is_some());
+
+    let _ = fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn zoned_cron_trigger_keeps_its_local_time_across_dst() {
+    let path = std::env::temp_dir().join(format!(
+        "runinator-trigger-zone-{}.db",
+        Utc::now().timestamp_nanos_opt().unwrap()
+    ));
+    let db = SqliteDb::new(path.to_str().unwrap()).await.unwrap();
+    db.run_init_scripts(&Vec::new()).await.unwrap();
+
+    let workflow_id = db
+        .upsert_workflow(&workflow("zoned-trigger"))
+        .await
+        .unwrap()
+        .id
+        .unwrap();
+    let at = |value: &str| {
+        DateTime::parse_from_rfc3339(value)
+            .unwrap()
+            .with_timezone(&Utc)
+    };
+    let trigger = |timezone: &str| WorkflowTrigger {
+        id: None,
+        workflow_id,
+        kind: WorkflowTriggerKind::Cron,
+        enabled: true,
+        configuration: runinator_models::json!({ "cron": "0 0 9 * * *", "timezone": timezone }),
+        next_execution: Some(at("2026-03-07T14:00:00Z")),
+        blackout_start: None,
+        blackout_end: None,
+        metadata: runinator_models::json!({}),
+        created_at: None,
+        updated_at: None,
+    };
+    let zoned = db
+        .upsert_workflow_trigger(&trigger("America/New_York"))
+        .await
+        .unwrap();
+
+    // 09:00 est on saturday is 14:00z; new york springs forward overnight, so sunday's 09:00 edt
+    // is 13:00z rather than another 14:00z.
+    let fired = db
+        .claim_due_workflow_trigger_firings("scheduler-a".into(), at("2026-03-07T14:00:00Z"), 10)
+        .await
+        .unwrap();
+    assert_eq!(fired.len(), 1);
+    let zoned = db
+        .fetch_workflow_trigger(zoned.id.unwrap())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(zoned.next_execution, Some(at("2026-03-08T13:00:00Z")));
+
+    // an unknown zone parks the trigger like an unparseable cron.
+    let unknown = db
+        .upsert_workflow_trigger(&trigger("Mars/Olympus_Mons"))
+        .await
+        .unwrap();
+    db.claim_due_workflow_trigger_firings("scheduler-a".into(), at("2026-03-07T14:00:00Z"), 10)
+        .await
+        .unwrap();
+    let unknown = db
+        .fetch_workflow_trigger(unknown.id.unwrap())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(
+        unknown
+            .schedule_error()
+            .is_some_and(|error| error.contains("Mars/Olympus_Mons"))
+    );
+
+    let _ = fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn jittered_trigger_fires_its_fixed_offset_after_each_slot() {
+    let path = std::env::temp_dir().join(format!(
+        "runinator-trigger-jitter-{}.db",
+        Utc::now().timestamp_nanos_opt().unwrap()
+    ));
+    let db = SqliteDb::new(path.to_str().unwrap()).await.unwrap();
+    db.run_init_scripts(&Vec::new()).await.unwrap();
+
+    let at = |value: &str| {
+        DateTime::parse_from_rfc3339(value)
+            .unwrap()
+            .with_timezone(&Utc)
+    };
+    let workflow_id = db
+        .upsert_workflow(&workflow("jittered"))
+        .await
+        .unwrap()
+        .id
+        .unwrap();
+    let trigger = db
+        .upsert_workflow_trigger(&WorkflowTrigger {
+            id: None,
+            workflow_id,
+            kind: WorkflowTriggerKind::Cron,
+            enabled: true,
+            configuration: runinator_models::json!({ "cron": "0 0 * * * *", "jitter_seconds": 300 }),
+            next_execution: None,
+            blackout_start: None,
+            blackout_end: None,
+            metadata: runinator_models::json!({}),
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+    let trigger_id = trigger.id.unwrap();
+    let offset = Duration::seconds(jitter_offset(trigger_id, 300));
+    assert!(offset >= Duration::zero() && offset <= Duration::seconds(300));
+
+    // the first pass schedules the trigger: the next slot, shifted by its offset.
+    db.claim_due_workflow_trigger_firings("scheduler-a".into(), at("2026-03-07T10:30:00Z"), 10)
+        .await
+        .unwrap();
+    let trigger = db
+        .fetch_workflow_trigger(trigger_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        trigger.next_execution,
+        Some(at("2026-03-07T11:00:00Z") + offset)
+    );
+
+    // it fires at its offset rather than on the slot, and moves on to the next slot's offset.
+    let runs = db
+        .claim_due_workflow_trigger_firings(
+            "scheduler-a".into(),
+            at("2026-03-07T11:00:00Z") + offset,
+            10,
+        )
+        .await
+        .unwrap();
+    assert_eq!(runs.len(), 1);
+    let trigger = db
+        .fetch_workflow_trigger(trigger_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        trigger.next_execution,
+        Some(at("2026-03-07T12:00:00Z") + offset)
+    );
+    assert_eq!(jitter_offset(trigger_id, 0), 0);
+
+    let _ = fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn offset_trigger_keeps_every_slot_even_past_the_cron_interval() {
+    let path = std::env::temp_dir().join(format!(
+        "runinator-trigger-offset-{}.db",
+        Utc::now().timestamp_nanos_opt().unwrap()
+    ));
+    let db = SqliteDb::new(path.to_str().unwrap()).await.unwrap();
+    db.run_init_scripts(&Vec::new()).await.unwrap();
+
+    let at = |value: &str| {
+        DateTime::parse_from_rfc3339(value)
+            .unwrap()
+            .with_timezone(&Utc)
+    };
+    let workflow_id = db
+        .upsert_workflow(&workflow("offset"))
+        .await
+        .unwrap()
+        .id
+        .unwrap();
+    // an hourly cron shifted by an hour and a half.
+    let trigger_id = db
+        .upsert_workflow_trigger(&WorkflowTrigger {
+            id: None,
+            workflow_id,
+            kind: WorkflowTriggerKind::Cron,
+            enabled: true,
+            configuration: runinator_models::json!({ "cron": "0 0 * * * *", "offset_seconds": 5400 }),
+            next_execution: None,
+            blackout_start: None,
+            blackout_end: None,
+            metadata: runinator_models::json!({}),
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap()
+        .id
+        .unwrap();
+
+    db.claim_due_workflow_trigger_firings("scheduler-a".into(), at("2026-03-07T10:45:00Z"), 10)
+        .await
+        .unwrap();
+    let trigger = db
+        .fetch_workflow_trigger(trigger_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(trigger.next_execution, Some(at("2026-03-07T11:30:00Z")));
+
+    // firing the 10:00 slot at 11:30 moves on to the 11:00 slot, not the 12:00 one.
+    let runs = db
+        .claim_due_workflow_trigger_firings("scheduler-a".into(), at("2026-03-07T11:30:00Z"), 10)
+        .await
+        .unwrap();
+    assert_eq!(runs.len(), 1);
+    let trigger = db
+        .fetch_workflow_trigger(trigger_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(trigger.next_execution, Some(at("2026-03-07T12:30:00Z")));
+
+    let _ = fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn calendar_blackout_defers_a_trigger_past_the_holiday() {
+    let path = std::env::temp_dir().join(format!(
+        "runinator-trigger-calendar-{}.db",
+        Utc::now().timestamp_nanos_opt().unwrap()
+    ));
+    let db = SqliteDb::new(path.to_str().unwrap()).await.unwrap();
+    db.run_init_scripts(&Vec::new()).await.unwrap();
+
+    let at = |value: &str| {
+        DateTime::parse_from_rfc3339(value)
+            .unwrap()
+            .with_timezone(&Utc)
+    };
+    let holidays = runinator_models::blackouts::BlackoutCalendar {
+        name: "holidays".into(),
+        dates: vec![chrono::NaiveDate::from_ymd_opt(2026, 12, 25).unwrap()],
+        ..Default::default()
+    };
+    db.upsert_blackout_calendar(&holidays).await.unwrap();
+    let stored = db.fetch_blackout_calendars().await.unwrap();
+    assert_eq!(stored.len(), 1);
+    assert_eq!(stored[0].dates, holidays.dates);
+
+    let workflow_id = db
+        .upsert_workflow(&workflow("holiday-aware"))
+        .await
+        .unwrap()
+        .id
+        .unwrap();
+    let trigger_id = db
+        .upsert_workflow_trigger(&WorkflowTrigger {
+            id: None,
+            workflow_id,
+            kind: WorkflowTriggerKind::Cron,
+            enabled: true,
+            configuration: runinator_models::json!({
+                "cron": "0 0 * * * *",
+                "blackouts": [{ "calendar": "holidays" }],
+            }),
+            next_execution: Some(at("2026-12-25T09:00:00Z")),
+            blackout_start: None,
+            blackout_end: None,
+            metadata: runinator_models::json!({}),
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap()
+        .id
+        .unwrap();
+
+    // due on the holiday: nothing fires and the trigger waits for the day to end.
+    let runs = db
+        .claim_due_workflow_trigger_firings("scheduler-a".into(), at("2026-12-25T09:00:00Z"), 10)
+        .await
+        .unwrap();
+    assert!(runs.is_empty());
+    let trigger = db
+        .fetch_workflow_trigger(trigger_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(trigger.next_execution, Some(at("2026-12-26T00:00:00Z")));
+
+    // the deferred firing goes out once the day is over (here, once the calendar is gone).
+    assert!(db.delete_blackout_calendar("holidays").await.unwrap());
+    assert!(!db.delete_blackout_calendar("holidays").await.unwrap());
+    let runs = db
+        .claim_due_workflow_trigger_firings("scheduler-a".into(), at("2026-12-26T00:00:00Z"), 10)
+        .await
+        .unwrap();
+    assert_eq!(runs.len(), 1);
+
+    let _ = fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn misfire_policy_decides_which_missed_slots_fire() {
+    let path = std::env::temp_dir().join(format!(
+        "runinator-trigger-misfire-{}.db",
+        Utc::now().timestamp_nanos_opt().unwrap()
+    ));
+    let db = SqliteDb::new(path.to_str().unwrap()).await.unwrap();
+    db.run_init_scripts(&Vec::new()).await.unwrap();
+
+    let at = |value: &str| {
+        DateTime::parse_from_rfc3339(value)
+            .unwrap()
+            .with_timezone(&Utc)
+    };
+    // an hourly trigger due at 10:00 whose scheduler only comes back at 13:30.
+    let mut fired = Vec::new();
+    for policy in ["skip", "run_once", "run_all_missed"] {
+        let workflow_id = db
+            .upsert_workflow(&workflow(&format!("misfire-{policy}")))
+            .await
+            .unwrap()
+            .id
+            .unwrap();
+        let trigger = db
+            .upsert_workflow_trigger(&WorkflowTrigger {
+                id: None,
+                workflow_id,
+                kind: WorkflowTriggerKind::Cron,
+                enabled: true,
+                configuration: runinator_models::json!({ "cron": "0 0 * * * *", "misfire": policy }),
+                next_execution: Some(at("2026-03-07T10:00:00Z")),
+                blackout_start: None,
+                blackout_end: None,
+                metadata: runinator_models::json!({}),
+                created_at: None,
+                updated_at: None,
+            })
+            .await
+            .unwrap();
+        let runs = db
+            .claim_due_workflow_trigger_firings(
+                "scheduler-a".into(),
+                at("2026-03-07T13:30:00Z"),
+                10,
+            )
+            .await
+            .unwrap();
+        let trigger = db
+            .fetch_workflow_trigger(trigger.id.unwrap())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(trigger.next_execution, Some(at("2026-03-07T14:00:00Z")));
+        fired.push(runs.len());
+    }
+    // skip drops the late slot, run_once fires it, run_all_missed replays 10:00 through 13:00.
+    assert_eq!(fired, vec![0, 1, 4]);
+
+    let _ = fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn webhook_trigger_is_found_by_its_current_token_hash() {
+    let path = std::env::temp_dir().join(format!(
+        "runinator-webhook-lookup-{}.db",
+        Utc::now().timestamp_nanos_opt().unwrap()
+    ));
+    let db = SqliteDb::new(path.to_str().unwrap()).await.unwrap();
+    db.run_init_scripts(&Vec::new()).await.unwrap();
+
+    let workflow_id = db
+        .upsert_workflow(&workflow("webhook-lookup"))
+        .await
+        .unwrap()
+        .id
+        .unwrap();
+    let mut trigger = db
+        .upsert_workflow_trigger(&WorkflowTrigger {
+            id: None,
+            workflow_id,
+            kind: WorkflowTriggerKind::Webhook,
+            enabled: true,
+            configuration: runinator_models::json!({ "token_hash": "first" }),
+            next_execution: None,
+            blackout_start: None,
+            blackout_end: None,
+            metadata: runinator_models::json!({}),
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+    let found = db.fetch_enabled_webhook_trigger("first").await.unwrap();
+    assert_eq!(found.and_then(|found| found.id), trigger.id);
+
+    // a rotated token retires the old hash; a disabled trigger is not found at all.
+    trigger.configuration = runinator_models::json!({ "token_hash": "second" });
+    let trigger = db.upsert_workflow_trigger(&trigger).await.unwrap();
+    assert!(
+        db.fetch_enabled_webhook_trigger("first")
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert!(
+        db.fetch_enabled_webhook_trigger("second")
+            .await
+            .unwrap()
+            .is_some()
+    );
+    db.upsert_workflow_trigger(&WorkflowTrigger {
+        enabled: false,
+        ..trigger
+    })
+    .await
+    .unwrap();
+    assert!(
+        db.fetch_enabled_webhook_trigger("second")
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    let _ = fs::remove_file(path);
+}
+
+// a per-second cron left behind for a month replays only its latest slots, without walking the
+// millions before them.
+#[test]
+fn run_all_missed_replays_the_latest_slots_of_a_long_outage() {
+    use crate::common::{MAX_MISSED_FIRINGS, misfire_slots};
+
+    let configuration =
+        runinator_models::json!({ "cron": "* * * * * *", "misfire": "run_all_missed" });
+    let now = DateTime::parse_from_rfc3339("2026-03-07T13:30:00Z")
+        .unwrap()
+        .with_timezone(&Utc);
+    let slots = misfire_slots(
+        &configuration,
+        Uuid::now_v7(),
+        now - Duration::days(30),
+        now,
+    )
+    .unwrap();
+    assert_eq!(slots.len(), MAX_MISSED_FIRINGS);
+    assert_eq!(slots.last(), Some(&now));
+    assert_eq!(
+        slots.first(),
+        Some(&(now - Duration::seconds(MAX_MISSED_FIRINGS as i64 - 1)))
+    );
+}
+
+#[test]
+fn effective_schedule_explains_offset_jitter_misfire_and_blackout() {
+    use crate::schedule::effective_schedule;
+    use runinator_models::workflows::ScheduleAdjustmentKind;
+
+    let at = |value: &str| {
+        DateTime::parse_from_rfc3339(value)
+            .unwrap()
+            .with_timezone(&Utc)
+    };
+    let trigger = |configuration: Value, next_execution: Option<DateTime<Utc>>| WorkflowTrigger {
+        id: Some(Uuid::now_v7()),
+        workflow_id: Uuid::now_v7(),
+        kind: WorkflowTriggerKind::Cron,
+        enabled: true,
+        configuration,
+        next_execution,
+        blackout_start: None,
+        blackout_end: None,
+        metadata: runinator_models::json!({}),
+        created_at: None,
+        updated_at: None,
+    };
+    let kinds = |schedule: &runinator_models::workflows::EffectiveSchedule| {
+        schedule
+            .schedule_adjustments
+            .iter()
+            .map(|adjustment| adjustment.kind)
+            .collect::<Vec<_>>()
+    };
+    let now = at("2026-03-07T10:30:00Z");
+
+    // an hourly slot shifted by its offset, then its jitter; the adjustments chain back to 11:00.
+    let mut jittered = trigger(
+        runinator_models::json!({ "cron": "0 0 * * * *", "offset_seconds": 60, "jitter_seconds": 300 }),
+        None,
+    );
+    let jitter = Duration::seconds(jitter_offset(jittered.id.unwrap(), 300));
+    let schedule = effective_schedule(&jittered, &[], now);
+    let expected = at("2026-03-07T11:01:00Z") + jitter;
+    assert_eq!(schedule.effective_next_run, Some(expected));
+    let offset = &schedule.schedule_adjustments[0];
+    assert_eq!(offset.kind, ScheduleAdjustmentKind::Offset);
+    assert_eq!(
+        (offset.from, offset.to),
+        (at("2026-03-07T11:00:00Z"), at("2026-03-07T11:01:00Z"))
+    );
+    if jitter > Duration::zero() {
+        assert_eq!(schedule.schedule_adjustments[1].to, expected);
+    }
+
+    // a one-off blackout over the slot holds it until the blackout ends.
+    jittered.blackout_start = Some(at("2026-03-07T11:00:00Z"));
+    jittered.blackout_end = Some(at("2026-03-07T12:30:00Z"));
+    let schedule = effective_schedule(&jittered, &[], now);
+    assert_eq!(
+        schedule.effective_next_run,
+        Some(at("2026-03-07T12:30:00Z"))
+    );
+    assert_eq!(
+        kinds(&schedule).last(),
+        Some(&ScheduleAdjustmentKind::Blackout)
+    );
+
+    // an overdue slot fires on the next tick unless its policy skips it to the next slot.
+    let overdue = |policy: &str| {
+        effective_schedule(
+            &trigger(
+                runinator_models::json!({ "cron": "0 0 * * * *", "misfire": policy }),
+                Some(at("2026-03-07T08:00:00Z")),
+            ),
+            &[],
+            now,
+        )
+    };
+    let skipped = overdue("skip");
+    assert_eq!(skipped.effective_next_run, Some(at("2026-03-07T11:00:00Z")));
+    assert_eq!(kinds(&skipped), vec![ScheduleAdjustmentKind::Misfire]);
+    let replayed = overdue("run_all_missed");
+    assert_eq!(replayed.effective_next_run, Some(now));
+    assert!(replayed.schedule_adjustments[0].reason.contains("3 missed"));
+
+    // a disabled trigger has nothing to report.
+    let mut disabled = trigger(runinator_models::json!({ "cron": "0 0 * * * *" }), None);
+    disabled.enabled = false;
+    assert_eq!(effective_schedule(&disabled, &[], now), Default::default());
+}
+
+#[tokio::test]
+async fn last_workflow_success_ignores_failed_and_open_runs() {
+    let path = std::env::temp_dir().join(format!(
+        "runinator-last-success-{}.db",
+        Utc::now().timestamp_nanos_opt().unwrap()
+    ));
+    let db = SqliteDb::new(path.to_str().unwrap()).await.unwrap();
+    db.run_init_scripts(&Vec::new()).await.unwrap();
+    let workflow_id = db
+        .upsert_workflow(&workflow("last-success-upstream"))
+        .await
+        .unwrap()
+        .id
+        .unwrap();
+    assert!(
+        db.fetch_last_workflow_success(workflow_id)
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    let snapshot = db.fetch_workflow(workflow_id).await.unwrap().unwrap();
+    let mut runs = Vec::new();
+    for _ in 0..3 {
+        runs.push(
+            db.create_workflow_run(
+                workflow_id,
+                snapshot.clone(),
+                runinator_models::json!({}),
+                runinator_models::json!({}),
+                None,
+                Default::default(),
+            )
+            .await
+            .unwrap(),
+        );
+    }
+    db.update_workflow_run_status(runs[0].id, WorkflowStatus::Failed, None, None, None)
+        .await
+        .unwrap();
+    assert!(
+        db.fetch_last_workflow_success(workflow_id)
+            .await
+            .unwrap()
+            .is_none(),
+        "a failed or still-queued run is not a success"
+    );
+
+    db.update_workflow_run_status(runs[1].id, WorkflowStatus::Succeeded, None, None, None)
+        .await
+        .unwrap();
+    let finished_at = db
+        .fetch_last_workflow_success(workflow_id)
+        .await
+        .unwrap()
+        .expect("the succeeded run should count");
+    assert_eq!(
+        Some(finished_at),
+        db.fetch_workflow_run(runs[1].id)
+            .await
+            .unwrap()
+            .unwrap()
+            .finished_at
+    );
+
+    let _ = fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn earlier_open_workflow_runs_count_only_older_unfinished_runs() {
+    let path = std::env::temp_dir().join(format!(
+        "runinator-earlier-open-{}.db",
+        Utc::now().timestamp_nanos_opt().unwrap()
+    ));
+    let db = SqliteDb::new(path.to_str().unwrap()).await.unwrap();
+    db.run_init_scripts(&Vec::new()).await.unwrap();
+    let workflow_id = db
+        .upsert_workflow(&workflow("earlier-open-runs"))
+        .await
+        .unwrap()
+        .id
+        .unwrap();
+    let snapshot = db.fetch_workflow(workflow_id).await.unwrap().unwrap();
+    let mut runs = Vec::new();
+    for _ in 0..3 {
+        runs.push(
+            db.create_workflow_run(
+                workflow_id,
+                snapshot.clone(),
+                runinator_models::json!({}),
+                runinator_models::json!({}),
+                None,
+                Default::default(),
+            )
+            .await
+            .unwrap(),
+        );
+    }
+    assert_eq!(
+        db.count_earlier_open_workflow_runs(&runs[0]).await.unwrap(),
+        0
+    );
+    assert_eq!(
+        db.count_earlier_open_workflow_runs(&runs[2]).await.unwrap(),
+        2
+    );
+
+    db.update_workflow_run_status(runs[0].id, WorkflowStatus::Succeeded, None, None, None)
+        .await
+        .unwrap();
+    assert_eq!(
+        db.count_earlier_open_workflow_runs(&runs[2]).await.unwrap(),
+        1,
+        "a finished run no longer blocks"
+    );
+    assert_eq!(
+        db.count_earlier_open_workflow_runs(&runs[1]).await.unwrap(),
+        0
+    );
+
+    let _ = fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn trigger_slot_fires_once_across_a_scheduler_restart() {
+    let path = std::env::temp_dir().join(format!(
+        "runinator-trigger-restart-{}.db",
+        Utc::now().timestamp_nanos_opt().unwrap()
+    ));
+    let db = SqliteDb::new(path.to_str().unwrap()).await.unwrap();
+    db.run_init_scripts(&Vec::new()).await.unwrap();
+
+    let workflow_id = db
+        .upsert_workflow(&workflow("trigger-restart-test"))
+        .await
+        .unwrap()
+        .id
+        .unwrap();
+    let due_at = Utc::now() - Duration::seconds(60);
+    let trigger_id = db
+        .upsert_workflow_trigger(&WorkflowTrigger {
+            id: None,
+            workflow_id,
+            kind: WorkflowTriggerKind::Cron,
+            enabled: true,
+            configuration: runinator_models::json!({ "cron": "*/5 * * * * *" }),
+            next_execution: Some(due_at),
+            blackout_start: None,
+            blackout_end: None,
+            metadata: runinator_models::json!({}),
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap()
+        .id
+        .unwrap();
+
+    let first = db
+        .claim_due_workflow_trigger_firings("scheduler-a".into(), Utc::now(), 10)
+        .await
+        .unwrap();
+    drop(db);
+
+    // a restarted scheduler that still sees the old slot (its advance was lost) must not fire it again.
+    let restarted = SqliteDb::new(path.to_str().unwrap()).await.unwrap();
+    restarted
+        .update_workflow_trigger_next_execution(trigger_id, Some(due_at))
+        .await
+        .unwrap();
+    let replay = restarted
+        .claim_due_workflow_trigger_firings("scheduler-a".into(), Utc::now(), 10)
+        .await
+        .unwrap();
+
+    assert_eq!(first.len(), 1);
+    assert!(replay.is_empty());
+
+    let _ = fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn invalid_cron_trigger_is_parked_reported_once_and_heals() {
+    let path = std::env::temp_dir().join(format!(
+        "runinator-trigger-bad-cron-{}.db",
+        Utc::now().timestamp_nanos_opt().unwrap()
+    ));
+    let db = SqliteDb::new(path.to_str().unwrap()).await.unwrap();
+    db.run_init_scripts(&Vec::new()).await.unwrap();
+
+    let workflow_id = db
+        .upsert_workflow(&workflow("bad-cron-test"))
+        .await
+        .unwrap()
+        .id
+        .unwrap();
+    let due_at = Utc::now() - Duration::seconds(60);
+    let trigger = db
+        .upsert_workflow_trigger(&WorkflowTrigger {
+            id: None,
+            workflow_id,
+            kind: WorkflowTriggerKind::Cron,
+            enabled: true,
+            configuration: runinator_models::json!({ "cron": "not a cron" }),
+            next_execution: Some(due_at),
+            blackout_start: None,
+            blackout_end: None,
+            metadata: runinator_models::json!({ "name": "bad-trigger" }),
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+    let trigger_id = trigger.id.unwrap();
+
+    // the bad cron no longer fails the batch: it is parked with a recorded error.
+    let runs = db
+        .claim_due_workflow_trigger_firings("scheduler-a".into(), Utc::now(), 10)
+        .await
+        .unwrap();
+    assert!(runs.is_empty());
+    let parked = db
+        .fetch_workflow_trigger(trigger_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(parked.schedule_error().is_some());
+    assert_eq!(parked.metadata["name"], "bad-trigger");
+    assert!(parked.next_execution.unwrap() > Utc::now());
+
+    // re-checking the same broken cron does not notify again.
+    db.update_workflow_trigger_next_execution(trigger_id, Some(due_at))
+        .await
+        .unwrap();
+    db.claim_due_workflow_trigger_firings("scheduler-a".into(), Utc::now(), 10)
+        .await
+        .unwrap();
+    let notifications = db.fetch_notifications(false, 10).await.unwrap();
+    assert_eq!(notifications.len(), 1);
+    assert_eq!(notifications[0].severity, "warning");
+
+    // fixing the cron (metadata round-tripped as-is) fires normally and clears the error.
+    let mut fixed = parked.clone();
+    fixed.configuration = runinator_models::json!({ "cron": "*/5 * * * * *" });
+    fixed.next_execution = Some(due_at);
+    db.upsert_workflow_trigger(&fixed).await.unwrap();
+    let runs = db
+        .claim_due_workflow_trigger_firings("scheduler-a".into(), Utc::now(), 10)
+        .await
+        .unwrap();
+    assert_eq!(runs.len(), 1);
+    let healed = db
+        .fetch_workflow_trigger(trigger_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(healed.schedule_error().is_none());
+
+    let _ = fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn reconcile_trigger_schedules_reports_and_repairs_drift() {
+    let path = std::env::temp_dir().join(format!(
+        "runinator-trigger-reconcile-{}.db",
+        Utc::now().timestamp_nanos_opt().unwrap()
+    ));
+    let db = SqliteDb::new(path.to_str().unwrap()).await.unwrap();
+    db.run_init_scripts(&Vec::new()).await.unwrap();
+
+    let workflow_id = db
+        .upsert_workflow(&workflow("reconcile-test"))
+        .await
+        .unwrap()
+        .id
+        .unwrap();
+    let now = Utc::now();
+    let expected = next_execution_in_zone("0 0 9 * * *", None, now).unwrap();
+    let cron_trigger = |cron: &str, next_execution| WorkflowTrigger {
+        id: None,
+        workflow_id,
+        kind: WorkflowTriggerKind::Cron,
+        enabled: true,
+        configuration: runinator_models::json!({ "cron": cron }),
+        next_execution,
+        blackout_start: None,
+        blackout_end: None,
+        metadata: Value::Null,
+        created_at: None,
+        updated_at: None,
+    };
+    let drifted = db
+        .upsert_workflow_trigger(&cron_trigger("0 0 9 * * *", Some(now - Duration::days(3))))
+        .await
+        .unwrap();
+    db.upsert_workflow_trigger(&cron_trigger("0 0 9 * * *", Some(expected)))
+        .await
+        .unwrap();
+    let broken = db
+        .upsert_workflow_trigger(&cron_trigger("not a cron", None))
+        .await
+        .unwrap();
+
+    let preview = db.reconcile_trigger_schedules(now, true).await.unwrap();
+    assert_eq!(preview.checked, 3);
+    assert_eq!(preview.changes.len(), 2);
+    let change = preview
+        .changes
+        .iter()
+        .find(|change| change.trigger_id == drifted.id.unwrap())
+        .unwrap();
+    assert_eq!(change.proposed_next_execution, Some(expected));
+    assert!(
+        preview
+            .changes
+            .iter()
+            .any(|change| change.trigger_id == broken.id.unwrap() && change.error.is_some())
+    );
+    // a dry run writes nothing.
+    let untouched = db
+        .fetch_workflow_trigger(drifted.id.unwrap())
         .await
         .unwrap()
         .unwrap();
+    assert_eq!(untouched.next_execution, drifted.next_execution);
 
-    assert_eq!(first.len(), 1);
-    assert_eq!(first[0].parameters["source"], "cron");
-    assert!(duplicate.is_empty());
-    assert!(refreshed.next_execution.is_some());
+    let applied = db.reconcile_trigger_schedules(now, false).await.unwrap();
+    assert_eq!(applied.changes.len(), 2);
+    let repaired = db
+        .fetch_workflow_trigger(drifted.id.unwrap())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(repaired.next_execution, Some(expected));
+    // only the unparseable trigger is still reported once repaired.
+    let again = db.reconcile_trigger_schedules(now, true).await.unwrap();
+    assert_eq!(again.changes.len(), 1);
 
     let _ = fs::remove_file(path);
 }
@@ -425,6 +1358,77 @@ async fn chained_trigger_kind_round_trips_and_firing_dedupes() {
     let _ = fs::remove_file(path);
 }
 
+#[tokio::test]
+async fn workflow_backfill_round_trips_and_allows_one_running_per_workflow() {
+    use runinator_models::backfills::{BackfillStatus, WorkflowBackfill};
+
+    let path = std::env::temp_dir().join(format!(
+        "runinator-backfill-{}.db",
+        Utc::now().timestamp_nanos_opt().unwrap()
+    ));
+    let db = SqliteDb::new(path.to_str().unwrap()).await.unwrap();
+    db.run_init_scripts(&Vec::new()).await.unwrap();
+
+    let workflow_id = db
+        .upsert_workflow(&workflow("backfill-test"))
+        .await
+        .unwrap()
+        .id
+        .unwrap();
+    let now = DateTime::<Utc>::from_timestamp(Utc::now().timestamp(), 0).unwrap();
+    let start = now - Duration::hours(3);
+    let backfill = |id: Uuid| WorkflowBackfill {
+        id,
+        workflow_id,
+        trigger_id: Uuid::now_v7(),
+        range_start: start,
+        range_end: now,
+        concurrency: 2,
+        slots: (0..3).map(|hour| start + Duration::hours(hour)).collect(),
+        fired: 0,
+        status: BackfillStatus::Running,
+        requested_by: None,
+        created_at: now,
+        updated_at: now,
+    };
+    let first = backfill(Uuid::now_v7());
+    assert!(db.create_workflow_backfill(&first).await.unwrap());
+    // a second backfill of the same workflow waits for the first to finish.
+    let second = backfill(Uuid::now_v7());
+    assert!(!db.create_workflow_backfill(&second).await.unwrap());
+
+    let stored = db.fetch_workflow_backfill(first.id).await.unwrap().unwrap();
+    assert_eq!(stored, first);
+    assert_eq!(
+        db.fetch_workflow_backfills(first.trigger_id)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+
+    db.update_workflow_backfill_progress(first.id, 3, BackfillStatus::Completed)
+        .await
+        .unwrap();
+    assert!(
+        db.fetch_running_workflow_backfills()
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    // a finished backfill can no longer be canceled or moved.
+    assert!(!db.cancel_workflow_backfill(first.id).await.unwrap());
+    let finished = db.fetch_workflow_backfill(first.id).await.unwrap().unwrap();
+    assert_eq!(finished.fired, 3);
+    assert_eq!(finished.status, BackfillStatus::Completed);
+
+    assert!(db.create_workflow_backfill(&second).await.unwrap());
+    assert!(db.cancel_workflow_backfill(second.id).await.unwrap());
+    assert_eq!(db.count_open_backfill_runs(workflow_id).await.unwrap(), 0);
+
+    let _ = fs::remove_file(path);
+}
+
 #[tokio::test]
 async fn upsert_workflow_without_id_updates_existing_name() {
     let path = std::env::temp_dir().join(format!(
@@ -492,6 +1496,455 @@ async fn namespaced_workflow_persists_and_resolves_by_qualified_name() {
     let _ = fs::remove_file(path);
 }
 
+#[tokio::test]
+async fn search_workflows_matches_names_and_definitions_ignoring_case() {
+    let path = std::env::temp_dir().join(format!(
+        "runinator-workflow-search-{}.db",
+        Utc::now().timestamp_nanos_opt().unwrap()
+    ));
+    let db = SqliteDb::new(path.to_str().unwrap()).await.unwrap();
+    db.run_init_scripts(&Vec::new()).await.unwrap();
+
+    let mut loader = workflow("nightly_load");
+    loader.definition = WorkflowGraph::from_value(runinator_models::json!({
+        "nodes": [
+            {
+                "id": "copy",
+                "kind": "action",
+                "action": {
+                    "provider": "sql",
+                    "function": "execute",
+                    "configuration": { "connection": "Server=Warehouse-DB;Database=sales" }
+                },
+                "transitions": { "on_success": { "$node": "done" } }
+            },
+            { "id": "done", "kind": "end" }
+        ]
+    }))
+    .unwrap();
+    let loader = db.upsert_workflow(&loader).await.unwrap();
+    let mut payroll = workflow("Payroll_Sync");
+    payroll.namespace = Some("finance".into());
+    let payroll = db.upsert_workflow(&payroll).await.unwrap();
+    db.upsert_workflow(&workflow("payroll-report"))
+        .await
+        .unwrap();
+
+    let ids = |found: Vec<WorkflowDefinition>| {
+        found
+            .into_iter()
+            .map(|workflow| workflow.id)
+            .collect::<Vec<_>>()
+    };
+    // configuration inside the definition, matched regardless of case.
+    let found = db.search_workflows("warehouse-db".into()).await.unwrap();
+    assert_eq!(ids(found), vec![loader.id]);
+    assert_eq!(
+        ids(db.search_workflows("FINANCE".into()).await.unwrap()),
+        vec![payroll.id]
+    );
+    // `_` is literal rather than a single-character wildcard.
+    assert_eq!(
+        ids(db.search_workflows("payroll_".into()).await.unwrap()),
+        vec![payroll.id]
+    );
+    assert_eq!(
+        db.search_workflows("payroll".into()).await.unwrap().len(),
+        2
+    );
+    assert!(db.search_workflows("100%".into()).await.unwrap().is_empty());
+
+    // every term has to match, in any order; a quoted phrase has to match as written.
+    assert_eq!(
+        ids(db
+            .search_workflows("sales warehouse-db".into())
+            .await
+            .unwrap()),
+        vec![loader.id]
+    );
+    assert!(
+        db.search_workflows("warehouse-db payroll".into())
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert!(
+        db.search_workflows("\"sales warehouse\"".into())
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    // a trigger's configuration counts as part of its workflow.
+    db.upsert_workflow_trigger(&WorkflowTrigger {
+        id: None,
+        workflow_id: payroll.id.unwrap(),
+        kind: WorkflowTriggerKind::Cron,
+        enabled: true,
+        configuration: runinator_models::json!({
+            "cron": "0 0 1 * * *",
+            "parameters": { "table": "ledger_entries" }
+        }),
+        next_execution: None,
+        blackout_start: None,
+        blackout_end: None,
+        metadata: runinator_models::json!({}),
+        created_at: None,
+        updated_at: None,
+    })
+    .await
+    .unwrap();
+    assert_eq!(
+        ids(db
+            .search_workflows("LEDGER_ENTRIES finance".into())
+            .await
+            .unwrap()),
+        vec![payroll.id]
+    );
+
+    let _ = fs::remove_file(path);
+}
+
+// the full-text index follows every save, rename, trigger edit, and delete.
+#[tokio::test]
+async fn workflow_search_index_follows_workflow_and_trigger_writes() {
+    let path = std::env::temp_dir().join(format!(
+        "runinator-workflow-search-index-{}.db",
+        Utc::now().timestamp_nanos_opt().unwrap()
+    ));
+    let db = SqliteDb::new(path.to_str().unwrap()).await.unwrap();
+    db.run_init_scripts(&Vec::new()).await.unwrap();
+
+    let saved = db.upsert_workflow(&workflow("orders_sync")).await.unwrap();
+    let ids = |found: Vec<WorkflowDefinition>| {
+        found
+            .into_iter()
+            .map(|workflow| workflow.id)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        ids(db.search_workflows("ORDERS".into()).await.unwrap()),
+        vec![saved.id]
+    );
+    // a term too short for the trigram index still matches.
+    assert_eq!(
+        ids(db.search_workflows("rs".into()).await.unwrap()),
+        vec![saved.id]
+    );
+
+    let mut renamed = saved.clone();
+    renamed.name = "invoices_sync".into();
+    db.upsert_workflow(&renamed).await.unwrap();
+    assert!(
+        db.search_workflows("orders".into())
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert_eq!(
+        ids(db.search_workflows("invoices".into()).await.unwrap()),
+        vec![saved.id]
+    );
+
+    let trigger = db
+        .upsert_workflow_trigger(&WorkflowTrigger {
+            id: None,
+            workflow_id: saved.id.unwrap(),
+            kind: WorkflowTriggerKind::Cron,
+            enabled: true,
+            configuration: runinator_models::json!({
+                "cron": "0 0 1 * * *",
+                "parameters": { "region": "emea" }
+            }),
+            next_execution: None,
+            blackout_start: None,
+            blackout_end: None,
+            metadata: runinator_models::json!({}),
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(
+        ids(db.search_workflows("emea".into()).await.unwrap()),
+        vec![saved.id]
+    );
+    db.delete_workflow_trigger(trigger.id.unwrap())
+        .await
+        .unwrap();
+    assert!(db.search_workflows("emea".into()).await.unwrap().is_empty());
+
+    // terms past the cap are dropped rather than growing the query.
+    let terms = format!("invoices {}", ["sync"; 7].join(" "));
+    assert_eq!(
+        ids(db
+            .search_workflows(format!("{terms} missing"))
+            .await
+            .unwrap()),
+        vec![saved.id]
+    );
+
+    db.delete_workflow(saved.id.unwrap()).await.unwrap();
+    assert!(
+        db.search_workflows("invoices".into())
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    let _ = fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn upsert_workflows_saves_a_batch_or_nothing() {
+    let path = std::env::temp_dir().join(format!(
+        "runinator-workflow-bulk-{}.db",
+        Utc::now().timestamp_nanos_opt().unwrap()
+    ));
+    let db = SqliteDb::new(path.to_str().unwrap()).await.unwrap();
+    db.run_init_scripts(&Vec::new()).await.unwrap();
+    let existing = db.upsert_workflow(&workflow("alpha")).await.unwrap();
+
+    let mut alpha = workflow("alpha");
+    alpha.version = runinator_models::semver::SemVer::new(2, 0, 0);
+    let saved = db
+        .upsert_workflows(&[alpha, workflow("beta")])
+        .await
+        .unwrap();
+    // results come back in input order; the name-matched entry updates in place.
+    assert_eq!(saved.len(), 2);
+    assert_eq!(saved[0].id, existing.id);
+    assert_eq!(
+        saved[0].version,
+        runinator_models::semver::SemVer::new(2, 0, 0)
+    );
+    assert_eq!(saved[1].name, "beta");
+    assert_eq!(db.fetch_workflows().await.unwrap().len(), 2);
+
+    // a row the database refuses part-way through rolls back the rows before it.
+    sqlx::query(
+        "CREATE TRIGGER reject_boom BEFORE INSERT ON workflows WHEN NEW.name = 'boom'
+         BEGIN SELECT RAISE(ABORT, 'rejected'); END",
+    )
+    .execute(&db.pool)
+    .await
+    .unwrap();
+    let result = db
+        .upsert_workflows(&[workflow("gamma"), workflow("boom")])
+        .await;
+    assert!(result.is_err());
+    let names = db
+        .fetch_workflows()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|workflow| workflow.name)
+        .collect::<Vec<_>>();
+    assert!(!names.contains(&"gamma".to_string()));
+    assert_eq!(names.len(), 2);
+
+    let _ = fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn set_workflow_enabled_flips_only_the_flag() {
+    let path = std::env::temp_dir().join(format!(
+        "runinator-workflow-enabled-{}.db",
+        Utc::now().timestamp_nanos_opt().unwrap()
+    ));
+    let db = SqliteDb::new(path.to_str().unwrap()).await.unwrap();
+    db.run_init_scripts(&Vec::new()).await.unwrap();
+    let mut original = workflow("toggle");
+    original.namespace = Some("ops".into());
+    let saved = db.upsert_workflow(&original).await.unwrap();
+    let id = saved.id.unwrap();
+
+    assert!(db.set_workflow_enabled(id, false).await.unwrap());
+    let disabled = db.fetch_workflow(id).await.unwrap().unwrap();
+    assert!(!disabled.enabled);
+    assert_eq!(disabled.namespace.as_deref(), Some("ops"));
+    assert_eq!(disabled.definition, saved.definition);
+
+    assert!(db.set_workflow_enabled(id, true).await.unwrap());
+    assert!(db.fetch_workflow(id).await.unwrap().unwrap().enabled);
+    assert!(!db.set_workflow_enabled(Uuid::new_v4(), true).await.unwrap());
+
+    let _ = fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn workflow_revisions_number_per_workflow_and_restore() {
+    let path = std::env::temp_dir().join(format!(
+        "runinator-workflow-revisions-{}.db",
+        Utc::now().timestamp_nanos_opt().unwrap()
+    ));
+    let db = SqliteDb::new(path.to_str().unwrap()).await.unwrap();
+    db.run_init_scripts(&Vec::new()).await.unwrap();
+    let first = db.upsert_workflow(&workflow("history")).await.unwrap();
+    let id = first.id.unwrap();
+    let author = Uuid::new_v4();
+    db.record_workflow_revision(&first, Some(author))
+        .await
+        .unwrap();
+    let mut edited = first.clone();
+    edited.enabled = false;
+    let edited = db.upsert_workflow(&edited).await.unwrap();
+    let second = db.record_workflow_revision(&edited, None).await.unwrap();
+    assert_eq!(second.revision, 2);
+
+    let revisions = db.fetch_workflow_revisions(id).await.unwrap();
+    assert_eq!(
+        revisions.iter().map(|r| r.revision).collect::<Vec<_>>(),
+        vec![2, 1]
+    );
+    let original = db.fetch_workflow_revision(id, 1).await.unwrap().unwrap();
+    assert_eq!(original.changed_by, Some(author));
+    let restored = original.restore();
+    assert_eq!(restored.id, Some(id));
+    assert!(restored.enabled);
+    assert!(db.fetch_workflow_revision(id, 3).await.unwrap().is_none());
+
+    // deleting the workflow takes its history with it.
+    db.delete_workflow(id).await.unwrap();
+    assert!(db.fetch_workflow_revisions(id).await.unwrap().is_empty());
+
+    let _ = fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn pending_changes_to_protected_workflows_are_reviewed_once() {
+    let path = std::env::temp_dir().join(format!(
+        "runinator-workflow-protection-{}.db",
+        Utc::now().timestamp_nanos_opt().unwrap()
+    ));
+    let db = SqliteDb::new(path.to_str().unwrap()).await.unwrap();
+    db.run_init_scripts(&Vec::new()).await.unwrap();
+    let saved = db.upsert_workflow(&workflow("guarded")).await.unwrap();
+    let id = saved.id.unwrap();
+    assert!(db.fetch_workflow_protection(id).await.unwrap().is_none());
+
+    let owner = Uuid::new_v4();
+    let protection = db.protect_workflow(id, Some(owner)).await.unwrap();
+    assert_eq!(protection.protected_by, Some(owner));
+    // protecting twice keeps the original record.
+    let again = db.protect_workflow(id, None).await.unwrap();
+    assert_eq!(again.protected_by, Some(owner));
+
+    let mut edited = saved.clone();
+    edited.enabled = false;
+    let change = db
+        .create_pending_workflow_change(&NewPendingWorkflowChange {
+            workflow_id: id,
+            kind: PendingChangeKind::Update,
+            payload: Value::encode(&edited).unwrap(),
+            requested_by: Some(owner),
+        })
+        .await
+        .unwrap();
+    assert_eq!(change.status, PendingChangeStatus::Pending);
+    assert!(!change.workflow().unwrap().enabled);
+    let disable = db
+        .create_pending_workflow_change(&NewPendingWorkflowChange {
+            workflow_id: id,
+            kind: PendingChangeKind::Disable,
+            payload: Value::Null,
+            requested_by: Some(owner),
+        })
+        .await
+        .unwrap();
+    assert_eq!(
+        db.fetch_pending_workflow_changes(id)
+            .await
+            .unwrap()
+            .iter()
+            .map(|change| change.id)
+            .collect::<Vec<_>>(),
+        vec![change.id, disable.id]
+    );
+
+    let approver = Uuid::new_v4();
+    assert!(
+        db.review_pending_workflow_change(change.id, PendingChangeStatus::Approved, Some(approver))
+            .await
+            .unwrap()
+    );
+    // a second review of the same change is refused.
+    assert!(
+        !db.review_pending_workflow_change(
+            change.id,
+            PendingChangeStatus::Rejected,
+            Some(approver)
+        )
+        .await
+        .unwrap()
+    );
+    let reviewed = db
+        .fetch_pending_workflow_change(change.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(reviewed.status, PendingChangeStatus::Approved);
+    assert_eq!(reviewed.reviewed_by, Some(approver));
+    assert!(reviewed.reviewed_at.is_some());
+    assert_eq!(
+        db.fetch_pending_workflow_changes(id).await.unwrap().len(),
+        1
+    );
+
+    assert!(db.unprotect_workflow(id).await.unwrap());
+    assert!(!db.unprotect_workflow(id).await.unwrap());
+
+    // deleting the workflow takes its pending changes with it.
+    db.protect_workflow(id, None).await.unwrap();
+    db.delete_workflow(id).await.unwrap();
+    assert!(db.fetch_workflow_protection(id).await.unwrap().is_none());
+    assert!(
+        db.fetch_pending_workflow_change(disable.id)
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    let _ = fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn workflow_templates_save_replace_and_delete() {
+    let path = std::env::temp_dir().join(format!(
+        "runinator-workflow-templates-{}.db",
+        Utc::now().timestamp_nanos_opt().unwrap()
+    ));
+    let db = SqliteDb::new(path.to_str().unwrap()).await.unwrap();
+    db.run_init_scripts(&Vec::new()).await.unwrap();
+    let mut template = runinator_models::templates::WorkflowTemplate {
+        id: None,
+        name: "nightly export".into(),
+        description: None,
+        org_id: None,
+        cron: Some("0 0 2 * * *".into()),
+        labels: vec!["data".into()],
+        workflow: workflow("export"),
+        created_at: None,
+        updated_at: None,
+    };
+    let saved = db.upsert_workflow_template(&template).await.unwrap();
+    let id = saved.id.unwrap();
+    assert_eq!(saved.labels, vec!["data".to_string()]);
+    assert_eq!(saved.workflow.name, "export");
+
+    template.id = Some(id);
+    template.cron = None;
+    let replaced = db.upsert_workflow_template(&template).await.unwrap();
+    assert_eq!(replaced.cron, None);
+    assert_eq!(replaced.created_at, saved.created_at);
+    assert_eq!(db.fetch_workflow_templates().await.unwrap().len(), 1);
+
+    assert!(db.delete_workflow_template(id).await.unwrap());
+    assert!(db.fetch_workflow_template(id).await.unwrap().is_none());
+    assert!(!db.delete_workflow_template(id).await.unwrap());
+
+    let _ = fs::remove_file(path);
+}
+
 #[tokio::test]
 async fn insert_workflow_creates_sibling_row_sharing_name() {
     let path = std::env::temp_dir().join(format!(
@@ -550,6 +2003,240 @@ async fn apply_workflow_result_event_is_idempotent_for_chunks() {
     let _ = fs::remove_file(path);
 }
 
+#[tokio::test]
+async fn cluster_pause_defaults_to_running_and_round_trips() {
+    let path = std::env::temp_dir().join(format!(
+        "runinator-cluster-pause-{}.db",
+        Utc::now().timestamp_nanos_opt().unwrap()
+    ));
+    let db = SqliteDb::new(path.to_str().unwrap()).await.unwrap();
+    db.run_init_scripts(&Vec::new()).await.unwrap();
+    assert!(!db.fetch_cluster_pause().await.unwrap().is_paused());
+
+    let operator = Uuid::new_v4();
+    let pause = runinator_models::cluster::ClusterPause {
+        dispatch: true,
+        execution: false,
+        scheduling: true,
+        reason: Some("broker failover".into()),
+        updated_by: Some(operator),
+        updated_at: DateTime::<Utc>::from_timestamp(Utc::now().timestamp(), 0),
+    };
+    db.set_cluster_pause(&pause).await.unwrap();
+    assert_eq!(db.fetch_cluster_pause().await.unwrap(), pause);
+
+    // resuming overwrites the same row rather than adding another.
+    let resumed = runinator_models::cluster::ClusterPause {
+        updated_at: pause.updated_at,
+        ..Default::default()
+    };
+    db.set_cluster_pause(&resumed).await.unwrap();
+    assert_eq!(db.fetch_cluster_pause().await.unwrap(), resumed);
+
+    let _ = fs::remove_file(path);
+}
+
+// a second scheduler cannot take a live lease, takes it once it lapses, and the first one is then
+// refused; a release by a non-holder is a no-op.
+#[tokio::test]
+async fn scheduler_lease_fails_over_only_after_it_expires() {
+    let path = std::env::temp_dir().join(format!(
+        "runinator-scheduler-lease-{}.db",
+        Utc::now().timestamp_nanos_opt().unwrap()
+    ));
+    let db = SqliteDb::new(path.to_str().unwrap()).await.unwrap();
+    db.run_init_scripts(&Vec::new()).await.unwrap();
+    let start = DateTime::<Utc>::from_timestamp(1_772_366_400, 0).unwrap();
+    let ttl = Duration::seconds(15);
+
+    assert!(
+        db.fetch_scheduler_lease("scheduler")
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert!(
+        db.acquire_scheduler_lease("scheduler", "a", start, start + ttl)
+            .await
+            .unwrap()
+    );
+    assert!(
+        !db.acquire_scheduler_lease("scheduler", "b", start, start + ttl)
+            .await
+            .unwrap()
+    );
+
+    // renewing keeps the original acquisition time.
+    let renewed = start + Duration::seconds(5);
+    assert!(
+        db.acquire_scheduler_lease("scheduler", "a", renewed, renewed + ttl)
+            .await
+            .unwrap()
+    );
+    let lease = db
+        .fetch_scheduler_lease("scheduler")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(lease.holder, "a");
+    assert_eq!(lease.acquired_at, start);
+    assert_eq!(lease.expires_at, renewed + ttl);
+
+    let lapsed = renewed + ttl + Duration::seconds(1);
+    assert!(
+        db.acquire_scheduler_lease("scheduler", "b", lapsed, lapsed + ttl)
+            .await
+            .unwrap()
+    );
+    assert!(
+        !db.acquire_scheduler_lease("scheduler", "a", lapsed, lapsed + ttl)
+            .await
+            .unwrap()
+    );
+    let lease = db
+        .fetch_scheduler_lease("scheduler")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!((lease.holder.as_str(), lease.acquired_at), ("b", lapsed));
+
+    db.release_scheduler_lease("scheduler", "a").await.unwrap();
+    assert!(
+        db.fetch_scheduler_lease("scheduler")
+            .await
+            .unwrap()
+            .is_some()
+    );
+    db.release_scheduler_lease("scheduler", "b").await.unwrap();
+    assert!(
+        db.acquire_scheduler_lease("scheduler", "a", lapsed, lapsed + ttl)
+            .await
+            .unwrap()
+    );
+
+    let _ = fs::remove_file(path);
+}
+
+// closing on shutdown waits for an open transaction to finish, and what it wrote survives a reopen.
+#[tokio::test]
+async fn close_waits_for_in_flight_writes() {
+    let path = std::env::temp_dir().join(format!(
+        "runinator-close-{}.db",
+        Utc::now().timestamp_nanos_opt().unwrap()
+    ));
+    let db = std::sync::Arc::new(SqliteDb::new(path.to_str().unwrap()).await.unwrap());
+    db.run_init_scripts(&Vec::new()).await.unwrap();
+
+    let mut tx = db.pool.begin().await.unwrap();
+    sqlx::query(
+        "INSERT INTO settings (kind, scope, name, value, updated_at) VALUES (?, ?, ?, ?, ?)",
+    )
+    .bind("config")
+    .bind("import")
+    .bind("batch")
+    .bind(b"1".to_vec())
+    .bind(1_i64)
+    .execute(&mut *tx)
+    .await
+    .unwrap();
+    let close = tokio::spawn({
+        let db = db.clone();
+        async move { db.close().await }
+    });
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    assert!(
+        !close.is_finished(),
+        "close must wait for the open transaction"
+    );
+    tx.commit().await.unwrap();
+    close.await.unwrap();
+    assert!(db.pool.is_closed());
+
+    let reopened = SqliteDb::new(path.to_str().unwrap()).await.unwrap();
+    let setting = reopened
+        .fetch_setting(SettingKind::Config, "import".into(), "batch".into())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(setting.value, b"1");
+
+    let _ = fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn job_results_and_executor_leases_apply_to_the_job_row() {
+    let path = std::env::temp_dir().join(format!(
+        "runinator-jobs-{}.db",
+        Utc::now().timestamp_nanos_opt().unwrap()
+    ));
+    let db = SqliteDb::new(path.to_str().unwrap()).await.unwrap();
+    db.run_init_scripts(&Vec::new()).await.unwrap();
+    let job_id = Uuid::now_v7();
+    let command = action_command(job_id, job_id, runinator_models::jobs::JOB_NODE_ID);
+    let owner = Uuid::new_v4();
+    let job = db
+        .create_job(
+            job_id,
+            &command.action,
+            runinator_models::jobs::JobRetry::default(),
+            Some(owner),
+        )
+        .await
+        .unwrap();
+    assert_eq!(job.status, WorkflowStatus::Queued);
+    assert_eq!(job.requested_by, Some(owner));
+    assert_eq!(job.action.provider, "test");
+
+    let worker = Uuid::new_v4();
+    let now = Utc::now();
+    let stale_before = now - Duration::seconds(120);
+    assert!(
+        db.claim_job_executor(job_id, worker, now, stale_before)
+            .await
+            .unwrap()
+    );
+    assert!(
+        !db.claim_job_executor(job_id, Uuid::new_v4(), now, stale_before)
+            .await
+            .unwrap()
+    );
+    db.release_job_executor(job_id, worker).await.unwrap();
+    assert!(
+        db.claim_job_executor(job_id, Uuid::new_v4(), now, stale_before)
+            .await
+            .unwrap()
+    );
+
+    let running = WorkflowResultEvent::status(&command, WorkflowStatus::Running, None, None);
+    let succeeded = WorkflowResultEvent::status(
+        &command,
+        WorkflowStatus::Succeeded,
+        Some(runinator_models::json!({ "flushed": 3 })),
+        Some("done".into()),
+    );
+    assert!(db.apply_job_result_event(&running).await.unwrap());
+    assert!(db.apply_job_result_event(&succeeded).await.unwrap());
+    // a redelivered event is a no-op, and a late status cannot reopen a finished job.
+    assert!(!db.apply_job_result_event(&running).await.unwrap());
+    let late = WorkflowResultEvent::status(&command, WorkflowStatus::Running, None, None);
+    assert!(db.apply_job_result_event(&late).await.unwrap());
+
+    let job = db.fetch_job(job_id).await.unwrap().unwrap();
+    assert_eq!(job.status, WorkflowStatus::Succeeded);
+    assert_eq!(job.attempts.len(), 1);
+    assert_eq!(job.attempts[0].status, WorkflowStatus::Succeeded);
+    assert_eq!(
+        job.output_json,
+        Some(runinator_models::json!({ "flushed": 3 }))
+    );
+    assert_eq!(job.message.as_deref(), Some("done"));
+    assert!(job.started_at.is_some());
+    assert!(job.finished_at.is_some());
+    assert!(db.fetch_job(Uuid::new_v4()).await.unwrap().is_none());
+
+    let _ = fs::remove_file(path);
+}
+
 #[tokio::test]
 async fn apply_workflow_result_event_does_not_regress_terminal_status() {
     let path = std::env::temp_dir().join(format!(
@@ -1282,6 +2969,90 @@ async fn delete_workflow_cascades_runs_and_execution_records() {
     let _ = fs::remove_file(path);
 }
 
+#[tokio::test]
+async fn run_outputs_upsert_and_resolve_from_latest_successful_run() {
+    let path = std::env::temp_dir().join(format!(
+        "runinator-run-outputs-{}.db",
+        Utc::now().timestamp_nanos_opt().unwrap()
+    ));
+    let db = SqliteDb::new(path.to_str().unwrap()).await.unwrap();
+    db.run_init_scripts(&Vec::new()).await.unwrap();
+
+    let workflow_id = db
+        .upsert_workflow(&workflow("outputs-test"))
+        .await
+        .unwrap()
+        .id
+        .unwrap();
+    let snapshot = db.fetch_workflow(workflow_id).await.unwrap().unwrap();
+    let mut runs = Vec::new();
+    for (count, status) in [
+        (1, WorkflowStatus::Succeeded),
+        (2, WorkflowStatus::Succeeded),
+        (3, WorkflowStatus::Failed),
+    ] {
+        let run = db
+            .create_workflow_run(
+                workflow_id,
+                snapshot.clone(),
+                runinator_models::json!({}),
+                runinator_models::json!({}),
+                None,
+                Default::default(),
+            )
+            .await
+            .unwrap();
+        let outputs = BTreeMap::from([
+            ("count".to_string(), runinator_models::json!(count)),
+            ("label".to_string(), runinator_models::json!("first")),
+        ]);
+        db.record_workflow_run_outputs(run.id, workflow_id, &outputs)
+            .await
+            .unwrap();
+        db.update_workflow_run_status(run.id, status, None, None, None)
+            .await
+            .unwrap();
+        runs.push(run.id);
+    }
+
+    // recording a name again replaces its value.
+    let recorded = db
+        .record_workflow_run_outputs(
+            runs[0],
+            workflow_id,
+            &BTreeMap::from([("label".to_string(), runinator_models::json!("second"))]),
+        )
+        .await
+        .unwrap();
+    assert_eq!(recorded.len(), 2);
+    assert_eq!(recorded[1].name, "label");
+    assert_eq!(recorded[1].value, runinator_models::json!("second"));
+
+    // the failed run is skipped.
+    assert_eq!(
+        db.fetch_latest_workflow_output(workflow_id, "count")
+            .await
+            .unwrap(),
+        Some(runinator_models::json!(2))
+    );
+    assert!(
+        db.fetch_latest_workflow_output(workflow_id, "missing")
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    db.delete_workflow(workflow_id).await.unwrap();
+    assert!(
+        db.fetch_workflow_run_outputs(runs[0])
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    let _ = fs::remove_file(path);
+}
+
 #[tokio::test]
 async fn waiting_signal_runs_are_routable_by_correlation_key() {
     let path = std::env::temp_dir().join(format!(
@@ -1324,50 +3095,131 @@ async fn waiting_signal_runs_are_routable_by_correlation_key() {
                 )
                 .await
                 .unwrap();
-            let state = runinator_models::workflow_state::SignalState {
-                name: "github.review".into(),
-                correlation_key: Some(key.into()),
-            };
-            db.update_workflow_node_run(
-                node_run.id,
-                WorkflowStatus::Waiting,
-                Some(1),
-                None,
-                None,
-                Some(serde_json::to_value(&state).unwrap().into()),
-                Some("signal_waiting".into()),
-                None,
-            )
-            .await
-            .unwrap();
-            (run.id, node_run.id)
+            let state = runinator_models::workflow_state::SignalState {
+                name: "github.review".into(),
+                correlation_key: Some(key.into()),
+            };
+            db.update_workflow_node_run(
+                node_run.id,
+                WorkflowStatus::Waiting,
+                Some(1),
+                None,
+                None,
+                Some(serde_json::to_value(&state).unwrap().into()),
+                Some("signal_waiting".into()),
+                None,
+            )
+            .await
+            .unwrap();
+            (run.id, node_run.id)
+        }
+    };
+    let (run_abc, node_abc) = park("ABC-1").await;
+    let (_run_xyz, _node_xyz) = park("XYZ-9").await;
+
+    let waiting = db
+        .fetch_workflow_node_runs_by_status(WorkflowStatus::Waiting)
+        .await
+        .unwrap();
+    assert_eq!(waiting.len(), 2);
+
+    // the same predicate the ws repository uses must select exactly the matching run.
+    let matched: Vec<_> = waiting
+        .iter()
+        .filter(|run| {
+            serde_json::from_value::<runinator_models::workflow_state::SignalState>(
+                run.state.clone().into(),
+            )
+            .map(|state| {
+                state.name == "github.review" && state.correlation_key.as_deref() == Some("ABC-1")
+            })
+            .unwrap_or(false)
+        })
+        .collect();
+    assert_eq!(matched.len(), 1);
+    assert_eq!(matched[0].id, node_abc);
+    assert_eq!(matched[0].workflow_run_id, run_abc);
+
+    let _ = fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn node_run_counts_aggregate_outcomes_and_durations_per_task() {
+    let path = std::env::temp_dir().join(format!(
+        "runinator-node-run-counts-{}.db",
+        Utc::now().timestamp_nanos_opt().unwrap()
+    ));
+    let db = SqliteDb::new(path.to_str().unwrap()).await.unwrap();
+    db.run_init_scripts(&Vec::new()).await.unwrap();
+
+    let workflow_id = db
+        .upsert_workflow(&workflow("counts-test"))
+        .await
+        .unwrap()
+        .id
+        .unwrap();
+    let snapshot = db.fetch_workflow(workflow_id).await.unwrap().unwrap();
+    let run = db
+        .create_workflow_run(
+            workflow_id,
+            snapshot,
+            runinator_models::json!({}),
+            runinator_models::json!({}),
+            None,
+            Default::default(),
+        )
+        .await
+        .unwrap();
+    let settle = |node_id: &'static str, status: WorkflowStatus, seconds: i64| {
+        let db = &db;
+        async move {
+            let node_run = db
+                .create_workflow_node_run(run.id, node_id.into(), runinator_models::json!({}), None)
+                .await
+                .unwrap();
+            db.update_workflow_node_run(node_run.id, status, None, None, None, None, None, None)
+                .await
+                .unwrap();
+            if status.is_terminal() {
+                sqlx::query(
+                    "UPDATE workflow_node_runs SET started_at = finished_at - ? WHERE id = ?",
+                )
+                .bind(seconds)
+                .bind(node_run.id)
+                .execute(&db.pool)
+                .await
+                .unwrap();
+            }
         }
     };
-    let (run_abc, node_abc) = park("ABC-1").await;
-    let (_run_xyz, _node_xyz) = park("XYZ-9").await;
+    settle("dump", WorkflowStatus::Succeeded, 4).await;
+    settle("dump", WorkflowStatus::TimedOut, 10).await;
+    settle("notify", WorkflowStatus::Running, 0).await;
 
-    let waiting = db
-        .fetch_workflow_node_runs_by_status(WorkflowStatus::Waiting)
+    let mut counts = db
+        .fetch_node_run_counts(Utc::now() - Duration::hours(1))
         .await
         .unwrap();
-    assert_eq!(waiting.len(), 2);
+    counts.sort_by(|a, b| a.node_id.cmp(&b.node_id));
+    assert_eq!(counts.len(), 2);
+    assert_eq!(counts[0].workflow_id, workflow_id);
+    assert_eq!(counts[0].node_id, "dump");
+    assert_eq!(counts[0].runs, 2);
+    assert_eq!(counts[0].succeeded, 1);
+    assert_eq!(counts[0].failed, 1);
+    assert_eq!(counts[0].timed_runs, 2);
+    assert_eq!(counts[0].duration_ms, 14_000);
+    assert_eq!(counts[1].node_id, "notify");
+    assert_eq!(counts[1].runs, 1);
+    assert_eq!(counts[1].timed_runs, 0);
+    assert_eq!(counts[1].duration_ms, 0);
 
-    // the same predicate the ws repository uses must select exactly the matching run.
-    let matched: Vec<_> = waiting
-        .iter()
-        .filter(|run| {
-            serde_json::from_value::<runinator_models::workflow_state::SignalState>(
-                run.state.clone().into(),
-            )
-            .map(|state| {
-                state.name == "github.review" && state.correlation_key.as_deref() == Some("ABC-1")
-            })
-            .unwrap_or(false)
-        })
-        .collect();
-    assert_eq!(matched.len(), 1);
-    assert_eq!(matched[0].id, node_abc);
-    assert_eq!(matched[0].workflow_run_id, run_abc);
+    assert!(
+        db.fetch_node_run_counts(Utc::now() + Duration::hours(1))
+            .await
+            .unwrap()
+            .is_empty()
+    );
 
     let _ = fs::remove_file(path);
 }
@@ -1632,6 +3484,11 @@ fn action_command(
             mcp_enabled: false,
             tags: Vec::new(),
             required_labels: Default::default(),
+            allowed_secrets: Vec::new(),
+            preflight: Vec::new(),
+            cache_ttl_seconds: None,
+            priority: 0,
+            queue: None,
         },
         attempt: 1,
         parameters: runinator_models::json!({}),
@@ -1805,6 +3662,82 @@ async fn orgs_and_memberships_round_trip() {
     assert!(db.fetch_org(acme_id).await.unwrap().is_none());
 }
 
+#[tokio::test]
+async fn org_run_quota_round_trips_and_counts_only_claimed_active_runs() {
+    let path = std::env::temp_dir().join(format!(
+        "runinator-org-run-quota-{}.db",
+        Utc::now().timestamp_nanos_opt().unwrap()
+    ));
+    let db = SqliteDb::new(path.to_str().unwrap()).await.unwrap();
+    db.run_init_scripts(&Vec::new()).await.unwrap();
+
+    let org_id = db
+        .create_org("Acme".into(), "acme".into())
+        .await
+        .unwrap()
+        .id
+        .unwrap();
+    db.upsert_org_quota(runinator_models::billing::OrgQuota {
+        org_id,
+        max_concurrent_runs: 2,
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+    let quota = db.fetch_org_quota(org_id).await.unwrap().unwrap();
+    assert_eq!(quota.max_concurrent_runs, 2);
+
+    let mut owned = workflow("acme work");
+    owned.org_id = Some(org_id);
+    let owned_id = db.upsert_workflow(&owned).await.unwrap().id.unwrap();
+    let global_id = db
+        .upsert_workflow(&workflow("global work"))
+        .await
+        .unwrap()
+        .id
+        .unwrap();
+
+    let mut runs = Vec::new();
+    for workflow_id in [owned_id, owned_id, owned_id, global_id] {
+        let snapshot = db.fetch_workflow(workflow_id).await.unwrap().unwrap();
+        let run = db
+            .create_workflow_run(
+                workflow_id,
+                snapshot,
+                runinator_models::json!({}),
+                runinator_models::json!({}),
+                None,
+                Default::default(),
+            )
+            .await
+            .unwrap();
+        runs.push(run.id);
+    }
+    // queued runs hold no claim yet, so they do not count against the quota.
+    assert_eq!(
+        db.count_active_workflow_runs_for_org(org_id).await.unwrap(),
+        0
+    );
+
+    // one running, one waiting, one finished; the org-less run never counts.
+    for (run_id, status) in [
+        (runs[0], WorkflowStatus::Running),
+        (runs[1], WorkflowStatus::Waiting),
+        (runs[2], WorkflowStatus::Succeeded),
+        (runs[3], WorkflowStatus::Running),
+    ] {
+        db.update_workflow_run_status(run_id, status, None, None, None)
+            .await
+            .unwrap();
+    }
+    assert_eq!(
+        db.count_active_workflow_runs_for_org(org_id).await.unwrap(),
+        2
+    );
+
+    let _ = fs::remove_file(path);
+}
+
 #[tokio::test]
 async fn api_keys_support_admin_lookup_update_and_revoke() {
     let path = std::env::temp_dir().join(format!(
@@ -2093,6 +4026,105 @@ async fn archive_marking_skips_unread_notifications() {
     let _ = std::fs::remove_file(path);
 }
 
+#[tokio::test]
+async fn archive_marking_honors_workflow_retention_overrides() {
+    let path = std::env::temp_dir().join(format!(
+        "runinator-archive-retention-{}.db",
+        Utc::now().timestamp_nanos_opt().unwrap()
+    ));
+    let db = SqliteDb::new(path.to_str().unwrap()).await.unwrap();
+    db.run_init_scripts(&Vec::new()).await.unwrap();
+
+    let mut runs = Vec::new();
+    for name in ["global-retention", "kept-longer"] {
+        let workflow_id = db
+            .upsert_workflow(&workflow(name))
+            .await
+            .unwrap()
+            .id
+            .unwrap();
+        let snapshot = db.fetch_workflow(workflow_id).await.unwrap().unwrap();
+        let run = db
+            .create_workflow_run(
+                workflow_id,
+                snapshot,
+                runinator_models::json!({}),
+                runinator_models::json!({}),
+                None,
+                Default::default(),
+            )
+            .await
+            .unwrap();
+        db.update_workflow_run_status(run.id, WorkflowStatus::Succeeded, None, None, None)
+            .await
+            .unwrap();
+        sqlx::query("UPDATE workflow_runs SET created_at = ? WHERE id = ?")
+            .bind((Utc::now() - Duration::days(40)).timestamp())
+            .bind(run.id)
+            .execute(&db.pool)
+            .await
+            .unwrap();
+        runs.push((workflow_id, run.id));
+    }
+    let (kept_workflow, kept_run) = runs[1];
+    let retention = db
+        .upsert_workflow_retention(runinator_models::retention::WorkflowRetention {
+            workflow_id: kept_workflow,
+            retention_days: 365,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+    assert!(retention.updated_at.is_some());
+    assert_eq!(db.fetch_workflow_retentions().await.unwrap().len(), 1);
+
+    // the global 30-day window skips the overridden workflow; its own 365-day window keeps the run.
+    let global_cutoff = Utc::now() - Duration::days(30);
+    assert_eq!(
+        db.mark_archive_candidates(ArchiveTable::WorkflowRuns, global_cutoff, 100)
+            .await
+            .unwrap(),
+        1
+    );
+    assert_eq!(
+        db.mark_workflow_run_archive_candidates(
+            kept_workflow,
+            Utc::now() - Duration::days(365),
+            100
+        )
+        .await
+        .unwrap(),
+        0
+    );
+
+    // once the override is removed the run falls back to the global policy.
+    assert!(db.delete_workflow_retention(kept_workflow).await.unwrap());
+    assert!(
+        db.fetch_workflow_retention(kept_workflow)
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert_eq!(
+        db.mark_archive_candidates(ArchiveTable::WorkflowRuns, global_cutoff, 100)
+            .await
+            .unwrap(),
+        1
+    );
+    let marks = db
+        .claim_archive_marks(
+            "archiver-a".into(),
+            Utc::now(),
+            Utc::now() + Duration::seconds(60),
+            100,
+        )
+        .await
+        .unwrap();
+    assert!(marks.iter().any(|mark| mark.primary_key == kept_run));
+
+    let _ = std::fs::remove_file(path);
+}
+
 #[tokio::test]
 async fn jwt_secret_is_encrypted_at_rest_and_round_trips() {
     let path = std::env::temp_dir().join(format!(
@@ -2247,3 +4279,207 @@ async fn pipeline_round_trip_create_update_delete() {
 
     let _ = std::fs::remove_file(path);
 }
+
+// a failed report keeps its message and status filterable, and a later report without a worker id
+// leaves the recorded worker in place.
+#[tokio::test]
+async fn run_status_records_failure_message_and_worker() {
+    let path = std::env::temp_dir().join(format!(
+        "runinator-run-status-{}.db",
+        Utc::now().timestamp_nanos_opt().unwrap()
+    ));
+    let db = SqliteDb::new(path.to_str().unwrap()).await.unwrap();
+    db.run_init_scripts(&Vec::new()).await.unwrap();
+
+    let run_id = Uuid::now_v7();
+    sqlx::query(
+        "INSERT INTO runs (id, status, parameters, \"trigger\", created_at) VALUES (?, 'queued', '{}', 'api', ?)",
+    )
+    .bind(run_id)
+    .bind(Utc::now().timestamp())
+    .execute(db.pool())
+    .await
+    .unwrap();
+
+    db.update_run_status(
+        run_id,
+        RunStatus::Running,
+        None,
+        None,
+        Some("worker-a".into()),
+    )
+    .await
+    .unwrap();
+    db.update_run_status(
+        run_id,
+        RunStatus::Failed,
+        None,
+        Some("exit code 2".into()),
+        None,
+    )
+    .await
+    .unwrap();
+
+    assert!(
+        db.fetch_runs_by_status(RunStatus::Succeeded)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    let failed = db.fetch_runs_by_status(RunStatus::Failed).await.unwrap();
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].message.as_deref(), Some("exit code 2"));
+    assert_eq!(failed[0].worker_id.as_deref(), Some("worker-a"));
+    assert!(failed[0].started_at.is_some() && failed[0].finished_at.is_some());
+    assert!(failed[0].workflow_name.is_none());
+
+    let _ = fs::remove_file(path);
+}
+
+// a run dispatched for a workflow node comes back with the workflow's name joined in.
+#[tokio::test]
+async fn run_summaries_carry_their_workflow_name() {
+    let path = std::env::temp_dir().join(format!(
+        "runinator-run-workflow-name-{}.db",
+        Utc::now().timestamp_nanos_opt().unwrap()
+    ));
+    let db = SqliteDb::new(path.to_str().unwrap()).await.unwrap();
+    db.run_init_scripts(&Vec::new()).await.unwrap();
+    let saved = db
+        .upsert_workflow(&workflow("nightly export"))
+        .await
+        .unwrap();
+    let workflow_run = db
+        .create_workflow_run(
+            saved.id.unwrap(),
+            saved.clone(),
+            runinator_models::json!({}),
+            runinator_models::json!({}),
+            None,
+            Default::default(),
+        )
+        .await
+        .unwrap();
+
+    sqlx::query(
+        "INSERT INTO runs (id, status, parameters, \"trigger\", created_at, workflow_run_id, workflow_node_id) VALUES (?, 'queued', '{}', 'workflow', ?, ?, 'export')",
+    )
+    .bind(Uuid::now_v7())
+    .bind(Utc::now().timestamp())
+    .bind(workflow_run.id)
+    .execute(db.pool())
+    .await
+    .unwrap();
+
+    let queued = db.fetch_runs_by_status(RunStatus::Queued).await.unwrap();
+    assert_eq!(queued.len(), 1);
+    assert_eq!(queued[0].workflow_name.as_deref(), Some("nightly export"));
+    assert_eq!(queued[0].workflow_node_id.as_deref(), Some("export"));
+
+    let _ = fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn recent_runs_and_their_node_runs_load_in_one_read_each() {
+    let path = std::env::temp_dir().join(format!(
+        "runinator-recent-runs-batch-{}.db",
+        Utc::now().timestamp_nanos_opt().unwrap()
+    ));
+    let db = SqliteDb::new(path.to_str().unwrap()).await.unwrap();
+    db.run_init_scripts(&Vec::new()).await.unwrap();
+
+    let mut runs_by_workflow = Vec::new();
+    for name in ["batch-a", "batch-b"] {
+        let workflow_id = db
+            .upsert_workflow(&workflow(name))
+            .await
+            .unwrap()
+            .id
+            .unwrap();
+        let snapshot = db.fetch_workflow(workflow_id).await.unwrap().unwrap();
+        let mut run_ids = Vec::new();
+        for age in [30, 20, 10] {
+            let run = db
+                .create_workflow_run(
+                    workflow_id,
+                    snapshot.clone(),
+                    runinator_models::json!({}),
+                    runinator_models::json!({}),
+                    None,
+                    Default::default(),
+                )
+                .await
+                .unwrap();
+            sqlx::query("UPDATE workflow_runs SET created_at = ? WHERE id = ?")
+                .bind(Utc::now().timestamp() - age)
+                .bind(run.id)
+                .execute(&db.pool)
+                .await
+                .unwrap();
+            run_ids.push(run.id);
+        }
+        runs_by_workflow.push((workflow_id, run_ids));
+    }
+    let (first_workflow, first_runs) = &runs_by_workflow[0];
+    let (second_workflow, second_runs) = &runs_by_workflow[1];
+
+    let recent = db
+        .fetch_recent_workflow_runs_for_workflows(vec![*first_workflow, *second_workflow], 2)
+        .await
+        .unwrap();
+    assert_eq!(recent.len(), 4);
+    for (workflow_id, run_ids) in &runs_by_workflow {
+        let kept = recent
+            .iter()
+            .filter(|run| run.workflow_id == *workflow_id)
+            .map(|run| run.id)
+            .collect::<Vec<_>>();
+        assert_eq!(kept, vec![run_ids[2], run_ids[1]]);
+    }
+    let only_second = db
+        .fetch_recent_workflow_runs_for_workflows(vec![*second_workflow], 5)
+        .await
+        .unwrap();
+    assert_eq!(
+        only_second.iter().map(|run| run.id).collect::<Vec<_>>(),
+        vec![second_runs[2], second_runs[1], second_runs[0]]
+    );
+
+    for node_id in ["extract", "load"] {
+        db.create_workflow_node_run(
+            first_runs[2],
+            node_id.into(),
+            runinator_models::json!({}),
+            None,
+        )
+        .await
+        .unwrap();
+    }
+    db.create_workflow_node_run(
+        second_runs[0],
+        "extract".into(),
+        runinator_models::json!({}),
+        None,
+    )
+    .await
+    .unwrap();
+    let node_runs = db
+        .fetch_workflow_node_runs_for_runs(vec![first_runs[2], second_runs[0], first_runs[0]])
+        .await
+        .unwrap();
+    assert_eq!(node_runs.len(), 3);
+    let first = node_runs
+        .iter()
+        .filter(|node_run| node_run.workflow_run_id == first_runs[2])
+        .map(|node_run| node_run.node_id.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(first, vec!["extract", "load"]);
+    assert!(
+        db.fetch_workflow_node_runs_for_runs(vec![first_runs[0]])
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    let _ = fs::remove_file(path);
+}
@@ -2,7 +2,10 @@
 
 use clap::ValueEnum;
 
-pub use runinator_database::{mysql::MySqlDb, postgres::PostgresDb, sqlite::SqliteDb};
+pub use runinator_database::{
+    mysql::MySqlDb, pool::PoolSettings, postgres::PostgresDb, replica::read_url_from_env,
+    sqlite::SqliteDb,
+};
 
 /// database backend selected by a CLI flag (also reads `RUNINATOR_DATABASE`).
 #[derive(Clone, Copy, Debug, ValueEnum)]
@@ -19,24 +22,58 @@ pub enum DatabaseBackend {
 /// `sqlite` and `url` are connection-string expressions evaluated only in their matching
 /// arm, so each arm can resolve (and error on) just the inputs it needs. `$body` is
 /// expanded once per backend with `$db` bound to an `Arc<concrete db>`; it may use `.await`
-/// and `?` from the surrounding async context.
+/// and `?` from the surrounding async context. the pool is tuned by an optional `pool:`
+/// [`PoolSettings`], else from the `RUNINATOR_DB_*` environment. an optional `read_url:` (else
+/// `RUNINATOR_DB_READ_URL`) points postgres listing and stats queries at a read replica; the other
+/// backends ignore it.
 #[macro_export]
 macro_rules! dispatch_database {
     ($backend:expr, sqlite: $sqlite:expr, url: $url:expr, |$db:ident| $body:block) => {
+        $crate::dispatch_database!(
+            $backend,
+            pool: $crate::PoolSettings::from_env(),
+            sqlite: $sqlite,
+            url: $url,
+            |$db| $body
+        )
+    };
+    ($backend:expr, pool: $pool:expr, sqlite: $sqlite:expr, url: $url:expr, |$db:ident| $body:block) => {
+        $crate::dispatch_database!(
+            $backend,
+            pool: $pool,
+            read_url: $crate::read_url_from_env(),
+            sqlite: $sqlite,
+            url: $url,
+            |$db| $body
+        )
+    };
+    ($backend:expr, pool: $pool:expr, read_url: $read_url:expr, sqlite: $sqlite:expr, url: $url:expr, |$db:ident| $body:block) => {
         match $backend {
             $crate::DatabaseBackend::Sqlite => {
                 let __conn: String = $sqlite;
-                let $db = ::std::sync::Arc::new($crate::SqliteDb::new(&__conn).await?);
+                let __pool: $crate::PoolSettings = $pool;
+                let $db = ::std::sync::Arc::new(
+                    $crate::SqliteDb::with_pool_settings(&__conn, &__pool).await?,
+                );
                 $body
             }
             $crate::DatabaseBackend::Postgres => {
                 let __conn: String = $url;
-                let $db = ::std::sync::Arc::new($crate::PostgresDb::new(&__conn).await?);
+                let __pool: $crate::PoolSettings = $pool;
+                let __read_url: Option<String> = $read_url;
+                let mut __db = $crate::PostgresDb::with_pool_settings(&__conn, &__pool).await?;
+                if let Some(__read_url) = __read_url {
+                    __db = __db.with_read_replica(&__read_url, &__pool)?;
+                }
+                let $db = ::std::sync::Arc::new(__db);
                 $body
             }
             $crate::DatabaseBackend::Mysql => {
                 let __conn: String = $url;
-                let $db = ::std::sync::Arc::new($crate::MySqlDb::new(&__conn).await?);
+                let __pool: $crate::PoolSettings = $pool;
+                let $db = ::std::sync::Arc::new(
+                    $crate::MySqlDb::with_pool_settings(&__conn, &__pool).await?,
+                );
                 $body
             }
         }
@@ -207,7 +207,12 @@ fn apply_event_metrics(shared: &SharedHandle, event: &WorkerEvent) {
                 duration_ms: *duration_ms,
             });
         }
-        WorkerEvent::ControlReceived { .. } => {}
+        WorkerEvent::PollingPaused { .. }
+        | WorkerEvent::PollingResumed { .. }
+        | WorkerEvent::ExecutionPaused { .. }
+        | WorkerEvent::ExecutionResumed
+        | WorkerEvent::VersionsOutdated { .. }
+        | WorkerEvent::ControlReceived { .. } => {}
     }
 }
 
@@ -262,6 +267,7 @@ fn describe_worker_event(event: &WorkerEvent) -> String {
             outcome,
             duration_ms,
             message,
+            error,
             ..
         } => {
             let subject = format!(
@@ -274,12 +280,52 @@ fn describe_worker_event(event: &WorkerEvent) -> String {
                 }
                 ActionOutcome::TimedOut => format!("Timed out {subject} after {duration_ms} ms."),
                 ActionOutcome::Canceled => format!("Canceled {subject} after {duration_ms} ms."),
-                ActionOutcome::Failed => format!(
-                    "Failed {subject} after {duration_ms} ms: {}.",
-                    message.as_deref().unwrap_or("no error message")
-                ),
+                ActionOutcome::Failed => match error {
+                    Some(error) => format!(
+                        "Failed {subject} after {duration_ms} ms [{}{}]: {}.",
+                        error.code,
+                        if error.retryable {
+                            ""
+                        } else {
+                            ", not retryable"
+                        },
+                        error.message
+                    ),
+                    None => format!(
+                        "Failed {subject} after {duration_ms} ms: {}.",
+                        message.as_deref().unwrap_or("no error message")
+                    ),
+                },
             }
         }
+        WorkerEvent::PollingPaused { unreachable_ms } => format!(
+            "API unreachable for {}s; paused picking up new actions.",
+            unreachable_ms / 1000
+        ),
+        WorkerEvent::PollingResumed { paused_ms } => format!(
+            "API reachable again; resumed picking up actions after {}s.",
+            paused_ms / 1000
+        ),
+        WorkerEvent::ExecutionPaused { reason } => match reason {
+            Some(reason) => {
+                format!("Execution paused cluster-wide ({reason}); holding new actions.")
+            }
+            None => "Execution paused cluster-wide; holding new actions.".to_string(),
+        },
+        WorkerEvent::ExecutionResumed => {
+            "Cluster execution resumed; picking up actions again.".to_string()
+        }
+        WorkerEvent::VersionsOutdated { outdated } if outdated.is_empty() => {
+            "Worker and plugins meet the fleet minimum version again.".to_string()
+        }
+        WorkerEvent::VersionsOutdated { outdated } => format!(
+            "Update available: {}.",
+            outdated
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("; ")
+        ),
         WorkerEvent::ControlReceived {
             kind,
             workflow_run_id,
@@ -479,6 +525,7 @@ async fn run_agent(shared: &SharedHandle, config: AgentConfig) -> Result<(), Str
                     broker_result_topic: DEFAULT_RESULT_TOPIC.to_string(),
                     broker_client_id: DEFAULT_BROKER_CLIENT_ID.to_string(),
                     api_key: config.api_key.clone(),
+                    broker_auth_secret: None,
                 },
                 description,
             )
@@ -497,6 +544,7 @@ async fn run_agent(shared: &SharedHandle, config: AgentConfig) -> Result<(), Str
                     broker_result_topic: DEFAULT_RESULT_TOPIC.to_string(),
                     broker_client_id: DEFAULT_BROKER_CLIENT_ID.to_string(),
                     api_key: config.api_key.clone(),
+                    broker_auth_secret: None,
                 },
                 description,
             )
@@ -622,9 +670,14 @@ async fn run_worker_loop_with_restart(
             replica_id: Some(replica_id),
             providers: providers.clone(),
             max_concurrent_actions,
+            max_delivery_attempts: None,
+            api_pause: None,
+            cluster_pause_check: Some(runinator_worker::DEFAULT_CLUSTER_PAUSE_CHECK),
             shutdown_grace,
             shutdown: shutdown.clone(),
             events: events.clone(),
+            command_listen: None,
+            update_channel: None,
         };
 
         set_connection(shared, ConnectionState::Connected);
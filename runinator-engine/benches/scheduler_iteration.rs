@@ -0,0 +1,102 @@
+//! scheduler iteration cost: one trigger-claim pass over a sqlite store holding 1k, 10k, and 100k
+//! cron triggers, none of them due. this is the steady-state work every scheduler tick pays, so it
+//! is what batching or parallel trigger evaluation has to beat.
+//!
+//! run with `cargo bench -p runinator-engine`. seeding the 100k store takes a while.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use runinator_database::interfaces::DatabaseImpl;
+use runinator_database::sqlite::SqliteDb;
+use runinator_engine::clock::ManualClock;
+use runinator_engine::trigger_source::{DatabaseTriggerSource, TriggerSource};
+use runinator_models::workflows::{
+    WorkflowDefinition, WorkflowGraph, WorkflowTrigger, WorkflowTriggerKind,
+};
+use tokio::runtime::Runtime;
+
+const CLAIM_LIMIT: i64 = 100;
+
+fn now() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap()
+}
+
+/// a fresh store with `count` hourly cron triggers whose next slot is a day past [`now`].
+async fn seeded_store(count: usize) -> (Arc<SqliteDb>, std::path::PathBuf) {
+    let path = std::env::temp_dir().join(format!(
+        "runinator-bench-scheduler-{count}-{}.db",
+        Utc::now().timestamp_nanos_opt().unwrap()
+    ));
+    let db = SqliteDb::new(path.to_str().unwrap()).await.unwrap();
+    db.run_init_scripts(&Vec::new()).await.unwrap();
+    let workflow_id = db
+        .upsert_workflow(&WorkflowDefinition {
+            id: None,
+            name: "bench".to_string(),
+            namespace: None,
+            org_id: None,
+            version: runinator_models::semver::SemVer::new(1, 0, 0),
+            enabled: true,
+            input_type: runinator_models::types::RuninatorType::Any,
+            definition: WorkflowGraph::from_value(runinator_models::json!({
+                "start": "start",
+                "nodes": [
+                    { "id": "start", "kind": "start", "transitions": { "next": { "$node": "done" } } },
+                    { "id": "done", "kind": "end" }
+                ]
+            }))
+            .unwrap(),
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap()
+        .id
+        .unwrap();
+    for _ in 0..count {
+        db.upsert_workflow_trigger(&WorkflowTrigger {
+            id: None,
+            workflow_id,
+            kind: WorkflowTriggerKind::Cron,
+            enabled: true,
+            configuration: runinator_models::json!({ "cron": "0 0 * * * *" }),
+            next_execution: Some(now() + Duration::days(1)),
+            blackout_start: None,
+            blackout_end: None,
+            metadata: runinator_models::json!({}),
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+    }
+    (Arc::new(db), path)
+}
+
+fn idle_claim_pass(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let mut group = c.benchmark_group("scheduler_iteration");
+    group.sample_size(20);
+    for count in [1_000, 10_000, 100_000] {
+        let (db, path) = runtime.block_on(seeded_store(count));
+        // pinned before every slot, so each pass scans and claims nothing.
+        let source = DatabaseTriggerSource::with_clock(db, ManualClock::new(now()));
+        group.bench_with_input(BenchmarkId::new("idle_claim", count), &count, |b, _| {
+            b.to_async(&runtime).iter(|| async {
+                let fired = source
+                    .claim_due_workflow_firings("bench", CLAIM_LIMIT)
+                    .await
+                    .unwrap();
+                assert!(fired.is_empty());
+            })
+        });
+        drop(source);
+        let _ = std::fs::remove_file(path);
+    }
+    group.finish();
+}
+
+criterion_group!(benches, idle_claim_pass);
+criterion_main!(benches);
@@ -0,0 +1,71 @@
+//! the time source for scheduling decisions.
+//!
+//! trigger claims ask a [`Clock`] for "now" instead of reading the wall clock, so cron slots and
+//! blackout windows can be exercised deterministically by pinning or stepping a [`ManualClock`].
+//! everything outside tests runs on [`SystemClock`].
+
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use chrono::{DateTime, Duration, Utc};
+
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// the wall clock.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+impl<C: Clock + ?Sized> Clock for Arc<C> {
+    fn now(&self) -> DateTime<Utc> {
+        self.as_ref().now()
+    }
+}
+
+/// a clock that only moves when told to. share it behind an `Arc` to step time while a loop or
+/// trigger source holds another handle.
+#[derive(Debug)]
+pub struct ManualClock {
+    now: Mutex<DateTime<Utc>>,
+}
+
+impl ManualClock {
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            now: Mutex::new(now),
+        }
+    }
+
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.lock() = now;
+    }
+
+    pub fn advance(&self, by: Duration) {
+        let mut now = self.lock();
+        *now += by;
+    }
+
+    fn lock(&self) -> MutexGuard<'_, DateTime<Utc>> {
+        match self.now.lock() {
+            Ok(guard) => guard,
+            // the guarded value is a plain timestamp; a panic elsewhere cannot leave it torn.
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.lock()
+    }
+}
+
+#[cfg(test)]
+#[path = "clock_tests.rs"]
+mod tests;
@@ -0,0 +1,140 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use runinator_database::interfaces::DatabaseImpl;
+use runinator_database::sqlite::SqliteDb;
+use runinator_models::workflows::{
+    WorkflowDefinition, WorkflowGraph, WorkflowTrigger, WorkflowTriggerKind,
+};
+use uuid::Uuid;
+
+use super::{Clock, ManualClock};
+use crate::trigger_source::{DatabaseTriggerSource, TriggerSource};
+
+fn at(hour: u32, minute: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 1, 1, hour, minute, 0).unwrap()
+}
+
+async fn next_execution(db: &SqliteDb, trigger_id: Uuid) -> Option<DateTime<Utc>> {
+    db.fetch_workflow_trigger(trigger_id)
+        .await
+        .unwrap()
+        .unwrap()
+        .next_execution
+}
+
+#[test]
+fn manual_clock_moves_only_when_stepped() {
+    let clock = ManualClock::new(at(9, 0));
+    assert_eq!(clock.now(), at(9, 0));
+    clock.advance(Duration::minutes(90));
+    assert_eq!(clock.now(), at(10, 30));
+    clock.set(at(8, 0));
+    assert_eq!(clock.now(), at(8, 0));
+}
+
+// an hourly trigger driven entirely by a pinned clock: nothing fires early, the due slot fires once,
+// and an overdue slot that lands in a blackout waits for the window to end.
+#[tokio::test]
+async fn trigger_claims_follow_the_supplied_clock() {
+    let path = std::env::temp_dir().join(format!(
+        "runinator-clock-{}.db",
+        Utc::now().timestamp_nanos_opt().unwrap()
+    ));
+    let db = Arc::new(SqliteDb::new(path.to_str().unwrap()).await.unwrap());
+    db.run_init_scripts(&Vec::new()).await.unwrap();
+
+    let workflow = db
+        .upsert_workflow(&WorkflowDefinition {
+            id: None,
+            name: "clock-test".to_string(),
+            namespace: None,
+            org_id: None,
+            version: runinator_models::semver::SemVer::new(1, 0, 0),
+            enabled: true,
+            input_type: runinator_models::types::RuninatorType::Any,
+            definition: WorkflowGraph::from_value(runinator_models::json!({
+                "start": "start",
+                "nodes": [
+                    { "id": "start", "kind": "start", "transitions": { "next": { "$node": "done" } } },
+                    { "id": "done", "kind": "end" }
+                ]
+            }))
+            .unwrap(),
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+    let trigger_id = db
+        .upsert_workflow_trigger(&WorkflowTrigger {
+            id: None,
+            workflow_id: workflow.id.unwrap(),
+            kind: WorkflowTriggerKind::Cron,
+            enabled: true,
+            configuration: runinator_models::json!({ "cron": "0 0 * * * *" }),
+            next_execution: Some(at(10, 0)),
+            blackout_start: Some(at(12, 0)),
+            blackout_end: Some(at(12, 45)),
+            metadata: runinator_models::json!({}),
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap()
+        .id
+        .unwrap();
+
+    let clock = Arc::new(ManualClock::new(at(9, 30)));
+    let source = DatabaseTriggerSource::with_clock(db.clone(), clock.clone());
+
+    assert!(
+        source
+            .claim_due_workflow_firings("a", 10)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    clock.advance(Duration::minutes(30));
+    let fired = source.claim_due_workflow_firings("a", 10).await.unwrap();
+    assert_eq!(fired.len(), 1);
+    let run = db
+        .fetch_workflow_run(fired[0].run_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(run.created_at, at(10, 0));
+    assert_eq!(next_execution(&db, trigger_id).await, Some(at(11, 0)));
+    assert!(
+        source
+            .claim_due_workflow_firings("a", 10)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    // the 11:00 slot is overdue at noon, but noon is blacked out: the slot moves to the window's end.
+    clock.set(at(12, 0));
+    assert!(
+        source
+            .claim_due_workflow_firings("a", 10)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert_eq!(next_execution(&db, trigger_id).await, Some(at(12, 45)));
+
+    clock.set(at(12, 46));
+    assert_eq!(
+        source
+            .claim_due_workflow_firings("a", 10)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+    assert_eq!(next_execution(&db, trigger_id).await, Some(at(13, 0)));
+
+    let _ = std::fs::remove_file(path);
+}
@@ -8,20 +8,27 @@ use tokio::task::JoinSet;
 use tracing::{error, info};
 
 use crate::events::EnginePublisher;
+use crate::file_arrival::{DirectoryArrivalSource, FileArrivalWatcher, run_file_arrival_loop};
+use crate::leader::{Leadership, release_leadership, run_leader_election};
 use crate::loops::{
-    run_action_dispatch_publisher, run_ingress_consumer, run_ready_node_reaper, run_replica_reaper,
-    run_trigger_loop, run_usage_sampler, run_wake_publisher,
+    run_action_dispatch_publisher, run_ingress_consumer, run_missed_run_checker,
+    run_ready_node_reaper, run_replica_reaper, run_trigger_loop, run_usage_sampler,
+    run_wake_publisher,
 };
 use crate::result_consumer::run_result_consumer;
+use crate::trigger_source::DatabaseTriggerSource;
 
-/// run the durable orchestration engine: the ingress/reducer, result, wake, trigger, action-dispatch
-/// loops plus the replica/ready-node/usage maintenance backstops. all loops share `shutdown`, and any
-/// loop exiting on its own (panic or early return) fails the whole process so it restarts and resumes
-/// from durable state rather than running on with a silently dead loop.
+/// run the durable orchestration engine: the ingress/reducer, result, wake, trigger, missed-run,
+/// file-arrival, action-dispatch loops plus the replica/ready-node/usage maintenance backstops. all
+/// loops share `shutdown`, and any loop exiting on its own (panic or early return) fails the whole
+/// process so it restarts and resumes from durable state rather than running on with a silently
+/// dead loop.
 ///
-/// the engine is safe to run N-up: the broker consumers compete on shared consumer ids, the trigger
-/// and action-dispatch loops claim disjoint rows per `instance_id`, wakes are broker-deduped, and the
-/// reapers are idempotent.
+/// the engine is safe to run N-up: the broker consumers compete on shared consumer ids, only the
+/// elected scheduler leader fires triggers and checks for missed runs (see [`crate::leader`]), the
+/// action-dispatch loop claims disjoint rows per `instance_id`, file arrivals are recorded as
+/// per-file trigger firings, missed-run alerts once per deadline, wakes are broker-deduped, and
+/// the reapers are idempotent.
 pub async fn run_background_engine<T: DatabaseImpl>(
     pool: Arc<T>,
     broker: Arc<dyn Broker>,
@@ -31,6 +38,7 @@ pub async fn run_background_engine<T: DatabaseImpl>(
 ) -> Result<(), SendableError> {
     crate::stability::init_metrics();
 
+    let leadership = Leadership::default();
     let mut loops: JoinSet<()> = JoinSet::new();
     loops.spawn(run_result_consumer(
         pool.clone(),
@@ -51,10 +59,29 @@ pub async fn run_background_engine<T: DatabaseImpl>(
         publisher.wake_nudge(),
         shutdown.clone(),
     ));
-    loops.spawn(run_trigger_loop(
+    loops.spawn(run_leader_election(
         pool.clone(),
+        leadership.clone(),
+        instance.clone(),
+        shutdown.clone(),
+    ));
+    loops.spawn(run_trigger_loop(
+        DatabaseTriggerSource::new(pool.clone()),
         publisher.clone(),
         instance.clone(),
+        leadership.clone(),
+        shutdown.clone(),
+    ));
+    loops.spawn(run_missed_run_checker(
+        pool.clone(),
+        publisher.clone(),
+        leadership,
+        shutdown.clone(),
+    ));
+    loops.spawn(run_file_arrival_loop(
+        pool.clone(),
+        FileArrivalWatcher::new(DirectoryArrivalSource),
+        publisher.clone(),
         shutdown.clone(),
     ));
     loops.spawn(run_action_dispatch_publisher(
@@ -75,6 +102,7 @@ pub async fn run_background_engine<T: DatabaseImpl>(
         _ = shutdown.notified() => {
             info!("shutting down background engine...");
             loops.shutdown().await;
+            release_leadership(pool.as_ref(), &instance).await;
             Ok(())
         }
         Some(joined) = loops.join_next() => {
@@ -92,6 +120,7 @@ pub async fn run_background_engine<T: DatabaseImpl>(
             crate::stability::record_background_loop_failure();
             shutdown.notify_waiters();
             loops.shutdown().await;
+            release_leadership(pool.as_ref(), &instance).await;
             Err(crate::errors::BACKGROUND_LOOP_EXITED.bare())
         }
     }
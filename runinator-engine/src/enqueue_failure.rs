@@ -0,0 +1,143 @@
+//! dead-lettering for action dispatches the broker keeps refusing. a failed publish leaves the
+//! dispatch in the outbox for the next pass; once it has failed [`dead_letter_threshold`] times in a
+//! row it is moved to the dead-letter queue instead, its node and run are failed, its workflow is
+//! disabled, and an error notification is raised so an operator looks at the broker.
+//!
+//! the dead letter is written before the outbox row is removed, so a dispatch is never dropped
+//! without a durable record. the notification is best-effort, like the other alert sinks.
+
+use runinator_comm::{ActionDispatchRecord, WorkflowResultEvent};
+use runinator_database::interfaces::DatabaseImpl;
+use runinator_models::{
+    errors::{SendableError, error_code_or_unknown},
+    json,
+    notifications::NewNotification,
+    workflows::WorkflowStatus,
+};
+use tracing::{error, warn};
+
+use crate::stability;
+
+/// environment variable overriding how many consecutive publish failures dead-letter a dispatch;
+/// `0` keeps retrying forever.
+pub const ENQUEUE_FAILURE_THRESHOLD_ENV: &str = "RUNINATOR_ENQUEUE_FAILURE_THRESHOLD";
+const DEFAULT_ENQUEUE_FAILURE_THRESHOLD: i64 = 5;
+/// the dead-letter channel dispatches are recorded under.
+pub const ENQUEUE_DEAD_LETTER_CHANNEL: &str = "action_dispatch";
+const ENQUEUE_FAILURE_NOTIFICATION_CHANNEL: &str = "enqueue_failure";
+
+/// the configured threshold, read from [`ENQUEUE_FAILURE_THRESHOLD_ENV`] and defaulting to five.
+/// `None` when dead-lettering is disabled.
+pub fn dead_letter_threshold() -> Option<i64> {
+    let threshold = std::env::var(ENQUEUE_FAILURE_THRESHOLD_ENV)
+        .ok()
+        .and_then(|raw| raw.trim().parse::<i64>().ok())
+        .unwrap_or(DEFAULT_ENQUEUE_FAILURE_THRESHOLD);
+    (threshold > 0).then_some(threshold)
+}
+
+/// move `dispatch`, which has now failed to publish `attempts` times with `reason`, out of the
+/// outbox and into the dead-letter queue, failing its node and run and disabling its workflow.
+pub async fn dead_letter_dispatch<T: DatabaseImpl>(
+    db: &T,
+    dispatch: &ActionDispatchRecord,
+    attempts: i64,
+    reason: &str,
+) -> Result<(), SendableError> {
+    let command = &dispatch.command;
+    db.record_dead_letter(json!({
+        "channel": ENQUEUE_DEAD_LETTER_CHANNEL,
+        "event_id": dispatch.id.to_string(),
+        "dedupe_key": dispatch.dedupe_key,
+        "attempts": attempts,
+        "error": reason,
+        "payload": serde_json::to_value(command)?,
+    }))
+    .await?;
+    db.delete_action_dispatch(dispatch.id).await?;
+    stability::enqueue_dead_letter();
+
+    let message = format!(
+        "Node '{}' failed to enqueue {attempts} times in a row: {reason}",
+        command.node_id
+    );
+    let job = runinator_models::jobs::is_job(
+        command.workflow_run_id,
+        command.workflow_node_run_id,
+        &command.node_id,
+    );
+    // a job has no run or workflow of its own; failing it goes through the result path so a job
+    // with retries left is dispatched again.
+    let workflow_id = if job {
+        let event = WorkflowResultEvent::status(
+            command,
+            WorkflowStatus::Failed,
+            None,
+            Some(message.clone()),
+        );
+        crate::repository::apply_workflow_result_event(db, &event).await?;
+        None
+    } else {
+        db.update_workflow_node_run(
+            command.workflow_node_run_id,
+            WorkflowStatus::Failed,
+            None,
+            None,
+            None,
+            None,
+            Some("enqueue_failed".into()),
+            Some(message.clone()),
+        )
+        .await?;
+        db.update_workflow_run_status(
+            command.workflow_run_id,
+            WorkflowStatus::Failed,
+            Some(command.node_id.clone()),
+            None,
+            Some(message.clone()),
+        )
+        .await?;
+        let workflow_id = db
+            .fetch_workflow_run(command.workflow_run_id)
+            .await?
+            .map(|run| run.workflow_id);
+        if let Some(workflow_id) = workflow_id {
+            db.set_workflow_enabled(workflow_id, false).await?;
+        }
+        workflow_id
+    };
+    warn!(
+        dispatch_id = %dispatch.id,
+        run_id = %command.workflow_run_id,
+        node_id = %command.node_id,
+        attempts,
+        "dead-lettered an action dispatch the broker kept refusing"
+    );
+
+    let notification = NewNotification {
+        workflow_run_id: (!job).then_some(command.workflow_run_id),
+        workflow_node_id: Some(command.node_id.clone()),
+        channel: ENQUEUE_FAILURE_NOTIFICATION_CHANNEL.into(),
+        severity: "error".into(),
+        title: format!("Node '{}' could not be enqueued", command.node_id),
+        body: Some(if job {
+            format!("{message}. The dispatch was dead-lettered and the job was failed.")
+        } else {
+            format!("{message}. The dispatch was dead-lettered and the workflow was disabled.")
+        }),
+        target: None,
+        metadata: json!({
+            "dispatch_id": dispatch.id.to_string(),
+            "workflow_id": workflow_id.map(|id| id.to_string()),
+            "workflow_node_run_id": command.workflow_node_run_id.to_string(),
+            "attempts": attempts,
+        }),
+    };
+    if let Err(err) = db.create_notification(&notification).await {
+        error!(
+            error_code = error_code_or_unknown(err.as_ref()),
+            "failed to persist enqueue failure alert: {}", err
+        );
+    }
+    Ok(())
+}
@@ -25,6 +25,38 @@ pub const IMPORT_UNKNOWN_PIPELINE_MEMBER: ErrorDescriptor = ErrorDescriptor::new
     "workflow.import.unknown_pipeline_member",
     "Imported pipeline references an unknown member workflow",
 );
+pub const INVALID_ONLY_IF: ErrorDescriptor = ErrorDescriptor::new(
+    "RUNI124",
+    "workflow.only_if.invalid",
+    "Workflow only_if expression is invalid",
+);
+pub const INVALID_DEPENDS_ON: ErrorDescriptor = ErrorDescriptor::new(
+    "RUNI127",
+    "workflow.depends_on.invalid",
+    "Workflow depends_on is invalid",
+);
+pub const INVALID_ALLOW_CONCURRENT: ErrorDescriptor = ErrorDescriptor::new(
+    "RUNI128",
+    "workflow.allow_concurrent.invalid",
+    "Workflow allow_concurrent is invalid",
+);
+pub const INVALID_FRESHNESS: ErrorDescriptor = ErrorDescriptor::new(
+    "RUNI129",
+    "workflow.freshness_seconds.invalid",
+    "Workflow freshness_seconds is invalid",
+);
+
+// file-arrival triggers.
+pub const INVALID_FILE_ARRIVAL: ErrorDescriptor = ErrorDescriptor::new(
+    "RUNI125",
+    "workflow.file_arrival.invalid",
+    "File arrival trigger configuration is invalid",
+);
+pub const FILE_ARRIVAL_UNSUPPORTED_LOCATION: ErrorDescriptor = ErrorDescriptor::new(
+    "RUNI126",
+    "workflow.file_arrival.unsupported_location",
+    "File arrival location is not a local directory",
+);
 
 // debug, control, and replay.
 pub const DEBUG_NOT_FOUND: ErrorDescriptor = ErrorDescriptor::new(
@@ -84,6 +116,23 @@ pub const REPLAY_CONTROL_FLOW: ErrorDescriptor = ErrorDescriptor::new(
     "workflow.replay.control_flow",
     "Cannot replay through a control-flow ancestor",
 );
+pub const INVALID_EXPECTED_MAX_INTERVAL: ErrorDescriptor = ErrorDescriptor::new(
+    "RUNI148",
+    "workflow.expected_max_interval_seconds.invalid",
+    "Workflow expected_max_interval_seconds is invalid",
+);
+// backfills.
+pub const INVALID_BACKFILL: ErrorDescriptor = ErrorDescriptor::new(
+    "RUNI146",
+    "workflow.backfill.invalid",
+    "Backfill request is invalid",
+);
+pub const BACKFILL_CONFLICT: ErrorDescriptor = ErrorDescriptor::new(
+    "RUNI147",
+    "workflow.backfill.conflict",
+    "Workflow already has a running backfill",
+);
+
 pub const BACKGROUND_LOOP_EXITED: ErrorDescriptor = ErrorDescriptor::new(
     "RUNI177",
     "ws.background.loop_exited",
@@ -95,6 +144,15 @@ pub const DICTIONARY: &[ErrorDescriptor] = &[
     IMPORT_INVALID_TRIGGER_BLACKOUT,
     IMPORT_UNKNOWN_CHAINED_TARGET,
     IMPORT_UNKNOWN_PIPELINE_MEMBER,
+    INVALID_ONLY_IF,
+    INVALID_DEPENDS_ON,
+    INVALID_ALLOW_CONCURRENT,
+    INVALID_FRESHNESS,
+    INVALID_EXPECTED_MAX_INTERVAL,
+    INVALID_FILE_ARRIVAL,
+    FILE_ARRIVAL_UNSUPPORTED_LOCATION,
+    INVALID_BACKFILL,
+    BACKFILL_CONFLICT,
     DEBUG_NOT_FOUND,
     DEBUG_DISABLED,
     DEBUG_TERMINAL,
@@ -0,0 +1,275 @@
+//! file-arrival triggers: start a workflow when a matching file lands in a watched directory.
+//!
+//! each enabled `file_arrival` trigger names a directory and a file name pattern
+//! ([`FileArrivalConfig`]). [`FileArrivalWatcher`] lists those directories through an
+//! [`ArrivalSource`] and treats a file as arrived once its size and modification time have held
+//! still for `stable_seconds`. settled files start one run per trigger, carrying their paths in the
+//! `files` parameter; `debounce_seconds` holds the next run until that long after the previous one,
+//! so files settling in between share it. every file is recorded as a trigger firing keyed on its
+//! path, size, and mtime, so engine instances watching the same directory start it once and a
+//! rewritten file fires again.
+
+use std::{collections::HashMap, future::Future, path::Path, sync::Arc, time::Duration};
+
+use chrono::{DateTime, Utc};
+use runinator_database::interfaces::DatabaseImpl;
+use runinator_models::errors::{SendableError, error_code_or_unknown};
+use runinator_models::workflows::{FileArrivalConfig, WorkflowTrigger, WorkflowTriggerKind};
+use tokio::sync::Notify;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::clock::{Clock, SystemClock};
+use crate::events::{AppEvent, AppEventKind, EventSender, emit, emit_workflow_run};
+use crate::repository;
+use crate::trigger_source::FiredRun;
+
+const FILE_ARRIVAL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// one file found in a watched location.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArrivedFile {
+    pub path: String,
+    pub size: u64,
+    pub modified: Option<DateTime<Utc>>,
+}
+
+impl ArrivedFile {
+    fn name(&self) -> Option<&str> {
+        Path::new(&self.path)
+            .file_name()
+            .and_then(|name| name.to_str())
+    }
+
+    // a rewrite changes the size or mtime, so it records (and fires) as a new arrival.
+    fn fire_key(&self) -> String {
+        let modified = self
+            .modified
+            .map(|modified| modified.timestamp_millis())
+            .unwrap_or_default();
+        format!("file:{}:{}:{modified}", self.path, self.size)
+    }
+}
+
+/// lists the files a `file_arrival` trigger watches.
+pub trait ArrivalSource: Send + Sync {
+    /// the files directly inside `location`; subdirectories are not descended into.
+    fn list(
+        &self,
+        location: &str,
+    ) -> impl Future<Output = Result<Vec<ArrivedFile>, SendableError>> + Send;
+}
+
+/// local directories on the engine host. `scheme://` locations such as s3 prefixes are rejected.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DirectoryArrivalSource;
+
+impl ArrivalSource for DirectoryArrivalSource {
+    async fn list(&self, location: &str) -> Result<Vec<ArrivedFile>, SendableError> {
+        if location.contains("://") {
+            return Err(crate::errors::FILE_ARRIVAL_UNSUPPORTED_LOCATION.error(location));
+        }
+        let mut entries = tokio::fs::read_dir(location).await?;
+        let mut files = Vec::new();
+        while let Some(entry) = entries.next_entry().await? {
+            let metadata = entry.metadata().await?;
+            if !metadata.is_file() {
+                continue;
+            }
+            files.push(ArrivedFile {
+                path: entry.path().to_string_lossy().into_owned(),
+                size: metadata.len(),
+                modified: metadata.modified().ok().map(DateTime::<Utc>::from),
+            });
+        }
+        Ok(files)
+    }
+}
+
+// what the watcher last saw of one file.
+struct Observation {
+    size: u64,
+    modified: Option<DateTime<Utc>>,
+    unchanged_since: DateTime<Utc>,
+    recorded: bool,
+}
+
+/// polls every enabled `file_arrival` trigger and starts runs for settled files. stability and
+/// debounce are tracked in memory, so a restarted watcher waits out `stable_seconds` again.
+pub struct FileArrivalWatcher<S, C = SystemClock> {
+    source: S,
+    clock: C,
+    observed: HashMap<Uuid, HashMap<String, Observation>>,
+    last_run: HashMap<Uuid, DateTime<Utc>>,
+}
+
+impl<S> FileArrivalWatcher<S> {
+    pub fn new(source: S) -> Self {
+        Self::with_clock(source, SystemClock)
+    }
+}
+
+impl<S, C> FileArrivalWatcher<S, C> {
+    pub fn with_clock(source: S, clock: C) -> Self {
+        Self {
+            source,
+            clock,
+            observed: HashMap::new(),
+            last_run: HashMap::new(),
+        }
+    }
+}
+
+impl<S: ArrivalSource, C: Clock> FileArrivalWatcher<S, C> {
+    /// one pass over the enabled `file_arrival` triggers, returning the runs it started. a trigger
+    /// that fails (bad configuration, unreadable directory) is logged and skipped.
+    pub async fn poll<T: DatabaseImpl>(&mut self, db: &T) -> Result<Vec<FiredRun>, SendableError> {
+        let now = self.clock.now();
+        let triggers = db
+            .fetch_enabled_workflow_triggers_by_kind(WorkflowTriggerKind::FileArrival)
+            .await?;
+        self.observed.retain(|trigger_id, _| {
+            triggers
+                .iter()
+                .any(|trigger| trigger.id == Some(*trigger_id))
+        });
+
+        let mut fired = Vec::new();
+        for trigger in &triggers {
+            let Some(trigger_id) = trigger.id else {
+                continue;
+            };
+            match self.poll_trigger(db, trigger, trigger_id, now).await {
+                Ok(Some(run)) => fired.push(run),
+                Ok(None) => {}
+                Err(err) => warn!(
+                    trigger_id = %trigger_id,
+                    error_code = error_code_or_unknown(err.as_ref()),
+                    "file arrival trigger poll failed: {err}"
+                ),
+            }
+        }
+        Ok(fired)
+    }
+
+    async fn poll_trigger<T: DatabaseImpl>(
+        &mut self,
+        db: &T,
+        trigger: &WorkflowTrigger,
+        trigger_id: Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<Option<FiredRun>, SendableError> {
+        let config = FileArrivalConfig::from_configuration(&trigger.configuration)
+            .map_err(|err| crate::errors::INVALID_FILE_ARRIVAL.error(err))?;
+        let listed = self.source.list(&config.path).await?;
+        let stable_for = chrono::Duration::seconds(config.stable_seconds as i64);
+
+        let previous = self.observed.remove(&trigger_id).unwrap_or_default();
+        let mut observed = HashMap::new();
+        let mut settled = Vec::new();
+        for file in listed {
+            if !file.name().is_some_and(|name| config.matches(name)) {
+                continue;
+            }
+            let observation = match previous.get(&file.path) {
+                Some(seen) if seen.size == file.size && seen.modified == file.modified => {
+                    Observation {
+                        size: file.size,
+                        modified: file.modified,
+                        unchanged_since: seen.unchanged_since,
+                        recorded: seen.recorded,
+                    }
+                }
+                _ => Observation {
+                    size: file.size,
+                    modified: file.modified,
+                    unchanged_since: now,
+                    recorded: false,
+                },
+            };
+            if !observation.recorded && now - observation.unchanged_since >= stable_for {
+                settled.push(file.clone());
+            }
+            observed.insert(file.path, observation);
+        }
+
+        let debounced = self.last_run.get(&trigger_id).is_some_and(|last| {
+            now - *last < chrono::Duration::seconds(config.debounce_seconds as i64)
+        });
+        if settled.is_empty() || debounced {
+            self.observed.insert(trigger_id, observed);
+            return Ok(None);
+        }
+
+        // only files this instance recorded start a run; the rest were taken by another instance.
+        let mut files = Vec::new();
+        for file in settled {
+            let recorded = db
+                .try_record_trigger_firing(trigger_id, file.fire_key())
+                .await?;
+            if let Some(observation) = observed.get_mut(&file.path) {
+                observation.recorded = true;
+            }
+            if recorded {
+                files.push(file.path);
+            }
+        }
+        self.observed.insert(trigger_id, observed);
+        if files.is_empty() {
+            return Ok(None);
+        }
+
+        let count = files.len();
+        let run =
+            repository::create_workflow_run_for_file_arrival(db, trigger, config.parameters, files)
+                .await?;
+        self.last_run.insert(trigger_id, now);
+        info!(
+            trigger_id = %trigger_id,
+            run_id = %run.id,
+            count,
+            "started workflow for arrived file(s)"
+        );
+        Ok(Some(FiredRun {
+            run_id: run.id,
+            org_id: repository::org_id_for_workflow_run(db, run.id).await,
+        }))
+    }
+}
+
+/// poll `watcher` until shutdown, announcing each run it starts.
+pub async fn run_file_arrival_loop<T: DatabaseImpl, S: ArrivalSource, C: Clock>(
+    db: Arc<T>,
+    mut watcher: FileArrivalWatcher<S, C>,
+    events: EventSender,
+    shutdown: Arc<Notify>,
+) {
+    info!("file arrival loop started");
+    loop {
+        match watcher.poll(db.as_ref()).await {
+            Ok(runs) if !runs.is_empty() => {
+                for run in &runs {
+                    emit_workflow_run(&events, run.run_id, run.org_id);
+                }
+                emit(&events, AppEvent::global(AppEventKind::WorkflowRunActivity));
+                events.nudge_wake_publisher();
+            }
+            Ok(_) => {}
+            Err(err) => warn!(
+                error_code = error_code_or_unknown(err.as_ref()),
+                "file arrival iteration failed: {err}"
+            ),
+        }
+        tokio::select! {
+            _ = shutdown.notified() => {
+                info!("file arrival loop shutting down");
+                return;
+            }
+            _ = tokio::time::sleep(FILE_ARRIVAL_INTERVAL) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "file_arrival_tests.rs"]
+mod tests;
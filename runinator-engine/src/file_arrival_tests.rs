@@ -0,0 +1,155 @@
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use runinator_database::interfaces::DatabaseImpl;
+use runinator_database::sqlite::SqliteDb;
+use runinator_models::errors::SendableError;
+use runinator_models::workflows::{
+    FileArrivalConfig, WorkflowDefinition, WorkflowGraph, WorkflowTrigger, WorkflowTriggerKind,
+};
+
+use super::{ArrivalSource, ArrivedFile, FileArrivalWatcher};
+use crate::clock::ManualClock;
+
+fn at(minute: u32, second: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 1, 1, 9, minute, second).unwrap()
+}
+
+// a directory listing the test rewrites between polls.
+#[derive(Clone, Default)]
+struct Listing(Arc<Mutex<Vec<ArrivedFile>>>);
+
+impl Listing {
+    fn set(&self, files: Vec<ArrivedFile>) {
+        *self.0.lock().unwrap() = files;
+    }
+}
+
+impl ArrivalSource for Listing {
+    async fn list(&self, _location: &str) -> Result<Vec<ArrivedFile>, SendableError> {
+        Ok(self.0.lock().unwrap().clone())
+    }
+}
+
+fn file(path: &str, size: u64, modified: DateTime<Utc>) -> ArrivedFile {
+    ArrivedFile {
+        path: path.to_string(),
+        size,
+        modified: Some(modified),
+    }
+}
+
+#[test]
+fn config_defaults_and_pattern_matching() {
+    let config =
+        FileArrivalConfig::from_configuration(&runinator_models::json!({ "path": "/in" })).unwrap();
+    assert_eq!(config.stable_seconds, 30);
+    assert_eq!(config.debounce_seconds, 0);
+    assert!(config.matches("anything.bin"));
+
+    let config = FileArrivalConfig::from_configuration(
+        &runinator_models::json!({ "path": "/in", "pattern": "orders_*.csv" }),
+    )
+    .unwrap();
+    assert!(config.matches("orders_2026.csv"));
+    assert!(!config.matches("orders_2026.csv.part"));
+
+    assert!(
+        FileArrivalConfig::from_configuration(&runinator_models::json!({ "path": " " })).is_err()
+    );
+}
+
+// a file fires once it stops changing for `stable_seconds`, once across instances, and a second
+// instance watching the same directory does not start it again.
+#[tokio::test]
+async fn settled_files_start_one_run() {
+    let path = std::env::temp_dir().join(format!(
+        "runinator-file-arrival-{}.db",
+        Utc::now().timestamp_nanos_opt().unwrap()
+    ));
+    let db = SqliteDb::new(path.to_str().unwrap()).await.unwrap();
+    db.run_init_scripts(&Vec::new()).await.unwrap();
+
+    let workflow = db
+        .upsert_workflow(&WorkflowDefinition {
+            id: None,
+            name: "file-arrival-test".to_string(),
+            namespace: None,
+            org_id: None,
+            version: runinator_models::semver::SemVer::new(1, 0, 0),
+            enabled: true,
+            input_type: runinator_models::types::RuninatorType::Any,
+            definition: WorkflowGraph::from_value(runinator_models::json!({
+                "start": "start",
+                "nodes": [
+                    { "id": "start", "kind": "start", "transitions": { "next": { "$node": "done" } } },
+                    { "id": "done", "kind": "end" }
+                ]
+            }))
+            .unwrap(),
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+    db.upsert_workflow_trigger(&WorkflowTrigger {
+        id: None,
+        workflow_id: workflow.id.unwrap(),
+        kind: WorkflowTriggerKind::FileArrival,
+        enabled: true,
+        configuration: runinator_models::json!({
+            "path": "/in",
+            "pattern": "*.csv",
+            "stable_seconds": 30,
+            "parameters": { "region": "us" }
+        }),
+        next_execution: None,
+        blackout_start: None,
+        blackout_end: None,
+        metadata: runinator_models::json!({}),
+        created_at: None,
+        updated_at: None,
+    })
+    .await
+    .unwrap();
+
+    let listing = Listing::default();
+    let clock = Arc::new(ManualClock::new(at(0, 0)));
+    let mut watcher = FileArrivalWatcher::with_clock(listing.clone(), clock.clone());
+    let mut other = FileArrivalWatcher::with_clock(listing.clone(), clock.clone());
+
+    listing.set(vec![
+        file("/in/a.csv", 10, at(0, 0)),
+        file("/in/a.csv.tmp", 10, at(0, 0)),
+    ]);
+    assert!(watcher.poll(&db).await.unwrap().is_empty());
+    assert!(other.poll(&db).await.unwrap().is_empty());
+
+    // still being written: the stability window restarts.
+    clock.set(at(0, 20));
+    listing.set(vec![file("/in/a.csv", 20, at(0, 20))]);
+    assert!(watcher.poll(&db).await.unwrap().is_empty());
+    assert!(other.poll(&db).await.unwrap().is_empty());
+
+    clock.set(at(0, 40));
+    assert!(watcher.poll(&db).await.unwrap().is_empty());
+
+    clock.advance(Duration::seconds(15));
+    let fired = watcher.poll(&db).await.unwrap();
+    assert_eq!(fired.len(), 1);
+    let run = db
+        .fetch_workflow_run(fired[0].run_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        run.parameters,
+        runinator_models::json!({ "region": "us", "files": ["/in/a.csv"] })
+    );
+
+    assert!(other.poll(&db).await.unwrap().is_empty());
+    clock.advance(Duration::seconds(60));
+    assert!(watcher.poll(&db).await.unwrap().is_empty());
+
+    let _ = std::fs::remove_file(path);
+}
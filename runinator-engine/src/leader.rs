@@ -0,0 +1,105 @@
+//! leader election between scheduler instances.
+//!
+//! every engine instance competes for one database lease, [`SCHEDULER_LEASE`]; the holder is the
+//! leader and is the only instance whose trigger loop fires cron and pipeline triggers, while the
+//! others stand by. file arrivals still run everywhere, since each instance watches its own disk.
+//! the leader renews the lease every [`RENEW_INTERVAL`]; if it dies or loses the database, the
+//! lease lapses after [`LEASE_TTL`] and the next standby to try takes it over. a clean shutdown
+//! releases the lease early so the takeover does not wait out the ttl.
+//!
+//! expiry is judged by the wall clock of the instance taking over, so clocks must agree to well
+//! within [`LEASE_TTL`]. a leader stalled past its lease can briefly overlap a new one; per-slot
+//! firing records still keep each cron slot to one run.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use runinator_database::interfaces::DatabaseImpl;
+use runinator_models::errors::error_code_or_unknown;
+use tokio::sync::Notify;
+use tracing::{error, info, warn};
+
+use crate::{repository, stability};
+
+/// the lease the engine's trigger leader holds.
+pub const SCHEDULER_LEASE: &str = "scheduler";
+const LEASE_TTL: Duration = Duration::from_secs(15);
+const RENEW_INTERVAL: Duration = Duration::from_secs(5);
+
+/// whether this instance currently leads, shared between the election loop and the loops it gates.
+#[derive(Debug, Clone, Default)]
+pub struct Leadership(Arc<AtomicBool>);
+
+impl Leadership {
+    /// a handle that leads from the start and never steps down, for a loop run without an election.
+    pub fn always() -> Self {
+        Self(Arc::new(AtomicBool::new(true)))
+    }
+
+    pub fn is_leader(&self) -> bool {
+        self.0.load(Ordering::Acquire)
+    }
+
+    // record the latest election outcome, returning whether it changed.
+    fn set(&self, leading: bool) -> bool {
+        self.0.swap(leading, Ordering::AcqRel) != leading
+    }
+}
+
+/// contend for [`SCHEDULER_LEASE`] as `instance_id` until shutdown, keeping `leadership` current.
+/// a failed renewal steps down at once rather than running on a lease that may already be lost.
+pub async fn run_leader_election<T: DatabaseImpl>(
+    db: Arc<T>,
+    leadership: Leadership,
+    instance_id: String,
+    shutdown: Arc<Notify>,
+) {
+    info!("scheduler leader election started");
+    let ttl = chrono::Duration::from_std(LEASE_TTL).unwrap_or_default();
+    loop {
+        let leading = match repository::acquire_scheduler_lease(
+            db.as_ref(),
+            SCHEDULER_LEASE,
+            &instance_id,
+            ttl,
+        )
+        .await
+        {
+            Ok(leading) => leading,
+            Err(err) => {
+                error!(
+                    error_code = error_code_or_unknown(err.as_ref()),
+                    "scheduler lease renewal failed: {}", err
+                );
+                false
+            }
+        };
+        if leadership.set(leading) {
+            stability::scheduler_leader(leading);
+            if leading {
+                info!(instance = %instance_id, "took the scheduler lease; firing triggers");
+            } else {
+                warn!(instance = %instance_id, "lost the scheduler lease; standing by");
+            }
+        }
+        tokio::select! {
+            _ = shutdown.notified() => {
+                info!("scheduler leader election shutting down");
+                return;
+            }
+            _ = tokio::time::sleep(RENEW_INTERVAL) => {}
+        }
+    }
+}
+
+/// give up [`SCHEDULER_LEASE`] if `instance_id` holds it, so a standby takes over on its next
+/// attempt instead of waiting out the lease. called once the gated loops have stopped.
+pub async fn release_leadership<T: DatabaseImpl>(db: &T, instance_id: &str) {
+    if let Err(err) = repository::release_scheduler_lease(db, SCHEDULER_LEASE, instance_id).await {
+        warn!(
+            error_code = error_code_or_unknown(err.as_ref()),
+            "failed to release the scheduler lease: {}", err
+        );
+    }
+}
@@ -6,14 +6,23 @@
 //! separate, horizontally-scalable process; both call [`run_background_engine`].
 
 pub mod audit;
+pub mod clock;
+pub mod enqueue_failure;
 pub mod errors;
 pub mod events;
+pub mod file_arrival;
+pub mod leader;
+pub mod missed_runs;
+pub mod queue_wait;
 pub mod repository;
 pub mod repository_runs;
 pub mod repository_state;
+pub mod schedule_gate;
 pub mod settings;
 pub mod simulate;
 pub mod stability;
+pub mod trigger_source;
+pub mod workflow_source;
 
 mod engine;
 mod loops;
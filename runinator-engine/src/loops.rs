@@ -11,7 +11,9 @@ use uuid::Uuid;
 
 use crate::{
     events::{AppEventKind, EventSender, emit, emit_pipeline_run, emit_workflow_run},
+    leader::Leadership,
     repository, stability,
+    trigger_source::TriggerSource,
 };
 
 const INGRESS_CONSUMER_ID: &str = "runinator-ws-ingress";
@@ -26,6 +28,7 @@ const REPLICA_REAP_INTERVAL: Duration = Duration::from_secs(60);
 const USAGE_SAMPLE_INTERVAL: Duration = Duration::from_secs(300);
 const READY_NODE_REAP_INTERVAL: Duration = Duration::from_secs(30);
 const READY_NODE_REAP_LIMIT: i64 = 1000;
+const MISSED_RUN_CHECK_INTERVAL: Duration = Duration::from_secs(60);
 
 /// periodically announce pending ready nodes for drive. due nodes are driven directly on ingress;
 /// future-dated nodes are published on the wake channel for the waker. `wake_nudge` interrupts the
@@ -193,82 +196,137 @@ pub async fn run_usage_sampler<T: DatabaseImpl>(db: Arc<T>, shutdown: Arc<Notify
     }
 }
 
-/// periodically turn due workflow triggers into runs (formerly a waker loop, now in-process).
-pub async fn run_trigger_loop<T: DatabaseImpl>(
-    db: Arc<T>,
+/// periodically turn due workflow triggers into runs (formerly a waker loop, now in-process). the
+/// claims go through `source`, so the loop itself is independent of where triggers are stored. only
+/// the elected leader claims; a standby instance idles until `leadership` passes to it.
+pub async fn run_trigger_loop<S: TriggerSource>(
+    source: S,
     events: EventSender,
     instance_id: String,
+    leadership: Leadership,
     shutdown: Arc<Notify>,
 ) {
     info!("trigger firing loop started");
     loop {
-        match repository::claim_due_workflow_trigger_firings(
-            db.as_ref(),
-            instance_id.clone(),
-            CLAIM_LIMIT,
-        )
+        if leadership.is_leader() {
+            claim_due_triggers(&source, &events, &instance_id).await;
+        }
+        tokio::select! {
+            _ = shutdown.notified() => {
+                info!("trigger firing loop shutting down");
+                return;
+            }
+            _ = tokio::time::sleep(TRIGGER_INTERVAL) => {}
+        }
+    }
+}
+
+// claim and announce one tick's due workflow and pipeline trigger firings and backfilled slots.
+async fn claim_due_triggers<S: TriggerSource>(source: &S, events: &EventSender, instance_id: &str) {
+    match source
+        .claim_due_workflow_firings(instance_id, CLAIM_LIMIT)
         .await
-        {
-            Ok(runs) => {
-                stability::triggers_fired(runs.len() as u64);
-                if !runs.is_empty() {
-                    info!(count = runs.len(), "fired due workflow trigger(s)");
-                }
+    {
+        Ok(runs) => {
+            stability::triggers_fired(runs.len() as u64);
+            if !runs.is_empty() {
+                info!(count = runs.len(), "fired due workflow trigger(s)");
+            }
+            for run in &runs {
+                emit_workflow_run(events, run.run_id, run.org_id);
+            }
+            if !runs.is_empty() {
+                // activity tip: unscoped when fired runs span unknown/unowned orgs; individual
+                // run events above carry org when resolvable.
+                emit(
+                    events,
+                    crate::events::AppEvent::global(AppEventKind::WorkflowRunActivity),
+                );
+                // ready nodes were just enqueued for each fired run — do not wait for the wake
+                // publisher poll interval before announcing them.
+                events.nudge_wake_publisher();
+            }
+        }
+        Err(err) => error!(
+            error_code = error_code_or_unknown(err.as_ref()),
+            "trigger firing iteration failed: {}", err
+        ),
+    }
+
+    // fire due cron pipeline triggers and start each created pipeline run's entry members.
+    match source
+        .claim_due_pipeline_firings(instance_id, CLAIM_LIMIT)
+        .await
+    {
+        Ok(runs) => {
+            if !runs.is_empty() {
+                info!(count = runs.len(), "fired due pipeline trigger(s)");
                 for run in &runs {
-                    let org_id = repository::org_id_for_workflow_run(db.as_ref(), run.id).await;
-                    emit_workflow_run(&events, run.id, org_id);
-                }
-                if !runs.is_empty() {
-                    // activity tip: unscoped when fired runs span unknown/unowned orgs; individual
-                    // run events above carry org when resolvable.
-                    emit(
-                        &events,
-                        crate::events::AppEvent::global(AppEventKind::WorkflowRunActivity),
-                    );
-                    // ready nodes were just enqueued for each fired run — do not wait for the wake
-                    // publisher poll interval before announcing them.
-                    events.nudge_wake_publisher();
+                    emit_pipeline_run(events, run.run_id, run.org_id);
                 }
+                emit(
+                    events,
+                    crate::events::AppEvent::global(AppEventKind::PipelineRunActivity),
+                );
+                events.nudge_wake_publisher();
             }
-            Err(err) => error!(
-                error_code = error_code_or_unknown(err.as_ref()),
-                "trigger firing iteration failed: {}", err
-            ),
         }
+        Err(err) => error!(
+            error_code = error_code_or_unknown(err.as_ref()),
+            "pipeline trigger firing iteration failed: {}", err
+        ),
+    }
 
-        // fire due cron pipeline triggers and start each created pipeline run's entry members.
-        match repository::claim_due_pipeline_trigger_firings(
-            db.as_ref(),
-            instance_id.clone(),
-            CLAIM_LIMIT,
-        )
-        .await
-        {
-            Ok(runs) => {
-                if !runs.is_empty() {
-                    info!(count = runs.len(), "fired due pipeline trigger(s)");
-                    for run in &runs {
-                        let org_id = repository::org_id_for_pipeline_run(db.as_ref(), run.id).await;
-                        emit_pipeline_run(&events, run.id, org_id);
-                    }
-                    emit(
-                        &events,
-                        crate::events::AppEvent::global(AppEventKind::PipelineRunActivity),
-                    );
-                    events.nudge_wake_publisher();
+    // start the next slots of running backfills, each within its concurrency cap.
+    match source.fire_backfills().await {
+        Ok(runs) => {
+            if !runs.is_empty() {
+                info!(count = runs.len(), "started backfilled workflow run(s)");
+                for run in &runs {
+                    emit_workflow_run(events, run.run_id, run.org_id);
                 }
+                emit(
+                    events,
+                    crate::events::AppEvent::global(AppEventKind::WorkflowRunActivity),
+                );
+                events.nudge_wake_publisher();
+            }
+        }
+        Err(err) => error!(
+            error_code = error_code_or_unknown(err.as_ref()),
+            "backfill iteration failed: {}", err
+        ),
+    }
+}
+
+/// periodically alert on workflows that went past their `expected_max_interval_seconds` without a
+/// successful run. like the trigger loop, only the elected leader checks.
+pub async fn run_missed_run_checker<T: DatabaseImpl>(
+    db: Arc<T>,
+    events: EventSender,
+    leadership: Leadership,
+    shutdown: Arc<Notify>,
+) {
+    info!("missed-run checker started");
+    loop {
+        if leadership.is_leader() {
+            match crate::missed_runs::check_missed_runs(db.as_ref(), &events, chrono::Utc::now())
+                .await
+            {
+                Ok(count) if count > 0 => info!(count, "raised missed-run alert(s)"),
+                Ok(_) => {}
+                Err(err) => error!(
+                    error_code = error_code_or_unknown(err.as_ref()),
+                    "missed-run check iteration failed: {}", err
+                ),
             }
-            Err(err) => error!(
-                error_code = error_code_or_unknown(err.as_ref()),
-                "pipeline trigger firing iteration failed: {}", err
-            ),
         }
         tokio::select! {
             _ = shutdown.notified() => {
-                info!("trigger firing loop shutting down");
+                info!("missed-run checker shutting down");
                 return;
             }
-            _ = tokio::time::sleep(TRIGGER_INTERVAL) => {}
+            _ = tokio::time::sleep(MISSED_RUN_CHECK_INTERVAL) => {}
         }
     }
 }
@@ -284,6 +342,7 @@ pub async fn run_action_dispatch_publisher<T: DatabaseImpl>(
     shutdown: Arc<Notify>,
 ) {
     info!("action dispatch publisher started");
+    let dead_letter_after = crate::enqueue_failure::dead_letter_threshold();
     loop {
         if let Err(err) = repository::publish_pending_action_dispatches(
             db.as_ref(),
@@ -291,6 +350,7 @@ pub async fn run_action_dispatch_publisher<T: DatabaseImpl>(
             &instance_id,
             ACTION_DISPATCH_LEASE_SECONDS,
             CLAIM_LIMIT,
+            dead_letter_after,
         )
         .await
         {
@@ -1,6 +1,16 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::Duration;
 
-use super::bucket_to_interval;
+use runinator_broker::in_memory::InMemoryBroker;
+use runinator_models::errors::SendableError;
+use tokio::sync::Notify;
+use uuid::Uuid;
+
+use super::{bucket_to_interval, run_trigger_loop};
+use crate::events::EnginePublisher;
+use crate::leader::Leadership;
+use crate::trigger_source::{FiredRun, TriggerSource};
 
 // two timestamps in the same 300s window must floor to the identical key, so N-up samplers that read
 // slightly different wall clocks still converge to one (org, backend, kind, sampled_at) row.
@@ -37,3 +47,95 @@ fn zero_interval_passes_through() {
     let now = chrono::DateTime::from_timestamp(1_700_000_123, 0).unwrap();
     assert_eq!(bucket_to_interval(now, Duration::from_secs(0)), now);
 }
+
+// a stub source that records how often each claim ran: one fired workflow run per claim, a
+// failing pipeline claim, and no backfills.
+#[derive(Default)]
+struct CountingSource {
+    workflow_claims: AtomicUsize,
+    pipeline_claims: AtomicUsize,
+    backfill_passes: AtomicUsize,
+}
+
+impl TriggerSource for CountingSource {
+    async fn claim_due_workflow_firings(
+        &self,
+        scheduler_id: &str,
+        _limit: i64,
+    ) -> Result<Vec<FiredRun>, SendableError> {
+        assert_eq!(scheduler_id, "scheduler-a");
+        self.workflow_claims.fetch_add(1, Ordering::SeqCst);
+        Ok(vec![FiredRun {
+            run_id: Uuid::new_v4(),
+            org_id: None,
+        }])
+    }
+
+    async fn claim_due_pipeline_firings(
+        &self,
+        _scheduler_id: &str,
+        _limit: i64,
+    ) -> Result<Vec<FiredRun>, SendableError> {
+        self.pipeline_claims.fetch_add(1, Ordering::SeqCst);
+        Err("pipeline backend unavailable".into())
+    }
+
+    async fn fire_backfills(&self) -> Result<Vec<FiredRun>, SendableError> {
+        self.backfill_passes.fetch_add(1, Ordering::SeqCst);
+        Ok(Vec::new())
+    }
+}
+
+// the trigger loop runs against any source: every claim is attempted each tick, a failing pipeline
+// claim does not stop the loop, and a pending shutdown ends it after the tick.
+#[tokio::test]
+async fn trigger_loop_claims_through_the_configured_source() {
+    let source = Arc::new(CountingSource::default());
+    let events = EnginePublisher::new(Arc::new(InMemoryBroker::new()));
+    let shutdown = Arc::new(Notify::new());
+    // notify_one stores a permit, so the loop's first sleep observes the shutdown.
+    shutdown.notify_one();
+
+    tokio::time::timeout(
+        Duration::from_secs(5),
+        run_trigger_loop(
+            source.clone(),
+            events,
+            "scheduler-a".to_string(),
+            Leadership::always(),
+            shutdown,
+        ),
+    )
+    .await
+    .expect("trigger loop stops on shutdown");
+
+    assert_eq!(source.workflow_claims.load(Ordering::SeqCst), 1);
+    assert_eq!(source.pipeline_claims.load(Ordering::SeqCst), 1);
+    assert_eq!(source.backfill_passes.load(Ordering::SeqCst), 1);
+}
+
+// a standby instance keeps ticking but never claims while another instance holds the lease.
+#[tokio::test]
+async fn trigger_loop_does_not_claim_while_standing_by() {
+    let source = Arc::new(CountingSource::default());
+    let events = EnginePublisher::new(Arc::new(InMemoryBroker::new()));
+    let shutdown = Arc::new(Notify::new());
+    shutdown.notify_one();
+
+    tokio::time::timeout(
+        Duration::from_secs(5),
+        run_trigger_loop(
+            source.clone(),
+            events,
+            "scheduler-a".to_string(),
+            Leadership::default(),
+            shutdown,
+        ),
+    )
+    .await
+    .expect("trigger loop stops on shutdown");
+
+    assert_eq!(source.workflow_claims.load(Ordering::SeqCst), 0);
+    assert_eq!(source.pipeline_claims.load(Ordering::SeqCst), 0);
+    assert_eq!(source.backfill_passes.load(Ordering::SeqCst), 0);
+}
@@ -0,0 +1,134 @@
+//! missed-run alerting. a workflow may set `expected_max_interval_seconds` in its definition
+//! metadata; the missed-run checker raises a warning notification once the workflow has gone longer
+//! than that without a successful run, so a schedule that quietly stopped producing runs (a disabled
+//! trigger, a cron that no longer parses, a gate that always skips) is noticed without anyone
+//! looking for it.
+//!
+//! each missed deadline alerts once: the deadline is recorded before the notification is raised, so
+//! instances racing on it agree, and the next success moves the deadline on. like the other alert
+//! sinks, the notification itself is best-effort.
+
+use chrono::{DateTime, Duration, Utc};
+use runinator_database::interfaces::DatabaseImpl;
+use runinator_models::{
+    errors::{SendableError, error_code_or_unknown},
+    json,
+    notifications::NewNotification,
+    workflows::{WorkflowDefinition, workflow_expected_max_interval_seconds},
+};
+use tracing::{error, warn};
+
+use crate::{
+    events::{AppEvent, AppEventKind, EventSender, emit},
+    stability,
+};
+
+/// the notification channel missed-run alerts are raised on.
+pub const MISSED_RUN_CHANNEL: &str = "missed_run";
+
+/// when `workflow` is expected to have succeeded by: its expected interval after `last_success`, or
+/// after the workflow was last saved when it has never succeeded. `None` when it sets no interval
+/// or the interval no longer reads.
+pub fn missed_run_deadline(
+    workflow: &WorkflowDefinition,
+    last_success: Option<DateTime<Utc>>,
+) -> Option<DateTime<Utc>> {
+    let interval = workflow_expected_max_interval_seconds(&workflow.definition.metadata).ok()??;
+    let since = last_success
+        .or(workflow.updated_at)
+        .or(workflow.created_at)?;
+    Some(since + Duration::seconds(interval))
+}
+
+/// raise an alert for every enabled workflow whose deadline for a successful run has passed by
+/// `now` and has not alerted yet. returns how many alerts were raised.
+pub async fn check_missed_runs<T: DatabaseImpl>(
+    db: &T,
+    events: &EventSender,
+    now: DateTime<Utc>,
+) -> Result<usize, SendableError> {
+    let mut raised = 0;
+    for workflow in db.fetch_workflows().await? {
+        let (Some(workflow_id), true, Ok(Some(_))) = (
+            workflow.id,
+            workflow.enabled,
+            workflow_expected_max_interval_seconds(&workflow.definition.metadata),
+        ) else {
+            continue;
+        };
+        let last_success = db.fetch_last_workflow_success(workflow_id).await?;
+        let Some(expected_by) = missed_run_deadline(&workflow, last_success) else {
+            continue;
+        };
+        if now < expected_by
+            || !db
+                .try_record_missed_run_alert(workflow_id, expected_by)
+                .await?
+        {
+            continue;
+        }
+        raised += 1;
+        alert(db, events, &workflow, last_success, expected_by, now).await;
+    }
+    Ok(raised)
+}
+
+async fn alert<T: DatabaseImpl>(
+    db: &T,
+    events: &EventSender,
+    workflow: &WorkflowDefinition,
+    last_success: Option<DateTime<Utc>>,
+    expected_by: DateTime<Utc>,
+    now: DateTime<Utc>,
+) {
+    let overdue_seconds = (now - expected_by).num_seconds();
+    stability::missed_run_alert();
+    warn!(
+        workflow_id = ?workflow.id,
+        workflow = %workflow.name,
+        expected_by = %expected_by.to_rfc3339(),
+        overdue_seconds,
+        "workflow has not succeeded within its expected interval"
+    );
+    let body = match last_success {
+        Some(last_success) => format!(
+            "the last successful run finished at {}; one was expected by {}.",
+            last_success.to_rfc3339(),
+            expected_by.to_rfc3339()
+        ),
+        None => format!(
+            "the workflow has never succeeded; a successful run was expected by {}.",
+            expected_by.to_rfc3339()
+        ),
+    };
+    let notification = NewNotification {
+        workflow_run_id: None,
+        workflow_node_id: None,
+        channel: MISSED_RUN_CHANNEL.into(),
+        severity: "warning".into(),
+        title: format!("Workflow '{}' missed its expected run", workflow.name),
+        body: Some(body),
+        target: None,
+        metadata: json!({
+            "workflow_id": workflow.id.map(|id| id.to_string()),
+            "expected_by": expected_by.to_rfc3339(),
+            "last_success_at": last_success.map(|at| at.to_rfc3339()),
+            "overdue_seconds": overdue_seconds,
+        }),
+    };
+    match db.create_notification(&notification).await {
+        Ok(notification) => emit(
+            events,
+            AppEvent::new(
+                workflow.org_id,
+                AppEventKind::NotificationCreated {
+                    notification_id: notification.id,
+                },
+            ),
+        ),
+        Err(err) => error!(
+            error_code = error_code_or_unknown(err.as_ref()),
+            "failed to persist missed-run alert: {}", err
+        ),
+    }
+}
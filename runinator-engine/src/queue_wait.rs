@@ -0,0 +1,116 @@
+//! queue wait-time alerting: when a worker reports a node started after waiting in the broker for
+//! longer than the configured threshold, record it and raise a warning notification.
+//!
+//! like the audit sinks, alerting is best-effort: a failure to persist the notification is logged
+//! and never fails the result event that carried the wait.
+
+use std::time::Duration;
+
+use runinator_comm::{WorkflowResultEvent, WorkflowResultEventKind};
+use runinator_database::interfaces::DatabaseImpl;
+use runinator_models::{
+    errors::error_code_or_unknown, json, notifications::NewNotification, workflows::WorkflowStatus,
+};
+use tracing::{error, warn};
+
+use crate::{
+    events::{AppEvent, AppEventKind, EventSender, emit},
+    repository, stability,
+};
+
+/// environment variable overriding the alert threshold in milliseconds; `0` disables alerting.
+pub const QUEUE_WAIT_ALERT_ENV: &str = "RUNINATOR_QUEUE_WAIT_ALERT_MS";
+const DEFAULT_QUEUE_WAIT_ALERT: Duration = Duration::from_secs(300);
+const QUEUE_WAIT_CHANNEL: &str = "queue_wait";
+
+/// the configured alert threshold, read from [`QUEUE_WAIT_ALERT_ENV`] and defaulting to five
+/// minutes. `None` when alerting is disabled.
+pub fn alert_threshold() -> Option<Duration> {
+    let threshold = std::env::var(QUEUE_WAIT_ALERT_ENV)
+        .ok()
+        .and_then(|raw| raw.trim().parse::<u64>().ok())
+        .map(Duration::from_millis)
+        .unwrap_or(DEFAULT_QUEUE_WAIT_ALERT);
+    (!threshold.is_zero()).then_some(threshold)
+}
+
+/// the wait a `running` status event carries, if any.
+pub fn reported_wait(event: &WorkflowResultEvent) -> Option<i64> {
+    match &event.kind {
+        WorkflowResultEventKind::Status {
+            status: WorkflowStatus::Running,
+            queue_wait_ms,
+            ..
+        } => *queue_wait_ms,
+        _ => None,
+    }
+}
+
+/// record the wait carried by an applied result event and alert when it exceeds `threshold`.
+pub async fn observe<T: DatabaseImpl>(
+    db: &T,
+    events: &EventSender,
+    event: &WorkflowResultEvent,
+    threshold: Option<Duration>,
+) {
+    let Some(wait_ms) = reported_wait(event) else {
+        return;
+    };
+    stability::record_queue_wait_ms(wait_ms.max(0) as f64);
+    let Some(threshold) = threshold else {
+        return;
+    };
+    let threshold_ms = threshold.as_millis() as i64;
+    if wait_ms <= threshold_ms {
+        return;
+    }
+
+    stability::queue_wait_alert();
+    warn!(
+        run_id = %event.workflow_run_id,
+        node_id = %event.node_id,
+        queue_wait_ms = wait_ms,
+        threshold_ms,
+        "node waited in the broker longer than the alert threshold"
+    );
+    let notification = NewNotification {
+        workflow_run_id: Some(event.workflow_run_id),
+        workflow_node_id: Some(event.node_id.clone()),
+        channel: QUEUE_WAIT_CHANNEL.into(),
+        severity: "warning".into(),
+        title: format!(
+            "Node '{}' waited {}s in the queue",
+            event.node_id,
+            wait_ms / 1000
+        ),
+        body: Some(format!(
+            "the command waited {wait_ms}ms before a worker started it, over the {threshold_ms}ms alert threshold."
+        )),
+        target: None,
+        metadata: json!({
+            "workflow_node_run_id": event.workflow_node_run_id.to_string(),
+            "queue_wait_ms": wait_ms,
+            "threshold_ms": threshold_ms,
+        }),
+    };
+    match db.create_notification(&notification).await {
+        Ok(notification) => {
+            let org_id = repository::org_id_for_workflow_run(db, event.workflow_run_id).await;
+            emit(
+                events,
+                AppEvent::new(
+                    org_id,
+                    AppEventKind::NotificationCreated {
+                        notification_id: notification.id,
+                    },
+                ),
+            );
+        }
+        Err(err) => {
+            error!(
+                error_code = error_code_or_unknown(err.as_ref()),
+                "failed to persist queue wait alert: {}", err
+            );
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use super::support;
+use super::triggers::trigger_state;
+use super::*;
+use runinator_database::schedule::trigger_slots_between;
+use runinator_models::backfills::{
+    BACKFILL_SLOT_PARAMETER, BackfillRequest, BackfillStatus, MAX_BACKFILL_SLOTS, WorkflowBackfill,
+};
+use runinator_models::replicas::{TriggerActorType, TriggerSourceKind, WorkflowRunProvenance};
+use runinator_models::workflows::WorkflowTriggerKind;
+use uuid::Uuid;
+
+/// record a backfill replaying the slots cron `trigger` had from `request.from` through
+/// `request.to`. nothing starts here; the trigger loop starts the slots. a window without slots or
+/// with more than [`MAX_BACKFILL_SLOTS`] is rejected, as is a workflow whose last backfill is still
+/// running.
+pub async fn create_workflow_backfill<T: DatabaseImpl>(
+    db: &T,
+    trigger: &WorkflowTrigger,
+    request: &BackfillRequest,
+    requested_by: Option<Uuid>,
+    now: DateTime<Utc>,
+) -> Result<WorkflowBackfill, SendableError> {
+    let Some(trigger_id) = trigger.id else {
+        return Err(crate::errors::INVALID_BACKFILL.error("the trigger has no id"));
+    };
+    if trigger.kind != WorkflowTriggerKind::Cron {
+        return Err(crate::errors::INVALID_BACKFILL.error("only cron triggers can be backfilled"));
+    }
+    let concurrency = request
+        .validate(now)
+        .map_err(|err| crate::errors::INVALID_BACKFILL.error(err))?;
+    let slots = trigger_slots_between(trigger, request.from, request.to, MAX_BACKFILL_SLOTS + 1)
+        .map_err(|err| crate::errors::INVALID_BACKFILL.error(err))?;
+    if slots.is_empty() {
+        return Err(crate::errors::INVALID_BACKFILL.error("the trigger has no slots in the window"));
+    }
+    if slots.len() > MAX_BACKFILL_SLOTS {
+        return Err(crate::errors::INVALID_BACKFILL.error(format!(
+            "the window holds more than {MAX_BACKFILL_SLOTS} slots; split it"
+        )));
+    }
+
+    let now = DateTime::<Utc>::from_timestamp(now.timestamp(), 0).unwrap_or(now);
+    let backfill = WorkflowBackfill {
+        id: Uuid::now_v7(),
+        workflow_id: trigger.workflow_id,
+        trigger_id,
+        range_start: request.from,
+        range_end: request.to,
+        concurrency,
+        slots,
+        fired: 0,
+        status: BackfillStatus::Running,
+        requested_by,
+        created_at: now,
+        updated_at: now,
+    };
+    if !db.create_workflow_backfill(&backfill).await? {
+        return Err(crate::errors::BACKFILL_CONFLICT.error(trigger.workflow_id));
+    }
+    Ok(backfill)
+}
+
+pub async fn fetch_workflow_backfills<T: DatabaseImpl>(
+    db: &T,
+    trigger_id: Uuid,
+) -> Result<Vec<WorkflowBackfill>, SendableError> {
+    db.fetch_workflow_backfills(trigger_id).await
+}
+
+pub async fn fetch_workflow_backfill<T: DatabaseImpl>(
+    db: &T,
+    backfill_id: Uuid,
+) -> Result<Option<WorkflowBackfill>, SendableError> {
+    db.fetch_workflow_backfill(backfill_id).await
+}
+
+/// stop a running backfill; runs it already started carry on.
+pub async fn cancel_workflow_backfill<T: DatabaseImpl>(
+    db: &T,
+    backfill_id: Uuid,
+) -> Result<bool, SendableError> {
+    db.cancel_workflow_backfill(backfill_id).await
+}
+
+/// start the next slots of every running backfill, oldest first, while fewer than its concurrency
+/// of the workflow's backfill runs are open. each slot is recorded as a firing of the trigger keyed
+/// on the backfill and slot, so it starts once however many instances tick. backfilled runs skip
+/// the workflow's `only_if`, `depends_on`, freshness, and overlap gates: replaying the slot was
+/// asked for explicitly. a backfill whose trigger is gone is canceled. a cluster-wide scheduling
+/// pause holds every backfill where it is.
+pub async fn fire_workflow_backfills<T: DatabaseImpl>(
+    db: &T,
+) -> Result<Vec<WorkflowRun>, SendableError> {
+    if super::scheduling_paused(db).await? {
+        return Ok(Vec::new());
+    }
+    let mut runs = Vec::new();
+    for backfill in db.fetch_running_workflow_backfills().await? {
+        let Some(trigger) = db.fetch_workflow_trigger(backfill.trigger_id).await? else {
+            db.cancel_workflow_backfill(backfill.id).await?;
+            continue;
+        };
+        let open = db.count_open_backfill_runs(backfill.workflow_id).await?;
+        let free = usize::try_from(backfill.concurrency - open).unwrap_or_default();
+        let mut fired = backfill.fired;
+        for slot in backfill.pending_slots().iter().take(free) {
+            let fire_key = format!("backfill:{}:{}", backfill.id, slot.timestamp());
+            if db
+                .try_record_trigger_firing(backfill.trigger_id, fire_key)
+                .await?
+            {
+                runs.push(create_workflow_run_for_backfill(db, &trigger, &backfill, *slot).await?);
+            }
+            fired += 1;
+        }
+        if fired == backfill.fired {
+            continue;
+        }
+        let status = if fired >= backfill.slots.len() as i64 {
+            BackfillStatus::Completed
+        } else {
+            BackfillStatus::Running
+        };
+        db.update_workflow_backfill_progress(backfill.id, fired, status)
+            .await?;
+    }
+    Ok(runs)
+}
+
+// a backfilled run gets the trigger's parameters plus its slot, so a run replaying a day's dump
+// knows which day it is for.
+async fn create_workflow_run_for_backfill<T: DatabaseImpl>(
+    db: &T,
+    trigger: &WorkflowTrigger,
+    backfill: &WorkflowBackfill,
+    slot: DateTime<Utc>,
+) -> Result<WorkflowRun, SendableError> {
+    let workflow_snapshot = support::fetch_workflow_snapshot(db, trigger.workflow_id).await?;
+    let mut parameters = match trigger.configuration.get("parameters") {
+        Some(Value::Object(object)) => object.clone(),
+        _ => Default::default(),
+    };
+    parameters.insert(
+        BACKFILL_SLOT_PARAMETER.into(),
+        Value::from(slot.to_rfc3339()),
+    );
+    let run = db
+        .create_workflow_run(
+            trigger.workflow_id,
+            workflow_snapshot,
+            Value::Object(parameters),
+            trigger_state(trigger),
+            None,
+            WorkflowRunProvenance {
+                source_kind: Some(TriggerSourceKind::Backfill),
+                actor_type: Some(TriggerActorType::System),
+                actor_replica_id: None,
+                actor_display_name: Some("backfill".into()),
+                request_host: None,
+                request_ip: None,
+                metadata: runinator_models::json!({
+                    "trigger_id": trigger.id,
+                    "backfill_id": backfill.id,
+                    "slot": slot.to_rfc3339(),
+                }),
+            },
+        )
+        .await?;
+    support::enqueue_start_ready_node(db, &run).await?;
+    Ok(run)
+}
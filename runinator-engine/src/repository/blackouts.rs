@@ -0,0 +1,42 @@
+use super::*;
+use runinator_models::blackouts::{BlackoutCalendar, BlackoutCalendarRequest};
+
+pub async fn fetch_blackout_calendars<T: DatabaseImpl>(
+    db: &T,
+) -> Result<Vec<BlackoutCalendar>, SendableError> {
+    db.fetch_blackout_calendars().await
+}
+
+/// store `request` as the calendar `name` and return it as saved: dates sorted and deduplicated,
+/// blank description and zone dropped.
+pub async fn set_blackout_calendar<T: DatabaseImpl>(
+    db: &T,
+    name: &str,
+    request: BlackoutCalendarRequest,
+) -> Result<BlackoutCalendar, SendableError> {
+    let mut dates = request.dates;
+    dates.sort_unstable();
+    dates.dedup();
+    let calendar = BlackoutCalendar {
+        name: name.trim().to_string(),
+        description: request
+            .description
+            .map(|description| description.trim().to_string())
+            .filter(|description| !description.is_empty()),
+        timezone: request
+            .timezone
+            .map(|timezone| timezone.trim().to_string())
+            .filter(|timezone| !timezone.is_empty()),
+        dates,
+        updated_at: DateTime::<Utc>::from_timestamp(Utc::now().timestamp(), 0),
+    };
+    db.upsert_blackout_calendar(&calendar).await?;
+    Ok(calendar)
+}
+
+pub async fn delete_blackout_calendar<T: DatabaseImpl>(
+    db: &T,
+    name: &str,
+) -> Result<bool, SendableError> {
+    db.delete_blackout_calendar(name).await
+}
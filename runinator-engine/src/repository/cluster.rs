@@ -0,0 +1,66 @@
+use super::*;
+use runinator_models::cluster::{ClusterPause, ClusterPauseRequest};
+use uuid::Uuid;
+
+pub async fn fetch_cluster_pause<T: DatabaseImpl>(db: &T) -> Result<ClusterPause, SendableError> {
+    db.fetch_cluster_pause().await
+}
+
+/// true while an operator holds scheduling: trigger claims and backfills then start nothing.
+pub async fn scheduling_paused<T: DatabaseImpl>(db: &T) -> Result<bool, SendableError> {
+    let pause = db.fetch_cluster_pause().await?;
+    crate::stability::cluster_pause(&pause);
+    Ok(pause.scheduling)
+}
+
+/// apply an operator's pause (or resume) and return the stored state. lifting a scheduling pause
+/// recomputes every enabled cron trigger's `next_execution` from now, so the slots that passed
+/// while it held are dropped instead of firing together as misfires.
+pub async fn set_cluster_pause<T: DatabaseImpl>(
+    db: &T,
+    request: ClusterPauseRequest,
+    updated_by: Option<Uuid>,
+) -> Result<ClusterPause, SendableError> {
+    let previous = db.fetch_cluster_pause().await?;
+    let pause = ClusterPause {
+        dispatch: request.dispatch,
+        execution: request.execution,
+        scheduling: request.scheduling,
+        reason: request
+            .reason
+            .map(|reason| reason.trim().to_string())
+            .filter(|reason| !reason.is_empty()),
+        updated_by,
+        updated_at: DateTime::<Utc>::from_timestamp(Utc::now().timestamp(), 0),
+    };
+    db.set_cluster_pause(&pause).await?;
+    if previous.scheduling && !pause.scheduling {
+        let report = db.reconcile_trigger_schedules(Utc::now(), false).await?;
+        log::info!(
+            "Scheduling resumed; recomputed next_execution for {} of {} cron trigger(s)",
+            report.changes.len(),
+            report.checked
+        );
+    }
+    Ok(pause)
+}
+
+/// take or renew the lease `name` for `holder` for `ttl` from now; true while `holder` leads.
+pub async fn acquire_scheduler_lease<T: DatabaseImpl>(
+    db: &T,
+    name: &str,
+    holder: &str,
+    ttl: Duration,
+) -> Result<bool, SendableError> {
+    let now = Utc::now();
+    db.acquire_scheduler_lease(name, holder, now, now + ttl)
+        .await
+}
+
+pub async fn release_scheduler_lease<T: DatabaseImpl>(
+    db: &T,
+    name: &str,
+    holder: &str,
+) -> Result<(), SendableError> {
+    db.release_scheduler_lease(name, holder).await
+}
@@ -1,6 +1,15 @@
 use super::*;
 use super::{catalog, triggers};
+use runinator_models::protection::{
+    NewPendingWorkflowChange, PendingChangeStatus, PendingWorkflowChange, WorkflowProtection,
+};
+use runinator_models::retention::WorkflowRetention;
+use runinator_models::revisions::WorkflowRevision;
 use runinator_models::semver::SemVerBump;
+use runinator_models::workflows::{
+    WorkflowDependencies, WorkflowImportOptions, WorkflowRunBaseline, workflow_allows_concurrent,
+    workflow_expected_max_interval_seconds, workflow_freshness_seconds,
+};
 use uuid::Uuid;
 
 /// shallow-merge `parameters` over `defaults` when both are json objects; used by task-parameter
@@ -24,8 +33,46 @@ pub async fn upsert_workflow<T: DatabaseImpl>(
     db: &T,
     workflow: &WorkflowDefinition,
 ) -> Result<WorkflowDefinition, SendableError> {
+    upsert_workflow_as(db, workflow, None).await
+}
+
+/// validate and save `workflow`, then append the saved record to its revision history attributed
+/// to `changed_by`.
+pub async fn upsert_workflow_as<T: DatabaseImpl>(
+    db: &T,
+    workflow: &WorkflowDefinition,
+    changed_by: Option<Uuid>,
+) -> Result<WorkflowDefinition, SendableError> {
+    let workflow = validate_workflow_definition_with_catalog(db, workflow).await?;
+    let saved = db.upsert_workflow(&workflow).await?;
+    db.record_workflow_revision(&saved, changed_by).await?;
+    Ok(saved)
+}
+
+/// like [`upsert_workflow_as`], but only when the stored workflow is still at `edit_version`.
+/// `None` means the caller edited a stale copy and nothing was written.
+pub async fn upsert_workflow_if_current<T: DatabaseImpl>(
+    db: &T,
+    workflow: &WorkflowDefinition,
+    changed_by: Option<Uuid>,
+    edit_version: i64,
+) -> Result<Option<WorkflowDefinition>, SendableError> {
     let workflow = validate_workflow_definition_with_catalog(db, workflow).await?;
-    db.upsert_workflow(&workflow).await
+    let Some(saved) = db
+        .upsert_workflow_if_current(&workflow, edit_version)
+        .await?
+    else {
+        return Ok(None);
+    };
+    db.record_workflow_revision(&saved, changed_by).await?;
+    Ok(Some(saved))
+}
+
+pub async fn fetch_workflow_edit_version<T: DatabaseImpl>(
+    db: &T,
+    workflow_id: Uuid,
+) -> Result<Option<i64>, SendableError> {
+    db.fetch_workflow_edit_version(workflow_id).await
 }
 
 pub async fn validate_workflow_definition_with_catalog<T: DatabaseImpl>(
@@ -33,6 +80,16 @@ pub async fn validate_workflow_definition_with_catalog<T: DatabaseImpl>(
     workflow: &WorkflowDefinition,
 ) -> Result<WorkflowDefinition, SendableError> {
     let workflow = validate_workflow_definition(workflow)?;
+    crate::schedule_gate::validate_only_if(&workflow)
+        .map_err(|err| crate::errors::INVALID_ONLY_IF.error(err))?;
+    crate::schedule_gate::validate_depends_on(&workflow)
+        .map_err(|err| crate::errors::INVALID_DEPENDS_ON.error(err))?;
+    workflow_allows_concurrent(&workflow.definition.metadata)
+        .map_err(|err| crate::errors::INVALID_ALLOW_CONCURRENT.error(err))?;
+    workflow_freshness_seconds(&workflow.definition.metadata)
+        .map_err(|err| crate::errors::INVALID_FRESHNESS.error(err))?;
+    workflow_expected_max_interval_seconds(&workflow.definition.metadata)
+        .map_err(|err| crate::errors::INVALID_EXPECTED_MAX_INTERVAL.error(err))?;
     let providers = catalog::fetch_catalog_items(db, Some("provider_metadata".into())).await?;
     let providers = provider_metadata_from_items(providers)?;
     // type-check `config.*` references against the stored settings schema.
@@ -40,9 +97,28 @@ pub async fn validate_workflow_definition_with_catalog<T: DatabaseImpl>(
     runinator_workflows::validate_workflow_with_config(&workflow, &providers, &config_type)
         .map_err(|err| -> SendableError { Box::new(err) })?;
     validate_workflow_subflows(db, &workflow).await?;
+    validate_workflow_dependencies(db, &workflow).await?;
     Ok(workflow)
 }
 
+async fn validate_workflow_dependencies<T: DatabaseImpl>(
+    db: &T,
+    workflow: &WorkflowDefinition,
+) -> Result<(), SendableError> {
+    let Ok(Some(dependencies)) = WorkflowDependencies::from_metadata(&workflow.definition.metadata)
+    else {
+        return Ok(());
+    };
+    for upstream in dependencies.upstream {
+        if db.fetch_workflow(upstream).await?.is_none() {
+            return Err(crate::errors::INVALID_DEPENDS_ON.error(format!(
+                "depends_on references non-existent workflow with id {upstream}"
+            )));
+        }
+    }
+    Ok(())
+}
+
 async fn validate_workflow_subflows<T: DatabaseImpl>(
     db: &T,
     workflow: &WorkflowDefinition,
@@ -86,6 +162,18 @@ pub async fn fetch_workflows<T: DatabaseImpl>(
     Ok(normalized)
 }
 
+pub async fn search_workflows<T: DatabaseImpl>(
+    db: &T,
+    term: String,
+) -> Result<Vec<WorkflowDefinition>, SendableError> {
+    let workflows = db.search_workflows(term).await?;
+    let mut normalized = Vec::with_capacity(workflows.len());
+    for workflow in workflows {
+        normalized.push(normalize_persisted_workflow(db, workflow).await?);
+    }
+    Ok(normalized)
+}
+
 pub async fn fetch_workflow<T: DatabaseImpl>(
     db: &T,
     workflow_id: Uuid,
@@ -104,6 +192,125 @@ pub async fn set_workflow_org<T: DatabaseImpl>(
     db.set_workflow_org(workflow_id, org_id).await
 }
 
+pub async fn set_workflow_enabled<T: DatabaseImpl>(
+    db: &T,
+    workflow_id: Uuid,
+    enabled: bool,
+) -> Result<bool, SendableError> {
+    db.set_workflow_enabled(workflow_id, enabled).await
+}
+
+pub async fn fetch_workflow_revisions<T: DatabaseImpl>(
+    db: &T,
+    workflow_id: Uuid,
+) -> Result<Vec<WorkflowRevision>, SendableError> {
+    db.fetch_workflow_revisions(workflow_id).await
+}
+
+pub async fn fetch_workflow_revision<T: DatabaseImpl>(
+    db: &T,
+    workflow_id: Uuid,
+    revision: i64,
+) -> Result<Option<WorkflowRevision>, SendableError> {
+    db.fetch_workflow_revision(workflow_id, revision).await
+}
+
+pub async fn fetch_workflow_retention<T: DatabaseImpl>(
+    db: &T,
+    workflow_id: Uuid,
+) -> Result<Option<WorkflowRetention>, SendableError> {
+    db.fetch_workflow_retention(workflow_id).await
+}
+
+pub async fn set_workflow_retention<T: DatabaseImpl>(
+    db: &T,
+    retention: WorkflowRetention,
+) -> Result<WorkflowRetention, SendableError> {
+    db.upsert_workflow_retention(retention).await
+}
+
+pub async fn clear_workflow_retention<T: DatabaseImpl>(
+    db: &T,
+    workflow_id: Uuid,
+) -> Result<bool, SendableError> {
+    db.delete_workflow_retention(workflow_id).await
+}
+
+pub async fn fetch_workflow_protection<T: DatabaseImpl>(
+    db: &T,
+    workflow_id: Uuid,
+) -> Result<Option<WorkflowProtection>, SendableError> {
+    db.fetch_workflow_protection(workflow_id).await
+}
+
+pub async fn protect_workflow<T: DatabaseImpl>(
+    db: &T,
+    workflow_id: Uuid,
+    protected_by: Option<Uuid>,
+) -> Result<WorkflowProtection, SendableError> {
+    db.protect_workflow(workflow_id, protected_by).await
+}
+
+pub async fn unprotect_workflow<T: DatabaseImpl>(
+    db: &T,
+    workflow_id: Uuid,
+) -> Result<bool, SendableError> {
+    db.unprotect_workflow(workflow_id).await
+}
+
+pub async fn create_pending_workflow_change<T: DatabaseImpl>(
+    db: &T,
+    change: &NewPendingWorkflowChange,
+) -> Result<PendingWorkflowChange, SendableError> {
+    db.create_pending_workflow_change(change).await
+}
+
+pub async fn fetch_pending_workflow_changes<T: DatabaseImpl>(
+    db: &T,
+    workflow_id: Uuid,
+) -> Result<Vec<PendingWorkflowChange>, SendableError> {
+    db.fetch_pending_workflow_changes(workflow_id).await
+}
+
+pub async fn fetch_pending_workflow_change<T: DatabaseImpl>(
+    db: &T,
+    change_id: Uuid,
+) -> Result<Option<PendingWorkflowChange>, SendableError> {
+    db.fetch_pending_workflow_change(change_id).await
+}
+
+pub async fn review_pending_workflow_change<T: DatabaseImpl>(
+    db: &T,
+    change_id: Uuid,
+    status: PendingChangeStatus,
+    reviewed_by: Option<Uuid>,
+) -> Result<bool, SendableError> {
+    db.review_pending_workflow_change(change_id, status, reviewed_by)
+        .await
+}
+
+/// the stored workflow an id-less save of `workflow` would update, matched on (namespace, name)
+/// the same way the upsert itself resolves it.
+pub async fn fetch_upsert_target<T: DatabaseImpl>(
+    db: &T,
+    workflow: &WorkflowDefinition,
+) -> Result<Option<Uuid>, SendableError> {
+    if let Some(id) = workflow.id {
+        return Ok(Some(id));
+    }
+    let name = match &workflow.namespace {
+        Some(namespace) => format!("{namespace}.{}", workflow.name),
+        None => workflow.name.clone(),
+    };
+    Ok(db
+        .fetch_workflow_by_name(name)
+        .await?
+        .filter(|existing| {
+            existing.name == workflow.name && existing.namespace == workflow.namespace
+        })
+        .and_then(|existing| existing.id))
+}
+
 pub async fn fetch_workflow_by_name<T: DatabaseImpl>(
     db: &T,
     name: String,
@@ -125,6 +332,23 @@ fn incoming_is_newer(incoming: Option<DateTime<Utc>>, stored: Option<DateTime<Ut
     }
 }
 
+/// the stored workflow importing `workflow` would change: its upsert target, unless a reconciling
+/// (non-`overwrite`) import would skip it because the stored copy is at least as new.
+pub async fn fetch_import_target<T: DatabaseImpl>(
+    db: &T,
+    workflow: &WorkflowDefinition,
+    overwrite: bool,
+) -> Result<Option<Uuid>, SendableError> {
+    if !overwrite
+        && workflow.id.is_none()
+        && let Some(existing) = db.fetch_workflow_by_name(workflow.name.clone()).await?
+        && !incoming_is_newer(workflow.updated_at, existing.updated_at)
+    {
+        return Ok(None);
+    }
+    fetch_upsert_target(db, workflow).await
+}
+
 pub async fn import_workflow_bundle<T: DatabaseImpl>(
     db: &T,
     bundle: WorkflowBundle,
@@ -178,9 +402,45 @@ pub async fn import_workflow_bundle_with<T: DatabaseImpl>(
     Ok(WorkflowBundle {
         workflows,
         triggers,
+        baselines: bundle.baselines,
     })
 }
 
+/// apply `options` to the cron triggers of every workflow `imported` brought in, whether the bundle
+/// listed them or the workflow declared them, so a copy of another environment does not fire
+/// alongside it. an offset trigger is rescheduled on the next trigger pass. `imported.triggers` is
+/// updated to match what was saved.
+pub async fn apply_workflow_import_options<T: DatabaseImpl>(
+    db: &T,
+    imported: &mut WorkflowBundle,
+    options: &WorkflowImportOptions,
+) -> Result<(), SendableError> {
+    if options.keeps_schedules() {
+        return Ok(());
+    }
+    for workflow_id in imported.workflows.iter().filter_map(|workflow| workflow.id) {
+        for mut trigger in triggers::fetch_workflow_triggers(db, workflow_id).await? {
+            let before = trigger.clone();
+            options.apply(&mut trigger);
+            if trigger.enabled == before.enabled && trigger.configuration == before.configuration {
+                continue;
+            }
+            if trigger.configuration != before.configuration {
+                trigger.next_execution = None;
+            }
+            let saved = triggers::upsert_workflow_trigger(db, &trigger).await?;
+            if let Some(listed) = imported
+                .triggers
+                .iter_mut()
+                .find(|listed| listed.id.is_some() && listed.id == saved.id)
+            {
+                *listed = saved;
+            }
+        }
+    }
+    Ok(())
+}
+
 /// validate that every subflow node targets a workflow present in the bundle or already stored.
 async fn validate_subflow_targets<T: DatabaseImpl>(
     db: &T,
@@ -294,6 +554,7 @@ async fn materialize_workflow_triggers<T: DatabaseImpl>(
     // drop the previous pack-managed header triggers for this workflow. pipeline-link triggers are
     // also managed_by=wdl but owned by a pipeline (keyed by configuration.pipeline_id); leave those
     // to pipeline reconciliation so materializing a workflow does not clobber its pipeline links.
+    let mut previous_crons = Vec::new();
     for existing in db.fetch_workflow_triggers(workflow_id).await? {
         let managed = existing
             .metadata
@@ -302,9 +563,15 @@ async fn materialize_workflow_triggers<T: DatabaseImpl>(
             == Some("wdl");
         let pipeline_owned = existing.configuration.pointer("/pipeline_id").is_some();
         if let (true, false, Some(trigger_id)) = (managed, pipeline_owned, existing.id) {
+            if existing.kind == runinator_models::workflows::WorkflowTriggerKind::Cron
+                && let Some(cron) = existing.configuration.get("cron").and_then(Value::as_str)
+            {
+                previous_crons.push(cron.to_string());
+            }
             db.delete_workflow_trigger(trigger_id).await?;
         }
     }
+    let mut declared_crons = Vec::new();
     // insert the currently declared triggers.
     for spec in &specs {
         let parameters = spec
@@ -350,6 +617,7 @@ async fn materialize_workflow_triggers<T: DatabaseImpl>(
                 else {
                     continue;
                 };
+                declared_crons.push(cron.to_string());
                 let blackout_start = spec
                     .get("blackout_start")
                     .and_then(Value::as_str)
@@ -377,6 +645,7 @@ async fn materialize_workflow_triggers<T: DatabaseImpl>(
         };
         db.upsert_workflow_trigger(&trigger).await?;
     }
+    triggers::log_declared_schedule_changes(&workflow.name, &previous_crons, &declared_crons);
     Ok(())
 }
 
@@ -388,9 +657,12 @@ fn parse_trigger_datetime(value: &str) -> Result<DateTime<Utc>, SendableError> {
         })
 }
 
+/// the stored workflows (or just `workflow_id`) with their triggers, plus up to `baseline_runs`
+/// of each workflow's most recent finished runs as baselines.
 pub async fn export_workflow_bundle<T: DatabaseImpl>(
     db: &T,
     workflow_id: Option<Uuid>,
+    baseline_runs: usize,
 ) -> Result<WorkflowBundle, SendableError> {
     let workflows = match workflow_id {
         Some(id) => match fetch_workflow(db, id).await? {
@@ -408,9 +680,25 @@ pub async fn export_workflow_bundle<T: DatabaseImpl>(
         triggers.extend(triggers::fetch_workflow_triggers(db, id).await?);
     }
 
+    let mut baselines = Vec::new();
+    if baseline_runs > 0 {
+        for workflow in &workflows {
+            let Some(id) = workflow.id else {
+                continue;
+            };
+            let runs = db.fetch_workflow_runs_for_workflow(id).await?;
+            baselines.extend(
+                runs.iter()
+                    .filter_map(|run| WorkflowRunBaseline::from_run(workflow, run))
+                    .take(baseline_runs),
+            );
+        }
+    }
+
     Ok(WorkflowBundle {
         workflows,
         triggers,
+        baselines,
     })
 }
 
@@ -448,6 +736,66 @@ pub async fn duplicate_workflow<T: DatabaseImpl>(
     db.insert_workflow(&copy).await
 }
 
+/// copy a workflow into a new, independent workflow: a fresh id, `name` (or the source name with a
+/// unique ` (copy)` suffix), and disabled. the source's triggers come along disabled so the clone
+/// never fires until someone turns it on.
+pub async fn clone_workflow<T: DatabaseImpl>(
+    db: &T,
+    workflow_id: Uuid,
+    name: Option<String>,
+) -> Result<WorkflowDefinition, SendableError> {
+    let Some(existing) = fetch_workflow(db, workflow_id).await? else {
+        return Err(
+            runinator_reducer::errors::WORKFLOW_NOT_FOUND.error(format!("id {workflow_id}"))
+        );
+    };
+    let taken = db
+        .fetch_workflows()
+        .await?
+        .into_iter()
+        .filter(|workflow| workflow.namespace == existing.namespace)
+        .map(|workflow| workflow.name)
+        .collect::<std::collections::HashSet<_>>();
+    let name = match name.map(|name| name.trim().to_string()) {
+        Some(name) if !name.is_empty() => name,
+        _ => clone_name(&existing.name, &taken),
+    };
+
+    let mut copy = existing;
+    copy.id = None;
+    copy.name = name;
+    copy.enabled = false;
+    copy.created_at = None;
+    copy.updated_at = None;
+    let copy = validate_workflow_definition_with_catalog(db, &copy).await?;
+    let copy = db.insert_workflow(&copy).await?;
+    let Some(copy_id) = copy.id else {
+        return Ok(copy);
+    };
+    for mut trigger in triggers::fetch_workflow_triggers(db, workflow_id).await? {
+        trigger.id = None;
+        trigger.workflow_id = copy_id;
+        trigger.enabled = false;
+        trigger.next_execution = None;
+        trigger.created_at = None;
+        trigger.updated_at = None;
+        triggers::upsert_workflow_trigger(db, &trigger).await?;
+    }
+    Ok(copy)
+}
+
+/// `<name> (copy)`, then `<name> (copy 2)`, `<name> (copy 3)`, ... until one is free.
+fn clone_name(name: &str, taken: &std::collections::HashSet<String>) -> String {
+    let first = format!("{name} (copy)");
+    if !taken.contains(&first) {
+        return first;
+    }
+    (2..)
+        .map(|n| format!("{name} (copy {n})"))
+        .find(|candidate| !taken.contains(candidate))
+        .unwrap_or(first)
+}
+
 pub async fn delete_workflow<T: DatabaseImpl>(
     db: &T,
     workflow_id: Uuid,
@@ -0,0 +1,78 @@
+use super::*;
+use runinator_comm::{ActionCommand, ActionTarget};
+use runinator_models::jobs::{JOB_NODE_ID, Job, JobRetry};
+use runinator_models::workflows::WorkflowAction;
+use uuid::Uuid;
+
+/// record a one-off job and queue its action for dispatch. the command carries the job id as both its
+/// run and node-run id under [`JOB_NODE_ID`], so the worker's result events find their way back to
+/// the job.
+pub async fn submit_job<T: DatabaseImpl>(
+    db: &T,
+    action: WorkflowAction,
+    retry: JobRetry,
+    requested_by: Option<Uuid>,
+) -> Result<Job, SendableError> {
+    let job_id = Uuid::now_v7();
+    let job = db.create_job(job_id, &action, retry, requested_by).await?;
+    db.enqueue_action_dispatch(format!("job:{job_id}"), job_command(job_id, action, 1))
+        .await?;
+    Ok(job)
+}
+
+/// queue the next attempt of a job its last result sent back to `queued`, held until the attempt's
+/// backoff has passed. the dispatch is keyed per attempt, so a redelivered result re-queues nothing.
+/// a no-op for a job that is not waiting on a retry.
+pub async fn dispatch_job_retry<T: DatabaseImpl>(
+    db: &T,
+    job_id: Uuid,
+) -> Result<(), SendableError> {
+    let Some(job) = db.fetch_job(job_id).await? else {
+        return Ok(());
+    };
+    if job.status != WorkflowStatus::Queued || job.attempt <= 1 {
+        return Ok(());
+    }
+    let failed_at = job
+        .attempts
+        .last()
+        .map(|attempt| attempt.finished_at)
+        .unwrap_or_else(Utc::now);
+    let not_before = failed_at + Duration::seconds(job.retry().delay_seconds(job.attempt - 1));
+    db.enqueue_action_dispatch_at(
+        format!("job:{job_id}:{}", job.attempt),
+        job_command(job_id, job.action, job.attempt),
+        Some(not_before),
+    )
+    .await?;
+    Ok(())
+}
+
+fn job_command(job_id: Uuid, action: WorkflowAction, attempt: i64) -> ActionCommand {
+    let target = if action.required_labels.is_empty() {
+        ActionTarget::Any
+    } else {
+        ActionTarget::Labels {
+            selector: action.required_labels.clone(),
+        }
+    };
+    ActionCommand {
+        command_id: Uuid::new_v4(),
+        workflow_run_id: job_id,
+        workflow_node_run_id: job_id,
+        node_id: JOB_NODE_ID.into(),
+        parameters: action.configuration.as_value().clone(),
+        action,
+        attempt,
+        target,
+        trace_id: Uuid::now_v7(),
+        trace_context: runinator_utilities::telemetry::current_trace_context(),
+    }
+}
+
+pub async fn fetch_job<T: DatabaseImpl>(
+    db: &T,
+    job_id: Uuid,
+) -> Result<Option<Job>, SendableError> {
+    db.fetch_job(job_id).await
+}
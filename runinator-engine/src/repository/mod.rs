@@ -25,20 +25,29 @@ pub use crate::repository_runs::{
 };
 use crate::repository_state::latest_node_run_for;
 
+pub use backfills::*;
+pub use blackouts::*;
 pub use catalog::*;
+pub use cluster::*;
 pub use debug::*;
 pub use definitions::*;
+pub use jobs::*;
 pub use node_runs::*;
 pub use org_scope::{org_id_for_pipeline_run, org_id_for_workflow_run};
 pub use pipelines::*;
 pub use provider_meta::{provider_metadata_from_item, provider_metadata_from_items};
 pub use replicas::*;
 pub use runs::*;
+pub use templates::*;
 pub use triggers::*;
 
+mod backfills;
+mod blackouts;
 mod catalog;
+mod cluster;
 mod debug;
 mod definitions;
+mod jobs;
 mod node_runs;
 mod org_scope;
 mod pipelines;
@@ -46,4 +55,5 @@ mod provider_meta;
 mod replicas;
 mod runs;
 mod support;
+mod templates;
 mod triggers;
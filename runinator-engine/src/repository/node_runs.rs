@@ -1,5 +1,10 @@
 use super::support;
 use super::*;
+use runinator_models::overview::executor_ids;
+use runinator_models::provider_stats::NodeRunCounts;
+use runinator_models::queue_wait::QueueWaitSample;
+use runinator_models::upcoming::ActiveNodeRun;
+use std::collections::HashMap;
 use uuid::Uuid;
 
 pub async fn fetch_workflow_run<T: DatabaseImpl>(
@@ -13,6 +18,51 @@ pub async fn fetch_workflow_run<T: DatabaseImpl>(
     Ok(Some((run, nodes)))
 }
 
+/// the replicas that executed each of `workflow_run_ids`, as [`executor_ids`] orders them, from
+/// one read of their node runs. runs with no node runs are absent.
+pub async fn fetch_run_executors<T: DatabaseImpl>(
+    db: &T,
+    workflow_run_ids: Vec<Uuid>,
+) -> Result<HashMap<Uuid, Vec<Uuid>>, SendableError> {
+    if workflow_run_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let mut by_run: HashMap<Uuid, Vec<WorkflowNodeRun>> = HashMap::new();
+    for node_run in db
+        .fetch_workflow_node_runs_for_runs(workflow_run_ids)
+        .await?
+    {
+        by_run
+            .entry(node_run.workflow_run_id)
+            .or_default()
+            .push(node_run);
+    }
+    Ok(by_run
+        .into_iter()
+        .map(|(run_id, node_runs)| (run_id, executor_ids(&node_runs)))
+        .collect())
+}
+
+pub async fn fetch_queue_wait_samples<T: DatabaseImpl>(
+    db: &T,
+    since: DateTime<Utc>,
+) -> Result<Vec<QueueWaitSample>, SendableError> {
+    db.fetch_queue_wait_samples(since).await
+}
+
+pub async fn fetch_active_node_runs<T: DatabaseImpl>(
+    db: &T,
+) -> Result<Vec<ActiveNodeRun>, SendableError> {
+    db.fetch_active_node_runs().await
+}
+
+pub async fn fetch_node_run_counts<T: DatabaseImpl>(
+    db: &T,
+    since: DateTime<Utc>,
+) -> Result<Vec<NodeRunCounts>, SendableError> {
+    db.fetch_node_run_counts(since).await
+}
+
 pub async fn fetch_workflow_node_run<T: DatabaseImpl>(
     db: &T,
     workflow_node_run_id: Uuid,
@@ -27,7 +77,7 @@ pub async fn claim_workflow_node_run_executor<T: DatabaseImpl>(
     claimed_at: DateTime<Utc>,
     stale_before: DateTime<Utc>,
 ) -> Result<TaskResponse, SendableError> {
-    let acquired = db
+    let mut acquired = db
         .claim_workflow_node_run_executor(
             workflow_node_run_id,
             replica_id,
@@ -35,6 +85,17 @@ pub async fn claim_workflow_node_run_executor<T: DatabaseImpl>(
             stale_before,
         )
         .await?;
+    // a one-off job's command carries the job id where a node run id would be.
+    if !acquired
+        && db
+            .fetch_workflow_node_run(workflow_node_run_id)
+            .await?
+            .is_none()
+    {
+        acquired = db
+            .claim_job_executor(workflow_node_run_id, replica_id, claimed_at, stale_before)
+            .await?;
+    }
     Ok(TaskResponse {
         success: acquired,
         message: if acquired {
@@ -53,6 +114,8 @@ pub async fn release_workflow_node_run_executor<T: DatabaseImpl>(
 ) -> Result<TaskResponse, SendableError> {
     db.release_workflow_node_run_executor(workflow_node_run_id, replica_id, released_at)
         .await?;
+    db.release_job_executor(workflow_node_run_id, replica_id)
+        .await?;
     Ok(TaskResponse {
         success: true,
         message: "Workflow node run executor released".into(),
@@ -102,6 +165,27 @@ pub async fn fetch_workflow_run_artifacts<T: DatabaseImpl>(
     db.fetch_workflow_run_artifacts(workflow_run_id).await
 }
 
+/// record named outputs on a workflow run; `None` when the run does not exist.
+pub async fn record_workflow_run_outputs<T: DatabaseImpl>(
+    db: &T,
+    workflow_run_id: Uuid,
+    outputs: &std::collections::BTreeMap<String, Value>,
+) -> Result<Option<Vec<runinator_models::workflows::WorkflowRunOutput>>, SendableError> {
+    let Some(run) = db.fetch_workflow_run(workflow_run_id).await? else {
+        return Ok(None);
+    };
+    db.record_workflow_run_outputs(run.id, run.workflow_id, outputs)
+        .await
+        .map(Some)
+}
+
+pub async fn fetch_workflow_run_outputs<T: DatabaseImpl>(
+    db: &T,
+    workflow_run_id: Uuid,
+) -> Result<Vec<runinator_models::workflows::WorkflowRunOutput>, SendableError> {
+    db.fetch_workflow_run_outputs(workflow_run_id).await
+}
+
 pub async fn fetch_run_transitions<T: DatabaseImpl>(
     db: &T,
     workflow_run_id: Uuid,
@@ -121,6 +205,21 @@ pub async fn apply_workflow_result_event<T: DatabaseImpl>(
     db: &T,
     event: &WorkflowResultEvent,
 ) -> Result<bool, SendableError> {
+    if runinator_models::jobs::is_job(
+        event.workflow_run_id,
+        event.workflow_node_run_id,
+        &event.node_id,
+    ) {
+        let applied = db.apply_job_result_event(event).await?;
+        // like the drive below, re-check on a duplicate too: the retry dispatch is keyed per attempt,
+        // so queueing it again is a no-op.
+        if let WorkflowResultEventKind::Status { status, .. } = &event.kind
+            && status.is_terminal()
+        {
+            dispatch_job_retry(db, event.workflow_node_run_id).await?;
+        }
+        return Ok(applied);
+    }
     let applied = db.apply_workflow_result_event(event).await?;
     // enqueue the drive even when the event is a duplicate: a redelivery usually means a prior
     // attempt failed between persisting the event and enqueueing this ready node, and skipping it
@@ -298,8 +298,22 @@ pub async fn claim_due_pipeline_trigger_firings<T: DatabaseImpl>(
     scheduler_id: String,
     limit: i64,
 ) -> Result<Vec<PipelineRun>, SendableError> {
+    claim_due_pipeline_trigger_firings_at(db, scheduler_id, Utc::now(), limit).await
+}
+
+/// [`claim_due_pipeline_trigger_firings`] evaluated at `now` rather than the wall clock. nothing is
+/// claimed while scheduling is paused cluster-wide.
+pub async fn claim_due_pipeline_trigger_firings_at<T: DatabaseImpl>(
+    db: &T,
+    scheduler_id: String,
+    now: DateTime<Utc>,
+    limit: i64,
+) -> Result<Vec<PipelineRun>, SendableError> {
+    if super::scheduling_paused(db).await? {
+        return Ok(Vec::new());
+    }
     let runs = db
-        .claim_due_pipeline_trigger_firings(scheduler_id, Utc::now(), limit)
+        .claim_due_pipeline_trigger_firings(scheduler_id, now, limit)
         .await?;
     for run in &runs {
         runinator_reducer::start_pipeline_run(db, run).await?;
@@ -3,6 +3,7 @@ use runinator_models::auth::AuthContext;
 use runinator_models::replicas::{
     ReplicaHeartbeatRequest, ReplicaKind, ReplicaListResponse, ReplicaProviderRegistration,
     ReplicaProviderRegistrationRequest, ReplicaRecord, ReplicaRegistrationRequest, ReplicaStatus,
+    WorkerSummary,
 };
 use runinator_models::telemetry::{ReplicaSample, ReplicaSampleSeries, ResourceTelemetry};
 use uuid::Uuid;
@@ -156,6 +157,30 @@ pub async fn fetch_replicas<T: DatabaseImpl>(
     })
 }
 
+/// the worker registry: every worker replica with its plugins, registered providers, and load.
+pub async fn fetch_workers<T: DatabaseImpl>(
+    db: &T,
+    status: Option<ReplicaStatus>,
+) -> Result<Vec<WorkerSummary>, SendableError> {
+    let listing = fetch_replicas(db, Some(ReplicaKind::Worker), status).await?;
+    let mut workers = Vec::with_capacity(listing.replicas.len());
+    for replica in &listing.replicas {
+        let providers = db
+            .fetch_replica_provider_registrations(replica.replica_id)
+            .await?
+            .into_iter()
+            .map(|registration| registration.provider_name)
+            .collect();
+        let running = listing
+            .running_tasks
+            .get(&replica.replica_id)
+            .copied()
+            .unwrap_or_default();
+        workers.push(WorkerSummary::from_replica(replica, providers, running));
+    }
+    Ok(workers)
+}
+
 pub async fn upsert_replica_provider_registration<T: DatabaseImpl>(
     db: &T,
     replica_id: Uuid,
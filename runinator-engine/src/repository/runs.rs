@@ -163,31 +163,54 @@ const READY_NODE_DRIVE_LEASE_SECONDS: i64 = 60;
 
 /// drain durable action-dispatch intents and publish them to the broker action channel. moved into
 /// the web service (which owns the database and the reducer) so the waker no longer relays them.
+/// while the cluster's dispatch is paused nothing is claimed, so intents wait in the outbox. a
+/// dispatch that fails to publish `dead_letter_after` times in a row is dead-lettered (see
+/// [`crate::enqueue_failure`]); `None` retries it forever.
 pub async fn publish_pending_action_dispatches<T: DatabaseImpl>(
     db: &T,
     broker: &dyn Broker,
     publisher_id: &str,
     lease_seconds: i64,
     limit: i64,
+    dead_letter_after: Option<i64>,
 ) -> Result<(), SendableError> {
+    let pause = db.fetch_cluster_pause().await?;
+    crate::stability::cluster_pause(&pause);
+    if pause.dispatch {
+        return Ok(());
+    }
     let now = Utc::now();
     let lease_until = now + Duration::seconds(lease_seconds);
     let dispatches = db
         .claim_pending_action_dispatches(publisher_id.to_string(), now, lease_until, limit)
         .await?;
     for dispatch in dispatches {
-        let dispatch_id = dispatch.id;
         let message = BrokerMessage {
-            command: dispatch.command,
-            dedupe_key: Some(dispatch.dedupe_key),
+            priority: dispatch.command.action.priority,
+            queue: dispatch.command.action.queue.clone(),
+            command: dispatch.command.clone(),
+            dedupe_key: Some(dispatch.dedupe_key.clone()),
             enqueued_at: Utc::now(),
         };
-        match broker.publish(message).await {
+        let started = std::time::Instant::now();
+        let published = broker.publish(message).await;
+        crate::stability::record_dispatch_publish_ms(started.elapsed().as_secs_f64() * 1000.0);
+        match published {
             Ok(()) | Err(BrokerError::Duplicate(_)) => {
-                db.mark_action_dispatch_published(dispatch_id).await?;
+                db.mark_action_dispatch_published(dispatch.id).await?;
+            }
+            // a published dispatch leaves the outbox, so its attempts count consecutive failures.
+            Err(err) if dead_letter_after.is_some_and(|after| dispatch.attempts + 1 >= after) => {
+                crate::enqueue_failure::dead_letter_dispatch(
+                    db,
+                    &dispatch,
+                    dispatch.attempts + 1,
+                    &err.to_string(),
+                )
+                .await?;
             }
             Err(err) => {
-                db.mark_action_dispatch_failed(dispatch_id, err.to_string())
+                db.mark_action_dispatch_failed(dispatch.id, err.to_string())
                     .await?;
             }
         }
@@ -318,6 +341,13 @@ pub async fn fetch_recent_workflow_runs<T: DatabaseImpl>(
     db.fetch_recent_workflow_runs(limit).await
 }
 
+pub async fn fetch_workflow_runs_since<T: DatabaseImpl>(
+    db: &T,
+    since: DateTime<Utc>,
+) -> Result<Vec<WorkflowRun>, SendableError> {
+    db.fetch_workflow_runs_since(since).await
+}
+
 pub async fn fetch_workflow_runs_for_workflow<T: DatabaseImpl>(
     db: &T,
     workflow_id: Uuid,
@@ -325,6 +355,22 @@ pub async fn fetch_workflow_runs_for_workflow<T: DatabaseImpl>(
     db.fetch_workflow_runs_for_workflow(workflow_id).await
 }
 
+/// the newest `per_workflow` runs of each workflow in `workflow_ids`, read in one query.
+pub async fn fetch_recent_workflow_runs_for_workflows<T: DatabaseImpl>(
+    db: &T,
+    workflow_ids: Vec<Uuid>,
+    per_workflow: usize,
+) -> Result<Vec<WorkflowRun>, SendableError> {
+    if workflow_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    db.fetch_recent_workflow_runs_for_workflows(
+        workflow_ids,
+        i64::try_from(per_workflow).unwrap_or(i64::MAX),
+    )
+    .await
+}
+
 pub async fn fetch_workflow_runs_by_name<T: DatabaseImpl>(
     db: &T,
     name: String,
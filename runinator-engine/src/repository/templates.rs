@@ -0,0 +1,81 @@
+use super::*;
+use super::{definitions, triggers};
+use runinator_models::templates::{InstantiateTemplateRequest, WorkflowTemplate};
+use uuid::Uuid;
+
+fn invalid(message: String) -> SendableError {
+    Box::new(std::io::Error::new(
+        std::io::ErrorKind::InvalidInput,
+        message,
+    ))
+}
+
+/// validate and save a template. its workflow must pass the same checks a saved workflow does, and
+/// its cron (if any) must parse, so every instance starts out valid.
+pub async fn upsert_workflow_template<T: DatabaseImpl>(
+    db: &T,
+    template: &WorkflowTemplate,
+) -> Result<WorkflowTemplate, SendableError> {
+    if template.name.trim().is_empty() {
+        return Err(invalid("template name must not be empty".into()));
+    }
+    if let Some(cron) = template.cron.as_deref() {
+        let preview = runinator_database::schedule::preview_cron(cron, None, Utc::now(), 1, None);
+        if !preview.valid {
+            return Err(invalid(format!(
+                "template cron `{cron}` is invalid: {}",
+                preview.error.unwrap_or_default()
+            )));
+        }
+    }
+    let mut template = template.clone();
+    template.workflow =
+        definitions::validate_workflow_definition_with_catalog(db, &template.workflow).await?;
+    db.upsert_workflow_template(&template).await
+}
+
+pub async fn fetch_workflow_templates<T: DatabaseImpl>(
+    db: &T,
+) -> Result<Vec<WorkflowTemplate>, SendableError> {
+    db.fetch_workflow_templates().await
+}
+
+pub async fn fetch_workflow_template<T: DatabaseImpl>(
+    db: &T,
+    template_id: Uuid,
+) -> Result<Option<WorkflowTemplate>, SendableError> {
+    db.fetch_workflow_template(template_id).await
+}
+
+pub async fn delete_workflow_template<T: DatabaseImpl>(
+    db: &T,
+    template_id: Uuid,
+) -> Result<bool, SendableError> {
+    db.delete_workflow_template(template_id).await
+}
+
+/// create a workflow from `template` owned by `org_id`, plus its cron trigger when the template
+/// has a schedule. returned as a bundle of the one workflow and its triggers.
+pub async fn instantiate_workflow_template<T: DatabaseImpl>(
+    db: &T,
+    template: &WorkflowTemplate,
+    request: &InstantiateTemplateRequest,
+    org_id: Option<Uuid>,
+    changed_by: Option<Uuid>,
+) -> Result<WorkflowBundle, SendableError> {
+    if request.name.trim().is_empty() {
+        return Err(invalid("workflow name must not be empty".into()));
+    }
+    let mut workflow = template.instantiate(request);
+    workflow.org_id = org_id;
+    let workflow = definitions::upsert_workflow_as(db, &workflow, changed_by).await?;
+    let mut created = Vec::new();
+    if let Some(trigger) = template.cron_trigger(&workflow) {
+        created.push(triggers::upsert_workflow_trigger(db, &trigger).await?);
+    }
+    Ok(WorkflowBundle {
+        workflows: vec![workflow],
+        triggers: created,
+        baselines: Vec::new(),
+    })
+}
@@ -1,12 +1,107 @@
+use std::collections::{HashMap, HashSet};
+
 use super::support;
 use super::*;
+use runinator_database::blackout::references_calendar;
+use runinator_database::schedule::{
+    CronSchedule, effective_schedule, preview_schedule_change, preview_trigger_firings,
+    trigger_deferred_until, trigger_fire_times,
+};
+use runinator_models::schedule_shift::{
+    ScheduleShiftChange, ScheduleShiftReport, ScheduleShiftRequest,
+};
+use runinator_models::upcoming::{PreviewFiring, PreviewInvalidTrigger, SchedulerPreview};
+use runinator_models::workflows::{
+    SchedulePreview, ScheduleReconcileReport, WebhookTriggerConfig, WorkflowTriggerKind,
+    WorkflowTriggerPreview, WorkflowTriggerView, trigger_timezone,
+};
 use uuid::Uuid;
 
+/// save a trigger. when an existing trigger's cron or zone changes, the before/after fire times are
+/// logged.
 pub async fn upsert_workflow_trigger<T: DatabaseImpl>(
     db: &T,
     trigger: &WorkflowTrigger,
 ) -> Result<WorkflowTrigger, SendableError> {
-    db.upsert_workflow_trigger(trigger).await
+    let previous = match trigger.id {
+        Some(trigger_id) => db.fetch_workflow_trigger(trigger_id).await?,
+        None => None,
+    };
+    let saved = db.upsert_workflow_trigger(trigger).await?;
+    if let Some(previous) = previous {
+        let preview = trigger_schedule_preview(Some(&previous), &saved);
+        if preview.changed() {
+            log::info!(
+                "Schedule of trigger {} on workflow {} changed: {}",
+                saved.id.unwrap_or_default(),
+                saved.workflow_id,
+                preview.describe()
+            );
+        }
+    }
+    Ok(saved)
+}
+
+/// what saving `trigger` would do to its schedule, without writing anything.
+pub async fn preview_workflow_trigger_update<T: DatabaseImpl>(
+    db: &T,
+    trigger: &WorkflowTrigger,
+) -> Result<WorkflowTriggerPreview, SendableError> {
+    let previous = match trigger.id {
+        Some(trigger_id) => db.fetch_workflow_trigger(trigger_id).await?,
+        None => None,
+    };
+    Ok(WorkflowTriggerPreview {
+        schedule: trigger_schedule_preview(previous.as_ref(), trigger),
+        trigger: trigger.clone(),
+    })
+}
+
+/// the next fire times of `previous`'s cron against `next`'s, each in its trigger's zone. non-cron
+/// triggers have no schedule.
+pub fn trigger_schedule_preview(
+    previous: Option<&WorkflowTrigger>,
+    next: &WorkflowTrigger,
+) -> SchedulePreview {
+    preview_schedule_change(
+        previous.and_then(trigger_schedule),
+        trigger_schedule(next),
+        Utc::now(),
+    )
+}
+
+/// log each pack-declared cron an import changed, pairing the old and new schedules of a workflow
+/// in declaration order.
+pub(crate) fn log_declared_schedule_changes(
+    workflow_name: &str,
+    previous: &[String],
+    declared: &[String],
+) {
+    if previous == declared {
+        return;
+    }
+    let now = Utc::now();
+    for index in 0..previous.len().max(declared.len()) {
+        let preview = preview_schedule_change(
+            previous.get(index).map(|cron| (cron.as_str(), None)),
+            declared.get(index).map(|cron| (cron.as_str(), None)),
+            now,
+        );
+        if preview.changed() {
+            log::info!(
+                "Import changed a schedule of workflow '{workflow_name}': {}",
+                preview.describe()
+            );
+        }
+    }
+}
+
+fn trigger_schedule(trigger: &WorkflowTrigger) -> Option<CronSchedule<'_>> {
+    if trigger.kind != WorkflowTriggerKind::Cron {
+        return None;
+    }
+    let cron = trigger.configuration.get("cron").and_then(Value::as_str)?;
+    Some((cron, trigger_timezone(&trigger.configuration)))
 }
 
 pub async fn fetch_workflow_triggers<T: DatabaseImpl>(
@@ -23,10 +118,117 @@ pub async fn fetch_workflow_trigger<T: DatabaseImpl>(
     db.fetch_workflow_trigger(trigger_id).await
 }
 
+/// a workflow's triggers, each with when it will actually fire next at `now` and why that differs
+/// from its cron slot (see [`effective_schedule`]).
+pub async fn fetch_workflow_trigger_views<T: DatabaseImpl>(
+    db: &T,
+    workflow_id: Uuid,
+    now: DateTime<Utc>,
+) -> Result<Vec<WorkflowTriggerView>, SendableError> {
+    let triggers = db.fetch_workflow_triggers(workflow_id).await?;
+    let calendars = db.fetch_blackout_calendars().await?;
+    Ok(triggers
+        .into_iter()
+        .map(|trigger| WorkflowTriggerView {
+            schedule: effective_schedule(&trigger, &calendars, now),
+            trigger,
+        })
+        .collect())
+}
+
+/// one trigger with its effective schedule at `now`, as [`fetch_workflow_trigger_views`].
+pub async fn fetch_workflow_trigger_view<T: DatabaseImpl>(
+    db: &T,
+    trigger_id: Uuid,
+    now: DateTime<Utc>,
+) -> Result<Option<WorkflowTriggerView>, SendableError> {
+    let Some(trigger) = db.fetch_workflow_trigger(trigger_id).await? else {
+        return Ok(None);
+    };
+    let calendars = db.fetch_blackout_calendars().await?;
+    Ok(Some(WorkflowTriggerView {
+        schedule: effective_schedule(&trigger, &calendars, now),
+        trigger,
+    }))
+}
+
+/// the enabled `webhook` trigger whose stored token hash is `token_hash`, if any.
+pub async fn fetch_webhook_trigger<T: DatabaseImpl>(
+    db: &T,
+    token_hash: &str,
+) -> Result<Option<WorkflowTrigger>, SendableError> {
+    db.fetch_enabled_webhook_trigger(token_hash).await
+}
+
+/// every enabled cron trigger with its firings through `until` (see [`trigger_fire_times`]). triggers whose schedule no longer parses
+/// are left out; the trigger loop has parked them.
+pub async fn project_cron_firings<T: DatabaseImpl>(
+    db: &T,
+    now: DateTime<Utc>,
+    until: DateTime<Utc>,
+    max_per_trigger: usize,
+) -> Result<Vec<(WorkflowTrigger, Vec<DateTime<Utc>>)>, SendableError> {
+    let triggers = db
+        .fetch_enabled_workflow_triggers_by_kind(WorkflowTriggerKind::Cron)
+        .await?;
+    Ok(triggers
+        .into_iter()
+        .filter_map(|trigger| {
+            let times = trigger_fire_times(&trigger, now, until, max_per_trigger).ok()?;
+            Some((trigger, times))
+        })
+        .collect())
+}
+
+/// dry-run the trigger loop from `now` through `until` without claiming or enqueueing anything:
+/// every enabled cron trigger's firings, blackout deferrals included (see
+/// [`preview_trigger_firings`]), and the triggers whose schedule does not evaluate.
+pub async fn preview_scheduling_cycle<T: DatabaseImpl>(
+    db: &T,
+    now: DateTime<Utc>,
+    until: DateTime<Utc>,
+    max_per_trigger: usize,
+) -> Result<SchedulerPreview, SendableError> {
+    let triggers = db
+        .fetch_enabled_workflow_triggers_by_kind(WorkflowTriggerKind::Cron)
+        .await?;
+    let calendars = db.fetch_blackout_calendars().await?;
+    let names = db
+        .fetch_workflows()
+        .await?
+        .into_iter()
+        .filter_map(|workflow| workflow.id.map(|id| (id, workflow.name)))
+        .collect::<HashMap<_, _>>();
+    let workflow_name = |workflow_id| names.get(&workflow_id).cloned().unwrap_or_default();
+
+    let mut firings = Vec::new();
+    let mut invalid = Vec::new();
+    for trigger in triggers {
+        let trigger_id = trigger.id.unwrap_or_default();
+        match preview_trigger_firings(&trigger, &calendars, now, until, max_per_trigger) {
+            Ok(slots) => firings.extend(slots.into_iter().map(|(slot, fire)| PreviewFiring {
+                trigger_id,
+                workflow_id: trigger.workflow_id,
+                workflow_name: workflow_name(trigger.workflow_id),
+                slot,
+                fires_at: fire.at,
+                deferred: fire.deferred,
+            })),
+            Err(err) => invalid.push(PreviewInvalidTrigger {
+                trigger_id,
+                workflow_id: trigger.workflow_id,
+                workflow_name: workflow_name(trigger.workflow_id),
+                error: err.to_string(),
+            }),
+        }
+    }
+    Ok(SchedulerPreview::build(now, until, firings, invalid))
+}
+
 pub async fn fetch_due_workflow_triggers<T: DatabaseImpl>(
     db: &T,
 ) -> Result<Vec<WorkflowTrigger>, SendableError> {
-    db.fetch_due_workflow_triggers(Utc::now()).await
+    db.fetch_due_workflow_triggers(Utc::now(), None).await
 }
 
 pub async fn claim_due_workflow_trigger_firings<T: DatabaseImpl>(
@@ -34,15 +236,224 @@ pub async fn claim_due_workflow_trigger_firings<T: DatabaseImpl>(
     scheduler_id: String,
     limit: i64,
 ) -> Result<Vec<WorkflowRun>, SendableError> {
-    let runs = db
-        .claim_due_workflow_trigger_firings(scheduler_id, Utc::now(), limit)
+    claim_due_workflow_trigger_firings_at(db, scheduler_id, Utc::now(), limit).await
+}
+
+/// claim the workflow triggers due at `now`, which decides both due-ness and blackout windows.
+/// nothing is claimed while scheduling is paused cluster-wide.
+pub async fn claim_due_workflow_trigger_firings_at<T: DatabaseImpl>(
+    db: &T,
+    scheduler_id: String,
+    now: DateTime<Utc>,
+    limit: i64,
+) -> Result<Vec<WorkflowRun>, SendableError> {
+    if super::scheduling_paused(db).await? {
+        return Ok(Vec::new());
+    }
+    record_scheduler_pass(db, now, limit).await?;
+    let mut runs = db
+        .claim_due_workflow_trigger_firings(scheduler_id, now, limit)
         .await?;
-    for run in &runs {
+    // every `url(...)` the claimed firings' gates need is fetched once, concurrently, so a slow
+    // endpoint costs the pass one short timeout rather than one per check.
+    let urls = crate::schedule_gate::check_urls(crate::schedule_gate::gate_urls(
+        runs.iter().filter_map(|run| run.workflow_snapshot.as_ref()),
+    ))
+    .await;
+    let mut enqueued = 0;
+    for run in &mut runs {
+        // a false `only_if`, an upstream workflow that has not succeeded recently, a success of
+        // its own still inside the freshness window, or an earlier run still open on a workflow
+        // that disallows overlap records the firing as a skipped run instead of starting it.
+        let skip = match &run.workflow_snapshot {
+            Some(workflow) => match crate::schedule_gate::skip_reason(workflow, now, &urls) {
+                Some(reason) => Some(reason),
+                None => match crate::schedule_gate::dependency_skip_reason(db, workflow, now)
+                    .await?
+                {
+                    Some(reason) => Some(reason),
+                    None => match crate::schedule_gate::freshness_skip_reason(db, workflow, now)
+                        .await?
+                    {
+                        Some(reason) => Some(reason),
+                        None => {
+                            crate::schedule_gate::overlap_skip_reason(db, workflow, run).await?
+                        }
+                    },
+                },
+            },
+            None => None,
+        };
+        if let Some(reason) = skip {
+            db.update_workflow_run_status(
+                run.id,
+                WorkflowStatus::Canceled,
+                None,
+                None,
+                Some(reason.clone()),
+            )
+            .await?;
+            run.status = WorkflowStatus::Canceled;
+            run.message = Some(reason);
+            continue;
+        }
         support::enqueue_start_ready_node(db, run).await?;
+        enqueued += 1;
     }
+    crate::stability::scheduler_runs_enqueued(enqueued);
     Ok(runs)
 }
 
+// the triggers a claim at `now` is about to evaluate, for the scheduler metrics: how many, how far
+// past their slot each one is, and how many a blackout will defer rather than fire. a trigger
+// without a `next_execution` yet is only scheduled by the claim, so it has no lag to report. like
+// the claim, this reads at most `limit` triggers and loads holiday calendars only when one of them
+// references a calendar.
+async fn record_scheduler_pass<T: DatabaseImpl>(
+    db: &T,
+    now: DateTime<Utc>,
+    limit: i64,
+) -> Result<(), SendableError> {
+    let due = db.fetch_due_workflow_triggers(now, Some(limit)).await?;
+    if due.is_empty() {
+        return Ok(());
+    }
+    let calendars = if due
+        .iter()
+        .any(|trigger| references_calendar(&trigger.configuration))
+    {
+        db.fetch_blackout_calendars().await?
+    } else {
+        Vec::new()
+    };
+    let mut deferred = 0;
+    for trigger in &due {
+        let Some(next_execution) = trigger.next_execution else {
+            continue;
+        };
+        crate::stability::record_schedule_lag_ms((now - next_execution).num_milliseconds() as f64);
+        if trigger_deferred_until(trigger, &calendars, now).is_some() {
+            deferred += 1;
+        }
+    }
+    crate::stability::scheduler_pass(due.len() as u64, deferred);
+    Ok(())
+}
+
+/// recompute every enabled cron trigger's `next_execution` (workflow and pipeline) against now,
+/// repairing drift left by db edits or schedule-semantics changes. `dry_run` only reports.
+pub async fn reconcile_trigger_schedules<T: DatabaseImpl>(
+    db: &T,
+    dry_run: bool,
+) -> Result<ScheduleReconcileReport, SendableError> {
+    db.reconcile_trigger_schedules(Utc::now(), dry_run).await
+}
+
+/// shift (or re-spread) the cron triggers picked by `request`. triggers whose cron cannot be
+/// rewritten are reported and left alone, as are triggers on protected workflows, which are marked
+/// `protected` for the caller to hold for approval. the rest are rewritten together, with their
+/// `next_execution` recomputed, in one transaction.
+pub async fn shift_trigger_schedules<T: DatabaseImpl>(
+    db: &T,
+    request: &ScheduleShiftRequest,
+) -> Result<ScheduleShiftReport, SendableError> {
+    let workflows = db.fetch_workflows().await?;
+    let names = workflows
+        .iter()
+        .filter_map(|workflow| Some((workflow.id?, workflow.name.clone())))
+        .collect::<HashMap<_, _>>();
+
+    let mut selected: Vec<WorkflowTrigger> = Vec::new();
+    for workflow in &workflows {
+        let Some(workflow_id) = workflow.id else {
+            continue;
+        };
+        let in_namespace = request.namespace.is_some() && workflow.namespace == request.namespace;
+        if in_namespace || request.workflow_ids.contains(&workflow_id) {
+            selected.extend(db.fetch_workflow_triggers(workflow_id).await?);
+        }
+    }
+    for trigger_id in &request.trigger_ids {
+        if selected
+            .iter()
+            .any(|trigger| trigger.id == Some(*trigger_id))
+        {
+            continue;
+        }
+        if let Some(trigger) = db.fetch_workflow_trigger(*trigger_id).await? {
+            selected.push(trigger);
+        }
+    }
+
+    let mut triggers = selected
+        .into_iter()
+        .filter(|trigger| trigger.kind == WorkflowTriggerKind::Cron)
+        .filter_map(|trigger| {
+            let cron = trigger.configuration.get("cron")?.as_str()?.to_string();
+            let name = names.get(&trigger.workflow_id).cloned().unwrap_or_default();
+            Some((name, trigger.id?, trigger.workflow_id, cron))
+        })
+        .collect::<Vec<_>>();
+    triggers.sort_by(|a, b| (&a.0, a.1).cmp(&(&b.0, b.1)));
+
+    let mut protected = HashSet::new();
+    for (_, _, workflow_id, _) in &triggers {
+        if !protected.contains(workflow_id)
+            && db.fetch_workflow_protection(*workflow_id).await?.is_some()
+        {
+            protected.insert(*workflow_id);
+        }
+    }
+
+    let crons = triggers
+        .iter()
+        .map(|(_, _, _, cron)| cron.clone())
+        .collect::<Vec<_>>();
+    let mut changes = triggers
+        .into_iter()
+        .zip(request.mode.apply(&crons))
+        .map(
+            |((workflow_name, trigger_id, workflow_id, old_cron), shifted)| {
+                let (new_cron, error) = match shifted {
+                    Ok(cron) => (Some(cron), None),
+                    Err(err) => (None, Some(err)),
+                };
+                ScheduleShiftChange {
+                    trigger_id,
+                    workflow_id,
+                    workflow_name,
+                    old_cron,
+                    new_cron,
+                    next_execution: None,
+                    error,
+                    protected: protected.contains(&workflow_id),
+                    pending_change_id: None,
+                }
+            },
+        )
+        .collect::<Vec<_>>();
+
+    let rewrites = changes
+        .iter()
+        .filter(|change| !change.protected)
+        .filter_map(|change| Some((change.trigger_id, change.new_cron.clone()?)))
+        .collect::<Vec<_>>();
+    let shifted = rewrites.len();
+    let scheduled = db
+        .set_workflow_trigger_crons(rewrites, Utc::now(), request.dry_run)
+        .await?
+        .into_iter()
+        .collect::<HashMap<_, _>>();
+    for change in &mut changes {
+        change.next_execution = scheduled.get(&change.trigger_id).copied();
+    }
+    Ok(ScheduleShiftReport {
+        dry_run: request.dry_run,
+        shifted,
+        changes,
+    })
+}
+
 pub async fn delete_workflow_trigger<T: DatabaseImpl>(
     db: &T,
     trigger_id: Uuid,
@@ -99,7 +510,81 @@ pub async fn create_workflow_run_for_trigger<T: DatabaseImpl>(
     Ok(run)
 }
 
-fn trigger_state(trigger: &WorkflowTrigger) -> Value {
+/// start a run of a `file_arrival` trigger's workflow for the files that settled in its directory.
+/// the run parameters are the trigger's configured `parameters` with a `files` list of paths added.
+pub async fn create_workflow_run_for_file_arrival<T: DatabaseImpl>(
+    db: &T,
+    trigger: &WorkflowTrigger,
+    parameters: Value,
+    files: Vec<String>,
+) -> Result<WorkflowRun, SendableError> {
+    let workflow_snapshot = support::fetch_workflow_snapshot(db, trigger.workflow_id).await?;
+    let mut parameters = match parameters {
+        Value::Object(object) => object,
+        _ => Default::default(),
+    };
+    parameters.insert(
+        "files".into(),
+        Value::Array(files.iter().cloned().map(Value::from).collect()),
+    );
+    let run = db
+        .create_workflow_run(
+            trigger.workflow_id,
+            workflow_snapshot,
+            Value::Object(parameters),
+            trigger_state(trigger),
+            None,
+            runinator_models::replicas::WorkflowRunProvenance {
+                source_kind: Some(runinator_models::replicas::TriggerSourceKind::FileArrival),
+                actor_type: Some(runinator_models::replicas::TriggerActorType::System),
+                actor_replica_id: None,
+                actor_display_name: Some("file_arrival".into()),
+                request_host: None,
+                request_ip: None,
+                metadata: runinator_models::json!({
+                    "trigger_id": trigger.id,
+                    "files": files,
+                }),
+            },
+        )
+        .await?;
+    support::enqueue_start_ready_node(db, &run).await?;
+    Ok(run)
+}
+
+/// start a run of a `webhook` trigger's workflow. `parameters` are the merged run parameters;
+/// `request_host`/`request_ip` describe the caller for the run's provenance.
+pub async fn create_workflow_run_for_webhook<T: DatabaseImpl>(
+    db: &T,
+    trigger: &WorkflowTrigger,
+    parameters: Value,
+    request_host: Option<String>,
+    request_ip: Option<String>,
+) -> Result<WorkflowRun, SendableError> {
+    let workflow_snapshot = support::fetch_workflow_snapshot(db, trigger.workflow_id).await?;
+    let run = db
+        .create_workflow_run(
+            trigger.workflow_id,
+            workflow_snapshot,
+            parameters,
+            trigger_state(trigger),
+            None,
+            runinator_models::replicas::WorkflowRunProvenance {
+                source_kind: Some(runinator_models::replicas::TriggerSourceKind::Webhook),
+                actor_type: Some(runinator_models::replicas::TriggerActorType::System),
+                actor_replica_id: None,
+                actor_display_name: Some("webhook".into()),
+                request_host,
+                request_ip,
+                metadata: runinator_models::json!({ "trigger_id": trigger.id }),
+            },
+        )
+        .await?;
+    support::enqueue_start_ready_node(db, &run).await?;
+    Ok(run)
+}
+
+pub(super) fn trigger_state(trigger: &WorkflowTrigger) -> Value {
     runinator_models::json!({
         "control": { "pause_requested": false },
         "trigger": {
@@ -29,8 +29,9 @@ pub async fn update_run_status<T: DatabaseImpl>(
     status: RunStatus,
     output_json: Option<Value>,
     message: Option<String>,
+    worker_id: Option<String>,
 ) -> Result<TaskResponse, SendableError> {
-    db.update_run_status(run_id, status, output_json, message)
+    db.update_run_status(run_id, status, output_json, message, worker_id)
         .await?;
     Ok(TaskResponse {
         success: true,
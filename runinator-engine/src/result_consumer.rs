@@ -12,7 +12,7 @@ use uuid::Uuid;
 use crate::{
     audit::persist_dead_letter,
     events::{EventSender, emit_workflow_node_run},
-    repository, stability,
+    queue_wait, repository, stability,
 };
 
 const RESULT_CONSUMER_ID: &str = "runinator-ws-results";
@@ -25,6 +25,8 @@ pub struct ResultConsumerPolicy {
     max_attempts: u32,
     retry_backoff: Duration,
     max_backoff: Duration,
+    /// alert when a node waited in the broker longer than this; `None` disables alerting.
+    queue_wait_alert: Option<Duration>,
 }
 
 impl Default for ResultConsumerPolicy {
@@ -33,6 +35,7 @@ impl Default for ResultConsumerPolicy {
             max_attempts: DEFAULT_MAX_RESULT_ATTEMPTS,
             retry_backoff: DEFAULT_RESULT_RETRY_BACKOFF,
             max_backoff: DEFAULT_RESULT_MAX_BACKOFF,
+            queue_wait_alert: queue_wait::alert_threshold(),
         }
     }
 }
@@ -45,9 +48,16 @@ impl ResultConsumerPolicy {
             max_attempts,
             retry_backoff,
             max_backoff: DEFAULT_RESULT_MAX_BACKOFF,
+            queue_wait_alert: queue_wait::alert_threshold(),
         }
     }
 
+    /// override the queue wait alert threshold taken from the environment.
+    pub fn with_queue_wait_alert(mut self, threshold: Option<Duration>) -> Self {
+        self.queue_wait_alert = threshold;
+        self
+    }
+
     /// compute the delay before the next retry with exponential backoff and full jitter.
     ///
     /// the base doubles per attempt (`base * 2^(attempt-1)`), is capped at `max_backoff`, then a
@@ -148,6 +158,15 @@ pub async fn run_result_consumer_with_policy<T: DatabaseImpl>(
                 Ok(applied) => {
                     stability::result_event_applied(applied);
                     attempts.remove(&delivery.event.event_id);
+                    if applied {
+                        queue_wait::observe(
+                            db.as_ref(),
+                            &events,
+                            &delivery.event,
+                            policy.queue_wait_alert,
+                        )
+                        .await;
+                    }
                     emit_workflow_node_run(db.as_ref(), &events, node_run_id).await;
                     // applying a result often enqueues the next ready node for drive.
                     events.nudge_wake_publisher();
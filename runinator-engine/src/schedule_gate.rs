@@ -0,0 +1,609 @@
+//! conditional scheduling. a workflow may carry an `only_if` expression in its definition metadata;
+//! the trigger loop evaluates it right before enqueueing a cron firing and, when it is false, records
+//! the run as skipped instead of starting it. externally gated jobs ("only once the upstream file
+//! has landed") then stop being modeled as failing retries.
+//!
+//! the language is deliberately tiny: `&&`, `||`, `!`, parentheses, `true`/`false`, and a fixed set
+//! of calls, all judged in utc at the firing time:
+//!
+//! - `weekday()` / `weekend()`
+//! - `holiday('US')`: a us federal holiday, on its observed date
+//! - `hour_between(9, 17)`: the hour is in `[9, 17)`
+//! - `url('https://…')`: a GET answers 2xx with the body `true`. only hosts listed in
+//!   `ONLY_IF_URL_ALLOW_ENV` are fetched; any other url counts as false. a pass fetches every url
+//!   its firings need at once, before judging any of them.
+//!
+//! a workflow may also list upstream workflow ids under `depends_on`; a cron firing then only
+//! starts once every one of them has a run that succeeded within `depends_within_seconds`.
+//!
+//! `allow_concurrent: false` keeps a cron firing from starting while an earlier run of the same
+//! workflow is still open, so a slow run skips the next slot instead of stacking another behind it.
+//!
+//! `freshness_seconds` skips a cron firing while the workflow's own last success is younger than the
+//! window, so a refresher fired by several cron entries runs only once its result has gone stale.
+
+use std::{collections::HashMap, sync::OnceLock, time::Duration};
+
+use chrono::{DateTime, Datelike, NaiveDate, Timelike, Utc, Weekday};
+use runinator_database::interfaces::DatabaseImpl;
+use runinator_models::errors::SendableError;
+use runinator_models::workflows::{
+    WORKFLOW_ALLOW_CONCURRENT_KEY, WORKFLOW_DEPENDS_ON_KEY, WORKFLOW_FRESHNESS_SECONDS_KEY,
+    WORKFLOW_ONLY_IF_KEY, WorkflowDefinition, WorkflowDependencies, WorkflowRun,
+    workflow_allows_concurrent, workflow_freshness_seconds,
+};
+use tokio::task::JoinSet;
+
+/// environment variable listing the hosts a `url(...)` check may reach, comma-separated. unset or
+/// blank allows none, so every `url(...)` counts as false.
+pub const ONLY_IF_URL_ALLOW_ENV: &str = "RUNINATOR_ONLY_IF_URL_ALLOW";
+
+/// how long a `url(...)` check may take before it counts as false. a pass runs its checks together,
+/// so this is also the longest they hold up its firings.
+const URL_CHECK_TIMEOUT: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GateExpression {
+    Literal(bool),
+    Not(Box<GateExpression>),
+    And(Box<GateExpression>, Box<GateExpression>),
+    Or(Box<GateExpression>, Box<GateExpression>),
+    Call(GateCall),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GateCall {
+    Weekday,
+    Weekend,
+    Holiday(String),
+    HourBetween(u32, u32),
+    Url(String),
+}
+
+/// a workflow's `only_if` source, if it has one.
+pub fn only_if(workflow: &WorkflowDefinition) -> Option<&str> {
+    workflow
+        .definition
+        .metadata
+        .get(WORKFLOW_ONLY_IF_KEY)
+        .and_then(|value| value.as_str())
+}
+
+/// reject a malformed `only_if` when a workflow is saved rather than when it next fires.
+pub fn validate_only_if(workflow: &WorkflowDefinition) -> Result<(), String> {
+    match workflow.definition.metadata.get(WORKFLOW_ONLY_IF_KEY) {
+        None => Ok(()),
+        Some(value) => match value.as_str() {
+            Some(source) => GateExpression::parse(source).map(|_| ()),
+            None => Err(format!("{WORKFLOW_ONLY_IF_KEY} must be a string")),
+        },
+    }
+}
+
+/// every distinct url the `only_if` expressions of `workflows` check.
+pub fn gate_urls<'a>(workflows: impl IntoIterator<Item = &'a WorkflowDefinition>) -> Vec<String> {
+    let mut urls = Vec::new();
+    for workflow in workflows {
+        let Some(Ok(expression)) = only_if(workflow).map(GateExpression::parse) else {
+            continue;
+        };
+        for url in expression.urls() {
+            if !urls.iter().any(|seen| seen == url) {
+                urls.push(url.to_string());
+            }
+        }
+    }
+    urls
+}
+
+/// fetch every allowed url at once and report which passed; a url whose host is not in
+/// [`ONLY_IF_URL_ALLOW_ENV`] is not fetched and counts as false.
+pub async fn check_urls(urls: Vec<String>) -> HashMap<String, bool> {
+    let allowed = url_allow_list(std::env::var(ONLY_IF_URL_ALLOW_ENV).ok().as_deref());
+    let mut results = HashMap::new();
+    let mut checks = JoinSet::new();
+    for url in urls {
+        if url_host_allowed(&allowed, &url) {
+            checks.spawn(async move {
+                let passed = check_url(&url).await;
+                (url, passed)
+            });
+        } else {
+            log::warn!(
+                "only_if url check {url} refused: its host is not in {ONLY_IF_URL_ALLOW_ENV}"
+            );
+            results.insert(url, false);
+        }
+    }
+    while let Some(checked) = checks.join_next().await {
+        if let Ok((url, passed)) = checked {
+            results.insert(url, passed);
+        }
+    }
+    results
+}
+
+/// the lowercased hosts in a comma-separated [`ONLY_IF_URL_ALLOW_ENV`] value.
+fn url_allow_list(raw: Option<&str>) -> Vec<String> {
+    raw.unwrap_or_default()
+        .split(',')
+        .map(|host| host.trim().trim_end_matches('.').to_ascii_lowercase())
+        .filter(|host| !host.is_empty())
+        .collect()
+}
+
+/// whether `url`'s host is exactly one of `allowed`.
+fn url_host_allowed(allowed: &[String], url: &str) -> bool {
+    reqwest::Url::parse(url)
+        .ok()
+        .and_then(|url| {
+            url.host_str()
+                .map(|host| host.trim_end_matches('.').to_ascii_lowercase())
+        })
+        .is_some_and(|host| allowed.contains(&host))
+}
+
+/// why a cron firing of `workflow` at `now` should be skipped, or `None` to start it, taking each
+/// `url(...)` result from `urls` (see [`check_urls`]). an expression that no longer parses skips
+/// too, with the parse error as the reason.
+pub fn skip_reason(
+    workflow: &WorkflowDefinition,
+    now: DateTime<Utc>,
+    urls: &HashMap<String, bool>,
+) -> Option<String> {
+    let source = only_if(workflow)?;
+    let expression = match GateExpression::parse(source) {
+        Ok(expression) => expression,
+        Err(err) => return Some(format!("Skipped: {WORKFLOW_ONLY_IF_KEY} is invalid: {err}")),
+    };
+    if expression.evaluate(now, urls) {
+        None
+    } else {
+        Some(format!(
+            "Skipped: {WORKFLOW_ONLY_IF_KEY} `{source}` was false"
+        ))
+    }
+}
+
+/// reject a malformed `depends_on` (or one naming the workflow itself) when a workflow is saved.
+pub fn validate_depends_on(workflow: &WorkflowDefinition) -> Result<(), String> {
+    let Some(dependencies) = WorkflowDependencies::from_metadata(&workflow.definition.metadata)?
+    else {
+        return Ok(());
+    };
+    if workflow
+        .id
+        .is_some_and(|id| dependencies.upstream.contains(&id))
+    {
+        return Err(format!(
+            "{WORKFLOW_DEPENDS_ON_KEY} cannot name the workflow itself"
+        ));
+    }
+    Ok(())
+}
+
+/// why a cron firing of `workflow` at `now` should wait on its upstream workflows, or `None` when
+/// each of them succeeded within the window. a `depends_on` that no longer reads skips too.
+pub async fn dependency_skip_reason<T: DatabaseImpl>(
+    db: &T,
+    workflow: &WorkflowDefinition,
+    now: DateTime<Utc>,
+) -> Result<Option<String>, SendableError> {
+    let dependencies = match WorkflowDependencies::from_metadata(&workflow.definition.metadata) {
+        Ok(Some(dependencies)) => dependencies,
+        Ok(None) => return Ok(None),
+        Err(err) => {
+            return Ok(Some(format!(
+                "Skipped: {WORKFLOW_DEPENDS_ON_KEY} is invalid: {err}"
+            )));
+        }
+    };
+    let since = now - chrono::Duration::seconds(dependencies.within_seconds);
+    let mut waiting = Vec::new();
+    for upstream in &dependencies.upstream {
+        match db.fetch_last_workflow_success(*upstream).await? {
+            Some(finished_at) if finished_at >= since => {}
+            _ => waiting.push(upstream.to_string()),
+        }
+    }
+    if waiting.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(format!(
+        "Skipped: upstream workflow(s) {} have not succeeded in the last {}s",
+        waiting.join(", "),
+        dependencies.within_seconds
+    )))
+}
+
+/// why a cron firing of `workflow` at `now` is unnecessary because the workflow last succeeded
+/// within its freshness window, or `None` when it has no window or its last success is older. a
+/// `freshness_seconds` that no longer reads is treated as absent, so the firing still runs.
+pub async fn freshness_skip_reason<T: DatabaseImpl>(
+    db: &T,
+    workflow: &WorkflowDefinition,
+    now: DateTime<Utc>,
+) -> Result<Option<String>, SendableError> {
+    let (Some(workflow_id), Ok(Some(freshness_seconds))) = (
+        workflow.id,
+        workflow_freshness_seconds(&workflow.definition.metadata),
+    ) else {
+        return Ok(None);
+    };
+    let Some(finished_at) = db.fetch_last_workflow_success(workflow_id).await? else {
+        return Ok(None);
+    };
+    let age = (now - finished_at).num_seconds().max(0);
+    if age >= freshness_seconds {
+        return Ok(None);
+    }
+    Ok(Some(format!(
+        "Skipped: last succeeded {age}s ago, within {WORKFLOW_FRESHNESS_SECONDS_KEY} of {freshness_seconds}s"
+    )))
+}
+
+/// why a cron firing's `run` should not start alongside earlier open runs of its workflow, or `None`
+/// when the workflow allows overlap or nothing earlier is still open. an `allow_concurrent` that no
+/// longer reads is treated as allowing overlap, the behaviour without the key.
+pub async fn overlap_skip_reason<T: DatabaseImpl>(
+    db: &T,
+    workflow: &WorkflowDefinition,
+    run: &WorkflowRun,
+) -> Result<Option<String>, SendableError> {
+    if workflow_allows_concurrent(&workflow.definition.metadata).unwrap_or(true) {
+        return Ok(None);
+    }
+    let open = db.count_earlier_open_workflow_runs(run).await?;
+    if open == 0 {
+        return Ok(None);
+    }
+    Ok(Some(format!(
+        "Skipped: {WORKFLOW_ALLOW_CONCURRENT_KEY} is false and {open} earlier run(s) are still open"
+    )))
+}
+
+async fn check_url(url: &str) -> bool {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    let client = CLIENT.get_or_init(|| {
+        // no redirects: following one could leave the allowed hosts.
+        reqwest::Client::builder()
+            .timeout(URL_CHECK_TIMEOUT)
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .unwrap_or_default()
+    });
+    let response = match client.get(url).send().await {
+        Ok(response) if response.status().is_success() => response,
+        Ok(response) => {
+            log::info!("only_if url check {url} answered {}", response.status());
+            return false;
+        }
+        Err(err) => {
+            log::warn!("only_if url check {url} failed: {err}");
+            return false;
+        }
+    };
+    response
+        .text()
+        .await
+        .is_ok_and(|body| body.trim().eq_ignore_ascii_case("true"))
+}
+
+impl GateExpression {
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let expression = parser.or()?;
+        match parser.tokens.get(parser.pos) {
+            None => Ok(expression),
+            Some(token) => Err(format!("unexpected {}", token.describe())),
+        }
+    }
+
+    /// every url the expression checks, so they can be fetched before evaluating.
+    pub fn urls(&self) -> Vec<&str> {
+        let mut urls = Vec::new();
+        self.collect_urls(&mut urls);
+        urls
+    }
+
+    fn collect_urls<'a>(&'a self, urls: &mut Vec<&'a str>) {
+        match self {
+            GateExpression::Literal(_) => {}
+            GateExpression::Not(inner) => inner.collect_urls(urls),
+            GateExpression::And(left, right) | GateExpression::Or(left, right) => {
+                left.collect_urls(urls);
+                right.collect_urls(urls);
+            }
+            GateExpression::Call(GateCall::Url(url)) => {
+                if !urls.contains(&url.as_str()) {
+                    urls.push(url);
+                }
+            }
+            GateExpression::Call(_) => {}
+        }
+    }
+
+    /// evaluate at `now`, taking each `url(...)` result from `urls` (missing = false).
+    pub fn evaluate(&self, now: DateTime<Utc>, urls: &HashMap<String, bool>) -> bool {
+        match self {
+            GateExpression::Literal(value) => *value,
+            GateExpression::Not(inner) => !inner.evaluate(now, urls),
+            GateExpression::And(left, right) => {
+                left.evaluate(now, urls) && right.evaluate(now, urls)
+            }
+            GateExpression::Or(left, right) => {
+                left.evaluate(now, urls) || right.evaluate(now, urls)
+            }
+            GateExpression::Call(call) => match call {
+                GateCall::Weekday => !is_weekend(now.date_naive()),
+                GateCall::Weekend => is_weekend(now.date_naive()),
+                GateCall::Holiday(calendar) => is_holiday(calendar, now.date_naive()),
+                GateCall::HourBetween(start, end) => (*start..*end).contains(&now.hour()),
+                GateCall::Url(url) => urls.get(url).copied().unwrap_or(false),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Int(u32),
+    And,
+    Or,
+    Not,
+    Open,
+    Close,
+    Comma,
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Token::Ident(name) => format!("`{name}`"),
+            Token::Str(value) => format!("string '{value}'"),
+            Token::Int(value) => format!("number {value}"),
+            Token::And => "`&&`".into(),
+            Token::Or => "`||`".into(),
+            Token::Not => "`!`".into(),
+            Token::Open => "`(`".into(),
+            Token::Close => "`)`".into(),
+            Token::Comma => "`,`".into(),
+        }
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = source.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' | ')' | ',' | '!' => {
+                chars.next();
+                tokens.push(match c {
+                    '(' => Token::Open,
+                    ')' => Token::Close,
+                    ',' => Token::Comma,
+                    _ => Token::Not,
+                });
+            }
+            '&' | '|' => {
+                chars.next();
+                if chars.next() != Some(c) {
+                    return Err(format!("expected `{c}{c}`"));
+                }
+                tokens.push(if c == '&' { Token::And } else { Token::Or });
+            }
+            '\'' | '"' => {
+                chars.next();
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some(end) if end == c => break,
+                        Some(ch) => value.push(ch),
+                        None => return Err("unterminated string".into()),
+                    }
+                }
+                tokens.push(Token::Str(value));
+            }
+            c if c.is_ascii_digit() => {
+                let mut digits = String::new();
+                while let Some(&d) = chars.peek().filter(|d| d.is_ascii_digit()) {
+                    digits.push(d);
+                    chars.next();
+                }
+                let value = digits
+                    .parse()
+                    .map_err(|_| format!("number {digits} is too large"))?;
+                tokens.push(Token::Int(value));
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let mut name = String::new();
+                while let Some(&ch) = chars
+                    .peek()
+                    .filter(|ch| ch.is_ascii_alphanumeric() || **ch == '_')
+                {
+                    name.push(ch);
+                    chars.next();
+                }
+                tokens.push(Token::Ident(name));
+            }
+            other => return Err(format!("unexpected character `{other}`")),
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.tokens.get(self.pos) == Some(token) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: Token) -> Result<(), String> {
+        match self.next() {
+            Some(found) if found == token => Ok(()),
+            Some(found) => Err(format!(
+                "expected {}, found {}",
+                token.describe(),
+                found.describe()
+            )),
+            None => Err(format!("expected {}, found end of input", token.describe())),
+        }
+    }
+
+    fn or(&mut self) -> Result<GateExpression, String> {
+        let mut left = self.and()?;
+        while self.eat(&Token::Or) {
+            left = GateExpression::Or(Box::new(left), Box::new(self.and()?));
+        }
+        Ok(left)
+    }
+
+    fn and(&mut self) -> Result<GateExpression, String> {
+        let mut left = self.unary()?;
+        while self.eat(&Token::And) {
+            left = GateExpression::And(Box::new(left), Box::new(self.unary()?));
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<GateExpression, String> {
+        if self.eat(&Token::Not) {
+            return Ok(GateExpression::Not(Box::new(self.unary()?)));
+        }
+        match self.next() {
+            Some(Token::Open) => {
+                let inner = self.or()?;
+                self.expect(Token::Close)?;
+                Ok(inner)
+            }
+            Some(Token::Ident(name)) if name == "true" => Ok(GateExpression::Literal(true)),
+            Some(Token::Ident(name)) if name == "false" => Ok(GateExpression::Literal(false)),
+            Some(Token::Ident(name)) => self.call(name).map(GateExpression::Call),
+            Some(token) => Err(format!("unexpected {}", token.describe())),
+            None => Err("unexpected end of input".into()),
+        }
+    }
+
+    fn call(&mut self, name: String) -> Result<GateCall, String> {
+        self.expect(Token::Open)?;
+        let mut args = Vec::new();
+        if !self.eat(&Token::Close) {
+            loop {
+                match self.next() {
+                    Some(token @ (Token::Str(_) | Token::Int(_))) => args.push(token),
+                    Some(token) => return Err(format!("unexpected {}", token.describe())),
+                    None => return Err("unexpected end of input".into()),
+                }
+                if self.eat(&Token::Close) {
+                    break;
+                }
+                self.expect(Token::Comma)?;
+            }
+        }
+        match (name.as_str(), args.as_slice()) {
+            ("weekday", []) => Ok(GateCall::Weekday),
+            ("weekend", []) => Ok(GateCall::Weekend),
+            ("holiday", [Token::Str(calendar)]) if calendar.eq_ignore_ascii_case("US") => {
+                Ok(GateCall::Holiday(calendar.to_ascii_uppercase()))
+            }
+            ("holiday", [Token::Str(calendar)]) => Err(format!(
+                "unknown holiday calendar '{calendar}' (supported: 'US')"
+            )),
+            ("hour_between", [Token::Int(start), Token::Int(end)]) => {
+                if *start > 24 || *end > 24 || start >= end {
+                    return Err(format!(
+                        "hour_between({start}, {end}) needs 0 <= start < end <= 24"
+                    ));
+                }
+                Ok(GateCall::HourBetween(*start, *end))
+            }
+            ("url", [Token::Str(url)]) => {
+                if !(url.starts_with("http://") || url.starts_with("https://")) {
+                    return Err(format!("url('{url}') must be an http(s) url"));
+                }
+                Ok(GateCall::Url(url.clone()))
+            }
+            ("weekday" | "weekend" | "holiday" | "hour_between" | "url", _) => {
+                Err(format!("wrong arguments to {name}()"))
+            }
+            _ => Err(format!("unknown function {name}()")),
+        }
+    }
+}
+
+fn is_weekend(date: NaiveDate) -> bool {
+    matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+}
+
+/// the `n`th (1-based) `weekday` of a month.
+fn nth_weekday(year: i32, month: u32, weekday: Weekday, n: u8) -> Option<NaiveDate> {
+    NaiveDate::from_weekday_of_month_opt(year, month, weekday, n)
+}
+
+fn last_weekday(year: i32, month: u32, weekday: Weekday) -> Option<NaiveDate> {
+    nth_weekday(year, month, weekday, 5).or_else(|| nth_weekday(year, month, weekday, 4))
+}
+
+/// a fixed-date holiday moves to friday when it falls on saturday and to monday on sunday.
+fn observed(date: NaiveDate) -> NaiveDate {
+    match date.weekday() {
+        Weekday::Sat => date.pred_opt().unwrap_or(date),
+        Weekday::Sun => date.succ_opt().unwrap_or(date),
+        _ => date,
+    }
+}
+
+fn us_holidays(year: i32) -> Vec<NaiveDate> {
+    let fixed = [(1, 1), (6, 19), (7, 4), (11, 11), (12, 25)]
+        .into_iter()
+        .filter_map(|(month, day)| NaiveDate::from_ymd_opt(year, month, day))
+        .map(observed);
+    let floating = [
+        nth_weekday(year, 1, Weekday::Mon, 3), // martin luther king jr. day
+        nth_weekday(year, 2, Weekday::Mon, 3), // washington's birthday
+        last_weekday(year, 5, Weekday::Mon),   // memorial day
+        nth_weekday(year, 9, Weekday::Mon, 1), // labor day
+        nth_weekday(year, 10, Weekday::Mon, 2), // columbus day
+        nth_weekday(year, 11, Weekday::Thu, 4), // thanksgiving
+    ]
+    .into_iter()
+    .flatten();
+    fixed.chain(floating).collect()
+}
+
+/// `calendar` is validated at parse time; `US` is the only one today.
+fn is_holiday(calendar: &str, date: NaiveDate) -> bool {
+    match calendar {
+        // new year's day on a saturday is observed on december 31st of the year before.
+        "US" => {
+            us_holidays(date.year()).contains(&date) || us_holidays(date.year() + 1).contains(&date)
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+#[path = "schedule_gate_tests.rs"]
+mod tests;
@@ -0,0 +1,161 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, TimeZone, Utc};
+
+use super::{GateExpression, url_allow_list, url_host_allowed};
+
+fn at(year: i32, month: u32, day: u32, hour: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(year, month, day, hour, 0, 0).unwrap()
+}
+
+fn eval(source: &str, now: DateTime<Utc>) -> bool {
+    GateExpression::parse(source)
+        .unwrap()
+        .evaluate(now, &HashMap::new())
+}
+
+#[test]
+fn calendar_calls_follow_the_firing_time() {
+    // 2026-07-03 is a friday: independence day (a saturday) is observed on it.
+    let observed_holiday = at(2026, 7, 3, 10);
+    assert!(eval("weekday()", observed_holiday));
+    assert!(eval("holiday('US')", observed_holiday));
+    assert!(!eval("weekday() && !holiday('US')", observed_holiday));
+    // thanksgiving 2026 is the fourth thursday, november 26th; the day after is a normal friday.
+    assert!(eval("holiday(\"us\")", at(2026, 11, 26, 10)));
+    assert!(eval("weekday() && !holiday('US')", at(2026, 11, 27, 10)));
+    assert!(eval(
+        "weekend() || hour_between(9, 17)",
+        at(2026, 11, 28, 3)
+    ));
+    assert!(!eval("hour_between(9, 17)", at(2026, 11, 27, 17)));
+}
+
+#[test]
+fn url_results_are_looked_up_and_default_to_false() {
+    let expression = GateExpression::parse("url('https://example.test/ready') && true").unwrap();
+    assert_eq!(expression.urls(), vec!["https://example.test/ready"]);
+    let now = at(2026, 1, 5, 12);
+    assert!(!expression.evaluate(now, &HashMap::new()));
+    let ready = HashMap::from([("https://example.test/ready".to_string(), true)]);
+    assert!(expression.evaluate(now, &ready));
+}
+
+#[test]
+fn url_checks_only_reach_allow_listed_hosts() {
+    let allowed = url_allow_list(Some(" Status.Example.test., ,files.example.test"));
+    assert_eq!(allowed, vec!["status.example.test", "files.example.test"]);
+    assert!(url_host_allowed(
+        &allowed,
+        "https://status.example.test/ready"
+    ));
+    assert!(url_host_allowed(
+        &allowed,
+        "http://FILES.example.test:8080/landed"
+    ));
+    assert!(!url_host_allowed(
+        &allowed,
+        "https://status.example.test.evil.test/ready"
+    ));
+    assert!(!url_host_allowed(
+        &allowed,
+        "http://169.254.169.254/latest/meta-data"
+    ));
+    assert!(!url_host_allowed(
+        &url_allow_list(None),
+        "https://status.example.test/ready"
+    ));
+}
+
+#[test]
+fn malformed_expressions_are_rejected() {
+    for source in [
+        "",
+        "weekday(",
+        "weekday() &",
+        "holiday('FR')",
+        "hour_between(17, 9)",
+        "url('ftp://example.test')",
+        "sometimes()",
+        "weekday() weekend()",
+    ] {
+        assert!(GateExpression::parse(source).is_err(), "{source} parsed");
+    }
+}
+
+#[test]
+fn depends_on_reads_upstream_ids_and_window() {
+    use runinator_models::workflows::{DEFAULT_DEPENDS_WITHIN_SECS, WorkflowDependencies};
+
+    let upstream = uuid::Uuid::new_v4();
+    let read = |metadata| WorkflowDependencies::from_metadata(&metadata);
+    assert_eq!(read(runinator_models::json!({})), Ok(None));
+    assert_eq!(
+        read(runinator_models::json!({ "depends_on": [] })),
+        Ok(None)
+    );
+    assert_eq!(
+        read(runinator_models::json!({ "depends_on": [upstream.to_string()] })),
+        Ok(Some(WorkflowDependencies {
+            upstream: vec![upstream],
+            within_seconds: DEFAULT_DEPENDS_WITHIN_SECS,
+        }))
+    );
+    assert_eq!(
+        read(runinator_models::json!({
+            "depends_on": [upstream.to_string()],
+            "depends_within_seconds": 3600,
+        }))
+        .unwrap()
+        .unwrap()
+        .within_seconds,
+        3600
+    );
+    assert!(read(runinator_models::json!({ "depends_on": "etl-extract" })).is_err());
+    assert!(read(runinator_models::json!({ "depends_on": ["not-a-uuid"] })).is_err());
+    assert!(
+        read(runinator_models::json!({
+            "depends_on": [upstream.to_string()],
+            "depends_within_seconds": 0,
+        }))
+        .is_err()
+    );
+}
+
+#[test]
+fn allow_concurrent_defaults_to_true_and_must_be_a_bool() {
+    use runinator_models::workflows::workflow_allows_concurrent;
+
+    assert_eq!(
+        workflow_allows_concurrent(&runinator_models::json!({})),
+        Ok(true)
+    );
+    assert_eq!(
+        workflow_allows_concurrent(&runinator_models::json!({ "allow_concurrent": false })),
+        Ok(false)
+    );
+    assert!(
+        workflow_allows_concurrent(&runinator_models::json!({ "allow_concurrent": "no" })).is_err()
+    );
+}
+
+#[test]
+fn freshness_seconds_is_optional_and_must_be_positive() {
+    use runinator_models::workflows::workflow_freshness_seconds;
+
+    assert_eq!(
+        workflow_freshness_seconds(&runinator_models::json!({})),
+        Ok(None)
+    );
+    assert_eq!(
+        workflow_freshness_seconds(&runinator_models::json!({ "freshness_seconds": 900 })),
+        Ok(Some(900))
+    );
+    assert!(
+        workflow_freshness_seconds(&runinator_models::json!({ "freshness_seconds": 0 })).is_err()
+    );
+    assert!(
+        workflow_freshness_seconds(&runinator_models::json!({ "freshness_seconds": "15m" }))
+            .is_err()
+    );
+}
@@ -2,7 +2,10 @@ use std::sync::OnceLock;
 use std::sync::atomic::{AtomicU64, Ordering};
 
 use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
-use opentelemetry::metrics::{Counter, Histogram};
+use opentelemetry::KeyValue;
+use opentelemetry::metrics::{Counter, Gauge, Histogram};
+use runinator_database::pool::PoolStatus;
+use runinator_models::cluster::ClusterPause;
 use serde::Serialize;
 use utoipa::ToSchema;
 
@@ -24,7 +27,26 @@ const METRIC_INGRESS_APPLIED: &str = "runinator_ws_ingress_applied_total";
 const METRIC_INGRESS_RETRIED: &str = "runinator_ws_ingress_retried_total";
 const METRIC_INGRESS_DEAD_LETTERED: &str = "runinator_ws_ingress_dead_lettered_total";
 const METRIC_TRIGGERS_FIRED: &str = "runinator_ws_triggers_fired_total";
+const METRIC_SCHEDULER_TRIGGERS_EVALUATED: &str = "runinator_ws_scheduler_triggers_evaluated_total";
+const METRIC_SCHEDULER_TRIGGERS_DEFERRED: &str = "runinator_ws_scheduler_triggers_deferred_total";
+const METRIC_SCHEDULER_RUNS_ENQUEUED: &str = "runinator_ws_scheduler_runs_enqueued_total";
+const METRIC_SCHEDULE_LAG_MS: &str = "runinator_ws_schedule_lag_ms";
+const METRIC_DISPATCH_PUBLISH_MS: &str = "runinator_ws_dispatch_publish_ms";
 const METRIC_REDUCER_DRIVE_MS: &str = "runinator_ws_reducer_drive_ms";
+const METRIC_QUEUE_WAIT_MS: &str = "runinator_ws_queue_wait_ms";
+const METRIC_QUEUE_WAIT_ALERTS: &str = "runinator_ws_queue_wait_alerts_total";
+const METRIC_MISSED_RUN_ALERTS: &str = "runinator_ws_missed_run_alerts_total";
+const METRIC_ENQUEUE_DEAD_LETTERS: &str = "runinator_ws_enqueue_dead_letters_total";
+const METRIC_HTTP_REQUESTS: &str = "runinator_ws_http_requests_total";
+const METRIC_HTTP_REQUEST_MS: &str = "runinator_ws_http_request_ms";
+const METRIC_DISPATCH_PAUSED: &str = "runinator_ws_dispatch_paused";
+const METRIC_EXECUTION_PAUSED: &str = "runinator_ws_execution_paused";
+const METRIC_SCHEDULING_PAUSED: &str = "runinator_ws_scheduling_paused";
+const METRIC_SCHEDULER_LEADER: &str = "runinator_ws_scheduler_leader";
+const METRIC_DB_POOL_CONNECTIONS: &str = "runinator_ws_db_pool_connections";
+const METRIC_DB_POOL_IDLE: &str = "runinator_ws_db_pool_idle";
+const METRIC_DB_POOL_IN_USE: &str = "runinator_ws_db_pool_in_use";
+const METRIC_DB_POOL_MAX: &str = "runinator_ws_db_pool_max_connections";
 
 static PROMETHEUS: OnceLock<PrometheusHandle> = OnceLock::new();
 
@@ -43,7 +65,26 @@ struct OtelCounters {
     ingress_retried: Counter<u64>,
     ingress_dead_lettered: Counter<u64>,
     triggers_fired: Counter<u64>,
+    scheduler_triggers_evaluated: Counter<u64>,
+    scheduler_triggers_deferred: Counter<u64>,
+    scheduler_runs_enqueued: Counter<u64>,
+    schedule_lag_ms: Histogram<f64>,
+    dispatch_publish_ms: Histogram<f64>,
     reducer_drive_ms: Histogram<f64>,
+    queue_wait_ms: Histogram<f64>,
+    queue_wait_alerts: Counter<u64>,
+    missed_run_alerts: Counter<u64>,
+    enqueue_dead_letters: Counter<u64>,
+    http_requests: Counter<u64>,
+    http_request_ms: Histogram<f64>,
+    dispatch_paused: Gauge<u64>,
+    execution_paused: Gauge<u64>,
+    scheduling_paused: Gauge<u64>,
+    scheduler_leader: Gauge<u64>,
+    db_pool_connections: Gauge<u64>,
+    db_pool_idle: Gauge<u64>,
+    db_pool_in_use: Gauge<u64>,
+    db_pool_max: Gauge<u64>,
 }
 
 static OTEL_COUNTERS: OnceLock<OtelCounters> = OnceLock::new();
@@ -63,10 +104,45 @@ fn otel_counters() -> &'static OtelCounters {
             ingress_retried: meter.u64_counter(METRIC_INGRESS_RETRIED).build(),
             ingress_dead_lettered: meter.u64_counter(METRIC_INGRESS_DEAD_LETTERED).build(),
             triggers_fired: meter.u64_counter(METRIC_TRIGGERS_FIRED).build(),
+            scheduler_triggers_evaluated: meter
+                .u64_counter(METRIC_SCHEDULER_TRIGGERS_EVALUATED)
+                .build(),
+            scheduler_triggers_deferred: meter
+                .u64_counter(METRIC_SCHEDULER_TRIGGERS_DEFERRED)
+                .build(),
+            scheduler_runs_enqueued: meter.u64_counter(METRIC_SCHEDULER_RUNS_ENQUEUED).build(),
+            schedule_lag_ms: meter
+                .f64_histogram(METRIC_SCHEDULE_LAG_MS)
+                .with_unit("ms")
+                .build(),
+            dispatch_publish_ms: meter
+                .f64_histogram(METRIC_DISPATCH_PUBLISH_MS)
+                .with_unit("ms")
+                .build(),
             reducer_drive_ms: meter
                 .f64_histogram(METRIC_REDUCER_DRIVE_MS)
                 .with_unit("ms")
                 .build(),
+            queue_wait_ms: meter
+                .f64_histogram(METRIC_QUEUE_WAIT_MS)
+                .with_unit("ms")
+                .build(),
+            queue_wait_alerts: meter.u64_counter(METRIC_QUEUE_WAIT_ALERTS).build(),
+            missed_run_alerts: meter.u64_counter(METRIC_MISSED_RUN_ALERTS).build(),
+            enqueue_dead_letters: meter.u64_counter(METRIC_ENQUEUE_DEAD_LETTERS).build(),
+            http_requests: meter.u64_counter(METRIC_HTTP_REQUESTS).build(),
+            http_request_ms: meter
+                .f64_histogram(METRIC_HTTP_REQUEST_MS)
+                .with_unit("ms")
+                .build(),
+            dispatch_paused: meter.u64_gauge(METRIC_DISPATCH_PAUSED).build(),
+            execution_paused: meter.u64_gauge(METRIC_EXECUTION_PAUSED).build(),
+            scheduling_paused: meter.u64_gauge(METRIC_SCHEDULING_PAUSED).build(),
+            scheduler_leader: meter.u64_gauge(METRIC_SCHEDULER_LEADER).build(),
+            db_pool_connections: meter.u64_gauge(METRIC_DB_POOL_CONNECTIONS).build(),
+            db_pool_idle: meter.u64_gauge(METRIC_DB_POOL_IDLE).build(),
+            db_pool_in_use: meter.u64_gauge(METRIC_DB_POOL_IN_USE).build(),
+            db_pool_max: meter.u64_gauge(METRIC_DB_POOL_MAX).build(),
         }
     })
 }
@@ -173,6 +249,42 @@ pub fn triggers_fired(count: u64) {
     otel_counters().triggers_fired.add(count, &[]);
 }
 
+/// one trigger-loop pass evaluated `evaluated` due workflow triggers, of which a blackout deferred
+/// `deferred` instead of letting them fire.
+pub fn scheduler_pass(evaluated: u64, deferred: u64) {
+    metrics::counter!(METRIC_SCHEDULER_TRIGGERS_EVALUATED).increment(evaluated);
+    metrics::counter!(METRIC_SCHEDULER_TRIGGERS_DEFERRED).increment(deferred);
+    otel_counters()
+        .scheduler_triggers_evaluated
+        .add(evaluated, &[]);
+    otel_counters()
+        .scheduler_triggers_deferred
+        .add(deferred, &[]);
+}
+
+/// `count` runs fired by triggers had their start node enqueued; the rest of the fired runs were
+/// skipped by a schedule gate.
+pub fn scheduler_runs_enqueued(count: u64) {
+    if count == 0 {
+        return;
+    }
+    metrics::counter!(METRIC_SCHEDULER_RUNS_ENQUEUED).increment(count);
+    otel_counters().scheduler_runs_enqueued.add(count, &[]);
+}
+
+/// record how far past its `next_execution` a due trigger was when the trigger loop evaluated it,
+/// in milliseconds. a lag that keeps growing means the scheduler is not keeping up.
+pub fn record_schedule_lag_ms(millis: f64) {
+    metrics::histogram!(METRIC_SCHEDULE_LAG_MS).record(millis);
+    otel_counters().schedule_lag_ms.record(millis, &[]);
+}
+
+/// record how long publishing one action dispatch to the broker took, in milliseconds.
+pub fn record_dispatch_publish_ms(millis: f64) {
+    metrics::histogram!(METRIC_DISPATCH_PUBLISH_MS).record(millis);
+    otel_counters().dispatch_publish_ms.record(millis, &[]);
+}
+
 /// record the wall-clock time the reducer spent advancing a run for one ingress drive, in
 /// milliseconds. surfaces reducer latency independent of broker/queue wait.
 pub fn record_reducer_drive_ms(millis: f64) {
@@ -180,6 +292,100 @@ pub fn record_reducer_drive_ms(millis: f64) {
     otel_counters().reducer_drive_ms.record(millis, &[]);
 }
 
+/// record how long a node's command waited in the broker before a worker started it, in
+/// milliseconds, as reported on the worker's `running` status.
+pub fn record_queue_wait_ms(millis: f64) {
+    metrics::histogram!(METRIC_QUEUE_WAIT_MS).record(millis);
+    otel_counters().queue_wait_ms.record(millis, &[]);
+}
+
+/// a node's queue wait exceeded the alert threshold and a warning notification was raised.
+pub fn queue_wait_alert() {
+    metrics::counter!(METRIC_QUEUE_WAIT_ALERTS).increment(1);
+    otel_counters().queue_wait_alerts.add(1, &[]);
+}
+
+/// a workflow went past its expected interval without a success and a missed-run alert was raised.
+pub fn missed_run_alert() {
+    metrics::counter!(METRIC_MISSED_RUN_ALERTS).increment(1);
+    otel_counters().missed_run_alerts.add(1, &[]);
+}
+
+/// an action dispatch kept failing to publish and was dead-lettered.
+pub fn enqueue_dead_letter() {
+    metrics::counter!(METRIC_ENQUEUE_DEAD_LETTERS).increment(1);
+    otel_counters().enqueue_dead_letters.add(1, &[]);
+}
+
+/// one http request answered by the web service, labelled by method, status, and the matched route
+/// template (`/workflows/{id}`, not the raw path, so label cardinality stays bounded).
+pub fn record_http_request(method: &str, route: &str, status: u16, millis: f64) {
+    let status = status.to_string();
+    metrics::counter!(
+        METRIC_HTTP_REQUESTS,
+        "method" => method.to_string(),
+        "route" => route.to_string(),
+        "status" => status.clone()
+    )
+    .increment(1);
+    metrics::histogram!(
+        METRIC_HTTP_REQUEST_MS,
+        "method" => method.to_string(),
+        "route" => route.to_string(),
+        "status" => status.clone()
+    )
+    .record(millis);
+    let attrs = [
+        KeyValue::new("method", method.to_string()),
+        KeyValue::new("route", route.to_string()),
+        KeyValue::new("status", status),
+    ];
+    otel_counters().http_requests.add(1, &attrs);
+    otel_counters().http_request_ms.record(millis, &attrs);
+}
+
+/// the cluster pause as last seen by this replica: 1 while dispatch/execution/scheduling is
+/// paused, else 0.
+pub fn cluster_pause(pause: &ClusterPause) {
+    metrics::gauge!(METRIC_DISPATCH_PAUSED).set(u8::from(pause.dispatch));
+    metrics::gauge!(METRIC_EXECUTION_PAUSED).set(u8::from(pause.execution));
+    metrics::gauge!(METRIC_SCHEDULING_PAUSED).set(u8::from(pause.scheduling));
+    otel_counters()
+        .dispatch_paused
+        .record(u64::from(pause.dispatch), &[]);
+    otel_counters()
+        .execution_paused
+        .record(u64::from(pause.execution), &[]);
+    otel_counters()
+        .scheduling_paused
+        .record(u64::from(pause.scheduling), &[]);
+}
+
+/// 1 while this instance holds the scheduler lease and fires triggers, else 0.
+pub fn scheduler_leader(leading: bool) {
+    metrics::gauge!(METRIC_SCHEDULER_LEADER).set(u8::from(leading));
+    otel_counters()
+        .scheduler_leader
+        .record(u64::from(leading), &[]);
+}
+
+/// the database pool as sampled when metrics are scraped: open, idle, and checked-out connections
+/// against the configured ceiling. `in_use` pinned at `max` means requests are queueing for a
+/// connection.
+pub fn db_pool(status: PoolStatus) {
+    let in_use = status.in_use();
+    metrics::gauge!(METRIC_DB_POOL_CONNECTIONS).set(status.size);
+    metrics::gauge!(METRIC_DB_POOL_IDLE).set(status.idle);
+    metrics::gauge!(METRIC_DB_POOL_IN_USE).set(in_use);
+    metrics::gauge!(METRIC_DB_POOL_MAX).set(status.max_connections);
+    let otel = otel_counters();
+    otel.db_pool_connections.record(u64::from(status.size), &[]);
+    otel.db_pool_idle.record(u64::from(status.idle), &[]);
+    otel.db_pool_in_use.record(u64::from(in_use), &[]);
+    otel.db_pool_max
+        .record(u64::from(status.max_connections), &[]);
+}
+
 pub fn snapshot() -> StabilityCounters {
     StabilityCounters {
         result_events_applied: RESULT_EVENTS_APPLIED.load(Ordering::Relaxed),
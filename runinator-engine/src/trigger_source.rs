@@ -0,0 +1,147 @@
+//! where the trigger loop claims due trigger firings from.
+//!
+//! the engine's trigger loop only needs "claim what is due and tell me which runs were created";
+//! the claim itself (advancing `next_execution`, recording the firing, enqueueing the start
+//! node) belongs to the backend, and so does starting the next slots of running backfills. [`DatabaseTriggerSource`] is the default and the only source the
+//! engine wires today; other backends implement [`TriggerSource`] without touching the loop. "due"
+//! is judged against a [`Clock`], the wall clock unless a test supplies its own.
+
+use std::{future::Future, sync::Arc};
+
+use runinator_database::interfaces::DatabaseImpl;
+use runinator_models::errors::SendableError;
+use uuid::Uuid;
+
+use crate::clock::{Clock, SystemClock};
+use crate::repository;
+
+/// a run created by claiming a due trigger, with the org used to scope its UI event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FiredRun {
+    pub run_id: Uuid,
+    pub org_id: Option<Uuid>,
+}
+
+/// claims due workflow and pipeline trigger firings on behalf of one scheduler instance. a claim
+/// must be exactly-once per trigger slot across instances; the returned runs are already enqueued.
+pub trait TriggerSource: Send + Sync {
+    fn claim_due_workflow_firings(
+        &self,
+        scheduler_id: &str,
+        limit: i64,
+    ) -> impl Future<Output = Result<Vec<FiredRun>, SendableError>> + Send;
+
+    fn claim_due_pipeline_firings(
+        &self,
+        scheduler_id: &str,
+        limit: i64,
+    ) -> impl Future<Output = Result<Vec<FiredRun>, SendableError>> + Send;
+
+    /// start the next slots of running backfills, within each one's concurrency. like a claim, a
+    /// slot must start once across instances.
+    fn fire_backfills(&self) -> impl Future<Output = Result<Vec<FiredRun>, SendableError>> + Send;
+}
+
+// a shared source, e.g. one handle kept by the caller for inspection while the loop owns another.
+impl<S: TriggerSource> TriggerSource for Arc<S> {
+    fn claim_due_workflow_firings(
+        &self,
+        scheduler_id: &str,
+        limit: i64,
+    ) -> impl Future<Output = Result<Vec<FiredRun>, SendableError>> + Send {
+        self.as_ref()
+            .claim_due_workflow_firings(scheduler_id, limit)
+    }
+
+    fn claim_due_pipeline_firings(
+        &self,
+        scheduler_id: &str,
+        limit: i64,
+    ) -> impl Future<Output = Result<Vec<FiredRun>, SendableError>> + Send {
+        self.as_ref()
+            .claim_due_pipeline_firings(scheduler_id, limit)
+    }
+
+    fn fire_backfills(&self) -> impl Future<Output = Result<Vec<FiredRun>, SendableError>> + Send {
+        self.as_ref().fire_backfills()
+    }
+}
+
+/// claims firings straight from the engine database, deciding what is due by `clock`.
+pub struct DatabaseTriggerSource<T, C = SystemClock> {
+    db: Arc<T>,
+    clock: C,
+}
+
+impl<T> DatabaseTriggerSource<T> {
+    pub fn new(db: Arc<T>) -> Self {
+        Self::with_clock(db, SystemClock)
+    }
+}
+
+impl<T, C> DatabaseTriggerSource<T, C> {
+    pub fn with_clock(db: Arc<T>, clock: C) -> Self {
+        Self { db, clock }
+    }
+}
+
+impl<T: DatabaseImpl, C: Clock> TriggerSource for DatabaseTriggerSource<T, C> {
+    async fn claim_due_workflow_firings(
+        &self,
+        scheduler_id: &str,
+        limit: i64,
+    ) -> Result<Vec<FiredRun>, SendableError> {
+        let runs = repository::claim_due_workflow_trigger_firings_at(
+            self.db.as_ref(),
+            scheduler_id.to_string(),
+            self.clock.now(),
+            limit,
+        )
+        .await?;
+        let mut fired = Vec::with_capacity(runs.len());
+        for run in runs {
+            let org_id = repository::org_id_for_workflow_run(self.db.as_ref(), run.id).await;
+            fired.push(FiredRun {
+                run_id: run.id,
+                org_id,
+            });
+        }
+        Ok(fired)
+    }
+
+    async fn claim_due_pipeline_firings(
+        &self,
+        scheduler_id: &str,
+        limit: i64,
+    ) -> Result<Vec<FiredRun>, SendableError> {
+        let runs = repository::claim_due_pipeline_trigger_firings_at(
+            self.db.as_ref(),
+            scheduler_id.to_string(),
+            self.clock.now(),
+            limit,
+        )
+        .await?;
+        let mut fired = Vec::with_capacity(runs.len());
+        for run in runs {
+            let org_id = repository::org_id_for_pipeline_run(self.db.as_ref(), run.id).await;
+            fired.push(FiredRun {
+                run_id: run.id,
+                org_id,
+            });
+        }
+        Ok(fired)
+    }
+
+    async fn fire_backfills(&self) -> Result<Vec<FiredRun>, SendableError> {
+        let runs = repository::fire_workflow_backfills(self.db.as_ref()).await?;
+        let mut fired = Vec::with_capacity(runs.len());
+        for run in runs {
+            let org_id = repository::org_id_for_workflow_run(self.db.as_ref(), run.id).await;
+            fired.push(FiredRun {
+                run_id: run.id,
+                org_id,
+            });
+        }
+        Ok(fired)
+    }
+}
@@ -0,0 +1,205 @@
+//! file-backed workflow definitions for installs without the web service.
+//!
+//! the engine always schedules from the database; a [`WorkflowSource`] only feeds definitions into
+//! it. [`FileWorkflowSource`] watches a directory of `.json`/`.yaml`/`.yml` files, each holding a
+//! single workflow definition or a `{ workflows, triggers }` bundle, and [`run_workflow_source_sync`]
+//! imports the merged bundle whenever the directory changes. removing a file does not delete the
+//! workflow it defined. [`render_workflow_file`] writes the same shape back out, which is how the
+//! web service exports a bundle for backups and gitops round-trips.
+
+use std::{
+    future::Future,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use runinator_database::interfaces::DatabaseImpl;
+use runinator_models::errors::{SendableError, error_code_or_unknown};
+use runinator_models::workflows::{WorkflowBundle, WorkflowDefinition};
+use serde::Deserialize;
+use tokio::sync::Notify;
+use tracing::{error, info};
+
+use crate::repository;
+
+const WORKFLOW_SOURCE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// a provider of workflow definitions that the engine imports into its database.
+pub trait WorkflowSource: Send {
+    /// the full set of definitions when they changed since the previous poll, `None` otherwise.
+    fn poll(
+        &mut self,
+    ) -> impl Future<Output = Result<Option<WorkflowBundle>, SendableError>> + Send;
+}
+
+/// a directory of workflow files, re-read when any file is added, removed, or modified.
+pub struct FileWorkflowSource {
+    dir: PathBuf,
+    fingerprint: Option<Vec<(PathBuf, u64, Option<SystemTime>)>>,
+}
+
+impl FileWorkflowSource {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            fingerprint: None,
+        }
+    }
+
+    async fn workflow_files(
+        &self,
+    ) -> Result<Vec<(PathBuf, u64, Option<SystemTime>)>, SendableError> {
+        let mut entries = tokio::fs::read_dir(&self.dir)
+            .await
+            .map_err(|err| -> SendableError {
+                format!("failed to read workflow dir {}: {err}", self.dir.display()).into()
+            })?;
+        let mut files = Vec::new();
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if !is_workflow_file(&path) {
+                continue;
+            }
+            let metadata = entry.metadata().await?;
+            if !metadata.is_file() {
+                continue;
+            }
+            files.push((path, metadata.len(), metadata.modified().ok()));
+        }
+        files.sort();
+        Ok(files)
+    }
+}
+
+impl WorkflowSource for FileWorkflowSource {
+    async fn poll(&mut self) -> Result<Option<WorkflowBundle>, SendableError> {
+        let files = self.workflow_files().await?;
+        if self.fingerprint.as_ref() == Some(&files) {
+            return Ok(None);
+        }
+        // recorded before parsing so a broken file is reported once per edit, not once per poll.
+        self.fingerprint = Some(files.clone());
+
+        let mut bundle = WorkflowBundle::default();
+        for (path, _, _) in files {
+            let contents = tokio::fs::read_to_string(&path).await?;
+            let parsed = parse_workflow_file(&path, &contents).map_err(|err| -> SendableError {
+                format!("invalid workflow file {}: {err}", path.display()).into()
+            })?;
+            bundle.workflows.extend(parsed.workflows);
+            bundle.triggers.extend(parsed.triggers);
+        }
+        Ok(Some(bundle))
+    }
+}
+
+fn is_workflow_file(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|ext| ext.to_str()),
+        Some("json" | "yaml" | "yml")
+    )
+}
+
+/// parse one workflow file. an object carrying `workflows` or `triggers` is a bundle; anything else
+/// is a single workflow definition.
+pub fn parse_workflow_file(path: &Path, contents: &str) -> Result<WorkflowBundle, SendableError> {
+    let value: serde_json::Value = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("yaml" | "yml") => serde_yaml::from_str(contents)?,
+        _ => serde_json::from_str(contents)?,
+    };
+    let is_bundle = value
+        .as_object()
+        .is_some_and(|object| object.contains_key("workflows") || object.contains_key("triggers"));
+    if is_bundle {
+        return Ok(serde_json::from_value(value)?);
+    }
+    let workflow: WorkflowDefinition = serde_json::from_value(value)?;
+    Ok(WorkflowBundle {
+        workflows: vec![workflow],
+        triggers: Vec::new(),
+        baselines: Vec::new(),
+    })
+}
+
+/// a serialization of workflow files that [`parse_workflow_file`] reads back.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WorkflowFileFormat {
+    #[default]
+    Json,
+    Yaml,
+}
+
+impl WorkflowFileFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            WorkflowFileFormat::Json => "json",
+            WorkflowFileFormat::Yaml => "yaml",
+        }
+    }
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            WorkflowFileFormat::Json => "application/json",
+            WorkflowFileFormat::Yaml => "application/yaml",
+        }
+    }
+}
+
+/// render `bundle` as a workflow file, the inverse of [`parse_workflow_file`] for a file named with
+/// the format's extension.
+pub fn render_workflow_file(
+    bundle: &WorkflowBundle,
+    format: WorkflowFileFormat,
+) -> Result<String, SendableError> {
+    match format {
+        WorkflowFileFormat::Json => Ok(serde_json::to_string_pretty(bundle)?),
+        WorkflowFileFormat::Yaml => Ok(serde_yaml::to_string(bundle)?),
+    }
+}
+
+/// poll `source` and import each changed bundle, overwriting stored workflows of the same name. a
+/// failed import is retried on the next tick with the same bundle.
+pub async fn run_workflow_source_sync<T: DatabaseImpl, S: WorkflowSource>(
+    db: Arc<T>,
+    mut source: S,
+    shutdown: Arc<Notify>,
+) {
+    info!("workflow source sync started");
+    let mut pending: Option<WorkflowBundle> = None;
+    loop {
+        match source.poll().await {
+            Ok(Some(bundle)) => pending = Some(bundle),
+            Ok(None) => {}
+            Err(err) => {
+                error!(
+                    error_code = error_code_or_unknown(err.as_ref()),
+                    "workflow source poll failed: {err}"
+                );
+            }
+        }
+        if let Some(bundle) = pending.take() {
+            let count = bundle.workflows.len();
+            match repository::import_workflow_bundle_with(db.as_ref(), bundle.clone(), true).await {
+                Ok(_) => info!("imported {count} workflow(s) from workflow source"),
+                Err(err) => {
+                    error!(
+                        error_code = error_code_or_unknown(err.as_ref()),
+                        "workflow source import failed: {err}"
+                    );
+                    pending = Some(bundle);
+                }
+            }
+        }
+
+        tokio::select! {
+            _ = shutdown.notified() => return,
+            _ = tokio::time::sleep(WORKFLOW_SOURCE_INTERVAL) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "workflow_source_tests.rs"]
+mod tests;
@@ -0,0 +1,75 @@
+use std::path::Path;
+
+use uuid::Uuid;
+
+use super::{
+    FileWorkflowSource, WorkflowFileFormat, WorkflowSource, parse_workflow_file,
+    render_workflow_file,
+};
+
+// a bare definition and a bundle are both accepted, in either json or yaml.
+#[test]
+fn parses_definitions_and_bundles_from_json_and_yaml() {
+    let single =
+        parse_workflow_file(Path::new("a.yaml"), "name: nightly\nenabled: true\n").unwrap();
+    assert_eq!(single.workflows.len(), 1);
+    assert_eq!(single.workflows[0].name, "nightly");
+    assert!(single.workflows[0].enabled);
+
+    let bundle = parse_workflow_file(
+        Path::new("b.json"),
+        r#"{"workflows":[{"name":"one"},{"name":"two"}]}"#,
+    )
+    .unwrap();
+    let names: Vec<_> = bundle.workflows.iter().map(|w| w.name.as_str()).collect();
+    assert_eq!(names, ["one", "two"]);
+
+    assert!(parse_workflow_file(Path::new("c.json"), "{not json").is_err());
+}
+
+// an exported bundle reads back as the same workflows in either format.
+#[test]
+fn rendered_workflow_files_parse_back() {
+    let bundle = parse_workflow_file(
+        Path::new("export.json"),
+        r#"{"workflows":[{"name":"nightly","namespace":"ops","version":"1.2.0","enabled":true}]}"#,
+    )
+    .unwrap();
+    for format in [WorkflowFileFormat::Json, WorkflowFileFormat::Yaml] {
+        let rendered = render_workflow_file(&bundle, format).unwrap();
+        let name = format!("export.{}", format.extension());
+        let parsed = parse_workflow_file(Path::new(&name), &rendered).unwrap();
+        assert_eq!(parsed.workflows.len(), 1);
+        let workflow = &parsed.workflows[0];
+        assert_eq!(workflow.name, "nightly");
+        assert_eq!(workflow.namespace.as_deref(), Some("ops"));
+        assert_eq!(workflow.version.to_string(), "1.2.0");
+        assert!(workflow.enabled);
+    }
+}
+
+// the directory is reported once per change and ignores files that are not workflow definitions.
+#[tokio::test]
+async fn file_source_reports_only_changes() {
+    let dir = std::env::temp_dir().join(format!("runinator-workflow-source-{}", Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("a.json"), r#"{"name":"a"}"#).unwrap();
+    std::fs::write(dir.join("notes.txt"), "ignored").unwrap();
+
+    let mut source = FileWorkflowSource::new(&dir);
+    let first = source.poll().await.unwrap().expect("initial load");
+    assert_eq!(first.workflows.len(), 1);
+    assert!(source.poll().await.unwrap().is_none());
+
+    std::fs::write(dir.join("b.yml"), "name: b\n").unwrap();
+    let second = source
+        .poll()
+        .await
+        .unwrap()
+        .expect("reload after a new file");
+    let mut names: Vec<_> = second.workflows.iter().map(|w| w.name.clone()).collect();
+    names.sort();
+    assert_eq!(names, ["a", "b"]);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -270,6 +270,7 @@ impl McpServer {
                 let bundle = WorkflowBundle {
                     workflows,
                     triggers: Vec::new(),
+                    baselines: Vec::new(),
                 };
                 // the mcp import path carries workflows + secrets only; pipelines are pack-managed
                 // via `runinatorctl workflows apply`.
@@ -0,0 +1,14 @@
+// generates the gRPC bindings for `runinator_models::grpc`; a no-op unless the `grpc` feature is on,
+// so crates that only want the plain models never run protoc.
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    #[cfg(feature = "grpc")]
+    {
+        println!("cargo:rerun-if-changed=proto");
+        let mut config = tonic_prost_build::Config::new();
+        config.protoc_executable(protoc_bin_vendored::protoc_bin_path()?);
+        tonic_prost_build::configure()
+            .build_transport(false)
+            .compile_with_config(config, &["proto/runinator/v1/runinator.proto"], &["proto"])?;
+    }
+    Ok(())
+}
@@ -1,5 +1,10 @@
 use uuid::Uuid;
 
+/// prefix every route is served under; the paths below are relative to it.
+pub const API_VERSION_PREFIX: &str = "/v1";
+
+/// readiness probe: 200 while the web service can reach its database, 503 otherwise.
+pub const API_READY: &str = "/ready";
 pub const API_PROVIDERS: &str = "/providers";
 pub const API_AUTH_CONFIG: &str = "/auth/config";
 pub const API_AUTH_LOGIN: &str = "/auth/login";
@@ -11,6 +16,16 @@ pub const API_WORKFLOWS_VALIDATE: &str = "/workflows/validate";
 pub const API_WORKFLOWS_SIMULATE: &str = "/workflows/simulate";
 pub const API_WORKFLOWS_IMPORT: &str = "/workflows/import";
 pub const API_WORKFLOWS_EXPORT: &str = "/workflows/export";
+/// dashboard overview: visible workflows with their recent runs and executing replicas (`?runs=`).
+pub const API_WORKFLOWS_OVERVIEW: &str = "/workflows/overview";
+/// read-only graphql over workflows, their runs and executors, workers, and provider aggregates.
+pub const API_GRAPHQL: &str = "/graphql";
+/// `(id, hash)` of every visible workflow's portable definition, for drift checks.
+pub const API_WORKFLOWS_HASHES: &str = "/workflows/hashes";
+/// case-insensitive search over workflow names, namespaces, and definitions (`?q=&limit=&offset=`).
+pub const API_WORKFLOWS_SEARCH: &str = "/workflows/search";
+/// transactional multi-workflow upsert with per-item results.
+pub const API_WORKFLOWS_BULK: &str = "/workflows/bulk";
 /// compiled pack zip import (workflows + optional secrets).
 pub const API_PACKS_IMPORT: &str = "/packs/import";
 /// header required before raw json workflow bundle imports are accepted.
@@ -28,10 +43,34 @@ pub const API_WDL_IMPORT: &str = "/wdl/import";
 pub const API_WORKFLOW_TRIGGERS_DUE: &str = "/workflow_triggers/due";
 pub const API_SCHEDULER_WORKFLOW_TRIGGER_FIRINGS_CLAIM: &str =
     "/scheduler/workflow_trigger_firings/claim";
+/// admin: recompute every enabled cron trigger's next_execution (`?dry_run=true` only reports).
+pub const API_ADMIN_RECONCILE_SCHEDULES: &str = "/admin/reconcile_schedules";
+/// admin: move a selection of cron triggers by an offset or re-spread them across a window.
+pub const API_ADMIN_SHIFT_SCHEDULES: &str = "/admin/shift_schedules";
+/// validate a cron expression and list its next fire times, optionally around a blackout window.
+pub const API_CRON_PREVIEW: &str = "/cron/preview";
+/// named holiday calendars cron triggers reference from their `blackouts` list.
+pub const API_BLACKOUT_CALENDARS: &str = "/blackout_calendars";
 pub const API_PIPELINES: &str = "/pipelines";
+pub const API_WORKFLOW_TEMPLATES: &str = "/workflow_templates";
 pub const API_WORKFLOW_RUNS: &str = "/workflow_runs";
+/// weekly or monthly run-health digest over the caller's visible workflows (`?period=`).
+pub const API_WORKFLOW_RUN_HEALTH: &str = "/workflow_run_health";
+/// queue wait-time aggregates, global and per task, over the caller's visible workflows (`?hours=`).
+pub const API_QUEUE_WAIT: &str = "/queue_wait";
+/// running, queued, and projected cron work over the next window (`?minutes=60`).
+pub const API_SCHEDULE_UPCOMING: &str = "/schedule/upcoming";
+/// dry run of the trigger loop: the cron firings the next window would enqueue (`?minutes=60`).
+pub const API_SCHEDULER_PREVIEW: &str = "/scheduler/preview";
+/// run counts and durations per provider action over recent windows (`?windows=1,24,168`, hours).
+pub const API_STATS_PROVIDERS: &str = "/stats/providers";
 pub const API_SCHEDULER_WORKFLOW_RUNS_CLAIM: &str = "/scheduler/workflow_runs/claim";
 pub const API_SCHEDULER_READY_NODES_CLAIM: &str = "/scheduler/ready_nodes/claim";
+/// one-off jobs: a single action run once without a stored workflow.
+pub const API_JOBS: &str = "/jobs";
+/// cluster-wide pause of action dispatch and/or worker execution.
+pub const API_CLUSTER_PAUSE: &str = "/cluster/pause";
+pub const API_DEBUG_INFO: &str = "/debug/info";
 pub const API_RUNS: &str = "/runs";
 pub const API_ARTIFACTS: &str = "/artifacts";
 pub const API_SCHEDULER_ACTION_DISPATCHES: &str = "/scheduler/action_dispatches";
@@ -43,6 +82,8 @@ pub const API_APPROVALS: &str = "/approvals";
 pub const API_IDEMPOTENCY_KEYS: &str = "/idempotency_keys";
 pub const API_CREDENTIALS: &str = "/credentials";
 pub const API_REPLICAS: &str = "/replicas";
+/// the worker registry: live membership, plugins, and load of every worker replica.
+pub const API_WORKERS: &str = "/workers";
 
 pub fn api_workflow(workflow_id: Uuid) -> String {
     format!("{API_WORKFLOWS}/{workflow_id}")
@@ -56,10 +97,43 @@ pub fn api_workflow_duplicate(workflow_id: Uuid) -> String {
     format!("{API_WORKFLOWS}/{workflow_id}/duplicate")
 }
 
+pub fn api_workflow_enable(workflow_id: Uuid) -> String {
+    format!("{API_WORKFLOWS}/{workflow_id}/enable")
+}
+
+pub fn api_workflow_disable(workflow_id: Uuid) -> String {
+    format!("{API_WORKFLOWS}/{workflow_id}/disable")
+}
+
 pub fn api_workflow_triggers(workflow_id: Uuid) -> String {
     format!("{API_WORKFLOWS}/{workflow_id}/triggers")
 }
 
+pub fn api_workflow_retention(workflow_id: Uuid) -> String {
+    format!("{API_WORKFLOWS}/{workflow_id}/retention")
+}
+
+pub fn api_workflow_revisions(workflow_id: Uuid) -> String {
+    format!("{API_WORKFLOWS}/{workflow_id}/revisions")
+}
+
+pub fn api_workflow_revert(workflow_id: Uuid, revision: i64) -> String {
+    format!("{API_WORKFLOWS}/{workflow_id}/revert/{revision}")
+}
+
+pub fn api_workflow_protection(workflow_id: Uuid) -> String {
+    format!("{API_WORKFLOWS}/{workflow_id}/protection")
+}
+
+pub fn api_workflow_pending(workflow_id: Uuid) -> String {
+    format!("{API_WORKFLOWS}/{workflow_id}/pending")
+}
+
+/// `verb` is `approve` or `reject`.
+pub fn api_workflow_pending_review(workflow_id: Uuid, change_id: Uuid, verb: &str) -> String {
+    format!("{API_WORKFLOWS}/{workflow_id}/pending/{change_id}/{verb}")
+}
+
 pub fn api_workflow_runs(workflow_id: Uuid) -> String {
     format!("{API_WORKFLOWS}/{workflow_id}/runs")
 }
@@ -72,6 +146,22 @@ pub fn api_workflow_trigger_runs(trigger_id: Uuid) -> String {
     format!("/workflow_triggers/{trigger_id}/runs")
 }
 
+pub fn api_workflow_trigger_backfill(trigger_id: Uuid) -> String {
+    format!("/workflow_triggers/{trigger_id}/backfill")
+}
+
+pub fn api_workflow_backfill_cancel(backfill_id: Uuid) -> String {
+    format!("/workflow_backfills/{backfill_id}/cancel")
+}
+
+pub fn api_workflow_template(template_id: Uuid) -> String {
+    format!("{API_WORKFLOW_TEMPLATES}/{template_id}")
+}
+
+pub fn api_workflow_template_instantiate(template_id: Uuid) -> String {
+    format!("{API_WORKFLOW_TEMPLATES}/{template_id}/instantiate")
+}
+
 pub fn api_pipeline(pipeline_id: Uuid) -> String {
     format!("{API_PIPELINES}/{pipeline_id}")
 }
@@ -100,6 +190,10 @@ pub fn api_workflow_run_artifacts(workflow_run_id: Uuid) -> String {
     format!("{API_WORKFLOW_RUNS}/{workflow_run_id}/artifacts")
 }
 
+pub fn api_workflow_run_outputs(workflow_run_id: Uuid) -> String {
+    format!("{API_WORKFLOW_RUNS}/{workflow_run_id}/outputs")
+}
+
 pub fn api_workflow_run_transitions(workflow_run_id: Uuid) -> String {
     format!("{API_WORKFLOW_RUNS}/{workflow_run_id}/transitions")
 }
@@ -128,6 +222,10 @@ pub fn api_scheduler_action_dispatch_failed(dispatch_id: Uuid) -> String {
     format!("/scheduler/action_dispatches/{dispatch_id}/failed")
 }
 
+pub fn api_job(job_id: Uuid) -> String {
+    format!("{API_JOBS}/{job_id}")
+}
+
 pub fn api_run(run_id: Uuid) -> String {
     format!("{API_RUNS}/{run_id}")
 }
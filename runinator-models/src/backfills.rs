@@ -0,0 +1,112 @@
+//! backfills: replay a cron trigger's slots over a past window, e.g. re-running a daily dump for
+//! the last 30 days after fixing a bug in it. the slots are worked out once when the backfill is
+//! requested; the trigger loop then starts them oldest first, never keeping more than
+//! `concurrency` of the workflow's backfill runs open at once.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// runs a backfill keeps open at once when the request does not say.
+pub const DEFAULT_BACKFILL_CONCURRENCY: i64 = 1;
+
+/// most runs a backfill may keep open at once.
+pub const MAX_BACKFILL_CONCURRENCY: i64 = 32;
+
+/// most slots one backfill replays; a longer window has to be split.
+pub const MAX_BACKFILL_SLOTS: usize = 1000;
+
+/// the run parameter a backfilled run receives its slot in, as an rfc 3339 timestamp.
+pub const BACKFILL_SLOT_PARAMETER: &str = "scheduled_for";
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BackfillStatus {
+    /// slots are still waiting to be started.
+    Running,
+    /// every slot has been started; its runs may still be open.
+    Completed,
+    /// stopped before every slot was started.
+    Canceled,
+}
+
+impl BackfillStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Running => "running",
+            Self::Completed => "completed",
+            Self::Canceled => "canceled",
+        }
+    }
+}
+
+impl TryFrom<&str> for BackfillStatus {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "running" => Ok(Self::Running),
+            "completed" => Ok(Self::Completed),
+            "canceled" => Ok(Self::Canceled),
+            other => Err(format!("Unknown backfill status '{other}'")),
+        }
+    }
+}
+
+/// the window a backfill replays: every slot of the trigger from `from` through `to`, inclusive.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackfillRequest {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    /// runs kept open at once; [`DEFAULT_BACKFILL_CONCURRENCY`] when absent.
+    #[serde(default)]
+    pub concurrency: Option<i64>,
+}
+
+impl BackfillRequest {
+    /// validate the window against `now` and return the concurrency to run it at. a backfill only
+    /// replays slots that have already passed.
+    pub fn validate(&self, now: DateTime<Utc>) -> Result<i64, String> {
+        if self.from > self.to {
+            return Err("from must not be after to".into());
+        }
+        if self.to > now {
+            return Err("to must not be in the future; a backfill only replays past slots".into());
+        }
+        let concurrency = self.concurrency.unwrap_or(DEFAULT_BACKFILL_CONCURRENCY);
+        if !(1..=MAX_BACKFILL_CONCURRENCY).contains(&concurrency) {
+            return Err(format!(
+                "concurrency must be between 1 and {MAX_BACKFILL_CONCURRENCY}"
+            ));
+        }
+        Ok(concurrency)
+    }
+}
+
+/// a requested backfill and how far it has got.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WorkflowBackfill {
+    pub id: Uuid,
+    pub workflow_id: Uuid,
+    pub trigger_id: Uuid,
+    pub range_start: DateTime<Utc>,
+    pub range_end: DateTime<Utc>,
+    pub concurrency: i64,
+    /// the trigger's slots in the window, oldest first.
+    pub slots: Vec<DateTime<Utc>>,
+    /// how many of `slots` have been started.
+    pub fired: i64,
+    pub status: BackfillStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requested_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl WorkflowBackfill {
+    /// the slots not started yet, oldest first.
+    pub fn pending_slots(&self) -> &[DateTime<Utc>] {
+        let fired = usize::try_from(self.fired).unwrap_or_default();
+        self.slots.get(fired..).unwrap_or_default()
+    }
+}
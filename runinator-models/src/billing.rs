@@ -68,7 +68,8 @@ impl RateCard {
 }
 
 /// an org's spending/scale caps. a `0` in `max_nodes_per_kind` blocks that kind entirely; an absent
-/// kind is unbounded on node count. `max_monthly_cents` of 0 means "no monthly budget cap".
+/// kind is unbounded on node count. `max_monthly_cents` of 0 means "no monthly budget cap", and
+/// `max_concurrent_runs` of 0 leaves the org's active workflow runs unbounded.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct OrgQuota {
     pub org_id: Uuid,
@@ -76,6 +77,8 @@ pub struct OrgQuota {
     pub max_nodes_per_kind: BTreeMap<String, u32>,
     #[serde(default)]
     pub max_monthly_cents: u32,
+    #[serde(default)]
+    pub max_concurrent_runs: u32,
 }
 
 impl OrgQuota {
@@ -120,6 +123,9 @@ pub struct OrgUsage {
     pub since: Option<DateTime<Utc>>,
     pub node_hours: BTreeMap<String, f64>,
     pub accrued_cents: u64,
+    /// workflow runs of the org currently claimed and active, counted against `max_concurrent_runs`.
+    #[serde(default)]
+    pub active_runs: u64,
 }
 
 // ---- request/response DTOs ----
@@ -140,4 +146,6 @@ pub struct UpdateOrgQuotaRequest {
     pub max_nodes_per_kind: BTreeMap<String, u32>,
     #[serde(default)]
     pub max_monthly_cents: u32,
+    #[serde(default)]
+    pub max_concurrent_runs: u32,
 }
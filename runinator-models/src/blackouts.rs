@@ -0,0 +1,93 @@
+//! recurring blackout windows for cron triggers. besides its one-off `blackout_start`/`blackout_end`
+//! pair, a trigger's configuration may list windows under `blackouts`: a weekly wall-clock window
+//! ("every sunday 02:00–04:00") or a named holiday calendar stored on the server. a slot falling in
+//! any of them is deferred to that window's end.
+
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+
+use crate::value::Value;
+
+/// cron trigger configuration key listing the trigger's [`TriggerBlackout`] windows.
+pub const TRIGGER_BLACKOUTS_KEY: &str = "blackouts";
+
+/// one entry of a trigger's `blackouts`: either `calendar`, or `start` and `end`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct TriggerBlackout {
+    /// a [`BlackoutCalendar`] by name; each of its dates is blacked out for the whole local day.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub calendar: Option<String>,
+    /// weekdays (`mon`…`sun`) the window opens on; empty means every day.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub days: Vec<Weekday>,
+    /// local opening time (`02:00`), inclusive.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start: Option<NaiveTime>,
+    /// local closing time, inclusive. at or before `start` wraps past midnight.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end: Option<NaiveTime>,
+    /// iana zone the times are read in; defaults to the trigger's own `timezone`, else utc.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+}
+
+/// the blackout windows a cron trigger's configuration lists. absent means none.
+pub fn trigger_blackouts(configuration: &Value) -> Result<Vec<TriggerBlackout>, String> {
+    let entries = match configuration.get(TRIGGER_BLACKOUTS_KEY) {
+        None | Some(Value::Null) => return Ok(Vec::new()),
+        Some(Value::Array(entries)) => entries,
+        Some(_) => return Err(format!("{TRIGGER_BLACKOUTS_KEY} must be a list of windows")),
+    };
+    entries
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            let blackout: TriggerBlackout = entry
+                .decode()
+                .map_err(|err| format!("{TRIGGER_BLACKOUTS_KEY}[{index}]: {err}"))?;
+            match (&blackout.calendar, blackout.start, blackout.end) {
+                (Some(name), None, None) if blackout.days.is_empty() => {
+                    if name.trim().is_empty() {
+                        Err(format!("{TRIGGER_BLACKOUTS_KEY}[{index}]: calendar must not be empty"))
+                    } else {
+                        Ok(blackout)
+                    }
+                }
+                (Some(_), ..) => Err(format!(
+                    "{TRIGGER_BLACKOUTS_KEY}[{index}]: a calendar window takes no days, start, or end"
+                )),
+                (None, Some(_), Some(_)) => Ok(blackout),
+                _ => Err(format!(
+                    "{TRIGGER_BLACKOUTS_KEY}[{index}]: give either a calendar name or both start and end"
+                )),
+            }
+        })
+        .collect()
+}
+
+/// a named holiday calendar. a trigger listing `{ "calendar": name }` in its `blackouts` does not
+/// fire on any of `dates`, each read as a whole day in `timezone` (utc when absent).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BlackoutCalendar {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    #[serde(default)]
+    pub dates: Vec<NaiveDate>,
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// a request to create or replace a blackout calendar; the name comes from the path.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BlackoutCalendarRequest {
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub timezone: Option<String>,
+    #[serde(default)]
+    pub dates: Vec<NaiveDate>,
+}
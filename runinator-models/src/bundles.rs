@@ -57,7 +57,8 @@ impl Bundle for SecretBundle {
 }
 
 /// the result of importing a compiled pack zip at `/packs/import`: the imported workflow bundle,
-/// the imported (redacted) secret bundle, and the pipelines that were upserted.
+/// the imported (redacted) secret bundle, the pipelines that were upserted, and the changes to
+/// protected workflows held for approval instead of imported.
 #[derive(Debug, Clone, Default, Serialize, serde::Deserialize)]
 pub struct PackImportResult {
     #[serde(default)]
@@ -66,4 +67,6 @@ pub struct PackImportResult {
     pub secrets: SecretBundle,
     #[serde(default)]
     pub pipelines: Vec<crate::pipelines::Pipeline>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub held: Vec<crate::protection::PendingWorkflowChange>,
 }
@@ -52,6 +52,12 @@ pub enum Capability {
     /// manage platform/admin settings.
     #[serde(rename = "settings:manage")]
     SettingsManage,
+    /// submit and poll one-off jobs that run a single action without a stored workflow.
+    #[serde(rename = "jobs:run")]
+    JobsRun,
+    /// pause and resume dispatch or execution across the whole cluster.
+    #[serde(rename = "cluster:manage")]
+    ClusterManage,
 
     // ---- organization capabilities (admin of the caller's active org, or platform admin) ----
     /// manage membership and roles within the active organization.
@@ -78,6 +84,8 @@ impl Capability {
         Capability::OrgsManage,
         Capability::BillingManage,
         Capability::SettingsManage,
+        Capability::JobsRun,
+        Capability::ClusterManage,
         Capability::OrgMembersManage,
         Capability::OrgNodesScale,
     ];
@@ -102,6 +110,8 @@ impl Capability {
             Capability::OrgsManage => "orgs:manage",
             Capability::BillingManage => "billing:manage",
             Capability::SettingsManage => "settings:manage",
+            Capability::JobsRun => "jobs:run",
+            Capability::ClusterManage => "cluster:manage",
             Capability::OrgMembersManage => "org:members:manage",
             Capability::OrgNodesScale => "org:nodes:scale",
         }
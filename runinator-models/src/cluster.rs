@@ -0,0 +1,66 @@
+//! cluster-wide pause for incidents. an operator can hold all action dispatch (queued actions wait in
+//! the outbox instead of reaching the broker), all execution (workers stop taking actions off the
+//! broker), and/or all scheduling (no trigger fires) with one call, and lift it just as quickly.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// the current cluster pause; both flags off means the cluster runs normally.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ClusterPause {
+    /// hold queued actions in the dispatch outbox rather than publishing them to the broker.
+    #[serde(default)]
+    pub dispatch: bool,
+    /// workers stop polling the broker for new actions; in-flight actions finish.
+    #[serde(default)]
+    pub execution: bool,
+    /// the trigger loop claims no cron or pipeline trigger firings and starts no backfilled slots.
+    /// lifting it recomputes every cron trigger's `next_execution` from then, so the slots that
+    /// passed while paused are dropped rather than fired as misfires.
+    #[serde(default)]
+    pub scheduling: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// the principal that last changed the pause; `None` when auth is disabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_by: Option<Uuid>,
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl ClusterPause {
+    pub fn is_paused(&self) -> bool {
+        self.dispatch || self.execution || self.scheduling
+    }
+}
+
+/// a request to set the cluster pause. omitted flags default to paused, so an empty body pauses
+/// everything.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClusterPauseRequest {
+    #[serde(default = "paused_by_default")]
+    pub dispatch: bool,
+    #[serde(default = "paused_by_default")]
+    pub execution: bool,
+    #[serde(default = "paused_by_default")]
+    pub scheduling: bool,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+fn paused_by_default() -> bool {
+    true
+}
+
+/// the lease electing one scheduler instance as trigger leader. only the holder claims due trigger
+/// firings; a standby takes the lease over once `expires_at` passes without a renewal.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SchedulerLease {
+    pub name: String,
+    /// the instance id of the current leader.
+    pub holder: String,
+    /// when `holder` first took the lease; renewals keep it.
+    pub acquired_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
@@ -1,5 +1,7 @@
 use std::fmt;
 
+use serde::{Deserialize, Serialize};
+
 pub type SendableError = Box<dyn std::error::Error + Send + Sync + 'static>;
 
 #[derive(Debug)]
@@ -24,11 +26,21 @@ impl RuntimeError {
         }
     }
 
+    /// the dotted runtime key, e.g. "workflow.cancel.not_found".
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
     /// the stable numbered code (e.g. "JIRA001") this error was raised from, if it was built via
-    /// [`ErrorDescriptor::error`] or [`ErrorDescriptor::bare`].
+    /// [`ErrorDescriptor::error`], [`ErrorDescriptor::bare`], or [`ErrorDescriptor::keyed`].
     pub fn numbered_code(&self) -> Option<&str> {
         self.numbered_code.as_deref()
     }
+
+    /// the broad class of this error, read from its key and numbered code.
+    pub fn category(&self) -> ErrorCategory {
+        ErrorCategory::classify(&self.code, self.numbered_code())
+    }
 }
 
 impl fmt::Display for RuntimeError {
@@ -78,6 +90,135 @@ impl ErrorDescriptor {
             numbered_code: Some(self.code.to_string()),
         })
     }
+
+    /// like `error`, but under a more specific dotted `key` than the descriptor's own (e.g. one
+    /// per call site), still carrying the numbered code.
+    pub fn keyed(&self, key: impl Into<String>, detail: impl fmt::Display) -> SendableError {
+        Box::new(RuntimeError {
+            code: key.into(),
+            message: format!("{} - {}: {detail}", self.code, self.summary),
+            numbered_code: Some(self.code.to_string()),
+        })
+    }
+
+    /// the broad class of errors raised from this descriptor.
+    pub fn category(&self) -> ErrorCategory {
+        ErrorCategory::classify(self.key, Some(self.code))
+    }
+}
+
+/// numbered-code prefixes owned by the transport layers: the broker, the wire codec, and the api
+/// client.
+const TRANSPORT_CODE_PREFIXES: &[&str] = &["BROKER", "COMM", "API"];
+
+/// numbered-code prefixes owned by action providers.
+const PROVIDER_CODE_PREFIXES: &[&str] = &[
+    "AI", "APPROVAL", "AWS", "CONSOLE", "EMAIL", "GIT", "GITHUB", "JIRA", "LOCALFS", "SLACK",
+    "SQL", "STD",
+];
+
+/// the broad class of an error, shared across crates so callers can branch on it without parsing
+/// messages. the web service maps each class onto an http status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCategory {
+    /// the request or configuration was rejected.
+    Validation,
+    /// the record the caller named does not exist.
+    NotFound,
+    /// the request clashes with current state: a duplicate, or a run that already finished.
+    Conflict,
+    /// the broker, wire codec, or a remote api could not be reached or answered badly.
+    Transport,
+    /// an action provider failed while running.
+    Provider,
+    /// the database failed or returned data that does not decode.
+    Database,
+    /// anything else: a broken invariant.
+    Internal,
+}
+
+impl ErrorCategory {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCategory::Validation => "validation",
+            ErrorCategory::NotFound => "not_found",
+            ErrorCategory::Conflict => "conflict",
+            ErrorCategory::Transport => "transport",
+            ErrorCategory::Provider => "provider",
+            ErrorCategory::Database => "database",
+            ErrorCategory::Internal => "internal",
+        }
+    }
+
+    /// classify a dotted runtime key (`workflow.cancel.not_found`) and, when known, its numbered
+    /// code (`RUNI137`). a `database.` key is a database error; otherwise the key's last segment
+    /// decides (`not_found`; `terminal`, `duplicate`, or `conflict`; `invalid*` or `unsupported*`),
+    /// then the owner: a transport code or a `broker` segment, or a provider code.
+    pub fn classify(key: &str, numbered_code: Option<&str>) -> Self {
+        let key = key.to_ascii_lowercase();
+        if key.starts_with("database.") {
+            return ErrorCategory::Database;
+        }
+        let suffix = key.rsplit('.').next().unwrap_or(&key);
+        if suffix == "not_found" {
+            return ErrorCategory::NotFound;
+        }
+        if matches!(suffix, "terminal" | "duplicate" | "conflict") {
+            return ErrorCategory::Conflict;
+        }
+        if suffix.starts_with("invalid") || suffix.starts_with("unsupported") {
+            return ErrorCategory::Validation;
+        }
+        let prefix = numbered_code
+            .map(|code| code.trim_end_matches(|c: char| c.is_ascii_digit()))
+            .unwrap_or_default();
+        if TRANSPORT_CODE_PREFIXES.contains(&prefix) || key.split('.').any(|part| part == "broker")
+        {
+            ErrorCategory::Transport
+        } else if PROVIDER_CODE_PREFIXES.contains(&prefix) {
+            ErrorCategory::Provider
+        } else {
+            ErrorCategory::Internal
+        }
+    }
+
+    /// the class of an arbitrary error: a [`RuntimeError`] by its key and code, a provider's
+    /// structured error as `provider`, an io error by its kind, and anything else by a numbered
+    /// code in its message, falling back to `internal`.
+    pub fn of(err: &(dyn std::error::Error + 'static)) -> Self {
+        if let Some(runtime_err) = err.downcast_ref::<RuntimeError>() {
+            return runtime_err.category();
+        }
+        if err.is::<crate::runs::ProviderError>() {
+            return ErrorCategory::Provider;
+        }
+        if let Some(io_err) = err.downcast_ref::<std::io::Error>() {
+            use std::io::ErrorKind;
+            return match io_err.kind() {
+                ErrorKind::NotFound => ErrorCategory::NotFound,
+                ErrorKind::AlreadyExists => ErrorCategory::Conflict,
+                ErrorKind::InvalidInput | ErrorKind::InvalidData => ErrorCategory::Validation,
+                ErrorKind::ConnectionRefused
+                | ErrorKind::ConnectionReset
+                | ErrorKind::ConnectionAborted
+                | ErrorKind::NotConnected
+                | ErrorKind::BrokenPipe
+                | ErrorKind::TimedOut => ErrorCategory::Transport,
+                _ => ErrorCategory::Internal,
+            };
+        }
+        match extract_error_code(err) {
+            Some(code) => ErrorCategory::classify("", Some(&code)),
+            None => ErrorCategory::Internal,
+        }
+    }
+}
+
+impl fmt::Display for ErrorCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 /// pulls a stable numbered error code (e.g. "BROKER005") out of an arbitrary error for use as a
@@ -0,0 +1,83 @@
+//! the runinator.v1 gRPC contract generated from `proto/runinator/v1/runinator.proto`, and the
+//! conversions between its messages and the model types they mirror. inbound workflows decode
+//! through the same serde path as a REST body, so both surfaces accept exactly the same definitions.
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde_json::{Map, Value as JsonValue};
+
+use crate::{runs, workflows::WorkflowDefinition};
+
+tonic::include_proto!("runinator.v1");
+
+impl TryFrom<&WorkflowDefinition> for Workflow {
+    type Error = String;
+
+    fn try_from(workflow: &WorkflowDefinition) -> Result<Self, Self::Error> {
+        let to_json = |field: &str, value: Result<String, serde_json::Error>| {
+            value.map_err(|err| format!("{field}: {err}"))
+        };
+        Ok(Self {
+            id: workflow.id.map(|id| id.to_string()).unwrap_or_default(),
+            name: workflow.name.clone(),
+            namespace: workflow.namespace.clone(),
+            org_id: workflow.org_id.map(|id| id.to_string()),
+            version: workflow.version.to_string(),
+            enabled: workflow.enabled,
+            input_type_json: to_json("input_type", serde_json::to_string(&workflow.input_type))?,
+            definition_json: to_json("definition", serde_json::to_string(&workflow.definition))?,
+            created_at: workflow.created_at.map(timestamp),
+            updated_at: workflow.updated_at.map(timestamp),
+        })
+    }
+}
+
+impl TryFrom<Workflow> for WorkflowDefinition {
+    type Error = String;
+
+    fn try_from(workflow: Workflow) -> Result<Self, Self::Error> {
+        let mut body = Map::new();
+        // empty strings are proto3's "unset": leave those keys out so the serde defaults apply.
+        let mut text = |key: &str, value: Option<String>| {
+            if let Some(value) = value.filter(|value| !value.is_empty()) {
+                body.insert(key.into(), JsonValue::String(value));
+            }
+        };
+        text("id", Some(workflow.id));
+        text("name", Some(workflow.name));
+        text("namespace", workflow.namespace);
+        text("org_id", workflow.org_id);
+        text("version", Some(workflow.version));
+        text("created_at", workflow.created_at);
+        text("updated_at", workflow.updated_at);
+        body.insert("enabled".into(), JsonValue::Bool(workflow.enabled));
+        for (key, raw) in [
+            ("input_type", workflow.input_type_json),
+            ("definition", workflow.definition_json),
+        ] {
+            if raw.trim().is_empty() {
+                continue;
+            }
+            let value = serde_json::from_str(&raw).map_err(|err| format!("{key}: {err}"))?;
+            body.insert(key.into(), value);
+        }
+        serde_json::from_value(JsonValue::Object(body)).map_err(|err| err.to_string())
+    }
+}
+
+impl From<&runs::RunChunk> for RunChunk {
+    fn from(chunk: &runs::RunChunk) -> Self {
+        Self {
+            id: chunk.id.to_string(),
+            run_id: chunk.run_id.to_string(),
+            sequence: chunk.sequence,
+            stream: chunk.stream.clone(),
+            content: chunk.content.clone(),
+            created_at: timestamp(chunk.created_at),
+        }
+    }
+}
+
+// the same rendering serde gives a `DateTime<Utc>` in a REST body.
+fn timestamp(at: DateTime<Utc>) -> String {
+    at.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+}
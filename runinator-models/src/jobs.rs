@@ -0,0 +1,214 @@
+//! one-off jobs: a single provider action dispatched once through the broker without a stored
+//! workflow, for ad-hoc operations triggered from scripts. a job's result lands on its own row, so it
+//! can be polled by id.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::value::Value;
+use crate::workflows::{WorkflowAction, WorkflowObject, WorkflowStatus};
+
+/// the node id a job's action command and result events carry. the command also carries the job id as
+/// both its run and node-run id, which no workflow node run can match.
+pub const JOB_NODE_ID: &str = "__job__";
+
+/// longest a job may run before its worker times it out, in seconds.
+pub const MAX_JOB_TIMEOUT_SECONDS: i64 = 24 * 60 * 60;
+
+/// most times a failed job is dispatched again.
+pub const MAX_JOB_RETRIES: i64 = 10;
+
+/// longest wait before a job retry, in seconds; the doubling backoff stops growing here.
+pub const MAX_JOB_RETRY_BACKOFF_SECONDS: i64 = 60 * 60;
+
+// actions that only make sense inside a workflow run: the std interpreters need the run's context,
+// and the local-files provider is pinned to the desktop replica that launched the run.
+const WORKFLOW_ONLY_ACTIONS: &[(&str, Option<&str>)] = &[
+    ("std", Some("exec")),
+    ("std", Some("code")),
+    ("local", None),
+];
+
+/// whether a result event with these ids belongs to a job rather than a workflow node run.
+pub fn is_job(workflow_run_id: Uuid, workflow_node_run_id: Uuid, node_id: &str) -> bool {
+    node_id == JOB_NODE_ID && workflow_run_id == workflow_node_run_id
+}
+
+/// a request to run one action once.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobRequest {
+    /// the provider to run, e.g. `http`.
+    pub action_name: String,
+    /// the provider function to call.
+    pub action_function: String,
+    #[serde(default)]
+    pub configuration: Value,
+    #[serde(default = "default_job_timeout_seconds")]
+    pub timeout_seconds: i64,
+    /// routing labels a worker must carry to receive the job; empty means the general pool.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub required_labels: BTreeMap<String, String>,
+    /// times a failed or timed-out attempt is dispatched again; 0 runs the job once.
+    #[serde(default)]
+    pub max_retries: i64,
+    /// wait before the first retry, in seconds; each later retry waits twice as long.
+    #[serde(default = "default_job_retry_backoff_seconds")]
+    pub retry_backoff_seconds: i64,
+    /// broker delivery priority; higher is handed to workers first.
+    #[serde(default)]
+    pub priority: i32,
+    /// broker queue to publish on; only workers subscribed to it receive the job.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub queue: Option<String>,
+}
+
+fn default_job_timeout_seconds() -> i64 {
+    60
+}
+
+fn default_job_retry_backoff_seconds() -> i64 {
+    30
+}
+
+/// how a job retries after a failed or timed-out attempt.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct JobRetry {
+    pub max_retries: i64,
+    pub backoff_seconds: i64,
+}
+
+impl JobRetry {
+    /// the wait after `attempt` fails before the next one is dispatched.
+    pub fn delay_seconds(&self, attempt: i64) -> i64 {
+        let doublings = u32::try_from(attempt.saturating_sub(1).clamp(0, 31)).unwrap_or(31);
+        self.backoff_seconds
+            .saturating_mul(1_i64 << doublings)
+            .min(MAX_JOB_RETRY_BACKOFF_SECONDS)
+    }
+}
+
+impl JobRequest {
+    /// validate the request's retry settings.
+    pub fn retry(&self) -> Result<JobRetry, String> {
+        if !(0..=MAX_JOB_RETRIES).contains(&self.max_retries) {
+            return Err(format!(
+                "max_retries must be between 0 and {MAX_JOB_RETRIES}"
+            ));
+        }
+        if !(1..=MAX_JOB_RETRY_BACKOFF_SECONDS).contains(&self.retry_backoff_seconds) {
+            return Err(format!(
+                "retry_backoff_seconds must be between 1 and {MAX_JOB_RETRY_BACKOFF_SECONDS}"
+            ));
+        }
+        Ok(JobRetry {
+            max_retries: self.max_retries,
+            backoff_seconds: self.retry_backoff_seconds,
+        })
+    }
+
+    /// validate the request and build the action a worker will run.
+    pub fn into_action(self) -> Result<WorkflowAction, String> {
+        if self.action_name.trim().is_empty() || self.action_function.trim().is_empty() {
+            return Err("action_name and action_function are required".into());
+        }
+        if !(1..=MAX_JOB_TIMEOUT_SECONDS).contains(&self.timeout_seconds) {
+            return Err(format!(
+                "timeout_seconds must be between 1 and {MAX_JOB_TIMEOUT_SECONDS}"
+            ));
+        }
+        let workflow_only = WORKFLOW_ONLY_ACTIONS.iter().any(|(provider, function)| {
+            *provider == self.action_name
+                && function.is_none_or(|function| function == self.action_function)
+        });
+        if workflow_only {
+            return Err(format!(
+                "{}.{} can only run inside a workflow",
+                self.action_name, self.action_function
+            ));
+        }
+        if let Some(queue) = &self.queue
+            && !queue
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "-_.".contains(c))
+        {
+            return Err(format!(
+                "queue '{queue}' may only hold ascii letters, digits, '-', '_', and '.'"
+            ));
+        }
+        let configuration = WorkflowObject::from_value(self.configuration)
+            .map_err(|err| format!("configuration {err}"))?;
+        Ok(WorkflowAction {
+            provider: self.action_name,
+            function: self.action_function,
+            timeout_seconds: self.timeout_seconds,
+            configuration,
+            mcp_enabled: false,
+            tags: Vec::new(),
+            required_labels: self.required_labels,
+            allowed_secrets: Vec::new(),
+            preflight: Vec::new(),
+            cache_ttl_seconds: None,
+            priority: self.priority,
+            queue: self.queue.filter(|queue| !queue.trim().is_empty()),
+        })
+    }
+}
+
+/// a submitted job and, once a worker reports back, its outcome.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Job {
+    pub id: Uuid,
+    pub action: WorkflowAction,
+    pub status: WorkflowStatus,
+    #[serde(default)]
+    pub output_json: Option<Value>,
+    #[serde(default)]
+    pub message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requested_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub started_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub finished_at: Option<DateTime<Utc>>,
+    /// the attempt running now, or the last one once the job has finished. starts at 1.
+    #[serde(default = "default_job_attempt")]
+    pub attempt: i64,
+    #[serde(default)]
+    pub max_retries: i64,
+    #[serde(default)]
+    pub retry_backoff_seconds: i64,
+    /// every finished attempt, oldest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attempts: Vec<JobAttempt>,
+}
+
+fn default_job_attempt() -> i64 {
+    1
+}
+
+impl Job {
+    pub fn retry(&self) -> JobRetry {
+        JobRetry {
+            max_retries: self.max_retries,
+            backoff_seconds: self.retry_backoff_seconds,
+        }
+    }
+}
+
+/// the outcome of one dispatch of a job.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobAttempt {
+    pub attempt: i64,
+    pub status: WorkflowStatus,
+    #[serde(default)]
+    pub output_json: Option<Value>,
+    #[serde(default)]
+    pub message: Option<String>,
+    #[serde(default)]
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: DateTime<Utc>,
+}
@@ -1,24 +1,44 @@
 pub mod api_routes;
 pub mod auth;
+pub mod backfills;
 pub mod billing;
+pub mod blackouts;
 pub mod bundles;
 pub mod capabilities;
 pub mod catalog_metadata;
+pub mod cluster;
 pub mod core;
 pub mod debug;
 pub mod errors;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+pub mod jobs;
 pub mod notifications;
 pub mod orchestration;
 pub mod orgs;
+pub mod output_refs;
+pub mod overview;
 pub mod pipelines;
+pub mod preflight;
+pub mod protection;
+pub mod provider_stats;
 pub mod providers;
 pub mod provisioning;
+pub mod queue_wait;
 pub mod replicas;
+pub mod retention;
+pub mod revisions;
+pub mod run_health;
 pub mod runs;
+pub mod schedule_shift;
+pub mod secret_refs;
 pub mod semver;
 pub mod settings;
 pub mod telemetry;
+pub mod templates;
 pub mod types;
+pub mod upcoming;
+pub mod update_channel;
 pub mod value;
 pub mod web;
 pub mod workflow_ast;
@@ -0,0 +1,173 @@
+//! `{{output:<workflow>.<key>}}` references to the small named outputs a workflow run records, and
+//! the limits on what a run may record. a reference reads the value the referenced workflow's most
+//! recent successful run recorded under `key`.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use crate::value::Value;
+
+/// most outputs one run may record.
+pub const MAX_RUN_OUTPUTS: usize = 64;
+/// longest output name.
+pub const MAX_OUTPUT_NAME_LEN: usize = 128;
+/// longest string output value, in bytes.
+pub const MAX_OUTPUT_STRING_LEN: usize = 4096;
+
+const OPEN: &str = "{{output:";
+const CLOSE: &str = "}}";
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OutputRef {
+    /// the workflow name, namespace-qualified or not.
+    pub workflow: String,
+    pub key: String,
+}
+
+impl fmt::Display for OutputRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.workflow, self.key)
+    }
+}
+
+/// parse the inside of `{{output:...}}`. the key follows the last `.`, so a namespaced workflow name
+/// keeps its own dots.
+fn parse_output_ref(inner: &str) -> Option<OutputRef> {
+    let (workflow, key) = inner.trim().rsplit_once('.')?;
+    if workflow.is_empty() || !valid_output_name(key) {
+        return None;
+    }
+    Some(OutputRef {
+        workflow: workflow.to_string(),
+        key: key.to_string(),
+    })
+}
+
+// each `{{output:...}}` in `text` as (start, end, reference); malformed ones are left as text.
+fn find_output_refs(text: &str) -> Vec<(usize, usize, OutputRef)> {
+    let mut found = Vec::new();
+    let mut offset = 0;
+    while let Some(start) = text[offset..].find(OPEN).map(|start| offset + start) {
+        let inner_start = start + OPEN.len();
+        let Some(end) = text[inner_start..].find(CLOSE).map(|end| inner_start + end) else {
+            break;
+        };
+        if let Some(output_ref) = parse_output_ref(&text[inner_start..end]) {
+            found.push((start, end + CLOSE.len(), output_ref));
+        }
+        offset = end + CLOSE.len();
+    }
+    found
+}
+
+/// every output reference anywhere in `value`.
+pub fn collect_output_refs(value: &Value, refs: &mut BTreeSet<OutputRef>) {
+    match value {
+        Value::String(text) => {
+            refs.extend(
+                find_output_refs(text)
+                    .into_iter()
+                    .map(|(_, _, output_ref)| output_ref),
+            );
+        }
+        Value::Array(values) => {
+            for value in values {
+                collect_output_refs(value, refs);
+            }
+        }
+        Value::Object(object) => {
+            for value in object.values() {
+                collect_output_refs(value, refs);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// replace every output reference in `value` with its entry in `outputs`. a string that is exactly
+/// one reference takes the output's own type; otherwise the output is spliced into the text. the
+/// error is the first reference `outputs` has no value for.
+pub fn substitute_output_refs(
+    value: &Value,
+    outputs: &BTreeMap<OutputRef, Value>,
+) -> Result<Value, OutputRef> {
+    match value {
+        Value::String(text) => {
+            let found = find_output_refs(text);
+            if found.is_empty() {
+                return Ok(value.clone());
+            }
+            if let [(0, end, output_ref)] = found.as_slice()
+                && *end == text.len()
+            {
+                return outputs
+                    .get(output_ref)
+                    .cloned()
+                    .ok_or_else(|| output_ref.clone());
+            }
+            let mut rendered = String::with_capacity(text.len());
+            let mut last = 0;
+            for (start, end, output_ref) in found {
+                let output = outputs.get(&output_ref).ok_or(output_ref)?;
+                rendered.push_str(&text[last..start]);
+                match output {
+                    Value::String(output) => rendered.push_str(output),
+                    other => rendered.push_str(&other.to_string()),
+                }
+                last = end;
+            }
+            rendered.push_str(&text[last..]);
+            Ok(Value::String(rendered))
+        }
+        Value::Array(values) => values
+            .iter()
+            .map(|value| substitute_output_refs(value, outputs))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Value::Array),
+        Value::Object(object) => {
+            let mut substituted = object.clone();
+            for (key, value) in object {
+                substituted.insert(key.clone(), substitute_output_refs(value, outputs)?);
+            }
+            Ok(Value::Object(substituted))
+        }
+        _ => Ok(value.clone()),
+    }
+}
+
+fn valid_output_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= MAX_OUTPUT_NAME_LEN
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '-')
+}
+
+/// check the outputs a run records: at most [`MAX_RUN_OUTPUTS`], names of letters, digits, `_` and
+/// `-`, and values that are numbers or strings of at most [`MAX_OUTPUT_STRING_LEN`] bytes.
+pub fn validate_run_outputs(outputs: &BTreeMap<String, Value>) -> Result<(), String> {
+    if outputs.len() > MAX_RUN_OUTPUTS {
+        return Err(format!(
+            "a run may record at most {MAX_RUN_OUTPUTS} outputs, got {}",
+            outputs.len()
+        ));
+    }
+    for (name, value) in outputs {
+        if !valid_output_name(name) {
+            return Err(format!(
+                "output name '{name}' must be 1-{MAX_OUTPUT_NAME_LEN} letters, digits, '_' or '-'"
+            ));
+        }
+        match value {
+            Value::Number(_) => {}
+            Value::String(text) if text.len() <= MAX_OUTPUT_STRING_LEN => {}
+            Value::String(_) => {
+                return Err(format!(
+                    "output '{name}' is longer than {MAX_OUTPUT_STRING_LEN} bytes"
+                ));
+            }
+            _ => return Err(format!("output '{name}' must be a string or a number")),
+        }
+    }
+    Ok(())
+}
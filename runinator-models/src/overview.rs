@@ -0,0 +1,147 @@
+//! workflow overview: every visible workflow with its most recent runs and the replicas that
+//! executed them, assembled server-side so a dashboard renders from one request instead of
+//! chaining workflow, run, node-run, and replica calls.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    replicas::{ReplicaKind, ReplicaRecord, ReplicaStatus},
+    workflows::{WorkflowDefinition, WorkflowNodeRun, WorkflowRun, WorkflowStatus},
+};
+
+/// runs listed per workflow when the caller does not ask for a count.
+pub const DEFAULT_OVERVIEW_RUNS: usize = 10;
+/// hard ceiling on runs per workflow, so one request can't pull a workflow's whole history.
+pub const MAX_OVERVIEW_RUNS: usize = 50;
+
+/// a replica that executed part of a run. the descriptive fields are `None` once the replica's
+/// record has been pruned.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ExecutorRef {
+    pub replica_id: Uuid,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replica_type: Option<ReplicaKind>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<ReplicaStatus>,
+}
+
+impl ExecutorRef {
+    fn unknown(replica_id: Uuid) -> Self {
+        ExecutorRef {
+            replica_id,
+            replica_type: None,
+            display_name: None,
+            status: None,
+        }
+    }
+}
+
+impl From<&ReplicaRecord> for ExecutorRef {
+    fn from(record: &ReplicaRecord) -> Self {
+        ExecutorRef {
+            replica_id: record.replica_id,
+            replica_type: Some(record.replica_type),
+            display_name: record.display_name.clone(),
+            status: Some(record.status),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunOverview {
+    pub id: Uuid,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub status: WorkflowStatus,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+    /// distinct replicas that executed the run's nodes, in node order.
+    pub executors: Vec<ExecutorRef>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowOverview {
+    pub workflow_id: Uuid,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    pub enabled: bool,
+    /// the most recent runs, newest first.
+    pub runs: Vec<RunOverview>,
+}
+
+/// the distinct replicas that executed `node_runs`, in order, preferring the live claim over the
+/// last released one.
+pub fn executor_ids(node_runs: &[WorkflowNodeRun]) -> Vec<Uuid> {
+    let mut ids = Vec::new();
+    for node_run in node_runs {
+        let Some(id) = node_run
+            .current_executor_replica_id
+            .or(node_run.last_executor_replica_id)
+        else {
+            continue;
+        };
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+    ids
+}
+
+impl WorkflowOverview {
+    /// the overview of `workflow` from its `runs`, keeping the newest `limit`. `executors` maps run
+    /// ids to [`executor_ids`]; `replicas` resolves those ids. `None` for an unsaved workflow.
+    pub fn build(
+        workflow: &WorkflowDefinition,
+        runs: &[WorkflowRun],
+        limit: usize,
+        executors: &HashMap<Uuid, Vec<Uuid>>,
+        replicas: &HashMap<Uuid, ExecutorRef>,
+    ) -> Option<Self> {
+        let workflow_id = workflow.id?;
+        let mut runs = runs
+            .iter()
+            .filter(|run| run.workflow_id == workflow_id)
+            .collect::<Vec<_>>();
+        runs.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(b.id.cmp(&a.id)));
+        runs.truncate(limit);
+
+        let runs = runs
+            .into_iter()
+            .map(|run| RunOverview {
+                id: run.id,
+                name: run.name.clone(),
+                status: run.status,
+                created_at: run.created_at,
+                started_at: run.started_at,
+                finished_at: run.finished_at,
+                executors: executors
+                    .get(&run.id)
+                    .into_iter()
+                    .flatten()
+                    .map(|id| {
+                        replicas
+                            .get(id)
+                            .cloned()
+                            .unwrap_or_else(|| ExecutorRef::unknown(*id))
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        Some(WorkflowOverview {
+            workflow_id,
+            name: workflow.name.clone(),
+            namespace: workflow.namespace.clone(),
+            enabled: workflow.enabled,
+            runs,
+        })
+    }
+}
@@ -4,7 +4,7 @@ use uuid::Uuid;
 
 use crate::replicas::{TriggerActorType, TriggerSourceKind};
 use crate::value::Value;
-use crate::workflows::{WorkflowRun, WorkflowStatus, WorkflowTriggerKind};
+use crate::workflows::{WorkflowRun, WorkflowStatus, WorkflowTriggerKind, trigger_schedule_error};
 
 /// what happens to downstream links when a member workflow fails. authoring-only: it seeds the
 /// `on` selector of newly drawn links (`Halt` -> fire on success, `Continue` -> fire on complete).
@@ -188,6 +188,13 @@ pub struct PipelineTrigger {
     pub updated_at: Option<DateTime<Utc>>,
 }
 
+impl PipelineTrigger {
+    /// why the trigger loop cannot schedule this trigger, when its cron last failed to parse.
+    pub fn schedule_error(&self) -> Option<&str> {
+        trigger_schedule_error(&self.metadata)
+    }
+}
+
 /// a first-class pipeline execution. an orchestration envelope over the member workflow runs it
 /// starts: each member run is stamped with this run's id, and the run settles when the reachable
 /// member graph reaches terminal. status reuses [`WorkflowStatus`] (only queued/running/waiting and
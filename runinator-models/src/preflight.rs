@@ -0,0 +1,77 @@
+//! pre-flight checks an action declares in its `preflight` list. the worker runs them before
+//! resolving secrets or invoking the provider, so an export that would die halfway (a full dump
+//! disk, a read-only mount, an unreachable target) fails fast with a clear message instead.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::semver::SemVer;
+
+/// how long a reachability check waits for a tcp connection unless it sets `timeout_ms`.
+pub const DEFAULT_REACHABLE_TIMEOUT_MS: u64 = 5_000;
+
+fn default_reachable_timeout_ms() -> u64 {
+    DEFAULT_REACHABLE_TIMEOUT_MS
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PreflightCheck {
+    /// the filesystem holding `path` has at least `min_free_mb` megabytes available.
+    FreeDisk { path: String, min_free_mb: u64 },
+    /// `path` is an existing directory the worker can create files in.
+    Writable { path: String },
+    /// a tcp connection to `host:port` opens within `timeout_ms`.
+    Reachable {
+        host: String,
+        port: u16,
+        #[serde(default = "default_reachable_timeout_ms")]
+        timeout_ms: u64,
+    },
+    /// the action's provider reports version `min` or newer, so an action written against a newer
+    /// provider is refused by a worker that has not been upgraded yet.
+    ProviderVersion { min: String },
+}
+
+impl PreflightCheck {
+    /// reject a declaration that could never pass, before the workflow is saved.
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            PreflightCheck::FreeDisk { path, .. } | PreflightCheck::Writable { path }
+                if path.trim().is_empty() =>
+            {
+                Err(format!("{self} needs a path"))
+            }
+            PreflightCheck::Reachable { host, .. } if host.trim().is_empty() => {
+                Err(format!("{self} needs a host"))
+            }
+            PreflightCheck::Reachable { port: 0, .. } => Err(format!("{self} needs a port")),
+            PreflightCheck::Reachable { timeout_ms: 0, .. } => {
+                Err(format!("{self} needs a non-zero timeout_ms"))
+            }
+            PreflightCheck::ProviderVersion { min } => min
+                .parse::<SemVer>()
+                .map(|_| ())
+                .map_err(|err| format!("{self} needs a valid version: {err}")),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for PreflightCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PreflightCheck::FreeDisk { path, min_free_mb } => {
+                write!(f, "free_disk check ({min_free_mb} MB at '{path}')")
+            }
+            PreflightCheck::Writable { path } => write!(f, "writable check ('{path}')"),
+            PreflightCheck::Reachable { host, port, .. } => {
+                write!(f, "reachable check ({host}:{port})")
+            }
+            PreflightCheck::ProviderVersion { min } => {
+                write!(f, "provider_version check (at least {min})")
+            }
+        }
+    }
+}
@@ -0,0 +1,198 @@
+//! second-approver protection for critical workflows. edits, deletes, enable/disable, ownership
+//! and retention changes, trigger edits, and lifting the protection itself are held as pending
+//! changes on a protected workflow, and only apply once a different principal than the one who
+//! asked approves them.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::retention::WorkflowRetention;
+use crate::value::Value;
+use crate::workflows::{WorkflowDefinition, WorkflowTrigger};
+
+/// a workflow's protection flag; present only while the workflow is protected.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WorkflowProtection {
+    pub workflow_id: Uuid,
+    /// the principal that turned protection on; `None` when auth is disabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protected_by: Option<Uuid>,
+    #[serde(default)]
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+/// the mutation a pending change applies once approved.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PendingChangeKind {
+    /// save the workflow in `payload`.
+    Update,
+    Delete,
+    Enable,
+    Disable,
+    /// lift the workflow's protection.
+    Unprotect,
+    /// move the workflow to the org in `payload` (`{"org_id": ...}`).
+    SetOwner,
+    /// set the retention override in `payload` (`{"retention_days": ...}`).
+    SetRetention,
+    ClearRetention,
+    /// save the trigger in `payload`.
+    UpsertTrigger,
+    /// delete the trigger in `payload`.
+    DeleteTrigger,
+}
+
+impl PendingChangeKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PendingChangeKind::Update => "update",
+            PendingChangeKind::Delete => "delete",
+            PendingChangeKind::Enable => "enable",
+            PendingChangeKind::Disable => "disable",
+            PendingChangeKind::Unprotect => "unprotect",
+            PendingChangeKind::SetOwner => "set_owner",
+            PendingChangeKind::SetRetention => "set_retention",
+            PendingChangeKind::ClearRetention => "clear_retention",
+            PendingChangeKind::UpsertTrigger => "upsert_trigger",
+            PendingChangeKind::DeleteTrigger => "delete_trigger",
+        }
+    }
+}
+
+impl TryFrom<&str> for PendingChangeKind {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "update" => Ok(PendingChangeKind::Update),
+            "delete" => Ok(PendingChangeKind::Delete),
+            "enable" => Ok(PendingChangeKind::Enable),
+            "disable" => Ok(PendingChangeKind::Disable),
+            "unprotect" => Ok(PendingChangeKind::Unprotect),
+            "set_owner" => Ok(PendingChangeKind::SetOwner),
+            "set_retention" => Ok(PendingChangeKind::SetRetention),
+            "clear_retention" => Ok(PendingChangeKind::ClearRetention),
+            "upsert_trigger" => Ok(PendingChangeKind::UpsertTrigger),
+            "delete_trigger" => Ok(PendingChangeKind::DeleteTrigger),
+            other => Err(format!("Unknown pending change kind '{other}'")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PendingChangeStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+impl PendingChangeStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PendingChangeStatus::Pending => "pending",
+            PendingChangeStatus::Approved => "approved",
+            PendingChangeStatus::Rejected => "rejected",
+        }
+    }
+}
+
+impl TryFrom<&str> for PendingChangeStatus {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "pending" => Ok(PendingChangeStatus::Pending),
+            "approved" => Ok(PendingChangeStatus::Approved),
+            "rejected" => Ok(PendingChangeStatus::Rejected),
+            other => Err(format!("Unknown pending change status '{other}'")),
+        }
+    }
+}
+
+/// input for holding a mutation to a protected workflow for approval.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewPendingWorkflowChange {
+    pub workflow_id: Uuid,
+    pub kind: PendingChangeKind,
+    /// what the change applies: the workflow for an `update`, the trigger for a trigger change,
+    /// the new setting for an owner or retention change; `null` otherwise.
+    #[serde(default)]
+    pub payload: Value,
+    #[serde(default)]
+    pub requested_by: Option<Uuid>,
+}
+
+/// the payload of a `set_owner` change.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WorkflowOwnerChange {
+    /// the org to own the workflow; `None` makes it platform-global.
+    #[serde(default)]
+    pub org_id: Option<Uuid>,
+}
+
+/// a mutation to a protected workflow, held until a second principal reviews it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingWorkflowChange {
+    pub id: Uuid,
+    pub workflow_id: Uuid,
+    pub kind: PendingChangeKind,
+    #[serde(default)]
+    pub payload: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requested_by: Option<Uuid>,
+    pub status: PendingChangeStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reviewed_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub reviewed_at: Option<DateTime<Utc>>,
+}
+
+impl PendingWorkflowChange {
+    /// the workflow an `update` saves.
+    pub fn workflow(&self) -> Result<WorkflowDefinition, String> {
+        self.payload
+            .decode()
+            .map_err(|err| format!("pending change {} has no valid workflow: {err}", self.id))
+    }
+
+    /// the trigger an `upsert_trigger` saves or a `delete_trigger` removes.
+    pub fn trigger(&self) -> Result<WorkflowTrigger, String> {
+        self.payload
+            .decode()
+            .map_err(|err| format!("pending change {} has no valid trigger: {err}", self.id))
+    }
+
+    /// the org a `set_owner` moves the workflow to.
+    pub fn owner(&self) -> Result<WorkflowOwnerChange, String> {
+        self.payload
+            .decode()
+            .map_err(|err| format!("pending change {} has no valid owner: {err}", self.id))
+    }
+
+    /// the override a `set_retention` stores.
+    pub fn retention(&self) -> Result<WorkflowRetention, String> {
+        self.payload
+            .decode()
+            .map_err(|err| format!("pending change {} has no valid retention: {err}", self.id))
+    }
+
+    /// an approver must be an authenticated principal other than the requester, so one credential
+    /// can never both ask for and approve a change.
+    pub fn check_approver(&self, approver: Option<Uuid>) -> Result<(), &'static str> {
+        let Some(approver) = approver else {
+            return Err(
+                "approving a change to a protected workflow needs an authenticated principal",
+            );
+        };
+        if self.requested_by == Some(approver) {
+            return Err(
+                "a change must be approved by a different principal than the one who requested it",
+            );
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,149 @@
+//! run counts and durations per provider action over recent windows, for capacity planning: which
+//! plugins carry enough load to deserve dedicated workers. the database aggregates node runs per
+//! task; tasks are attributed to a provider action through their workflow's current definition.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// windows the provider stats report covers when none are requested: the last hour, day, and week.
+pub const DEFAULT_PROVIDER_STATS_WINDOW_HOURS: &[i64] = &[1, 24, 24 * 7];
+
+/// node-run totals for one task (a node of one workflow) since a cutoff, aggregated in the database.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NodeRunCounts {
+    pub workflow_id: Uuid,
+    pub node_id: String,
+    pub runs: i64,
+    pub succeeded: i64,
+    /// failed or timed out.
+    pub failed: i64,
+    /// runs with both a start and a finish, the ones `duration_ms` sums over.
+    pub timed_runs: i64,
+    pub duration_ms: i64,
+}
+
+/// run totals for a provider or one of its actions.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct RunCounts {
+    /// distinct tasks (workflow nodes) that ran the action in the window.
+    pub tasks: i64,
+    pub runs: i64,
+    pub succeeded: i64,
+    pub failed: i64,
+    /// mean wall time of the runs that finished; `None` when none did.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub average_duration_ms: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionStats {
+    pub function: String,
+    #[serde(flatten)]
+    pub counts: RunCounts,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderStats {
+    pub provider: String,
+    #[serde(flatten)]
+    pub counts: RunCounts,
+    /// the provider's actions, most runs first.
+    pub actions: Vec<ActionStats>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderStatsWindow {
+    pub hours: i64,
+    pub since: DateTime<Utc>,
+    /// providers, most runs first.
+    pub providers: Vec<ProviderStats>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderStatsReport {
+    pub generated_at: DateTime<Utc>,
+    pub windows: Vec<ProviderStatsWindow>,
+}
+
+#[derive(Default)]
+struct Totals {
+    tasks: HashSet<(Uuid, String)>,
+    runs: i64,
+    succeeded: i64,
+    failed: i64,
+    timed_runs: i64,
+    duration_ms: i64,
+}
+
+impl Totals {
+    fn add(&mut self, counts: &NodeRunCounts) {
+        self.tasks
+            .insert((counts.workflow_id, counts.node_id.clone()));
+        self.runs += counts.runs;
+        self.succeeded += counts.succeeded;
+        self.failed += counts.failed;
+        self.timed_runs += counts.timed_runs;
+        self.duration_ms += counts.duration_ms;
+    }
+
+    fn into_counts(self) -> RunCounts {
+        RunCounts {
+            tasks: self.tasks.len() as i64,
+            runs: self.runs,
+            succeeded: self.succeeded,
+            failed: self.failed,
+            average_duration_ms: (self.timed_runs > 0).then(|| self.duration_ms / self.timed_runs),
+        }
+    }
+}
+
+impl ProviderStatsWindow {
+    /// roll per-task `counts` up to provider actions. `actions` maps a task to the provider and
+    /// function it runs; tasks without an entry (control nodes, deleted workflows) are skipped.
+    pub fn build(
+        hours: i64,
+        since: DateTime<Utc>,
+        counts: &[NodeRunCounts],
+        actions: &HashMap<(Uuid, String), (String, String)>,
+    ) -> Self {
+        let mut by_provider: BTreeMap<&str, (Totals, BTreeMap<&str, Totals>)> = BTreeMap::new();
+        for task in counts {
+            let Some((provider, function)) = actions.get(&(task.workflow_id, task.node_id.clone()))
+            else {
+                continue;
+            };
+            let (provider_totals, functions) = by_provider.entry(provider).or_default();
+            provider_totals.add(task);
+            functions.entry(function).or_default().add(task);
+        }
+
+        let mut providers = by_provider
+            .into_iter()
+            .map(|(provider, (totals, functions))| {
+                let mut actions = functions
+                    .into_iter()
+                    .map(|(function, totals)| ActionStats {
+                        function: function.to_string(),
+                        counts: totals.into_counts(),
+                    })
+                    .collect::<Vec<_>>();
+                actions.sort_by_key(|action| std::cmp::Reverse(action.counts.runs));
+                ProviderStats {
+                    provider: provider.to_string(),
+                    counts: totals.into_counts(),
+                    actions,
+                }
+            })
+            .collect::<Vec<_>>();
+        providers.sort_by_key(|provider| std::cmp::Reverse(provider.counts.runs));
+
+        ProviderStatsWindow {
+            hours,
+            since,
+            providers,
+        }
+    }
+}
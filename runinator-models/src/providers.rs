@@ -20,6 +20,10 @@ pub struct ProviderRuntimeMetadata {
     pub credential_scopes: Vec<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub contract: Option<String>,
+    /// the provider's own version, checked by `provider_version` pre-flight checks and the update
+    /// channel. built-in providers without one report the worker build.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -0,0 +1,122 @@
+//! queue wait-time aggregates: how long node commands sat in the broker before a worker started
+//! them, summarized globally and per task (workflow node) over a recent window.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// default look-back for the queue wait report.
+pub const DEFAULT_QUEUE_WAIT_WINDOW_HOURS: i64 = 24;
+
+/// one node run's recorded queue wait.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueueWaitSample {
+    pub workflow_id: Uuid,
+    pub node_id: String,
+    pub queue_wait_ms: i64,
+    pub started_at: DateTime<Utc>,
+}
+
+/// wait-time distribution over a set of samples. percentiles use the nearest-rank method.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct QueueWaitStats {
+    pub count: i64,
+    pub average_ms: i64,
+    pub p50_ms: i64,
+    pub p95_ms: i64,
+    pub max_ms: i64,
+    /// samples whose wait exceeded the report's alert threshold.
+    pub over_threshold: i64,
+}
+
+impl QueueWaitStats {
+    fn from_waits(mut waits: Vec<i64>, threshold_ms: Option<i64>) -> Self {
+        if waits.is_empty() {
+            return Self::default();
+        }
+        waits.sort_unstable();
+        let count = waits.len();
+        let rank = |percentile: usize| waits[(count * percentile).div_ceil(100).max(1) - 1];
+        QueueWaitStats {
+            count: count as i64,
+            average_ms: waits.iter().sum::<i64>() / count as i64,
+            p50_ms: rank(50),
+            p95_ms: rank(95),
+            max_ms: waits[count - 1],
+            over_threshold: threshold_ms
+                .map(|threshold| waits.iter().filter(|wait| **wait > threshold).count() as i64)
+                .unwrap_or(0),
+        }
+    }
+}
+
+/// queue wait for one task: a node of one workflow.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskQueueWait {
+    pub workflow_id: Uuid,
+    pub workflow_name: String,
+    pub node_id: String,
+    #[serde(flatten)]
+    pub stats: QueueWaitStats,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueueWaitReport {
+    pub since: DateTime<Utc>,
+    /// the alert threshold the report counts against; `None` when alerting is disabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub threshold_ms: Option<i64>,
+    pub overall: QueueWaitStats,
+    /// per-task aggregates, longest p95 wait first.
+    pub tasks: Vec<TaskQueueWait>,
+}
+
+impl QueueWaitReport {
+    /// aggregate `samples` started at or after `since`. `names` maps workflow ids to display names.
+    pub fn build(
+        since: DateTime<Utc>,
+        threshold_ms: Option<i64>,
+        samples: &[QueueWaitSample],
+        names: &HashMap<Uuid, String>,
+    ) -> Self {
+        let mut overall = Vec::new();
+        let mut by_task: HashMap<(Uuid, &str), Vec<i64>> = HashMap::new();
+        for sample in samples.iter().filter(|sample| sample.started_at >= since) {
+            let wait = sample.queue_wait_ms.max(0);
+            overall.push(wait);
+            by_task
+                .entry((sample.workflow_id, sample.node_id.as_str()))
+                .or_default()
+                .push(wait);
+        }
+
+        let mut tasks = by_task
+            .into_iter()
+            .map(|((workflow_id, node_id), waits)| TaskQueueWait {
+                workflow_id,
+                workflow_name: names
+                    .get(&workflow_id)
+                    .cloned()
+                    .unwrap_or_else(|| workflow_id.to_string()),
+                node_id: node_id.to_string(),
+                stats: QueueWaitStats::from_waits(waits, threshold_ms),
+            })
+            .collect::<Vec<_>>();
+        tasks.sort_by(|a, b| {
+            b.stats
+                .p95_ms
+                .cmp(&a.stats.p95_ms)
+                .then_with(|| a.workflow_name.cmp(&b.workflow_name))
+                .then_with(|| a.node_id.cmp(&b.node_id))
+        });
+
+        QueueWaitReport {
+            since,
+            threshold_ms,
+            overall: QueueWaitStats::from_waits(overall, threshold_ms),
+            tasks,
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -180,6 +182,94 @@ pub struct ReplicaRecord {
     pub registered_by_org_id: Option<Uuid>,
 }
 
+/// a worker as the registry sees it: cluster membership, what it can run, and how busy it is. built
+/// from the worker's replica record; `plugins` and `max_concurrent_actions` come from its
+/// registration attributes and `in_flight_actions` from the `load` it reports on each heartbeat.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkerSummary {
+    pub replica_id: Uuid,
+    pub instance_id: String,
+    pub status: ReplicaStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+    /// plugin libraries the worker loaded at startup, sorted.
+    #[serde(default)]
+    pub plugins: Vec<String>,
+    /// providers the worker registered with the api, sorted.
+    #[serde(default)]
+    pub providers: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_actions: Option<u64>,
+    /// actions executing as of the last heartbeat; `None` for a worker that does not report load.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub in_flight_actions: Option<u64>,
+    /// node runs whose executor lease this worker currently holds.
+    #[serde(default)]
+    pub running_node_runs: i64,
+    pub first_seen_at: DateTime<Utc>,
+    pub last_heartbeat_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+}
+
+impl WorkerSummary {
+    pub fn from_replica(
+        replica: &ReplicaRecord,
+        mut providers: Vec<String>,
+        running_node_runs: i64,
+    ) -> Self {
+        let attributes = &replica.attributes;
+        let labels = attributes
+            .get("labels")
+            .and_then(Value::as_object)
+            .map(|labels| {
+                labels
+                    .iter()
+                    .filter_map(|(key, value)| Some((key.clone(), value.as_str()?.to_string())))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let mut plugins = attributes
+            .get("plugins")
+            .and_then(Value::as_array)
+            .map(|plugins| {
+                plugins
+                    .iter()
+                    .filter_map(|plugin| plugin.as_str().map(str::to_string))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        plugins.sort();
+        providers.sort();
+        Self {
+            replica_id: replica.replica_id,
+            instance_id: replica.instance_id.clone(),
+            status: replica.status,
+            display_name: replica.display_name.clone(),
+            host: replica.host.clone(),
+            version: replica.version.clone(),
+            labels,
+            plugins,
+            providers,
+            max_concurrent_actions: attributes
+                .get("max_concurrent_actions")
+                .and_then(Value::as_u64),
+            in_flight_actions: attributes
+                .pointer("/load/in_flight_actions")
+                .and_then(Value::as_u64),
+            running_node_runs,
+            first_seen_at: replica.first_seen_at,
+            last_heartbeat_at: replica.last_heartbeat_at,
+            last_seen_at: replica.last_seen_at,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReplicaProviderRegistration {
     pub replica_id: Uuid,
@@ -254,6 +344,12 @@ pub enum TriggerSourceKind {
     Chained,
     /// a member workflow run started as part of a pipeline run.
     Pipeline,
+    /// a run started by a `file_arrival` trigger.
+    FileArrival,
+    /// a run started by a post to a `webhook` trigger's token url.
+    Webhook,
+    /// a past slot of a cron trigger replayed by a backfill.
+    Backfill,
 }
 
 impl TriggerSourceKind {
@@ -270,6 +366,9 @@ impl TriggerSourceKind {
             Self::Map => "map",
             Self::Chained => "chained",
             Self::Pipeline => "pipeline",
+            Self::FileArrival => "file_arrival",
+            Self::Webhook => "webhook",
+            Self::Backfill => "backfill",
         }
     }
 }
@@ -290,6 +389,9 @@ impl TryFrom<&str> for TriggerSourceKind {
             "map" => Ok(Self::Map),
             "chained" => Ok(Self::Chained),
             "pipeline" => Ok(Self::Pipeline),
+            "file_arrival" => Ok(Self::FileArrival),
+            "webhook" => Ok(Self::Webhook),
+            "backfill" => Ok(Self::Backfill),
             other => Err(format!("Unknown trigger source kind '{other}'")),
         }
     }
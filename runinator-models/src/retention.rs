@@ -0,0 +1,38 @@
+//! per-workflow run-history retention. a workflow with an override has its finished runs archived
+//! after its own window instead of the archiver's global workflow-run retention, so compliance
+//! jobs can keep a year of history while noisy health checks keep a week.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// the longest retention a workflow may override to: ten years.
+pub const MAX_RETENTION_DAYS: u32 = 3650;
+
+/// a workflow's run-history retention override.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WorkflowRetention {
+    pub workflow_id: Uuid,
+    /// finished runs older than this many days are archived.
+    pub retention_days: u32,
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateWorkflowRetentionRequest {
+    pub retention_days: u32,
+}
+
+impl UpdateWorkflowRetentionRequest {
+    /// reject a zero window (which would archive runs as soon as they finish) and anything past
+    /// [`MAX_RETENTION_DAYS`].
+    pub fn validate(&self) -> Result<(), String> {
+        if self.retention_days == 0 || self.retention_days > MAX_RETENTION_DAYS {
+            return Err(format!(
+                "retention_days must be between 1 and {MAX_RETENTION_DAYS}"
+            ));
+        }
+        Ok(())
+    }
+}
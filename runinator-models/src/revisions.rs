@@ -0,0 +1,36 @@
+//! workflow revision history. every save of a workflow appends a numbered revision holding the full
+//! record as it was saved, so a change can be reviewed against its predecessor ("who changed what,
+//! when") and a bad edit reverted by saving an earlier revision again.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::workflows::WorkflowDefinition;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowRevision {
+    pub id: Uuid,
+    pub workflow_id: Uuid,
+    /// 1 for the first recorded save, counting up per workflow.
+    pub revision: i64,
+    /// the workflow exactly as this revision saved it.
+    pub workflow: WorkflowDefinition,
+    /// the principal that saved it; `None` for system writes (imports, auth disabled).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub changed_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl WorkflowRevision {
+    /// the workflow as a revert to this revision would save it: the snapshot's definition and
+    /// settings under the live workflow's id, with timestamps left for the store to assign.
+    pub fn restore(&self) -> WorkflowDefinition {
+        WorkflowDefinition {
+            id: Some(self.workflow_id),
+            created_at: None,
+            updated_at: None,
+            ..self.workflow.clone()
+        }
+    }
+}
@@ -0,0 +1,304 @@
+//! periodic run-health digests: success rate, slowest workflows, and workflows that started
+//! failing, aggregated from workflow runs over a weekly or monthly window.
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::workflows::{WorkflowRun, WorkflowStatus};
+
+/// how many workflows the digest lists as slowest.
+pub const SLOWEST_WORKFLOW_LIMIT: usize = 5;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SummaryPeriod {
+    #[default]
+    Weekly,
+    Monthly,
+}
+
+impl SummaryPeriod {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SummaryPeriod::Weekly => "weekly",
+            SummaryPeriod::Monthly => "monthly",
+        }
+    }
+
+    /// the window length; a month is a rolling 30 days so consecutive digests never overlap.
+    pub fn length(self) -> Duration {
+        match self {
+            SummaryPeriod::Weekly => Duration::days(7),
+            SummaryPeriod::Monthly => Duration::days(30),
+        }
+    }
+}
+
+impl TryFrom<&str> for SummaryPeriod {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "weekly" | "week" => Ok(SummaryPeriod::Weekly),
+            "monthly" | "month" => Ok(SummaryPeriod::Monthly),
+            other => Err(format!(
+                "unknown summary period '{other}' (use weekly or monthly)"
+            )),
+        }
+    }
+}
+
+/// a workflow's finished-run durations within the period.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowDurationStat {
+    pub workflow_id: Uuid,
+    pub workflow_name: String,
+    pub runs: i64,
+    pub average_seconds: i64,
+    pub max_seconds: i64,
+}
+
+/// a workflow that failed during the period without having failed in the period before it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowFailureStat {
+    pub workflow_id: Uuid,
+    pub workflow_name: String,
+    pub failures: i64,
+    pub last_failed_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunHealthSummary {
+    pub period: SummaryPeriod,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    pub total_runs: i64,
+    pub succeeded: i64,
+    /// failed and timed-out runs.
+    pub failed: i64,
+    pub canceled: i64,
+    /// runs created in the period that have not reached a terminal status yet.
+    pub in_progress: i64,
+    /// succeeded / (succeeded + failed); `None` when nothing finished in the period.
+    pub success_rate: Option<f64>,
+    pub slowest: Vec<WorkflowDurationStat>,
+    pub new_failures: Vec<WorkflowFailureStat>,
+}
+
+impl RunHealthSummary {
+    /// aggregate `runs` into the period ending at `now`. runs from the preceding period decide
+    /// which failures are new, so callers should pass everything created since
+    /// `now - 2 * period.length()`. `names` maps workflow ids to display names.
+    pub fn build(
+        period: SummaryPeriod,
+        now: DateTime<Utc>,
+        runs: &[WorkflowRun],
+        names: &HashMap<Uuid, String>,
+    ) -> Self {
+        let period_start = now - period.length();
+        let previous_start = period_start - period.length();
+        let name_of = |workflow_id: Uuid| {
+            names
+                .get(&workflow_id)
+                .cloned()
+                .unwrap_or_else(|| workflow_id.to_string())
+        };
+
+        let mut summary = RunHealthSummary {
+            period,
+            period_start,
+            period_end: now,
+            total_runs: 0,
+            succeeded: 0,
+            failed: 0,
+            canceled: 0,
+            in_progress: 0,
+            success_rate: None,
+            slowest: Vec::new(),
+            new_failures: Vec::new(),
+        };
+        let mut previously_failed = HashSet::new();
+        let mut durations: HashMap<Uuid, Vec<i64>> = HashMap::new();
+        let mut failures: HashMap<Uuid, WorkflowFailureStat> = HashMap::new();
+
+        for run in runs {
+            if run.created_at > now {
+                continue;
+            }
+            let failed = matches!(
+                run.status,
+                WorkflowStatus::Failed | WorkflowStatus::TimedOut
+            );
+            if run.created_at < period_start {
+                if failed && run.created_at >= previous_start {
+                    previously_failed.insert(run.workflow_id);
+                }
+                continue;
+            }
+
+            summary.total_runs += 1;
+            match run.status {
+                WorkflowStatus::Succeeded => summary.succeeded += 1,
+                WorkflowStatus::Canceled => summary.canceled += 1,
+                _ if failed => summary.failed += 1,
+                _ => summary.in_progress += 1,
+            }
+            if let (true, Some(started), Some(finished)) =
+                (run.status.is_terminal(), run.started_at, run.finished_at)
+            {
+                durations
+                    .entry(run.workflow_id)
+                    .or_default()
+                    .push((finished - started).num_seconds().max(0));
+            }
+            if failed {
+                let failed_at = run.finished_at.unwrap_or(run.created_at);
+                let entry =
+                    failures
+                        .entry(run.workflow_id)
+                        .or_insert_with(|| WorkflowFailureStat {
+                            workflow_id: run.workflow_id,
+                            workflow_name: name_of(run.workflow_id),
+                            failures: 0,
+                            last_failed_at: failed_at,
+                            last_message: None,
+                        });
+                entry.failures += 1;
+                if failed_at >= entry.last_failed_at {
+                    entry.last_failed_at = failed_at;
+                    entry.last_message = run.message.clone();
+                }
+            }
+        }
+
+        let finished = summary.succeeded + summary.failed;
+        if finished > 0 {
+            summary.success_rate = Some(summary.succeeded as f64 / finished as f64);
+        }
+
+        let mut slowest = durations
+            .into_iter()
+            .map(|(workflow_id, seconds)| WorkflowDurationStat {
+                workflow_id,
+                workflow_name: name_of(workflow_id),
+                runs: seconds.len() as i64,
+                average_seconds: seconds.iter().sum::<i64>() / seconds.len() as i64,
+                max_seconds: seconds.iter().copied().max().unwrap_or(0),
+            })
+            .collect::<Vec<_>>();
+        slowest.sort_by(|a, b| {
+            b.average_seconds
+                .cmp(&a.average_seconds)
+                .then_with(|| a.workflow_name.cmp(&b.workflow_name))
+        });
+        slowest.truncate(SLOWEST_WORKFLOW_LIMIT);
+        summary.slowest = slowest;
+
+        let mut new_failures = failures
+            .into_values()
+            .filter(|stat| !previously_failed.contains(&stat.workflow_id))
+            .collect::<Vec<_>>();
+        new_failures.sort_by(|a, b| {
+            b.failures
+                .cmp(&a.failures)
+                .then_with(|| a.workflow_name.cmp(&b.workflow_name))
+        });
+        summary.new_failures = new_failures;
+        summary
+    }
+
+    /// a one-line subject suitable for an email or chat message header.
+    pub fn subject(&self) -> String {
+        let rate = self
+            .success_rate
+            .map(|rate| format!("{:.1}% success", rate * 100.0))
+            .unwrap_or_else(|| "no finished runs".into());
+        format!(
+            "Runinator {} health: {} runs, {}, {} new failure(s)",
+            self.period.as_str(),
+            self.total_runs,
+            rate,
+            self.new_failures.len()
+        )
+    }
+
+    /// the plain-text digest body.
+    pub fn render_text(&self) -> String {
+        let mut out = String::new();
+        out.push_str(&self.subject());
+        out.push_str("\n\n");
+        out.push_str(&format!(
+            "Period: {} to {}\n",
+            self.period_start.format("%Y-%m-%d %H:%M UTC"),
+            self.period_end.format("%Y-%m-%d %H:%M UTC")
+        ));
+        out.push_str(&format!(
+            "Runs: {} total, {} succeeded, {} failed, {} canceled, {} in progress\n",
+            self.total_runs, self.succeeded, self.failed, self.canceled, self.in_progress
+        ));
+
+        out.push_str("\nSlowest workflows (average duration):\n");
+        if self.slowest.is_empty() {
+            out.push_str("  none\n");
+        }
+        for stat in &self.slowest {
+            out.push_str(&format!(
+                "  {}  avg {}  max {}  ({} run(s))\n",
+                stat.workflow_name,
+                format_seconds(stat.average_seconds),
+                format_seconds(stat.max_seconds),
+                stat.runs
+            ));
+        }
+
+        out.push_str("\nNew failures:\n");
+        if self.new_failures.is_empty() {
+            out.push_str("  none\n");
+        }
+        for stat in &self.new_failures {
+            out.push_str(&format!(
+                "  {}  {} failure(s), last at {}",
+                stat.workflow_name,
+                stat.failures,
+                stat.last_failed_at.format("%Y-%m-%d %H:%M UTC")
+            ));
+            if let Some(message) = stat.last_message.as_deref() {
+                out.push_str(&format!(": {message}"));
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// the api payload: a summary together with its rendered digest, so a delivery action can forward
+/// `subject` and `text` without formatting anything itself.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunHealthReport {
+    pub summary: RunHealthSummary,
+    pub subject: String,
+    pub text: String,
+}
+
+impl From<RunHealthSummary> for RunHealthReport {
+    fn from(summary: RunHealthSummary) -> Self {
+        RunHealthReport {
+            subject: summary.subject(),
+            text: summary.render_text(),
+            summary,
+        }
+    }
+}
+
+fn format_seconds(seconds: i64) -> String {
+    match seconds {
+        s if s >= 3600 => format!("{}h{:02}m", s / 3600, (s % 3600) / 60),
+        s if s >= 60 => format!("{}m{:02}s", s / 60, s % 60),
+        s => format!("{s}s"),
+    }
+}
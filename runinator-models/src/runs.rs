@@ -1,6 +1,8 @@
+use crate::errors::extract_error_code;
 use crate::value::Value;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt};
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -72,6 +74,13 @@ pub struct RunSummary {
     pub created_at: DateTime<Utc>,
     pub workflow_run_id: Option<Uuid>,
     pub workflow_node_id: Option<String>,
+    /// the name of the workflow the run executes a node of, joined in so consumers need not look
+    /// it up; `None` for a standalone run or one whose workflow was deleted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workflow_name: Option<String>,
+    /// the worker that last reported the run's status; `None` until a reporter identifies itself.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worker_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -159,6 +168,9 @@ pub struct ProviderExecutionResponse {
     pub chunks: Vec<NewRunChunk>,
     #[serde(default)]
     pub artifacts: Vec<NewRunArtifact>,
+    /// set when the provider failed; the host reports it instead of an opaque exit code.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<ProviderError>,
 }
 
 impl From<ProviderExecutionResponse> for TaskExecutionResult {
@@ -223,3 +235,69 @@ impl From<ProviderExecutionEvent> for Option<NewRunArtifact> {
         }
     }
 }
+
+/// a provider failure with a machine-readable code. providers return it (boxed as the execution
+/// error, or as `error` in an ffi response) so run records, notifications, and retry decisions see
+/// the code and whether another attempt could help, not just a rendered string.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProviderError {
+    /// stable code, e.g. "GITHUB004" or "slack.rate_limited".
+    pub code: String,
+    pub message: String,
+    /// false when retrying cannot succeed (bad credentials, invalid input); the node's retry
+    /// policy is skipped and the failure transitions immediately.
+    #[serde(default = "default_retryable")]
+    pub retryable: bool,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub details: BTreeMap<String, Value>,
+}
+
+fn default_retryable() -> bool {
+    true
+}
+
+impl ProviderError {
+    /// a retryable failure.
+    pub fn new(code: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            code: code.into(),
+            message: message.into(),
+            retryable: true,
+            details: BTreeMap::new(),
+        }
+    }
+
+    /// a failure another attempt cannot fix.
+    pub fn permanent(code: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            retryable: false,
+            ..Self::new(code, message)
+        }
+    }
+
+    pub fn with_detail(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.details.insert(key.into(), value.into());
+        self
+    }
+
+    /// the structured form of any execution error: a boxed `ProviderError` as-is, anything else
+    /// as a retryable failure under its numbered code (or "UNKNOWN"), matching how untyped
+    /// failures have always been retried.
+    pub fn from_error(err: &(dyn std::error::Error + 'static)) -> Self {
+        if let Some(err) = err.downcast_ref::<ProviderError>() {
+            return err.clone();
+        }
+        Self::new(
+            extract_error_code(err).unwrap_or_else(|| "UNKNOWN".to_string()),
+            err.to_string(),
+        )
+    }
+}
+
+impl fmt::Display for ProviderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} - {}", self.code, self.message)
+    }
+}
+
+impl std::error::Error for ProviderError {}
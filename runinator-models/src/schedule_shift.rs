@@ -0,0 +1,243 @@
+//! bulk schedule shifts: move a set of cron triggers by a fixed offset, or re-spread them evenly
+//! across a daily window. only plain minute/hour lists are rewritten; steps, ranges, and aliases are
+//! reported as not rewritable and left alone.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+const MINUTES_PER_DAY: i64 = 24 * 60;
+
+/// how a bulk shift moves the selected schedules.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum ScheduleShiftMode {
+    /// move every schedule by `minutes` (negative moves earlier).
+    Offset { minutes: i64 },
+    /// give each schedule its own time of day, evenly spaced from `start` up to (not including)
+    /// `end`, both `HH:MM`. triggers are spread in the order they are selected.
+    Spread { start: String, end: String },
+}
+
+/// which cron triggers a bulk shift touches: the union of the listed workflows, every workflow in
+/// `namespace`, and the listed triggers. at least one selector is required.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleShiftRequest {
+    #[serde(default)]
+    pub workflow_ids: Vec<Uuid>,
+    #[serde(default)]
+    pub namespace: Option<String>,
+    #[serde(default)]
+    pub trigger_ids: Vec<Uuid>,
+    #[serde(flatten)]
+    pub mode: ScheduleShiftMode,
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+impl ScheduleShiftRequest {
+    pub fn has_selector(&self) -> bool {
+        !self.workflow_ids.is_empty() || self.namespace.is_some() || !self.trigger_ids.is_empty()
+    }
+}
+
+/// one selected cron trigger. `new_cron` is `None` (with `error` set) when its schedule cannot be
+/// rewritten; such triggers keep their schedule. a trigger on a `protected` workflow is not
+/// rewritten either: its new cron is held as a trigger save for a second approver, and
+/// `pending_change_id` names that held change.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleShiftChange {
+    pub trigger_id: Uuid,
+    pub workflow_id: Uuid,
+    pub workflow_name: String,
+    pub old_cron: String,
+    pub new_cron: Option<String>,
+    pub next_execution: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub protected: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pending_change_id: Option<Uuid>,
+}
+
+/// outcome of a bulk shift. on a dry run nothing is written; otherwise every rewritable trigger on
+/// an unprotected workflow was updated in one transaction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleShiftReport {
+    pub dry_run: bool,
+    /// triggers whose schedule was (or would be) rewritten; held triggers are not counted.
+    pub shifted: usize,
+    pub changes: Vec<ScheduleShiftChange>,
+}
+
+impl ScheduleShiftMode {
+    /// reject a malformed mode before any trigger is examined.
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            ScheduleShiftMode::Offset { minutes } if *minutes == 0 => {
+                Err("offset must be non-zero".into())
+            }
+            ScheduleShiftMode::Offset { minutes } if minutes.abs() >= MINUTES_PER_DAY => {
+                Err("offset must be less than a day".into())
+            }
+            ScheduleShiftMode::Offset { .. } => Ok(()),
+            ScheduleShiftMode::Spread { start, end } => {
+                if parse_clock(start)? >= parse_clock(end)? {
+                    return Err(format!(
+                        "spread window {start}-{end} must end after it starts"
+                    ));
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// the rewritten cron for each of `crons`, in order.
+    pub fn apply(&self, crons: &[String]) -> Vec<Result<String, String>> {
+        match self {
+            ScheduleShiftMode::Offset { minutes } => crons
+                .iter()
+                .map(|cron| shift_cron(cron, *minutes))
+                .collect(),
+            ScheduleShiftMode::Spread { start, end } => {
+                let (start, end) = match (parse_clock(start), parse_clock(end)) {
+                    (Ok(start), Ok(end)) => (start, end),
+                    (Err(err), _) | (_, Err(err)) => {
+                        return crons.iter().map(|_| Err(err.clone())).collect();
+                    }
+                };
+                let count = crons.len().max(1) as i64;
+                crons
+                    .iter()
+                    .enumerate()
+                    .map(|(index, cron)| {
+                        cron_at(cron, start + (end - start) * index as i64 / count)
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// minutes since midnight for an `HH:MM` clock time.
+fn parse_clock(value: &str) -> Result<i64, String> {
+    let invalid = || format!("invalid time '{value}' (use HH:MM)");
+    let (hour, minute) = value.trim().split_once(':').ok_or_else(invalid)?;
+    let hour: i64 = hour.parse().map_err(|_| invalid())?;
+    let minute: i64 = minute.parse().map_err(|_| invalid())?;
+    if !(0..24).contains(&hour) || !(0..60).contains(&minute) {
+        return Err(invalid());
+    }
+    Ok(hour * 60 + minute)
+}
+
+/// split a cron into its fields and the index of the minute field (5 fields, or 6 with seconds).
+fn cron_fields(cron: &str) -> Result<(Vec<String>, usize), String> {
+    let fields = cron
+        .split_whitespace()
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    match fields.len() {
+        5 => Ok((fields, 0)),
+        6 => Ok((fields, 1)),
+        _ => Err(format!("cron '{cron}' is not a 5- or 6-field expression")),
+    }
+}
+
+fn parse_list(field: &str, max: i64, label: &str) -> Result<Vec<i64>, String> {
+    field
+        .split(',')
+        .map(|part| match part.parse::<i64>() {
+            Ok(value) if (0..=max).contains(&value) => Ok(value),
+            _ => Err(format!(
+                "{label} field '{field}' is not a plain list of {label}s"
+            )),
+        })
+        .collect()
+}
+
+fn join_list(mut values: Vec<i64>) -> String {
+    values.sort_unstable();
+    values.dedup();
+    values
+        .iter()
+        .map(i64::to_string)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// move a cron schedule by `offset` minutes. an hourly schedule (`*` hours) only moves its minutes.
+/// a shift that crosses midnight is refused when the schedule is pinned to specific days, since the
+/// day fields would have to move too.
+pub fn shift_cron(cron: &str, offset: i64) -> Result<String, String> {
+    let (mut fields, minute_index) = cron_fields(cron)?;
+    let hour_index = minute_index + 1;
+    let minutes = parse_list(&fields[minute_index], 59, "minute")?;
+
+    if fields[hour_index] == "*" {
+        fields[minute_index] = join_list(
+            minutes
+                .iter()
+                .map(|minute| (minute + offset).rem_euclid(60))
+                .collect(),
+        );
+        return Ok(fields.join(" "));
+    }
+
+    let hours = parse_list(&fields[hour_index], 23, "hour")?;
+    let carries = minutes
+        .iter()
+        .map(|minute| (minute + offset).div_euclid(60))
+        .collect::<Vec<_>>();
+    if carries.iter().any(|carry| *carry != carries[0]) {
+        return Err(format!(
+            "minutes '{}' would land in different hours after the shift",
+            fields[minute_index]
+        ));
+    }
+    let crosses_midnight = hours.iter().any(|hour| {
+        let shifted = hour * 60 + minutes[0] + offset;
+        !(0..MINUTES_PER_DAY).contains(&shifted)
+    });
+    let pinned_days = fields[hour_index + 1] != "*" || fields[hour_index + 3] != "*";
+    if crosses_midnight && pinned_days {
+        return Err(
+            "the shift crosses midnight and the schedule is pinned to specific days".into(),
+        );
+    }
+
+    fields[minute_index] = join_list(
+        minutes
+            .iter()
+            .map(|minute| (minute + offset).rem_euclid(60))
+            .collect(),
+    );
+    fields[hour_index] = join_list(
+        hours
+            .iter()
+            .map(|hour| (hour + carries[0]).rem_euclid(24))
+            .collect(),
+    );
+    Ok(fields.join(" "))
+}
+
+/// pin a once-a-day cron schedule to `minute_of_day`, keeping its day fields (and seconds).
+pub fn cron_at(cron: &str, minute_of_day: i64) -> Result<String, String> {
+    let (mut fields, minute_index) = cron_fields(cron)?;
+    let hour_index = minute_index + 1;
+    let minutes = parse_list(&fields[minute_index], 59, "minute")?;
+    let hours = if fields[hour_index] == "*" {
+        Vec::new()
+    } else {
+        parse_list(&fields[hour_index], 23, "hour")?
+    };
+    if minutes.len() != 1 || hours.len() != 1 {
+        return Err(format!(
+            "cron '{cron}' runs more than once a day and cannot be placed at one time"
+        ));
+    }
+    fields[minute_index] = (minute_of_day % 60).to_string();
+    fields[hour_index] = (minute_of_day / 60).to_string();
+    Ok(fields.join(" "))
+}
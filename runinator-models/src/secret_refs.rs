@@ -0,0 +1,123 @@
+//! `secret://<scope>/<name>` references in action parameters, and the `scope/name` glob patterns an
+//! action's `allowed_secrets` uses to restrict which of them it may resolve.
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+use crate::value::Value;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SecretRef {
+    pub scope: String,
+    pub name: String,
+}
+
+impl fmt::Display for SecretRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.scope, self.name)
+    }
+}
+
+/// parse one `secret://<scope>/<name>` string; both parts may be percent-encoded.
+pub fn parse_secret_ref(raw: &str) -> Option<SecretRef> {
+    let path = raw.strip_prefix("secret://")?;
+    let (scope, name) = path.split_once('/')?;
+    if scope.is_empty() || name.is_empty() {
+        return None;
+    }
+    Some(SecretRef {
+        scope: percent_decode(scope)?,
+        name: percent_decode(name)?,
+    })
+}
+
+/// every secret reference anywhere in `value`.
+pub fn collect_secret_refs(value: &Value, refs: &mut BTreeSet<SecretRef>) {
+    match value {
+        Value::String(raw) => {
+            if let Some(secret_ref) = parse_secret_ref(raw) {
+                refs.insert(secret_ref);
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                collect_secret_refs(value, refs);
+            }
+        }
+        Value::Object(object) => {
+            for value in object.values() {
+                collect_secret_refs(value, refs);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// check an `allowed_secrets` pattern is `scope/name`, where either part may use `*` wildcards.
+pub fn validate_secret_pattern(pattern: &str) -> Result<(), String> {
+    match pattern.split_once('/') {
+        Some((scope, name)) if !scope.is_empty() && !name.is_empty() && !name.contains('/') => {
+            Ok(())
+        }
+        _ => Err(format!(
+            "secret pattern '{pattern}' must have the form 'scope/name' (wildcards: '*')"
+        )),
+    }
+}
+
+/// whether `secret_ref` is permitted by `allowed`. an empty list leaves the action unrestricted.
+pub fn secret_allowed(allowed: &[String], secret_ref: &SecretRef) -> bool {
+    allowed.is_empty()
+        || allowed.iter().any(|pattern| {
+            pattern.split_once('/').is_some_and(|(scope, name)| {
+                glob_matches(scope, &secret_ref.scope) && glob_matches(name, &secret_ref.name)
+            })
+        })
+}
+
+pub(crate) fn glob_matches(pattern: &str, value: &str) -> bool {
+    let Some((first, rest)) = pattern.split_once('*') else {
+        return pattern == value;
+    };
+    let Some(mut remaining) = value.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts = rest.split('*').peekable();
+    while let Some(part) = parts.next() {
+        if parts.peek().is_none() {
+            return remaining.ends_with(part);
+        }
+        match remaining.find(part) {
+            Some(index) => remaining = &remaining[index + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+fn percent_decode(raw: &str) -> Option<String> {
+    let bytes = raw.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'%' {
+            let hi = hex_value(*bytes.get(index + 1)?)?;
+            let lo = hex_value(*bytes.get(index + 2)?)?;
+            decoded.push((hi << 4) | lo);
+            index += 3;
+            continue;
+        }
+        decoded.push(bytes[index]);
+        index += 1;
+    }
+    String::from_utf8(decoded).ok()
+}
+
+fn hex_value(byte: u8) -> Option<u8> {
+    match byte {
+        b'0'..=b'9' => Some(byte - b'0'),
+        b'a'..=b'f' => Some(byte - b'a' + 10),
+        b'A'..=b'F' => Some(byte - b'A' + 10),
+        _ => None,
+    }
+}
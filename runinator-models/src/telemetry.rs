@@ -2,6 +2,8 @@
 // `attributes.telemetry`. kept transport-friendly so it round-trips through the replica
 // registry and out the `/replicas` api without bespoke mapping.
 
+use std::collections::BTreeMap;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -152,6 +154,26 @@ pub struct HostMetadata {
     pub boot_time_unix: u64,
 }
 
+/// how a service process started, logged once at startup and served to support bundles. secrets in
+/// `config` and `args` are redacted before the report is built.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct StartupReport {
+    pub component: String,
+    pub version: String,
+    pub started_at: Option<DateTime<Utc>>,
+    pub pid: u32,
+    pub host: HostMetadata,
+    /// the process's `RUNINATOR_*`, `OTEL_*`, and `RUST_LOG` environment, by name.
+    #[serde(default)]
+    pub config: BTreeMap<String, String>,
+    /// command-line arguments after the program name.
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// providers or plugin libraries the process loaded, sorted.
+    #[serde(default)]
+    pub plugins: Vec<String>,
+}
+
 /// telemetry for a single gpu. fields are optional because backends expose different metrics.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct GpuTelemetry {
@@ -0,0 +1,82 @@
+//! server-managed workflow templates. a template carries a pre-filled workflow (provider configs
+//! included), an optional cron schedule, and labels for finding it, so teams start new workflows
+//! from a shared, reviewed starting point instead of copying an existing one by hand.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::workflows::{WorkflowDefinition, WorkflowTrigger, WorkflowTriggerKind};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowTemplate {
+    pub id: Option<Uuid>,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// the organization that owns this template. `None` is a platform-wide template visible to
+    /// every org.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub org_id: Option<Uuid>,
+    /// the schedule given to workflows created from this template; `None` creates no trigger.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cron: Option<String>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// the workflow a new instance starts from. its id, org, and timestamps are ignored.
+    pub workflow: WorkflowDefinition,
+    #[serde(default)]
+    pub created_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// what to call a workflow created from a template.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstantiateTemplateRequest {
+    pub name: String,
+    /// defaults to the template workflow's namespace.
+    #[serde(default)]
+    pub namespace: Option<String>,
+    /// defaults to false so a new workflow never fires before someone has looked at it.
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+impl WorkflowTemplate {
+    /// the new, unsaved workflow `request` asks for.
+    pub fn instantiate(&self, request: &InstantiateTemplateRequest) -> WorkflowDefinition {
+        WorkflowDefinition {
+            id: None,
+            name: request.name.clone(),
+            namespace: request
+                .namespace
+                .clone()
+                .or_else(|| self.workflow.namespace.clone()),
+            org_id: None,
+            enabled: request.enabled,
+            created_at: None,
+            updated_at: None,
+            ..self.workflow.clone()
+        }
+    }
+
+    /// the cron trigger a workflow created from this template gets, if the template has a
+    /// schedule. it follows the workflow's enabled flag.
+    pub fn cron_trigger(&self, workflow: &WorkflowDefinition) -> Option<WorkflowTrigger> {
+        let cron = self.cron.as_deref()?;
+        Some(WorkflowTrigger {
+            id: None,
+            workflow_id: workflow.id?,
+            kind: WorkflowTriggerKind::Cron,
+            enabled: workflow.enabled,
+            configuration: crate::json!({ "cron": cron }),
+            next_execution: None,
+            blackout_start: None,
+            blackout_end: None,
+            metadata: crate::json!({}),
+            created_at: None,
+            updated_at: None,
+        })
+    }
+}
@@ -1,4 +1,6 @@
 use crate::{
+    backfills::{BackfillRequest, DEFAULT_BACKFILL_CONCURRENCY},
+    blackouts::{TriggerBlackout, trigger_blackouts},
     orgs::{OrgRole, slugify},
     providers::{
         ActionMetadata, ParameterMetadata, ProviderMetadata, ResultMetadata,
@@ -786,6 +788,97 @@ fn workflow_bundle_uses_importer_shape() {
     assert!(value.get("triggers").is_some());
 }
 
+// a boxed provider error survives the trip through `SendableError`; anything else becomes a
+// retryable error under its numbered code.
+#[test]
+fn provider_error_recovers_structure_from_execution_errors() {
+    use crate::errors::{ErrorDescriptor, SendableError};
+    use crate::runs::ProviderError;
+
+    let typed: SendableError =
+        Box::new(ProviderError::permanent("github.auth", "bad token").with_detail("status", 401));
+    let recovered = ProviderError::from_error(typed.as_ref());
+    assert_eq!(recovered.code, "github.auth");
+    assert!(!recovered.retryable);
+    assert_eq!(recovered.details["status"], 401);
+
+    let coded = ErrorDescriptor::new("JIRA001", "jira.config", "Could not parse URL").bare();
+    let recovered = ProviderError::from_error(coded.as_ref());
+    assert_eq!(recovered.code, "JIRA001");
+    assert!(recovered.retryable);
+
+    // omitted `retryable` keeps the historical retry-everything behavior.
+    let parsed: ProviderError =
+        serde_json::from_value(json!({ "code": "x", "message": "y" })).unwrap();
+    assert!(parsed.retryable);
+}
+
+// every numbered error lands in one category, from its key first and its code prefix second.
+#[test]
+fn error_categories_classify_keys_and_codes() {
+    use crate::errors::{ErrorCategory, ErrorDescriptor, SendableError};
+
+    let cases = [
+        (
+            "RUNI137",
+            "workflow.cancel.not_found",
+            ErrorCategory::NotFound,
+        ),
+        (
+            "RUNI132",
+            "workflow.debug.terminal",
+            ErrorCategory::Conflict,
+        ),
+        ("BROKER001", "broker.duplicate", ErrorCategory::Conflict),
+        (
+            "RUNI124",
+            "workflow.only_if.invalid",
+            ErrorCategory::Validation,
+        ),
+        ("SQL001", "sql.unsupported_call", ErrorCategory::Validation),
+        ("BROKER005", "broker.internal", ErrorCategory::Transport),
+        ("RUNI217", "worker.broker.publish", ErrorCategory::Transport),
+        ("SQL005", "sql.query_failed", ErrorCategory::Provider),
+        (
+            "RUNI501",
+            "database.action_dispatch.invalid_command_json",
+            ErrorCategory::Database,
+        ),
+        (
+            "RUNI177",
+            "ws.background.loop_exited",
+            ErrorCategory::Internal,
+        ),
+    ];
+    for (code, key, expected) in cases {
+        let descriptor = ErrorDescriptor::new(code, key, "summary");
+        assert_eq!(descriptor.category(), expected, "{key}");
+        assert_eq!(
+            ErrorCategory::of(descriptor.bare().as_ref()),
+            expected,
+            "{key}"
+        );
+    }
+
+    let keyed = ErrorDescriptor::new("RUNI217", "worker.broker.operation", "Broker failed")
+        .keyed("worker.broker.ack", "connection reset");
+    assert_eq!(
+        crate::errors::extract_error_code(keyed.as_ref()).as_deref(),
+        Some("RUNI217")
+    );
+    assert_eq!(
+        keyed.to_string(),
+        "worker.broker.ack: RUNI217 - Broker failed: connection reset"
+    );
+
+    let io: SendableError = Box::new(std::io::Error::from(std::io::ErrorKind::TimedOut));
+    assert_eq!(ErrorCategory::of(io.as_ref()), ErrorCategory::Transport);
+    let untyped: SendableError = "JIRA004 - Request failed".into();
+    assert_eq!(ErrorCategory::of(untyped.as_ref()), ErrorCategory::Provider);
+    let opaque: SendableError = "something broke".into();
+    assert_eq!(ErrorCategory::of(opaque.as_ref()), ErrorCategory::Internal);
+}
+
 #[test]
 fn retry_class_selects_retryable_statuses() {
     assert!(WorkflowRetryClass::Any.retryable(WorkflowStatus::Failed));
@@ -809,3 +902,481 @@ fn retry_defaults_preserve_legacy_shape() {
     assert!(!retry.jitter);
     assert_eq!(retry.retry_on, WorkflowRetryClass::Any);
 }
+
+fn health_run(
+    workflow_id: uuid::Uuid,
+    status: WorkflowStatus,
+    created_at: chrono::DateTime<chrono::Utc>,
+    seconds: i64,
+) -> WorkflowRun {
+    serde_json::from_value(json!({
+        "id": uuid::Uuid::new_v4(),
+        "workflow_id": workflow_id,
+        "status": status,
+        "active_node_id": null,
+        "parameters": {},
+        "state": {},
+        "created_at": created_at,
+        "started_at": created_at,
+        "finished_at": created_at + chrono::Duration::seconds(seconds),
+        "message": if status == WorkflowStatus::Failed { Some("boom") } else { None },
+    }))
+    .unwrap()
+}
+
+// a failure only counts as new when the workflow did not also fail in the preceding period, and
+// the slowest list ranks by average duration.
+#[test]
+fn run_health_summary_ranks_slowest_and_flags_new_failures() {
+    use crate::run_health::{RunHealthSummary, SummaryPeriod};
+
+    let now = chrono::Utc::now();
+    let steady = uuid::Uuid::new_v4();
+    let flaky = uuid::Uuid::new_v4();
+    let broke = uuid::Uuid::new_v4();
+    let day = chrono::Duration::days(1);
+    let runs = vec![
+        health_run(steady, WorkflowStatus::Succeeded, now - day, 30),
+        health_run(steady, WorkflowStatus::Succeeded, now - day * 2, 90),
+        health_run(flaky, WorkflowStatus::Failed, now - day, 10),
+        health_run(flaky, WorkflowStatus::Failed, now - day * 10, 10),
+        health_run(broke, WorkflowStatus::Failed, now - day * 3, 600),
+        health_run(broke, WorkflowStatus::Succeeded, now - day * 20, 5),
+    ];
+    let names = std::collections::HashMap::from([
+        (steady, "steady".to_string()),
+        (flaky, "flaky".to_string()),
+        (broke, "broke".to_string()),
+    ]);
+
+    let summary = RunHealthSummary::build(SummaryPeriod::Weekly, now, &runs, &names);
+    assert_eq!(summary.total_runs, 4);
+    assert_eq!(summary.succeeded, 2);
+    assert_eq!(summary.failed, 2);
+    assert_eq!(summary.success_rate, Some(0.5));
+    assert_eq!(summary.slowest[0].workflow_name, "broke");
+    assert_eq!(summary.slowest[1].workflow_name, "steady");
+    assert_eq!(summary.slowest[1].average_seconds, 60);
+    assert_eq!(summary.new_failures.len(), 1);
+    assert_eq!(summary.new_failures[0].workflow_name, "broke");
+
+    let text = summary.render_text();
+    assert!(text.starts_with("Runinator weekly health: 4 runs, 50.0% success, 1 new failure(s)"));
+    assert!(text.contains("broke  avg 10m00s"));
+    assert_eq!(
+        SummaryPeriod::try_from("monthly"),
+        Ok(SummaryPeriod::Monthly)
+    );
+}
+
+// offsets rewrite plain minute/hour lists (carrying into the hour), refuse day-pinned midnight
+// crossings, and spread places each daily schedule at its own slot.
+#[test]
+fn schedule_shift_rewrites_plain_crons_and_spreads_daily_ones() {
+    use crate::schedule_shift::{ScheduleShiftMode, cron_at, shift_cron};
+
+    assert_eq!(shift_cron("30 9 * * *", 120).unwrap(), "30 11 * * *");
+    assert_eq!(
+        shift_cron("0 45 9,21 * * *", 30).unwrap(),
+        "0 15 10,22 * * *"
+    );
+    assert_eq!(shift_cron("15 * * * 1", -30).unwrap(), "45 * * * 1");
+    assert_eq!(shift_cron("0 23 * * *", 90).unwrap(), "30 0 * * *");
+    assert!(shift_cron("0 23 * * 1-5", 90).is_err());
+    assert!(shift_cron("*/5 * * * *", 10).is_err());
+    assert!(shift_cron("0,50 9 * * *", 15).is_err());
+    assert!(cron_at("0 9,17 * * *", 60).is_err());
+
+    let spread = ScheduleShiftMode::Spread {
+        start: "01:00".into(),
+        end: "03:00".into(),
+    };
+    assert!(spread.validate().is_ok());
+    let crons = ["0 5 * * *", "0 0 5 * * 1", "30 5 * * *", "0 22 1 * *"]
+        .map(String::from)
+        .to_vec();
+    let rewritten = spread
+        .apply(&crons)
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(
+        rewritten,
+        vec!["0 1 * * *", "0 30 1 * * 1", "0 2 * * *", "30 2 1 * *"]
+    );
+    assert!(ScheduleShiftMode::Offset { minutes: 0 }.validate().is_err());
+}
+
+// samples outside the window are ignored, percentiles use nearest rank, and tasks are ordered by
+// their p95 wait.
+#[test]
+fn queue_wait_report_aggregates_globally_and_per_task() {
+    use crate::queue_wait::{QueueWaitReport, QueueWaitSample};
+
+    let now = chrono::Utc::now();
+    let since = now - chrono::Duration::hours(1);
+    let workflow = uuid::Uuid::new_v4();
+    let sample = |node_id: &str, queue_wait_ms: i64, minutes_ago: i64| QueueWaitSample {
+        workflow_id: workflow,
+        node_id: node_id.to_string(),
+        queue_wait_ms,
+        started_at: now - chrono::Duration::minutes(minutes_ago),
+    };
+    let samples = vec![
+        sample("fetch", 100, 5),
+        sample("fetch", 300, 10),
+        sample("load", 5_000, 15),
+        sample("load", 1_000, 20),
+        sample("load", 90_000, 120),
+    ];
+    let names = std::collections::HashMap::from([(workflow, "etl".to_string())]);
+
+    let report = QueueWaitReport::build(since, Some(2_000), &samples, &names);
+    assert_eq!(report.overall.count, 4);
+    assert_eq!(report.overall.average_ms, 1_600);
+    assert_eq!(report.overall.p50_ms, 300);
+    assert_eq!(report.overall.p95_ms, 5_000);
+    assert_eq!(report.overall.max_ms, 5_000);
+    assert_eq!(report.overall.over_threshold, 1);
+    assert_eq!(report.tasks.len(), 2);
+    assert_eq!(report.tasks[0].node_id, "load");
+    assert_eq!(report.tasks[0].workflow_name, "etl");
+    assert_eq!(report.tasks[0].stats.count, 2);
+    assert_eq!(report.tasks[1].stats.p50_ms, 100);
+}
+
+// the overview keeps only the newest runs of its own workflow and resolves executors, falling back
+// to a bare id for replicas that are no longer on record.
+#[test]
+fn workflow_overview_keeps_newest_runs_and_resolves_executors() {
+    use crate::overview::{ExecutorRef, WorkflowOverview};
+    use crate::replicas::{ReplicaKind, ReplicaStatus};
+
+    let now = chrono::Utc::now();
+    let workflow_id = uuid::Uuid::new_v4();
+    let workflow: WorkflowDefinition = serde_json::from_value(json!({
+        "id": workflow_id,
+        "name": "etl",
+        "enabled": true,
+    }))
+    .unwrap();
+    let runs = vec![
+        health_run(
+            workflow_id,
+            WorkflowStatus::Succeeded,
+            now - chrono::Duration::hours(3),
+            5,
+        ),
+        health_run(
+            workflow_id,
+            WorkflowStatus::Failed,
+            now - chrono::Duration::hours(1),
+            5,
+        ),
+        health_run(
+            workflow_id,
+            WorkflowStatus::Succeeded,
+            now - chrono::Duration::hours(2),
+            5,
+        ),
+        health_run(uuid::Uuid::new_v4(), WorkflowStatus::Succeeded, now, 5),
+    ];
+    let (known, pruned) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+    let executors = std::collections::HashMap::from([(runs[1].id, vec![known, pruned])]);
+    let replicas = std::collections::HashMap::from([(
+        known,
+        ExecutorRef {
+            replica_id: known,
+            replica_type: Some(ReplicaKind::Worker),
+            display_name: Some("worker-1".to_string()),
+            status: Some(ReplicaStatus::Live),
+        },
+    )]);
+
+    let overview = WorkflowOverview::build(&workflow, &runs, 2, &executors, &replicas).unwrap();
+    assert_eq!(overview.name, "etl");
+    assert_eq!(
+        overview.runs.iter().map(|run| run.id).collect::<Vec<_>>(),
+        vec![runs[1].id, runs[2].id]
+    );
+    assert_eq!(overview.runs[0].executors.len(), 2);
+    assert_eq!(
+        overview.runs[0].executors[0].display_name.as_deref(),
+        Some("worker-1")
+    );
+    assert_eq!(overview.runs[0].executors[1].replica_id, pruned);
+    assert!(overview.runs[0].executors[1].replica_type.is_none());
+    assert!(overview.runs[1].executors.is_empty());
+}
+
+// a whole-string reference keeps the output's type; embedded ones splice text; namespaced workflow
+// names keep their dots.
+#[test]
+fn output_refs_are_collected_and_substituted() {
+    use crate::output_refs::{OutputRef, collect_output_refs, substitute_output_refs};
+    use std::collections::{BTreeMap, BTreeSet};
+
+    let config: Value = json!({
+        "rows": "{{output:extract.row_count}}",
+        "url": "s3://bucket/{{output:ops.extract.prefix}}/part-{{output:extract.row_count}}",
+        "nested": ["{{output:extract.missing}}", "{{output:no-key}}"],
+    })
+    .into();
+    let mut refs = BTreeSet::new();
+    collect_output_refs(&config, &mut refs);
+    let output_ref = |workflow: &str, key: &str| OutputRef {
+        workflow: workflow.into(),
+        key: key.into(),
+    };
+    assert_eq!(
+        refs,
+        BTreeSet::from([
+            output_ref("extract", "row_count"),
+            output_ref("ops.extract", "prefix"),
+            output_ref("extract", "missing"),
+        ])
+    );
+
+    let mut outputs = BTreeMap::from([
+        (output_ref("extract", "row_count"), Value::from(42)),
+        (output_ref("ops.extract", "prefix"), Value::from("2026/10")),
+    ]);
+    assert_eq!(
+        substitute_output_refs(&config, &outputs),
+        Err(output_ref("extract", "missing"))
+    );
+    outputs.insert(output_ref("extract", "missing"), Value::from("found"));
+    let resolved = substitute_output_refs(&config, &outputs).unwrap();
+    assert_eq!(
+        resolved,
+        Value::from(json!({
+            "rows": 42,
+            "url": "s3://bucket/2026/10/part-42",
+            "nested": ["found", "{{output:no-key}}"],
+        }))
+    );
+}
+
+#[test]
+fn run_outputs_are_limited_to_small_scalars() {
+    use crate::output_refs::{MAX_OUTPUT_STRING_LEN, validate_run_outputs};
+    use std::collections::BTreeMap;
+
+    let outputs = |pairs: Vec<(&str, Value)>| {
+        pairs
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect::<BTreeMap<_, _>>()
+    };
+    assert!(
+        validate_run_outputs(&outputs(vec![
+            ("row_count", Value::from(42)),
+            ("batch-id", Value::from("b1"))
+        ]))
+        .is_ok()
+    );
+    assert!(validate_run_outputs(&outputs(vec![("a.b", Value::from(1))])).is_err());
+    assert!(validate_run_outputs(&outputs(vec![("flag", Value::from(true))])).is_err());
+    assert!(
+        validate_run_outputs(&outputs(vec![(
+            "blob",
+            Value::from("x".repeat(MAX_OUTPUT_STRING_LEN + 1))
+        )]))
+        .is_err()
+    );
+}
+
+// tasks roll up to their provider action, unattributed tasks are dropped, and the average only
+// counts runs that finished.
+#[test]
+fn provider_stats_roll_task_counts_up_to_provider_actions() {
+    use crate::provider_stats::{NodeRunCounts, ProviderStatsWindow};
+
+    let workflow = uuid::Uuid::new_v4();
+    let task = |node_id: &str, runs: i64, timed_runs: i64, duration_ms: i64| NodeRunCounts {
+        workflow_id: workflow,
+        node_id: node_id.to_string(),
+        runs,
+        succeeded: timed_runs,
+        failed: runs - timed_runs,
+        timed_runs,
+        duration_ms,
+    };
+    let counts = vec![
+        task("dump", 4, 4, 8_000),
+        task("dump_again", 2, 1, 4_000),
+        task("notify", 1, 1, 100),
+        task("start", 9, 9, 0),
+    ];
+    let actions = std::collections::HashMap::from([
+        (
+            (workflow, "dump".to_string()),
+            ("sql".into(), "dump_data".into()),
+        ),
+        (
+            (workflow, "dump_again".to_string()),
+            ("sql".into(), "dump_data".into()),
+        ),
+        (
+            (workflow, "notify".to_string()),
+            ("slack".into(), "post".into()),
+        ),
+    ]);
+
+    let window = ProviderStatsWindow::build(24, chrono::Utc::now(), &counts, &actions);
+    assert_eq!(window.providers.len(), 2);
+    let sql = &window.providers[0];
+    assert_eq!(sql.provider, "sql");
+    assert_eq!(sql.counts.tasks, 2);
+    assert_eq!(sql.counts.runs, 6);
+    assert_eq!(sql.counts.failed, 1);
+    assert_eq!(sql.counts.average_duration_ms, Some(2_400));
+    assert_eq!(sql.actions.len(), 1);
+    assert_eq!(sql.actions[0].function, "dump_data");
+    assert_eq!(window.providers[1].provider, "slack");
+    assert_eq!(window.providers[1].counts.average_duration_ms, Some(100));
+}
+
+// a trigger's blackouts are weekly windows or calendar references, never a mix of the two.
+#[test]
+fn trigger_blackouts_accept_weekly_windows_and_calendars() {
+    let configuration = Value::from(json!({
+        "cron": "0 * * * *",
+        "blackouts": [
+            { "days": ["sun", "Saturday"], "start": "02:00", "end": "04:00" },
+            { "calendar": "us-holidays" },
+        ],
+    }));
+    let blackouts = trigger_blackouts(&configuration).unwrap();
+
+    assert_eq!(blackouts.len(), 2);
+    assert_eq!(
+        blackouts[0].days,
+        vec![chrono::Weekday::Sun, chrono::Weekday::Sat]
+    );
+    assert_eq!(blackouts[0].start, chrono::NaiveTime::from_hms_opt(2, 0, 0));
+    assert_eq!(
+        blackouts[1],
+        TriggerBlackout {
+            calendar: Some("us-holidays".into()),
+            ..TriggerBlackout::default()
+        }
+    );
+    assert!(
+        trigger_blackouts(&Value::from(json!({ "cron": "0 * * * *" })))
+            .unwrap()
+            .is_empty()
+    );
+
+    for bad in [
+        json!({ "blackouts": { "start": "02:00", "end": "04:00" } }),
+        json!({ "blackouts": [{ "start": "02:00" }] }),
+        json!({ "blackouts": [{ "calendar": "us-holidays", "start": "02:00", "end": "04:00" }] }),
+        json!({ "blackouts": [{ "calendar": " " }] }),
+        json!({ "blackouts": [{ "days": ["someday"], "start": "02:00", "end": "04:00" }] }),
+        json!({ "blackouts": [{ "start": "02:00", "end": "04:00", "every": "week" }] }),
+    ] {
+        assert!(
+            trigger_blackouts(&Value::from(bad.clone())).is_err(),
+            "{bad} should be rejected"
+        );
+    }
+}
+
+#[test]
+fn backfill_requests_replay_a_past_window_at_a_bounded_concurrency() {
+    let at = |raw: &str| raw.parse::<chrono::DateTime<chrono::Utc>>().unwrap();
+    let now = at("2026-03-31T00:00:00Z");
+    let request = |from: &str, to: &str, concurrency: Option<i64>| BackfillRequest {
+        from: at(from),
+        to: at(to),
+        concurrency,
+    };
+
+    assert_eq!(
+        request("2026-03-01T00:00:00Z", "2026-03-30T00:00:00Z", None).validate(now),
+        Ok(DEFAULT_BACKFILL_CONCURRENCY)
+    );
+    assert_eq!(
+        request("2026-03-01T00:00:00Z", "2026-03-31T00:00:00Z", Some(4)).validate(now),
+        Ok(4)
+    );
+    for bad in [
+        request("2026-03-30T00:00:00Z", "2026-03-01T00:00:00Z", None),
+        request("2026-03-01T00:00:00Z", "2026-04-01T00:00:00Z", None),
+        request("2026-03-01T00:00:00Z", "2026-03-30T00:00:00Z", Some(0)),
+        request("2026-03-01T00:00:00Z", "2026-03-30T00:00:00Z", Some(33)),
+    ] {
+        assert!(bad.validate(now).is_err(), "{bad:?} should be rejected");
+    }
+}
+
+// the gRPC messages must carry a workflow and a run chunk exactly as the REST bodies do, so the
+// two surfaces cannot drift.
+#[cfg(feature = "grpc")]
+#[test]
+fn grpc_messages_round_trip_the_rest_bodies() {
+    use crate::grpc;
+
+    let workflow: WorkflowDefinition = serde_json::from_value(json!({
+        "id": uuid::Uuid::new_v4(),
+        "name": "etl",
+        "namespace": "data.nightly",
+        "org_id": uuid::Uuid::new_v4(),
+        "version": "1.4.2",
+        "enabled": true,
+        "input_type": {
+            "kind": "object",
+            "fields": [{ "name": "day", "type": { "kind": "string" }, "required": true }]
+        },
+        "definition": {
+            "start": "build",
+            "nodes": [{
+                "id": "build",
+                "kind": "action",
+                "action": { "provider": "console", "function": "run", "configuration": {} }
+            }],
+            "metadata": { "team": "data" }
+        },
+        "created_at": "2026-03-01T08:00:00.123456Z",
+        "updated_at": "2026-03-02T09:30:00Z"
+    }))
+    .unwrap();
+    let message = grpc::Workflow::try_from(&workflow).unwrap();
+    assert_eq!(message.version, "1.4.2");
+    assert_eq!(
+        message.created_at.as_deref(),
+        Some("2026-03-01T08:00:00.123456Z")
+    );
+    let decoded = WorkflowDefinition::try_from(message).unwrap();
+    assert_eq!(
+        serde_json::to_value(&decoded).unwrap(),
+        serde_json::to_value(&workflow).unwrap()
+    );
+
+    // a create leaves the id and timestamps unset, which must decode as absent.
+    let created = WorkflowDefinition::try_from(grpc::Workflow {
+        name: "fresh".into(),
+        ..Default::default()
+    })
+    .unwrap();
+    assert_eq!((created.id, created.created_at), (None, None));
+    assert_eq!(created.definition, WorkflowGraph::default());
+
+    let chunk = crate::runs::RunChunk {
+        id: uuid::Uuid::new_v4(),
+        run_id: uuid::Uuid::new_v4(),
+        sequence: 7,
+        stream: "stdout".into(),
+        content: "hello".into(),
+        created_at: "2026-03-01T08:00:01Z".parse().unwrap(),
+    };
+    let message = grpc::RunChunk::from(&chunk);
+    let rest = serde_json::to_value(&chunk).unwrap();
+    assert_eq!(rest["id"], json!(message.id));
+    assert_eq!(rest["run_id"], json!(message.run_id));
+    assert_eq!(rest["sequence"], json!(message.sequence));
+    assert_eq!(rest["stream"], json!(message.stream));
+    assert_eq!(rest["content"], json!(message.content));
+    assert_eq!(rest["created_at"], json!(message.created_at));
+}
@@ -0,0 +1,145 @@
+//! the "what's about to happen" timeline: node runs executing now, work waiting for a worker or a
+//! scheduler, and cron firings projected over the next window, merged into one list.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::workflows::WorkflowStatus;
+
+/// look-ahead for the upcoming timeline when none is requested.
+pub const DEFAULT_UPCOMING_MINUTES: i64 = 60;
+/// longest look-ahead the upcoming timeline projects: one day.
+pub const MAX_UPCOMING_MINUTES: i64 = 24 * 60;
+/// most firings projected per trigger, so a per-second cron cannot flood the timeline.
+pub const MAX_UPCOMING_FIRINGS_PER_TRIGGER: usize = 100;
+
+/// a queued or running node run, tagged with its workflow, as read for the timeline.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActiveNodeRun {
+    pub workflow_id: Uuid,
+    pub workflow_run_id: Uuid,
+    pub node_run_id: Uuid,
+    pub node_id: String,
+    pub status: WorkflowStatus,
+    pub created_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum UpcomingKind {
+    /// a node run a worker is executing.
+    Running,
+    /// a node command waiting in the broker for a worker, or a run waiting for a scheduler.
+    Queued,
+    /// a cron firing projected from a trigger's schedule.
+    Scheduled,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpcomingEntry {
+    pub kind: UpcomingKind,
+    /// when the entry started (running), was enqueued (queued), or fires (scheduled).
+    pub at: DateTime<Utc>,
+    pub workflow_id: Uuid,
+    pub workflow_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workflow_run_id: Option<Uuid>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trigger_id: Option<Uuid>,
+    /// a scheduled firing whose slot already passed; the next scheduler tick picks it up.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub overdue: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpcomingSchedule {
+    pub generated_at: DateTime<Utc>,
+    pub until: DateTime<Utc>,
+    pub running: i64,
+    pub queued: i64,
+    pub scheduled: i64,
+    /// every entry, running first, then queued, then scheduled, each oldest first.
+    pub entries: Vec<UpcomingEntry>,
+}
+
+impl UpcomingSchedule {
+    pub fn build(
+        generated_at: DateTime<Utc>,
+        until: DateTime<Utc>,
+        mut entries: Vec<UpcomingEntry>,
+    ) -> Self {
+        entries.sort_by_key(|entry| (entry.kind, entry.at));
+        let count = |kind: UpcomingKind| entries.iter().filter(|e| e.kind == kind).count() as i64;
+        UpcomingSchedule {
+            generated_at,
+            until,
+            running: count(UpcomingKind::Running),
+            queued: count(UpcomingKind::Queued),
+            scheduled: count(UpcomingKind::Scheduled),
+            entries,
+        }
+    }
+}
+
+/// one cron slot the next scheduling cycle would fire, as the trigger loop would enqueue it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreviewFiring {
+    pub trigger_id: Uuid,
+    pub workflow_id: Uuid,
+    pub workflow_name: String,
+    /// the cron slot, jitter included.
+    pub slot: DateTime<Utc>,
+    /// when the run would be enqueued: the slot itself, `generated_at` for an overdue slot, or the
+    /// end of the blackout holding it.
+    pub fires_at: DateTime<Utc>,
+    /// a blackout moved the firing to `fires_at`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub deferred: bool,
+}
+
+/// an enabled cron trigger left out of the preview because its schedule does not evaluate.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreviewInvalidTrigger {
+    pub trigger_id: Uuid,
+    pub workflow_id: Uuid,
+    pub workflow_name: String,
+    pub error: String,
+}
+
+/// a dry run of the trigger loop over the next window: every enabled cron trigger evaluated, with
+/// nothing claimed or enqueued. gates judged when a run starts (`only_if`, `depends_on`,
+/// `freshness_seconds`, overlap) are not evaluated.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchedulerPreview {
+    pub generated_at: DateTime<Utc>,
+    pub until: DateTime<Utc>,
+    /// firings held back by a blackout.
+    pub deferred: i64,
+    /// every firing, ordered by when it would be enqueued.
+    pub firings: Vec<PreviewFiring>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub invalid: Vec<PreviewInvalidTrigger>,
+}
+
+impl SchedulerPreview {
+    pub fn build(
+        generated_at: DateTime<Utc>,
+        until: DateTime<Utc>,
+        mut firings: Vec<PreviewFiring>,
+        invalid: Vec<PreviewInvalidTrigger>,
+    ) -> Self {
+        firings.sort_by_key(|firing| (firing.fires_at, firing.slot));
+        SchedulerPreview {
+            generated_at,
+            until,
+            deferred: firings.iter().filter(|firing| firing.deferred).count() as i64,
+            firings,
+            invalid,
+        }
+    }
+}
@@ -0,0 +1,87 @@
+//! the version manifest an update channel serves: the oldest worker build and plugin versions the
+//! fleet still accepts. workers fetch it on an interval and warn when they have fallen behind;
+//! nothing is installed for them.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::semver::SemVer;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct VersionManifest {
+    /// the oldest worker build the fleet accepts; any build when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_worker_version: Option<SemVer>,
+    /// the oldest accepted version of each plugin, by provider name. plugins not listed are not
+    /// checked.
+    #[serde(default)]
+    pub min_plugin_versions: BTreeMap<String, SemVer>,
+}
+
+/// a worker build or plugin older than the manifest allows.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutdatedComponent {
+    /// the plugin's provider name; `None` for the worker build itself.
+    pub plugin: Option<String>,
+    /// the installed version; `None` when the plugin reports none.
+    pub installed: Option<SemVer>,
+    pub minimum: SemVer,
+}
+
+impl VersionManifest {
+    /// the components of a worker at `worker` with `plugins` (provider name to reported version)
+    /// that are older than the manifest allows. a plugin that reports no version counts as
+    /// outdated, since it cannot show it is new enough.
+    pub fn outdated(
+        &self,
+        worker: SemVer,
+        plugins: &BTreeMap<String, Option<SemVer>>,
+    ) -> Vec<OutdatedComponent> {
+        let mut outdated = Vec::new();
+        if let Some(minimum) = self.min_worker_version
+            && worker < minimum
+        {
+            outdated.push(OutdatedComponent {
+                plugin: None,
+                installed: Some(worker),
+                minimum,
+            });
+        }
+        for (name, minimum) in &self.min_plugin_versions {
+            let Some(installed) = plugins.get(name) else {
+                continue;
+            };
+            if installed.is_none_or(|installed| installed < *minimum) {
+                outdated.push(OutdatedComponent {
+                    plugin: Some(name.clone()),
+                    installed: *installed,
+                    minimum: *minimum,
+                });
+            }
+        }
+        outdated
+    }
+}
+
+impl fmt::Display for OutdatedComponent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.plugin {
+            Some(name) => write!(f, "plugin '{name}'")?,
+            None => write!(f, "worker")?,
+        }
+        match self.installed {
+            Some(installed) => write!(
+                f,
+                " {installed} is older than the fleet minimum {}",
+                self.minimum
+            ),
+            None => write!(
+                f,
+                " reports no version; the fleet minimum is {}",
+                self.minimum
+            ),
+        }
+    }
+}
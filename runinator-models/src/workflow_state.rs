@@ -9,6 +9,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::orchestration::GateKind;
+use crate::runs::ProviderError;
 use crate::value::{Map, Value};
 
 use crate::workflows::WorkflowNodeKind;
@@ -402,6 +403,9 @@ pub struct TaskStatusOutput {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub duration_ms: Option<i64>,
     pub message: Option<String>,
+    /// the structured provider failure, when the action failed with one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<ProviderError>,
 }
 
 /// output recorded when a node is skipped.
@@ -7,6 +7,7 @@ use uuid::Uuid;
 
 use crate::value::{Map, Value};
 
+use crate::preflight::PreflightCheck;
 use crate::replicas::{TriggerActorType, TriggerSourceKind};
 use crate::semver::{SemVer, SemVerBump};
 use crate::types::RuninatorType;
@@ -168,6 +169,14 @@ pub struct WorkflowDuplicateRequest {
     pub bump: SemVerBump,
 }
 
+/// request for cloning a workflow into a new, independent workflow. `name` defaults to the source
+/// name with a ` (copy)` suffix.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WorkflowCloneRequest {
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
 /// request body for a server-side dry-run (branch preview). The `workflow` is walked with the
 /// reducer's evaluators against live config, publishing no actions; `inputs` seed the run and an
 /// optional `replay_run` replays that run's recorded node outputs so the walk follows real branches.
@@ -186,6 +195,169 @@ pub struct WorkflowBundle {
     pub workflows: Vec<WorkflowDefinition>,
     #[serde(default)]
     pub triggers: Vec<WorkflowTrigger>,
+    /// recent finished runs of the exported workflows, when the export asked for them. a reference
+    /// for a cloned environment's first runs; imports carry them through untouched.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub baselines: Vec<WorkflowRunBaseline>,
+}
+
+/// the most runs an export carries per workflow.
+pub const MAX_EXPORT_BASELINE_RUNS: usize = 100;
+
+/// one finished run of an exported workflow, newest first per workflow.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WorkflowRunBaseline {
+    pub workflow_id: Uuid,
+    pub workflow_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    pub status: WorkflowStatus,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<i64>,
+}
+
+impl WorkflowRunBaseline {
+    /// the baseline of `run`, a run of `workflow`; `None` while the run is still open.
+    pub fn from_run(workflow: &WorkflowDefinition, run: &WorkflowRun) -> Option<Self> {
+        if !run.status.is_terminal() {
+            return None;
+        }
+        let duration_ms = run
+            .started_at
+            .zip(run.finished_at)
+            .map(|(started, finished)| (finished - started).num_milliseconds());
+        Some(WorkflowRunBaseline {
+            workflow_id: run.workflow_id,
+            workflow_name: workflow.name.clone(),
+            namespace: workflow.namespace.clone(),
+            status: run.status,
+            started_at: run.started_at,
+            finished_at: run.finished_at,
+            duration_ms,
+        })
+    }
+}
+
+/// what a workflow import does to the cron triggers it brings in, for standing up a copy of another
+/// environment that does not fire alongside the original.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WorkflowImportOptions {
+    /// import every cron trigger disabled.
+    #[serde(default)]
+    pub disable_schedules: bool,
+    /// shift every cron trigger this many seconds later; see [`TRIGGER_OFFSET_KEY`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule_offset_seconds: Option<i64>,
+}
+
+impl WorkflowImportOptions {
+    /// true when the import leaves schedules as the bundle declares them.
+    pub fn keeps_schedules(&self) -> bool {
+        !self.disable_schedules && self.schedule_offset_seconds.is_none()
+    }
+
+    /// rejects an offset no cron trigger may carry.
+    pub fn validate(&self) -> Result<(), String> {
+        match self.schedule_offset_seconds {
+            Some(offset) if !(0..=MAX_TRIGGER_OFFSET_SECONDS).contains(&offset) => Err(format!(
+                "schedule_offset_seconds must be between 0 and {MAX_TRIGGER_OFFSET_SECONDS}"
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// `trigger` as this import saves it: disabled, or carrying the offset, when it is a cron
+    /// trigger. other kinds pass through.
+    pub fn apply(&self, trigger: &mut WorkflowTrigger) {
+        if trigger.kind != WorkflowTriggerKind::Cron {
+            return;
+        }
+        if self.disable_schedules {
+            trigger.enabled = false;
+        }
+        if let Some(offset) = self.schedule_offset_seconds
+            && let Some(configuration) = trigger.configuration.as_object_mut()
+        {
+            configuration.insert(TRIGGER_OFFSET_KEY.into(), Value::from(offset));
+        }
+    }
+}
+
+/// a live workflow's portable definition hash, for comparing against the hashes of workflow files
+/// without downloading every definition.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WorkflowDefinitionHash {
+    pub id: Uuid,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    pub version: SemVer,
+    pub hash: String,
+}
+
+/// workflows returned per search page when the caller does not ask for a size.
+pub const DEFAULT_WORKFLOW_SEARCH_LIMIT: usize = 50;
+/// the largest search page a caller may request.
+pub const MAX_WORKFLOW_SEARCH_LIMIT: usize = 200;
+/// the most terms a search query may hold; each one is another full-text condition in the query.
+pub const MAX_WORKFLOW_SEARCH_TERMS: usize = 8;
+
+/// split a search query into the terms that must each match: whitespace separates terms, and a
+/// `"double-quoted phrase"` stays one term. an unclosed quote runs to the end of the query.
+pub fn workflow_search_terms(query: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut flush = |current: &mut String| {
+        let term = current.trim();
+        if !term.is_empty() {
+            terms.push(term.to_string());
+        }
+        current.clear();
+    };
+    for ch in query.chars() {
+        match ch {
+            '"' => {
+                quoted = !quoted;
+                flush(&mut current);
+            }
+            ch if ch.is_whitespace() && !quoted => flush(&mut current),
+            ch => current.push(ch),
+        }
+    }
+    flush(&mut current);
+    terms
+}
+
+/// one page of the workflows matching a search, with the number of matches across every page.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowSearchPage {
+    pub total: usize,
+    pub offset: usize,
+    pub limit: usize,
+    pub workflows: Vec<WorkflowDefinition>,
+}
+
+/// the outcome of one entry in a bulk workflow upsert, by its position in the request. exactly one
+/// of `workflow` (the saved row) and `error` is set.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowBulkItem {
+    pub index: usize,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workflow: Option<WorkflowDefinition>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// a bulk upsert is all-or-nothing: `saved` is false when any entry was rejected, in which case no
+/// workflow was written and the rejected entries carry their errors.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowBulkResponse {
+    pub saved: bool,
+    pub results: Vec<WorkflowBulkItem>,
 }
 
 // note: raw json workflow bundles use an explicit client method because the server requires
@@ -199,14 +371,20 @@ pub enum WorkflowTriggerKind {
     /// fire when a source workflow run reaches a terminal state (workflow-to-workflow chaining).
     /// the trigger belongs to the source workflow; the target lives in `configuration`.
     Chained,
+    /// fire when a matching file settles in a watched directory; see [`FileArrivalConfig`].
+    FileArrival,
+    /// fire when an external system posts to `/triggers/{token}`; see [`WebhookTriggerConfig`].
+    Webhook,
 }
 
 impl WorkflowTriggerKind {
     /// every trigger kind in a stable, ui-facing order.
-    pub const ALL: [WorkflowTriggerKind; 3] = [
+    pub const ALL: [WorkflowTriggerKind; 5] = [
         WorkflowTriggerKind::Cron,
         WorkflowTriggerKind::Manual,
         WorkflowTriggerKind::Chained,
+        WorkflowTriggerKind::FileArrival,
+        WorkflowTriggerKind::Webhook,
     ];
 
     pub fn as_str(&self) -> &'static str {
@@ -214,6 +392,8 @@ impl WorkflowTriggerKind {
             WorkflowTriggerKind::Cron => "cron",
             WorkflowTriggerKind::Manual => "manual",
             WorkflowTriggerKind::Chained => "chained",
+            WorkflowTriggerKind::FileArrival => "file_arrival",
+            WorkflowTriggerKind::Webhook => "webhook",
         }
     }
 }
@@ -226,6 +406,8 @@ impl TryFrom<&str> for WorkflowTriggerKind {
             "cron" => Ok(WorkflowTriggerKind::Cron),
             "manual" => Ok(WorkflowTriggerKind::Manual),
             "chained" => Ok(WorkflowTriggerKind::Chained),
+            "file_arrival" => Ok(WorkflowTriggerKind::FileArrival),
+            "webhook" => Ok(WorkflowTriggerKind::Webhook),
             other => Err(format!("Unknown workflow trigger kind '{other}'")),
         }
     }
@@ -250,6 +432,481 @@ pub struct WorkflowTrigger {
     pub updated_at: Option<DateTime<Utc>>,
 }
 
+/// configuration of a `file_arrival` trigger. a file matching `pattern` in `path` starts a run once
+/// its size and modification time have held still for `stable_seconds`; `debounce_seconds` spaces
+/// consecutive runs of the trigger, batching the files that settle in between into one run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileArrivalConfig {
+    /// the watched directory.
+    pub path: String,
+    /// file name pattern; `*` matches any run of characters.
+    #[serde(default = "default_file_arrival_pattern")]
+    pub pattern: String,
+    #[serde(default = "default_file_arrival_stable_seconds")]
+    pub stable_seconds: u64,
+    #[serde(default)]
+    pub debounce_seconds: u64,
+    /// run parameters, merged under the `files` list the trigger adds.
+    #[serde(default)]
+    pub parameters: Value,
+}
+
+fn default_file_arrival_pattern() -> String {
+    "*".to_string()
+}
+
+fn default_file_arrival_stable_seconds() -> u64 {
+    30
+}
+
+impl FileArrivalConfig {
+    /// read the configuration of a `file_arrival` trigger.
+    pub fn from_configuration(configuration: &Value) -> Result<Self, String> {
+        let config: FileArrivalConfig = serde_json::from_value(configuration.clone().into())
+            .map_err(|err| format!("invalid file_arrival configuration: {err}"))?;
+        if config.path.trim().is_empty() {
+            return Err("file_arrival configuration needs a non-empty path".to_string());
+        }
+        Ok(config)
+    }
+
+    /// whether a file name matches `pattern`.
+    pub fn matches(&self, file_name: &str) -> bool {
+        crate::secret_refs::glob_matches(&self.pattern, file_name)
+    }
+}
+
+/// configuration of a `webhook` trigger. only the sha256 of the token is stored; the plain token is
+/// handed out once, when the trigger is saved.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookTriggerConfig {
+    #[serde(default)]
+    pub token_hash: Option<String>,
+    /// run parameters; an object posted with the request is merged over them.
+    #[serde(default)]
+    pub parameters: Value,
+}
+
+impl WebhookTriggerConfig {
+    /// read the configuration of a `webhook` trigger.
+    pub fn from_configuration(configuration: &Value) -> Result<Self, String> {
+        serde_json::from_value(configuration.clone().into())
+            .map_err(|err| format!("invalid webhook configuration: {err}"))
+    }
+}
+
+/// workflow definition metadata key holding an `only_if` gate expression. the trigger loop skips a
+/// cron firing when it evaluates false.
+pub const WORKFLOW_ONLY_IF_KEY: &str = "only_if";
+
+/// workflow definition metadata key listing upstream workflow ids. the trigger loop skips a cron
+/// firing unless each of them has a run that succeeded within the dependency window.
+pub const WORKFLOW_DEPENDS_ON_KEY: &str = "depends_on";
+/// workflow definition metadata key overriding how recent an upstream success must be, in seconds.
+pub const WORKFLOW_DEPENDS_WITHIN_KEY: &str = "depends_within_seconds";
+/// how recent an upstream success must be when a workflow names no window: one day.
+pub const DEFAULT_DEPENDS_WITHIN_SECS: i64 = 24 * 60 * 60;
+
+/// the upstream workflows a workflow's scheduled runs wait on, read from its definition metadata.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkflowDependencies {
+    pub upstream: Vec<Uuid>,
+    pub within_seconds: i64,
+}
+
+impl WorkflowDependencies {
+    /// `None` when the metadata names no upstream workflows.
+    pub fn from_metadata(metadata: &Value) -> Result<Option<Self>, String> {
+        let upstream = match metadata.get(WORKFLOW_DEPENDS_ON_KEY) {
+            None | Some(Value::Null) => return Ok(None),
+            Some(Value::Array(items)) => items
+                .iter()
+                .map(|item| {
+                    item.as_str()
+                        .and_then(|raw| raw.parse::<Uuid>().ok())
+                        .ok_or_else(|| {
+                            format!("{WORKFLOW_DEPENDS_ON_KEY} entries must be workflow ids")
+                        })
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            Some(_) => {
+                return Err(format!(
+                    "{WORKFLOW_DEPENDS_ON_KEY} must be a list of workflow ids"
+                ));
+            }
+        };
+        if upstream.is_empty() {
+            return Ok(None);
+        }
+        let within_seconds = match metadata.get(WORKFLOW_DEPENDS_WITHIN_KEY) {
+            None | Some(Value::Null) => DEFAULT_DEPENDS_WITHIN_SECS,
+            Some(value) => value.as_i64().filter(|secs| *secs > 0).ok_or_else(|| {
+                format!("{WORKFLOW_DEPENDS_WITHIN_KEY} must be a positive number of seconds")
+            })?,
+        };
+        Ok(Some(WorkflowDependencies {
+            upstream,
+            within_seconds,
+        }))
+    }
+}
+
+/// workflow definition metadata key that, set to `false`, keeps a cron firing from starting while an
+/// earlier run of the workflow is still open. absent means overlapping runs are allowed.
+pub const WORKFLOW_ALLOW_CONCURRENT_KEY: &str = "allow_concurrent";
+
+/// whether a workflow's scheduled runs may overlap, read from its definition metadata.
+pub fn workflow_allows_concurrent(metadata: &Value) -> Result<bool, String> {
+    match metadata.get(WORKFLOW_ALLOW_CONCURRENT_KEY) {
+        None | Some(Value::Null) => Ok(true),
+        Some(value) => value
+            .as_bool()
+            .ok_or_else(|| format!("{WORKFLOW_ALLOW_CONCURRENT_KEY} must be true or false")),
+    }
+}
+
+/// workflow definition metadata key holding a freshness window in seconds: a cron firing is skipped
+/// while the workflow's last success is younger than it. absent means every firing runs.
+pub const WORKFLOW_FRESHNESS_SECONDS_KEY: &str = "freshness_seconds";
+
+/// a workflow's freshness window in seconds, read from its definition metadata.
+pub fn workflow_freshness_seconds(metadata: &Value) -> Result<Option<i64>, String> {
+    match metadata.get(WORKFLOW_FRESHNESS_SECONDS_KEY) {
+        None | Some(Value::Null) => Ok(None),
+        Some(value) => value
+            .as_i64()
+            .filter(|secs| *secs > 0)
+            .map(Some)
+            .ok_or_else(|| {
+                format!("{WORKFLOW_FRESHNESS_SECONDS_KEY} must be a positive number of seconds")
+            }),
+    }
+}
+
+/// workflow definition metadata key holding the longest the workflow may go without a successful
+/// run, in seconds; past it the missed-run checker raises an alert. absent means never checked.
+pub const WORKFLOW_EXPECTED_MAX_INTERVAL_KEY: &str = "expected_max_interval_seconds";
+
+/// a workflow's expected maximum interval between successes in seconds, read from its definition
+/// metadata.
+pub fn workflow_expected_max_interval_seconds(metadata: &Value) -> Result<Option<i64>, String> {
+    match metadata.get(WORKFLOW_EXPECTED_MAX_INTERVAL_KEY) {
+        None | Some(Value::Null) => Ok(None),
+        Some(value) => value
+            .as_i64()
+            .filter(|secs| *secs > 0)
+            .map(Some)
+            .ok_or_else(|| {
+                format!("{WORKFLOW_EXPECTED_MAX_INTERVAL_KEY} must be a positive number of seconds")
+            }),
+    }
+}
+
+/// trigger metadata key the trigger loop records an unparseable cron schedule under
+/// (`{message, cron, detected_at}`); removed again once the schedule parses.
+pub const TRIGGER_SCHEDULE_ERROR_KEY: &str = "schedule_error";
+
+/// the recorded schedule error message in a trigger's metadata, if any.
+pub fn trigger_schedule_error(metadata: &Value) -> Option<&str> {
+    metadata
+        .get(TRIGGER_SCHEDULE_ERROR_KEY)
+        .and_then(|error| error.get("message"))
+        .and_then(Value::as_str)
+}
+
+/// cron trigger configuration key naming the iana zone (`Europe/Berlin`) the cron's fields are
+/// read in. absent means utc.
+pub const TRIGGER_TIMEZONE_KEY: &str = "timezone";
+
+/// the zone a cron trigger's configuration schedules in, if it names one.
+pub fn trigger_timezone(configuration: &Value) -> Option<&str> {
+    configuration
+        .get(TRIGGER_TIMEZONE_KEY)
+        .and_then(Value::as_str)
+        .filter(|zone| !zone.is_empty())
+}
+
+/// cron trigger configuration key choosing what happens to slots that passed while no scheduler
+/// was running; see [`MisfirePolicy`].
+pub const TRIGGER_MISFIRE_KEY: &str = "misfire";
+
+/// how a cron trigger catches up after scheduler downtime.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MisfirePolicy {
+    /// drop a slot that is already late; the trigger resumes at its next future slot.
+    Skip,
+    /// fire once for the missed slots, then resume.
+    #[default]
+    RunOnce,
+    /// fire every missed slot, oldest first.
+    RunAllMissed,
+}
+
+impl MisfirePolicy {
+    pub const ALL: [MisfirePolicy; 3] = [
+        MisfirePolicy::Skip,
+        MisfirePolicy::RunOnce,
+        MisfirePolicy::RunAllMissed,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MisfirePolicy::Skip => "skip",
+            MisfirePolicy::RunOnce => "run_once",
+            MisfirePolicy::RunAllMissed => "run_all_missed",
+        }
+    }
+}
+
+impl TryFrom<&str> for MisfirePolicy {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        MisfirePolicy::ALL
+            .into_iter()
+            .find(|policy| policy.as_str() == value)
+            .ok_or_else(|| {
+                format!(
+                    "unknown misfire policy '{value}'; expected skip, run_once, or run_all_missed"
+                )
+            })
+    }
+}
+
+/// the misfire policy a cron trigger's configuration names. absent means [`MisfirePolicy::RunOnce`].
+pub fn trigger_misfire_policy(configuration: &Value) -> Result<MisfirePolicy, String> {
+    match configuration.get(TRIGGER_MISFIRE_KEY) {
+        None | Some(Value::Null) => Ok(MisfirePolicy::default()),
+        Some(value) => value
+            .as_str()
+            .ok_or_else(|| format!("{TRIGGER_MISFIRE_KEY} must be a string"))
+            .and_then(MisfirePolicy::try_from),
+    }
+}
+
+/// cron trigger configuration key spreading a trigger's firings over up to this many seconds after
+/// each cron slot, so many triggers sharing a schedule do not all start in the same second.
+pub const TRIGGER_JITTER_KEY: &str = "jitter_seconds";
+
+/// longest jitter a cron trigger may ask for: one hour.
+pub const MAX_TRIGGER_JITTER_SECONDS: i64 = 60 * 60;
+
+/// the jitter window a cron trigger's configuration asks for. absent means 0 (fire on the slot).
+pub fn trigger_jitter_seconds(configuration: &Value) -> Result<i64, String> {
+    match configuration.get(TRIGGER_JITTER_KEY) {
+        None | Some(Value::Null) => Ok(0),
+        Some(value) => value
+            .as_i64()
+            .filter(|seconds| (0..=MAX_TRIGGER_JITTER_SECONDS).contains(seconds))
+            .ok_or_else(|| {
+                format!(
+                    "{TRIGGER_JITTER_KEY} must be a whole number of seconds between 0 and {MAX_TRIGGER_JITTER_SECONDS}"
+                )
+            }),
+    }
+}
+
+/// cron trigger configuration key shifting every one of a trigger's firings this many seconds after
+/// its cron slot, so a copied environment can run the same schedules later than the original.
+pub const TRIGGER_OFFSET_KEY: &str = "offset_seconds";
+
+/// longest offset a cron trigger may ask for: one day.
+pub const MAX_TRIGGER_OFFSET_SECONDS: i64 = 24 * 60 * 60;
+
+/// the offset a cron trigger's configuration asks for. absent means 0 (fire on the slot).
+pub fn trigger_offset_seconds(configuration: &Value) -> Result<i64, String> {
+    match configuration.get(TRIGGER_OFFSET_KEY) {
+        None | Some(Value::Null) => Ok(0),
+        Some(value) => value
+            .as_i64()
+            .filter(|seconds| (0..=MAX_TRIGGER_OFFSET_SECONDS).contains(seconds))
+            .ok_or_else(|| {
+                format!(
+                    "{TRIGGER_OFFSET_KEY} must be a whole number of seconds between 0 and {MAX_TRIGGER_OFFSET_SECONDS}"
+                )
+            }),
+    }
+}
+
+impl WorkflowTrigger {
+    /// why the trigger loop cannot schedule this trigger, when its cron last failed to parse.
+    pub fn schedule_error(&self) -> Option<&str> {
+        trigger_schedule_error(&self.metadata)
+    }
+}
+
+/// why a trigger fires at a different time than its cron slot.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ScheduleAdjustmentKind {
+    /// `offset_seconds` shifts every slot.
+    Offset,
+    /// `jitter_seconds` places this trigger a fixed number of seconds after each slot.
+    Jitter,
+    /// the slot is already overdue, and the misfire policy decides what fires.
+    Misfire,
+    /// a blackout holds the firing until it ends.
+    Blackout,
+}
+
+/// one adjustment between a trigger's cron slot and when it actually fires, in the order the
+/// trigger loop applies them. `from` is the time before the adjustment, `to` the time after.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScheduleAdjustment {
+    pub kind: ScheduleAdjustmentKind,
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub reason: String,
+}
+
+/// when a trigger will actually fire next and why that differs from its cron slot.
+/// `effective_next_run` is `None` for a disabled trigger, a trigger that is not cron, or one whose
+/// schedule does not evaluate.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct EffectiveSchedule {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effective_next_run: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub schedule_adjustments: Vec<ScheduleAdjustment>,
+}
+
+/// a trigger as the trigger read endpoints return it: the stored trigger's fields plus its
+/// [`EffectiveSchedule`], so a client reading a plain [`WorkflowTrigger`] still decodes it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowTriggerView {
+    #[serde(flatten)]
+    pub trigger: WorkflowTrigger,
+    #[serde(flatten)]
+    pub schedule: EffectiveSchedule,
+}
+
+/// one cron trigger whose stored `next_execution` disagrees with its schedule, as found by a
+/// schedule reconcile. `proposed_next_execution` is `None` (with `error` set) when the cron cannot
+/// be parsed; such triggers are reported but left for the trigger loop to park.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleReconciliation {
+    /// `workflow` or `pipeline`.
+    pub trigger_type: String,
+    pub trigger_id: Uuid,
+    /// the workflow or pipeline the trigger starts.
+    pub target_id: Uuid,
+    pub cron: String,
+    pub current_next_execution: Option<DateTime<Utc>>,
+    pub proposed_next_execution: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// outcome of recomputing every enabled cron trigger's `next_execution`. on a dry run `changes`
+/// lists what would be written; otherwise what was.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleReconcileReport {
+    pub dry_run: bool,
+    /// enabled cron triggers examined.
+    pub checked: usize,
+    pub changes: Vec<ScheduleReconciliation>,
+}
+
+/// how many upcoming fire times a schedule change preview lists on each side.
+pub const SCHEDULE_PREVIEW_COUNT: usize = 5;
+
+/// the next fire times of a trigger's cron before and after an edit, so a reviewer can confirm a
+/// schedule change does what they intended. a side is empty when it has no cron (the trigger is
+/// new, or not a cron trigger) or when its cron cannot be parsed, in which case `error` says why.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SchedulePreview {
+    pub before_cron: Option<String>,
+    pub after_cron: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub before_timezone: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after_timezone: Option<String>,
+    pub before: Vec<DateTime<Utc>>,
+    pub after: Vec<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl SchedulePreview {
+    pub fn changed(&self) -> bool {
+        self.before_cron != self.after_cron || self.before_timezone != self.after_timezone
+    }
+
+    /// a one-line rendering for logs: `'<before>' -> '<after>'` with both sides' fire times. a
+    /// zoned side is followed by its zone.
+    pub fn describe(&self) -> String {
+        let times = |times: &[DateTime<Utc>]| {
+            times
+                .iter()
+                .map(|time| time.format("%Y-%m-%d %H:%M:%S").to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let schedule = |cron: &Option<String>, timezone: &Option<String>| match timezone {
+            Some(timezone) => format!("'{}' {timezone}", cron.as_deref().unwrap_or("-")),
+            None => format!("'{}'", cron.as_deref().unwrap_or("-")),
+        };
+        format!(
+            "{} -> {} (before: [{}]; after: [{}])",
+            schedule(&self.before_cron, &self.before_timezone),
+            schedule(&self.after_cron, &self.after_timezone),
+            times(&self.before),
+            times(&self.after)
+        )
+    }
+}
+
+/// the most fire times a standalone cron preview lists.
+pub const MAX_CRON_PREVIEW_COUNT: usize = 100;
+
+/// a standalone cron check, independent of any trigger: validate `cron` and list its next `count`
+/// fire times after `from` (default now), read in `timezone` when given. `blackout_start`/
+/// `blackout_end` preview a blackout window.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CronPreviewRequest {
+    pub cron: String,
+    #[serde(default)]
+    pub count: Option<usize>,
+    #[serde(default)]
+    pub from: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub blackout_start: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub blackout_end: Option<DateTime<Utc>>,
+    /// iana zone the cron's fields are read in; utc when absent.
+    #[serde(default)]
+    pub timezone: Option<String>,
+}
+
+/// one upcoming firing. `deferred` marks a slot that fell inside the blackout window and so fires
+/// when the window ends, as the trigger loop would run it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CronFireTime {
+    pub at: DateTime<Utc>,
+    #[serde(default)]
+    pub deferred: bool,
+}
+
+/// the result of a cron preview. an unparseable expression is `valid: false` with no fire times and
+/// the parse error in `error`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CronPreview {
+    pub cron: String,
+    pub valid: bool,
+    pub fire_times: Vec<CronFireTime>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// a trigger update previewed with `?preview=true`: the trigger as it would be saved (nothing is
+/// written) and the schedule change it would make.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowTriggerPreview {
+    pub trigger: WorkflowTrigger,
+    pub schedule: SchedulePreview,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct WorkflowObject(Value);
 
@@ -604,6 +1261,32 @@ pub struct WorkflowAction {
     /// is live.
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub required_labels: BTreeMap<String, String>,
+    /// `scope/name` patterns (with `*` wildcards) naming the only secrets this action may resolve.
+    /// checked at save time against literal references and by the worker before fetching. empty
+    /// leaves the action unrestricted.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_secrets: Vec<String>,
+    /// checks the worker runs before invoking the provider (free disk, writable paths, reachable
+    /// targets). the first failure fails the node without running the action.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub preflight: Vec<PreflightCheck>,
+    /// marks the action idempotent and read-only: a worker that ran the same command successfully
+    /// within this many seconds records that result instead of executing again.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_ttl_seconds: Option<i64>,
+    /// broker delivery priority of this action's commands. backends that order their queue hand
+    /// higher priorities to workers first, so an alerting action does not wait behind bulk exports;
+    /// 0 is the default.
+    #[serde(default, skip_serializing_if = "is_default_priority")]
+    pub priority: i32,
+    /// broker queue this action's commands are published on; only workers subscribed to it pick
+    /// them up, so heavy exports can run on dedicated workers. `None` is the default queue.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub queue: Option<String>,
+}
+
+fn is_default_priority(priority: &i32) -> bool {
+    *priority == 0
 }
 
 fn default_timeout_seconds() -> i64 {
@@ -629,6 +1312,16 @@ impl<'de> Deserialize<'de> for WorkflowAction {
             pub tags: Vec<String>,
             #[serde(default)]
             pub required_labels: BTreeMap<String, String>,
+            #[serde(default)]
+            pub allowed_secrets: Vec<String>,
+            #[serde(default)]
+            pub preflight: Vec<PreflightCheck>,
+            #[serde(default)]
+            pub cache_ttl_seconds: Option<i64>,
+            #[serde(default)]
+            pub priority: i32,
+            #[serde(default)]
+            pub queue: Option<String>,
             #[serde(flatten)]
             pub extra: Map,
         }
@@ -649,6 +1342,11 @@ impl<'de> Deserialize<'de> for WorkflowAction {
             mcp_enabled: raw.mcp_enabled,
             tags: raw.tags,
             required_labels: raw.required_labels,
+            allowed_secrets: raw.allowed_secrets,
+            preflight: raw.preflight,
+            cache_ttl_seconds: raw.cache_ttl_seconds,
+            priority: raw.priority,
+            queue: raw.queue.filter(|queue| !queue.trim().is_empty()),
         })
     }
 }
@@ -983,6 +1681,9 @@ pub struct WorkflowNodeRun {
     pub executor_claimed_at: Option<DateTime<Utc>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub executor_released_at: Option<DateTime<Utc>>,
+    /// how long the node's command waited in the broker before a worker started it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub queue_wait_ms: Option<i64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1034,3 +1735,13 @@ pub struct WorkflowRunArtifact {
     pub metadata: Value,
     pub created_at: DateTime<Utc>,
 }
+
+/// A small named value (string or number) a workflow run recorded for other workflows to reference
+/// as `{{output:workflow.key}}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowRunOutput {
+    pub workflow_run_id: Uuid,
+    pub name: String,
+    pub value: Value,
+    pub created_at: DateTime<Utc>,
+}
@@ -239,6 +239,7 @@ pub fn load_workflow_bundle_with_providers(
                     providers,
                 )?,
                 triggers: Vec::new(),
+                baselines: Vec::new(),
             })
         }
         _ => Err(PackError::source(format!(
@@ -432,6 +433,7 @@ fn load_wdl_directory(dir: &Path, providers: &[ProviderMetadata]) -> Result<Work
     Ok(WorkflowBundle {
         workflows,
         triggers: Vec::new(),
+        baselines: Vec::new(),
     })
 }
 
@@ -489,6 +491,7 @@ fn load_wdl_pack_manifest(path: &Path, providers: &[ProviderMetadata]) -> Result
     Ok(WorkflowBundle {
         workflows,
         triggers,
+        baselines: Vec::new(),
     })
 }
 
@@ -56,6 +56,7 @@ pub(crate) fn execute_request(
         ),
         chunks: Vec::new(),
         artifacts: Vec::new(),
+        error: None,
     };
     std::fs::write(response_path, serde_json::to_vec_pretty(&response)?)?;
     Ok(if exit_code == 0 { 0 } else { exit_code })
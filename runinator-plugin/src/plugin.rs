@@ -163,13 +163,19 @@ impl Plugin {
             let _ = canceller.join();
         }
 
+        // a plugin that wrote a structured error is reported by it, whatever its exit code; a bare
+        // non-zero exit keeps the generic failure.
+        let response = read_response(&response_path);
+        if let Ok(ProviderExecutionResponse {
+            error: Some(error), ..
+        }) = response
+        {
+            return Err(Box::new(error));
+        }
         if result != 0 {
             return Err(crate::errors::EXECUTION_FAILED.bare());
         }
-
-        let response_file = File::open(&response_path)?;
-        let response: ProviderExecutionResponse = serde_json::from_reader(response_file)?;
-        Ok(response.into())
+        Ok(response?.into())
     }
 
     fn plugin_metadata(&self) -> Result<ProviderMetadata, SendableError> {
@@ -189,6 +195,11 @@ impl Plugin {
     }
 }
 
+fn read_response(path: &Path) -> Result<ProviderExecutionResponse, SendableError> {
+    let response_file = File::open(path)?;
+    Ok(serde_json::from_reader(response_file)?)
+}
+
 fn path_to_cstring(path: &Path, kind: &str) -> Result<CString, SendableError> {
     CString::new(path.to_string_lossy().as_bytes()).map_err(|err| {
         crate::errors::PATH_INVALID.error(format!(
@@ -8,6 +8,10 @@ use runinator_models::{
 
 use crate::cancel::CancellationToken;
 
+/// the structured failure a provider returns (boxed as its execution error) so the worker can
+/// record a machine-readable code and honour the retryable flag.
+pub use runinator_models::runs::ProviderError;
+
 pub trait ProviderEventSink: Send + Sync {
     fn emit(&self, event: ProviderExecutionEvent);
 }
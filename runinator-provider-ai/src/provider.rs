@@ -59,6 +59,7 @@ impl Provider for AiCommandProvider {
             metadata: ProviderRuntimeMetadata {
                 credential_scopes: Vec::new(),
                 contract: Some("stdin/stdout JSON".into()),
+                version: None,
             },
         }
     }
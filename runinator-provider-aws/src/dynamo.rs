@@ -19,7 +19,10 @@ use crate::errors::{
     DYNAMO_TIMEOUT, INVALID_ATTRIBUTE_VALUE, MISSING_KEY_CONDITION, MISSING_PARTIQL_STATEMENT,
 };
 use runinator_utilities::data_export::{
-    TableData, TableExportContext, TableExporter, csv::CsvTableExporter, excel::ExcelTableExporter,
+    TableData, TableExportContext, TableExporter,
+    csv::CsvTableExporter,
+    excel::ExcelTableExporter,
+    manifest::{ExportManifest, MANIFEST_MIME_TYPE},
 };
 use serde::Deserialize;
 use serde_json::{Map as JsonMap, Value as JsonValue, json};
@@ -264,7 +267,35 @@ fn export_results(
         .into(),
     };
 
-    Ok(DynamoDumpResult { rows, artifact })
+    let manifest = if request.manifest {
+        let mut manifest = ExportManifest::new("AWS");
+        manifest.add_file(&output_path, rows)?;
+        let manifest_name = format!("{file_stem}.manifest.json");
+        let manifest_path = dump_dir.join(&manifest_name);
+        let size_bytes = manifest.write(&manifest_path)?;
+        info!("Wrote export manifest to {}", manifest_path.display());
+        Some(NewRunArtifact {
+            name: manifest_name,
+            mime_type: MANIFEST_MIME_TYPE.to_string(),
+            size_bytes: size_bytes as i64,
+            uri: manifest_path.to_string_lossy().into_owned(),
+            metadata: json!({
+                "provider": "AWS",
+                "service": "DynamoDB",
+                "table_name": request.table_name,
+                "manifest": true,
+            })
+            .into(),
+        })
+    } else {
+        None
+    };
+
+    Ok(DynamoDumpResult {
+        rows,
+        artifact,
+        manifest,
+    })
 }
 
 fn items_to_table_data(items: &[HashMap<String, AttributeValue>]) -> TableData {
@@ -645,6 +676,8 @@ impl DynamoQueryType {
 pub struct DynamoDumpResult {
     pub rows: usize,
     pub artifact: NewRunArtifact,
+    /// the integrity manifest, when the request asked for one.
+    pub manifest: Option<NewRunArtifact>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -683,4 +716,7 @@ struct DynamoDumpRequest {
     partiql_statement: Option<String>,
     #[serde(default)]
     partiql_parameters: Option<Vec<JsonValue>>,
+    /// also write `<file>.manifest.json` with the export's rows and sha-256.
+    #[serde(default)]
+    manifest: bool,
 }
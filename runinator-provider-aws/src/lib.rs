@@ -40,17 +40,21 @@ impl Provider for AwsProvider {
                         ParameterMetadata::optional("partiql_statement", RuninatorType::String),
                         ParameterMetadata::optional("format", RuninatorType::String)
                             .with_default(json!("excel")),
+                        ParameterMetadata::optional("manifest", RuninatorType::Boolean)
+                            .with_default(json!(false)),
                     ])
                     .with_results(vec![
                         ResultMetadata::new("provider", RuninatorType::String),
                         ResultMetadata::new("service", RuninatorType::String),
                         ResultMetadata::new("rows", RuninatorType::Integer),
                         ResultMetadata::new("artifact", artifact_type()),
+                        ResultMetadata::new("manifest", artifact_type()),
                     ]),
             ],
             metadata: ProviderRuntimeMetadata {
                 credential_scopes: vec!["aws".into()],
                 contract: None,
+                version: None,
             },
         }
     }
@@ -67,22 +71,25 @@ impl Provider for AwsProvider {
             "dynamo_dump" => {
                 let result =
                     dynamo::run_dynamo_dump(request.parameters.into(), request.timeout_secs)?;
+                let mut output = json!({
+                    "provider": "AWS",
+                    "service": "DynamoDB",
+                    "rows": result.rows,
+                    "artifact": result.artifact,
+                });
+                if let Some(manifest) = &result.manifest {
+                    output["manifest"] = json!(manifest);
+                }
                 Ok(TaskExecutionResult {
                     message: Some(format!(
                         "Exported {} DynamoDB row(s) to {}",
                         result.rows, result.artifact.uri
                     )),
-                    output_json: Some(
-                        json!({
-                            "provider": "AWS",
-                            "service": "DynamoDB",
-                            "rows": result.rows,
-                            "artifact": result.artifact,
-                        })
-                        .into(),
-                    ),
+                    output_json: Some(output.into()),
                     chunks: Vec::new(),
-                    artifacts: vec![result.artifact],
+                    artifacts: std::iter::once(result.artifact)
+                        .chain(result.manifest)
+                        .collect(),
                 })
             }
             _ => Err(errors::UNSUPPORTED_CALL.error(format!(
@@ -0,0 +1,415 @@
+//! outbound network policy for console commands. a worker (`EGRESS_ALLOW_ENV`) or a single task
+//! (the `egress_allow` parameter) lists the hosts and cidrs a command may reach; the command then
+//! runs with its proxy variables pointed at a loopback proxy that only tunnels to those
+//! destinations. a refused connection is logged, streamed onto the run as a stderr chunk, and listed
+//! in the node's output.
+//!
+//! enforcement is cooperative: a tool that ignores proxy variables or opens raw sockets is not
+//! contained, so a locked-down worker should pair this with host firewall or cluster network policy.
+
+use std::{
+    io::{self, Read, Write},
+    net::{IpAddr, Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    process::Command,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread,
+    time::Duration,
+};
+
+use log::warn;
+use runinator_models::runs::ProviderExecutionEvent;
+use runinator_plugin::provider::ProviderEventSink;
+
+/// one allowed destination.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum EgressRule {
+    /// an exact host name, lowercased.
+    Host(String),
+    /// `*.example.com`: any subdomain of the stored suffix (`.example.com`), not the apex itself.
+    Subdomains(String),
+    /// an address range; a bare address is a /32 (or /128).
+    Cidr(IpAddr, u8),
+}
+
+/// the destinations a console command may connect to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct EgressPolicy {
+    rules: Vec<EgressRule>,
+}
+
+impl EgressPolicy {
+    /// parse host names, `*.` wildcards, addresses, and cidrs. an empty list allows nothing.
+    pub(crate) fn parse<S: AsRef<str>>(entries: &[S]) -> Result<Self, String> {
+        let rules = entries
+            .iter()
+            .map(|entry| parse_rule(entry.as_ref()))
+            .collect::<Result<_, _>>()?;
+        Ok(EgressPolicy { rules })
+    }
+
+    /// the worker policy from a comma-separated `EGRESS_ALLOW_ENV` value; unset or blank means the
+    /// worker imposes none.
+    pub(crate) fn from_env_value(raw: Option<&str>) -> Result<Option<Self>, String> {
+        let Some(raw) = raw.filter(|raw| !raw.trim().is_empty()) else {
+            return Ok(None);
+        };
+        let entries: Vec<&str> = raw
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .collect();
+        EgressPolicy::parse(&entries).map(Some)
+    }
+
+    /// whether `host` (a name or an address literal) is allowed by name, without resolving it.
+    pub(crate) fn allows_host(&self, host: &str) -> bool {
+        let host = host.trim_end_matches('.').to_ascii_lowercase();
+        if let Ok(ip) = host.parse::<IpAddr>() {
+            return self.allows_ip(ip);
+        }
+        self.rules.iter().any(|rule| match rule {
+            EgressRule::Host(allowed) => *allowed == host,
+            EgressRule::Subdomains(suffix) => host.ends_with(suffix.as_str()),
+            EgressRule::Cidr(..) => false,
+        })
+    }
+
+    pub(crate) fn allows_ip(&self, ip: IpAddr) -> bool {
+        self.rules.iter().any(|rule| match rule {
+            EgressRule::Cidr(network, prefix) => cidr_contains(*network, *prefix, ip),
+            _ => false,
+        })
+    }
+
+    /// whether every destination `other` allows is also allowed here, so a task policy can narrow
+    /// the worker's but never widen it.
+    pub(crate) fn covers(&self, other: &EgressPolicy) -> bool {
+        other.rules.iter().all(|rule| {
+            match rule {
+            EgressRule::Host(host) => self.allows_host(host),
+            EgressRule::Subdomains(suffix) => self.rules.iter().any(|allowed| {
+                matches!(allowed, EgressRule::Subdomains(outer) if suffix.ends_with(outer.as_str()))
+            }),
+            EgressRule::Cidr(network, prefix) => self.rules.iter().any(|allowed| {
+                matches!(allowed, EgressRule::Cidr(outer, outer_prefix)
+                    if outer_prefix <= prefix && cidr_contains(*outer, *outer_prefix, *network))
+            }),
+        }
+        })
+    }
+}
+
+fn parse_rule(entry: &str) -> Result<EgressRule, String> {
+    let entry = entry.trim().trim_end_matches('.').to_ascii_lowercase();
+    if entry.is_empty() {
+        return Err("egress entries must not be empty".into());
+    }
+    if let Some((address, prefix)) = entry.split_once('/') {
+        let address: IpAddr = address
+            .parse()
+            .map_err(|_| format!("'{entry}' is not a valid cidr"))?;
+        let max = if address.is_ipv4() { 32 } else { 128 };
+        let prefix: u8 = prefix
+            .parse()
+            .ok()
+            .filter(|prefix| *prefix <= max)
+            .ok_or_else(|| format!("'{entry}' has a prefix outside 0..={max}"))?;
+        return Ok(EgressRule::Cidr(address, prefix));
+    }
+    if let Ok(address) = entry.parse::<IpAddr>() {
+        let prefix = if address.is_ipv4() { 32 } else { 128 };
+        return Ok(EgressRule::Cidr(address, prefix));
+    }
+    let (rule, name) = match entry.strip_prefix("*.") {
+        Some(name) => (EgressRule::Subdomains(format!(".{name}")), name),
+        None => (EgressRule::Host(entry.clone()), entry.as_str()),
+    };
+    let valid = !name.is_empty()
+        && name.split('.').all(|label| {
+            !label.is_empty()
+                && label
+                    .chars()
+                    .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+        });
+    if valid {
+        Ok(rule)
+    } else {
+        Err(format!("'{entry}' is not a host name, address, or cidr"))
+    }
+}
+
+fn cidr_contains(network: IpAddr, prefix: u8, ip: IpAddr) -> bool {
+    match (network, ip) {
+        (IpAddr::V4(network), IpAddr::V4(ip)) => {
+            let mask = u32::MAX.checked_shl(32 - u32::from(prefix)).unwrap_or(0);
+            u32::from(network) & mask == u32::from(ip) & mask
+        }
+        (IpAddr::V6(network), IpAddr::V6(ip)) => {
+            let mask = u128::MAX.checked_shl(128 - u32::from(prefix)).unwrap_or(0);
+            u128::from(network) & mask == u128::from(ip) & mask
+        }
+        _ => false,
+    }
+}
+
+/// proxy variables the command sees. the bypass lists are cleared so a command cannot exempt
+/// itself.
+const PROXY_VARS: [&str; 6] = [
+    "HTTP_PROXY",
+    "HTTPS_PROXY",
+    "ALL_PROXY",
+    "http_proxy",
+    "https_proxy",
+    "all_proxy",
+];
+const NO_PROXY_VARS: [&str; 2] = ["NO_PROXY", "no_proxy"];
+
+/// longest request head the proxy reads before giving up on a connection.
+const MAX_HEAD_BYTES: usize = 16 * 1024;
+
+/// how long the proxy waits on each address of an allowed destination before trying the next.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// a loopback http proxy enforcing one policy for the life of one console command.
+pub(crate) struct EgressProxy {
+    addr: SocketAddr,
+    stop: Arc<AtomicBool>,
+    denied: Arc<Mutex<Vec<String>>>,
+}
+
+impl EgressProxy {
+    pub(crate) fn start(
+        policy: EgressPolicy,
+        sink: Option<Arc<dyn ProviderEventSink>>,
+    ) -> io::Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", 0))?;
+        listener.set_nonblocking(true)?;
+        let addr = listener.local_addr()?;
+        let stop = Arc::new(AtomicBool::new(false));
+        let denied = Arc::new(Mutex::new(Vec::new()));
+        let policy = Arc::new(policy);
+        {
+            let stop = Arc::clone(&stop);
+            let denied = Arc::clone(&denied);
+            thread::spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    match listener.accept() {
+                        Ok((client, _)) => {
+                            let policy = Arc::clone(&policy);
+                            let denied = Arc::clone(&denied);
+                            let sink = sink.clone();
+                            thread::spawn(move || {
+                                let _ = serve(client, &policy, &denied, sink.as_deref());
+                            });
+                        }
+                        Err(err) if err.kind() == io::ErrorKind::WouldBlock => {
+                            thread::sleep(Duration::from_millis(20));
+                        }
+                        Err(err) => {
+                            warn!("Console egress proxy stopped accepting: {err}");
+                            break;
+                        }
+                    }
+                }
+            });
+        }
+        Ok(EgressProxy { addr, stop, denied })
+    }
+
+    /// point `command`'s proxy variables at this proxy.
+    pub(crate) fn apply(&self, command: &mut Command) {
+        let url = format!("http://{}", self.addr);
+        for var in PROXY_VARS {
+            command.env(var, &url);
+        }
+        for var in NO_PROXY_VARS {
+            command.env_remove(var);
+        }
+    }
+
+    /// every `host:port` refused so far, in order.
+    pub(crate) fn denied(&self) -> Vec<String> {
+        self.denied
+            .lock()
+            .map(|denied| denied.clone())
+            .unwrap_or_default()
+    }
+
+    #[cfg(test)]
+    pub(crate) fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+}
+
+impl Drop for EgressProxy {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+// one proxied connection: `CONNECT host:port` tunnels, an absolute-uri request is forwarded in
+// origin form. a destination outside the policy gets a 403 and is recorded; an allowed one that
+// cannot be resolved or reached gets a 502.
+fn serve(
+    mut client: TcpStream,
+    policy: &EgressPolicy,
+    denied: &Mutex<Vec<String>>,
+    sink: Option<&dyn ProviderEventSink>,
+) -> io::Result<()> {
+    client.set_nonblocking(false)?;
+    let (head, body) = read_head(&mut client)?;
+    let Some(request) = ProxyRequest::parse(&head) else {
+        return client.write_all(b"HTTP/1.1 400 Bad Request\r\nContent-Length: 0\r\n\r\n");
+    };
+    let destination = format!("{}:{}", request.host, request.port);
+    let addresses = match allowed_addresses(policy, &request.host, request.port) {
+        Ok(addresses) => addresses,
+        Err(err) => return bad_gateway(&mut client, &destination, err),
+    };
+    let Some(addresses) = addresses else {
+        warn!("Console egress denied: {destination}");
+        if let Ok(mut denied) = denied.lock() {
+            denied.push(destination.clone());
+        }
+        if let Some(sink) = sink {
+            sink.emit(ProviderExecutionEvent::Chunk {
+                stream: "stderr".into(),
+                content: format!("egress denied: {destination} is outside the egress policy"),
+            });
+        }
+        return client.write_all(b"HTTP/1.1 403 Forbidden\r\nContent-Length: 0\r\n\r\n");
+    };
+    let mut upstream = match connect(&addresses) {
+        Ok(upstream) => upstream,
+        Err(err) => return bad_gateway(&mut client, &destination, err),
+    };
+    match request.forward_head {
+        None => client.write_all(b"HTTP/1.1 200 Connection Established\r\n\r\n")?,
+        Some(forward_head) => upstream.write_all(forward_head.as_bytes())?,
+    }
+    upstream.write_all(&body)?;
+    relay(client, upstream)
+}
+
+fn read_head(client: &mut TcpStream) -> io::Result<(String, Vec<u8>)> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 4096];
+    loop {
+        if let Some(end) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+            let body = buffer.split_off(end + 4);
+            return Ok((String::from_utf8_lossy(&buffer).into_owned(), body));
+        }
+        if buffer.len() > MAX_HEAD_BYTES {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "proxy request head too large",
+            ));
+        }
+        let read = client.read(&mut chunk)?;
+        if read == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    }
+}
+
+struct ProxyRequest {
+    host: String,
+    port: u16,
+    /// the head to send upstream for a plain http request; `None` for a `CONNECT` tunnel.
+    forward_head: Option<String>,
+}
+
+impl ProxyRequest {
+    fn parse(head: &str) -> Option<Self> {
+        let (request_line, rest) = head.split_once("\r\n")?;
+        let mut parts = request_line.split_whitespace();
+        let (method, target, version) = (parts.next()?, parts.next()?, parts.next()?);
+        if method.eq_ignore_ascii_case("CONNECT") {
+            let (host, port) = split_host_port(target, None)?;
+            return Some(ProxyRequest {
+                host,
+                port,
+                forward_head: None,
+            });
+        }
+        let remainder = target.strip_prefix("http://")?;
+        let (authority, path) = match remainder.find('/') {
+            Some(index) => remainder.split_at(index),
+            None => (remainder, "/"),
+        };
+        let (host, port) = split_host_port(authority, Some(80))?;
+        Some(ProxyRequest {
+            host,
+            port,
+            forward_head: Some(format!("{method} {path} {version}\r\n{rest}")),
+        })
+    }
+}
+
+// `host:port`, `[v6]:port`, or (with a default port) a bare host.
+fn split_host_port(authority: &str, default_port: Option<u16>) -> Option<(String, u16)> {
+    if let Some(rest) = authority.strip_prefix('[') {
+        let (host, after) = rest.split_once(']')?;
+        let port = match after.strip_prefix(':') {
+            Some(port) => port.parse().ok()?,
+            None => default_port?,
+        };
+        return Some((host.to_string(), port));
+    }
+    match authority.rsplit_once(':') {
+        Some((host, port)) => Some((host.to_string(), port.parse().ok()?)),
+        None => Some((authority.to_string(), default_port?)),
+    }
+}
+
+// the addresses `host` may be reached at, or `None` when the policy refuses it. a host allowed by
+// name may use whatever it resolves to; otherwise only its addresses inside an allowed cidr count.
+// a failed lookup is an error, not a refusal.
+fn allowed_addresses(
+    policy: &EgressPolicy,
+    host: &str,
+    port: u16,
+) -> io::Result<Option<Vec<SocketAddr>>> {
+    let by_name = policy.allows_host(host);
+    let addresses: Vec<SocketAddr> = (host, port)
+        .to_socket_addrs()?
+        .filter(|address| by_name || policy.allows_ip(address.ip()))
+        .collect();
+    Ok(Some(addresses).filter(|addresses| !addresses.is_empty()))
+}
+
+// the first of `addresses` that accepts within `CONNECT_TIMEOUT`.
+fn connect(addresses: &[SocketAddr]) -> io::Result<TcpStream> {
+    let mut last_err = io::Error::new(io::ErrorKind::NotFound, "no address to connect to");
+    for address in addresses {
+        match TcpStream::connect_timeout(address, CONNECT_TIMEOUT) {
+            Ok(stream) => return Ok(stream),
+            Err(err) => last_err = err,
+        }
+    }
+    Err(last_err)
+}
+
+// an allowed destination that could not be reached: a network failure, not a policy violation.
+fn bad_gateway(client: &mut TcpStream, destination: &str, err: io::Error) -> io::Result<()> {
+    warn!("Console egress to {destination} failed: {err}");
+    client.write_all(b"HTTP/1.1 502 Bad Gateway\r\nContent-Length: 0\r\n\r\n")
+}
+
+fn relay(client: TcpStream, upstream: TcpStream) -> io::Result<()> {
+    let mut client_read = client.try_clone()?;
+    let mut upstream_write = upstream.try_clone()?;
+    let outbound = thread::spawn(move || {
+        let _ = io::copy(&mut client_read, &mut upstream_write);
+        let _ = upstream_write.shutdown(Shutdown::Write);
+    });
+    let (mut upstream_read, mut client_write) = (upstream, client);
+    let _ = io::copy(&mut upstream_read, &mut client_write);
+    let _ = client_write.shutdown(Shutdown::Write);
+    let _ = outbound.join();
+    Ok(())
+}
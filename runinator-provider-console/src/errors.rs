@@ -37,6 +37,17 @@ pub(crate) const WORKING_DIR_MISSING: ErrorDescriptor = ErrorDescriptor::new(
     "Configured console working directory does not exist",
 );
 
+pub(crate) const EGRESS_INVALID_POLICY: ErrorDescriptor = ErrorDescriptor::new(
+    "CONSOLE010",
+    "console.egress.invalid_policy",
+    "Invalid console egress policy",
+);
+pub(crate) const EGRESS_NOT_PERMITTED: ErrorDescriptor = ErrorDescriptor::new(
+    "CONSOLE011",
+    "console.egress.not_permitted",
+    "Requested egress is outside this worker's egress policy",
+);
+
 pub(crate) const DICTIONARY: &[ErrorDescriptor] = &[
     INVALID_PARAMS,
     IO,
@@ -47,6 +58,8 @@ pub(crate) const DICTIONARY: &[ErrorDescriptor] = &[
     TIMEOUT,
     INTERACTIVE_NOT_PERMITTED,
     WORKING_DIR_MISSING,
+    EGRESS_INVALID_POLICY,
+    EGRESS_NOT_PERMITTED,
 ];
 
 impl ProviderErrors for ConsoleProvider {
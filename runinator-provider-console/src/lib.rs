@@ -1,3 +1,4 @@
+mod egress;
 mod errors;
 mod params;
 mod provider;
@@ -16,5 +17,11 @@ pub const ALLOW_INTERACTIVE_ENV: &str = "RUNINATOR_CONSOLE_ALLOW_INTERACTIVE";
 /// desktop agent sets it from its configured working directory.
 pub const WORKING_DIR_ENV: &str = "RUNINATOR_CONSOLE_WORKING_DIR";
 
+/// env var a worker sets to the comma-separated hosts (`api.example.com`, `*.example.com`),
+/// addresses, and cidrs console commands may reach. commands then run behind a loopback proxy that
+/// refuses everything else, and a task's `egress_allow` may only narrow the list. unset/empty means
+/// no worker policy.
+pub const EGRESS_ALLOW_ENV: &str = "RUNINATOR_CONSOLE_EGRESS_ALLOW";
+
 #[cfg(test)]
 mod tests;
@@ -11,6 +11,16 @@ pub(crate) struct ConsoleParams {
     // output is not captured or streamed in this mode. defaults to false: capture and stream.
     #[serde(default)]
     pub interactive: bool,
+    // non-zero exit codes that still settle the node as succeeded, with `exit_code` in its output,
+    // so `transitions.branches` can route follow-ups on `steps.<node>.output.exit_code` (exit 2 ->
+    // cleanup, exit 3 -> notify). any other non-zero exit still fails the node.
+    #[serde(default)]
+    pub success_exit_codes: Vec<i32>,
+    // hosts, `*.` wildcards, and cidrs this command may reach, enforced through a loopback proxy.
+    // must stay inside the worker's `EGRESS_ALLOW_ENV` policy when one is set; omitted means the
+    // worker's policy (or none).
+    #[serde(default)]
+    pub egress_allow: Option<Vec<String>>,
 }
 
 #[derive(Serialize)]
@@ -19,6 +29,8 @@ pub(crate) struct ConsoleResult {
     pub exit_code: i32,
     pub duration_ms: i64,
     pub command: String,
+    // destinations the egress proxy refused while the command ran; empty without a policy.
+    pub egress_denied: Vec<String>,
 }
 
 pub(crate) fn parse_params(
@@ -32,12 +32,33 @@ impl Provider for ConsoleProvider {
                                 "run attached to the worker's desktop session so the command can \
                                  prompt (browser login, Keychain dialog); output is not streamed",
                             ),
+                        ParameterMetadata::optional(
+                            "success_exit_codes",
+                            RuninatorType::array(RuninatorType::Integer),
+                        )
+                        .with_description(
+                            "non-zero exit codes that succeed with `exit_code` in the output, so \
+                             branches can route follow-ups by exit code",
+                        ),
+                        ParameterMetadata::optional(
+                            "egress_allow",
+                            RuninatorType::array(RuninatorType::String),
+                        )
+                        .with_description(
+                            "hosts, `*.` wildcards, and cidrs the command may reach through the \
+                             egress proxy; narrows the worker's policy",
+                        ),
                     ])
                     .with_results(vec![
                         ResultMetadata::new("success", RuninatorType::Boolean),
                         ResultMetadata::new("exit_code", RuninatorType::Integer),
                         ResultMetadata::new("duration_ms", RuninatorType::Integer),
                         ResultMetadata::new("command", RuninatorType::String),
+                        ResultMetadata::new(
+                            "egress_denied",
+                            RuninatorType::array(RuninatorType::String),
+                        )
+                        .with_description("`host:port` destinations the egress proxy refused"),
                     ]),
             ],
             metadata: ProviderRuntimeMetadata::default(),
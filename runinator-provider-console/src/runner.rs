@@ -18,9 +18,10 @@ use runinator_models::{
 use runinator_plugin::cancel::CancellationToken;
 use runinator_plugin::provider::ProviderEventSink;
 
+use crate::egress::{EgressPolicy, EgressProxy};
 use crate::errors::{
-    CANCELED, INTERACTIVE_NOT_PERMITTED, NONZERO_EXIT, STDERR_UNAVAILABLE, STDOUT_UNAVAILABLE,
-    TIMEOUT, WORKING_DIR_MISSING,
+    CANCELED, EGRESS_INVALID_POLICY, EGRESS_NOT_PERMITTED, INTERACTIVE_NOT_PERMITTED, NONZERO_EXIT,
+    STDERR_UNAVAILABLE, STDOUT_UNAVAILABLE, TIMEOUT, WORKING_DIR_MISSING,
 };
 use crate::params::{ConsoleResult, parse_params, to_runtime_error};
 
@@ -49,6 +50,41 @@ fn working_dir(raw: Option<&str>) -> Option<PathBuf> {
     }
 }
 
+// the policy this command runs under: the task's `egress_allow` within the worker's
+// `EGRESS_ALLOW_ENV` policy. a malformed worker policy fails closed rather than running unguarded.
+fn command_egress_policy(task: Option<&[String]>) -> Result<Option<EgressPolicy>, SendableError> {
+    let worker =
+        EgressPolicy::from_env_value(std::env::var(crate::EGRESS_ALLOW_ENV).ok().as_deref())
+            .map_err(|err| {
+                EGRESS_INVALID_POLICY.error(format!("{}: {err}", crate::EGRESS_ALLOW_ENV))
+            })?;
+    let Some(task) = task else {
+        return Ok(worker);
+    };
+    let task = EgressPolicy::parse(task)
+        .map_err(|err| EGRESS_INVALID_POLICY.error(format!("egress_allow: {err}")))?;
+    match worker {
+        Some(worker) if !worker.covers(&task) => Err(EGRESS_NOT_PERMITTED
+            .error("egress_allow names a destination outside this worker's egress policy")),
+        _ => Ok(Some(task)),
+    }
+}
+
+// start the egress proxy for `policy`, if any, and route `command` through it. the proxy lives as
+// long as the returned guard.
+fn route_egress(
+    command: &mut Command,
+    policy: Option<EgressPolicy>,
+    sink: Option<Arc<dyn ProviderEventSink>>,
+) -> Result<Option<EgressProxy>, SendableError> {
+    let Some(policy) = policy else {
+        return Ok(None);
+    };
+    let proxy = EgressProxy::start(policy, sink).map_err(to_runtime_error)?;
+    proxy.apply(command);
+    Ok(Some(proxy))
+}
+
 // build the shell command for `command_text`, pinning its `current_dir` to the configured working
 // directory when one is set so a relative path in the command resolves predictably. surfaces a clear
 // error if that directory is configured but missing, rather than letting `spawn` fail obscurely.
@@ -72,6 +108,7 @@ pub(crate) fn execute_command(
     let command_text = params.command;
     let started = Instant::now();
     let timeout = Duration::from_secs(request.timeout_secs.max(1) as u64);
+    let egress = command_egress_policy(params.egress_allow.as_deref())?;
 
     // interactive mode inherits the worker's stdio so the command runs in the operator's desktop
     // session and can present its own prompts (a browser-based `aws sso login`, a Keychain access
@@ -89,13 +126,21 @@ pub(crate) fn execute_command(
             .stdin(Stdio::inherit())
             .stdout(Stdio::inherit())
             .stderr(Stdio::inherit());
+        let proxy = route_egress(&mut command, egress, sink)?;
         let mut child = command.spawn().map_err(to_runtime_error)?;
         let status = wait_for_child(&mut child, timeout, started, token)?;
-        return build_result(status, started, command_text);
+        return build_result(
+            status,
+            started,
+            command_text,
+            &params.success_exit_codes,
+            egress_denied(proxy.as_ref()),
+        );
     }
 
     let mut command = build_shell_command(&command_text)?;
     command.stdout(Stdio::piped()).stderr(Stdio::piped());
+    let proxy = route_egress(&mut command, egress, sink.clone())?;
     let mut child = command.spawn().map_err(to_runtime_error)?;
     let stdout = child
         .stdout
@@ -115,15 +160,29 @@ pub(crate) fn execute_command(
     let _ = stdout_thread.join();
     let _ = stderr_thread.join();
 
-    build_result(status, started, command_text)
+    build_result(
+        status,
+        started,
+        command_text,
+        &params.success_exit_codes,
+        egress_denied(proxy.as_ref()),
+    )
 }
 
-// build the task result from an exited child: success carries the console outcome, a non-zero exit
-// surfaces the shared error code. shared by the piped and interactive execution paths.
+fn egress_denied(proxy: Option<&EgressProxy>) -> Vec<String> {
+    proxy.map(EgressProxy::denied).unwrap_or_default()
+}
+
+// build the task result from an exited child: success (or an exit listed in `success_exit_codes`)
+// carries the console outcome, any other non-zero exit surfaces the shared error code (naming any
+// refused egress, the usual reason a guarded command fails). shared by the piped and interactive
+// execution paths.
 fn build_result(
     status: ExitStatus,
     started: Instant,
     command_text: String,
+    success_exit_codes: &[i32],
+    egress_denied: Vec<String>,
 ) -> Result<TaskExecutionResult, SendableError> {
     let exit_code = status.code().unwrap_or(-1);
     let duration_ms = started.elapsed().as_millis() as i64;
@@ -132,20 +191,31 @@ fn build_result(
         exit_code,
         duration_ms,
         command: command_text,
+        egress_denied,
     };
 
-    if result.success {
+    if exit_accepted(result.success, exit_code, success_exit_codes) {
         Ok(TaskExecutionResult {
             message: Some(format!("Console command exited with code {exit_code}")),
             output_json: serde_json::to_value(result).ok().map(Into::into),
             chunks: Vec::new(),
             artifacts: Vec::new(),
         })
-    } else {
+    } else if result.egress_denied.is_empty() {
         Err(NONZERO_EXIT.error(format!("exit code {exit_code}")))
+    } else {
+        Err(NONZERO_EXIT.error(format!(
+            "exit code {exit_code}; egress denied to {}",
+            result.egress_denied.join(", ")
+        )))
     }
 }
 
+// `success` stays the raw zero-exit flag in the output; acceptance also admits the listed codes.
+fn exit_accepted(success: bool, exit_code: i32, success_exit_codes: &[i32]) -> bool {
+    success || success_exit_codes.contains(&exit_code)
+}
+
 fn spawn_output_thread<R: std::io::Read + Send + 'static>(
     reader: R,
     stop_flag: Arc<AtomicBool>,
@@ -183,7 +253,7 @@ fn spawn_output_thread<R: std::io::Read + Send + 'static>(
 
 #[cfg(test)]
 mod runner_tests {
-    use super::{allow_interactive, working_dir};
+    use super::{allow_interactive, exit_accepted, working_dir};
     use std::path::PathBuf;
 
     #[test]
@@ -196,6 +266,14 @@ mod runner_tests {
         assert!(!allow_interactive(None));
     }
 
+    #[test]
+    fn listed_exit_codes_are_accepted_for_branching() {
+        assert!(exit_accepted(true, 0, &[]));
+        assert!(!exit_accepted(false, 2, &[]));
+        assert!(exit_accepted(false, 2, &[2, 3]));
+        assert!(!exit_accepted(false, 4, &[2, 3]));
+    }
+
     #[test]
     fn working_dir_reads_env_path() {
         // a non-empty, trimmed path is used; unset/empty/blank inherit the process cwd (None).
@@ -1,6 +1,13 @@
+use std::{
+    io::{Read, Write},
+    net::{TcpListener, TcpStream},
+    time::Duration,
+};
+
 use runinator_models::json;
 use runinator_models::runs::ProviderExecutionRequest;
 
+use crate::egress::{EgressPolicy, EgressProxy};
 use crate::params::parse_params;
 
 fn request(parameters: runinator_models::value::Value) -> ProviderExecutionRequest {
@@ -34,6 +41,16 @@ fn parse_params_reads_interactive_flag() {
     assert!(params.interactive);
 }
 
+#[test]
+fn parse_params_reads_success_exit_codes() {
+    let params = parse_params(&request(
+        json!({ "command": "./deploy.sh", "success_exit_codes": [2, 3] }),
+    ))
+    .unwrap();
+
+    assert_eq!(params.success_exit_codes, vec![2, 3]);
+}
+
 #[test]
 fn parse_params_rejects_missing_command() {
     let err = match parse_params(&request(json!({}))) {
@@ -44,3 +61,109 @@ fn parse_params_rejects_missing_command() {
     assert!(err.to_string().contains("CONSOLE001"));
     assert!(err.to_string().contains("missing field `command`"));
 }
+
+#[test]
+fn parse_params_reads_egress_allow() {
+    let params = parse_params(&request(json!({
+        "command": "curl https://api.example.com",
+        "egress_allow": ["api.example.com", "10.0.0.0/8"],
+    })))
+    .unwrap();
+
+    assert_eq!(
+        params.egress_allow,
+        Some(vec![
+            "api.example.com".to_string(),
+            "10.0.0.0/8".to_string()
+        ])
+    );
+}
+
+#[test]
+fn egress_policy_matches_hosts_wildcards_and_cidrs() {
+    let policy =
+        EgressPolicy::parse(&["API.example.com", "*.internal.test", "10.0.0.0/8", "::1"]).unwrap();
+
+    assert!(policy.allows_host("api.example.com"));
+    assert!(policy.allows_host("api.example.com."));
+    assert!(!policy.allows_host("www.example.com"));
+    // a wildcard covers subdomains, not the apex.
+    assert!(policy.allows_host("vault.internal.test"));
+    assert!(!policy.allows_host("internal.test"));
+    assert!(policy.allows_host("10.20.30.40"));
+    assert!(!policy.allows_host("11.0.0.1"));
+    assert!(policy.allows_host("::1"));
+    assert!(!policy.allows_host("::2"));
+}
+
+#[test]
+fn egress_policy_rejects_malformed_entries() {
+    for entry in ["", "10.0.0.0/33", "not a host", "*.", "bad..host"] {
+        assert!(
+            EgressPolicy::parse(&[entry]).is_err(),
+            "{entry:?} should be rejected"
+        );
+    }
+    assert_eq!(EgressPolicy::from_env_value(None), Ok(None));
+    assert_eq!(EgressPolicy::from_env_value(Some("  ")), Ok(None));
+    assert!(
+        EgressPolicy::from_env_value(Some("api.example.com, 10.0.0.0/8"))
+            .unwrap()
+            .is_some()
+    );
+}
+
+#[test]
+fn task_egress_may_only_narrow_the_worker_policy() {
+    let worker = EgressPolicy::parse(&["*.example.com", "10.0.0.0/8"]).unwrap();
+
+    assert!(worker.covers(&EgressPolicy::parse(&["api.example.com", "10.1.0.0/16"]).unwrap()));
+    assert!(worker.covers(&EgressPolicy::parse(&["*.eu.example.com", "10.1.2.3"]).unwrap()));
+    assert!(!worker.covers(&EgressPolicy::parse(&["example.org"]).unwrap()));
+    assert!(!worker.covers(&EgressPolicy::parse(&["0.0.0.0/0"]).unwrap()));
+}
+
+fn proxy_request(proxy: &EgressProxy, target: &str) -> (String, TcpStream) {
+    let mut client = TcpStream::connect(proxy.addr()).unwrap();
+    client
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .unwrap();
+    write!(
+        client,
+        "CONNECT {target} HTTP/1.1\r\nHost: {target}\r\n\r\n"
+    )
+    .unwrap();
+    let mut response = [0u8; 64];
+    let read = client.read(&mut response).unwrap();
+    (
+        String::from_utf8_lossy(&response[..read]).into_owned(),
+        client,
+    )
+}
+
+#[test]
+fn egress_proxy_tunnels_allowed_destinations_and_records_denials() {
+    let upstream = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+    let upstream_addr = upstream.local_addr().unwrap();
+    let proxy = EgressProxy::start(EgressPolicy::parse(&["127.0.0.1/32"]).unwrap(), None).unwrap();
+
+    let (response, mut client) = proxy_request(&proxy, &upstream_addr.to_string());
+    assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+    client.write_all(b"ping").unwrap();
+    let (mut tunneled, _) = upstream.accept().unwrap();
+    let mut received = [0u8; 4];
+    tunneled.read_exact(&mut received).unwrap();
+    assert_eq!(&received, b"ping");
+
+    let (response, _) = proxy_request(&proxy, "10.255.255.1:443");
+    assert!(response.starts_with("HTTP/1.1 403"), "{response}");
+    assert_eq!(proxy.denied(), vec!["10.255.255.1:443".to_string()]);
+
+    // an allowed destination that refuses the connection is a gateway failure, not a violation.
+    let closed = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+    let closed_addr = closed.local_addr().unwrap();
+    drop(closed);
+    let (response, _) = proxy_request(&proxy, &closed_addr.to_string());
+    assert!(response.starts_with("HTTP/1.1 502"), "{response}");
+    assert_eq!(proxy.denied(), vec!["10.255.255.1:443".to_string()]);
+}
@@ -169,6 +169,7 @@ impl Provider for GitHubProvider {
             metadata: ProviderRuntimeMetadata {
                 credential_scopes: vec!["github".into()],
                 contract: None,
+                version: None,
             },
         }
     }
@@ -93,6 +93,7 @@ impl Provider for JiraProvider {
             metadata: ProviderRuntimeMetadata {
                 credential_scopes: vec!["jira".into()],
                 contract: None,
+                version: None,
             },
         }
     }
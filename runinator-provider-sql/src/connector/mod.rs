@@ -3,8 +3,18 @@ use std::time::Duration;
 use runinator_models::errors::SendableError;
 pub use runinator_utilities::data_export::TableData;
 
+use crate::sink::TableWrite;
+
 pub mod postgres;
 
 pub trait DatabaseConnector: Send + Sync {
     fn execute_query(&self, sql: &str, timeout: Duration) -> Result<TableData, SendableError>;
+
+    /// write `data` into the target table in batches, returning the number of rows written.
+    fn write_rows(
+        &self,
+        data: &TableData,
+        write: &TableWrite<'_>,
+        timeout: Duration,
+    ) -> Result<usize, SendableError>;
 }
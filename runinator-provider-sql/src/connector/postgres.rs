@@ -12,6 +12,7 @@ use uuid::Uuid;
 
 use super::{DatabaseConnector, TableData};
 use crate::errors::{QUERY_FAILED, QUERY_TIMEOUT};
+use crate::sink::{TableWrite, batch_insert_sql};
 
 #[derive(Clone)]
 pub struct PostgresConnector {
@@ -50,6 +51,25 @@ impl PostgresConnector {
         })
     }
 
+    fn run_write(
+        connection_string: String,
+        sql: String,
+        batches: Vec<JsonValue>,
+    ) -> Result<usize, SendableError> {
+        let mut client = Client::connect(&connection_string, NoTls).map_err(to_sendable)?;
+        // one transaction for the whole result so a failed batch leaves the target untouched.
+        let mut transaction = client.transaction().map_err(to_sendable)?;
+        let statement = transaction.prepare(&sql).map_err(to_sendable)?;
+        let mut written = 0usize;
+        for batch in &batches {
+            written += transaction
+                .execute(&statement, &[batch])
+                .map_err(to_sendable)? as usize;
+        }
+        transaction.commit().map_err(to_sendable)?;
+        Ok(written)
+    }
+
     fn cell_to_string(row: &Row, idx: usize, column: &Column) -> Result<String, SendableError> {
         let ty = column.type_();
         let value = match *ty {
@@ -139,34 +159,87 @@ impl PostgresConnector {
 
 impl DatabaseConnector for PostgresConnector {
     fn execute_query(&self, sql: &str, timeout: Duration) -> Result<TableData, SendableError> {
-        let effective_timeout = if timeout.is_zero() {
-            Duration::from_secs(30)
-        } else {
-            timeout
-        };
-
         let connection_string = self.connection_string.clone();
         let sql = sql.to_string();
-        let (sender, receiver) = mpsc::channel();
-
-        thread::spawn(move || {
-            let result = Self::run_query(connection_string, sql);
-            let _ = sender.send(result);
-        });
-
-        match receiver.recv_timeout(effective_timeout) {
-            Ok(result) => result,
-            Err(RecvTimeoutError::Timeout) => Err(QUERY_TIMEOUT.error(format!(
-                "PostgreSQL query timed out after {} seconds",
-                effective_timeout.as_secs()
-            ))),
-            Err(RecvTimeoutError::Disconnected) => {
-                Err(QUERY_FAILED.error("PostgreSQL query worker exited before returning a result"))
-            }
+        run_with_timeout(timeout, move || Self::run_query(connection_string, sql))
+    }
+
+    fn write_rows(
+        &self,
+        data: &TableData,
+        write: &TableWrite<'_>,
+        timeout: Duration,
+    ) -> Result<usize, SendableError> {
+        if data.rows.is_empty() {
+            return Ok(0);
         }
+        let sql = batch_insert_sql(write, &data.headers);
+        let batches = data
+            .rows
+            .chunks(write.batch_size.max(1))
+            .map(|chunk| rows_to_json(&data.headers, chunk))
+            .collect::<Vec<_>>();
+        let connection_string = self.connection_string.clone();
+        run_with_timeout(timeout, move || {
+            Self::run_write(connection_string, sql, batches)
+        })
     }
 }
 
+fn run_with_timeout<T, F>(timeout: Duration, work: F) -> Result<T, SendableError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, SendableError> + Send + 'static,
+{
+    let effective_timeout = if timeout.is_zero() {
+        Duration::from_secs(30)
+    } else {
+        timeout
+    };
+
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+        let _ = sender.send(work());
+    });
+
+    match receiver.recv_timeout(effective_timeout) {
+        Ok(result) => result,
+        Err(RecvTimeoutError::Timeout) => Err(QUERY_TIMEOUT.error(format!(
+            "PostgreSQL query timed out after {} seconds",
+            effective_timeout.as_secs()
+        ))),
+        Err(RecvTimeoutError::Disconnected) => {
+            Err(QUERY_FAILED.error("PostgreSQL query worker exited before returning a result"))
+        }
+    }
+}
+
+/// one batch as a json array of objects keyed by column. query results arrive as text with
+/// nulls rendered empty, so empty cells are written back as null.
+pub(crate) fn rows_to_json(headers: &[String], rows: &[Vec<String>]) -> JsonValue {
+    JsonValue::Array(
+        rows.iter()
+            .map(|row| {
+                JsonValue::Object(
+                    headers
+                        .iter()
+                        .zip(row)
+                        .map(|(header, cell)| {
+                            let value = if cell.is_empty() {
+                                JsonValue::Null
+                            } else {
+                                JsonValue::String(cell.clone())
+                            };
+                            (header.clone(), value)
+                        })
+                        .collect(),
+                )
+            })
+            .collect(),
+    )
+}
+
 fn option_to_string<T>(value: Option<T>) -> String
 where
     T: ToString,
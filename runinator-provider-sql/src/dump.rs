@@ -1,15 +1,19 @@
 use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
+use std::time::Duration;
 
-use log::info;
+use log::{info, warn};
 use runinator_models::{
     errors::SendableError,
     runs::{NewRunArtifact, TaskExecutionResult},
 };
 use runinator_plugin::cancel::CancellationToken;
 use runinator_utilities::data_export::{
-    TableExportContext, TableExporter, csv::CsvTableExporter, excel::ExcelTableExporter,
+    TableExportContext, TableExporter,
+    csv::CsvTableExporter,
+    excel::ExcelTableExporter,
+    manifest::{ExportManifest, MANIFEST_MIME_TYPE},
 };
 use serde::Deserialize;
 use serde_json::json;
@@ -17,11 +21,12 @@ use serde_json::json;
 use crate::SqlProvider;
 use crate::connector::DatabaseConnector;
 use crate::connector::postgres::PostgresConnector;
-use crate::errors::{INVALID_ARGUMENT, QUERY_CANCELED};
+use crate::errors::{INVALID_ARGUMENT, QUERY_CANCELED, ROW_COUNT_VIOLATION};
 use crate::format::{DatabaseKind, DumpFormat};
 use crate::helpers::{
     file_size, next_available_stem, normalize_timeout, sanitize_file_stem, to_sendable,
 };
+use crate::sink::TableSink;
 
 pub(crate) struct SqlExport {
     pub name: String,
@@ -36,12 +41,19 @@ pub(crate) struct SqlExport {
 pub(crate) struct DumpDataRequest {
     pub database: DatabaseKind,
     pub connection_string: String,
+    /// required unless `sink` sends the results to a table instead.
+    #[serde(default)]
     pub dump_folder: String,
     pub queries: Vec<QueryConfig>,
     #[serde(default)]
     pub file_prefix: Option<String>,
     #[serde(default)]
     pub format: DumpFormat,
+    #[serde(default)]
+    pub sink: Option<TableSink>,
+    /// also write `<file_prefix>manifest.json` listing each file's rows and sha-256.
+    #[serde(default)]
+    pub manifest: bool,
 }
 
 #[derive(Deserialize)]
@@ -49,6 +61,57 @@ pub(crate) struct QueryConfig {
     pub sql: String,
     #[serde(default)]
     pub name: Option<String>,
+    /// the target table for this query when writing to a table sink.
+    #[serde(default)]
+    pub table: Option<String>,
+    /// fail the dump when the query returns fewer rows than this.
+    #[serde(default)]
+    pub min_rows: Option<usize>,
+    /// fail the dump when the query returns more rows than this.
+    #[serde(default)]
+    pub max_rows: Option<usize>,
+    #[serde(default)]
+    pub fail_on_empty: bool,
+    /// keep going on an empty result but report it in the output's `warnings`.
+    #[serde(default)]
+    pub warn_on_empty: bool,
+}
+
+impl QueryConfig {
+    fn label(&self, idx: usize) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("Query {}", idx + 1))
+    }
+
+    /// enforce the query's row-count expectations, returning a warning to report when the
+    /// result is empty but only `warn_on_empty` is set.
+    pub(crate) fn check_row_count(
+        &self,
+        idx: usize,
+        rows: usize,
+    ) -> Result<Option<String>, SendableError> {
+        let label = self.label(idx);
+        if rows == 0 && self.fail_on_empty {
+            return Err(ROW_COUNT_VIOLATION.error(format!("{label} returned no rows")));
+        }
+        if let Some(min_rows) = self.min_rows.filter(|min_rows| rows < *min_rows) {
+            return Err(ROW_COUNT_VIOLATION.error(format!(
+                "{label} returned {rows} row(s), expected at least {min_rows}"
+            )));
+        }
+        if let Some(max_rows) = self.max_rows.filter(|max_rows| rows > *max_rows) {
+            return Err(ROW_COUNT_VIOLATION.error(format!(
+                "{label} returned {rows} row(s), expected at most {max_rows}"
+            )));
+        }
+        if rows == 0 && self.warn_on_empty {
+            let warning = format!("{label} returned no rows");
+            warn!("{}", warning);
+            return Ok(Some(warning));
+        }
+        Ok(None)
+    }
 }
 
 impl SqlProvider {
@@ -65,11 +128,20 @@ impl SqlProvider {
         }
 
         let timeout = normalize_timeout(timeout_secs);
+        if let Some(sink) = &request.sink {
+            return self.copy_to_table(&request, sink, timeout, token);
+        }
+        if request.dump_folder.trim().is_empty() {
+            return Err(INVALID_ARGUMENT.error("dump_folder is required without a table sink"));
+        }
+
         let dump_dir = PathBuf::from(&request.dump_folder);
         fs::create_dir_all(&dump_dir).map_err(to_sendable)?;
 
         let connector: Box<dyn DatabaseConnector> = match request.database {
-            DatabaseKind::Postgres => Box::new(PostgresConnector::new(request.connection_string)),
+            DatabaseKind::Postgres => {
+                Box::new(PostgresConnector::new(request.connection_string.clone()))
+            }
         };
 
         let mut file_counts: HashMap<String, usize> = HashMap::new();
@@ -79,6 +151,7 @@ impl SqlProvider {
             DumpFormat::Csv => Box::new(CsvTableExporter::new()),
         };
         let mut exports = Vec::new();
+        let mut warnings = Vec::new();
 
         for (idx, query) in request.queries.iter().enumerate() {
             if token.is_cancelled() {
@@ -89,6 +162,7 @@ impl SqlProvider {
             if token.is_cancelled() {
                 return Err(QUERY_CANCELED.error("SQL dump canceled"));
             }
+            warnings.extend(query.check_row_count(idx, table_data.rows.len())?);
 
             let default_stem = format!("query_{:02}", idx + 1);
             let query_stem = query
@@ -103,10 +177,7 @@ impl SqlProvider {
             let unique_stem = next_available_stem(combined_stem, &mut file_counts);
             let file_path = dump_dir.join(format!("{unique_stem}.{}", format.file_extension()));
 
-            let sheet_name_owned = query
-                .name
-                .clone()
-                .unwrap_or_else(|| format!("Query {}", idx + 1));
+            let sheet_name_owned = query.label(idx);
 
             let sheet_name_ref = if format.requires_sheet_name() {
                 Some(sheet_name_owned.as_str())
@@ -134,7 +205,7 @@ impl SqlProvider {
             );
         }
 
-        let artifacts = exports
+        let mut artifacts = exports
             .iter()
             .map(|export| NewRunArtifact {
                 name: export
@@ -155,25 +226,121 @@ impl SqlProvider {
             })
             .collect::<Vec<_>>();
 
+        let manifest_path = if request.manifest {
+            let mut manifest = ExportManifest::new("SQL");
+            for export in &exports {
+                manifest.add_file(&export.path, export.rows)?;
+            }
+            let file_prefix = request.file_prefix.as_deref().unwrap_or("");
+            let manifest_path = dump_dir.join(format!("{file_prefix}manifest.json"));
+            let size_bytes = manifest.write(&manifest_path)?;
+            info!("Wrote export manifest to {}", manifest_path.display());
+            artifacts.push(NewRunArtifact {
+                name: format!("{file_prefix}manifest.json"),
+                mime_type: MANIFEST_MIME_TYPE.to_string(),
+                size_bytes: size_bytes as i64,
+                uri: manifest_path.to_string_lossy().into_owned(),
+                metadata: json!({ "provider": "SQL", "manifest": true }).into(),
+            });
+            Some(manifest_path)
+        } else {
+            None
+        };
+
+        let mut output = json!({
+            "provider": "SQL",
+            "exports": exports.iter().map(|export| {
+                json!({
+                    "name": export.name,
+                    "rows": export.rows,
+                    "path": export.path,
+                    "format": export.format.as_str(),
+                    "size_bytes": export.size_bytes,
+                })
+            }).collect::<Vec<_>>(),
+            "warnings": warnings,
+        });
+        if let Some(manifest_path) = manifest_path {
+            output["manifest"] = json!(manifest_path);
+        }
+
         Ok(TaskExecutionResult {
-            message: Some(format!("Exported {} SQL result file(s)", artifacts.len())),
+            message: Some(format!("Exported {} SQL result file(s)", exports.len())),
+            output_json: Some(output.into()),
+            chunks: Vec::new(),
+            artifacts,
+        })
+    }
+
+    fn copy_to_table(
+        &self,
+        request: &DumpDataRequest,
+        sink: &TableSink,
+        timeout: Duration,
+        token: CancellationToken,
+    ) -> Result<TaskExecutionResult, SendableError> {
+        sink.validate()?;
+        let writes = request
+            .queries
+            .iter()
+            .map(|query| sink.write_for(query.table.as_deref()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let target_connection = sink
+            .connection_string
+            .clone()
+            .unwrap_or_else(|| request.connection_string.clone());
+        let (source, target): (Box<dyn DatabaseConnector>, Box<dyn DatabaseConnector>) =
+            match request.database {
+                DatabaseKind::Postgres => (
+                    Box::new(PostgresConnector::new(request.connection_string.clone())),
+                    Box::new(PostgresConnector::new(target_connection)),
+                ),
+            };
+
+        let mut copies = Vec::new();
+        let mut warnings = Vec::new();
+        for (idx, (query, write)) in request.queries.iter().zip(&writes).enumerate() {
+            if token.is_cancelled() {
+                return Err(QUERY_CANCELED.error("SQL copy canceled"));
+            }
+            info!("Executing query {} for table copy", idx + 1);
+            let table_data = source.execute_query(&query.sql, timeout)?;
+            if token.is_cancelled() {
+                return Err(QUERY_CANCELED.error("SQL copy canceled"));
+            }
+            warnings.extend(query.check_row_count(idx, table_data.rows.len())?);
+
+            let written = target.write_rows(&table_data, write, timeout)?;
+            info!(
+                "Wrote {} of {} rows to table {} ({})",
+                written,
+                table_data.rows.len(),
+                write.table,
+                write.mode.as_str()
+            );
+            copies.push(json!({
+                "name": query.label(idx),
+                "rows": table_data.rows.len(),
+                "written": written,
+                "table": write.table,
+                "mode": write.mode.as_str(),
+            }));
+        }
+
+        Ok(TaskExecutionResult {
+            message: Some(format!("Copied {} SQL result(s) to tables", copies.len())),
             output_json: Some(
                 json!({
                     "provider": "SQL",
-                    "exports": exports.iter().map(|export| {
-                        json!({
-                            "name": export.name,
-                            "rows": export.rows,
-                            "path": export.path,
-                            "format": export.format.as_str(),
-                            "size_bytes": export.size_bytes,
-                        })
-                    }).collect::<Vec<_>>()
+                    "exports": [],
+                    "copies": copies,
+                    "warnings": warnings,
                 })
                 .into(),
             ),
             chunks: Vec::new(),
-            artifacts,
+            artifacts: Vec::new(),
         })
     }
 }
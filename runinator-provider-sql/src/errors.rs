@@ -3,16 +3,24 @@ use runinator_models::errors::{ErrorDescriptor, ProviderErrors};
 use crate::SqlProvider;
 
 // numbered error dictionary for the sql provider.
-pub(crate) const UNSUPPORTED_CALL: ErrorDescriptor =
-    ErrorDescriptor::new("SQL001", "UNSUPPORTED_CALL", "Unsupported provider call");
+pub(crate) const UNSUPPORTED_CALL: ErrorDescriptor = ErrorDescriptor::new(
+    "SQL001",
+    "sql.unsupported_call",
+    "Unsupported provider call",
+);
 pub(crate) const INVALID_ARGUMENT: ErrorDescriptor =
-    ErrorDescriptor::new("SQL002", "INVALID_ARGUMENT", "Invalid argument");
+    ErrorDescriptor::new("SQL002", "sql.invalid_argument", "Invalid argument");
 pub(crate) const QUERY_CANCELED: ErrorDescriptor =
-    ErrorDescriptor::new("SQL003", "QUERY_CANCELED", "Query canceled");
+    ErrorDescriptor::new("SQL003", "sql.query_canceled", "Query canceled");
 pub(crate) const QUERY_TIMEOUT: ErrorDescriptor =
-    ErrorDescriptor::new("SQL004", "QUERY_TIMEOUT", "Query timed out");
+    ErrorDescriptor::new("SQL004", "sql.query_timeout", "Query timed out");
 pub(crate) const QUERY_FAILED: ErrorDescriptor =
-    ErrorDescriptor::new("SQL005", "QUERY_FAILED", "Query failed");
+    ErrorDescriptor::new("SQL005", "sql.query_failed", "Query failed");
+pub(crate) const ROW_COUNT_VIOLATION: ErrorDescriptor = ErrorDescriptor::new(
+    "SQL006",
+    "sql.row_count_violation",
+    "Query returned an unexpected number of rows",
+);
 
 pub(crate) const DICTIONARY: &[ErrorDescriptor] = &[
     UNSUPPORTED_CALL,
@@ -20,6 +28,7 @@ pub(crate) const DICTIONARY: &[ErrorDescriptor] = &[
     QUERY_CANCELED,
     QUERY_TIMEOUT,
     QUERY_FAILED,
+    ROW_COUNT_VIOLATION,
 ];
 
 impl ProviderErrors for SqlProvider {
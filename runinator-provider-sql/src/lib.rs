@@ -3,6 +3,7 @@ mod dump;
 mod errors;
 mod format;
 mod helpers;
+mod sink;
 
 use std::sync::Arc;
 
@@ -32,23 +33,64 @@ impl Provider for SqlProvider {
             actions: vec![
                 ActionMetadata::new(
                     "dump_data",
-                    "Execute SQL queries and export results to Excel/CSV",
+                    "Execute SQL queries and export results to Excel/CSV or copy them into a table",
                 )
                 .with_parameters(vec![
                     ParameterMetadata::required("database", RuninatorType::String),
                     ParameterMetadata::required("connection_string", RuninatorType::String)
                         .secret(),
-                    ParameterMetadata::required("dump_folder", RuninatorType::String),
+                    ParameterMetadata::optional("dump_folder", RuninatorType::String)
+                        .with_description("Required unless a table sink is configured"),
                     ParameterMetadata::required(
                         "queries",
                         RuninatorType::array(RuninatorType::typed_structure([
                             ("sql", RuninatorField::required(RuninatorType::String)),
                             ("name", RuninatorField::optional(RuninatorType::String)),
+                            ("table", RuninatorField::optional(RuninatorType::String)),
+                            ("min_rows", RuninatorField::optional(RuninatorType::Integer)),
+                            ("max_rows", RuninatorField::optional(RuninatorType::Integer)),
+                            (
+                                "fail_on_empty",
+                                RuninatorField::optional(RuninatorType::Boolean),
+                            ),
+                            (
+                                "warn_on_empty",
+                                RuninatorField::optional(RuninatorType::Boolean),
+                            ),
                         ])),
                     ),
                     ParameterMetadata::optional("file_prefix", RuninatorType::String),
                     ParameterMetadata::optional("format", RuninatorType::String)
                         .with_default(json!("excel")),
+                    ParameterMetadata::optional("manifest", RuninatorType::Boolean)
+                        .with_default(json!(false))
+                        .with_description(
+                            "Write a manifest.json with row counts and SHA-256 checksums",
+                        ),
+                    ParameterMetadata::optional(
+                        "sink",
+                        RuninatorType::typed_structure([
+                            (
+                                "connection_string",
+                                RuninatorField::optional(RuninatorType::String),
+                            ),
+                            ("table", RuninatorField::optional(RuninatorType::String)),
+                            ("mode", RuninatorField::optional(RuninatorType::String)),
+                            (
+                                "key_columns",
+                                RuninatorField::optional(RuninatorType::array(
+                                    RuninatorType::String,
+                                )),
+                            ),
+                            (
+                                "batch_size",
+                                RuninatorField::optional(RuninatorType::Integer),
+                            ),
+                        ]),
+                    )
+                    .with_description(
+                        "Write results into a table (insert or upsert) instead of files",
+                    ),
                 ])
                 .with_results(vec![
                     ResultMetadata::new("provider", RuninatorType::String),
@@ -62,11 +104,24 @@ impl Provider for SqlProvider {
                             ("size_bytes", RuninatorType::Integer),
                         ])),
                     ),
+                    ResultMetadata::new(
+                        "copies",
+                        RuninatorType::array(RuninatorType::structure([
+                            ("name", RuninatorType::String),
+                            ("rows", RuninatorType::Integer),
+                            ("written", RuninatorType::Integer),
+                            ("table", RuninatorType::String),
+                            ("mode", RuninatorType::String),
+                        ])),
+                    ),
+                    ResultMetadata::new("warnings", RuninatorType::array(RuninatorType::String)),
+                    ResultMetadata::new("manifest", RuninatorType::String),
                 ]),
             ],
             metadata: ProviderRuntimeMetadata {
                 credential_scopes: vec!["sql".into()],
                 contract: None,
+                version: None,
             },
         }
     }
@@ -0,0 +1,136 @@
+use runinator_models::errors::SendableError;
+use serde::Deserialize;
+
+use crate::errors::INVALID_ARGUMENT;
+
+const DEFAULT_BATCH_SIZE: usize = 500;
+
+/// writes query results into a database table instead of exporting files, so a scheduled
+/// workflow can keep two tables in sync without intermediate csvs.
+#[derive(Deserialize)]
+pub(crate) struct TableSink {
+    /// the target database; defaults to the source connection when omitted.
+    #[serde(default)]
+    pub connection_string: Option<String>,
+    /// the target table, optionally schema-qualified. a query's own `table` overrides it.
+    #[serde(default)]
+    pub table: Option<String>,
+    #[serde(default)]
+    pub mode: SinkMode,
+    /// the conflict target for upserts; must match a unique index on the target table.
+    #[serde(default)]
+    pub key_columns: Vec<String>,
+    #[serde(default = "default_batch_size")]
+    pub batch_size: usize,
+}
+
+#[derive(Clone, Copy, Default, Deserialize, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum SinkMode {
+    #[default]
+    Insert,
+    Upsert,
+}
+
+impl SinkMode {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            SinkMode::Insert => "insert",
+            SinkMode::Upsert => "upsert",
+        }
+    }
+}
+
+/// how one query's rows are written; resolved from the sink and the query.
+pub(crate) struct TableWrite<'a> {
+    pub table: &'a str,
+    pub mode: SinkMode,
+    pub key_columns: &'a [String],
+    pub batch_size: usize,
+}
+
+fn default_batch_size() -> usize {
+    DEFAULT_BATCH_SIZE
+}
+
+impl TableSink {
+    pub(crate) fn validate(&self) -> Result<(), SendableError> {
+        if self.batch_size == 0 {
+            return Err(INVALID_ARGUMENT.error("Table sink batch_size must be greater than zero"));
+        }
+        if self.mode == SinkMode::Upsert && self.key_columns.is_empty() {
+            return Err(INVALID_ARGUMENT.error("Table sink upsert mode requires key_columns"));
+        }
+        Ok(())
+    }
+
+    pub(crate) fn write_for<'a>(
+        &'a self,
+        query_table: Option<&'a str>,
+    ) -> Result<TableWrite<'a>, SendableError> {
+        let table = query_table
+            .or(self.table.as_deref())
+            .map(str::trim)
+            .filter(|table| !table.is_empty())
+            .ok_or_else(|| {
+                INVALID_ARGUMENT.error("Table sink requires a table on the sink or the query")
+            })?;
+        Ok(TableWrite {
+            table,
+            mode: self.mode,
+            key_columns: &self.key_columns,
+            batch_size: self.batch_size,
+        })
+    }
+}
+
+/// quote an identifier, keeping a `schema.table` qualification as two quoted parts.
+pub(crate) fn quote_qualified(name: &str) -> String {
+    name.split('.')
+        .map(quote_identifier)
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+pub(crate) fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.trim().replace('"', "\"\""))
+}
+
+/// the statement that copies one batch, passed as a json array of objects keyed by column.
+/// `json_populate_recordset` casts each text value to the target column's type.
+pub(crate) fn batch_insert_sql(write: &TableWrite<'_>, headers: &[String]) -> String {
+    let table = quote_qualified(write.table);
+    let columns = headers
+        .iter()
+        .map(|header| quote_identifier(header))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut sql = format!(
+        "INSERT INTO {table} ({columns}) SELECT {columns} FROM json_populate_recordset(NULL::{table}, $1::json)"
+    );
+    if write.mode == SinkMode::Upsert {
+        let keys = write
+            .key_columns
+            .iter()
+            .map(|key| quote_identifier(key))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let updates = headers
+            .iter()
+            .filter(|header| !write.key_columns.iter().any(|key| key == *header))
+            .map(|header| {
+                let column = quote_identifier(header);
+                format!("{column} = EXCLUDED.{column}")
+            })
+            .collect::<Vec<_>>();
+        if updates.is_empty() {
+            sql.push_str(&format!(" ON CONFLICT ({keys}) DO NOTHING"));
+        } else {
+            sql.push_str(&format!(
+                " ON CONFLICT ({keys}) DO UPDATE SET {}",
+                updates.join(", ")
+            ));
+        }
+    }
+    sql
+}
@@ -6,6 +6,7 @@ use serde_json::json;
 use crate::dump::DumpDataRequest;
 use crate::format::DumpFormat;
 use crate::helpers::{next_available_stem, normalize_timeout, sanitize_file_stem};
+use crate::sink::{SinkMode, batch_insert_sql};
 
 #[test]
 fn dump_format_reports_wire_values_and_artifact_metadata() {
@@ -64,3 +65,76 @@ fn next_available_stem_keeps_duplicate_names_unique() {
     assert_eq!(next_available_stem(String::new(), &mut counts), "query_01");
     assert_eq!(next_available_stem(String::new(), &mut counts), "query_02");
 }
+
+#[test]
+fn table_sink_builds_batched_upserts_and_requires_key_columns() {
+    let request: DumpDataRequest = serde_json::from_value(json!({
+        "database": "postgres",
+        "connection_string": "postgres://source",
+        "queries": [
+            { "sql": "select id, name from accounts", "table": "mirror.accounts" },
+            { "sql": "select 1" }
+        ],
+        "sink": {
+            "connection_string": "postgres://target",
+            "mode": "upsert",
+            "key_columns": ["id"],
+            "batch_size": 200
+        }
+    }))
+    .unwrap();
+
+    assert!(request.dump_folder.is_empty());
+    let sink = request.sink.as_ref().unwrap();
+    sink.validate().unwrap();
+    assert_eq!(sink.mode, SinkMode::Upsert);
+
+    let write = sink.write_for(request.queries[0].table.as_deref()).unwrap();
+    assert_eq!(write.batch_size, 200);
+    assert_eq!(
+        batch_insert_sql(&write, &["id".to_string(), "name".to_string()]),
+        "INSERT INTO \"mirror\".\"accounts\" (\"id\", \"name\") SELECT \"id\", \"name\" \
+         FROM json_populate_recordset(NULL::\"mirror\".\"accounts\", $1::json) \
+         ON CONFLICT (\"id\") DO UPDATE SET \"name\" = EXCLUDED.\"name\""
+    );
+    // the second query names no table and the sink has no default.
+    assert!(sink.write_for(request.queries[1].table.as_deref()).is_err());
+
+    let keyless: DumpDataRequest = serde_json::from_value(json!({
+        "database": "postgres",
+        "connection_string": "postgres://source",
+        "queries": [{ "sql": "select 1" }],
+        "sink": { "table": "target", "mode": "upsert" }
+    }))
+    .unwrap();
+    assert!(keyless.sink.unwrap().validate().is_err());
+}
+
+#[test]
+fn query_row_count_expectations_fail_or_warn() {
+    let request: DumpDataRequest = serde_json::from_value(json!({
+        "database": "postgres",
+        "connection_string": "postgres://example",
+        "dump_folder": "/tmp/runinator-sql-test",
+        "queries": [
+            { "sql": "select 1", "name": "nightly", "fail_on_empty": true, "max_rows": 10 },
+            { "sql": "select 2", "min_rows": 3 },
+            { "sql": "select 3", "warn_on_empty": true }
+        ]
+    }))
+    .unwrap();
+
+    let nightly = &request.queries[0];
+    assert!(nightly.check_row_count(0, 0).is_err());
+    assert!(nightly.check_row_count(0, 11).is_err());
+    assert_eq!(nightly.check_row_count(0, 10).unwrap(), None);
+
+    let err = request.queries[1].check_row_count(1, 2).unwrap_err();
+    assert!(err.to_string().contains("Query 2 returned 2 row(s)"));
+
+    assert_eq!(
+        request.queries[2].check_row_count(2, 0).unwrap().as_deref(),
+        Some("Query 3 returned no rows")
+    );
+    assert_eq!(request.queries[2].check_row_count(2, 1).unwrap(), None);
+}
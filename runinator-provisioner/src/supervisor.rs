@@ -102,6 +102,7 @@ impl SupervisorProvisioner {
             args,
             cwd: template.cwd.clone(),
             env,
+            env_files: Vec::new(),
             autostart: true,
             restart_on_failure: true,
             max_restarts_per_minute: 10,
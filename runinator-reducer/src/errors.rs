@@ -88,6 +88,11 @@ pub const FOREIGN_LANGUAGE_CONFIG_MISSING: ErrorDescriptor = ErrorDescriptor::ne
     "workflow.foreign_language.config_missing",
     "Foreign language runtime config is missing",
 );
+pub const OUTPUT_REF_UNRESOLVED: ErrorDescriptor = ErrorDescriptor::new(
+    "RUNI145",
+    "workflow.output_ref.unresolved",
+    "Output reference has no value from a successful run",
+);
 
 pub const ASSERT_PARAMS_MISSING: ErrorDescriptor = ErrorDescriptor::new(
     "RUNI150",
@@ -181,6 +186,7 @@ pub const DICTIONARY: &[ErrorDescriptor] = &[
     COMPUTE_NODE_FAILED,
     ARTIFACT_SOURCE_UNRESOLVED,
     FOREIGN_LANGUAGE_CONFIG_MISSING,
+    OUTPUT_REF_UNRESOLVED,
     PIPELINE_NOT_FOUND,
     PIPELINE_TRIGGER_NOT_FOUND,
     PIPELINE_NO_ENTRY_MEMBERS,
@@ -1,3 +1,4 @@
+use std::collections::{BTreeMap, BTreeSet};
 use std::future::Future;
 
 use super::context::{is_reentry_stale, merge_parameters, runtime_context};
@@ -8,6 +9,7 @@ use super::transitions::{
 };
 use super::*;
 use runinator_comm::ActionTarget;
+use runinator_models::output_refs::{collect_output_refs, substitute_output_refs};
 use runinator_models::replicas::{ReplicaKind, ReplicaStatus};
 use uuid::Uuid;
 
@@ -354,8 +356,41 @@ async fn build_node_parameters<T: DatabaseImpl>(
     }
     let base = merge_parameters(&action.configuration, &node.parameters);
     let context = runtime_context(db, workflow_run, node_runs).await;
-    runinator_workflows::resolve_value_refs(&base, &context)
-        .map_err(|err| -> SendableError { Box::new(err) })
+    let parameters = runinator_workflows::resolve_value_refs(&base, &context)
+        .map_err(|err| -> SendableError { Box::new(err) })?;
+    resolve_output_refs(db, workflow, parameters).await
+}
+
+/// replace `{{output:workflow.key}}` references with the value the named workflow's most recent
+/// successful run recorded. only workflows in the dispatching workflow's org resolve.
+async fn resolve_output_refs<T: DatabaseImpl>(
+    db: &T,
+    workflow: &runinator_models::workflows::WorkflowDefinition,
+    parameters: Value,
+) -> Result<Value, SendableError> {
+    let mut refs = BTreeSet::new();
+    collect_output_refs(&parameters, &mut refs);
+    if refs.is_empty() {
+        return Ok(parameters);
+    }
+    let mut outputs = BTreeMap::new();
+    for output_ref in refs {
+        let source = db
+            .fetch_workflow_by_name(output_ref.workflow.clone())
+            .await?
+            .filter(|source| source.org_id == workflow.org_id);
+        let Some(source_id) = source.and_then(|source| source.id) else {
+            continue;
+        };
+        if let Some(value) = db
+            .fetch_latest_workflow_output(source_id, &output_ref.key)
+            .await?
+        {
+            outputs.insert(output_ref, value);
+        }
+    }
+    substitute_output_refs(&parameters, &outputs)
+        .map_err(|output_ref| crate::errors::OUTPUT_REF_UNRESOLVED.error(output_ref))
 }
 
 fn build_action_command(
@@ -4,7 +4,7 @@ use super::*;
 use super::{
     action, approval, assert, audit, await_run, barrier, basic, checkpoint, circuit_breaker,
     collect, compensation, control_flow, debounce, event_source, gate, input, map, mutex, output,
-    signal, subflow, throttle, transform, transitions, wait,
+    quota, signal, subflow, throttle, transform, transitions, wait,
 };
 use uuid::Uuid;
 
@@ -23,6 +23,13 @@ pub async fn process_ready_node<T: DatabaseImpl>(
         return Ok(ReadyNodeDisposition::Complete);
     };
     if workflow_run.status == WorkflowStatus::Queued {
+        // per-org fairness: a tenant at its concurrent-run cap keeps this run queued and re-polls,
+        // so one org's burst cannot take every worker.
+        if quota::org_run_quota_exhausted(db, &workflow_run).await? {
+            tracing::debug!("org run quota exhausted; deferring claim");
+            quota::enqueue_quota_poll(db, ready_node).await?;
+            return Ok(ReadyNodeDisposition::KeepClaim);
+        }
         db.update_workflow_run_status(
             workflow_run.id,
             WorkflowStatus::Running,
@@ -59,6 +59,7 @@ mod map;
 mod mutex;
 mod output;
 mod pipeline_orchestration;
+mod quota;
 mod signal;
 mod subflow;
 mod throttle;
@@ -0,0 +1,44 @@
+use super::*;
+
+const QUOTA_POLL_INTERVAL: i64 = 5;
+
+/// true when claiming this queued run would push its org past `max_concurrent_runs`. runs of
+/// workflows without an org, or of orgs without a run cap, are never held back.
+pub(super) async fn org_run_quota_exhausted<T: DatabaseImpl>(
+    db: &T,
+    workflow_run: &WorkflowRun,
+) -> Result<bool, SendableError> {
+    let Some(org_id) = db
+        .fetch_workflow(workflow_run.workflow_id)
+        .await?
+        .and_then(|workflow| workflow.org_id)
+    else {
+        return Ok(false);
+    };
+    let Some(quota) = db.fetch_org_quota(org_id).await? else {
+        return Ok(false);
+    };
+    if quota.max_concurrent_runs == 0 {
+        return Ok(false);
+    }
+    let active = db.count_active_workflow_runs_for_org(org_id).await?;
+    Ok(active >= i64::from(quota.max_concurrent_runs))
+}
+
+/// park a queued run behind its org's run quota: re-offer the same ready node after a short poll
+/// interval, leaving the run queued until a slot frees up.
+pub(super) async fn enqueue_quota_poll<T: DatabaseImpl>(
+    db: &T,
+    ready_node: &ReadyNodeRecord,
+) -> Result<(), SendableError> {
+    let poll_at = Utc::now() + chrono::Duration::seconds(QUOTA_POLL_INTERVAL);
+    let event = NewOrchestrationEvent::new(
+        ready_node.workflow_run_id,
+        Some(ready_node.node_id.clone()),
+        "quota_poll",
+        runinator_models::json!({ "node_id": ready_node.node_id }),
+    );
+    db.enqueue_ready_node(event, ready_node.node_id.clone(), poll_at)
+        .await?;
+    Ok(())
+}
@@ -1,5 +1,6 @@
 use super::context::{runtime_context, set_step_output};
 use super::*;
+use runinator_models::workflow_state::TaskStatusOutput;
 use runinator_models::workflows::WorkflowRetry;
 use uuid::Uuid;
 
@@ -31,6 +32,15 @@ pub(super) async fn retry_or_transition<T: DatabaseImpl>(
     Ok(())
 }
 
+/// true when the worker recorded a provider error marked non-retryable, so another attempt would
+/// only fail the same way.
+fn permanent_failure(output_json: Option<&Value>) -> bool {
+    output_json
+        .and_then(|output| TaskStatusOutput::from_wire_value(output).ok())
+        .and_then(|output| output.error)
+        .is_some_and(|error| !error.retryable)
+}
+
 /// exponential backoff from the node's retry config: `base * 2^(attempt-1)`, capped at `max`, with
 /// optional jitter spreading the delay into `[delay/2, delay]` so simultaneous retries disperse.
 fn retry_backoff_delay(retry: &WorkflowRetry, attempt: i64) -> chrono::Duration {
@@ -261,7 +271,10 @@ pub(super) async fn transition_from_node<T: DatabaseImpl>(
     message: Option<String>,
     node_runs: &[WorkflowNodeRun],
 ) -> Result<Option<String>, SendableError> {
-    if node.retry.retry_on.retryable(status) && node_run.attempt < node.retry.max_attempts {
+    if node.retry.retry_on.retryable(status)
+        && node_run.attempt < node.retry.max_attempts
+        && !permanent_failure(output_json.as_ref())
+    {
         schedule_node_retry(db, workflow_run, node, node_run, output_json, message).await?;
         return Ok(Some(node.id.clone()));
     }
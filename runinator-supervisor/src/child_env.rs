@@ -0,0 +1,111 @@
+// the environment a child is spawned with, assembled at spawn time so secrets stay out of the
+// config file. `env_files` are read first (dotenv-style `KEY=VALUE` lines), then `env` entries are
+// layered on top after expanding their `${file:PATH}` (file contents, trailing newline dropped) and
+// `${env:NAME}` (the supervisor's own environment) templates. relative paths resolve against the
+// process's working directory. other `${...}` text is passed through untouched.
+
+use std::{collections::BTreeMap, env, fs, path::Path};
+
+use crate::config::{ProcessConfig, resolve_path};
+
+/// the variables to add to the child's inherited environment, or why they cannot be built.
+pub fn resolve_env(
+    process: &ProcessConfig,
+    base_dir: &Path,
+) -> Result<BTreeMap<String, String>, String> {
+    let mut resolved = BTreeMap::new();
+    for file in &process.env_files {
+        let path = resolve_path(base_dir, Path::new(file));
+        let text = read(&path, "env file")?;
+        for (key, value) in
+            parse_env_file(&text).map_err(|err| format!("env file {}: {err}", path.display()))?
+        {
+            resolved.insert(key, value);
+        }
+    }
+    for (key, value) in &process.env {
+        let value = expand(value, base_dir, |name| env::var(name).ok())
+            .map_err(|err| format!("env {key}: {err}"))?;
+        resolved.insert(key.clone(), value);
+    }
+    Ok(resolved)
+}
+
+/// expand the `${file:...}` and `${env:...}` templates in `value`. `lookup` reads a variable of
+/// the supervisor's environment; an unset one is an error rather than an empty string.
+pub fn expand(
+    value: &str,
+    base_dir: &Path,
+    lookup: impl Fn(&str) -> Option<String>,
+) -> Result<String, String> {
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        out.push_str(&rest[..start]);
+        let template = &rest[start + 2..];
+        let Some((kind, _)) = template
+            .split_once(':')
+            .filter(|(kind, _)| matches!(*kind, "file" | "env"))
+        else {
+            out.push_str("${");
+            rest = template;
+            continue;
+        };
+        let Some(end) = template.find('}') else {
+            return Err(format!("unterminated ${{{kind}:...}} template"));
+        };
+        let argument = &template[kind.len() + 1..end];
+        if argument.is_empty() {
+            return Err(format!("empty ${{{kind}:}} template"));
+        }
+        match kind {
+            "file" => {
+                let path = resolve_path(base_dir, Path::new(argument));
+                let contents = read(&path, "secret file")?;
+                out.push_str(contents.trim_end_matches(['\n', '\r']));
+            }
+            _ => match lookup(argument) {
+                Some(found) => out.push_str(&found),
+                None => return Err(format!("environment variable {argument} is not set")),
+            },
+        }
+        rest = &template[end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// parse dotenv-style text: `KEY=VALUE` per line, with blank lines, `#` comments, an optional
+/// `export ` prefix, and one pair of matching quotes around the value allowed.
+pub fn parse_env_file(text: &str) -> Result<Vec<(String, String)>, String> {
+    let mut entries = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let Some((key, value)) = line.split_once('=') else {
+            return Err(format!("line {} is not KEY=VALUE", index + 1));
+        };
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(format!("line {} has an empty key", index + 1));
+        }
+        let value = value.trim();
+        let value = ['"', '\'']
+            .iter()
+            .find_map(|quote| {
+                value
+                    .strip_prefix(*quote)
+                    .and_then(|inner| inner.strip_suffix(*quote))
+            })
+            .unwrap_or(value);
+        entries.push((key.to_string(), value.to_string()));
+    }
+    Ok(entries)
+}
+
+fn read(path: &Path, what: &str) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|err| format!("cannot read {what} {}: {err}", path.display()))
+}
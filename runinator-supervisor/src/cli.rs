@@ -54,6 +54,16 @@ pub enum Commands {
         #[arg(short, long, default_value_t = false)]
         watch: bool,
     },
+    /// Re-read the config in the running supervisor: start added processes, stop removed ones,
+    /// and restart only those whose command, args, cwd, or env changed.
+    Reload,
+    /// Report pid, stop, state, and control files left inconsistent by a supervisor that died,
+    /// and fix them.
+    Doctor {
+        /// Only report; change nothing.
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+    },
     /// Add, start, stop, or remove a dynamic process in the running supervisor.
     Process {
         #[command(subcommand)]
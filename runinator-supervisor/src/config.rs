@@ -16,6 +16,12 @@ pub struct SupervisorConfig {
     pub shutdown_timeout_secs: u64,
     #[serde(default = "default_restart_delay_ms")]
     pub restart_delay_ms: u64,
+    /// `host:port` to serve prometheus metrics on; no listener when absent.
+    #[serde(default)]
+    pub metrics_listen: Option<String>,
+    /// dotenv-style files every process reads before its own `env_files` and `env`.
+    #[serde(default)]
+    pub env_files: Vec<String>,
     #[serde(default)]
     pub processes: Vec<ProcessConfig>,
 }
@@ -27,8 +33,12 @@ pub struct ProcessConfig {
     #[serde(default)]
     pub args: Vec<String>,
     pub cwd: Option<String>,
+    /// values may hold `${file:PATH}` and `${env:NAME}` templates, expanded at spawn time.
     #[serde(default)]
     pub env: BTreeMap<String, String>,
+    /// dotenv-style files read at spawn time; `env` entries override what they set.
+    #[serde(default)]
+    pub env_files: Vec<String>,
     #[serde(default = "default_true")]
     pub autostart: bool,
     #[serde(default = "default_true")]
@@ -60,6 +70,48 @@ impl ProcessConfig {
         self.args_windows = None;
         self
     }
+
+    /// true when the two configs would launch a different process (command, args, cwd, or env).
+    pub fn launch_differs(&self, other: &ProcessConfig) -> bool {
+        self.command != other.command
+            || self.args != other.args
+            || self.cwd != other.cwd
+            || self.env != other.env
+            || self.env_files != other.env_files
+    }
+}
+
+/// how a re-read config differs from the config-sourced processes the supervisor is running.
+/// every entry is already resolved for the current platform.
+#[derive(Debug, Default)]
+pub struct ReloadPlan {
+    /// processes new to the config; started when their `autostart` is set.
+    pub added: Vec<ProcessConfig>,
+    /// processes no longer in the config; stopped and forgotten.
+    pub removed: Vec<String>,
+    /// processes whose launch changed; restarted with the new config.
+    pub changed: Vec<ProcessConfig>,
+    /// processes whose launch is unchanged; only their restart policy is refreshed in place.
+    pub unchanged: Vec<ProcessConfig>,
+}
+
+/// diffs the desired process list against the current one by name.
+pub fn plan_reload(current: &[ProcessConfig], desired: &[ProcessConfig]) -> ReloadPlan {
+    let mut plan = ReloadPlan::default();
+    for process in desired {
+        let process = process.clone().resolve_for_platform();
+        match current.iter().find(|c| c.name == process.name) {
+            None => plan.added.push(process),
+            Some(existing) if existing.launch_differs(&process) => plan.changed.push(process),
+            Some(_) => plan.unchanged.push(process),
+        }
+    }
+    plan.removed = current
+        .iter()
+        .filter(|c| !desired.iter().any(|d| d.name == c.name))
+        .map(|c| c.name.clone())
+        .collect();
+    plan
 }
 
 #[derive(Debug)]
@@ -97,12 +149,26 @@ pub fn load_config(path: &Path) -> Result<(SupervisorConfig, Paths), DynError> {
         .to_path_buf();
 
     let data = fs::read_to_string(&config_path)?;
-    let config: SupervisorConfig = serde_json::from_str(&data).map_err(|err| {
+    let mut config: SupervisorConfig = serde_json::from_str(&data).map_err(|err| {
         io::Error::new(
             io::ErrorKind::InvalidData,
             format!("Invalid config JSON in {}: {err}", config_path.display()),
         )
     })?;
+    // the shared env files are absolute here, since each process resolves relative ones against
+    // its own working directory.
+    let shared = config
+        .env_files
+        .iter()
+        .map(|file| {
+            resolve_path(&config_dir, Path::new(file))
+                .to_string_lossy()
+                .into_owned()
+        })
+        .collect::<Vec<_>>();
+    for process in &mut config.processes {
+        process.env_files.splice(0..0, shared.iter().cloned());
+    }
 
     if config.processes.is_empty() {
         return Err(io::Error::new(io::ErrorKind::InvalidInput, "Config has no processes").into());
@@ -25,6 +25,8 @@ pub enum ControlCommand {
     StopProcess { name: String },
     /// terminate and forget a process entirely.
     RemoveProcess { name: String },
+    /// re-read the config file and converge the running processes on it.
+    Reload,
 }
 
 /// write a control command into the queue atomically (temp file then rename).
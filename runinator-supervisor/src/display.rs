@@ -5,6 +5,7 @@ use chrono::{DateTime, Local};
 use crate::{
     config::Paths,
     snapshot::{StateSnapshot, read_snapshot},
+    stale::running_supervisor,
     types::DynError,
 };
 
@@ -16,6 +17,14 @@ pub fn show_status(paths: &Paths, watch: bool) -> Result<(), DynError> {
                     clear_screen();
                 }
                 render_snapshot(&snapshot);
+                if running_supervisor(paths).is_none() {
+                    println!();
+                    println!(
+                        "Supervisor is not running; this state is left over from PID {}. \
+                         Run `runinator-supervisor doctor` to clean it up.",
+                        snapshot.supervisor_pid
+                    );
+                }
             }
             Err(err) => {
                 if watch {
@@ -2,9 +2,13 @@
 // provisioner control queue). the config + control modules are exported so external callers can
 // build process templates and enqueue dynamic add/start/stop/remove commands.
 
+pub mod child_env;
 pub mod config;
 pub mod control;
+pub mod metrics;
+pub mod os;
 pub mod snapshot;
+pub mod stale;
 pub mod types;
 
 #[cfg(test)]
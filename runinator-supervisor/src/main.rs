@@ -1,9 +1,12 @@
+mod child_env;
 mod cli;
 mod config;
 mod control;
 mod display;
+mod metrics;
 mod os;
 mod snapshot;
+mod stale;
 mod supervisor;
 mod types;
 
@@ -48,12 +51,54 @@ fn main() -> Result<(), DynError> {
             watch,
         } => show_logs(&paths, process.as_deref(), lines, watch)?,
         Commands::Process { command } => run_process_command(command, &paths)?,
+        Commands::Reload => {
+            // the config was already parsed above, so a broken edit fails here instead of in the
+            // daemon.
+            enqueue(&paths.control_dir, &ControlCommand::Reload)?;
+            println!(
+                "Queued config reload for supervisor at {}.",
+                paths.control_dir.display()
+            );
+        }
+        Commands::Doctor { dry_run } => run_doctor(&paths, dry_run)?,
         Commands::Supervise { foreground } => run_supervisor(&config, &paths, foreground)?,
     }
 
     Ok(())
 }
 
+fn run_doctor(paths: &config::Paths, dry_run: bool) -> Result<(), DynError> {
+    if let Some(record) = stale::running_supervisor(paths) {
+        println!(
+            "Supervisor is running (PID {}); its state is current.",
+            record.pid
+        );
+        return Ok(());
+    }
+    let issues = stale::diagnose(paths);
+    if issues.is_empty() {
+        println!(
+            "No stale supervisor state in {}.",
+            paths.state_dir.display()
+        );
+        return Ok(());
+    }
+    if !dry_run {
+        stale::repair(paths, &issues)?;
+    }
+    for issue in &issues {
+        if dry_run {
+            println!("- {}", issue.describe());
+        } else {
+            println!("- {}: {}", issue.describe(), issue.remedy());
+        }
+    }
+    if dry_run {
+        println!("Run without --dry-run to fix these.");
+    }
+    Ok(())
+}
+
 fn run_process_command(command: ProcessCommands, paths: &config::Paths) -> Result<(), DynError> {
     let control_command = match command {
         ProcessCommands::Add {
@@ -78,6 +123,7 @@ fn run_process_command(command: ProcessCommands, paths: &config::Paths) -> Resul
                     args,
                     cwd,
                     env: env_map,
+                    env_files: Vec::new(),
                     autostart: !no_autostart,
                     restart_on_failure: true,
                     max_restarts_per_minute: 10,
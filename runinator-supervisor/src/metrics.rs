@@ -0,0 +1,153 @@
+use std::{
+    fmt::Write as _,
+    io::{BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream},
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+use crate::{snapshot::StateSnapshot, types::DynError};
+
+/// the latest rendered exposition, refreshed by the supervisor loop and served by the listener.
+pub type SharedMetrics = Arc<Mutex<String>>;
+
+/// bind the metrics listener and serve `GET /metrics` from a background thread. binding happens on
+/// the caller's thread so a bad address fails supervisor startup instead of going unnoticed.
+pub fn serve(addr: &str) -> Result<SharedMetrics, DynError> {
+    let listener = TcpListener::bind(addr)?;
+    let shared: SharedMetrics = Arc::new(Mutex::new(String::new()));
+    let body = Arc::clone(&shared);
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let _ = respond(stream, &body);
+        }
+    });
+    Ok(shared)
+}
+
+fn respond(mut stream: TcpStream, body: &SharedMetrics) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(2)))?;
+    let mut request_line = String::new();
+    BufReader::new(&stream).read_line(&mut request_line)?;
+    let path = request_line.split_whitespace().nth(1).unwrap_or_default();
+    let (status, payload) = if path == "/metrics" {
+        let payload = body.lock().map(|body| body.clone()).unwrap_or_default();
+        ("200 OK", payload)
+    } else {
+        ("404 Not Found", String::from("not found\n"))
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{payload}",
+        payload.len()
+    )?;
+    stream.flush()
+}
+
+/// render a state snapshot in the prometheus text exposition format.
+pub fn render(snapshot: &StateSnapshot) -> String {
+    let mut out = String::new();
+    out.push_str("# HELP runinator_supervisor_processes Processes managed by the supervisor.\n");
+    out.push_str("# TYPE runinator_supervisor_processes gauge\n");
+    let _ = writeln!(
+        out,
+        "runinator_supervisor_processes {}",
+        snapshot.processes.len()
+    );
+
+    family(
+        &mut out,
+        "runinator_supervisor_process_up",
+        "gauge",
+        "1 when the process has a live child, 0 otherwise.",
+        snapshot.processes.iter().map(|p| {
+            (
+                p.name.as_str(),
+                None,
+                Some(if p.pid.is_some() { 1.0 } else { 0.0 }),
+            )
+        }),
+    );
+    family(
+        &mut out,
+        "runinator_supervisor_process_status",
+        "gauge",
+        "Current supervisor status of the process, as a label.",
+        snapshot
+            .processes
+            .iter()
+            .map(|p| (p.name.as_str(), Some(p.status.as_str()), Some(1.0))),
+    );
+    family(
+        &mut out,
+        "runinator_supervisor_process_restarts_total",
+        "counter",
+        "Automatic restarts since the process was registered.",
+        snapshot
+            .processes
+            .iter()
+            .map(|p| (p.name.as_str(), None, Some(p.restarts as f64))),
+    );
+    family(
+        &mut out,
+        "runinator_supervisor_process_uptime_seconds",
+        "gauge",
+        "Seconds since the current child started.",
+        snapshot.processes.iter().map(|p| {
+            (
+                p.name.as_str(),
+                None,
+                Some(p.uptime_seconds.unwrap_or_default() as f64),
+            )
+        }),
+    );
+    family(
+        &mut out,
+        "runinator_supervisor_process_last_exit_code",
+        "gauge",
+        "Exit code of the most recent child exit; absent until one has exited with a code.",
+        snapshot
+            .processes
+            .iter()
+            .map(|p| (p.name.as_str(), None, p.last_exit_code.map(f64::from))),
+    );
+    out
+}
+
+// one metric family; samples without a value are skipped.
+fn family<'a>(
+    out: &mut String,
+    name: &str,
+    kind: &str,
+    help: &str,
+    samples: impl Iterator<Item = (&'a str, Option<&'a str>, Option<f64>)>,
+) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+    for (process, status, value) in samples {
+        let Some(value) = value else {
+            continue;
+        };
+        let _ = match status {
+            Some(status) => writeln!(
+                out,
+                "{name}{{process=\"{}\",status=\"{}\"}} {value}",
+                escape_label(process),
+                escape_label(status)
+            ),
+            None => writeln!(
+                out,
+                "{name}{{process=\"{}\"}} {value}",
+                escape_label(process)
+            ),
+        };
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
@@ -1,8 +1,11 @@
 use std::{
     io,
     process::{Command, Stdio},
+    sync::atomic::{AtomicBool, Ordering},
 };
 
+use chrono::{DateTime, Utc};
+
 use crate::types::DynError;
 
 #[cfg(unix)]
@@ -12,7 +15,7 @@ pub fn detach_daemon(command: &mut Command) {
     // start the daemon in a new session so callers can exit cleanly.
     unsafe {
         command.pre_exec(|| {
-            if setsid() == -1 {
+            if libc::setsid() == -1 {
                 return Err(io::Error::last_os_error());
             }
             Ok(())
@@ -23,9 +26,32 @@ pub fn detach_daemon(command: &mut Command) {
 #[cfg(windows)]
 pub fn detach_daemon(_command: &mut Command) {}
 
+// set from the SIGHUP handler and consumed by the supervisor loop; an atomic store is the only
+// thing the handler does, which keeps it async-signal-safe.
+static RELOAD_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+#[cfg(unix)]
+extern "C" fn on_sighup(_signum: libc::c_int) {
+    RELOAD_REQUESTED.store(true, Ordering::SeqCst);
+}
+
+/// route SIGHUP to a config reload instead of the default terminate.
 #[cfg(unix)]
-unsafe extern "C" {
-    fn setsid() -> i32;
+pub fn install_reload_signal() {
+    unsafe {
+        libc::signal(
+            libc::SIGHUP,
+            on_sighup as extern "C" fn(libc::c_int) as libc::sighandler_t,
+        );
+    }
+}
+
+#[cfg(windows)]
+pub fn install_reload_signal() {}
+
+/// true once per SIGHUP received since the last call.
+pub fn take_reload_signal() -> bool {
+    RELOAD_REQUESTED.swap(false, Ordering::SeqCst)
 }
 
 #[cfg(unix)]
@@ -53,6 +79,44 @@ pub fn is_process_running(pid: u32) -> bool {
     }
 }
 
+/// when `pid` started, from `ps`'s elapsed time; `None` when that cannot be read. second
+/// resolution, so comparisons need a tolerance.
+#[cfg(unix)]
+pub fn process_started_at(pid: u32) -> Option<DateTime<Utc>> {
+    let output = Command::new("ps")
+        .args(["-o", "etime=", "-p", &pid.to_string()])
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let elapsed = parse_elapsed(String::from_utf8_lossy(&output.stdout).trim())?;
+    Some(Utc::now() - chrono::Duration::seconds(elapsed as i64))
+}
+
+#[cfg(windows)]
+pub fn process_started_at(_pid: u32) -> Option<DateTime<Utc>> {
+    None
+}
+
+/// parse `ps`'s `[[dd-]hh:]mm:ss` elapsed time into seconds.
+pub fn parse_elapsed(value: &str) -> Option<u64> {
+    let (days, clock) = match value.split_once('-') {
+        Some((days, clock)) => (days.parse::<u64>().ok()?, clock),
+        None => (0, value),
+    };
+    let mut seconds = 0;
+    let parts: Vec<&str> = clock.split(':').collect();
+    if !(2..=3).contains(&parts.len()) {
+        return None;
+    }
+    for part in parts {
+        seconds = seconds * 60 + part.parse::<u64>().ok()?;
+    }
+    Some(days * 86_400 + seconds)
+}
+
 #[cfg(unix)]
 pub fn send_terminate(pid: u32) -> Result<(), DynError> {
     if !is_process_running(pid) {
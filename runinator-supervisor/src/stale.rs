@@ -0,0 +1,275 @@
+//! stale runtime-file detection. a supervisor killed with its host leaves its pid, stop, state,
+//! and control files behind; a later start must not mistake them for a live supervisor, and
+//! `status` must not show its children as running. a recorded pid only counts as alive when the
+//! process exists and started when the record says it did, so a pid the OS handed to something
+//! else after a reboot reads as stale.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    config::Paths,
+    os::{is_process_running, process_started_at, send_terminate},
+    snapshot::{read_snapshot, write_snapshot},
+    types::DynError,
+};
+
+/// how far a process's observed start may drift from the recorded one and still be the same
+/// process. `ps` reports elapsed time in whole seconds and the record is taken around the spawn.
+pub const START_TIME_TOLERANCE_SECS: i64 = 5;
+
+// snapshot statuses that claim a live child.
+const LIVE_STATUSES: [&str; 3] = ["starting", "running", "stopping"];
+
+/// the supervisor pid file: the pid, then when that process started.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PidRecord {
+    pub pid: u32,
+    /// absent in pid files written before start times were recorded; liveness alone decides then.
+    pub started_at: Option<DateTime<Utc>>,
+}
+
+impl PidRecord {
+    /// the record for the running process.
+    pub fn current() -> Self {
+        let pid = std::process::id();
+        Self {
+            pid,
+            started_at: Some(process_started_at(pid).unwrap_or_else(Utc::now)),
+        }
+    }
+
+    /// true when the pid is running and, where both are known, started at the recorded time.
+    pub fn is_alive(&self) -> bool {
+        is_alive(self.pid, self.started_at)
+    }
+}
+
+/// true when `pid` is running and, where both are known, started within
+/// [`START_TIME_TOLERANCE_SECS`] of `started_at`.
+pub fn is_alive(pid: u32, started_at: Option<DateTime<Utc>>) -> bool {
+    if !is_process_running(pid) {
+        return false;
+    }
+    match (started_at, process_started_at(pid)) {
+        (Some(recorded), Some(observed)) => {
+            (observed - recorded).num_seconds().abs() <= START_TIME_TOLERANCE_SECS
+        }
+        _ => true,
+    }
+}
+
+pub fn write_pid_record(path: &Path, record: &PidRecord) -> io::Result<()> {
+    let started_at = record
+        .started_at
+        .map(|started_at| started_at.to_rfc3339())
+        .unwrap_or_default();
+    fs::write(path, format!("{}\n{started_at}\n", record.pid))
+}
+
+/// the pid file's record, `None` when it is missing or empty. a file whose pid does not parse is
+/// an error; an unreadable start time is treated as unrecorded.
+pub fn read_pid_record(path: &Path) -> Result<Option<PidRecord>, DynError> {
+    let value = match fs::read_to_string(path) {
+        Ok(value) => value,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+    let mut lines = value.lines().map(str::trim);
+    let pid = match lines.next() {
+        Some(pid) if !pid.is_empty() => pid,
+        _ => return Ok(None),
+    };
+    let pid = pid.parse::<u32>().map_err(|err| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Invalid PID file {}: {err}", path.display()),
+        )
+    })?;
+    let started_at = lines
+        .next()
+        .and_then(|started_at| DateTime::parse_from_rfc3339(started_at).ok())
+        .map(|started_at| started_at.with_timezone(&Utc));
+    Ok(Some(PidRecord { pid, started_at }))
+}
+
+/// the live supervisor's record, or `None` when no supervisor is running.
+pub fn running_supervisor(paths: &Paths) -> Option<PidRecord> {
+    read_pid_record(&paths.pid_file)
+        .ok()
+        .flatten()
+        .filter(PidRecord::is_alive)
+}
+
+/// one inconsistency between the runtime files and what is actually running.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StaleIssue {
+    /// the pid file names a supervisor that is gone, or a pid now used by another process.
+    StalePidFile { pid: u32 },
+    /// the pid file cannot be parsed.
+    CorruptPidFile { path: PathBuf },
+    /// a stop request no supervisor will ever act on; it would stop the next one at once.
+    StaleStopFile,
+    /// state.json claims a child is up, but its process is gone.
+    PhantomProcess { name: String, pid: u32 },
+    /// a child of a dead supervisor that is still running unsupervised.
+    OrphanedProcess { name: String, pid: u32 },
+    /// control files queued for, or half-written by, a supervisor that is gone.
+    StaleControlFiles { count: usize },
+}
+
+impl StaleIssue {
+    pub fn describe(&self) -> String {
+        match self {
+            Self::StalePidFile { pid } => {
+                format!("pid file names PID {pid}, which is not a running supervisor")
+            }
+            Self::CorruptPidFile { path } => format!("pid file {} is unreadable", path.display()),
+            Self::StaleStopFile => "stop file left behind with no supervisor running".into(),
+            Self::PhantomProcess { name, pid } => {
+                format!("state shows '{name}' up as PID {pid}, but that process is gone")
+            }
+            Self::OrphanedProcess { name, pid } => {
+                format!("'{name}' (PID {pid}) outlived its supervisor and is running unsupervised")
+            }
+            Self::StaleControlFiles { count } => {
+                format!("{count} control file(s) left for a supervisor that is gone")
+            }
+        }
+    }
+
+    pub fn remedy(&self) -> &'static str {
+        match self {
+            Self::StalePidFile { .. } | Self::CorruptPidFile { .. } => "removed the pid file",
+            Self::StaleStopFile => "removed the stop file",
+            Self::PhantomProcess { .. } => "marked it stopped in the state file",
+            Self::OrphanedProcess { .. } => "terminated it and marked it stopped",
+            Self::StaleControlFiles { .. } => "removed the control files",
+        }
+    }
+}
+
+/// the stale runtime files under `paths`. empty while a supervisor is running: its files are
+/// current by definition.
+pub fn diagnose(paths: &Paths) -> Vec<StaleIssue> {
+    let mut issues = Vec::new();
+    match read_pid_record(&paths.pid_file) {
+        Ok(Some(record)) if record.is_alive() => return issues,
+        Ok(Some(record)) => issues.push(StaleIssue::StalePidFile { pid: record.pid }),
+        Ok(None) => {}
+        Err(_) => issues.push(StaleIssue::CorruptPidFile {
+            path: paths.pid_file.clone(),
+        }),
+    }
+    if paths.stop_file.exists() {
+        issues.push(StaleIssue::StaleStopFile);
+    }
+    if let Ok(snapshot) = read_snapshot(&paths.state_file) {
+        for process in &snapshot.processes {
+            let Some(pid) = process.pid else {
+                continue;
+            };
+            if !LIVE_STATUSES.contains(&process.status.as_str()) {
+                continue;
+            }
+            let started_at = process
+                .started_at
+                .as_deref()
+                .and_then(|started_at| DateTime::parse_from_rfc3339(started_at).ok())
+                .map(|started_at| started_at.with_timezone(&Utc));
+            let name = process.name.clone();
+            issues.push(if is_alive(pid, started_at) {
+                StaleIssue::OrphanedProcess { name, pid }
+            } else {
+                StaleIssue::PhantomProcess { name, pid }
+            });
+        }
+    }
+    let control_files = control_files(&paths.control_dir);
+    if !control_files.is_empty() {
+        issues.push(StaleIssue::StaleControlFiles {
+            count: control_files.len(),
+        });
+    }
+    issues
+}
+
+/// fix every issue `diagnose` reported. orphaned children are terminated so the next start does
+/// not run a second copy beside them; every process the state file claims is then marked stopped.
+pub fn repair(paths: &Paths, issues: &[StaleIssue]) -> Result<(), DynError> {
+    let mut stopped = Vec::new();
+    for issue in issues {
+        match issue {
+            StaleIssue::StalePidFile { .. } | StaleIssue::CorruptPidFile { .. } => {
+                remove_if_exists(&paths.pid_file)?
+            }
+            StaleIssue::StaleStopFile => remove_if_exists(&paths.stop_file)?,
+            StaleIssue::PhantomProcess { name, .. } => stopped.push(name.as_str()),
+            StaleIssue::OrphanedProcess { name, pid } => {
+                send_terminate(*pid)?;
+                stopped.push(name.as_str());
+            }
+            StaleIssue::StaleControlFiles { .. } => {
+                for path in control_files(&paths.control_dir) {
+                    remove_if_exists(&path)?;
+                }
+            }
+        }
+    }
+    if !stopped.is_empty()
+        && let Ok(mut snapshot) = read_snapshot(&paths.state_file)
+    {
+        for process in &mut snapshot.processes {
+            if stopped.contains(&process.name.as_str()) {
+                process.status = "stopped".into();
+                process.pid = None;
+                process.uptime_seconds = None;
+            }
+        }
+        snapshot.updated_at = Utc::now().to_rfc3339();
+        write_snapshot(&paths.state_file, &snapshot)?;
+    }
+    Ok(())
+}
+
+/// diagnose and repair, logging each fix to stderr (the supervisor log, for the daemon).
+pub fn clean_up(paths: &Paths) -> Result<(), DynError> {
+    let issues = diagnose(paths);
+    repair(paths, &issues)?;
+    for issue in &issues {
+        eprintln!(
+            "{} stale state: {}; {}",
+            Utc::now().to_rfc3339(),
+            issue.describe(),
+            issue.remedy()
+        );
+    }
+    Ok(())
+}
+
+// queued commands and half-written temp files in the control directory.
+fn control_files(control_dir: &Path) -> Vec<PathBuf> {
+    match fs::read_dir(control_dir) {
+        Ok(read) => read
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|ext| ext == "json" || ext == "tmp")
+            })
+            .collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+fn remove_if_exists(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Ok(()) => Ok(()),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err),
+    }
+}
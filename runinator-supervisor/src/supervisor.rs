@@ -11,11 +11,16 @@ use std::{
 use chrono::{DateTime, Utc};
 
 use crate::{
-    config::{Paths, ProcessConfig, SupervisorConfig, resolve_path},
+    child_env::resolve_env,
+    config::{Paths, ProcessConfig, SupervisorConfig, load_config, plan_reload, resolve_path},
     control::{ControlCommand, drain as drain_control},
     display::{clear_screen, render_snapshot},
-    os::{is_process_running, send_kill, send_terminate},
+    metrics::{self, SharedMetrics},
+    os::{
+        install_reload_signal, is_process_running, send_kill, send_terminate, take_reload_signal,
+    },
     snapshot::{ProcessSnapshot, StateSnapshot, write_snapshot},
+    stale::{self, PidRecord, read_pid_record, running_supervisor, write_pid_record},
     types::DynError,
 };
 
@@ -63,23 +68,24 @@ struct ManagedProcess {
     start_count: u32,
     // set when a control command stopped this process, so the poll loop does not auto-restart it.
     manual_stop: bool,
+    // true for processes that came from the config file; a reload only stops or restarts these,
+    // leaving ones registered through `process add` alone.
+    from_config: bool,
 }
 
 pub fn start_daemon(paths: &Paths) -> Result<(), DynError> {
     fs::create_dir_all(&paths.state_dir)?;
     fs::create_dir_all(&paths.logs_dir)?;
-    remove_file_if_exists(&paths.stop_file)?;
 
-    if let Some(pid) = read_pid(&paths.pid_file)? {
-        if is_process_running(pid) {
-            println!(
-                "Supervisor is already running (PID {}). Use `status` or `stop`.",
-                pid
-            );
-            return Ok(());
-        }
-        remove_file_if_exists(&paths.pid_file)?;
+    if let Some(record) = running_supervisor(paths) {
+        println!(
+            "Supervisor is already running (PID {}). Use `status` or `stop`.",
+            record.pid
+        );
+        return Ok(());
     }
+    // a supervisor that died with its host leaves its files behind; clear them before starting.
+    stale::clean_up(paths)?;
 
     let exe = env::current_exe()?;
     let stdout = fs::OpenOptions::new()
@@ -127,23 +133,29 @@ pub fn run_supervisor(
     fs::create_dir_all(&paths.state_dir)?;
     fs::create_dir_all(&paths.logs_dir)?;
     fs::create_dir_all(&paths.control_dir)?;
-    remove_file_if_exists(&paths.stop_file)?;
 
-    if let Some(pid) = read_pid(&paths.pid_file)?
-        && is_process_running(pid)
-    {
+    if let Some(record) = running_supervisor(paths) {
         return Err(io::Error::new(
             io::ErrorKind::AlreadyExists,
-            format!("Supervisor already running with PID {}", pid),
+            format!("Supervisor already running with PID {}", record.pid),
         )
         .into());
     }
+    stale::clean_up(paths)?;
+
+    // bound before the pid file is written so a bad address leaves no stale pid behind.
+    let metrics_body: Option<SharedMetrics> = match &config.metrics_listen {
+        Some(addr) => Some(metrics::serve(addr)?),
+        None => None,
+    };
 
-    fs::write(&paths.pid_file, format!("{}\n", std::process::id()))?;
+    write_pid_record(&paths.pid_file, &PidRecord::current())?;
 
     let mut processes = build_processes(config, paths)?;
     let started_at = Utc::now();
-    let restart_delay = Duration::from_millis(config.restart_delay_ms);
+    let mut restart_delay = Duration::from_millis(config.restart_delay_ms);
+    let mut shutdown_timeout = Duration::from_secs(config.shutdown_timeout_secs);
+    install_reload_signal();
 
     // discard any control commands left over from a previous run before honoring new ones.
     let _ = drain_control(&paths.control_dir);
@@ -159,9 +171,22 @@ pub fn run_supervisor(
     loop {
         let now = Instant::now();
 
+        let mut reload = take_reload_signal();
         for command in drain_control(&paths.control_dir) {
+            if matches!(command, ControlCommand::Reload) {
+                reload = true;
+                continue;
+            }
             apply_control(&mut processes, command, paths, restart_delay)?;
         }
+        if reload {
+            reload_config(
+                &mut processes,
+                paths,
+                &mut restart_delay,
+                &mut shutdown_timeout,
+            )?;
+        }
 
         for process in &mut processes {
             poll_process(process, now, restart_delay)?;
@@ -169,6 +194,11 @@ pub fn run_supervisor(
 
         let snapshot = build_snapshot(paths, started_at, &processes);
         write_snapshot(&paths.state_file, &snapshot)?;
+        if let Some(shared) = &metrics_body
+            && let Ok(mut body) = shared.lock()
+        {
+            *body = metrics::render(&snapshot);
+        }
 
         if foreground {
             clear_screen();
@@ -187,10 +217,7 @@ pub fn run_supervisor(
         thread::sleep(Duration::from_millis(500));
     }
 
-    stop_children(
-        &mut processes,
-        Duration::from_secs(config.shutdown_timeout_secs),
-    )?;
+    stop_children(&mut processes, shutdown_timeout)?;
 
     let final_snapshot = build_snapshot(paths, started_at, &processes);
     write_snapshot(&paths.state_file, &final_snapshot)?;
@@ -201,13 +228,22 @@ pub fn run_supervisor(
 }
 
 pub fn stop_supervisor(config: &SupervisorConfig, paths: &Paths) -> Result<(), DynError> {
-    let pid = match read_pid(&paths.pid_file)? {
-        Some(pid) => pid,
+    let record = match read_pid_record(&paths.pid_file)? {
+        Some(record) => record,
         None => {
             println!("Supervisor is not running.");
             return Ok(());
         }
     };
+    if !record.is_alive() {
+        stale::clean_up(paths)?;
+        println!(
+            "Supervisor is not running; cleared stale state left by PID {}.",
+            record.pid
+        );
+        return Ok(());
+    }
+    let pid = record.pid;
 
     fs::create_dir_all(&paths.state_dir)?;
     fs::write(&paths.stop_file, b"stop\n")?;
@@ -248,7 +284,9 @@ fn build_processes(
 ) -> Result<Vec<ManagedProcess>, DynError> {
     let mut processes = Vec::with_capacity(config.processes.len());
     for process in &config.processes {
-        processes.push(build_one_process(process, paths));
+        let mut managed = build_one_process(process, paths);
+        managed.from_config = true;
+        processes.push(managed);
     }
     Ok(processes)
 }
@@ -290,6 +328,7 @@ fn build_one_process(process: &ProcessConfig, paths: &Paths) -> ManagedProcess {
         log_path,
         start_count: 0,
         manual_stop: false,
+        from_config: false,
     }
 }
 
@@ -331,7 +370,90 @@ fn apply_control(
                 processes.remove(idx);
             }
         }
+        // handled by the run loop, which folds queued reloads and SIGHUP into one pass.
+        ControlCommand::Reload => {}
+    }
+    Ok(())
+}
+
+// re-reads the config file and converges the live process table on it. an unreadable or invalid
+// config is reported and ignored so a bad edit never takes the running processes down. the state
+// dir cannot move under a running supervisor, so only timings and processes are picked up.
+fn reload_config(
+    processes: &mut Vec<ManagedProcess>,
+    paths: &Paths,
+    restart_delay: &mut Duration,
+    shutdown_timeout: &mut Duration,
+) -> Result<(), DynError> {
+    let config = match load_config(&paths.config_path) {
+        Ok((config, _)) => config,
+        Err(err) => {
+            eprintln!("{} config reload skipped: {err}", Utc::now().to_rfc3339());
+            return Ok(());
+        }
+    };
+    *restart_delay = Duration::from_millis(config.restart_delay_ms);
+    *shutdown_timeout = Duration::from_secs(config.shutdown_timeout_secs);
+
+    let current: Vec<ProcessConfig> = processes
+        .iter()
+        .filter(|p| p.from_config)
+        .map(|p| p.config.clone())
+        .collect();
+    let plan = plan_reload(&current, &config.processes);
+
+    for name in &plan.removed {
+        if let Some(idx) = processes
+            .iter()
+            .position(|p| p.from_config && p.config.name == *name)
+        {
+            stop_one(&mut processes[idx]);
+            processes.remove(idx);
+        }
+    }
+
+    for process in &plan.changed {
+        if let Some(idx) = processes.iter().position(|p| p.config.name == process.name) {
+            // only bring it back if it was live; a manually stopped process stays stopped.
+            let was_active =
+                processes[idx].child.is_some() || processes[idx].next_restart_at.is_some();
+            stop_one(&mut processes[idx]);
+            append_process_log_event(&processes[idx], "restarting for config reload");
+            processes[idx] = build_one_process(process, paths);
+            processes[idx].from_config = true;
+            if was_active {
+                attempt_start(&mut processes[idx], *restart_delay)?;
+            }
+        }
+    }
+
+    for process in plan.unchanged {
+        if let Some(existing) = processes.iter_mut().find(|p| p.config.name == process.name) {
+            existing.config = process;
+        }
+    }
+
+    for process in &plan.added {
+        // a config entry takes over a dynamic process that already uses its name.
+        if let Some(idx) = processes.iter().position(|p| p.config.name == process.name) {
+            stop_one(&mut processes[idx]);
+            processes.remove(idx);
+        }
+        let mut added = build_one_process(process, paths);
+        added.from_config = true;
+        if process.autostart {
+            attempt_start(&mut added, *restart_delay)?;
+        }
+        processes.push(added);
     }
+
+    eprintln!(
+        "{} config reloaded: {} added, {} removed, {} restarted",
+        Utc::now().to_rfc3339(),
+        plan.added.len(),
+        plan.removed.len(),
+        plan.changed.len()
+    );
     Ok(())
 }
 
@@ -381,6 +503,20 @@ fn sanitize_name(name: &str) -> String {
 
 fn attempt_start(process: &mut ManagedProcess, restart_delay: Duration) -> Result<(), DynError> {
     process.manual_stop = false;
+    // templates and env files are read on every start, so a rotated secret reaches the next one.
+    let env = match resolve_env(&process.config, &process.cwd_path) {
+        Ok(env) => env,
+        Err(err) => {
+            process.last_error = Some(format!(
+                "Failed to build the environment of '{}': {err}",
+                process.config.name
+            ));
+            process.status = ProcStatus::Failed;
+            append_process_log_event(process, &format!("failed_to_start env error={err}"));
+            schedule_restart(process, restart_delay);
+            return Ok(());
+        }
+    };
     process.status = ProcStatus::Starting;
     let started_at = Utc::now();
     process.start_count = process.start_count.saturating_add(1);
@@ -412,9 +548,7 @@ fn attempt_start(process: &mut ManagedProcess, restart_delay: Duration) -> Resul
         .stdout(Stdio::from(stdout))
         .stderr(Stdio::from(stderr));
 
-    for (key, value) in &process.config.env {
-        cmd.env(key, value);
-    }
+    cmd.envs(&env);
 
     match cmd.spawn() {
         Ok(child) => {
@@ -649,25 +783,6 @@ fn append_process_log_event(process: &ManagedProcess, message: &str) {
     }
 }
 
-fn read_pid(path: &Path) -> Result<Option<u32>, DynError> {
-    if !path.exists() {
-        return Ok(None);
-    }
-
-    let value = fs::read_to_string(path)?;
-    let trimmed = value.trim();
-    if trimmed.is_empty() {
-        return Ok(None);
-    }
-    let pid = trimmed.parse::<u32>().map_err(|err| {
-        io::Error::new(
-            io::ErrorKind::InvalidData,
-            format!("Invalid PID file {}: {err}", path.display()),
-        )
-    })?;
-    Ok(Some(pid))
-}
-
 fn remove_file_if_exists(path: &Path) -> io::Result<()> {
     match fs::remove_file(path) {
         Ok(()) => Ok(()),
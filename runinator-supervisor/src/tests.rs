@@ -1,7 +1,15 @@
 use std::collections::BTreeMap;
 
-use crate::config::ProcessConfig;
+use crate::child_env::{expand, parse_env_file, resolve_env};
+use crate::config::{Paths, ProcessConfig, plan_reload};
 use crate::control::{ControlCommand, drain, enqueue};
+use crate::metrics::render;
+use crate::os::parse_elapsed;
+use crate::snapshot::{ProcessSnapshot, StateSnapshot, read_snapshot, write_snapshot};
+use crate::stale::{
+    PidRecord, StaleIssue, diagnose, is_alive, read_pid_record, repair, running_supervisor,
+    write_pid_record,
+};
 
 fn temp_dir(tag: &str) -> std::path::PathBuf {
     let dir = std::env::temp_dir().join(format!(
@@ -28,6 +36,7 @@ fn enqueue_then_drain_round_trips_in_order() {
         args: vec!["--flag".to_string()],
         cwd: None,
         env,
+        env_files: Vec::new(),
         autostart: true,
         restart_on_failure: true,
         max_restarts_per_minute: 10,
@@ -80,3 +89,264 @@ fn drain_skips_malformed_files() {
     assert!(matches!(drained[0], ControlCommand::StopProcess { .. }));
     let _ = std::fs::remove_dir_all(&dir);
 }
+
+fn process(name: &str, command: &str) -> ProcessConfig {
+    ProcessConfig {
+        name: name.to_string(),
+        command: command.to_string(),
+        args: Vec::new(),
+        cwd: None,
+        env: BTreeMap::new(),
+        env_files: Vec::new(),
+        autostart: true,
+        restart_on_failure: true,
+        max_restarts_per_minute: 10,
+        command_windows: None,
+        args_windows: None,
+    }
+}
+
+#[test]
+fn plan_reload_restarts_only_changed_launches() {
+    let current = vec![
+        process("keep", "./keep"),
+        process("env-change", "./svc"),
+        process("gone", "./gone"),
+    ];
+
+    let mut keep = process("keep", "./keep");
+    keep.restart_on_failure = false;
+    let mut env_change = process("env-change", "./svc");
+    env_change
+        .env
+        .insert("RUNINATOR_LOG".to_string(), "debug".to_string());
+    let desired = vec![keep, env_change, process("new", "./new")];
+
+    let plan = plan_reload(&current, &desired);
+    let names = |list: &[ProcessConfig]| list.iter().map(|p| p.name.clone()).collect::<Vec<_>>();
+    assert_eq!(names(&plan.added), vec!["new"]);
+    assert_eq!(plan.removed, vec!["gone"]);
+    assert_eq!(names(&plan.changed), vec!["env-change"]);
+    // a policy-only edit is refreshed in place without a restart.
+    assert_eq!(names(&plan.unchanged), vec!["keep"]);
+    assert!(!plan.unchanged[0].restart_on_failure);
+}
+
+#[test]
+fn metrics_render_per_process_health() {
+    let snapshot = StateSnapshot {
+        supervisor_pid: 1,
+        config_path: "runinator-supervisor.json".to_string(),
+        started_at: String::new(),
+        updated_at: String::new(),
+        processes: vec![
+            ProcessSnapshot {
+                name: "worker".to_string(),
+                status: "running".to_string(),
+                pid: Some(42),
+                restarts: 3,
+                uptime_seconds: Some(90),
+                last_exit_code: None,
+                last_error: None,
+                started_at: None,
+                command: "./worker".to_string(),
+                cwd: ".".to_string(),
+                log_file: String::new(),
+            },
+            ProcessSnapshot {
+                name: "we\"ird".to_string(),
+                status: "backoff".to_string(),
+                pid: None,
+                restarts: 7,
+                uptime_seconds: None,
+                last_exit_code: Some(101),
+                last_error: Some("Exited with code 101".to_string()),
+                started_at: None,
+                command: "./weird".to_string(),
+                cwd: ".".to_string(),
+                log_file: String::new(),
+            },
+        ],
+    };
+
+    let body = render(&snapshot);
+    assert!(body.contains("runinator_supervisor_processes 2\n"));
+    assert!(body.contains("runinator_supervisor_process_up{process=\"worker\"} 1\n"));
+    assert!(body.contains("runinator_supervisor_process_up{process=\"we\\\"ird\"} 0\n"));
+    assert!(body.contains("runinator_supervisor_process_restarts_total{process=\"worker\"} 3\n"));
+    assert!(body.contains(
+        "runinator_supervisor_process_status{process=\"we\\\"ird\",status=\"backoff\"} 1\n"
+    ));
+    assert!(
+        body.contains("runinator_supervisor_process_last_exit_code{process=\"we\\\"ird\"} 101\n")
+    );
+    // no exit yet, so no exit-code sample for the running process.
+    assert!(!body.contains("runinator_supervisor_process_last_exit_code{process=\"worker\"}"));
+}
+
+#[test]
+fn env_templates_expand_files_and_supervisor_variables() {
+    let dir = temp_dir("env-template");
+    std::fs::write(dir.join("db-password"), "s3cret\n").unwrap();
+    let lookup = |name: &str| (name == "HOME_REGION").then(|| "eu-west-1".to_string());
+
+    let expanded = expand(
+        "postgres://app:${file:db-password}@db/${env:HOME_REGION}?x=${other}",
+        &dir,
+        lookup,
+    )
+    .unwrap();
+    assert_eq!(expanded, "postgres://app:s3cret@db/eu-west-1?x=${other}");
+
+    let missing = expand("${env:NOT_SET}", &dir, lookup).unwrap_err();
+    assert!(missing.contains("NOT_SET"));
+    assert!(expand("${file:absent}", &dir, lookup).is_err());
+    assert!(expand("${env:HOME_REGION", &dir, lookup).is_err());
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn env_files_load_first_and_process_env_overrides_them() {
+    let dir = temp_dir("env-files");
+    std::fs::write(
+        dir.join("shared.env"),
+        "# shared secrets\nexport API_KEY=\"from-file\"\n\nREGION='us-east-1'\n",
+    )
+    .unwrap();
+    std::fs::write(dir.join("token"), "tok-123").unwrap();
+
+    let mut config = process("web", "./web");
+    config.env_files = vec!["shared.env".to_string()];
+    config
+        .env
+        .insert("API_KEY".to_string(), "${file:token}".to_string());
+    let env = resolve_env(&config, &dir).unwrap();
+    assert_eq!(env.get("API_KEY").map(String::as_str), Some("tok-123"));
+    assert_eq!(env.get("REGION").map(String::as_str), Some("us-east-1"));
+
+    assert!(parse_env_file("NO_EQUALS_SIGN").is_err());
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+fn paths_in(dir: &std::path::Path) -> Paths {
+    Paths {
+        config_path: dir.join("runinator-supervisor.json"),
+        config_dir: dir.to_path_buf(),
+        state_dir: dir.to_path_buf(),
+        pid_file: dir.join("supervisor.pid"),
+        stop_file: dir.join("stop"),
+        state_file: dir.join("state.json"),
+        control_dir: dir.join("control"),
+        logs_dir: dir.join("logs"),
+        supervisor_log: dir.join("supervisor.log"),
+    }
+}
+
+#[test]
+fn ps_elapsed_times_parse_to_seconds() {
+    assert_eq!(parse_elapsed("00:07"), Some(7));
+    assert_eq!(parse_elapsed("12:34"), Some(754));
+    assert_eq!(parse_elapsed("01:00:05"), Some(3605));
+    assert_eq!(parse_elapsed("2-03:00:00"), Some(2 * 86_400 + 3 * 3600));
+    assert_eq!(parse_elapsed("7"), None);
+    assert_eq!(parse_elapsed("x:10"), None);
+}
+
+#[test]
+fn pid_records_round_trip_and_read_legacy_files() {
+    let dir = temp_dir("pid-record");
+    let path = dir.join("supervisor.pid");
+    let record = PidRecord {
+        pid: 4242,
+        started_at: Some("2026-01-02T03:04:05Z".parse().unwrap()),
+    };
+    write_pid_record(&path, &record).unwrap();
+    assert_eq!(read_pid_record(&path).unwrap(), Some(record));
+
+    // a pid file from before start times were recorded holds only the pid.
+    std::fs::write(&path, "4242\n").unwrap();
+    let legacy = read_pid_record(&path).unwrap().unwrap();
+    assert_eq!(legacy.pid, 4242);
+    assert!(legacy.started_at.is_none());
+
+    std::fs::write(&path, "not-a-pid\n").unwrap();
+    assert!(read_pid_record(&path).is_err());
+    assert!(read_pid_record(&dir.join("absent.pid")).unwrap().is_none());
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+// the pid of this test process is alive, but a record saying it started years ago describes some
+// earlier process that held the same pid.
+#[cfg(unix)]
+#[test]
+fn a_reused_pid_is_not_taken_for_the_recorded_process() {
+    let pid = std::process::id();
+    assert!(PidRecord::current().is_alive());
+    assert!(is_alive(pid, None));
+    assert!(!is_alive(
+        pid,
+        Some("2001-01-01T00:00:00Z".parse().unwrap())
+    ));
+}
+
+#[test]
+fn doctor_finds_and_repairs_what_a_dead_supervisor_left_behind() {
+    let dir = temp_dir("stale");
+    let paths = paths_in(&dir);
+    // no process runs with a pid this large.
+    let dead_pid = 999_999_999;
+    write_pid_record(
+        &paths.pid_file,
+        &PidRecord {
+            pid: dead_pid,
+            started_at: Some(chrono::Utc::now()),
+        },
+    )
+    .unwrap();
+    std::fs::write(&paths.stop_file, b"stop\n").unwrap();
+    enqueue(&paths.control_dir, &ControlCommand::Reload).unwrap();
+    let snapshot = StateSnapshot {
+        supervisor_pid: dead_pid,
+        config_path: "runinator-supervisor.json".to_string(),
+        started_at: String::new(),
+        updated_at: String::new(),
+        processes: vec![ProcessSnapshot {
+            name: "worker".to_string(),
+            status: "running".to_string(),
+            pid: Some(dead_pid),
+            restarts: 0,
+            uptime_seconds: Some(600),
+            last_exit_code: None,
+            last_error: None,
+            started_at: None,
+            command: "./worker".to_string(),
+            cwd: ".".to_string(),
+            log_file: String::new(),
+        }],
+    };
+    write_snapshot(&paths.state_file, &snapshot).unwrap();
+
+    assert!(running_supervisor(&paths).is_none());
+    let issues = diagnose(&paths);
+    assert_eq!(
+        issues,
+        vec![
+            StaleIssue::StalePidFile { pid: dead_pid },
+            StaleIssue::StaleStopFile,
+            StaleIssue::PhantomProcess {
+                name: "worker".to_string(),
+                pid: dead_pid,
+            },
+            StaleIssue::StaleControlFiles { count: 1 },
+        ]
+    );
+
+    repair(&paths, &issues).unwrap();
+    assert!(diagnose(&paths).is_empty());
+    assert!(!paths.pid_file.exists());
+    assert!(!paths.stop_file.exists());
+    let repaired = read_snapshot(&paths.state_file).unwrap();
+    assert_eq!(repaired.processes[0].status, "stopped");
+    assert!(repaired.processes[0].pid.is_none());
+    let _ = std::fs::remove_dir_all(&dir);
+}
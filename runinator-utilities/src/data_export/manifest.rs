@@ -0,0 +1,89 @@
+//! integrity manifests written next to export files: each file's name, row count, sha-256 and
+//! generation time, for downstream ingestion to verify what it picked up.
+
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use runinator_models::errors::SendableError;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+pub const MANIFEST_MIME_TYPE: &str = "application/json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// the file name, relative to the manifest's folder.
+    pub file: String,
+    pub rows: usize,
+    pub size_bytes: u64,
+    /// lower-case hex sha-256 of the file contents.
+    pub sha256: String,
+    pub generated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportManifest {
+    pub provider: String,
+    pub generated_at: DateTime<Utc>,
+    pub files: Vec<ManifestEntry>,
+}
+
+impl ExportManifest {
+    pub fn new(provider: impl Into<String>) -> Self {
+        Self {
+            provider: provider.into(),
+            generated_at: Utc::now(),
+            files: Vec::new(),
+        }
+    }
+
+    /// checksum a finished export file and record it.
+    pub fn add_file(&mut self, path: &Path, rows: usize) -> Result<(), SendableError> {
+        let metadata = fs::metadata(path)?;
+        self.files.push(ManifestEntry {
+            file: path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            rows,
+            size_bytes: metadata.len(),
+            sha256: sha256_file(path)?,
+            generated_at: metadata
+                .modified()
+                .map(DateTime::from)
+                .unwrap_or_else(|_| Utc::now()),
+        });
+        Ok(())
+    }
+
+    /// write the manifest as pretty json, returning its size in bytes.
+    pub fn write(&self, path: &Path) -> Result<u64, SendableError> {
+        let payload = serde_json::to_vec_pretty(self)?;
+        fs::write(path, &payload)?;
+        Ok(payload.len() as u64)
+    }
+}
+
+pub fn sha256_file(path: &Path) -> Result<String, SendableError> {
+    let mut file = fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect())
+}
+
+#[cfg(test)]
+#[path = "manifest_tests.rs"]
+mod tests;
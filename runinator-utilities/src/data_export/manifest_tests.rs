@@ -0,0 +1,30 @@
+use super::*;
+
+#[test]
+fn manifest_records_checksums_and_round_trips() {
+    let dir = std::env::temp_dir().join(format!("runinator-manifest-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let export = dir.join("report.csv");
+    fs::write(&export, b"abc").unwrap();
+
+    let mut manifest = ExportManifest::new("SQL");
+    manifest.add_file(&export, 1).unwrap();
+    let manifest_path = dir.join("manifest.json");
+    let size = manifest.write(&manifest_path).unwrap();
+
+    let parsed: ExportManifest =
+        serde_json::from_slice(&fs::read(&manifest_path).unwrap()).unwrap();
+    assert_eq!(size, fs::metadata(&manifest_path).unwrap().len());
+    assert_eq!(parsed.provider, "SQL");
+    assert_eq!(parsed.files.len(), 1);
+    assert_eq!(parsed.files[0].file, "report.csv");
+    assert_eq!(parsed.files[0].rows, 1);
+    assert_eq!(parsed.files[0].size_bytes, 3);
+    // sha-256 of "abc".
+    assert_eq!(
+        parsed.files[0].sha256,
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -4,6 +4,7 @@ use runinator_models::errors::SendableError;
 
 pub mod csv;
 pub mod excel;
+pub mod manifest;
 
 #[derive(Debug, Clone)]
 pub struct TableData {
@@ -1,4 +1,5 @@
 use std::env;
+use std::path::Path;
 
 use log::info;
 use runinator_models::errors::SendableError;
@@ -13,3 +14,15 @@ pub fn set_exe_dir_as_cwd() -> Result<(), SendableError> {
     info!("Current working directory: {:?}", cwd);
     Ok(())
 }
+
+/// bytes available to this process on the filesystem holding `path` (the disk with the longest
+/// mount point containing it). `None` when the path cannot be resolved or no disk matches.
+pub fn available_space(path: &Path) -> Option<u64> {
+    let path = path.canonicalize().ok()?;
+    let disks = sysinfo::Disks::new_with_refreshed_list();
+    disks
+        .iter()
+        .filter(|disk| path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())
+        .map(|disk| disk.available_space())
+}
@@ -114,6 +114,7 @@ mod tests {
                 updated_at: None,
             }],
             triggers: Vec::new(),
+            baselines: Vec::new(),
         };
         let secrets = SecretBundle {
             secrets: vec![SecretBundleEntry {
@@ -0,0 +1,65 @@
+use super::{redact_args, reported_config};
+
+fn strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+#[test]
+fn config_keeps_service_env_and_redacts_secrets() {
+    let config = reported_config([
+        ("RUNINATOR_PORT".to_string(), "8080".to_string()),
+        (
+            "RUNINATOR_BROKER_AUTH_SECRET".to_string(),
+            "hunter2".to_string(),
+        ),
+        (
+            "RUNINATOR_DATABASE_URL".to_string(),
+            "postgres://runinator:hunter2@db:5432/runinator".to_string(),
+        ),
+        ("OTEL_SERVICE_NAME".to_string(), "ws".to_string()),
+        ("RUST_LOG".to_string(), "info".to_string()),
+        ("HOME".to_string(), "/root".to_string()),
+    ]);
+
+    assert_eq!(config.len(), 5);
+    assert_eq!(config["RUNINATOR_PORT"], "8080");
+    assert_eq!(config["RUNINATOR_BROKER_AUTH_SECRET"], "[redacted]");
+    assert_eq!(
+        config["RUNINATOR_DATABASE_URL"],
+        "postgres://[redacted]@db:5432/runinator"
+    );
+    assert_eq!(config["OTEL_SERVICE_NAME"], "ws");
+    assert!(!config.contains_key("HOME"));
+}
+
+#[test]
+fn args_redact_secret_flags_in_both_forms() {
+    let args = redact_args(strings(&[
+        "--port",
+        "8080",
+        "--broker-auth-secret",
+        "hunter2",
+        "--auth-static-api-keys=k1,k2",
+        "--database-url=mysql://root:hunter2@db/runinator?ssl=true",
+        "--disable-gossip",
+    ]));
+
+    assert_eq!(
+        args,
+        strings(&[
+            "--port",
+            "8080",
+            "--broker-auth-secret",
+            "[redacted]",
+            "--auth-static-api-keys=[redacted]",
+            "--database-url=mysql://[redacted]@db/runinator?ssl=true",
+            "--disable-gossip",
+        ])
+    );
+}
+
+#[test]
+fn a_secret_flag_without_a_value_leaves_the_next_flag_alone() {
+    let args = redact_args(strings(&["--token-refresh", "--port", "8080"]));
+    assert_eq!(args, strings(&["--token-refresh", "--port", "8080"]));
+}
@@ -49,6 +49,10 @@ pub struct Config {
     #[arg(long, default_value = "runinator-waker")]
     pub broker_client_id: String,
 
+    /// shared secret used to mint bearer tokens for an auth-gated http broker.
+    #[arg(long, env = "RUNINATOR_BROKER_AUTH_SECRET")]
+    pub broker_auth_secret: Option<String>,
+
     #[arg(long, default_value = "http://127.0.0.1:8080/")]
     pub api_base_url: String,
 
@@ -9,7 +9,7 @@ use runinator_api::{
 use runinator_broker::{
     Broker,
     adapters::{kafka::KafkaBrokerConfig, rabbitmq::RabbitMqBrokerConfig},
-    http::client::HttpBroker,
+    http::{auth::BrokerTokenSource, client::HttpBroker},
     in_memory::InMemoryBroker,
     tcp::client::TcpBroker,
 };
@@ -32,6 +32,10 @@ async fn main() -> Result<(), SendableError> {
 
     info!("parsing waker config");
     let config = parse_config()?;
+    startup::publish_report(startup::collect_report(
+        "Runinator Waker",
+        env!("CARGO_PKG_VERSION"),
+    ));
     info!(waker_id = %config.waker_id, "waker starting");
 
     let broker = build_broker(&config).await?;
@@ -167,7 +171,8 @@ async fn build_broker(config: &Config) -> Result<Arc<dyn Broker>, SendableError>
             let client = reqwest::Client::builder()
                 .build()
                 .map_err(|err| runinator_waker::errors::BROKER_CLIENT.error(err))?;
-            Ok(Arc::new(HttpBroker::new(url, client)) as Arc<dyn Broker>)
+            let auth = BrokerTokenSource::from_secret(config.broker_auth_secret.as_deref());
+            Ok(Arc::new(HttpBroker::new(url, client).with_auth(auth)) as Arc<dyn Broker>)
         }
         "in-memory" => Ok(Arc::new(InMemoryBroker::new()) as Arc<dyn Broker>),
         "tcp" => Ok(Arc::new(TcpBroker::new(config.broker_endpoint.clone())) as Arc<dyn Broker>),
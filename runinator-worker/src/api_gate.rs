@@ -0,0 +1,175 @@
+//! pause action polling while the web service is unreachable. a worker can keep executing without
+//! the api, but it cannot claim executor leases or record runs through it, so a sustained outage
+//! turns every delivery into a nack loop. a readiness probe flips a shared flag the action loop
+//! waits on; the control loop keeps running so cancels for in-flight work still land.
+
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use runinator_api::{AsyncApiClient, StaticLocator};
+use tokio::{
+    sync::{Notify, watch},
+    task::JoinHandle,
+};
+use tracing::{info, warn};
+
+use crate::events::{WorkerEvent, WorkerEventSink};
+use crate::metrics;
+
+const DEFAULT_PROBE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// when the action loop stops polling the broker because the api is unreachable.
+#[derive(Debug, Clone, Copy)]
+pub struct ApiPausePolicy {
+    /// how long the api must stay unreachable before polling pauses.
+    pub unavailable_after: Duration,
+    /// how often the api readiness endpoint is probed.
+    pub probe_interval: Duration,
+}
+
+impl ApiPausePolicy {
+    /// pause after `unavailable_after` of failed probes, probing at most every five seconds.
+    pub fn new(unavailable_after: Duration) -> Self {
+        Self {
+            unavailable_after,
+            probe_interval: DEFAULT_PROBE_INTERVAL
+                .min(unavailable_after)
+                .max(Duration::from_millis(100)),
+        }
+    }
+}
+
+/// why the action loop is not polling the broker; it polls only while neither is set.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct PollingPause {
+    /// the api has been unreachable for longer than the configured window.
+    pub(crate) api_unreachable: bool,
+    /// an operator paused execution cluster-wide.
+    pub(crate) cluster: bool,
+}
+
+impl PollingPause {
+    pub(crate) fn is_paused(&self) -> bool {
+        self.api_unreachable || self.cluster
+    }
+}
+
+/// apply `update` to the shared pause, moving the `polling_paused` gauge only when the combined flag
+/// flips so the api probe and the cluster watch never double-count.
+pub(crate) fn update_polling_pause(
+    paused: &watch::Sender<PollingPause>,
+    update: impl FnOnce(&mut PollingPause),
+) {
+    let mut flipped = None;
+    paused.send_if_modified(|state| {
+        let before = *state;
+        update(state);
+        if before.is_paused() != state.is_paused() {
+            flipped = Some(state.is_paused());
+        }
+        before != *state
+    });
+    if let Some(now_paused) = flipped {
+        metrics::polling_paused(now_paused);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ApiTransition {
+    Paused { unreachable_for: Duration },
+    Resumed { paused_for: Duration },
+}
+
+/// folds probe results into pause/resume transitions. a single failed probe never pauses; only an
+/// outage lasting `unavailable_after` does, and the first successful probe resumes.
+#[derive(Debug, Default)]
+pub(crate) struct ApiAvailability {
+    unavailable_since: Option<Instant>,
+    paused_at: Option<Instant>,
+}
+
+impl ApiAvailability {
+    pub(crate) fn observe(
+        &mut self,
+        reachable: bool,
+        now: Instant,
+        unavailable_after: Duration,
+    ) -> Option<ApiTransition> {
+        if reachable {
+            self.unavailable_since = None;
+            return self
+                .paused_at
+                .take()
+                .map(|paused_at| ApiTransition::Resumed {
+                    paused_for: now.saturating_duration_since(paused_at),
+                });
+        }
+        let since = *self.unavailable_since.get_or_insert(now);
+        let unreachable_for = now.saturating_duration_since(since);
+        if self.paused_at.is_none() && unreachable_for >= unavailable_after {
+            self.paused_at = Some(now);
+            return Some(ApiTransition::Paused { unreachable_for });
+        }
+        None
+    }
+}
+
+/// probe the api on `policy.probe_interval` until `shutdown`, publishing the unreachable flag on
+/// `paused`. the flag always clears when the probe stops.
+pub(crate) fn spawn_probe(
+    api_client: AsyncApiClient<StaticLocator>,
+    policy: ApiPausePolicy,
+    paused: Arc<watch::Sender<PollingPause>>,
+    events: Arc<dyn WorkerEventSink>,
+    shutdown: Arc<Notify>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut availability = ApiAvailability::default();
+        let mut ticker = tokio::time::interval(policy.probe_interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            tokio::select! {
+                _ = shutdown.notified() => break,
+                _ = ticker.tick() => {}
+            }
+            let reachable =
+                match tokio::time::timeout(policy.probe_interval, api_client.check_ready()).await {
+                    Ok(Ok(())) => true,
+                    Ok(Err(err)) => {
+                        warn!("api readiness probe failed: {}", err);
+                        false
+                    }
+                    Err(_) => {
+                        warn!("api readiness probe timed out");
+                        false
+                    }
+                };
+            match availability.observe(reachable, Instant::now(), policy.unavailable_after) {
+                Some(ApiTransition::Paused { unreachable_for }) => {
+                    warn!(
+                        unreachable_secs = unreachable_for.as_secs(),
+                        "api unreachable; pausing action polling until it returns"
+                    );
+                    update_polling_pause(&paused, |state| state.api_unreachable = true);
+                    events.handle(WorkerEvent::PollingPaused {
+                        unreachable_ms: unreachable_for.as_millis() as i64,
+                    });
+                }
+                Some(ApiTransition::Resumed { paused_for }) => {
+                    info!(
+                        paused_secs = paused_for.as_secs(),
+                        "api reachable again; resuming action polling"
+                    );
+                    update_polling_pause(&paused, |state| state.api_unreachable = false);
+                    events.handle(WorkerEvent::PollingResumed {
+                        paused_ms: paused_for.as_millis() as i64,
+                    });
+                }
+                None => {}
+            }
+        }
+        update_polling_pause(&paused, |state| state.api_unreachable = false);
+    })
+}
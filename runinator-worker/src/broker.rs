@@ -5,11 +5,11 @@ use runinator_broker::ws::client::WsBroker;
 use runinator_broker::{
     Broker, BrokerError,
     adapters::{kafka::KafkaBrokerConfig, rabbitmq::RabbitMqBrokerConfig},
-    http::client::HttpBroker,
+    http::{auth::BrokerTokenSource, client::HttpBroker},
     in_memory::InMemoryBroker,
     tcp::client::TcpBroker,
 };
-use runinator_models::errors::{RuntimeError, SendableError};
+use runinator_models::errors::SendableError;
 
 use crate::config;
 
@@ -28,8 +28,10 @@ pub struct BrokerConfig {
     pub broker_control_topic: String,
     pub broker_result_topic: String,
     pub broker_client_id: String,
-    /// presented as a bearer token; only used by the `http`/`ws` backends today.
+    /// presented as a bearer token; only used by the `ws` backend today.
     pub api_key: Option<String>,
+    /// shared secret the `http` backend mints bearer tokens from when the broker is auth-gated.
+    pub broker_auth_secret: Option<String>,
 }
 
 impl config::Config {
@@ -43,6 +45,7 @@ impl config::Config {
             broker_result_topic: self.broker_result_topic.clone(),
             broker_client_id: self.broker_client_id.clone(),
             api_key: self.api_key.clone(),
+            broker_auth_secret: self.broker_auth_secret.clone(),
         }
     }
 }
@@ -63,7 +66,8 @@ pub async fn build_broker(config: &BrokerConfig) -> Result<Arc<dyn Broker>, Send
                 .build()
                 .map_err(|err| crate::errors::BROKER_CLIENT.error(err))?;
 
-            Arc::new(HttpBroker::new(url, client))
+            let auth = BrokerTokenSource::from_secret(config.broker_auth_secret.as_deref());
+            Arc::new(HttpBroker::new(url, client).with_auth(auth))
         }
         #[cfg(feature = "ws")]
         "ws" => Arc::new(WsBroker::connect(
@@ -119,13 +123,9 @@ pub async fn build_broker(config: &BrokerConfig) -> Result<Arc<dyn Broker>, Send
 }
 
 pub(crate) fn broker_error(context: &'static str, err: BrokerError) -> SendableError {
-    // keep the per-context dotted key for back-compat while rendering the numbered code.
-    let descriptor = crate::errors::BROKER_OPERATION;
-    Box::new(RuntimeError::new(
+    // keep the per-context dotted key for back-compat while carrying the numbered code.
+    crate::errors::BROKER_OPERATION.keyed(
         format!("worker.broker.{context}"),
-        format!(
-            "{} - {}: {context}: {err}",
-            descriptor.code, descriptor.summary
-        ),
-    ))
+        format!("{context}: {err}"),
+    )
 }
@@ -0,0 +1,73 @@
+//! honour an operator's cluster-wide execution pause. the web service holds the pause, and every
+//! worker checks it on an interval and stops taking actions off the broker while execution is
+//! paused, so one call reaches the whole fleet. a failed check keeps the last known state rather
+//! than guessing; the api gate handles an api that stays away.
+
+use std::{sync::Arc, time::Duration};
+
+use runinator_api::{AsyncApiClient, StaticLocator};
+use tokio::{
+    sync::{Notify, watch},
+    task::JoinHandle,
+};
+use tracing::{debug, info, warn};
+
+use crate::api_gate::{PollingPause, update_polling_pause};
+use crate::events::{WorkerEvent, WorkerEventSink};
+
+/// default interval between cluster pause checks.
+pub const DEFAULT_CLUSTER_PAUSE_CHECK: Duration = Duration::from_secs(5);
+
+const MIN_CHECK_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// check the cluster pause every `interval` until `shutdown`, publishing the execution flag on
+/// `paused`. the flag always clears when the watch stops.
+pub(crate) fn spawn_watch(
+    api_client: AsyncApiClient<StaticLocator>,
+    interval: Duration,
+    paused: Arc<watch::Sender<PollingPause>>,
+    events: Arc<dyn WorkerEventSink>,
+    shutdown: Arc<Notify>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            tokio::select! {
+                _ = shutdown.notified() => break,
+                _ = ticker.tick() => {}
+            }
+            let timeout = interval.max(MIN_CHECK_TIMEOUT);
+            let pause = match tokio::time::timeout(timeout, api_client.fetch_cluster_pause()).await
+            {
+                Ok(Ok(pause)) => pause,
+                Ok(Err(err)) => {
+                    debug!("cluster pause check failed: {}", err);
+                    continue;
+                }
+                Err(_) => {
+                    debug!("cluster pause check timed out");
+                    continue;
+                }
+            };
+            let was_paused = paused.borrow().cluster;
+            if pause.execution == was_paused {
+                continue;
+            }
+            update_polling_pause(&paused, |state| state.cluster = pause.execution);
+            if pause.execution {
+                warn!(
+                    reason = pause.reason.as_deref().unwrap_or("-"),
+                    "execution paused cluster-wide; pausing action polling"
+                );
+                events.handle(WorkerEvent::ExecutionPaused {
+                    reason: pause.reason,
+                });
+            } else {
+                info!("cluster execution resumed; resuming action polling");
+                events.handle(WorkerEvent::ExecutionResumed);
+            }
+        }
+        update_polling_pause(&paused, |state| state.cluster = false);
+    })
+}
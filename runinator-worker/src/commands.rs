@@ -0,0 +1,151 @@
+//! the worker's optional local command port: answers `status` with what is executing and which
+//! plugins are loaded, `cancel` for one dispatched action, and `info` with the startup report. the
+//! protocol types live in `runinator_comm::worker_command` so operator tooling can speak it without
+//! depending on the worker.
+
+use std::{collections::HashMap, sync::Arc, sync::atomic::Ordering, time::Instant};
+
+use runinator_comm::{
+    RunningActionStatus, WireCodec, WorkerCancelReport, WorkerCommandRequest,
+    WorkerCommandResponse, WorkerStatusReport,
+};
+use runinator_models::errors::SendableError;
+use runinator_utilities::startup;
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+    sync::{Mutex, Notify, watch},
+    task::JoinHandle,
+};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::api_gate::PollingPause;
+use crate::worker::InFlightAction;
+
+/// what the command port reports on; shared with the action loop through `in_flight`.
+#[derive(Clone)]
+pub(crate) struct CommandState {
+    pub(crate) replica_id: Option<Uuid>,
+    pub(crate) started_at: Instant,
+    pub(crate) max_concurrent_actions: usize,
+    pub(crate) plugins: Vec<String>,
+    pub(crate) in_flight: Arc<Mutex<HashMap<Uuid, InFlightAction>>>,
+    pub(crate) polling_paused: watch::Receiver<PollingPause>,
+}
+
+/// bind the command port and serve it until `shutdown` is notified. binding happens before the
+/// task is spawned so a bad address fails worker startup.
+pub(crate) async fn spawn(
+    addr: &str,
+    state: CommandState,
+    shutdown: Arc<Notify>,
+) -> Result<JoinHandle<()>, SendableError> {
+    let listener = TcpListener::bind(addr)
+        .await
+        .map_err(|err| crate::errors::COMMAND_LISTEN_BIND.error(err))?;
+    info!(addr, "worker command port listening");
+    Ok(tokio::spawn(async move {
+        loop {
+            let stream = tokio::select! {
+                _ = shutdown.notified() => return,
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, _)) => stream,
+                    Err(err) => {
+                        warn!("worker command port accept failed: {}", err);
+                        continue;
+                    }
+                },
+            };
+            let state = state.clone();
+            tokio::spawn(async move {
+                if let Err(err) = handle_connection(stream, &state).await {
+                    warn!("worker command connection error: {}", err);
+                }
+            });
+        }
+    }))
+}
+
+async fn handle_connection(stream: TcpStream, state: &CommandState) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line).await?;
+    let response = match WorkerCommandRequest::from_wire(line.trim_end()) {
+        Ok(request) => handle_request(state, request).await,
+        Err(err) => WorkerCommandResponse::Error {
+            message: err.to_string(),
+        },
+    };
+
+    let mut stream = reader.into_inner();
+    let payload = response.to_wire().map_err(std::io::Error::other)?;
+    stream.write_all(payload.as_bytes()).await?;
+    stream.write_all(b"\n").await
+}
+
+pub(crate) async fn handle_request(
+    state: &CommandState,
+    request: WorkerCommandRequest,
+) -> WorkerCommandResponse {
+    match request {
+        WorkerCommandRequest::Status => {
+            let mut running = state
+                .in_flight
+                .lock()
+                .await
+                .iter()
+                .map(|(node_run_id, action)| RunningActionStatus {
+                    command_id: action.command_id,
+                    workflow_run_id: action.workflow_run_id,
+                    workflow_node_run_id: *node_run_id,
+                    node_id: action.node_id.clone(),
+                    provider: action.provider.clone(),
+                    function: action.function.clone(),
+                    attempt: action.attempt,
+                    started_at: action.started_at,
+                })
+                .collect::<Vec<_>>();
+            running.sort_by_key(|action| action.started_at);
+            let pause = *state.polling_paused.borrow();
+            WorkerCommandResponse::Status(WorkerStatusReport {
+                replica_id: state.replica_id,
+                uptime_seconds: state.started_at.elapsed().as_secs(),
+                max_concurrent_actions: state.max_concurrent_actions,
+                running,
+                plugins: state.plugins.clone(),
+                polling_paused: pause.is_paused(),
+                execution_paused: pause.cluster,
+            })
+        }
+        WorkerCommandRequest::Cancel { command_id } => {
+            let action = state
+                .in_flight
+                .lock()
+                .await
+                .values()
+                .find(|action| action.command_id == command_id)
+                .cloned();
+            let canceled = match action {
+                Some(action) => {
+                    // an operator cancel settles the node as canceled, exactly like a control-channel
+                    // cancel, rather than requeueing it as shutdown preemption would.
+                    action.canceled_by_control.store(true, Ordering::Release);
+                    action.token.cancel();
+                    info!(command_id = %command_id, "canceled local execution from command port");
+                    true
+                }
+                None => false,
+            };
+            WorkerCommandResponse::Cancel(WorkerCancelReport {
+                command_id,
+                canceled,
+            })
+        }
+        WorkerCommandRequest::Info => WorkerCommandResponse::Info(Box::new(
+            startup::published_report().cloned().unwrap_or_else(|| {
+                startup::collect_report("Runinator Worker", env!("CARGO_PKG_VERSION"))
+            }),
+        )),
+    }
+}
@@ -1,7 +1,9 @@
 use clap::Parser;
 use runinator_models::errors::SendableError;
 use runinator_utilities::app_data;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use tracing::warn;
 use uuid::Uuid;
 
 #[derive(Debug, Clone)]
@@ -14,7 +16,16 @@ pub struct Config {
     pub broker_result_topic: String,
     pub broker_client_id: String,
     pub broker_consumer_id: String,
+    pub broker_auth_secret: Option<String>,
     pub max_concurrent_actions: usize,
+    /// delivery attempts an action gets before a further redelivery fails its node run; unlimited
+    /// when `None`.
+    pub max_delivery_attempts: Option<u32>,
+    /// seconds the api may stay unreachable before the worker pauses action polling; never pauses
+    /// when `None`.
+    pub pause_on_api_unavailable_seconds: Option<u64>,
+    /// seconds between checks for a cluster-wide execution pause; never checks when `None`.
+    pub cluster_pause_check_seconds: Option<u64>,
     pub shutdown_grace_seconds: u64,
     pub api_base_url: String,
     pub api_key: Option<String>,
@@ -24,6 +35,13 @@ pub struct Config {
     /// routing labels this worker advertises; the reducer pins label-targeted actions to a worker
     /// whose labels are a superset of the action's required selector.
     pub labels: BTreeMap<String, String>,
+    /// broker queues this worker takes actions from; empty takes the default queue only.
+    pub queues: BTreeSet<String>,
+    /// `host:port` for the local status/cancel command port; disabled when `None`.
+    pub command_listen: Option<String>,
+    /// url serving the fleet's version manifest; never checked when `None`.
+    pub update_channel_url: Option<String>,
+    pub update_check_seconds: u64,
 }
 
 #[derive(Parser, Debug)]
@@ -53,9 +71,32 @@ struct CliArgs {
     #[arg(long)]
     broker_consumer_id: Option<String>,
 
+    /// shared secret used to mint bearer tokens for an auth-gated http broker.
+    #[arg(long, env = "RUNINATOR_BROKER_AUTH_SECRET")]
+    broker_auth_secret: Option<String>,
+
     #[arg(long, default_value_t = 4)]
     max_concurrent_actions: usize,
 
+    /// delivery attempts an action gets; a redelivery past this (after nacks or expired leases)
+    /// fails the node run instead of retrying forever. unlimited when unset or 0.
+    #[arg(long, env = "RUNINATOR_WORKER_MAX_DELIVERY_ATTEMPTS")]
+    max_delivery_attempts: Option<u32>,
+
+    /// pause broker polling once the web service has been unreachable for this many seconds, and
+    /// resume when it answers again. in-flight actions keep running. disabled when unset or 0.
+    #[arg(long, env = "RUNINATOR_WORKER_PAUSE_ON_API_UNAVAILABLE_SECONDS")]
+    pause_on_api_unavailable_seconds: Option<u64>,
+
+    /// how often to check the web service for a cluster-wide execution pause; while one is set the
+    /// worker takes no new actions off the broker. disabled when 0.
+    #[arg(
+        long,
+        env = "RUNINATOR_WORKER_CLUSTER_PAUSE_CHECK_SECONDS",
+        default_value_t = 5
+    )]
+    cluster_pause_check_seconds: u64,
+
     #[arg(long, default_value_t = 30)]
     shutdown_grace_seconds: u64,
 
@@ -69,6 +110,15 @@ struct CliArgs {
     #[arg(long)]
     worker_id: Option<String>,
 
+    /// file holding the worker's generated identity so it survives restarts when `--worker-id` is
+    /// not given. defaults to `worker-identity` under the app data dir; set to empty to disable.
+    #[arg(long, env = "RUNINATOR_WORKER_IDENTITY_FILE")]
+    identity_file: Option<String>,
+
+    /// discard the persisted identity and mint a fresh one.
+    #[arg(long, default_value_t = false)]
+    reset_identity: bool,
+
     // stable address other components display for this worker; in k8s this is the headless-service
     // dns name so it survives pod ip churn.
     #[arg(long)]
@@ -83,18 +133,44 @@ struct CliArgs {
     /// actions that require a label are pinned to a worker carrying it (general pool when empty).
     #[arg(long, env = "RUNINATOR_WORKER_LABELS")]
     labels: Option<String>,
+
+    /// comma-separated broker queues to take actions from, e.g. `exports` for a worker dedicated to
+    /// heavy exports. list `default` too to keep taking unqueued work. the default queue when unset.
+    #[arg(long, env = "RUNINATOR_WORKER_QUEUES")]
+    queues: Option<String>,
+
+    /// `host:port` to serve the local status/cancel command port on, e.g. `127.0.0.1:7171`.
+    /// disabled when unset.
+    #[arg(long, env = "RUNINATOR_WORKER_COMMAND_LISTEN")]
+    command_listen: Option<String>,
+
+    /// url serving the fleet's version manifest (`min_worker_version`, `min_plugin_versions`). the
+    /// worker warns when it or one of its plugins is older than the minimum. disabled when unset.
+    #[arg(long, env = "RUNINATOR_WORKER_UPDATE_CHANNEL_URL")]
+    update_channel_url: Option<String>,
+
+    /// how often to fetch the update channel's version manifest.
+    #[arg(
+        long,
+        env = "RUNINATOR_WORKER_UPDATE_CHECK_SECONDS",
+        default_value_t = 3600
+    )]
+    update_check_seconds: u64,
 }
 
 pub fn parse_config() -> Result<Config, SendableError> {
     let args = CliArgs::parse();
-    // a non-uuid identity (e.g. a stable k8s pod name) is folded into a deterministic uuid so the
-    // same pod keeps the same replica identity across restarts; a fresh uuid is minted only when no
-    // identity is supplied.
-    let worker_id = match args.worker_id {
-        Some(ref value) if !value.is_empty() => Uuid::parse_str(value)
-            .unwrap_or_else(|_| Uuid::new_v5(&Uuid::NAMESPACE_DNS, value.as_bytes())),
-        _ => Uuid::new_v4(),
+    let identity_file = match args.identity_file.as_deref() {
+        Some(path) => Some(path.to_string()).filter(|path| !path.trim().is_empty()),
+        None => app_data::app_data_path("worker-identity")
+            .ok()
+            .map(|path| path.to_string_lossy().into_owned()),
     };
+    let worker_id = resolve_worker_id(
+        args.worker_id.as_deref(),
+        identity_file.as_deref().map(Path::new),
+        args.reset_identity,
+    );
 
     let consumer_id = args.broker_consumer_id.unwrap_or_else(|| {
         if args.broker_backend == "kafka" {
@@ -113,7 +189,16 @@ pub fn parse_config() -> Result<Config, SendableError> {
         broker_result_topic: args.broker_result_topic,
         broker_client_id: args.broker_client_id,
         broker_consumer_id: consumer_id,
+        broker_auth_secret: args
+            .broker_auth_secret
+            .filter(|value| !value.trim().is_empty()),
         max_concurrent_actions: args.max_concurrent_actions.max(1),
+        max_delivery_attempts: args.max_delivery_attempts.filter(|attempts| *attempts > 0),
+        pause_on_api_unavailable_seconds: args
+            .pause_on_api_unavailable_seconds
+            .filter(|seconds| *seconds > 0),
+        cluster_pause_check_seconds: Some(args.cluster_pause_check_seconds)
+            .filter(|seconds| *seconds > 0),
         shutdown_grace_seconds: args.shutdown_grace_seconds.max(1),
         api_base_url: args.api_base_url,
         api_key: args.api_key.filter(|value| !value.trim().is_empty()),
@@ -121,9 +206,52 @@ pub fn parse_config() -> Result<Config, SendableError> {
         advertise_host: args.advertise_host.filter(|value| !value.trim().is_empty()),
         liveness_file: args.liveness_file,
         labels: parse_labels(args.labels.as_deref()),
+        queues: parse_queues(args.queues.as_deref()),
+        command_listen: args.command_listen.filter(|value| !value.trim().is_empty()),
+        update_channel_url: args
+            .update_channel_url
+            .filter(|value| !value.trim().is_empty()),
+        update_check_seconds: args.update_check_seconds.max(1),
     })
 }
 
+/// pick the worker identity. a non-uuid identity (e.g. a stable k8s pod name) is folded into a
+/// deterministic uuid so the same pod keeps the same replica identity across restarts. without one,
+/// the uuid persisted in `identity_file` is reused (minted and written on first run, or when
+/// `reset` is set), so failure history and label pinning survive a restart. an unreadable or
+/// unwritable file degrades to a fresh per-process uuid rather than blocking startup.
+pub fn resolve_worker_id(
+    explicit: Option<&str>,
+    identity_file: Option<&Path>,
+    reset: bool,
+) -> Uuid {
+    if let Some(value) = explicit.filter(|value| !value.is_empty()) {
+        return Uuid::parse_str(value)
+            .unwrap_or_else(|_| Uuid::new_v5(&Uuid::NAMESPACE_DNS, value.as_bytes()));
+    }
+    let Some(path) = identity_file else {
+        return Uuid::new_v4();
+    };
+    if !reset && let Ok(raw) = std::fs::read_to_string(path) {
+        match Uuid::parse_str(raw.trim()) {
+            Ok(id) => return id,
+            Err(err) => {
+                warn!(path = %path.display(), "ignoring invalid worker identity file: {}", err)
+            }
+        }
+    }
+
+    let id = Uuid::new_v4();
+    let written = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|()| std::fs::write(path, format!("{id}\n")));
+    if let Err(err) = written {
+        warn!(path = %path.display(), "failed to persist worker identity: {}", err);
+    }
+    id
+}
+
 /// parse a `k=v,k=v` label string into a map; blank entries and entries without a `=` are skipped.
 /// shared with `runinator-desktop-agent` so both surfaces accept the same label syntax.
 pub fn parse_labels(raw: Option<&str>) -> BTreeMap<String, String> {
@@ -145,6 +273,16 @@ pub fn parse_labels(raw: Option<&str>) -> BTreeMap<String, String> {
     labels
 }
 
+/// parse a comma-separated queue list; blank entries are skipped.
+pub fn parse_queues(raw: Option<&str>) -> BTreeSet<String> {
+    raw.unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|queue| !queue.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
 fn plugin_search_paths(mut paths: Vec<String>) -> Vec<String> {
     paths.push(default_dll_path());
     paths.sort();
@@ -39,6 +39,11 @@ pub const REPLICA_REGISTER: ErrorDescriptor = ErrorDescriptor::new(
     "worker.replica.register",
     "Failed to register worker replica",
 );
+pub const COMMAND_LISTEN_BIND: ErrorDescriptor = ErrorDescriptor::new(
+    "RUNI208",
+    "worker.command.bind",
+    "Failed to bind worker command port",
+);
 
 // broker wiring.
 pub const BROKER_INVALID_ENDPOINT: ErrorDescriptor = ErrorDescriptor::new(
@@ -70,6 +75,25 @@ pub const BROKER_FEATURE_DISABLED: ErrorDescriptor = ErrorDescriptor::new(
     "worker.broker.feature_disabled",
     "Broker backend compiled out",
 );
+pub const DELIVERY_ATTEMPTS_EXHAUSTED: ErrorDescriptor = ErrorDescriptor::new(
+    "RUNI219",
+    "worker.broker.attempts_exhausted",
+    "Action delivery attempts exhausted",
+);
+
+// action secrets.
+pub const SECRET_NOT_ALLOWED: ErrorDescriptor = ErrorDescriptor::new(
+    "RUNI220",
+    "worker.secret.not_allowed",
+    "Secret is not allowed for this action",
+);
+
+// action pre-flight checks.
+pub const PREFLIGHT_FAILED: ErrorDescriptor = ErrorDescriptor::new(
+    "RUNI230",
+    "worker.preflight.failed",
+    "Action pre-flight check failed",
+);
 
 pub const DICTIONARY: &[ErrorDescriptor] = &[
     RUNTIME_BUILD,
@@ -79,6 +103,7 @@ pub const DICTIONARY: &[ErrorDescriptor] = &[
     CONCURRENCY_CLOSED,
     PROVIDER_NOT_FOUND,
     REPLICA_REGISTER,
+    COMMAND_LISTEN_BIND,
     BROKER_INVALID_ENDPOINT,
     BROKER_CLIENT,
     BROKER_UNKNOWN_BACKEND,
@@ -86,6 +111,9 @@ pub const DICTIONARY: &[ErrorDescriptor] = &[
     BROKER_RABBITMQ,
     BROKER_OPERATION,
     BROKER_FEATURE_DISABLED,
+    DELIVERY_ATTEMPTS_EXHAUSTED,
+    SECRET_NOT_ALLOWED,
+    PREFLIGHT_FAILED,
 ];
 
 /// worker engine error dictionary.
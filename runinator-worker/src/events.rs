@@ -3,6 +3,8 @@
 //! the standalone binary uses [`NoopEventSink`] since tracing already covers it there.
 
 use runinator_comm::ControlKind;
+use runinator_models::runs::ProviderError;
+use runinator_models::update_channel::OutdatedComponent;
 use uuid::Uuid;
 
 /// terminal outcome of one action execution.
@@ -51,7 +53,21 @@ pub enum WorkerEvent {
         outcome: ActionOutcome,
         duration_ms: i64,
         message: Option<String>,
+        /// the structured provider failure behind a `Failed` outcome, when the provider gave one.
+        error: Option<ProviderError>,
     },
+    /// the api stayed unreachable for `unreachable_ms`, so the action loop stopped polling the
+    /// broker for new work.
+    PollingPaused { unreachable_ms: i64 },
+    /// the api answered again after polling had been paused for `paused_ms`.
+    PollingResumed { paused_ms: i64 },
+    /// an operator paused execution cluster-wide, so the action loop stopped polling the broker.
+    ExecutionPaused { reason: Option<String> },
+    /// the cluster-wide execution pause was lifted.
+    ExecutionResumed,
+    /// an update-channel check found a different set of components older than the fleet minimum;
+    /// empty once the worker and its plugins meet it again.
+    VersionsOutdated { outdated: Vec<OutdatedComponent> },
     /// a control command (cancel/pause/resume) was received for a run.
     ControlReceived {
         kind: ControlKind,
@@ -2,7 +2,9 @@ use std::{collections::HashMap, fs, path::PathBuf, sync::Arc, time::Duration};
 
 use chrono::{DateTime, Utc};
 use runinator_models::providers::ActionMetadata;
-use runinator_models::runs::{ProviderExecutionRequest, RunStatus, TaskExecutionResult};
+use runinator_models::runs::{
+    ProviderError, ProviderExecutionRequest, RunStatus, TaskExecutionResult,
+};
 use runinator_models::value::Value;
 use runinator_models::workflows::WorkflowAction;
 use runinator_plugin::cancel::CancellationToken;
@@ -26,6 +28,8 @@ pub struct ExecutionTaskResult {
     pub started_at: DateTime<Utc>,
     pub finished_at: DateTime<Utc>,
     pub message: Option<String>,
+    /// the structured provider failure, set when the provider itself returned an error.
+    pub error: Option<ProviderError>,
 }
 
 impl ExecutionTaskResult {
@@ -98,14 +102,17 @@ pub async fn execute_task(
                                 started_at,
                                 finished_at,
                                 message,
+                                error: None,
                             },
                         }
                     }
                     Ok(Err(err)) => {
+                        let provider_error = ProviderError::from_error(err.as_ref());
                         error!(
                             provider = %action.provider,
                             function = %action.function,
-                            error_code = runinator_models::errors::error_code_or_unknown(err.as_ref()),
+                            error_code = %provider_error.code,
+                            retryable = provider_error.retryable,
                             "provider execution error: {}",
                             err
                         );
@@ -117,6 +124,7 @@ pub async fn execute_task(
                                 started_at,
                                 finished_at: Utc::now(),
                                 message: Some(err.to_string()),
+                                error: Some(provider_error),
                             },
                         }
                     }
@@ -130,6 +138,7 @@ pub async fn execute_task(
                                 started_at,
                                 finished_at: Utc::now(),
                                 message: Some("Task panicked during execution".into()),
+                                error: None,
                             },
                         }
                     }
@@ -150,6 +159,7 @@ pub async fn execute_task(
                             started_at,
                             finished_at: Utc::now(),
                             message: Some(format!("Task timed out after {} seconds", timeout)),
+                            error: None,
                         },
                     }
                 },
@@ -187,6 +197,7 @@ fn canceled_outcome(started_at: DateTime<Utc>) -> ExecutionOutcome {
             started_at,
             finished_at: Utc::now(),
             message: Some("Task canceled".into()),
+            error: None,
         },
     }
 }
@@ -200,6 +211,7 @@ fn failed_outcome(started_at: DateTime<Utc>, message: String) -> ExecutionOutcom
             started_at,
             finished_at: Utc::now(),
             message: Some(message),
+            error: None,
         },
     }
 }
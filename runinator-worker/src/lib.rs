@@ -2,22 +2,32 @@
 //! publishing results back through the broker. exposed as a library so the standalone binary and an
 //! embedded host (the desktop command center) can both drive the same loop.
 
+mod api_gate;
 pub mod broker;
+mod cluster_gate;
+mod commands;
 pub mod config;
 pub mod errors;
 pub mod events;
 pub mod executor;
+pub mod manifest;
 pub mod metrics;
 pub mod output_sink;
+mod preflight;
 pub mod provider_repository;
+mod result_cache;
 pub mod secrets;
+pub mod update_channel;
 pub mod worker;
 
 #[cfg(test)]
 mod tests;
 
+pub use api_gate::ApiPausePolicy;
 pub use broker::{BrokerConfig, build_broker};
+pub use cluster_gate::DEFAULT_CLUSTER_PAUSE_CHECK;
 pub use config::{Config, parse_config, parse_labels};
 pub use events::{ActionOutcome, NoopEventSink, WorkerEvent, WorkerEventSink};
 pub use provider_repository::{ProviderFactory, default_provider_factory, resolve_provider};
+pub use update_channel::{DEFAULT_UPDATE_CHECK, UpdateChannel};
 pub use worker::{WorkerRuntime, load_libraries, start_worker_loop};
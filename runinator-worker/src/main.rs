@@ -1,8 +1,8 @@
 use std::{env, ffi::OsString, sync::Arc, time::Duration};
 
 use runinator_api::{
-    AsyncApiClient, ReplicaServiceConfig, ReplicaSession, StaticLocator, register_replica_session,
-    spawn_replica_heartbeat_with_telemetry,
+    AsyncApiClient, LiveAttributes, ReplicaServiceConfig, ReplicaSession, StaticLocator,
+    register_replica_session, spawn_replica_heartbeat_with_live_attributes,
 };
 use runinator_comm::ConsumerProfile;
 use runinator_models::errors::SendableError;
@@ -13,8 +13,8 @@ use tokio::sync::Notify;
 use tracing::{error, info};
 
 use runinator_worker::{
-    Config, NoopEventSink, WorkerRuntime, build_broker, default_provider_factory, errors,
-    load_libraries, parse_config, start_worker_loop,
+    ApiPausePolicy, Config, NoopEventSink, UpdateChannel, WorkerRuntime, build_broker,
+    default_provider_factory, errors, load_libraries, metrics, parse_config, start_worker_loop,
 };
 
 #[cfg(test)]
@@ -43,12 +43,14 @@ fn main() -> Result<(), SendableError> {
 }
 
 async fn run(config: Config) -> Result<(), SendableError> {
-    // log the advertised routing labels: a label-targeted action (e.g. a `.runner("creds-sync")`
-    // node) only lands here when these satisfy its selector, so surfacing them makes "which worker
-    // did this go to" answerable from the worker's own log.
+    // log the advertised routing labels and queues: a label-targeted action (e.g. a
+    // `.runner("creds-sync")` node) only lands here when these satisfy its selector, and a queued
+    // one only when its queue is listed, so surfacing them makes "which worker did this go to"
+    // answerable from the worker's own log.
     info!(
         worker_id = %config.worker_id,
         labels = ?config.labels,
+        queues = ?config.queues,
         "worker starting"
     );
 
@@ -57,12 +59,18 @@ async fn run(config: Config) -> Result<(), SendableError> {
     let api_client = build_api_client(&config)?;
     let shutdown = Arc::new(Notify::new());
 
+    let mut plugins = libraries.keys().cloned().collect::<Vec<_>>();
+    plugins.sort();
+    let mut report = startup::collect_report("Runinator Worker", env!("CARGO_PKG_VERSION"));
+    report.plugins = plugins.clone();
+    startup::publish_report(report);
+
     spawn_liveness(&config, shutdown.clone());
     // registration is required: a worker that never registers is invisible in the replica registry
     // and cannot heartbeat, so retry with backoff and fail loudly rather than run as a phantom. stay
     // interruptible so ctrl_c during a retry window still shuts the process down cleanly.
     let replica_session = tokio::select! {
-        result = register_worker_replica_with_retry(&api_client, &config) => result?,
+        result = register_worker_replica_with_retry(&api_client, &config, &plugins) => result?,
         signal = tokio::signal::ctrl_c() => {
             signal.map_err(|err| errors::SIGNAL_CTRL_C.error(err))?;
             info!("shutdown signal received before worker registration completed, stopping worker");
@@ -70,11 +78,18 @@ async fn run(config: Config) -> Result<(), SendableError> {
         }
     };
     let telemetry = Arc::new(TelemetryCollector::new());
-    let _heartbeat = spawn_replica_heartbeat_with_telemetry(
+    // report current load on every heartbeat so the worker registry shows how busy each worker is.
+    let load: LiveAttributes = Arc::new(|| {
+        runinator_models::json!({
+            "load": { "in_flight_actions": metrics::actions_in_flight() }
+        })
+    });
+    let _heartbeat = spawn_replica_heartbeat_with_live_attributes(
         api_client.clone(),
         replica_session.clone(),
         shutdown.clone(),
         Some(telemetry.clone()),
+        Some(load),
     );
     let mut worker_task = {
         let runtime = WorkerRuntime {
@@ -83,16 +98,27 @@ async fn run(config: Config) -> Result<(), SendableError> {
             // routed to the worker holding an action's executor lease — reach this worker.
             profile: ConsumerProfile::shared(config.broker_consumer_id.clone())
                 .with_replica_id(replica_session.replica_id())
-                .with_labels(config.labels.clone()),
+                .with_labels(config.labels.clone())
+                .with_queues(config.queues.clone()),
             libraries: Arc::clone(&libraries),
             api_client: api_client.clone(),
             replica_id: Some(replica_session.replica_id()),
             providers: default_provider_factory(),
             max_concurrent_actions: config.max_concurrent_actions,
+            max_delivery_attempts: config.max_delivery_attempts,
+            api_pause: config
+                .pause_on_api_unavailable_seconds
+                .map(|seconds| ApiPausePolicy::new(Duration::from_secs(seconds))),
+            cluster_pause_check: config.cluster_pause_check_seconds.map(Duration::from_secs),
             shutdown_grace: Duration::from_secs(config.shutdown_grace_seconds),
             shutdown: shutdown.clone(),
             // tracing already reports loop activity for the standalone binary.
             events: Arc::new(NoopEventSink),
+            command_listen: config.command_listen.clone(),
+            update_channel: config.update_channel_url.clone().map(|url| UpdateChannel {
+                url,
+                interval: Duration::from_secs(config.update_check_seconds),
+            }),
         };
         tokio::spawn(start_worker_loop(runtime))
     };
@@ -201,10 +227,11 @@ fn register_backoff(attempt: u32) -> Duration {
 async fn register_worker_replica_with_retry(
     api_client: &AsyncApiClient<StaticLocator>,
     config: &Config,
+    plugins: &[String],
 ) -> Result<ReplicaSession, SendableError> {
     let mut attempt = 1;
     loop {
-        match register_worker_replica(api_client, config).await {
+        match register_worker_replica(api_client, config, plugins).await {
             Ok(session) => {
                 if attempt > 1 {
                     info!(attempt, "worker replica registered");
@@ -237,6 +264,7 @@ async fn register_worker_replica_with_retry(
 async fn register_worker_replica(
     api_client: &AsyncApiClient<StaticLocator>,
     config: &Config,
+    plugins: &[String],
 ) -> Result<ReplicaSession, runinator_api::ApiError> {
     register_replica_session(
         api_client,
@@ -253,6 +281,9 @@ async fn register_worker_replica(
                 "broker_client_id": config.broker_client_id,
                 "broker_consumer_id": config.broker_consumer_id,
                 "labels": config.labels,
+                "queues": config.queues,
+                "plugins": plugins,
+                "max_concurrent_actions": config.max_concurrent_actions,
             })),
             heartbeat_interval: Duration::from_secs(10),
         },
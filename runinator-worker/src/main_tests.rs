@@ -88,7 +88,11 @@ fn test_config() -> Config {
         broker_result_topic: "runinator.results".into(),
         broker_client_id: "test-worker".into(),
         broker_consumer_id: "test-consumer".into(),
+        broker_auth_secret: None,
         max_concurrent_actions: 1,
+        max_delivery_attempts: None,
+        pause_on_api_unavailable_seconds: None,
+        cluster_pause_check_seconds: None,
         shutdown_grace_seconds: 30,
         api_base_url: "http://127.0.0.1:8080/".into(),
         api_key: None,
@@ -96,5 +100,9 @@ fn test_config() -> Config {
         advertise_host: None,
         liveness_file: String::new(),
         labels: Default::default(),
+        queues: Default::default(),
+        command_listen: None,
+        update_channel_url: None,
+        update_check_seconds: 3600,
     }
 }
@@ -0,0 +1,138 @@
+//! per-execution environment manifest: what exactly executed a node run (provider source and
+//! version, plugin file hash, worker build and platform, relevant env var names) and how it got
+//! there (broker delivery attempt, time spent queued). recorded as a run artifact so it is stored
+//! with the node run and queryable through the existing artifact endpoints.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use runinator_broker::BrokerDelivery;
+use runinator_models::runs::ProviderExecutionEvent;
+use runinator_models::value::Value;
+use runinator_models::workflows::WorkflowAction;
+use runinator_plugin::plugin::Plugin;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::provider_repository::ProviderFactory;
+
+pub const MANIFEST_ARTIFACT_NAME: &str = "run-manifest.json";
+
+// only names are recorded, never values; these prefixes cover the runtime's own configuration.
+const RELEVANT_ENV_PREFIXES: &[&str] = &["RUNINATOR_", "OTEL_"];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RunManifest {
+    pub provider: String,
+    pub function: String,
+    /// `built_in` or `plugin`; `unresolved` when neither knows the provider.
+    pub provider_source: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider_contract: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plugin_file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plugin_sha256: Option<String>,
+    pub worker_version: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replica_id: Option<Uuid>,
+    pub os: &'static str,
+    pub arch: &'static str,
+    pub env_var_names: Vec<String>,
+    /// which broker delivery of the command this was; above 1 after nacks or expired leases.
+    pub delivery_attempt: u32,
+    /// time between the command's first publish and this execution picking it up.
+    pub queue_wait_ms: i64,
+}
+
+impl RunManifest {
+    /// record the delivery this execution came from, as of `now`.
+    pub fn record_delivery(&mut self, delivery: &BrokerDelivery, now: DateTime<Utc>) {
+        self.delivery_attempt = delivery.attempt;
+        self.queue_wait_ms = delivery.queue_wait(now).num_milliseconds();
+    }
+
+    /// the artifact event carrying this manifest in its metadata.
+    pub fn artifact_event(&self, workflow_node_run_id: Uuid) -> ProviderExecutionEvent {
+        let metadata = serde_json::to_value(self)
+            .map(Value::from)
+            .unwrap_or_default();
+        let size_bytes = serde_json::to_vec(self)
+            .map(|bytes| bytes.len() as i64)
+            .unwrap_or_default();
+        ProviderExecutionEvent::Artifact {
+            name: MANIFEST_ARTIFACT_NAME.to_string(),
+            mime_type: "application/json".to_string(),
+            size_bytes,
+            uri: format!("runinator-manifest:{workflow_node_run_id}"),
+            metadata,
+        }
+    }
+}
+
+/// capture the manifest for one action, resolving the provider in the same order as
+/// [`crate::provider_repository::resolve_provider`]. plugin hashing runs on a blocking thread.
+pub async fn capture(
+    providers: &ProviderFactory,
+    libraries: &HashMap<String, Plugin>,
+    action: &WorkflowAction,
+    replica_id: Option<Uuid>,
+) -> RunManifest {
+    let mut manifest = RunManifest {
+        provider: action.provider.clone(),
+        function: action.function.clone(),
+        provider_source: "unresolved",
+        provider_contract: None,
+        plugin_file: None,
+        plugin_sha256: None,
+        worker_version: env!("CARGO_PKG_VERSION"),
+        replica_id,
+        os: std::env::consts::OS,
+        arch: std::env::consts::ARCH,
+        env_var_names: relevant_env_var_names(std::env::vars().map(|(name, _)| name)),
+        delivery_attempt: 1,
+        queue_wait_ms: 0,
+    };
+
+    if let Some(provider) = providers()
+        .into_iter()
+        .find(|p| p.name() == action.provider)
+    {
+        manifest.provider_source = "built_in";
+        manifest.provider_contract = provider.metadata().metadata.contract;
+        return manifest;
+    }
+
+    let Some(plugin) = libraries.get(&action.provider) else {
+        return manifest;
+    };
+    manifest.provider_source = "plugin";
+    manifest.plugin_file = Some(plugin.file_name.to_string_lossy().into_owned());
+    let path = plugin.file_name.clone();
+    manifest.plugin_sha256 = tokio::task::spawn_blocking(move || file_sha256(&path))
+        .await
+        .ok()
+        .flatten();
+    manifest
+}
+
+/// sorted, de-duplicated names of the env vars that shape runtime behavior.
+pub fn relevant_env_var_names(names: impl Iterator<Item = String>) -> Vec<String> {
+    let mut names = names
+        .filter(|name| {
+            RELEVANT_ENV_PREFIXES
+                .iter()
+                .any(|prefix| name.starts_with(prefix))
+        })
+        .collect::<Vec<_>>();
+    names.sort();
+    names.dedup();
+    names
+}
+
+fn file_sha256(path: &Path) -> Option<String> {
+    let bytes = std::fs::read(path).ok()?;
+    Some(format!("{:x}", Sha256::digest(&bytes)))
+}
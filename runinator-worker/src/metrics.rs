@@ -2,6 +2,7 @@
 // otlp when otel is configured and are cheap no-ops otherwise. names are stable public contracts.
 
 use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU64, Ordering};
 
 use opentelemetry::KeyValue;
 use opentelemetry::metrics::{Counter, Histogram, UpDownCounter};
@@ -13,13 +14,18 @@ struct WorkerMetrics {
     actions_completed: Counter<u64>,
     actions_duplicate: Counter<u64>,
     action_duration_ms: Histogram<f64>,
+    queue_wait_ms: Histogram<f64>,
     actions_in_flight: UpDownCounter<i64>,
+    polling_paused: UpDownCounter<i64>,
     control_commands: Counter<u64>,
     secret_resolution_failures: Counter<u64>,
 }
 
 static METRICS: OnceLock<WorkerMetrics> = OnceLock::new();
 
+// mirrors the in-flight gauge so the replica heartbeat can report load; otel counters are write-only.
+static ACTIONS_IN_FLIGHT: AtomicU64 = AtomicU64::new(0);
+
 fn metrics() -> &'static WorkerMetrics {
     METRICS.get_or_init(|| {
         let meter = opentelemetry::global::meter(METER_NAME);
@@ -37,9 +43,16 @@ fn metrics() -> &'static WorkerMetrics {
                 .f64_histogram("runinator_worker_action_duration_ms")
                 .with_unit("ms")
                 .build(),
+            queue_wait_ms: meter
+                .f64_histogram("runinator_worker_queue_wait_ms")
+                .with_unit("ms")
+                .build(),
             actions_in_flight: meter
                 .i64_up_down_counter("runinator_worker_actions_in_flight")
                 .build(),
+            polling_paused: meter
+                .i64_up_down_counter("runinator_worker_polling_paused")
+                .build(),
             control_commands: meter
                 .u64_counter("runinator_worker_control_commands_total")
                 .build(),
@@ -60,14 +73,27 @@ pub(crate) fn action_duplicate() {
     metrics().actions_duplicate.add(1, &[]);
 }
 
-/// an action finished executing. `outcome` is one of succeeded/failed/timed_out/canceled; the same
-/// label is applied to the duration histogram so latency can be split by result.
+/// an action finished executing. `outcome` is one of succeeded/failed/timed_out/canceled, or cached
+/// for a delivery settled from the result cache; the same label is applied to the duration
+/// histogram so latency can be split by result.
 pub(crate) fn action_completed(outcome: &'static str, duration_ms: f64) {
     let attrs = [KeyValue::new("outcome", outcome)];
     metrics().actions_completed.add(1, &attrs);
     metrics().action_duration_ms.record(duration_ms, &attrs);
 }
 
+/// an action started executing after waiting `wait_ms` in the broker since it was first enqueued.
+pub(crate) fn queue_wait(wait_ms: i64) {
+    metrics().queue_wait_ms.record(wait_ms as f64, &[]);
+}
+
+/// action polling was paused (1) or resumed (0) because of api availability.
+pub(crate) fn polling_paused(paused: bool) {
+    metrics()
+        .polling_paused
+        .add(if paused { 1 } else { -1 }, &[]);
+}
+
 /// resolving `secret://` references for an action failed, so it was reported failed without running.
 pub(crate) fn secret_resolution_failure() {
     metrics().secret_resolution_failures.add(1, &[]);
@@ -84,13 +110,20 @@ pub(crate) fn control_command(kind: &'static str) {
 /// exit path (including error returns) is accounted for.
 pub(crate) fn in_flight_guard() -> InFlightGuard {
     metrics().actions_in_flight.add(1, &[]);
+    ACTIONS_IN_FLIGHT.fetch_add(1, Ordering::Relaxed);
     InFlightGuard
 }
 
+/// actions executing in this process right now.
+pub fn actions_in_flight() -> u64 {
+    ACTIONS_IN_FLIGHT.load(Ordering::Relaxed)
+}
+
 pub(crate) struct InFlightGuard;
 
 impl Drop for InFlightGuard {
     fn drop(&mut self) {
         metrics().actions_in_flight.add(-1, &[]);
+        ACTIONS_IN_FLIGHT.fetch_sub(1, Ordering::Relaxed);
     }
 }
@@ -104,6 +104,12 @@ impl RunOutputSink {
         .await
     }
 
+    /// publish the `running` status, carrying how long the command waited in the broker.
+    pub async fn publish_running(&self, queue_wait_ms: i64) -> Result<(), BrokerError> {
+        self.publish_event(WorkflowResultEvent::running(&self.command, queue_wait_ms))
+            .await
+    }
+
     fn emit_chunk(&self, stream: String, content: String) {
         let event = WorkflowResultEvent::chunk(&self.command, NewRunChunk { stream, content });
         let broker = self.broker.clone();
@@ -0,0 +1,87 @@
+//! runs an action's declared pre-flight checks before the provider is invoked, turning a full disk,
+//! an unwritable path, an unreachable target, or a provider too old for the action into one clear
+//! failure instead of a half-finished export.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use runinator_models::errors::SendableError;
+use runinator_models::preflight::PreflightCheck;
+use runinator_models::semver::SemVer;
+use uuid::Uuid;
+
+const BYTES_PER_MB: u64 = 1024 * 1024;
+
+/// run `checks` in order, stopping at the first failure. `provider_version` is the version the
+/// action's provider reports, `None` when it reports none.
+pub(crate) async fn run_preflight_checks(
+    checks: &[PreflightCheck],
+    provider_version: Option<SemVer>,
+) -> Result<(), SendableError> {
+    for check in checks {
+        if let Err(reason) = run_check(check, provider_version).await {
+            return Err(crate::errors::PREFLIGHT_FAILED.error(format!("{check} failed: {reason}")));
+        }
+    }
+    Ok(())
+}
+
+async fn run_check(check: &PreflightCheck, provider_version: Option<SemVer>) -> Result<(), String> {
+    match check {
+        PreflightCheck::FreeDisk { path, min_free_mb } => {
+            let path = PathBuf::from(path);
+            let min_free_mb = *min_free_mb;
+            tokio::task::spawn_blocking(move || check_free_disk(&path, min_free_mb))
+                .await
+                .map_err(|err| err.to_string())?
+        }
+        PreflightCheck::Writable { path } => {
+            let path = PathBuf::from(path);
+            tokio::task::spawn_blocking(move || check_writable(&path))
+                .await
+                .map_err(|err| err.to_string())?
+        }
+        PreflightCheck::Reachable {
+            host,
+            port,
+            timeout_ms,
+        } => {
+            let connect = tokio::net::TcpStream::connect((host.as_str(), *port));
+            match tokio::time::timeout(Duration::from_millis(*timeout_ms), connect).await {
+                Ok(Ok(_)) => Ok(()),
+                Ok(Err(err)) => Err(err.to_string()),
+                Err(_) => Err(format!("no connection within {timeout_ms} ms")),
+            }
+        }
+        PreflightCheck::ProviderVersion { min } => {
+            let min = min.parse::<SemVer>()?;
+            match provider_version {
+                Some(version) if version >= min => Ok(()),
+                Some(version) => Err(format!("the provider is at {version}")),
+                None => Err("the provider reports no version".into()),
+            }
+        }
+    }
+}
+
+pub(crate) fn check_free_disk(path: &Path, min_free_mb: u64) -> Result<(), String> {
+    let available = runinator_utilities::dirutils::available_space(path)
+        .ok_or_else(|| "cannot determine free space (does the path exist?)".to_string())?;
+    let available_mb = available / BYTES_PER_MB;
+    if available_mb < min_free_mb {
+        return Err(format!("only {available_mb} MB free"));
+    }
+    Ok(())
+}
+
+/// create and remove a probe file, which also catches read-only mounts and full quotas that
+/// permission bits alone would not reveal.
+pub(crate) fn check_writable(path: &Path) -> Result<(), String> {
+    if !path.is_dir() {
+        return Err("not an existing directory".into());
+    }
+    let probe = path.join(format!(".runinator-preflight-{}", Uuid::new_v4()));
+    std::fs::write(&probe, b"").map_err(|err| err.to_string())?;
+    let _ = std::fs::remove_file(&probe);
+    Ok(())
+}
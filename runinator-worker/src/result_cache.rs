@@ -0,0 +1,92 @@
+//! reuse recent results of idempotent, read-only actions. an action with `cache_ttl_seconds` set
+//! that succeeded here is keyed by a hash of its provider, function, configuration and parameters;
+//! an identical command arriving within the window is settled with the stored output instead of
+//! running the provider again, so a burst of run-now requests costs one execution. the cache is
+//! local to the worker process and forgotten on restart.
+
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use runinator_comm::ActionCommand;
+use runinator_models::value::Value;
+use sha2::{Digest, Sha256};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// a successful result kept for reuse.
+#[derive(Debug, Clone)]
+pub(crate) struct CachedResult {
+    pub(crate) output_json: Value,
+    pub(crate) message: Option<String>,
+    /// the node run whose execution produced the result.
+    pub(crate) node_run_id: Uuid,
+    pub(crate) stored_at: Instant,
+}
+
+impl CachedResult {
+    /// the status message a replay settles with: where the result came from, then the message the
+    /// provider originally returned, if any.
+    pub(crate) fn replay_message(&self) -> String {
+        let source = format!(
+            "served from cache: result of node run {} from {}s ago",
+            self.node_run_id,
+            self.stored_at.elapsed().as_secs()
+        );
+        match &self.message {
+            Some(original) => format!("{source}: {original}"),
+            None => source,
+        }
+    }
+}
+
+// entries carry their own expiry, since actions sharing the cache opt into different windows.
+#[derive(Default)]
+pub(crate) struct ResultCache(Mutex<HashMap<String, (Instant, CachedResult)>>);
+
+impl ResultCache {
+    /// the stored result for `key` if its window has not passed at `now`.
+    pub(crate) async fn fresh(&self, key: &str, now: Instant) -> Option<CachedResult> {
+        self.0
+            .lock()
+            .await
+            .get(key)
+            .filter(|(expires_at, _)| now < *expires_at)
+            .map(|(_, entry)| entry.clone())
+    }
+
+    /// keep `result` under `key` for `ttl`, dropping expired entries so the map stays bounded by
+    /// recent distinct commands.
+    pub(crate) async fn insert(&self, key: String, result: CachedResult, ttl: Duration) {
+        let mut guard = self.0.lock().await;
+        guard.retain(|_, (expires_at, _)| result.stored_at < *expires_at);
+        guard.insert(key, (result.stored_at + ttl, result));
+    }
+}
+
+/// the cache window for a command, when its action opted in.
+pub(crate) fn cache_ttl(command: &ActionCommand) -> Option<Duration> {
+    command
+        .action
+        .cache_ttl_seconds
+        .filter(|ttl| *ttl > 0)
+        .map(|ttl| Duration::from_secs(ttl as u64))
+}
+
+/// identifies a command by what it would execute. parameters are hashed before secret resolution,
+/// so a secret reference is keyed by name and its value never enters the cache key.
+pub(crate) fn cache_key(command: &ActionCommand) -> String {
+    let mut hasher = Sha256::new();
+    for part in [
+        command.action.provider.as_bytes(),
+        command.action.function.as_bytes(),
+    ] {
+        hasher.update(part);
+        hasher.update([0]);
+    }
+    hasher.update(serde_json::to_vec(&command.action.configuration).unwrap_or_default());
+    hasher.update([0]);
+    hasher.update(serde_json::to_vec(&command.parameters).unwrap_or_default());
+    format!("{:x}", hasher.finalize())
+}
@@ -2,6 +2,9 @@ use std::collections::{BTreeSet, HashMap};
 
 use runinator_api::{ApiError, AsyncApiClient, StaticLocator};
 use runinator_models::errors::SendableError;
+use runinator_models::secret_refs::{
+    SecretRef, collect_secret_refs, parse_secret_ref, secret_allowed,
+};
 use runinator_models::value::Value;
 
 /// whether a secret-resolution failure is transient (web service unreachable, 5xx, discovery) and
@@ -15,8 +18,11 @@ pub(crate) fn is_transient_secret_error(err: &SendableError) -> bool {
     }
 }
 
+/// resolve every `secret://` reference in the parameters, refusing any the action's
+/// `allowed_secrets` does not cover before fetching anything.
 pub(crate) async fn resolve_secret_refs(
     api_client: &AsyncApiClient<StaticLocator>,
+    allowed_secrets: &[String],
     parameters: Value,
 ) -> Result<Value, SendableError> {
     let mut refs = BTreeSet::new();
@@ -24,6 +30,7 @@ pub(crate) async fn resolve_secret_refs(
     if refs.is_empty() {
         return Ok(parameters);
     }
+    check_allowed_secrets(allowed_secrets, &refs)?;
 
     tracing::debug!(count = refs.len(), "resolving action secret reference(s)");
     let mut secrets = HashMap::new();
@@ -47,24 +54,18 @@ pub(crate) async fn resolve_secret_refs(
     Ok(replace_secret_refs(parameters, &secrets))
 }
 
-fn collect_secret_refs(value: &Value, refs: &mut BTreeSet<SecretRef>) {
-    match value {
-        Value::String(raw) => {
-            if let Some(secret_ref) = parse_secret_ref(raw) {
-                refs.insert(secret_ref);
-            }
-        }
-        Value::Array(values) => {
-            for value in values {
-                collect_secret_refs(value, refs);
-            }
-        }
-        Value::Object(object) => {
-            for value in object.values() {
-                collect_secret_refs(value, refs);
-            }
-        }
-        _ => {}
+pub(crate) fn check_allowed_secrets(
+    allowed_secrets: &[String],
+    refs: &BTreeSet<SecretRef>,
+) -> Result<(), SendableError> {
+    match refs
+        .iter()
+        .find(|secret_ref| !secret_allowed(allowed_secrets, secret_ref))
+    {
+        Some(secret_ref) => Err(crate::errors::SECRET_NOT_ALLOWED.error(format!(
+            "secret '{secret_ref}' is not covered by the action's allowed_secrets"
+        ))),
+        None => Ok(()),
     }
 }
 
@@ -89,48 +90,3 @@ fn replace_secret_refs(value: Value, secrets: &HashMap<SecretRef, String>) -> Va
         other => other,
     }
 }
-
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-struct SecretRef {
-    scope: String,
-    name: String,
-}
-
-fn parse_secret_ref(raw: &str) -> Option<SecretRef> {
-    let path = raw.strip_prefix("secret://")?;
-    let (scope, name) = path.split_once('/')?;
-    if scope.is_empty() || name.is_empty() {
-        return None;
-    }
-    Some(SecretRef {
-        scope: percent_decode(scope)?,
-        name: percent_decode(name)?,
-    })
-}
-
-fn percent_decode(raw: &str) -> Option<String> {
-    let bytes = raw.as_bytes();
-    let mut decoded = Vec::with_capacity(bytes.len());
-    let mut index = 0;
-    while index < bytes.len() {
-        if bytes[index] == b'%' {
-            let hi = hex_value(*bytes.get(index + 1)?)?;
-            let lo = hex_value(*bytes.get(index + 2)?)?;
-            decoded.push((hi << 4) | lo);
-            index += 3;
-            continue;
-        }
-        decoded.push(bytes[index]);
-        index += 1;
-    }
-    String::from_utf8(decoded).ok()
-}
-
-fn hex_value(byte: u8) -> Option<u8> {
-    match byte {
-        b'0'..=b'9' => Some(byte - b'0'),
-        b'a'..=b'f' => Some(byte - b'a' + 10),
-        b'A'..=b'F' => Some(byte - b'A' + 10),
-        _ => None,
-    }
-}
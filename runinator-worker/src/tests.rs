@@ -159,6 +159,11 @@ async fn worker_accepts_std_exec_program_with_context() {
         mcp_enabled: false,
         tags: Vec::new(),
         required_labels: Default::default(),
+        allowed_secrets: Vec::new(),
+        preflight: Vec::new(),
+        cache_ttl_seconds: None,
+        priority: 0,
+        queue: None,
     };
     let parameters = json!({
         "program": [ { "$return": { "ok": true } } ],
@@ -195,6 +200,11 @@ async fn worker_rejects_undeclared_std_exec_parameter() {
         mcp_enabled: false,
         tags: Vec::new(),
         required_labels: Default::default(),
+        allowed_secrets: Vec::new(),
+        preflight: Vec::new(),
+        cache_ttl_seconds: None,
+        priority: 0,
+        queue: None,
     };
     let parameters = json!({
         "program": [ { "$return": true } ],
@@ -239,6 +249,11 @@ fn worker_validates_provider_output_fields_when_present() {
         mcp_enabled: false,
         tags: Vec::new(),
         required_labels: Default::default(),
+        allowed_secrets: Vec::new(),
+        preflight: Vec::new(),
+        cache_ttl_seconds: None,
+        priority: 0,
+        queue: None,
     };
     let result = TaskExecutionResult {
         message: None,
@@ -320,9 +335,14 @@ fn blocking_worker_runtime_with_concurrency(
             }) as runinator_provider_catalog::StaticProvider]
         }),
         max_concurrent_actions,
+        max_delivery_attempts: None,
+        api_pause: None,
+        cluster_pause_check: None,
         shutdown_grace: std::time::Duration::from_secs(5),
         shutdown,
         events: std::sync::Arc::new(crate::events::NoopEventSink),
+        command_listen: None,
+        update_channel: None,
     }
 }
 
@@ -345,6 +365,8 @@ async fn shutdown_preempted_action_is_requeued_not_canceled() {
             command: command.clone(),
             dedupe_key: None,
             enqueued_at: chrono::Utc::now(),
+            priority: 0,
+            queue: None,
         })
         .await
         .unwrap();
@@ -361,8 +383,13 @@ async fn shutdown_preempted_action_is_requeued_not_canceled() {
     // the run was not canceled: no terminal status may be published, only the initial `Running`.
     let running = broker.receive_result("test-ws").await.unwrap();
     match running.event.kind {
-        WorkflowResultEventKind::Status { status, .. } => {
-            assert_eq!(status, WorkflowStatus::Running)
+        WorkflowResultEventKind::Status {
+            status,
+            queue_wait_ms,
+            ..
+        } => {
+            assert_eq!(status, WorkflowStatus::Running);
+            assert!(queue_wait_ms.is_some_and(|wait| wait >= 0));
         }
         other => panic!("expected running status, got {other:?}"),
     }
@@ -388,6 +415,229 @@ async fn shutdown_preempted_action_is_requeued_not_canceled() {
         redelivered.command.workflow_node_run_id,
         command.workflow_node_run_id
     );
+    assert_eq!(redelivered.attempt, 2);
+    assert_eq!(
+        redelivered.last_error.as_deref(),
+        Some("action preempted by worker shutdown")
+    );
+}
+
+#[tokio::test]
+async fn delivery_past_max_attempts_fails_without_running_the_provider() {
+    let broker = std::sync::Arc::new(InMemoryBroker::new());
+    broker
+        .publish(runinator_broker::BrokerMessage {
+            command: action_command(),
+            dedupe_key: None,
+            enqueued_at: chrono::Utc::now(),
+            priority: 0,
+            queue: None,
+        })
+        .await
+        .unwrap();
+    // two earlier deliveries came back, so the worker sees the third.
+    for reason in ["first failure", "second failure"] {
+        let delivery = broker.receive("earlier-worker").await.unwrap();
+        broker
+            .nack_with_error("earlier-worker", delivery.delivery_id, Some(reason.into()))
+            .await
+            .unwrap();
+    }
+
+    let started = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+    let shutdown = std::sync::Arc::new(tokio::sync::Notify::new());
+    let mut runtime = blocking_worker_runtime(broker.clone(), started.clone(), shutdown.clone());
+    runtime.max_delivery_attempts = Some(2);
+    let worker = tokio::spawn(crate::worker::start_worker_loop(runtime));
+
+    let mut statuses = Vec::new();
+    while statuses.len() < 2 {
+        let delivery = tokio::time::timeout(
+            std::time::Duration::from_secs(5),
+            broker.receive_result("test-ws"),
+        )
+        .await
+        .expect("worker should publish statuses")
+        .unwrap();
+        if let WorkflowResultEventKind::Status {
+            status, message, ..
+        } = delivery.event.kind
+        {
+            statuses.push((status, message));
+        }
+    }
+    shutdown.notify_waiters();
+    worker.await.unwrap().unwrap();
+
+    assert_eq!(statuses[0].0, WorkflowStatus::Running);
+    assert_eq!(statuses[1].0, WorkflowStatus::Failed);
+    let message = statuses[1].1.clone().unwrap_or_default();
+    assert!(message.contains("RUNI219"), "{message}");
+    assert!(message.contains("second failure"), "{message}");
+    assert!(!started.load(std::sync::atomic::Ordering::SeqCst));
+}
+
+// a single failed probe never pauses polling; an outage lasting the configured window does, and the
+// first successful probe resumes it.
+#[test]
+fn api_availability_pauses_only_after_a_sustained_outage() {
+    use crate::api_gate::{ApiAvailability, ApiTransition};
+
+    let window = std::time::Duration::from_secs(30);
+    let start = std::time::Instant::now();
+    let at = |secs| start + std::time::Duration::from_secs(secs);
+    let mut availability = ApiAvailability::default();
+    assert_eq!(availability.observe(false, at(0), window), None);
+    assert_eq!(availability.observe(true, at(10), window), None);
+    assert_eq!(availability.observe(false, at(20), window), None);
+    assert_eq!(availability.observe(false, at(40), window), None);
+    assert_eq!(
+        availability.observe(false, at(50), window),
+        Some(ApiTransition::Paused {
+            unreachable_for: std::time::Duration::from_secs(30)
+        })
+    );
+    assert_eq!(availability.observe(false, at(60), window), None);
+    assert_eq!(
+        availability.observe(true, at(65), window),
+        Some(ApiTransition::Resumed {
+            paused_for: std::time::Duration::from_secs(15)
+        })
+    );
+}
+
+// once the api probe pauses polling, a newly published action stays on the broker.
+#[tokio::test]
+async fn paused_worker_leaves_actions_on_the_broker_while_the_api_is_down() {
+    let broker = std::sync::Arc::new(InMemoryBroker::new());
+    let started = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+    let shutdown = std::sync::Arc::new(tokio::sync::Notify::new());
+    let (events_tx, mut events_rx) = tokio::sync::mpsc::unbounded_channel();
+    let mut runtime = blocking_worker_runtime(broker.clone(), started.clone(), shutdown.clone());
+    runtime.api_pause = Some(crate::ApiPausePolicy {
+        unavailable_after: std::time::Duration::ZERO,
+        probe_interval: std::time::Duration::from_millis(20),
+    });
+    runtime.events = std::sync::Arc::new(move |event: crate::WorkerEvent| {
+        let _ = events_tx.send(event);
+    });
+    let worker = tokio::spawn(crate::worker::start_worker_loop(runtime));
+
+    let paused = tokio::time::timeout(std::time::Duration::from_secs(5), async {
+        while let Some(event) = events_rx.recv().await {
+            if matches!(event, crate::WorkerEvent::PollingPaused { .. }) {
+                return true;
+            }
+        }
+        false
+    })
+    .await
+    .expect("the probe should pause polling");
+    assert!(paused);
+
+    broker
+        .publish(runinator_broker::BrokerMessage {
+            command: action_command(),
+            dedupe_key: None,
+            enqueued_at: chrono::Utc::now(),
+            priority: 0,
+            queue: None,
+        })
+        .await
+        .unwrap();
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    shutdown.notify_waiters();
+    worker.await.unwrap().unwrap();
+
+    assert!(!started.load(std::sync::atomic::Ordering::SeqCst));
+    let delivery = tokio::time::timeout(
+        std::time::Duration::from_secs(1),
+        broker.receive("another-worker"),
+    )
+    .await
+    .expect("the action should still be queued")
+    .unwrap();
+    assert_eq!(delivery.command.node_id, action_command().node_id);
+}
+
+// answer every request with `body` as json; enough of a web service for the worker's api client.
+async fn serve_json(body: &'static str) -> String {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                let mut request = [0u8; 4096];
+                let _ = stream.read(&mut request).await;
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            });
+        }
+    });
+    format!("http://{addr}/")
+}
+
+// an operator's cluster-wide execution pause stops the worker taking actions off the broker.
+#[tokio::test]
+async fn cluster_execution_pause_leaves_actions_on_the_broker() {
+    let base_url = serve_json(r#"{"dispatch":false,"execution":true,"reason":"incident"}"#).await;
+    let broker = std::sync::Arc::new(InMemoryBroker::new());
+    let started = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+    let shutdown = std::sync::Arc::new(tokio::sync::Notify::new());
+    let (events_tx, mut events_rx) = tokio::sync::mpsc::unbounded_channel();
+    let mut runtime = blocking_worker_runtime(broker.clone(), started.clone(), shutdown.clone());
+    runtime.api_client = runinator_api::AsyncApiClient::with_credentials(
+        runinator_api::StaticLocator::new(base_url),
+        None,
+    )
+    .unwrap();
+    runtime.cluster_pause_check = Some(std::time::Duration::from_millis(20));
+    runtime.events = std::sync::Arc::new(move |event: crate::WorkerEvent| {
+        let _ = events_tx.send(event);
+    });
+    let worker = tokio::spawn(crate::worker::start_worker_loop(runtime));
+
+    let reason = tokio::time::timeout(std::time::Duration::from_secs(5), async {
+        while let Some(event) = events_rx.recv().await {
+            if let crate::WorkerEvent::ExecutionPaused { reason } = event {
+                return reason;
+            }
+        }
+        None
+    })
+    .await
+    .expect("the cluster watch should pause polling");
+    assert_eq!(reason.as_deref(), Some("incident"));
+
+    broker
+        .publish(runinator_broker::BrokerMessage {
+            command: action_command(),
+            dedupe_key: None,
+            enqueued_at: chrono::Utc::now(),
+            priority: 0,
+            queue: None,
+        })
+        .await
+        .unwrap();
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    shutdown.notify_waiters();
+    worker.await.unwrap().unwrap();
+
+    assert!(!started.load(std::sync::atomic::Ordering::SeqCst));
+    let delivery = tokio::time::timeout(
+        std::time::Duration::from_secs(1),
+        broker.receive("another-worker"),
+    )
+    .await
+    .expect("the action should still be queued")
+    .unwrap();
+    assert_eq!(delivery.command.node_id, action_command().node_id);
 }
 
 #[tokio::test]
@@ -399,6 +649,8 @@ async fn control_canceled_action_still_publishes_canceled_status() {
             command: command.clone(),
             dedupe_key: None,
             enqueued_at: chrono::Utc::now(),
+            priority: 0,
+            queue: None,
         })
         .await
         .unwrap();
@@ -451,6 +703,117 @@ async fn control_canceled_action_still_publishes_canceled_status() {
     );
 }
 
+#[tokio::test]
+async fn command_port_reports_status_and_cancels_by_command_id() {
+    let broker = std::sync::Arc::new(InMemoryBroker::new());
+    let command = action_command();
+    broker
+        .publish(runinator_broker::BrokerMessage {
+            command: command.clone(),
+            dedupe_key: None,
+            enqueued_at: chrono::Utc::now(),
+            priority: 0,
+            queue: None,
+        })
+        .await
+        .unwrap();
+
+    // reserve a free port, then hand it to the worker.
+    let addr = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .to_string();
+    let started = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+    let shutdown = std::sync::Arc::new(tokio::sync::Notify::new());
+    let mut runtime = blocking_worker_runtime(broker.clone(), started.clone(), shutdown.clone());
+    runtime.command_listen = Some(addr.clone());
+    let worker = tokio::spawn(crate::worker::start_worker_loop(runtime));
+    wait_until_started(&started).await;
+
+    let timeout = std::time::Duration::from_secs(5);
+    let status = runinator_comm::send_worker_command(
+        &addr,
+        &runinator_comm::WorkerCommandRequest::Status,
+        timeout,
+    )
+    .await
+    .unwrap();
+    let runinator_comm::WorkerCommandResponse::Status(report) = status else {
+        panic!("expected a status report, got {status:?}");
+    };
+    assert_eq!(report.running.len(), 1);
+    assert_eq!(report.running[0].command_id, command.command_id);
+    assert_eq!(report.running[0].provider, command.action.provider);
+
+    let info = runinator_comm::send_worker_command(
+        &addr,
+        &runinator_comm::WorkerCommandRequest::Info,
+        timeout,
+    )
+    .await
+    .unwrap();
+    let runinator_comm::WorkerCommandResponse::Info(info) = info else {
+        panic!("expected a startup report, got {info:?}");
+    };
+    assert_eq!(info.pid, std::process::id());
+
+    // an unknown command id cancels nothing.
+    let missed = runinator_comm::send_worker_command(
+        &addr,
+        &runinator_comm::WorkerCommandRequest::Cancel {
+            command_id: Uuid::now_v7(),
+        },
+        timeout,
+    )
+    .await
+    .unwrap();
+    assert!(matches!(
+        missed,
+        runinator_comm::WorkerCommandResponse::Cancel(runinator_comm::WorkerCancelReport {
+            canceled: false,
+            ..
+        })
+    ));
+
+    let canceled = runinator_comm::send_worker_command(
+        &addr,
+        &runinator_comm::WorkerCommandRequest::Cancel {
+            command_id: command.command_id,
+        },
+        timeout,
+    )
+    .await
+    .unwrap();
+    assert!(matches!(
+        canceled,
+        runinator_comm::WorkerCommandResponse::Cancel(runinator_comm::WorkerCancelReport {
+            canceled: true,
+            ..
+        })
+    ));
+
+    // an operator cancel settles the node as canceled, like a control-channel cancel.
+    let canceled_seen = tokio::time::timeout(timeout, async {
+        loop {
+            let delivery = broker.receive_result("test-ws").await.unwrap();
+            if let WorkflowResultEventKind::Status { status, .. } = delivery.event.kind
+                && status == WorkflowStatus::Canceled
+            {
+                return;
+            }
+        }
+    })
+    .await;
+    assert!(
+        canceled_seen.is_ok(),
+        "a command-port cancel must publish a canceled status"
+    );
+
+    shutdown.notify_waiters();
+    worker.await.unwrap().unwrap();
+}
+
 #[tokio::test]
 async fn duplicate_delivery_of_in_flight_node_run_is_acked_without_executing() {
     let broker = std::sync::Arc::new(InMemoryBroker::new());
@@ -465,6 +828,8 @@ async fn duplicate_delivery_of_in_flight_node_run_is_acked_without_executing() {
                 command: duplicate,
                 dedupe_key: None,
                 enqueued_at: chrono::Utc::now(),
+                priority: 0,
+                queue: None,
             })
             .await
             .unwrap();
@@ -543,6 +908,59 @@ async fn own_stale_leases_match_only_at_or_past_the_recorded_attempt() {
     assert!(!leases.matches(node_run_id, 2).await);
 }
 
+#[tokio::test]
+async fn result_cache_serves_identical_commands_only_within_the_window() {
+    use crate::result_cache::{CachedResult, ResultCache, cache_key, cache_ttl};
+    use std::time::{Duration, Instant};
+
+    let mut command = action_command();
+    assert!(cache_ttl(&command).is_none());
+    command.action.cache_ttl_seconds = Some(600);
+    let ttl = cache_ttl(&command).unwrap();
+    assert_eq!(ttl, Duration::from_secs(600));
+
+    // a fresh node run of the same command shares the key; different parameters do not.
+    let mut rerun = command.clone();
+    rerun.workflow_node_run_id = Uuid::new_v4();
+    assert_eq!(cache_key(&command), cache_key(&rerun));
+    let mut other = command.clone();
+    other.parameters = json!({ "table": "orders" });
+    assert_ne!(cache_key(&command), cache_key(&other));
+
+    let cache = ResultCache::default();
+    let stored_at = Instant::now();
+    cache
+        .insert(
+            cache_key(&command),
+            CachedResult {
+                output_json: json!({ "rows": 3 }),
+                message: Some("dumped 3 rows".into()),
+                node_run_id: command.workflow_node_run_id,
+                stored_at,
+            },
+            ttl,
+        )
+        .await;
+
+    let hit = cache
+        .fresh(&cache_key(&rerun), stored_at + Duration::from_secs(599))
+        .await
+        .expect("identical command within the window is served");
+    assert_eq!(hit.node_run_id, command.workflow_node_run_id);
+    assert!(hit.replay_message().starts_with(&format!(
+        "served from cache: result of node run {}",
+        command.workflow_node_run_id
+    )));
+    assert!(hit.replay_message().ends_with(": dumped 3 rows"));
+    assert!(cache.fresh(&cache_key(&other), stored_at).await.is_none());
+    assert!(
+        cache
+            .fresh(&cache_key(&rerun), stored_at + ttl)
+            .await
+            .is_none()
+    );
+}
+
 #[test]
 fn secret_resolution_errors_classify_transient_vs_definitive() {
     use crate::secrets::is_transient_secret_error;
@@ -571,6 +989,179 @@ fn secret_resolution_errors_classify_transient_vs_definitive() {
     assert!(!is_transient_secret_error(&unrelated));
 }
 
+#[test]
+fn secrets_outside_allowed_patterns_are_refused_before_fetching() {
+    use crate::secrets::{check_allowed_secrets, is_transient_secret_error};
+    use runinator_models::secret_refs::collect_secret_refs;
+
+    let mut refs = std::collections::BTreeSet::new();
+    collect_secret_refs(
+        &runinator_models::json!({
+            "db": "secret://warehouse/reporting-ro",
+            "nested": ["secret://aws/prod-root"]
+        }),
+        &mut refs,
+    );
+
+    assert!(check_allowed_secrets(&[], &refs).is_ok());
+    assert!(check_allowed_secrets(&["*/*".into()], &refs).is_ok());
+    let err = check_allowed_secrets(&["warehouse/reporting-*".into()], &refs).unwrap_err();
+    assert!(err.to_string().contains("aws/prod-root"));
+    // a policy refusal is definitive, so the node fails rather than being redelivered.
+    assert!(!is_transient_secret_error(&err));
+}
+
+#[tokio::test]
+async fn preflight_checks_fail_fast_with_the_failing_check() {
+    use crate::preflight::run_preflight_checks;
+    use runinator_models::preflight::PreflightCheck;
+
+    let dir = std::env::temp_dir();
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let passing = vec![
+        PreflightCheck::Writable {
+            path: dir.to_string_lossy().into_owned(),
+        },
+        PreflightCheck::FreeDisk {
+            path: dir.to_string_lossy().into_owned(),
+            min_free_mb: 1,
+        },
+        PreflightCheck::Reachable {
+            host: "127.0.0.1".into(),
+            port,
+            timeout_ms: 2_000,
+        },
+    ];
+    run_preflight_checks(&passing, None).await.unwrap();
+
+    let missing = dir.join(format!("runinator-preflight-missing-{}", Uuid::new_v4()));
+    let err = run_preflight_checks(
+        &[PreflightCheck::Writable {
+            path: missing.to_string_lossy().into_owned(),
+        }],
+        None,
+    )
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("RUNI230"));
+    assert!(err.to_string().contains("writable check"));
+
+    let err = run_preflight_checks(
+        &[PreflightCheck::FreeDisk {
+            path: dir.to_string_lossy().into_owned(),
+            min_free_mb: u64::MAX,
+        }],
+        None,
+    )
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("MB free"));
+
+    drop(listener);
+    let err = run_preflight_checks(
+        &[PreflightCheck::Reachable {
+            host: "127.0.0.1".into(),
+            port,
+            timeout_ms: 2_000,
+        }],
+        None,
+    )
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("reachable check"));
+}
+
+// an action declaring a minimum provider version is refused by a worker whose provider is older.
+#[tokio::test]
+async fn provider_version_preflight_refuses_older_providers() {
+    use crate::preflight::run_preflight_checks;
+    use runinator_models::preflight::PreflightCheck;
+    use runinator_models::semver::SemVer;
+
+    let check = [PreflightCheck::ProviderVersion { min: "2.1".into() }];
+    run_preflight_checks(&check, Some(SemVer::new(2, 1, 0)))
+        .await
+        .unwrap();
+    let err = run_preflight_checks(&check, Some(SemVer::new(2, 0, 9)))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("RUNI230"));
+    assert!(err.to_string().contains("the provider is at 2.0.9"));
+    let err = run_preflight_checks(&check, None).await.unwrap_err();
+    assert!(err.to_string().contains("reports no version"));
+
+    // a built-in provider without its own version reports the worker build.
+    assert_eq!(
+        crate::update_channel::provider_version(
+            &default_provider_factory(),
+            &Default::default(),
+            "console"
+        ),
+        Some(crate::update_channel::worker_version())
+    );
+    assert_eq!(
+        crate::update_channel::provider_version(
+            &default_provider_factory(),
+            &Default::default(),
+            "no-such-provider"
+        ),
+        None
+    );
+}
+
+#[tokio::test]
+async fn update_channel_announces_components_behind_the_fleet_minimum() {
+    let url = serve_json(
+        r#"{"min_worker_version":"0.0.1","min_plugin_versions":{"legacy":"2.0.0","unversioned":"1.0.0","absent":"1.0.0"}}"#,
+    )
+    .await;
+    let (events_tx, mut events_rx) = tokio::sync::mpsc::unbounded_channel();
+    let shutdown = std::sync::Arc::new(tokio::sync::Notify::new());
+    let plugins = std::collections::BTreeMap::from([
+        (
+            "legacy".to_string(),
+            Some(runinator_models::semver::SemVer::new(1, 4, 0)),
+        ),
+        ("unversioned".to_string(), None),
+    ]);
+    let task = crate::update_channel::spawn_check(
+        crate::UpdateChannel {
+            url,
+            interval: std::time::Duration::from_millis(20),
+        },
+        plugins,
+        std::sync::Arc::new(move |event: crate::WorkerEvent| {
+            let _ = events_tx.send(event);
+        }),
+        shutdown.clone(),
+    );
+
+    let outdated = tokio::time::timeout(std::time::Duration::from_secs(5), async {
+        while let Some(event) = events_rx.recv().await {
+            if let crate::WorkerEvent::VersionsOutdated { outdated } = event {
+                return outdated;
+            }
+        }
+        Vec::new()
+    })
+    .await
+    .expect("the update channel check should report outdated plugins");
+    let described = outdated.iter().map(ToString::to_string).collect::<Vec<_>>();
+    assert_eq!(
+        described,
+        vec![
+            "plugin 'legacy' 1.4.0 is older than the fleet minimum 2.0.0",
+            "plugin 'unversioned' reports no version; the fleet minimum is 1.0.0",
+        ]
+    );
+
+    // the same result on later checks is not announced again.
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    assert!(events_rx.try_recv().is_err());
+    task.abort();
+}
+
 fn action_command() -> ActionCommand {
     ActionCommand {
         command_id: Uuid::new_v4(),
@@ -585,6 +1176,11 @@ fn action_command() -> ActionCommand {
             mcp_enabled: false,
             tags: Vec::new(),
             required_labels: Default::default(),
+            allowed_secrets: Vec::new(),
+            preflight: Vec::new(),
+            cache_ttl_seconds: None,
+            priority: 0,
+            queue: None,
         },
         attempt: 1,
         parameters: json!({}),
@@ -604,7 +1200,11 @@ fn test_config() -> Config {
         broker_result_topic: "runinator.results".into(),
         broker_client_id: "test-worker".into(),
         broker_consumer_id: "test-consumer".into(),
+        broker_auth_secret: None,
         max_concurrent_actions: 1,
+        max_delivery_attempts: None,
+        pause_on_api_unavailable_seconds: None,
+        cluster_pause_check_seconds: None,
         shutdown_grace_seconds: 30,
         api_base_url: "http://127.0.0.1:8080/".into(),
         api_key: None,
@@ -612,5 +1212,90 @@ fn test_config() -> Config {
         advertise_host: None,
         liveness_file: String::new(),
         labels: Default::default(),
+        queues: Default::default(),
+        command_listen: None,
+        update_channel_url: None,
+        update_check_seconds: 3600,
+    }
+}
+
+#[test]
+fn worker_identity_persists_until_reset_and_yields_to_explicit_ids() {
+    let dir = std::env::temp_dir().join(format!("runinator-worker-identity-{}", Uuid::new_v4()));
+    let path = dir.join("state").join("worker-identity");
+
+    let first = crate::config::resolve_worker_id(None, Some(&path), false);
+    assert_eq!(
+        crate::config::resolve_worker_id(None, Some(&path), false),
+        first
+    );
+
+    let reset = crate::config::resolve_worker_id(None, Some(&path), true);
+    assert_ne!(reset, first);
+    assert_eq!(
+        crate::config::resolve_worker_id(None, Some(&path), false),
+        reset
+    );
+
+    // an explicit id wins and leaves the persisted one alone.
+    let explicit = crate::config::resolve_worker_id(Some("pod-a"), Some(&path), false);
+    assert_eq!(explicit, Uuid::new_v5(&Uuid::NAMESPACE_DNS, b"pod-a"));
+    assert_eq!(
+        crate::config::resolve_worker_id(None, Some(&path), false),
+        reset
+    );
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn worker_queues_parse_from_a_comma_separated_list() {
+    let queues = crate::config::parse_queues(Some(" exports, ,default,exports "));
+    assert_eq!(
+        queues.into_iter().collect::<Vec<_>>(),
+        vec!["default".to_string(), "exports".to_string()]
+    );
+    assert!(crate::config::parse_queues(None).is_empty());
+}
+
+#[test]
+fn manifest_env_var_names_keep_only_runtime_prefixes() {
+    let names = crate::manifest::relevant_env_var_names(
+        [
+            "HOME",
+            "RUNINATOR_PORT",
+            "OTEL_SERVICE_NAME",
+            "RUNINATOR_PORT",
+            "PATH",
+        ]
+        .into_iter()
+        .map(String::from),
+    );
+    assert_eq!(names, vec!["OTEL_SERVICE_NAME", "RUNINATOR_PORT"]);
+}
+
+#[tokio::test]
+async fn manifest_records_unresolved_provider_and_worker_platform() {
+    let mut action = action_command().action;
+    action.provider = "no-such-provider".into();
+    let replica_id = Uuid::new_v4();
+    let manifest = crate::manifest::capture(
+        &default_provider_factory(),
+        &Default::default(),
+        &action,
+        Some(replica_id),
+    )
+    .await;
+    assert_eq!(manifest.provider_source, "unresolved");
+    assert_eq!(manifest.os, std::env::consts::OS);
+    assert_eq!(manifest.replica_id, Some(replica_id));
+
+    let node_run_id = Uuid::new_v4();
+    match manifest.artifact_event(node_run_id) {
+        runinator_models::runs::ProviderExecutionEvent::Artifact { name, metadata, .. } => {
+            assert_eq!(name, crate::manifest::MANIFEST_ARTIFACT_NAME);
+            assert_eq!(metadata["provider"], json!("no-such-provider"));
+        }
+        other => panic!("expected an artifact event, got {other:?}"),
     }
 }
@@ -0,0 +1,141 @@
+//! optional update-channel check. the worker fetches a version manifest from a configured url on an
+//! interval and warns, in its log and to the embedding host, when its build or one of its plugins
+//! is older than the fleet minimum. it never updates itself: an outdated worker keeps running until
+//! an operator rolls it, and only actions that declare a `provider_version` pre-flight check are
+//! refused.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+    time::Duration,
+};
+
+use runinator_models::errors::SendableError;
+use runinator_models::semver::SemVer;
+use runinator_models::update_channel::VersionManifest;
+use runinator_plugin::{plugin::Plugin, provider::Provider};
+use tokio::{sync::Notify, task::JoinHandle};
+use tracing::{debug, info, warn};
+
+use crate::events::{WorkerEvent, WorkerEventSink};
+use crate::provider_repository::ProviderFactory;
+
+/// default interval between update-channel checks.
+pub const DEFAULT_UPDATE_CHECK: Duration = Duration::from_secs(3600);
+
+const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// where to fetch the version manifest from, and how often.
+#[derive(Debug, Clone)]
+pub struct UpdateChannel {
+    pub url: String,
+    pub interval: Duration,
+}
+
+/// this worker build's version, without any pre-release or build suffix.
+pub fn worker_version() -> SemVer {
+    env!("CARGO_PKG_VERSION")
+        .split(['-', '+'])
+        .next()
+        .and_then(|version| version.parse().ok())
+        .unwrap_or(SemVer::new(0, 0, 0))
+}
+
+/// the version `provider` reports, resolved in the same order as
+/// [`crate::provider_repository::resolve_provider`]. a built-in provider without its own version
+/// reports the worker build; a plugin reports only what its metadata declares.
+pub(crate) fn provider_version(
+    providers: &ProviderFactory,
+    libraries: &HashMap<String, Plugin>,
+    provider: &str,
+) -> Option<SemVer> {
+    if let Some(built_in) = providers().into_iter().find(|p| p.name() == provider) {
+        return match built_in.metadata().metadata.version {
+            Some(version) => version.parse().ok(),
+            None => Some(worker_version()),
+        };
+    }
+    libraries.get(provider).and_then(plugin_version)
+}
+
+/// the version each loaded plugin reports, by provider name.
+pub(crate) fn plugin_versions(
+    libraries: &HashMap<String, Plugin>,
+) -> BTreeMap<String, Option<SemVer>> {
+    libraries
+        .iter()
+        .map(|(name, plugin)| (name.clone(), plugin_version(plugin)))
+        .collect()
+}
+
+fn plugin_version(plugin: &Plugin) -> Option<SemVer> {
+    plugin
+        .metadata()
+        .metadata
+        .version
+        .and_then(|version| version.parse().ok())
+}
+
+/// check `channel` until `shutdown`, warning on every check that finds this worker behind and
+/// announcing to `events` whenever the set of outdated components changes. a failed fetch keeps
+/// the last result.
+pub(crate) fn spawn_check(
+    channel: UpdateChannel,
+    plugins: BTreeMap<String, Option<SemVer>>,
+    events: Arc<dyn WorkerEventSink>,
+    shutdown: Arc<Notify>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let client = match reqwest::Client::builder().timeout(FETCH_TIMEOUT).build() {
+            Ok(client) => client,
+            Err(err) => {
+                warn!("update channel check disabled: {}", err);
+                return;
+            }
+        };
+        let mut ticker = tokio::time::interval(channel.interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        let mut reported = Vec::new();
+        loop {
+            tokio::select! {
+                _ = shutdown.notified() => break,
+                _ = ticker.tick() => {}
+            }
+            let manifest = match fetch_manifest(&client, &channel.url).await {
+                Ok(manifest) => manifest,
+                Err(err) => {
+                    debug!(url = %channel.url, "update channel check failed: {}", err);
+                    continue;
+                }
+            };
+            let outdated = manifest.outdated(worker_version(), &plugins);
+            for component in &outdated {
+                warn!(url = %channel.url, "{}; upgrade this worker", component);
+            }
+            if outdated == reported {
+                continue;
+            }
+            if outdated.is_empty() {
+                info!(url = %channel.url, "worker and plugins meet the fleet minimum again");
+            }
+            events.handle(WorkerEvent::VersionsOutdated {
+                outdated: outdated.clone(),
+            });
+            reported = outdated;
+        }
+    })
+}
+
+async fn fetch_manifest(
+    client: &reqwest::Client,
+    url: &str,
+) -> Result<VersionManifest, SendableError> {
+    let manifest = client
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<VersionManifest>()
+        .await?;
+    Ok(manifest)
+}
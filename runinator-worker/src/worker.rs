@@ -4,33 +4,42 @@ use std::{
         Arc,
         atomic::{AtomicBool, Ordering},
     },
-    time::Duration,
+    time::{Duration, Instant},
 };
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use runinator_api::{AsyncApiClient, StaticLocator};
 use runinator_broker::{Broker, BrokerDelivery, ControlDelivery};
 use runinator_comm::{ConsumerProfile, ControlKind, WireCodec};
 use runinator_models::errors::{SendableError, error_code_or_unknown};
+use runinator_models::preflight::PreflightCheck;
 use runinator_models::workflow_state::TaskStatusOutput;
 use runinator_models::workflows::WorkflowStatus;
 use runinator_plugin::{
     cancel::CancellationToken, load_libraries_from_path, plugin::Plugin, print_libs,
+    provider::ProviderEventSink,
 };
 use tokio::{
-    sync::{Mutex, Notify, Semaphore},
+    sync::{Mutex, Notify, Semaphore, watch},
     task::JoinSet,
 };
 use tracing::{error, info, warn};
 use uuid::Uuid;
 
+use crate::api_gate::{self, ApiPausePolicy, PollingPause};
 use crate::broker::broker_error;
+use crate::cluster_gate;
+use crate::commands::{self, CommandState};
 use crate::events::{ActionOutcome, WorkerEvent, WorkerEventSink};
 use crate::executor;
+use crate::manifest;
 use crate::metrics;
 use crate::output_sink::RunOutputSink;
+use crate::preflight;
 use crate::provider_repository::ProviderFactory;
+use crate::result_cache::{self, CachedResult, ResultCache};
 use crate::secrets::{is_transient_secret_error, resolve_secret_refs};
+use crate::update_channel::{self, UpdateChannel};
 
 // grace added to an action's timeout before its executor lease is considered abandoned, so a worker
 // that is merely slow (clock skew, a long flush) is never preempted by a duplicate delivery.
@@ -74,14 +83,21 @@ impl OwnStaleLeases {
 }
 
 // one in-flight action execution, tracked so a control command can cancel it. the owning run id is
-// retained so a run-wide cancel can fan out to every node run of that run.
+// retained so a run-wide cancel can fan out to every node run of that run; the rest describes the
+// execution for the command port's status report.
 #[derive(Clone)]
-struct InFlightAction {
-    workflow_run_id: Uuid,
-    token: CancellationToken,
+pub(crate) struct InFlightAction {
+    pub(crate) workflow_run_id: Uuid,
+    pub(crate) command_id: Uuid,
+    pub(crate) node_id: String,
+    pub(crate) provider: String,
+    pub(crate) function: String,
+    pub(crate) attempt: i64,
+    pub(crate) started_at: DateTime<Utc>,
+    pub(crate) token: CancellationToken,
     // set by the control loop before it cancels the token, so the result path can tell a genuine
     // (ws-requested) cancel from a shutdown preemption that should requeue the delivery instead.
-    canceled_by_control: Arc<AtomicBool>,
+    pub(crate) canceled_by_control: Arc<AtomicBool>,
 }
 
 /// everything the action loop needs to run. assembled by the binary (or an embedded host such as the
@@ -94,10 +110,21 @@ pub struct WorkerRuntime {
     pub replica_id: Option<Uuid>,
     pub providers: ProviderFactory,
     pub max_concurrent_actions: usize,
+    /// delivery attempts an action gets before a further redelivery fails its node run; unlimited
+    /// when `None`.
+    pub max_delivery_attempts: Option<u32>,
+    /// stop polling for actions while the api is unreachable; always polls when `None`.
+    pub api_pause: Option<ApiPausePolicy>,
+    /// how often to check for a cluster-wide execution pause; never checks when `None`.
+    pub cluster_pause_check: Option<Duration>,
     pub shutdown_grace: Duration,
     pub shutdown: Arc<Notify>,
     /// observer for loop activity; use [`crate::events::NoopEventSink`] when nothing listens.
     pub events: Arc<dyn WorkerEventSink>,
+    /// `host:port` for the local status/cancel command port; not served when `None`.
+    pub command_listen: Option<String>,
+    /// where to check for the fleet's minimum worker and plugin versions; never checks when `None`.
+    pub update_channel: Option<UpdateChannel>,
 }
 
 /// load plugin libraries from the supplied search paths, skipping any that do not exist.
@@ -116,6 +143,18 @@ pub fn load_libraries(paths: &[String]) -> Result<HashMap<String, Plugin>, Senda
     Ok(libraries)
 }
 
+// wait until polling is (or is not) paused. the watch borrow guard is not `Send`, so it is dropped
+// here rather than carried out of a `select!` branch in the spawned worker loop.
+async fn wait_for_polling(
+    polling_paused: &mut watch::Receiver<PollingPause>,
+    paused: bool,
+) -> bool {
+    polling_paused
+        .wait_for(|value| value.is_paused() == paused)
+        .await
+        .is_ok()
+}
+
 /// run the action and control loops until `shutdown` is notified, draining in-flight work within the
 /// configured grace period. caller owns signal handling; this never installs a signal handler.
 pub async fn start_worker_loop(runtime: WorkerRuntime) -> Result<(), SendableError> {
@@ -127,9 +166,14 @@ pub async fn start_worker_loop(runtime: WorkerRuntime) -> Result<(), SendableErr
         replica_id,
         providers,
         max_concurrent_actions,
+        max_delivery_attempts,
+        api_pause,
+        cluster_pause_check,
         shutdown_grace,
         shutdown,
         events,
+        command_listen,
+        update_channel,
     } = runtime;
 
     // the ack channels are keyed by the consumer id; the action and control channels route by
@@ -146,6 +190,51 @@ pub async fn start_worker_loop(runtime: WorkerRuntime) -> Result<(), SendableErr
     // work) each get their own cancellation token; a targeted cancel reaches exactly one branch.
     let in_flight = Arc::new(Mutex::new(HashMap::<Uuid, InFlightAction>::new()));
     let stale_leases = Arc::new(OwnStaleLeases::default());
+    let results = Arc::new(ResultCache::default());
+    let (paused_tx, mut polling_paused) = watch::channel(PollingPause::default());
+    let paused_tx = Arc::new(paused_tx);
+    let probe_task = api_pause.map(|policy| {
+        api_gate::spawn_probe(
+            api_client.clone(),
+            policy,
+            Arc::clone(&paused_tx),
+            Arc::clone(&events),
+            shutdown.clone(),
+        )
+    });
+    let cluster_task = cluster_pause_check.map(|interval| {
+        cluster_gate::spawn_watch(
+            api_client.clone(),
+            interval,
+            Arc::clone(&paused_tx),
+            Arc::clone(&events),
+            shutdown.clone(),
+        )
+    });
+    let update_task = update_channel.map(|channel| {
+        update_channel::spawn_check(
+            channel,
+            update_channel::plugin_versions(&libraries),
+            Arc::clone(&events),
+            shutdown.clone(),
+        )
+    });
+    let command_task = match command_listen {
+        Some(addr) => {
+            let mut plugins = libraries.keys().cloned().collect::<Vec<_>>();
+            plugins.sort();
+            let state = CommandState {
+                replica_id,
+                started_at: std::time::Instant::now(),
+                max_concurrent_actions,
+                plugins,
+                in_flight: Arc::clone(&in_flight),
+                polling_paused: polling_paused.clone(),
+            };
+            Some(commands::spawn(&addr, state, shutdown.clone()).await?)
+        }
+        None => None,
+    };
     let control_task = tokio::spawn(run_control_loop(
         broker.clone(),
         control_profile,
@@ -157,6 +246,18 @@ pub async fn start_worker_loop(runtime: WorkerRuntime) -> Result<(), SendableErr
     info!(max_concurrent_actions, "worker action loop started");
 
     loop {
+        // while the api is unreachable or execution is paused cluster-wide, leave new work on the
+        // broker; in-flight deliveries keep running and the control loop keeps serving cancels.
+        if polling_paused.borrow().is_paused() {
+            tokio::select! {
+                _ = shutdown.notified() => {
+                    info!("worker loop shutting down");
+                    break;
+                }
+                _ = wait_for_polling(&mut polling_paused, false) => {}
+            }
+            continue;
+        }
         let permit = tokio::select! {
             biased;
             _ = shutdown.notified() => {
@@ -180,6 +281,12 @@ pub async fn start_worker_loop(runtime: WorkerRuntime) -> Result<(), SendableErr
                 info!("worker loop shutting down");
                 break;
             }
+            // stop waiting for work as soon as polling pauses; a receive abandoned mid-lease is
+            // redelivered once its lease expires.
+            true = wait_for_polling(&mut polling_paused, true) => {
+                drop(permit);
+                continue;
+            }
             result = broker.receive_for(&profile) => {
                 match result {
                     Ok(delivery) => delivery,
@@ -213,6 +320,7 @@ pub async fn start_worker_loop(runtime: WorkerRuntime) -> Result<(), SendableErr
         let replica_id = replica_id;
         let in_flight = Arc::clone(&in_flight);
         let stale_leases = Arc::clone(&stale_leases);
+        let results = Arc::clone(&results);
         let events = Arc::clone(&events);
         deliveries.spawn(async move {
             let _permit = permit;
@@ -224,8 +332,10 @@ pub async fn start_worker_loop(runtime: WorkerRuntime) -> Result<(), SendableErr
                 providers,
                 replica_id,
                 maybe_delivery,
+                max_delivery_attempts,
                 in_flight,
                 stale_leases,
+                results,
                 events,
             )
             .await
@@ -261,6 +371,18 @@ pub async fn start_worker_loop(runtime: WorkerRuntime) -> Result<(), SendableErr
         Err(err) if err.is_cancelled() => {}
         Err(err) => error!("worker control task join error: {}", err),
     }
+    if let Some(command_task) = command_task {
+        command_task.abort();
+    }
+    if let Some(probe_task) = probe_task {
+        probe_task.abort();
+    }
+    if let Some(cluster_task) = cluster_task {
+        cluster_task.abort();
+    }
+    if let Some(update_task) = update_task {
+        update_task.abort();
+    }
 
     Ok(())
 }
@@ -434,8 +556,10 @@ async fn process_delivery(
     providers: ProviderFactory,
     replica_id: Option<Uuid>,
     delivery: BrokerDelivery,
+    max_delivery_attempts: Option<u32>,
     in_flight: Arc<Mutex<HashMap<Uuid, InFlightAction>>>,
     stale_leases: Arc<OwnStaleLeases>,
+    results: Arc<ResultCache>,
     events: Arc<dyn WorkerEventSink>,
 ) -> Result<(), SendableError> {
     // link this execution span to the trace that dispatched the action (w3c context from the broker
@@ -472,6 +596,12 @@ async fn process_delivery(
             command.workflow_node_run_id,
             InFlightAction {
                 workflow_run_id: command.workflow_run_id,
+                command_id: command.command_id,
+                node_id: command.node_id.clone(),
+                provider: action.provider.clone(),
+                function: action.function.clone(),
+                attempt: command.attempt,
+                started_at: Utc::now(),
                 token: token.clone(),
                 canceled_by_control: Arc::new(AtomicBool::new(false)),
             },
@@ -583,10 +713,9 @@ async fn process_delivery(
         function: action.function.clone(),
         attempt: command.attempt,
     });
-    if let Err(err) = sink
-        .publish_status(WorkflowStatus::Running, None, None)
-        .await
-    {
+    let queue_wait_ms = delivery.queue_wait(Utc::now()).num_milliseconds();
+    metrics::queue_wait(queue_wait_ms);
+    if let Err(err) = sink.publish_running(queue_wait_ms).await {
         error!(
             node_run_id = %command.workflow_node_run_id,
             "failed to publish running status: {}",
@@ -602,11 +731,100 @@ async fn process_delivery(
             command.workflow_node_run_id,
             command.attempt,
             delivery.delivery_id,
+            format!("failed to publish running status: {err}"),
         )
         .await?;
         return Err(broker_error("publish_result", err));
     }
-    let parameters = match resolve_secret_refs(&api_client, command.parameters.clone()).await {
+    // a delivery handed out too many times (nacks and expired leases alike) is failed rather than
+    // returned again, so an action that keeps breaking its worker cannot cycle through the pool.
+    if let Some(max_attempts) = max_delivery_attempts
+        && delivery.attempt > max_attempts
+    {
+        let err = crate::errors::DELIVERY_ATTEMPTS_EXHAUSTED.error(format!(
+            "gave up after {max_attempts} delivery attempts; last error: {}",
+            delivery.last_error.as_deref().unwrap_or("none recorded")
+        ));
+        let message = err.to_string();
+        warn!(
+            node_run_id = %command.workflow_node_run_id,
+            error_code = error_code_or_unknown(err.as_ref()),
+            "{}",
+            message
+        );
+        return fail_before_execution(
+            broker,
+            consumer_id,
+            &api_client,
+            replica_id,
+            &stale_leases,
+            &in_flight,
+            events.as_ref(),
+            &sink,
+            &delivery,
+            message,
+        )
+        .await;
+    }
+    // an idempotent action that succeeded here within its cache window is settled with that result
+    // instead of running again, before pre-flight checks or secrets are touched.
+    let cache =
+        result_cache::cache_ttl(&command).map(|ttl| (ttl, result_cache::cache_key(&command)));
+    if let Some((_, key)) = &cache
+        && let Some(cached) = results.fresh(key, Instant::now()).await
+    {
+        return serve_from_cache(
+            broker,
+            consumer_id,
+            &api_client,
+            replica_id,
+            &stale_leases,
+            &in_flight,
+            events.as_ref(),
+            &sink,
+            &delivery,
+            cached,
+        )
+        .await;
+    }
+    // pre-flight checks run before secrets are fetched, so an action that cannot succeed here
+    // never touches the secret store or the provider. the provider's version is only looked up
+    // when a check asks for it.
+    let provider_version = action
+        .preflight
+        .iter()
+        .any(|check| matches!(check, PreflightCheck::ProviderVersion { .. }))
+        .then(|| update_channel::provider_version(&providers, &libraries, &action.provider))
+        .flatten();
+    if let Err(err) = preflight::run_preflight_checks(&action.preflight, provider_version).await {
+        let message = err.to_string();
+        warn!(
+            node_run_id = %command.workflow_node_run_id,
+            error_code = error_code_or_unknown(err.as_ref()),
+            "{}",
+            message
+        );
+        return fail_before_execution(
+            broker,
+            consumer_id,
+            &api_client,
+            replica_id,
+            &stale_leases,
+            &in_flight,
+            events.as_ref(),
+            &sink,
+            &delivery,
+            message,
+        )
+        .await;
+    }
+    let parameters = match resolve_secret_refs(
+        &api_client,
+        &action.allowed_secrets,
+        command.parameters.clone(),
+    )
+    .await
+    {
         Ok(parameters) => parameters,
         // a transport failure or web-service outage is transient: the secret may resolve fine in a
         // moment, so return the delivery for redelivery instead of failing the node (the default
@@ -631,6 +849,7 @@ async fn process_delivery(
                 command.workflow_node_run_id,
                 command.attempt,
                 delivery.delivery_id,
+                format!("transient failure resolving action secrets: {err}"),
             )
             .await;
         }
@@ -643,69 +862,24 @@ async fn process_delivery(
                 message
             );
             metrics::secret_resolution_failure();
-            events.handle(WorkerEvent::ActionFinished {
-                workflow_run_id: command.workflow_run_id,
-                node_id: command.node_id.clone(),
-                node_run_id: command.workflow_node_run_id,
-                provider: action.provider.clone(),
-                function: action.function.clone(),
-                outcome: ActionOutcome::Failed,
-                duration_ms: 0,
-                message: Some(message.clone()),
-            });
-            let output_json = TaskStatusOutput {
-                success: false,
-                duration_ms: None,
-                message: Some(message.clone()),
-            }
-            .to_wire_value()?;
-            if let Err(err) = sink
-                .publish_status(
-                    WorkflowStatus::Failed,
-                    Some(output_json),
-                    Some(message.clone()),
-                )
-                .await
-            {
-                error!(
-                    node_run_id = %command.workflow_node_run_id,
-                    error_code = error_code_or_unknown(&err),
-                    "failed to publish failed status: {}",
-                    err
-                );
-                in_flight.lock().await.remove(&command.workflow_node_run_id);
-                nack_action_delivery(
-                    broker,
-                    consumer_id,
-                    &api_client,
-                    replica_id,
-                    &stale_leases,
-                    command.workflow_node_run_id,
-                    command.attempt,
-                    delivery.delivery_id,
-                )
-                .await?;
-                return Err(broker_error("publish_result", err));
-            }
-            broker
-                .ack(consumer_id, delivery.delivery_id)
-                .await
-                .map_err(|err| broker_error("ack", err))?;
-            if let Some(replica_id) = replica_id {
-                // this execution settled terminally, so only the next attempt may reclaim.
-                release_executor_lease(
-                    &api_client,
-                    &stale_leases,
-                    replica_id,
-                    command.workflow_node_run_id,
-                    command.attempt + 1,
-                )
-                .await;
-            }
-            in_flight.lock().await.remove(&command.workflow_node_run_id);
-            return Ok(());
+            return fail_before_execution(
+                broker,
+                consumer_id,
+                &api_client,
+                replica_id,
+                &stale_leases,
+                &in_flight,
+                events.as_ref(),
+                &sink,
+                &delivery,
+                message,
+            )
+            .await;
         }
     };
+    // captured before execution so it describes what actually ran; published once the node settles.
+    let mut manifest = manifest::capture(&providers, &libraries, &action, replica_id).await;
+    manifest.record_delivery(&delivery, Utc::now());
     let result = {
         // raise the in-flight gauge only around actual execution, so it reflects running providers
         // rather than deliveries parked on lease/secret checks.
@@ -758,10 +932,12 @@ async fn process_delivery(
             command.workflow_node_run_id,
             command.attempt,
             delivery.delivery_id,
+            "action preempted by worker shutdown".to_string(),
         )
         .await;
     }
     metrics::action_completed(outcome.as_str(), result.task_result.duration_ms() as f64);
+    sink.emit(manifest.artifact_event(command.workflow_node_run_id));
     if let Some(execution_result) = &result.execution_result
         && let Err(err) = sink.persist_result(execution_result).await
     {
@@ -780,6 +956,7 @@ async fn process_delivery(
             command.workflow_node_run_id,
             command.attempt,
             delivery.delivery_id,
+            format!("failed to publish result artifacts: {err}"),
         )
         .await?;
         return Err(broker_error("publish_result", err));
@@ -795,6 +972,7 @@ async fn process_delivery(
         outcome,
         duration_ms: task_result.duration_ms(),
         message: provider_message.clone(),
+        error: task_result.error.clone(),
     });
 
     if task_result.success {
@@ -821,6 +999,7 @@ async fn process_delivery(
                 command.workflow_node_run_id,
                 command.attempt,
                 delivery.delivery_id,
+                format!("failed to flush action output: {err}"),
             )
             .await?;
             return Err(broker_error("publish_result", err));
@@ -836,9 +1015,11 @@ async fn process_delivery(
                     success: true,
                     duration_ms: Some(task_result.duration_ms()),
                     message: provider_message.clone(),
+                    error: None,
                 }
                 .to_wire_value()
             })?;
+        let cached_output = cache.as_ref().map(|_| output_json.clone());
         if let Err(err) = sink
             .publish_status(
                 WorkflowStatus::Succeeded,
@@ -862,10 +1043,25 @@ async fn process_delivery(
                 command.workflow_node_run_id,
                 command.attempt,
                 delivery.delivery_id,
+                format!("failed to publish succeeded status: {err}"),
             )
             .await?;
             return Err(broker_error("publish_result", err));
         }
+        if let (Some((ttl, key)), Some(output_json)) = (cache, cached_output) {
+            results
+                .insert(
+                    key,
+                    CachedResult {
+                        output_json,
+                        message: provider_message.clone(),
+                        node_run_id: command.workflow_node_run_id,
+                        stored_at: Instant::now(),
+                    },
+                    ttl,
+                )
+                .await;
+        }
     } else {
         warn!(
             node_run_id = %command.workflow_node_run_id,
@@ -892,6 +1088,7 @@ async fn process_delivery(
                 command.workflow_node_run_id,
                 command.attempt,
                 delivery.delivery_id,
+                format!("failed to flush action output: {err}"),
             )
             .await?;
             return Err(broker_error("publish_result", err));
@@ -906,6 +1103,7 @@ async fn process_delivery(
             success: false,
             duration_ms: Some(task_result.duration_ms()),
             message: provider_message.clone(),
+            error: task_result.error.clone(),
         }
         .to_wire_value()?;
         if let Err(err) = sink
@@ -927,6 +1125,7 @@ async fn process_delivery(
                 command.workflow_node_run_id,
                 command.attempt,
                 delivery.delivery_id,
+                format!("failed to publish terminal status: {err}"),
             )
             .await?;
             return Err(broker_error("publish_result", err));
@@ -955,11 +1154,185 @@ async fn process_delivery(
     Ok(())
 }
 
+/// settle a node run that failed before its provider ran (a pre-flight check or a rejected secret):
+/// publish the failed status, ack the delivery, and release the executor lease to the next attempt.
+#[allow(clippy::too_many_arguments)]
+async fn fail_before_execution(
+    broker: &Arc<dyn Broker>,
+    consumer_id: &str,
+    api_client: &AsyncApiClient<StaticLocator>,
+    replica_id: Option<Uuid>,
+    stale_leases: &OwnStaleLeases,
+    in_flight: &Mutex<HashMap<Uuid, InFlightAction>>,
+    events: &dyn WorkerEventSink,
+    sink: &RunOutputSink,
+    delivery: &BrokerDelivery,
+    message: String,
+) -> Result<(), SendableError> {
+    let command = &delivery.command;
+    events.handle(WorkerEvent::ActionFinished {
+        workflow_run_id: command.workflow_run_id,
+        node_id: command.node_id.clone(),
+        node_run_id: command.workflow_node_run_id,
+        provider: command.action.provider.clone(),
+        function: command.action.function.clone(),
+        outcome: ActionOutcome::Failed,
+        duration_ms: 0,
+        message: Some(message.clone()),
+        error: None,
+    });
+    let output_json = TaskStatusOutput {
+        success: false,
+        duration_ms: None,
+        message: Some(message.clone()),
+        error: None,
+    }
+    .to_wire_value()?;
+    if let Err(err) = sink
+        .publish_status(WorkflowStatus::Failed, Some(output_json), Some(message))
+        .await
+    {
+        error!(
+            node_run_id = %command.workflow_node_run_id,
+            error_code = error_code_or_unknown(&err),
+            "failed to publish failed status: {}",
+            err
+        );
+        in_flight.lock().await.remove(&command.workflow_node_run_id);
+        nack_action_delivery(
+            broker,
+            consumer_id,
+            api_client,
+            replica_id,
+            stale_leases,
+            command.workflow_node_run_id,
+            command.attempt,
+            delivery.delivery_id,
+            format!("failed to publish failed status: {err}"),
+        )
+        .await?;
+        return Err(broker_error("publish_result", err));
+    }
+    broker
+        .ack(consumer_id, delivery.delivery_id)
+        .await
+        .map_err(|err| broker_error("ack", err))?;
+    if let Some(replica_id) = replica_id {
+        // this execution settled terminally, so only the next attempt may reclaim.
+        release_executor_lease(
+            api_client,
+            stale_leases,
+            replica_id,
+            command.workflow_node_run_id,
+            command.attempt + 1,
+        )
+        .await;
+    }
+    in_flight.lock().await.remove(&command.workflow_node_run_id);
+    Ok(())
+}
+
+/// settle a node run with a cached result from an identical earlier execution: publish it as
+/// succeeded with a message naming its source, ack the delivery, and release the executor lease.
+#[allow(clippy::too_many_arguments)]
+async fn serve_from_cache(
+    broker: &Arc<dyn Broker>,
+    consumer_id: &str,
+    api_client: &AsyncApiClient<StaticLocator>,
+    replica_id: Option<Uuid>,
+    stale_leases: &OwnStaleLeases,
+    in_flight: &Mutex<HashMap<Uuid, InFlightAction>>,
+    events: &dyn WorkerEventSink,
+    sink: &RunOutputSink,
+    delivery: &BrokerDelivery,
+    cached: CachedResult,
+) -> Result<(), SendableError> {
+    let command = &delivery.command;
+    let message = cached.replay_message();
+    info!(
+        node_run_id = %command.workflow_node_run_id,
+        source_node_run_id = %cached.node_run_id,
+        provider = %command.action.provider,
+        function = %command.action.function,
+        "{}",
+        message
+    );
+    metrics::action_completed("cached", 0.0);
+    events.handle(WorkerEvent::ActionFinished {
+        workflow_run_id: command.workflow_run_id,
+        node_id: command.node_id.clone(),
+        node_run_id: command.workflow_node_run_id,
+        provider: command.action.provider.clone(),
+        function: command.action.function.clone(),
+        outcome: ActionOutcome::Succeeded,
+        duration_ms: 0,
+        message: Some(message.clone()),
+        error: None,
+    });
+    sink.emit_log(format!(
+        "Action {}.{} {}.",
+        command.action.provider, command.action.function, message
+    ));
+    let published = match sink.flush().await {
+        Ok(()) => sink
+            .publish_status(
+                WorkflowStatus::Succeeded,
+                Some(cached.output_json),
+                Some(message),
+            )
+            .await
+            .map_err(|err| ("failed to publish cached status", err)),
+        Err(err) => Err(("failed to flush action output", err)),
+    };
+    if let Err((context, err)) = published {
+        error!(
+            node_run_id = %command.workflow_node_run_id,
+            error_code = error_code_or_unknown(&err),
+            "{}: {}",
+            context,
+            err
+        );
+        in_flight.lock().await.remove(&command.workflow_node_run_id);
+        nack_action_delivery(
+            broker,
+            consumer_id,
+            api_client,
+            replica_id,
+            stale_leases,
+            command.workflow_node_run_id,
+            command.attempt,
+            delivery.delivery_id,
+            format!("{context}: {err}"),
+        )
+        .await?;
+        return Err(broker_error("publish_result", err));
+    }
+    broker
+        .ack(consumer_id, delivery.delivery_id)
+        .await
+        .map_err(|err| broker_error("ack", err))?;
+    if let Some(replica_id) = replica_id {
+        // this execution settled terminally, so only the next attempt may reclaim.
+        release_executor_lease(
+            api_client,
+            stale_leases,
+            replica_id,
+            command.workflow_node_run_id,
+            command.attempt + 1,
+        )
+        .await;
+    }
+    in_flight.lock().await.remove(&command.workflow_node_run_id);
+    Ok(())
+}
+
 /// return a delivery to the broker for redelivery, releasing this worker's executor lease first.
 /// without the release the retry is lost: the executor claim is not re-entrant, so a redelivery
 /// landing on another worker is dropped as a duplicate and acked until the lease goes stale,
 /// parking the node run until the reducer's timeout backstop fires. a failed release is remembered
-/// so a redelivery landing back on this worker reclaims the leftover lease instead.
+/// so a redelivery landing back on this worker reclaims the leftover lease instead. `error` travels
+/// with the redelivery as its `last_error`.
+#[allow(clippy::too_many_arguments)]
 async fn nack_action_delivery(
     broker: &Arc<dyn Broker>,
     consumer_id: &str,
@@ -969,12 +1342,13 @@ async fn nack_action_delivery(
     node_run_id: Uuid,
     attempt: i64,
     delivery_id: uuid::Uuid,
+    error: String,
 ) -> Result<(), SendableError> {
     if let Some(replica_id) = replica_id {
         release_executor_lease(api_client, stale_leases, replica_id, node_run_id, attempt).await;
     }
     broker
-        .nack(consumer_id, delivery_id)
+        .nack_with_error(consumer_id, delivery_id, Some(error))
         .await
         .map_err(|err| broker_error("nack", err))
 }
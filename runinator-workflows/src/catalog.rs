@@ -1024,6 +1024,33 @@ fn trigger_kind_metadata(kind: WorkflowTriggerKind) -> WorkflowTriggerKindMetada
                         .with_description("Cron expression, e.g. `0 * * * *`."),
                 )
                 .with_widget("cron"),
+                UiField::new(
+                    ParameterMetadata::optional("timezone", RuninatorType::String).with_description(
+                        "IANA zone the schedule follows, e.g. `America/New_York`; UTC when empty.",
+                    ),
+                ),
+                UiField::new(
+                    ParameterMetadata::optional("misfire", RuninatorType::String)
+                        .with_description(
+                            "Catch-up after scheduler downtime: `skip`, `run_once` (default), or `run_all_missed`.",
+                        )
+                        .with_default("run_once"),
+                ),
+                UiField::new(
+                    ParameterMetadata::optional("jitter_seconds", RuninatorType::Integer)
+                        .with_description(
+                            "Fire up to this many seconds after each slot (at most 3600), spreading triggers that share a cron.",
+                        ),
+                ),
+                UiField::new(
+                    ParameterMetadata::optional(
+                        "blackouts",
+                        RuninatorType::array(RuninatorType::Any),
+                    )
+                    .with_description(
+                        "Recurring windows with no firings: `{\"days\": [\"sun\"], \"start\": \"02:00\", \"end\": \"04:00\"}` or `{\"calendar\": \"<name>\"}`.",
+                    ),
+                ),
             ],
             default_configuration: json!({ "cron": "0 * * * *", "parameters": {} }),
         },
@@ -1055,6 +1082,45 @@ fn trigger_kind_metadata(kind: WorkflowTriggerKind) -> WorkflowTriggerKindMetada
             ],
             default_configuration: json!({ "on": "success", "target_workflow": "", "parameters": {} }),
         },
+        WorkflowTriggerKind::FileArrival => WorkflowTriggerKindMetadata {
+            kind,
+            label: "File arrival".to_string(),
+            icon: "file".to_string(),
+            description: "Fires when a matching file settles in a watched directory.".to_string(),
+            fields: vec![
+                UiField::new(
+                    ParameterMetadata::required("path", RuninatorType::String)
+                        .with_description("Directory to watch on the engine host."),
+                ),
+                UiField::new(
+                    ParameterMetadata::optional("pattern", RuninatorType::String)
+                        .with_description("File name pattern; `*` matches any characters."),
+                ),
+                UiField::new(
+                    ParameterMetadata::optional("stable_seconds", RuninatorType::Integer)
+                        .with_description("Seconds a file must stay unchanged before it fires."),
+                ),
+                UiField::new(
+                    ParameterMetadata::optional("debounce_seconds", RuninatorType::Integer)
+                        .with_description(
+                            "Minimum seconds between runs; files settling in between share a run.",
+                        ),
+                ),
+            ],
+            default_configuration: json!({
+                "path": "", "pattern": "*", "stable_seconds": 30, "debounce_seconds": 0,
+                "parameters": {},
+            }),
+        },
+        WorkflowTriggerKind::Webhook => WorkflowTriggerKindMetadata {
+            kind,
+            label: "Webhook".to_string(),
+            icon: "webhook".to_string(),
+            description: "Fires when an external system posts to the trigger's token url."
+                .to_string(),
+            fields: Vec::new(),
+            default_configuration: json!({ "parameters": {} }),
+        },
     }
 }
 
@@ -86,6 +86,16 @@ pub enum WorkflowValidationError {
         "WORKFLOW029 - mutex release node '{node}' for lock '{name}' can be reached before the lock is acquired"
     )]
     MutexReleaseBeforeAcquire { node: String, name: String },
+    #[error("WORKFLOW030 - workflow node '{node}' allowed_secrets is invalid: {message}")]
+    InvalidSecretPattern { node: String, message: String },
+    #[error(
+        "WORKFLOW031 - workflow node '{node}' references secret '{secret}' outside its allowed_secrets"
+    )]
+    SecretNotAllowed { node: String, secret: String },
+    #[error("WORKFLOW032 - workflow node '{node}' preflight is invalid: {message}")]
+    InvalidPreflight { node: String, message: String },
+    #[error("WORKFLOW033 - workflow node '{0}' cache_ttl_seconds must be greater than zero")]
+    InvalidCacheTtl(String),
 }
 
 // numbered error dictionary for the workflow validator.
@@ -231,6 +241,26 @@ pub const MUTEX_RELEASE_BEFORE_ACQUIRE: ErrorDescriptor = ErrorDescriptor::new(
     "workflow.mutex_release_before_acquire",
     "Mutex release can be reached before the lock is acquired",
 );
+pub const INVALID_SECRET_PATTERN: ErrorDescriptor = ErrorDescriptor::new(
+    "WORKFLOW030",
+    "workflow.invalid_secret_pattern",
+    "allowed_secrets pattern is invalid",
+);
+pub const SECRET_NOT_ALLOWED: ErrorDescriptor = ErrorDescriptor::new(
+    "WORKFLOW031",
+    "workflow.secret_not_allowed",
+    "Secret reference is outside the action's allowed_secrets",
+);
+pub const INVALID_PREFLIGHT: ErrorDescriptor = ErrorDescriptor::new(
+    "WORKFLOW032",
+    "workflow.invalid_preflight",
+    "Action preflight check is invalid",
+);
+pub const INVALID_CACHE_TTL: ErrorDescriptor = ErrorDescriptor::new(
+    "WORKFLOW033",
+    "workflow.invalid_cache_ttl",
+    "cache_ttl_seconds must be greater than zero",
+);
 
 pub const DICTIONARY: &[ErrorDescriptor] = &[
     MISSING_NODES,
@@ -262,6 +292,10 @@ pub const DICTIONARY: &[ErrorDescriptor] = &[
     INVALID_GOTO_TARGET,
     INVALID_NODE_REFERENCE_TYPE,
     MUTEX_RELEASE_BEFORE_ACQUIRE,
+    INVALID_SECRET_PATTERN,
+    SECRET_NOT_ALLOWED,
+    INVALID_PREFLIGHT,
+    INVALID_CACHE_TTL,
 ];
 
 impl EngineErrors for WorkflowValidationError {
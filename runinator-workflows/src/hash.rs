@@ -0,0 +1,42 @@
+//! a portable fingerprint of a workflow definition, so gitops tooling can tell whether the workflow
+//! files in a repository still match what is live without comparing field by field. only what a
+//! workflow file carries is hashed: the instance fields (id, org, timestamps) and the enabled
+//! toggle are left out, and the definition is normalized first, so a compiled file and the row it
+//! was imported into hash the same.
+
+use runinator_models::{
+    types::RuninatorType,
+    value::Value,
+    workflows::{WorkflowDefinition, WorkflowGraph},
+};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::normalize_workflow;
+
+#[derive(Serialize)]
+struct PortableWorkflow<'a> {
+    name: &'a str,
+    namespace: Option<&'a str>,
+    version: String,
+    input_type: &'a RuninatorType,
+    definition: &'a WorkflowGraph,
+}
+
+/// `sha256:<hex>` over the workflow's canonical json. object keys are sorted at every depth, so
+/// key order in the source never changes the hash.
+pub fn workflow_definition_hash(workflow: &WorkflowDefinition) -> String {
+    let workflow = normalize_workflow(workflow);
+    let portable = PortableWorkflow {
+        name: &workflow.name,
+        namespace: workflow.namespace.as_deref(),
+        version: workflow.version.to_string(),
+        input_type: &workflow.input_type,
+        definition: &workflow.definition,
+    };
+    // `Value` keeps objects in a sorted map, which is what makes the encoding canonical.
+    let canonical = Value::encode(&portable)
+        .and_then(|value| serde_json::to_vec(&value))
+        .unwrap_or_default();
+    format!("sha256:{:x}", Sha256::digest(canonical))
+}
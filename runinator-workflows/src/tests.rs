@@ -2166,3 +2166,146 @@ fn catalog_metadata_serializes_to_stable_json_shape() {
     assert!(node.get("default_template").is_some());
     assert!(node.get("supports_predicate_edges").is_some());
 }
+
+#[test]
+fn allowed_secrets_restrict_literal_secret_references() {
+    let graph = |allowed: runinator_models::value::Value| {
+        workflow(runinator_models::json!({
+            "start": "start",
+            "nodes": [
+                { "id": "start", "kind": "start", "transitions": { "next": { "$node": "export" } } },
+                {
+                    "id": "export",
+                    "kind": "action",
+                    "action": {
+                        "provider": "sql",
+                        "function": "dump_data",
+                        "configuration": { "connection_string": "secret://warehouse/reporting-ro" },
+                        "allowed_secrets": allowed
+                    },
+                    "parameters": { "token": "secret://slack/bot%2Dtoken" },
+                    "transitions": { "next": { "$node": "done" } }
+                },
+                { "id": "done", "kind": "end" }
+            ]
+        }))
+    };
+
+    validate_workflow(&graph(runinator_models::json!([]))).expect("no patterns is unrestricted");
+    validate_workflow(&graph(runinator_models::json!([
+        "warehouse/reporting-*",
+        "slack/bot-token"
+    ])))
+    .expect("every reference is covered");
+
+    assert!(matches!(
+        validate_workflow(&graph(runinator_models::json!(["warehouse/*"]))),
+        Err(WorkflowValidationError::SecretNotAllowed { secret, .. }) if secret == "slack/bot-token"
+    ));
+    assert!(matches!(
+        validate_workflow(&graph(runinator_models::json!(["warehouse"]))),
+        Err(WorkflowValidationError::InvalidSecretPattern { .. })
+    ));
+}
+
+#[test]
+fn action_preflight_checks_are_validated_at_save_time() {
+    let graph = |preflight: runinator_models::value::Value| {
+        workflow(runinator_models::json!({
+            "start": "start",
+            "nodes": [
+                { "id": "start", "kind": "start", "transitions": { "next": { "$node": "export" } } },
+                {
+                    "id": "export",
+                    "kind": "action",
+                    "action": {
+                        "provider": "sql",
+                        "function": "dump_data",
+                        "preflight": preflight
+                    },
+                    "transitions": { "next": { "$node": "done" } }
+                },
+                { "id": "done", "kind": "end" }
+            ]
+        }))
+    };
+
+    validate_workflow(&graph(runinator_models::json!([
+        { "kind": "free_disk", "path": "/var/dumps", "min_free_mb": 10240 },
+        { "kind": "writable", "path": "/var/dumps" },
+        { "kind": "reachable", "host": "warehouse.internal", "port": 5432 },
+        { "kind": "provider_version", "min": "2.1.0" }
+    ])))
+    .expect("well-formed checks validate");
+
+    assert!(matches!(
+        validate_workflow(&graph(runinator_models::json!([
+            { "kind": "reachable", "host": "warehouse.internal", "port": 0 }
+        ]))),
+        Err(WorkflowValidationError::InvalidPreflight { node, .. }) if node == "export"
+    ));
+    assert!(matches!(
+        validate_workflow(&graph(runinator_models::json!([
+            { "kind": "provider_version", "min": "latest" }
+        ]))),
+        Err(WorkflowValidationError::InvalidPreflight { node, .. }) if node == "export"
+    ));
+}
+
+#[test]
+fn action_cache_ttl_must_be_positive() {
+    let graph = |cache_ttl_seconds: i64| {
+        workflow(runinator_models::json!({
+            "start": "start",
+            "nodes": [
+                { "id": "start", "kind": "start", "transitions": { "next": { "$node": "export" } } },
+                {
+                    "id": "export",
+                    "kind": "action",
+                    "action": {
+                        "provider": "sql",
+                        "function": "dump_data",
+                        "cache_ttl_seconds": cache_ttl_seconds
+                    },
+                    "transitions": { "next": { "$node": "done" } }
+                },
+                { "id": "done", "kind": "end" }
+            ]
+        }))
+    };
+
+    validate_workflow(&graph(600)).expect("a positive cache window validates");
+    assert!(matches!(
+        validate_workflow(&graph(0)),
+        Err(WorkflowValidationError::InvalidCacheTtl(node)) if node == "export"
+    ));
+}
+
+#[test]
+fn definition_hash_covers_the_portable_definition_only() {
+    let graph = |timeout_seconds: i64| {
+        runinator_models::json!({
+            "start": "start",
+            "nodes": [
+                { "id": "start", "kind": "start", "transitions": { "next": { "$node": "build" } } },
+                { "id": "build", "kind": "action", "action": { "provider": "console", "function": "run", "timeout_seconds": timeout_seconds, "configuration": {} }, "transitions": { "on_success": { "$node": "done" } } },
+                { "id": "done", "kind": "end" }
+            ]
+        })
+    };
+    let stored = workflow(graph(60));
+    let hash = workflow_definition_hash(&stored);
+    assert!(hash.starts_with("sha256:"));
+
+    // the same file imported elsewhere: another id, org, toggle, and timestamps.
+    let mut elsewhere = workflow(graph(60));
+    elsewhere.org_id = Some(Uuid::now_v7());
+    elsewhere.enabled = false;
+    elsewhere.updated_at = chrono::DateTime::from_timestamp(1_700_000_000, 0);
+    assert_eq!(workflow_definition_hash(&elsewhere), hash);
+
+    assert_ne!(workflow_definition_hash(&workflow(graph(90))), hash);
+    let mut bumped = stored.clone();
+    bumped.version = runinator_models::semver::SemVer::new(1, 1, 0);
+    assert_ne!(workflow_definition_hash(&bumped), hash);
+}
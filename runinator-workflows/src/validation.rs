@@ -1,7 +1,8 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 
 use runinator_models::{
     providers::ProviderMetadata,
+    secret_refs::{collect_secret_refs, secret_allowed, validate_secret_pattern},
     types::RuninatorType,
     value::Value,
     workflows::{
@@ -108,6 +109,18 @@ pub fn validate_workflow(
         }
         validate_condition(&node.condition.to_value())?;
         validate_control_node_parameters(node)?;
+        validate_allowed_secrets(node)?;
+        validate_preflight(node)?;
+        let actions = [node.action.as_ref(), node.compensation.as_ref()];
+        if actions
+            .into_iter()
+            .flatten()
+            .any(|action| action.cache_ttl_seconds.is_some_and(|ttl| ttl <= 0))
+        {
+            return Err(WorkflowValidationError::InvalidCacheTtl(
+                node.id.as_str().to_string(),
+            ));
+        }
         for target in transition_targets(&node.transitions) {
             validate_node_ref(node, &target, NodeReferenceRole::Transition, &node_map)?;
         }
@@ -129,6 +142,61 @@ pub fn validate_workflow(
     Ok((start, nodes))
 }
 
+/// reject malformed `allowed_secrets` patterns and literal secret references they do not cover.
+/// references built at runtime are checked again by the worker before it fetches anything.
+fn validate_allowed_secrets(node: &WorkflowNode) -> Result<(), WorkflowValidationError> {
+    let actions = [
+        node.action.as_ref().map(|action| (action, true)),
+        node.compensation.as_ref().map(|action| (action, false)),
+    ];
+    for (action, reads_node_parameters) in actions.into_iter().flatten() {
+        if action.allowed_secrets.is_empty() {
+            continue;
+        }
+        for pattern in &action.allowed_secrets {
+            validate_secret_pattern(pattern).map_err(|message| {
+                WorkflowValidationError::InvalidSecretPattern {
+                    node: node.id.as_str().to_string(),
+                    message,
+                }
+            })?;
+        }
+        let mut refs = BTreeSet::new();
+        collect_secret_refs(action.configuration.as_value(), &mut refs);
+        if reads_node_parameters {
+            collect_secret_refs(node.parameters.as_value(), &mut refs);
+        }
+        if let Some(secret_ref) = refs
+            .iter()
+            .find(|secret_ref| !secret_allowed(&action.allowed_secrets, secret_ref))
+        {
+            return Err(WorkflowValidationError::SecretNotAllowed {
+                node: node.id.as_str().to_string(),
+                secret: secret_ref.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// reject action preflight checks that could never pass (an empty path or host, port 0).
+fn validate_preflight(node: &WorkflowNode) -> Result<(), WorkflowValidationError> {
+    let actions = [node.action.as_ref(), node.compensation.as_ref()];
+    for check in actions
+        .into_iter()
+        .flatten()
+        .flat_map(|action| &action.preflight)
+    {
+        check
+            .validate()
+            .map_err(|message| WorkflowValidationError::InvalidPreflight {
+                node: node.id.as_str().to_string(),
+                message,
+            })?;
+    }
+    Ok(())
+}
+
 #[derive(Debug, Clone, Copy)]
 enum NodeReferenceRole {
     Transition,
@@ -53,19 +53,21 @@ impl<T: DatabaseImpl> runinator_auth::CredentialStore for AuthState<T> {
 
 // ---- request gating ----
 
-/// paths reachable without a credential.
+/// paths reachable without a credential. `/triggers/{token}` carries its own credential in the path.
 fn is_public_path(path: &str) -> bool {
     matches!(
         path,
         "/health"
+            | "/healthz"
             | "/ready"
+            | "/readyz"
             | "/metrics"
             | "/openapi.json"
             | "/docs"
             | "/auth/config"
             | "/auth/login"
             | "/auth/refresh"
-    )
+    ) || path.starts_with("/triggers/")
 }
 
 /// pull a presented credential from `Authorization: Bearer …`, `X-Api-Key`, or `?token=` (the last
@@ -11,11 +11,13 @@ use runinator_models::auth::{
     AuthContext, Grant, Permission, PrincipalKind, PrincipalType, ResourceType,
 };
 use runinator_models::capabilities::Capability;
+use runinator_models::errors::SendableError;
 use runinator_models::orgs::OrgRole;
 use runinator_models::value::Value;
+use runinator_models::workflows::WorkflowDefinition;
 use uuid::Uuid;
 
-use crate::models::{ApiError, ApiResponse};
+use crate::models::{ApiError, ApiErrorCode, ApiResponse};
 
 type Reply = (StatusCode, Json<ApiResponse>);
 
@@ -23,6 +25,7 @@ fn forbidden() -> Reply {
     (
         StatusCode::FORBIDDEN,
         Json(ApiResponse::ApiError(ApiError::new(
+            ApiErrorCode::Forbidden,
             "insufficient permission for this resource",
         ))),
     )
@@ -31,7 +34,10 @@ fn forbidden() -> Reply {
 fn not_found() -> Reply {
     (
         StatusCode::NOT_FOUND,
-        Json(ApiResponse::ApiError(ApiError::new("resource not found"))),
+        Json(ApiResponse::ApiError(ApiError::new(
+            ApiErrorCode::NotFound,
+            "resource not found",
+        ))),
     )
 }
 
@@ -222,6 +228,25 @@ pub async fn visible_workflow_ids<T: DatabaseImpl>(
     Some(ids)
 }
 
+/// every saved workflow the caller can see: org-visible and, unless admin, among
+/// [`visible_workflow_ids`].
+pub async fn visible_workflows<T: DatabaseImpl>(
+    db: &T,
+    ctx: &AuthContext,
+) -> Result<Vec<WorkflowDefinition>, SendableError> {
+    let visible = visible_workflow_ids(db, ctx).await;
+    Ok(crate::repository::fetch_workflows(db)
+        .await?
+        .into_iter()
+        .filter(|workflow| org_visible(ctx, workflow.org_id))
+        .filter(|workflow| match (&visible, workflow.id) {
+            (Some(ids), Some(id)) => ids.contains(&id),
+            (Some(_), None) => false,
+            (None, id) => id.is_some(),
+        })
+        .collect())
+}
+
 /// stamp the creator as `own` on a freshly created workflow. a no-op for service/admin principals
 /// without a user id (nothing to own it).
 pub async fn grant_owner<T: DatabaseImpl>(db: &T, ctx: &AuthContext, workflow_id: Uuid) {
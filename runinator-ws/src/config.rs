@@ -10,6 +10,10 @@ pub(crate) struct CliArgs {
     #[arg(long, env = "RUNINATOR_PORT", default_value_t = 8080)]
     pub port: u16,
 
+    /// Port to serve the runinator.v1 gRPC API on, beside the HTTP port. Unset serves no gRPC.
+    #[arg(long, env = "RUNINATOR_GRPC_PORT")]
+    pub grpc_port: Option<u16>,
+
     /// Database backend to use. Also reads RUNINATOR_DATABASE.
     #[arg(
         long,
@@ -27,6 +31,37 @@ pub(crate) struct CliArgs {
     #[arg(long, env = "RUNINATOR_DATABASE_URL")]
     pub database_url: Option<String>,
 
+    /// Connection string for a read-only Postgres replica serving workflow lists, run history, and
+    /// stats. Reads fall back to the primary while it is unreachable. Also reads
+    /// RUNINATOR_DB_READ_URL.
+    #[arg(long, env = "RUNINATOR_DB_READ_URL")]
+    pub db_read_url: Option<String>,
+
+    /// Most pooled database connections. SQLite ignores it; its writes serialize anyway.
+    #[arg(long, env = "RUNINATOR_DB_MAX_CONNECTIONS", default_value_t = 20)]
+    pub db_max_connections: u32,
+
+    /// Seconds a request waits for a free pooled connection before failing.
+    #[arg(
+        long,
+        env = "RUNINATOR_DB_ACQUIRE_TIMEOUT_SECONDS",
+        default_value_t = 30
+    )]
+    pub db_acquire_timeout_seconds: u64,
+
+    /// Seconds an unused pooled connection stays open; 0 keeps idle connections open.
+    #[arg(long, env = "RUNINATOR_DB_IDLE_TIMEOUT_SECONDS", default_value_t = 600)]
+    pub db_idle_timeout_seconds: u64,
+
+    /// Prepared statements cached per pooled connection; 0 disables the cache, which some
+    /// connection poolers (e.g. PgBouncer in transaction mode) require.
+    #[arg(
+        long,
+        env = "RUNINATOR_DB_STATEMENT_CACHE_CAPACITY",
+        default_value_t = 100
+    )]
+    pub db_statement_cache_capacity: usize,
+
     /// Address to bind the gossip socket for service discovery
     #[arg(long, default_value = "0.0.0.0")]
     pub gossip_bind: String,
@@ -79,6 +114,10 @@ pub(crate) struct CliArgs {
     #[arg(long, default_value = "runinator-ws")]
     pub broker_client_id: String,
 
+    /// Shared secret used to mint bearer tokens for an auth-gated http broker
+    #[arg(long, env = "RUNINATOR_BROKER_AUTH_SECRET")]
+    pub broker_auth_secret: Option<String>,
+
     /// Stable address advertised to the replica list; in k8s this is the pod's headless-service DNS
     /// name so it stays resolvable across IP churn.
     #[arg(long, default_value = "")]
@@ -108,19 +147,53 @@ pub(crate) struct CliArgs {
     )]
     pub auth_refresh_ttl_seconds: i64,
 
-    /// Enable per-principal/per-IP rate limiting on the HTTP API. On by default; set to false to
-    /// disable. The unauthenticated auth endpoints carry a separate, always-on brute-force throttle.
+    /// Static API keys accepted as admin service credentials when authentication is enabled, for
+    /// bootstrap and automation without a database-issued key. Comma-separated. Also reads
+    /// RUNINATOR_AUTH_STATIC_API_KEYS.
+    #[arg(long, env = "RUNINATOR_AUTH_STATIC_API_KEYS", value_delimiter = ',')]
+    pub auth_static_api_keys: Vec<String>,
+
+    /// Enable per-principal, per-IP, and global rate limiting on the HTTP API. On by default; set to
+    /// false to disable. The unauthenticated auth endpoints carry a separate, always-on brute-force
+    /// throttle.
     #[arg(long, env = "RUNINATOR_RATE_LIMIT_ENABLED", default_value_t = true)]
     pub rate_limit_enabled: bool,
 
-    /// Sustained requests per second allowed per principal/IP (token-bucket refill rate).
+    /// Sustained requests per second allowed per authenticated principal (token-bucket refill rate).
     #[arg(long, env = "RUNINATOR_RATE_LIMIT_RPS", default_value_t = 50.0)]
     pub rate_limit_rps: f64,
 
-    /// Maximum burst capacity per principal/IP (token-bucket size).
+    /// Maximum burst capacity per authenticated principal (token-bucket size).
     #[arg(long, env = "RUNINATOR_RATE_LIMIT_BURST", default_value_t = 100.0)]
     pub rate_limit_burst: f64,
 
+    /// Sustained requests per second allowed per client IP for requests without a principal.
+    #[arg(long, env = "RUNINATOR_RATE_LIMIT_IP_RPS", default_value_t = 100.0)]
+    pub rate_limit_ip_rps: f64,
+
+    /// Maximum burst capacity per client IP.
+    #[arg(long, env = "RUNINATOR_RATE_LIMIT_IP_BURST", default_value_t = 200.0)]
+    pub rate_limit_ip_burst: f64,
+
+    /// Header a trusted reverse proxy sets to the client's address, e.g. X-Forwarded-For. Its
+    /// rightmost entry keys anonymous requests. Set it only when every request reaches the API
+    /// through that proxy; unset, the connection's peer address is used.
+    #[arg(long, env = "RUNINATOR_RATE_LIMIT_CLIENT_IP_HEADER")]
+    pub rate_limit_client_ip_header: Option<String>,
+
+    /// Sustained requests per second this replica accepts from all clients together. 0 disables
+    /// the global bucket.
+    #[arg(long, env = "RUNINATOR_RATE_LIMIT_GLOBAL_RPS", default_value_t = 500.0)]
+    pub rate_limit_global_rps: f64,
+
+    /// Maximum burst capacity of the global bucket.
+    #[arg(
+        long,
+        env = "RUNINATOR_RATE_LIMIT_GLOBAL_BURST",
+        default_value_t = 1000.0
+    )]
+    pub rate_limit_global_burst: f64,
+
     /// Enable global overload protection (a concurrency cap + per-request timeout) on the HTTP API.
     /// On by default; set to false to disable both the concurrency limit and the request timeout.
     #[arg(
@@ -138,10 +211,58 @@ pub(crate) struct CliArgs {
     #[arg(long, env = "RUNINATOR_REQUEST_TIMEOUT_SECONDS", default_value_t = 30)]
     pub request_timeout_seconds: u64,
 
+    /// Seconds in-flight requests get to finish on shutdown once new connections stop being
+    /// accepted; whatever is still running afterwards is dropped.
+    #[arg(
+        long,
+        env = "RUNINATOR_WS_SHUTDOWN_GRACE_SECONDS",
+        default_value_t = 30
+    )]
+    pub shutdown_grace_seconds: u64,
+
+    /// Bind the HTTP port with SO_REUSEPORT so a new replica can start listening before the old one
+    /// stops. The old replica then drains its in-flight requests while the new one already takes
+    /// connections. Unix only; ignored with a warning elsewhere.
+    #[arg(long, env = "RUNINATOR_WS_REUSE_PORT", default_value_t = false)]
+    pub reuse_port: bool,
+
+    /// Origins browsers may call the HTTP API from, e.g. `https://dash.example.com`. Comma-separated;
+    /// `*` allows any origin.
+    #[arg(
+        long,
+        env = "RUNINATOR_CORS_ALLOWED_ORIGINS",
+        value_delimiter = ',',
+        default_value = "*"
+    )]
+    pub cors_allowed_origins: Vec<String>,
+
+    /// HTTP methods allowed on cross-origin requests. Comma-separated; `*` allows any method.
+    #[arg(
+        long,
+        env = "RUNINATOR_CORS_ALLOWED_METHODS",
+        value_delimiter = ',',
+        default_value = "*"
+    )]
+    pub cors_allowed_methods: Vec<String>,
+
+    /// Request headers allowed on cross-origin requests. Comma-separated; `*` allows any header.
+    #[arg(
+        long,
+        env = "RUNINATOR_CORS_ALLOWED_HEADERS",
+        value_delimiter = ',',
+        default_value = "*"
+    )]
+    pub cors_allowed_headers: Vec<String>,
+
     /// Run the durable orchestration engine (reducer, wake/trigger/action/ingress loops, result
     /// consumer, maintenance backstops) in-process. On by default so the single-process local/dev
     /// stack runs unchanged; set to false when a standalone `runinator-background-worker` owns the
     /// engine and this replica serves HTTP/WebSocket only.
     #[arg(long, env = "RUNINATOR_WS_RUN_ENGINE", default_value_t = true)]
     pub run_engine: bool,
+
+    /// Serve the interactive API reference at /docs. The raw OpenAPI document at /openapi.json is
+    /// always served so clients can be generated from it.
+    #[arg(long, env = "RUNINATOR_API_DOCS_ENABLED", default_value_t = true)]
+    pub api_docs_enabled: bool,
 }
@@ -0,0 +1,109 @@
+//! cross-origin policy for the http api, so a browser dashboard served from another origin can call
+//! it directly. each list is either `*` (any) or explicit values; the default allows everything,
+//! which is what the api shipped with before the policy was configurable. the api authenticates with
+//! bearer tokens rather than cookies, so credentialed requests are never allowed.
+
+use axum::http::{HeaderName, HeaderValue, Method};
+use runinator_models::errors::SendableError;
+use tower_http::cors::{AllowHeaders, AllowMethods, AllowOrigin, Any, CorsLayer};
+
+#[cfg(test)]
+#[path = "cors_tests.rs"]
+mod tests;
+
+const WILDCARD: &str = "*";
+
+/// parsed cross-origin policy applied to every route.
+#[derive(Debug, Clone)]
+pub struct CorsConfig {
+    origins: AllowOrigin,
+    methods: AllowMethods,
+    headers: AllowHeaders,
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        Self {
+            origins: Any.into(),
+            methods: Any.into(),
+            headers: Any.into(),
+        }
+    }
+}
+
+impl CorsConfig {
+    /// parse comma-split configuration lists. blank entries are ignored and an empty list or one
+    /// containing `*` allows any value; anything unparseable is a startup error.
+    pub fn parse(
+        origins: &[String],
+        methods: &[String],
+        headers: &[String],
+    ) -> Result<Self, SendableError> {
+        let origins = match explicit(origins) {
+            None => Any.into(),
+            Some(origins) => AllowOrigin::list(
+                origins
+                    .into_iter()
+                    .map(|origin| {
+                        HeaderValue::from_str(origin.trim_end_matches('/'))
+                            .map_err(|_| invalid("origin", origin))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?,
+            ),
+        };
+        let methods = match explicit(methods) {
+            None => Any.into(),
+            Some(methods) => AllowMethods::list(
+                methods
+                    .into_iter()
+                    .map(|method| {
+                        Method::from_bytes(method.to_ascii_uppercase().as_bytes())
+                            .map_err(|_| invalid("method", method))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?,
+            ),
+        };
+        let headers = match explicit(headers) {
+            None => Any.into(),
+            Some(headers) => AllowHeaders::list(
+                headers
+                    .into_iter()
+                    .map(|header| {
+                        HeaderName::from_bytes(header.as_bytes())
+                            .map_err(|_| invalid("header", header))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?,
+            ),
+        };
+        Ok(Self {
+            origins,
+            methods,
+            headers,
+        })
+    }
+
+    pub(crate) fn layer(&self) -> CorsLayer {
+        CorsLayer::new()
+            .allow_origin(self.origins.clone())
+            .allow_methods(self.methods.clone())
+            .allow_headers(self.headers.clone())
+            .expose_headers(Any)
+    }
+}
+
+// the trimmed, non-blank entries, or `None` when the list allows anything.
+fn explicit(values: &[String]) -> Option<Vec<&str>> {
+    let values: Vec<&str> = values
+        .iter()
+        .map(|value| value.trim())
+        .filter(|value| !value.is_empty())
+        .collect();
+    if values.is_empty() || values.contains(&WILDCARD) {
+        return None;
+    }
+    Some(values)
+}
+
+fn invalid(kind: &str, value: &str) -> SendableError {
+    crate::errors::INVALID_CORS_CONFIG.error(format!("invalid {kind} '{value}'"))
+}
@@ -0,0 +1,71 @@
+use axum::{
+    Router,
+    body::Body,
+    http::{Method, Request, header},
+    routing::get,
+};
+use tower::ServiceExt;
+
+use super::CorsConfig;
+
+fn list(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+// send a preflight for `origin` through a router carrying `config`'s layer.
+async fn preflight(config: &CorsConfig, origin: &str) -> axum::http::HeaderMap {
+    let router = Router::new()
+        .route("/workflows", get(|| async { "ok" }))
+        .layer(config.layer());
+    let response = router
+        .oneshot(
+            Request::builder()
+                .method(Method::OPTIONS)
+                .uri("/workflows")
+                .header(header::ORIGIN, origin)
+                .header(header::ACCESS_CONTROL_REQUEST_METHOD, "GET")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    response.headers().clone()
+}
+
+#[tokio::test]
+async fn default_policy_allows_any_origin() {
+    let headers = preflight(&CorsConfig::default(), "https://dash.example.com").await;
+    assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_ORIGIN], "*");
+}
+
+#[tokio::test]
+async fn explicit_origins_are_allowed_and_others_refused() {
+    let config = CorsConfig::parse(
+        &list(&["https://dash.example.com/", " "]),
+        &list(&["get", "POST"]),
+        &list(&["authorization", "content-type"]),
+    )
+    .unwrap();
+
+    let headers = preflight(&config, "https://dash.example.com").await;
+    assert_eq!(
+        headers[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+        "https://dash.example.com"
+    );
+    assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_METHODS], "GET,POST");
+    assert_eq!(
+        headers[header::ACCESS_CONTROL_ALLOW_HEADERS],
+        "authorization,content-type"
+    );
+
+    let headers = preflight(&config, "https://evil.example.com").await;
+    assert!(headers.get(header::ACCESS_CONTROL_ALLOW_ORIGIN).is_none());
+}
+
+#[test]
+fn a_wildcard_entry_allows_anything_and_bad_values_are_rejected() {
+    assert!(CorsConfig::parse(&list(&["https://a.example.com", "*"]), &[], &[]).is_ok());
+    assert!(CorsConfig::parse(&list(&["bad\norigin"]), &[], &[]).is_err());
+    assert!(CorsConfig::parse(&[], &list(&["GE T"]), &[]).is_err());
+    assert!(CorsConfig::parse(&[], &[], &list(&["bad header"])).is_err());
+}
@@ -30,6 +30,13 @@ pub const BROKER_KAFKA: ErrorDescriptor =
 pub const BROKER_RABBITMQ: ErrorDescriptor =
     ErrorDescriptor::new("RUNI176", "ws.broker.rabbitmq", "RabbitMQ broker error");
 
+// server configuration.
+pub const INVALID_CORS_CONFIG: ErrorDescriptor = ErrorDescriptor::new(
+    "RUNI175",
+    "ws.cors.invalid_config",
+    "Invalid CORS configuration",
+);
+
 pub const DICTIONARY: &[ErrorDescriptor] = &[
     BROKER_WORKFLOW_RESULTS,
     BROKER_INVALID_ENDPOINT,
@@ -37,6 +44,7 @@ pub const DICTIONARY: &[ErrorDescriptor] = &[
     BROKER_UNKNOWN_BACKEND,
     BROKER_KAFKA,
     BROKER_RABBITMQ,
+    INVALID_CORS_CONFIG,
 ];
 
 /// web service engine error dictionary.
@@ -58,6 +58,10 @@ pub(crate) fn nudge_wake_publisher(events: &EventSender) {
     events.publisher.nudge_wake_publisher();
 }
 
+pub(crate) fn nudge_action_dispatch_publisher(events: &EventSender) {
+    events.publisher.nudge_action_dispatch_publisher();
+}
+
 pub(crate) fn emit_task_run(
     events: &EventSender,
     run_id: Uuid,
@@ -0,0 +1,459 @@
+//! read-only graphql over the dashboard's data: visible workflows, their newest runs, the replicas
+//! that executed those runs, live workers, and provider run aggregates. a dashboard asks for exactly
+//! the fields it renders (`workflows { name runs(last: 10) { status executors { displayName } } }`)
+//! in one round trip.
+//!
+//! nested lists resolve through per-request data loaders, so a level of the query costs one read
+//! however many parents it spans: every workflow's runs come from one windowed query and every
+//! run's executors from one read of their node runs.
+
+use std::{collections::HashMap, marker::PhantomData, sync::Arc};
+
+use async_graphql::{
+    Context, EmptyMutation, EmptySubscription, Error, Object, Schema, SimpleObject,
+    dataloader::{DataLoader, Loader},
+};
+use axum::{Extension, Json};
+use chrono::{DateTime, Duration, Utc};
+use runinator_database::interfaces::DatabaseImpl;
+use runinator_models::{
+    auth::AuthContext,
+    overview::{DEFAULT_OVERVIEW_RUNS, MAX_OVERVIEW_RUNS},
+    provider_stats::{ActionStats, ProviderStats, ProviderStatsWindow, RunCounts},
+    replicas::{ReplicaKind, ReplicaRecord},
+    workflows::{WorkflowDefinition, WorkflowRun},
+};
+use uuid::Uuid;
+
+use crate::{authz, handlers::providers, repository};
+
+/// deepest selection nesting a query may use; the schema itself is four levels deep.
+const MAX_QUERY_DEPTH: usize = 8;
+
+pub(crate) type DashboardSchema<T> = Schema<QueryRoot<T>, EmptyMutation, EmptySubscription>;
+
+pub(crate) fn schema<T: DatabaseImpl>() -> DashboardSchema<T> {
+    Schema::build(QueryRoot(PhantomData), EmptyMutation, EmptySubscription)
+        .limit_depth(MAX_QUERY_DEPTH)
+        .finish()
+}
+
+/// execute a graphql request as the caller, with loaders scoped to this request.
+pub(crate) async fn graphql<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+    Extension(schema): Extension<DashboardSchema<T>>,
+    Extension(ctx): Extension<AuthContext>,
+    Json(request): Json<async_graphql::Request>,
+) -> Json<async_graphql::Response> {
+    let request = request
+        .data(ctx)
+        .data(DataLoader::new(
+            RecentRunsLoader { db: db.clone() },
+            tokio::spawn,
+        ))
+        .data(DataLoader::new(
+            RunExecutorsLoader { db: db.clone() },
+            tokio::spawn,
+        ))
+        .data(db);
+    Json(schema.execute(request).await)
+}
+
+fn repository_failure(err: runinator_models::errors::SendableError) -> Error {
+    Error::new(err.to_string())
+}
+
+pub(crate) struct QueryRoot<T>(PhantomData<T>);
+
+#[Object(name = "Query")]
+impl<T: DatabaseImpl> QueryRoot<T> {
+    /// every workflow visible to the caller, by name.
+    async fn workflows(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<Workflow<T>>> {
+        let db = ctx.data::<Arc<T>>()?;
+        let auth = ctx.data::<AuthContext>()?;
+        let workflows = authz::visible_workflows(db.as_ref(), auth)
+            .await
+            .map_err(repository_failure)?;
+        Ok(workflows.into_iter().filter_map(Workflow::new).collect())
+    }
+
+    /// one visible workflow, or null when it does not exist or the caller cannot see it.
+    async fn workflow(
+        &self,
+        ctx: &Context<'_>,
+        id: Uuid,
+    ) -> async_graphql::Result<Option<Workflow<T>>> {
+        let db = ctx.data::<Arc<T>>()?;
+        let auth = ctx.data::<AuthContext>()?;
+        let Some(workflow) = repository::fetch_workflow(db.as_ref(), id)
+            .await
+            .map_err(repository_failure)?
+        else {
+            return Ok(None);
+        };
+        if !authz::org_visible(auth, workflow.org_id) {
+            return Ok(None);
+        }
+        if let Some(visible) = authz::visible_workflow_ids(db.as_ref(), auth).await
+            && !visible.contains(&id)
+        {
+            return Ok(None);
+        }
+        Ok(Workflow::new(workflow))
+    }
+
+    /// registered worker replicas, most recently seen first.
+    async fn workers(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<Replica>> {
+        let db = ctx.data::<Arc<T>>()?;
+        let listing = repository::fetch_replicas(db.as_ref(), Some(ReplicaKind::Worker), None)
+            .await
+            .map_err(repository_failure)?;
+        Ok(listing.replicas.iter().map(Replica::from).collect())
+    }
+
+    /// node-run totals per provider and action over the last `hours` (default a day, at most 30
+    /// days), across the caller's visible workflows.
+    async fn provider_stats(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 24)] hours: i64,
+    ) -> async_graphql::Result<ProviderWindow> {
+        if hours <= 0 {
+            return Err(Error::new("hours must be a positive whole number"));
+        }
+        let hours = hours.min(providers::MAX_PROVIDER_STATS_WINDOW_HOURS);
+        let db = ctx.data::<Arc<T>>()?;
+        let auth = ctx.data::<AuthContext>()?;
+        let actions = providers::visible_task_actions(db.as_ref(), auth)
+            .await
+            .map_err(repository_failure)?;
+        let since = Utc::now() - Duration::hours(hours);
+        let counts = repository::fetch_node_run_counts(db.as_ref(), since)
+            .await
+            .map_err(repository_failure)?;
+        Ok(ProviderStatsWindow::build(hours, since, &counts, &actions).into())
+    }
+}
+
+pub(crate) struct Workflow<T> {
+    id: Uuid,
+    definition: WorkflowDefinition,
+    _db: PhantomData<T>,
+}
+
+impl<T> Workflow<T> {
+    fn new(definition: WorkflowDefinition) -> Option<Self> {
+        Some(Workflow {
+            id: definition.id?,
+            definition,
+            _db: PhantomData,
+        })
+    }
+}
+
+#[Object(name = "Workflow")]
+impl<T: DatabaseImpl> Workflow<T> {
+    async fn id(&self) -> Uuid {
+        self.id
+    }
+
+    async fn name(&self) -> &str {
+        &self.definition.name
+    }
+
+    async fn namespace(&self) -> Option<&str> {
+        self.definition.namespace.as_deref()
+    }
+
+    async fn org_id(&self) -> Option<Uuid> {
+        self.definition.org_id
+    }
+
+    async fn version(&self) -> String {
+        self.definition.version.to_string()
+    }
+
+    async fn enabled(&self) -> bool {
+        self.definition.enabled
+    }
+
+    /// the newest `last` runs (default 10, at most 50), newest first.
+    async fn runs(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 10)] last: i32,
+    ) -> async_graphql::Result<Vec<Run<T>>> {
+        let key = RecentRunsKey {
+            workflow_id: self.id,
+            last: usize::try_from(last.max(1))
+                .unwrap_or(DEFAULT_OVERVIEW_RUNS)
+                .min(MAX_OVERVIEW_RUNS),
+        };
+        let runs = ctx
+            .data::<DataLoader<RecentRunsLoader<T>>>()?
+            .load_one(key)
+            .await?
+            .unwrap_or_default();
+        Ok(runs.into_iter().map(Run::new).collect())
+    }
+}
+
+pub(crate) struct Run<T> {
+    run: WorkflowRun,
+    _db: PhantomData<T>,
+}
+
+impl<T> Run<T> {
+    fn new(run: WorkflowRun) -> Self {
+        Run {
+            run,
+            _db: PhantomData,
+        }
+    }
+}
+
+#[Object(name = "Run")]
+impl<T: DatabaseImpl> Run<T> {
+    async fn id(&self) -> Uuid {
+        self.run.id
+    }
+
+    async fn workflow_id(&self) -> Uuid {
+        self.run.workflow_id
+    }
+
+    async fn name(&self) -> Option<&str> {
+        self.run.name.as_deref()
+    }
+
+    async fn status(&self) -> &'static str {
+        self.run.status.as_str()
+    }
+
+    async fn message(&self) -> Option<&str> {
+        self.run.message.as_deref()
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.run.created_at
+    }
+
+    async fn started_at(&self) -> Option<DateTime<Utc>> {
+        self.run.started_at
+    }
+
+    async fn finished_at(&self) -> Option<DateTime<Utc>> {
+        self.run.finished_at
+    }
+
+    /// distinct replicas that executed the run's nodes, in node order.
+    async fn executors(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<Replica>> {
+        Ok(ctx
+            .data::<DataLoader<RunExecutorsLoader<T>>>()?
+            .load_one(self.run.id)
+            .await?
+            .unwrap_or_default())
+    }
+}
+
+/// a replica. only `replicaId` is set for an executor whose record has since been pruned.
+#[derive(SimpleObject, Clone)]
+pub(crate) struct Replica {
+    replica_id: Uuid,
+    replica_type: Option<&'static str>,
+    display_name: Option<String>,
+    status: Option<&'static str>,
+    host: Option<String>,
+    version: Option<String>,
+    last_seen_at: Option<DateTime<Utc>>,
+}
+
+impl From<&ReplicaRecord> for Replica {
+    fn from(record: &ReplicaRecord) -> Self {
+        Replica {
+            replica_id: record.replica_id,
+            replica_type: Some(record.replica_type.as_str()),
+            display_name: record.display_name.clone(),
+            status: Some(record.status.as_str()),
+            host: record.host.clone(),
+            version: record.version.clone(),
+            last_seen_at: Some(record.last_seen_at),
+        }
+    }
+}
+
+impl Replica {
+    fn pruned(replica_id: Uuid) -> Self {
+        Replica {
+            replica_id,
+            replica_type: None,
+            display_name: None,
+            status: None,
+            host: None,
+            version: None,
+            last_seen_at: None,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub(crate) struct ProviderWindow {
+    hours: i64,
+    since: DateTime<Utc>,
+    /// providers, most runs first.
+    providers: Vec<ProviderAggregate>,
+}
+
+#[derive(SimpleObject)]
+pub(crate) struct ProviderAggregate {
+    provider: String,
+    counts: RunTotals,
+    /// the provider's actions, most runs first.
+    actions: Vec<ActionAggregate>,
+}
+
+#[derive(SimpleObject)]
+pub(crate) struct ActionAggregate {
+    function: String,
+    counts: RunTotals,
+}
+
+#[derive(SimpleObject)]
+pub(crate) struct RunTotals {
+    /// distinct tasks (workflow nodes) that ran in the window.
+    tasks: i64,
+    runs: i64,
+    succeeded: i64,
+    failed: i64,
+    /// mean wall time of the runs that finished; null when none did.
+    average_duration_ms: Option<i64>,
+}
+
+impl From<ProviderStatsWindow> for ProviderWindow {
+    fn from(window: ProviderStatsWindow) -> Self {
+        ProviderWindow {
+            hours: window.hours,
+            since: window.since,
+            providers: window.providers.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<ProviderStats> for ProviderAggregate {
+    fn from(stats: ProviderStats) -> Self {
+        ProviderAggregate {
+            provider: stats.provider,
+            counts: stats.counts.into(),
+            actions: stats.actions.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<ActionStats> for ActionAggregate {
+    fn from(stats: ActionStats) -> Self {
+        ActionAggregate {
+            function: stats.function,
+            counts: stats.counts.into(),
+        }
+    }
+}
+
+impl From<RunCounts> for RunTotals {
+    fn from(counts: RunCounts) -> Self {
+        RunTotals {
+            tasks: counts.tasks,
+            runs: counts.runs,
+            succeeded: counts.succeeded,
+            failed: counts.failed,
+            average_duration_ms: counts.average_duration_ms,
+        }
+    }
+}
+
+/// a workflow's newest `last` runs. runs are batched per distinct `last`, one query each.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct RecentRunsKey {
+    workflow_id: Uuid,
+    last: usize,
+}
+
+pub(crate) struct RecentRunsLoader<T> {
+    db: Arc<T>,
+}
+
+impl<T: DatabaseImpl> Loader<RecentRunsKey> for RecentRunsLoader<T> {
+    type Value = Vec<WorkflowRun>;
+    type Error = Error;
+
+    async fn load(
+        &self,
+        keys: &[RecentRunsKey],
+    ) -> Result<HashMap<RecentRunsKey, Self::Value>, Self::Error> {
+        let mut by_last: HashMap<usize, Vec<Uuid>> = HashMap::new();
+        for key in keys {
+            by_last.entry(key.last).or_default().push(key.workflow_id);
+        }
+        // every requested workflow answers, with no runs when it has none.
+        let mut loaded = keys
+            .iter()
+            .map(|key| (*key, Vec::new()))
+            .collect::<HashMap<_, _>>();
+        for (last, workflow_ids) in by_last {
+            let runs = repository::fetch_recent_workflow_runs_for_workflows(
+                self.db.as_ref(),
+                workflow_ids,
+                last,
+            )
+            .await
+            .map_err(repository_failure)?;
+            for run in runs {
+                let key = RecentRunsKey {
+                    workflow_id: run.workflow_id,
+                    last,
+                };
+                loaded.entry(key).or_default().push(run);
+            }
+        }
+        Ok(loaded)
+    }
+}
+
+pub(crate) struct RunExecutorsLoader<T> {
+    db: Arc<T>,
+}
+
+impl<T: DatabaseImpl> Loader<Uuid> for RunExecutorsLoader<T> {
+    type Value = Vec<Replica>;
+    type Error = Error;
+
+    async fn load(&self, run_ids: &[Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
+        let executors = repository::fetch_run_executors(self.db.as_ref(), run_ids.to_vec())
+            .await
+            .map_err(repository_failure)?;
+        if executors.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let replicas = repository::fetch_replicas(self.db.as_ref(), None, None)
+            .await
+            .map_err(repository_failure)?
+            .replicas
+            .iter()
+            .map(|record| (record.replica_id, Replica::from(record)))
+            .collect::<HashMap<_, _>>();
+        Ok(executors
+            .into_iter()
+            .map(|(run_id, ids)| {
+                let resolved = ids
+                    .into_iter()
+                    .map(|id| {
+                        replicas
+                            .get(&id)
+                            .cloned()
+                            .unwrap_or_else(|| Replica::pruned(id))
+                    })
+                    .collect();
+                (run_id, resolved)
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,324 @@
+//! the runinator.v1 gRPC service (contract in runinator-models) for internal callers that prefer
+//! gRPC or want a streamed event feed. each call runs the REST handler for the same operation, so
+//! authorization, validation, protection holds, and emitted events match the REST API exactly;
+//! this module only translates messages and maps the handler's reply onto a gRPC status.
+
+use std::{pin::Pin, sync::Arc};
+
+use axum::{
+    Extension, Json,
+    extract::{Path, Query},
+    http::{HeaderMap, StatusCode},
+    middleware::from_fn_with_state,
+};
+use futures::{Stream, stream};
+use runinator_database::interfaces::DatabaseImpl;
+use runinator_models::{
+    auth::AuthContext,
+    grpc::{
+        self,
+        runinator_server::{Runinator, RuninatorServer},
+    },
+    runs::NewRunChunk,
+    workflows::WorkflowDefinition,
+};
+use tokio::sync::broadcast::error::RecvError;
+use tonic::{Request, Response, Status};
+use uuid::Uuid;
+
+use crate::{
+    auth::{AuthState, auth_middleware},
+    authz,
+    events::{AppEvent, AppEventKind, EventSender},
+    handlers::{runs, workflows},
+    models::{ApiErrorCode, ApiResponse},
+};
+
+/// the gRPC service behind the shared auth middleware, which resolves the same `Authorization`,
+/// `x-api-key`, and `x-org-id` metadata a REST request carries.
+pub(crate) fn router<T: DatabaseImpl>(
+    db: Arc<T>,
+    events: EventSender,
+    auth: AuthState<T>,
+) -> axum::Router {
+    tonic::service::Routes::new(RuninatorServer::new(GrpcService { db, events }))
+        .into_axum_router()
+        .layer(from_fn_with_state(auth, auth_middleware::<T>))
+}
+
+pub(crate) struct GrpcService<T: DatabaseImpl> {
+    pub(crate) db: Arc<T>,
+    pub(crate) events: EventSender,
+}
+
+type Reply = (StatusCode, Json<ApiResponse>);
+
+type EventStream = Pin<Box<dyn Stream<Item = Result<grpc::Event, Status>> + Send>>;
+
+#[tonic::async_trait]
+impl<T: DatabaseImpl> Runinator for GrpcService<T> {
+    async fn list_workflows(
+        &self,
+        request: Request<grpc::ListWorkflowsRequest>,
+    ) -> Result<Response<grpc::ListWorkflowsResponse>, Status> {
+        let ctx = auth_context(&request)?;
+        let query = workflows::WorkflowQuery {
+            name: request.into_inner().name,
+        };
+        let reply =
+            workflows::get_workflows(Extension(self.db.clone()), Extension(ctx), Query(query))
+                .await;
+        let found = match into_result(reply)? {
+            ApiResponse::WorkflowList(found) => found,
+            ApiResponse::Workflow(workflow) => vec![workflow],
+            _ => return Err(unexpected_reply()),
+        };
+        let workflows = found
+            .iter()
+            .map(workflow_message)
+            .collect::<Result<_, _>>()?;
+        Ok(Response::new(grpc::ListWorkflowsResponse { workflows }))
+    }
+
+    async fn get_workflow(
+        &self,
+        request: Request<grpc::GetWorkflowRequest>,
+    ) -> Result<Response<grpc::Workflow>, Status> {
+        let ctx = auth_context(&request)?;
+        let id = parse_id("id", &request.get_ref().id)?;
+        let reply =
+            workflows::get_workflow(Extension(self.db.clone()), Extension(ctx), Path(id)).await;
+        workflow_response(reply)
+    }
+
+    async fn upsert_workflow(
+        &self,
+        request: Request<grpc::UpsertWorkflowRequest>,
+    ) -> Result<Response<grpc::Workflow>, Status> {
+        let ctx = auth_context(&request)?;
+        let workflow = request
+            .into_inner()
+            .workflow
+            .ok_or_else(|| Status::invalid_argument("workflow is required"))?;
+        let workflow = WorkflowDefinition::try_from(workflow).map_err(Status::invalid_argument)?;
+        let reply = workflows::upsert_workflow(
+            Extension(self.db.clone()),
+            Extension(self.events.clone()),
+            Extension(ctx),
+            HeaderMap::new(),
+            Json(workflow),
+        )
+        .await;
+        workflow_response(reply)
+    }
+
+    async fn delete_workflow(
+        &self,
+        request: Request<grpc::DeleteWorkflowRequest>,
+    ) -> Result<Response<grpc::DeleteWorkflowResponse>, Status> {
+        let ctx = auth_context(&request)?;
+        let id = parse_id("id", &request.get_ref().id)?;
+        let reply =
+            workflows::delete_workflow(Extension(self.db.clone()), Extension(ctx), Path(id)).await;
+        into_result(reply)?;
+        Ok(Response::new(grpc::DeleteWorkflowResponse {}))
+    }
+
+    async fn append_run_chunk(
+        &self,
+        request: Request<grpc::AppendRunChunkRequest>,
+    ) -> Result<Response<grpc::RunChunk>, Status> {
+        let ctx = auth_context(&request)?;
+        let request = request.into_inner();
+        let run_id = parse_id("run_id", &request.run_id)?;
+        let chunk = NewRunChunk {
+            stream: request.stream,
+            content: request.content,
+        };
+        let reply = runs::append_run_chunk(
+            Extension(self.db.clone()),
+            Extension(self.events.clone()),
+            Extension(ctx),
+            Path(run_id),
+            Json(chunk),
+        )
+        .await;
+        match into_result(reply)? {
+            ApiResponse::RunChunks(chunks) => chunks
+                .first()
+                .map(|chunk| Response::new(chunk.into()))
+                .ok_or_else(unexpected_reply),
+            _ => Err(unexpected_reply()),
+        }
+    }
+
+    async fn list_run_chunks(
+        &self,
+        request: Request<grpc::ListRunChunksRequest>,
+    ) -> Result<Response<grpc::ListRunChunksResponse>, Status> {
+        let ctx = auth_context(&request)?;
+        let request = request.into_inner();
+        let run_id = parse_id("run_id", &request.run_id)?;
+        let query = runs::ChunkQuery {
+            cursor: request.cursor,
+            limit: request.limit,
+        };
+        let reply = runs::get_run_chunks(
+            Extension(self.db.clone()),
+            Extension(ctx),
+            Path(run_id),
+            Query(query),
+        )
+        .await;
+        match into_result(reply)? {
+            ApiResponse::RunChunks(chunks) => Ok(Response::new(grpc::ListRunChunksResponse {
+                chunks: chunks.iter().map(Into::into).collect(),
+            })),
+            _ => Err(unexpected_reply()),
+        }
+    }
+
+    type WatchEventsStream = EventStream;
+
+    // the feed `/ws/events` pushes: the same local broadcast, filtered to the caller's org. a
+    // subscriber that falls behind gets a `resync` event in place of the ones it missed.
+    async fn watch_events(
+        &self,
+        request: Request<grpc::WatchEventsRequest>,
+    ) -> Result<Response<Self::WatchEventsStream>, Status> {
+        let ctx = auth_context(&request)?;
+        let types = request.into_inner().types;
+        let receiver = self.events.subscribe();
+        let events = stream::unfold(receiver, move |mut receiver| {
+            let ctx = ctx.clone();
+            let types = types.clone();
+            async move {
+                loop {
+                    let event = match receiver.recv().await {
+                        Ok(event) if authz::org_visible(&ctx, event.org_id) => {
+                            event_message(&event)
+                        }
+                        Ok(_) => continue,
+                        Err(RecvError::Lagged(missed)) => {
+                            log::warn!("gRPC event watcher lagged, missed {} events", missed);
+                            grpc::Event {
+                                r#type: "resync".into(),
+                                ..Default::default()
+                            }
+                        }
+                        Err(RecvError::Closed) => return None,
+                    };
+                    if types.is_empty() || types.contains(&event.r#type) {
+                        return Some((Ok(event), receiver));
+                    }
+                }
+            }
+        });
+        Ok(Response::new(Box::pin(events)))
+    }
+}
+
+// the principal the auth middleware resolved for this call.
+fn auth_context<M>(request: &Request<M>) -> Result<AuthContext, Status> {
+    request
+        .extensions()
+        .get::<AuthContext>()
+        .cloned()
+        .ok_or_else(|| Status::unauthenticated("missing credential"))
+}
+
+fn parse_id(field: &str, raw: &str) -> Result<Uuid, Status> {
+    Uuid::parse_str(raw.trim())
+        .map_err(|err| Status::invalid_argument(format!("{field} is not a uuid: {err}")))
+}
+
+fn workflow_message(workflow: &WorkflowDefinition) -> Result<grpc::Workflow, Status> {
+    grpc::Workflow::try_from(workflow).map_err(Status::internal)
+}
+
+fn workflow_response(reply: Reply) -> Result<Response<grpc::Workflow>, Status> {
+    match into_result(reply)? {
+        ApiResponse::Workflow(workflow) => workflow_message(&workflow).map(Response::new),
+        _ => Err(unexpected_reply()),
+    }
+}
+
+// the handler's success body, or its error as the nearest gRPC status.
+fn into_result((status, Json(body)): Reply) -> Result<ApiResponse, Status> {
+    match body {
+        ApiResponse::ApiError(err) => Err(Status::new(status_code(err.code), err.message)),
+        // a protected workflow's change waits for a second approver instead of applying.
+        ApiResponse::PendingWorkflowChange(change) => Err(Status::failed_precondition(format!(
+            "workflow {} is protected; the change is held for approval as pending change {}",
+            change.workflow_id, change.id
+        ))),
+        body if status.is_success() => Ok(body),
+        _ => Err(Status::internal(format!("request failed with {status}"))),
+    }
+}
+
+fn unexpected_reply() -> Status {
+    Status::internal("unexpected reply from the request handler")
+}
+
+fn status_code(code: ApiErrorCode) -> tonic::Code {
+    match code {
+        ApiErrorCode::NotFound => tonic::Code::NotFound,
+        ApiErrorCode::ValidationFailed => tonic::Code::InvalidArgument,
+        ApiErrorCode::Conflict => tonic::Code::Aborted,
+        ApiErrorCode::Unauthorized => tonic::Code::Unauthenticated,
+        ApiErrorCode::Forbidden => tonic::Code::PermissionDenied,
+        ApiErrorCode::RateLimited => tonic::Code::ResourceExhausted,
+        ApiErrorCode::Unavailable => tonic::Code::Unavailable,
+        ApiErrorCode::DbError | ApiErrorCode::ProviderFailed | ApiErrorCode::Internal => {
+            tonic::Code::Internal
+        }
+    }
+}
+
+// the event as `/ws/events` tags it: `type` is the serde tag, the ids are the variant's fields.
+fn event_message(event: &AppEvent) -> grpc::Event {
+    let mut message = grpc::Event {
+        org_id: event.org_id.map(|id| id.to_string()),
+        ..Default::default()
+    };
+    let id = |id: &Uuid| Some(id.to_string());
+    message.r#type = match &event.kind {
+        AppEventKind::RunStatusChanged { run_id, terminal } => {
+            message.run_id = id(run_id);
+            message.terminal = Some(*terminal);
+            "run_status_changed"
+        }
+        AppEventKind::RunChunkAdded { run_id } => {
+            message.run_id = id(run_id);
+            "run_chunk_added"
+        }
+        AppEventKind::WorkflowsChanged => "workflows_changed",
+        AppEventKind::WorkflowRunChanged { run_id } => {
+            message.run_id = id(run_id);
+            "workflow_run_changed"
+        }
+        AppEventKind::WorkflowRunActivity => "workflow_run_activity",
+        AppEventKind::PipelineRunChanged { run_id } => {
+            message.run_id = id(run_id);
+            "pipeline_run_changed"
+        }
+        AppEventKind::PipelineRunActivity => "pipeline_run_activity",
+        AppEventKind::TasksChanged => "tasks_changed",
+        AppEventKind::ArtifactCreated {
+            artifact_id,
+            run_id,
+        } => {
+            message.artifact_id = id(artifact_id);
+            message.run_id = id(run_id);
+            "artifact_created"
+        }
+        AppEventKind::NotificationCreated { notification_id } => {
+            message.notification_id = id(notification_id);
+            "notification_created"
+        }
+        AppEventKind::NotificationsChanged => "notifications_changed",
+    }
+    .into();
+    message
+}
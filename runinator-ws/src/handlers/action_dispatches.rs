@@ -12,7 +12,7 @@ use runinator_models::web::TaskResponse;
 use runinator_models::{auth::AuthContext, orchestration::ActionDispatchClaimRequest};
 use serde::Deserialize;
 
-use crate::responses::api_error;
+use crate::responses::repository_error;
 
 #[derive(Debug, Deserialize)]
 pub(crate) struct EnqueueActionDispatchRequest {
@@ -41,7 +41,7 @@ pub(crate) async fn enqueue_action_dispatch<T: DatabaseImpl>(
     db.enqueue_action_dispatch(request.dedupe_key, request.command)
         .await
         .map(|record| (StatusCode::ACCEPTED, Json(record)))
-        .map_err(|err| api_error(err.to_string()))
+        .map_err(|err| repository_error(err.as_ref()))
 }
 
 pub(crate) async fn pending_action_dispatches<T: DatabaseImpl>(
@@ -53,7 +53,7 @@ pub(crate) async fn pending_action_dispatches<T: DatabaseImpl>(
     db.fetch_pending_action_dispatches(query.limit.unwrap_or(100))
         .await
         .map(Json)
-        .map_err(|err| api_error(err.to_string()))
+        .map_err(|err| repository_error(err.as_ref()))
 }
 
 pub(crate) async fn claim_action_dispatches<T: DatabaseImpl>(
@@ -70,7 +70,7 @@ pub(crate) async fn claim_action_dispatches<T: DatabaseImpl>(
     )
     .await
     .map(Json)
-    .map_err(|err| api_error(err.to_string()))
+    .map_err(|err| repository_error(err.as_ref()))
 }
 
 pub(crate) async fn mark_action_dispatch_published<T: DatabaseImpl>(
@@ -82,7 +82,7 @@ pub(crate) async fn mark_action_dispatch_published<T: DatabaseImpl>(
     db.mark_action_dispatch_published(dispatch_id)
         .await
         .map(|_| Json(success("Action dispatch marked published")))
-        .map_err(|err| api_error(err.to_string()))
+        .map_err(|err| repository_error(err.as_ref()))
 }
 
 pub(crate) async fn mark_action_dispatch_failed<T: DatabaseImpl>(
@@ -95,7 +95,7 @@ pub(crate) async fn mark_action_dispatch_failed<T: DatabaseImpl>(
     db.mark_action_dispatch_failed(dispatch_id, request.error)
         .await
         .map(|_| Json(success("Action dispatch failure recorded")))
-        .map_err(|err| api_error(err.to_string()))
+        .map_err(|err| repository_error(err.as_ref()))
 }
 
 fn success(message: impl Into<String>) -> TaskResponse {
@@ -15,7 +15,7 @@ use runinator_models::runs::NewRunArtifact;
 use crate::events::{AppEvent, AppEventKind, EventSender, emit};
 use crate::models::ApiResponse;
 use crate::repository;
-use crate::responses::{api_error, bad_request};
+use crate::responses::{bad_request, repository_error};
 
 pub(crate) async fn get_run_artifacts<T: DatabaseImpl>(
     Extension(db): Extension<Arc<T>>,
@@ -27,7 +27,7 @@ pub(crate) async fn get_run_artifacts<T: DatabaseImpl>(
     }
     match repository::fetch_run_artifacts(db.as_ref(), run_id).await {
         Ok(artifacts) => (StatusCode::OK, Json(ApiResponse::RunArtifacts(artifacts))),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -45,7 +45,7 @@ pub(crate) async fn add_run_artifact<T: DatabaseImpl>(
             StatusCode::ACCEPTED,
             Json(ApiResponse::RunArtifacts(vec![artifact])),
         ),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -58,7 +58,7 @@ pub(crate) async fn list_artifacts<T: DatabaseImpl>(
     }
     match repository::fetch_all_artifacts(db.as_ref()).await {
         Ok(artifacts) => (StatusCode::OK, Json(ApiResponse::RunArtifacts(artifacts))),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -181,7 +181,7 @@ pub(crate) async fn upload_artifact<T: DatabaseImpl>(
                 Json(ApiResponse::RunArtifacts(vec![artifact])),
             )
         }
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -204,7 +204,7 @@ pub(crate) async fn delete_artifact<T: DatabaseImpl>(
             )),
         ),
         Ok(false) => crate::responses::not_found(format!("Artifact {artifact_id} not found")),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -25,24 +25,30 @@ use crate::auth::{
 };
 use crate::authz;
 use crate::models::{
-    ApiError, ApiResponse, AuthConfigResponseSchema, LoginRequestSchema, LoginResponseSchema,
-    RefreshRequestSchema,
+    ApiError, ApiErrorCode, ApiResponse, AuthConfigResponseSchema, LoginRequestSchema,
+    LoginResponseSchema, RefreshRequestSchema,
 };
-use crate::responses::{api_error, not_found, task_response_success};
+use crate::responses::{api_error, not_found, repository_error, task_response_success};
 
 type Reply = (StatusCode, Json<ApiResponse>);
 
 fn unauthorized(message: &str) -> Reply {
     (
         StatusCode::UNAUTHORIZED,
-        Json(ApiResponse::ApiError(ApiError::new(message))),
+        Json(ApiResponse::ApiError(ApiError::new(
+            ApiErrorCode::Unauthorized,
+            message,
+        ))),
     )
 }
 
 fn forbidden(message: &str) -> Reply {
     (
         StatusCode::FORBIDDEN,
-        Json(ApiResponse::ApiError(ApiError::new(message))),
+        Json(ApiResponse::ApiError(ApiError::new(
+            ApiErrorCode::Forbidden,
+            message,
+        ))),
     )
 }
 
@@ -50,9 +56,10 @@ fn too_many_requests(retry_after_secs: f64) -> Reply {
     let secs = retry_after_secs.ceil().max(1.0) as u64;
     (
         StatusCode::TOO_MANY_REQUESTS,
-        Json(ApiResponse::ApiError(ApiError::new(&format!(
-            "too many login attempts; retry in {secs}s"
-        )))),
+        Json(ApiResponse::ApiError(ApiError::new(
+            ApiErrorCode::RateLimited,
+            format!("too many login attempts; retry in {secs}s"),
+        ))),
     )
 }
 
@@ -65,7 +72,7 @@ async fn enabled_admin_count<T: DatabaseImpl>(db: &T) -> Result<usize, Reply> {
                 .filter(|user| user.is_admin && !user.disabled)
                 .count()
         })
-        .map_err(|err| api_error(err.to_string()))
+        .map_err(|err| repository_error(err.as_ref()))
 }
 
 async fn would_remove_last_enabled_admin<T: DatabaseImpl>(
@@ -125,7 +132,7 @@ async fn issue_session<T: DatabaseImpl>(
     };
     db.create_session(session)
         .await
-        .map_err(|err| api_error(err.to_string()))?;
+        .map_err(|err| repository_error(err.as_ref()))?;
     Ok(LoginResponse {
         access_token,
         refresh_token,
@@ -174,7 +181,7 @@ pub(crate) async fn login<T: DatabaseImpl>(
     let username = request.username.clone();
     let credential = match db.fetch_local_credential(request.username).await {
         Ok(credential) => credential,
-        Err(err) => return api_error(err.to_string()),
+        Err(err) => return repository_error(err.as_ref()),
     };
     // always perform an argon2 verification so login timing does not reveal whether the username
     // exists. an unknown user verifies against a throwaway hash; the result is discarded below.
@@ -253,7 +260,7 @@ pub(crate) async fn refresh<T: DatabaseImpl>(
     let session = match db.fetch_session_by_hash(hash).await {
         Ok(Some(session)) => session,
         Ok(None) => return unauthorized("invalid refresh token"),
-        Err(err) => return api_error(err.to_string()),
+        Err(err) => return repository_error(err.as_ref()),
     };
     if session.expires_at < Utc::now() {
         return unauthorized("refresh token expired");
@@ -261,11 +268,11 @@ pub(crate) async fn refresh<T: DatabaseImpl>(
     let user = match db.fetch_user(session.user_id).await {
         Ok(Some(user)) if !user.disabled => user,
         Ok(_) => return unauthorized("user unavailable"),
-        Err(err) => return api_error(err.to_string()),
+        Err(err) => return repository_error(err.as_ref()),
     };
     // rotate: revoke the presented session and mint a fresh one.
     if let Err(err) = db.revoke_session(session.id).await {
-        return api_error(err.to_string());
+        return repository_error(err.as_ref());
     }
     match issue_session(db.as_ref(), &config, user).await {
         Ok(response) => ok_value(&response),
@@ -290,7 +297,7 @@ pub(crate) async fn logout<T: DatabaseImpl>(
     let hash = hash_secret(&request.refresh_token);
     if let Ok(Some(session)) = db.fetch_session_by_hash(hash).await {
         if let Err(err) = db.revoke_session(session.id).await {
-            return api_error(err.to_string());
+            return repository_error(err.as_ref());
         }
     }
     task_response_success("Logged out")
@@ -331,7 +338,7 @@ pub(crate) async fn me<T: DatabaseImpl>(
             ok_value(&value)
         }
         Ok(None) => not_found("user not found"),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -349,7 +356,7 @@ pub(crate) async fn list_users<T: DatabaseImpl>(
             Ok(values) => (StatusCode::OK, Json(ApiResponse::JsonList(values))),
             Err(reply) => reply,
         },
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -375,7 +382,7 @@ pub(crate) async fn create_user<T: DatabaseImpl>(
         .await
     {
         Ok(user) => ok_value(&user),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -391,7 +398,7 @@ pub(crate) async fn update_user<T: DatabaseImpl>(
     let current = match db.fetch_user(user_id).await {
         Ok(Some(user)) => user,
         Ok(None) => return not_found("user not found"),
-        Err(err) => return api_error(err.to_string()),
+        Err(err) => return repository_error(err.as_ref()),
     };
     let demotes_enabled_admin = request.is_admin == Some(false) || request.disabled == Some(true);
     match would_remove_last_enabled_admin(db.as_ref(), &current, demotes_enabled_admin).await {
@@ -406,7 +413,7 @@ pub(crate) async fn update_user<T: DatabaseImpl>(
             Err(err) => return api_error(err),
         };
         if let Err(err) = db.set_local_password(user_id, hash).await {
-            return api_error(err.to_string());
+            return repository_error(err.as_ref());
         }
     }
     match db
@@ -416,12 +423,12 @@ pub(crate) async fn update_user<T: DatabaseImpl>(
         Ok(user) => {
             if password_changed || user.disabled {
                 if let Err(err) = db.revoke_user_sessions(user_id).await {
-                    return api_error(err.to_string());
+                    return repository_error(err.as_ref());
                 }
             }
             ok_value(&user)
         }
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -436,7 +443,7 @@ pub(crate) async fn delete_user<T: DatabaseImpl>(
     let current = match db.fetch_user(user_id).await {
         Ok(Some(user)) => user,
         Ok(None) => return not_found("user not found"),
-        Err(err) => return api_error(err.to_string()),
+        Err(err) => return repository_error(err.as_ref()),
     };
     match would_remove_last_enabled_admin(db.as_ref(), &current, true).await {
         Ok(true) => return forbidden("cannot delete the last enabled admin user"),
@@ -445,7 +452,7 @@ pub(crate) async fn delete_user<T: DatabaseImpl>(
     }
     match db.delete_user(user_id).await {
         Ok(()) => task_response_success("User deleted"),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -462,7 +469,7 @@ pub(crate) async fn list_api_keys<T: DatabaseImpl>(
             Ok(values) => (StatusCode::OK, Json(ApiResponse::JsonList(values))),
             Err(reply) => reply,
         },
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -484,7 +491,7 @@ pub(crate) async fn create_api_key<T: DatabaseImpl>(
         match db.fetch_user(user_id).await {
             Ok(Some(user)) => Some(user),
             Ok(None) => return not_found("user not found"),
-            Err(err) => return api_error(err.to_string()),
+            Err(err) => return repository_error(err.as_ref()),
         }
     } else {
         None
@@ -519,7 +526,7 @@ pub(crate) async fn create_api_key<T: DatabaseImpl>(
             api_key: stored,
             secret: generated.secret,
         }),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -537,7 +544,7 @@ pub(crate) async fn update_api_key<T: DatabaseImpl>(
         .await
     {
         Ok(key) => ok_value(&key),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -552,7 +559,7 @@ pub(crate) async fn rotate_api_key<T: DatabaseImpl>(
     let current = match db.fetch_api_key(key_id).await {
         Ok(Some(record)) => record,
         Ok(None) => return not_found("api key not found"),
-        Err(err) => return api_error(err.to_string()),
+        Err(err) => return repository_error(err.as_ref()),
     };
     let generated = new_api_key();
     let key = ApiKey {
@@ -574,14 +581,14 @@ pub(crate) async fn rotate_api_key<T: DatabaseImpl>(
     match db.create_api_key(record).await {
         Ok(stored) => {
             if let Err(err) = db.revoke_api_key(key_id).await {
-                return api_error(err.to_string());
+                return repository_error(err.as_ref());
             }
             ok_value(&CreateApiKeyResponse {
                 api_key: stored,
                 secret: generated.secret,
             })
         }
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -595,7 +602,7 @@ pub(crate) async fn revoke_api_key<T: DatabaseImpl>(
     }
     match db.revoke_api_key(key_id).await {
         Ok(()) => task_response_success("API key revoked"),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -620,7 +627,7 @@ pub(crate) async fn list_workflow_grants<T: DatabaseImpl>(
             Ok(values) => (StatusCode::OK, Json(ApiResponse::JsonList(values))),
             Err(reply) => reply,
         },
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -646,7 +653,7 @@ pub(crate) async fn create_workflow_grant<T: DatabaseImpl>(
     };
     match db.create_grant(grant).await {
         Ok(stored) => ok_value(&stored),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -662,7 +669,7 @@ pub(crate) async fn revoke_workflow_grant<T: DatabaseImpl>(
     }
     match db.revoke_grant(grant_id).await {
         Ok(()) => task_response_success("Grant revoked"),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -680,7 +687,7 @@ pub(crate) async fn list_teams<T: DatabaseImpl>(
             Ok(values) => (StatusCode::OK, Json(ApiResponse::JsonList(values))),
             Err(reply) => reply,
         },
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -697,7 +704,7 @@ pub(crate) async fn list_user_teams<T: DatabaseImpl>(
             Ok(values) => (StatusCode::OK, Json(ApiResponse::JsonList(values))),
             Err(reply) => reply,
         },
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -711,7 +718,7 @@ pub(crate) async fn create_team<T: DatabaseImpl>(
     }
     match db.create_team(request.name).await {
         Ok(team) => ok_value(&team),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -726,7 +733,7 @@ pub(crate) async fn update_team<T: DatabaseImpl>(
     }
     match db.update_team(team_id, request.name).await {
         Ok(team) => ok_value(&team),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -740,7 +747,7 @@ pub(crate) async fn delete_team<T: DatabaseImpl>(
     }
     match db.delete_team(team_id).await {
         Ok(()) => task_response_success("Team deleted"),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -757,7 +764,7 @@ pub(crate) async fn list_team_members<T: DatabaseImpl>(
             Ok(values) => (StatusCode::OK, Json(ApiResponse::JsonList(values))),
             Err(reply) => reply,
         },
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -772,7 +779,7 @@ pub(crate) async fn add_team_member<T: DatabaseImpl>(
     }
     match db.add_team_member(team_id, request.user_id).await {
         Ok(()) => task_response_success("Member added"),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -786,6 +793,6 @@ pub(crate) async fn remove_team_member<T: DatabaseImpl>(
     }
     match db.remove_team_member(team_id, user_id).await {
         Ok(()) => task_response_success("Member removed"),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
@@ -15,7 +15,7 @@ use crate::models::{
     GateResolutionRequest, IdempotencyRequest,
 };
 use crate::repository;
-use crate::responses::{api_error, not_found};
+use crate::responses::{bad_request, not_found, repository_error};
 
 async fn list_records<T: DatabaseImpl>(
     Extension(db): Extension<Arc<T>>,
@@ -35,7 +35,7 @@ async fn list_records<T: DatabaseImpl>(
             Ok(records) => (StatusCode::OK, Json(ApiResponse::JsonList(records))),
             Err(reply) => reply,
         },
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -50,7 +50,7 @@ async fn create_record<T: DatabaseImpl>(
     }
     match repository::create_automation_record(db.as_ref(), record_type, record).await {
         Ok(record) => (StatusCode::ACCEPTED, Json(ApiResponse::JsonValue(record))),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -80,7 +80,7 @@ pub(crate) async fn get_gates<T: DatabaseImpl>(
             Ok(records) => (StatusCode::OK, Json(ApiResponse::JsonList(records))),
             Err(reply) => reply,
         },
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -97,7 +97,7 @@ pub(crate) async fn get_gate<T: DatabaseImpl>(
     match repository::fetch_gate(db.as_ref(), gate_id).await {
         Ok(Some(record)) => (StatusCode::OK, Json(ApiResponse::JsonValue(record))),
         Ok(None) => not_found("Gate not found"),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -111,7 +111,7 @@ pub(crate) async fn create_gate<T: DatabaseImpl>(
     }
     match repository::create_gate(db.as_ref(), record).await {
         Ok(record) => (StatusCode::ACCEPTED, Json(ApiResponse::JsonValue(record))),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -148,7 +148,7 @@ pub(crate) async fn open_gate<T: DatabaseImpl>(
     .await
     {
         Ok(record) => (StatusCode::OK, Json(ApiResponse::JsonValue(record))),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -185,7 +185,7 @@ pub(crate) async fn close_gate<T: DatabaseImpl>(
     .await
     {
         Ok(record) => (StatusCode::OK, Json(ApiResponse::JsonValue(record))),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -202,7 +202,7 @@ pub(crate) async fn delete_gate<T: DatabaseImpl>(
     match repository::delete_gate(db.as_ref(), gate_id).await {
         Ok(true) => (StatusCode::OK, Json(ApiResponse::JsonValue(Value::Null))),
         Ok(false) => not_found("Gate not found"),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -241,7 +241,7 @@ pub(crate) async fn delete_automation_event<T: DatabaseImpl>(
     match repository::delete_automation_record(db.as_ref(), "automation_events", event_id).await {
         Ok(true) => (StatusCode::OK, Json(ApiResponse::JsonValue(Value::Null))),
         Ok(false) => not_found("Automation event not found"),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -340,7 +340,7 @@ async fn resolve_approval_audited<T: DatabaseImpl>(
             .await;
             (StatusCode::OK, Json(ApiResponse::JsonValue(record)))
         }
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -353,15 +353,15 @@ pub(crate) async fn get_idempotency_key<T: DatabaseImpl>(
         return reply;
     }
     let Some(scope) = query.get("scope").cloned() else {
-        return api_error("idempotency query requires scope");
+        return bad_request("idempotency query requires scope");
     };
     let Some(key) = query.get("key").cloned() else {
-        return api_error("idempotency query requires key");
+        return bad_request("idempotency query requires key");
     };
     match repository::fetch_idempotency_key(db.as_ref(), scope, key).await {
         Ok(Some(record)) => (StatusCode::OK, Json(ApiResponse::JsonValue(record))),
         Ok(None) => not_found("idempotency key not found"),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -377,7 +377,7 @@ pub(crate) async fn put_idempotency_key<T: DatabaseImpl>(
         .await
     {
         Ok(record) => (StatusCode::OK, Json(ApiResponse::JsonValue(record))),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -399,7 +399,7 @@ async fn filter_records<T: DatabaseImpl>(
         };
         let Some((run, _)) = repository::fetch_workflow_run(db, workflow_run_id)
             .await
-            .map_err(|err| api_error(err.to_string()))?
+            .map_err(|err| repository_error(err.as_ref()))?
         else {
             continue;
         };
@@ -17,8 +17,8 @@ use serde::Serialize;
 use uuid::Uuid;
 
 use crate::authz;
-use crate::models::{ApiError, ApiResponse};
-use crate::responses::{api_error, bad_request};
+use crate::models::{ApiError, ApiErrorCode, ApiResponse};
+use crate::responses::{api_error, bad_request, not_found, repository_error};
 
 type Reply = (StatusCode, Json<ApiResponse>);
 
@@ -38,7 +38,10 @@ fn ok_value<T: Serialize>(value: &T) -> Reply {
 fn quota_error(message: impl Into<String>) -> Reply {
     (
         StatusCode::FORBIDDEN,
-        Json(ApiResponse::ApiError(ApiError::new(&message.into()))),
+        Json(ApiResponse::ApiError(ApiError::new(
+            ApiErrorCode::Forbidden,
+            message,
+        ))),
     )
 }
 
@@ -71,7 +74,7 @@ pub(crate) async fn get_org_nodes<T: DatabaseImpl>(
     }
     let groups = match db.list_org_resource_groups(org_id).await {
         Ok(groups) => groups,
-        Err(err) => return api_error(err.to_string()),
+        Err(err) => return repository_error(err.as_ref()),
     };
     let card = rate_card();
     let monthly = projected_monthly_cents(&groups, &card);
@@ -96,7 +99,7 @@ pub(crate) async fn scale_org_nodes<T: DatabaseImpl>(
     let card = rate_card();
     let quota = match db.fetch_org_quota(org_id).await {
         Ok(quota) => quota,
-        Err(err) => return api_error(err.to_string()),
+        Err(err) => return repository_error(err.as_ref()),
     };
 
     // per-kind node cap.
@@ -115,7 +118,7 @@ pub(crate) async fn scale_org_nodes<T: DatabaseImpl>(
     // monthly budget cap: project the org's spend after this change across all its allocations.
     let mut groups = match db.list_org_resource_groups(org_id).await {
         Ok(groups) => groups,
-        Err(err) => return api_error(err.to_string()),
+        Err(err) => return repository_error(err.as_ref()),
     };
     apply_allocation(
         &mut groups,
@@ -145,12 +148,12 @@ pub(crate) async fn scale_org_nodes<T: DatabaseImpl>(
         dedicated: true,
     };
     if let Err(err) = db.upsert_org_resource_group(group.clone()).await {
-        return api_error(err.to_string());
+        return repository_error(err.as_ref());
     }
     let slug = match db.fetch_org(org_id).await {
         Ok(Some(org)) => org.slug,
-        Ok(None) => return api_error("organization not found"),
-        Err(err) => return api_error(err.to_string()),
+        Ok(None) => return not_found("organization not found"),
+        Err(err) => return repository_error(err.as_ref()),
     };
     scale_org_pool(
         &registry,
@@ -178,7 +181,7 @@ pub(crate) async fn get_org_quota<T: DatabaseImpl>(
             org_id,
             ..Default::default()
         },
-        Err(err) => return api_error(err.to_string()),
+        Err(err) => return repository_error(err.as_ref()),
     };
     ok_value(&quota)
 }
@@ -205,14 +208,15 @@ pub(crate) async fn put_org_quota<T: DatabaseImpl>(
         org_id,
         max_nodes_per_kind: request.max_nodes_per_kind,
         max_monthly_cents: request.max_monthly_cents,
+        max_concurrent_runs: request.max_concurrent_runs,
     };
     match db.upsert_org_quota(quota).await {
         Ok(quota) => ok_value(&quota),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
-/// an org's accrued usage and cost since a rolling 30-day window.
+/// an org's accrued usage and cost since a rolling 30-day window, plus its currently active runs.
 pub(crate) async fn get_org_usage<T: DatabaseImpl>(
     Extension(db): Extension<Arc<T>>,
     Extension(ctx): Extension<AuthContext>,
@@ -224,9 +228,13 @@ pub(crate) async fn get_org_usage<T: DatabaseImpl>(
     let since = chrono::Utc::now() - chrono::Duration::days(30);
     let samples = match db.fetch_usage_samples(org_id, since.timestamp()).await {
         Ok(samples) => samples,
-        Err(err) => return api_error(err.to_string()),
+        Err(err) => return repository_error(err.as_ref()),
+    };
+    let mut usage = integrate_usage(org_id, samples, &rate_card());
+    usage.active_runs = match db.count_active_workflow_runs_for_org(org_id).await {
+        Ok(count) => count.max(0) as u64,
+        Err(err) => return repository_error(err.as_ref()),
     };
-    let usage = integrate_usage(org_id, samples, &rate_card());
     ok_value(&usage)
 }
 
@@ -329,5 +337,6 @@ pub(crate) fn integrate_usage(
         since,
         node_hours,
         accrued_cents: accrued_cents.round() as u64,
+        active_runs: 0,
     }
 }
@@ -0,0 +1,120 @@
+//! named holiday calendars for cron trigger blackouts. a trigger lists `{ "calendar": name }` under
+//! its configuration's `blackouts` and then does not fire on any of the calendar's dates. any
+//! authenticated principal can read them; changing one takes `settings:manage`, since a calendar
+//! can silence every trigger that references it.
+
+use std::sync::Arc;
+
+use axum::{Extension, Json, extract::Path, http::StatusCode};
+use runinator_database::interfaces::DatabaseImpl;
+use runinator_models::{
+    auth::{AuthContext, PrincipalKind},
+    blackouts::BlackoutCalendarRequest,
+    capabilities::Capability,
+};
+
+use crate::audit::{AuditOutcome, record_audit};
+use crate::authz;
+use crate::models::ApiResponse;
+use crate::repository;
+use crate::responses::{bad_request, not_found, repository_error, task_response_success};
+
+type Reply = (StatusCode, Json<ApiResponse>);
+
+/// longest calendar name accepted.
+const MAX_CALENDAR_NAME_LEN: usize = 128;
+
+pub(crate) async fn get_blackout_calendars<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+) -> Reply {
+    match repository::fetch_blackout_calendars(db.as_ref()).await {
+        Ok(calendars) => (
+            StatusCode::OK,
+            Json(ApiResponse::BlackoutCalendarList(calendars)),
+        ),
+        Err(err) => repository_error(err.as_ref()),
+    }
+}
+
+/// create or replace a calendar's dates.
+pub(crate) async fn put_blackout_calendar<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+    Extension(ctx): Extension<AuthContext>,
+    Path(name): Path<String>,
+    Json(request): Json<BlackoutCalendarRequest>,
+) -> Reply {
+    if let Err(reply) = authz::require_capability(&ctx, Capability::SettingsManage) {
+        return reply;
+    }
+    let name = name.trim();
+    if name.is_empty() || name.len() > MAX_CALENDAR_NAME_LEN {
+        return bad_request(format!(
+            "calendar name must be 1 to {MAX_CALENDAR_NAME_LEN} characters"
+        ));
+    }
+    if let Some(timezone) = request
+        .timezone
+        .as_deref()
+        .filter(|zone| !zone.trim().is_empty())
+        && let Err(err) = runinator_database::schedule::parse_timezone(timezone.trim())
+    {
+        return bad_request(err.to_string());
+    }
+    let calendar = match repository::set_blackout_calendar(db.as_ref(), name, request).await {
+        Ok(calendar) => calendar,
+        Err(err) => return repository_error(err.as_ref()),
+    };
+    audit(
+        db.as_ref(),
+        &ctx,
+        "blackout_calendar.saved",
+        &format!("name={} dates={}", calendar.name, calendar.dates.len()),
+    )
+    .await;
+    (
+        StatusCode::OK,
+        Json(ApiResponse::BlackoutCalendar(calendar)),
+    )
+}
+
+/// delete a calendar; triggers still naming it stop being blacked out by it.
+pub(crate) async fn delete_blackout_calendar<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+    Extension(ctx): Extension<AuthContext>,
+    Path(name): Path<String>,
+) -> Reply {
+    if let Err(reply) = authz::require_capability(&ctx, Capability::SettingsManage) {
+        return reply;
+    }
+    match repository::delete_blackout_calendar(db.as_ref(), &name).await {
+        Ok(true) => {
+            audit(
+                db.as_ref(),
+                &ctx,
+                "blackout_calendar.deleted",
+                &format!("name={name}"),
+            )
+            .await;
+            task_response_success(format!("Deleted blackout calendar {name}"))
+        }
+        Ok(false) => not_found(format!("blackout calendar '{name}' not found")),
+        Err(err) => repository_error(err.as_ref()),
+    }
+}
+
+async fn audit<T: DatabaseImpl>(db: &T, ctx: &AuthContext, action: &str, detail: &str) {
+    record_audit(
+        db,
+        ctx.principal_id,
+        match ctx.kind {
+            PrincipalKind::User => "user",
+            PrincipalKind::Service => "service",
+        },
+        action,
+        AuditOutcome::Success,
+        Some("blackout_calendar"),
+        None,
+        Some(detail),
+    )
+    .await;
+}
@@ -11,7 +11,7 @@ use runinator_models::value::Value;
 use crate::handlers::providers::provider_catalog_item;
 use crate::models::{ApiResponse, CatalogQuery};
 use crate::repository;
-use crate::responses::{api_error, not_found};
+use crate::responses::{not_found, repository_error};
 
 pub(crate) async fn get_catalog_items<T: DatabaseImpl>(
     Extension(db): Extension<Arc<T>>,
@@ -22,12 +22,12 @@ pub(crate) async fn get_catalog_items<T: DatabaseImpl>(
         return match repository::fetch_catalog_item(db.as_ref(), uri.clone()).await {
             Ok(Some(item)) => (StatusCode::OK, Json(ApiResponse::JsonValue(item))),
             Ok(None) => not_found(format!("Catalog item {uri} not found")),
-            Err(err) => api_error(err.to_string()),
+            Err(err) => repository_error(err.as_ref()),
         };
     }
     match repository::fetch_catalog_items(db.as_ref(), query.item_type).await {
         Ok(items) => (StatusCode::OK, Json(ApiResponse::JsonList(items))),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -41,7 +41,7 @@ pub(crate) async fn upsert_catalog_item<T: DatabaseImpl>(
     }
     match repository::upsert_catalog_item(db.as_ref(), item).await {
         Ok(item) => (StatusCode::OK, Json(ApiResponse::JsonValue(item))),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -0,0 +1,106 @@
+//! cluster-wide pause for incidents. pausing dispatch holds queued actions in the outbox; pausing
+//! execution makes every worker stop taking actions off the broker until it is lifted; pausing
+//! scheduling stops every trigger firing. workers read the state from `GET /cluster/pause`, so it
+//! reaches the whole fleet rather than one consumer.
+
+use std::sync::Arc;
+
+use axum::{Extension, Json, http::StatusCode};
+use runinator_database::interfaces::DatabaseImpl;
+use runinator_models::{
+    auth::{AuthContext, PrincipalKind},
+    capabilities::Capability,
+    cluster::{ClusterPause, ClusterPauseRequest},
+};
+
+use crate::audit::{AuditOutcome, record_audit};
+use crate::authz;
+use crate::events::{EventSender, nudge_action_dispatch_publisher};
+use crate::models::ApiResponse;
+use crate::repository;
+use crate::responses::repository_error;
+
+type Reply = (StatusCode, Json<ApiResponse>);
+
+/// the current cluster pause. open to any authenticated principal so workers can poll it.
+pub(crate) async fn get_cluster_pause<T: DatabaseImpl>(Extension(db): Extension<Arc<T>>) -> Reply {
+    match repository::fetch_cluster_pause(db.as_ref()).await {
+        Ok(pause) => (StatusCode::OK, Json(ApiResponse::ClusterPause(pause))),
+        Err(err) => repository_error(err.as_ref()),
+    }
+}
+
+/// pause dispatch, execution, and/or scheduling across the cluster.
+pub(crate) async fn put_cluster_pause<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+    Extension(ctx): Extension<AuthContext>,
+    Extension(events): Extension<EventSender>,
+    Json(request): Json<ClusterPauseRequest>,
+) -> Reply {
+    apply_cluster_pause(db.as_ref(), &ctx, &events, request).await
+}
+
+/// lift the cluster pause entirely.
+pub(crate) async fn delete_cluster_pause<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+    Extension(ctx): Extension<AuthContext>,
+    Extension(events): Extension<EventSender>,
+) -> Reply {
+    let request = ClusterPauseRequest {
+        dispatch: false,
+        execution: false,
+        scheduling: false,
+        reason: None,
+    };
+    apply_cluster_pause(db.as_ref(), &ctx, &events, request).await
+}
+
+async fn apply_cluster_pause<T: DatabaseImpl>(
+    db: &T,
+    ctx: &AuthContext,
+    events: &EventSender,
+    request: ClusterPauseRequest,
+) -> Reply {
+    if let Err(reply) = authz::require_capability(ctx, Capability::ClusterManage) {
+        return reply;
+    }
+    let pause = match repository::set_cluster_pause(db, request, ctx.principal_id).await {
+        Ok(pause) => pause,
+        Err(err) => return repository_error(err.as_ref()),
+    };
+    crate::stability::cluster_pause(&pause);
+    record_audit(
+        db,
+        ctx.principal_id,
+        match ctx.kind {
+            PrincipalKind::User => "user",
+            PrincipalKind::Service => "service",
+        },
+        if pause.is_paused() {
+            "cluster.paused"
+        } else {
+            "cluster.resumed"
+        },
+        AuditOutcome::Success,
+        Some("cluster"),
+        None,
+        Some(&describe(&pause)),
+    )
+    .await;
+    // wake the publisher so held dispatches go out as soon as dispatch resumes.
+    if !pause.dispatch {
+        nudge_action_dispatch_publisher(events);
+    }
+    (StatusCode::OK, Json(ApiResponse::ClusterPause(pause)))
+}
+
+fn describe(pause: &ClusterPause) -> String {
+    let mut detail = format!(
+        "dispatch={} execution={} scheduling={}",
+        pause.dispatch, pause.execution, pause.scheduling
+    );
+    if let Some(reason) = &pause.reason {
+        detail.push_str(&format!(" reason={reason}"));
+    }
+    detail
+}
@@ -14,7 +14,7 @@ use runinator_models::{
 use runinator_utilities::secret_cipher::SecretCipher;
 
 use crate::models::{ApiResponse, CredentialPutRequest, CredentialQuery};
-use crate::responses::{api_error, bad_request, not_found};
+use crate::responses::{api_error, bad_request, not_found, repository_error};
 use crate::settings::{decode_config_schema, decode_config_value, validate_and_encode};
 
 // the cipher that protects setting values at rest, keyed by `RUNINATOR_CREDENTIAL_KEY` (plus any
@@ -58,7 +58,7 @@ pub(crate) async fn get_credential<T: DatabaseImpl>(
                         .collect(),
                 )),
             ),
-            Err(err) => api_error(err.to_string()),
+            Err(err) => repository_error(err.as_ref()),
         };
     }
 
@@ -94,7 +94,7 @@ pub(crate) async fn get_credential<T: DatabaseImpl>(
             )
         }
         Ok(None) => not_found("credential not found"),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -151,7 +151,7 @@ pub(crate) async fn put_credential<T: DatabaseImpl>(
                 "stored": true
             }))),
         ),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -168,7 +168,7 @@ pub(crate) async fn reencrypt_settings<T: DatabaseImpl>(
     let cipher = settings_cipher();
     let entries = match db.list_settings().await {
         Ok(entries) => entries,
-        Err(err) => return api_error(err.to_string()),
+        Err(err) => return repository_error(err.as_ref()),
     };
     let mut rewritten = 0usize;
     let mut skipped = 0usize;
@@ -192,7 +192,7 @@ pub(crate) async fn reencrypt_settings<T: DatabaseImpl>(
             )
             .await
         {
-            return api_error(err.to_string());
+            return repository_error(err.as_ref());
         }
         rewritten += 1;
     }
@@ -374,6 +374,6 @@ pub(crate) async fn delete_credential<T: DatabaseImpl>(
                 message: "Credential deleted".into(),
             })),
         ),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
@@ -21,6 +21,10 @@ pub(crate) struct ReadinessResponse {
     status: String,
     database: String,
     broker_result_channels: bool,
+    /// whether action dispatch / worker execution / trigger scheduling is paused cluster-wide.
+    dispatch_paused: bool,
+    execution_paused: bool,
+    scheduling_paused: bool,
     counters: stability::StabilityCounters,
 }
 
@@ -46,7 +50,11 @@ pub(crate) async fn health() -> Json<HealthResponse> {
     security(),
     responses((status = 200, description = "prometheus metrics", content_type = "text/plain")),
 )]
-pub(crate) async fn metrics() -> impl IntoResponse {
+pub(crate) async fn metrics<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+) -> impl IntoResponse {
+    // pool gauges are sampled at scrape time rather than on every checkout.
+    stability::db_pool(db.pool_status());
     (
         [(
             header::CONTENT_TYPE,
@@ -56,7 +64,7 @@ pub(crate) async fn metrics() -> impl IntoResponse {
     )
 }
 
-/// readiness probe: reports database and broker reachability.
+/// readiness probe: reports database and broker reachability and any cluster pause.
 #[utoipa::path(
     get,
     path = "/ready",
@@ -73,6 +81,8 @@ pub(crate) async fn ready<T: DatabaseImpl>(
 ) -> (StatusCode, Json<ReadinessResponse>) {
     // a cheap connectivity probe: fetch at most one row rather than the whole run history.
     let database_ready = db.fetch_recent_workflow_runs(1).await.is_ok();
+    // a cluster pause is an operator decision, not a fault, so it never fails readiness.
+    let pause = db.fetch_cluster_pause().await.unwrap_or_default();
     let status = if database_ready { "ready" } else { "not_ready" };
     let code = if database_ready {
         StatusCode::OK
@@ -86,6 +96,9 @@ pub(crate) async fn ready<T: DatabaseImpl>(
             status: status.into(),
             database: if database_ready { "ok" } else { "error" }.into(),
             broker_result_channels: broker.supports_workflow_result_channels(),
+            dispatch_paused: pause.dispatch,
+            execution_paused: pause.execution,
+            scheduling_paused: pause.scheduling,
             counters: stability::snapshot(),
         }),
     )
@@ -0,0 +1,78 @@
+//! one-off jobs: run a single provider action once, straight through the broker, without storing a
+//! workflow. the submit call returns the job id to poll for its status and output.
+
+use std::sync::Arc;
+use uuid::Uuid;
+
+use axum::{Extension, Json, extract::Path, http::StatusCode};
+use runinator_database::interfaces::DatabaseImpl;
+use runinator_models::{
+    auth::{AuthContext, PrincipalKind},
+    capabilities::Capability,
+    jobs::JobRequest,
+};
+
+use crate::audit::{AuditOutcome, record_audit};
+use crate::authz;
+use crate::events::{EventSender, nudge_action_dispatch_publisher};
+use crate::models::ApiResponse;
+use crate::repository;
+use crate::responses::{bad_request, not_found, repository_error};
+
+type Reply = (StatusCode, Json<ApiResponse>);
+
+/// queue one action for a worker; 202 with the queued job.
+pub(crate) async fn submit_job<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+    Extension(ctx): Extension<AuthContext>,
+    Extension(events): Extension<EventSender>,
+    Json(request): Json<JobRequest>,
+) -> Reply {
+    if let Err(reply) = authz::require_capability(&ctx, Capability::JobsRun) {
+        return reply;
+    }
+    let retry = match request.retry() {
+        Ok(retry) => retry,
+        Err(message) => return bad_request(message),
+    };
+    let action = match request.into_action() {
+        Ok(action) => action,
+        Err(message) => return bad_request(message),
+    };
+    let job = match repository::submit_job(db.as_ref(), action, retry, ctx.principal_id).await {
+        Ok(job) => job,
+        Err(err) => return repository_error(err.as_ref()),
+    };
+    record_audit(
+        db.as_ref(),
+        ctx.principal_id,
+        match ctx.kind {
+            PrincipalKind::User => "user",
+            PrincipalKind::Service => "service",
+        },
+        "job.submitted",
+        AuditOutcome::Success,
+        Some("job"),
+        Some(job.id),
+        Some(&format!("{}.{}", job.action.provider, job.action.function)),
+    )
+    .await;
+    nudge_action_dispatch_publisher(&events);
+    (StatusCode::ACCEPTED, Json(ApiResponse::Job(job)))
+}
+
+/// a job's status, and its output once it has finished.
+pub(crate) async fn get_job<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+    Extension(ctx): Extension<AuthContext>,
+    Path(job_id): Path<Uuid>,
+) -> Reply {
+    if let Err(reply) = authz::require_capability(&ctx, Capability::JobsRun) {
+        return reply;
+    }
+    match repository::fetch_job(db.as_ref(), job_id).await {
+        Ok(Some(job)) => (StatusCode::OK, Json(ApiResponse::Job(job))),
+        Ok(None) => not_found(format!("Job {job_id} not found")),
+        Err(err) => repository_error(err.as_ref()),
+    }
+}
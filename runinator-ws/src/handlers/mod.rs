@@ -3,22 +3,27 @@ pub(crate) mod artifacts;
 pub(crate) mod auth;
 pub(crate) mod automation;
 pub(crate) mod billing;
+pub(crate) mod blackouts;
 pub(crate) mod catalog;
 pub(crate) mod catalog_metadata;
+pub(crate) mod cluster;
 pub(crate) mod credentials;
 pub(crate) mod debug;
 pub(crate) mod health;
+pub(crate) mod jobs;
 pub(crate) mod node_runs;
 pub(crate) mod notifications;
 pub(crate) mod observability;
 pub(crate) mod orgs;
 pub(crate) mod packs;
 pub(crate) mod pipelines;
+pub(crate) mod protection;
 pub(crate) mod providers;
 pub(crate) mod provisioning;
 pub(crate) mod replicas;
 pub(crate) mod runs;
 pub(crate) mod supervisor;
+pub(crate) mod templates;
 pub(crate) mod triggers;
 pub(crate) mod wdl;
 pub(crate) mod webhook;
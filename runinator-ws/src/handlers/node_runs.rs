@@ -7,6 +7,7 @@ use axum::{
     http::StatusCode,
 };
 use runinator_database::interfaces::DatabaseImpl;
+use runinator_models::output_refs::validate_run_outputs;
 use runinator_models::runs::{NewRunArtifact, NewRunChunk};
 
 use crate::events::{EventSender, emit_workflow_node_run, emit_workflow_run};
@@ -14,9 +15,10 @@ use crate::handlers::runs::ChunkQuery;
 use crate::models::{
     ApiResponse, WorkflowNodeRunExecutorClaimRequest, WorkflowNodeRunExecutorReleaseRequest,
     WorkflowNodeRunInputRequest, WorkflowNodeRunRequest, WorkflowNodeRunStatusRequest,
+    WorkflowRunOutputsRequest,
 };
 use crate::repository;
-use crate::responses::api_error;
+use crate::responses::{bad_request, not_found, repository_error};
 
 pub(crate) async fn create_workflow_node_run<T: DatabaseImpl>(
     Extension(db): Extension<Arc<T>>,
@@ -52,7 +54,7 @@ pub(crate) async fn create_workflow_node_run<T: DatabaseImpl>(
                 Json(ApiResponse::WorkflowNodeRun(step)),
             )
         }
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -83,7 +85,7 @@ pub(crate) async fn update_workflow_node_run<T: DatabaseImpl>(
             emit_workflow_node_run(db.as_ref(), &events, node_run_id).await;
             (StatusCode::OK, Json(ApiResponse::TaskResponse(resp)))
         }
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -117,7 +119,7 @@ pub(crate) async fn resolve_workflow_input<T: DatabaseImpl>(
             emit_workflow_node_run(db.as_ref(), &events, node_run_id).await;
             (StatusCode::OK, Json(ApiResponse::TaskResponse(resp)))
         }
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -144,7 +146,7 @@ pub(crate) async fn claim_workflow_node_run_executor<T: DatabaseImpl>(
             emit_workflow_node_run(db.as_ref(), &events, node_run_id).await;
             (StatusCode::OK, Json(ApiResponse::TaskResponse(resp)))
         }
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -170,7 +172,7 @@ pub(crate) async fn release_workflow_node_run_executor<T: DatabaseImpl>(
             emit_workflow_node_run(db.as_ref(), &events, node_run_id).await;
             (StatusCode::OK, Json(ApiResponse::TaskResponse(resp)))
         }
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -202,7 +204,7 @@ pub(crate) async fn get_workflow_node_run_chunks<T: DatabaseImpl>(
             StatusCode::OK,
             Json(ApiResponse::WorkflowNodeRunChunks(chunks)),
         ),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -224,7 +226,7 @@ pub(crate) async fn append_workflow_node_run_chunk<T: DatabaseImpl>(
                 Json(ApiResponse::WorkflowNodeRunChunks(vec![chunk])),
             )
         }
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -248,7 +250,7 @@ pub(crate) async fn get_workflow_node_run_artifacts<T: DatabaseImpl>(
             StatusCode::OK,
             Json(ApiResponse::WorkflowNodeRunArtifacts(artifacts)),
         ),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -272,7 +274,55 @@ pub(crate) async fn get_workflow_run_artifacts<T: DatabaseImpl>(
             StatusCode::OK,
             Json(ApiResponse::WorkflowRunArtifacts(artifacts)),
         ),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
+    }
+}
+
+pub(crate) async fn record_workflow_run_outputs<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+    Extension(ctx): Extension<runinator_models::auth::AuthContext>,
+    Path(workflow_run_id): Path<Uuid>,
+    Json(request): Json<WorkflowRunOutputsRequest>,
+) -> (StatusCode, Json<ApiResponse>) {
+    if let Err(reply) = crate::authz::require_service_or_admin(&ctx) {
+        return reply;
+    }
+    if let Err(err) = validate_run_outputs(&request.outputs) {
+        return bad_request(err);
+    }
+    match repository::record_workflow_run_outputs(db.as_ref(), workflow_run_id, &request.outputs)
+        .await
+    {
+        Ok(Some(outputs)) => (
+            StatusCode::ACCEPTED,
+            Json(ApiResponse::WorkflowRunOutputs(outputs)),
+        ),
+        Ok(None) => not_found("workflow run not found"),
+        Err(err) => repository_error(err.as_ref()),
+    }
+}
+
+pub(crate) async fn get_workflow_run_outputs<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+    Extension(ctx): Extension<runinator_models::auth::AuthContext>,
+    Path(workflow_run_id): Path<Uuid>,
+) -> (StatusCode, Json<ApiResponse>) {
+    if let Err(reply) = crate::authz::require_run_workflow(
+        db.as_ref(),
+        &ctx,
+        workflow_run_id,
+        runinator_models::auth::Permission::View,
+    )
+    .await
+    {
+        return reply;
+    }
+    match repository::fetch_workflow_run_outputs(db.as_ref(), workflow_run_id).await {
+        Ok(outputs) => (
+            StatusCode::OK,
+            Json(ApiResponse::WorkflowRunOutputs(outputs)),
+        ),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -296,7 +346,7 @@ pub(crate) async fn get_workflow_run_transitions<T: DatabaseImpl>(
             StatusCode::OK,
             Json(ApiResponse::NodeTransitions(transitions)),
         ),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -320,7 +370,7 @@ pub(crate) async fn get_workflow_node_transitions<T: DatabaseImpl>(
             StatusCode::OK,
             Json(ApiResponse::NodeTransitionStats(stats)),
         ),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -342,6 +392,6 @@ pub(crate) async fn add_workflow_node_run_artifact<T: DatabaseImpl>(
                 Json(ApiResponse::WorkflowNodeRunArtifacts(vec![artifact])),
             )
         }
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
@@ -13,7 +13,7 @@ use serde::Deserialize;
 use crate::events::{AppEvent, AppEventKind, EventSender, emit};
 use crate::models::ApiResponse;
 use crate::repository;
-use crate::responses::{api_error, not_found};
+use crate::responses::{not_found, repository_error};
 
 #[derive(Deserialize, Default)]
 pub(crate) struct NotificationsListQuery {
@@ -34,7 +34,7 @@ pub(crate) async fn list_notifications<T: DatabaseImpl>(
             StatusCode::OK,
             Json(ApiResponse::NotificationList(notifications)),
         ),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -64,7 +64,7 @@ pub(crate) async fn create_notification<T: DatabaseImpl>(
                 Json(ApiResponse::Notification(notification)),
             )
         }
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -86,7 +86,7 @@ pub(crate) async fn mark_notification_read<T: DatabaseImpl>(
             )
         }
         Ok(None) => not_found(format!("Notification {notification_id} not found")),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -110,7 +110,7 @@ pub(crate) async fn delete_notification<T: DatabaseImpl>(
             )
         }
         Ok(false) => not_found(format!("Notification {notification_id} not found")),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -132,6 +132,6 @@ pub(crate) async fn mark_all_notifications_read<T: DatabaseImpl>(
                 })),
             )
         }
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
@@ -1,4 +1,5 @@
-//! admin-only read endpoints over the dead-letter queue and the audit log.
+//! admin-only read endpoints over the dead-letter queue, the audit log, and this replica's startup
+//! report.
 
 use std::sync::Arc;
 
@@ -6,10 +7,11 @@ use axum::{Extension, Json, extract::Query, http::StatusCode};
 use runinator_database::interfaces::DatabaseImpl;
 use runinator_models::auth::AuthContext;
 use runinator_models::capabilities::Capability;
+use runinator_utilities::startup;
 
 use crate::authz::require_capability;
 use crate::models::{ApiResponse, AuditLogQuery, DeadLetterQuery};
-use crate::responses::api_error;
+use crate::responses::repository_error;
 
 // cap the page size so a single query cannot scan an unbounded log.
 const DEFAULT_LIMIT: i64 = 100;
@@ -39,7 +41,7 @@ pub(crate) async fn get_dead_letters<T: DatabaseImpl>(
         .await
     {
         Ok(records) => (StatusCode::OK, Json(ApiResponse::JsonList(records))),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -63,6 +65,21 @@ pub(crate) async fn get_audit_log<T: DatabaseImpl>(
         .await
     {
         Ok(records) => (StatusCode::OK, Json(ApiResponse::JsonList(records))),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
+
+/// this replica's startup report for support bundles: version, host, redacted config, and the
+/// providers it serves.
+pub(crate) async fn get_debug_info(
+    Extension(ctx): Extension<AuthContext>,
+) -> (StatusCode, Json<ApiResponse>) {
+    if let Err(reply) = require_capability(&ctx, Capability::SettingsManage) {
+        return reply;
+    }
+    // the binary publishes its report at startup; an embedded router reports on demand instead.
+    let report = startup::published_report().cloned().unwrap_or_else(|| {
+        startup::collect_report("Runinator Web Service", env!("CARGO_PKG_VERSION"))
+    });
+    (StatusCode::OK, Json(ApiResponse::StartupReport(report)))
+}
@@ -14,8 +14,8 @@ use uuid::Uuid;
 
 use crate::auth::{AuthConfig, issue_access_token};
 use crate::authz;
-use crate::models::{ApiError, ApiResponse};
-use crate::responses::{api_error, bad_request, not_found};
+use crate::models::{ApiError, ApiErrorCode, ApiResponse};
+use crate::responses::{api_error, bad_request, conflict, not_found, repository_error};
 
 type Reply = (StatusCode, Json<ApiResponse>);
 
@@ -23,6 +23,7 @@ fn forbidden() -> Reply {
     (
         StatusCode::FORBIDDEN,
         Json(ApiResponse::ApiError(ApiError::new(
+            ApiErrorCode::Forbidden,
             "insufficient permission for this organization",
         ))),
     )
@@ -43,6 +44,7 @@ fn require_principal(ctx: &AuthContext) -> Result<Uuid, Reply> {
         (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::ApiError(ApiError::new(
+                ApiErrorCode::ValidationFailed,
                 "this action requires a user principal",
             ))),
         )
@@ -71,19 +73,19 @@ pub(crate) async fn create_org<T: DatabaseImpl>(
         return bad_request("organization slug resolves to empty; provide an explicit slug");
     }
     match db.fetch_org_by_slug(slug.clone()).await {
-        Ok(Some(_)) => return bad_request(format!("slug '{slug}' is already taken")),
+        Ok(Some(_)) => return conflict(format!("slug '{slug}' is already taken")),
         Ok(None) => {}
-        Err(err) => return api_error(err.to_string()),
+        Err(err) => return repository_error(err.as_ref()),
     }
     let org = match db.create_org(name, slug).await {
         Ok(org) => org,
-        Err(err) => return api_error(err.to_string()),
+        Err(err) => return repository_error(err.as_ref()),
     };
     let Some(org_id) = org.id else {
         return api_error("created org is missing an id");
     };
     if let Err(err) = db.add_org_member(org_id, user_id, OrgRole::Owner).await {
-        return api_error(err.to_string());
+        return repository_error(err.as_ref());
     }
     ok_value(&org)
 }
@@ -98,7 +100,7 @@ pub(crate) async fn list_orgs<T: DatabaseImpl>(
     }
     match db.list_orgs().await {
         Ok(orgs) => ok_value(&orgs),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -119,7 +121,7 @@ pub(crate) async fn list_my_orgs<T: DatabaseImpl>(
                 .collect();
             ok_value(&views)
         }
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -135,7 +137,7 @@ pub(crate) async fn get_org<T: DatabaseImpl>(
     match db.fetch_org(org_id).await {
         Ok(Some(org)) => ok_value(&org),
         Ok(None) => not_found("organization not found"),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -155,7 +157,7 @@ pub(crate) async fn update_org<T: DatabaseImpl>(
     }
     match db.update_org(org_id, name, request.disabled).await {
         Ok(org) => ok_value(&org),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -170,7 +172,7 @@ pub(crate) async fn delete_org<T: DatabaseImpl>(
     }
     match db.delete_org(org_id).await {
         Ok(()) => ok_value(&serde_json::json!({ "deleted": org_id })),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -185,7 +187,7 @@ pub(crate) async fn list_org_members<T: DatabaseImpl>(
     }
     match db.list_org_members(org_id).await {
         Ok(members) => ok_value(&members),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -212,7 +214,7 @@ pub(crate) async fn add_org_member<T: DatabaseImpl>(
         .await
     {
         Ok(()) => ok_value(&serde_json::json!({ "org_id": org_id, "user_id": request.user_id })),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -232,7 +234,7 @@ pub(crate) async fn update_org_member<T: DatabaseImpl>(
     }
     match db.add_org_member(org_id, user_id, request.role).await {
         Ok(()) => ok_value(&serde_json::json!({ "org_id": org_id, "user_id": user_id })),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -251,7 +253,7 @@ pub(crate) async fn remove_org_member<T: DatabaseImpl>(
     }
     match db.remove_org_member(org_id, user_id).await {
         Ok(()) => ok_value(&serde_json::json!({ "removed": user_id })),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -269,12 +271,12 @@ pub(crate) async fn switch_org<T: DatabaseImpl>(
     let membership = match db.fetch_org_membership(request.org_id, user_id).await {
         Ok(Some(membership)) => membership,
         Ok(None) => return forbidden(),
-        Err(err) => return api_error(err.to_string()),
+        Err(err) => return repository_error(err.as_ref()),
     };
     let org = match db.fetch_org(request.org_id).await {
         Ok(Some(org)) => org,
         Ok(None) => return not_found("organization not found"),
-        Err(err) => return api_error(err.to_string()),
+        Err(err) => return repository_error(err.as_ref()),
     };
     if org.disabled {
         return forbidden();
@@ -310,7 +312,7 @@ async fn guard_last_owner<T: DatabaseImpl>(
     let members = db
         .list_org_members(org_id)
         .await
-        .map_err(|err| api_error(err.to_string()))?;
+        .map_err(|err| repository_error(err.as_ref()))?;
     let is_target_owner = members
         .iter()
         .any(|m| m.user_id == user_id && m.role == OrgRole::Owner);
@@ -11,18 +11,20 @@ use runinator_models::workflows::WorkflowBundle;
 use runinator_models::{
     auth::AuthContext,
     bundles::{PackImportResult, SecretBundle},
+    protection::PendingWorkflowChange,
 };
 use serde::Deserialize;
 use utoipa::IntoParams;
 
 use crate::events::{EventSender, emit_workflows_changed};
 use crate::handlers::credentials::import_secret_entries_with;
+use crate::handlers::protection::hold_protected_imports;
 use crate::handlers::workflows::{
     json_workflow_import_risk_acknowledged, json_workflow_import_risk_required,
 };
 use crate::models::ApiResponse;
 use crate::repository;
-use crate::responses::{api_error, bad_request};
+use crate::responses::{bad_request, repository_error};
 
 // query parameters for the pack import endpoint.
 #[derive(Debug, Default, Deserialize, IntoParams)]
@@ -57,6 +59,7 @@ pub(crate) struct PackImportParams {
     ),
     responses(
         (status = 200, description = "pack or workflow bundle imported", body = serde_json::Value),
+        (status = 202, description = "imported, with the changes to protected workflows listed in `held` awaiting approval", body = serde_json::Value),
         (status = 400, description = "invalid zip, invalid json, or missing risk acknowledgment", body = crate::models::ApiError),
         (status = 401, description = "request is missing or has an invalid credential", body = crate::models::ApiError),
     ),
@@ -89,6 +92,10 @@ pub(crate) async fn import_pack<T: DatabaseImpl>(
             Err(err) => return bad_request(format!("invalid workflow bundle json: {err}")),
         };
         stamp_bundle_org(&mut bundle, import_org);
+        let held = match hold_protected_imports(db.as_ref(), &ctx, &mut bundle, overwrite).await {
+            Ok(held) => held,
+            Err(reply) => return reply,
+        };
         log::info!(
             "Importing json workflow bundle through pack endpoint: {} workflows, {} triggers (overwrite={overwrite})",
             bundle.workflows.len(),
@@ -97,15 +104,16 @@ pub(crate) async fn import_pack<T: DatabaseImpl>(
         let workflows =
             match repository::import_workflow_bundle_with(db.as_ref(), bundle, overwrite).await {
                 Ok(bundle) => bundle,
-                Err(err) => return api_error(err.to_string()),
+                Err(err) => return repository_error(err.as_ref()),
             };
         emit_workflows_changed(&events, import_org);
         return (
-            StatusCode::OK,
+            import_status(&held),
             Json(ApiResponse::PackImport(PackImportResult {
                 workflows,
                 secrets: SecretBundle::default(),
                 pipelines: Vec::new(),
+                held,
             })),
         );
     }
@@ -118,6 +126,11 @@ pub(crate) async fn import_pack<T: DatabaseImpl>(
     let secret_bundle = contents.secrets;
     let pipeline_bundle = contents.pipelines;
     stamp_bundle_org(&mut workflow_bundle, import_org);
+    let held =
+        match hold_protected_imports(db.as_ref(), &ctx, &mut workflow_bundle, overwrite).await {
+            Ok(held) => held,
+            Err(reply) => return reply,
+        };
     log::info!(
         "Importing pack: {} workflows, {} triggers, {} secrets (overwrite={overwrite})",
         workflow_bundle.workflows.len(),
@@ -144,7 +157,7 @@ pub(crate) async fn import_pack<T: DatabaseImpl>(
     .await
     {
         Ok(bundle) => bundle,
-        Err(err) => return api_error(err.to_string()),
+        Err(err) => return repository_error(err.as_ref()),
     };
     // import pipelines after workflows so member names resolve to freshly-imported ids, and their
     // links materialize as managed chained triggers stamped with the pipeline id.
@@ -152,7 +165,7 @@ pub(crate) async fn import_pack<T: DatabaseImpl>(
         Some(bundle) => {
             match repository::import_pipeline_bundle_with(db.as_ref(), bundle, import_org).await {
                 Ok(imported) => imported,
-                Err(err) => return api_error(err.to_string()),
+                Err(err) => return repository_error(err.as_ref()),
             }
         }
         None => Vec::new(),
@@ -162,15 +175,25 @@ pub(crate) async fn import_pack<T: DatabaseImpl>(
     }
     emit_workflows_changed(&events, import_org);
     (
-        StatusCode::OK,
+        import_status(&held),
         Json(ApiResponse::PackImport(PackImportResult {
             workflows,
             secrets,
             pipelines,
+            held,
         })),
     )
 }
 
+// `202` when part of the pack was held for approval rather than imported.
+fn import_status(held: &[PendingWorkflowChange]) -> StatusCode {
+    if held.is_empty() {
+        StatusCode::OK
+    } else {
+        StatusCode::ACCEPTED
+    }
+}
+
 // stamp every workflow in an imported bundle with the target org so it lands in the caller's tenant.
 fn stamp_bundle_org(bundle: &mut WorkflowBundle, org_id: Option<uuid::Uuid>) {
     for workflow in &mut bundle.workflows {
@@ -15,7 +15,7 @@ use crate::events::{
 };
 use crate::models::{ApiResponse, PipelineOwnerRequest, PipelineRunRequest};
 use crate::repository;
-use crate::responses::{api_error, not_found};
+use crate::responses::{not_found, repository_error};
 
 pub(crate) async fn get_pipelines<T: DatabaseImpl>(
     Extension(db): Extension<Arc<T>>,
@@ -39,7 +39,7 @@ pub(crate) async fn get_pipelines<T: DatabaseImpl>(
             };
             (StatusCode::OK, Json(ApiResponse::PipelineList(pipelines)))
         }
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -60,7 +60,7 @@ pub(crate) async fn get_pipeline<T: DatabaseImpl>(
         }
         Ok(Some(pipeline)) => (StatusCode::OK, Json(ApiResponse::Pipeline(pipeline))),
         Ok(None) => not_found(format!("Pipeline {pipeline_id} not found")),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -81,7 +81,7 @@ pub(crate) async fn create_pipeline<T: DatabaseImpl>(
             emit_workflows_changed(&events, pipeline.org_id);
             (StatusCode::OK, Json(ApiResponse::Pipeline(pipeline)))
         }
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -102,14 +102,14 @@ pub(crate) async fn update_pipeline<T: DatabaseImpl>(
     pipeline.org_id = match repository::fetch_pipeline(db.as_ref(), pipeline_id).await {
         Ok(Some(existing)) => existing.org_id,
         Ok(None) => return not_found(format!("Pipeline {pipeline_id} not found")),
-        Err(err) => return api_error(err.to_string()),
+        Err(err) => return repository_error(err.as_ref()),
     };
     match repository::upsert_pipeline(db.as_ref(), &pipeline).await {
         Ok(pipeline) => {
             emit_workflows_changed(&events, pipeline.org_id);
             (StatusCode::OK, Json(ApiResponse::Pipeline(pipeline)))
         }
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -138,10 +138,10 @@ pub(crate) async fn set_pipeline_owner<T: DatabaseImpl>(
             match repository::fetch_pipeline(db.as_ref(), pipeline_id).await {
                 Ok(Some(pipeline)) => (StatusCode::OK, Json(ApiResponse::Pipeline(pipeline))),
                 Ok(None) => not_found(format!("Pipeline {pipeline_id} not found")),
-                Err(err) => api_error(err.to_string()),
+                Err(err) => repository_error(err.as_ref()),
             }
         }
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -166,7 +166,7 @@ pub(crate) async fn delete_pipeline<T: DatabaseImpl>(
             emit_workflows_changed(&events, org_id);
             (StatusCode::OK, Json(ApiResponse::TaskResponse(resp)))
         }
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -187,7 +187,7 @@ pub(crate) async fn get_pipeline_triggers<T: DatabaseImpl>(
             StatusCode::OK,
             Json(ApiResponse::PipelineTriggerList(triggers)),
         ),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -210,7 +210,7 @@ pub(crate) async fn upsert_pipeline_trigger<T: DatabaseImpl>(
             emit_workflows_changed(&events, org_id);
             (StatusCode::OK, Json(ApiResponse::PipelineTrigger(trigger)))
         }
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -233,7 +233,7 @@ pub(crate) async fn update_pipeline_trigger<T: DatabaseImpl>(
             emit_workflows_changed(&events, org_id);
             (StatusCode::OK, Json(ApiResponse::PipelineTrigger(trigger)))
         }
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -257,7 +257,7 @@ pub(crate) async fn delete_pipeline_trigger<T: DatabaseImpl>(
             emit_workflows_changed(&events, org_id);
             (StatusCode::OK, Json(ApiResponse::TaskResponse(resp)))
         }
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -294,7 +294,7 @@ pub(crate) async fn create_pipeline_run<T: DatabaseImpl>(
             nudge_wake_publisher(&events);
             (StatusCode::ACCEPTED, Json(ApiResponse::PipelineRun(run)))
         }
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -329,7 +329,7 @@ pub(crate) async fn create_pipeline_trigger_run<T: DatabaseImpl>(
             nudge_wake_publisher(&events);
             (StatusCode::ACCEPTED, Json(ApiResponse::PipelineRun(run)))
         }
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -349,7 +349,7 @@ pub(crate) async fn get_pipeline_runs<T: DatabaseImpl>(
             };
             (StatusCode::OK, Json(ApiResponse::PipelineRunList(runs)))
         }
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -366,7 +366,7 @@ pub(crate) async fn get_pipeline_run<T: DatabaseImpl>(
     match repository::fetch_pipeline_run_detail(db.as_ref(), pipeline_run_id).await {
         Ok(Some(detail)) => (StatusCode::OK, Json(ApiResponse::PipelineRunDetail(detail))),
         Ok(None) => not_found(format!("Pipeline run {pipeline_run_id} not found")),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -391,7 +391,7 @@ pub(crate) async fn cancel_pipeline_run<T: DatabaseImpl>(
             );
             (StatusCode::OK, Json(ApiResponse::TaskResponse(resp)))
         }
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -0,0 +1,540 @@
+//! second-approver protection for critical workflows. while a workflow is protected, saving,
+//! importing, deleting, enabling, disabling, reverting, re-owning, changing its retention, editing
+//! its triggers, or unprotecting it is held as a pending change instead of applied; a different
+//! authenticated principal with edit rights then approves or rejects it.
+
+use std::sync::Arc;
+use uuid::Uuid;
+
+use axum::{Extension, Json, extract::Path, http::StatusCode};
+use runinator_database::interfaces::DatabaseImpl;
+use runinator_models::{
+    auth::{AuthContext, Permission, PrincipalKind},
+    protection::{
+        NewPendingWorkflowChange, PendingChangeKind, PendingChangeStatus, PendingWorkflowChange,
+        WorkflowOwnerChange,
+    },
+    retention::WorkflowRetention,
+    schedule_shift::ScheduleShiftReport,
+    value::Value,
+    workflows::{WorkflowBundle, WorkflowDefinition, WorkflowTrigger},
+};
+
+use crate::audit::{AuditOutcome, record_audit};
+use crate::authz;
+use crate::events::{EventSender, emit_workflows_changed};
+use crate::models::{ApiError, ApiErrorCode, ApiResponse};
+use crate::repository;
+use crate::responses::{api_error, bad_request, not_found, repository_error, validation_error};
+
+type Reply = (StatusCode, Json<ApiResponse>);
+
+fn actor_kind(ctx: &AuthContext) -> &'static str {
+    match ctx.kind {
+        PrincipalKind::User => "user",
+        PrincipalKind::Service => "service",
+    }
+}
+
+fn forbidden(message: &str) -> Reply {
+    (
+        StatusCode::FORBIDDEN,
+        Json(ApiResponse::ApiError(ApiError::new(
+            ApiErrorCode::Forbidden,
+            message,
+        ))),
+    )
+}
+
+async fn audit_change<T: DatabaseImpl>(
+    db: &T,
+    ctx: &AuthContext,
+    action: &str,
+    change: &PendingWorkflowChange,
+) {
+    record_audit(
+        db,
+        ctx.principal_id,
+        actor_kind(ctx),
+        action,
+        AuditOutcome::Success,
+        Some("workflow"),
+        Some(change.workflow_id),
+        Some(&format!(
+            "change={} kind={}",
+            change.id,
+            change.kind.as_str()
+        )),
+    )
+    .await;
+}
+
+/// hold a mutation to `workflow_id` for approval when the workflow is protected. `None` means it
+/// is not protected and the caller applies the mutation itself; otherwise the reply is the held
+/// change (202) or an error.
+pub(crate) async fn hold_if_protected<T: DatabaseImpl>(
+    db: &T,
+    ctx: &AuthContext,
+    workflow_id: Uuid,
+    kind: PendingChangeKind,
+    payload: Value,
+) -> Option<Reply> {
+    match hold_change(db, ctx, workflow_id, kind, payload).await {
+        Ok(Some(change)) => Some((
+            StatusCode::ACCEPTED,
+            Json(ApiResponse::PendingWorkflowChange(change)),
+        )),
+        Ok(None) => None,
+        Err(reply) => Some(reply),
+    }
+}
+
+// the held change, or `None` when `workflow_id` is not protected.
+async fn hold_change<T: DatabaseImpl>(
+    db: &T,
+    ctx: &AuthContext,
+    workflow_id: Uuid,
+    kind: PendingChangeKind,
+    payload: Value,
+) -> Result<Option<PendingWorkflowChange>, Reply> {
+    match repository::fetch_workflow_protection(db, workflow_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return Ok(None),
+        Err(err) => return Err(repository_error(err.as_ref())),
+    }
+    let change = NewPendingWorkflowChange {
+        workflow_id,
+        kind,
+        payload,
+        requested_by: ctx.principal_id,
+    };
+    let change = repository::create_pending_workflow_change(db, &change)
+        .await
+        .map_err(|err| repository_error(err.as_ref()))?;
+    audit_change(db, ctx, "workflow.change.requested", &change).await;
+    Ok(Some(change))
+}
+
+/// take the parts of an import that would change a protected workflow out of `bundle` and hold
+/// each for approval: the workflow as an update, and each of the bundle's triggers on it as a
+/// trigger save. the rest of the bundle imports as usual.
+pub(crate) async fn hold_protected_imports<T: DatabaseImpl>(
+    db: &T,
+    ctx: &AuthContext,
+    bundle: &mut WorkflowBundle,
+    overwrite: bool,
+) -> Result<Vec<PendingWorkflowChange>, Reply> {
+    let mut held = Vec::new();
+    let mut workflows = Vec::with_capacity(bundle.workflows.len());
+    for workflow in std::mem::take(&mut bundle.workflows) {
+        let target = repository::fetch_import_target(db, &workflow, overwrite)
+            .await
+            .map_err(|err| repository_error(err.as_ref()))?;
+        let Some(id) = target else {
+            workflows.push(workflow);
+            continue;
+        };
+        let mut pending = workflow.clone();
+        pending.id = Some(id);
+        let payload = Value::encode(&pending).map_err(|err| api_error(err.to_string()))?;
+        match hold_change(db, ctx, id, PendingChangeKind::Update, payload).await? {
+            Some(change) => held.push(change),
+            None => workflows.push(workflow),
+        }
+    }
+    bundle.workflows = workflows;
+    let mut triggers = Vec::with_capacity(bundle.triggers.len());
+    for trigger in std::mem::take(&mut bundle.triggers) {
+        let payload = Value::encode(&trigger).map_err(|err| api_error(err.to_string()))?;
+        let kind = PendingChangeKind::UpsertTrigger;
+        match hold_change(db, ctx, trigger.workflow_id, kind, payload).await? {
+            Some(change) => held.push(change),
+            None => triggers.push(trigger),
+        }
+    }
+    bundle.triggers = triggers;
+    Ok(held)
+}
+
+/// hold the new cron of each bulk-shifted trigger the shift left alone because its workflow is
+/// protected, as a trigger save awaiting approval, and record the held change on its report entry.
+pub(crate) async fn hold_protected_shifts<T: DatabaseImpl>(
+    db: &T,
+    ctx: &AuthContext,
+    report: &mut ScheduleShiftReport,
+) -> Result<(), Reply> {
+    for change in report.changes.iter_mut().filter(|change| change.protected) {
+        let Some(cron) = change.new_cron.clone() else {
+            continue;
+        };
+        let mut trigger = match repository::fetch_workflow_trigger(db, change.trigger_id).await {
+            Ok(Some(trigger)) => trigger,
+            Ok(None) => continue,
+            Err(err) => return Err(repository_error(err.as_ref())),
+        };
+        match trigger.configuration.as_object_mut() {
+            Some(configuration) => {
+                configuration.insert("cron".into(), Value::String(cron));
+            }
+            None => trigger.configuration = runinator_models::json!({ "cron": cron }),
+        }
+        let payload = Value::encode(&trigger).map_err(|err| api_error(err.to_string()))?;
+        let kind = PendingChangeKind::UpsertTrigger;
+        if let Some(held) = hold_change(db, ctx, change.workflow_id, kind, payload).await? {
+            change.pending_change_id = Some(held.id);
+        }
+    }
+    Ok(())
+}
+
+/// the workflow's protection flag; `null` when it is not protected.
+pub(crate) async fn get_workflow_protection<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+    Extension(ctx): Extension<AuthContext>,
+    Path(workflow_id): Path<Uuid>,
+) -> Reply {
+    if let Err(reply) =
+        authz::require_workflow(db.as_ref(), &ctx, workflow_id, Permission::View).await
+    {
+        return reply;
+    }
+    match repository::fetch_workflow_protection(db.as_ref(), workflow_id).await {
+        Ok(protection) => (
+            StatusCode::OK,
+            Json(ApiResponse::WorkflowProtection(protection)),
+        ),
+        Err(err) => repository_error(err.as_ref()),
+    }
+}
+
+/// protect a workflow. turning protection on applies immediately; it only ever adds review.
+pub(crate) async fn protect_workflow<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+    Extension(ctx): Extension<AuthContext>,
+    Path(workflow_id): Path<Uuid>,
+) -> Reply {
+    if let Err(reply) =
+        authz::require_workflow(db.as_ref(), &ctx, workflow_id, Permission::Edit).await
+    {
+        return reply;
+    }
+    // with no principal nobody could ever approve a change, which would lock the workflow.
+    if ctx.principal_id.is_none() {
+        return bad_request("protecting a workflow needs an authenticated principal");
+    }
+    match repository::fetch_workflow(db.as_ref(), workflow_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return not_found(format!("Workflow {workflow_id} not found")),
+        Err(err) => return repository_error(err.as_ref()),
+    }
+    match repository::protect_workflow(db.as_ref(), workflow_id, ctx.principal_id).await {
+        Ok(protection) => {
+            record_audit(
+                db.as_ref(),
+                ctx.principal_id,
+                actor_kind(&ctx),
+                "workflow.protected",
+                AuditOutcome::Success,
+                Some("workflow"),
+                Some(workflow_id),
+                None,
+            )
+            .await;
+            (
+                StatusCode::OK,
+                Json(ApiResponse::WorkflowProtection(Some(protection))),
+            )
+        }
+        Err(err) => repository_error(err.as_ref()),
+    }
+}
+
+/// ask to lift a workflow's protection. like any other change to a protected workflow, this is held
+/// until a second principal approves it.
+pub(crate) async fn unprotect_workflow<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+    Extension(ctx): Extension<AuthContext>,
+    Path(workflow_id): Path<Uuid>,
+) -> Reply {
+    if let Err(reply) =
+        authz::require_workflow(db.as_ref(), &ctx, workflow_id, Permission::Edit).await
+    {
+        return reply;
+    }
+    match hold_if_protected(
+        db.as_ref(),
+        &ctx,
+        workflow_id,
+        PendingChangeKind::Unprotect,
+        Value::Null,
+    )
+    .await
+    {
+        Some(reply) => reply,
+        None => not_found(format!("Workflow {workflow_id} is not protected")),
+    }
+}
+
+/// a workflow's changes awaiting review, oldest first.
+pub(crate) async fn get_pending_workflow_changes<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+    Extension(ctx): Extension<AuthContext>,
+    Path(workflow_id): Path<Uuid>,
+) -> Reply {
+    if let Err(reply) =
+        authz::require_workflow(db.as_ref(), &ctx, workflow_id, Permission::View).await
+    {
+        return reply;
+    }
+    match repository::fetch_pending_workflow_changes(db.as_ref(), workflow_id).await {
+        Ok(changes) => (
+            StatusCode::OK,
+            Json(ApiResponse::PendingWorkflowChangeList(changes)),
+        ),
+        Err(err) => repository_error(err.as_ref()),
+    }
+}
+
+// the still-pending change `change_id` of `workflow_id`, after checking the caller may edit it.
+async fn reviewable_change<T: DatabaseImpl>(
+    db: &T,
+    ctx: &AuthContext,
+    workflow_id: Uuid,
+    change_id: Uuid,
+) -> Result<PendingWorkflowChange, Reply> {
+    authz::require_workflow(db, ctx, workflow_id, Permission::Edit).await?;
+    match repository::fetch_pending_workflow_change(db, change_id).await {
+        Ok(Some(change))
+            if change.workflow_id == workflow_id
+                && change.status == PendingChangeStatus::Pending =>
+        {
+            Ok(change)
+        }
+        Ok(_) => Err(not_found(format!(
+            "Workflow {workflow_id} has no pending change {change_id}"
+        ))),
+        Err(err) => Err(repository_error(err.as_ref())),
+    }
+}
+
+// what an approved change applies, decoded from its payload.
+enum ChangePayload {
+    Empty,
+    Workflow(WorkflowDefinition),
+    Trigger(WorkflowTrigger),
+    Owner(WorkflowOwnerChange),
+    Retention(WorkflowRetention),
+}
+
+// decode `change`'s payload, pinning it to `workflow_id` so an approval can only ever touch the
+// workflow the change was held on.
+async fn change_payload<T: DatabaseImpl>(
+    db: &T,
+    change: &PendingWorkflowChange,
+    workflow_id: Uuid,
+    current: &WorkflowDefinition,
+) -> Result<ChangePayload, Reply> {
+    match change.kind {
+        PendingChangeKind::Update => {
+            let mut workflow = change.workflow().map_err(api_error)?;
+            workflow.id = Some(workflow_id);
+            workflow.org_id = current.org_id;
+            repository::validate_workflow_definition_with_catalog(db, &workflow)
+                .await
+                .map(ChangePayload::Workflow)
+                .map_err(|err| validation_error(err.as_ref()))
+        }
+        PendingChangeKind::UpsertTrigger | PendingChangeKind::DeleteTrigger => {
+            let mut trigger = change.trigger().map_err(api_error)?;
+            if let Some(trigger_id) = trigger.id {
+                match repository::fetch_workflow_trigger(db, trigger_id).await {
+                    Ok(Some(stored)) if stored.workflow_id != workflow_id => {
+                        return Err(bad_request(format!(
+                            "Trigger {trigger_id} does not belong to workflow {workflow_id}"
+                        )));
+                    }
+                    Ok(_) => {}
+                    Err(err) => return Err(repository_error(err.as_ref())),
+                }
+            }
+            trigger.workflow_id = workflow_id;
+            Ok(ChangePayload::Trigger(trigger))
+        }
+        PendingChangeKind::SetOwner => change.owner().map(ChangePayload::Owner).map_err(api_error),
+        PendingChangeKind::SetRetention => {
+            let mut retention = change.retention().map_err(api_error)?;
+            retention.workflow_id = workflow_id;
+            Ok(ChangePayload::Retention(retention))
+        }
+        PendingChangeKind::Delete
+        | PendingChangeKind::Enable
+        | PendingChangeKind::Disable
+        | PendingChangeKind::Unprotect
+        | PendingChangeKind::ClearRetention => Ok(ChangePayload::Empty),
+    }
+}
+
+/// approve a pending change and apply it. the approver must be an authenticated principal other
+/// than the one who requested the change.
+pub(crate) async fn approve_pending_workflow_change<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+    Extension(events): Extension<EventSender>,
+    Extension(ctx): Extension<AuthContext>,
+    Path((workflow_id, change_id)): Path<(Uuid, Uuid)>,
+) -> Reply {
+    let mut change = match reviewable_change(db.as_ref(), &ctx, workflow_id, change_id).await {
+        Ok(change) => change,
+        Err(reply) => return reply,
+    };
+    if let Err(message) = change.check_approver(ctx.principal_id) {
+        record_audit(
+            db.as_ref(),
+            ctx.principal_id,
+            actor_kind(&ctx),
+            "workflow.change.approved",
+            AuditOutcome::Denied,
+            Some("workflow"),
+            Some(workflow_id),
+            Some(&format!("change={change_id} reason={message}")),
+        )
+        .await;
+        return forbidden(message);
+    }
+    let current = match repository::fetch_workflow(db.as_ref(), workflow_id).await {
+        Ok(Some(current)) => current,
+        Ok(None) => return not_found(format!("Workflow {workflow_id} not found")),
+        Err(err) => return repository_error(err.as_ref()),
+    };
+    // decode the change, and validate an edit, before marking it approved, so one that can no
+    // longer apply stays pending instead of being approved and then failing.
+    let payload = match change_payload(db.as_ref(), &change, workflow_id, &current).await {
+        Ok(payload) => payload,
+        Err(reply) => return reply,
+    };
+    // claim the review first so two concurrent approvals cannot both apply the change.
+    match repository::review_pending_workflow_change(
+        db.as_ref(),
+        change_id,
+        PendingChangeStatus::Approved,
+        ctx.principal_id,
+    )
+    .await
+    {
+        Ok(true) => {}
+        Ok(false) => {
+            return not_found(format!(
+                "Workflow {workflow_id} has no pending change {change_id}"
+            ));
+        }
+        Err(err) => return repository_error(err.as_ref()),
+    }
+    let mut org_id = current.org_id;
+    let applied = match (change.kind, payload) {
+        (PendingChangeKind::Update, ChangePayload::Workflow(workflow)) => {
+            repository::upsert_workflow_as(db.as_ref(), &workflow, change.requested_by)
+                .await
+                .map(|_| ())
+        }
+        (PendingChangeKind::Delete, _) => repository::delete_workflow(db.as_ref(), workflow_id)
+            .await
+            .map(|_| ()),
+        (PendingChangeKind::Enable | PendingChangeKind::Disable, _) => {
+            let enabled = change.kind == PendingChangeKind::Enable;
+            match repository::set_workflow_enabled(db.as_ref(), workflow_id, enabled).await {
+                Ok(_) => match repository::fetch_workflow(db.as_ref(), workflow_id).await {
+                    Ok(Some(workflow)) => db
+                        .record_workflow_revision(&workflow, change.requested_by)
+                        .await
+                        .map(|_| ()),
+                    Ok(None) => Ok(()),
+                    Err(err) => Err(err),
+                },
+                Err(err) => Err(err),
+            }
+        }
+        (PendingChangeKind::Unprotect, _) => {
+            repository::unprotect_workflow(db.as_ref(), workflow_id)
+                .await
+                .map(|_| ())
+        }
+        (PendingChangeKind::SetOwner, ChangePayload::Owner(owner)) => {
+            org_id = owner.org_id;
+            repository::set_workflow_org(db.as_ref(), workflow_id, owner.org_id).await
+        }
+        (PendingChangeKind::SetRetention, ChangePayload::Retention(retention)) => {
+            repository::set_workflow_retention(db.as_ref(), retention)
+                .await
+                .map(|_| ())
+        }
+        (PendingChangeKind::ClearRetention, _) => {
+            repository::clear_workflow_retention(db.as_ref(), workflow_id)
+                .await
+                .map(|_| ())
+        }
+        (PendingChangeKind::UpsertTrigger, ChangePayload::Trigger(trigger)) => {
+            repository::upsert_workflow_trigger(db.as_ref(), &trigger)
+                .await
+                .map(|_| ())
+        }
+        (PendingChangeKind::DeleteTrigger, ChangePayload::Trigger(trigger)) => match trigger.id {
+            Some(trigger_id) => repository::delete_workflow_trigger(db.as_ref(), trigger_id)
+                .await
+                .map(|_| ()),
+            None => Ok(()),
+        },
+        _ => Ok(()),
+    };
+    if let Err(err) = applied {
+        log::error!("failed to apply approved change {change_id} to workflow {workflow_id}: {err}");
+        return repository_error(err.as_ref());
+    }
+    audit_change(db.as_ref(), &ctx, "workflow.change.approved", &change).await;
+    if org_id != current.org_id {
+        emit_workflows_changed(&events, current.org_id);
+    }
+    emit_workflows_changed(&events, org_id);
+    change.status = PendingChangeStatus::Approved;
+    change.reviewed_by = ctx.principal_id;
+    change.reviewed_at = Some(chrono::Utc::now());
+    (
+        StatusCode::OK,
+        Json(ApiResponse::PendingWorkflowChange(change)),
+    )
+}
+
+/// reject a pending change; it is kept for the record but never applied. the requester may reject
+/// (withdraw) their own change.
+pub(crate) async fn reject_pending_workflow_change<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+    Extension(ctx): Extension<AuthContext>,
+    Path((workflow_id, change_id)): Path<(Uuid, Uuid)>,
+) -> Reply {
+    let mut change = match reviewable_change(db.as_ref(), &ctx, workflow_id, change_id).await {
+        Ok(change) => change,
+        Err(reply) => return reply,
+    };
+    match repository::review_pending_workflow_change(
+        db.as_ref(),
+        change_id,
+        PendingChangeStatus::Rejected,
+        ctx.principal_id,
+    )
+    .await
+    {
+        Ok(true) => {}
+        Ok(false) => {
+            return not_found(format!(
+                "Workflow {workflow_id} has no pending change {change_id}"
+            ));
+        }
+        Err(err) => return repository_error(err.as_ref()),
+    }
+    audit_change(db.as_ref(), &ctx, "workflow.change.rejected", &change).await;
+    change.status = PendingChangeStatus::Rejected;
+    change.reviewed_by = ctx.principal_id;
+    change.reviewed_at = Some(chrono::Utc::now());
+    (
+        StatusCode::OK,
+        Json(ApiResponse::PendingWorkflowChange(change)),
+    )
+}
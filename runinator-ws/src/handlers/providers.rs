@@ -1,17 +1,24 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
-use axum::{Extension, Json, http::StatusCode};
+use axum::{Extension, Json, extract::Query, http::StatusCode};
 use runinator_database::interfaces::DatabaseImpl;
 use runinator_models::value::Value;
 use runinator_models::{
     auth::AuthContext,
     bundles::ProviderBundle,
+    errors::SendableError,
+    provider_stats::{
+        DEFAULT_PROVIDER_STATS_WINDOW_HOURS, ProviderStatsReport, ProviderStatsWindow,
+    },
     providers::{ProviderMetadata, validate_provider_metadata},
 };
 
-use crate::models::ApiResponse;
+use uuid::Uuid;
+
+use crate::models::{ApiResponse, ProviderStatsQuery};
 use crate::repository;
-use crate::responses::{api_error, bad_request};
+use crate::responses::{api_error, bad_request, repository_error};
 
 /// list registered task providers and their action metadata.
 #[utoipa::path(
@@ -28,7 +35,7 @@ pub(crate) async fn get_providers<T: DatabaseImpl>(
         .await
     {
         Ok(items) => items,
-        Err(err) => return api_error(err.to_string()),
+        Err(err) => return repository_error(err.as_ref()),
     };
 
     match provider_metadata_from_items(items) {
@@ -37,6 +44,97 @@ pub(crate) async fn get_providers<T: DatabaseImpl>(
     }
 }
 
+/// the longest window the provider stats report aggregates over: 30 days.
+pub(crate) const MAX_PROVIDER_STATS_WINDOW_HOURS: i64 = 24 * 30;
+
+/// at most this many windows per request, each costing one aggregate query.
+const MAX_PROVIDER_STATS_WINDOWS: usize = 8;
+
+/// run counts and average durations per provider action over recent windows, for deciding which
+/// plugins deserve dedicated workers. covers the caller's visible workflows.
+#[utoipa::path(
+    get,
+    path = "/stats/providers",
+    tag = "Providers",
+    responses((status = 200, description = "provider stats report", body = serde_json::Value)),
+)]
+pub(crate) async fn get_provider_stats<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+    Extension(ctx): Extension<AuthContext>,
+    Query(query): Query<ProviderStatsQuery>,
+) -> (StatusCode, Json<ApiResponse>) {
+    let windows = match parse_stats_windows(query.windows.as_deref()) {
+        Ok(windows) => windows,
+        Err(message) => return bad_request(message),
+    };
+    let actions = match visible_task_actions(db.as_ref(), &ctx).await {
+        Ok(actions) => actions,
+        Err(err) => return repository_error(err.as_ref()),
+    };
+    let generated_at = chrono::Utc::now();
+    let mut report = ProviderStatsReport {
+        generated_at,
+        windows: Vec::with_capacity(windows.len()),
+    };
+    for hours in windows {
+        let since = generated_at - chrono::Duration::hours(hours);
+        let counts = match repository::fetch_node_run_counts(db.as_ref(), since).await {
+            Ok(counts) => counts,
+            Err(err) => return repository_error(err.as_ref()),
+        };
+        report
+            .windows
+            .push(ProviderStatsWindow::build(hours, since, &counts, &actions));
+    }
+    (StatusCode::OK, Json(ApiResponse::ProviderStats(report)))
+}
+
+/// the provider and function each task (workflow node) of the caller's visible workflows runs.
+/// tasks are attributed through the current definition, so a node whose action was edited reports
+/// under its new provider.
+pub(crate) async fn visible_task_actions<T: DatabaseImpl>(
+    db: &T,
+    ctx: &AuthContext,
+) -> Result<HashMap<(Uuid, String), (String, String)>, SendableError> {
+    let visible = crate::authz::visible_workflow_ids(db, ctx).await;
+    Ok(repository::fetch_workflows(db)
+        .await?
+        .into_iter()
+        .filter_map(|workflow| workflow.id.map(|id| (id, workflow.definition.nodes)))
+        .filter(|(id, _)| visible.as_ref().is_none_or(|ids| ids.contains(id)))
+        .flat_map(|(id, nodes)| {
+            nodes.into_iter().filter_map(move |node| {
+                let action = node.action?;
+                Some(((id, node.id), (action.provider, action.function)))
+            })
+        })
+        .collect())
+}
+
+/// parse `?windows=` as comma-separated hours, clamped to the longest window. absent uses the
+/// default hour/day/week windows.
+fn parse_stats_windows(windows: Option<&str>) -> Result<Vec<i64>, String> {
+    let Some(windows) = windows.filter(|windows| !windows.trim().is_empty()) else {
+        return Ok(DEFAULT_PROVIDER_STATS_WINDOW_HOURS.to_vec());
+    };
+    let hours = windows
+        .split(',')
+        .map(|window| match window.trim().parse::<i64>() {
+            Ok(hours) if hours > 0 => Ok(hours.min(MAX_PROVIDER_STATS_WINDOW_HOURS)),
+            _ => Err(format!(
+                "windows must be positive whole hours, got '{}'",
+                window.trim()
+            )),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if hours.len() > MAX_PROVIDER_STATS_WINDOWS {
+        return Err(format!(
+            "at most {MAX_PROVIDER_STATS_WINDOWS} windows may be requested"
+        ));
+    }
+    Ok(hours)
+}
+
 pub(crate) async fn upsert_provider<T: DatabaseImpl>(
     Extension(db): Extension<Arc<T>>,
     Extension(ctx): Extension<AuthContext>,
@@ -51,7 +149,7 @@ pub(crate) async fn upsert_provider<T: DatabaseImpl>(
     let item = provider_catalog_item(&provider);
     let item = match repository::upsert_catalog_item(db.as_ref(), item).await {
         Ok(item) => item,
-        Err(err) => return api_error(err.to_string()),
+        Err(err) => return repository_error(err.as_ref()),
     };
 
     match provider_metadata_from_item(item) {
@@ -76,7 +174,7 @@ pub(crate) async fn import_provider_bundle<T: DatabaseImpl>(
         let item = provider_catalog_item(provider);
         let item = match repository::upsert_catalog_item(db.as_ref(), item).await {
             Ok(item) => item,
-            Err(err) => return api_error(err.to_string()),
+            Err(err) => return repository_error(err.as_ref()),
         };
         match provider_metadata_from_item(item) {
             Ok(provider) => imported.push(provider),
@@ -7,7 +7,7 @@ use runinator_models::provisioning::{NodeBackendsResponse, ScaleNodesRequest, St
 use runinator_provisioner::ProvisionerRegistry;
 
 use crate::models::ApiResponse;
-use crate::responses::api_error;
+use crate::responses::repository_error;
 
 /// list every configured provisioning backend and the node kinds it can manage.
 #[utoipa::path(
@@ -59,14 +59,14 @@ pub(crate) async fn scale_nodes(
     }
     let provisioner = match registry.require(request.backend) {
         Ok(provisioner) => provisioner,
-        Err(err) => return api_error(err.to_string()),
+        Err(err) => return repository_error(err.as_ref()),
     };
     match provisioner
         .scale(request.kind, request.desired, &request.spec)
         .await
     {
         Ok(group) => (StatusCode::OK, Json(ApiResponse::NodeGroup(group))),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -81,7 +81,7 @@ pub(crate) async fn stop_node(
     }
     let provisioner = match registry.require(request.backend) {
         Ok(provisioner) => provisioner,
-        Err(err) => return api_error(err.to_string()),
+        Err(err) => return repository_error(err.as_ref()),
     };
     match provisioner.stop(&request.node_id).await {
         Ok(()) => (
@@ -90,6 +90,6 @@ pub(crate) async fn stop_node(
                 "stopped": request.node_id,
             }))),
         ),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
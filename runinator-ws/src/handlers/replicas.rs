@@ -15,9 +15,9 @@ use runinator_models::{
     },
 };
 
-use crate::models::{ApiResponse, ReplicaQuery, ReplicaSampleQuery};
+use crate::models::{ApiResponse, ReplicaQuery, ReplicaSampleQuery, WorkerQuery};
 use crate::repository;
-use crate::responses::{api_error, not_found};
+use crate::responses::{not_found, repository_error};
 
 pub(crate) async fn register_replica<T: DatabaseImpl>(
     Extension(db): Extension<Arc<T>>,
@@ -33,7 +33,7 @@ pub(crate) async fn register_replica<T: DatabaseImpl>(
         .await
     {
         Ok(replica) => (StatusCode::OK, Json(ApiResponse::Replica(replica))),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -60,7 +60,7 @@ pub(crate) async fn heartbeat_replica<T: DatabaseImpl>(
         Ok(None) => not_found(format!(
             "Replica {replica_id} not found or runtime mismatch"
         )),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -78,7 +78,7 @@ pub(crate) async fn mark_replica_offline<T: DatabaseImpl>(
         Ok(None) => not_found(format!(
             "Replica {replica_id} not found or runtime mismatch"
         )),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -96,7 +96,19 @@ pub(crate) async fn get_replicas<T: DatabaseImpl>(
 ) -> (StatusCode, Json<ApiResponse>) {
     match repository::fetch_replicas(db.as_ref(), query.replica_type, query.status).await {
         Ok(replicas) => (StatusCode::OK, Json(ApiResponse::ReplicaList(replicas))),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
+    }
+}
+
+/// list worker replicas with their plugins, registered providers, and current load.
+pub(crate) async fn get_workers<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+    Extension(_ctx): Extension<AuthContext>,
+    Query(query): Query<WorkerQuery>,
+) -> (StatusCode, Json<ApiResponse>) {
+    match repository::fetch_workers(db.as_ref(), query.status).await {
+        Ok(workers) => (StatusCode::OK, Json(ApiResponse::WorkerList(workers))),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -109,7 +121,7 @@ pub(crate) async fn get_replica_samples<T: DatabaseImpl>(
 ) -> (StatusCode, Json<ApiResponse>) {
     match repository::fetch_replica_samples(db.as_ref(), replica_id, query.since_seconds).await {
         Ok(series) => (StatusCode::OK, Json(ApiResponse::ReplicaSamples(series))),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -127,7 +139,7 @@ pub(crate) async fn upsert_replica_provider<T: DatabaseImpl>(
             StatusCode::OK,
             Json(ApiResponse::ReplicaProviderRegistration(registration)),
         ),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -141,7 +153,7 @@ pub(crate) async fn get_replica_providers<T: DatabaseImpl>(
             StatusCode::OK,
             Json(ApiResponse::ReplicaProviderRegistrationList(registrations)),
         ),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
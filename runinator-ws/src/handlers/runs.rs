@@ -1,4 +1,4 @@
-use std::{net::SocketAddr, sync::Arc};
+use std::{collections::HashMap, net::SocketAddr, sync::Arc};
 use uuid::Uuid;
 
 use axum::{
@@ -9,8 +9,15 @@ use axum::{
 use runinator_broker::Broker;
 use runinator_database::interfaces::DatabaseImpl;
 use runinator_models::orchestration::{ReadyNodeClaimRequest, ReadyNodeProcessRequest};
+use runinator_models::queue_wait::{DEFAULT_QUEUE_WAIT_WINDOW_HOURS, QueueWaitReport};
 use runinator_models::replicas::{TriggerActorType, TriggerSourceKind, WorkflowRunProvenance};
+use runinator_models::run_health::RunHealthSummary;
 use runinator_models::runs::NewRunChunk;
+use runinator_models::upcoming::{
+    DEFAULT_UPCOMING_MINUTES, MAX_UPCOMING_FIRINGS_PER_TRIGGER, MAX_UPCOMING_MINUTES,
+    SchedulerPreview, UpcomingEntry, UpcomingKind, UpcomingSchedule,
+};
+use runinator_models::workflows::WorkflowStatus;
 use serde::Deserialize;
 
 use crate::events::{
@@ -18,13 +25,13 @@ use crate::events::{
     nudge_wake_publisher,
 };
 use crate::models::{
-    self, ApiResponse, RunStatusQuery, RunStatusRequest, SchedulerRunClaimReleaseRequest,
-    SchedulerRunClaimRenewRequest, SchedulerRunClaimRequest, TaskResponseSchema,
-    WorkflowRunRequest, WorkflowRunStatusQuery, WorkflowRunStatusRequest,
-    WorkflowTriggerRunRequest,
+    self, ApiResponse, QueueWaitQuery, RunHealthQuery, RunStatusQuery, RunStatusRequest,
+    SchedulerRunClaimReleaseRequest, SchedulerRunClaimRenewRequest, SchedulerRunClaimRequest,
+    TaskResponseSchema, UpcomingQuery, WorkflowRunRequest, WorkflowRunStatusQuery,
+    WorkflowRunStatusRequest, WorkflowTriggerRunRequest,
 };
 use crate::repository;
-use crate::responses::{api_error, bad_request, not_found};
+use crate::responses::{bad_request, not_found, repository_error};
 
 #[derive(Debug, Default, Deserialize)]
 pub(crate) struct ChunkQuery {
@@ -73,7 +80,7 @@ pub(crate) async fn create_workflow_trigger_run<T: DatabaseImpl>(
                 })),
             )
         }
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -123,7 +130,7 @@ pub(crate) async fn create_workflow_run<T: DatabaseImpl>(
                 })),
             )
         }
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -138,22 +145,31 @@ fn request_provenance(
         actor_type: Some(TriggerActorType::User),
         actor_replica_id: None,
         actor_display_name: Some(request_actor_display_name()),
-        request_host: headers
-            .get("host")
-            .and_then(|value| value.to_str().ok())
-            .map(str::to_string),
-        request_ip: headers
-            .get("x-forwarded-for")
-            .and_then(|value| value.to_str().ok())
-            .and_then(|value| value.split(',').next())
-            .map(str::trim)
-            .filter(|value| !value.is_empty())
-            .map(str::to_string)
-            .or_else(|| Some(connect.ip().to_string())),
+        request_host: request_host(headers),
+        request_ip: Some(request_ip(headers, connect)),
         metadata,
     }
 }
 
+pub(crate) fn request_host(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get("host")
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
+/// the first `X-Forwarded-For` hop, else the connection's ip.
+pub(crate) fn request_ip(headers: &HeaderMap, connect: SocketAddr) -> String {
+    headers
+        .get("x-forwarded-for")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(',').next())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(|| connect.ip().to_string())
+}
+
 fn request_actor_display_name() -> String {
     "api".into()
 }
@@ -189,7 +205,7 @@ pub(crate) async fn claim_workflow_runs_for_scheduler<T: DatabaseImpl>(
     .await
     {
         Ok(runs) => (StatusCode::OK, Json(ApiResponse::WorkflowRunList(runs))),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -213,7 +229,7 @@ pub(crate) async fn claim_ready_nodes<T: DatabaseImpl>(
             StatusCode::OK,
             Json(ApiResponse::JsonValue(runinator_models::json!(nodes))),
         ),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -245,7 +261,7 @@ pub(crate) async fn process_ready_node<T: DatabaseImpl>(
     .await
     {
         Ok(response) => (StatusCode::OK, Json(ApiResponse::TaskResponse(response))),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -276,7 +292,7 @@ pub(crate) async fn renew_workflow_run_claim<T: DatabaseImpl>(
             )),
         ),
         Ok(false) => not_found(format!("Workflow run claim {workflow_run_id} not held")),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -301,7 +317,7 @@ pub(crate) async fn release_workflow_run_claim<T: DatabaseImpl>(
                 },
             )),
         ),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -557,7 +573,7 @@ pub(crate) async fn get_workflow_runs<T: DatabaseImpl>(
                 StatusCode::OK,
                 Json(ApiResponse::WorkflowRunList(filter_runs(runs, &visible))),
             ),
-            Err(err) => api_error(err.to_string()),
+            Err(err) => repository_error(err.as_ref()),
         };
     }
 
@@ -574,7 +590,7 @@ pub(crate) async fn get_workflow_runs<T: DatabaseImpl>(
         }
         return match repository::fetch_workflow_runs_for_workflow(db.as_ref(), workflow_id).await {
             Ok(runs) => (StatusCode::OK, Json(ApiResponse::WorkflowRunList(runs))),
-            Err(err) => api_error(err.to_string()),
+            Err(err) => repository_error(err.as_ref()),
         };
     }
 
@@ -584,7 +600,7 @@ pub(crate) async fn get_workflow_runs<T: DatabaseImpl>(
                 StatusCode::OK,
                 Json(ApiResponse::WorkflowRunList(filter_runs(runs, &visible))),
             ),
-            Err(err) => api_error(err.to_string()),
+            Err(err) => repository_error(err.as_ref()),
         };
     }
 
@@ -597,10 +613,235 @@ pub(crate) async fn get_workflow_runs<T: DatabaseImpl>(
             StatusCode::OK,
             Json(ApiResponse::WorkflowRunList(filter_runs(runs, &visible))),
         ),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/workflow_run_health",
+    tag = "Workflow Runs",
+    responses((status = 200, description = "run health summary", body = serde_json::Value)),
+)]
+pub(crate) async fn get_workflow_run_health<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+    Extension(ctx): Extension<runinator_models::auth::AuthContext>,
+    Query(query): Query<RunHealthQuery>,
+) -> (StatusCode, Json<ApiResponse>) {
+    let visible = crate::authz::visible_workflow_ids(db.as_ref(), &ctx).await;
+    let now = chrono::Utc::now();
+    // the preceding window comes along so the summary can tell new failures from recurring ones.
+    let since = now - query.period.length() * 2;
+    let runs = match repository::fetch_workflow_runs_since(db.as_ref(), since).await {
+        Ok(runs) => filter_runs(runs, &visible),
+        Err(err) => return repository_error(err.as_ref()),
+    };
+    let names = match repository::fetch_workflows(db.as_ref()).await {
+        Ok(workflows) => workflows
+            .into_iter()
+            .filter_map(|workflow| workflow.id.map(|id| (id, workflow.name)))
+            .collect::<HashMap<_, _>>(),
+        Err(err) => return repository_error(err.as_ref()),
+    };
+    let summary = RunHealthSummary::build(query.period, now, &runs, &names);
+    (
+        StatusCode::OK,
+        Json(ApiResponse::RunHealthReport(summary.into())),
+    )
+}
+
+#[utoipa::path(
+    get,
+    path = "/queue_wait",
+    tag = "Workflow Runs",
+    responses((status = 200, description = "queue wait report", body = serde_json::Value)),
+)]
+pub(crate) async fn get_queue_wait<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+    Extension(ctx): Extension<runinator_models::auth::AuthContext>,
+    Query(query): Query<QueueWaitQuery>,
+) -> (StatusCode, Json<ApiResponse>) {
+    let visible = crate::authz::visible_workflow_ids(db.as_ref(), &ctx).await;
+    let hours = query
+        .hours
+        .unwrap_or(DEFAULT_QUEUE_WAIT_WINDOW_HOURS)
+        .clamp(1, MAX_QUEUE_WAIT_WINDOW_HOURS);
+    let since = chrono::Utc::now() - chrono::Duration::hours(hours);
+    let samples = match repository::fetch_queue_wait_samples(db.as_ref(), since).await {
+        Ok(samples) => match &visible {
+            Some(ids) => samples
+                .into_iter()
+                .filter(|sample| ids.contains(&sample.workflow_id))
+                .collect(),
+            None => samples,
+        },
+        Err(err) => return repository_error(err.as_ref()),
+    };
+    let names = match repository::fetch_workflows(db.as_ref()).await {
+        Ok(workflows) => workflows
+            .into_iter()
+            .filter_map(|workflow| workflow.id.map(|id| (id, workflow.name)))
+            .collect::<HashMap<_, _>>(),
+        Err(err) => return repository_error(err.as_ref()),
+    };
+    let threshold_ms = runinator_engine::queue_wait::alert_threshold()
+        .map(|threshold| threshold.as_millis() as i64);
+    let report = QueueWaitReport::build(since, threshold_ms, &samples, &names);
+    (StatusCode::OK, Json(ApiResponse::QueueWaitReport(report)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/schedule/upcoming",
+    tag = "Workflow Runs",
+    responses((status = 200, description = "upcoming schedule timeline", body = serde_json::Value)),
+)]
+pub(crate) async fn get_upcoming_schedule<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+    Extension(ctx): Extension<runinator_models::auth::AuthContext>,
+    Query(query): Query<UpcomingQuery>,
+) -> (StatusCode, Json<ApiResponse>) {
+    let visible = crate::authz::visible_workflow_ids(db.as_ref(), &ctx).await;
+    let minutes = query
+        .minutes
+        .unwrap_or(DEFAULT_UPCOMING_MINUTES)
+        .clamp(1, MAX_UPCOMING_MINUTES);
+    let now = chrono::Utc::now();
+    let until = now + chrono::Duration::minutes(minutes);
+    let names = match repository::fetch_workflows(db.as_ref()).await {
+        Ok(workflows) => workflows
+            .into_iter()
+            .filter_map(|workflow| workflow.id.map(|id| (id, workflow.name)))
+            .collect::<HashMap<_, _>>(),
+        Err(err) => return repository_error(err.as_ref()),
+    };
+    let node_runs = match repository::fetch_active_node_runs(db.as_ref()).await {
+        Ok(node_runs) => node_runs,
+        Err(err) => return repository_error(err.as_ref()),
+    };
+    let queued_runs = match repository::fetch_workflow_runs_by_status(
+        db.as_ref(),
+        WorkflowStatus::Queued,
+    )
+    .await
+    {
+        Ok(runs) => runs,
+        Err(err) => return repository_error(err.as_ref()),
+    };
+    let firings = match repository::project_cron_firings(
+        db.as_ref(),
+        now,
+        until,
+        MAX_UPCOMING_FIRINGS_PER_TRIGGER,
+    )
+    .await
+    {
+        Ok(firings) => firings,
+        Err(err) => return repository_error(err.as_ref()),
+    };
+
+    let entry = |kind, at, workflow_id: Uuid| UpcomingEntry {
+        kind,
+        at,
+        workflow_id,
+        workflow_name: names.get(&workflow_id).cloned().unwrap_or_default(),
+        workflow_run_id: None,
+        node_id: None,
+        trigger_id: None,
+        overdue: false,
+    };
+    let mut entries = Vec::new();
+    for node_run in node_runs {
+        let (kind, at) = match (node_run.status, node_run.started_at) {
+            (WorkflowStatus::Running, Some(started_at)) => (UpcomingKind::Running, started_at),
+            (WorkflowStatus::Running, None) => (UpcomingKind::Running, node_run.created_at),
+            _ => (UpcomingKind::Queued, node_run.created_at),
+        };
+        entries.push(UpcomingEntry {
+            workflow_run_id: Some(node_run.workflow_run_id),
+            node_id: Some(node_run.node_id),
+            ..entry(kind, at, node_run.workflow_id)
+        });
+    }
+    // a run the scheduler has not picked up yet has no node runs; list the run itself.
+    for run in queued_runs {
+        entries.push(UpcomingEntry {
+            workflow_run_id: Some(run.id),
+            ..entry(UpcomingKind::Queued, run.created_at, run.workflow_id)
+        });
+    }
+    for (trigger, times) in firings {
+        for at in times {
+            entries.push(UpcomingEntry {
+                trigger_id: trigger.id,
+                overdue: at < now,
+                ..entry(UpcomingKind::Scheduled, at, trigger.workflow_id)
+            });
+        }
     }
+    if let Some(ids) = &visible {
+        entries.retain(|entry| ids.contains(&entry.workflow_id));
+    }
+    (
+        StatusCode::OK,
+        Json(ApiResponse::UpcomingSchedule(UpcomingSchedule::build(
+            now, until, entries,
+        ))),
+    )
 }
 
+#[utoipa::path(
+    get,
+    path = "/scheduler/preview",
+    tag = "Workflow Runs",
+    responses((status = 200, description = "scheduling cycle dry run", body = serde_json::Value)),
+)]
+pub(crate) async fn get_scheduler_preview<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+    Extension(ctx): Extension<runinator_models::auth::AuthContext>,
+    Query(query): Query<UpcomingQuery>,
+) -> (StatusCode, Json<ApiResponse>) {
+    let visible = crate::authz::visible_workflow_ids(db.as_ref(), &ctx).await;
+    let minutes = query
+        .minutes
+        .unwrap_or(DEFAULT_UPCOMING_MINUTES)
+        .clamp(1, MAX_UPCOMING_MINUTES);
+    let now = chrono::Utc::now();
+    let until = now + chrono::Duration::minutes(minutes);
+    let preview = match repository::preview_scheduling_cycle(
+        db.as_ref(),
+        now,
+        until,
+        MAX_UPCOMING_FIRINGS_PER_TRIGGER,
+    )
+    .await
+    {
+        Ok(preview) => preview,
+        Err(err) => return repository_error(err.as_ref()),
+    };
+    let preview = match &visible {
+        Some(ids) => SchedulerPreview::build(
+            preview.generated_at,
+            preview.until,
+            preview
+                .firings
+                .into_iter()
+                .filter(|firing| ids.contains(&firing.workflow_id))
+                .collect(),
+            preview
+                .invalid
+                .into_iter()
+                .filter(|trigger| ids.contains(&trigger.workflow_id))
+                .collect(),
+        ),
+        None => preview,
+    };
+    (StatusCode::OK, Json(ApiResponse::SchedulerPreview(preview)))
+}
+
+/// the longest window the queue wait report aggregates over: 30 days.
+const MAX_QUEUE_WAIT_WINDOW_HOURS: i64 = 24 * 30;
+
 /// default cap on the unfiltered recent-runs list, so a long-lived deployment's history doesn't grow
 /// the dashboard's poll payload without bound. clients can request more via `?limit=` up to the max.
 const DEFAULT_RECENT_RUN_LIMIT: i64 = 200;
@@ -621,7 +862,7 @@ pub(crate) async fn get_runs<T: DatabaseImpl>(
     };
     match repository::fetch_runs_by_status(db.as_ref(), status).await {
         Ok(runs) => (StatusCode::OK, Json(ApiResponse::RunList(runs))),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -641,6 +882,7 @@ pub(crate) async fn update_run<T: DatabaseImpl>(
         request.status,
         request.output_json,
         request.message,
+        request.worker_id,
     )
     .await
     {
@@ -648,7 +890,7 @@ pub(crate) async fn update_run<T: DatabaseImpl>(
             emit_task_run(&events, run_id, request.status, ctx.org_id);
             (StatusCode::OK, Json(ApiResponse::TaskResponse(resp)))
         }
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -670,7 +912,7 @@ pub(crate) async fn get_run_chunks<T: DatabaseImpl>(
     .await
     {
         Ok(chunks) => (StatusCode::OK, Json(ApiResponse::RunChunks(chunks))),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -695,7 +937,7 @@ pub(crate) async fn append_run_chunk<T: DatabaseImpl>(
                 Json(ApiResponse::RunChunks(vec![chunk])),
             )
         }
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -724,7 +966,7 @@ pub(crate) async fn update_workflow_run<T: DatabaseImpl>(
             emit_workflow_run(&events, workflow_run_id, org_id);
             (StatusCode::OK, Json(ApiResponse::TaskResponse(resp)))
         }
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -752,7 +994,7 @@ pub(crate) async fn get_workflow_run<T: DatabaseImpl>(
             })),
         ),
         Ok(None) => not_found(format!("Workflow run {workflow_run_id} not found")),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
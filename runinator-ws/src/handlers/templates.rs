@@ -0,0 +1,180 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use axum::{Extension, Json, extract::Path, http::StatusCode};
+use runinator_database::interfaces::DatabaseImpl;
+use runinator_models::{
+    auth::AuthContext,
+    templates::{InstantiateTemplateRequest, WorkflowTemplate},
+    web::TaskResponse,
+};
+
+use crate::authz;
+use crate::events::{EventSender, emit_workflows_changed};
+use crate::models::ApiResponse;
+use crate::repository;
+use crate::responses::{bad_request, not_found, repository_error};
+
+type Reply = (StatusCode, Json<ApiResponse>);
+
+/// the stored template when the caller's org can see it; cross-tenant templates are not-found.
+async fn visible_template<T: DatabaseImpl>(
+    db: &T,
+    ctx: &AuthContext,
+    template_id: Uuid,
+) -> Result<WorkflowTemplate, Reply> {
+    match repository::fetch_workflow_template(db, template_id).await {
+        Ok(Some(template)) if authz::org_visible(ctx, template.org_id) => Ok(template),
+        Ok(_) => Err(not_found(format!(
+            "Workflow template {template_id} not found"
+        ))),
+        Err(err) => Err(repository_error(err.as_ref())),
+    }
+}
+
+/// templates visible to the caller: their org's plus the platform-wide ones.
+pub(crate) async fn get_workflow_templates<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+    Extension(ctx): Extension<AuthContext>,
+) -> Reply {
+    match repository::fetch_workflow_templates(db.as_ref()).await {
+        Ok(templates) => {
+            let templates = templates
+                .into_iter()
+                .filter(|template| authz::org_visible(&ctx, template.org_id))
+                .collect();
+            (
+                StatusCode::OK,
+                Json(ApiResponse::WorkflowTemplateList(templates)),
+            )
+        }
+        Err(err) => repository_error(err.as_ref()),
+    }
+}
+
+pub(crate) async fn get_workflow_template<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+    Extension(ctx): Extension<AuthContext>,
+    Path(template_id): Path<Uuid>,
+) -> Reply {
+    match visible_template(db.as_ref(), &ctx, template_id).await {
+        Ok(template) => (
+            StatusCode::OK,
+            Json(ApiResponse::WorkflowTemplate(template)),
+        ),
+        Err(reply) => reply,
+    }
+}
+
+/// create a template owned by the caller's active org (`None` = platform-wide).
+pub(crate) async fn create_workflow_template<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+    Extension(ctx): Extension<AuthContext>,
+    Json(mut template): Json<WorkflowTemplate>,
+) -> Reply {
+    template.id = None;
+    template.org_id = ctx.org_id;
+    save_workflow_template(db.as_ref(), &ctx, template).await
+}
+
+/// replace a template. it keeps its owning org.
+pub(crate) async fn update_workflow_template<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+    Extension(ctx): Extension<AuthContext>,
+    Path(template_id): Path<Uuid>,
+    Json(mut template): Json<WorkflowTemplate>,
+) -> Reply {
+    let existing = match visible_template(db.as_ref(), &ctx, template_id).await {
+        Ok(existing) => existing,
+        Err(reply) => return reply,
+    };
+    template.id = Some(template_id);
+    template.org_id = existing.org_id;
+    save_workflow_template(db.as_ref(), &ctx, template).await
+}
+
+async fn save_workflow_template<T: DatabaseImpl>(
+    db: &T,
+    ctx: &AuthContext,
+    template: WorkflowTemplate,
+) -> Reply {
+    // templates are shared starting points, so writing one takes org-admin in its org, or
+    // platform admin for a platform-wide (`org_id: None`) template.
+    let authorized = match template.org_id {
+        Some(org_id) => authz::require_org_admin(ctx, org_id),
+        None => authz::require_admin(ctx),
+    };
+    if let Err(reply) = authorized {
+        return reply;
+    }
+    match repository::upsert_workflow_template(db, &template).await {
+        Ok(template) => (
+            StatusCode::OK,
+            Json(ApiResponse::WorkflowTemplate(template)),
+        ),
+        Err(err) => bad_request(err.to_string()),
+    }
+}
+
+pub(crate) async fn delete_workflow_template<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+    Extension(ctx): Extension<AuthContext>,
+    Path(template_id): Path<Uuid>,
+) -> Reply {
+    let existing = match visible_template(db.as_ref(), &ctx, template_id).await {
+        Ok(existing) => existing,
+        Err(reply) => return reply,
+    };
+    let authorized = match existing.org_id {
+        Some(org_id) => authz::require_org_admin(&ctx, org_id),
+        None => authz::require_admin(&ctx),
+    };
+    if let Err(reply) = authorized {
+        return reply;
+    }
+    match repository::delete_workflow_template(db.as_ref(), template_id).await {
+        Ok(_) => (
+            StatusCode::OK,
+            Json(ApiResponse::TaskResponse(TaskResponse {
+                success: true,
+                message: "Workflow template deleted".into(),
+            })),
+        ),
+        Err(err) => repository_error(err.as_ref()),
+    }
+}
+
+/// create a workflow (and its cron trigger, when the template has one) from a template. the new
+/// workflow belongs to the caller's active org and is owned by the caller, like any other create.
+pub(crate) async fn instantiate_workflow_template<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+    Extension(events): Extension<EventSender>,
+    Extension(ctx): Extension<AuthContext>,
+    Path(template_id): Path<Uuid>,
+    Json(request): Json<InstantiateTemplateRequest>,
+) -> Reply {
+    let template = match visible_template(db.as_ref(), &ctx, template_id).await {
+        Ok(template) => template,
+        Err(reply) => return reply,
+    };
+    match repository::instantiate_workflow_template(
+        db.as_ref(),
+        &template,
+        &request,
+        ctx.org_id,
+        ctx.principal_id,
+    )
+    .await
+    {
+        Ok(bundle) => {
+            for workflow in &bundle.workflows {
+                if let Some(id) = workflow.id {
+                    authz::grant_owner(db.as_ref(), &ctx, id).await;
+                }
+            }
+            emit_workflows_changed(&events, ctx.org_id);
+            (StatusCode::OK, Json(ApiResponse::WorkflowBundle(bundle)))
+        }
+        Err(err) => bad_request(err.to_string()),
+    }
+}
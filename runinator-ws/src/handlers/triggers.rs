@@ -1,18 +1,34 @@
 use std::sync::Arc;
 use uuid::Uuid;
 
-use axum::{Extension, Json, extract::Path, http::StatusCode};
+use axum::{
+    Extension, Json,
+    extract::{Path, Query},
+    http::StatusCode,
+};
 use runinator_database::interfaces::DatabaseImpl;
 use runinator_models::{
-    auth::{AuthContext, Permission},
-    workflows::WorkflowTrigger,
+    auth::{AuthContext, Permission, PrincipalKind},
+    backfills::BackfillRequest,
+    blackouts::{TRIGGER_BLACKOUTS_KEY, trigger_blackouts},
+    protection::PendingChangeKind,
+    schedule_shift::ScheduleShiftRequest,
+    value::Value,
+    workflows::{
+        CronPreviewRequest, MAX_CRON_PREVIEW_COUNT, SCHEDULE_PREVIEW_COUNT, WorkflowTrigger,
+        WorkflowTriggerKind, trigger_jitter_seconds, trigger_misfire_policy,
+        trigger_offset_seconds, trigger_timezone,
+    },
 };
 
 use crate::authz;
 use crate::events::{EventSender, emit_workflows_changed};
-use crate::models::{ApiResponse, SchedulerTriggerClaimRequest};
+use crate::handlers::protection::{hold_if_protected, hold_protected_shifts};
+use crate::models::{
+    ApiResponse, ReconcileSchedulesQuery, SchedulerTriggerClaimRequest, TriggerUpdateQuery,
+};
 use crate::repository;
-use crate::responses::{api_error, not_found};
+use crate::responses::{api_error, bad_request, not_found, repository_error};
 
 pub(crate) async fn upsert_workflow_trigger<T: DatabaseImpl>(
     Extension(db): Extension<Arc<T>>,
@@ -27,21 +43,45 @@ pub(crate) async fn upsert_workflow_trigger<T: DatabaseImpl>(
         return reply;
     }
     trigger.workflow_id = workflow_id;
+    if let Some(message) = invalid_schedule_options(&trigger) {
+        return bad_request(message);
+    }
+    let issued_token = hash_webhook_token(&mut trigger);
+    if let Some(reply) = hold_trigger_change(
+        db.as_ref(),
+        &ctx,
+        workflow_id,
+        PendingChangeKind::UpsertTrigger,
+        &trigger,
+        issued_token.is_some(),
+    )
+    .await
+    {
+        return reply;
+    }
     match repository::upsert_workflow_trigger(db.as_ref(), &trigger).await {
         Ok(trigger) => {
             let org_id = workflow_org(db.as_ref(), workflow_id, ctx.org_id).await;
             emit_workflows_changed(&events, org_id);
-            (StatusCode::OK, Json(ApiResponse::WorkflowTrigger(trigger)))
+            (
+                StatusCode::OK,
+                Json(ApiResponse::WorkflowTrigger(with_issued_token(
+                    trigger,
+                    issued_token,
+                ))),
+            )
         }
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
+/// `?preview=true` saves nothing and returns the before/after fire times of the trigger's cron.
 pub(crate) async fn update_workflow_trigger<T: DatabaseImpl>(
     Extension(db): Extension<Arc<T>>,
     Extension(events): Extension<EventSender>,
     Extension(ctx): Extension<AuthContext>,
     Path(trigger_id): Path<Uuid>,
+    Query(query): Query<TriggerUpdateQuery>,
     Json(mut trigger): Json<WorkflowTrigger>,
 ) -> (StatusCode, Json<ApiResponse>) {
     if let Err(reply) =
@@ -50,13 +90,51 @@ pub(crate) async fn update_workflow_trigger<T: DatabaseImpl>(
         return reply;
     }
     trigger.id = Some(trigger_id);
+    if let Some(message) = invalid_schedule_options(&trigger) {
+        return bad_request(message);
+    }
+    if query.preview {
+        return match repository::preview_workflow_trigger_update(db.as_ref(), &trigger).await {
+            Ok(preview) => (
+                StatusCode::OK,
+                Json(ApiResponse::WorkflowTriggerPreview(preview)),
+            ),
+            Err(err) => repository_error(err.as_ref()),
+        };
+    }
+    // the change is held on the workflow the trigger belongs to now, which the caller was
+    // authorized against.
+    let workflow_id = match repository::fetch_workflow_trigger(db.as_ref(), trigger_id).await {
+        Ok(Some(stored)) => stored.workflow_id,
+        Ok(None) => trigger.workflow_id,
+        Err(err) => return repository_error(err.as_ref()),
+    };
+    let issued_token = hash_webhook_token(&mut trigger);
+    if let Some(reply) = hold_trigger_change(
+        db.as_ref(),
+        &ctx,
+        workflow_id,
+        PendingChangeKind::UpsertTrigger,
+        &trigger,
+        issued_token.is_some(),
+    )
+    .await
+    {
+        return reply;
+    }
     match repository::upsert_workflow_trigger(db.as_ref(), &trigger).await {
         Ok(trigger) => {
             let org_id = workflow_org(db.as_ref(), trigger.workflow_id, ctx.org_id).await;
             emit_workflows_changed(&events, org_id);
-            (StatusCode::OK, Json(ApiResponse::WorkflowTrigger(trigger)))
+            (
+                StatusCode::OK,
+                Json(ApiResponse::WorkflowTrigger(with_issued_token(
+                    trigger,
+                    issued_token,
+                ))),
+            )
         }
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -70,10 +148,11 @@ pub(crate) async fn get_workflow_trigger<T: DatabaseImpl>(
     {
         return reply;
     }
-    match repository::fetch_workflow_trigger(db.as_ref(), trigger_id).await {
-        Ok(Some(trigger)) => (StatusCode::OK, Json(ApiResponse::WorkflowTrigger(trigger))),
+    match repository::fetch_workflow_trigger_view(db.as_ref(), trigger_id, chrono::Utc::now()).await
+    {
+        Ok(Some(view)) => (StatusCode::OK, Json(ApiResponse::WorkflowTriggerView(view))),
         Ok(None) => not_found(format!("Workflow trigger {trigger_id} not found")),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -87,12 +166,14 @@ pub(crate) async fn get_workflow_triggers<T: DatabaseImpl>(
     {
         return reply;
     }
-    match repository::fetch_workflow_triggers(db.as_ref(), workflow_id).await {
-        Ok(triggers) => (
+    match repository::fetch_workflow_trigger_views(db.as_ref(), workflow_id, chrono::Utc::now())
+        .await
+    {
+        Ok(views) => (
             StatusCode::OK,
-            Json(ApiResponse::WorkflowTriggerList(triggers)),
+            Json(ApiResponse::WorkflowTriggerViewList(views)),
         ),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -108,7 +189,7 @@ pub(crate) async fn get_due_workflow_triggers<T: DatabaseImpl>(
             StatusCode::OK,
             Json(ApiResponse::WorkflowTriggerList(triggers)),
         ),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -128,7 +209,193 @@ pub(crate) async fn claim_due_workflow_trigger_firings<T: DatabaseImpl>(
     .await
     {
         Ok(runs) => (StatusCode::OK, Json(ApiResponse::WorkflowRunList(runs))),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
+    }
+}
+
+/// admin repair for drifted schedules: recompute every enabled cron trigger's `next_execution`.
+/// `?dry_run=true` returns the proposed changes without writing them.
+pub(crate) async fn reconcile_trigger_schedules<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+    Extension(ctx): Extension<AuthContext>,
+    Query(query): Query<ReconcileSchedulesQuery>,
+) -> (StatusCode, Json<ApiResponse>) {
+    if let Err(reply) = authz::require_admin(&ctx) {
+        return reply;
+    }
+    match repository::reconcile_trigger_schedules(db.as_ref(), query.dry_run).await {
+        Ok(report) => {
+            if !report.dry_run {
+                let actor_kind = match ctx.kind {
+                    PrincipalKind::User => "user",
+                    PrincipalKind::Service => "service",
+                };
+                crate::audit::record_audit(
+                    db.as_ref(),
+                    ctx.principal_id,
+                    actor_kind,
+                    "schedules.reconciled",
+                    crate::audit::AuditOutcome::Success,
+                    None,
+                    None,
+                    Some(&format!(
+                        "checked={} changed={}",
+                        report.checked,
+                        report.changes.len()
+                    )),
+                )
+                .await;
+            }
+            (
+                StatusCode::OK,
+                Json(ApiResponse::ScheduleReconcileReport(report)),
+            )
+        }
+        Err(err) => repository_error(err.as_ref()),
+    }
+}
+
+/// admin bulk shift: move the selected cron triggers by an offset or re-spread them across a daily
+/// window, rewriting their crons and `next_execution` in one transaction. a protected workflow's
+/// triggers are held for a second approver instead. `dry_run` only reports.
+pub(crate) async fn shift_trigger_schedules<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+    Extension(events): Extension<EventSender>,
+    Extension(ctx): Extension<AuthContext>,
+    Json(request): Json<ScheduleShiftRequest>,
+) -> (StatusCode, Json<ApiResponse>) {
+    if let Err(reply) = authz::require_admin(&ctx) {
+        return reply;
+    }
+    if !request.has_selector() {
+        return bad_request("select triggers with workflow_ids, namespace, or trigger_ids");
+    }
+    if let Err(err) = request.mode.validate() {
+        return bad_request(err);
+    }
+    match repository::shift_trigger_schedules(db.as_ref(), &request).await {
+        Ok(mut report) => {
+            if !report.dry_run {
+                if let Err(reply) = hold_protected_shifts(db.as_ref(), &ctx, &mut report).await {
+                    return reply;
+                }
+                let actor_kind = match ctx.kind {
+                    PrincipalKind::User => "user",
+                    PrincipalKind::Service => "service",
+                };
+                crate::audit::record_audit(
+                    db.as_ref(),
+                    ctx.principal_id,
+                    actor_kind,
+                    "schedules.shifted",
+                    crate::audit::AuditOutcome::Success,
+                    None,
+                    None,
+                    Some(&format!(
+                        "selected={} shifted={} held={}",
+                        report.changes.len(),
+                        report.shifted,
+                        report
+                            .changes
+                            .iter()
+                            .filter(|change| change.pending_change_id.is_some())
+                            .count()
+                    )),
+                )
+                .await;
+                if report.shifted > 0 {
+                    emit_workflows_changed(&events, None);
+                }
+            }
+            (
+                StatusCode::OK,
+                Json(ApiResponse::ScheduleShiftReport(report)),
+            )
+        }
+        Err(err) => repository_error(err.as_ref()),
+    }
+}
+
+/// replay the cron trigger's slots from `from` through `to`. the backfill is recorded here and the
+/// trigger loop starts its runs, `concurrency` at a time.
+pub(crate) async fn create_workflow_backfill<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+    Extension(ctx): Extension<AuthContext>,
+    Path(trigger_id): Path<Uuid>,
+    Query(request): Query<BackfillRequest>,
+) -> (StatusCode, Json<ApiResponse>) {
+    if let Err(reply) =
+        authz::require_trigger_workflow(db.as_ref(), &ctx, trigger_id, Permission::Run).await
+    {
+        return reply;
+    }
+    let trigger = match repository::fetch_workflow_trigger(db.as_ref(), trigger_id).await {
+        Ok(Some(trigger)) => trigger,
+        Ok(None) => return not_found(format!("Workflow trigger {trigger_id} not found")),
+        Err(err) => return repository_error(err.as_ref()),
+    };
+    match repository::create_workflow_backfill(
+        db.as_ref(),
+        &trigger,
+        &request,
+        ctx.principal_id,
+        chrono::Utc::now(),
+    )
+    .await
+    {
+        Ok(backfill) => (
+            StatusCode::ACCEPTED,
+            Json(ApiResponse::WorkflowBackfill(backfill)),
+        ),
+        Err(err) => repository_error(err.as_ref()),
+    }
+}
+
+pub(crate) async fn get_workflow_backfills<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+    Extension(ctx): Extension<AuthContext>,
+    Path(trigger_id): Path<Uuid>,
+) -> (StatusCode, Json<ApiResponse>) {
+    if let Err(reply) =
+        authz::require_trigger_workflow(db.as_ref(), &ctx, trigger_id, Permission::View).await
+    {
+        return reply;
+    }
+    match repository::fetch_workflow_backfills(db.as_ref(), trigger_id).await {
+        Ok(backfills) => (
+            StatusCode::OK,
+            Json(ApiResponse::WorkflowBackfillList(backfills)),
+        ),
+        Err(err) => repository_error(err.as_ref()),
+    }
+}
+
+/// stop a running backfill from starting more slots; runs it already started carry on.
+pub(crate) async fn cancel_workflow_backfill<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+    Extension(ctx): Extension<AuthContext>,
+    Path(backfill_id): Path<Uuid>,
+) -> (StatusCode, Json<ApiResponse>) {
+    let backfill = match repository::fetch_workflow_backfill(db.as_ref(), backfill_id).await {
+        Ok(Some(backfill)) => backfill,
+        Ok(None) => return not_found(format!("Backfill {backfill_id} not found")),
+        Err(err) => return repository_error(err.as_ref()),
+    };
+    if let Err(reply) =
+        authz::require_workflow(db.as_ref(), &ctx, backfill.workflow_id, Permission::Run).await
+    {
+        return reply;
+    }
+    if let Err(err) = repository::cancel_workflow_backfill(db.as_ref(), backfill_id).await {
+        return repository_error(err.as_ref());
+    }
+    match repository::fetch_workflow_backfill(db.as_ref(), backfill_id).await {
+        Ok(Some(backfill)) => (
+            StatusCode::OK,
+            Json(ApiResponse::WorkflowBackfill(backfill)),
+        ),
+        Ok(None) => not_found(format!("Backfill {backfill_id} not found")),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -143,19 +410,131 @@ pub(crate) async fn delete_workflow_trigger<T: DatabaseImpl>(
     {
         return reply;
     }
-    let org_id = match repository::fetch_workflow_trigger(db.as_ref(), trigger_id).await {
-        Ok(Some(trigger)) => workflow_org(db.as_ref(), trigger.workflow_id, ctx.org_id).await,
-        _ => ctx.org_id,
+    let stored = match repository::fetch_workflow_trigger(db.as_ref(), trigger_id).await {
+        Ok(stored) => stored,
+        Err(err) => return repository_error(err.as_ref()),
+    };
+    if let Some(trigger) = &stored
+        && let Some(reply) = hold_trigger_change(
+            db.as_ref(),
+            &ctx,
+            trigger.workflow_id,
+            PendingChangeKind::DeleteTrigger,
+            trigger,
+            false,
+        )
+        .await
+    {
+        return reply;
+    }
+    let org_id = match &stored {
+        Some(trigger) => workflow_org(db.as_ref(), trigger.workflow_id, ctx.org_id).await,
+        None => ctx.org_id,
     };
     match repository::delete_workflow_trigger(db.as_ref(), trigger_id).await {
         Ok(resp) => {
             emit_workflows_changed(&events, org_id);
             (StatusCode::OK, Json(ApiResponse::TaskResponse(resp)))
         }
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
+/// store only the hash of a `webhook` trigger's token. a `token` the caller chose is hashed in
+/// place; with neither `token` nor `token_hash` a fresh token is minted and returned so the response
+/// can show it once.
+fn hash_webhook_token(trigger: &mut WorkflowTrigger) -> Option<String> {
+    if trigger.kind != WorkflowTriggerKind::Webhook {
+        return None;
+    }
+    if !trigger.configuration.is_object() {
+        trigger.configuration = Value::Object(Default::default());
+    }
+    let configuration = trigger.configuration.as_object_mut()?;
+    if let Some(token) = configuration.remove("token")
+        && let Some(token) = token.as_str().filter(|token| !token.is_empty())
+    {
+        configuration.insert("token_hash".into(), crate::auth::hash_secret(token).into());
+        return None;
+    }
+    if configuration
+        .get("token_hash")
+        .is_some_and(Value::is_string)
+    {
+        return None;
+    }
+    let (token, hash) = runinator_auth::new_webhook_token();
+    configuration.insert("token_hash".into(), hash.into());
+    Some(token)
+}
+
+// a zone chrono-tz does not know, a misfire policy the trigger loop does not, or an out-of-range
+// jitter or offset is rejected on save rather than parking the trigger (or silently running it with
+// the default) on its first tick.
+fn invalid_schedule_options(trigger: &WorkflowTrigger) -> Option<String> {
+    if let Err(message) = trigger_misfire_policy(&trigger.configuration) {
+        return Some(message);
+    }
+    if let Err(message) = trigger_jitter_seconds(&trigger.configuration) {
+        return Some(message);
+    }
+    if let Err(message) = trigger_offset_seconds(&trigger.configuration) {
+        return Some(message);
+    }
+    let blackouts = match trigger_blackouts(&trigger.configuration) {
+        Ok(blackouts) => blackouts,
+        Err(message) => return Some(message),
+    };
+    for timezone in blackouts
+        .iter()
+        .filter_map(|window| window.timezone.as_deref())
+    {
+        if let Err(err) = runinator_database::schedule::parse_timezone(timezone) {
+            return Some(format!("{TRIGGER_BLACKOUTS_KEY}: {err}"));
+        }
+    }
+    let timezone = trigger_timezone(&trigger.configuration)?;
+    runinator_database::schedule::parse_timezone(timezone)
+        .err()
+        .map(|err| err.to_string())
+}
+
+// hold a trigger change when `workflow_id` is protected. a token minted for the request would only
+// be shown in this reply, and never once the change is approved, so a held webhook trigger must
+// bring its own `token`.
+async fn hold_trigger_change<T: DatabaseImpl>(
+    db: &T,
+    ctx: &AuthContext,
+    workflow_id: Uuid,
+    kind: PendingChangeKind,
+    trigger: &WorkflowTrigger,
+    minted_token: bool,
+) -> Option<(StatusCode, Json<ApiResponse>)> {
+    if minted_token {
+        match repository::fetch_workflow_protection(db, workflow_id).await {
+            Ok(Some(_)) => {
+                return Some(bad_request(
+                    "a webhook trigger on a protected workflow needs its own `token`",
+                ));
+            }
+            Ok(None) => return None,
+            Err(err) => return Some(repository_error(err.as_ref())),
+        }
+    }
+    let payload = match Value::encode(trigger) {
+        Ok(payload) => payload,
+        Err(err) => return Some(api_error(err.to_string())),
+    };
+    hold_if_protected(db, ctx, workflow_id, kind, payload).await
+}
+
+fn with_issued_token(mut trigger: WorkflowTrigger, token: Option<String>) -> WorkflowTrigger {
+    if let (Some(token), Some(configuration)) = (token, trigger.configuration.as_object_mut()) {
+        configuration.insert("token".into(), token.into());
+    }
+    trigger
+}
+
 async fn workflow_org<T: DatabaseImpl>(
     db: &T,
     workflow_id: Uuid,
@@ -166,3 +545,33 @@ async fn workflow_org<T: DatabaseImpl>(
         _ => fallback,
     }
 }
+
+/// validate a cron expression and list its next fire times without touching any trigger, so an
+/// editor or importer can flag a bad schedule before it is saved. an invalid expression is a 200
+/// with `valid: false`; only a malformed blackout window is rejected.
+pub(crate) async fn preview_cron(
+    Json(request): Json<CronPreviewRequest>,
+) -> (StatusCode, Json<ApiResponse>) {
+    let blackout = match (request.blackout_start, request.blackout_end) {
+        (Some(start), Some(end)) if start <= end => Some((start, end)),
+        (Some(_), Some(_)) => return bad_request("blackout_start must not be after blackout_end"),
+        (None, None) => None,
+        _ => return bad_request("blackout_start and blackout_end must be given together"),
+    };
+    let count = request
+        .count
+        .unwrap_or(SCHEDULE_PREVIEW_COUNT)
+        .clamp(1, MAX_CRON_PREVIEW_COUNT);
+    let preview = runinator_database::schedule::preview_cron(
+        request.cron.trim(),
+        request
+            .timezone
+            .as_deref()
+            .map(str::trim)
+            .filter(|timezone| !timezone.is_empty()),
+        request.from.unwrap_or_else(chrono::Utc::now),
+        count,
+        blackout,
+    );
+    (StatusCode::OK, Json(ApiResponse::CronPreview(preview)))
+}
@@ -14,10 +14,11 @@ use serde::{Deserialize, Serialize};
 
 use crate::authz;
 use crate::events::{EventSender, emit_workflows_changed};
+use crate::handlers::protection::hold_protected_imports;
 use crate::handlers::providers::provider_metadata_from_items;
 use crate::models::ApiResponse;
 use crate::repository;
-use crate::responses::{api_error, bad_request};
+use crate::responses::{api_error, bad_request, repository_error};
 
 pub(crate) async fn complete_wdl(
     Json(request): Json<runinator_wdl::WdlCompletionRequest>,
@@ -145,9 +146,16 @@ pub(crate) async fn import_wdl<T: DatabaseImpl>(
     {
         workflow.definition.extra.insert("ui".to_string(), ui);
     }
-    let bundle = WorkflowBundle {
+    let mut bundle = WorkflowBundle {
         workflows: vec![workflow],
         triggers: request.triggers,
+        baselines: Vec::new(),
+    };
+    // saving over a protected workflow is held for approval like any other edit to it.
+    let status = match hold_protected_imports(db.as_ref(), &ctx, &mut bundle, false).await {
+        Ok(held) if held.is_empty() => StatusCode::OK,
+        Ok(_) => StatusCode::ACCEPTED,
+        Err(reply) => return reply,
     };
     match repository::import_workflow_bundle(db.as_ref(), bundle).await {
         Ok(saved) => {
@@ -164,9 +172,9 @@ pub(crate) async fn import_wdl<T: DatabaseImpl>(
                 .and_then(|workflow| workflow.org_id)
                 .or(ctx.org_id);
             emit_workflows_changed(&events, org_id);
-            (StatusCode::OK, Json(ApiResponse::WorkflowBundle(saved)))
+            (status, Json(ApiResponse::WorkflowBundle(saved)))
         }
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
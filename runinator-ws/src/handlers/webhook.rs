@@ -1,15 +1,28 @@
-use std::sync::Arc;
+use std::{net::SocketAddr, sync::Arc};
 
-use axum::{Extension, Json, http::StatusCode};
+use axum::{
+    Extension, Json,
+    body::Bytes,
+    extract::{ConnectInfo, Path},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
 use runinator_database::interfaces::DatabaseImpl;
 use runinator_models::auth::AuthContext;
+use runinator_models::value::Value;
+use runinator_models::workflows::WebhookTriggerConfig;
 
+use crate::audit::{AuditOutcome, record_audit};
 use crate::events::{
     AppEvent, AppEventKind, EventSender, emit, emit_workflow_run, nudge_wake_publisher,
 };
-use crate::models::{ApiResponse, WebhookSignalRequest, WebhookWakeRequest};
+use crate::handlers::runs::{request_host, request_ip};
+use crate::models::{
+    ApiError, ApiErrorCode, ApiResponse, WebhookSignalRequest, WebhookWakeRequest,
+    WorkflowRunResponse,
+};
 use crate::repository;
-use crate::responses::{api_error, not_found, task_response_success};
+use crate::responses::{bad_request, not_found, repository_error, task_response_success};
 use crate::websocket::merge_json;
 
 pub(crate) async fn webhook_wake<T: DatabaseImpl>(
@@ -30,7 +43,7 @@ pub(crate) async fn webhook_wake<T: DatabaseImpl>(
                     request.workflow_run_id
                 ));
             }
-            Err(err) => return api_error(err.to_string()),
+            Err(err) => return repository_error(err.as_ref()),
         };
     let (run, node_runs) = workflow_run;
     let node_id = request
@@ -66,7 +79,7 @@ pub(crate) async fn webhook_wake<T: DatabaseImpl>(
     )
     .await
     {
-        return api_error(err.to_string());
+        return repository_error(err.as_ref());
     }
     if let Err(err) = repository::update_workflow_run_status(
         db.as_ref(),
@@ -78,7 +91,7 @@ pub(crate) async fn webhook_wake<T: DatabaseImpl>(
     )
     .await
     {
-        return api_error(err.to_string());
+        return repository_error(err.as_ref());
     }
     let org_id = repository::org_id_for_workflow_run(db.as_ref(), request.workflow_run_id).await;
     emit_workflow_run(&events, request.workflow_run_id, org_id);
@@ -113,6 +126,115 @@ pub(crate) async fn webhook_signal<T: DatabaseImpl>(
             nudge_wake_publisher(&events);
             (StatusCode::OK, Json(ApiResponse::TaskResponse(response)))
         }
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
+    }
+}
+
+/// start a run of the `webhook` trigger the path token belongs to. the route is public: the token is
+/// the credential, so upstream systems need no api key. a json object body is merged over the
+/// trigger's configured parameters. each token is throttled on its own and every start or throttled
+/// request is audited.
+pub(crate) async fn trigger_webhook<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+    Extension(events): Extension<EventSender>,
+    headers: HeaderMap,
+    ConnectInfo(connect): ConnectInfo<SocketAddr>,
+    Path(token): Path<String>,
+    body: Bytes,
+) -> Response {
+    let token_hash = crate::auth::hash_secret(&token);
+    let trigger = match repository::fetch_webhook_trigger(db.as_ref(), &token_hash).await {
+        Ok(Some(trigger)) => trigger,
+        Ok(None) => return not_found("Unknown webhook trigger").into_response(),
+        Err(err) => return repository_error(err.as_ref()).into_response(),
+    };
+    let Some(trigger_id) = trigger.id else {
+        return not_found("Unknown webhook trigger").into_response();
+    };
+    let ip = request_ip(&headers, connect);
+
+    if let Err(retry_after) = crate::rate_limit::check_webhook_trigger(trigger_id) {
+        record_audit(
+            db.as_ref(),
+            None,
+            "webhook",
+            "trigger.webhook",
+            AuditOutcome::Denied,
+            Some("workflow_trigger"),
+            Some(trigger_id),
+            Some(&format!("rate limited ip={ip}")),
+        )
+        .await;
+        let secs = retry_after.ceil().max(1.0) as u64;
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            [("Retry-After", secs.to_string())],
+            Json(ApiResponse::ApiError(ApiError::new(
+                ApiErrorCode::RateLimited,
+                format!("webhook trigger rate limit exceeded; retry in {secs}s"),
+            ))),
+        )
+            .into_response();
+    }
+
+    let parameters = match webhook_parameters(&trigger.configuration, &body) {
+        Ok(parameters) => parameters,
+        Err(message) => return bad_request(message).into_response(),
+    };
+    let run = match repository::create_workflow_run_for_webhook(
+        db.as_ref(),
+        &trigger,
+        parameters,
+        request_host(&headers),
+        Some(ip.clone()),
+    )
+    .await
+    {
+        Ok(run) => run,
+        Err(err) => return repository_error(err.as_ref()).into_response(),
+    };
+    record_audit(
+        db.as_ref(),
+        None,
+        "webhook",
+        "trigger.webhook",
+        AuditOutcome::Success,
+        Some("workflow_trigger"),
+        Some(trigger_id),
+        Some(&format!("run={} ip={ip}", run.id)),
+    )
+    .await;
+    let org_id = repository::org_id_for_workflow_run(db.as_ref(), run.id).await;
+    emit_workflow_run(&events, run.id, org_id);
+    nudge_wake_publisher(&events);
+    (
+        StatusCode::ACCEPTED,
+        Json(ApiResponse::WorkflowRun(WorkflowRunResponse {
+            run,
+            nodes: Vec::new(),
+        })),
+    )
+        .into_response()
+}
+
+// the trigger's configured parameters with a posted json object merged over them. an empty body
+// posts nothing.
+fn webhook_parameters(configuration: &Value, body: &[u8]) -> Result<Value, String> {
+    let config = WebhookTriggerConfig::from_configuration(configuration)?;
+    let mut parameters = match config.parameters {
+        Value::Object(object) => object,
+        _ => Default::default(),
+    };
+    if body.iter().all(u8::is_ascii_whitespace) {
+        return Ok(Value::Object(parameters));
+    }
+    let posted: serde_json::Value =
+        serde_json::from_slice(body).map_err(|err| format!("webhook body must be json: {err}"))?;
+    let Value::Object(posted) = Value::from(posted) else {
+        return Err("webhook body must be a json object".to_string());
+    };
+    for (key, value) in posted {
+        parameters.insert(key, value);
     }
+    Ok(Value::Object(parameters))
 }
@@ -1,36 +1,54 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 use uuid::Uuid;
 
 use axum::{
     Extension, Json,
     extract::{Path, Query},
-    http::{HeaderMap, StatusCode},
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    response::{IntoResponse, Response},
 };
 use runinator_database::interfaces::DatabaseImpl;
+use runinator_engine::workflow_source::{WorkflowFileFormat, render_workflow_file};
 use runinator_models::{
     api_routes::{WORKFLOW_JSON_IMPORT_RISK_ACK, WORKFLOW_JSON_IMPORT_RISK_HEADER},
     auth::{AuthContext, Permission},
     capabilities::Capability,
     errors::error_code_or_unknown,
+    overview::{DEFAULT_OVERVIEW_RUNS, ExecutorRef, MAX_OVERVIEW_RUNS, WorkflowOverview},
+    protection::{PendingChangeKind, WorkflowOwnerChange},
+    retention::{UpdateWorkflowRetentionRequest, WorkflowRetention},
     value::Value,
+    web::TaskResponse,
     workflows::{
-        WorkflowBundle, WorkflowDefinition, WorkflowDuplicateRequest, WorkflowSimulateRequest,
+        DEFAULT_WORKFLOW_SEARCH_LIMIT, MAX_EXPORT_BASELINE_RUNS, MAX_WORKFLOW_SEARCH_LIMIT,
+        MAX_WORKFLOW_SEARCH_TERMS, WorkflowBulkItem, WorkflowBulkResponse, WorkflowBundle,
+        WorkflowCloneRequest, WorkflowDefinition, WorkflowDefinitionHash, WorkflowDuplicateRequest,
+        WorkflowImportOptions, WorkflowSearchPage, WorkflowSimulateRequest, workflow_search_terms,
     },
 };
 use serde::Deserialize;
+use sha2::{Digest, Sha256};
 
 use crate::authz;
 use crate::events::{EventSender, emit_workflows_changed};
-use crate::models::ApiResponse;
+use crate::handlers::protection::{hold_if_protected, hold_protected_imports};
+use crate::models::{ApiResponse, WorkflowExportQuery, WorkflowOverviewQuery, WorkflowSearchQuery};
 use crate::repository;
-use crate::responses::{api_error, bad_request, not_found, validation_error};
+use crate::responses::{
+    api_error, bad_request, conflict, not_found, repository_error, validation_error,
+};
 
 pub(crate) async fn upsert_workflow<T: DatabaseImpl>(
     Extension(db): Extension<Arc<T>>,
     Extension(events): Extension<EventSender>,
     Extension(ctx): Extension<AuthContext>,
+    headers: HeaderMap,
     Json(mut workflow): Json<WorkflowDefinition>,
 ) -> (StatusCode, Json<ApiResponse>) {
+    let expected_version = match if_match_version(&headers) {
+        Ok(version) => version,
+        Err(tag) => return bad_request(format!("If-Match {tag} is not a workflow version ETag")),
+    };
     // updating an existing workflow requires edit; creating one stamps the creator as owner.
     let is_update = workflow.id.is_some();
     if let Some(id) = workflow.id {
@@ -41,13 +59,57 @@ pub(crate) async fn upsert_workflow<T: DatabaseImpl>(
         workflow.org_id = match repository::fetch_workflow(db.as_ref(), id).await {
             Ok(Some(existing)) => existing.org_id,
             Ok(None) => workflow.org_id,
-            Err(err) => return api_error(err.to_string()),
+            Err(err) => return repository_error(err.as_ref()),
         };
     } else {
         // a new workflow is owned by the creator's active org (None = platform-global).
         workflow.org_id = ctx.org_id;
     }
-    match repository::upsert_workflow(db.as_ref(), &workflow).await {
+    // an id-less save of an existing name updates that workflow, so it is held like any other
+    // edit when the matched workflow is protected.
+    let target = match repository::fetch_upsert_target(db.as_ref(), &workflow).await {
+        Ok(target) => target,
+        Err(err) => return repository_error(err.as_ref()),
+    };
+    if let Some(id) = target {
+        let mut held = workflow.clone();
+        held.id = Some(id);
+        let payload = match Value::encode(&held) {
+            Ok(payload) => payload,
+            Err(err) => return api_error(err.to_string()),
+        };
+        if let Some(reply) =
+            hold_if_protected(db.as_ref(), &ctx, id, PendingChangeKind::Update, payload).await
+        {
+            return reply;
+        }
+    }
+    let saved = match expected_version {
+        // a conditional save pins the matched row, so an id-less body still targets it.
+        Some(version) => {
+            let mut checked = workflow.clone();
+            checked.id = target;
+            match repository::upsert_workflow_if_current(
+                db.as_ref(),
+                &checked,
+                ctx.principal_id,
+                version,
+            )
+            .await
+            {
+                Ok(Some(saved)) => Ok(saved),
+                Ok(None) => {
+                    return conflict(format!(
+                        "Workflow {} is no longer at version {version}; reload it and reapply the edit",
+                        target.map_or_else(|| workflow.name.clone(), |id| id.to_string()),
+                    ));
+                }
+                Err(err) => Err(err),
+            }
+        }
+        None => repository::upsert_workflow_as(db.as_ref(), &workflow, ctx.principal_id).await,
+    };
+    match saved {
         Ok(workflow) => {
             if !is_update {
                 if let Some(id) = workflow.id {
@@ -57,10 +119,145 @@ pub(crate) async fn upsert_workflow<T: DatabaseImpl>(
             emit_workflows_changed(&events, workflow.org_id);
             (StatusCode::OK, Json(ApiResponse::Workflow(workflow)))
         }
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
+/// create or update several workflows at once. every entry is authorized and validated first; if
+/// any is rejected nothing is written and the response lists each entry's outcome. otherwise all of
+/// them are saved in one transaction.
+#[utoipa::path(
+    post,
+    path = "/workflows/bulk",
+    tag = "Workflows",
+    request_body(description = "An array of workflow definitions.", content(("application/json"))),
+    responses(
+        (status = 200, description = "every workflow saved; per-item results in request order", body = serde_json::Value),
+        (status = 400, description = "one or more workflows rejected; nothing was saved", body = serde_json::Value),
+        (status = 401, description = "request is missing or has an invalid credential", body = crate::models::ApiError),
+    ),
+)]
+pub(crate) async fn upsert_workflows_bulk<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+    Extension(events): Extension<EventSender>,
+    Extension(ctx): Extension<AuthContext>,
+    Json(workflows): Json<Vec<WorkflowDefinition>>,
+) -> (StatusCode, Json<ApiResponse>) {
+    if workflows.is_empty() {
+        return bad_request("bulk upsert needs at least one workflow");
+    }
+
+    // same per-workflow rules as a single upsert: edit on existing ids, stored org preserved on
+    // update, the caller's org stamped on create.
+    let mut prepared = Vec::with_capacity(workflows.len());
+    let mut results = Vec::with_capacity(workflows.len());
+    for (index, mut workflow) in workflows.into_iter().enumerate() {
+        let name = workflow.name.clone();
+        let outcome = async {
+            if let Some(id) = workflow.id {
+                if authz::require_workflow(db.as_ref(), &ctx, id, Permission::Edit)
+                    .await
+                    .is_err()
+                {
+                    return Err("insufficient permission for this resource".to_string());
+                }
+                workflow.org_id = match repository::fetch_workflow(db.as_ref(), id).await {
+                    Ok(Some(existing)) => existing.org_id,
+                    Ok(None) => workflow.org_id,
+                    Err(err) => return Err(err.to_string()),
+                };
+            } else {
+                workflow.org_id = ctx.org_id;
+            }
+            // a batch is all-or-nothing, so it cannot hold one entry for review and save the rest.
+            let target = repository::fetch_upsert_target(db.as_ref(), &workflow)
+                .await
+                .map_err(|err| err.to_string())?;
+            if let Some(id) = target {
+                let protection = repository::fetch_workflow_protection(db.as_ref(), id)
+                    .await
+                    .map_err(|err| err.to_string())?;
+                if protection.is_some() {
+                    return Err(
+                        "workflow is protected; save it on its own so the change can be reviewed"
+                            .to_string(),
+                    );
+                }
+            }
+            repository::validate_workflow_definition_with_catalog(db.as_ref(), &workflow)
+                .await
+                .map_err(|err| err.to_string())
+        }
+        .await;
+        match outcome {
+            Ok(workflow) => prepared.push(workflow),
+            Err(error) => results.push(WorkflowBulkItem {
+                index,
+                name,
+                workflow: None,
+                error: Some(error),
+            }),
+        }
+    }
+    if !results.is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::WorkflowBulk(WorkflowBulkResponse {
+                saved: false,
+                results,
+            })),
+        );
+    }
+
+    let created = prepared
+        .iter()
+        .map(|workflow| workflow.id.is_none())
+        .collect::<Vec<_>>();
+    let saved = match db.upsert_workflows(&prepared).await {
+        Ok(saved) => saved,
+        Err(err) => return repository_error(err.as_ref()),
+    };
+    let mut orgs = Vec::new();
+    for (workflow, created) in saved.iter().zip(created) {
+        if created && let Some(id) = workflow.id {
+            authz::grant_owner(db.as_ref(), &ctx, id).await;
+        }
+        // history is best-effort here: the batch is already committed.
+        if let Err(err) = db
+            .record_workflow_revision(workflow, ctx.principal_id)
+            .await
+        {
+            log::warn!(
+                "failed to record revision of workflow {:?}: {err}",
+                workflow.id
+            );
+        }
+        if !orgs.contains(&workflow.org_id) {
+            orgs.push(workflow.org_id);
+        }
+    }
+    for org_id in orgs {
+        emit_workflows_changed(&events, org_id);
+    }
+    let results = saved
+        .into_iter()
+        .enumerate()
+        .map(|(index, workflow)| WorkflowBulkItem {
+            index,
+            name: workflow.name.clone(),
+            workflow: Some(workflow),
+            error: None,
+        })
+        .collect();
+    (
+        StatusCode::OK,
+        Json(ApiResponse::WorkflowBulk(WorkflowBulkResponse {
+            saved: true,
+            results,
+        })),
+    )
+}
+
 /// reassign a workflow's owning organization. requires `Own` on the workflow (owner or platform
 /// admin); moving it into an org additionally requires org-admin on the target org.
 pub(crate) async fn set_workflow_owner<T: DatabaseImpl>(
@@ -80,16 +277,272 @@ pub(crate) async fn set_workflow_owner<T: DatabaseImpl>(
             return reply;
         }
     }
+    let owner = WorkflowOwnerChange {
+        org_id: request.org_id,
+    };
+    let payload = match Value::encode(&owner) {
+        Ok(payload) => payload,
+        Err(err) => return api_error(err.to_string()),
+    };
+    if let Some(reply) = hold_if_protected(
+        db.as_ref(),
+        &ctx,
+        workflow_id,
+        PendingChangeKind::SetOwner,
+        payload,
+    )
+    .await
+    {
+        return reply;
+    }
     match repository::set_workflow_org(db.as_ref(), workflow_id, request.org_id).await {
         Ok(()) => {
             emit_workflows_changed(&events, request.org_id);
             match repository::fetch_workflow(db.as_ref(), workflow_id).await {
                 Ok(Some(workflow)) => (StatusCode::OK, Json(ApiResponse::Workflow(workflow))),
                 Ok(None) => not_found(format!("Workflow {workflow_id} not found")),
-                Err(err) => api_error(err.to_string()),
+                Err(err) => repository_error(err.as_ref()),
             }
         }
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
+    }
+}
+
+/// enable a workflow without resending its definition, so a concurrent edit to other fields is
+/// never overwritten by a stale copy. requires `Edit`.
+pub(crate) async fn enable_workflow<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+    Extension(events): Extension<EventSender>,
+    Extension(ctx): Extension<AuthContext>,
+    Path(workflow_id): Path<Uuid>,
+) -> (StatusCode, Json<ApiResponse>) {
+    set_workflow_enabled(db, events, ctx, workflow_id, true).await
+}
+
+/// disable a workflow; the counterpart of [`enable_workflow`].
+pub(crate) async fn disable_workflow<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+    Extension(events): Extension<EventSender>,
+    Extension(ctx): Extension<AuthContext>,
+    Path(workflow_id): Path<Uuid>,
+) -> (StatusCode, Json<ApiResponse>) {
+    set_workflow_enabled(db, events, ctx, workflow_id, false).await
+}
+
+async fn set_workflow_enabled<T: DatabaseImpl>(
+    db: Arc<T>,
+    events: EventSender,
+    ctx: AuthContext,
+    workflow_id: Uuid,
+    enabled: bool,
+) -> (StatusCode, Json<ApiResponse>) {
+    if let Err(reply) =
+        authz::require_workflow(db.as_ref(), &ctx, workflow_id, Permission::Edit).await
+    {
+        return reply;
+    }
+    let kind = if enabled {
+        PendingChangeKind::Enable
+    } else {
+        PendingChangeKind::Disable
+    };
+    if let Some(reply) = hold_if_protected(db.as_ref(), &ctx, workflow_id, kind, Value::Null).await
+    {
+        return reply;
+    }
+    match repository::set_workflow_enabled(db.as_ref(), workflow_id, enabled).await {
+        Ok(true) => {}
+        Ok(false) => return not_found(format!("Workflow {workflow_id} not found")),
+        Err(err) => return repository_error(err.as_ref()),
+    }
+    match repository::fetch_workflow(db.as_ref(), workflow_id).await {
+        Ok(Some(workflow)) => {
+            if let Err(err) = db
+                .record_workflow_revision(&workflow, ctx.principal_id)
+                .await
+            {
+                log::warn!("failed to record revision of workflow {workflow_id}: {err}");
+            }
+            emit_workflows_changed(&events, workflow.org_id);
+            (StatusCode::OK, Json(ApiResponse::Workflow(workflow)))
+        }
+        Ok(None) => not_found(format!("Workflow {workflow_id} not found")),
+        Err(err) => repository_error(err.as_ref()),
+    }
+}
+
+/// a workflow's saved revisions, newest first. each carries the full workflow as it was saved, so
+/// any two can be diffed client-side.
+pub(crate) async fn get_workflow_revisions<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+    Extension(ctx): Extension<AuthContext>,
+    Path(workflow_id): Path<Uuid>,
+) -> (StatusCode, Json<ApiResponse>) {
+    if let Err(reply) =
+        authz::require_workflow(db.as_ref(), &ctx, workflow_id, Permission::View).await
+    {
+        return reply;
+    }
+    match repository::fetch_workflow_revisions(db.as_ref(), workflow_id).await {
+        Ok(revisions) => (
+            StatusCode::OK,
+            Json(ApiResponse::WorkflowRevisions(revisions)),
+        ),
+        Err(err) => repository_error(err.as_ref()),
+    }
+}
+
+/// save an earlier revision of a workflow again. the revert is itself recorded as a new revision,
+/// so history only ever grows. requires `Edit`; the workflow keeps its current org.
+pub(crate) async fn revert_workflow<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+    Extension(events): Extension<EventSender>,
+    Extension(ctx): Extension<AuthContext>,
+    Path((workflow_id, revision)): Path<(Uuid, i64)>,
+) -> (StatusCode, Json<ApiResponse>) {
+    if let Err(reply) =
+        authz::require_workflow(db.as_ref(), &ctx, workflow_id, Permission::Edit).await
+    {
+        return reply;
+    }
+    let current = match repository::fetch_workflow(db.as_ref(), workflow_id).await {
+        Ok(Some(current)) => current,
+        Ok(None) => return not_found(format!("Workflow {workflow_id} not found")),
+        Err(err) => return repository_error(err.as_ref()),
+    };
+    let mut workflow =
+        match repository::fetch_workflow_revision(db.as_ref(), workflow_id, revision).await {
+            Ok(Some(revision)) => revision.restore(),
+            Ok(None) => {
+                return not_found(format!("Workflow {workflow_id} has no revision {revision}"));
+            }
+            Err(err) => return repository_error(err.as_ref()),
+        };
+    workflow.org_id = current.org_id;
+    let payload = match Value::encode(&workflow) {
+        Ok(payload) => payload,
+        Err(err) => return api_error(err.to_string()),
+    };
+    if let Some(reply) = hold_if_protected(
+        db.as_ref(),
+        &ctx,
+        workflow_id,
+        PendingChangeKind::Update,
+        payload,
+    )
+    .await
+    {
+        return reply;
+    }
+    match repository::upsert_workflow_as(db.as_ref(), &workflow, ctx.principal_id).await {
+        Ok(workflow) => {
+            emit_workflows_changed(&events, workflow.org_id);
+            (StatusCode::OK, Json(ApiResponse::Workflow(workflow)))
+        }
+        Err(err) => repository_error(err.as_ref()),
+    }
+}
+
+/// a workflow's run-history retention override; `null` when its runs follow the archiver's global
+/// workflow-run retention.
+pub(crate) async fn get_workflow_retention<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+    Extension(ctx): Extension<AuthContext>,
+    Path(workflow_id): Path<Uuid>,
+) -> (StatusCode, Json<ApiResponse>) {
+    if let Err(reply) =
+        authz::require_workflow(db.as_ref(), &ctx, workflow_id, Permission::View).await
+    {
+        return reply;
+    }
+    match repository::fetch_workflow_retention(db.as_ref(), workflow_id).await {
+        Ok(retention) => (
+            StatusCode::OK,
+            Json(ApiResponse::WorkflowRetention(retention)),
+        ),
+        Err(err) => repository_error(err.as_ref()),
+    }
+}
+
+/// override how long the archiver keeps a workflow's finished runs.
+pub(crate) async fn set_workflow_retention<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+    Extension(ctx): Extension<AuthContext>,
+    Path(workflow_id): Path<Uuid>,
+    Json(request): Json<UpdateWorkflowRetentionRequest>,
+) -> (StatusCode, Json<ApiResponse>) {
+    if let Err(reply) =
+        authz::require_workflow(db.as_ref(), &ctx, workflow_id, Permission::Edit).await
+    {
+        return reply;
+    }
+    if let Err(message) = request.validate() {
+        return bad_request(message);
+    }
+    let retention = WorkflowRetention {
+        workflow_id,
+        retention_days: request.retention_days,
+        updated_at: None,
+    };
+    let payload = match Value::encode(&retention) {
+        Ok(payload) => payload,
+        Err(err) => return api_error(err.to_string()),
+    };
+    if let Some(reply) = hold_if_protected(
+        db.as_ref(),
+        &ctx,
+        workflow_id,
+        PendingChangeKind::SetRetention,
+        payload,
+    )
+    .await
+    {
+        return reply;
+    }
+    match repository::set_workflow_retention(db.as_ref(), retention).await {
+        Ok(retention) => (
+            StatusCode::OK,
+            Json(ApiResponse::WorkflowRetention(Some(retention))),
+        ),
+        Err(err) => repository_error(err.as_ref()),
+    }
+}
+
+/// drop a workflow's retention override so its runs follow the global retention again.
+pub(crate) async fn clear_workflow_retention<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+    Extension(ctx): Extension<AuthContext>,
+    Path(workflow_id): Path<Uuid>,
+) -> (StatusCode, Json<ApiResponse>) {
+    if let Err(reply) =
+        authz::require_workflow(db.as_ref(), &ctx, workflow_id, Permission::Edit).await
+    {
+        return reply;
+    }
+    if let Some(reply) = hold_if_protected(
+        db.as_ref(),
+        &ctx,
+        workflow_id,
+        PendingChangeKind::ClearRetention,
+        Value::Null,
+    )
+    .await
+    {
+        return reply;
+    }
+    match repository::clear_workflow_retention(db.as_ref(), workflow_id).await {
+        Ok(removed) => (
+            StatusCode::OK,
+            Json(ApiResponse::TaskResponse(TaskResponse {
+                success: true,
+                message: if removed {
+                    "Workflow retention override removed".into()
+                } else {
+                    "Workflow has no retention override".into()
+                },
+            })),
+        ),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -127,7 +580,7 @@ pub(crate) async fn simulate_workflow<T: DatabaseImpl>(
                 }
             }
             Ok(None) => return not_found(format!("Workflow run {run_id} not found")),
-            Err(err) => return api_error(err.to_string()),
+            Err(err) => return repository_error(err.as_ref()),
         }
     }
     match runinator_engine::simulate::simulate_run(
@@ -151,13 +604,75 @@ pub(crate) struct WorkflowQuery {
     pub(crate) name: Option<String>,
 }
 
-/// list workflow definitions visible to the caller.
+/// `GET /workflows` with conditional-request support. the list carries an `ETag` digest of what
+/// the caller can see plus the newest definition's `Last-Modified`; a poller that resends the tag in
+/// `If-None-Match` gets an empty `304` while that list is unchanged, skipping the download and
+/// decode of every definition.
 #[utoipa::path(
     get,
     path = "/workflows",
     tag = "Workflows",
-    responses((status = 200, description = "workflow definitions", body = serde_json::Value)),
+    responses(
+        (status = 200, description = "workflow definitions", body = serde_json::Value),
+        (status = 304, description = "the caller's workflow list is unchanged since the sent ETag"),
+    ),
 )]
+pub(crate) async fn get_workflows_conditional<T: DatabaseImpl>(
+    db: Extension<Arc<T>>,
+    ctx: Extension<AuthContext>,
+    query: Query<WorkflowQuery>,
+    headers: HeaderMap,
+) -> Response {
+    let (status, body) = get_workflows(db, ctx, query).await;
+    let ApiResponse::WorkflowList(workflows) = &body.0 else {
+        return (status, body).into_response();
+    };
+    let Ok(serialized) = serde_json::to_vec(workflows) else {
+        return (status, body).into_response();
+    };
+    let etag = workflow_list_etag(&serialized);
+    if if_none_match(&headers, &etag) {
+        return (StatusCode::NOT_MODIFIED, [(header::ETAG, etag)]).into_response();
+    }
+    // deletions do not move the newest `updated_at`, so the etag stays the validator that counts.
+    let last_modified = workflows
+        .iter()
+        .filter_map(|workflow| workflow.updated_at)
+        .max()
+        .map(|at| at.format("%a, %d %b %Y %H:%M:%S GMT").to_string());
+    let mut response = (status, body).into_response();
+    if let Ok(value) = HeaderValue::from_str(&etag) {
+        response.headers_mut().insert(header::ETAG, value);
+    }
+    if let Some(value) = last_modified.and_then(|at| HeaderValue::from_str(&at).ok()) {
+        response.headers_mut().insert(header::LAST_MODIFIED, value);
+    }
+    response
+}
+
+// a strong etag over the serialized list, so it is stable across ws replicas.
+fn workflow_list_etag(serialized: &[u8]) -> String {
+    let digest = Sha256::digest(serialized);
+    let hex = digest
+        .iter()
+        .take(16)
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>();
+    format!("\"{hex}\"")
+}
+
+// true when any tag in `If-None-Match` (weak or strong) or the `*` wildcard matches.
+fn if_none_match(headers: &HeaderMap, etag: &str) -> bool {
+    headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
+}
+
+/// list workflow definitions visible to the caller.
 pub(crate) async fn get_workflows<T: DatabaseImpl>(
     Extension(db): Extension<Arc<T>>,
     Extension(ctx): Extension<AuthContext>,
@@ -180,30 +695,204 @@ pub(crate) async fn get_workflows<T: DatabaseImpl>(
                 _ => (StatusCode::OK, Json(ApiResponse::Workflow(workflow))),
             },
             Ok(None) => not_found("Workflow not found"),
-            Err(err) => api_error(err.to_string()),
+            Err(err) => repository_error(err.as_ref()),
         };
     }
 
     match repository::fetch_workflows(db.as_ref()).await {
         Ok(workflows) => {
-            // scope to the caller's org first (cross-tenant workflows are never listed), then to the
-            // grant-based visibility set (None = admin/auth-disabled = all grant-visible).
-            let workflows: Vec<_> = workflows
-                .into_iter()
-                .filter(|workflow| authz::org_visible(&ctx, workflow.org_id))
-                .collect();
-            let visible = authz::visible_workflow_ids(db.as_ref(), &ctx).await;
-            let workflows = match visible {
-                Some(ids) => workflows
-                    .into_iter()
-                    .filter(|workflow| workflow.id.is_some_and(|id| ids.contains(&id)))
-                    .collect(),
-                None => workflows,
-            };
+            let workflows = visible_workflows(db.as_ref(), &ctx, workflows).await;
             (StatusCode::OK, Json(ApiResponse::WorkflowList(workflows)))
         }
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
+    }
+}
+
+// scope to the caller's org first (cross-tenant workflows are never listed), then to the
+// grant-based visibility set (None = admin/auth-disabled = all grant-visible).
+async fn visible_workflows<T: DatabaseImpl>(
+    db: &T,
+    ctx: &AuthContext,
+    workflows: Vec<WorkflowDefinition>,
+) -> Vec<WorkflowDefinition> {
+    let workflows: Vec<_> = workflows
+        .into_iter()
+        .filter(|workflow| authz::org_visible(ctx, workflow.org_id))
+        .collect();
+    match authz::visible_workflow_ids(db, ctx).await {
+        Some(ids) => workflows
+            .into_iter()
+            .filter(|workflow| workflow.id.is_some_and(|id| ids.contains(&id)))
+            .collect(),
+        None => workflows,
+    }
+}
+
+/// case-insensitive search over the name, namespace, definition, and trigger configurations of the
+/// workflows visible to the caller, every term of `q` matching somewhere, so finding the one that
+/// references a connection string or provider action does not mean downloading them all. without
+/// `q` it pages through every visible workflow, which lets list views fetch rows as they scroll.
+/// the database does the matching; visibility and the org scope are applied before paging so every
+/// page is full.
+#[utoipa::path(
+    get,
+    path = "/workflows/search",
+    tag = "Workflows",
+    responses(
+        (status = 200, description = "one page of matching workflows and the total match count", body = serde_json::Value),
+        (status = 400, description = "the query has more than 8 terms", body = crate::models::ApiError),
+    ),
+)]
+pub(crate) async fn search_workflows<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+    Extension(ctx): Extension<AuthContext>,
+    Query(query): Query<WorkflowSearchQuery>,
+) -> (StatusCode, Json<ApiResponse>) {
+    let term = query.q.trim();
+    if workflow_search_terms(term).len() > MAX_WORKFLOW_SEARCH_TERMS {
+        return bad_request(format!(
+            "a search takes at most {MAX_WORKFLOW_SEARCH_TERMS} terms; quote a phrase to search for it as one"
+        ));
+    }
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_WORKFLOW_SEARCH_LIMIT)
+        .clamp(1, MAX_WORKFLOW_SEARCH_LIMIT);
+    let offset = query.offset.unwrap_or(0);
+    let matches = if term.is_empty() {
+        repository::fetch_workflows(db.as_ref()).await
+    } else {
+        repository::search_workflows(db.as_ref(), term.to_string()).await
+    };
+    let matches = match matches {
+        Ok(matches) => visible_workflows(db.as_ref(), &ctx, matches).await,
+        Err(err) => return repository_error(err.as_ref()),
+    };
+    let matches: Vec<_> = matches
+        .into_iter()
+        .filter(|workflow| {
+            query
+                .org_id
+                .is_none_or(|org_id| workflow.org_id == Some(org_id))
+        })
+        .filter(|workflow| !query.global || workflow.org_id.is_none())
+        .collect();
+    let total = matches.len();
+    let workflows = matches.into_iter().skip(offset).take(limit).collect();
+    (
+        StatusCode::OK,
+        Json(ApiResponse::WorkflowSearch(WorkflowSearchPage {
+            total,
+            offset,
+            limit,
+            workflows,
+        })),
+    )
+}
+
+/// the portable definition hash of every workflow visible to the caller. gitops tooling compares
+/// these with the hashes of its workflow files to find drift without pulling full definitions.
+#[utoipa::path(
+    get,
+    path = "/workflows/hashes",
+    tag = "Workflows",
+    responses((status = 200, description = "id, identity, and definition hash of each visible workflow", body = serde_json::Value)),
+)]
+pub(crate) async fn get_workflow_hashes<T: DatabaseImpl>(
+    db: Extension<Arc<T>>,
+    ctx: Extension<AuthContext>,
+) -> (StatusCode, Json<ApiResponse>) {
+    let (status, Json(body)) = get_workflows(db, ctx, Query(WorkflowQuery { name: None })).await;
+    let ApiResponse::WorkflowList(workflows) = body else {
+        return (status, Json(body));
+    };
+    let hashes = workflows
+        .iter()
+        .filter_map(|workflow| {
+            Some(WorkflowDefinitionHash {
+                id: workflow.id?,
+                name: workflow.name.clone(),
+                namespace: workflow.namespace.clone(),
+                version: workflow.version,
+                hash: runinator_workflows::workflow_definition_hash(workflow),
+            })
+        })
+        .collect();
+    (StatusCode::OK, Json(ApiResponse::WorkflowHashes(hashes)))
+}
+
+/// every workflow visible to the caller with its newest runs and the replicas that executed them,
+/// so the dashboard renders from one request.
+#[utoipa::path(
+    get,
+    path = "/workflows/overview",
+    tag = "Workflows",
+    responses((status = 200, description = "visible workflows with their recent runs and executors", body = serde_json::Value)),
+)]
+pub(crate) async fn get_workflow_overview<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+    Extension(ctx): Extension<AuthContext>,
+    Query(query): Query<WorkflowOverviewQuery>,
+) -> (StatusCode, Json<ApiResponse>) {
+    let limit = query
+        .runs
+        .unwrap_or(DEFAULT_OVERVIEW_RUNS)
+        .clamp(1, MAX_OVERVIEW_RUNS);
+    let workflows = match authz::visible_workflows(db.as_ref(), &ctx).await {
+        Ok(workflows) => workflows,
+        Err(err) => return repository_error(err.as_ref()),
+    };
+    let replicas = match repository::fetch_replicas(db.as_ref(), None, None).await {
+        Ok(list) => list
+            .replicas
+            .iter()
+            .map(|record| (record.replica_id, ExecutorRef::from(record)))
+            .collect::<HashMap<_, _>>(),
+        Err(err) => return repository_error(err.as_ref()),
+    };
+
+    // one read for every workflow's newest runs and one for all of their node runs, however many
+    // workflows are visible.
+    let workflow_ids = workflows
+        .iter()
+        .filter_map(|workflow| workflow.id)
+        .collect();
+    let runs = match repository::fetch_recent_workflow_runs_for_workflows(
+        db.as_ref(),
+        workflow_ids,
+        limit,
+    )
+    .await
+    {
+        Ok(runs) => runs,
+        Err(err) => return repository_error(err.as_ref()),
+    };
+    let run_ids = runs.iter().map(|run| run.id).collect();
+    let mut runs_by_workflow: HashMap<Uuid, Vec<_>> = HashMap::new();
+    for run in runs {
+        runs_by_workflow
+            .entry(run.workflow_id)
+            .or_default()
+            .push(run);
     }
+    let executors = match repository::fetch_run_executors(db.as_ref(), run_ids).await {
+        Ok(executors) => executors,
+        Err(err) => return repository_error(err.as_ref()),
+    };
+    let overviews = workflows
+        .iter()
+        .filter_map(|workflow| {
+            let runs = workflow
+                .id
+                .and_then(|id| runs_by_workflow.get(&id))
+                .map_or(&[][..], Vec::as_slice);
+            WorkflowOverview::build(workflow, runs, limit, &executors, &replicas)
+        })
+        .collect();
+    (
+        StatusCode::OK,
+        Json(ApiResponse::WorkflowOverview(overviews)),
+    )
 }
 
 #[utoipa::path(
@@ -216,7 +905,9 @@ pub(crate) async fn get_workflows<T: DatabaseImpl>(
             Header,
             description = "Required to acknowledge the risk of importing a raw JSON workflow bundle.",
             example = "system-breakage-possible"
-        )
+        ),
+        ("disable_schedules" = Option<bool>, Query, description = "Import every cron trigger disabled."),
+        ("schedule_offset_seconds" = Option<i64>, Query, description = "Shift every cron trigger this many seconds (0 to 86400) after its slot."),
     ),
     request_body(
         description = "A raw workflow bundle JSON payload. This path is the legacy non-zip import flow.",
@@ -224,6 +915,7 @@ pub(crate) async fn get_workflows<T: DatabaseImpl>(
     ),
     responses(
         (status = 200, description = "workflow bundle imported", body = serde_json::Value),
+        (status = 202, description = "workflow bundle imported, with its changes to protected workflows held for approval", body = serde_json::Value),
         (status = 400, description = "invalid bundle or missing risk acknowledgment", body = crate::models::ApiError),
         (status = 401, description = "request is missing or has an invalid credential", body = crate::models::ApiError),
     ),
@@ -232,6 +924,7 @@ pub(crate) async fn import_workflow_bundle<T: DatabaseImpl>(
     Extension(db): Extension<Arc<T>>,
     Extension(events): Extension<EventSender>,
     Extension(ctx): Extension<AuthContext>,
+    Query(options): Query<WorkflowImportOptions>,
     headers: HeaderMap,
     Json(bundle): Json<WorkflowBundle>,
 ) -> (StatusCode, Json<ApiResponse>) {
@@ -241,30 +934,60 @@ pub(crate) async fn import_workflow_bundle<T: DatabaseImpl>(
     if !json_workflow_import_risk_acknowledged(&headers) {
         return json_workflow_import_risk_required();
     }
-    import_acknowledged_workflow_bundle(db, events, ctx.org_id, bundle).await
+    if let Err(message) = options.validate() {
+        return bad_request(message);
+    }
+    import_acknowledged_workflow_bundle(db, events, ctx, bundle, options).await
 }
 
+/// import `bundle` once the caller has acknowledged the raw-json risk. what it would change on a
+/// protected workflow is held for approval instead, and the reply is then `202` with the bundle
+/// that was applied.
 pub(crate) async fn import_acknowledged_workflow_bundle<T: DatabaseImpl>(
     db: Arc<T>,
     events: EventSender,
-    org_id: Option<Uuid>,
-    bundle: WorkflowBundle,
+    ctx: AuthContext,
+    mut bundle: WorkflowBundle,
+    options: WorkflowImportOptions,
 ) -> (StatusCode, Json<ApiResponse>) {
     log::info!(
         "Importing workflow bundle: {} workflows, {} triggers",
         bundle.workflows.len(),
         bundle.triggers.len()
     );
-    match repository::import_workflow_bundle(db.as_ref(), bundle).await {
+    let held = match hold_protected_imports(db.as_ref(), &ctx, &mut bundle, false).await {
+        Ok(held) => held,
+        Err(reply) => return reply,
+    };
+    if !held.is_empty() {
+        log::info!(
+            "Held {} change(s) to protected workflows for approval",
+            held.len()
+        );
+    }
+    let status = if held.is_empty() {
+        StatusCode::OK
+    } else {
+        StatusCode::ACCEPTED
+    };
+    let imported = match repository::import_workflow_bundle(db.as_ref(), bundle).await {
+        Ok(mut imported) => {
+            repository::apply_workflow_import_options(db.as_ref(), &mut imported, &options)
+                .await
+                .map(|()| imported)
+        }
+        Err(err) => Err(err),
+    };
+    match imported {
         Ok(bundle) => {
             log::info!("Imported workflow bundle successfully");
             let org_id = bundle
                 .workflows
                 .first()
                 .and_then(|workflow| workflow.org_id)
-                .or(org_id);
+                .or(ctx.org_id);
             emit_workflows_changed(&events, org_id);
-            (StatusCode::OK, Json(ApiResponse::WorkflowBundle(bundle)))
+            (status, Json(ApiResponse::WorkflowBundle(bundle)))
         }
         Err(err) => {
             log::error!(
@@ -272,7 +995,7 @@ pub(crate) async fn import_acknowledged_workflow_bundle<T: DatabaseImpl>(
                 error_code_or_unknown(err.as_ref()),
                 err
             );
-            api_error(err.to_string())
+            repository_error(err.as_ref())
         }
     }
 }
@@ -293,8 +1016,10 @@ pub(crate) fn json_workflow_import_risk_required() -> (StatusCode, Json<ApiRespo
 pub(crate) async fn export_workflow_bundle<T: DatabaseImpl>(
     Extension(db): Extension<Arc<T>>,
     Extension(ctx): Extension<AuthContext>,
-) -> (StatusCode, Json<ApiResponse>) {
-    match repository::export_workflow_bundle(db.as_ref(), None).await {
+    Query(query): Query<WorkflowExportQuery>,
+) -> Response {
+    let runs = export_baseline_runs(&query);
+    match repository::export_workflow_bundle(db.as_ref(), None, runs).await {
         Ok(mut bundle) => {
             if let Some(ids) = authz::visible_workflow_ids(db.as_ref(), &ctx).await {
                 bundle
@@ -303,10 +1028,13 @@ pub(crate) async fn export_workflow_bundle<T: DatabaseImpl>(
                 bundle
                     .triggers
                     .retain(|trigger| ids.contains(&trigger.workflow_id));
+                bundle
+                    .baselines
+                    .retain(|baseline| ids.contains(&baseline.workflow_id));
             }
-            (StatusCode::OK, Json(ApiResponse::WorkflowBundle(bundle)))
+            export_response(bundle, query.format, "workflows")
         }
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()).into_response(),
     }
 }
 
@@ -314,19 +1042,108 @@ pub(crate) async fn export_single_workflow_bundle<T: DatabaseImpl>(
     Extension(db): Extension<Arc<T>>,
     Extension(ctx): Extension<AuthContext>,
     Path(workflow_id): Path<Uuid>,
-) -> (StatusCode, Json<ApiResponse>) {
+    Query(query): Query<WorkflowExportQuery>,
+) -> Response {
     if let Err(reply) =
         authz::require_workflow(db.as_ref(), &ctx, workflow_id, Permission::View).await
     {
-        return reply;
+        return reply.into_response();
     }
-    match repository::export_workflow_bundle(db.as_ref(), Some(workflow_id)).await {
+    let runs = export_baseline_runs(&query);
+    match repository::export_workflow_bundle(db.as_ref(), Some(workflow_id), runs).await {
         Ok(bundle) if bundle.workflows.is_empty() => {
-            not_found(format!("Workflow {workflow_id} not found"))
+            not_found(format!("Workflow {workflow_id} not found")).into_response()
         }
-        Ok(bundle) => (StatusCode::OK, Json(ApiResponse::WorkflowBundle(bundle))),
-        Err(err) => api_error(err.to_string()),
+        Ok(bundle) => {
+            let stem = bundle.workflows[0].name.clone();
+            export_response(bundle, query.format, &stem)
+        }
+        Err(err) => repository_error(err.as_ref()).into_response(),
+    }
+}
+
+// the finished runs an export carries per workflow, at most `MAX_EXPORT_BASELINE_RUNS`; none unless
+// asked for.
+fn export_baseline_runs(query: &WorkflowExportQuery) -> usize {
+    query.runs.unwrap_or(0).min(MAX_EXPORT_BASELINE_RUNS)
+}
+
+// without `format` the bundle is the usual json response; with one it is a download in that format,
+// in the shape the file-backed workflow source and `workflows apply` read back.
+fn export_response(
+    bundle: WorkflowBundle,
+    format: Option<WorkflowFileFormat>,
+    stem: &str,
+) -> Response {
+    let Some(format) = format else {
+        return (StatusCode::OK, Json(ApiResponse::WorkflowBundle(bundle))).into_response();
+    };
+    let body = match render_workflow_file(&bundle, format) {
+        Ok(body) => body,
+        Err(err) => return api_error(err.to_string()).into_response(),
+    };
+    let filename = format!(
+        "{}.{}",
+        stem.replace(
+            |c: char| !c.is_ascii_alphanumeric() && c != '-' && c != '_',
+            "_"
+        ),
+        format.extension()
+    );
+    (
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{filename}\""),
+            ),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+/// [`get_workflow`] with the workflow's edit version as its `ETag`, for a later `If-Match` save.
+/// a matching `If-None-Match` gets 304.
+pub(crate) async fn get_workflow_tagged<T: DatabaseImpl>(
+    db: Extension<Arc<T>>,
+    ctx: Extension<AuthContext>,
+    path: Path<Uuid>,
+    headers: HeaderMap,
+) -> Response {
+    // read the version before the body: a save in between then yields a spurious 409 on the
+    // caller's next edit rather than an etag newer than what they were shown.
+    let version = repository::fetch_workflow_edit_version(db.0.as_ref(), path.0).await;
+    let (status, body) = get_workflow(db, ctx, path).await;
+    let Ok(Some(version)) = version else {
+        return (status, body).into_response();
+    };
+    if status != StatusCode::OK {
+        return (status, body).into_response();
+    }
+    let etag = format!("\"{version}\"");
+    if if_none_match(&headers, &etag) {
+        return (StatusCode::NOT_MODIFIED, [(header::ETAG, etag)]).into_response();
+    }
+    (status, [(header::ETAG, etag)], body).into_response()
+}
+
+// the edit version a save's `If-Match` names: `None` without the header or for `*`, the
+// unparsable tag as the error.
+fn if_match_version(headers: &HeaderMap) -> Result<Option<i64>, String> {
+    let Some(value) = headers.get(header::IF_MATCH) else {
+        return Ok(None);
+    };
+    let tag = value.to_str().map_err(|_| "header".to_string())?.trim();
+    if tag == "*" {
+        return Ok(None);
     }
+    tag.trim_start_matches("W/")
+        .trim_matches('"')
+        .parse::<i64>()
+        .map(Some)
+        .map_err(|_| tag.to_string())
 }
 
 pub(crate) async fn get_workflow<T: DatabaseImpl>(
@@ -346,7 +1163,7 @@ pub(crate) async fn get_workflow<T: DatabaseImpl>(
         }
         Ok(Some(workflow)) => (StatusCode::OK, Json(ApiResponse::Workflow(workflow))),
         Ok(None) => not_found(format!("Workflow {workflow_id} not found")),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -370,7 +1187,31 @@ pub(crate) async fn duplicate_workflow<T: DatabaseImpl>(
             emit_workflows_changed(&events, workflow.org_id.or(ctx.org_id));
             (StatusCode::OK, Json(ApiResponse::Workflow(workflow)))
         }
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
+    }
+}
+
+pub(crate) async fn clone_workflow<T: DatabaseImpl>(
+    Extension(db): Extension<Arc<T>>,
+    Extension(events): Extension<EventSender>,
+    Extension(ctx): Extension<AuthContext>,
+    Path(workflow_id): Path<Uuid>,
+    Query(request): Query<WorkflowCloneRequest>,
+) -> (StatusCode, Json<ApiResponse>) {
+    if let Err(reply) =
+        authz::require_workflow(db.as_ref(), &ctx, workflow_id, Permission::View).await
+    {
+        return reply;
+    }
+    match repository::clone_workflow(db.as_ref(), workflow_id, request.name).await {
+        Ok(workflow) => {
+            if let Some(id) = workflow.id {
+                authz::grant_owner(db.as_ref(), &ctx, id).await;
+            }
+            emit_workflows_changed(&events, workflow.org_id.or(ctx.org_id));
+            (StatusCode::OK, Json(ApiResponse::Workflow(workflow)))
+        }
+        Err(err) => repository_error(err.as_ref()),
     }
 }
 
@@ -384,8 +1225,19 @@ pub(crate) async fn delete_workflow<T: DatabaseImpl>(
     {
         return reply;
     }
+    if let Some(reply) = hold_if_protected(
+        db.as_ref(),
+        &ctx,
+        workflow_id,
+        PendingChangeKind::Delete,
+        Value::Null,
+    )
+    .await
+    {
+        return reply;
+    }
     match repository::delete_workflow(db.as_ref(), workflow_id).await {
         Ok(resp) => (StatusCode::OK, Json(ApiResponse::TaskResponse(resp))),
-        Err(err) => api_error(err.to_string()),
+        Err(err) => repository_error(err.as_ref()),
     }
 }
@@ -1,9 +1,12 @@
 mod auth;
 mod authz;
 mod config;
+mod cors;
 pub mod errors;
 mod event_consumer;
 mod events;
+mod graphql;
+mod grpc;
 mod handlers;
 mod models;
 mod openapi;
@@ -13,6 +16,7 @@ pub mod orchestration {
     pub use runinator_reducer::{ReadyNodeDisposition, process_ready_node};
 }
 mod rate_limit;
+mod request_id;
 mod responses;
 mod router;
 mod server;
@@ -35,6 +39,7 @@ pub(crate) mod result_consumer {
 }
 
 pub use auth::AuthOptions;
+pub use cors::CorsConfig;
 pub use events::{AppEvent, AppEventKind, EventSender};
 pub use overload::OverloadConfig;
 pub use rate_limit::RateLimitConfig;
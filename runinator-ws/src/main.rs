@@ -6,17 +6,17 @@ use log::info;
 use runinator_broker::{
     Broker,
     adapters::{kafka::KafkaBrokerConfig, rabbitmq::RabbitMqBrokerConfig},
-    http::client::HttpBroker,
+    http::{auth::BrokerTokenSource, client::HttpBroker},
     in_memory::InMemoryBroker,
     tcp::client::TcpBroker,
 };
-use runinator_db_cli::{DatabaseBackend, dispatch_database};
+use runinator_db_cli::{DatabaseBackend, PoolSettings, dispatch_database};
 use runinator_models::errors::SendableError;
 use tokio::sync::Notify;
 use uuid::Uuid;
 
 use runinator_ws::{
-    AuthOptions, OverloadConfig, RateLimitConfig, ReplicaAdvertisement, run_webserver,
+    AuthOptions, CorsConfig, OverloadConfig, RateLimitConfig, ReplicaAdvertisement, run_webserver,
 };
 
 use crate::config::CliArgs;
@@ -35,6 +35,13 @@ async fn main() -> Result<(), SendableError> {
     let _telemetry = startup::startup("Runinator Web Service")?;
 
     let args = CliArgs::parse();
+    let mut report = startup::collect_report("Runinator Web Service", env!("CARGO_PKG_VERSION"));
+    report.plugins = runinator_provider_catalog::metadata()
+        .into_iter()
+        .map(|provider| provider.name)
+        .collect();
+    report.plugins.sort();
+    startup::publish_report(report);
 
     let notify = Arc::new(Notify::new());
     let shutdown_listener = notify.clone();
@@ -49,9 +56,15 @@ async fn main() -> Result<(), SendableError> {
 
     let CliArgs {
         port,
+        grpc_port,
         database,
         sqlite_path,
         database_url,
+        db_read_url,
+        db_max_connections,
+        db_acquire_timeout_seconds,
+        db_idle_timeout_seconds,
+        db_statement_cache_capacity,
         gossip_bind,
         gossip_port,
         gossip_targets,
@@ -65,34 +78,58 @@ async fn main() -> Result<(), SendableError> {
         broker_control_topic,
         broker_result_topic,
         broker_client_id,
+        broker_auth_secret,
         advertise_host,
         instance_id,
         auth_enabled,
         auth_access_ttl_seconds,
         auth_refresh_ttl_seconds,
+        auth_static_api_keys,
         rate_limit_enabled,
         rate_limit_rps,
         rate_limit_burst,
+        rate_limit_ip_rps,
+        rate_limit_ip_burst,
+        rate_limit_client_ip_header,
+        rate_limit_global_rps,
+        rate_limit_global_burst,
         overload_protection_enabled,
         max_concurrent_requests,
         request_timeout_seconds,
+        shutdown_grace_seconds,
+        reuse_port,
+        cors_allowed_origins,
+        cors_allowed_methods,
+        cors_allowed_headers,
         run_engine,
+        api_docs_enabled,
     } = args;
     let auth_options = AuthOptions {
         enabled: auth_enabled,
         access_ttl_secs: auth_access_ttl_seconds,
         refresh_ttl_secs: auth_refresh_ttl_seconds,
+        static_api_keys: auth_static_api_keys,
     };
     let rate_limit_options = RateLimitConfig {
         enabled: rate_limit_enabled,
         requests_per_second: rate_limit_rps,
         burst: rate_limit_burst,
+        ip_requests_per_second: rate_limit_ip_rps,
+        ip_burst: rate_limit_ip_burst,
+        client_ip_header: rate_limit_client_ip_header,
+        global_requests_per_second: rate_limit_global_rps,
+        global_burst: rate_limit_global_burst,
     };
     let overload_options = OverloadConfig {
         enabled: overload_protection_enabled,
         max_concurrent_requests,
         request_timeout: std::time::Duration::from_secs(request_timeout_seconds),
     };
+    let cors_options = CorsConfig::parse(
+        &cors_allowed_origins,
+        &cors_allowed_methods,
+        &cors_allowed_headers,
+    )?;
     // treat a blank advertise host as unset so the replica list omits it rather than storing "".
     let advertise_host = {
         let trimmed = advertise_host.trim();
@@ -119,6 +156,7 @@ async fn main() -> Result<(), SendableError> {
     let broker = build_broker(
         &broker_backend,
         &broker_endpoint,
+        broker_auth_secret.as_deref(),
         KafkaBrokerConfig::new(broker_endpoint.clone())
             .with_topics(
                 broker_action_topic.clone(),
@@ -136,6 +174,9 @@ async fn main() -> Result<(), SendableError> {
     )
     .await?;
 
+    // the advertiser outlives the shutdown signal: it keeps announcing this replica until the web
+    // server has drained, so clients are not left without a service while a successor takes over.
+    let advertise_shutdown = Arc::new(Notify::new());
     let service_id = Uuid::new_v4();
     if !should_spawn_gossip_advertiser(disable_gossip) {
         info!("Web service gossip advertisements disabled");
@@ -148,14 +189,22 @@ async fn main() -> Result<(), SendableError> {
             announce_address: announce_address.clone(),
             announce_base_path: announce_base_path.clone(),
             interval_seconds: gossip_interval_seconds,
-            shutdown: notify.clone(),
+            shutdown: advertise_shutdown.clone(),
             service_port: port,
         });
     }
 
+    let pool_settings = PoolSettings::from_seconds(
+        db_max_connections,
+        db_acquire_timeout_seconds,
+        db_idle_timeout_seconds,
+        db_statement_cache_capacity,
+    );
     info!("Starting Runinator webservice with {database_backend} database");
     dispatch_database!(
         database,
+        pool: pool_settings,
+        read_url: db_read_url.filter(|url| !url.trim().is_empty()),
         sqlite: {
             let sqlite_path = sqlite_path.unwrap_or(app_data::default_sqlite_path()?);
             if let Some(parent) = sqlite_path.parent() {
@@ -174,16 +223,22 @@ async fn main() -> Result<(), SendableError> {
                 db,
                 notify.clone(),
                 port,
+                grpc_port,
                 broker,
                 advertisement.clone(),
                 auth_options.clone(),
                 rate_limit_options,
                 overload_options,
+                cors_options.clone(),
                 run_engine,
+                api_docs_enabled,
+                std::time::Duration::from_secs(shutdown_grace_seconds),
+                reuse_port,
             )
             .await?;
         }
     );
+    advertise_shutdown.notify_one();
 
     Ok(())
 }
@@ -195,6 +250,7 @@ fn should_spawn_gossip_advertiser(disable_gossip: bool) -> bool {
 async fn build_broker(
     backend: &str,
     endpoint: &str,
+    auth_secret: Option<&str>,
     kafka_config: KafkaBrokerConfig,
     rabbitmq_config: RabbitMqBrokerConfig,
 ) -> Result<Arc<dyn Broker>, SendableError> {
@@ -213,7 +269,9 @@ async fn build_broker(
             let client = reqwest::Client::builder()
                 .build()
                 .map_err(|err| runinator_ws::errors::BROKER_CLIENT.error(err))?;
-            Arc::new(HttpBroker::new(url, client))
+            Arc::new(
+                HttpBroker::new(url, client).with_auth(BrokerTokenSource::from_secret(auth_secret)),
+            )
         }
         "in-memory" => Arc::new(InMemoryBroker::new()),
         "tcp" => Arc::new(TcpBroker::new(endpoint.to_string())),
@@ -249,6 +307,7 @@ mod startup_tests {
         let err = match build_broker(
             "kafka",
             "localhost:9092",
+            None,
             KafkaBrokerConfig::new("localhost:9092").with_topics("actions", "control", " "),
             RabbitMqBrokerConfig::new("amqp://127.0.0.1:5672/%2f"),
         )
@@ -267,6 +326,7 @@ mod startup_tests {
         let err = match build_broker(
             "rabbitmq",
             "amqp://127.0.0.1:5672/%2f",
+            None,
             KafkaBrokerConfig::new("localhost:9092"),
             RabbitMqBrokerConfig::new("amqp://127.0.0.1:5672/%2f")
                 .with_queues("actions", "control", ""),
@@ -6,38 +6,72 @@ use runinator_models::{
     pipelines::{Pipeline, PipelineRun, PipelineRunDetail, PipelineTrigger},
     providers::ProviderMetadata,
     provisioning::{NodeBackendsResponse, ProvisionedGroup},
-    replicas::{ReplicaListResponse, ReplicaProviderRegistration, ReplicaRecord, ReplicaStatus},
+    replicas::{
+        ReplicaListResponse, ReplicaProviderRegistration, ReplicaRecord, ReplicaStatus,
+        WorkerSummary,
+    },
     runs::{RunArtifact, RunChunk, RunStatus, RunSummary},
+    schedule_shift::ScheduleShiftReport,
     settings::SettingKind,
     telemetry::ReplicaSampleSeries,
     web::TaskResponse,
     workflows::{
-        WorkflowBundle, WorkflowDefinition, WorkflowNodeRun, WorkflowNodeRunArtifact,
-        WorkflowNodeRunChunk, WorkflowRun, WorkflowRunArtifact, WorkflowStatus, WorkflowTrigger,
+        ScheduleReconcileReport, WorkflowBundle, WorkflowDefinition, WorkflowNodeRun,
+        WorkflowNodeRunArtifact, WorkflowNodeRunChunk, WorkflowRun, WorkflowRunArtifact,
+        WorkflowStatus, WorkflowTrigger, WorkflowTriggerPreview,
     },
 };
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use uuid::Uuid;
 
+/// machine-readable failure class carried on every error body, so clients can branch on it rather
+/// than on the message text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiErrorCode {
+    NotFound,
+    ValidationFailed,
+    Conflict,
+    Unauthorized,
+    Forbidden,
+    RateLimited,
+    DbError,
+    /// the broker or another service the request depends on could not be reached.
+    Unavailable,
+    /// an action provider failed.
+    ProviderFailed,
+    Internal,
+}
+
 #[derive(Debug, Serialize, ToSchema)]
 pub struct ApiError {
+    pub code: ApiErrorCode,
     pub message: String,
+    /// the stable numbered code (e.g. `RUNI124`) of the error behind a failure, when it has one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub path: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub expected: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub actual: Option<String>,
+    /// the `x-request-id` of the failed request, for finding its log lines.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
 }
 
 impl ApiError {
-    pub fn new(message: impl Into<String>) -> Self {
+    pub fn new(code: ApiErrorCode, message: impl Into<String>) -> Self {
         Self {
+            code,
             message: message.into(),
+            error_code: None,
             path: None,
             expected: None,
             actual: None,
+            request_id: crate::request_id::current(),
         }
     }
 }
@@ -96,6 +130,26 @@ pub enum ApiResponse {
     WorkflowList(Vec<WorkflowDefinition>),
     WorkflowTrigger(WorkflowTrigger),
     WorkflowTriggerList(Vec<WorkflowTrigger>),
+    WorkflowTriggerView(runinator_models::workflows::WorkflowTriggerView),
+    WorkflowTriggerViewList(Vec<runinator_models::workflows::WorkflowTriggerView>),
+    WorkflowBackfill(runinator_models::backfills::WorkflowBackfill),
+    WorkflowBackfillList(Vec<runinator_models::backfills::WorkflowBackfill>),
+    WorkflowRetention(Option<runinator_models::retention::WorkflowRetention>),
+    WorkflowProtection(Option<runinator_models::protection::WorkflowProtection>),
+    PendingWorkflowChange(runinator_models::protection::PendingWorkflowChange),
+    PendingWorkflowChangeList(Vec<runinator_models::protection::PendingWorkflowChange>),
+    Job(runinator_models::jobs::Job),
+    ClusterPause(runinator_models::cluster::ClusterPause),
+    BlackoutCalendar(runinator_models::blackouts::BlackoutCalendar),
+    BlackoutCalendarList(Vec<runinator_models::blackouts::BlackoutCalendar>),
+    StartupReport(runinator_models::telemetry::StartupReport),
+    WorkflowRevisions(Vec<runinator_models::revisions::WorkflowRevision>),
+    WorkflowTemplate(runinator_models::templates::WorkflowTemplate),
+    WorkflowTemplateList(Vec<runinator_models::templates::WorkflowTemplate>),
+    ScheduleReconcileReport(ScheduleReconcileReport),
+    ScheduleShiftReport(ScheduleShiftReport),
+    WorkflowTriggerPreview(WorkflowTriggerPreview),
+    CronPreview(runinator_models::workflows::CronPreview),
     Pipeline(Pipeline),
     PipelineList(Vec<Pipeline>),
     PipelineTrigger(PipelineTrigger),
@@ -109,14 +163,25 @@ pub enum ApiResponse {
     WorkflowNodeRunChunks(Vec<WorkflowNodeRunChunk>),
     WorkflowNodeRunArtifacts(Vec<WorkflowNodeRunArtifact>),
     WorkflowRunArtifacts(Vec<WorkflowRunArtifact>),
+    WorkflowRunOutputs(Vec<runinator_models::workflows::WorkflowRunOutput>),
     NodeTransitions(Vec<runinator_models::orchestration::NodeTransition>),
     NodeTransitionStats(Vec<runinator_models::orchestration::NodeTransitionStat>),
+    RunHealthReport(runinator_models::run_health::RunHealthReport),
+    QueueWaitReport(runinator_models::queue_wait::QueueWaitReport),
+    UpcomingSchedule(runinator_models::upcoming::UpcomingSchedule),
+    SchedulerPreview(runinator_models::upcoming::SchedulerPreview),
+    ProviderStats(runinator_models::provider_stats::ProviderStatsReport),
+    WorkflowOverview(Vec<runinator_models::overview::WorkflowOverview>),
+    WorkflowBulk(runinator_models::workflows::WorkflowBulkResponse),
+    WorkflowHashes(Vec<runinator_models::workflows::WorkflowDefinitionHash>),
+    WorkflowSearch(runinator_models::workflows::WorkflowSearchPage),
     Provider(ProviderMetadata),
     ProviderList(Vec<ProviderMetadata>),
     ProviderBundle(ProviderBundle),
     Replica(ReplicaRecord),
     ReplicaList(ReplicaListResponse),
     ReplicaSamples(ReplicaSampleSeries),
+    WorkerList(Vec<WorkerSummary>),
     ReplicaProviderRegistration(ReplicaProviderRegistration),
     ReplicaProviderRegistrationList(Vec<ReplicaProviderRegistration>),
     NodeBackends(NodeBackendsResponse),
@@ -164,6 +229,61 @@ pub struct WorkflowRunStatusQuery {
     pub limit: Option<i64>,
 }
 
+#[derive(Debug, Default, Deserialize)]
+pub struct RunHealthQuery {
+    #[serde(default)]
+    pub period: runinator_models::run_health::SummaryPeriod,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct QueueWaitQuery {
+    /// look-back window in hours; clamped server-side. absent uses the default window.
+    pub hours: Option<i64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct UpcomingQuery {
+    /// look-ahead in minutes; clamped server-side. absent uses the default window.
+    pub minutes: Option<i64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ProviderStatsQuery {
+    /// comma-separated look-back windows in hours; each is clamped server-side. absent uses the
+    /// default windows.
+    pub windows: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct WorkflowExportQuery {
+    /// `json` or `yaml` to download the bundle as a workflow file; absent returns the plain json
+    /// response.
+    pub format: Option<runinator_engine::workflow_source::WorkflowFileFormat>,
+    /// finished runs exported per workflow as baselines; clamped server-side. absent exports none.
+    pub runs: Option<usize>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct WorkflowOverviewQuery {
+    /// recent runs listed per workflow; clamped server-side. absent uses the default count.
+    pub runs: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WorkflowSearchQuery {
+    /// empty or absent pages through every visible workflow.
+    #[serde(default)]
+    pub q: String,
+    /// page size; clamped server-side. absent uses the default page size.
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+    /// only workflows owned by this org.
+    pub org_id: Option<Uuid>,
+    /// only workflows owned by no org.
+    #[serde(default)]
+    pub global: bool,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct RunStatusQuery {
     pub status: Option<RunStatus>,
@@ -176,6 +296,9 @@ pub struct RunStatusRequest {
     pub output_json: Option<Value>,
     #[serde(default)]
     pub message: Option<String>,
+    /// the reporting worker; kept from an earlier report when absent.
+    #[serde(default)]
+    pub worker_id: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -196,6 +319,19 @@ pub struct SchedulerTriggerClaimRequest {
     pub limit: Option<i64>,
 }
 
+#[derive(Debug, Default, Deserialize)]
+pub struct ReconcileSchedulesQuery {
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct TriggerUpdateQuery {
+    /// return the schedule change the update would make without saving it.
+    #[serde(default)]
+    pub preview: bool,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct SchedulerRunClaimRequest {
     pub scheduler_id: String,
@@ -240,6 +376,12 @@ pub struct WebhookSignalRequest {
     pub payload: Value,
 }
 
+/// named outputs a run records for other workflows to reference as `{{output:workflow.key}}`.
+#[derive(Debug, Deserialize)]
+pub struct WorkflowRunOutputsRequest {
+    pub outputs: std::collections::BTreeMap<String, Value>,
+}
+
 #[derive(Debug, Default, Deserialize, ToSchema)]
 pub struct WorkflowRunReplayRequest {
     #[serde(default)]
@@ -383,6 +525,11 @@ pub struct ReplicaQuery {
     pub status: Option<ReplicaStatus>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct WorkerQuery {
+    pub status: Option<ReplicaStatus>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct WorkflowOwnerRequest {
     /// the org to own the workflow, or `null`/absent to make it platform-global.
@@ -12,7 +12,9 @@ use serde_json::{Map, Value, json};
 use utoipa::openapi::security::{ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityScheme};
 use utoipa::{Modify, OpenApi};
 
-use crate::models::ApiError;
+use runinator_models::api_routes::API_VERSION_PREFIX;
+
+use crate::models::{ApiError, ApiErrorCode};
 
 #[derive(OpenApi)]
 #[openapi(
@@ -47,6 +49,10 @@ use crate::models::ApiError;
         (name = "Webhooks", description = "External webhook ingress."),
         (name = "WDL", description = "WDL language tooling."),
         (name = "WebSockets", description = "Streaming API endpoints."),
+        (name = "Pipelines", description = "Pipelines of workflows, their triggers, and runs."),
+        (name = "Organizations", description = "Tenants, memberships, quotas, and usage."),
+        (name = "Nodes", description = "On-demand node provisioning."),
+        (name = "Observability", description = "Dead letters and the audit log."),
     ),
     paths(
         crate::handlers::health::health,
@@ -58,8 +64,12 @@ use crate::models::ApiError;
         crate::handlers::auth::logout,
         crate::handlers::auth::me,
         crate::handlers::packs::import_pack,
-        crate::handlers::workflows::get_workflows,
+        crate::handlers::workflows::get_workflows_conditional,
         crate::handlers::workflows::import_workflow_bundle,
+        crate::handlers::workflows::get_workflow_overview,
+        crate::handlers::workflows::get_workflow_hashes,
+        crate::handlers::workflows::search_workflows,
+        crate::handlers::workflows::upsert_workflows_bulk,
         crate::handlers::automation::open_gate,
         crate::handlers::automation::close_gate,
         crate::handlers::runs::cancel_workflow_run,
@@ -68,7 +78,12 @@ use crate::models::ApiError;
         crate::handlers::runs::replay_workflow_run,
         crate::handlers::runs::rename_workflow_run,
         crate::handlers::runs::get_workflow_runs,
+        crate::handlers::runs::get_workflow_run_health,
+        crate::handlers::runs::get_queue_wait,
+        crate::handlers::runs::get_upcoming_schedule,
+        crate::handlers::runs::get_scheduler_preview,
         crate::handlers::providers::get_providers,
+        crate::handlers::providers::get_provider_stats,
         crate::handlers::catalog_metadata::get_node_kinds,
         crate::handlers::catalog_metadata::get_trigger_kinds,
         crate::handlers::catalog_metadata::get_enum_catalogs,
@@ -78,7 +93,7 @@ use crate::models::ApiError;
         crate::handlers::observability::get_dead_letters,
         crate::handlers::observability::get_audit_log,
     ),
-    components(schemas(ApiError)),
+    components(schemas(ApiError, ApiErrorCode)),
 )]
 pub struct ApiDoc;
 
@@ -187,9 +202,17 @@ enum Example {
     Workflow,
     WorkflowList,
     WorkflowBundle,
+    WorkflowOverview,
+    WorkflowHashes,
+    WorkflowSearch,
+    WorkflowBulk,
     WorkflowRunRequest,
     WorkflowRun,
     WorkflowRunList,
+    RunHealthSummary,
+    QueueWaitReport,
+    UpcomingSchedule,
+    SchedulerPreview,
     WorkflowRunStatus,
     WorkflowRunReplay,
     WorkflowRunRename,
@@ -206,7 +229,16 @@ enum Example {
     WdlEvaluate,
     Trigger,
     TriggerList,
+    TriggerView,
+    TriggerViewList,
     TriggerClaim,
+    Backfill,
+    BackfillList,
+    ScheduleReconcile,
+    ScheduleShiftRequest,
+    ScheduleShift,
+    CronPreviewRequest,
+    CronPreview,
     SchedulerRunClaim,
     SchedulerReadyNodeClaim,
     SchedulerRunLease,
@@ -219,6 +251,8 @@ enum Example {
     NodeRunClaim,
     NodeRunRelease,
     ArtifactList,
+    RunOutputs,
+    RunOutputList,
     CatalogItem,
     AutomationRecord,
     GateResolution,
@@ -229,8 +263,10 @@ enum Example {
     Provider,
     ProviderList,
     ProviderBundle,
+    ProviderStats,
     Replica,
     ReplicaList,
+    WorkerList,
     ReplicaProvider,
     Notification,
     NotificationList,
@@ -242,7 +278,38 @@ enum Example {
     Team,
     WebhookWake,
     WebhookSignal,
+    WebhookTriggerParameters,
     Supervisor,
+    WorkflowRetention,
+    WorkflowRevisions,
+    WorkflowProtection,
+    PendingWorkflowChange,
+    PendingWorkflowChangeList,
+    JobRequest,
+    Job,
+    ClusterPause,
+    ClusterPauseRequest,
+    BlackoutCalendar,
+    BlackoutCalendarList,
+    BlackoutCalendarRequest,
+    StartupReport,
+    WorkflowTemplate,
+    WorkflowTemplateList,
+    InstantiateTemplate,
+    WorkflowRetentionRequest,
+    Pipeline,
+    PipelineTrigger,
+    PipelineRunRequest,
+    OwnerRequest,
+    CreateOrg,
+    UpdateOrg,
+    OrgMember,
+    OrgRole,
+    SwitchOrg,
+    OrgQuota,
+    ScaleOrgNodes,
+    ScaleNodes,
+    StopNode,
 }
 
 const UUID_EXAMPLE: &str = "018f5f7c-4b74-7f44-8fd1-cde6b5c4d111";
@@ -299,6 +366,108 @@ const WORKFLOW_RUN_FILTERS: &[ParamDoc] = &[
         example: "true",
     },
 ];
+const WORKFLOW_CLONE_PARAMS: &[ParamDoc] = &[ParamDoc {
+    name: "name",
+    location: "query",
+    description: "Name for the clone; defaults to the source name with a ` (copy)` suffix.",
+    required: false,
+    example: "nightly deploy (staging)",
+}];
+const RUN_HEALTH_FILTERS: &[ParamDoc] = &[ParamDoc {
+    name: "period",
+    location: "query",
+    description: "Summary window: weekly (7 days) or monthly (30 days).",
+    required: false,
+    example: "weekly",
+}];
+const WORKFLOW_EXPORT_FILTERS: &[ParamDoc] = &[
+    ParamDoc {
+        name: "format",
+        location: "query",
+        description: "`json` or `yaml` to download the bundle as a workflow file; omit for the plain JSON response.",
+        required: false,
+        example: "yaml",
+    },
+    ParamDoc {
+        name: "runs",
+        location: "query",
+        description: "Finished runs exported per workflow as `baselines` (at most 100); omit to export none.",
+        required: false,
+        example: "10",
+    },
+];
+const WORKFLOW_SEARCH_FILTERS: &[ParamDoc] = &[
+    ParamDoc {
+        name: "q",
+        location: "query",
+        description: "Text to find in workflow names, namespaces, definitions, and trigger configurations, ignoring case. Every whitespace-separated term has to match; a double-quoted phrase is one term. Empty or absent pages through every visible workflow.",
+        required: false,
+        example: "warehouse-db",
+    },
+    ParamDoc {
+        name: "limit",
+        location: "query",
+        description: "Page size (default 50, at most 200).",
+        required: false,
+        example: "50",
+    },
+    ParamDoc {
+        name: "offset",
+        location: "query",
+        description: "Number of matches to skip.",
+        required: false,
+        example: "0",
+    },
+    ParamDoc {
+        name: "org_id",
+        location: "query",
+        description: "Only workflows owned by this organization.",
+        required: false,
+        example: UUID_EXAMPLE,
+    },
+    ParamDoc {
+        name: "global",
+        location: "query",
+        description: "Only workflows owned by no organization.",
+        required: false,
+        example: "true",
+    },
+];
+const PROVIDER_STATS_FILTERS: &[ParamDoc] = &[ParamDoc {
+    name: "windows",
+    location: "query",
+    description: "Comma-separated look-back windows in hours, each at most 720 (default 1,24,168; at most 8 windows).",
+    required: false,
+    example: "1,24,168",
+}];
+const QUEUE_WAIT_FILTERS: &[ParamDoc] = &[ParamDoc {
+    name: "hours",
+    location: "query",
+    description: "Look-back window in hours (default 24, at most 720).",
+    required: false,
+    example: "24",
+}];
+const UPCOMING_FILTERS: &[ParamDoc] = &[ParamDoc {
+    name: "minutes",
+    location: "query",
+    description: "Look-ahead window in minutes (default 60, at most 1440).",
+    required: false,
+    example: "60",
+}];
+const WORKFLOW_OVERVIEW_FILTERS: &[ParamDoc] = &[ParamDoc {
+    name: "runs",
+    location: "query",
+    description: "Recent runs listed per workflow (default 10, at most 50).",
+    required: false,
+    example: "10",
+}];
+const REPLICA_SAMPLE_FILTERS: &[ParamDoc] = &[ParamDoc {
+    name: "since_seconds",
+    location: "query",
+    description: "Only return samples from the last N seconds.",
+    required: false,
+    example: "3600",
+}];
 const RUN_FILTERS: &[ParamDoc] = &[ParamDoc {
     name: "status",
     location: "query",
@@ -322,13 +491,52 @@ const PACK_IMPORT_PARAMS: &[ParamDoc] = &[
         example: "system-breakage-possible",
     },
 ];
-const WORKFLOW_IMPORT_HEADERS: &[ParamDoc] = &[ParamDoc {
-    name: "x-runinator-json-workflow-risk",
-    location: "header",
-    description: "Required acknowledgement for importing raw JSON workflow bundles.",
-    required: true,
-    example: "system-breakage-possible",
-}];
+const WORKFLOW_IMPORT_PARAMS: &[ParamDoc] = &[
+    ParamDoc {
+        name: "x-runinator-json-workflow-risk",
+        location: "header",
+        description: "Required acknowledgement for importing raw JSON workflow bundles.",
+        required: true,
+        example: "system-breakage-possible",
+    },
+    ParamDoc {
+        name: "disable_schedules",
+        location: "query",
+        description: "Import every cron trigger of the imported workflows disabled.",
+        required: false,
+        example: "true",
+    },
+    ParamDoc {
+        name: "schedule_offset_seconds",
+        location: "query",
+        description: "Shift every cron trigger of the imported workflows this many seconds (0 to 86400) after its slot.",
+        required: false,
+        example: "3600",
+    },
+];
+const BACKFILL_PARAMS: &[ParamDoc] = &[
+    ParamDoc {
+        name: "from",
+        location: "query",
+        description: "First instant of the window, RFC 3339.",
+        required: true,
+        example: "2026-03-01T00:00:00Z",
+    },
+    ParamDoc {
+        name: "to",
+        location: "query",
+        description: "Last instant of the window, RFC 3339; must not be in the future.",
+        required: true,
+        example: "2026-03-30T23:59:59Z",
+    },
+    ParamDoc {
+        name: "concurrency",
+        location: "query",
+        description: "Backfill runs kept open at once, 1 to 32. Defaults to 1.",
+        required: false,
+        example: "4",
+    },
+];
 const WORKFLOW_TRIGGER_FILTERS: &[ParamDoc] = &[ParamDoc {
     name: "status",
     location: "query",
@@ -336,6 +544,20 @@ const WORKFLOW_TRIGGER_FILTERS: &[ParamDoc] = &[ParamDoc {
     required: false,
     example: "enabled",
 }];
+const RECONCILE_SCHEDULES_PARAMS: &[ParamDoc] = &[ParamDoc {
+    name: "dry_run",
+    location: "query",
+    description: "Report the proposed next_execution changes without writing them.",
+    required: false,
+    example: "true",
+}];
+const TRIGGER_UPDATE_PARAMS: &[ParamDoc] = &[ParamDoc {
+    name: "preview",
+    location: "query",
+    description: "Return the schedule change the update would make without saving it.",
+    required: false,
+    example: "true",
+}];
 const REPLICA_FILTERS: &[ParamDoc] = &[
     ParamDoc {
         name: "replica_type",
@@ -352,6 +574,13 @@ const REPLICA_FILTERS: &[ParamDoc] = &[
         example: "online",
     },
 ];
+const WORKER_FILTERS: &[ParamDoc] = &[ParamDoc {
+    name: "status",
+    location: "query",
+    description: "Filter workers by current status.",
+    required: false,
+    example: "live",
+}];
 const CATALOG_FILTERS: &[ParamDoc] = &[
     ParamDoc {
         name: "item_type",
@@ -467,6 +696,45 @@ const ENDPOINT_DOCS: &[EndpointDoc] = &[
         "service is ready",
         Example::Ready,
     ),
+    endpoint(
+        "get",
+        "/metrics",
+        "Meta",
+        "Scrape Prometheus metrics",
+        "Returns the service's Prometheus exposition text: request, engine, and broker counters, gauges, and histograms.",
+        true,
+        None,
+        &[],
+        200,
+        "prometheus metrics",
+        Example::None,
+    ),
+    endpoint(
+        "get",
+        "/healthz",
+        "Meta",
+        "Check service health (probe alias)",
+        "Alias of /health for load balancers and orchestrators that expect the conventional probe path.",
+        true,
+        None,
+        &[],
+        200,
+        "service is alive",
+        Example::Health,
+    ),
+    endpoint(
+        "get",
+        "/readyz",
+        "Meta",
+        "Check service readiness (probe alias)",
+        "Alias of /ready: answers 503 while the database is unreachable, so a load balancer stops routing to this replica.",
+        true,
+        None,
+        &[],
+        200,
+        "service is ready",
+        Example::Ready,
+    ),
     endpoint(
         "get",
         "/openapi.json",
@@ -506,6 +774,19 @@ const ENDPOINT_DOCS: &[EndpointDoc] = &[
         "websocket upgrade accepted",
         Example::None,
     ),
+    endpoint(
+        "get",
+        "/ws/desktop-worker",
+        "WebSockets",
+        "Attach a desktop worker",
+        "Upgrades to a bidirectional, multiplexed websocket that proxies broker receive/ack/publish calls for a desktop worker that cannot reach the broker directly.",
+        false,
+        None,
+        &[],
+        101,
+        "websocket upgrade accepted",
+        Example::None,
+    ),
     endpoint(
         "get",
         "/ws/workflow-runs/{id}",
@@ -563,7 +844,7 @@ const ENDPOINT_DOCS: &[EndpointDoc] = &[
         "/workflows",
         "Workflows",
         "Create or replace a workflow",
-        "Stores a workflow definition. New workflows are owned by the creator; updating an existing workflow requires edit access.",
+        "Stores a workflow definition. New workflows are owned by the creator; updating an existing workflow requires edit access. On a protected workflow the change is held for approval and 202 returns the pending change.",
         false,
         json_body(
             "Workflow definition to create or replace.",
@@ -731,10 +1012,10 @@ const ENDPOINT_DOCS: &[EndpointDoc] = &[
         "/workflows/import",
         "Packs",
         "Import a raw workflow bundle",
-        "Legacy JSON bundle import. This is intentionally guarded because raw JSON can bypass WDL well-formedness constraints.",
+        "Legacy JSON bundle import. This is intentionally guarded because raw JSON can bypass WDL well-formedness constraints. `disable_schedules` or `schedule_offset_seconds` adjust the cron triggers of the imported workflows, for standing up a copy of another environment that does not fire alongside it.",
         false,
         json_body("Raw workflow bundle JSON.", Example::WorkflowBundle),
-        WORKFLOW_IMPORT_HEADERS,
+        WORKFLOW_IMPORT_PARAMS,
         200,
         "imported workflow bundle",
         Example::WorkflowBundle,
@@ -744,20 +1025,72 @@ const ENDPOINT_DOCS: &[EndpointDoc] = &[
         "/workflows/export",
         "Packs",
         "Export visible workflows",
-        "Exports the caller's visible workflow definitions and triggers as a JSON workflow bundle.",
+        "Exports the caller's visible workflow definitions and triggers as a JSON workflow bundle. With `format=json` or `format=yaml` the bundle is returned as a file download that the file-backed workflow source and `runinatorctl workflows apply` import as-is. With `runs` each workflow's most recent finished runs ride along as `baselines`.",
         false,
         None,
-        &[],
+        WORKFLOW_EXPORT_FILTERS,
         200,
         "workflow bundle",
         Example::WorkflowBundle,
     ),
+    endpoint(
+        "get",
+        "/workflows/overview",
+        "Workflows",
+        "Overview visible workflows",
+        "Returns every workflow visible to the caller with its most recent runs, newest first, and the replicas that executed each run's nodes, so a dashboard can render from one request.",
+        false,
+        None,
+        WORKFLOW_OVERVIEW_FILTERS,
+        200,
+        "workflow overview",
+        Example::WorkflowOverview,
+    ),
+    endpoint(
+        "get",
+        "/workflows/hashes",
+        "Workflows",
+        "Workflow definition hashes",
+        "Lists the id, name, namespace, version, and portable definition hash of every workflow visible to the caller. The hash covers what a workflow file carries (name, namespace, version, input type, and the normalized graph), so comparing it with `runinatorctl workflows drift` output detects drift without downloading definitions.",
+        false,
+        None,
+        &[],
+        200,
+        "workflow definition hashes",
+        Example::WorkflowHashes,
+    ),
+    endpoint(
+        "get",
+        "/workflows/search",
+        "Workflows",
+        "Search workflows",
+        "Finds the workflows visible to the caller where every term of `q` appears in the name, namespace, definition (node actions and their configuration included), or a trigger configuration, ignoring case, ordered by name. Terms are separated by whitespace; a double-quoted phrase is one term. Without `q` it pages through every visible workflow, which is how list views fetch rows on demand. Returns one page of matches and the total across all pages.",
+        false,
+        None,
+        WORKFLOW_SEARCH_FILTERS,
+        200,
+        "matching workflows",
+        Example::WorkflowSearch,
+    ),
+    endpoint(
+        "post",
+        "/workflows/bulk",
+        "Workflows",
+        "Upsert workflows in bulk",
+        "Creates or updates an array of workflow definitions in a single transaction. Every entry is authorized and validated first; if any is rejected the request fails with 400, nothing is written, and the per-item results carry each rejection.",
+        false,
+        json_body("Array of workflow definitions.", Example::WorkflowList),
+        &[],
+        200,
+        "per-item results in request order",
+        Example::WorkflowBulk,
+    ),
     endpoint(
         "get",
         "/workflows/{id}",
         "Workflows",
         "Get a workflow",
-        "Fetches one workflow definition by id if the caller has view access.",
+        "Fetches one workflow definition by id if the caller has view access. The `ETag` header carries the workflow's edit version, bumped on every write; send it back as `If-Match` on the next PATCH.",
         false,
         None,
         &[],
@@ -770,7 +1103,7 @@ const ENDPOINT_DOCS: &[EndpointDoc] = &[
         "/workflows/{id}",
         "Workflows",
         "Update a workflow",
-        "Replaces the stored workflow definition for the id in the path. The request body should carry the full workflow definition.",
+        "Replaces the stored workflow definition for the id in the path. The request body should carry the full workflow definition. With `If-Match` set to the ETag from a GET, the save is refused with 409 when the workflow changed since, instead of overwriting the newer edit.",
         false,
         json_body("Workflow definition to store.", Example::Workflow),
         &[],
@@ -783,7 +1116,7 @@ const ENDPOINT_DOCS: &[EndpointDoc] = &[
         "/workflows/{id}",
         "Workflows",
         "Delete a workflow",
-        "Deletes a workflow definition. The caller must have edit access.",
+        "Deletes a workflow definition. The caller must have edit access. On a protected workflow the delete is held for approval and 202 returns the pending change.",
         false,
         None,
         &[],
@@ -796,10 +1129,10 @@ const ENDPOINT_DOCS: &[EndpointDoc] = &[
         "/workflows/{id}/export",
         "Packs",
         "Export one workflow",
-        "Exports one workflow definition and its triggers as a JSON workflow bundle.",
+        "Exports one workflow definition and its triggers as a JSON workflow bundle, or as a file download with `format=json` or `format=yaml`.",
         false,
         None,
-        &[],
+        WORKFLOW_EXPORT_FILTERS,
         200,
         "workflow bundle",
         Example::WorkflowBundle,
@@ -818,1544 +1151,2696 @@ const ENDPOINT_DOCS: &[EndpointDoc] = &[
         Example::Workflow,
     ),
     endpoint(
-        "get",
-        "/workflows/{id}/triggers",
+        "post",
+        "/workflows/{id}/clone",
         "Workflows",
-        "List workflow triggers",
-        "Lists triggers attached to one workflow definition.",
+        "Clone a workflow",
+        "Copies a workflow into a new, disabled workflow with its own id. The optional `name` query sets the clone's name; it defaults to the source name with a ` (copy)` suffix. The source's triggers are copied disabled.",
         false,
         None,
-        &[],
+        WORKFLOW_CLONE_PARAMS,
         200,
-        "workflow triggers",
-        Example::TriggerList,
+        "cloned workflow",
+        Example::Workflow,
     ),
     endpoint(
         "post",
-        "/workflows/{id}/triggers",
+        "/workflows/{id}/enable",
         "Workflows",
-        "Create or replace a workflow trigger",
-        "Creates or upserts a trigger for the workflow definition in the path.",
-        false,
-        json_body("Workflow trigger definition.", Example::Trigger),
-        &[],
-        200,
-        "stored workflow trigger",
-        Example::Trigger,
-    ),
-    endpoint(
-        "get",
-        "/workflow_triggers/due",
-        "Control Plane",
-        "List due workflow triggers",
-        "Returns workflow triggers that are ready to fire. Used by scheduler loops and diagnostics.",
+        "Enable a workflow",
+        "Sets the workflow's enabled flag without resending its definition, so concurrent edits to other fields are kept. Requires edit permission on the workflow. On a protected workflow the change is held for approval and 202 returns the pending change.",
         false,
         None,
-        WORKFLOW_TRIGGER_FILTERS,
+        &[],
         200,
-        "due workflow triggers",
-        Example::TriggerList,
+        "updated workflow",
+        Example::Workflow,
     ),
     endpoint(
         "post",
-        "/scheduler/workflow_trigger_firings/claim",
-        "Control Plane",
-        "Claim due trigger firings",
-        "Service-control endpoint used by schedulers to claim due workflow-trigger firings with a lease.",
+        "/workflows/{id}/disable",
+        "Workflows",
+        "Disable a workflow",
+        "Clears the workflow's enabled flag without resending its definition, so concurrent edits to other fields are kept. Requires edit permission on the workflow. On a protected workflow the change is held for approval and 202 returns the pending change.",
         false,
-        json_body("Scheduler id and claim limit.", Example::TriggerClaim),
+        None,
         &[],
         200,
-        "claimed trigger firings",
-        Example::TriggerList,
+        "updated workflow",
+        Example::Workflow,
     ),
     endpoint(
         "get",
-        "/workflow_triggers/{id}",
+        "/workflows/{id}/retention",
         "Workflows",
-        "Get a workflow trigger",
-        "Fetches one workflow trigger by id.",
+        "Get a workflow's retention override",
+        "Returns the workflow's run-history retention override in days, or null when the workflow follows the archiver's global window.",
         false,
         None,
         &[],
         200,
-        "workflow trigger",
-        Example::Trigger,
+        "retention override",
+        Example::WorkflowRetention,
     ),
     endpoint(
         "patch",
-        "/workflow_triggers/{id}",
+        "/workflows/{id}/retention",
         "Workflows",
-        "Update a workflow trigger",
-        "Updates one workflow trigger by id.",
+        "Set a workflow's retention override",
+        "Sets how many days of finished runs the archiver keeps for this workflow, overriding the global window. Requires edit access.",
         false,
-        json_body("Workflow trigger fields to store.", Example::Trigger),
+        json_body(
+            "Retention override payload.",
+            Example::WorkflowRetentionRequest,
+        ),
         &[],
         200,
-        "updated workflow trigger",
-        Example::Trigger,
+        "retention override",
+        Example::WorkflowRetention,
     ),
     endpoint(
         "delete",
-        "/workflow_triggers/{id}",
+        "/workflows/{id}/retention",
         "Workflows",
-        "Delete a workflow trigger",
-        "Deletes one workflow trigger by id.",
+        "Clear a workflow's retention override",
+        "Removes the workflow's retention override so its runs fall back to the archiver's global window. Requires edit access.",
         false,
         None,
         &[],
         200,
-        "workflow trigger deleted",
+        "retention override cleared",
         Example::TaskResponse,
     ),
     endpoint(
-        "post",
-        "/workflow_triggers/{id}/runs",
-        "Workflow Runs",
-        "Start a run from a trigger",
-        "Creates a workflow run using a trigger id and the supplied parameters.",
+        "get",
+        "/workflows/{id}/revisions",
+        "Workflows",
+        "List a workflow's revisions",
+        "Returns every saved revision of the workflow, newest first. Each revision holds the full workflow as saved along with who saved it and when.",
         false,
-        json_body("Trigger run parameters.", Example::WorkflowRunRequest),
+        None,
         &[],
-        202,
-        "workflow run accepted",
-        Example::WorkflowRun,
+        200,
+        "workflow revisions",
+        Example::WorkflowRevisions,
     ),
     endpoint(
-        "get",
-        "/workflow_runs",
-        "Workflow Runs",
-        "List workflow runs",
-        "Lists recent workflow runs visible to the caller, with optional filters by status, workflow id, name, or open state.",
+        "post",
+        "/workflows/{id}/revert/{revision}",
+        "Workflows",
+        "Revert a workflow to a revision",
+        "Saves the given revision's workflow again, recording the revert as a new revision. The workflow keeps its current organization. Requires edit access. On a protected workflow the revert is held for approval and 202 returns the pending change.",
         false,
         None,
-        WORKFLOW_RUN_FILTERS,
+        &[],
         200,
-        "workflow runs",
-        Example::WorkflowRunList,
+        "reverted workflow",
+        Example::Workflow,
     ),
     endpoint(
         "get",
-        "/replicas",
-        "Replicas",
-        "List service replicas",
-        "Lists registered web, worker, waker, scheduler, and other runtime replicas, optionally filtered by kind or status.",
+        "/workflows/{id}/protection",
+        "Workflows",
+        "Get a workflow's protection",
+        "Returns the workflow's second-approver protection, or null when it is not protected.",
         false,
         None,
-        REPLICA_FILTERS,
+        &[],
         200,
-        "replicas",
-        Example::ReplicaList,
+        "workflow protection",
+        Example::WorkflowProtection,
     ),
     endpoint(
-        "post",
-        "/replicas/register",
-        "Replicas",
-        "Register a replica",
-        "Registers a runtime replica and its advertised identity.",
+        "put",
+        "/workflows/{id}/protection",
+        "Workflows",
+        "Protect a workflow",
+        "Protects the workflow: from now on saving, deleting, enabling, disabling, or reverting it is held as a pending change until a different principal approves it. Requires edit access and an authenticated principal.",
         false,
-        json_body("Replica registration record.", Example::Replica),
+        None,
         &[],
         200,
-        "registered replica",
-        Example::Replica,
+        "workflow protection",
+        Example::WorkflowProtection,
     ),
     endpoint(
-        "post",
-        "/replicas/{replica_id}/heartbeat",
-        "Replicas",
-        "Heartbeat a replica",
-        "Updates a replica heartbeat and status so the service can track liveness.",
+        "delete",
+        "/workflows/{id}/protection",
+        "Workflows",
+        "Request to unprotect a workflow",
+        "Holds lifting the workflow's protection as a pending change for a second principal to approve. Requires edit access.",
         false,
-        json_body("Replica heartbeat fields.", Example::Replica),
+        None,
         &[],
-        200,
-        "heartbeat recorded",
-        Example::Replica,
+        202,
+        "pending change",
+        Example::PendingWorkflowChange,
     ),
     endpoint(
-        "post",
-        "/replicas/{replica_id}/offline",
-        "Replicas",
-        "Mark a replica offline",
-        "Marks a registered replica offline.",
+        "get",
+        "/workflows/{id}/pending",
+        "Workflows",
+        "List a workflow's pending changes",
+        "Returns the changes to a protected workflow that still await review, oldest first.",
         false,
         None,
         &[],
         200,
-        "replica marked offline",
-        Example::TaskResponse,
+        "pending changes",
+        Example::PendingWorkflowChangeList,
     ),
     endpoint(
-        "get",
-        "/replicas/{replica_id}/providers",
-        "Replicas",
-        "List replica providers",
-        "Lists provider registrations advertised by one replica.",
+        "post",
+        "/workflows/{id}/pending/{change_id}/approve",
+        "Workflows",
+        "Approve a pending change",
+        "Approves and applies a pending change. The approver needs edit access and must be an authenticated principal other than the one who requested the change.",
         false,
         None,
         &[],
         200,
-        "replica providers",
-        Example::ProviderList,
+        "approved change",
+        Example::PendingWorkflowChange,
     ),
     endpoint(
         "post",
-        "/replicas/{replica_id}/providers",
-        "Replicas",
-        "Upsert a replica provider",
-        "Stores provider metadata advertised by one replica.",
+        "/workflows/{id}/pending/{change_id}/reject",
+        "Workflows",
+        "Reject a pending change",
+        "Rejects a pending change without applying it. Requires edit access; the requester may reject their own change.",
         false,
-        json_body("Replica provider registration.", Example::ReplicaProvider),
+        None,
         &[],
         200,
-        "replica provider stored",
-        Example::ReplicaProvider,
+        "rejected change",
+        Example::PendingWorkflowChange,
     ),
     endpoint(
-        "post",
-        "/scheduler/workflow_runs/claim",
-        "Control Plane",
-        "Claim workflow runs for scheduling",
-        "Service-control endpoint used by scheduler loops to claim runnable workflow runs with a lease.",
+        "get",
+        "/workflows/{id}/triggers",
+        "Workflows",
+        "List workflow triggers",
+        "Lists triggers attached to one workflow definition. Each enabled cron trigger carries `effective_next_run`, when it will actually fire next, and `schedule_adjustments`, the offset, jitter, misfire, and blackout steps between its cron slot and that time.",
         false,
-        json_body(
-            "Scheduler id, lease deadline, statuses, and limit.",
-            Example::SchedulerRunClaim,
-        ),
+        None,
         &[],
         200,
-        "claimed workflow runs",
-        Example::WorkflowRunList,
+        "workflow triggers",
+        Example::TriggerViewList,
     ),
     endpoint(
         "post",
-        "/scheduler/ready_nodes/claim",
-        "Control Plane",
-        "Claim ready nodes",
-        "Service-control endpoint used by scheduler loops to claim ready workflow nodes before dispatching wakes or actions.",
+        "/workflows/{id}/triggers",
+        "Workflows",
+        "Create or replace a workflow trigger",
+        "Creates or upserts a trigger for the workflow definition in the path.",
         false,
-        json_body(
-            "Scheduler id, lease deadline, and limit.",
-            Example::SchedulerReadyNodeClaim,
-        ),
+        json_body("Workflow trigger definition.", Example::Trigger),
         &[],
         200,
-        "claimed ready nodes",
-        Example::WorkflowRunList,
+        "stored workflow trigger",
+        Example::Trigger,
     ),
     endpoint(
         "get",
-        "/runs",
-        "Runs",
-        "List task runs by status",
-        "Service-control endpoint that lists low-level task runs for a required status.",
+        "/workflow_triggers/due",
+        "Control Plane",
+        "List due workflow triggers",
+        "Returns workflow triggers that are ready to fire. Used by scheduler loops and diagnostics.",
         false,
         None,
-        RUN_FILTERS,
+        WORKFLOW_TRIGGER_FILTERS,
         200,
-        "task runs",
-        Example::RunList,
+        "due workflow triggers",
+        Example::TriggerList,
     ),
     endpoint(
-        "patch",
-        "/runs/{id}",
-        "Runs",
-        "Update a task run",
-        "Service-control endpoint used by workers to update low-level task-run status and output.",
+        "post",
+        "/scheduler/workflow_trigger_firings/claim",
+        "Control Plane",
+        "Claim due trigger firings",
+        "Service-control endpoint used by schedulers to claim due workflow-trigger firings with a lease.",
         false,
-        json_body("Task run status update.", Example::RunStatus),
+        json_body("Scheduler id and claim limit.", Example::TriggerClaim),
         &[],
         200,
-        "task run updated",
-        Example::TaskResponse,
+        "claimed trigger firings",
+        Example::TriggerList,
     ),
     endpoint(
-        "get",
-        "/runs/{id}/chunks",
-        "Runs",
-        "List task run chunks",
-        "Service-control endpoint that returns streamed chunks for a low-level task run.",
+        "post",
+        "/admin/reconcile_schedules",
+        "Control Plane",
+        "Reconcile trigger schedules",
+        "Admin-only repair that recomputes next_execution for every enabled cron workflow and pipeline trigger and reports each drifted value. With dry_run=true nothing is written.",
         false,
         None,
-        CURSOR,
+        RECONCILE_SCHEDULES_PARAMS,
         200,
-        "task run chunks",
-        Example::RunChunk,
+        "schedule reconcile report",
+        Example::ScheduleReconcile,
     ),
     endpoint(
         "post",
-        "/runs/{id}/chunks",
-        "Runs",
-        "Append a task run chunk",
-        "Service-control endpoint used by workers to append stdout, stderr, log, or structured chunks.",
-        false,
-        json_body("Run chunk to append.", Example::RunChunk),
-        &[],
-        202,
-        "task run chunk appended",
-        Example::RunChunk,
-    ),
-    endpoint(
-        "get",
-        "/runs/{id}/artifacts",
-        "Artifacts",
-        "List task run artifacts",
-        "Lists artifacts linked to a low-level task run.",
+        "/admin/shift_schedules",
+        "Control Plane",
+        "Shift trigger schedules",
+        "Admin-only bulk move of the cron triggers selected by workflow_ids, namespace, and/or trigger_ids, either by an offset in minutes or by spreading them evenly across a daily HH:MM window. Rewritable crons and their next_execution are updated in one transaction; crons that cannot be rewritten are reported with an error. Triggers on a protected workflow are marked protected and, unless dry_run, held as pending trigger saves (pending_change_id) for a second approver instead of rewritten. With dry_run=true nothing is written.",
         false,
-        None,
+        json_body(
+            "Trigger selection, shift mode, and dry_run flag.",
+            Example::ScheduleShiftRequest,
+        ),
         &[],
         200,
-        "run artifacts",
-        Example::Artifact,
+        "schedule shift report",
+        Example::ScheduleShift,
     ),
     endpoint(
         "post",
-        "/runs/{id}/artifacts",
-        "Artifacts",
-        "Attach a task run artifact",
-        "Registers an artifact produced by a low-level task run.",
+        "/cron/preview",
+        "Control Plane",
+        "Preview a cron expression",
+        "Validates a cron expression and lists its next fire times (count defaults to 5, at most 100) after `from`, which defaults to now. `timezone` reads the expression in an IANA zone instead of UTC. With blackout_start and blackout_end, a slot inside the window is shown firing at the window's end, marked deferred, as the trigger loop would run it. An unparseable expression returns valid=false with the parse error. Nothing is saved.",
         false,
-        json_body("Artifact metadata to attach.", Example::Artifact),
+        json_body(
+            "Cron expression, optional count, start time, and zone, and an optional blackout window.",
+            Example::CronPreviewRequest,
+        ),
         &[],
-        202,
-        "run artifact attached",
-        Example::Artifact,
+        200,
+        "cron preview",
+        Example::CronPreview,
     ),
     endpoint(
         "get",
-        "/artifacts",
-        "Artifacts",
-        "List artifacts",
-        "Lists stored artifacts across runs when permitted by the caller.",
+        "/blackout_calendars",
+        "Control Plane",
+        "List blackout calendars",
+        "Lists the named holiday calendars cron triggers can reference from the `blackouts` list in their configuration (`{\"calendar\": \"us-market-holidays\"}`). A trigger does not fire on a calendar date, read as a whole day in the calendar's timezone.",
         false,
         None,
         &[],
         200,
-        "artifacts",
-        Example::Artifact,
+        "blackout calendars",
+        Example::BlackoutCalendarList,
     ),
     endpoint(
-        "post",
-        "/artifacts/upload",
-        "Artifacts",
-        "Upload artifact bytes",
-        "Uploads artifact content as multipart form data and records artifact metadata.",
+        "put",
+        "/blackout_calendars/{name}",
+        "Control Plane",
+        "Save a blackout calendar",
+        "Creates or replaces the named calendar's dates, description, and IANA timezone (UTC when omitted). Dates are stored sorted and deduplicated. Requires the `settings:manage` capability.",
         false,
-        Some(RequestDoc {
-            description: "Multipart artifact upload payload.",
-            example: Example::Artifact,
-            content_type: "multipart/form-data",
-        }),
+        json_body(
+            "Calendar dates, timezone, and description.",
+            Example::BlackoutCalendarRequest,
+        ),
         &[],
         200,
-        "artifact uploaded",
-        Example::Artifact,
+        "saved blackout calendar",
+        Example::BlackoutCalendar,
     ),
     endpoint(
-        "get",
-        "/artifacts/{id}/download",
-        "Artifacts",
-        "Download an artifact",
-        "Downloads artifact bytes for the requested artifact id.",
+        "delete",
+        "/blackout_calendars/{name}",
+        "Control Plane",
+        "Delete a blackout calendar",
+        "Deletes the named calendar; triggers still naming it are no longer blacked out by it. Requires the `settings:manage` capability.",
         false,
         None,
         &[],
         200,
-        "artifact bytes",
-        Example::None,
+        "deleted",
+        Example::TaskResponse,
     ),
     endpoint(
         "get",
-        "/notifications",
-        "Notifications",
-        "List notifications",
-        "Lists notifications for the current principal.",
+        "/workflow_triggers/{id}",
+        "Workflows",
+        "Get a workflow trigger",
+        "Fetches one workflow trigger by id, with its `effective_next_run` and `schedule_adjustments` as in the trigger list.",
         false,
         None,
         &[],
         200,
-        "notifications",
-        Example::NotificationList,
+        "workflow trigger",
+        Example::TriggerView,
     ),
     endpoint(
-        "post",
-        "/notifications",
-        "Notifications",
-        "Create a notification",
-        "Creates a notification record.",
+        "patch",
+        "/workflow_triggers/{id}",
+        "Workflows",
+        "Update a workflow trigger",
+        "Updates one workflow trigger by id and logs the before/after fire times when its cron changes. With preview=true nothing is saved and the response is the trigger as it would be stored together with the next five fire times of the old and new cron.",
         false,
-        json_body("Notification payload.", Example::Notification),
-        &[],
+        json_body("Workflow trigger fields to store.", Example::Trigger),
+        TRIGGER_UPDATE_PARAMS,
         200,
-        "created notification",
-        Example::Notification,
+        "updated workflow trigger",
+        Example::Trigger,
     ),
     endpoint(
-        "post",
-        "/notifications/{id}/mark_read",
-        "Notifications",
-        "Mark a notification read",
-        "Marks one notification as read.",
+        "delete",
+        "/workflow_triggers/{id}",
+        "Workflows",
+        "Delete a workflow trigger",
+        "Deletes one workflow trigger by id.",
         false,
         None,
         &[],
         200,
-        "notification marked read",
+        "workflow trigger deleted",
         Example::TaskResponse,
     ),
     endpoint(
         "post",
-        "/notifications/mark_all_read",
-        "Notifications",
-        "Mark all notifications read",
-        "Marks all notifications visible to the caller as read.",
+        "/workflow_triggers/{id}/runs",
+        "Workflow Runs",
+        "Start a run from a trigger",
+        "Creates a workflow run using a trigger id and the supplied parameters.",
         false,
-        None,
+        json_body("Trigger run parameters.", Example::WorkflowRunRequest),
         &[],
-        200,
-        "notifications marked read",
-        Example::TaskResponse,
+        202,
+        "workflow run accepted",
+        Example::WorkflowRun,
     ),
     endpoint(
         "post",
-        "/workflows/{id}/runs",
+        "/workflow_triggers/{id}/backfill",
         "Workflow Runs",
-        "Start a workflow run",
-        "Creates a workflow run from a workflow definition and supplied parameters.",
+        "Backfill a cron trigger",
+        "Replays every slot the cron trigger had from `from` through `to`, e.g. to re-run a daily dump for the last 30 days after fixing it. The slots are worked out now, at most 1000 of them; the trigger loop then starts them oldest first, keeping at most `concurrency` of the workflow's backfill runs open. Each run gets the trigger's parameters plus `scheduled_for`, its slot. Backfilled runs skip the only_if, depends_on, freshness, and overlap gates. A workflow runs one backfill at a time; another while one is running is a 409.",
         false,
-        json_body("Workflow run parameters.", Example::WorkflowRunRequest),
-        &[],
+        None,
+        BACKFILL_PARAMS,
         202,
-        "workflow run accepted",
-        Example::WorkflowRun,
+        "backfill accepted",
+        Example::Backfill,
     ),
     endpoint(
         "get",
-        "/workflow_runs/{id}",
+        "/workflow_triggers/{id}/backfill",
         "Workflow Runs",
-        "Get a workflow run",
-        "Fetches a workflow run plus node-run records.",
+        "List a trigger's backfills",
+        "Lists the trigger's backfills, newest first, with their slots and how many have been started.",
         false,
         None,
         &[],
         200,
-        "workflow run",
-        Example::WorkflowRun,
+        "backfills",
+        Example::BackfillList,
     ),
     endpoint(
-        "patch",
-        "/workflow_runs/{id}",
-        "Control Plane",
-        "Update a workflow run",
-        "Service-control endpoint used by runtime loops to update workflow-run status, state, and active node.",
+        "post",
+        "/workflow_backfills/{id}/cancel",
+        "Workflow Runs",
+        "Cancel a backfill",
+        "Stops a running backfill from starting any more slots. Runs it already started carry on.",
         false,
-        json_body("Workflow run status update.", Example::WorkflowRunStatus),
+        None,
         &[],
         200,
-        "workflow run updated",
-        Example::TaskResponse,
+        "canceled backfill",
+        Example::Backfill,
     ),
     endpoint(
-        "post",
-        "/scheduler/workflow_runs/{id}/claim/renew",
-        "Control Plane",
-        "Renew a workflow-run claim",
-        "Renews a scheduler lease for a claimed workflow run.",
+        "get",
+        "/workflow_runs",
+        "Workflow Runs",
+        "List workflow runs",
+        "Lists recent workflow runs visible to the caller, with optional filters by status, workflow id, name, or open state.",
         false,
-        json_body(
-            "Scheduler id and new lease deadline.",
-            Example::SchedulerRunLease,
-        ),
-        &[],
+        None,
+        WORKFLOW_RUN_FILTERS,
         200,
-        "workflow-run claim renewed",
-        Example::TaskResponse,
+        "workflow runs",
+        Example::WorkflowRunList,
     ),
     endpoint(
-        "post",
-        "/scheduler/workflow_runs/{id}/claim/release",
-        "Control Plane",
-        "Release a workflow-run claim",
-        "Releases a scheduler lease for a claimed workflow run.",
+        "get",
+        "/workflow_run_health",
+        "Workflow Runs",
+        "Summarize workflow run health",
+        "Aggregates the caller's visible workflow runs over a weekly or monthly window into a success rate, the slowest workflows, and workflows that started failing, with a plain-text digest.",
         false,
-        json_body(
-            "Scheduler id releasing the claim.",
-            Example::SchedulerRunLease,
-        ),
-        &[],
+        None,
+        RUN_HEALTH_FILTERS,
         200,
-        "workflow-run claim released",
-        Example::TaskResponse,
+        "run health summary",
+        Example::RunHealthSummary,
     ),
     endpoint(
-        "post",
-        "/scheduler/action_dispatches",
-        "Control Plane",
-        "Enqueue an action dispatch",
-        "Durable outbox endpoint for scheduling an action command that a worker will execute.",
+        "get",
+        "/queue_wait",
+        "Workflow Runs",
+        "Summarize queue wait times",
+        "Aggregates how long node commands waited in the broker before a worker started them, globally and per task, over the caller's visible workflows. Waits over the alert threshold are counted and raise a warning notification as they are reported.",
         false,
-        json_body("Action dispatch record.", Example::ActionDispatch),
-        &[],
+        None,
+        QUEUE_WAIT_FILTERS,
         200,
-        "action dispatch queued",
-        Example::ActionDispatch,
+        "queue wait report",
+        Example::QueueWaitReport,
     ),
     endpoint(
         "get",
-        "/scheduler/action_dispatches/pending",
-        "Control Plane",
-        "List pending action dispatches",
-        "Lists action dispatches waiting to be published to the broker action channel.",
+        "/schedule/upcoming",
+        "Workflow Runs",
+        "Preview upcoming work",
+        "Merges node runs executing now, node commands and runs waiting for a worker or scheduler, and the cron firings projected through the window into one timeline over the caller's visible workflows. Entries are ordered running, then queued, then scheduled, each oldest first. A scheduled slot already past due is marked overdue. At most 100 firings are projected per trigger.",
         false,
         None,
-        &[],
+        UPCOMING_FILTERS,
         200,
-        "pending action dispatches",
-        Example::ActionDispatchList,
+        "upcoming schedule",
+        Example::UpcomingSchedule,
     ),
     endpoint(
-        "post",
-        "/scheduler/action_dispatches/claim",
-        "Control Plane",
-        "Claim action dispatches",
-        "Claims pending action dispatches for the action publisher loop.",
+        "get",
+        "/scheduler/preview",
+        "Workflow Runs",
+        "Dry-run the next scheduling cycle",
+        "Evaluates every enabled cron trigger over the caller's visible workflows as the trigger loop would through the window, without claiming or enqueueing anything. Each firing lists its cron slot and when it would be enqueued; a slot inside a blackout is marked deferred and fires when the blackout ends, even past the window. Triggers whose schedule does not evaluate are listed under invalid. Gates judged when a run starts (only_if, depends_on, freshness_seconds, overlap) are not evaluated. At most 100 firings are previewed per trigger.",
         false,
-        json_body("Claim owner and limit.", Example::ActionDispatch),
-        &[],
+        None,
+        UPCOMING_FILTERS,
         200,
-        "claimed action dispatches",
-        Example::ActionDispatchList,
+        "scheduling cycle preview",
+        Example::SchedulerPreview,
     ),
     endpoint(
-        "post",
-        "/scheduler/action_dispatches/{id}/published",
-        "Control Plane",
-        "Mark action dispatch published",
-        "Marks an action dispatch as successfully published to the broker.",
+        "get",
+        "/replicas",
+        "Replicas",
+        "List service replicas",
+        "Lists registered web, worker, waker, scheduler, and other runtime replicas, optionally filtered by kind or status.",
         false,
         None,
-        &[],
+        REPLICA_FILTERS,
         200,
-        "action dispatch marked published",
-        Example::TaskResponse,
+        "replicas",
+        Example::ReplicaList,
     ),
     endpoint(
-        "post",
-        "/scheduler/action_dispatches/{id}/failed",
-        "Control Plane",
-        "Mark action dispatch failed",
-        "Records a publish failure for an action dispatch.",
+        "get",
+        "/workers",
+        "Replicas",
+        "List workers",
+        "Lists worker replicas with their last heartbeat, loaded plugins, registered providers, labels, and current load (in-flight actions and held node-run leases).",
         false,
-        json_body("Failure detail.", Example::ActionDispatch),
-        &[],
+        None,
+        WORKER_FILTERS,
         200,
-        "action dispatch marked failed",
-        Example::TaskResponse,
+        "workers",
+        Example::WorkerList,
     ),
     endpoint(
         "post",
-        "/scheduler/ready_nodes/{id}/process",
-        "Control Plane",
-        "Complete a ready-node claim",
-        "Marks a ready node processed and optionally records the next wake that should be scheduled.",
+        "/replicas/register",
+        "Replicas",
+        "Register a replica",
+        "Registers a runtime replica and its advertised identity.",
         false,
-        json_body("Ready-node completion payload.", Example::ReadyNodeProcess),
+        json_body("Replica registration record.", Example::Replica),
         &[],
         200,
-        "ready node processed",
-        Example::TaskResponse,
+        "registered replica",
+        Example::Replica,
     ),
     endpoint(
         "post",
-        "/workflow_runs/{id}/debug/command",
-        "Debug",
-        "Run a debugger command",
-        "Applies a debugger command to a paused or debuggable workflow run.",
+        "/replicas/{replica_id}/heartbeat",
+        "Replicas",
+        "Heartbeat a replica",
+        "Updates a replica heartbeat and status so the service can track liveness.",
         false,
-        json_body("Debugger command payload.", Example::AutomationRecord),
+        json_body("Replica heartbeat fields.", Example::Replica),
         &[],
         200,
-        "debug command applied",
-        Example::TaskResponse,
+        "heartbeat recorded",
+        Example::Replica,
     ),
     endpoint(
         "post",
-        "/workflow_runs/{id}/debug/step",
-        "Debug",
-        "Step a workflow run",
-        "Advances a debug-paused workflow run by one reducer step.",
+        "/replicas/{replica_id}/offline",
+        "Replicas",
+        "Mark a replica offline",
+        "Marks a registered replica offline.",
         false,
         None,
         &[],
         200,
-        "debug step applied",
+        "replica marked offline",
         Example::TaskResponse,
     ),
     endpoint(
-        "post",
-        "/workflow_runs/{id}/debug/continue",
-        "Debug",
-        "Continue a workflow run",
-        "Continues a debug-paused workflow run.",
+        "get",
+        "/replicas/{replica_id}/providers",
+        "Replicas",
+        "List replica providers",
+        "Lists provider registrations advertised by one replica.",
         false,
         None,
         &[],
         200,
-        "workflow run continued",
-        Example::TaskResponse,
+        "replica providers",
+        Example::ProviderList,
     ),
     endpoint(
-        "patch",
-        "/workflow_runs/{id}/debug",
-        "Debug",
-        "Update debugger state",
-        "Updates debugger flags or breakpoints for a workflow run.",
+        "post",
+        "/replicas/{replica_id}/providers",
+        "Replicas",
+        "Upsert a replica provider",
+        "Stores provider metadata advertised by one replica.",
         false,
-        json_body("Debug state patch.", Example::AutomationRecord),
+        json_body("Replica provider registration.", Example::ReplicaProvider),
         &[],
         200,
-        "debug state updated",
-        Example::TaskResponse,
+        "replica provider stored",
+        Example::ReplicaProvider,
     ),
     endpoint(
-        "post",
-        "/workflow_runs/{id}/cancel",
-        "Workflow Runs",
-        "Cancel a workflow run",
-        "Requests cancellation for a workflow run and publishes the required runtime control signals.",
+        "get",
+        "/replicas/{replica_id}/samples",
+        "Replicas",
+        "List replica telemetry samples",
+        "Returns a replica's recent resource telemetry samples for charting. `since_seconds` bounds the look-back window.",
         false,
         None,
-        &[],
+        REPLICA_SAMPLE_FILTERS,
         200,
-        "workflow run cancel requested",
-        Example::TaskResponse,
+        "replica samples",
+        Example::None,
     ),
     endpoint(
         "post",
-        "/workflow_runs/{id}/pause",
-        "Workflow Runs",
-        "Pause a workflow run",
-        "Requests that the reducer pause a workflow run at a safe runtime boundary.",
+        "/scheduler/workflow_runs/claim",
+        "Control Plane",
+        "Claim workflow runs for scheduling",
+        "Service-control endpoint used by scheduler loops to claim runnable workflow runs with a lease.",
         false,
-        None,
+        json_body(
+            "Scheduler id, lease deadline, statuses, and limit.",
+            Example::SchedulerRunClaim,
+        ),
         &[],
         200,
-        "workflow run pause requested",
-        Example::TaskResponse,
+        "claimed workflow runs",
+        Example::WorkflowRunList,
     ),
     endpoint(
         "post",
-        "/workflow_runs/{id}/resume",
-        "Workflow Runs",
-        "Resume a workflow run",
-        "Requests that a paused workflow run resume execution.",
+        "/scheduler/ready_nodes/claim",
+        "Control Plane",
+        "Claim ready nodes",
+        "Service-control endpoint used by scheduler loops to claim ready workflow nodes before dispatching wakes or actions.",
         false,
-        None,
+        json_body(
+            "Scheduler id, lease deadline, and limit.",
+            Example::SchedulerReadyNodeClaim,
+        ),
         &[],
         200,
-        "workflow run resume requested",
-        Example::TaskResponse,
+        "claimed ready nodes",
+        Example::WorkflowRunList,
     ),
     endpoint(
         "post",
-        "/workflow_runs/{id}/signals",
-        "Workflow Runs",
-        "Deliver a signal to a run",
-        "Delivers an external signal payload to a parked node in one workflow run.",
+        "/jobs",
+        "Runs",
+        "Submit a one-off job",
+        "Queues a single provider action to run once on a worker without storing a workflow, and returns the job id to poll. With `max_retries`, a failed or timed-out attempt is dispatched again after `retry_backoff_seconds`, doubling for each later retry. A higher `priority` is handed to workers first; a `queue` reaches only workers subscribed to it. `std.exec`, `std.code`, and the local-files provider only run inside workflows. Requires the `jobs:run` capability.",
         false,
-        json_body("Signal name and payload.", Example::WebhookSignal),
+        json_body("Action to run once.", Example::JobRequest),
         &[],
-        200,
-        "signal delivered",
-        Example::TaskResponse,
+        202,
+        "job queued",
+        Example::Job,
     ),
     endpoint(
-        "post",
-        "/workflow_runs/{id}/debug/run_to_cursor",
-        "Debug",
-        "Run to debugger cursor",
-        "Continues a debug-paused run until the requested node or breakpoint is reached.",
+        "get",
+        "/jobs/{id}",
+        "Runs",
+        "Get a one-off job",
+        "Returns a job's status, and its output and message once a worker has reported back. `attempts` lists each finished attempt of a retried job. Requires the `jobs:run` capability.",
         false,
-        json_body("Debugger cursor target.", Example::AutomationRecord),
+        None,
         &[],
         200,
-        "run-to-cursor started",
-        Example::TaskResponse,
+        "job",
+        Example::Job,
     ),
     endpoint(
-        "post",
-        "/workflow_runs/{id}/debug/skip",
-        "Debug",
-        "Skip a debug node",
-        "Skips the selected node while debugging a workflow run.",
+        "get",
+        "/cluster/pause",
+        "Control Plane",
+        "Get the cluster pause",
+        "Returns whether action dispatch, worker execution, and trigger scheduling are paused cluster-wide. Workers poll this to honour an execution pause.",
         false,
-        json_body("Node skip request.", Example::AutomationRecord),
+        None,
         &[],
         200,
-        "debug node skipped",
-        Example::TaskResponse,
+        "cluster pause",
+        Example::ClusterPause,
     ),
     endpoint(
-        "post",
-        "/workflow_runs/{id}/debug/rerun_node",
-        "Debug",
-        "Rerun a debug node",
-        "Reruns a selected node while debugging a workflow run.",
+        "put",
+        "/cluster/pause",
+        "Control Plane",
+        "Pause the cluster",
+        "Pauses dispatch (queued actions wait in the outbox), execution (workers stop taking actions off the broker), and/or scheduling (no trigger fires and no backfilled slot starts) fleet-wide. Omitted flags default to paused. Lifting a scheduling pause recomputes every cron trigger's `next_execution` from then. Requires the `cluster:manage` capability.",
         false,
-        json_body("Node rerun request.", Example::AutomationRecord),
+        json_body(
+            "Which halves to pause, and why.",
+            Example::ClusterPauseRequest,
+        ),
         &[],
         200,
-        "debug node rerun requested",
+        "cluster pause",
+        Example::ClusterPause,
+    ),
+    endpoint(
+        "delete",
+        "/cluster/pause",
+        "Control Plane",
+        "Resume the cluster",
+        "Lifts any cluster-wide pause so held dispatches go out and workers pick up actions again. A lifted scheduling pause recomputes every cron trigger's `next_execution` from now, so slots missed while paused do not fire. Requires the `cluster:manage` capability.",
+        false,
+        None,
+        &[],
+        200,
+        "cluster pause",
+        Example::ClusterPause,
+    ),
+    endpoint(
+        "get",
+        "/runs",
+        "Runs",
+        "List task runs by status",
+        "Service-control endpoint that lists low-level task runs for a required status.",
+        false,
+        None,
+        RUN_FILTERS,
+        200,
+        "task runs",
+        Example::RunList,
+    ),
+    endpoint(
+        "patch",
+        "/runs/{id}",
+        "Runs",
+        "Update a task run",
+        "Service-control endpoint used by workers to update low-level task-run status and output.",
+        false,
+        json_body("Task run status update.", Example::RunStatus),
+        &[],
+        200,
+        "task run updated",
         Example::TaskResponse,
     ),
+    endpoint(
+        "get",
+        "/runs/{id}/chunks",
+        "Runs",
+        "List task run chunks",
+        "Service-control endpoint that returns streamed chunks for a low-level task run.",
+        false,
+        None,
+        CURSOR,
+        200,
+        "task run chunks",
+        Example::RunChunk,
+    ),
     endpoint(
         "post",
-        "/workflow_runs/{id}/replay",
+        "/runs/{id}/chunks",
+        "Runs",
+        "Append a task run chunk",
+        "Service-control endpoint used by workers to append stdout, stderr, log, or structured chunks.",
+        false,
+        json_body("Run chunk to append.", Example::RunChunk),
+        &[],
+        202,
+        "task run chunk appended",
+        Example::RunChunk,
+    ),
+    endpoint(
+        "get",
+        "/runs/{id}/artifacts",
+        "Artifacts",
+        "List task run artifacts",
+        "Lists artifacts linked to a low-level task run.",
+        false,
+        None,
+        &[],
+        200,
+        "run artifacts",
+        Example::Artifact,
+    ),
+    endpoint(
+        "post",
+        "/runs/{id}/artifacts",
+        "Artifacts",
+        "Attach a task run artifact",
+        "Registers an artifact produced by a low-level task run.",
+        false,
+        json_body("Artifact metadata to attach.", Example::Artifact),
+        &[],
+        202,
+        "run artifact attached",
+        Example::Artifact,
+    ),
+    endpoint(
+        "get",
+        "/artifacts",
+        "Artifacts",
+        "List artifacts",
+        "Lists stored artifacts across runs when permitted by the caller.",
+        false,
+        None,
+        &[],
+        200,
+        "artifacts",
+        Example::Artifact,
+    ),
+    endpoint(
+        "post",
+        "/artifacts/upload",
+        "Artifacts",
+        "Upload artifact bytes",
+        "Uploads artifact content as multipart form data and records artifact metadata.",
+        false,
+        Some(RequestDoc {
+            description: "Multipart artifact upload payload.",
+            example: Example::Artifact,
+            content_type: "multipart/form-data",
+        }),
+        &[],
+        200,
+        "artifact uploaded",
+        Example::Artifact,
+    ),
+    endpoint(
+        "get",
+        "/artifacts/{id}/download",
+        "Artifacts",
+        "Download an artifact",
+        "Downloads artifact bytes for the requested artifact id.",
+        false,
+        None,
+        &[],
+        200,
+        "artifact bytes",
+        Example::None,
+    ),
+    endpoint(
+        "get",
+        "/notifications",
+        "Notifications",
+        "List notifications",
+        "Lists notifications for the current principal.",
+        false,
+        None,
+        &[],
+        200,
+        "notifications",
+        Example::NotificationList,
+    ),
+    endpoint(
+        "post",
+        "/notifications",
+        "Notifications",
+        "Create a notification",
+        "Creates a notification record.",
+        false,
+        json_body("Notification payload.", Example::Notification),
+        &[],
+        200,
+        "created notification",
+        Example::Notification,
+    ),
+    endpoint(
+        "post",
+        "/notifications/{id}/mark_read",
+        "Notifications",
+        "Mark a notification read",
+        "Marks one notification as read.",
+        false,
+        None,
+        &[],
+        200,
+        "notification marked read",
+        Example::TaskResponse,
+    ),
+    endpoint(
+        "post",
+        "/notifications/mark_all_read",
+        "Notifications",
+        "Mark all notifications read",
+        "Marks all notifications visible to the caller as read.",
+        false,
+        None,
+        &[],
+        200,
+        "notifications marked read",
+        Example::TaskResponse,
+    ),
+    endpoint(
+        "post",
+        "/workflows/{id}/runs",
         "Workflow Runs",
-        "Replay a workflow run",
-        "Creates a replay of a workflow run, optionally starting from a specific node id.",
+        "Start a workflow run",
+        "Creates a workflow run from a workflow definition and supplied parameters.",
         false,
-        json_body("Optional replay start node.", Example::WorkflowRunReplay),
+        json_body("Workflow run parameters.", Example::WorkflowRunRequest),
         &[],
         202,
-        "workflow run replay accepted",
+        "workflow run accepted",
         Example::WorkflowRun,
     ),
     endpoint(
-        "post",
-        "/workflow_runs/{id}/rename",
+        "get",
+        "/workflow_runs/{id}",
         "Workflow Runs",
-        "Rename a workflow run",
-        "Sets or clears the human-readable name of a workflow run.",
+        "Get a workflow run",
+        "Fetches a workflow run plus node-run records.",
+        false,
+        None,
+        &[],
+        200,
+        "workflow run",
+        Example::WorkflowRun,
+    ),
+    endpoint(
+        "patch",
+        "/workflow_runs/{id}",
+        "Control Plane",
+        "Update a workflow run",
+        "Service-control endpoint used by runtime loops to update workflow-run status, state, and active node.",
+        false,
+        json_body("Workflow run status update.", Example::WorkflowRunStatus),
+        &[],
+        200,
+        "workflow run updated",
+        Example::TaskResponse,
+    ),
+    endpoint(
+        "post",
+        "/scheduler/workflow_runs/{id}/claim/renew",
+        "Control Plane",
+        "Renew a workflow-run claim",
+        "Renews a scheduler lease for a claimed workflow run.",
         false,
         json_body(
-            "New workflow-run name; null clears it.",
-            Example::WorkflowRunRename,
+            "Scheduler id and new lease deadline.",
+            Example::SchedulerRunLease,
         ),
         &[],
         200,
-        "workflow run renamed",
+        "workflow-run claim renewed",
+        Example::TaskResponse,
+    ),
+    endpoint(
+        "post",
+        "/scheduler/workflow_runs/{id}/claim/release",
+        "Control Plane",
+        "Release a workflow-run claim",
+        "Releases a scheduler lease for a claimed workflow run.",
+        false,
+        json_body(
+            "Scheduler id releasing the claim.",
+            Example::SchedulerRunLease,
+        ),
+        &[],
+        200,
+        "workflow-run claim released",
+        Example::TaskResponse,
+    ),
+    endpoint(
+        "post",
+        "/scheduler/action_dispatches",
+        "Control Plane",
+        "Enqueue an action dispatch",
+        "Durable outbox endpoint for scheduling an action command that a worker will execute.",
+        false,
+        json_body("Action dispatch record.", Example::ActionDispatch),
+        &[],
+        200,
+        "action dispatch queued",
+        Example::ActionDispatch,
+    ),
+    endpoint(
+        "get",
+        "/scheduler/action_dispatches/pending",
+        "Control Plane",
+        "List pending action dispatches",
+        "Lists action dispatches waiting to be published to the broker action channel.",
+        false,
+        None,
+        &[],
+        200,
+        "pending action dispatches",
+        Example::ActionDispatchList,
+    ),
+    endpoint(
+        "post",
+        "/scheduler/action_dispatches/claim",
+        "Control Plane",
+        "Claim action dispatches",
+        "Claims pending action dispatches for the action publisher loop.",
+        false,
+        json_body("Claim owner and limit.", Example::ActionDispatch),
+        &[],
+        200,
+        "claimed action dispatches",
+        Example::ActionDispatchList,
+    ),
+    endpoint(
+        "post",
+        "/scheduler/action_dispatches/{id}/published",
+        "Control Plane",
+        "Mark action dispatch published",
+        "Marks an action dispatch as successfully published to the broker.",
+        false,
+        None,
+        &[],
+        200,
+        "action dispatch marked published",
+        Example::TaskResponse,
+    ),
+    endpoint(
+        "post",
+        "/scheduler/action_dispatches/{id}/failed",
+        "Control Plane",
+        "Mark action dispatch failed",
+        "Records a publish failure for an action dispatch.",
+        false,
+        json_body("Failure detail.", Example::ActionDispatch),
+        &[],
+        200,
+        "action dispatch marked failed",
+        Example::TaskResponse,
+    ),
+    endpoint(
+        "post",
+        "/scheduler/ready_nodes/{id}/process",
+        "Control Plane",
+        "Complete a ready-node claim",
+        "Marks a ready node processed and optionally records the next wake that should be scheduled.",
+        false,
+        json_body("Ready-node completion payload.", Example::ReadyNodeProcess),
+        &[],
+        200,
+        "ready node processed",
+        Example::TaskResponse,
+    ),
+    endpoint(
+        "post",
+        "/workflow_runs/{id}/debug/command",
+        "Debug",
+        "Run a debugger command",
+        "Applies a debugger command to a paused or debuggable workflow run.",
+        false,
+        json_body("Debugger command payload.", Example::AutomationRecord),
+        &[],
+        200,
+        "debug command applied",
+        Example::TaskResponse,
+    ),
+    endpoint(
+        "post",
+        "/workflow_runs/{id}/debug/step",
+        "Debug",
+        "Step a workflow run",
+        "Advances a debug-paused workflow run by one reducer step.",
+        false,
+        None,
+        &[],
+        200,
+        "debug step applied",
+        Example::TaskResponse,
+    ),
+    endpoint(
+        "post",
+        "/workflow_runs/{id}/debug/continue",
+        "Debug",
+        "Continue a workflow run",
+        "Continues a debug-paused workflow run.",
+        false,
+        None,
+        &[],
+        200,
+        "workflow run continued",
+        Example::TaskResponse,
+    ),
+    endpoint(
+        "patch",
+        "/workflow_runs/{id}/debug",
+        "Debug",
+        "Update debugger state",
+        "Updates debugger flags or breakpoints for a workflow run.",
+        false,
+        json_body("Debug state patch.", Example::AutomationRecord),
+        &[],
+        200,
+        "debug state updated",
+        Example::TaskResponse,
+    ),
+    endpoint(
+        "post",
+        "/workflow_runs/{id}/cancel",
+        "Workflow Runs",
+        "Cancel a workflow run",
+        "Requests cancellation for a workflow run and publishes the required runtime control signals.",
+        false,
+        None,
+        &[],
+        200,
+        "workflow run cancel requested",
+        Example::TaskResponse,
+    ),
+    endpoint(
+        "post",
+        "/workflow_runs/{id}/pause",
+        "Workflow Runs",
+        "Pause a workflow run",
+        "Requests that the reducer pause a workflow run at a safe runtime boundary.",
+        false,
+        None,
+        &[],
+        200,
+        "workflow run pause requested",
+        Example::TaskResponse,
+    ),
+    endpoint(
+        "post",
+        "/workflow_runs/{id}/resume",
+        "Workflow Runs",
+        "Resume a workflow run",
+        "Requests that a paused workflow run resume execution.",
+        false,
+        None,
+        &[],
+        200,
+        "workflow run resume requested",
+        Example::TaskResponse,
+    ),
+    endpoint(
+        "post",
+        "/workflow_runs/{id}/signals",
+        "Workflow Runs",
+        "Deliver a signal to a run",
+        "Delivers an external signal payload to a parked node in one workflow run.",
+        false,
+        json_body("Signal name and payload.", Example::WebhookSignal),
+        &[],
+        200,
+        "signal delivered",
+        Example::TaskResponse,
+    ),
+    endpoint(
+        "post",
+        "/workflow_runs/{id}/debug/run_to_cursor",
+        "Debug",
+        "Run to debugger cursor",
+        "Continues a debug-paused run until the requested node or breakpoint is reached.",
+        false,
+        json_body("Debugger cursor target.", Example::AutomationRecord),
+        &[],
+        200,
+        "run-to-cursor started",
+        Example::TaskResponse,
+    ),
+    endpoint(
+        "post",
+        "/workflow_runs/{id}/debug/skip",
+        "Debug",
+        "Skip a debug node",
+        "Skips the selected node while debugging a workflow run.",
+        false,
+        json_body("Node skip request.", Example::AutomationRecord),
+        &[],
+        200,
+        "debug node skipped",
+        Example::TaskResponse,
+    ),
+    endpoint(
+        "post",
+        "/workflow_runs/{id}/debug/rerun_node",
+        "Debug",
+        "Rerun a debug node",
+        "Reruns a selected node while debugging a workflow run.",
+        false,
+        json_body("Node rerun request.", Example::AutomationRecord),
+        &[],
+        200,
+        "debug node rerun requested",
+        Example::TaskResponse,
+    ),
+    endpoint(
+        "post",
+        "/workflow_runs/{id}/replay",
+        "Workflow Runs",
+        "Replay a workflow run",
+        "Creates a replay of a workflow run, optionally starting from a specific node id.",
+        false,
+        json_body("Optional replay start node.", Example::WorkflowRunReplay),
+        &[],
+        202,
+        "workflow run replay accepted",
+        Example::WorkflowRun,
+    ),
+    endpoint(
+        "post",
+        "/workflow_runs/{id}/rename",
+        "Workflow Runs",
+        "Rename a workflow run",
+        "Sets or clears the human-readable name of a workflow run.",
+        false,
+        json_body(
+            "New workflow-run name; null clears it.",
+            Example::WorkflowRunRename,
+        ),
+        &[],
+        200,
+        "workflow run renamed",
+        Example::TaskResponse,
+    ),
+    endpoint(
+        "get",
+        "/supervisor/status",
+        "Supervisor",
+        "Get local supervisor status",
+        "Returns status for the local supervisor stack when the web service is running under it.",
+        false,
+        None,
+        &[],
+        200,
+        "supervisor status",
+        Example::Supervisor,
+    ),
+    endpoint(
+        "post",
+        "/workflow_runs/{id}/nodes",
+        "Control Plane",
+        "Create a workflow node run",
+        "Service-control endpoint used by the reducer to create a node-run record.",
+        false,
+        json_body("Node-run creation payload.", Example::NodeRun),
+        &[],
+        200,
+        "workflow node run",
+        Example::NodeRun,
+    ),
+    endpoint(
+        "post",
+        "/workflow_node_runs/{id}/claim",
+        "Control Plane",
+        "Claim a node run for execution",
+        "Worker-control endpoint used to claim a node run before executing the provider action.",
+        false,
+        json_body("Executor claim payload.", Example::NodeRunClaim),
+        &[],
+        200,
+        "node run claimed",
+        Example::NodeRun,
+    ),
+    endpoint(
+        "post",
+        "/workflow_node_runs/{id}/release",
+        "Control Plane",
+        "Release a node-run claim",
+        "Worker-control endpoint used to release a node-run execution claim.",
+        false,
+        json_body("Executor release payload.", Example::NodeRunRelease),
+        &[],
+        200,
+        "node-run claim released",
+        Example::TaskResponse,
+    ),
+    endpoint(
+        "patch",
+        "/workflow_node_runs/{id}",
+        "Control Plane",
+        "Update a workflow node run",
+        "Worker-control endpoint used to update node-run status, attempt, parameters, output, state, or message.",
+        false,
+        json_body("Node-run status update.", Example::NodeRunStatus),
+        &[],
+        200,
+        "node run updated",
+        Example::NodeRun,
+    ),
+    endpoint(
+        "post",
+        "/workflow_node_runs/{id}/input",
+        "Control Plane",
+        "Resolve a node-run input",
+        "Records a human or external input resolution for a node run waiting on input.",
+        false,
+        json_body("Resolved input payload.", Example::NodeRunInput),
+        &[],
+        200,
+        "node-run input resolved",
+        Example::NodeRun,
+    ),
+    endpoint(
+        "get",
+        "/workflow_node_runs/{id}/chunks",
+        "Control Plane",
+        "List node-run chunks",
+        "Returns streamed chunks for a workflow node run.",
+        false,
+        None,
+        CURSOR,
+        200,
+        "node-run chunks",
+        Example::RunChunk,
+    ),
+    endpoint(
+        "post",
+        "/workflow_node_runs/{id}/chunks",
+        "Control Plane",
+        "Append a node-run chunk",
+        "Appends stdout, stderr, log, or structured chunks for a workflow node run.",
+        false,
+        json_body("Node-run chunk to append.", Example::RunChunk),
+        &[],
+        202,
+        "node-run chunk appended",
+        Example::RunChunk,
+    ),
+    endpoint(
+        "get",
+        "/workflow_node_runs/{id}/artifacts",
+        "Artifacts",
+        "List node-run artifacts",
+        "Lists artifacts attached to one workflow node run.",
+        false,
+        None,
+        &[],
+        200,
+        "node-run artifacts",
+        Example::Artifact,
+    ),
+    endpoint(
+        "post",
+        "/workflow_node_runs/{id}/artifacts",
+        "Artifacts",
+        "Attach a node-run artifact",
+        "Registers an artifact produced by one workflow node run.",
+        false,
+        json_body("Artifact metadata to attach.", Example::Artifact),
+        &[],
+        202,
+        "node-run artifact attached",
+        Example::Artifact,
+    ),
+    endpoint(
+        "get",
+        "/workflow_runs/{id}/artifacts",
+        "Workflow Runs",
+        "List workflow run artifacts",
+        "Lists artifacts declared by output nodes in one workflow run.",
+        false,
+        None,
+        &[],
+        200,
+        "workflow run artifacts",
+        Example::ArtifactList,
+    ),
+    endpoint(
+        "get",
+        "/workflow_runs/{id}/outputs",
+        "Workflow Runs",
+        "List workflow run outputs",
+        "Lists the named outputs one workflow run recorded.",
+        false,
+        None,
+        &[],
+        200,
+        "workflow run outputs",
+        Example::RunOutputList,
+    ),
+    endpoint(
+        "post",
+        "/workflow_runs/{id}/outputs",
+        "Workflow Runs",
+        "Record workflow run outputs",
+        "Records small named outputs (strings or numbers) on a workflow run, replacing earlier values of the same name. Other workflows reference them as `{{output:workflow.key}}` in node configuration, resolved at dispatch from the referenced workflow's most recent successful run. Requires a service or admin principal.",
+        false,
+        json_body("Outputs keyed by name.", Example::RunOutputs),
+        &[],
+        202,
+        "recorded outputs",
+        Example::RunOutputList,
+    ),
+    endpoint(
+        "get",
+        "/catalog/items",
+        "Catalog",
+        "List catalog items",
+        "Lists catalog entries such as provider metadata used by authoring clients.",
+        false,
+        None,
+        CATALOG_FILTERS,
+        200,
+        "catalog items",
+        Example::CatalogItem,
+    ),
+    endpoint(
+        "post",
+        "/catalog/items",
+        "Catalog",
+        "Upsert a catalog item",
+        "Creates or replaces a catalog entry.",
+        false,
+        json_body("Catalog item payload.", Example::CatalogItem),
+        &[],
+        200,
+        "catalog item stored",
+        Example::CatalogItem,
+    ),
+    endpoint(
+        "get",
+        "/external_items",
+        "Automation",
+        "List external items",
+        "Lists external automation records, optionally filtered by workflow run or linked item.",
+        false,
+        None,
+        AUTOMATION_FILTERS,
+        200,
+        "external items",
+        Example::AutomationRecord,
+    ),
+    endpoint(
+        "post",
+        "/external_items",
+        "Automation",
+        "Create an external item",
+        "Creates an external automation record. Service credentials or admin privileges are required.",
+        false,
+        json_body("External item record.", Example::AutomationRecord),
+        &[],
+        202,
+        "external item created",
+        Example::AutomationRecord,
+    ),
+    endpoint(
+        "get",
+        "/gates",
+        "Automation",
+        "List gates",
+        "Lists gate records, optionally filtered by workflow run or status.",
+        false,
+        None,
+        GATE_FILTERS,
+        200,
+        "gates",
+        Example::AutomationRecord,
+    ),
+    endpoint(
+        "post",
+        "/gates",
+        "Automation",
+        "Create a gate",
+        "Creates a gate automation record. Service credentials or admin privileges are required.",
+        false,
+        json_body("Gate record.", Example::AutomationRecord),
+        &[],
+        202,
+        "gate created",
+        Example::AutomationRecord,
+    ),
+    endpoint(
+        "get",
+        "/gates/{id}",
+        "Automation",
+        "Get a gate",
+        "Fetches one gate record by id if the caller can view the owning workflow.",
+        false,
+        None,
+        &[],
+        200,
+        "gate",
+        Example::AutomationRecord,
+    ),
+    endpoint(
+        "delete",
+        "/gates/{id}",
+        "Automation",
+        "Delete a gate",
+        "Deletes one gate record if the caller can edit the owning workflow.",
+        false,
+        None,
+        &[],
+        200,
+        "gate deleted",
+        Example::TaskResponse,
+    ),
+    endpoint(
+        "post",
+        "/gates/{id}/open",
+        "Automation",
+        "Open a gate",
+        "Resolves a gate as open and unblocks any reducer path waiting on it.",
+        false,
+        json_body("Gate resolution metadata.", Example::GateResolution),
+        &[],
+        200,
+        "gate opened",
+        Example::AutomationRecord,
+    ),
+    endpoint(
+        "post",
+        "/gates/{id}/close",
+        "Automation",
+        "Close a gate",
+        "Resolves a gate as closed and unblocks any reducer path waiting on it.",
+        false,
+        json_body("Gate resolution metadata.", Example::GateResolution),
+        &[],
+        200,
+        "gate closed",
+        Example::AutomationRecord,
+    ),
+    endpoint(
+        "get",
+        "/automation_events",
+        "Automation",
+        "List automation events",
+        "Lists generic automation event records.",
+        false,
+        None,
+        AUTOMATION_FILTERS,
+        200,
+        "automation events",
+        Example::AutomationRecord,
+    ),
+    endpoint(
+        "post",
+        "/automation_events",
+        "Automation",
+        "Create an automation event",
+        "Creates a generic automation event record. Service credentials or admin privileges are required.",
+        false,
+        json_body("Automation event record.", Example::AutomationRecord),
+        &[],
+        202,
+        "automation event created",
+        Example::AutomationRecord,
+    ),
+    endpoint(
+        "get",
+        "/approvals",
+        "Automation",
+        "List approval requests",
+        "Lists approval request records, optionally filtered by workflow run or linked item.",
+        false,
+        None,
+        AUTOMATION_FILTERS,
+        200,
+        "approval requests",
+        Example::AutomationRecord,
+    ),
+    endpoint(
+        "post",
+        "/approvals",
+        "Automation",
+        "Create an approval request",
+        "Creates an approval request record. Service credentials or admin privileges are required.",
+        false,
+        json_body("Approval request record.", Example::AutomationRecord),
+        &[],
+        202,
+        "approval request created",
+        Example::AutomationRecord,
+    ),
+    endpoint(
+        "post",
+        "/approvals/{id}/approve",
+        "Automation",
+        "Approve a request",
+        "Resolves an approval request as approved and stores optional output.",
+        false,
+        json_body("Approval resolution payload.", Example::ApprovalResolution),
+        &[],
+        200,
+        "approval request approved",
+        Example::AutomationRecord,
+    ),
+    endpoint(
+        "post",
+        "/approvals/{id}/reject",
+        "Automation",
+        "Reject a request",
+        "Resolves an approval request as rejected and stores optional output.",
+        false,
+        json_body("Approval resolution payload.", Example::ApprovalResolution),
+        &[],
+        200,
+        "approval request rejected",
+        Example::AutomationRecord,
+    ),
+    endpoint(
+        "get",
+        "/idempotency_keys",
+        "Control Plane",
+        "Get an idempotency key",
+        "Fetches a stored idempotency result by scope and key. Service credentials or admin privileges are required.",
+        false,
+        None,
+        IDEMPOTENCY_QUERY,
+        200,
+        "idempotency result",
+        Example::Idempotency,
+    ),
+    endpoint(
+        "post",
+        "/idempotency_keys",
+        "Control Plane",
+        "Put an idempotency key",
+        "Stores an idempotency result for later duplicate-request suppression.",
+        false,
+        json_body("Idempotency scope, key, and result.", Example::Idempotency),
+        &[],
+        200,
+        "idempotency key stored",
+        Example::Idempotency,
+    ),
+    endpoint(
+        "get",
+        "/credentials",
+        "Credentials",
+        "Get credentials or config",
+        "Fetches a credential/config entry or lists entries by scope/kind. Secret values remain protected by the credential store behavior.",
+        false,
+        None,
+        CREDENTIAL_QUERY,
+        200,
+        "credential metadata",
+        Example::Credential,
+    ),
+    endpoint(
+        "post",
+        "/credentials",
+        "Credentials",
+        "Store a credential or config value",
+        "Stores a secret or typed config value. Config values carry or infer a JSON schema pinned for future updates.",
+        false,
+        json_body("Credential or config value.", Example::Credential),
+        &[],
+        200,
+        "credential stored",
+        Example::Credential,
+    ),
+    endpoint(
+        "delete",
+        "/credentials",
+        "Credentials",
+        "Delete a credential or config value",
+        "Deletes a secret or config setting selected by query parameters.",
+        false,
+        None,
+        CREDENTIAL_QUERY,
+        200,
+        "credential deleted",
+        Example::TaskResponse,
+    ),
+    endpoint(
+        "post",
+        "/credentials/import",
+        "Credentials",
+        "Import a secret bundle",
+        "Imports secret/config entries from a compiled pack secret bundle.",
+        false,
+        json_body("Secret bundle to import.", Example::SecretBundle),
+        &[],
+        200,
+        "secret bundle imported",
+        Example::SecretBundle,
+    ),
+    endpoint(
+        "post",
+        "/credentials/reencrypt",
+        "Credentials",
+        "Re-encrypt stored settings",
+        "Re-encrypts stored secrets/config values after credential-store rotation.",
+        false,
+        None,
+        &[],
+        200,
+        "settings re-encrypted",
+        Example::TaskResponse,
+    ),
+    endpoint(
+        "get",
+        "/providers",
+        "Providers",
+        "List providers",
+        "Lists registered provider metadata used by workers and workflow authoring.",
+        false,
+        None,
+        &[],
+        200,
+        "providers",
+        Example::ProviderList,
+    ),
+    endpoint(
+        "post",
+        "/providers",
+        "Providers",
+        "Upsert a provider",
+        "Stores provider metadata for a provider implementation.",
+        false,
+        json_body("Provider metadata.", Example::Provider),
+        &[],
+        200,
+        "provider stored",
+        Example::Provider,
+    ),
+    endpoint(
+        "post",
+        "/providers/import",
+        "Providers",
+        "Import provider bundle",
+        "Imports provider metadata from a provider bundle.",
+        false,
+        json_body("Provider bundle.", Example::ProviderBundle),
+        &[],
+        200,
+        "provider bundle imported",
+        Example::ProviderBundle,
+    ),
+    endpoint(
+        "get",
+        "/stats/providers",
+        "Providers",
+        "Summarize runs per provider action",
+        "Counts node runs per provider and action over each requested window, with outcomes, the number of tasks running each action, and average durations, over the caller's visible workflows. Tasks are attributed through their workflow's current definition.",
+        false,
+        None,
+        PROVIDER_STATS_FILTERS,
+        200,
+        "provider stats report",
+        Example::ProviderStats,
+    ),
+    endpoint(
+        "post",
+        "/webhooks/wake",
+        "Webhooks",
+        "Drive a waiting run by webhook",
+        "External webhook ingress that wakes or updates a parked workflow node by run id.",
+        false,
+        json_body("Webhook wake payload.", Example::WebhookWake),
+        &[],
+        202,
+        "webhook accepted",
+        Example::TaskResponse,
+    ),
+    endpoint(
+        "post",
+        "/webhooks/signal",
+        "Webhooks",
+        "Deliver a signal by correlation key",
+        "External webhook ingress that routes a signal to a parked node by business correlation key.",
+        false,
+        json_body("Webhook signal payload.", Example::WebhookSignal),
+        &[],
+        202,
+        "signal accepted",
+        Example::TaskResponse,
+    ),
+    endpoint(
+        "post",
+        "/triggers/{token}",
+        "Webhooks",
+        "Start a run from a webhook trigger",
+        "Public ingress for upstream systems: the path token identifies an enabled webhook trigger and stands in for credentials. An optional JSON object body is merged over the trigger's configured parameters. Each token is limited to about one run a second (429 with Retry-After beyond a burst of 10), and every start is audited.",
+        true,
+        json_body(
+            "Optional run parameters merged over the trigger's.",
+            Example::WebhookTriggerParameters,
+        ),
+        &[],
+        202,
+        "workflow run accepted",
+        Example::WorkflowRun,
+    ),
+    endpoint(
+        "get",
+        "/auth/config",
+        "Auth",
+        "Read auth configuration",
+        "Public endpoint that tells clients whether authentication is enabled.",
+        true,
+        None,
+        &[],
+        200,
+        "auth configuration",
+        Example::AuthConfig,
+    ),
+    endpoint(
+        "post",
+        "/auth/login",
+        "Auth",
+        "Log in",
+        "Exchanges a local username and password for an access token and refresh token.",
+        true,
+        json_body("Username and password.", Example::LoginRequest),
+        &[],
+        200,
+        "token pair",
+        Example::LoginResponse,
+    ),
+    endpoint(
+        "post",
+        "/auth/refresh",
+        "Auth",
+        "Refresh a session",
+        "Rotates a refresh token and returns a new access token, refresh token, and user record.",
+        true,
+        json_body("Refresh token to rotate.", Example::RefreshRequest),
+        &[],
+        200,
+        "rotated token pair",
+        Example::LoginResponse,
+    ),
+    endpoint(
+        "post",
+        "/auth/logout",
+        "Auth",
+        "Log out",
+        "Revokes a refresh token. The response is successful even if the token is already gone.",
+        false,
+        json_body("Refresh token to revoke.", Example::RefreshRequest),
+        &[],
+        200,
+        "refresh session revoked",
+        Example::TaskResponse,
+    ),
+    endpoint(
+        "get",
+        "/auth/me",
+        "Auth",
+        "Get current principal",
+        "Returns the current authenticated user, or a service principal marker for service API keys.",
+        false,
+        None,
+        &[],
+        200,
+        "current principal",
+        Example::User,
+    ),
+    endpoint(
+        "get",
+        "/users",
+        "Auth",
+        "List users",
+        "Admin endpoint that lists local users.",
+        false,
+        None,
+        &[],
+        200,
+        "users",
+        Example::UserList,
+    ),
+    endpoint(
+        "post",
+        "/users",
+        "Auth",
+        "Create a user",
+        "Admin endpoint that creates a local user and password credential.",
+        false,
+        json_body("User creation payload.", Example::User),
+        &[],
+        200,
+        "created user",
+        Example::User,
+    ),
+    endpoint(
+        "patch",
+        "/users/{id}",
+        "Auth",
+        "Update a user",
+        "Admin endpoint that updates user flags, email, or password.",
+        false,
+        json_body("User update payload.", Example::User),
+        &[],
+        200,
+        "updated user",
+        Example::User,
+    ),
+    endpoint(
+        "delete",
+        "/users/{id}",
+        "Auth",
+        "Delete a user",
+        "Admin endpoint that deletes a local user unless it is the last enabled admin.",
+        false,
+        None,
+        &[],
+        200,
+        "user deleted",
         Example::TaskResponse,
     ),
     endpoint(
         "get",
-        "/supervisor/status",
-        "Supervisor",
-        "Get local supervisor status",
-        "Returns status for the local supervisor stack when the web service is running under it.",
+        "/users/{id}/teams",
+        "Auth",
+        "List user teams",
+        "Admin endpoint that lists the teams a user belongs to.",
         false,
         None,
         &[],
         200,
-        "supervisor status",
-        Example::Supervisor,
+        "user teams",
+        Example::Team,
     ),
     endpoint(
-        "post",
-        "/workflow_runs/{id}/nodes",
-        "Control Plane",
-        "Create a workflow node run",
-        "Service-control endpoint used by the reducer to create a node-run record.",
+        "get",
+        "/api_keys",
+        "Auth",
+        "List API keys",
+        "Lists API keys visible to the caller. Admins see all keys; users see their own keys.",
         false,
-        json_body("Node-run creation payload.", Example::NodeRun),
+        None,
         &[],
         200,
-        "workflow node run",
-        Example::NodeRun,
+        "api keys",
+        Example::ApiKeyList,
     ),
     endpoint(
         "post",
-        "/workflow_node_runs/{id}/claim",
-        "Control Plane",
-        "Claim a node run for execution",
-        "Worker-control endpoint used to claim a node run before executing the provider action.",
+        "/api_keys",
+        "Auth",
+        "Create an API key",
+        "Creates a personal or, for admins, service API key and returns the secret once.",
         false,
-        json_body("Executor claim payload.", Example::NodeRunClaim),
+        json_body("API key creation payload.", Example::ApiKey),
         &[],
         200,
-        "node run claimed",
-        Example::NodeRun,
+        "created api key and secret",
+        Example::ApiKey,
     ),
     endpoint(
-        "post",
-        "/workflow_node_runs/{id}/release",
-        "Control Plane",
-        "Release a node-run claim",
-        "Worker-control endpoint used to release a node-run execution claim.",
+        "delete",
+        "/api_keys/{id}",
+        "Auth",
+        "Revoke an API key",
+        "Admin endpoint that revokes an API key.",
         false,
-        json_body("Executor release payload.", Example::NodeRunRelease),
+        None,
         &[],
         200,
-        "node-run claim released",
+        "api key revoked",
         Example::TaskResponse,
     ),
     endpoint(
         "patch",
-        "/workflow_node_runs/{id}",
-        "Control Plane",
-        "Update a workflow node run",
-        "Worker-control endpoint used to update node-run status, attempt, parameters, output, state, or message.",
+        "/api_keys/{id}",
+        "Auth",
+        "Update an API key",
+        "Admin endpoint that updates API key metadata such as name, expiry, or disabled state.",
         false,
-        json_body("Node-run status update.", Example::NodeRunStatus),
+        json_body("API key update payload.", Example::ApiKey),
         &[],
         200,
-        "node run updated",
-        Example::NodeRun,
+        "updated api key",
+        Example::ApiKeyList,
     ),
     endpoint(
         "post",
-        "/workflow_node_runs/{id}/input",
-        "Control Plane",
-        "Resolve a node-run input",
-        "Records a human or external input resolution for a node run waiting on input.",
+        "/api_keys/{id}/rotate",
+        "Auth",
+        "Rotate an API key",
+        "Admin endpoint that disables an API key and returns a replacement secret once.",
         false,
-        json_body("Resolved input payload.", Example::NodeRunInput),
+        None,
         &[],
         200,
-        "node-run input resolved",
-        Example::NodeRun,
+        "rotated api key and secret",
+        Example::ApiKey,
     ),
     endpoint(
         "get",
-        "/workflow_node_runs/{id}/chunks",
-        "Control Plane",
-        "List node-run chunks",
-        "Returns streamed chunks for a workflow node run.",
+        "/workflows/{id}/grants",
+        "Auth",
+        "List workflow grants",
+        "Lists sharing grants for one workflow. Owner or admin access is required.",
         false,
         None,
-        CURSOR,
+        &[],
         200,
-        "node-run chunks",
-        Example::RunChunk,
+        "workflow grants",
+        Example::Grant,
     ),
     endpoint(
         "post",
-        "/workflow_node_runs/{id}/chunks",
-        "Control Plane",
-        "Append a node-run chunk",
-        "Appends stdout, stderr, log, or structured chunks for a workflow node run.",
+        "/workflows/{id}/grants",
+        "Auth",
+        "Create a workflow grant",
+        "Creates a sharing grant for one workflow. Owner or admin access is required.",
         false,
-        json_body("Node-run chunk to append.", Example::RunChunk),
+        json_body("Workflow grant payload.", Example::Grant),
         &[],
-        202,
-        "node-run chunk appended",
-        Example::RunChunk,
+        200,
+        "workflow grant created",
+        Example::Grant,
+    ),
+    endpoint(
+        "delete",
+        "/workflows/{id}/grants/{grant_id}",
+        "Auth",
+        "Revoke a workflow grant",
+        "Revokes a sharing grant for one workflow. Owner or admin access is required.",
+        false,
+        None,
+        &[],
+        200,
+        "workflow grant revoked",
+        Example::TaskResponse,
     ),
     endpoint(
         "get",
-        "/workflow_node_runs/{id}/artifacts",
-        "Artifacts",
-        "List node-run artifacts",
-        "Lists artifacts attached to one workflow node run.",
+        "/teams",
+        "Auth",
+        "List teams",
+        "Admin endpoint that lists teams.",
         false,
         None,
         &[],
         200,
-        "node-run artifacts",
-        Example::Artifact,
+        "teams",
+        Example::Team,
     ),
     endpoint(
         "post",
-        "/workflow_node_runs/{id}/artifacts",
-        "Artifacts",
-        "Attach a node-run artifact",
-        "Registers an artifact produced by one workflow node run.",
+        "/teams",
+        "Auth",
+        "Create a team",
+        "Admin endpoint that creates a team.",
         false,
-        json_body("Artifact metadata to attach.", Example::Artifact),
+        json_body("Team creation payload.", Example::Team),
         &[],
-        202,
-        "node-run artifact attached",
-        Example::Artifact,
+        200,
+        "team created",
+        Example::Team,
     ),
     endpoint(
-        "get",
-        "/workflow_runs/{id}/artifacts",
-        "Workflow Runs",
-        "List workflow run artifacts",
-        "Lists artifacts declared by output nodes in one workflow run.",
+        "delete",
+        "/teams/{id}",
+        "Auth",
+        "Delete a team",
+        "Admin endpoint that deletes a team.",
         false,
         None,
         &[],
         200,
-        "workflow run artifacts",
-        Example::ArtifactList,
+        "team deleted",
+        Example::TaskResponse,
+    ),
+    endpoint(
+        "patch",
+        "/teams/{id}",
+        "Auth",
+        "Update a team",
+        "Admin endpoint that renames a team.",
+        false,
+        json_body("Team update payload.", Example::Team),
+        &[],
+        200,
+        "team updated",
+        Example::Team,
     ),
     endpoint(
         "get",
-        "/catalog/items",
-        "Catalog",
-        "List catalog items",
-        "Lists catalog entries such as provider metadata used by authoring clients.",
+        "/teams/{id}/members",
+        "Auth",
+        "List team members",
+        "Admin endpoint that lists users assigned to a team.",
         false,
         None,
-        CATALOG_FILTERS,
+        &[],
         200,
-        "catalog items",
-        Example::CatalogItem,
+        "team members",
+        Example::UserList,
     ),
     endpoint(
         "post",
-        "/catalog/items",
-        "Catalog",
-        "Upsert a catalog item",
-        "Creates or replaces a catalog entry.",
+        "/teams/{id}/members",
+        "Auth",
+        "Add a team member",
+        "Admin endpoint that adds a user to a team.",
         false,
-        json_body("Catalog item payload.", Example::CatalogItem),
+        json_body("Team member payload.", Example::Team),
         &[],
         200,
-        "catalog item stored",
-        Example::CatalogItem,
+        "member added",
+        Example::TaskResponse,
     ),
     endpoint(
-        "get",
-        "/external_items",
-        "Automation",
-        "List external items",
-        "Lists external automation records, optionally filtered by workflow run or linked item.",
+        "delete",
+        "/teams/{id}/members/{user_id}",
+        "Auth",
+        "Remove a team member",
+        "Admin endpoint that removes a user from a team.",
         false,
         None,
-        AUTOMATION_FILTERS,
+        &[],
         200,
-        "external items",
-        Example::AutomationRecord,
+        "member removed",
+        Example::TaskResponse,
     ),
     endpoint(
-        "post",
-        "/external_items",
-        "Automation",
-        "Create an external item",
-        "Creates an external automation record. Service credentials or admin privileges are required.",
+        "get",
+        "/workflow_runs/{id}/transitions",
+        "Workflow Runs",
+        "List run transitions",
+        "Lists the recorded node status transitions for a workflow run in the order they happened.",
         false,
-        json_body("External item record.", Example::AutomationRecord),
+        None,
         &[],
-        202,
-        "external item created",
-        Example::AutomationRecord,
+        200,
+        "run transitions",
+        Example::None,
     ),
     endpoint(
         "get",
-        "/gates",
-        "Automation",
-        "List gates",
-        "Lists gate records, optionally filtered by workflow run or status.",
+        "/workflows/{id}/nodes/{node_id}/transitions",
+        "Workflows",
+        "List node transitions",
+        "Lists recent status transitions for one node across the workflow's runs.",
         false,
         None,
-        GATE_FILTERS,
+        &[],
         200,
-        "gates",
-        Example::AutomationRecord,
+        "node transitions",
+        Example::None,
     ),
     endpoint(
-        "post",
-        "/gates",
-        "Automation",
-        "Create a gate",
-        "Creates a gate automation record. Service credentials or admin privileges are required.",
+        "get",
+        "/node-kinds",
+        "Catalog",
+        "List node kinds",
+        "Lists the workflow node kinds the authoring tools and WDL compiler understand.",
         false,
-        json_body("Gate record.", Example::AutomationRecord),
+        None,
         &[],
-        202,
-        "gate created",
-        Example::AutomationRecord,
+        200,
+        "node kinds",
+        Example::None,
     ),
     endpoint(
         "get",
-        "/gates/{id}",
-        "Automation",
-        "Get a gate",
-        "Fetches one gate record by id if the caller can view the owning workflow.",
+        "/trigger-kinds",
+        "Catalog",
+        "List trigger kinds",
+        "Lists the trigger kinds a workflow or pipeline trigger can use, with their configuration shape.",
         false,
         None,
         &[],
         200,
-        "gate",
-        Example::AutomationRecord,
+        "trigger kinds",
+        Example::None,
     ),
     endpoint(
-        "post",
-        "/gates/{id}/open",
-        "Automation",
-        "Open a gate",
-        "Resolves a gate as open and unblocks any reducer path waiting on it.",
+        "get",
+        "/catalog/enums",
+        "Catalog",
+        "List enum catalogs",
+        "Lists the named enum catalogs that authoring tools use to offer completions for constrained values.",
         false,
-        json_body("Gate resolution metadata.", Example::GateResolution),
+        None,
         &[],
         200,
-        "gate opened",
-        Example::AutomationRecord,
+        "enum catalogs",
+        Example::None,
     ),
     endpoint(
-        "post",
-        "/gates/{id}/close",
-        "Automation",
-        "Close a gate",
-        "Resolves a gate as closed and unblocks any reducer path waiting on it.",
+        "get",
+        "/dead_letters",
+        "Observability",
+        "List dead letters",
+        "Lists broker deliveries that exhausted their redelivery attempts, newest first.",
         false,
-        json_body("Gate resolution metadata.", Example::GateResolution),
+        None,
         &[],
         200,
-        "gate closed",
-        Example::AutomationRecord,
+        "dead letters",
+        Example::None,
     ),
     endpoint(
         "get",
-        "/automation_events",
-        "Automation",
-        "List automation events",
-        "Lists generic automation event records.",
+        "/audit_log",
+        "Observability",
+        "List audit log entries",
+        "Lists recorded audit events for administrative and workflow changes, newest first.",
         false,
         None,
-        AUTOMATION_FILTERS,
+        &[],
         200,
-        "automation events",
-        Example::AutomationRecord,
+        "audit log entries",
+        Example::None,
     ),
     endpoint(
-        "post",
-        "/automation_events",
-        "Automation",
-        "Create an automation event",
-        "Creates a generic automation event record. Service credentials or admin privileges are required.",
+        "get",
+        "/debug/info",
+        "Observability",
+        "Get the startup report",
+        "Returns how this replica started, for support bundles: version, host facts, the redacted `RUNINATOR_*`/`OTEL_*` environment and arguments, and the providers it serves. Requires the `settings:manage` capability.",
         false,
-        json_body("Automation event record.", Example::AutomationRecord),
+        None,
         &[],
-        202,
-        "automation event created",
-        Example::AutomationRecord,
+        200,
+        "startup report",
+        Example::StartupReport,
     ),
     endpoint(
         "get",
-        "/approvals",
-        "Automation",
-        "List approval requests",
-        "Lists approval request records, optionally filtered by workflow run or linked item.",
+        "/nodes/backends",
+        "Nodes",
+        "List provisioning backends",
+        "Lists every configured provisioning backend and the node kinds it can manage.",
         false,
         None,
-        AUTOMATION_FILTERS,
+        &[],
         200,
-        "approval requests",
-        Example::AutomationRecord,
+        "provisioning backends",
+        Example::None,
     ),
     endpoint(
-        "post",
-        "/approvals",
-        "Automation",
-        "Create an approval request",
-        "Creates an approval request record. Service credentials or admin privileges are required.",
+        "get",
+        "/nodes",
+        "Nodes",
+        "List node groups",
+        "Lists current node groups with desired and available counts across every configured backend.",
         false,
-        json_body("Approval request record.", Example::AutomationRecord),
+        None,
         &[],
-        202,
-        "approval request created",
-        Example::AutomationRecord,
+        200,
+        "node groups",
+        Example::None,
     ),
     endpoint(
         "post",
-        "/approvals/{id}/approve",
-        "Automation",
-        "Approve a request",
-        "Resolves an approval request as approved and stores optional output.",
+        "/nodes/scale",
+        "Nodes",
+        "Scale a node group",
+        "Sets the desired node count for a kind on a provisioning backend, spinning nodes up or down.",
         false,
-        json_body("Approval resolution payload.", Example::ApprovalResolution),
+        json_body("Scale request payload.", Example::ScaleNodes),
         &[],
         200,
-        "approval request approved",
-        Example::AutomationRecord,
+        "node group scaled",
+        Example::TaskResponse,
     ),
     endpoint(
         "post",
-        "/approvals/{id}/reject",
-        "Automation",
-        "Reject a request",
-        "Resolves an approval request as rejected and stores optional output.",
+        "/nodes/stop",
+        "Nodes",
+        "Stop a node",
+        "Stops and removes a single provisioned node instance.",
         false,
-        json_body("Approval resolution payload.", Example::ApprovalResolution),
+        json_body("Stop request payload.", Example::StopNode),
         &[],
         200,
-        "approval request rejected",
-        Example::AutomationRecord,
+        "node stopped",
+        Example::TaskResponse,
     ),
     endpoint(
         "get",
-        "/idempotency_keys",
-        "Control Plane",
-        "Get an idempotency key",
-        "Fetches a stored idempotency result by scope and key. Service credentials or admin privileges are required.",
+        "/workflow_templates",
+        "Workflows",
+        "List workflow templates",
+        "Returns the templates visible to the caller: their active org's plus the platform-wide ones.",
         false,
         None,
-        IDEMPOTENCY_QUERY,
+        &[],
         200,
-        "idempotency result",
-        Example::Idempotency,
+        "workflow templates",
+        Example::WorkflowTemplateList,
     ),
     endpoint(
         "post",
-        "/idempotency_keys",
-        "Control Plane",
-        "Put an idempotency key",
-        "Stores an idempotency result for later duplicate-request suppression.",
+        "/workflow_templates",
+        "Workflows",
+        "Create a workflow template",
+        "Stores a pre-filled workflow, optional cron schedule, and labels as a template owned by the caller's active org. Requires org admin, or platform admin for a platform-wide template.",
         false,
-        json_body("Idempotency scope, key, and result.", Example::Idempotency),
+        json_body("Workflow template to store.", Example::WorkflowTemplate),
         &[],
         200,
-        "idempotency key stored",
-        Example::Idempotency,
+        "created template",
+        Example::WorkflowTemplate,
     ),
     endpoint(
         "get",
-        "/credentials",
-        "Credentials",
-        "Get credentials or config",
-        "Fetches a credential/config entry or lists entries by scope/kind. Secret values remain protected by the credential store behavior.",
+        "/workflow_templates/{id}",
+        "Workflows",
+        "Get a workflow template",
+        "Fetches one template by id if the caller's org can see it.",
         false,
         None,
-        CREDENTIAL_QUERY,
+        &[],
         200,
-        "credential metadata",
-        Example::Credential,
+        "workflow template",
+        Example::WorkflowTemplate,
     ),
     endpoint(
-        "post",
-        "/credentials",
-        "Credentials",
-        "Store a credential or config value",
-        "Stores a secret or typed config value. Config values carry or infer a JSON schema pinned for future updates.",
+        "patch",
+        "/workflow_templates/{id}",
+        "Workflows",
+        "Update a workflow template",
+        "Replaces the template for the id in the path; it keeps its owning org. Requires org admin, or platform admin for a platform-wide template.",
         false,
-        json_body("Credential or config value.", Example::Credential),
+        json_body("Workflow template to store.", Example::WorkflowTemplate),
         &[],
         200,
-        "credential stored",
-        Example::Credential,
+        "updated template",
+        Example::WorkflowTemplate,
     ),
     endpoint(
         "delete",
-        "/credentials",
-        "Credentials",
-        "Delete a credential or config value",
-        "Deletes a secret or config setting selected by query parameters.",
+        "/workflow_templates/{id}",
+        "Workflows",
+        "Delete a workflow template",
+        "Deletes a template. Workflows created from it are unaffected. Requires org admin, or platform admin for a platform-wide template.",
         false,
         None,
-        CREDENTIAL_QUERY,
-        200,
-        "credential deleted",
-        Example::TaskResponse,
-    ),
-    endpoint(
-        "post",
-        "/credentials/import",
-        "Credentials",
-        "Import a secret bundle",
-        "Imports secret/config entries from a compiled pack secret bundle.",
-        false,
-        json_body("Secret bundle to import.", Example::SecretBundle),
         &[],
         200,
-        "secret bundle imported",
-        Example::SecretBundle,
+        "template deleted",
+        Example::TaskResponse,
     ),
     endpoint(
         "post",
-        "/credentials/reencrypt",
-        "Credentials",
-        "Re-encrypt stored settings",
-        "Re-encrypts stored secrets/config values after credential-store rotation.",
+        "/workflow_templates/{id}/instantiate",
+        "Workflows",
+        "Create a workflow from a template",
+        "Creates a disabled (unless requested otherwise) workflow from the template under the given name, plus a cron trigger when the template has a schedule. Returns the created workflow and triggers as a bundle.",
         false,
-        None,
+        json_body(
+            "Name and options for the new workflow.",
+            Example::InstantiateTemplate,
+        ),
         &[],
         200,
-        "settings re-encrypted",
-        Example::TaskResponse,
+        "created workflow and triggers",
+        Example::WorkflowBundle,
     ),
     endpoint(
         "get",
-        "/providers",
-        "Providers",
-        "List providers",
-        "Lists registered provider metadata used by workers and workflow authoring.",
+        "/pipelines/{id}",
+        "Pipelines",
+        "Get a pipeline",
+        "Fetches one pipeline definition by id if the caller has view access.",
         false,
         None,
         &[],
         200,
-        "providers",
-        Example::ProviderList,
+        "pipeline",
+        Example::Pipeline,
     ),
     endpoint(
-        "post",
-        "/providers",
-        "Providers",
-        "Upsert a provider",
-        "Stores provider metadata for a provider implementation.",
+        "patch",
+        "/pipelines/{id}",
+        "Pipelines",
+        "Update a pipeline",
+        "Replaces the stored pipeline definition for the id in the path. Requires edit access.",
         false,
-        json_body("Provider metadata.", Example::Provider),
+        json_body("Pipeline definition to store.", Example::Pipeline),
         &[],
         200,
-        "provider stored",
-        Example::Provider,
+        "updated pipeline",
+        Example::Pipeline,
     ),
     endpoint(
-        "post",
-        "/providers/import",
-        "Providers",
-        "Import provider bundle",
-        "Imports provider metadata from a provider bundle.",
+        "delete",
+        "/pipelines/{id}",
+        "Pipelines",
+        "Delete a pipeline",
+        "Deletes a pipeline definition and its triggers. Requires edit access.",
         false,
-        json_body("Provider bundle.", Example::ProviderBundle),
+        None,
         &[],
         200,
-        "provider bundle imported",
-        Example::ProviderBundle,
-    ),
-    endpoint(
-        "post",
-        "/webhooks/wake",
-        "Webhooks",
-        "Drive a waiting run by webhook",
-        "External webhook ingress that wakes or updates a parked workflow node by run id.",
-        false,
-        json_body("Webhook wake payload.", Example::WebhookWake),
-        &[],
-        202,
-        "webhook accepted",
+        "pipeline deleted",
         Example::TaskResponse,
     ),
     endpoint(
-        "post",
-        "/webhooks/signal",
-        "Webhooks",
-        "Deliver a signal by correlation key",
-        "External webhook ingress that routes a signal to a parked node by business correlation key.",
+        "patch",
+        "/pipelines/{id}/owner",
+        "Pipelines",
+        "Reassign a pipeline's organization",
+        "Moves a pipeline into another organization, or clears its owner. Requires owner access, plus org admin on the target org.",
         false,
-        json_body("Webhook signal payload.", Example::WebhookSignal),
+        json_body("Owner payload.", Example::OwnerRequest),
         &[],
-        202,
-        "signal accepted",
+        200,
+        "pipeline reassigned",
         Example::TaskResponse,
     ),
     endpoint(
         "get",
-        "/auth/config",
-        "Auth",
-        "Read auth configuration",
-        "Public endpoint that tells clients whether authentication is enabled.",
-        true,
+        "/pipelines/{id}/triggers",
+        "Pipelines",
+        "List pipeline triggers",
+        "Lists the triggers configured for one pipeline.",
+        false,
         None,
         &[],
         200,
-        "auth configuration",
-        Example::AuthConfig,
-    ),
-    endpoint(
-        "post",
-        "/auth/login",
-        "Auth",
-        "Log in",
-        "Exchanges a local username and password for an access token and refresh token.",
-        true,
-        json_body("Username and password.", Example::LoginRequest),
-        &[],
-        200,
-        "token pair",
-        Example::LoginResponse,
-    ),
-    endpoint(
-        "post",
-        "/auth/refresh",
-        "Auth",
-        "Refresh a session",
-        "Rotates a refresh token and returns a new access token, refresh token, and user record.",
-        true,
-        json_body("Refresh token to rotate.", Example::RefreshRequest),
-        &[],
-        200,
-        "rotated token pair",
-        Example::LoginResponse,
+        "pipeline triggers",
+        Example::None,
     ),
     endpoint(
         "post",
-        "/auth/logout",
-        "Auth",
-        "Log out",
-        "Revokes a refresh token. The response is successful even if the token is already gone.",
+        "/pipelines/{id}/triggers",
+        "Pipelines",
+        "Create a pipeline trigger",
+        "Creates or replaces a trigger for the pipeline in the path.",
         false,
-        json_body("Refresh token to revoke.", Example::RefreshRequest),
+        json_body("Pipeline trigger payload.", Example::PipelineTrigger),
         &[],
         200,
-        "refresh session revoked",
-        Example::TaskResponse,
+        "pipeline trigger",
+        Example::None,
     ),
     endpoint(
-        "get",
-        "/auth/me",
-        "Auth",
-        "Get current principal",
-        "Returns the current authenticated user, or a service principal marker for service API keys.",
+        "patch",
+        "/pipeline_triggers/{id}",
+        "Pipelines",
+        "Update a pipeline trigger",
+        "Updates one pipeline trigger by id.",
         false,
-        None,
+        json_body("Pipeline trigger payload.", Example::PipelineTrigger),
         &[],
         200,
-        "current principal",
-        Example::User,
+        "pipeline trigger",
+        Example::None,
     ),
     endpoint(
-        "get",
-        "/users",
-        "Auth",
-        "List users",
-        "Admin endpoint that lists local users.",
+        "delete",
+        "/pipeline_triggers/{id}",
+        "Pipelines",
+        "Delete a pipeline trigger",
+        "Deletes one pipeline trigger by id.",
         false,
         None,
         &[],
         200,
-        "users",
-        Example::UserList,
+        "pipeline trigger deleted",
+        Example::TaskResponse,
     ),
     endpoint(
         "post",
-        "/users",
-        "Auth",
-        "Create a user",
-        "Admin endpoint that creates a local user and password credential.",
+        "/pipeline_triggers/{id}/runs",
+        "Pipelines",
+        "Fire a pipeline trigger",
+        "Starts a pipeline run from a trigger immediately, using the supplied parameters.",
         false,
-        json_body("User creation payload.", Example::User),
+        json_body("Pipeline run parameters.", Example::PipelineRunRequest),
         &[],
         200,
-        "created user",
-        Example::User,
+        "pipeline run",
+        Example::None,
     ),
     endpoint(
-        "patch",
-        "/users/{id}",
-        "Auth",
-        "Update a user",
-        "Admin endpoint that updates user flags, email, or password.",
+        "post",
+        "/pipelines/{id}/runs",
+        "Pipelines",
+        "Start a pipeline run",
+        "Starts a run of every member workflow of the pipeline with the supplied parameters.",
         false,
-        json_body("User update payload.", Example::User),
+        json_body("Pipeline run parameters.", Example::PipelineRunRequest),
         &[],
         200,
-        "updated user",
-        Example::User,
+        "pipeline run",
+        Example::None,
     ),
     endpoint(
-        "delete",
-        "/users/{id}",
-        "Auth",
-        "Delete a user",
-        "Admin endpoint that deletes a local user unless it is the last enabled admin.",
+        "get",
+        "/pipeline_runs",
+        "Pipelines",
+        "List pipeline runs",
+        "Lists the most recent pipeline runs the caller can view.",
         false,
         None,
         &[],
         200,
-        "user deleted",
-        Example::TaskResponse,
+        "pipeline runs",
+        Example::None,
     ),
     endpoint(
         "get",
-        "/users/{id}/teams",
-        "Auth",
-        "List user teams",
-        "Admin endpoint that lists the teams a user belongs to.",
+        "/pipeline_runs/{id}",
+        "Pipelines",
+        "Get a pipeline run",
+        "Fetches one pipeline run with its member workflow runs.",
         false,
         None,
         &[],
         200,
-        "user teams",
-        Example::Team,
+        "pipeline run",
+        Example::None,
     ),
     endpoint(
-        "get",
-        "/api_keys",
-        "Auth",
-        "List API keys",
-        "Lists API keys visible to the caller. Admins see all keys; users see their own keys.",
+        "post",
+        "/pipeline_runs/{id}/cancel",
+        "Pipelines",
+        "Cancel a pipeline run",
+        "Cancels a pipeline run and its open member workflow runs.",
         false,
         None,
         &[],
         200,
-        "api keys",
-        Example::ApiKeyList,
+        "pipeline run cancelled",
+        Example::TaskResponse,
     ),
     endpoint(
         "post",
-        "/api_keys",
-        "Auth",
-        "Create an API key",
-        "Creates a personal or, for admins, service API key and returns the secret once.",
+        "/auth/switch-org",
+        "Organizations",
+        "Switch the active organization",
+        "Re-issues an access token bound to the requested organization and the caller's role there.",
         false,
-        json_body("API key creation payload.", Example::ApiKey),
+        json_body("Target organization.", Example::SwitchOrg),
         &[],
         200,
-        "created api key and secret",
-        Example::ApiKey,
+        "org-bound access token",
+        Example::None,
     ),
     endpoint(
-        "delete",
-        "/api_keys/{id}",
-        "Auth",
-        "Revoke an API key",
-        "Admin endpoint that revokes an API key.",
+        "get",
+        "/orgs",
+        "Organizations",
+        "List organizations",
+        "Platform-admin view of every organization.",
         false,
         None,
         &[],
         200,
-        "api key revoked",
-        Example::TaskResponse,
+        "organizations",
+        Example::None,
     ),
     endpoint(
-        "patch",
-        "/api_keys/{id}",
-        "Auth",
-        "Update an API key",
-        "Admin endpoint that updates API key metadata such as name, expiry, or disabled state.",
+        "post",
+        "/orgs",
+        "Organizations",
+        "Create an organization",
+        "Creates an organization; the creating user becomes its owner.",
         false,
-        json_body("API key update payload.", Example::ApiKey),
+        json_body("Organization payload.", Example::CreateOrg),
         &[],
         200,
-        "updated api key",
-        Example::ApiKeyList,
+        "organization",
+        Example::None,
     ),
     endpoint(
-        "post",
-        "/api_keys/{id}/rotate",
-        "Auth",
-        "Rotate an API key",
-        "Admin endpoint that disables an API key and returns a replacement secret once.",
+        "get",
+        "/orgs/me",
+        "Organizations",
+        "List my organizations",
+        "Lists the caller's organization memberships, each with their role.",
         false,
         None,
         &[],
         200,
-        "rotated api key and secret",
-        Example::ApiKey,
+        "memberships",
+        Example::None,
     ),
     endpoint(
         "get",
-        "/workflows/{id}/grants",
-        "Auth",
-        "List workflow grants",
-        "Lists sharing grants for one workflow. Owner or admin access is required.",
+        "/orgs/{id}",
+        "Organizations",
+        "Get an organization",
+        "Fetches one organization for any member or a platform admin.",
         false,
         None,
         &[],
         200,
-        "workflow grants",
-        Example::Grant,
+        "organization",
+        Example::None,
     ),
     endpoint(
-        "post",
-        "/workflows/{id}/grants",
-        "Auth",
-        "Create a workflow grant",
-        "Creates a sharing grant for one workflow. Owner or admin access is required.",
+        "patch",
+        "/orgs/{id}",
+        "Organizations",
+        "Update an organization",
+        "Renames, disables, or re-enables an organization. Requires org admin or platform admin.",
         false,
-        json_body("Workflow grant payload.", Example::Grant),
+        json_body("Organization changes.", Example::UpdateOrg),
         &[],
         200,
-        "workflow grant created",
-        Example::Grant,
+        "organization",
+        Example::None,
     ),
     endpoint(
         "delete",
-        "/workflows/{id}/grants/{grant_id}",
-        "Auth",
-        "Revoke a workflow grant",
-        "Revokes a sharing grant for one workflow. Owner or admin access is required.",
+        "/orgs/{id}",
+        "Organizations",
+        "Delete an organization",
+        "Deletes an organization and its memberships. Requires org owner or platform admin.",
         false,
         None,
         &[],
         200,
-        "workflow grant revoked",
+        "organization deleted",
         Example::TaskResponse,
     ),
     endpoint(
         "get",
-        "/teams",
-        "Auth",
-        "List teams",
-        "Admin endpoint that lists teams.",
+        "/orgs/{id}/members",
+        "Organizations",
+        "List organization members",
+        "Lists an organization's members and their roles.",
         false,
         None,
         &[],
         200,
-        "teams",
-        Example::Team,
+        "members",
+        Example::None,
     ),
     endpoint(
         "post",
-        "/teams",
-        "Auth",
-        "Create a team",
-        "Admin endpoint that creates a team.",
+        "/orgs/{id}/members",
+        "Organizations",
+        "Add an organization member",
+        "Adds a user to the organization, or changes the role of an existing member. Requires org admin.",
         false,
-        json_body("Team creation payload.", Example::Team),
+        json_body("Member payload.", Example::OrgMember),
         &[],
         200,
-        "team created",
-        Example::Team,
+        "member added",
+        Example::TaskResponse,
+    ),
+    endpoint(
+        "patch",
+        "/orgs/{id}/members/{user_id}",
+        "Organizations",
+        "Change a member's role",
+        "Changes one member's role. The last owner cannot be demoted.",
+        false,
+        json_body("Role payload.", Example::OrgRole),
+        &[],
+        200,
+        "member updated",
+        Example::TaskResponse,
     ),
     endpoint(
         "delete",
-        "/teams/{id}",
-        "Auth",
-        "Delete a team",
-        "Admin endpoint that deletes a team.",
+        "/orgs/{id}/members/{user_id}",
+        "Organizations",
+        "Remove an organization member",
+        "Removes one member from the organization. The last owner cannot be removed.",
         false,
         None,
         &[],
         200,
-        "team deleted",
+        "member removed",
         Example::TaskResponse,
     ),
     endpoint(
-        "patch",
-        "/teams/{id}",
-        "Auth",
-        "Update a team",
-        "Admin endpoint that renames a team.",
+        "get",
+        "/rate-card",
+        "Organizations",
+        "Get the rate card",
+        "Returns the platform rate card used to price dedicated node allocations.",
         false,
-        json_body("Team update payload.", Example::Team),
+        None,
         &[],
         200,
-        "team updated",
-        Example::Team,
+        "rate card",
+        Example::None,
     ),
     endpoint(
         "get",
-        "/teams/{id}/members",
-        "Auth",
-        "List team members",
-        "Admin endpoint that lists users assigned to a team.",
+        "/orgs/{id}/nodes",
+        "Organizations",
+        "List dedicated allocations",
+        "Lists an organization's dedicated node allocations with their projected monthly cost.",
         false,
         None,
         &[],
         200,
-        "team members",
-        Example::UserList,
+        "allocations",
+        Example::None,
     ),
     endpoint(
         "post",
-        "/teams/{id}/members",
-        "Auth",
-        "Add a team member",
-        "Admin endpoint that adds a user to a team.",
+        "/orgs/{id}/nodes/scale",
+        "Organizations",
+        "Scale a dedicated allocation",
+        "Scales an organization's dedicated allocation for a backend and kind, enforcing its quota.",
         false,
-        json_body("Team member payload.", Example::Team),
+        json_body("Scale request payload.", Example::ScaleOrgNodes),
         &[],
         200,
-        "member added",
-        Example::TaskResponse,
+        "allocation scaled",
+        Example::None,
     ),
     endpoint(
-        "delete",
-        "/teams/{id}/members/{user_id}",
-        "Auth",
-        "Remove a team member",
-        "Admin endpoint that removes a user from a team.",
+        "get",
+        "/orgs/{id}/quota",
+        "Organizations",
+        "Get an organization's quota",
+        "Returns the organization's quota, or the unset default.",
         false,
         None,
         &[],
         200,
-        "member removed",
-        Example::TaskResponse,
+        "quota",
+        Example::None,
+    ),
+    endpoint(
+        "put",
+        "/orgs/{id}/quota",
+        "Organizations",
+        "Set an organization's quota",
+        "Sets an organization's node, spend, and concurrency caps. Platform admin only.",
+        false,
+        json_body("Quota payload.", Example::OrgQuota),
+        &[],
+        200,
+        "quota",
+        Example::None,
+    ),
+    endpoint(
+        "get",
+        "/orgs/{id}/usage",
+        "Organizations",
+        "Get an organization's usage",
+        "Returns accrued usage and cost over a rolling 30-day window, plus currently active runs.",
+        false,
+        None,
+        &[],
+        200,
+        "usage",
+        Example::None,
     ),
 ];
 
@@ -2396,6 +3881,9 @@ const fn json_body(description: &'static str, example: Example) -> Option<Reques
 }
 
 fn enrich_openapi_document(document: &mut Value) {
+    if let Some(document) = document.as_object_mut() {
+        document.insert("servers".into(), json!([{ "url": API_VERSION_PREFIX }]));
+    }
     let Some(paths) = document.get_mut("paths").and_then(Value::as_object_mut) else {
         return;
     };
@@ -2544,7 +4032,7 @@ fn curl_sample(doc: &EndpointDoc) -> String {
         path = path.replace(&format!("{{{name}}}"), UUID_EXAMPLE);
     }
     let mut command = format!(
-        "curl -X {} http://127.0.0.1:8080{}",
+        "curl -X {} http://127.0.0.1:8080{API_VERSION_PREFIX}{}",
         doc.method.to_uppercase(),
         path
     );
@@ -2601,6 +4089,80 @@ fn example_value(example: Example) -> Option<Value> {
             json!({ "run": workflow_run_example(), "nodes": [node_run_example()] })
         }
         Example::WorkflowRunList => json!([workflow_run_example()]),
+        Example::RunHealthSummary => json!({
+            "summary": {
+                "period": "weekly",
+                "period_start": "2026-01-01T00:00:00Z",
+                "period_end": "2026-01-08T00:00:00Z",
+                "total_runs": 42,
+                "succeeded": 39,
+                "failed": 2,
+                "canceled": 1,
+                "in_progress": 0,
+                "success_rate": 0.95,
+                "slowest": [{ "workflow_id": UUID_EXAMPLE, "workflow_name": "nightly deploy", "runs": 7, "average_seconds": 840, "max_seconds": 1260 }],
+                "new_failures": [],
+            },
+            "subject": "Runinator weekly health: 42 runs, 95.1% success, 0 new failure(s)",
+            "text": "Runinator weekly health: 42 runs, 95.1% success, 0 new failure(s)\n...",
+        }),
+        Example::QueueWaitReport => json!({
+            "since": "2026-01-07T00:00:00Z",
+            "threshold_ms": 300000,
+            "overall": { "count": 120, "average_ms": 850, "p50_ms": 420, "p95_ms": 4100, "max_ms": 310000, "over_threshold": 1 },
+            "tasks": [{ "workflow_id": UUID_EXAMPLE, "workflow_name": "nightly deploy", "node_id": "deploy", "count": 7, "average_ms": 2300, "p50_ms": 900, "p95_ms": 310000, "max_ms": 310000, "over_threshold": 1 }],
+        }),
+        Example::UpcomingSchedule => json!({
+            "generated_at": "2026-01-08T01:55:00Z",
+            "until": "2026-01-08T02:55:00Z",
+            "running": 1,
+            "queued": 1,
+            "scheduled": 1,
+            "entries": [
+                { "kind": "running", "at": "2026-01-08T01:50:12Z", "workflow_id": UUID_EXAMPLE, "workflow_name": "hourly sync", "workflow_run_id": UUID_EXAMPLE, "node_id": "sync" },
+                { "kind": "queued", "at": "2026-01-08T01:54:40Z", "workflow_id": UUID_EXAMPLE, "workflow_name": "hourly sync", "workflow_run_id": UUID_EXAMPLE, "node_id": "notify" },
+                { "kind": "scheduled", "at": "2026-01-08T02:00:00Z", "workflow_id": UUID_EXAMPLE, "workflow_name": "nightly deploy", "trigger_id": UUID_EXAMPLE },
+            ],
+        }),
+        Example::SchedulerPreview => json!({
+            "generated_at": "2026-01-08T01:55:00Z",
+            "until": "2026-01-08T02:55:00Z",
+            "deferred": 1,
+            "firings": [
+                { "trigger_id": UUID_EXAMPLE, "workflow_id": UUID_EXAMPLE, "workflow_name": "hourly sync", "slot": "2026-01-08T02:00:00Z", "fires_at": "2026-01-08T02:00:00Z" },
+                { "trigger_id": UUID_EXAMPLE, "workflow_id": UUID_EXAMPLE, "workflow_name": "nightly deploy", "slot": "2026-01-08T02:00:00Z", "fires_at": "2026-01-08T03:00:00Z", "deferred": true },
+            ],
+        }),
+        Example::WorkflowOverview => json!([{
+            "workflow_id": UUID_EXAMPLE,
+            "name": "nightly deploy",
+            "enabled": true,
+            "runs": [{
+                "id": UUID_EXAMPLE,
+                "status": "succeeded",
+                "created_at": "2026-01-08T02:00:00Z",
+                "started_at": "2026-01-08T02:00:01Z",
+                "finished_at": "2026-01-08T02:03:10Z",
+                "executors": [{ "replica_id": UUID_EXAMPLE, "replica_type": "worker", "display_name": "worker-1", "status": "live" }],
+            }],
+        }]),
+        Example::WorkflowHashes => json!([{
+            "id": UUID_EXAMPLE,
+            "name": "nightly deploy",
+            "namespace": "ops",
+            "version": "1.2.0",
+            "hash": "sha256:9f2c4d0e6b1a7f3c8e5d2b9a4c7f1e0d3b6a9c2f5e8d1b4a7c0f3e6d9b2a5c8f",
+        }]),
+        Example::WorkflowSearch => json!({
+            "total": 1,
+            "offset": 0,
+            "limit": 50,
+            "workflows": [workflow_example()],
+        }),
+        Example::WorkflowBulk => json!({
+            "saved": true,
+            "results": [{ "index": 0, "name": "nightly deploy", "workflow": workflow_example() }],
+        }),
         Example::WorkflowRunStatus => {
             json!({ "status": "running", "active_node_id": "start", "state": {}, "message": "dispatching start node" })
         }
@@ -2608,7 +4170,7 @@ fn example_value(example: Example) -> Option<Value> {
         Example::WorkflowRunRename => json!({ "name": "nightly deploy" }),
         Example::RunList => json!([{ "id": UUID_EXAMPLE, "status": "running", "provider": "std" }]),
         Example::RunStatus => {
-            json!({ "status": "succeeded", "output_json": { "ok": true }, "message": "completed" })
+            json!({ "status": "succeeded", "output_json": { "ok": true }, "message": "completed", "worker_id": "worker-1" })
         }
         Example::RunChunk => json!([{ "cursor": 1, "stream": "stdout", "content": "hello\n" }]),
         Example::Artifact => {
@@ -2635,7 +4197,57 @@ fn example_value(example: Example) -> Option<Value> {
         }
         Example::Trigger => trigger_example(),
         Example::TriggerList => json!([trigger_example()]),
+        Example::TriggerView => trigger_view_example(),
+        Example::TriggerViewList => json!([trigger_view_example()]),
         Example::TriggerClaim => json!({ "scheduler_id": "scheduler-1", "limit": 25 }),
+        Example::Backfill => backfill_example(),
+        Example::BackfillList => json!([backfill_example()]),
+        Example::ScheduleReconcile => json!({
+            "dry_run": true,
+            "checked": 12,
+            "changes": [{
+                "trigger_type": "workflow",
+                "trigger_id": UUID_EXAMPLE,
+                "target_id": UUID_EXAMPLE,
+                "cron": "0 0 9 * * *",
+                "current_next_execution": "2026-06-18T08:00:00Z",
+                "proposed_next_execution": "2026-06-18T09:00:00Z",
+            }],
+        }),
+        Example::ScheduleShiftRequest => json!({
+            "namespace": "reports",
+            "mode": "offset",
+            "minutes": 30,
+            "dry_run": true,
+        }),
+        Example::ScheduleShift => json!({
+            "dry_run": true,
+            "shifted": 1,
+            "changes": [{
+                "trigger_id": UUID_EXAMPLE,
+                "workflow_id": UUID_EXAMPLE,
+                "workflow_name": "nightly-report",
+                "old_cron": "0 0 9 * * *",
+                "new_cron": "0 30 9 * * *",
+                "next_execution": "2026-06-18T09:30:00Z",
+            }],
+        }),
+        Example::CronPreviewRequest => json!({
+            "cron": "0 0 * * * *",
+            "count": 3,
+            "from": "2026-06-18T09:30:00Z",
+            "blackout_start": "2026-06-18T11:00:00Z",
+            "blackout_end": "2026-06-18T12:30:00Z",
+        }),
+        Example::CronPreview => json!({
+            "cron": "0 0 * * * *",
+            "valid": true,
+            "fire_times": [
+                { "at": "2026-06-18T10:00:00Z", "deferred": false },
+                { "at": "2026-06-18T12:30:00Z", "deferred": true },
+                { "at": "2026-06-18T13:00:00Z", "deferred": false },
+            ],
+        }),
         Example::SchedulerRunClaim => {
             json!({ "scheduler_id": "scheduler-1", "lease_until": "2026-06-18T13:00:00Z", "statuses": ["queued", "running"], "limit": 50 })
         }
@@ -2670,6 +4282,12 @@ fn example_value(example: Example) -> Option<Value> {
         Example::ArtifactList => {
             json!([{ "id": UUID_EXAMPLE, "workflow_run_id": UUID_EXAMPLE, "node_id": "report", "artifact_id": UUID_EXAMPLE, "name": "summary", "mime_type": "application/pdf", "size_bytes": 1024, "uri": "s3://bucket/key", "metadata": {}, "created_at": "2026-06-22T12:00:00Z" }])
         }
+        Example::RunOutputs => {
+            json!({ "outputs": { "row_count": 1200, "batch_id": "2026-06-22" } })
+        }
+        Example::RunOutputList => {
+            json!([{ "workflow_run_id": UUID_EXAMPLE, "name": "row_count", "value": 1200, "created_at": "2026-06-22T12:00:00Z" }])
+        }
         Example::CatalogItem => {
             json!({ "item_type": "provider_metadata", "uri": "runinator://providers/std", "value": provider_example() })
         }
@@ -2694,12 +4312,39 @@ fn example_value(example: Example) -> Option<Value> {
         Example::Provider => provider_example(),
         Example::ProviderList => json!([provider_example()]),
         Example::ProviderBundle => json!({ "providers": [provider_example()] }),
+        Example::ProviderStats => json!({
+            "generated_at": "2026-01-08T00:00:00Z",
+            "windows": [{
+                "hours": 24,
+                "since": "2026-01-07T00:00:00Z",
+                "providers": [{
+                    "provider": "sql", "tasks": 3, "runs": 140, "succeeded": 136, "failed": 4, "average_duration_ms": 8200,
+                    "actions": [{ "function": "dump_data", "tasks": 2, "runs": 96, "succeeded": 94, "failed": 2, "average_duration_ms": 11000 }],
+                }],
+            }],
+        }),
         Example::Replica => {
             json!({ "id": UUID_EXAMPLE, "replica_type": "worker", "status": "online", "address": "worker-1" })
         }
         Example::ReplicaList => {
             json!({ "replicas": [{ "id": UUID_EXAMPLE, "replica_type": "worker", "status": "online" }] })
         }
+        Example::WorkerList => json!([{
+            "replica_id": UUID_EXAMPLE,
+            "instance_id": "worker-1",
+            "status": "live",
+            "display_name": "worker-1",
+            "version": "0.1.0",
+            "labels": { "zone": "onprem" },
+            "plugins": ["runinator_plugin_console"],
+            "providers": ["slack", "sql"],
+            "max_concurrent_actions": 4,
+            "in_flight_actions": 1,
+            "running_node_runs": 1,
+            "first_seen_at": "2026-01-01T00:00:00Z",
+            "last_heartbeat_at": "2026-01-01T00:05:00Z",
+            "last_seen_at": "2026-01-01T00:05:00Z",
+        }]),
         Example::ReplicaProvider => {
             json!({ "replica_id": UUID_EXAMPLE, "provider": provider_example() })
         }
@@ -2727,9 +4372,190 @@ fn example_value(example: Example) -> Option<Value> {
         Example::WebhookSignal => {
             json!({ "name": "ticket.closed", "correlation_key": "PROJ-123", "payload": { "status": "done" } })
         }
+        Example::WebhookTriggerParameters => json!({ "environment": "dev", "ref": "main" }),
         Example::Supervisor => {
             json!({ "running": true, "services": [{ "name": "runinator-ws", "status": "running" }] })
         }
+        Example::WorkflowRetention => json!({
+            "workflow_id": UUID_EXAMPLE,
+            "retention_days": 14,
+            "updated_at": "2026-01-01T00:00:00Z",
+        }),
+        Example::WorkflowRetentionRequest => json!({ "retention_days": 14 }),
+        Example::WorkflowProtection => json!({
+            "workflow_id": UUID_EXAMPLE,
+            "protected_by": UUID_EXAMPLE,
+            "created_at": "2026-01-01T00:00:00Z",
+        }),
+        Example::PendingWorkflowChange => pending_workflow_change_example(),
+        Example::PendingWorkflowChangeList => json!([pending_workflow_change_example()]),
+        Example::JobRequest => json!({
+            "action_name": "slack",
+            "action_function": "send_message",
+            "configuration": job_configuration_example(),
+            "timeout_seconds": 30,
+            "max_retries": 2,
+            "retry_backoff_seconds": 30,
+            "priority": 10,
+        }),
+        Example::ClusterPause => json!({
+            "dispatch": true,
+            "execution": false,
+            "scheduling": false,
+            "reason": "broker failover",
+            "updated_by": UUID_EXAMPLE,
+            "updated_at": "2026-01-01T00:00:00Z",
+        }),
+        Example::ClusterPauseRequest => json!({
+            "dispatch": true,
+            "execution": false,
+            "scheduling": false,
+            "reason": "broker failover",
+        }),
+        Example::BlackoutCalendar => blackout_calendar_example(),
+        Example::BlackoutCalendarList => json!([blackout_calendar_example()]),
+        Example::BlackoutCalendarRequest => json!({
+            "description": "US market holidays",
+            "timezone": "America/New_York",
+            "dates": ["2026-11-26", "2026-12-25"],
+        }),
+        Example::StartupReport => json!({
+            "component": "Runinator Web Service",
+            "version": "0.1.0",
+            "started_at": "2026-01-01T09:00:00Z",
+            "pid": 4121,
+            "host": {
+                "host_name": "runinator-ws-0",
+                "os": "Debian GNU/Linux",
+                "cpu_arch": "x86_64",
+                "logical_cores": 4,
+                "mem_total_bytes": 8_589_934_592_u64,
+                "boot_time_unix": 1_767_250_000,
+            },
+            "config": {
+                "RUNINATOR_DATABASE": "postgres",
+                "RUNINATOR_DATABASE_URL": "postgres://[redacted]@db:5432/runinator",
+                "RUNINATOR_BROKER_AUTH_SECRET": "[redacted]",
+            },
+            "args": ["--port", "8080"],
+            "plugins": ["console", "git", "sql"],
+        }),
+        Example::Job => json!({
+            "id": UUID_EXAMPLE,
+            "action": {
+                "provider": "slack",
+                "function": "send_message",
+                "timeout_seconds": 30,
+                "configuration": job_configuration_example(),
+            },
+            "status": "succeeded",
+            "output_json": { "ok": true },
+            "message": null,
+            "requested_by": UUID_EXAMPLE,
+            "created_at": "2026-01-01T00:00:00Z",
+            "started_at": "2026-01-01T00:00:32Z",
+            "finished_at": "2026-01-01T00:00:33Z",
+            "attempt": 2,
+            "max_retries": 2,
+            "retry_backoff_seconds": 30,
+            "attempts": [
+                {
+                    "attempt": 1,
+                    "status": "failed",
+                    "output_json": null,
+                    "message": "slack answered 503",
+                    "started_at": "2026-01-01T00:00:01Z",
+                    "finished_at": "2026-01-01T00:00:02Z",
+                },
+                {
+                    "attempt": 2,
+                    "status": "succeeded",
+                    "output_json": { "ok": true },
+                    "message": null,
+                    "started_at": "2026-01-01T00:00:32Z",
+                    "finished_at": "2026-01-01T00:00:33Z",
+                },
+            ],
+        }),
+        Example::WorkflowTemplate => workflow_template_example(),
+        Example::WorkflowTemplateList => json!([workflow_template_example()]),
+        Example::InstantiateTemplate => json!({ "name": "nightly-export", "enabled": false }),
+        Example::WorkflowRevisions => json!([{
+            "id": UUID_EXAMPLE,
+            "workflow_id": UUID_EXAMPLE,
+            "revision": 2,
+            "workflow": workflow_example(),
+            "changed_by": UUID_EXAMPLE,
+            "created_at": "2026-01-01T00:00:00Z",
+        }]),
+        Example::Pipeline => json!({
+            "id": UUID_EXAMPLE,
+            "name": "release train",
+            "workflow_ids": [UUID_EXAMPLE],
+            "defaults": { "on_step_failure": "halt", "links_enabled_by_default": true },
+        }),
+        Example::PipelineTrigger => json!({
+            "pipeline_id": UUID_EXAMPLE,
+            "kind": "cron",
+            "enabled": true,
+            "configuration": { "schedule": "0 9 * * *" },
+        }),
+        Example::PipelineRunRequest => json!({ "parameters": { "environment": "dev" } }),
+        Example::OwnerRequest => json!({ "org_id": UUID_EXAMPLE }),
+        Example::CreateOrg => json!({ "name": "Acme", "slug": "acme" }),
+        Example::UpdateOrg => json!({ "name": "Acme Corp", "disabled": false }),
+        Example::OrgMember => json!({ "user_id": UUID_EXAMPLE, "role": "member" }),
+        Example::OrgRole => json!({ "role": "admin" }),
+        Example::SwitchOrg => json!({ "org_id": UUID_EXAMPLE }),
+        Example::OrgQuota => json!({
+            "max_nodes_per_kind": { "worker": 4 },
+            "max_monthly_cents": 50000,
+            "max_concurrent_runs": 20,
+        }),
+        Example::ScaleOrgNodes => {
+            json!({ "backend": "kubernetes", "kind": "worker", "desired": 2 })
+        }
+        Example::ScaleNodes => json!({
+            "backend": "kubernetes",
+            "kind": "worker",
+            "desired": 3,
+            "spec": {},
+        }),
+        Example::StopNode => json!({ "backend": "supervisor", "node_id": "worker-1" }),
+    })
+}
+
+fn pending_workflow_change_example() -> Value {
+    json!({
+        "id": UUID_EXAMPLE,
+        "workflow_id": UUID_EXAMPLE,
+        "kind": "disable",
+        "payload": null,
+        "requested_by": UUID_EXAMPLE,
+        "status": "pending",
+        "created_at": "2026-01-01T00:00:00Z",
+        "reviewed_at": null,
+    })
+}
+
+fn job_configuration_example() -> Value {
+    json!({
+        "token": "secret://slack/bot-token",
+        "channel": "#ops",
+        "text": "cache flushed",
+    })
+}
+
+fn workflow_template_example() -> Value {
+    json!({
+        "id": UUID_EXAMPLE,
+        "name": "nightly export",
+        "description": "Export a table to the shared bucket every night.",
+        "cron": "0 0 2 * * *",
+        "labels": ["data", "nightly"],
+        "workflow": workflow_example(),
+        "created_at": "2026-01-01T00:00:00Z",
+        "updated_at": "2026-01-01T00:00:00Z",
     })
 }
 
@@ -2775,6 +4601,44 @@ fn trigger_example() -> Value {
     })
 }
 
+fn trigger_view_example() -> Value {
+    let mut trigger = trigger_example();
+    trigger["effective_next_run"] = json!("2026-04-02T09:04:10Z");
+    trigger["schedule_adjustments"] = json!([{
+        "kind": "jitter",
+        "from": "2026-04-02T09:00:00Z",
+        "to": "2026-04-02T09:04:10Z",
+        "reason": "jitter_seconds places this trigger 250s after its slot",
+    }]);
+    trigger
+}
+
+fn backfill_example() -> Value {
+    json!({
+        "id": "018f7d2a-7c1e-7b3a-9c11-5e2f4a6b8c90",
+        "workflow_id": "3a8c6d2e-51f4-4b8e-9f6a-2c1d7e9b0a13",
+        "trigger_id": "6b2f9e41-0c7d-4a58-b3e2-8d1f5c7a9e24",
+        "range_start": "2026-03-01T00:00:00Z",
+        "range_end": "2026-03-03T00:00:00Z",
+        "concurrency": 2,
+        "slots": ["2026-03-01T00:00:00Z", "2026-03-02T00:00:00Z", "2026-03-03T00:00:00Z"],
+        "fired": 2,
+        "status": "running",
+        "created_at": "2026-03-31T09:00:00Z",
+        "updated_at": "2026-03-31T09:00:01Z",
+    })
+}
+
+fn blackout_calendar_example() -> Value {
+    json!({
+        "name": "us-market-holidays",
+        "description": "US market holidays",
+        "timezone": "America/New_York",
+        "dates": ["2026-11-26", "2026-12-25"],
+        "updated_at": "2026-01-01T00:00:00Z",
+    })
+}
+
 fn provider_example() -> Value {
     json!({
         "name": "std",
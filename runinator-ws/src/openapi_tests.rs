@@ -10,6 +10,12 @@ fn document_builds_and_serializes() {
         !json["info"]["version"].as_str().unwrap_or("").is_empty(),
         "version is populated from the crate version"
     );
+    // paths are relative to the versioned prefix the router serves them under.
+    assert_eq!(json["servers"][0]["url"], "/v1");
+    assert_eq!(
+        json["components"]["schemas"]["ApiErrorCode"]["enum"][0],
+        "not_found"
+    );
 }
 
 #[test]
@@ -149,6 +155,43 @@ fn every_cataloged_route_has_operation_text_and_curl_sample() {
     }
 }
 
+// generated clients group operations by tag, so every tag an operation uses must be declared, and
+// the org/pipeline/retention routes must be in the document rather than only in the router.
+#[test]
+fn operation_tags_are_declared_and_tenant_routes_are_documented() {
+    let json = openapi_document();
+    let declared: Vec<&str> = json["tags"]
+        .as_array()
+        .expect("tags array")
+        .iter()
+        .filter_map(|tag| tag["name"].as_str())
+        .collect();
+    for (path, item) in json["paths"].as_object().expect("paths object") {
+        for (method, operation) in item.as_object().expect("path item object") {
+            for tag in operation["tags"].as_array().into_iter().flatten() {
+                let tag = tag.as_str().unwrap_or_default();
+                assert!(
+                    declared.contains(&tag),
+                    "{method} {path} uses undeclared tag {tag}"
+                );
+            }
+        }
+    }
+    for (method, path) in [
+        ("get", "/workflows/{id}/retention"),
+        ("patch", "/workflows/{id}/retention"),
+        ("delete", "/workflows/{id}/retention"),
+        ("post", "/pipelines/{id}/runs"),
+        ("post", "/orgs/{id}/members"),
+        ("put", "/orgs/{id}/quota"),
+    ] {
+        assert!(
+            json["paths"][path][method].is_object(),
+            "missing {method} {path}"
+        );
+    }
+}
+
 #[test]
 fn scalar_docs_point_at_generated_openapi_json() {
     assert!(super::SCALAR_HTML.contains("data-url=\"/openapi.json\""));
@@ -1,8 +1,12 @@
-//! per-principal / per-ip token-bucket rate limiting for the http api.
+//! per-principal / per-ip / global token-bucket rate limiting for the http api.
 //!
-//! the limiter runs after the auth middleware so it can key by the resolved principal when present
-//! and fall back to the connection ip for anonymous/public requests. buckets live in process memory;
-//! each replica limits independently, which is the intended behavior for a horizontally scaled api.
+//! the limiter runs after the auth middleware so it can key by the resolved principal when present.
+//! an authenticated request spends from its principal's bucket and an anonymous one from its client
+//! ip's, so users sharing an address behind a reverse proxy do not throttle each other. every request
+//! also spends from one global bucket that caps the replica's aggregate rate, so many clients at
+//! once cannot drain the database pool. a request is admitted only when every bucket it draws on has
+//! a token, and only then are they spent. buckets live in process memory; each replica limits
+//! independently, which is the intended behavior for a horizontally scaled api.
 
 use std::{
     collections::HashMap,
@@ -19,18 +23,29 @@ use axum::{
     response::{IntoResponse, Response},
 };
 use runinator_models::auth::AuthContext;
+use uuid::Uuid;
 
 // prune the bucket map when it grows past this many keys to bound memory under ip churn.
 const PRUNE_THRESHOLD: usize = 10_000;
 
 /// runtime configuration for the token-bucket limiter.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct RateLimitConfig {
     pub enabled: bool,
-    /// sustained requests allowed per second (the bucket refill rate).
+    /// sustained requests allowed per second per principal (the bucket refill rate).
     pub requests_per_second: f64,
-    /// maximum burst capacity (the bucket size).
+    /// maximum burst capacity per principal (the bucket size).
     pub burst: f64,
+    /// sustained requests per second per anonymous client ip.
+    pub ip_requests_per_second: f64,
+    pub ip_burst: f64,
+    /// header a trusted reverse proxy sets to the client's address (e.g. `x-forwarded-for`); its
+    /// rightmost entry, the one the proxy appended, keys anonymous requests. unset keys them by the
+    /// connection's peer address.
+    pub client_ip_header: Option<String>,
+    /// sustained requests per second across all clients; `0` turns the global bucket off.
+    pub global_requests_per_second: f64,
+    pub global_burst: f64,
 }
 
 impl Default for RateLimitConfig {
@@ -39,6 +54,11 @@ impl Default for RateLimitConfig {
             enabled: false,
             requests_per_second: 50.0,
             burst: 100.0,
+            ip_requests_per_second: 100.0,
+            ip_burst: 200.0,
+            client_ip_header: None,
+            global_requests_per_second: 500.0,
+            global_burst: 1000.0,
         }
     }
 }
@@ -46,6 +66,8 @@ impl Default for RateLimitConfig {
 struct Bucket {
     tokens: f64,
     last: Instant,
+    rate: f64,
+    burst: f64,
 }
 
 /// shared, in-memory token-bucket limiter keyed by an opaque principal/ip string.
@@ -62,11 +84,20 @@ impl RateLimiter {
         }
     }
 
-    /// try to spend one token for `key`. returns `Ok(())` when allowed, or `Err(retry_after_secs)`
-    /// with the wait before a token is available.
+    /// try to spend one token for `key` at the principal rate. returns `Ok(())` when allowed, or
+    /// `Err(retry_after_secs)` with the wait before a token is available.
     fn check(&self, key: &str) -> Result<(), f64> {
-        let rate = self.config.requests_per_second.max(f64::MIN_POSITIVE);
-        let burst = self.config.burst.max(1.0);
+        self.check_all(&[(
+            key.to_string(),
+            self.config.requests_per_second,
+            self.config.burst,
+        )])
+    }
+
+    /// try to spend one token from each of `limits` (key, refill rate per second, burst) at once.
+    /// nothing is spent unless every bucket has a token; otherwise returns the longest wait before
+    /// they all do.
+    fn check_all(&self, limits: &[(String, f64, f64)]) -> Result<(), f64> {
         let now = Instant::now();
         let mut buckets = match self.buckets.lock() {
             Ok(guard) => guard,
@@ -76,22 +107,36 @@ impl RateLimiter {
         if buckets.len() > PRUNE_THRESHOLD {
             // drop full, idle buckets; they carry no state worth keeping.
             buckets.retain(|_, bucket| {
-                let refilled = bucket.tokens + now.duration_since(bucket.last).as_secs_f64() * rate;
-                refilled < burst
+                bucket.tokens + now.duration_since(bucket.last).as_secs_f64() * bucket.rate
+                    < bucket.burst
             });
         }
-        let bucket = buckets.entry(key.to_string()).or_insert(Bucket {
-            tokens: burst,
-            last: now,
-        });
-        let elapsed = now.duration_since(bucket.last).as_secs_f64();
-        bucket.tokens = (bucket.tokens + elapsed * rate).min(burst);
-        bucket.last = now;
-        if bucket.tokens >= 1.0 {
-            bucket.tokens -= 1.0;
-            return Ok(());
+        let mut wait: f64 = 0.0;
+        for (key, rate, burst) in limits {
+            let rate = rate.max(f64::MIN_POSITIVE);
+            let burst = burst.max(1.0);
+            let bucket = buckets.entry(key.clone()).or_insert(Bucket {
+                tokens: burst,
+                last: now,
+                rate,
+                burst,
+            });
+            let elapsed = now.duration_since(bucket.last).as_secs_f64();
+            bucket.tokens = (bucket.tokens + elapsed * rate).min(burst);
+            bucket.last = now;
+            if bucket.tokens < 1.0 {
+                wait = wait.max((1.0 - bucket.tokens) / rate);
+            }
+        }
+        if wait > 0.0 {
+            return Err(wait);
         }
-        Err((1.0 - bucket.tokens) / rate)
+        for (key, _, _) in limits {
+            if let Some(bucket) = buckets.get_mut(key) {
+                bucket.tokens -= 1.0;
+            }
+        }
+        Ok(())
     }
 }
 
@@ -108,6 +153,7 @@ fn login_throttle() -> &'static RateLimiter {
             requests_per_second: 0.2,
             // absorb a short burst of honest retries before throttling kicks in.
             burst: 10.0,
+            ..RateLimitConfig::default()
         })
     })
 }
@@ -117,26 +163,84 @@ pub fn check_login_attempt(ip: IpAddr) -> Result<(), f64> {
     login_throttle().check(&format!("login:{ip}"))
 }
 
+/// per-token throttle for `POST /triggers/{token}`, always on, so a misbehaving upstream system
+/// cannot start runs faster than about one a second no matter how the global limiter is set.
+fn webhook_trigger_throttle() -> &'static RateLimiter {
+    static THROTTLE: OnceLock<RateLimiter> = OnceLock::new();
+    THROTTLE.get_or_init(|| {
+        RateLimiter::new(RateLimitConfig {
+            enabled: true,
+            requests_per_second: 1.0,
+            burst: 10.0,
+            ..RateLimitConfig::default()
+        })
+    })
+}
+
+/// spend one run request for webhook trigger `trigger_id`. returns `Err(retry_after_secs)` when the
+/// bucket is empty.
+pub fn check_webhook_trigger(trigger_id: Uuid) -> Result<(), f64> {
+    webhook_trigger_throttle().check(&format!("webhook:{trigger_id}"))
+}
+
 /// paths exempt from rate limiting so health/metrics scrapers are never throttled.
 fn is_exempt(path: &str) -> bool {
-    matches!(path, "/health" | "/ready" | "/metrics")
+    matches!(
+        path,
+        "/health" | "/healthz" | "/ready" | "/readyz" | "/metrics"
+    )
 }
 
-/// derive the rate-limit key: the authenticated principal when present, else the connection ip.
-fn rate_limit_key(req: &Request<Body>) -> String {
-    if let Some(context) = req.extensions().get::<AuthContext>() {
-        if let Some(id) = context.principal_id {
-            return format!("principal:{id}");
-        }
+/// the authenticated principal's bucket key, if the request carries one.
+fn principal_key(req: &Request<Body>) -> Option<String> {
+    let id = req.extensions().get::<AuthContext>()?.principal_id?;
+    Some(format!("principal:{id}"))
+}
+
+/// the client ip's bucket key: the rightmost address in the configured proxy header, which the
+/// trusted proxy appended (entries to its left come from the client and could be rotated to dodge
+/// the bucket), or else the connection's peer address.
+fn ip_key(config: &RateLimitConfig, req: &Request<Body>) -> String {
+    let forwarded = config
+        .client_ip_header
+        .as_deref()
+        .and_then(|header| req.headers().get(header))
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.rsplit(',').next())
+        .and_then(|entry| entry.trim().parse::<IpAddr>().ok());
+    if let Some(ip) = forwarded {
+        return format!("ip:{ip}");
     }
-    if let Some(ConnectInfo(addr)) = req.extensions().get::<ConnectInfo<SocketAddr>>() {
-        return format!("ip:{}", addr.ip());
+    match req.extensions().get::<ConnectInfo<SocketAddr>>() {
+        Some(ConnectInfo(addr)) => format!("ip:{}", addr.ip()),
+        None => "anonymous".to_string(),
     }
-    "anonymous".to_string()
 }
 
-/// gate every non-exempt request through the token bucket; reply `429` with `Retry-After` when the
-/// bucket is empty.
+/// spend one token from each bucket `req` draws on: its principal when authenticated or else its
+/// client ip, then the global bucket. nothing is spent when any of them is empty.
+fn check_request(limiter: &RateLimiter, req: &Request<Body>) -> Result<(), f64> {
+    let config = &limiter.config;
+    let mut limits = vec![match principal_key(req) {
+        Some(key) => (key, config.requests_per_second, config.burst),
+        None => (
+            ip_key(config, req),
+            config.ip_requests_per_second,
+            config.ip_burst,
+        ),
+    }];
+    if config.global_requests_per_second > 0.0 {
+        limits.push((
+            "global".to_string(),
+            config.global_requests_per_second,
+            config.global_burst,
+        ));
+    }
+    limiter.check_all(&limits)
+}
+
+/// gate every non-exempt request through the token buckets; reply `429` with `Retry-After` when one
+/// is empty.
 pub async fn rate_limit_middleware(
     State(limiter): State<Arc<RateLimiter>>,
     req: Request<Body>,
@@ -145,8 +249,7 @@ pub async fn rate_limit_middleware(
     if !limiter.config.enabled || is_exempt(req.uri().path()) {
         return next.run(req).await;
     }
-    let key = rate_limit_key(&req);
-    match limiter.check(&key) {
+    match check_request(&limiter, &req) {
         Ok(()) => next.run(req).await,
         Err(retry_after) => {
             let secs = retry_after.ceil().max(1.0) as u64;
@@ -170,6 +273,7 @@ mod tests {
             enabled: true,
             requests_per_second: 1.0,
             burst: 3.0,
+            ..RateLimitConfig::default()
         });
         // three immediate requests fit the burst.
         assert!(limiter.check("k").is_ok());
@@ -186,10 +290,127 @@ mod tests {
             enabled: true,
             requests_per_second: 1.0,
             burst: 1.0,
+            ..RateLimitConfig::default()
         });
         assert!(limiter.check("a").is_ok());
         // a separate key has its own bucket.
         assert!(limiter.check("b").is_ok());
         assert!(limiter.check("a").is_err());
     }
+
+    fn request_from(ip: [u8; 4], principal: Option<uuid::Uuid>) -> Request<Body> {
+        let mut req = Request::new(Body::empty());
+        req.extensions_mut()
+            .insert(ConnectInfo(SocketAddr::from((ip, 40_000))));
+        if let Some(principal_id) = principal {
+            let mut context = AuthContext::disabled_admin();
+            context.principal_id = Some(principal_id);
+            req.extensions_mut().insert(context);
+        }
+        req
+    }
+
+    #[test]
+    fn principals_behind_one_address_have_their_own_buckets() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            enabled: true,
+            requests_per_second: 1.0,
+            burst: 1.0,
+            ip_requests_per_second: 1.0,
+            ip_burst: 2.0,
+            ..RateLimitConfig::default()
+        });
+        // a noisy user behind a proxy drains only their own bucket.
+        let noisy = uuid::Uuid::new_v4();
+        assert!(check_request(&limiter, &request_from([10, 0, 0, 1], Some(noisy))).is_ok());
+        assert!(check_request(&limiter, &request_from([10, 0, 0, 1], Some(noisy))).is_err());
+        let quiet = request_from([10, 0, 0, 1], Some(uuid::Uuid::new_v4()));
+        assert!(check_request(&limiter, &quiet).is_ok());
+
+        // anonymous requests share their address's bucket.
+        for _ in 0..2 {
+            assert!(check_request(&limiter, &request_from([10, 0, 0, 1], None)).is_ok());
+        }
+        assert!(check_request(&limiter, &request_from([10, 0, 0, 1], None)).is_err());
+        assert!(check_request(&limiter, &request_from([10, 0, 0, 2], None)).is_ok());
+    }
+
+    #[test]
+    fn client_ip_comes_from_the_trusted_proxy_header() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            enabled: true,
+            ip_requests_per_second: 1.0,
+            ip_burst: 1.0,
+            client_ip_header: Some("x-forwarded-for".into()),
+            ..RateLimitConfig::default()
+        });
+        let forwarded_for = |value: &str| {
+            let mut req = request_from([10, 0, 0, 1], None);
+            req.headers_mut()
+                .insert("x-forwarded-for", value.parse().unwrap());
+            req
+        };
+        // clients behind the same proxy are told apart by the address it appended.
+        assert!(check_request(&limiter, &forwarded_for("203.0.113.7")).is_ok());
+        assert!(check_request(&limiter, &forwarded_for("203.0.113.8")).is_ok());
+        // a spoofed entry to the left does not buy a fresh bucket.
+        assert!(check_request(&limiter, &forwarded_for("198.51.100.1, 203.0.113.7")).is_err());
+        // without the header the peer address is used.
+        assert!(check_request(&limiter, &request_from([10, 0, 0, 1], None)).is_ok());
+    }
+
+    #[test]
+    fn a_rejected_request_spends_no_tokens() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            enabled: true,
+            ip_requests_per_second: 1.0,
+            ip_burst: 2.0,
+            global_requests_per_second: 1.0,
+            global_burst: 1.0,
+            ..RateLimitConfig::default()
+        });
+        assert!(check_request(&limiter, &request_from([10, 0, 0, 1], None)).is_ok());
+        // the global bucket turns these away, leaving the client's own bucket untouched.
+        for _ in 0..3 {
+            assert!(check_request(&limiter, &request_from([10, 0, 0, 1], None)).is_err());
+        }
+        let ip_bucket = limiter.check_all(&[("ip:10.0.0.1".into(), 1.0, 2.0)]);
+        assert!(ip_bucket.is_ok());
+    }
+
+    #[test]
+    fn global_bucket_caps_all_clients() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            enabled: true,
+            global_requests_per_second: 1.0,
+            global_burst: 3.0,
+            ..RateLimitConfig::default()
+        });
+        for last_octet in 1..=3 {
+            let req = request_from([10, 0, 0, last_octet], None);
+            assert!(check_request(&limiter, &req).is_ok());
+        }
+        let retry = check_request(&limiter, &request_from([10, 0, 0, 4], None)).unwrap_err();
+        assert!(retry > 0.0);
+
+        // a zero global rate leaves only the per-client buckets.
+        let limiter = RateLimiter::new(RateLimitConfig {
+            enabled: true,
+            global_requests_per_second: 0.0,
+            global_burst: 1.0,
+            ..RateLimitConfig::default()
+        });
+        for last_octet in 1..=5 {
+            let req = request_from([10, 0, 0, last_octet], None);
+            assert!(check_request(&limiter, &req).is_ok());
+        }
+    }
+
+    #[test]
+    fn probe_aliases_are_exempt() {
+        for path in ["/health", "/healthz", "/ready", "/readyz", "/metrics"] {
+            assert!(is_exempt(path), "{path} should bypass the limiter");
+        }
+        assert!(!is_exempt("/workflows"));
+    }
 }
@@ -0,0 +1,41 @@
+//! per-request correlation ids. the trace middleware adopts an inbound `x-request-id` from a fronting
+//! proxy (or mints one), echoes it on every response, and scopes it to the request so json error
+//! bodies carry it too. quoting the id from an error finds that request's log lines.
+
+use std::future::Future;
+
+use axum::http::HeaderMap;
+
+pub(crate) const REQUEST_ID_HEADER: &str = "x-request-id";
+
+// longest inbound id adopted as is; anything longer is replaced rather than echoed into every log line.
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+/// the inbound request id when it is a short printable token, otherwise a freshly minted one.
+pub(crate) fn resolve(headers: &HeaderMap) -> String {
+    headers
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .filter(|value| {
+            !value.is_empty()
+                && value.len() <= MAX_REQUEST_ID_LEN
+                && value.chars().all(|ch| ch.is_ascii_graphic())
+        })
+        .map(str::to_string)
+        .unwrap_or_else(|| uuid::Uuid::now_v7().to_string())
+}
+
+/// run `future` as the request identified by `request_id`.
+pub(crate) async fn scope<F: Future>(request_id: String, future: F) -> F::Output {
+    REQUEST_ID.scope(request_id, future).await
+}
+
+/// the id of the request being handled; `None` outside a request, e.g. in a spawned task.
+pub(crate) fn current() -> Option<String> {
+    REQUEST_ID.try_with(Clone::clone).ok()
+}
@@ -1,11 +1,53 @@
 use axum::{Json, http::StatusCode};
+use runinator_models::errors::{ErrorCategory, extract_error_code};
 use runinator_models::web::TaskResponse;
 use runinator_workflows::WorkflowValidationError;
 
-use crate::models::{ApiError, ApiResponse};
+use crate::models::{ApiError, ApiErrorCode, ApiResponse};
 
+/// a 500 for a broken server-side invariant rather than a failed request.
 pub(crate) fn api_error(message: impl Into<String>) -> (StatusCode, Json<ApiResponse>) {
-    api_error_status(StatusCode::INTERNAL_SERVER_ERROR, ApiError::new(message))
+    api_error_status(
+        StatusCode::INTERNAL_SERVER_ERROR,
+        ApiError::new(ApiErrorCode::Internal, message),
+    )
+}
+
+/// map an error from the repository layer onto the status and code that describe it, by its
+/// [`ErrorCategory`]: a missing record is a 404, a duplicate key or a run that already finished is a
+/// 409, rejected input is a 400, an unreachable broker is a 503, a failed provider is a 502, and
+/// anything else is a 500 `db_error`. the body carries the error's numbered code when it has one.
+pub(crate) fn repository_error(
+    err: &(dyn std::error::Error + 'static),
+) -> (StatusCode, Json<ApiResponse>) {
+    if err.is::<WorkflowValidationError>() {
+        return validation_error(err);
+    }
+    let category = match ErrorCategory::of(err) {
+        ErrorCategory::Internal if runinator_database::errors::is_unique_violation(err) => {
+            ErrorCategory::Conflict
+        }
+        category => category,
+    };
+    let (status, code) = category_status(category);
+    let mut error = ApiError::new(code, err.to_string());
+    error.error_code = extract_error_code(err);
+    api_error_status(status, error)
+}
+
+/// the status and code an error of `category` is reported with. an unclassified repository error
+/// almost always comes from the database driver, so it stays a `db_error`.
+fn category_status(category: ErrorCategory) -> (StatusCode, ApiErrorCode) {
+    match category {
+        ErrorCategory::Validation => (StatusCode::BAD_REQUEST, ApiErrorCode::ValidationFailed),
+        ErrorCategory::NotFound => (StatusCode::NOT_FOUND, ApiErrorCode::NotFound),
+        ErrorCategory::Conflict => (StatusCode::CONFLICT, ApiErrorCode::Conflict),
+        ErrorCategory::Transport => (StatusCode::SERVICE_UNAVAILABLE, ApiErrorCode::Unavailable),
+        ErrorCategory::Provider => (StatusCode::BAD_GATEWAY, ApiErrorCode::ProviderFailed),
+        ErrorCategory::Database | ErrorCategory::Internal => {
+            (StatusCode::INTERNAL_SERVER_ERROR, ApiErrorCode::DbError)
+        }
+    }
 }
 
 pub(crate) fn validation_error(
@@ -17,14 +59,17 @@ pub(crate) fn validation_error(
         return api_error_status(
             StatusCode::BAD_REQUEST,
             ApiError {
+                code: ApiErrorCode::ValidationFailed,
                 message: diagnostic.message.clone(),
+                error_code: None,
                 path: Some(diagnostic.path.clone()),
                 expected: Some(diagnostic.expected.clone()),
                 actual: Some(diagnostic.actual.clone()),
+                request_id: crate::request_id::current(),
             },
         );
     }
-    api_error_status(StatusCode::BAD_REQUEST, ApiError::new(err.to_string()))
+    bad_request(err.to_string())
 }
 
 fn api_error_status(status: StatusCode, error: ApiError) -> (StatusCode, Json<ApiResponse>) {
@@ -32,16 +77,23 @@ fn api_error_status(status: StatusCode, error: ApiError) -> (StatusCode, Json<Ap
 }
 
 pub(crate) fn not_found(message: impl Into<String>) -> (StatusCode, Json<ApiResponse>) {
-    (
+    api_error_status(
         StatusCode::NOT_FOUND,
-        Json(ApiResponse::ApiError(ApiError::new(message))),
+        ApiError::new(ApiErrorCode::NotFound, message),
     )
 }
 
 pub(crate) fn bad_request(message: impl Into<String>) -> (StatusCode, Json<ApiResponse>) {
-    (
+    api_error_status(
         StatusCode::BAD_REQUEST,
-        Json(ApiResponse::ApiError(ApiError::new(message))),
+        ApiError::new(ApiErrorCode::ValidationFailed, message),
+    )
+}
+
+pub(crate) fn conflict(message: impl Into<String>) -> (StatusCode, Json<ApiResponse>) {
+    api_error_status(
+        StatusCode::CONFLICT,
+        ApiError::new(ApiErrorCode::Conflict, message),
     )
 }
 
@@ -5,27 +5,31 @@ use axum::{
     Extension, Router,
     extract::DefaultBodyLimit,
     middleware::from_fn_with_state,
-    routing::{delete, get, patch, post},
+    routing::{delete, get, patch, post, put},
 };
 use runinator_broker::Broker;
 use runinator_database::interfaces::DatabaseImpl;
 use runinator_models::api_routes::{
-    API_ARTIFACTS, API_PACKS_IMPORT, API_PIPELINES, API_PROVIDERS, API_REPLICAS, API_RUNS,
-    API_SCHEDULER_ACTION_DISPATCHES, API_SCHEDULER_ACTION_DISPATCHES_CLAIM,
-    API_SCHEDULER_ACTION_DISPATCHES_PENDING, API_SCHEDULER_READY_NODES_CLAIM,
-    API_SCHEDULER_WORKFLOW_RUNS_CLAIM, API_SCHEDULER_WORKFLOW_TRIGGER_FIRINGS_CLAIM,
+    API_ADMIN_RECONCILE_SCHEDULES, API_ADMIN_SHIFT_SCHEDULES, API_ARTIFACTS,
+    API_BLACKOUT_CALENDARS, API_CLUSTER_PAUSE, API_CRON_PREVIEW, API_DEBUG_INFO, API_GRAPHQL,
+    API_JOBS, API_PACKS_IMPORT, API_PIPELINES, API_PROVIDERS, API_QUEUE_WAIT, API_REPLICAS,
+    API_RUNS, API_SCHEDULE_UPCOMING, API_SCHEDULER_ACTION_DISPATCHES,
+    API_SCHEDULER_ACTION_DISPATCHES_CLAIM, API_SCHEDULER_ACTION_DISPATCHES_PENDING,
+    API_SCHEDULER_PREVIEW, API_SCHEDULER_READY_NODES_CLAIM, API_SCHEDULER_WORKFLOW_RUNS_CLAIM,
+    API_SCHEDULER_WORKFLOW_TRIGGER_FIRINGS_CLAIM, API_STATS_PROVIDERS, API_VERSION_PREFIX,
     API_WDL_ANALYZE, API_WDL_COMPILE, API_WDL_COMPLETE, API_WDL_DECOMPILE, API_WDL_EVALUATE,
-    API_WDL_FORMAT, API_WDL_HOVER, API_WDL_IMPORT, API_WORKFLOW_RUNS, API_WORKFLOW_TRIGGERS_DUE,
-    API_WORKFLOWS, API_WORKFLOWS_EXPORT, API_WORKFLOWS_IMPORT, API_WORKFLOWS_SIMULATE,
-    API_WORKFLOWS_VALIDATE,
+    API_WDL_FORMAT, API_WDL_HOVER, API_WDL_IMPORT, API_WORKERS, API_WORKFLOW_RUN_HEALTH,
+    API_WORKFLOW_RUNS, API_WORKFLOW_TEMPLATES, API_WORKFLOW_TRIGGERS_DUE, API_WORKFLOWS,
+    API_WORKFLOWS_BULK, API_WORKFLOWS_EXPORT, API_WORKFLOWS_HASHES, API_WORKFLOWS_IMPORT,
+    API_WORKFLOWS_OVERVIEW, API_WORKFLOWS_SEARCH, API_WORKFLOWS_SIMULATE, API_WORKFLOWS_VALIDATE,
 };
 use runinator_provisioner::ProvisionerRegistry;
 use tower_http::catch_panic::CatchPanicLayer;
-use tower_http::cors::{Any, CorsLayer};
 
 use crate::models::{ApiError, ApiResponse};
 
 use crate::auth::{AuthConfig, AuthState, auth_middleware};
+use crate::cors::CorsConfig;
 use crate::events::EventSender;
 use crate::handlers::{
     action_dispatches::{
@@ -52,8 +56,10 @@ use crate::handlers::{
     billing::{
         get_org_nodes, get_org_quota, get_org_usage, get_rate_card, put_org_quota, scale_org_nodes,
     },
+    blackouts::{delete_blackout_calendar, get_blackout_calendars, put_blackout_calendar},
     catalog::{get_catalog_items, upsert_catalog_item},
     catalog_metadata::{get_enum_catalogs, get_node_kinds, get_trigger_kinds},
+    cluster::{delete_cluster_pause, get_cluster_pause, put_cluster_pause},
     credentials::{
         delete_credential, get_credential, import_secret_bundle, put_credential, reencrypt_settings,
     },
@@ -63,18 +69,20 @@ use crate::handlers::{
         update_workflow_run_debug,
     },
     health::{health, metrics, ready},
+    jobs::{get_job, submit_job},
     node_runs::{
         add_workflow_node_run_artifact, append_workflow_node_run_chunk,
         claim_workflow_node_run_executor, create_workflow_node_run,
         get_workflow_node_run_artifacts, get_workflow_node_run_chunks,
-        get_workflow_node_transitions, get_workflow_run_artifacts, get_workflow_run_transitions,
+        get_workflow_node_transitions, get_workflow_run_artifacts, get_workflow_run_outputs,
+        get_workflow_run_transitions, record_workflow_run_outputs,
         release_workflow_node_run_executor, resolve_workflow_input, update_workflow_node_run,
     },
     notifications::{
         create_notification, delete_notification, list_notifications, mark_all_notifications_read,
         mark_notification_read,
     },
-    observability::{get_audit_log, get_dead_letters},
+    observability::{get_audit_log, get_dead_letters, get_debug_info},
     orgs::{
         add_org_member, create_org, delete_org, get_org, list_my_orgs, list_org_members, list_orgs,
         remove_org_member, switch_org, update_org, update_org_member,
@@ -86,35 +94,48 @@ use crate::handlers::{
         get_pipeline_runs, get_pipeline_triggers, get_pipelines, set_pipeline_owner,
         update_pipeline, update_pipeline_trigger, upsert_pipeline_trigger,
     },
-    providers::{get_providers, import_provider_bundle, upsert_provider},
+    protection::{
+        approve_pending_workflow_change, get_pending_workflow_changes, get_workflow_protection,
+        protect_workflow, reject_pending_workflow_change, unprotect_workflow,
+    },
+    providers::{get_provider_stats, get_providers, import_provider_bundle, upsert_provider},
     provisioning::{get_node_backends, get_nodes, scale_nodes, stop_node},
     replicas::{
-        get_replica_providers, get_replica_samples, get_replicas, heartbeat_replica,
+        get_replica_providers, get_replica_samples, get_replicas, get_workers, heartbeat_replica,
         mark_replica_offline, register_replica, upsert_replica_provider,
     },
     runs::{
         append_run_chunk, cancel_workflow_run, claim_ready_nodes,
         claim_workflow_runs_for_scheduler, create_workflow_run, create_workflow_trigger_run,
-        deliver_signal, get_run_chunks, get_runs, get_workflow_run, get_workflow_runs,
+        deliver_signal, get_queue_wait, get_run_chunks, get_runs, get_scheduler_preview,
+        get_upcoming_schedule, get_workflow_run, get_workflow_run_health, get_workflow_runs,
         pause_workflow_run, process_ready_node, release_workflow_run_claim, rename_workflow_run,
         renew_workflow_run_claim, replay_workflow_run, resume_workflow_run, update_run,
         update_workflow_run,
     },
     supervisor::get_supervisor_status,
+    templates::{
+        create_workflow_template, delete_workflow_template, get_workflow_template,
+        get_workflow_templates, instantiate_workflow_template, update_workflow_template,
+    },
     triggers::{
-        claim_due_workflow_trigger_firings, delete_workflow_trigger, get_due_workflow_triggers,
-        get_workflow_trigger, get_workflow_triggers, update_workflow_trigger,
-        upsert_workflow_trigger,
+        cancel_workflow_backfill, claim_due_workflow_trigger_firings, create_workflow_backfill,
+        delete_workflow_trigger, get_due_workflow_triggers, get_workflow_backfills,
+        get_workflow_trigger, get_workflow_triggers, preview_cron, reconcile_trigger_schedules,
+        shift_trigger_schedules, update_workflow_trigger, upsert_workflow_trigger,
     },
     wdl::{
         analyze_wdl, compile_wdl, complete_wdl, decompile_to_wdl, evaluate_expression, format_wdl,
         hover_wdl, import_wdl,
     },
-    webhook::{webhook_signal, webhook_wake},
+    webhook::{trigger_webhook, webhook_signal, webhook_wake},
     workflows::{
-        delete_workflow, duplicate_workflow, export_single_workflow_bundle, export_workflow_bundle,
-        get_workflow, get_workflows, import_workflow_bundle, set_workflow_owner, simulate_workflow,
-        upsert_workflow, validate_workflow,
+        clear_workflow_retention, clone_workflow, delete_workflow, disable_workflow,
+        duplicate_workflow, enable_workflow, export_single_workflow_bundle, export_workflow_bundle,
+        get_workflow_hashes, get_workflow_overview, get_workflow_retention, get_workflow_revisions,
+        get_workflow_tagged, get_workflows_conditional, import_workflow_bundle, revert_workflow,
+        search_workflows, set_workflow_owner, set_workflow_retention, simulate_workflow,
+        upsert_workflow, upsert_workflows_bulk, validate_workflow,
     },
 };
 use crate::overload::{OverloadConfig, apply_overload_protection};
@@ -123,6 +144,7 @@ use crate::websocket::{
     ws_desktop_worker, ws_events, ws_run_stream, ws_workflow_node_run_stream, ws_workflow_run,
 };
 
+#[allow(clippy::too_many_arguments)]
 pub fn build_router<T: DatabaseImpl>(
     pool: Arc<T>,
     events: EventSender,
@@ -131,21 +153,26 @@ pub fn build_router<T: DatabaseImpl>(
     auth: AuthConfig,
     rate_limit: RateLimitConfig,
     overload: OverloadConfig,
+    cors: CorsConfig,
+    api_docs_enabled: bool,
 ) -> Router {
     let auth_config_arc = Arc::new(auth);
     let rate_limiter = Arc::new(RateLimiter::new(rate_limit));
-    let cors = CorsLayer::new()
-        .allow_origin(Any)
-        .allow_methods(Any)
-        .allow_headers(Any)
-        .expose_headers(Any);
+    let cors = cors.layer();
 
-    let router = Router::new()
+    let mut router = Router::new()
         .route("/health", get(health))
-        .route("/metrics", get(metrics))
+        .route("/metrics", get(metrics::<T>).layer(Extension(pool.clone())))
         .route("/ready", get(ready::<T>).layer(Extension(pool.clone())))
-        .route("/openapi.json", get(crate::openapi::openapi_json))
-        .route("/docs", get(crate::openapi::openapi_docs))
+        // kubernetes-style aliases so standard probe configs work without path overrides.
+        .route("/healthz", get(health))
+        .route("/readyz", get(ready::<T>).layer(Extension(pool.clone())))
+        .route("/openapi.json", get(crate::openapi::openapi_json));
+    // the interactive reference is optional; `/openapi.json` stays up so clients can be generated.
+    if api_docs_enabled {
+        router = router.route("/docs", get(crate::openapi::openapi_docs));
+    }
+    let router = router
         .route("/ws/events", get(ws_events))
         .route(
             "/ws/workflow-runs/{id}",
@@ -165,10 +192,14 @@ pub fn build_router<T: DatabaseImpl>(
         )
         .route(
             API_WORKFLOWS,
-            get(get_workflows::<T>)
+            get(get_workflows_conditional::<T>)
                 .post(upsert_workflow::<T>)
                 .layer(Extension(pool.clone())),
         )
+        .route(
+            API_WORKFLOWS_BULK,
+            post(upsert_workflows_bulk::<T>).layer(Extension(pool.clone())),
+        )
         .route(
             API_WORKFLOWS_VALIDATE,
             post(validate_workflow::<T>).layer(Extension(pool.clone())),
@@ -206,9 +237,28 @@ pub fn build_router<T: DatabaseImpl>(
             API_WORKFLOWS_EXPORT,
             get(export_workflow_bundle::<T>).layer(Extension(pool.clone())),
         )
+        .route(
+            API_WORKFLOWS_HASHES,
+            get(get_workflow_hashes::<T>).layer(Extension(pool.clone())),
+        )
+        .route(
+            API_WORKFLOWS_SEARCH,
+            get(search_workflows::<T>).layer(Extension(pool.clone())),
+        )
+        .route(
+            API_WORKFLOWS_OVERVIEW,
+            get(get_workflow_overview::<T>).layer(Extension(pool.clone())),
+        )
+        .route(
+            API_GRAPHQL,
+            post(crate::graphql::graphql::<T>).layer((
+                Extension(pool.clone()),
+                Extension(crate::graphql::schema::<T>()),
+            )),
+        )
         .route(
             "/workflows/{id}",
-            get(get_workflow::<T>)
+            get(get_workflow_tagged::<T>)
                 .patch(upsert_workflow::<T>)
                 .delete(delete_workflow::<T>)
                 .layer(Extension(pool.clone())),
@@ -221,10 +271,56 @@ pub fn build_router<T: DatabaseImpl>(
             "/workflows/{id}/duplicate",
             post(duplicate_workflow::<T>).layer(Extension(pool.clone())),
         )
+        .route(
+            "/workflows/{id}/clone",
+            post(clone_workflow::<T>).layer(Extension(pool.clone())),
+        )
+        .route(
+            "/workflows/{id}/enable",
+            post(enable_workflow::<T>).layer(Extension(pool.clone())),
+        )
+        .route(
+            "/workflows/{id}/disable",
+            post(disable_workflow::<T>).layer(Extension(pool.clone())),
+        )
         .route(
             "/workflows/{id}/owner",
             axum::routing::patch(set_workflow_owner::<T>).layer(Extension(pool.clone())),
         )
+        .route(
+            "/workflows/{id}/retention",
+            get(get_workflow_retention::<T>)
+                .patch(set_workflow_retention::<T>)
+                .delete(clear_workflow_retention::<T>)
+                .layer(Extension(pool.clone())),
+        )
+        .route(
+            "/workflows/{id}/revisions",
+            get(get_workflow_revisions::<T>).layer(Extension(pool.clone())),
+        )
+        .route(
+            "/workflows/{id}/protection",
+            get(get_workflow_protection::<T>)
+                .put(protect_workflow::<T>)
+                .delete(unprotect_workflow::<T>)
+                .layer(Extension(pool.clone())),
+        )
+        .route(
+            "/workflows/{id}/pending",
+            get(get_pending_workflow_changes::<T>).layer(Extension(pool.clone())),
+        )
+        .route(
+            "/workflows/{id}/pending/{change_id}/approve",
+            post(approve_pending_workflow_change::<T>).layer(Extension(pool.clone())),
+        )
+        .route(
+            "/workflows/{id}/pending/{change_id}/reject",
+            post(reject_pending_workflow_change::<T>).layer(Extension(pool.clone())),
+        )
+        .route(
+            "/workflows/{id}/revert/{revision}",
+            post(revert_workflow::<T>).layer(Extension(pool.clone())),
+        )
         .route(
             "/workflows/{id}/triggers",
             get(get_workflow_triggers::<T>)
@@ -239,6 +335,25 @@ pub fn build_router<T: DatabaseImpl>(
             API_SCHEDULER_WORKFLOW_TRIGGER_FIRINGS_CLAIM,
             post(claim_due_workflow_trigger_firings::<T>).layer(Extension(pool.clone())),
         )
+        .route(
+            API_ADMIN_RECONCILE_SCHEDULES,
+            post(reconcile_trigger_schedules::<T>).layer(Extension(pool.clone())),
+        )
+        .route(
+            API_ADMIN_SHIFT_SCHEDULES,
+            post(shift_trigger_schedules::<T>).layer(Extension(pool.clone())),
+        )
+        .route(API_CRON_PREVIEW, post(preview_cron))
+        .route(
+            API_BLACKOUT_CALENDARS,
+            get(get_blackout_calendars::<T>).layer(Extension(pool.clone())),
+        )
+        .route(
+            "/blackout_calendars/{name}",
+            put(put_blackout_calendar::<T>)
+                .delete(delete_blackout_calendar::<T>)
+                .layer(Extension(pool.clone())),
+        )
         .route(
             "/workflow_triggers/{id}",
             get(get_workflow_trigger::<T>)
@@ -250,6 +365,33 @@ pub fn build_router<T: DatabaseImpl>(
             "/workflow_triggers/{id}/runs",
             post(create_workflow_trigger_run::<T>).layer(Extension(pool.clone())),
         )
+        .route(
+            "/workflow_triggers/{id}/backfill",
+            get(get_workflow_backfills::<T>)
+                .post(create_workflow_backfill::<T>)
+                .layer(Extension(pool.clone())),
+        )
+        .route(
+            "/workflow_backfills/{id}/cancel",
+            post(cancel_workflow_backfill::<T>).layer(Extension(pool.clone())),
+        )
+        .route(
+            API_WORKFLOW_TEMPLATES,
+            get(get_workflow_templates::<T>)
+                .post(create_workflow_template::<T>)
+                .layer(Extension(pool.clone())),
+        )
+        .route(
+            "/workflow_templates/{id}",
+            get(get_workflow_template::<T>)
+                .patch(update_workflow_template::<T>)
+                .delete(delete_workflow_template::<T>)
+                .layer(Extension(pool.clone())),
+        )
+        .route(
+            "/workflow_templates/{id}/instantiate",
+            post(instantiate_workflow_template::<T>).layer(Extension(pool.clone())),
+        )
         .route(
             API_PIPELINES,
             get(get_pipelines::<T>)
@@ -303,10 +445,30 @@ pub fn build_router<T: DatabaseImpl>(
             API_WORKFLOW_RUNS,
             get(get_workflow_runs::<T>).layer(Extension(pool.clone())),
         )
+        .route(
+            API_WORKFLOW_RUN_HEALTH,
+            get(get_workflow_run_health::<T>).layer(Extension(pool.clone())),
+        )
+        .route(
+            API_QUEUE_WAIT,
+            get(get_queue_wait::<T>).layer(Extension(pool.clone())),
+        )
+        .route(
+            API_SCHEDULE_UPCOMING,
+            get(get_upcoming_schedule::<T>).layer(Extension(pool.clone())),
+        )
+        .route(
+            API_SCHEDULER_PREVIEW,
+            get(get_scheduler_preview::<T>).layer(Extension(pool.clone())),
+        )
         .route(
             API_REPLICAS,
             get(get_replicas::<T>).layer(Extension(pool.clone())),
         )
+        .route(
+            API_WORKERS,
+            get(get_workers::<T>).layer(Extension(pool.clone())),
+        )
         .route(
             "/replicas/register",
             post(register_replica::<T>).layer(Extension(pool.clone())),
@@ -341,6 +503,21 @@ pub fn build_router<T: DatabaseImpl>(
             API_SCHEDULER_READY_NODES_CLAIM,
             post(claim_ready_nodes::<T>).layer(Extension(pool.clone())),
         )
+        .route(
+            API_JOBS,
+            post(submit_job::<T>).layer(Extension(pool.clone())),
+        )
+        .route(
+            "/jobs/{id}",
+            get(get_job::<T>).layer(Extension(pool.clone())),
+        )
+        .route(
+            API_CLUSTER_PAUSE,
+            get(get_cluster_pause::<T>)
+                .put(put_cluster_pause::<T>)
+                .delete(delete_cluster_pause::<T>)
+                .layer(Extension(pool.clone())),
+        )
         .route(API_RUNS, get(get_runs::<T>).layer(Extension(pool.clone())))
         .route(
             "/runs/{id}",
@@ -523,6 +700,12 @@ pub fn build_router<T: DatabaseImpl>(
             "/workflow_runs/{id}/artifacts",
             get(get_workflow_run_artifacts::<T>).layer(Extension(pool.clone())),
         )
+        .route(
+            "/workflow_runs/{id}/outputs",
+            get(get_workflow_run_outputs::<T>)
+                .post(record_workflow_run_outputs::<T>)
+                .layer(Extension(pool.clone())),
+        )
         .route(
             "/workflow_runs/{id}/transitions",
             get(get_workflow_run_transitions::<T>).layer(Extension(pool.clone())),
@@ -571,6 +754,7 @@ pub fn build_router<T: DatabaseImpl>(
             "/audit_log",
             get(get_audit_log::<T>).layer(Extension(pool.clone())),
         )
+        .route(API_DEBUG_INFO, get(get_debug_info))
         .route(
             "/automation_events",
             get(get_automation_events::<T>)
@@ -626,6 +810,10 @@ pub fn build_router<T: DatabaseImpl>(
             "/providers/import",
             post(import_provider_bundle::<T>).layer(Extension(pool.clone())),
         )
+        .route(
+            API_STATS_PROVIDERS,
+            get(get_provider_stats::<T>).layer(Extension(pool.clone())),
+        )
         .route("/node-kinds", get(get_node_kinds))
         .route("/trigger-kinds", get(get_trigger_kinds))
         .route("/catalog/enums", get(get_enum_catalogs))
@@ -637,6 +825,10 @@ pub fn build_router<T: DatabaseImpl>(
             "/webhooks/signal",
             post(webhook_signal::<T>).layer(Extension(pool.clone())),
         )
+        .route(
+            "/triggers/{token}",
+            post(trigger_webhook::<T>).layer(Extension(pool.clone())),
+        )
         .route("/auth/config", get(auth_config))
         .route(
             "/auth/login",
@@ -787,6 +979,13 @@ pub fn build_router<T: DatabaseImpl>(
         // requests silently fell back to axum's stricter 2 MB default. 10 MB accommodates pack uploads.
         .layer(DefaultBodyLimit::max(10 * 1024 * 1024));
 
+    // every route is served under the `/v1` version prefix. the unversioned paths stay mounted for
+    // clients built before the prefix existed; nesting strips the prefix, so auth, rate limiting, and
+    // handlers see the same path either way.
+    let router = Router::new()
+        .nest(API_VERSION_PREFIX, router.clone())
+        .merge(router);
+
     // global overload protection (concurrency cap + per-request timeout) wraps everything above so a
     // flood is shed or timed out before auth/handler work runs. applied here rather than in the chain
     // so the catch-panic and trace layers below stay outermost and still cover its 503/408 responses.
@@ -801,8 +1000,6 @@ pub fn build_router<T: DatabaseImpl>(
         .layer(axum::middleware::from_fn(trace_propagation_middleware))
 }
 
-const REQUEST_ID_HEADER: &str = "x-request-id";
-
 /// open a per-request tracing span, re-parent it onto any inbound `traceparent` header so the server
 /// side of a distributed trace links to the caller, and log an access line with status/duration once
 /// the handler completes. a no-op for trace context when otel is off, leaving an ordinary local span;
@@ -816,14 +1013,15 @@ async fn trace_propagation_middleware(
 
     let method = request.method().clone();
     let path = request.uri().path().to_string();
+    // metrics are labelled by the route template so per-id paths don't each mint a series.
+    let route = request
+        .extensions()
+        .get::<axum::extract::MatchedPath>()
+        .map(|matched| matched.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
     // reuse an inbound request id from a fronting proxy/gateway when present, so this request's logs
     // line up with that layer's; otherwise mint one so every request is correlatable even with otel off.
-    let request_id = request
-        .headers()
-        .get(REQUEST_ID_HEADER)
-        .and_then(|value| value.to_str().ok())
-        .map(str::to_string)
-        .unwrap_or_else(|| uuid::Uuid::now_v7().to_string());
+    let request_id = crate::request_id::resolve(request.headers());
 
     let span = tracing::info_span!(
         "http_request",
@@ -833,11 +1031,20 @@ async fn trace_propagation_middleware(
     );
     runinator_utilities::telemetry::apply_http_context(&span, request.headers());
 
+    let scoped_id = request_id.clone();
     async move {
         let started = std::time::Instant::now();
-        let mut response = next.run(request).await;
-        let duration_ms = started.elapsed().as_millis() as u64;
+        // scoped so json error bodies built anywhere below can carry the id.
+        let mut response = crate::request_id::scope(scoped_id, next.run(request)).await;
+        let elapsed = started.elapsed();
+        let duration_ms = elapsed.as_millis() as u64;
         let status = response.status().as_u16();
+        crate::stability::record_http_request(
+            method.as_str(),
+            &route,
+            status,
+            elapsed.as_secs_f64() * 1000.0,
+        );
         if status >= 500 {
             tracing::error!(status, duration_ms, "request completed");
         } else if status >= 400 {
@@ -846,7 +1053,9 @@ async fn trace_propagation_middleware(
             tracing::info!(status, duration_ms, "request completed");
         }
         if let Ok(value) = axum::http::HeaderValue::from_str(&request_id) {
-            response.headers_mut().insert(REQUEST_ID_HEADER, value);
+            response
+                .headers_mut()
+                .insert(crate::request_id::REQUEST_ID_HEADER, value);
         }
         response
     }
@@ -869,6 +1078,7 @@ pub(crate) fn handle_panic(
     (
         axum::http::StatusCode::INTERNAL_SERVER_ERROR,
         axum::Json(ApiResponse::ApiError(ApiError::new(
+            crate::models::ApiErrorCode::Internal,
             "internal server error",
         ))),
     )
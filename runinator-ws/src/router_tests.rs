@@ -1,7 +1,9 @@
-use axum::body::to_bytes;
-use axum::http::StatusCode;
+use axum::body::{Body, to_bytes};
+use axum::http::{Request, StatusCode};
+use axum::{Router, routing::get};
+use tower::ServiceExt;
 
-use super::handle_panic;
+use super::{handle_panic, trace_propagation_middleware};
 
 // the various payload types `panic!`/`assert!` produce should all map to a 500 without the panic
 // handler itself panicking on an unexpected payload type, and the body must be the generic envelope
@@ -21,3 +23,44 @@ async fn handle_panic_returns_internal_error_envelope() {
         assert_eq!(parsed["message"], "internal server error");
     }
 }
+
+// send `GET /missing` (a handler answering 404) with an optional inbound request id; returns the
+// echoed header and the error body.
+async fn missing_with_request_id(inbound: Option<&str>) -> (String, serde_json::Value) {
+    let router = Router::new()
+        .route(
+            "/missing",
+            get(|| async { crate::responses::not_found("no such thing") }),
+        )
+        .layer(axum::middleware::from_fn(trace_propagation_middleware));
+    let mut request = Request::builder().uri("/missing");
+    if let Some(inbound) = inbound {
+        request = request.header("x-request-id", inbound);
+    }
+    let response = router
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let echoed = response.headers()["x-request-id"]
+        .to_str()
+        .unwrap()
+        .to_string();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (echoed, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test]
+async fn error_responses_carry_the_request_id() {
+    let (echoed, body) = missing_with_request_id(Some("gateway-abc-123")).await;
+    assert_eq!(echoed, "gateway-abc-123");
+    assert_eq!(body["request_id"], "gateway-abc-123");
+    assert_eq!(body["message"], "no such thing");
+
+    // without one (or with one too long to echo) a fresh id is minted and reported the same way.
+    for inbound in [None, Some("x".repeat(129).as_str())] {
+        let (echoed, body) = missing_with_request_id(inbound).await;
+        assert!(uuid::Uuid::parse_str(&echoed).is_ok());
+        assert_eq!(body["request_id"], echoed.as_str());
+    }
+}
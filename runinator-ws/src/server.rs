@@ -1,6 +1,7 @@
 use std::{
     net::{IpAddr, Ipv4Addr, SocketAddr},
     sync::Arc,
+    time::Duration,
 };
 
 use runinator_broker::Broker;
@@ -11,8 +12,8 @@ use runinator_models::replicas::{
     ReplicaHeartbeatRequest, ReplicaKind, ReplicaRegistrationRequest,
 };
 use tokio::{
-    net::TcpListener,
-    sync::{Notify, broadcast},
+    net::{TcpListener, TcpSocket},
+    sync::{Notify, broadcast, oneshot},
     task::JoinSet,
 };
 use tracing::{error, info, warn};
@@ -34,16 +35,22 @@ pub struct ReplicaAdvertisement {
     pub attributes: runinator_models::value::Value,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn run_webserver<T: DatabaseImpl>(
     pool: Arc<T>,
     notify: Arc<Notify>,
     port: u16,
+    grpc_port: Option<u16>,
     broker: Arc<dyn Broker>,
     advertisement: ReplicaAdvertisement,
     auth: crate::auth::AuthOptions,
     rate_limit: crate::rate_limit::RateLimitConfig,
     overload: crate::overload::OverloadConfig,
+    cors: crate::cors::CorsConfig,
     run_engine: bool,
+    api_docs_enabled: bool,
+    shutdown_grace: Duration,
+    reuse_port: bool,
 ) -> Result<(), SendableError> {
     crate::stability::init_metrics();
     seed_builtin_catalog(pool.as_ref()).await?;
@@ -54,6 +61,20 @@ pub async fn run_webserver<T: DatabaseImpl>(
     } else {
         warn!("HTTP API authentication is DISABLED");
     }
+    // blank entries (e.g. a trailing comma in the env var) must not become an empty-string key.
+    let static_api_key_hashes: Vec<String> = auth
+        .static_api_keys
+        .iter()
+        .map(|key| key.trim())
+        .filter(|key| !key.is_empty())
+        .map(crate::auth::hash_secret)
+        .collect();
+    if auth.enabled && !static_api_key_hashes.is_empty() {
+        info!(
+            count = static_api_key_hashes.len(),
+            "accepting static api keys from configuration"
+        );
+    }
     if jwt_secret_previous.is_some() {
         info!("accepting a previous jwt signing secret (key rotation overlap window is open)");
     }
@@ -63,6 +84,7 @@ pub async fn run_webserver<T: DatabaseImpl>(
         jwt_secret_previous,
         access_ttl_secs: auth.access_ttl_secs,
         refresh_ttl_secs: auth.refresh_ttl_secs,
+        static_api_key_hashes,
     };
     let (events_tx, _) = broadcast::channel::<AppEvent>(1024);
     let instance = advertisement
@@ -90,8 +112,8 @@ pub async fn run_webserver<T: DatabaseImpl>(
         &AuthContext::disabled_admin(),
     )
     .await?;
+    let web_replica_id = web_replica.replica_id;
     let heartbeat_db = pool.clone();
-    let heartbeat_notify = notify.clone();
     let heartbeat_runtime_id = runtime_id.clone();
     let heartbeat_instance = instance.clone();
     let heartbeat_host = advertisement.host.clone();
@@ -101,38 +123,30 @@ pub async fn run_webserver<T: DatabaseImpl>(
     // every long-lived loop runs in this set so an unexpected exit (panic or early return) is
     // observed at the join below instead of silently leaving a dead loop behind.
     let mut background: JoinSet<()> = JoinSet::new();
+    // the heartbeat keeps this replica live through the shutdown drain; it is marked offline only
+    // once in-flight requests have finished, so a rolling restart never shows a gap in the fleet.
     background.spawn(async move {
         let mut ticker = tokio::time::interval(std::time::Duration::from_secs(10));
         loop {
-            tokio::select! {
-                _ = heartbeat_notify.notified() => {
-                    let _ = crate::repository::mark_replica_offline(
-                        heartbeat_db.as_ref(),
-                        web_replica.replica_id,
-                        heartbeat_runtime_id.clone(),
-                    ).await;
-                    return;
-                }
-                _ = ticker.tick() => {
-                    let attributes = runinator_utilities::resource_telemetry::attributes_with_telemetry(
-                        &heartbeat_attributes,
-                        heartbeat_telemetry.as_ref(),
-                    );
-                    let _ = crate::repository::heartbeat_replica(
-                        heartbeat_db.as_ref(),
-                        web_replica.replica_id,
-                        ReplicaHeartbeatRequest {
-                            runtime_id: heartbeat_runtime_id.clone(),
-                            display_name: Some(heartbeat_instance.clone()),
-                            host: heartbeat_host.clone(),
-                            port: Some(port),
-                            base_path: Some("/".into()),
-                            attributes,
-                        },
-                        None,
-                    ).await;
-                }
-            }
+            ticker.tick().await;
+            let attributes = runinator_utilities::resource_telemetry::attributes_with_telemetry(
+                &heartbeat_attributes,
+                heartbeat_telemetry.as_ref(),
+            );
+            let _ = crate::repository::heartbeat_replica(
+                heartbeat_db.as_ref(),
+                web_replica.replica_id,
+                ReplicaHeartbeatRequest {
+                    runtime_id: heartbeat_runtime_id.clone(),
+                    display_name: Some(heartbeat_instance.clone()),
+                    host: heartbeat_host.clone(),
+                    port: Some(port),
+                    base_path: Some("/".into()),
+                    attributes,
+                },
+                None,
+            )
+            .await;
         }
     });
     // the bus publishes emitted events to the broker; the event consumer is the sole writer to the
@@ -183,6 +197,9 @@ pub async fn run_webserver<T: DatabaseImpl>(
         info!(
             requests_per_second = rate_limit.requests_per_second,
             burst = rate_limit.burst,
+            ip_requests_per_second = rate_limit.ip_requests_per_second,
+            client_ip_header = rate_limit.client_ip_header.as_deref(),
+            global_requests_per_second = rate_limit.global_requests_per_second,
             "HTTP API rate limiting is ENABLED"
         );
     }
@@ -199,6 +216,27 @@ pub async fn run_webserver<T: DatabaseImpl>(
     if !provisioner.is_empty() {
         info!("on-demand node provisioning is ENABLED");
     }
+    let db = pool.clone();
+    if let Some(grpc_port) = grpc_port {
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), grpc_port);
+        let listener = bind_listener(addr, reuse_port)?;
+        let grpc = crate::grpc::router(
+            pool.clone(),
+            bus.clone(),
+            crate::auth::AuthState {
+                config: Arc::new(auth_config.clone()),
+                db: pool.clone(),
+            },
+        );
+        info!("gRPC API started at {}:{}", addr.ip(), addr.port());
+        // event watches never end on their own, so the gRPC listener is not drained on shutdown;
+        // it stops with the background loops once the HTTP drain is done.
+        background.spawn(async move {
+            if let Err(err) = axum::serve(listener, grpc).await {
+                error!("gRPC server error: {}", err);
+            }
+        });
+    }
     let app = build_router(
         pool,
         bus,
@@ -207,12 +245,23 @@ pub async fn run_webserver<T: DatabaseImpl>(
         auth_config,
         rate_limit,
         overload,
+        cors,
+        api_docs_enabled,
     );
     let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), port);
-    let listener = TcpListener::bind(addr).await?;
-    let server = axum::serve(
-        listener,
-        app.into_make_service_with_connect_info::<SocketAddr>(),
+    let listener = bind_listener(addr, reuse_port)?;
+    // on shutdown the server stops accepting connections and lets in-flight requests finish, so a
+    // bulk import is not cut off mid-transaction; `shutdown_grace` bounds how long that may take.
+    let (drain_tx, drain_rx) = oneshot::channel::<()>();
+    let mut server = std::pin::pin!(
+        axum::serve(
+            listener,
+            app.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .with_graceful_shutdown(async move {
+            let _ = drain_rx.await;
+        })
+        .into_future()
     );
     info!("Webserver started at {}:{}", addr.ip(), addr.port());
 
@@ -221,12 +270,33 @@ pub async fn run_webserver<T: DatabaseImpl>(
         // failure when a background task also winds down on the same notification.
         biased;
         _ = notify.notified() => {
-            info!("Shutting down web server...");
+            info!(
+                grace_seconds = shutdown_grace.as_secs(),
+                "Shutting down web server; draining in-flight requests..."
+            );
+            let _ = drain_tx.send(());
+            let deadline = tokio::time::Instant::now() + shutdown_grace;
+            match tokio::time::timeout_at(deadline, server.as_mut()).await {
+                Ok(Ok(())) => info!("in-flight requests drained"),
+                Ok(Err(err)) => error!("webserver error while draining: {}", err),
+                Err(_) => warn!(
+                    grace_seconds = shutdown_grace.as_secs(),
+                    "shutdown grace period elapsed; abandoning requests still in flight"
+                ),
+            }
             background.shutdown().await;
+            mark_offline(db.as_ref(), web_replica_id, &runtime_id).await;
+            // closing waits for every checked-out connection, so writes still in flight commit
+            // before the process exits. an abandoned request's task still holds its connection, so
+            // the wait only gets what is left of the grace period.
+            if tokio::time::timeout_at(deadline, db.close()).await.is_err() {
+                warn!("shutdown grace period elapsed before the database pool closed");
+            }
             Ok(())
         }
-        result = server => {
+        result = server.as_mut() => {
             background.shutdown().await;
+            mark_offline(db.as_ref(), web_replica_id, &runtime_id).await;
             if let Err(err) = result {
                 error!("webserver error: {}", err);
                 return Err(Box::new(err));
@@ -251,7 +321,39 @@ pub async fn run_webserver<T: DatabaseImpl>(
             crate::stability::record_background_loop_failure();
             notify.notify_waiters();
             background.shutdown().await;
+            mark_offline(db.as_ref(), web_replica_id, &runtime_id).await;
             Err(runinator_engine::errors::BACKGROUND_LOOP_EXITED.bare())
         }
     }
 }
+
+/// bind the HTTP listener. with `reuse_port`, other processes may bind the same port alongside this
+/// one and the kernel spreads new connections across them, which lets a replacement replica start
+/// before this one has drained.
+pub(crate) fn bind_listener(addr: SocketAddr, reuse_port: bool) -> std::io::Result<TcpListener> {
+    let socket = match addr {
+        SocketAddr::V4(_) => TcpSocket::new_v4()?,
+        SocketAddr::V6(_) => TcpSocket::new_v6()?,
+    };
+    // matches std's listener so a restart can rebind while old connections sit in TIME_WAIT.
+    #[cfg(unix)]
+    socket.set_reuseaddr(true)?;
+    if reuse_port {
+        #[cfg(unix)]
+        socket.set_reuseport(true)?;
+        #[cfg(not(unix))]
+        warn!("--reuse-port is only supported on unix; binding the port exclusively");
+    }
+    socket.bind(addr)?;
+    socket.listen(1024)
+}
+
+// mark this replica offline after its server and loops have stopped. best-effort: the replica
+// reaper retires it anyway once its heartbeat goes quiet.
+async fn mark_offline<T: DatabaseImpl>(db: &T, replica_id: Uuid, runtime_id: &str) {
+    if let Err(err) =
+        crate::repository::mark_replica_offline(db, replica_id, runtime_id.to_string()).await
+    {
+        warn!("failed to mark the web service replica offline: {}", err);
+    }
+}
@@ -28,7 +28,8 @@ use runinator_models::{
     runs::{NewRunArtifact, NewRunChunk},
     workflows::{
         NewWorkflowRunArtifact, WorkflowAction, WorkflowBundle, WorkflowDefinition, WorkflowGraph,
-        WorkflowNodeRun, WorkflowStatus, WorkflowTrigger, WorkflowTriggerKind,
+        WorkflowImportOptions, WorkflowNodeRun, WorkflowStatus, WorkflowTrigger,
+        WorkflowTriggerKind,
     },
 };
 use runinator_wdl::WdlFragmentKind;
@@ -268,6 +269,7 @@ async fn org_scale_enforces_node_and_budget_quotas() {
         org_id,
         max_nodes_per_kind: [("worker".to_string(), 2u32)].into_iter().collect(),
         max_monthly_cents: 20_000,
+        max_concurrent_runs: 0,
     })
     .await
     .unwrap();
@@ -860,6 +862,210 @@ async fn workflow_listing_is_isolated_by_org() {
     let _ = std::fs::remove_file(path);
 }
 
+#[tokio::test]
+async fn workflow_listing_answers_not_modified_until_the_list_changes() {
+    use axum::extract::Query;
+    use axum::http::{HeaderMap, HeaderValue, header};
+
+    let (db, path) = test_db().await;
+    let db = Arc::new(db);
+    crate::repository::upsert_workflow(db.as_ref(), &workflow(None, "alpha"))
+        .await
+        .unwrap();
+    let admin_ctx = AuthContext {
+        principal_id: None,
+        is_admin: true,
+        kind: PrincipalKind::Service,
+        org_id: None,
+        org_role: None,
+    };
+    let list = |headers: HeaderMap| {
+        crate::handlers::workflows::get_workflows_conditional::<SqliteDb>(
+            Extension(db.clone()),
+            Extension(admin_ctx.clone()),
+            Query(crate::handlers::workflows::WorkflowQuery { name: None }),
+            headers,
+        )
+    };
+
+    let first = list(HeaderMap::new()).await;
+    assert_eq!(first.status(), StatusCode::OK);
+    assert!(first.headers().contains_key(header::LAST_MODIFIED));
+    let etag = first.headers().get(header::ETAG).unwrap().clone();
+
+    // resending the tag (weak or strong) skips the body entirely.
+    let mut headers = HeaderMap::new();
+    headers.insert(header::IF_NONE_MATCH, etag.clone());
+    assert_eq!(list(headers).await.status(), StatusCode::NOT_MODIFIED);
+    let mut headers = HeaderMap::new();
+    let weak = format!("\"stale\", W/{}", etag.to_str().unwrap());
+    headers.insert(header::IF_NONE_MATCH, HeaderValue::from_str(&weak).unwrap());
+    assert_eq!(list(headers).await.status(), StatusCode::NOT_MODIFIED);
+
+    // any change to the visible list yields a fresh tag.
+    crate::repository::upsert_workflow(db.as_ref(), &workflow(None, "beta"))
+        .await
+        .unwrap();
+    let mut headers = HeaderMap::new();
+    headers.insert(header::IF_NONE_MATCH, etag.clone());
+    let changed = list(headers).await;
+    assert_eq!(changed.status(), StatusCode::OK);
+    assert_ne!(changed.headers().get(header::ETAG), Some(&etag));
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn workflow_save_with_a_stale_if_match_is_rejected() {
+    use axum::http::{HeaderMap, HeaderValue, header};
+
+    let (db, path) = test_db().await;
+    let db = Arc::new(db);
+    let events = crate::events::EventBus::new(
+        tokio::sync::broadcast::channel(16).0,
+        Arc::new(InMemoryBroker::new()),
+    );
+    let saved = crate::repository::upsert_workflow(db.as_ref(), &workflow(None, "alpha"))
+        .await
+        .unwrap();
+    let workflow_id = saved.id.unwrap();
+    let admin_ctx = AuthContext {
+        principal_id: None,
+        is_admin: true,
+        kind: PrincipalKind::Service,
+        org_id: None,
+        org_role: None,
+    };
+    let fetch_etag = || async {
+        let response = crate::handlers::workflows::get_workflow_tagged::<SqliteDb>(
+            Extension(db.clone()),
+            Extension(admin_ctx.clone()),
+            Path(workflow_id),
+            HeaderMap::new(),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        response.headers().get(header::ETAG).unwrap().clone()
+    };
+    let save = |etag: HeaderValue, enabled: bool| {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_MATCH, etag);
+        let mut edit = saved.clone();
+        edit.enabled = enabled;
+        crate::handlers::workflows::upsert_workflow::<SqliteDb>(
+            Extension(db.clone()),
+            Extension(events.clone()),
+            Extension(admin_ctx.clone()),
+            headers,
+            Json(edit),
+        )
+    };
+
+    // two editors load the same version; the first save wins and moves the tag.
+    let etag = fetch_etag().await;
+    let (status, _) = save(etag.clone(), false).await;
+    assert_eq!(status, StatusCode::OK);
+    let fresh = fetch_etag().await;
+    assert_ne!(fresh, etag);
+
+    // the second editor's copy is stale, so their save is refused and nothing is overwritten.
+    let (status, _) = save(etag, true).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert!(
+        !db.fetch_workflow(workflow_id)
+            .await
+            .unwrap()
+            .unwrap()
+            .enabled
+    );
+
+    // after reloading they can save.
+    let (status, _) = save(fresh, true).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(
+        db.fetch_workflow(workflow_id)
+            .await
+            .unwrap()
+            .unwrap()
+            .enabled
+    );
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn workflow_search_without_text_pages_through_the_scoped_list() {
+    use axum::extract::Query;
+
+    let (db, path) = test_db().await;
+    let db = Arc::new(db);
+    let org = Uuid::now_v7();
+    for name in ["alpha", "bravo", "charlie", "delta"] {
+        let mut definition = workflow(None, name);
+        if name != "delta" {
+            definition.org_id = Some(org);
+        }
+        crate::repository::upsert_workflow(db.as_ref(), &definition)
+            .await
+            .unwrap();
+    }
+    let page = |offset, org_id, global| {
+        crate::handlers::workflows::search_workflows::<SqliteDb>(
+            Extension(db.clone()),
+            Extension(AuthContext::disabled_admin()),
+            Query(crate::models::WorkflowSearchQuery {
+                q: String::new(),
+                limit: Some(2),
+                offset: Some(offset),
+                org_id,
+                global,
+            }),
+        )
+    };
+    let names = |body: &serde_json::Value| {
+        body["workflows"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|workflow| workflow["name"].as_str().unwrap().to_string())
+            .collect::<Vec<_>>()
+    };
+
+    // an empty search lists every workflow by name, one page at a time, with the full count.
+    let (status, Json(body)) = page(2, None, false).await;
+    assert_eq!(status, StatusCode::OK);
+    let body = serde_json::to_value(&body).unwrap();
+    assert_eq!(body["total"], 4);
+    assert_eq!(names(&body), vec!["charlie", "delta"]);
+
+    // the scope narrows the count as well as the rows.
+    let (_, Json(body)) = page(0, Some(org), false).await;
+    let body = serde_json::to_value(&body).unwrap();
+    assert_eq!(body["total"], 3);
+    assert_eq!(names(&body), vec!["alpha", "bravo"]);
+    let (_, Json(body)) = page(0, None, true).await;
+    let body = serde_json::to_value(&body).unwrap();
+    assert_eq!(body["total"], 1);
+    assert_eq!(names(&body), vec!["delta"]);
+
+    // each term is another full-text condition, so a query with too many is refused.
+    let (status, _) = crate::handlers::workflows::search_workflows::<SqliteDb>(
+        Extension(db.clone()),
+        Extension(AuthContext::disabled_admin()),
+        Query(crate::models::WorkflowSearchQuery {
+            q: "a b c d e f g h i".into(),
+            limit: None,
+            offset: None,
+            org_id: None,
+            global: false,
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let _ = std::fs::remove_file(path);
+}
+
 // pull workflow names out of a WorkflowList api response for assertions.
 fn workflow_list_names(body: &Json<crate::models::ApiResponse>) -> Vec<String> {
     match &body.0 {
@@ -989,1822 +1195,4231 @@ async fn workflow_permission_admin_owns_everything_without_grants() {
 }
 
 #[tokio::test]
-async fn wdl_evaluate_accepts_legacy_lowered_expression() {
-    let request = crate::handlers::wdl::EvaluateExpressionRequest {
-        expression: Some(json!({ "$concat": ["hello ", { "$ref": { "params": ["name"] } }] })),
-        source: None,
-        kind: WdlFragmentKind::Expression,
-        context: json!({ "input": { "name": "Ada" } }),
-    };
+async fn workflow_retention_override_is_validated_and_visible() {
+    use runinator_models::retention::UpdateWorkflowRetentionRequest;
 
-    let Json(value) = crate::handlers::wdl::evaluate_expression(Json(request))
+    let (db, path) = test_db().await;
+    let db = Arc::new(db);
+    let workflow_id = crate::repository::upsert_workflow(db.as_ref(), &workflow(None, "kept"))
         .await
-        .expect("evaluate");
-
-    assert_eq!(value, Value::from("hello Ada"));
-}
-
-#[tokio::test]
-async fn wdl_evaluate_accepts_source_fragments() {
-    let request = crate::handlers::wdl::EvaluateExpressionRequest {
-        expression: None,
-        source: Some("params.count >= 3 && exists params.count".into()),
-        kind: WdlFragmentKind::Condition,
-        context: json!({ "input": { "count": 3 } }),
+        .unwrap()
+        .id
+        .unwrap();
+    let admin = AuthContext {
+        principal_id: None,
+        is_admin: true,
+        kind: PrincipalKind::User,
+        org_id: None,
+        org_role: None,
+    };
+    let set = |retention_days| {
+        crate::handlers::workflows::set_workflow_retention::<SqliteDb>(
+            Extension(db.clone()),
+            Extension(admin.clone()),
+            Path(workflow_id),
+            Json(UpdateWorkflowRetentionRequest { retention_days }),
+        )
     };
 
-    let Json(value) = crate::handlers::wdl::evaluate_expression(Json(request))
-        .await
-        .expect("evaluate");
-
-    assert_eq!(value, Value::from(true));
-}
-
-#[tokio::test]
-async fn get_node_kinds_returns_catalog_json() {
-    let (status, Json(response)) = crate::handlers::catalog_metadata::get_node_kinds().await;
-
+    let (status, _) = set(0).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = set(365).await;
     assert_eq!(status, StatusCode::OK);
-    let crate::models::ApiResponse::JsonValue(value) = response else {
-        panic!("node catalog response must be json");
-    };
-    assert_eq!(value.as_array().map(Vec::len), Some(35));
-}
 
-#[tokio::test]
-async fn get_trigger_kinds_returns_catalog_json() {
-    let (status, Json(response)) = crate::handlers::catalog_metadata::get_trigger_kinds().await;
+    let (status, Json(body)) = crate::handlers::workflows::get_workflow_retention::<SqliteDb>(
+        Extension(db.clone()),
+        Extension(admin.clone()),
+        Path(workflow_id),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let body = serde_json::to_value(&body).unwrap();
+    assert_eq!(body["retention_days"], 365);
 
+    let (status, _) = crate::handlers::workflows::clear_workflow_retention::<SqliteDb>(
+        Extension(db.clone()),
+        Extension(admin),
+        Path(workflow_id),
+    )
+    .await;
     assert_eq!(status, StatusCode::OK);
-    let crate::models::ApiResponse::JsonValue(value) = response else {
-        panic!("trigger catalog response must be json");
-    };
-    // cron, manual, chained.
-    assert_eq!(value.as_array().map(Vec::len), Some(3));
+    assert!(
+        db.fetch_workflow_retention(workflow_id)
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    let _ = std::fs::remove_file(path);
 }
 
 #[tokio::test]
-async fn get_enum_catalogs_returns_catalog_json() {
-    let (status, Json(response)) = crate::handlers::catalog_metadata::get_enum_catalogs().await;
+async fn one_off_jobs_dispatch_once_and_record_their_result() {
+    use crate::handlers::jobs::{get_job, submit_job};
+    use runinator_models::jobs::{JOB_NODE_ID, JobRequest};
 
-    assert_eq!(status, StatusCode::OK);
-    let crate::models::ApiResponse::JsonValue(value) = response else {
-        panic!("enum catalog response must be json");
+    let (db, path) = test_db().await;
+    let db = Arc::new(db);
+    let (events, job) = job_fixture(Arc::new(InMemoryBroker::new()));
+    let request = |provider: &str, function: &str| JobRequest {
+        action_name: provider.into(),
+        action_function: function.into(),
+        ..job.clone()
+    };
+    let member = AuthContext {
+        principal_id: Some(Uuid::new_v4()),
+        is_admin: false,
+        kind: PrincipalKind::User,
+        org_id: None,
+        org_role: None,
     };
-    assert_eq!(value.as_array().map(Vec::len), Some(4));
-}
 
-#[tokio::test]
-async fn wdl_analyze_validates_source_fragments() {
-    let (db, path) = test_db().await;
-    let Json(diagnostics) = crate::handlers::wdl::analyze_wdl(
-        Extension(Arc::new(db)),
-        Json(crate::handlers::wdl::WdlSourceRequest {
-            source: "params.count >".into(),
-            fragment: Some(WdlFragmentKind::Condition),
-        }),
+    let (status, _) = submit_job::<SqliteDb>(
+        Extension(db.clone()),
+        Extension(member),
+        Extension(events.clone()),
+        Json(request("slack", "send_message")),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    // the std interpreters need a workflow run's context.
+    let (status, _) = submit_job::<SqliteDb>(
+        Extension(db.clone()),
+        Extension(AuthContext::disabled_admin()),
+        Extension(events.clone()),
+        Json(request("std", "exec")),
     )
     .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
 
-    assert_eq!(diagnostics.len(), 1);
-    assert_eq!(diagnostics[0].severity, "error");
-    let _ = std::fs::remove_file(path);
-}
+    let (status, Json(body)) = submit_job::<SqliteDb>(
+        Extension(db.clone()),
+        Extension(AuthContext::disabled_admin()),
+        Extension(events.clone()),
+        Json(request("slack", "send_message")),
+    )
+    .await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    let body = serde_json::to_value(&body).unwrap();
+    assert_eq!(body["status"], "queued");
+    let job_id = Uuid::parse_str(body["id"].as_str().unwrap()).unwrap();
 
-#[tokio::test]
-async fn workflow_runs_can_be_named_and_fetched_by_open_name() {
-    let (db, path) = test_db().await;
-    let workflow = crate::repository::upsert_workflow(&db, &workflow(None, "Ticket Work"))
-        .await
-        .unwrap();
-    let workflow_id = workflow.id.unwrap();
-    let open = crate::repository::create_workflow_run(
-        &db,
-        workflow_id,
-        json!({}),
-        false,
-        Some("Ticket Work: ITP-123".into()),
-        Default::default(),
+    // exactly one dispatch, routed back to the job rather than a workflow node run.
+    let dispatches = db.fetch_pending_action_dispatches(10).await.unwrap();
+    assert_eq!(dispatches.len(), 1);
+    let command = dispatches[0].command.clone();
+    assert_eq!(dispatches[0].dedupe_key, format!("job:{job_id}"));
+    assert_eq!(command.node_id, JOB_NODE_ID);
+    assert_eq!(command.workflow_node_run_id, job_id);
+    assert_eq!(command.parameters["channel"], "#ops");
+
+    let worker = Uuid::new_v4();
+    let now = chrono::Utc::now();
+    let stale_before = now - chrono::Duration::seconds(60);
+    let claim = crate::repository::claim_workflow_node_run_executor(
+        db.as_ref(),
+        job_id,
+        worker,
+        now,
+        stale_before,
     )
     .await
     .unwrap();
-    let terminal = crate::repository::create_workflow_run(
-        &db,
-        workflow_id,
-        json!({}),
-        false,
-        Some("Ticket Work: ITP-123".into()),
-        Default::default(),
+    assert!(claim.success);
+    let duplicate = crate::repository::claim_workflow_node_run_executor(
+        db.as_ref(),
+        job_id,
+        Uuid::new_v4(),
+        now,
+        stale_before,
     )
     .await
     .unwrap();
-    crate::repository::update_workflow_run_status(
-        &db,
-        terminal.id,
+    assert!(!duplicate.success);
+
+    let result = runinator_comm::WorkflowResultEvent::status(
+        &command,
         WorkflowStatus::Succeeded,
+        Some(runinator_models::json!({ "ok": true })),
         None,
-        None,
-        None,
+    );
+    assert!(
+        crate::repository::apply_workflow_result_event(db.as_ref(), &result)
+            .await
+            .unwrap()
+    );
+    let (status, Json(body)) = get_job::<SqliteDb>(
+        Extension(db.clone()),
+        Extension(AuthContext::disabled_admin()),
+        Path(job_id),
     )
-    .await
-    .unwrap();
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let body = serde_json::to_value(&body).unwrap();
+    assert_eq!(body["status"], "succeeded");
+    assert_eq!(body["output_json"]["ok"], true);
 
-    let open_only =
-        crate::repository::fetch_workflow_runs_by_name(&db, "Ticket Work: ITP-123".into(), true)
-            .await
-            .unwrap();
+    let (status, _) = get_job::<SqliteDb>(
+        Extension(db.clone()),
+        Extension(AuthContext::disabled_admin()),
+        Path(Uuid::new_v4()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
 
-    assert_eq!(open.name.as_deref(), Some("Ticket Work: ITP-123"));
-    assert_eq!(
-        open_only.iter().map(|run| run.id).collect::<Vec<_>>(),
-        vec![open.id]
-    );
     let _ = std::fs::remove_file(path);
 }
 
+// a failed job attempt with retries left goes back to queued behind a delayed dispatch.
 #[tokio::test]
-async fn ready_node_processing_reduces_start_to_action_dispatch() {
+async fn failed_jobs_retry_with_backoff_and_record_each_attempt() {
+    use crate::handlers::jobs::{get_job, submit_job};
+    use runinator_models::jobs::JobRequest;
+
     let (db, path) = test_db().await;
-    let mut workflow = workflow(None, "ready-reducer");
-    workflow.definition = WorkflowGraph::from_value(json!({
-        "start": "start",
-        "nodes": [
-            { "id": "start", "kind": "start", "transitions": { "next": { "$node": "run" } } },
-            {
-                "id": "run",
-                "kind": "action",
-                "action": {
-                    "provider": "test",
-                    "function": "execute",
-                    "configuration": { "message": "hello" }
-                },
-                "transitions": { "on_success": { "$node": "done" } }
-            },
-            { "id": "done", "kind": "end" }
-        ]
-    }))
-    .unwrap();
-    let workflow = db.upsert_workflow(&workflow).await.unwrap();
-    let run = crate::repository::create_workflow_run(
-        &db,
-        workflow.id.unwrap(),
-        json!({}),
-        false,
-        None,
-        Default::default(),
+    let db = Arc::new(db);
+    let (events, job) = job_fixture(Arc::new(InMemoryBroker::new()));
+    let request = |max_retries: i64| JobRequest {
+        max_retries,
+        retry_backoff_seconds: 60,
+        ..job.clone()
+    };
+
+    let (status, _) = submit_job::<SqliteDb>(
+        Extension(db.clone()),
+        Extension(AuthContext::disabled_admin()),
+        Extension(events.clone()),
+        Json(request(11)),
     )
-    .await
-    .unwrap();
-    let ready = crate::repository::claim_ready_nodes(
-        &db,
-        "scheduler-a".into(),
-        chrono::Utc::now() + chrono::Duration::seconds(30),
-        10,
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, Json(body)) = submit_job::<SqliteDb>(
+        Extension(db.clone()),
+        Extension(AuthContext::disabled_admin()),
+        Extension(events.clone()),
+        Json(request(1)),
     )
-    .await
-    .unwrap();
-    assert_eq!(ready.len(), 1);
+    .await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    let body = serde_json::to_value(&body).unwrap();
+    let job_id = Uuid::parse_str(body["id"].as_str().unwrap()).unwrap();
+    let first = db.fetch_pending_action_dispatches(10).await.unwrap()[0]
+        .command
+        .clone();
+
+    let failed = runinator_comm::WorkflowResultEvent::status(
+        &first,
+        WorkflowStatus::Failed,
+        None,
+        Some("connection reset".into()),
+    );
+    assert!(
+        crate::repository::apply_workflow_result_event(db.as_ref(), &failed)
+            .await
+            .unwrap()
+    );
+    let job = db.fetch_job(job_id).await.unwrap().unwrap();
+    assert_eq!(job.status, WorkflowStatus::Queued);
+    assert_eq!(job.attempt, 2);
+    assert_eq!(job.attempts.len(), 1);
+    assert_eq!(job.attempts[0].status, WorkflowStatus::Failed);
 
-    crate::repository::complete_ready_node(&db, ready[0].id, "scheduler-a".into(), None)
+    // the retry waits out its backoff under a lease no publisher holds.
+    let retry = db
+        .fetch_pending_action_dispatches(10)
         .await
-        .unwrap();
+        .unwrap()
+        .into_iter()
+        .find(|dispatch| dispatch.dedupe_key == format!("job:{job_id}:2"))
+        .expect("the retry should be queued");
+    assert_eq!(retry.command.attempt, 2);
+    let held_until = retry.claimed_until.expect("the retry should be delayed");
+    assert!(held_until >= job.attempts[0].finished_at + chrono::Duration::seconds(60));
 
-    let (updated, nodes) = crate::repository::fetch_workflow_run(&db, run.id)
+    // a late result from the superseded attempt leaves the retry alone.
+    let late =
+        runinator_comm::WorkflowResultEvent::status(&first, WorkflowStatus::Succeeded, None, None);
+    crate::repository::apply_workflow_result_event(db.as_ref(), &late)
         .await
-        .unwrap()
         .unwrap();
-    assert_eq!(updated.status, WorkflowStatus::Running);
-    assert_eq!(updated.active_node_id.as_deref(), Some("run"));
-    assert!(
-        nodes
-            .iter()
-            .any(|node| node.node_id == "run" && node.status == WorkflowStatus::Running)
+    assert_eq!(
+        db.fetch_job(job_id).await.unwrap().unwrap().status,
+        WorkflowStatus::Queued
     );
-    let dispatches = db.fetch_pending_action_dispatches(10).await.unwrap();
-    assert_eq!(dispatches.len(), 1);
-    assert_eq!(dispatches[0].command.node_id, "run");
+
+    // the last allowed attempt failing is final.
+    let failed = runinator_comm::WorkflowResultEvent::status(
+        &retry.command,
+        WorkflowStatus::Failed,
+        None,
+        Some("connection reset".into()),
+    );
+    crate::repository::apply_workflow_result_event(db.as_ref(), &failed)
+        .await
+        .unwrap();
+    let (_, Json(body)) = get_job::<SqliteDb>(
+        Extension(db.clone()),
+        Extension(AuthContext::disabled_admin()),
+        Path(job_id),
+    )
+    .await;
+    let body = serde_json::to_value(&body).unwrap();
+    assert_eq!(body["status"], "failed");
+    assert_eq!(body["attempt"], 2);
+    assert_eq!(body["attempts"].as_array().unwrap().len(), 2);
 
     let _ = std::fs::remove_file(path);
 }
 
+// an action's priority rides along to the broker, which hands the higher one out first.
 #[tokio::test]
-async fn output_nodes_write_automation_events_for_the_events_tab() {
+async fn job_priority_reaches_the_broker_and_orders_delivery() {
+    use crate::handlers::jobs::submit_job;
+    use runinator_models::jobs::JobRequest;
+
     let (db, path) = test_db().await;
-    let mut workflow = workflow(None, "output-events");
-    workflow.definition = WorkflowGraph::from_value(json!({
-        "start": "start",
-        "nodes": [
-            { "id": "start", "kind": "start", "transitions": { "next": { "$node": "output" } } },
-            {
-                "id": "output",
-                "kind": "output",
-                "parameters": {
-                    "event_type": "workflow.routed",
-                    "data": { "ok": true, "count": 1 }
-                },
-                "transitions": { "next": { "$node": "done" } }
-            },
-            { "id": "done", "kind": "end" }
-        ]
-    }))
-    .unwrap();
-    let workflow = db.upsert_workflow(&workflow).await.unwrap();
-    let run = crate::repository::create_workflow_run(
-        &db,
-        workflow.id.unwrap(),
-        json!({}),
-        false,
-        None,
-        Default::default(),
-    )
-    .await
-    .unwrap();
-    let ready = crate::repository::claim_ready_nodes(
-        &db,
-        "scheduler-a".into(),
-        chrono::Utc::now() + chrono::Duration::seconds(30),
-        10,
+    let db = Arc::new(db);
+    let broker = Arc::new(InMemoryBroker::new());
+    let (events, job) = job_fixture(broker.clone());
+    let mut job_ids = Vec::new();
+    for priority in [0, 10] {
+        let (status, Json(body)) = submit_job::<SqliteDb>(
+            Extension(db.clone()),
+            Extension(AuthContext::disabled_admin()),
+            Extension(events.clone()),
+            Json(JobRequest {
+                priority,
+                ..job.clone()
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::ACCEPTED);
+        let body = serde_json::to_value(&body).unwrap();
+        job_ids.push(Uuid::parse_str(body["id"].as_str().unwrap()).unwrap());
+    }
+    publish_dispatches(db.as_ref(), broker.as_ref(), None).await;
+
+    let delivery = tokio::time::timeout(
+        std::time::Duration::from_secs(1),
+        broker.receive("test-worker"),
     )
     .await
+    .expect("a dispatch should reach the broker")
     .unwrap();
-    assert_eq!(ready.len(), 1);
+    assert_eq!(delivery.priority, 10);
+    assert_eq!(delivery.command.workflow_node_run_id, job_ids[1]);
 
-    crate::repository::complete_ready_node(&db, ready[0].id, "scheduler-a".into(), None)
-        .await
-        .unwrap();
+    let _ = std::fs::remove_file(path);
+}
 
-    let events = db
-        .fetch_automation_records("automation_events".into(), Some(run.id), None)
-        .await
-        .unwrap();
-    assert_eq!(events.len(), 1);
-    let event = &events[0];
-    let metadata = event
-        .get("metadata")
-        .and_then(Value::as_object)
-        .cloned()
-        .unwrap_or_default();
-    assert_eq!(
-        event.get("event_type").and_then(Value::as_str),
-        Some("workflow.routed")
-    );
-    assert_eq!(
-        event.get("provider").and_then(Value::as_str),
-        Some("runinator")
-    );
-    assert_eq!(
-        event.get("status").and_then(Value::as_str),
-        Some("output_recorded")
-    );
-    assert_eq!(
-        metadata
-            .get("data")
-            .and_then(Value::as_object)
-            .and_then(|data| data.get("ok"))
-            .and_then(Value::as_bool),
-        Some(true)
+// a queued job is published on its queue, so only a worker subscribed to it picks the job up.
+#[tokio::test]
+async fn job_queue_reaches_the_broker_and_routes_delivery() {
+    use crate::handlers::jobs::submit_job;
+    use runinator_broker::ConsumerProfile;
+    use runinator_models::jobs::JobRequest;
+
+    let (db, path) = test_db().await;
+    let db = Arc::new(db);
+    let broker = Arc::new(InMemoryBroker::new());
+    let (events, job) = job_fixture(broker.clone());
+    let (status, Json(body)) = submit_job::<SqliteDb>(
+        Extension(db.clone()),
+        Extension(AuthContext::disabled_admin()),
+        Extension(events.clone()),
+        Json(JobRequest {
+            queue: Some("exports".into()),
+            ..job
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    let body = serde_json::to_value(&body).unwrap();
+    let job_id = Uuid::parse_str(body["id"].as_str().unwrap()).unwrap();
+    publish_dispatches(db.as_ref(), broker.as_ref(), None).await;
+
+    let general = tokio::time::timeout(
+        std::time::Duration::from_millis(100),
+        broker.receive("general-worker"),
+    )
+    .await;
+    assert!(
+        general.is_err(),
+        "a general worker must not take queued work"
     );
+    let exporter = ConsumerProfile::shared("export-worker").with_queues(["exports".into()].into());
+    let delivery = tokio::time::timeout(
+        std::time::Duration::from_secs(1),
+        broker.receive_for(&exporter),
+    )
+    .await
+    .expect("a dispatch should reach the broker")
+    .unwrap();
+    assert_eq!(delivery.queue.as_deref(), Some("exports"));
+    assert_eq!(delivery.command.workflow_node_run_id, job_id);
 
     let _ = std::fs::remove_file(path);
 }
 
+// a dispatch the broker keeps refusing is dead-lettered, failing its job, once it hits the threshold.
 #[tokio::test]
-async fn pure_compute_node_reruns_in_loop_body() {
+async fn repeated_enqueue_failures_dead_letter_the_dispatch() {
+    use crate::handlers::jobs::submit_job;
+
     let (db, path) = test_db().await;
-    let mut workflow = workflow(None, "loop-compute");
-    workflow.definition = WorkflowGraph::from_value(json!({
-        "start": "start",
-        "nodes": [
-            { "id": "start", "kind": "start", "transitions": { "next": { "$node": "each" } } },
-            {
-                "id": "each",
-                "kind": "loop",
-                "parameters": { "items": { "$ref": { "input": ["xs"] } } },
-                "max_iterations": 10,
-                "transitions": {
-                    "next": { "$node": "double" },
-                    "on_success": { "$node": "done" }
-                }
-            },
-            {
-                "id": "double",
-                "kind": "action",
-                "action": {
-                    "provider": "std",
-                    "function": "run",
-                    "configuration": {
-                        "program": [
-                            { "$return": { "$mul": [{ "$ref": { "node": "each", "output": ["item"] } }, 2] } }
-                        ]
-                    }
-                },
-                "transitions": { "on_success": { "$node": "each" } }
-            },
-            { "id": "done", "kind": "end" }
-        ]
-    }))
-    .unwrap();
-    let workflow = db.upsert_workflow(&workflow).await.unwrap();
-    let run = crate::repository::create_workflow_run(
-        &db,
-        workflow.id.unwrap(),
-        json!({ "xs": [1, 2, 3] }),
-        false,
-        None,
-        Default::default(),
+    let db = Arc::new(db);
+    let broker = Arc::new(RecordingBroker::new());
+    broker
+        .refuse_publish
+        .store(true, std::sync::atomic::Ordering::Release);
+    let (events, job) = job_fixture(broker.clone());
+    let (status, _) = submit_job::<SqliteDb>(
+        Extension(db.clone()),
+        Extension(AuthContext::disabled_admin()),
+        Extension(events.clone()),
+        Json(job),
     )
-    .await
-    .unwrap();
+    .await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    let job_id = db.fetch_pending_action_dispatches(10).await.unwrap()[0]
+        .command
+        .workflow_run_id;
 
-    drain_ready_nodes(&db).await;
+    // below the threshold the dispatch stays in the outbox for the next pass.
+    publish_dispatches(db.as_ref(), broker.as_ref(), Some(2)).await;
+    let pending = db.fetch_pending_action_dispatches(10).await.unwrap();
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].attempts, 1);
+    assert!(db.fetch_dead_letters(None, 10).await.unwrap().is_empty());
 
-    let (updated, nodes) = crate::repository::fetch_workflow_run(&db, run.id)
-        .await
-        .unwrap()
-        .unwrap();
-    assert_eq!(updated.status, WorkflowStatus::Succeeded);
-    // the compute body ran once per item, re-creating a fresh node run each iteration.
-    let runs = nodes
-        .iter()
-        .filter(|node| node.node_id == "double" && node.status == WorkflowStatus::Succeeded)
-        .count();
-    assert_eq!(runs, 3, "compute body should run once per loop item");
-    // and never dispatched to a worker.
+    publish_dispatches(db.as_ref(), broker.as_ref(), Some(2)).await;
     assert!(
         db.fetch_pending_action_dispatches(10)
             .await
             .unwrap()
             .is_empty()
     );
+    let dead_letters = db
+        .fetch_dead_letters(Some("action_dispatch".into()), 10)
+        .await
+        .unwrap();
+    assert_eq!(dead_letters.len(), 1);
+    assert_eq!(
+        dead_letters[0].get("attempts").and_then(Value::as_i64),
+        Some(2)
+    );
+    assert!(
+        dead_letters[0]
+            .get("error")
+            .and_then(Value::as_str)
+            .is_some_and(|error| error.contains("broker unavailable"))
+    );
+    let job = db.fetch_job(job_id).await.unwrap().unwrap();
+    assert_eq!(job.status, WorkflowStatus::Failed);
+    assert!(
+        job.message
+            .as_deref()
+            .is_some_and(|message| message.contains("failed to enqueue 2 times"))
+    );
 
     let _ = std::fs::remove_file(path);
 }
 
+// blackout calendars are readable by anyone but only settings managers can change them.
 #[tokio::test]
-async fn pure_compute_node_reduces_in_process_without_dispatch() {
+async fn blackout_calendars_are_saved_listed_and_deleted() {
+    use crate::handlers::blackouts::{
+        delete_blackout_calendar, get_blackout_calendars, put_blackout_calendar,
+    };
+    use runinator_models::blackouts::BlackoutCalendarRequest;
+
     let (db, path) = test_db().await;
-    let mut workflow = workflow(None, "pure-compute");
-    workflow.definition = WorkflowGraph::from_value(json!({
-        "start": "start",
-        "nodes": [
-            { "id": "start", "kind": "start", "transitions": { "next": { "$node": "calc" } } },
-            {
-                "id": "calc",
-                "kind": "action",
-                "action": {
-                    "provider": "std",
-                    "function": "run",
-                    "configuration": {
-                        "program": [
-                            { "$let": "total", "value": { "$add": [{ "$ref": { "input": ["a"] } }, 3] } },
-                            { "$return": { "total": { "$ref": { "let": ["total"] } } } }
-                        ]
-                    }
-                },
-                "transitions": { "on_success": { "$node": "done" } }
-            },
-            { "id": "done", "kind": "end" }
-        ]
-    }))
-    .unwrap();
-    let workflow = db.upsert_workflow(&workflow).await.unwrap();
-    let run = crate::repository::create_workflow_run(
-        &db,
-        workflow.id.unwrap(),
-        json!({ "a": 4 }),
-        false,
-        None,
-        Default::default(),
+    let db = Arc::new(db);
+    let request = |timezone: &str| BlackoutCalendarRequest {
+        description: Some("  market holidays ".into()),
+        timezone: Some(timezone.into()),
+        dates: vec![
+            chrono::NaiveDate::from_ymd_opt(2026, 12, 25).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2026, 11, 26).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2026, 12, 25).unwrap(),
+        ],
+    };
+    let member = AuthContext {
+        principal_id: Some(Uuid::new_v4()),
+        is_admin: false,
+        kind: PrincipalKind::User,
+        org_id: None,
+        org_role: None,
+    };
+
+    let (status, _) = put_blackout_calendar::<SqliteDb>(
+        Extension(db.clone()),
+        Extension(member),
+        Path("market".into()),
+        Json(request("America/New_York")),
     )
-    .await
-    .unwrap();
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = put_blackout_calendar::<SqliteDb>(
+        Extension(db.clone()),
+        Extension(AuthContext::disabled_admin()),
+        Path("market".into()),
+        Json(request("Mars/Olympus")),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = put_blackout_calendar::<SqliteDb>(
+        Extension(db.clone()),
+        Extension(AuthContext::disabled_admin()),
+        Path("market".into()),
+        Json(request("America/New_York")),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
 
-    drain_ready_nodes(&db).await;
+    let (_, Json(body)) = get_blackout_calendars::<SqliteDb>(Extension(db.clone())).await;
+    let body = serde_json::to_value(&body).unwrap();
+    assert_eq!(body[0]["name"], "market");
+    assert_eq!(body[0]["description"], "market holidays");
+    assert_eq!(
+        body[0]["dates"],
+        serde_json::json!(["2026-11-26", "2026-12-25"])
+    );
 
-    let (updated, nodes) = crate::repository::fetch_workflow_run(&db, run.id)
-        .await
-        .unwrap()
-        .unwrap();
-    // the pure compute node reduced in-process and the run reached the end node.
-    assert_eq!(updated.status, WorkflowStatus::Succeeded);
-    let calc = nodes.iter().find(|node| node.node_id == "calc").unwrap();
-    assert_eq!(calc.status, WorkflowStatus::Succeeded);
-    assert_eq!(calc.output_json, Some(json!({ "total": 7 })));
-    // no worker dispatch was enqueued for the pure node.
+    let (status, _) = delete_blackout_calendar::<SqliteDb>(
+        Extension(db.clone()),
+        Extension(AuthContext::disabled_admin()),
+        Path("market".into()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = delete_blackout_calendar::<SqliteDb>(
+        Extension(db.clone()),
+        Extension(AuthContext::disabled_admin()),
+        Path("market".into()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let _ = std::fs::remove_file(path);
+}
+
+// a dispatch pause holds queued actions in the outbox until an operator lifts it.
+#[tokio::test]
+async fn cluster_dispatch_pause_holds_the_outbox_until_resumed() {
+    use crate::handlers::cluster::{delete_cluster_pause, get_cluster_pause, put_cluster_pause};
+    use crate::handlers::jobs::submit_job;
+    use runinator_models::cluster::ClusterPauseRequest;
+
+    let (db, path) = test_db().await;
+    let db = Arc::new(db);
+    let broker = Arc::new(InMemoryBroker::new());
+    let (events, job) = job_fixture(broker.clone());
+    let pause = || ClusterPauseRequest {
+        dispatch: true,
+        execution: false,
+        scheduling: false,
+        reason: Some("broker failover".into()),
+    };
+    let member = AuthContext {
+        principal_id: Some(Uuid::new_v4()),
+        is_admin: false,
+        kind: PrincipalKind::User,
+        org_id: None,
+        org_role: None,
+    };
+
+    let (status, _) = put_cluster_pause::<SqliteDb>(
+        Extension(db.clone()),
+        Extension(member),
+        Extension(events.clone()),
+        Json(pause()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, Json(body)) = put_cluster_pause::<SqliteDb>(
+        Extension(db.clone()),
+        Extension(AuthContext::disabled_admin()),
+        Extension(events.clone()),
+        Json(pause()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let body = serde_json::to_value(&body).unwrap();
+    assert_eq!(body["dispatch"], true);
+    assert_eq!(body["execution"], false);
+
+    let (status, _) = submit_job::<SqliteDb>(
+        Extension(db.clone()),
+        Extension(AuthContext::disabled_admin()),
+        Extension(events.clone()),
+        Json(job),
+    )
+    .await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    publish_dispatches(db.as_ref(), broker.as_ref(), None).await;
+    assert_eq!(
+        db.fetch_pending_action_dispatches(10).await.unwrap().len(),
+        1
+    );
+
+    let (status, _) = delete_cluster_pause::<SqliteDb>(
+        Extension(db.clone()),
+        Extension(AuthContext::disabled_admin()),
+        Extension(events.clone()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, Json(body)) = get_cluster_pause::<SqliteDb>(Extension(db.clone())).await;
+    let body = serde_json::to_value(&body).unwrap();
+    assert_eq!(body["dispatch"], false);
+    assert!(body.get("reason").is_none());
+
+    publish_dispatches(db.as_ref(), broker.as_ref(), None).await;
     assert!(
         db.fetch_pending_action_dispatches(10)
             .await
             .unwrap()
             .is_empty()
     );
+    let delivery = tokio::time::timeout(
+        std::time::Duration::from_secs(1),
+        broker.receive("test-worker"),
+    )
+    .await
+    .expect("the held dispatch should reach the broker")
+    .unwrap();
+    assert_eq!(
+        delivery.command.node_id,
+        runinator_models::jobs::JOB_NODE_ID
+    );
 
     let _ = std::fs::remove_file(path);
 }
 
+// a scheduling pause stops triggers firing; lifting it moves overdue triggers on to their next
+// slot instead of firing what was missed.
 #[tokio::test]
-async fn compute_goto_sets_active_node() {
+async fn cluster_scheduling_pause_holds_triggers_and_resume_reschedules() {
+    use crate::handlers::cluster::{delete_cluster_pause, put_cluster_pause};
+    use chrono::{Duration, Utc};
+    use runinator_models::cluster::ClusterPauseRequest;
+
     let (db, path) = test_db().await;
-    let mut workflow = workflow(None, "compute-goto");
-    workflow.definition = WorkflowGraph::from_value(json!({
-        "start": "start",
-        "nodes": [
-            { "id": "start", "kind": "start", "transitions": { "next": { "$node": "gate" } } },
-            {
-                "id": "gate",
-                "kind": "action",
-                "action": {
-                    "provider": "std",
-                    "function": "run",
-                    "configuration": {
-                        "program": [
-                            { "$if": { "value": { "$ref": { "input": ["x"] } }, "less_than": 0 },
-                              "then": [ { "$goto": "fail" } ],
-                              "else": [] },
-                            { "$return": "ok" }
-                        ]
-                    }
-                },
-                "transitions": { "on_success": { "$node": "done" } }
-            },
-            { "id": "fail", "kind": "fail" },
-            { "id": "done", "kind": "end" }
-        ]
-    }))
-    .unwrap();
-    let workflow = db.upsert_workflow(&workflow).await.unwrap();
-    let run = crate::repository::create_workflow_run(
-        &db,
-        workflow.id.unwrap(),
-        json!({ "x": -1 }),
-        false,
-        None,
-        Default::default(),
-    )
-    .await
-    .unwrap();
+    let db = Arc::new(db);
+    let events = crate::events::EventBus::new(
+        tokio::sync::broadcast::channel(16).0,
+        Arc::new(InMemoryBroker::new()),
+    );
+    let saved = crate::repository::upsert_workflow(db.as_ref(), &workflow(None, "hourly"))
+        .await
+        .unwrap();
+    let mut cron = trigger(None, saved.id.unwrap());
+    cron.kind = WorkflowTriggerKind::Cron;
+    cron.configuration = json!({ "cron": "0 0 * * * *", "misfire": "run_all_missed" });
+    cron.next_execution = Some(Utc::now() - Duration::hours(3));
+    let cron = db.upsert_workflow_trigger(&cron).await.unwrap();
 
-    drain_ready_nodes(&db).await;
+    let (status, Json(body)) = put_cluster_pause::<SqliteDb>(
+        Extension(db.clone()),
+        Extension(AuthContext::disabled_admin()),
+        Extension(events.clone()),
+        Json(ClusterPauseRequest {
+            dispatch: false,
+            execution: false,
+            scheduling: true,
+            reason: Some("incident".into()),
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(serde_json::to_value(&body).unwrap()["scheduling"], true);
+    let claim =
+        || crate::repository::claim_due_workflow_trigger_firings(db.as_ref(), "ws".into(), 10);
+    assert!(claim().await.unwrap().is_empty());
 
-    let (updated, _) = crate::repository::fetch_workflow_run(&db, run.id)
+    let (status, _) = delete_cluster_pause::<SqliteDb>(
+        Extension(db.clone()),
+        Extension(AuthContext::disabled_admin()),
+        Extension(events.clone()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let resumed = db
+        .fetch_workflow_trigger(cron.id.unwrap())
         .await
         .unwrap()
         .unwrap();
-    // goto fail routed the run to the fail node, ending the run as failed.
-    assert_eq!(updated.status, WorkflowStatus::Failed);
-
+    assert!(resumed.next_execution.unwrap() > Utc::now());
+    // the three hours of slots missed while paused are dropped, not replayed.
+    assert!(claim().await.unwrap().is_empty());
     let _ = std::fs::remove_file(path);
 }
 
+// the worker registry reports what each worker loaded and the load it last heartbeated.
 #[tokio::test]
-async fn action_failure_schedules_retry_with_backoff() {
+async fn worker_registry_reports_plugins_providers_and_load() {
+    use crate::handlers::replicas::get_workers;
+    use crate::models::WorkerQuery;
+    use runinator_models::providers::{ActionMetadata, ProviderMetadata};
+    use runinator_models::replicas::{
+        ReplicaHeartbeatRequest, ReplicaKind, ReplicaProviderRegistrationRequest,
+        ReplicaRegistrationRequest,
+    };
+
     let (db, path) = test_db().await;
-    let mut workflow = workflow(None, "action-retry");
-    workflow.definition = WorkflowGraph::from_value(json!({
-        "start": "start",
-        "nodes": [
-            { "id": "start", "kind": "start", "transitions": { "next": { "$node": "run" } } },
-            {
-                "id": "run",
-                "kind": "action",
-                "action": {
-                    "provider": "test",
-                    "function": "execute",
-                    "configuration": { "message": "hello" }
-                },
-                "retry": { "max_attempts": 3 },
-                "transitions": { "on_failure": { "$node": "failed" } }
+    let db = Arc::new(db);
+    let runtime_id = Uuid::new_v4().to_string();
+    let attributes = || {
+        json!({
+            "labels": { "region": "us-east" },
+            "plugins": ["shell", "http"],
+            "max_concurrent_actions": 4
+        })
+    };
+    let worker = db
+        .register_replica(
+            ReplicaRegistrationRequest {
+                replica_type: ReplicaKind::Worker,
+                instance_id: "registry-worker".into(),
+                runtime_id: runtime_id.clone(),
+                display_name: Some("registry worker".into()),
+                host: Some("10.0.0.7".into()),
+                port: None,
+                base_path: None,
+                version: Some("1.2.3".into()),
+                attributes: attributes(),
             },
-            { "id": "failed", "kind": "fail" },
-            { "id": "end", "kind": "end" }
-        ]
-    }))
+            None,
+            &AuthContext::disabled_admin(),
+        )
+        .await
+        .unwrap();
+    db.register_replica(
+        ReplicaRegistrationRequest {
+            replica_type: ReplicaKind::Waker,
+            instance_id: "registry-waker".into(),
+            runtime_id: Uuid::new_v4().to_string(),
+            display_name: None,
+            host: None,
+            port: None,
+            base_path: None,
+            version: None,
+            attributes: json!({}),
+        },
+        None,
+        &AuthContext::disabled_admin(),
+    )
+    .await
     .unwrap();
-    let workflow = db.upsert_workflow(&workflow).await.unwrap();
-    let run = crate::repository::create_workflow_run(
-        &db,
-        workflow.id.unwrap(),
-        json!({}),
-        false,
+    db.upsert_replica_provider_registration(
+        worker.replica_id,
+        ReplicaProviderRegistrationRequest {
+            runtime_id: runtime_id.clone(),
+            provider: ProviderMetadata {
+                name: "shell".into(),
+                actions: vec![ActionMetadata::new("run", "Run a command")],
+                metadata: Default::default(),
+            },
+        },
+    )
+    .await
+    .unwrap();
+    // the heartbeat re-sends the registration attributes with the live load merged in.
+    let mut heartbeat = attributes();
+    heartbeat
+        .as_object_mut()
+        .unwrap()
+        .insert("load".into(), json!({ "in_flight_actions": 2 }));
+    db.heartbeat_replica(
+        worker.replica_id,
+        ReplicaHeartbeatRequest {
+            runtime_id,
+            display_name: None,
+            host: None,
+            port: None,
+            base_path: None,
+            attributes: heartbeat,
+        },
         None,
-        Default::default(),
     )
     .await
     .unwrap();
 
-    drain_ready_nodes(&db).await;
-    let dispatch = db.fetch_pending_action_dispatches(10).await.unwrap()[0].clone();
-    db.mark_action_dispatch_published(dispatch.id)
-        .await
-        .unwrap();
-    let event = WorkflowResultEvent::status(&dispatch.command, WorkflowStatus::Failed, None, None);
-    crate::repository::apply_workflow_result_event(&db, &event)
-        .await
-        .unwrap();
-
-    drain_ready_nodes(&db).await;
-
-    let (updated, nodes) = crate::repository::fetch_workflow_run(&db, run.id)
-        .await
-        .unwrap()
-        .unwrap();
-    assert_eq!(updated.status, WorkflowStatus::Waiting);
-    assert_eq!(updated.active_node_id.as_deref(), Some("run"));
-    let run_node = nodes.iter().find(|node| node.node_id == "run").unwrap();
-    assert_eq!(run_node.status, WorkflowStatus::Queued);
-    assert_eq!(run_node.attempt, 1);
-    let retry_ready = db
-        .fetch_pending_ready_nodes(chrono::Utc::now(), 10)
-        .await
-        .unwrap()
-        .into_iter()
-        .find(|ready| ready.workflow_run_id == run.id && ready.node_id == "run")
-        .expect("retry ready node is pending");
-    assert!(retry_ready.ready_at > chrono::Utc::now());
-    assert!(
-        db.fetch_pending_action_dispatches(10)
-            .await
-            .unwrap()
-            .is_empty()
-    );
+    let (status, Json(body)) = get_workers::<SqliteDb>(
+        Extension(db.clone()),
+        Extension(AuthContext::disabled_admin()),
+        axum::extract::Query(WorkerQuery { status: None }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let body = serde_json::to_value(&body).unwrap();
+    let workers = body.as_array().unwrap();
+    // the waker is a replica but not a worker.
+    assert_eq!(workers.len(), 1);
+    assert_eq!(workers[0]["instance_id"], "registry-worker");
+    assert_eq!(workers[0]["status"], "live");
+    assert_eq!(workers[0]["plugins"], serde_json::json!(["http", "shell"]));
+    assert_eq!(workers[0]["providers"], serde_json::json!(["shell"]));
+    assert_eq!(workers[0]["labels"]["region"], "us-east");
+    assert_eq!(workers[0]["max_concurrent_actions"], 4);
+    assert_eq!(workers[0]["in_flight_actions"], 2);
+
+    let workers = crate::repository::fetch_workers(
+        db.as_ref(),
+        Some(runinator_models::replicas::ReplicaStatus::Offline),
+    )
+    .await
+    .unwrap();
+    assert!(workers.is_empty());
 
     let _ = std::fs::remove_file(path);
 }
 
+// every route answers under `/v1` as well as its legacy unversioned path, and a failure carries a
+// machine-readable code alongside its status.
 #[tokio::test]
-async fn action_retry_republishes_dispatch_after_backoff() {
+async fn versioned_routes_answer_with_structured_error_codes() {
+    use axum::body::{Body, to_bytes};
+    use axum::http::Request;
+    use tower::ServiceExt;
+
     let (db, path) = test_db().await;
-    let run_id = seed_run(
-        &db,
-        "action-retry-redispatch",
-        json!({
-            "start": "start",
-            "nodes": [
-                { "id": "start", "kind": "start", "transitions": { "next": { "$node": "run" } } },
-                {
-                    "id": "run",
-                    "kind": "action",
-                    "action": { "provider": "test", "function": "execute", "configuration": {} },
-                    "retry": { "max_attempts": 3 },
-                    "transitions": { "on_failure": { "$node": "failed" } }
-                },
-                { "id": "failed", "kind": "fail" },
-                { "id": "end", "kind": "end" }
-            ]
-        }),
-    )
-    .await;
+    let db = Arc::new(db);
+    let broker: Arc<dyn Broker> = Arc::new(InMemoryBroker::new());
+    let router = crate::router::build_router(
+        db.clone(),
+        crate::events::EventBus::new(tokio::sync::broadcast::channel(16).0, broker.clone()),
+        broker,
+        Arc::new(runinator_provisioner::ProvisionerRegistry::default()),
+        crate::auth::AuthConfig {
+            enabled: false,
+            jwt_secret: b"test-secret".to_vec(),
+            jwt_secret_previous: None,
+            access_ttl_secs: 900,
+            refresh_ttl_secs: 3600,
+            static_api_key_hashes: Vec::new(),
+        },
+        Default::default(),
+        Default::default(),
+        Default::default(),
+        false,
+    );
+    let get = |uri: String| {
+        let router = router.clone();
+        async move {
+            let response = router
+                .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            let status = response.status();
+            let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            (
+                status,
+                serde_json::from_slice::<serde_json::Value>(&body).ok(),
+            )
+        }
+    };
 
-    drain_ready_nodes(&db).await;
-    let dispatch = db.fetch_pending_action_dispatches(10).await.unwrap()[0].clone();
-    db.mark_action_dispatch_published(dispatch.id)
-        .await
-        .unwrap();
-    let event = WorkflowResultEvent::status(&dispatch.command, WorkflowStatus::Failed, None, None);
-    crate::repository::apply_workflow_result_event(&db, &event)
+    let (status, _) = get("/v1/health".into()).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = get("/v1/workflows".into()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.unwrap().is_array());
+
+    let missing = Uuid::now_v7();
+    for uri in [
+        format!("/v1/workflows/{missing}"),
+        format!("/workflows/{missing}"),
+    ] {
+        let (status, body) = get(uri).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body.unwrap()["code"], "not_found");
+    }
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn metrics_scrape_reports_database_pool_usage() {
+    use axum::body::{Body, to_bytes};
+    use axum::http::Request;
+    use tower::ServiceExt;
+
+    crate::stability::init_metrics();
+    let (db, path) = test_db().await;
+    let db = Arc::new(db);
+    let broker: Arc<dyn Broker> = Arc::new(InMemoryBroker::new());
+    let router = crate::router::build_router(
+        db.clone(),
+        crate::events::EventBus::new(tokio::sync::broadcast::channel(16).0, broker.clone()),
+        broker,
+        Arc::new(runinator_provisioner::ProvisionerRegistry::default()),
+        crate::auth::AuthConfig {
+            enabled: false,
+            jwt_secret: b"test-secret".to_vec(),
+            jwt_secret_previous: None,
+            access_ttl_secs: 900,
+            refresh_ttl_secs: 3600,
+            static_api_key_hashes: Vec::new(),
+        },
+        Default::default(),
+        Default::default(),
+        Default::default(),
+        false,
+    );
+
+    let status = db.pool_status();
+    assert!(status.max_connections > 0);
+    assert!(status.in_use() <= status.size);
+
+    let response = router
+        .oneshot(Request::get("/metrics").body(Body::empty()).unwrap())
         .await
         .unwrap();
-    drain_ready_nodes(&db).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body = String::from_utf8(body.to_vec()).unwrap();
+    for metric in [
+        "runinator_ws_db_pool_connections",
+        "runinator_ws_db_pool_idle",
+        "runinator_ws_db_pool_in_use",
+        "runinator_ws_db_pool_max_connections",
+    ] {
+        assert!(body.contains(metric), "{metric} missing from /metrics");
+    }
 
-    // wait out the first retry backoff, then drive the retry ready node to its re-dispatch.
-    tokio::time::sleep(Duration::from_millis(1500)).await;
-    drain_ready_nodes(&db).await;
+    let _ = std::fs::remove_file(path);
+}
 
-    // the retried attempt must publish a fresh outbox row; reusing the first attempt's dedupe key
-    // would collide with the already-published row and park the run in `running` forever.
-    let pending = db.fetch_pending_action_dispatches(10).await.unwrap();
-    assert_eq!(pending.len(), 1, "retry must enqueue a fresh dispatch");
-    assert_eq!(pending[0].command.attempt, 2);
-    assert_ne!(pending[0].dedupe_key, dispatch.dedupe_key);
-    let (run, _) = crate::repository::fetch_workflow_run(&db, run_id)
+// a claim pass reports how many due triggers it evaluated, how late they were, how many a
+// blackout deferred, and how many runs it enqueued.
+#[tokio::test]
+async fn trigger_claims_report_scheduler_metrics() {
+    use chrono::{Duration, Utc};
+
+    crate::stability::init_metrics();
+    let (db, path) = test_db().await;
+    let saved = crate::repository::upsert_workflow(&db, &workflow(None, "metered"))
         .await
-        .unwrap()
         .unwrap();
-    assert_eq!(run.status, WorkflowStatus::Running);
+    let now = Utc::now();
+    for blacked_out in [false, true] {
+        let mut cron = trigger(None, saved.id.unwrap());
+        cron.kind = WorkflowTriggerKind::Cron;
+        cron.configuration = json!({ "cron": "0 0 * * * *" });
+        cron.next_execution = Some(now - Duration::minutes(10));
+        if blacked_out {
+            cron.blackout_start = Some(now - Duration::hours(1));
+            cron.blackout_end = Some(now + Duration::hours(1));
+        }
+        db.upsert_workflow_trigger(&cron).await.unwrap();
+    }
 
+    let runs = crate::repository::claim_due_workflow_trigger_firings(&db, "ws".into(), 10)
+        .await
+        .unwrap();
+    assert_eq!(runs.len(), 1);
+    let scrape = crate::stability::render_metrics();
+    for metric in [
+        "runinator_ws_scheduler_triggers_evaluated_total",
+        "runinator_ws_scheduler_triggers_deferred_total",
+        "runinator_ws_scheduler_runs_enqueued_total",
+        "runinator_ws_schedule_lag_ms",
+    ] {
+        assert!(scrape.contains(metric), "{metric} missing from the scrape");
+    }
     let _ = std::fs::remove_file(path);
 }
 
+#[test]
+fn repository_errors_map_to_their_status_and_code() {
+    use runinator_models::errors::{ErrorDescriptor, SendableError};
+
+    let cases: Vec<(SendableError, StatusCode, &str)> = vec![
+        (
+            Box::new(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "Workflow trigger 1 not found",
+            )),
+            StatusCode::NOT_FOUND,
+            "not_found",
+        ),
+        (
+            ErrorDescriptor::new(
+                "RUNI132",
+                "workflow.debug.terminal",
+                "Workflow run is terminal",
+            )
+            .bare(),
+            StatusCode::CONFLICT,
+            "conflict",
+        ),
+        (
+            Box::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "bad cron",
+            )),
+            StatusCode::BAD_REQUEST,
+            "validation_failed",
+        ),
+        (
+            Box::new(std::io::Error::other("connection reset")),
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "db_error",
+        ),
+        (
+            ErrorDescriptor::new(
+                "RUNI217",
+                "worker.broker.publish",
+                "Broker operation failed",
+            )
+            .error("connection refused"),
+            StatusCode::SERVICE_UNAVAILABLE,
+            "unavailable",
+        ),
+        (
+            ErrorDescriptor::new("SQL005", "sql.query_failed", "Query failed").bare(),
+            StatusCode::BAD_GATEWAY,
+            "provider_failed",
+        ),
+    ];
+    for (err, expected_status, expected_code) in cases {
+        let (status, Json(body)) = crate::responses::repository_error(err.as_ref());
+        assert_eq!(status, expected_status, "{err}");
+        assert_eq!(serde_json::to_value(&body).unwrap()["code"], expected_code);
+    }
+
+    let (_, Json(body)) = crate::responses::repository_error(
+        ErrorDescriptor::new("RUNI124", "workflow.only_if.invalid", "Invalid only_if")
+            .error("unexpected token")
+            .as_ref(),
+    );
+    let body = serde_json::to_value(&body).unwrap();
+    assert_eq!(body["code"], "validation_failed");
+    assert_eq!(body["error_code"], "RUNI124");
+}
+
+// changes to a protected workflow wait in its pending list until a second principal approves
+// them; the requester can neither approve their own change nor skip the hold by saving by name.
 #[tokio::test]
-async fn duplicate_terminal_result_event_still_enqueues_drive() {
+async fn protected_workflow_changes_wait_for_a_second_approver() {
+    use crate::handlers::protection::{
+        approve_pending_workflow_change, get_pending_workflow_changes, protect_workflow,
+        reject_pending_workflow_change, unprotect_workflow,
+    };
+
     let (db, path) = test_db().await;
-    let run_id = seed_run(
-        &db,
-        "duplicate-result-drive",
-        json!({
-            "start": "start",
-            "nodes": [
-                { "id": "start", "kind": "start", "transitions": { "next": { "$node": "run" } } },
-                {
-                    "id": "run",
-                    "kind": "action",
-                    "action": { "provider": "test", "function": "execute", "configuration": {} },
-                    "transitions": { "next": { "$node": "end" } }
-                },
-                { "id": "end", "kind": "end" }
-            ]
-        }),
+    let db = Arc::new(db);
+    let events = crate::events::EventBus::new(
+        tokio::sync::broadcast::channel(16).0,
+        Arc::new(InMemoryBroker::new()),
+    );
+    let workflow_id = crate::repository::upsert_workflow(db.as_ref(), &workflow(None, "payroll"))
+        .await
+        .unwrap()
+        .id
+        .unwrap();
+    let principal = |id| AuthContext {
+        principal_id: Some(id),
+        is_admin: true,
+        kind: PrincipalKind::User,
+        org_id: None,
+        org_role: None,
+    };
+    let (author, reviewer) = (principal(Uuid::new_v4()), principal(Uuid::new_v4()));
+    let change_id = |body: &crate::models::ApiResponse| {
+        let body = serde_json::to_value(body).unwrap();
+        Uuid::parse_str(body["id"].as_str().unwrap()).unwrap()
+    };
+
+    // without a principal nobody could approve anything, so protection is refused.
+    let (status, _) = protect_workflow::<SqliteDb>(
+        Extension(db.clone()),
+        Extension(AuthContext::disabled_admin()),
+        Path(workflow_id),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = protect_workflow::<SqliteDb>(
+        Extension(db.clone()),
+        Extension(author.clone()),
+        Path(workflow_id),
     )
     .await;
+    assert_eq!(status, StatusCode::OK);
 
-    drain_ready_nodes(&db).await;
-    let dispatch = db.fetch_pending_action_dispatches(10).await.unwrap()[0].clone();
-    db.mark_action_dispatch_published(dispatch.id)
-        .await
-        .unwrap();
-    let event = WorkflowResultEvent::status(
-        &dispatch.command,
-        WorkflowStatus::Succeeded,
-        Some(json!({ "ok": true })),
-        None,
+    let (status, Json(disable)) = crate::handlers::workflows::disable_workflow::<SqliteDb>(
+        Extension(db.clone()),
+        Extension(events.clone()),
+        Extension(author.clone()),
+        Path(workflow_id),
+    )
+    .await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    let disable = change_id(&disable);
+    // an id-less save matching the workflow's name is held too.
+    let (status, Json(edit)) = crate::handlers::workflows::upsert_workflow::<SqliteDb>(
+        Extension(db.clone()),
+        Extension(events.clone()),
+        Extension(author.clone()),
+        axum::http::HeaderMap::new(),
+        Json(workflow(None, "payroll")),
+    )
+    .await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    let edit = change_id(&edit);
+    assert!(
+        db.fetch_workflow(workflow_id)
+            .await
+            .unwrap()
+            .unwrap()
+            .enabled
+    );
+
+    let (status, Json(pending)) = get_pending_workflow_changes::<SqliteDb>(
+        Extension(db.clone()),
+        Extension(reviewer.clone()),
+        Path(workflow_id),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        serde_json::to_value(&pending)
+            .unwrap()
+            .as_array()
+            .unwrap()
+            .len(),
+        2
     );
+
+    let approve = |ctx: AuthContext, change| {
+        approve_pending_workflow_change::<SqliteDb>(
+            Extension(db.clone()),
+            Extension(events.clone()),
+            Extension(ctx),
+            Path((workflow_id, change)),
+        )
+    };
+    let (status, _) = approve(author.clone(), disable).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = approve(reviewer.clone(), disable).await;
+    assert_eq!(status, StatusCode::OK);
     assert!(
-        crate::repository::apply_workflow_result_event(&db, &event)
+        !db.fetch_workflow(workflow_id)
             .await
             .unwrap()
+            .unwrap()
+            .enabled
     );
-    drain_ready_nodes(&db).await;
-    let (run, _) = crate::repository::fetch_workflow_run(&db, run_id)
-        .await
-        .unwrap()
-        .unwrap();
-    assert_eq!(run.status, WorkflowStatus::Succeeded);
+    // an approved change cannot be applied twice.
+    let (status, _) = approve(reviewer.clone(), disable).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
 
-    // a redelivered duplicate can follow a crash that lost the first drive enqueue; it must still
-    // enqueue a drive even though the event itself is not re-applied.
+    let (status, _) = reject_pending_workflow_change::<SqliteDb>(
+        Extension(db.clone()),
+        Extension(author.clone()),
+        Path((workflow_id, edit)),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
     assert!(
-        !crate::repository::apply_workflow_result_event(&db, &event)
+        db.fetch_pending_workflow_changes(workflow_id)
             .await
             .unwrap()
+            .is_empty()
     );
-    let pending = db
-        .fetch_pending_ready_nodes(chrono::Utc::now(), 10)
-        .await
-        .unwrap();
+
+    let (status, Json(unprotect)) = unprotect_workflow::<SqliteDb>(
+        Extension(db.clone()),
+        Extension(author.clone()),
+        Path(workflow_id),
+    )
+    .await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    let (status, _) = approve(reviewer.clone(), change_id(&unprotect)).await;
+    assert_eq!(status, StatusCode::OK);
     assert!(
-        pending
-            .iter()
-            .any(|node| node.workflow_run_id == run_id && node.node_id == "run")
+        db.fetch_workflow_protection(workflow_id)
+            .await
+            .unwrap()
+            .is_none()
     );
 
+    // both the request and the review are audited, including the refused self-approval.
+    for (action, count) in [
+        ("workflow.change.requested", 3),
+        ("workflow.change.approved", 3),
+        ("workflow.change.rejected", 1),
+    ] {
+        let rows = db
+            .fetch_audit_log(None, Some(action.to_string()), 10)
+            .await
+            .unwrap();
+        assert_eq!(rows.len(), count, "{action}");
+    }
+
     let _ = std::fs::remove_file(path);
 }
 
+// an import or a trigger edit that would change a protected workflow is held like a direct save,
+// while the rest of the import still applies.
 #[tokio::test]
-async fn action_node_timeout_recovers_parked_run() {
-    let (db, path) = test_db().await;
-    let run_id = seed_run(
-        &db,
-        "action-timeout",
-        json!({
-            "start": "start",
-            "nodes": [
-                { "id": "start", "kind": "start", "transitions": { "next": { "$node": "run" } } },
-                {
-                    "id": "run",
-                    "kind": "action",
-                    "timeout_seconds": 1,
-                    "action": { "provider": "test", "function": "execute", "configuration": {} },
-                    "transitions": { "next": { "$node": "end" } }
-                },
-                { "id": "end", "kind": "end" }
-            ]
-        }),
-    )
-    .await;
+async fn imports_and_trigger_edits_of_a_protected_workflow_are_held() {
+    use crate::handlers::protection::approve_pending_workflow_change;
+    use crate::handlers::triggers::{delete_workflow_trigger, upsert_workflow_trigger};
 
-    drain_ready_nodes(&db).await;
-    let dispatch = db.fetch_pending_action_dispatches(10).await.unwrap()[0].clone();
-    db.mark_action_dispatch_published(dispatch.id)
+    let (db, path) = test_db().await;
+    let db = Arc::new(db);
+    let events = crate::events::EventBus::new(
+        tokio::sync::broadcast::channel(16).0,
+        Arc::new(InMemoryBroker::new()),
+    );
+    let workflow_id = crate::repository::upsert_workflow(db.as_ref(), &workflow(None, "payroll"))
         .await
+        .unwrap()
+        .id
         .unwrap();
-
-    // no worker result ever arrives; the armed timeout wake must settle the parked node.
-    tokio::time::sleep(Duration::from_millis(1500)).await;
-    drain_ready_nodes(&db).await;
-
-    let (run, nodes) = crate::repository::fetch_workflow_run(&db, run_id)
+    let principal = |id| AuthContext {
+        principal_id: Some(id),
+        is_admin: true,
+        kind: PrincipalKind::User,
+        org_id: None,
+        org_role: None,
+    };
+    let (author, reviewer) = (principal(Uuid::new_v4()), principal(Uuid::new_v4()));
+    crate::repository::protect_workflow(db.as_ref(), workflow_id, author.principal_id)
         .await
-        .unwrap()
         .unwrap();
-    let node_run = nodes.iter().find(|node| node.node_id == "run").unwrap();
-    assert_eq!(node_run.status, WorkflowStatus::TimedOut);
-    assert_eq!(run.status, WorkflowStatus::TimedOut);
-
-    let _ = std::fs::remove_file(path);
-}
 
-#[tokio::test]
-async fn action_node_fails_promptly_when_its_executing_worker_disconnects() {
-    use runinator_models::replicas::{ReplicaKind, ReplicaRegistrationRequest};
+    let mut edited = workflow(Some(workflow_id), "payroll");
+    edited.enabled = false;
+    let bundle = WorkflowBundle {
+        workflows: vec![edited, workflow(None, "ledger")],
+        triggers: vec![trigger(None, workflow_id)],
+        baselines: Vec::new(),
+    };
+    let (status, Json(imported)) =
+        crate::handlers::workflows::import_acknowledged_workflow_bundle::<SqliteDb>(
+            db.clone(),
+            events.clone(),
+            author.clone(),
+            bundle,
+            WorkflowImportOptions::default(),
+        )
+        .await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    let imported = serde_json::to_value(&imported).unwrap();
+    assert_eq!(imported["workflows"].as_array().unwrap().len(), 1);
+    assert_eq!(imported["workflows"][0]["name"], "ledger");
+    assert!(imported["triggers"].as_array().unwrap().is_empty());
+    assert!(
+        db.fetch_workflow(workflow_id)
+            .await
+            .unwrap()
+            .unwrap()
+            .enabled
+    );
 
-    let (db, path) = test_db().await;
-    // no timeout_seconds: only the armed liveness poll can catch the dead executor.
-    let run_id = seed_run(
-        &db,
-        "executor-disconnect",
-        json!({
-            "start": "start",
-            "nodes": [
-                { "id": "start", "kind": "start", "transitions": { "next": { "$node": "run" } } },
-                {
-                    "id": "run",
-                    "kind": "action",
-                    "action": { "provider": "test", "function": "execute", "configuration": {} },
-                    "transitions": { "next": { "$node": "end" } }
-                },
-                { "id": "end", "kind": "end" }
-            ]
-        }),
+    let (status, Json(held)) = upsert_workflow_trigger::<SqliteDb>(
+        Extension(db.clone()),
+        Extension(events.clone()),
+        Extension(author.clone()),
+        Path(workflow_id),
+        Json(trigger(None, workflow_id)),
     )
     .await;
-
-    drain_ready_nodes(&db).await;
-    let dispatch = db.fetch_pending_action_dispatches(10).await.unwrap()[0].clone();
-    db.mark_action_dispatch_published(dispatch.id)
-        .await
-        .unwrap();
-
-    // a worker claimed the execution, then went offline without releasing the claim (crash or
-    // grace-period abort), so the reducer must treat the holder as disconnected.
-    let node_run_id = dispatch.command.workflow_node_run_id;
-    let runtime_id = Uuid::new_v4().to_string();
-    let dead_replica = db
-        .register_replica(
-            ReplicaRegistrationRequest {
-                replica_type: ReplicaKind::Worker,
-                instance_id: "doomed-worker".into(),
-                runtime_id: runtime_id.clone(),
-                display_name: None,
-                host: None,
-                port: None,
-                base_path: None,
-                version: None,
-                attributes: runinator_models::json!({}),
-            },
-            None,
-            &AuthContext {
-                principal_id: None,
-                is_admin: true,
-                kind: PrincipalKind::User,
-                org_id: None,
-                org_role: None,
-            },
-        )
-        .await
-        .unwrap()
-        .replica_id;
+    assert_eq!(status, StatusCode::ACCEPTED);
     assert!(
-        db.claim_workflow_node_run_executor(
-            node_run_id,
-            dead_replica,
-            chrono::Utc::now(),
-            chrono::Utc::now() - chrono::Duration::seconds(60),
-        )
-        .await
-        .unwrap()
+        db.fetch_workflow_triggers(workflow_id)
+            .await
+            .unwrap()
+            .is_empty()
     );
-    db.mark_replica_offline(dead_replica, runtime_id)
+
+    let mut kinds: Vec<_> = db
+        .fetch_pending_workflow_changes(workflow_id)
         .await
-        .unwrap();
+        .unwrap()
+        .iter()
+        .map(|change| change.kind.as_str())
+        .collect();
+    kinds.sort_unstable();
+    assert_eq!(kinds, ["update", "upsert_trigger", "upsert_trigger"]);
 
-    // the armed liveness poll is 15 seconds out; fire an equivalent due wake instead of sleeping.
-    db.enqueue_ready_node(
-        runinator_models::orchestration::NewOrchestrationEvent::new(
-            run_id,
-            Some("run".into()),
-            "dispatch_liveness_poll",
-            json!({ "node_id": "run" }),
-        ),
-        "run".into(),
-        chrono::Utc::now(),
+    let held = serde_json::to_value(&held).unwrap();
+    let change_id = Uuid::parse_str(held["id"].as_str().unwrap()).unwrap();
+    let (status, _) = approve_pending_workflow_change::<SqliteDb>(
+        Extension(db.clone()),
+        Extension(events.clone()),
+        Extension(reviewer.clone()),
+        Path((workflow_id, change_id)),
     )
-    .await
-    .unwrap();
-    drain_ready_nodes(&db).await;
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let triggers = db.fetch_workflow_triggers(workflow_id).await.unwrap();
+    assert_eq!(triggers.len(), 1);
 
-    let (run, nodes) = crate::repository::fetch_workflow_run(&db, run_id)
-        .await
-        .unwrap()
-        .unwrap();
-    let node_run = nodes.iter().find(|node| node.node_id == "run").unwrap();
-    assert_eq!(node_run.status, WorkflowStatus::TimedOut);
-    // the dead worker's claim must be released, or the retry this schedules would be dropped as a
-    // duplicate delivery by whichever worker picks it up.
-    assert!(node_run.current_executor_replica_id.is_none());
-    assert_eq!(run.status, WorkflowStatus::TimedOut);
+    let (status, _) = delete_workflow_trigger::<SqliteDb>(
+        Extension(db.clone()),
+        Extension(events.clone()),
+        Extension(author.clone()),
+        Path(triggers[0].id.unwrap()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    assert_eq!(
+        db.fetch_workflow_triggers(workflow_id).await.unwrap().len(),
+        1
+    );
 
     let _ = std::fs::remove_file(path);
 }
 
-#[test]
-fn merges_json_objects() {
-    let defaults = json!({ "a": 1, "b": 2 });
-    let parameters = json!({ "b": 3, "c": 4 });
-    let merged = crate::repository::merge_json_object(&defaults, &parameters);
-    assert_eq!(merged, json!({ "a": 1, "b": 3, "c": 4 }));
+#[tokio::test]
+async fn wdl_evaluate_accepts_legacy_lowered_expression() {
+    let request = crate::handlers::wdl::EvaluateExpressionRequest {
+        expression: Some(json!({ "$concat": ["hello ", { "$ref": { "params": ["name"] } }] })),
+        source: None,
+        kind: WdlFragmentKind::Expression,
+        context: json!({ "input": { "name": "Ada" } }),
+    };
+
+    let Json(value) = crate::handlers::wdl::evaluate_expression(Json(request))
+        .await
+        .expect("evaluate");
+
+    assert_eq!(value, Value::from("hello Ada"));
 }
 
-#[test]
-fn registered_provider_items_become_provider_metadata() {
-    let providers = crate::provider_metadata_from_items(vec![json!({
-        "document": {
-            "name": "github",
-            "actions": [
-                { "function_name": "create_pr", "description": "Create a pull request" }
-            ]
-        }
-    })])
-    .expect("provider metadata parses");
+#[tokio::test]
+async fn wdl_evaluate_accepts_source_fragments() {
+    let request = crate::handlers::wdl::EvaluateExpressionRequest {
+        expression: None,
+        source: Some("params.count >= 3 && exists params.count".into()),
+        kind: WdlFragmentKind::Condition,
+        context: json!({ "input": { "count": 3 } }),
+    };
 
-    assert_eq!(providers[0].name, "github");
-    assert_eq!(providers[0].actions[0].function_name, "create_pr");
+    let Json(value) = crate::handlers::wdl::evaluate_expression(Json(request))
+        .await
+        .expect("evaluate");
+
+    assert_eq!(value, Value::from(true));
 }
 
-#[test]
-fn provider_metadata_becomes_registered_catalog_item() {
-    let item = crate::provider_catalog_item(&runinator_models::providers::ProviderMetadata {
-        name: "git".into(),
-        actions: vec![runinator_models::providers::ActionMetadata::new(
-            "diff", "Get diff",
-        )],
-        metadata: Default::default(),
-    });
+#[tokio::test]
+async fn get_node_kinds_returns_catalog_json() {
+    let (status, Json(response)) = crate::handlers::catalog_metadata::get_node_kinds().await;
 
-    assert_eq!(item["item_type"], "provider_metadata");
-    assert_eq!(item["document"]["name"], "git");
-    assert_eq!(item["document"]["actions"][0]["function_name"], "diff");
+    assert_eq!(status, StatusCode::OK);
+    let crate::models::ApiResponse::JsonValue(value) = response else {
+        panic!("node catalog response must be json");
+    };
+    assert_eq!(value.as_array().map(Vec::len), Some(35));
 }
 
-#[test]
-fn validate_workflow_returns_normalized_definition() {
-    let workflow = workflow(None, "validate");
-    let validated = crate::repository::validate_workflow_definition(&workflow).unwrap();
+#[tokio::test]
+async fn get_trigger_kinds_returns_catalog_json() {
+    let (status, Json(response)) = crate::handlers::catalog_metadata::get_trigger_kinds().await;
 
-    assert_eq!(validated.name, "validate");
-    assert_eq!(validated.definition.start.as_deref(), Some("start"));
+    assert_eq!(status, StatusCode::OK);
+    let crate::models::ApiResponse::JsonValue(value) = response else {
+        panic!("trigger catalog response must be json");
+    };
+    // cron, manual, chained, file_arrival, webhook.
+    assert_eq!(value.as_array().map(Vec::len), Some(5));
 }
 
-#[test]
-fn validate_workflow_rejects_invalid_definition_without_persistence() {
-    let mut workflow = workflow(None, "invalid");
-    workflow.definition = WorkflowGraph::from_value(json!({
-        "start": "start",
-        "nodes": [
-            { "id": "start", "kind": "start", "transitions": { "next": { "$node": "missing" } } },
-            { "id": "done", "kind": "end" }
-        ]
-    }))
-    .unwrap();
+#[tokio::test]
+async fn get_enum_catalogs_returns_catalog_json() {
+    let (status, Json(response)) = crate::handlers::catalog_metadata::get_enum_catalogs().await;
 
-    assert!(crate::repository::validate_workflow_definition(&workflow).is_err());
+    assert_eq!(status, StatusCode::OK);
+    let crate::models::ApiResponse::JsonValue(value) = response else {
+        panic!("enum catalog response must be json");
+    };
+    assert_eq!(value.as_array().map(Vec::len), Some(4));
 }
 
-#[test]
-fn validation_error_response_exposes_structured_type_diagnostic() {
-    let err = WorkflowValidationError::TypeDiagnostic(WorkflowTypeDiagnostic {
-        path: "action parameter 'config.name'".into(),
-        expected: "string".into(),
-        actual: "integer".into(),
-        message: "action parameter 'config.name' expected string, got integer".into(),
-    });
+#[tokio::test]
+async fn wdl_analyze_validates_source_fragments() {
+    let (db, path) = test_db().await;
+    let Json(diagnostics) = crate::handlers::wdl::analyze_wdl(
+        Extension(Arc::new(db)),
+        Json(crate::handlers::wdl::WdlSourceRequest {
+            source: "params.count >".into(),
+            fragment: Some(WdlFragmentKind::Condition),
+        }),
+    )
+    .await;
 
-    let (status, axum::Json(response)) = crate::responses::validation_error(&err);
-    assert_eq!(status, axum::http::StatusCode::BAD_REQUEST);
-    match response {
-        crate::models::ApiResponse::ApiError(error) => {
-            assert_eq!(
-                error.path.as_deref(),
-                Some("action parameter 'config.name'")
-            );
-            assert_eq!(error.expected.as_deref(), Some("string"));
-            assert_eq!(error.actual.as_deref(), Some("integer"));
-        }
-        _ => panic!("expected api error"),
-    }
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, "error");
+    let _ = std::fs::remove_file(path);
 }
 
 #[tokio::test]
-async fn export_all_includes_workflows_and_matching_triggers() {
+async fn workflow_runs_can_be_named_and_fetched_by_open_name() {
     let (db, path) = test_db().await;
-    let saved = crate::repository::upsert_workflow(&db, &workflow(None, "export-all"))
-        .await
-        .unwrap();
-    let workflow_id = saved.id.unwrap();
-    crate::repository::upsert_workflow_trigger(&db, &trigger(None, workflow_id))
-        .await
-        .unwrap();
-
-    let bundle = crate::repository::export_workflow_bundle(&db, None)
+    let workflow = crate::repository::upsert_workflow(&db, &workflow(None, "Ticket Work"))
         .await
         .unwrap();
+    let workflow_id = workflow.id.unwrap();
+    let open = crate::repository::create_workflow_run(
+        &db,
+        workflow_id,
+        json!({}),
+        false,
+        Some("Ticket Work: ITP-123".into()),
+        Default::default(),
+    )
+    .await
+    .unwrap();
+    let terminal = crate::repository::create_workflow_run(
+        &db,
+        workflow_id,
+        json!({}),
+        false,
+        Some("Ticket Work: ITP-123".into()),
+        Default::default(),
+    )
+    .await
+    .unwrap();
+    crate::repository::update_workflow_run_status(
+        &db,
+        terminal.id,
+        WorkflowStatus::Succeeded,
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
 
-    assert_eq!(bundle.workflows.len(), 1);
-    assert_eq!(bundle.workflows[0].id, Some(workflow_id));
-    assert_eq!(bundle.triggers.len(), 1);
-    assert_eq!(bundle.triggers[0].workflow_id, workflow_id);
+    let open_only =
+        crate::repository::fetch_workflow_runs_by_name(&db, "Ticket Work: ITP-123".into(), true)
+            .await
+            .unwrap();
+
+    assert_eq!(open.name.as_deref(), Some("Ticket Work: ITP-123"));
+    assert_eq!(
+        open_only.iter().map(|run| run.id).collect::<Vec<_>>(),
+        vec![open.id]
+    );
     let _ = std::fs::remove_file(path);
 }
 
 #[tokio::test]
-async fn export_one_includes_only_that_workflow_and_its_triggers() {
+async fn ready_node_processing_reduces_start_to_action_dispatch() {
     let (db, path) = test_db().await;
-    let first = crate::repository::upsert_workflow(&db, &workflow(None, "first"))
-        .await
-        .unwrap();
-    let second = crate::repository::upsert_workflow(&db, &workflow(None, "second"))
-        .await
-        .unwrap();
-    let first_id = first.id.unwrap();
-    let second_id = second.id.unwrap();
-    crate::repository::upsert_workflow_trigger(&db, &trigger(None, first_id))
-        .await
-        .unwrap();
-    crate::repository::upsert_workflow_trigger(&db, &trigger(None, second_id))
-        .await
-        .unwrap();
+    let mut workflow = workflow(None, "ready-reducer");
+    workflow.definition = WorkflowGraph::from_value(json!({
+        "start": "start",
+        "nodes": [
+            { "id": "start", "kind": "start", "transitions": { "next": { "$node": "run" } } },
+            {
+                "id": "run",
+                "kind": "action",
+                "action": {
+                    "provider": "test",
+                    "function": "execute",
+                    "configuration": { "message": "hello" }
+                },
+                "transitions": { "on_success": { "$node": "done" } }
+            },
+            { "id": "done", "kind": "end" }
+        ]
+    }))
+    .unwrap();
+    let workflow = db.upsert_workflow(&workflow).await.unwrap();
+    let run = crate::repository::create_workflow_run(
+        &db,
+        workflow.id.unwrap(),
+        json!({}),
+        false,
+        None,
+        Default::default(),
+    )
+    .await
+    .unwrap();
+    let ready = crate::repository::claim_ready_nodes(
+        &db,
+        "scheduler-a".into(),
+        chrono::Utc::now() + chrono::Duration::seconds(30),
+        10,
+    )
+    .await
+    .unwrap();
+    assert_eq!(ready.len(), 1);
 
-    let bundle = crate::repository::export_workflow_bundle(&db, Some(second_id))
+    crate::repository::complete_ready_node(&db, ready[0].id, "scheduler-a".into(), None)
         .await
         .unwrap();
 
-    assert_eq!(bundle.workflows.len(), 1);
-    assert_eq!(bundle.workflows[0].id, Some(second_id));
-    assert_eq!(bundle.triggers.len(), 1);
-    assert_eq!(bundle.triggers[0].workflow_id, second_id);
-    let _ = std::fs::remove_file(path);
-}
-
-#[tokio::test]
-async fn import_upserts_workflows_before_triggers() {
-    let (db, path) = test_db().await;
-    let wf_id = Uuid::now_v7();
-    let trig_id = Uuid::now_v7();
-    let bundle = WorkflowBundle {
-        workflows: vec![workflow(Some(wf_id), "imported")],
-        triggers: vec![trigger(Some(trig_id), wf_id)],
-    };
-
-    let saved = crate::repository::import_workflow_bundle(&db, bundle)
+    let (updated, nodes) = crate::repository::fetch_workflow_run(&db, run.id)
         .await
+        .unwrap()
         .unwrap();
+    assert_eq!(updated.status, WorkflowStatus::Running);
+    assert_eq!(updated.active_node_id.as_deref(), Some("run"));
+    assert!(
+        nodes
+            .iter()
+            .any(|node| node.node_id == "run" && node.status == WorkflowStatus::Running)
+    );
+    let dispatches = db.fetch_pending_action_dispatches(10).await.unwrap();
+    assert_eq!(dispatches.len(), 1);
+    assert_eq!(dispatches[0].command.node_id, "run");
 
-    assert_eq!(saved.workflows[0].id, Some(wf_id));
-    assert_eq!(saved.triggers[0].id, Some(trig_id));
-    assert!(db.fetch_workflow(wf_id).await.unwrap().is_some());
-    assert_eq!(db.fetch_workflow_triggers(wf_id).await.unwrap().len(), 1);
     let _ = std::fs::remove_file(path);
 }
 
 #[tokio::test]
-async fn import_skips_workflow_when_name_already_exists() {
+async fn output_nodes_write_automation_events_for_the_events_tab() {
     let (db, path) = test_db().await;
-    let first = WorkflowBundle {
-        workflows: vec![workflow(None, "Core Team SDLC Pipeline")],
-        triggers: vec![],
-    };
-    let initial = crate::repository::import_workflow_bundle(&db, first)
-        .await
-        .unwrap();
-    let initial_version = initial.workflows[0].version;
-    let initial_definition = initial.workflows[0].definition.clone();
-    let mut changed = workflow(None, "Core Team SDLC Pipeline");
-    changed.version = runinator_models::semver::SemVer::new(2, 0, 0);
-    changed.definition = WorkflowGraph::from_value(json!({
-        "start": "done",
+    let mut workflow = workflow(None, "output-events");
+    workflow.definition = WorkflowGraph::from_value(json!({
+        "start": "start",
         "nodes": [
+            { "id": "start", "kind": "start", "transitions": { "next": { "$node": "output" } } },
+            {
+                "id": "output",
+                "kind": "output",
+                "parameters": {
+                    "event_type": "workflow.routed",
+                    "data": { "ok": true, "count": 1 }
+                },
+                "transitions": { "next": { "$node": "done" } }
+            },
             { "id": "done", "kind": "end" }
         ]
     }))
     .unwrap();
-    let second = WorkflowBundle {
-        workflows: vec![changed.clone()],
-        triggers: vec![],
-    };
+    let workflow = db.upsert_workflow(&workflow).await.unwrap();
+    let run = crate::repository::create_workflow_run(
+        &db,
+        workflow.id.unwrap(),
+        json!({}),
+        false,
+        None,
+        Default::default(),
+    )
+    .await
+    .unwrap();
+    let ready = crate::repository::claim_ready_nodes(
+        &db,
+        "scheduler-a".into(),
+        chrono::Utc::now() + chrono::Duration::seconds(30),
+        10,
+    )
+    .await
+    .unwrap();
+    assert_eq!(ready.len(), 1);
 
-    let saved = crate::repository::import_workflow_bundle(&db, second)
+    crate::repository::complete_ready_node(&db, ready[0].id, "scheduler-a".into(), None)
         .await
         .unwrap();
-    let workflows = db.fetch_workflows().await.unwrap();
 
-    // re-importing the same workflow name leaves the existing row untouched.
-    assert_eq!(workflows.len(), 1);
-    assert_eq!(saved.workflows[0].id, workflows[0].id);
-    assert_eq!(workflows[0].name, "Core Team SDLC Pipeline");
-    assert_eq!(workflows[0].version, initial_version);
-    assert_eq!(workflows[0].definition, initial_definition);
+    let events = db
+        .fetch_automation_records("automation_events".into(), Some(run.id), None)
+        .await
+        .unwrap();
+    assert_eq!(events.len(), 1);
+    let event = &events[0];
+    let metadata = event
+        .get("metadata")
+        .and_then(Value::as_object)
+        .cloned()
+        .unwrap_or_default();
+    assert_eq!(
+        event.get("event_type").and_then(Value::as_str),
+        Some("workflow.routed")
+    );
+    assert_eq!(
+        event.get("provider").and_then(Value::as_str),
+        Some("runinator")
+    );
+    assert_eq!(
+        event.get("status").and_then(Value::as_str),
+        Some("output_recorded")
+    );
+    assert_eq!(
+        metadata
+            .get("data")
+            .and_then(Value::as_object)
+            .and_then(|data| data.get("ok"))
+            .and_then(Value::as_bool),
+        Some(true)
+    );
+
     let _ = std::fs::remove_file(path);
 }
 
 #[tokio::test]
-async fn import_overwrite_updates_existing_workflow_in_place() {
+async fn pure_compute_node_reruns_in_loop_body() {
     let (db, path) = test_db().await;
-    let first = WorkflowBundle {
-        workflows: vec![workflow(None, "Core Team SDLC Pipeline")],
-        triggers: vec![],
-    };
-    let initial = crate::repository::import_workflow_bundle(&db, first)
-        .await
-        .unwrap();
-    let existing_id = initial.workflows[0].id;
-    assert_ne!(
-        initial.workflows[0].version,
-        runinator_models::semver::SemVer::new(2, 0, 0)
-    );
-
-    // an explicit re-apply carries no id and no newer timestamp, but overwrite must still win.
-    let mut changed = workflow(None, "Core Team SDLC Pipeline");
-    changed.version = runinator_models::semver::SemVer::new(2, 0, 0);
-    changed.definition = WorkflowGraph::from_value(json!({
-        "start": "done",
+    let mut workflow = workflow(None, "loop-compute");
+    workflow.definition = WorkflowGraph::from_value(json!({
+        "start": "start",
         "nodes": [
-            { "id": "done", "kind": "end" }
-        ]
-    }))
-    .unwrap();
-    let second = WorkflowBundle {
-        workflows: vec![changed.clone()],
-        triggers: vec![],
-    };
-
-    let saved = crate::repository::import_workflow_bundle_with(&db, second, true)
-        .await
-        .unwrap();
-    let workflows = db.fetch_workflows().await.unwrap();
-
-    // the existing row is updated in place: same id, bumped version, no duplicate row. the skip
-    // path would have left the stored version unchanged, so version == 2 proves the overwrite.
-    assert_eq!(workflows.len(), 1);
-    assert_eq!(saved.workflows[0].id, existing_id);
-    assert_eq!(workflows[0].id, existing_id);
-    assert_eq!(
-        workflows[0].version,
-        runinator_models::semver::SemVer::new(2, 0, 0)
-    );
-    let _ = std::fs::remove_file(path);
-}
-
-#[tokio::test]
-async fn import_pipeline_creates_managed_chained_triggers_idempotently() {
-    use runinator_models::pipelines::{
-        PipelineBundle, PipelineLinkSelector, PipelineLinkSpec, PipelineSpec,
-    };
-
-    let (db, path) = test_db().await;
-    // import the two member workflows first so the pipeline can resolve their names to ids.
-    let members = WorkflowBundle {
-        workflows: vec![
-            workflow(None, "SDLC: Development"),
-            workflow(None, "SDLC: Review"),
-        ],
-        triggers: vec![],
-    };
-    crate::repository::import_workflow_bundle(&db, members)
-        .await
-        .unwrap();
-
-    let bundle = PipelineBundle {
-        pipelines: vec![PipelineSpec {
-            name: "Core SDLC".into(),
-            description: Some("test".into()),
-            defaults: Default::default(),
-            members: vec!["SDLC: Development".into(), "SDLC: Review".into()],
-            links: vec![PipelineLinkSpec {
-                from: "SDLC: Development".into(),
-                to: "SDLC: Review".into(),
-                on: PipelineLinkSelector::Complete,
-                enabled: true,
-            }],
-            triggers: vec![],
-        }],
-    };
+            { "id": "start", "kind": "start", "transitions": { "next": { "$node": "each" } } },
+            {
+                "id": "each",
+                "kind": "loop",
+                "parameters": { "items": { "$ref": { "input": ["xs"] } } },
+                "max_iterations": 10,
+                "transitions": {
+                    "next": { "$node": "double" },
+                    "on_success": { "$node": "done" }
+                }
+            },
+            {
+                "id": "double",
+                "kind": "action",
+                "action": {
+                    "provider": "std",
+                    "function": "run",
+                    "configuration": {
+                        "program": [
+                            { "$return": { "$mul": [{ "$ref": { "node": "each", "output": ["item"] } }, 2] } }
+                        ]
+                    }
+                },
+                "transitions": { "on_success": { "$node": "each" } }
+            },
+            { "id": "done", "kind": "end" }
+        ]
+    }))
+    .unwrap();
+    let workflow = db.upsert_workflow(&workflow).await.unwrap();
+    let run = crate::repository::create_workflow_run(
+        &db,
+        workflow.id.unwrap(),
+        json!({ "xs": [1, 2, 3] }),
+        false,
+        None,
+        Default::default(),
+    )
+    .await
+    .unwrap();
 
-    // first import: creates the pipeline and one managed chained trigger on the source workflow.
-    let imported = crate::repository::import_pipeline_bundle_with(&db, &bundle, None)
-        .await
-        .unwrap();
-    assert_eq!(imported.len(), 1);
-    let pipeline_id = imported[0].id.expect("pipeline id");
-    assert_eq!(imported[0].workflow_ids.len(), 2);
+    drain_ready_nodes(&db).await;
 
-    let dev_id = db
-        .fetch_workflow_by_name("SDLC: Development".into())
+    let (updated, nodes) = crate::repository::fetch_workflow_run(&db, run.id)
         .await
         .unwrap()
-        .unwrap()
-        .id
         .unwrap();
-    let triggers = db.fetch_workflow_triggers(dev_id).await.unwrap();
-    assert_eq!(triggers.len(), 1);
-    let trigger = &triggers[0];
-    assert_eq!(
-        trigger
-            .configuration
-            .pointer("/target_workflow")
-            .and_then(Value::as_str),
-        Some("SDLC: Review")
-    );
-    assert_eq!(
-        trigger.configuration.pointer("/on").and_then(Value::as_str),
-        Some("complete")
-    );
-    // the frontend keys pipeline membership off configuration.pipeline_id.
-    assert_eq!(
-        trigger
-            .configuration
-            .pointer("/pipeline_id")
-            .and_then(Value::as_str),
-        Some(pipeline_id.to_string().as_str())
+    assert_eq!(updated.status, WorkflowStatus::Succeeded);
+    // the compute body ran once per item, re-creating a fresh node run each iteration.
+    let runs = nodes
+        .iter()
+        .filter(|node| node.node_id == "double" && node.status == WorkflowStatus::Succeeded)
+        .count();
+    assert_eq!(runs, 3, "compute body should run once per loop item");
+    // and never dispatched to a worker.
+    assert!(
+        db.fetch_pending_action_dispatches(10)
+            .await
+            .unwrap()
+            .is_empty()
     );
 
-    // re-import: reconciles in place — no duplicate pipeline and no duplicate trigger.
-    let reimported = crate::repository::import_pipeline_bundle_with(&db, &bundle, None)
-        .await
-        .unwrap();
-    assert_eq!(reimported[0].id, Some(pipeline_id));
-    assert_eq!(db.fetch_pipelines().await.unwrap().len(), 1);
-    assert_eq!(db.fetch_workflow_triggers(dev_id).await.unwrap().len(), 1);
-
     let _ = std::fs::remove_file(path);
 }
 
 #[tokio::test]
-async fn manual_pipeline_run_starts_entry_member_chains_and_settles() {
-    use runinator_models::pipelines::{
-        PipelineBundle, PipelineLinkSelector, PipelineLinkSpec, PipelineSpec,
-    };
-    use runinator_models::workflows::WorkflowStatus;
-
+async fn pure_compute_node_reduces_in_process_without_dispatch() {
     let (db, path) = test_db().await;
-    let members = WorkflowBundle {
-        workflows: vec![workflow(None, "Build"), workflow(None, "Deploy")],
-        triggers: vec![],
-    };
-    crate::repository::import_workflow_bundle(&db, members)
-        .await
-        .unwrap();
-
-    // Build -> Deploy on complete: Build is the sole entry member, Deploy is downstream.
-    let bundle = PipelineBundle {
-        pipelines: vec![PipelineSpec {
-            name: "Release".into(),
-            description: None,
-            defaults: Default::default(),
-            members: vec!["Build".into(), "Deploy".into()],
-            links: vec![PipelineLinkSpec {
-                from: "Build".into(),
-                to: "Deploy".into(),
-                on: PipelineLinkSelector::Complete,
-                enabled: true,
-            }],
-            triggers: vec![],
-        }],
-    };
-    let imported = crate::repository::import_pipeline_bundle_with(&db, &bundle, None)
-        .await
-        .unwrap();
-    let pipeline_id = imported[0].id.expect("pipeline id");
-
-    // start a manual pipeline run and drive the whole member graph to completion.
-    let run = crate::repository::create_manual_pipeline_run(
+    let mut workflow = workflow(None, "pure-compute");
+    workflow.definition = WorkflowGraph::from_value(json!({
+        "start": "start",
+        "nodes": [
+            { "id": "start", "kind": "start", "transitions": { "next": { "$node": "calc" } } },
+            {
+                "id": "calc",
+                "kind": "action",
+                "action": {
+                    "provider": "std",
+                    "function": "run",
+                    "configuration": {
+                        "program": [
+                            { "$let": "total", "value": { "$add": [{ "$ref": { "input": ["a"] } }, 3] } },
+                            { "$return": { "total": { "$ref": { "let": ["total"] } } } }
+                        ]
+                    }
+                },
+                "transitions": { "on_success": { "$node": "done" } }
+            },
+            { "id": "done", "kind": "end" }
+        ]
+    }))
+    .unwrap();
+    let workflow = db.upsert_workflow(&workflow).await.unwrap();
+    let run = crate::repository::create_workflow_run(
         &db,
-        pipeline_id,
-        json!({}),
+        workflow.id.unwrap(),
+        json!({ "a": 4 }),
+        false,
         None,
-        Some("test".into()),
+        Default::default(),
     )
     .await
     .unwrap();
+
     drain_ready_nodes(&db).await;
 
-    // both members ran, each tagged with the pipeline run, and the chained Deploy inherited the tag.
-    let members = db
-        .fetch_workflow_runs_for_pipeline_run(run.id)
+    let (updated, nodes) = crate::repository::fetch_workflow_run(&db, run.id)
         .await
+        .unwrap()
         .unwrap();
-    assert_eq!(members.len(), 2, "entry member + chained downstream member");
-    assert!(members.iter().all(|m| m.pipeline_run_id == Some(run.id)));
+    // the pure compute node reduced in-process and the run reached the end node.
+    assert_eq!(updated.status, WorkflowStatus::Succeeded);
+    let calc = nodes.iter().find(|node| node.node_id == "calc").unwrap();
+    assert_eq!(calc.status, WorkflowStatus::Succeeded);
+    assert_eq!(calc.output_json, Some(json!({ "total": 7 })));
+    // no worker dispatch was enqueued for the pure node.
     assert!(
-        members
-            .iter()
-            .all(|m| m.status == WorkflowStatus::Succeeded)
+        db.fetch_pending_action_dispatches(10)
+            .await
+            .unwrap()
+            .is_empty()
     );
 
-    // the pipeline run settles Succeeded once the reachable graph is terminal.
-    let settled = db.fetch_pipeline_run(run.id).await.unwrap().unwrap();
-    assert_eq!(settled.status, WorkflowStatus::Succeeded);
-
     let _ = std::fs::remove_file(path);
 }
 
 #[tokio::test]
-async fn import_upserts_existing_workflow_when_id_is_present() {
+async fn compute_goto_sets_active_node() {
     let (db, path) = test_db().await;
-    let first = WorkflowBundle {
-        workflows: vec![workflow(None, "Core Team SDLC Pipeline")],
-        triggers: vec![],
-    };
-    let initial = crate::repository::import_workflow_bundle(&db, first)
-        .await
-        .unwrap();
-    let existing_id = initial.workflows[0].id;
-
-    // a save from the command center carries the existing id and must overwrite.
-    let mut changed = initial.workflows[0].clone();
-    changed.version = runinator_models::semver::SemVer::new(2, 0, 0);
-    changed.definition = WorkflowGraph::from_value(json!({
-        "start": "done",
+    let mut workflow = workflow(None, "compute-goto");
+    workflow.definition = WorkflowGraph::from_value(json!({
+        "start": "start",
         "nodes": [
+            { "id": "start", "kind": "start", "transitions": { "next": { "$node": "gate" } } },
+            {
+                "id": "gate",
+                "kind": "action",
+                "action": {
+                    "provider": "std",
+                    "function": "run",
+                    "configuration": {
+                        "program": [
+                            { "$if": { "value": { "$ref": { "input": ["x"] } }, "less_than": 0 },
+                              "then": [ { "$goto": "fail" } ],
+                              "else": [] },
+                            { "$return": "ok" }
+                        ]
+                    }
+                },
+                "transitions": { "on_success": { "$node": "done" } }
+            },
+            { "id": "fail", "kind": "fail" },
             { "id": "done", "kind": "end" }
         ]
     }))
     .unwrap();
-    let second = WorkflowBundle {
-        workflows: vec![changed.clone()],
-        triggers: vec![],
-    };
+    let workflow = db.upsert_workflow(&workflow).await.unwrap();
+    let run = crate::repository::create_workflow_run(
+        &db,
+        workflow.id.unwrap(),
+        json!({ "x": -1 }),
+        false,
+        None,
+        Default::default(),
+    )
+    .await
+    .unwrap();
 
-    let saved = crate::repository::import_workflow_bundle(&db, second)
+    drain_ready_nodes(&db).await;
+
+    let (updated, _) = crate::repository::fetch_workflow_run(&db, run.id)
         .await
+        .unwrap()
         .unwrap();
-    let workflows = db.fetch_workflows().await.unwrap();
+    // goto fail routed the run to the fail node, ending the run as failed.
+    assert_eq!(updated.status, WorkflowStatus::Failed);
 
-    assert_eq!(workflows.len(), 1);
-    assert_eq!(saved.workflows[0].id, existing_id);
-    // an upsert bumps the version to 2; a skip would have left it at 1.
-    assert_eq!(
-        workflows[0].version,
-        runinator_models::semver::SemVer::new(2, 0, 0)
-    );
-    assert_eq!(
-        saved.workflows[0].version,
-        runinator_models::semver::SemVer::new(2, 0, 0)
-    );
     let _ = std::fs::remove_file(path);
 }
 
 #[tokio::test]
-async fn import_overwrites_id_less_workflow_when_incoming_is_newer() {
+async fn action_failure_schedules_retry_with_backoff() {
     let (db, path) = test_db().await;
-    let first = WorkflowBundle {
-        workflows: vec![workflow(None, "pack")],
-        triggers: vec![],
-    };
-    crate::repository::import_workflow_bundle(&db, first)
-        .await
-        .unwrap();
-
-    // a pack import carrying a future updated_at is newer than the stored copy.
-    let mut newer = workflow(None, "pack");
-    newer.version = runinator_models::semver::SemVer::new(5, 0, 0);
-    newer.updated_at = chrono::DateTime::from_timestamp(4_102_444_800, 0);
-    let saved = crate::repository::import_workflow_bundle(
+    let mut workflow = workflow(None, "action-retry");
+    workflow.definition = WorkflowGraph::from_value(json!({
+        "start": "start",
+        "nodes": [
+            { "id": "start", "kind": "start", "transitions": { "next": { "$node": "run" } } },
+            {
+                "id": "run",
+                "kind": "action",
+                "action": {
+                    "provider": "test",
+                    "function": "execute",
+                    "configuration": { "message": "hello" }
+                },
+                "retry": { "max_attempts": 3 },
+                "transitions": { "on_failure": { "$node": "failed" } }
+            },
+            { "id": "failed", "kind": "fail" },
+            { "id": "end", "kind": "end" }
+        ]
+    }))
+    .unwrap();
+    let workflow = db.upsert_workflow(&workflow).await.unwrap();
+    let run = crate::repository::create_workflow_run(
         &db,
-        WorkflowBundle {
-            workflows: vec![newer],
-            triggers: vec![],
-        },
+        workflow.id.unwrap(),
+        json!({}),
+        false,
+        None,
+        Default::default(),
     )
     .await
     .unwrap();
-    let workflows = db.fetch_workflows().await.unwrap();
-
-    assert_eq!(workflows.len(), 1);
-    assert_eq!(
-        workflows[0].version,
-        runinator_models::semver::SemVer::new(5, 0, 0)
-    );
-    assert_eq!(
-        saved.workflows[0].version,
-        runinator_models::semver::SemVer::new(5, 0, 0)
-    );
-    let _ = std::fs::remove_file(path);
-}
 
-#[tokio::test]
-async fn import_skips_id_less_workflow_when_incoming_is_older() {
-    let (db, path) = test_db().await;
-    let first = WorkflowBundle {
-        workflows: vec![workflow(None, "pack")],
-        triggers: vec![],
-    };
-    let initial = crate::repository::import_workflow_bundle(&db, first)
+    drain_ready_nodes(&db).await;
+    let dispatch = db.fetch_pending_action_dispatches(10).await.unwrap()[0].clone();
+    db.mark_action_dispatch_published(dispatch.id)
+        .await
+        .unwrap();
+    let event = WorkflowResultEvent::status(&dispatch.command, WorkflowStatus::Failed, None, None);
+    crate::repository::apply_workflow_result_event(&db, &event)
         .await
         .unwrap();
-    let initial_version = initial.workflows[0].version;
 
-    // a pack import carrying a past updated_at is older than the stored copy.
-    let mut older = workflow(None, "pack");
-    older.version = runinator_models::semver::SemVer::new(5, 0, 0);
-    older.updated_at = chrono::DateTime::from_timestamp(1, 0);
-    crate::repository::import_workflow_bundle(
-        &db,
-        WorkflowBundle {
-            workflows: vec![older],
-            triggers: vec![],
-        },
-    )
-    .await
-    .unwrap();
-    let workflows = db.fetch_workflows().await.unwrap();
+    drain_ready_nodes(&db).await;
+
+    let (updated, nodes) = crate::repository::fetch_workflow_run(&db, run.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(updated.status, WorkflowStatus::Waiting);
+    assert_eq!(updated.active_node_id.as_deref(), Some("run"));
+    let run_node = nodes.iter().find(|node| node.node_id == "run").unwrap();
+    assert_eq!(run_node.status, WorkflowStatus::Queued);
+    assert_eq!(run_node.attempt, 1);
+    let retry_ready = db
+        .fetch_pending_ready_nodes(chrono::Utc::now(), 10)
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|ready| ready.workflow_run_id == run.id && ready.node_id == "run")
+        .expect("retry ready node is pending");
+    assert!(retry_ready.ready_at > chrono::Utc::now());
+    assert!(
+        db.fetch_pending_action_dispatches(10)
+            .await
+            .unwrap()
+            .is_empty()
+    );
 
-    assert_eq!(workflows.len(), 1);
-    assert_eq!(workflows[0].version, initial_version);
     let _ = std::fs::remove_file(path);
 }
 
+// a provider error marked non-retryable skips the node's remaining attempts.
 #[tokio::test]
-async fn duplicate_workflow_creates_bumped_sibling() {
+async fn permanent_provider_error_skips_retry() {
+    use runinator_comm::WireCodec;
+    use runinator_models::runs::ProviderError;
+    use runinator_models::workflow_state::TaskStatusOutput;
+
     let (db, path) = test_db().await;
-    let initial = crate::repository::import_workflow_bundle(
-        &db,
-        WorkflowBundle {
-            workflows: vec![workflow(None, "Core Team SDLC Pipeline")],
-            triggers: vec![],
-        },
-    )
-    .await
+    let mut workflow = workflow(None, "action-permanent-failure");
+    workflow.definition = WorkflowGraph::from_value(json!({
+        "start": "start",
+        "nodes": [
+            { "id": "start", "kind": "start", "transitions": { "next": { "$node": "run" } } },
+            {
+                "id": "run",
+                "kind": "action",
+                "action": { "provider": "test", "function": "execute", "configuration": {} },
+                "retry": { "max_attempts": 3 },
+                "transitions": { "on_failure": { "$node": "failed" } }
+            },
+            { "id": "failed", "kind": "fail" },
+            { "id": "end", "kind": "end" }
+        ]
+    }))
     .unwrap();
-    let original = initial.workflows[0].clone();
-    let original_id = original.id.unwrap();
-
-    let copy = crate::repository::duplicate_workflow(
+    let workflow = db.upsert_workflow(&workflow).await.unwrap();
+    let run = crate::repository::create_workflow_run(
         &db,
-        original_id,
-        runinator_models::semver::SemVerBump::Minor,
+        workflow.id.unwrap(),
+        json!({}),
+        false,
+        None,
+        Default::default(),
     )
     .await
     .unwrap();
-    let workflows = db.fetch_workflows().await.unwrap();
 
-    // a new disabled row sharing the name, with the minor version bumped.
-    assert_eq!(workflows.len(), 2);
-    assert_ne!(copy.id, original.id);
-    assert_eq!(copy.name, original.name);
-    assert!(!copy.enabled);
-    assert_eq!(
-        copy.version,
-        original
-            .version
-            .bump(runinator_models::semver::SemVerBump::Minor)
+    drain_ready_nodes(&db).await;
+    let dispatch = db.fetch_pending_action_dispatches(10).await.unwrap()[0].clone();
+    db.mark_action_dispatch_published(dispatch.id)
+        .await
+        .unwrap();
+    let error =
+        ProviderError::permanent("test.credentials", "token rejected").with_detail("status", 401);
+    let output = TaskStatusOutput {
+        success: false,
+        duration_ms: Some(5),
+        message: Some(error.to_string()),
+        error: Some(error),
+    }
+    .to_wire_value()
+    .unwrap();
+    let event = WorkflowResultEvent::status(
+        &dispatch.command,
+        WorkflowStatus::Failed,
+        Some(output),
+        Some("test.credentials - token rejected".into()),
     );
+    crate::repository::apply_workflow_result_event(&db, &event)
+        .await
+        .unwrap();
+    drain_ready_nodes(&db).await;
+
+    let (updated, nodes) = crate::repository::fetch_workflow_run(&db, run.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(updated.status, WorkflowStatus::Failed);
+    let run_node = nodes.iter().find(|node| node.node_id == "run").unwrap();
+    assert_eq!(run_node.status, WorkflowStatus::Failed);
+    assert_eq!(run_node.attempt, 1);
+    let recorded = run_node.output_json.as_ref().unwrap();
+    assert_eq!(recorded["error"]["code"], "test.credentials");
+    assert_eq!(recorded["error"]["retryable"], false);
+    assert_eq!(recorded["error"]["details"]["status"], 401);
+
     let _ = std::fs::remove_file(path);
 }
 
 #[tokio::test]
-async fn result_consumer_acks_duplicate_deliveries_and_persists_results_once() {
+async fn action_retry_republishes_dispatch_after_backoff() {
     let (db, path) = test_db().await;
-    let db = Arc::new(db);
-    let node_run = create_node_run(&db).await;
-    let command = action_command(node_run.workflow_run_id, node_run.id, &node_run.node_id);
-    let chunk = WorkflowResultEvent::chunk(
-        &command,
-        NewRunChunk {
-            stream: "log".into(),
-            content: "hello".into(),
-        },
-    );
-    let status = WorkflowResultEvent::status(
-        &command,
-        WorkflowStatus::Succeeded,
-        Some(json!({ "ok": true })),
-        Some("done".into()),
-    );
-    let artifact = WorkflowResultEvent::artifact(
-        &command,
-        NewRunArtifact {
-            name: "report.json".into(),
-            mime_type: "application/json".into(),
-            size_bytes: 17,
-            uri: "memory://report.json".into(),
-            metadata: json!({ "source": "test" }),
-        },
-    );
-    let broker = Arc::new(RecordingBroker::new());
-    let broker_for_consumer: Arc<dyn Broker> = broker.clone();
-    let publisher = runinator_engine::EnginePublisher::new(broker_for_consumer.clone());
-    let shutdown = Arc::new(Notify::new());
-    let consumer = tokio::spawn(crate::result_consumer::run_result_consumer(
-        db.clone(),
-        broker_for_consumer,
-        publisher,
-        shutdown.clone(),
-    ));
-
-    publish_duplicate_results(&broker, &[chunk.clone(), status.clone(), artifact.clone()]).await;
-    wait_until(|| broker.result_acks().len() == 6).await;
+    let run_id = seed_run(
+        &db,
+        "action-retry-redispatch",
+        json!({
+            "start": "start",
+            "nodes": [
+                { "id": "start", "kind": "start", "transitions": { "next": { "$node": "run" } } },
+                {
+                    "id": "run",
+                    "kind": "action",
+                    "action": { "provider": "test", "function": "execute", "configuration": {} },
+                    "retry": { "max_attempts": 3 },
+                    "transitions": { "on_failure": { "$node": "failed" } }
+                },
+                { "id": "failed", "kind": "fail" },
+                { "id": "end", "kind": "end" }
+            ]
+        }),
+    )
+    .await;
 
-    shutdown.notify_waiters();
-    tokio::time::timeout(Duration::from_secs(1), consumer)
+    drain_ready_nodes(&db).await;
+    let dispatch = db.fetch_pending_action_dispatches(10).await.unwrap()[0].clone();
+    db.mark_action_dispatch_published(dispatch.id)
         .await
-        .unwrap()
         .unwrap();
-
-    let chunks = db
-        .fetch_workflow_node_run_chunks(node_run.id, None, 100)
+    let event = WorkflowResultEvent::status(&dispatch.command, WorkflowStatus::Failed, None, None);
+    crate::repository::apply_workflow_result_event(&db, &event)
         .await
         .unwrap();
-    assert_eq!(chunks.len(), 1);
-    assert_eq!(chunks[0].stream, "log");
-    assert_eq!(chunks[0].content, "hello");
+    drain_ready_nodes(&db).await;
 
-    let node_run = db
-        .fetch_workflow_node_run(node_run.id)
-        .await
-        .unwrap()
-        .unwrap();
-    assert_eq!(node_run.status, WorkflowStatus::Succeeded);
-    assert_eq!(node_run.output_json, Some(json!({ "ok": true })));
-    assert_eq!(node_run.message.as_deref(), Some("done"));
+    // wait out the first retry backoff, then drive the retry ready node to its re-dispatch.
+    tokio::time::sleep(Duration::from_millis(1500)).await;
+    drain_ready_nodes(&db).await;
 
-    let artifacts = db
-        .fetch_workflow_node_run_artifacts(node_run.id)
+    // the retried attempt must publish a fresh outbox row; reusing the first attempt's dedupe key
+    // would collide with the already-published row and park the run in `running` forever.
+    let pending = db.fetch_pending_action_dispatches(10).await.unwrap();
+    assert_eq!(pending.len(), 1, "retry must enqueue a fresh dispatch");
+    assert_eq!(pending[0].command.attempt, 2);
+    assert_ne!(pending[0].dedupe_key, dispatch.dedupe_key);
+    let (run, _) = crate::repository::fetch_workflow_run(&db, run_id)
         .await
+        .unwrap()
         .unwrap();
-    assert_eq!(artifacts.len(), 1);
-    assert_eq!(artifacts[0].name, "report.json");
-    assert_eq!(artifacts[0].uri, "memory://report.json");
+    assert_eq!(run.status, WorkflowStatus::Running);
 
-    let received = broker.result_receives();
-    let acked = broker.result_acks();
-    assert_eq!(received.len(), 6);
-    assert_eq!(acked.len(), 6);
-    assert_eq!(received, acked);
-    assert!(broker.result_nacks().is_empty());
     let _ = std::fs::remove_file(path);
 }
 
 #[tokio::test]
-async fn result_consumer_dead_letters_poison_result_events_after_retries() {
+async fn duplicate_terminal_result_event_still_enqueues_drive() {
     let (db, path) = test_db().await;
-    let db = Arc::new(db);
-    let node_run = create_node_run(&db).await;
-    let command = action_command(
-        node_run.workflow_run_id,
-        node_run.id,
-        "__force_result_persist_failure__",
-    );
-    let poison = WorkflowResultEvent::chunk(
-        &command,
-        NewRunChunk {
-            stream: "log".into(),
-            content: "poison".into(),
-        },
-    );
-    let broker = Arc::new(RecordingBroker::new());
-    let broker_for_consumer: Arc<dyn Broker> = broker.clone();
-    let publisher = runinator_engine::EnginePublisher::new(broker_for_consumer.clone());
-    let shutdown = Arc::new(Notify::new());
-    let consumer = tokio::spawn(crate::result_consumer::run_result_consumer_with_policy(
-        db.clone(),
-        broker_for_consumer,
-        publisher,
-        shutdown.clone(),
-        crate::result_consumer::ResultConsumerPolicy::new(2, Duration::from_millis(1)),
-    ));
+    let run_id = seed_run(
+        &db,
+        "duplicate-result-drive",
+        json!({
+            "start": "start",
+            "nodes": [
+                { "id": "start", "kind": "start", "transitions": { "next": { "$node": "run" } } },
+                {
+                    "id": "run",
+                    "kind": "action",
+                    "action": { "provider": "test", "function": "execute", "configuration": {} },
+                    "transitions": { "next": { "$node": "end" } }
+                },
+                { "id": "end", "kind": "end" }
+            ]
+        }),
+    )
+    .await;
 
-    broker
-        .publish_result(ResultMessage {
-            event: poison,
-            dedupe_key: Some("poison-result".into()),
-            enqueued_at: chrono::Utc::now(),
-        })
+    drain_ready_nodes(&db).await;
+    let dispatch = db.fetch_pending_action_dispatches(10).await.unwrap()[0].clone();
+    db.mark_action_dispatch_published(dispatch.id)
         .await
         .unwrap();
-    wait_until(|| broker.result_acks().len() == 1 && broker.result_nacks().len() == 1).await;
-
-    shutdown.notify_waiters();
-    tokio::time::timeout(Duration::from_secs(1), consumer)
+    let event = WorkflowResultEvent::status(
+        &dispatch.command,
+        WorkflowStatus::Succeeded,
+        Some(json!({ "ok": true })),
+        None,
+    );
+    assert!(
+        crate::repository::apply_workflow_result_event(&db, &event)
+            .await
+            .unwrap()
+    );
+    drain_ready_nodes(&db).await;
+    let (run, _) = crate::repository::fetch_workflow_run(&db, run_id)
         .await
         .unwrap()
         .unwrap();
+    assert_eq!(run.status, WorkflowStatus::Succeeded);
 
-    let chunks = db
-        .fetch_workflow_node_run_chunks(node_run.id, None, 100)
+    // a redelivered duplicate can follow a crash that lost the first drive enqueue; it must still
+    // enqueue a drive even though the event itself is not re-applied.
+    assert!(
+        !crate::repository::apply_workflow_result_event(&db, &event)
+            .await
+            .unwrap()
+    );
+    let pending = db
+        .fetch_pending_ready_nodes(chrono::Utc::now(), 10)
         .await
         .unwrap();
-    assert!(chunks.is_empty());
-
-    // the poison event leaves a durable dead-letter record on the result channel.
-    let dead_letters = db.fetch_dead_letters(None, 100).await.unwrap();
-    assert_eq!(dead_letters.len(), 1);
-    assert_eq!(
-        dead_letters[0]
-            .get("channel")
-            .and_then(runinator_models::value::Value::as_str),
-        Some("result")
+    assert!(
+        pending
+            .iter()
+            .any(|node| node.workflow_run_id == run_id && node.node_id == "run")
     );
 
     let _ = std::fs::remove_file(path);
 }
 
-#[test]
-fn result_consumer_backoff_grows_and_is_capped() {
-    let policy = crate::result_consumer::ResultConsumerPolicy::new(5, Duration::from_millis(100));
-    // full jitter keeps every delay within [0, base * 2^(attempt-1)] for early attempts.
-    for attempt in 1..=4u32 {
-        let ceiling = 100u128 * (1u128 << (attempt - 1));
-        let backoff = policy.backoff_for(attempt).as_millis();
-        assert!(
-            backoff <= ceiling,
-            "attempt {attempt} backoff {backoff} exceeded ceiling {ceiling}"
-        );
-    }
-    // a large attempt count is clamped to the 30s max backoff and cannot overflow.
-    let huge = policy.backoff_for(40).as_millis();
-    assert!(huge <= 30_000, "backoff {huge} exceeded max_backoff");
-}
-
 #[tokio::test]
-async fn output_node_promotes_artifacts_to_the_run() {
+async fn action_node_timeout_recovers_parked_run() {
     let (db, path) = test_db().await;
-    let node_run = create_node_run(&db).await;
-    // a node artifact, then an output node promoting it to the run.
-    let artifact = db
-        .add_workflow_node_run_artifact(
-            node_run.id,
-            &NewRunArtifact {
-                name: "dump.csv".into(),
-                mime_type: "text/csv".into(),
-                size_bytes: 42,
-                uri: "memory://dump.csv".into(),
-                metadata: json!({}),
-            },
-        )
+    let run_id = seed_run(
+        &db,
+        "action-timeout",
+        json!({
+            "start": "start",
+            "nodes": [
+                { "id": "start", "kind": "start", "transitions": { "next": { "$node": "run" } } },
+                {
+                    "id": "run",
+                    "kind": "action",
+                    "timeout_seconds": 1,
+                    "action": { "provider": "test", "function": "execute", "configuration": {} },
+                    "transitions": { "next": { "$node": "end" } }
+                },
+                { "id": "end", "kind": "end" }
+            ]
+        }),
+    )
+    .await;
+
+    drain_ready_nodes(&db).await;
+    let dispatch = db.fetch_pending_action_dispatches(10).await.unwrap()[0].clone();
+    db.mark_action_dispatch_published(dispatch.id)
         .await
         .unwrap();
 
-    // the run-wide artifact fetch (used to build runtime context) sees it.
-    let run_artifacts = db
-        .fetch_workflow_node_run_artifacts_for_run(node_run.workflow_run_id)
-        .await
-        .unwrap();
-    assert_eq!(run_artifacts.len(), 1);
-    assert_eq!(run_artifacts[0].id, artifact.id);
+    // no worker result ever arrives; the armed timeout wake must settle the parked node.
+    tokio::time::sleep(Duration::from_millis(1500)).await;
+    drain_ready_nodes(&db).await;
 
-    let stored = db
-        .add_workflow_run_artifact(&NewWorkflowRunArtifact {
-            workflow_run_id: node_run.workflow_run_id,
-            node_id: node_run.node_id.clone(),
-            artifact_id: artifact.id,
-            name: "report".into(),
-            mime_type: artifact.mime_type.clone(),
-            size_bytes: artifact.size_bytes,
-            uri: artifact.uri.clone(),
-            metadata: json!({}),
-        })
+    let (run, nodes) = crate::repository::fetch_workflow_run(&db, run_id)
         .await
+        .unwrap()
         .unwrap();
-    assert_eq!(stored.name, "report");
+    let node_run = nodes.iter().find(|node| node.node_id == "run").unwrap();
+    assert_eq!(node_run.status, WorkflowStatus::TimedOut);
+    assert_eq!(run.status, WorkflowStatus::TimedOut);
 
-    let artifacts = db
-        .fetch_workflow_run_artifacts(node_run.workflow_run_id)
-        .await
-        .unwrap();
-    assert_eq!(artifacts.len(), 1);
-    assert_eq!(artifacts[0].artifact_id, artifact.id);
-    assert_eq!(artifacts[0].name, "report");
-    assert_eq!(artifacts[0].uri, "memory://dump.csv");
     let _ = std::fs::remove_file(path);
 }
 
-async fn test_db() -> (SqliteDb, std::path::PathBuf) {
-    let path = std::env::temp_dir().join(format!(
-        "runinator-ws-workflows-{}.db",
-        uuid::Uuid::new_v4()
-    ));
-    let db = SqliteDb::new(path.to_str().unwrap()).await.unwrap();
-    db.run_init_scripts(&Vec::new()).await.unwrap();
-    (db, path)
-}
+#[tokio::test]
+async fn action_node_fails_promptly_when_its_executing_worker_disconnects() {
+    use runinator_models::replicas::{ReplicaKind, ReplicaRegistrationRequest};
 
-async fn create_node_run(db: &SqliteDb) -> WorkflowNodeRun {
-    let workflow = crate::repository::upsert_workflow(db, &workflow(None, "result-consumer"))
+    let (db, path) = test_db().await;
+    // no timeout_seconds: only the armed liveness poll can catch the dead executor.
+    let run_id = seed_run(
+        &db,
+        "executor-disconnect",
+        json!({
+            "start": "start",
+            "nodes": [
+                { "id": "start", "kind": "start", "transitions": { "next": { "$node": "run" } } },
+                {
+                    "id": "run",
+                    "kind": "action",
+                    "action": { "provider": "test", "function": "execute", "configuration": {} },
+                    "transitions": { "next": { "$node": "end" } }
+                },
+                { "id": "end", "kind": "end" }
+            ]
+        }),
+    )
+    .await;
+
+    drain_ready_nodes(&db).await;
+    let dispatch = db.fetch_pending_action_dispatches(10).await.unwrap()[0].clone();
+    db.mark_action_dispatch_published(dispatch.id)
         .await
         .unwrap();
-    let workflow_id = workflow.id.unwrap();
-    let run = crate::repository::create_workflow_run(
-        db,
-        workflow_id,
-        json!({}),
-        false,
-        None,
-        Default::default(),
-    )
-    .await
-    .unwrap();
-    crate::repository::update_workflow_run_status(
-        db,
-        run.id,
-        WorkflowStatus::Running,
-        Some("start".into()),
-        None,
-        None,
+
+    // a worker claimed the execution, then went offline without releasing the claim (crash or
+    // grace-period abort), so the reducer must treat the holder as disconnected.
+    let node_run_id = dispatch.command.workflow_node_run_id;
+    let runtime_id = Uuid::new_v4().to_string();
+    let dead_replica = db
+        .register_replica(
+            ReplicaRegistrationRequest {
+                replica_type: ReplicaKind::Worker,
+                instance_id: "doomed-worker".into(),
+                runtime_id: runtime_id.clone(),
+                display_name: None,
+                host: None,
+                port: None,
+                base_path: None,
+                version: None,
+                attributes: runinator_models::json!({}),
+            },
+            None,
+            &AuthContext {
+                principal_id: None,
+                is_admin: true,
+                kind: PrincipalKind::User,
+                org_id: None,
+                org_role: None,
+            },
+        )
+        .await
+        .unwrap()
+        .replica_id;
+    assert!(
+        db.claim_workflow_node_run_executor(
+            node_run_id,
+            dead_replica,
+            chrono::Utc::now(),
+            chrono::Utc::now() - chrono::Duration::seconds(60),
+        )
+        .await
+        .unwrap()
+    );
+    db.mark_replica_offline(dead_replica, runtime_id)
+        .await
+        .unwrap();
+
+    // the armed liveness poll is 15 seconds out; fire an equivalent due wake instead of sleeping.
+    db.enqueue_ready_node(
+        runinator_models::orchestration::NewOrchestrationEvent::new(
+            run_id,
+            Some("run".into()),
+            "dispatch_liveness_poll",
+            json!({ "node_id": "run" }),
+        ),
+        "run".into(),
+        chrono::Utc::now(),
     )
     .await
     .unwrap();
-    crate::repository::create_workflow_node_run(db, run.id, "node-a".into(), json!({}), None)
+    drain_ready_nodes(&db).await;
+
+    let (run, nodes) = crate::repository::fetch_workflow_run(&db, run_id)
         .await
         .unwrap()
-}
+        .unwrap();
+    let node_run = nodes.iter().find(|node| node.node_id == "run").unwrap();
+    assert_eq!(node_run.status, WorkflowStatus::TimedOut);
+    // the dead worker's claim must be released, or the retry this schedules would be dropped as a
+    // duplicate delivery by whichever worker picks it up.
+    assert!(node_run.current_executor_replica_id.is_none());
+    assert_eq!(run.status, WorkflowStatus::TimedOut);
 
-fn action_command(
-    workflow_run_id: Uuid,
-    workflow_node_run_id: Uuid,
-    node_id: &str,
-) -> ActionCommand {
-    ActionCommand {
-        command_id: Uuid::new_v4(),
-        workflow_run_id,
-        workflow_node_run_id,
-        node_id: node_id.into(),
-        action: WorkflowAction {
-            provider: "test".into(),
-            function: "execute".into(),
-            timeout_seconds: 60,
-            configuration: runinator_models::workflows::WorkflowObject::default(),
-            mcp_enabled: false,
-            tags: Vec::new(),
-            required_labels: Default::default(),
-        },
-        attempt: 1,
-        parameters: json!({}),
-        target: Default::default(),
-        trace_id: Uuid::nil(),
-        trace_context: Default::default(),
-    }
+    let _ = std::fs::remove_file(path);
 }
 
-async fn publish_duplicate_results(broker: &RecordingBroker, events: &[WorkflowResultEvent]) {
-    for event in events {
-        for duplicate in 0..2 {
-            broker
-                .publish_result(ResultMessage {
-                    event: event.clone(),
-                    dedupe_key: Some(format!("{}-{duplicate}", event.event_id)),
-                    enqueued_at: chrono::Utc::now(),
-                })
-                .await
-                .unwrap();
-        }
-    }
+#[test]
+fn merges_json_objects() {
+    let defaults = json!({ "a": 1, "b": 2 });
+    let parameters = json!({ "b": 3, "c": 4 });
+    let merged = crate::repository::merge_json_object(&defaults, &parameters);
+    assert_eq!(merged, json!({ "a": 1, "b": 3, "c": 4 }));
 }
 
-async fn wait_until(mut condition: impl FnMut() -> bool) {
-    let deadline = Instant::now() + Duration::from_secs(2);
-    while Instant::now() < deadline {
-        if condition() {
-            return;
+#[test]
+fn registered_provider_items_become_provider_metadata() {
+    let providers = crate::provider_metadata_from_items(vec![json!({
+        "document": {
+            "name": "github",
+            "actions": [
+                { "function_name": "create_pr", "description": "Create a pull request" }
+            ]
         }
-        tokio::time::sleep(Duration::from_millis(10)).await;
-    }
-    assert!(condition(), "condition was not met before timeout");
-}
+    })])
+    .expect("provider metadata parses");
 
-#[derive(Clone, Default)]
-struct RecordingBroker {
-    inner: InMemoryBroker,
-    result_receives: Arc<Mutex<HashSet<Uuid>>>,
-    result_acks: Arc<Mutex<HashSet<Uuid>>>,
-    result_nacks: Arc<Mutex<HashSet<Uuid>>>,
+    assert_eq!(providers[0].name, "github");
+    assert_eq!(providers[0].actions[0].function_name, "create_pr");
 }
 
-impl RecordingBroker {
-    fn new() -> Self {
-        Self::default()
-    }
-
-    fn result_receives(&self) -> HashSet<Uuid> {
-        self.result_receives.lock().unwrap().clone()
-    }
-
-    fn result_acks(&self) -> HashSet<Uuid> {
-        self.result_acks.lock().unwrap().clone()
-    }
+#[test]
+fn provider_metadata_becomes_registered_catalog_item() {
+    let item = crate::provider_catalog_item(&runinator_models::providers::ProviderMetadata {
+        name: "git".into(),
+        actions: vec![runinator_models::providers::ActionMetadata::new(
+            "diff", "Get diff",
+        )],
+        metadata: Default::default(),
+    });
 
-    fn result_nacks(&self) -> HashSet<Uuid> {
-        self.result_nacks.lock().unwrap().clone()
-    }
+    assert_eq!(item["item_type"], "provider_metadata");
+    assert_eq!(item["document"]["name"], "git");
+    assert_eq!(item["document"]["actions"][0]["function_name"], "diff");
 }
 
-#[async_trait::async_trait]
-impl Broker for RecordingBroker {
-    async fn publish(&self, message: BrokerMessage) -> Result<(), BrokerError> {
-        self.inner.publish(message).await
-    }
+#[test]
+fn validate_workflow_returns_normalized_definition() {
+    let workflow = workflow(None, "validate");
+    let validated = crate::repository::validate_workflow_definition(&workflow).unwrap();
 
-    async fn receive(&self, consumer: &str) -> Result<BrokerDelivery, BrokerError> {
-        self.inner.receive(consumer).await
-    }
+    assert_eq!(validated.name, "validate");
+    assert_eq!(validated.definition.start.as_deref(), Some("start"));
+}
 
-    async fn ack(&self, consumer: &str, delivery_id: Uuid) -> Result<(), BrokerError> {
-        self.inner.ack(consumer, delivery_id).await
-    }
+#[test]
+fn validate_workflow_rejects_invalid_definition_without_persistence() {
+    let mut workflow = workflow(None, "invalid");
+    workflow.definition = WorkflowGraph::from_value(json!({
+        "start": "start",
+        "nodes": [
+            { "id": "start", "kind": "start", "transitions": { "next": { "$node": "missing" } } },
+            { "id": "done", "kind": "end" }
+        ]
+    }))
+    .unwrap();
 
-    async fn nack(&self, consumer: &str, delivery_id: Uuid) -> Result<(), BrokerError> {
-        self.inner.nack(consumer, delivery_id).await
-    }
+    assert!(crate::repository::validate_workflow_definition(&workflow).is_err());
+}
 
-    async fn publish_control(&self, command: ControlCommand) -> Result<(), BrokerError> {
-        self.inner.publish_control(command).await
-    }
+#[test]
+fn validation_error_response_exposes_structured_type_diagnostic() {
+    let err = WorkflowValidationError::TypeDiagnostic(WorkflowTypeDiagnostic {
+        path: "action parameter 'config.name'".into(),
+        expected: "string".into(),
+        actual: "integer".into(),
+        message: "action parameter 'config.name' expected string, got integer".into(),
+    });
 
-    async fn receive_control(&self, consumer: &str) -> Result<ControlDelivery, BrokerError> {
-        self.inner.receive_control(consumer).await
+    let (status, axum::Json(response)) = crate::responses::validation_error(&err);
+    assert_eq!(status, axum::http::StatusCode::BAD_REQUEST);
+    match response {
+        crate::models::ApiResponse::ApiError(error) => {
+            assert_eq!(
+                error.path.as_deref(),
+                Some("action parameter 'config.name'")
+            );
+            assert_eq!(error.expected.as_deref(), Some("string"));
+            assert_eq!(error.actual.as_deref(), Some("integer"));
+        }
+        _ => panic!("expected api error"),
     }
+}
 
-    async fn ack_control(&self, consumer: &str, delivery_id: Uuid) -> Result<(), BrokerError> {
-        self.inner.ack_control(consumer, delivery_id).await
-    }
+#[tokio::test]
+async fn export_all_includes_workflows_and_matching_triggers() {
+    let (db, path) = test_db().await;
+    let saved = crate::repository::upsert_workflow(&db, &workflow(None, "export-all"))
+        .await
+        .unwrap();
+    let workflow_id = saved.id.unwrap();
+    crate::repository::upsert_workflow_trigger(&db, &trigger(None, workflow_id))
+        .await
+        .unwrap();
 
-    async fn nack_control(&self, consumer: &str, delivery_id: Uuid) -> Result<(), BrokerError> {
-        self.inner.nack_control(consumer, delivery_id).await
-    }
+    let bundle = crate::repository::export_workflow_bundle(&db, None, 0)
+        .await
+        .unwrap();
 
-    async fn publish_result(&self, message: ResultMessage) -> Result<(), BrokerError> {
-        self.inner.publish_result(message).await
-    }
+    assert_eq!(bundle.workflows.len(), 1);
+    assert_eq!(bundle.workflows[0].id, Some(workflow_id));
+    assert_eq!(bundle.triggers.len(), 1);
+    assert_eq!(bundle.triggers[0].workflow_id, workflow_id);
+    let _ = std::fs::remove_file(path);
+}
 
-    async fn receive_result(&self, consumer: &str) -> Result<ResultDelivery, BrokerError> {
-        let delivery = self.inner.receive_result(consumer).await?;
-        self.result_receives
-            .lock()
-            .unwrap()
-            .insert(delivery.delivery_id);
-        Ok(delivery)
-    }
+#[tokio::test]
+async fn export_one_includes_only_that_workflow_and_its_triggers() {
+    let (db, path) = test_db().await;
+    let first = crate::repository::upsert_workflow(&db, &workflow(None, "first"))
+        .await
+        .unwrap();
+    let second = crate::repository::upsert_workflow(&db, &workflow(None, "second"))
+        .await
+        .unwrap();
+    let first_id = first.id.unwrap();
+    let second_id = second.id.unwrap();
+    crate::repository::upsert_workflow_trigger(&db, &trigger(None, first_id))
+        .await
+        .unwrap();
+    crate::repository::upsert_workflow_trigger(&db, &trigger(None, second_id))
+        .await
+        .unwrap();
 
-    async fn ack_result(&self, consumer: &str, delivery_id: Uuid) -> Result<(), BrokerError> {
-        self.inner.ack_result(consumer, delivery_id).await?;
-        self.result_acks.lock().unwrap().insert(delivery_id);
-        Ok(())
-    }
+    let bundle = crate::repository::export_workflow_bundle(&db, Some(second_id), 0)
+        .await
+        .unwrap();
 
-    async fn nack_result(&self, consumer: &str, delivery_id: Uuid) -> Result<(), BrokerError> {
-        self.inner.nack_result(consumer, delivery_id).await?;
-        self.result_nacks.lock().unwrap().insert(delivery_id);
-        Ok(())
-    }
+    assert_eq!(bundle.workflows.len(), 1);
+    assert_eq!(bundle.workflows[0].id, Some(second_id));
+    assert_eq!(bundle.triggers.len(), 1);
+    assert_eq!(bundle.triggers[0].workflow_id, second_id);
+    let _ = std::fs::remove_file(path);
+}
 
-    async fn publish_wake(&self, message: WakeMessage) -> Result<(), BrokerError> {
-        self.inner.publish_wake(message).await
+// cloning an environment: the export carries finished runs as baselines, and importing it with
+// schedule options disables and offsets the cron triggers it brings in.
+#[tokio::test]
+async fn export_baselines_and_import_schedule_options_clone_an_environment() {
+    let (db, path) = test_db().await;
+    let saved = crate::repository::upsert_workflow(&db, &workflow(None, "nightly-dump"))
+        .await
+        .unwrap();
+    let workflow_id = saved.id.unwrap();
+    let mut cron = trigger(None, workflow_id);
+    cron.kind = WorkflowTriggerKind::Cron;
+    cron.configuration = json!({ "cron": "0 0 2 * * *" });
+    crate::repository::upsert_workflow_trigger(&db, &cron)
+        .await
+        .unwrap();
+    for status in [WorkflowStatus::Succeeded, WorkflowStatus::Running] {
+        let run = crate::repository::create_workflow_run(
+            &db,
+            workflow_id,
+            json!({}),
+            false,
+            None,
+            Default::default(),
+        )
+        .await
+        .unwrap();
+        crate::repository::update_workflow_run_status(&db, run.id, status, None, None, None)
+            .await
+            .unwrap();
     }
 
-    async fn receive_wake(&self, consumer: &str) -> Result<WakeDelivery, BrokerError> {
-        self.inner.receive_wake(consumer).await
-    }
+    // only the finished run is a baseline.
+    let bundle = crate::repository::export_workflow_bundle(&db, None, 5)
+        .await
+        .unwrap();
+    assert_eq!(bundle.baselines.len(), 1);
+    assert_eq!(bundle.baselines[0].workflow_name, "nightly-dump");
+    assert_eq!(bundle.baselines[0].status, WorkflowStatus::Succeeded);
 
-    async fn ack_wake(&self, consumer: &str, delivery_id: Uuid) -> Result<(), BrokerError> {
-        self.inner.ack_wake(consumer, delivery_id).await
-    }
+    let (target, target_path) = test_db().await;
+    let mut imported = crate::repository::import_workflow_bundle(&target, bundle)
+        .await
+        .unwrap();
+    assert_eq!(imported.baselines.len(), 1);
+    crate::repository::apply_workflow_import_options(
+        &target,
+        &mut imported,
+        &WorkflowImportOptions {
+            disable_schedules: true,
+            schedule_offset_seconds: Some(3600),
+        },
+    )
+    .await
+    .unwrap();
 
-    async fn nack_wake(&self, consumer: &str, delivery_id: Uuid) -> Result<(), BrokerError> {
-        self.inner.nack_wake(consumer, delivery_id).await
-    }
+    let triggers = target.fetch_workflow_triggers(workflow_id).await.unwrap();
+    assert_eq!(triggers.len(), 1);
+    assert!(!triggers[0].enabled);
+    assert_eq!(
+        triggers[0]
+            .configuration
+            .get("offset_seconds")
+            .and_then(Value::as_i64),
+        Some(3600)
+    );
+    assert!(triggers[0].next_execution.is_none());
+    assert!(!imported.triggers[0].enabled);
+    let _ = std::fs::remove_file(path);
+    let _ = std::fs::remove_file(target_path);
+}
 
-    async fn publish_ingress(&self, message: IngressMessage) -> Result<(), BrokerError> {
-        self.inner.publish_ingress(message).await
-    }
+#[tokio::test]
+async fn import_upserts_workflows_before_triggers() {
+    let (db, path) = test_db().await;
+    let wf_id = Uuid::now_v7();
+    let trig_id = Uuid::now_v7();
+    let bundle = WorkflowBundle {
+        workflows: vec![workflow(Some(wf_id), "imported")],
+        triggers: vec![trigger(Some(trig_id), wf_id)],
+        baselines: Vec::new(),
+    };
 
-    async fn receive_ingress(&self, consumer: &str) -> Result<IngressDelivery, BrokerError> {
-        self.inner.receive_ingress(consumer).await
-    }
+    let saved = crate::repository::import_workflow_bundle(&db, bundle)
+        .await
+        .unwrap();
 
-    async fn ack_ingress(&self, consumer: &str, delivery_id: Uuid) -> Result<(), BrokerError> {
-        self.inner.ack_ingress(consumer, delivery_id).await
-    }
+    assert_eq!(saved.workflows[0].id, Some(wf_id));
+    assert_eq!(saved.triggers[0].id, Some(trig_id));
+    assert!(db.fetch_workflow(wf_id).await.unwrap().is_some());
+    assert_eq!(db.fetch_workflow_triggers(wf_id).await.unwrap().len(), 1);
+    let _ = std::fs::remove_file(path);
+}
 
-    async fn nack_ingress(&self, consumer: &str, delivery_id: Uuid) -> Result<(), BrokerError> {
-        self.inner.nack_ingress(consumer, delivery_id).await
-    }
+#[tokio::test]
+async fn import_skips_workflow_when_name_already_exists() {
+    let (db, path) = test_db().await;
+    let first = WorkflowBundle {
+        workflows: vec![workflow(None, "Core Team SDLC Pipeline")],
+        triggers: vec![],
+        baselines: vec![],
+    };
+    let initial = crate::repository::import_workflow_bundle(&db, first)
+        .await
+        .unwrap();
+    let initial_version = initial.workflows[0].version;
+    let initial_definition = initial.workflows[0].definition.clone();
+    let mut changed = workflow(None, "Core Team SDLC Pipeline");
+    changed.version = runinator_models::semver::SemVer::new(2, 0, 0);
+    changed.definition = WorkflowGraph::from_value(json!({
+        "start": "done",
+        "nodes": [
+            { "id": "done", "kind": "end" }
+        ]
+    }))
+    .unwrap();
+    let second = WorkflowBundle {
+        workflows: vec![changed.clone()],
+        triggers: vec![],
+        baselines: vec![],
+    };
 
-    async fn publish_event(&self, message: EventMessage) -> Result<(), BrokerError> {
-        self.inner.publish_event(message).await
-    }
+    let saved = crate::repository::import_workflow_bundle(&db, second)
+        .await
+        .unwrap();
+    let workflows = db.fetch_workflows().await.unwrap();
 
-    async fn receive_event(&self, consumer: &str) -> Result<EventDelivery, BrokerError> {
-        self.inner.receive_event(consumer).await
-    }
+    // re-importing the same workflow name leaves the existing row untouched.
+    assert_eq!(workflows.len(), 1);
+    assert_eq!(saved.workflows[0].id, workflows[0].id);
+    assert_eq!(workflows[0].name, "Core Team SDLC Pipeline");
+    assert_eq!(workflows[0].version, initial_version);
+    assert_eq!(workflows[0].definition, initial_definition);
+    let _ = std::fs::remove_file(path);
 }
 
-fn workflow(id: Option<Uuid>, name: &str) -> WorkflowDefinition {
-    WorkflowDefinition {
-        id,
-        name: name.into(),
-        namespace: None,
-        org_id: None,
-        version: runinator_models::semver::SemVer::new(1, 0, 0),
-        enabled: true,
-        input_type: runinator_models::types::RuninatorType::from_json_schema(
-            &json!({ "type": "object" }),
-        ),
-        definition: WorkflowGraph::from_value(json!({
-            "start": "start",
-            "nodes": [
-                { "id": "start", "kind": "start", "transitions": { "next": { "$node": "done" } } },
-                { "id": "done", "kind": "end" }
-            ]
-        }))
-        .unwrap(),
+#[tokio::test]
+async fn import_overwrite_updates_existing_workflow_in_place() {
+    let (db, path) = test_db().await;
+    let first = WorkflowBundle {
+        workflows: vec![workflow(None, "Core Team SDLC Pipeline")],
+        triggers: vec![],
+        baselines: vec![],
+    };
+    let initial = crate::repository::import_workflow_bundle(&db, first)
+        .await
+        .unwrap();
+    let existing_id = initial.workflows[0].id;
+    assert_ne!(
+        initial.workflows[0].version,
+        runinator_models::semver::SemVer::new(2, 0, 0)
+    );
+
+    // an explicit re-apply carries no id and no newer timestamp, but overwrite must still win.
+    let mut changed = workflow(None, "Core Team SDLC Pipeline");
+    changed.version = runinator_models::semver::SemVer::new(2, 0, 0);
+    changed.definition = WorkflowGraph::from_value(json!({
+        "start": "done",
+        "nodes": [
+            { "id": "done", "kind": "end" }
+        ]
+    }))
+    .unwrap();
+    let second = WorkflowBundle {
+        workflows: vec![changed.clone()],
+        triggers: vec![],
+        baselines: vec![],
+    };
+
+    let saved = crate::repository::import_workflow_bundle_with(&db, second, true)
+        .await
+        .unwrap();
+    let workflows = db.fetch_workflows().await.unwrap();
+
+    // the existing row is updated in place: same id, bumped version, no duplicate row. the skip
+    // path would have left the stored version unchanged, so version == 2 proves the overwrite.
+    assert_eq!(workflows.len(), 1);
+    assert_eq!(saved.workflows[0].id, existing_id);
+    assert_eq!(workflows[0].id, existing_id);
+    assert_eq!(
+        workflows[0].version,
+        runinator_models::semver::SemVer::new(2, 0, 0)
+    );
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn import_pipeline_creates_managed_chained_triggers_idempotently() {
+    use runinator_models::pipelines::{
+        PipelineBundle, PipelineLinkSelector, PipelineLinkSpec, PipelineSpec,
+    };
+
+    let (db, path) = test_db().await;
+    // import the two member workflows first so the pipeline can resolve their names to ids.
+    let members = WorkflowBundle {
+        workflows: vec![
+            workflow(None, "SDLC: Development"),
+            workflow(None, "SDLC: Review"),
+        ],
+        triggers: vec![],
+        baselines: vec![],
+    };
+    crate::repository::import_workflow_bundle(&db, members)
+        .await
+        .unwrap();
+
+    let bundle = PipelineBundle {
+        pipelines: vec![PipelineSpec {
+            name: "Core SDLC".into(),
+            description: Some("test".into()),
+            defaults: Default::default(),
+            members: vec!["SDLC: Development".into(), "SDLC: Review".into()],
+            links: vec![PipelineLinkSpec {
+                from: "SDLC: Development".into(),
+                to: "SDLC: Review".into(),
+                on: PipelineLinkSelector::Complete,
+                enabled: true,
+            }],
+            triggers: vec![],
+        }],
+    };
+
+    // first import: creates the pipeline and one managed chained trigger on the source workflow.
+    let imported = crate::repository::import_pipeline_bundle_with(&db, &bundle, None)
+        .await
+        .unwrap();
+    assert_eq!(imported.len(), 1);
+    let pipeline_id = imported[0].id.expect("pipeline id");
+    assert_eq!(imported[0].workflow_ids.len(), 2);
+
+    let dev_id = db
+        .fetch_workflow_by_name("SDLC: Development".into())
+        .await
+        .unwrap()
+        .unwrap()
+        .id
+        .unwrap();
+    let triggers = db.fetch_workflow_triggers(dev_id).await.unwrap();
+    assert_eq!(triggers.len(), 1);
+    let trigger = &triggers[0];
+    assert_eq!(
+        trigger
+            .configuration
+            .pointer("/target_workflow")
+            .and_then(Value::as_str),
+        Some("SDLC: Review")
+    );
+    assert_eq!(
+        trigger.configuration.pointer("/on").and_then(Value::as_str),
+        Some("complete")
+    );
+    // the frontend keys pipeline membership off configuration.pipeline_id.
+    assert_eq!(
+        trigger
+            .configuration
+            .pointer("/pipeline_id")
+            .and_then(Value::as_str),
+        Some(pipeline_id.to_string().as_str())
+    );
+
+    // re-import: reconciles in place — no duplicate pipeline and no duplicate trigger.
+    let reimported = crate::repository::import_pipeline_bundle_with(&db, &bundle, None)
+        .await
+        .unwrap();
+    assert_eq!(reimported[0].id, Some(pipeline_id));
+    assert_eq!(db.fetch_pipelines().await.unwrap().len(), 1);
+    assert_eq!(db.fetch_workflow_triggers(dev_id).await.unwrap().len(), 1);
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn manual_pipeline_run_starts_entry_member_chains_and_settles() {
+    use runinator_models::pipelines::{
+        PipelineBundle, PipelineLinkSelector, PipelineLinkSpec, PipelineSpec,
+    };
+    use runinator_models::workflows::WorkflowStatus;
+
+    let (db, path) = test_db().await;
+    let members = WorkflowBundle {
+        workflows: vec![workflow(None, "Build"), workflow(None, "Deploy")],
+        triggers: vec![],
+        baselines: vec![],
+    };
+    crate::repository::import_workflow_bundle(&db, members)
+        .await
+        .unwrap();
+
+    // Build -> Deploy on complete: Build is the sole entry member, Deploy is downstream.
+    let bundle = PipelineBundle {
+        pipelines: vec![PipelineSpec {
+            name: "Release".into(),
+            description: None,
+            defaults: Default::default(),
+            members: vec!["Build".into(), "Deploy".into()],
+            links: vec![PipelineLinkSpec {
+                from: "Build".into(),
+                to: "Deploy".into(),
+                on: PipelineLinkSelector::Complete,
+                enabled: true,
+            }],
+            triggers: vec![],
+        }],
+    };
+    let imported = crate::repository::import_pipeline_bundle_with(&db, &bundle, None)
+        .await
+        .unwrap();
+    let pipeline_id = imported[0].id.expect("pipeline id");
+
+    // start a manual pipeline run and drive the whole member graph to completion.
+    let run = crate::repository::create_manual_pipeline_run(
+        &db,
+        pipeline_id,
+        json!({}),
+        None,
+        Some("test".into()),
+    )
+    .await
+    .unwrap();
+    drain_ready_nodes(&db).await;
+
+    // both members ran, each tagged with the pipeline run, and the chained Deploy inherited the tag.
+    let members = db
+        .fetch_workflow_runs_for_pipeline_run(run.id)
+        .await
+        .unwrap();
+    assert_eq!(members.len(), 2, "entry member + chained downstream member");
+    assert!(members.iter().all(|m| m.pipeline_run_id == Some(run.id)));
+    assert!(
+        members
+            .iter()
+            .all(|m| m.status == WorkflowStatus::Succeeded)
+    );
+
+    // the pipeline run settles Succeeded once the reachable graph is terminal.
+    let settled = db.fetch_pipeline_run(run.id).await.unwrap().unwrap();
+    assert_eq!(settled.status, WorkflowStatus::Succeeded);
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn import_upserts_existing_workflow_when_id_is_present() {
+    let (db, path) = test_db().await;
+    let first = WorkflowBundle {
+        workflows: vec![workflow(None, "Core Team SDLC Pipeline")],
+        triggers: vec![],
+        baselines: vec![],
+    };
+    let initial = crate::repository::import_workflow_bundle(&db, first)
+        .await
+        .unwrap();
+    let existing_id = initial.workflows[0].id;
+
+    // a save from the command center carries the existing id and must overwrite.
+    let mut changed = initial.workflows[0].clone();
+    changed.version = runinator_models::semver::SemVer::new(2, 0, 0);
+    changed.definition = WorkflowGraph::from_value(json!({
+        "start": "done",
+        "nodes": [
+            { "id": "done", "kind": "end" }
+        ]
+    }))
+    .unwrap();
+    let second = WorkflowBundle {
+        workflows: vec![changed.clone()],
+        triggers: vec![],
+        baselines: vec![],
+    };
+
+    let saved = crate::repository::import_workflow_bundle(&db, second)
+        .await
+        .unwrap();
+    let workflows = db.fetch_workflows().await.unwrap();
+
+    assert_eq!(workflows.len(), 1);
+    assert_eq!(saved.workflows[0].id, existing_id);
+    // an upsert bumps the version to 2; a skip would have left it at 1.
+    assert_eq!(
+        workflows[0].version,
+        runinator_models::semver::SemVer::new(2, 0, 0)
+    );
+    assert_eq!(
+        saved.workflows[0].version,
+        runinator_models::semver::SemVer::new(2, 0, 0)
+    );
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn import_overwrites_id_less_workflow_when_incoming_is_newer() {
+    let (db, path) = test_db().await;
+    let first = WorkflowBundle {
+        workflows: vec![workflow(None, "pack")],
+        triggers: vec![],
+        baselines: vec![],
+    };
+    crate::repository::import_workflow_bundle(&db, first)
+        .await
+        .unwrap();
+
+    // a pack import carrying a future updated_at is newer than the stored copy.
+    let mut newer = workflow(None, "pack");
+    newer.version = runinator_models::semver::SemVer::new(5, 0, 0);
+    newer.updated_at = chrono::DateTime::from_timestamp(4_102_444_800, 0);
+    let saved = crate::repository::import_workflow_bundle(
+        &db,
+        WorkflowBundle {
+            workflows: vec![newer],
+            triggers: vec![],
+            baselines: vec![],
+        },
+    )
+    .await
+    .unwrap();
+    let workflows = db.fetch_workflows().await.unwrap();
+
+    assert_eq!(workflows.len(), 1);
+    assert_eq!(
+        workflows[0].version,
+        runinator_models::semver::SemVer::new(5, 0, 0)
+    );
+    assert_eq!(
+        saved.workflows[0].version,
+        runinator_models::semver::SemVer::new(5, 0, 0)
+    );
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn import_skips_id_less_workflow_when_incoming_is_older() {
+    let (db, path) = test_db().await;
+    let first = WorkflowBundle {
+        workflows: vec![workflow(None, "pack")],
+        triggers: vec![],
+        baselines: vec![],
+    };
+    let initial = crate::repository::import_workflow_bundle(&db, first)
+        .await
+        .unwrap();
+    let initial_version = initial.workflows[0].version;
+
+    // a pack import carrying a past updated_at is older than the stored copy.
+    let mut older = workflow(None, "pack");
+    older.version = runinator_models::semver::SemVer::new(5, 0, 0);
+    older.updated_at = chrono::DateTime::from_timestamp(1, 0);
+    crate::repository::import_workflow_bundle(
+        &db,
+        WorkflowBundle {
+            workflows: vec![older],
+            triggers: vec![],
+            baselines: vec![],
+        },
+    )
+    .await
+    .unwrap();
+    let workflows = db.fetch_workflows().await.unwrap();
+
+    assert_eq!(workflows.len(), 1);
+    assert_eq!(workflows[0].version, initial_version);
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn duplicate_workflow_creates_bumped_sibling() {
+    let (db, path) = test_db().await;
+    let initial = crate::repository::import_workflow_bundle(
+        &db,
+        WorkflowBundle {
+            workflows: vec![workflow(None, "Core Team SDLC Pipeline")],
+            triggers: vec![],
+            baselines: vec![],
+        },
+    )
+    .await
+    .unwrap();
+    let original = initial.workflows[0].clone();
+    let original_id = original.id.unwrap();
+
+    let copy = crate::repository::duplicate_workflow(
+        &db,
+        original_id,
+        runinator_models::semver::SemVerBump::Minor,
+    )
+    .await
+    .unwrap();
+    let workflows = db.fetch_workflows().await.unwrap();
+
+    // a new disabled row sharing the name, with the minor version bumped.
+    assert_eq!(workflows.len(), 2);
+    assert_ne!(copy.id, original.id);
+    assert_eq!(copy.name, original.name);
+    assert!(!copy.enabled);
+    assert_eq!(
+        copy.version,
+        original
+            .version
+            .bump(runinator_models::semver::SemVerBump::Minor)
+    );
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn clone_workflow_creates_disabled_copy_with_disabled_triggers() {
+    let (db, path) = test_db().await;
+    let original = crate::repository::upsert_workflow(&db, &workflow(None, "nightly"))
+        .await
+        .unwrap();
+    let original_id = original.id.unwrap();
+    let mut source_trigger = trigger(None, original_id);
+    source_trigger.configuration = json!({ "note": "kept" });
+    crate::repository::upsert_workflow_trigger(&db, &source_trigger)
+        .await
+        .unwrap();
+
+    let first = crate::repository::clone_workflow(&db, original_id, None)
+        .await
+        .unwrap();
+    let second = crate::repository::clone_workflow(&db, original_id, None)
+        .await
+        .unwrap();
+    let named = crate::repository::clone_workflow(&db, original_id, Some("staging".into()))
+        .await
+        .unwrap();
+
+    // each clone is a separate, disabled workflow whose name never collides with an existing one.
+    assert_ne!(first.id, original.id);
+    assert_eq!(first.name, "nightly (copy)");
+    assert_eq!(second.name, "nightly (copy 2)");
+    assert_eq!(named.name, "staging");
+    assert!(!first.enabled);
+    assert_eq!(
+        first.definition.nodes.len(),
+        original.definition.nodes.len()
+    );
+    let triggers = crate::repository::fetch_workflow_triggers(&db, first.id.unwrap())
+        .await
+        .unwrap();
+    assert_eq!(triggers.len(), 1);
+    assert!(!triggers[0].enabled);
+    assert_eq!(triggers[0].configuration, json!({ "note": "kept" }));
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn shift_trigger_schedules_rewrites_namespace_crons_and_next_execution() {
+    let (db, path) = test_db().await;
+    let mut selected_ids = Vec::new();
+    for (name, cron) in [("alpha", "0 0 9 * * *"), ("beta", "0 */5 * * * *")] {
+        let mut definition = workflow(None, name);
+        definition.namespace = Some("reports".into());
+        let saved = crate::repository::upsert_workflow(&db, &definition)
+            .await
+            .unwrap();
+        let mut cron_trigger = trigger(None, saved.id.unwrap());
+        cron_trigger.kind = WorkflowTriggerKind::Cron;
+        cron_trigger.configuration = json!({ "cron": cron, "parameters": { "region": "eu" } });
+        let saved_trigger = crate::repository::upsert_workflow_trigger(&db, &cron_trigger)
+            .await
+            .unwrap();
+        selected_ids.push(saved_trigger.id.unwrap());
+    }
+    let outside = crate::repository::upsert_workflow(&db, &workflow(None, "gamma"))
+        .await
+        .unwrap();
+    let mut outside_trigger = trigger(None, outside.id.unwrap());
+    outside_trigger.kind = WorkflowTriggerKind::Cron;
+    outside_trigger.configuration = json!({ "cron": "0 0 9 * * *" });
+    let outside_trigger = crate::repository::upsert_workflow_trigger(&db, &outside_trigger)
+        .await
+        .unwrap();
+
+    let request = runinator_models::schedule_shift::ScheduleShiftRequest {
+        workflow_ids: Vec::new(),
+        namespace: Some("reports".into()),
+        trigger_ids: Vec::new(),
+        mode: runinator_models::schedule_shift::ScheduleShiftMode::Offset { minutes: 120 },
+        dry_run: false,
+    };
+    let report = crate::repository::shift_trigger_schedules(&db, &request)
+        .await
+        .unwrap();
+
+    // the daily cron moves two hours later; the step cron is reported and left as it was.
+    assert_eq!(report.changes.len(), 2);
+    assert_eq!(report.shifted, 1);
+    let alpha = &report.changes[0];
+    assert_eq!(alpha.workflow_name, "alpha");
+    assert_eq!(alpha.new_cron.as_deref(), Some("0 0 11 * * *"));
+    assert!(alpha.next_execution.is_some());
+    assert!(report.changes[1].error.is_some());
+
+    let stored = crate::repository::fetch_workflow_trigger(&db, selected_ids[0])
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        stored.configuration,
+        json!({ "cron": "0 0 11 * * *", "parameters": { "region": "eu" } })
+    );
+    assert_eq!(stored.next_execution, alpha.next_execution);
+    let untouched = crate::repository::fetch_workflow_trigger(&db, selected_ids[1])
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(untouched.configuration["cron"], json!("0 */5 * * * *"));
+    let outside = crate::repository::fetch_workflow_trigger(&db, outside_trigger.id.unwrap())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(outside.configuration["cron"], json!("0 0 9 * * *"));
+    let _ = std::fs::remove_file(path);
+}
+
+// a bulk shift cannot reschedule a protected workflow on one admin's say: its trigger's new cron
+// is held for a second approver while the unprotected workflow's trigger is rewritten.
+#[tokio::test]
+async fn shift_trigger_schedules_holds_protected_workflow_triggers() {
+    let (db, path) = test_db().await;
+    let db = Arc::new(db);
+    let events = crate::events::EventBus::new(
+        tokio::sync::broadcast::channel(16).0,
+        Arc::new(InMemoryBroker::new()),
+    );
+    let admin = AuthContext {
+        principal_id: Some(Uuid::new_v4()),
+        is_admin: true,
+        kind: PrincipalKind::User,
+        org_id: None,
+        org_role: None,
+    };
+    let mut trigger_ids = Vec::new();
+    for name in ["payroll", "ledger"] {
+        let mut definition = workflow(None, name);
+        definition.namespace = Some("finance".into());
+        let saved = crate::repository::upsert_workflow(db.as_ref(), &definition)
+            .await
+            .unwrap();
+        let mut cron_trigger = trigger(None, saved.id.unwrap());
+        cron_trigger.kind = WorkflowTriggerKind::Cron;
+        cron_trigger.configuration = json!({ "cron": "0 0 9 * * *" });
+        let saved_trigger = crate::repository::upsert_workflow_trigger(db.as_ref(), &cron_trigger)
+            .await
+            .unwrap();
+        trigger_ids.push((saved.id.unwrap(), saved_trigger.id.unwrap()));
+    }
+    let (payroll, payroll_trigger) = trigger_ids[1];
+    crate::repository::protect_workflow(db.as_ref(), payroll, admin.principal_id)
+        .await
+        .unwrap();
+
+    let request = runinator_models::schedule_shift::ScheduleShiftRequest {
+        workflow_ids: Vec::new(),
+        namespace: Some("finance".into()),
+        trigger_ids: Vec::new(),
+        mode: runinator_models::schedule_shift::ScheduleShiftMode::Offset { minutes: 60 },
+        dry_run: false,
+    };
+    let (status, Json(report)) = crate::handlers::triggers::shift_trigger_schedules::<SqliteDb>(
+        Extension(db.clone()),
+        Extension(events.clone()),
+        Extension(admin.clone()),
+        Json(request),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let crate::models::ApiResponse::ScheduleShiftReport(report) = report else {
+        panic!("expected a schedule shift report");
+    };
+    assert_eq!(report.shifted, 1);
+    let held = report
+        .changes
+        .iter()
+        .find(|change| change.trigger_id == payroll_trigger)
+        .unwrap();
+    assert!(held.protected);
+    assert!(held.next_execution.is_none());
+    let pending = crate::repository::fetch_pending_workflow_changes(db.as_ref(), payroll)
+        .await
+        .unwrap();
+    assert_eq!(pending.len(), 1);
+    assert_eq!(held.pending_change_id, Some(pending[0].id));
+    assert_eq!(
+        pending[0].payload["configuration"]["cron"],
+        json!("0 0 10 * * *")
+    );
+
+    let stored = |trigger_id| {
+        let db = db.clone();
+        async move {
+            crate::repository::fetch_workflow_trigger(db.as_ref(), trigger_id)
+                .await
+                .unwrap()
+                .unwrap()
+                .configuration["cron"]
+                .clone()
+        }
+    };
+    assert_eq!(stored(payroll_trigger).await, json!("0 0 9 * * *"));
+    assert_eq!(stored(trigger_ids[0].1).await, json!("0 0 10 * * *"));
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn trigger_update_preview_lists_fire_times_without_saving() {
+    let (db, path) = test_db().await;
+    let saved = crate::repository::upsert_workflow(&db, &workflow(None, "digest"))
+        .await
+        .unwrap();
+    let mut cron_trigger = trigger(None, saved.id.unwrap());
+    cron_trigger.kind = WorkflowTriggerKind::Cron;
+    cron_trigger.configuration = json!({ "cron": "0 0 9 * * *" });
+    let stored = crate::repository::upsert_workflow_trigger(&db, &cron_trigger)
+        .await
+        .unwrap();
+
+    let mut edited = stored.clone();
+    edited.configuration = json!({ "cron": "0 30 17 * * *" });
+    let preview = crate::repository::preview_workflow_trigger_update(&db, &edited)
+        .await
+        .unwrap();
+
+    let schedule = preview.schedule;
+    assert!(schedule.changed());
+    assert_eq!(schedule.before_cron.as_deref(), Some("0 0 9 * * *"));
+    assert_eq!(schedule.after_cron.as_deref(), Some("0 30 17 * * *"));
+    assert_eq!(schedule.before.len(), 5);
+    assert_eq!(schedule.after.len(), 5);
+    assert!(schedule.before.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(
+        schedule
+            .after
+            .iter()
+            .all(|time| time.format("%H:%M").to_string() == "17:30")
+    );
+    assert!(schedule.error.is_none());
+
+    // a preview never writes: the stored trigger keeps its old cron.
+    let reloaded = crate::repository::fetch_workflow_trigger(&db, stored.id.unwrap())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(reloaded.configuration["cron"], json!("0 0 9 * * *"));
+    let _ = std::fs::remove_file(path);
+}
+
+// a backfill replays the cron slots in its window oldest first, never keeping more than its
+// concurrency of runs open, and a workflow takes one running backfill at a time.
+#[tokio::test]
+async fn backfill_replays_past_slots_at_its_concurrency() {
+    use axum::extract::Query;
+    use runinator_models::backfills::{BackfillRequest, BackfillStatus};
+
+    let (db, path) = test_db().await;
+    let db = Arc::new(db);
+    let saved = crate::repository::upsert_workflow(db.as_ref(), &workflow(None, "dump"))
+        .await
+        .unwrap();
+    let mut hourly = trigger(None, saved.id.unwrap());
+    hourly.kind = WorkflowTriggerKind::Cron;
+    hourly.configuration = json!({ "cron": "0 0 * * * *", "parameters": { "table": "orders" } });
+    let stored = crate::repository::upsert_workflow_trigger(db.as_ref(), &hourly)
+        .await
+        .unwrap();
+    let trigger_id = stored.id.unwrap();
+
+    let request = || BackfillRequest {
+        from: "2026-01-01T00:00:00Z".parse().unwrap(),
+        to: "2026-01-01T04:00:00Z".parse().unwrap(),
+        concurrency: Some(2),
+    };
+    let (status, Json(response)) = crate::handlers::triggers::create_workflow_backfill(
+        Extension(db.clone()),
+        Extension(AuthContext::disabled_admin()),
+        Path(trigger_id),
+        Query(request()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    let crate::models::ApiResponse::WorkflowBackfill(backfill) = response else {
+        panic!("expected the backfill");
+    };
+    assert_eq!(backfill.slots.len(), 5);
+
+    let (status, _) = crate::handlers::triggers::create_workflow_backfill(
+        Extension(db.clone()),
+        Extension(AuthContext::disabled_admin()),
+        Path(trigger_id),
+        Query(request()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let first = crate::repository::fire_workflow_backfills(db.as_ref())
+        .await
+        .unwrap();
+    assert_eq!(first.len(), 2);
+    assert_eq!(
+        first[0].parameters,
+        json!({ "table": "orders", "scheduled_for": "2026-01-01T00:00:00+00:00" })
+    );
+    assert_eq!(
+        first[0].trigger_source_kind,
+        Some(runinator_models::replicas::TriggerSourceKind::Backfill)
+    );
+    // both slots are still open, so nothing more starts.
+    assert!(
+        crate::repository::fire_workflow_backfills(db.as_ref())
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    for run in &first {
+        db.update_workflow_run_status(run.id, WorkflowStatus::Succeeded, None, None, None)
+            .await
+            .unwrap();
+    }
+    let second = crate::repository::fire_workflow_backfills(db.as_ref())
+        .await
+        .unwrap();
+    assert_eq!(second.len(), 2);
+    assert_eq!(
+        second[1].parameters["scheduled_for"],
+        json!("2026-01-01T03:00:00+00:00")
+    );
+
+    let (status, Json(response)) = crate::handlers::triggers::cancel_workflow_backfill(
+        Extension(db.clone()),
+        Extension(AuthContext::disabled_admin()),
+        Path(backfill.id),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let crate::models::ApiResponse::WorkflowBackfill(canceled) = response else {
+        panic!("expected the canceled backfill");
+    };
+    assert_eq!(canceled.status, BackfillStatus::Canceled);
+    assert_eq!(canceled.fired, 4);
+    for run in &second {
+        db.update_workflow_run_status(run.id, WorkflowStatus::Succeeded, None, None, None)
+            .await
+            .unwrap();
+    }
+    assert!(
+        crate::repository::fire_workflow_backfills(db.as_ref())
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    let _ = std::fs::remove_file(path);
+}
+
+// saving a webhook trigger hands out its token once and stores only the hash; posting the token
+// starts a run with the posted parameters merged over the configured ones.
+#[tokio::test]
+async fn webhook_trigger_token_is_issued_once_and_starts_runs() {
+    use axum::{body::Bytes, extract::ConnectInfo, http::HeaderMap};
+
+    let (db, path) = test_db().await;
+    let db = Arc::new(db);
+    let events = crate::events::EventBus::new(
+        tokio::sync::broadcast::channel(16).0,
+        Arc::new(InMemoryBroker::new()),
+    );
+    let saved = crate::repository::upsert_workflow(db.as_ref(), &workflow(None, "deploy"))
+        .await
+        .unwrap();
+    let workflow_id = saved.id.unwrap();
+    let mut webhook = trigger(None, workflow_id);
+    webhook.kind = WorkflowTriggerKind::Webhook;
+    webhook.configuration = json!({ "parameters": { "environment": "dev", "ref": "main" } });
+
+    let (status, Json(response)) = crate::handlers::triggers::upsert_workflow_trigger(
+        Extension(db.clone()),
+        Extension(events.clone()),
+        Extension(AuthContext::disabled_admin()),
+        Path(workflow_id),
+        Json(webhook),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let crate::models::ApiResponse::WorkflowTrigger(issued) = response else {
+        panic!("expected the saved trigger");
+    };
+    let token = issued.configuration["token"].as_str().unwrap().to_string();
+    let stored = db
+        .fetch_workflow_trigger(issued.id.unwrap())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(stored.configuration.get("token").is_none());
+    assert_eq!(
+        stored.configuration["token_hash"],
+        json!(crate::auth::hash_secret(&token))
+    );
+
+    let post = |token: String, body: &'static str| {
+        crate::handlers::webhook::trigger_webhook(
+            Extension(db.clone()),
+            Extension(events.clone()),
+            HeaderMap::new(),
+            ConnectInfo("127.0.0.1:9000".parse().unwrap()),
+            Path(token),
+            Bytes::from_static(body.as_bytes()),
+        )
+    };
+    let response = post(token.clone(), r#"{ "ref": "release" }"#).await;
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body: Value = serde_json::from_slice::<serde_json::Value>(&body)
+        .unwrap()
+        .into();
+    assert_eq!(
+        body["run"]["parameters"],
+        json!({ "environment": "dev", "ref": "release" })
+    );
+    assert_eq!(body["run"]["trigger_source_kind"], json!("webhook"));
+
+    assert_eq!(post(token, "[1]").await.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+        post("not-a-token".into(), "").await.status(),
+        StatusCode::NOT_FOUND
+    );
+    let _ = std::fs::remove_file(path);
+}
+
+// the trigger read endpoints report when a trigger will actually fire, not just its cron slot.
+#[tokio::test]
+async fn trigger_reads_report_the_effective_next_run() {
+    use chrono::{DateTime, Duration, Utc};
+    use runinator_models::workflows::ScheduleAdjustmentKind;
+
+    let (db, path) = test_db().await;
+    let db = Arc::new(db);
+    let saved = crate::repository::upsert_workflow(db.as_ref(), &workflow(None, "nightly"))
+        .await
+        .unwrap();
+    let workflow_id = saved.id.unwrap();
+    let now = DateTime::from_timestamp(Utc::now().timestamp(), 0).unwrap();
+    let mut cron = trigger(None, workflow_id);
+    cron.kind = WorkflowTriggerKind::Cron;
+    cron.configuration = json!({ "cron": "0 * * * * *" });
+    cron.blackout_start = Some(now - Duration::hours(1));
+    cron.blackout_end = Some(now + Duration::hours(1));
+    let cron = db.upsert_workflow_trigger(&cron).await.unwrap();
+    db.upsert_workflow_trigger(&trigger(None, workflow_id))
+        .await
+        .unwrap();
+
+    let (status, Json(response)) = crate::handlers::triggers::get_workflow_trigger(
+        Extension(db.clone()),
+        Extension(AuthContext::disabled_admin()),
+        Path(cron.id.unwrap()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let crate::models::ApiResponse::WorkflowTriggerView(view) = response else {
+        panic!("expected the trigger with its effective schedule");
+    };
+    // every minute is a slot, but the blackout holds them all until it ends.
+    assert_eq!(view.schedule.effective_next_run, cron.blackout_end);
+    assert_eq!(
+        view.schedule
+            .schedule_adjustments
+            .last()
+            .map(|adjustment| adjustment.kind),
+        Some(ScheduleAdjustmentKind::Blackout)
+    );
+
+    let (status, Json(response)) = crate::handlers::triggers::get_workflow_triggers(
+        Extension(db.clone()),
+        Extension(AuthContext::disabled_admin()),
+        Path(workflow_id),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let crate::models::ApiResponse::WorkflowTriggerViewList(views) = response else {
+        panic!("expected the workflow's triggers");
+    };
+    assert_eq!(views.len(), 2);
+    // a manual trigger has no schedule, so the field is left out rather than null.
+    let manual = views
+        .iter()
+        .find(|view| view.trigger.kind == WorkflowTriggerKind::Manual)
+        .unwrap();
+    let body = serde_json::to_value(manual).unwrap();
+    assert!(body.get("effective_next_run").is_none());
+    assert_eq!(body["workflow_id"], serde_json::json!(workflow_id));
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn cron_preview_validates_and_defers_blacked_out_slots() {
+    use chrono::{TimeZone, Utc};
+    use runinator_models::workflows::CronPreviewRequest;
+
+    let at = |hour: u32, minute: u32| Utc.with_ymd_and_hms(2026, 6, 18, hour, minute, 0).unwrap();
+    let request = |cron: &str| CronPreviewRequest {
+        cron: cron.to_string(),
+        count: Some(4),
+        from: Some(at(9, 30)),
+        blackout_start: Some(at(11, 0)),
+        blackout_end: Some(at(12, 30)),
+        timezone: None,
+    };
+
+    let (status, Json(response)) =
+        crate::handlers::triggers::preview_cron(Json(request("0 0 * * * *"))).await;
+    assert_eq!(status, StatusCode::OK);
+    let crate::models::ApiResponse::CronPreview(preview) = response else {
+        panic!("expected a cron preview");
+    };
+    assert!(preview.valid);
+    // 11:00 and 12:00 fall in the blackout and collapse into one firing when it ends.
+    let fires = preview
+        .fire_times
+        .iter()
+        .map(|fire| (fire.at, fire.deferred))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        fires,
+        vec![
+            (at(10, 0), false),
+            (at(12, 30), true),
+            (at(13, 0), false),
+            (at(14, 0), false),
+        ]
+    );
+
+    let (status, Json(response)) =
+        crate::handlers::triggers::preview_cron(Json(request("every tuesday"))).await;
+    assert_eq!(status, StatusCode::OK);
+    let crate::models::ApiResponse::CronPreview(preview) = response else {
+        panic!("expected a cron preview");
+    };
+    assert!(!preview.valid);
+    assert!(preview.fire_times.is_empty());
+    assert!(preview.error.is_some());
+
+    let mut inverted = request("0 0 * * * *");
+    inverted.blackout_end = Some(at(10, 0));
+    let (status, _) = crate::handlers::triggers::preview_cron(Json(inverted)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn result_consumer_acks_duplicate_deliveries_and_persists_results_once() {
+    let (db, path) = test_db().await;
+    let db = Arc::new(db);
+    let node_run = create_node_run(&db).await;
+    let command = action_command(node_run.workflow_run_id, node_run.id, &node_run.node_id);
+    let chunk = WorkflowResultEvent::chunk(
+        &command,
+        NewRunChunk {
+            stream: "log".into(),
+            content: "hello".into(),
+        },
+    );
+    let status = WorkflowResultEvent::status(
+        &command,
+        WorkflowStatus::Succeeded,
+        Some(json!({ "ok": true })),
+        Some("done".into()),
+    );
+    let artifact = WorkflowResultEvent::artifact(
+        &command,
+        NewRunArtifact {
+            name: "report.json".into(),
+            mime_type: "application/json".into(),
+            size_bytes: 17,
+            uri: "memory://report.json".into(),
+            metadata: json!({ "source": "test" }),
+        },
+    );
+    let broker = Arc::new(RecordingBroker::new());
+    let broker_for_consumer: Arc<dyn Broker> = broker.clone();
+    let publisher = runinator_engine::EnginePublisher::new(broker_for_consumer.clone());
+    let shutdown = Arc::new(Notify::new());
+    let consumer = tokio::spawn(crate::result_consumer::run_result_consumer(
+        db.clone(),
+        broker_for_consumer,
+        publisher,
+        shutdown.clone(),
+    ));
+
+    publish_duplicate_results(&broker, &[chunk.clone(), status.clone(), artifact.clone()]).await;
+    wait_until(|| broker.result_acks().len() == 6).await;
+
+    shutdown.notify_waiters();
+    tokio::time::timeout(Duration::from_secs(1), consumer)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let chunks = db
+        .fetch_workflow_node_run_chunks(node_run.id, None, 100)
+        .await
+        .unwrap();
+    assert_eq!(chunks.len(), 1);
+    assert_eq!(chunks[0].stream, "log");
+    assert_eq!(chunks[0].content, "hello");
+
+    let node_run = db
+        .fetch_workflow_node_run(node_run.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(node_run.status, WorkflowStatus::Succeeded);
+    assert_eq!(node_run.output_json, Some(json!({ "ok": true })));
+    assert_eq!(node_run.message.as_deref(), Some("done"));
+
+    let artifacts = db
+        .fetch_workflow_node_run_artifacts(node_run.id)
+        .await
+        .unwrap();
+    assert_eq!(artifacts.len(), 1);
+    assert_eq!(artifacts[0].name, "report.json");
+    assert_eq!(artifacts[0].uri, "memory://report.json");
+
+    let received = broker.result_receives();
+    let acked = broker.result_acks();
+    assert_eq!(received.len(), 6);
+    assert_eq!(acked.len(), 6);
+    assert_eq!(received, acked);
+    assert!(broker.result_nacks().is_empty());
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn result_consumer_dead_letters_poison_result_events_after_retries() {
+    let (db, path) = test_db().await;
+    let db = Arc::new(db);
+    let node_run = create_node_run(&db).await;
+    let command = action_command(
+        node_run.workflow_run_id,
+        node_run.id,
+        "__force_result_persist_failure__",
+    );
+    let poison = WorkflowResultEvent::chunk(
+        &command,
+        NewRunChunk {
+            stream: "log".into(),
+            content: "poison".into(),
+        },
+    );
+    let broker = Arc::new(RecordingBroker::new());
+    let broker_for_consumer: Arc<dyn Broker> = broker.clone();
+    let publisher = runinator_engine::EnginePublisher::new(broker_for_consumer.clone());
+    let shutdown = Arc::new(Notify::new());
+    let consumer = tokio::spawn(crate::result_consumer::run_result_consumer_with_policy(
+        db.clone(),
+        broker_for_consumer,
+        publisher,
+        shutdown.clone(),
+        crate::result_consumer::ResultConsumerPolicy::new(2, Duration::from_millis(1)),
+    ));
+
+    broker
+        .publish_result(ResultMessage {
+            event: poison,
+            dedupe_key: Some("poison-result".into()),
+            enqueued_at: chrono::Utc::now(),
+        })
+        .await
+        .unwrap();
+    wait_until(|| broker.result_acks().len() == 1 && broker.result_nacks().len() == 1).await;
+
+    shutdown.notify_waiters();
+    tokio::time::timeout(Duration::from_secs(1), consumer)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let chunks = db
+        .fetch_workflow_node_run_chunks(node_run.id, None, 100)
+        .await
+        .unwrap();
+    assert!(chunks.is_empty());
+
+    // the poison event leaves a durable dead-letter record on the result channel.
+    let dead_letters = db.fetch_dead_letters(None, 100).await.unwrap();
+    assert_eq!(dead_letters.len(), 1);
+    assert_eq!(
+        dead_letters[0]
+            .get("channel")
+            .and_then(runinator_models::value::Value::as_str),
+        Some("result")
+    );
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn result_consumer_backoff_grows_and_is_capped() {
+    let policy = crate::result_consumer::ResultConsumerPolicy::new(5, Duration::from_millis(100));
+    // full jitter keeps every delay within [0, base * 2^(attempt-1)] for early attempts.
+    for attempt in 1..=4u32 {
+        let ceiling = 100u128 * (1u128 << (attempt - 1));
+        let backoff = policy.backoff_for(attempt).as_millis();
+        assert!(
+            backoff <= ceiling,
+            "attempt {attempt} backoff {backoff} exceeded ceiling {ceiling}"
+        );
+    }
+    // a large attempt count is clamped to the 30s max backoff and cannot overflow.
+    let huge = policy.backoff_for(40).as_millis();
+    assert!(huge <= 30_000, "backoff {huge} exceeded max_backoff");
+}
+
+#[tokio::test]
+async fn output_node_promotes_artifacts_to_the_run() {
+    let (db, path) = test_db().await;
+    let node_run = create_node_run(&db).await;
+    // a node artifact, then an output node promoting it to the run.
+    let artifact = db
+        .add_workflow_node_run_artifact(
+            node_run.id,
+            &NewRunArtifact {
+                name: "dump.csv".into(),
+                mime_type: "text/csv".into(),
+                size_bytes: 42,
+                uri: "memory://dump.csv".into(),
+                metadata: json!({}),
+            },
+        )
+        .await
+        .unwrap();
+
+    // the run-wide artifact fetch (used to build runtime context) sees it.
+    let run_artifacts = db
+        .fetch_workflow_node_run_artifacts_for_run(node_run.workflow_run_id)
+        .await
+        .unwrap();
+    assert_eq!(run_artifacts.len(), 1);
+    assert_eq!(run_artifacts[0].id, artifact.id);
+
+    let stored = db
+        .add_workflow_run_artifact(&NewWorkflowRunArtifact {
+            workflow_run_id: node_run.workflow_run_id,
+            node_id: node_run.node_id.clone(),
+            artifact_id: artifact.id,
+            name: "report".into(),
+            mime_type: artifact.mime_type.clone(),
+            size_bytes: artifact.size_bytes,
+            uri: artifact.uri.clone(),
+            metadata: json!({}),
+        })
+        .await
+        .unwrap();
+    assert_eq!(stored.name, "report");
+
+    let artifacts = db
+        .fetch_workflow_run_artifacts(node_run.workflow_run_id)
+        .await
+        .unwrap();
+    assert_eq!(artifacts.len(), 1);
+    assert_eq!(artifacts[0].artifact_id, artifact.id);
+    assert_eq!(artifacts[0].name, "report");
+    assert_eq!(artifacts[0].uri, "memory://dump.csv");
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn run_outputs_are_validated_recorded_and_listed() {
+    let (db, path) = test_db().await;
+    let db = Arc::new(db);
+    let node_run = create_node_run(db.as_ref()).await;
+    let record = |outputs: Value| {
+        crate::handlers::node_runs::record_workflow_run_outputs(
+            Extension(db.clone()),
+            Extension(AuthContext::disabled_admin()),
+            Path(node_run.workflow_run_id),
+            Json(serde_json::from_value(serde_json::json!({ "outputs": outputs })).unwrap()),
+        )
+    };
+
+    let (status, _) = record(json!({ "rows": { "nested": true } })).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, Json(response)) = record(json!({ "rows": 1200, "batch": "b-7" })).await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    let crate::models::ApiResponse::WorkflowRunOutputs(outputs) = response else {
+        panic!("expected the recorded outputs");
+    };
+    assert_eq!(outputs.len(), 2);
+
+    let (status, Json(response)) = crate::handlers::node_runs::get_workflow_run_outputs(
+        Extension(db.clone()),
+        Extension(AuthContext::disabled_admin()),
+        Path(node_run.workflow_run_id),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let crate::models::ApiResponse::WorkflowRunOutputs(outputs) = response else {
+        panic!("expected the run outputs");
+    };
+    assert_eq!(outputs[0].name, "batch");
+    assert_eq!(outputs[1].value, json!(1200));
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn graphql_nests_visible_workflows_runs_and_executors() {
+    use runinator_models::replicas::{ReplicaKind, ReplicaRegistrationRequest};
+
+    let (db, path) = test_db().await;
+    let db = Arc::new(db);
+    let worker = db
+        .register_replica(
+            ReplicaRegistrationRequest {
+                replica_type: ReplicaKind::Worker,
+                instance_id: "graphql-worker".into(),
+                runtime_id: Uuid::new_v4().to_string(),
+                display_name: Some("graphql worker".into()),
+                host: None,
+                port: None,
+                base_path: None,
+                version: None,
+                attributes: json!({}),
+            },
+            None,
+            &AuthContext::disabled_admin(),
+        )
+        .await
+        .unwrap();
+
+    let org_a = Uuid::now_v7();
+    let mut hidden = workflow(None, "hidden");
+    hidden.org_id = Some(org_a);
+    crate::repository::upsert_workflow(db.as_ref(), &hidden)
+        .await
+        .unwrap();
+    let shown = crate::repository::upsert_workflow(db.as_ref(), &workflow(None, "shown"))
+        .await
+        .unwrap();
+    let mut run_ids = Vec::new();
+    for _ in 0..3 {
+        let run = crate::repository::create_workflow_run(
+            db.as_ref(),
+            shown.id.unwrap(),
+            json!({}),
+            false,
+            None,
+            Default::default(),
+        )
+        .await
+        .unwrap();
+        run_ids.push(run.id);
+    }
+    let newest = *run_ids.last().unwrap();
+    let node_run = crate::repository::create_workflow_node_run(
+        db.as_ref(),
+        newest,
+        "start".into(),
+        json!({}),
+        None,
+    )
+    .await
+    .unwrap();
+    let now = chrono::Utc::now();
+    crate::repository::claim_workflow_node_run_executor(
+        db.as_ref(),
+        node_run.id,
+        worker.replica_id,
+        now,
+        now - chrono::Duration::seconds(60),
+    )
+    .await
+    .unwrap();
+
+    // an org-B member granted view on both sees the global workflow and not org A's.
+    let hidden_id = crate::repository::fetch_workflows(db.as_ref())
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|workflow| workflow.name == "hidden")
+        .and_then(|workflow| workflow.id)
+        .unwrap();
+    let user_id = db
+        .create_user("graphql-user".into(), None, false, None)
+        .await
+        .unwrap()
+        .id
+        .unwrap();
+    for workflow_id in [hidden_id, shown.id.unwrap()] {
+        db.create_grant(grant(
+            workflow_id,
+            PrincipalType::User,
+            user_id,
+            Permission::View,
+        ))
+        .await
+        .unwrap();
+    }
+    let ctx = AuthContext {
+        principal_id: Some(user_id),
+        is_admin: false,
+        kind: PrincipalKind::User,
+        org_id: Some(Uuid::now_v7()),
+        org_role: Some(OrgRole::Member),
+    };
+    let query = |query: &str, ctx: AuthContext| {
+        let db = db.clone();
+        let query = query.to_string();
+        async move {
+            let Json(response) = crate::graphql::graphql::<SqliteDb>(
+                Extension(db),
+                Extension(crate::graphql::schema::<SqliteDb>()),
+                Extension(ctx),
+                Json(async_graphql::Request::new(query)),
+            )
+            .await;
+            assert!(response.errors.is_empty(), "{:?}", response.errors);
+            serde_json::to_value(&response.data).unwrap()
+        }
+    };
+    let data = query(
+        "{ workflows { name runs(last: 2) { id executors { replicaId displayName } } } }",
+        ctx.clone(),
+    )
+    .await;
+    let workflows = data["workflows"].as_array().unwrap();
+    assert_eq!(workflows.len(), 1);
+    assert_eq!(workflows[0]["name"], "shown");
+    let runs = workflows[0]["runs"].as_array().unwrap();
+    assert_eq!(runs.len(), 2);
+    assert_eq!(runs[0]["id"], newest.to_string());
+    assert_eq!(
+        runs[0]["executors"],
+        serde_json::json!([{
+            "replicaId": worker.replica_id.to_string(),
+            "displayName": "graphql worker"
+        }])
+    );
+    assert_eq!(runs[1]["executors"], serde_json::json!([]));
+
+    let data = query(
+        &format!("{{ workflow(id: \"{hidden_id}\") {{ name }} workers {{ displayName status }} }}"),
+        ctx,
+    )
+    .await;
+    assert_eq!(data["workflow"], serde_json::Value::Null);
+    assert_eq!(
+        data["workers"],
+        serde_json::json!([{ "displayName": "graphql worker", "status": "live" }])
+    );
+
+    let data = query(
+        &format!("{{ workflow(id: \"{hidden_id}\") {{ name }} }}"),
+        AuthContext::disabled_admin(),
+    )
+    .await;
+    assert_eq!(data["workflow"]["name"], "hidden");
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn grpc_service_runs_the_rest_handlers_and_streams_visible_events() {
+    use crate::events::{AppEvent, AppEventKind};
+    use futures::StreamExt;
+    use runinator_models::grpc::{self, runinator_server::Runinator};
+    use tonic::{Code, Request};
+
+    let (db, path) = test_db().await;
+    let db = Arc::new(db);
+    let local = tokio::sync::broadcast::channel(16).0;
+    let service = crate::grpc::GrpcService {
+        db: db.clone(),
+        events: crate::events::EventBus::new(local.clone(), Arc::new(InMemoryBroker::new())),
+    };
+    let admin = AuthContext::disabled_admin();
+    // the auth middleware's part: attach the resolved principal to the call.
+    fn call<M>(ctx: &AuthContext, message: M) -> Request<M> {
+        let mut request = Request::new(message);
+        request.extensions_mut().insert(ctx.clone());
+        request
+    }
+
+    let created = service
+        .upsert_workflow(call(
+            &admin,
+            grpc::UpsertWorkflowRequest {
+                workflow: Some(grpc::Workflow::try_from(&workflow(None, "over-grpc")).unwrap()),
+            },
+        ))
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(!created.id.is_empty());
+    let fetched = service
+        .get_workflow(call(
+            &admin,
+            grpc::GetWorkflowRequest {
+                id: created.id.clone(),
+            },
+        ))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(fetched, created);
+    let listed = service
+        .list_workflows(call(
+            &admin,
+            grpc::ListWorkflowsRequest {
+                name: Some("over-grpc".into()),
+            },
+        ))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(listed.workflows, vec![created.clone()]);
+
+    // a user without a grant is refused as over REST, and run logs stay service-only.
+    let user = AuthContext {
+        principal_id: Some(
+            db.create_user("grpc-user".into(), None, false, None)
+                .await
+                .unwrap()
+                .id
+                .unwrap(),
+        ),
+        is_admin: false,
+        kind: PrincipalKind::User,
+        org_id: None,
+        org_role: None,
+    };
+    let denied = service
+        .delete_workflow(call(
+            &user,
+            grpc::DeleteWorkflowRequest {
+                id: created.id.clone(),
+            },
+        ))
+        .await
+        .unwrap_err();
+    assert_eq!(denied.code(), Code::PermissionDenied);
+    let run_id = Uuid::now_v7().to_string();
+    let denied = service
+        .append_run_chunk(call(
+            &user,
+            grpc::AppendRunChunkRequest {
+                run_id: run_id.clone(),
+                stream: "stdout".into(),
+                content: "hi".into(),
+            },
+        ))
+        .await
+        .unwrap_err();
+    assert_eq!(denied.code(), Code::PermissionDenied);
+    let chunks = service
+        .list_run_chunks(call(
+            &admin,
+            grpc::ListRunChunksRequest {
+                run_id,
+                cursor: None,
+                limit: None,
+            },
+        ))
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(chunks.chunks.is_empty());
+
+    service
+        .delete_workflow(call(
+            &admin,
+            grpc::DeleteWorkflowRequest {
+                id: created.id.clone(),
+            },
+        ))
+        .await
+        .unwrap();
+    let missing = service
+        .get_workflow(call(&admin, grpc::GetWorkflowRequest { id: created.id }))
+        .await
+        .unwrap_err();
+    assert_eq!(missing.code(), Code::NotFound);
+
+    // the watch drops other orgs' events and any type the caller did not ask for.
+    let org = Uuid::now_v7();
+    let member = AuthContext {
+        org_id: Some(org),
+        org_role: Some(OrgRole::Member),
+        ..user
+    };
+    let mut events = service
+        .watch_events(call(
+            &member,
+            grpc::WatchEventsRequest {
+                types: vec!["run_status_changed".into()],
+            },
+        ))
+        .await
+        .unwrap()
+        .into_inner();
+    let run_id = Uuid::now_v7();
+    for event in [
+        AppEvent::for_org(
+            Uuid::now_v7(),
+            AppEventKind::RunStatusChanged {
+                run_id,
+                terminal: false,
+            },
+        ),
+        AppEvent::for_org(org, AppEventKind::RunChunkAdded { run_id }),
+        AppEvent::for_org(
+            org,
+            AppEventKind::RunStatusChanged {
+                run_id,
+                terminal: true,
+            },
+        ),
+    ] {
+        local.send(event).unwrap();
+    }
+    let event = events.next().await.unwrap().unwrap();
+    assert_eq!(
+        event,
+        grpc::Event {
+            org_id: Some(org.to_string()),
+            r#type: "run_status_changed".into(),
+            run_id: Some(run_id.to_string()),
+            terminal: Some(true),
+            ..Default::default()
+        }
+    );
+
+    let _ = std::fs::remove_file(path);
+}
+
+async fn test_db() -> (SqliteDb, std::path::PathBuf) {
+    let path = std::env::temp_dir().join(format!(
+        "runinator-ws-workflows-{}.db",
+        uuid::Uuid::new_v4()
+    ));
+    let db = SqliteDb::new(path.to_str().unwrap()).await.unwrap();
+    db.run_init_scripts(&Vec::new()).await.unwrap();
+    (db, path)
+}
+
+// a one-off slack job with no retries, priority, or queue, and the event bus it is submitted
+// through, which publishes onto `broker`. tests override only the request fields they check.
+fn job_fixture(
+    broker: Arc<dyn Broker>,
+) -> (
+    crate::events::EventSender,
+    runinator_models::jobs::JobRequest,
+) {
+    let events = crate::events::EventBus::new(tokio::sync::broadcast::channel(16).0, broker);
+    let request = runinator_models::jobs::JobRequest {
+        action_name: "slack".into(),
+        action_function: "send_message".into(),
+        configuration: json!({ "channel": "#ops", "text": "hi" }),
+        timeout_seconds: 30,
+        required_labels: Default::default(),
+        max_retries: 0,
+        retry_backoff_seconds: 30,
+        priority: 0,
+        queue: None,
+    };
+    (events, request)
+}
+
+// one outbox pass onto `broker`, as the web service's dispatch loop runs it.
+async fn publish_dispatches(db: &SqliteDb, broker: &dyn Broker, dead_letter_after: Option<i64>) {
+    crate::repository::publish_pending_action_dispatches(
+        db,
+        broker,
+        "ws",
+        30,
+        10,
+        dead_letter_after,
+    )
+    .await
+    .unwrap();
+}
+
+async fn create_node_run(db: &SqliteDb) -> WorkflowNodeRun {
+    let workflow = crate::repository::upsert_workflow(db, &workflow(None, "result-consumer"))
+        .await
+        .unwrap();
+    let workflow_id = workflow.id.unwrap();
+    let run = crate::repository::create_workflow_run(
+        db,
+        workflow_id,
+        json!({}),
+        false,
+        None,
+        Default::default(),
+    )
+    .await
+    .unwrap();
+    crate::repository::update_workflow_run_status(
+        db,
+        run.id,
+        WorkflowStatus::Running,
+        Some("start".into()),
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    crate::repository::create_workflow_node_run(db, run.id, "node-a".into(), json!({}), None)
+        .await
+        .unwrap()
+}
+
+fn action_command(
+    workflow_run_id: Uuid,
+    workflow_node_run_id: Uuid,
+    node_id: &str,
+) -> ActionCommand {
+    ActionCommand {
+        command_id: Uuid::new_v4(),
+        workflow_run_id,
+        workflow_node_run_id,
+        node_id: node_id.into(),
+        action: WorkflowAction {
+            provider: "test".into(),
+            function: "execute".into(),
+            timeout_seconds: 60,
+            configuration: runinator_models::workflows::WorkflowObject::default(),
+            mcp_enabled: false,
+            tags: Vec::new(),
+            required_labels: Default::default(),
+            allowed_secrets: Vec::new(),
+            preflight: Vec::new(),
+            cache_ttl_seconds: None,
+            priority: 0,
+            queue: None,
+        },
+        attempt: 1,
+        parameters: json!({}),
+        target: Default::default(),
+        trace_id: Uuid::nil(),
+        trace_context: Default::default(),
+    }
+}
+
+async fn publish_duplicate_results(broker: &RecordingBroker, events: &[WorkflowResultEvent]) {
+    for event in events {
+        for duplicate in 0..2 {
+            broker
+                .publish_result(ResultMessage {
+                    event: event.clone(),
+                    dedupe_key: Some(format!("{}-{duplicate}", event.event_id)),
+                    enqueued_at: chrono::Utc::now(),
+                })
+                .await
+                .unwrap();
+        }
+    }
+}
+
+async fn wait_until(mut condition: impl FnMut() -> bool) {
+    let deadline = Instant::now() + Duration::from_secs(2);
+    while Instant::now() < deadline {
+        if condition() {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert!(condition(), "condition was not met before timeout");
+}
+
+#[derive(Clone, Default)]
+struct RecordingBroker {
+    inner: InMemoryBroker,
+    result_receives: Arc<Mutex<HashSet<Uuid>>>,
+    result_acks: Arc<Mutex<HashSet<Uuid>>>,
+    result_nacks: Arc<Mutex<HashSet<Uuid>>>,
+    refuse_publish: Arc<std::sync::atomic::AtomicBool>,
+}
+
+impl RecordingBroker {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn result_receives(&self) -> HashSet<Uuid> {
+        self.result_receives.lock().unwrap().clone()
+    }
+
+    fn result_acks(&self) -> HashSet<Uuid> {
+        self.result_acks.lock().unwrap().clone()
+    }
+
+    fn result_nacks(&self) -> HashSet<Uuid> {
+        self.result_nacks.lock().unwrap().clone()
+    }
+}
+
+#[async_trait::async_trait]
+impl Broker for RecordingBroker {
+    async fn publish(&self, message: BrokerMessage) -> Result<(), BrokerError> {
+        if self
+            .refuse_publish
+            .load(std::sync::atomic::Ordering::Acquire)
+        {
+            return Err(BrokerError::Internal("broker unavailable".into()));
+        }
+        self.inner.publish(message).await
+    }
+
+    async fn receive(&self, consumer: &str) -> Result<BrokerDelivery, BrokerError> {
+        self.inner.receive(consumer).await
+    }
+
+    async fn ack(&self, consumer: &str, delivery_id: Uuid) -> Result<(), BrokerError> {
+        self.inner.ack(consumer, delivery_id).await
+    }
+
+    async fn nack(&self, consumer: &str, delivery_id: Uuid) -> Result<(), BrokerError> {
+        self.inner.nack(consumer, delivery_id).await
+    }
+
+    async fn publish_control(&self, command: ControlCommand) -> Result<(), BrokerError> {
+        self.inner.publish_control(command).await
+    }
+
+    async fn receive_control(&self, consumer: &str) -> Result<ControlDelivery, BrokerError> {
+        self.inner.receive_control(consumer).await
+    }
+
+    async fn ack_control(&self, consumer: &str, delivery_id: Uuid) -> Result<(), BrokerError> {
+        self.inner.ack_control(consumer, delivery_id).await
+    }
+
+    async fn nack_control(&self, consumer: &str, delivery_id: Uuid) -> Result<(), BrokerError> {
+        self.inner.nack_control(consumer, delivery_id).await
+    }
+
+    async fn publish_result(&self, message: ResultMessage) -> Result<(), BrokerError> {
+        self.inner.publish_result(message).await
+    }
+
+    async fn receive_result(&self, consumer: &str) -> Result<ResultDelivery, BrokerError> {
+        let delivery = self.inner.receive_result(consumer).await?;
+        self.result_receives
+            .lock()
+            .unwrap()
+            .insert(delivery.delivery_id);
+        Ok(delivery)
+    }
+
+    async fn ack_result(&self, consumer: &str, delivery_id: Uuid) -> Result<(), BrokerError> {
+        self.inner.ack_result(consumer, delivery_id).await?;
+        self.result_acks.lock().unwrap().insert(delivery_id);
+        Ok(())
+    }
+
+    async fn nack_result(&self, consumer: &str, delivery_id: Uuid) -> Result<(), BrokerError> {
+        self.inner.nack_result(consumer, delivery_id).await?;
+        self.result_nacks.lock().unwrap().insert(delivery_id);
+        Ok(())
+    }
+
+    async fn publish_wake(&self, message: WakeMessage) -> Result<(), BrokerError> {
+        self.inner.publish_wake(message).await
+    }
+
+    async fn receive_wake(&self, consumer: &str) -> Result<WakeDelivery, BrokerError> {
+        self.inner.receive_wake(consumer).await
+    }
+
+    async fn ack_wake(&self, consumer: &str, delivery_id: Uuid) -> Result<(), BrokerError> {
+        self.inner.ack_wake(consumer, delivery_id).await
+    }
+
+    async fn nack_wake(&self, consumer: &str, delivery_id: Uuid) -> Result<(), BrokerError> {
+        self.inner.nack_wake(consumer, delivery_id).await
+    }
+
+    async fn publish_ingress(&self, message: IngressMessage) -> Result<(), BrokerError> {
+        self.inner.publish_ingress(message).await
+    }
+
+    async fn receive_ingress(&self, consumer: &str) -> Result<IngressDelivery, BrokerError> {
+        self.inner.receive_ingress(consumer).await
+    }
+
+    async fn ack_ingress(&self, consumer: &str, delivery_id: Uuid) -> Result<(), BrokerError> {
+        self.inner.ack_ingress(consumer, delivery_id).await
+    }
+
+    async fn nack_ingress(&self, consumer: &str, delivery_id: Uuid) -> Result<(), BrokerError> {
+        self.inner.nack_ingress(consumer, delivery_id).await
+    }
+
+    async fn publish_event(&self, message: EventMessage) -> Result<(), BrokerError> {
+        self.inner.publish_event(message).await
+    }
+
+    async fn receive_event(&self, consumer: &str) -> Result<EventDelivery, BrokerError> {
+        self.inner.receive_event(consumer).await
+    }
+}
+
+fn workflow(id: Option<Uuid>, name: &str) -> WorkflowDefinition {
+    WorkflowDefinition {
+        id,
+        name: name.into(),
+        namespace: None,
+        org_id: None,
+        version: runinator_models::semver::SemVer::new(1, 0, 0),
+        enabled: true,
+        input_type: runinator_models::types::RuninatorType::from_json_schema(
+            &json!({ "type": "object" }),
+        ),
+        definition: WorkflowGraph::from_value(json!({
+            "start": "start",
+            "nodes": [
+                { "id": "start", "kind": "start", "transitions": { "next": { "$node": "done" } } },
+                { "id": "done", "kind": "end" }
+            ]
+        }))
+        .unwrap(),
         created_at: None,
         updated_at: None,
     }
@@ -3314,663 +5929,1231 @@ async fn reducer_maps_items_concurrently_in_order() {
 }
 
 #[tokio::test]
-async fn reducer_map_fails_fast_when_item_fails() {
+async fn reducer_map_fails_fast_when_item_fails() {
+    let (db, path) = test_db().await;
+    let run_id = seed_run(
+        &db,
+        "map-fail-flow",
+        json!({
+            "start": "start",
+            "nodes": [
+                { "id": "start", "kind": "start", "transitions": { "next": { "$node": "map" } } },
+                {
+                    "id": "map",
+                    "kind": "map",
+                    "parameters": {
+                        "items": [1, 2, 3],
+                        "target": { "$node": "work" },
+                        "concurrency": 3
+                    },
+                    "transitions": { "on_success": { "$node": "done" } }
+                },
+                {
+                    "id": "work",
+                    "kind": "action",
+                    "action": { "provider": "console", "function": "run" },
+                    "transitions": { "on_success": { "$node": "map" } }
+                },
+                { "id": "done", "kind": "end" }
+            ]
+        }),
+    )
+    .await;
+
+    // drive the fan-out, then fail the first item's action and succeed the rest.
+    let mut failed_one = false;
+    let mut run = crate::repository::fetch_workflow_run(&db, run_id)
+        .await
+        .unwrap()
+        .unwrap()
+        .0;
+    for _ in 0..64 {
+        drain_ready_nodes(&db).await;
+        run = crate::repository::fetch_workflow_run(&db, run_id)
+            .await
+            .unwrap()
+            .unwrap()
+            .0;
+        if run.status.is_terminal() {
+            break;
+        }
+        let dispatches = db.fetch_pending_action_dispatches(50).await.unwrap();
+        if dispatches.is_empty() {
+            break;
+        }
+        for dispatch in dispatches {
+            db.mark_action_dispatch_published(dispatch.id)
+                .await
+                .unwrap();
+            let status = if failed_one {
+                WorkflowStatus::Succeeded
+            } else {
+                failed_one = true;
+                WorkflowStatus::Failed
+            };
+            let event = WorkflowResultEvent::status(&dispatch.command, status, None, None);
+            crate::repository::apply_workflow_result_event(&db, &event)
+                .await
+                .unwrap();
+        }
+    }
+    // a single failed item fails the whole map (no on_failure routing here).
+    assert_eq!(run.status, WorkflowStatus::Failed);
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn reducer_try_node_runs_body_then_finally() {
+    let (db, path) = test_db().await;
+    let run_id = seed_run(
+        &db,
+        "try-flow",
+        json!({
+            "start": "start",
+            "nodes": [
+                { "id": "start", "kind": "start", "transitions": { "next": { "$node": "try" } } },
+                {
+                    "id": "try",
+                    "kind": "try",
+                    "parameters": { "body": { "$node": "body" }, "finally": { "$node": "cleanup" } },
+                    "transitions": { "on_success": { "$node": "done" } }
+                },
+                { "id": "body", "kind": "output", "transitions": { "on_success": { "$node": "try" } } },
+                { "id": "cleanup", "kind": "output", "transitions": { "on_success": { "$node": "try" } } },
+                { "id": "done", "kind": "end" }
+            ]
+        }),
+    )
+    .await;
+
+    let run = run_to_completion(&db, run_id).await;
+    assert_eq!(run.status, WorkflowStatus::Succeeded);
+    let nodes = db.fetch_workflow_node_runs(run_id).await.unwrap();
+    for stage in ["body", "cleanup"] {
+        assert!(
+            nodes
+                .iter()
+                .any(|n| n.node_id == stage && n.status == WorkflowStatus::Succeeded),
+            "{stage} should have run"
+        );
+    }
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn reducer_parks_approval_then_resolution_wakes_and_completes() {
     let (db, path) = test_db().await;
     let run_id = seed_run(
         &db,
-        "map-fail-flow",
+        "approval-flow",
         json!({
             "start": "start",
             "nodes": [
-                { "id": "start", "kind": "start", "transitions": { "next": { "$node": "map" } } },
+                { "id": "start", "kind": "start", "transitions": { "next": { "$node": "gate" } } },
                 {
-                    "id": "map",
-                    "kind": "map",
-                    "parameters": {
-                        "items": [1, 2, 3],
-                        "target": { "$node": "work" },
-                        "concurrency": 3
-                    },
+                    "id": "gate",
+                    "kind": "approval",
+                    "parameters": { "prompt": "approve?" },
                     "transitions": { "on_success": { "$node": "done" } }
                 },
-                {
-                    "id": "work",
-                    "kind": "action",
-                    "action": { "provider": "console", "function": "run" },
-                    "transitions": { "on_success": { "$node": "map" } }
-                },
                 { "id": "done", "kind": "end" }
             ]
         }),
     )
     .await;
 
-    // drive the fan-out, then fail the first item's action and succeed the rest.
-    let mut failed_one = false;
-    let mut run = crate::repository::fetch_workflow_run(&db, run_id)
+    // the approval node parks the run waiting for an external decision.
+    let run = run_to_completion(&db, run_id).await;
+    assert_eq!(run.status, WorkflowStatus::ApprovalRequired);
+    assert_eq!(run.active_node_id.as_deref(), Some("gate"));
+
+    // resolve the approval the way the api handler would.
+    let approvals = db
+        .fetch_automation_records("approval_requests".into(), Some(run_id), None)
+        .await
+        .unwrap();
+    assert_eq!(approvals.len(), 1);
+    let approval_id = approvals[0]
+        .get("id")
+        .and_then(Value::as_str)
+        .and_then(|raw| raw.parse::<Uuid>().ok())
+        .unwrap();
+    crate::repository::resolve_approval(&db, approval_id, true, None, None, None)
+        .await
+        .unwrap();
+
+    // resolution should have enqueued a ready node; draining now completes the run.
+    let run = run_to_completion(&db, run_id).await;
+    assert_eq!(run.status, WorkflowStatus::Succeeded);
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn reducer_subflow_waits_for_child_and_child_terminal_wakes_parent() {
+    let (db, path) = test_db().await;
+
+    // child workflow that completes on its own.
+    let mut child = workflow(None, "child-flow");
+    child.definition = WorkflowGraph::from_value(json!({
+        "start": "start",
+        "nodes": [
+            { "id": "start", "kind": "start", "transitions": { "next": { "$node": "done" } } },
+            { "id": "done", "kind": "end" }
+        ]
+    }))
+    .unwrap();
+    let child = db.upsert_workflow(&child).await.unwrap();
+    let child_id = child.id.unwrap();
+
+    // parent that launches the child as a waiting subflow.
+    let mut parent = workflow(None, "parent-flow");
+    parent.definition = WorkflowGraph::from_value(json!({
+        "start": "start",
+        "nodes": [
+            { "id": "start", "kind": "start", "transitions": { "next": { "$node": "sub" } } },
+            {
+                "id": "sub",
+                "kind": "subflow",
+                "subflow_id": child_id,
+                "subflow": { "type": "wait" },
+                "transitions": { "on_success": { "$node": "done" } }
+            },
+            { "id": "done", "kind": "end" }
+        ]
+    }))
+    .unwrap();
+    let parent = db.upsert_workflow(&parent).await.unwrap();
+    let parent_run = crate::repository::create_workflow_run(
+        &db,
+        parent.id.unwrap(),
+        json!({}),
+        false,
+        None,
+        Default::default(),
+    )
+    .await
+    .unwrap();
+
+    // draining drives the parent to launch + the child to completion; the terminal child wakes the
+    // parent's subflow node, which then transitions to its end.
+    drain_ready_nodes(&db).await;
+    let (run, _) = crate::repository::fetch_workflow_run(&db, parent_run.id)
         .await
         .unwrap()
+        .unwrap();
+    assert_eq!(
+        run.status,
+        WorkflowStatus::Succeeded,
+        "parent run should complete after child finishes, got {:?}",
+        run.status
+    );
+    let _ = std::fs::remove_file(path);
+}
+
+/// build a `managed_by: wdl` chained trigger for `source_id` targeting `target_name` on `on`.
+fn chained_trigger(source_id: Uuid, target_name: &str, on: &str) -> WorkflowTrigger {
+    WorkflowTrigger {
+        id: None,
+        workflow_id: source_id,
+        kind: WorkflowTriggerKind::Chained,
+        enabled: true,
+        configuration: json!({ "on": on, "target_workflow": target_name, "parameters": {} }),
+        next_execution: None,
+        blackout_start: None,
+        blackout_end: None,
+        metadata: json!({ "managed_by": "wdl" }),
+        created_at: None,
+        updated_at: None,
+    }
+}
+
+#[tokio::test]
+async fn reducer_chains_target_on_source_success() {
+    let (db, path) = test_db().await;
+
+    let target = db
+        .upsert_workflow(&workflow(None, "chain-target-b"))
+        .await
+        .unwrap();
+    let target_id = target.id.unwrap();
+    let source = db
+        .upsert_workflow(&workflow(None, "chain-source-a"))
+        .await
+        .unwrap();
+    let source_id = source.id.unwrap();
+    db.upsert_workflow_trigger(&chained_trigger(source_id, "chain-target-b", "success"))
+        .await
+        .unwrap();
+
+    let source_run = crate::repository::create_workflow_run(
+        &db,
+        source_id,
+        json!({}),
+        false,
+        None,
+        Default::default(),
+    )
+    .await
+    .unwrap();
+    drain_ready_nodes(&db).await;
+
+    let (run, _) = crate::repository::fetch_workflow_run(&db, source_run.id)
+        .await
         .unwrap()
-        .0;
-    for _ in 0..64 {
-        drain_ready_nodes(&db).await;
-        run = crate::repository::fetch_workflow_run(&db, run_id)
+        .unwrap();
+    assert_eq!(run.status, WorkflowStatus::Succeeded);
+
+    // the target started exactly once, provenance-stamped as chained.
+    let target_runs = db
+        .fetch_workflow_runs_for_workflow(target_id)
+        .await
+        .unwrap();
+    assert_eq!(target_runs.len(), 1, "expected one chained target run");
+    assert_eq!(
+        target_runs[0].trigger_source_kind,
+        Some(runinator_models::replicas::TriggerSourceKind::Chained)
+    );
+    assert_eq!(
+        target_runs[0].trigger_metadata.get("chained_from_run_id"),
+        Some(&json!(source_run.id))
+    );
+
+    // re-draining the same terminal run must not start a duplicate (firing dedupe).
+    drain_ready_nodes(&db).await;
+    let target_runs = db
+        .fetch_workflow_runs_for_workflow(target_id)
+        .await
+        .unwrap();
+    assert_eq!(
+        target_runs.len(),
+        1,
+        "chain must be exactly-once per source run"
+    );
+    let _ = std::fs::remove_file(path);
+}
+
+// one source fans out to every target chained on its outcome, and none chained on the other.
+#[tokio::test]
+async fn reducer_chains_every_target_listed_for_the_outcome() {
+    let (db, path) = test_db().await;
+
+    let source = db
+        .upsert_workflow(&workflow(None, "chain-fan-source"))
+        .await
+        .unwrap();
+    let source_id = source.id.unwrap();
+    let mut targets = Vec::new();
+    for (name, on) in [
+        ("chain-fan-report", "success"),
+        ("chain-fan-export", "success"),
+        ("chain-fan-page", "failure"),
+    ] {
+        let target = db.upsert_workflow(&workflow(None, name)).await.unwrap();
+        db.upsert_workflow_trigger(&chained_trigger(source_id, name, on))
             .await
-            .unwrap()
-            .unwrap()
-            .0;
-        if run.status.is_terminal() {
-            break;
-        }
-        let dispatches = db.fetch_pending_action_dispatches(50).await.unwrap();
-        if dispatches.is_empty() {
-            break;
-        }
-        for dispatch in dispatches {
-            db.mark_action_dispatch_published(dispatch.id)
-                .await
-                .unwrap();
-            let status = if failed_one {
-                WorkflowStatus::Succeeded
-            } else {
-                failed_one = true;
-                WorkflowStatus::Failed
-            };
-            let event = WorkflowResultEvent::status(&dispatch.command, status, None, None);
-            crate::repository::apply_workflow_result_event(&db, &event)
-                .await
-                .unwrap();
-        }
+            .unwrap();
+        targets.push((target.id.unwrap(), on));
+    }
+
+    crate::repository::create_workflow_run(
+        &db,
+        source_id,
+        json!({}),
+        false,
+        None,
+        Default::default(),
+    )
+    .await
+    .unwrap();
+    drain_ready_nodes(&db).await;
+
+    for (target_id, on) in targets {
+        let runs = db
+            .fetch_workflow_runs_for_workflow(target_id)
+            .await
+            .unwrap();
+        let expected = usize::from(on == "success");
+        assert_eq!(runs.len(), expected, "target chained on {on}");
     }
-    // a single failed item fails the whole map (no on_failure routing here).
-    assert_eq!(run.status, WorkflowStatus::Failed);
     let _ = std::fs::remove_file(path);
 }
 
 #[tokio::test]
-async fn reducer_try_node_runs_body_then_finally() {
+async fn reducer_chain_respects_status_selector() {
     let (db, path) = test_db().await;
-    let run_id = seed_run(
+
+    let target = db
+        .upsert_workflow(&workflow(None, "chain-target-fail"))
+        .await
+        .unwrap();
+    let target_id = target.id.unwrap();
+    let source = db
+        .upsert_workflow(&workflow(None, "chain-source-ok"))
+        .await
+        .unwrap();
+    let source_id = source.id.unwrap();
+    // only chains on failure; the source succeeds, so nothing should start.
+    db.upsert_workflow_trigger(&chained_trigger(source_id, "chain-target-fail", "failure"))
+        .await
+        .unwrap();
+
+    let source_run = crate::repository::create_workflow_run(
         &db,
-        "try-flow",
-        json!({
-            "start": "start",
-            "nodes": [
-                { "id": "start", "kind": "start", "transitions": { "next": { "$node": "try" } } },
-                {
-                    "id": "try",
-                    "kind": "try",
-                    "parameters": { "body": { "$node": "body" }, "finally": { "$node": "cleanup" } },
-                    "transitions": { "on_success": { "$node": "done" } }
-                },
-                { "id": "body", "kind": "output", "transitions": { "on_success": { "$node": "try" } } },
-                { "id": "cleanup", "kind": "output", "transitions": { "on_success": { "$node": "try" } } },
-                { "id": "done", "kind": "end" }
-            ]
-        }),
+        source_id,
+        json!({}),
+        false,
+        None,
+        Default::default(),
     )
-    .await;
+    .await
+    .unwrap();
+    drain_ready_nodes(&db).await;
 
-    let run = run_to_completion(&db, run_id).await;
+    let (run, _) = crate::repository::fetch_workflow_run(&db, source_run.id)
+        .await
+        .unwrap()
+        .unwrap();
     assert_eq!(run.status, WorkflowStatus::Succeeded);
-    let nodes = db.fetch_workflow_node_runs(run_id).await.unwrap();
-    for stage in ["body", "cleanup"] {
-        assert!(
-            nodes
-                .iter()
-                .any(|n| n.node_id == stage && n.status == WorkflowStatus::Succeeded),
-            "{stage} should have run"
-        );
-    }
+    let target_runs = db
+        .fetch_workflow_runs_for_workflow(target_id)
+        .await
+        .unwrap();
+    assert!(
+        target_runs.is_empty(),
+        "on_failure trigger must not fire when the source succeeds"
+    );
     let _ = std::fs::remove_file(path);
 }
 
 #[tokio::test]
-async fn reducer_parks_approval_then_resolution_wakes_and_completes() {
+async fn reducer_chain_depth_is_bounded() {
     let (db, path) = test_db().await;
-    let run_id = seed_run(
-        &db,
-        "approval-flow",
-        json!({
-            "start": "start",
-            "nodes": [
-                { "id": "start", "kind": "start", "transitions": { "next": { "$node": "gate" } } },
-                {
-                    "id": "gate",
-                    "kind": "approval",
-                    "parameters": { "prompt": "approve?" },
-                    "transitions": { "on_success": { "$node": "done" } }
-                },
-                { "id": "done", "kind": "end" }
-            ]
-        }),
-    )
-    .await;
 
-    // the approval node parks the run waiting for an external decision.
-    let run = run_to_completion(&db, run_id).await;
-    assert_eq!(run.status, WorkflowStatus::ApprovalRequired);
-    assert_eq!(run.active_node_id.as_deref(), Some("gate"));
+    // a workflow that chains to itself would loop forever without the depth cap.
+    let a = db
+        .upsert_workflow(&workflow(None, "chain-loop-a"))
+        .await
+        .unwrap();
+    let a_id = a.id.unwrap();
+    db.upsert_workflow_trigger(&chained_trigger(a_id, "chain-loop-a", "success"))
+        .await
+        .unwrap();
 
-    // resolve the approval the way the api handler would.
-    let approvals = db
-        .fetch_automation_records("approval_requests".into(), Some(run_id), None)
+    crate::repository::create_workflow_run(&db, a_id, json!({}), false, None, Default::default())
         .await
         .unwrap();
-    assert_eq!(approvals.len(), 1);
-    let approval_id = approvals[0]
-        .get("id")
-        .and_then(Value::as_str)
-        .and_then(|raw| raw.parse::<Uuid>().ok())
+    drain_ready_nodes(&db).await;
+
+    // depth 0 (original) chains through depth 32, which is the cap and stops: 33 runs total.
+    let runs = db.fetch_workflow_runs_for_workflow(a_id).await.unwrap();
+    assert_eq!(runs.len(), 33, "self-chain must stop at MAX_CHAIN_DEPTH");
+    let _ = std::fs::remove_file(path);
+}
+
+/// end-to-end coverage for the `/ws/desktop-worker` relay: a minimal router carrying just that one
+/// route (plus its `Extension`s) rather than the full `build_router` stack, since the policy
+/// allow-list and replica-ownership check are this handler's own logic, independent of auth
+/// middleware/CORS/rate-limiting already covered elsewhere. Run with `--features ws` (the real
+/// `WsBroker` client needs `runinator-broker`'s `ws` feature; without it every call would just return
+/// `FeatureDisabled` immediately, which isn't what this test wants to exercise).
+#[cfg(feature = "ws")]
+#[tokio::test]
+async fn ws_desktop_worker_relay_enforces_policy_and_ownership() {
+    use axum::{Router, routing::get};
+    use runinator_broker::ConsumerProfile;
+    use runinator_broker::ws::client::WsBroker;
+    use runinator_comm::ActionTarget;
+    use runinator_models::replicas::{ReplicaKind, ReplicaRegistrationRequest};
+    use tokio::net::TcpListener;
+
+    let (db, path) = test_db().await;
+    let db = Arc::new(db);
+    let inner_broker = Arc::new(InMemoryBroker::new());
+    let broker_ext: Arc<dyn Broker> = inner_broker.clone();
+    let ctx = AuthContext {
+        principal_id: Some(Uuid::now_v7()),
+        is_admin: false,
+        kind: PrincipalKind::Service,
+        org_id: None,
+        org_role: None,
+    };
+
+    let router = Router::new()
+        .route(
+            "/ws/desktop-worker",
+            get(crate::websocket::ws_desktop_worker::<SqliteDb>),
+        )
+        .layer(Extension(db.clone()))
+        .layer(Extension(broker_ext))
+        .layer(Extension(ctx.clone()));
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = tokio::spawn(async move {
+        let _ = axum::serve(listener, router).await;
+    });
+
+    let client = WsBroker::connect(format!("ws://{addr}/ws/desktop-worker"), None);
+
+    // policy: a non-exclusive profile is refused outright, regardless of replica_id/labels.
+    let non_exclusive = ConsumerProfile::shared("cloud-worker");
+    let err = client
+        .receive_for(&non_exclusive)
+        .await
+        .expect_err("a non-exclusive profile must be refused");
+    assert!(err.to_string().contains("exclusive"));
+
+    // policy: an exclusive profile presenting a replica_id nobody registered is refused.
+    let unknown_replica = ConsumerProfile::shared("desktop")
+        .with_replica_id(Uuid::now_v7())
+        .exclusive();
+    let err = client
+        .receive_for(&unknown_replica)
+        .await
+        .expect_err("an unregistered replica_id must be refused");
+    assert!(err.to_string().contains("unknown replica_id"));
+
+    // ownership: register a replica under `ctx`'s principal, then presenting that replica_id from
+    // the same principal must be accepted (dispatched through to the real broker underneath).
+    let registration = db
+        .register_replica(
+            ReplicaRegistrationRequest {
+                replica_type: ReplicaKind::Worker,
+                instance_id: "desktop-test".into(),
+                runtime_id: Uuid::new_v4().to_string(),
+                display_name: None,
+                host: None,
+                port: None,
+                base_path: None,
+                version: None,
+                attributes: runinator_models::json!({}),
+            },
+            None,
+            &ctx,
+        )
+        .await
         .unwrap();
-    crate::repository::resolve_approval(&db, approval_id, true, None, None, None)
+    let owned_profile = ConsumerProfile::shared("desktop")
+        .with_replica_id(registration.replica_id)
+        .exclusive();
+    inner_broker
+        .publish(BrokerMessage {
+            command: {
+                let mut command =
+                    action_command(Uuid::now_v7(), Uuid::now_v7(), "relay-ownership-node");
+                command.target = ActionTarget::Replica {
+                    replica_id: registration.replica_id,
+                };
+                command
+            },
+            dedupe_key: Some("relay-ownership-test".into()),
+            enqueued_at: chrono::Utc::now(),
+            priority: 0,
+            queue: None,
+        })
+        .await
+        .unwrap();
+    let delivery = tokio::time::timeout(
+        std::time::Duration::from_secs(5),
+        client.receive_for(&owned_profile),
+    )
+    .await
+    .unwrap()
+    .expect("the owning principal must be able to receive its own replica's targeted action");
+    client
+        .ack(&owned_profile.id, delivery.delivery_id)
+        .await
+        .unwrap();
+
+    // policy: a disallowed op (publishing a general action) is refused even though the connection
+    // and the broker underneath both work fine, per the two checks above.
+    let err = client
+        .publish(BrokerMessage {
+            command: action_command(Uuid::now_v7(), Uuid::now_v7(), "relay-disallowed-node"),
+            dedupe_key: Some("relay-disallowed-test".into()),
+            enqueued_at: chrono::Utc::now(),
+            priority: 0,
+            queue: None,
+        })
+        .await
+        .expect_err("publish must not be permitted over the desktop-worker relay");
+    assert!(err.to_string().contains("not permitted"));
+
+    // allow-list: control-channel receive/ack is permitted (a desktop worker needs it for cancel).
+    inner_broker
+        .publish_control(ControlCommand::new(
+            Uuid::now_v7(),
+            runinator_comm::ControlKind::Cancel,
+        ))
+        .await
+        .unwrap();
+    let control_delivery = tokio::time::timeout(
+        std::time::Duration::from_secs(5),
+        client.receive_control("desktop-control"),
+    )
+    .await
+    .unwrap()
+    .expect("receive_control must be permitted over the desktop-worker relay");
+    client
+        .ack_control("desktop-control", control_delivery.delivery_id)
         .await
-        .unwrap();
+        .expect("ack_control must be permitted over the desktop-worker relay");
 
-    // resolution should have enqueued a ready node; draining now completes the run.
-    let run = run_to_completion(&db, run_id).await;
-    assert_eq!(run.status, WorkflowStatus::Succeeded);
+    server.abort();
     let _ = std::fs::remove_file(path);
 }
 
-#[tokio::test]
-async fn reducer_subflow_waits_for_child_and_child_terminal_wakes_parent() {
-    let (db, path) = test_db().await;
+// ---- capability resolver (authz::capabilities_for / require_capability) ----
 
-    // child workflow that completes on its own.
-    let mut child = workflow(None, "child-flow");
-    child.definition = WorkflowGraph::from_value(json!({
-        "start": "start",
-        "nodes": [
-            { "id": "start", "kind": "start", "transitions": { "next": { "$node": "done" } } },
-            { "id": "done", "kind": "end" }
-        ]
-    }))
-    .unwrap();
-    let child = db.upsert_workflow(&child).await.unwrap();
-    let child_id = child.id.unwrap();
+fn auth_ctx(is_admin: bool, org_role: Option<OrgRole>) -> AuthContext {
+    AuthContext {
+        principal_id: Some(Uuid::now_v7()),
+        is_admin,
+        kind: PrincipalKind::User,
+        org_id: org_role.map(|_| Uuid::now_v7()),
+        org_role,
+    }
+}
 
-    // parent that launches the child as a waiting subflow.
-    let mut parent = workflow(None, "parent-flow");
-    parent.definition = WorkflowGraph::from_value(json!({
-        "start": "start",
-        "nodes": [
-            { "id": "start", "kind": "start", "transitions": { "next": { "$node": "sub" } } },
-            {
-                "id": "sub",
-                "kind": "subflow",
-                "subflow_id": child_id,
-                "subflow": { "type": "wait" },
-                "transitions": { "on_success": { "$node": "done" } }
-            },
-            { "id": "done", "kind": "end" }
-        ]
-    }))
-    .unwrap();
-    let parent = db.upsert_workflow(&parent).await.unwrap();
-    let parent_run = crate::repository::create_workflow_run(
-        &db,
-        parent.id.unwrap(),
-        json!({}),
-        false,
-        None,
-        Default::default(),
-    )
-    .await
-    .unwrap();
+#[test]
+fn org_visible_matches_ui_event_egress_policy() {
+    let org_a = Uuid::now_v7();
+    let org_b = Uuid::now_v7();
+    let admin = AuthContext {
+        principal_id: Some(Uuid::now_v7()),
+        is_admin: true,
+        kind: PrincipalKind::User,
+        org_id: Some(org_a),
+        org_role: Some(OrgRole::Admin),
+    };
+    let member_a = AuthContext {
+        principal_id: Some(Uuid::now_v7()),
+        is_admin: false,
+        kind: PrincipalKind::User,
+        org_id: Some(org_a),
+        org_role: Some(OrgRole::Member),
+    };
+    let member_b = AuthContext {
+        principal_id: Some(Uuid::now_v7()),
+        is_admin: false,
+        kind: PrincipalKind::User,
+        org_id: Some(org_b),
+        org_role: Some(OrgRole::Member),
+    };
 
-    // draining drives the parent to launch + the child to completion; the terminal child wakes the
-    // parent's subflow node, which then transitions to its end.
-    drain_ready_nodes(&db).await;
-    let (run, _) = crate::repository::fetch_workflow_run(&db, parent_run.id)
-        .await
-        .unwrap()
-        .unwrap();
+    // platform admin sees every scoped and unscoped event.
+    assert!(crate::authz::org_visible(&admin, Some(org_a)));
+    assert!(crate::authz::org_visible(&admin, Some(org_b)));
+    assert!(crate::authz::org_visible(&admin, None));
+    // unscoped (rollout / global) tips stay visible to every client.
+    assert!(crate::authz::org_visible(&member_a, None));
+    assert!(crate::authz::org_visible(&member_b, None));
+    // scoped tips only reach the matching active org.
+    assert!(crate::authz::org_visible(&member_a, Some(org_a)));
+    assert!(!crate::authz::org_visible(&member_a, Some(org_b)));
+    assert!(!crate::authz::org_visible(&member_b, Some(org_a)));
+}
+
+#[test]
+fn platform_admin_holds_every_capability() {
+    let ctx = auth_ctx(true, None);
+    let caps = crate::authz::capabilities_for(&ctx);
+    for cap in runinator_models::capabilities::Capability::ALL {
+        assert!(caps.contains(cap), "admin must hold {cap:?}");
+    }
+}
+
+#[test]
+fn disabled_admin_holds_every_capability() {
+    let caps = crate::authz::capabilities_for(&AuthContext::disabled_admin());
     assert_eq!(
-        run.status,
-        WorkflowStatus::Succeeded,
-        "parent run should complete after child finishes, got {:?}",
-        run.status
+        caps.len(),
+        runinator_models::capabilities::Capability::ALL.len()
     );
-    let _ = std::fs::remove_file(path);
 }
 
-/// build a `managed_by: wdl` chained trigger for `source_id` targeting `target_name` on `on`.
-fn chained_trigger(source_id: Uuid, target_name: &str, on: &str) -> WorkflowTrigger {
-    WorkflowTrigger {
-        id: None,
-        workflow_id: source_id,
-        kind: WorkflowTriggerKind::Chained,
-        enabled: true,
-        configuration: json!({ "on": on, "target_workflow": target_name, "parameters": {} }),
-        next_execution: None,
-        blackout_start: None,
-        blackout_end: None,
-        metadata: json!({ "managed_by": "wdl" }),
-        created_at: None,
-        updated_at: None,
-    }
+#[test]
+fn org_admin_holds_only_org_capabilities() {
+    use runinator_models::capabilities::Capability;
+    let ctx = auth_ctx(false, Some(OrgRole::Admin));
+    let caps = crate::authz::capabilities_for(&ctx);
+    assert!(caps.contains(&Capability::OrgMembersManage));
+    assert!(caps.contains(&Capability::OrgNodesScale));
+    assert!(!caps.contains(&Capability::UsersManage));
+    assert!(!caps.contains(&Capability::SecretsRead));
+    assert!(!caps.contains(&Capability::NodesScale));
 }
 
-#[tokio::test]
-async fn reducer_chains_target_on_source_success() {
-    let (db, path) = test_db().await;
+#[test]
+fn org_member_holds_no_capabilities() {
+    let ctx = auth_ctx(false, Some(OrgRole::Member));
+    assert!(crate::authz::capabilities_for(&ctx).is_empty());
+}
 
-    let target = db
-        .upsert_workflow(&workflow(None, "chain-target-b"))
-        .await
-        .unwrap();
-    let target_id = target.id.unwrap();
-    let source = db
-        .upsert_workflow(&workflow(None, "chain-source-a"))
-        .await
-        .unwrap();
-    let source_id = source.id.unwrap();
-    db.upsert_workflow_trigger(&chained_trigger(source_id, "chain-target-b", "success"))
-        .await
-        .unwrap();
+#[test]
+fn require_capability_gates_by_holder() {
+    use runinator_models::capabilities::Capability;
+    let admin = auth_ctx(true, None);
+    let member = auth_ctx(false, Some(OrgRole::Member));
+    let org_admin = auth_ctx(false, Some(OrgRole::Admin));
 
-    let source_run = crate::repository::create_workflow_run(
-        &db,
-        source_id,
-        json!({}),
-        false,
-        None,
-        Default::default(),
-    )
-    .await
-    .unwrap();
-    drain_ready_nodes(&db).await;
+    assert!(crate::authz::require_capability(&admin, Capability::UsersManage).is_ok());
+    assert!(crate::authz::require_capability(&member, Capability::UsersManage).is_err());
+    assert!(crate::authz::require_capability(&org_admin, Capability::UsersManage).is_err());
+    assert!(crate::authz::require_capability(&org_admin, Capability::OrgMembersManage).is_ok());
+}
 
-    let (run, _) = crate::repository::fetch_workflow_run(&db, source_run.id)
+#[tokio::test]
+async fn me_returns_resolved_capabilities() {
+    let (db, path) = test_db().await;
+    let db = Arc::new(db);
+    let user = db
+        .create_user("member".into(), None, false, None)
         .await
-        .unwrap()
         .unwrap();
-    assert_eq!(run.status, WorkflowStatus::Succeeded);
+    let user_id = user.id.expect("user id");
 
-    // the target started exactly once, provenance-stamped as chained.
-    let target_runs = db
-        .fetch_workflow_runs_for_workflow(target_id)
-        .await
-        .unwrap();
-    assert_eq!(target_runs.len(), 1, "expected one chained target run");
-    assert_eq!(
-        target_runs[0].trigger_source_kind,
-        Some(runinator_models::replicas::TriggerSourceKind::Chained)
-    );
-    assert_eq!(
-        target_runs[0].trigger_metadata.get("chained_from_run_id"),
-        Some(&json!(source_run.id))
-    );
+    // an org admin sees the org capabilities but none of the platform ones.
+    let ctx = AuthContext {
+        principal_id: Some(user_id),
+        is_admin: false,
+        kind: PrincipalKind::User,
+        org_id: Some(Uuid::now_v7()),
+        org_role: Some(OrgRole::Admin),
+    };
+    let (status, Json(body)) =
+        crate::handlers::auth::me::<SqliteDb>(Extension(db.clone()), Extension(ctx)).await;
+    assert_eq!(status, StatusCode::OK);
+    let crate::models::ApiResponse::JsonValue(value) = body else {
+        panic!("me response must be json");
+    };
+    let capabilities: Vec<String> = value
+        .get("capabilities")
+        .and_then(|caps| caps.as_array())
+        .expect("capabilities array")
+        .iter()
+        .filter_map(|cap| cap.as_str().map(str::to_string))
+        .collect();
+    assert!(capabilities.contains(&"org:members:manage".to_string()));
+    assert!(!capabilities.contains(&"users:manage".to_string()));
 
-    // re-draining the same terminal run must not start a duplicate (firing dedupe).
-    drain_ready_nodes(&db).await;
-    let target_runs = db
-        .fetch_workflow_runs_for_workflow(target_id)
-        .await
-        .unwrap();
-    assert_eq!(
-        target_runs.len(),
-        1,
-        "chain must be exactly-once per source run"
-    );
     let _ = std::fs::remove_file(path);
 }
 
+// the run-health digest only aggregates runs of workflows the caller can see.
 #[tokio::test]
-async fn reducer_chain_respects_status_selector() {
-    let (db, path) = test_db().await;
+async fn run_health_summary_is_scoped_to_visible_workflows() {
+    use axum::extract::Query;
 
-    let target = db
-        .upsert_workflow(&workflow(None, "chain-target-fail"))
+    let (db, path) = test_db().await;
+    let db = Arc::new(db);
+    let mut failed = Vec::new();
+    for name in ["visible", "hidden"] {
+        let workflow_id = crate::repository::upsert_workflow(db.as_ref(), &workflow(None, name))
+            .await
+            .unwrap()
+            .id
+            .unwrap();
+        let run = crate::repository::create_workflow_run(
+            db.as_ref(),
+            workflow_id,
+            json!({}),
+            false,
+            None,
+            Default::default(),
+        )
         .await
         .unwrap();
-    let target_id = target.id.unwrap();
-    let source = db
-        .upsert_workflow(&workflow(None, "chain-source-ok"))
+        crate::repository::update_workflow_run_status(
+            db.as_ref(),
+            run.id,
+            WorkflowStatus::Failed,
+            None,
+            None,
+            Some("boom".into()),
+        )
         .await
         .unwrap();
-    let source_id = source.id.unwrap();
-    // only chains on failure; the source succeeds, so nothing should start.
-    db.upsert_workflow_trigger(&chained_trigger(source_id, "chain-target-fail", "failure"))
+        failed.push(workflow_id);
+    }
+    let user_id = db
+        .create_user("digest-reader".into(), None, false, None)
         .await
+        .unwrap()
+        .id
         .unwrap();
-
-    let source_run = crate::repository::create_workflow_run(
-        &db,
-        source_id,
-        json!({}),
-        false,
-        None,
-        Default::default(),
-    )
+    db.create_grant(grant(
+        failed[0],
+        PrincipalType::User,
+        user_id,
+        Permission::View,
+    ))
     .await
     .unwrap();
-    drain_ready_nodes(&db).await;
 
-    let (run, _) = crate::repository::fetch_workflow_run(&db, source_run.id)
-        .await
-        .unwrap()
-        .unwrap();
-    assert_eq!(run.status, WorkflowStatus::Succeeded);
-    let target_runs = db
-        .fetch_workflow_runs_for_workflow(target_id)
-        .await
-        .unwrap();
-    assert!(
-        target_runs.is_empty(),
-        "on_failure trigger must not fire when the source succeeds"
-    );
+    let (status, body) = crate::handlers::runs::get_workflow_run_health::<SqliteDb>(
+        Extension(db.clone()),
+        Extension(user_ctx(user_id)),
+        Query(crate::models::RunHealthQuery::default()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let crate::models::ApiResponse::RunHealthReport(report) = body.0 else {
+        panic!("expected a run health report");
+    };
+    assert_eq!(report.summary.total_runs, 1);
+    assert_eq!(report.summary.failed, 1);
+    assert_eq!(report.summary.success_rate, Some(0.0));
+    assert_eq!(report.summary.new_failures.len(), 1);
+    assert_eq!(report.summary.new_failures[0].workflow_name, "visible");
+    assert!(report.text.contains("visible  1 failure(s)"));
+    assert!(!report.text.contains("hidden"));
+
     let _ = std::fs::remove_file(path);
 }
 
+// a running status carrying a queue wait is recorded on the node run, raises a warning notification
+// once it exceeds the consumer's threshold, and feeds the queue wait report.
 #[tokio::test]
-async fn reducer_chain_depth_is_bounded() {
+async fn result_consumer_records_queue_wait_and_alerts_over_threshold() {
+    use axum::extract::Query;
+
     let (db, path) = test_db().await;
+    let db = Arc::new(db);
+    let node_run = create_node_run(&db).await;
+    let command = action_command(node_run.workflow_run_id, node_run.id, &node_run.node_id);
+    let broker = Arc::new(RecordingBroker::new());
+    let broker_for_consumer: Arc<dyn Broker> = broker.clone();
+    let publisher = runinator_engine::EnginePublisher::new(broker_for_consumer.clone());
+    let shutdown = Arc::new(Notify::new());
+    let consumer = tokio::spawn(crate::result_consumer::run_result_consumer_with_policy(
+        db.clone(),
+        broker_for_consumer,
+        publisher,
+        shutdown.clone(),
+        crate::result_consumer::ResultConsumerPolicy::new(2, Duration::from_millis(1))
+            .with_queue_wait_alert(Some(Duration::from_secs(1))),
+    ));
 
-    // a workflow that chains to itself would loop forever without the depth cap.
-    let a = db
-        .upsert_workflow(&workflow(None, "chain-loop-a"))
+    broker
+        .publish_result(ResultMessage {
+            event: WorkflowResultEvent::running(&command, 5_000),
+            dedupe_key: None,
+            enqueued_at: chrono::Utc::now(),
+        })
         .await
         .unwrap();
-    let a_id = a.id.unwrap();
-    db.upsert_workflow_trigger(&chained_trigger(a_id, "chain-loop-a", "success"))
+    wait_until(|| broker.result_acks().len() == 1).await;
+
+    shutdown.notify_waiters();
+    tokio::time::timeout(Duration::from_secs(1), consumer)
         .await
+        .unwrap()
         .unwrap();
 
-    crate::repository::create_workflow_run(&db, a_id, json!({}), false, None, Default::default())
+    let stored = db
+        .fetch_workflow_node_run(node_run.id)
         .await
+        .unwrap()
         .unwrap();
-    drain_ready_nodes(&db).await;
+    assert_eq!(stored.status, WorkflowStatus::Running);
+    assert_eq!(stored.queue_wait_ms, Some(5_000));
+
+    let notifications = db.fetch_notifications(false, 10).await.unwrap();
+    assert_eq!(notifications.len(), 1);
+    assert_eq!(notifications[0].channel, "queue_wait");
+    assert_eq!(notifications[0].severity, "warning");
+    assert_eq!(
+        notifications[0].workflow_node_id.as_deref(),
+        Some(node_run.node_id.as_str())
+    );
+
+    let (status, body) = crate::handlers::runs::get_queue_wait::<SqliteDb>(
+        Extension(db.clone()),
+        Extension(AuthContext {
+            principal_id: None,
+            is_admin: true,
+            kind: PrincipalKind::User,
+            org_id: None,
+            org_role: None,
+        }),
+        Query(crate::models::QueueWaitQuery::default()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let crate::models::ApiResponse::QueueWaitReport(report) = body.0 else {
+        panic!("expected a queue wait report");
+    };
+    assert_eq!(report.overall.count, 1);
+    assert_eq!(report.overall.max_ms, 5_000);
+    assert_eq!(report.tasks.len(), 1);
+    assert_eq!(report.tasks[0].node_id, node_run.node_id);
 
-    // depth 0 (original) chains through depth 32, which is the cap and stops: 33 runs total.
-    let runs = db.fetch_workflow_runs_for_workflow(a_id).await.unwrap();
-    assert_eq!(runs.len(), 33, "self-chain must stop at MAX_CHAIN_DEPTH");
     let _ = std::fs::remove_file(path);
 }
 
-/// end-to-end coverage for the `/ws/desktop-worker` relay: a minimal router carrying just that one
-/// route (plus its `Extension`s) rather than the full `build_router` stack, since the policy
-/// allow-list and replica-ownership check are this handler's own logic, independent of auth
-/// middleware/CORS/rate-limiting already covered elsewhere. Run with `--features ws` (the real
-/// `WsBroker` client needs `runinator-broker`'s `ws` feature; without it every call would just return
-/// `FeatureDisabled` immediately, which isn't what this test wants to exercise).
-#[cfg(feature = "ws")]
 #[tokio::test]
-async fn ws_desktop_worker_relay_enforces_policy_and_ownership() {
-    use axum::{Router, routing::get};
-    use runinator_broker::ConsumerProfile;
-    use runinator_broker::ws::client::WsBroker;
-    use runinator_comm::ActionTarget;
-    use runinator_models::replicas::{ReplicaKind, ReplicaRegistrationRequest};
-    use tokio::net::TcpListener;
+async fn upcoming_schedule_merges_queued_work_and_projected_firings() {
+    use axum::extract::Query;
+    use runinator_models::upcoming::UpcomingKind;
 
     let (db, path) = test_db().await;
     let db = Arc::new(db);
-    let inner_broker = Arc::new(InMemoryBroker::new());
-    let broker_ext: Arc<dyn Broker> = inner_broker.clone();
-    let ctx = AuthContext {
-        principal_id: Some(Uuid::now_v7()),
-        is_admin: false,
-        kind: PrincipalKind::Service,
-        org_id: None,
-        org_role: None,
-    };
-
-    let router = Router::new()
-        .route(
-            "/ws/desktop-worker",
-            get(crate::websocket::ws_desktop_worker::<SqliteDb>),
-        )
-        .layer(Extension(db.clone()))
-        .layer(Extension(broker_ext))
-        .layer(Extension(ctx.clone()));
-
-    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
-    let addr = listener.local_addr().unwrap();
-    let server = tokio::spawn(async move {
-        let _ = axum::serve(listener, router).await;
-    });
+    let node_run = create_node_run(&db).await;
+    let workflow_id = db
+        .fetch_workflow_run(node_run.workflow_run_id)
+        .await
+        .unwrap()
+        .unwrap()
+        .workflow_id;
+    let waiting = crate::repository::create_workflow_run(
+        db.as_ref(),
+        workflow_id,
+        json!({}),
+        false,
+        None,
+        Default::default(),
+    )
+    .await
+    .unwrap();
+    // the quarter hour just passed, so only the stored slot is overdue: a slot a fixed time ago
+    // could have another quarter hour between it and now.
+    let overdue_slot =
+        chrono::DurationRound::duration_trunc(chrono::Utc::now(), chrono::Duration::minutes(15))
+            .unwrap();
+    let saved = crate::repository::upsert_workflow_trigger(
+        db.as_ref(),
+        &WorkflowTrigger {
+            kind: WorkflowTriggerKind::Cron,
+            configuration: json!({ "cron": "0 */15 * * * *" }),
+            next_execution: Some(overdue_slot),
+            ..trigger(None, workflow_id)
+        },
+    )
+    .await
+    .unwrap();
 
-    let client = WsBroker::connect(format!("ws://{addr}/ws/desktop-worker"), None);
+    let (status, body) = crate::handlers::runs::get_upcoming_schedule::<SqliteDb>(
+        Extension(db.clone()),
+        Extension(AuthContext {
+            principal_id: None,
+            is_admin: true,
+            kind: PrincipalKind::User,
+            org_id: None,
+            org_role: None,
+        }),
+        Query(crate::models::UpcomingQuery { minutes: Some(60) }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let crate::models::ApiResponse::UpcomingSchedule(upcoming) = body.0 else {
+        panic!("expected an upcoming schedule");
+    };
 
-    // policy: a non-exclusive profile is refused outright, regardless of replica_id/labels.
-    let non_exclusive = ConsumerProfile::shared("cloud-worker");
-    let err = client
-        .receive_for(&non_exclusive)
-        .await
-        .expect_err("a non-exclusive profile must be refused");
-    assert!(err.to_string().contains("exclusive"));
+    // the queued node run and the run no scheduler has picked up yet.
+    assert_eq!(upcoming.running, 0);
+    assert_eq!(upcoming.queued, 2);
+    let queued = upcoming
+        .entries
+        .iter()
+        .filter(|entry| entry.kind == UpcomingKind::Queued)
+        .collect::<Vec<_>>();
+    assert!(
+        queued
+            .iter()
+            .any(|entry| entry.node_id.as_deref() == Some("node-a"))
+    );
+    assert!(
+        queued
+            .iter()
+            .any(|entry| entry.workflow_run_id == Some(waiting.id) && entry.node_id.is_none())
+    );
 
-    // policy: an exclusive profile presenting a replica_id nobody registered is refused.
-    let unknown_replica = ConsumerProfile::shared("desktop")
-        .with_replica_id(Uuid::now_v7())
-        .exclusive();
-    let err = client
-        .receive_for(&unknown_replica)
-        .await
-        .expect_err("an unregistered replica_id must be refused");
-    assert!(err.to_string().contains("unknown replica_id"));
+    // the overdue stored slot, then every quarter hour through the window.
+    let scheduled = upcoming
+        .entries
+        .iter()
+        .filter(|entry| entry.kind == UpcomingKind::Scheduled)
+        .collect::<Vec<_>>();
+    assert!((5..=6).contains(&scheduled.len()), "{scheduled:?}");
+    assert_eq!(Some(scheduled[0].at), saved.next_execution);
+    assert!(scheduled[0].overdue);
+    assert!(
+        scheduled[1..]
+            .iter()
+            .all(|entry| !entry.overdue && entry.at <= upcoming.until)
+    );
+    assert!(scheduled.iter().all(|entry| entry.trigger_id == saved.id));
+    assert_eq!(
+        upcoming.entries.last().unwrap().kind,
+        UpcomingKind::Scheduled
+    );
 
-    // ownership: register a replica under `ctx`'s principal, then presenting that replica_id from
-    // the same principal must be accepted (dispatched through to the real broker underneath).
-    let registration = db
-        .register_replica(
-            ReplicaRegistrationRequest {
-                replica_type: ReplicaKind::Worker,
-                instance_id: "desktop-test".into(),
-                runtime_id: Uuid::new_v4().to_string(),
-                display_name: None,
-                host: None,
-                port: None,
-                base_path: None,
-                version: None,
-                attributes: runinator_models::json!({}),
-            },
-            None,
-            &ctx,
-        )
-        .await
-        .unwrap();
-    let owned_profile = ConsumerProfile::shared("desktop")
-        .with_replica_id(registration.replica_id)
-        .exclusive();
-    inner_broker
-        .publish(BrokerMessage {
-            command: {
-                let mut command =
-                    action_command(Uuid::now_v7(), Uuid::now_v7(), "relay-ownership-node");
-                command.target = ActionTarget::Replica {
-                    replica_id: registration.replica_id,
-                };
-                command
-            },
-            dedupe_key: Some("relay-ownership-test".into()),
-            enqueued_at: chrono::Utc::now(),
-        })
-        .await
-        .unwrap();
-    let delivery = tokio::time::timeout(
-        std::time::Duration::from_secs(5),
-        client.receive_for(&owned_profile),
-    )
-    .await
-    .unwrap()
-    .expect("the owning principal must be able to receive its own replica's targeted action");
-    client
-        .ack(&owned_profile.id, delivery.delivery_id)
-        .await
-        .unwrap();
+    let _ = std::fs::remove_file(path);
+}
 
-    // policy: a disallowed op (publishing a general action) is refused even though the connection
-    // and the broker underneath both work fine, per the two checks above.
-    let err = client
-        .publish(BrokerMessage {
-            command: action_command(Uuid::now_v7(), Uuid::now_v7(), "relay-disallowed-node"),
-            dedupe_key: Some("relay-disallowed-test".into()),
-            enqueued_at: chrono::Utc::now(),
-        })
-        .await
-        .expect_err("publish must not be permitted over the desktop-worker relay");
-    assert!(err.to_string().contains("not permitted"));
+#[tokio::test]
+async fn scheduler_preview_defers_blacked_out_slots_without_enqueueing() {
+    use axum::extract::Query;
 
-    // allow-list: control-channel receive/ack is permitted (a desktop worker needs it for cancel).
-    inner_broker
-        .publish_control(ControlCommand::new(
-            Uuid::now_v7(),
-            runinator_comm::ControlKind::Cancel,
-        ))
+    let (db, path) = test_db().await;
+    let db = Arc::new(db);
+    let workflow = crate::repository::upsert_workflow(db.as_ref(), &workflow(None, "previewed"))
         .await
         .unwrap();
-    let control_delivery = tokio::time::timeout(
-        std::time::Duration::from_secs(5),
-        client.receive_control("desktop-control"),
+    let workflow_id = workflow.id.unwrap();
+    let now = chrono::Utc::now();
+    let saved = crate::repository::upsert_workflow_trigger(
+        db.as_ref(),
+        &WorkflowTrigger {
+            kind: WorkflowTriggerKind::Cron,
+            configuration: json!({ "cron": "0 */15 * * * *" }),
+            next_execution: Some(now + chrono::Duration::minutes(10)),
+            blackout_start: Some(now),
+            blackout_end: Some(now + chrono::Duration::minutes(20)),
+            ..trigger(None, workflow_id)
+        },
     )
     .await
-    .unwrap()
-    .expect("receive_control must be permitted over the desktop-worker relay");
-    client
-        .ack_control("desktop-control", control_delivery.delivery_id)
+    .unwrap();
+
+    let (status, body) = crate::handlers::runs::get_scheduler_preview::<SqliteDb>(
+        Extension(db.clone()),
+        Extension(AuthContext {
+            principal_id: None,
+            is_admin: true,
+            kind: PrincipalKind::User,
+            org_id: None,
+            org_role: None,
+        }),
+        Query(crate::models::UpcomingQuery { minutes: Some(60) }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let crate::models::ApiResponse::SchedulerPreview(preview) = body.0 else {
+        panic!("expected a scheduler preview");
+    };
+
+    // the stored slot falls in the blackout and fires when it ends; later slots fire on time.
+    assert_eq!(preview.deferred, 1);
+    assert!(preview.invalid.is_empty());
+    let first = &preview.firings[0];
+    assert_eq!(first.trigger_id, saved.id.unwrap());
+    assert_eq!(first.workflow_name, "previewed");
+    assert_eq!(Some(first.slot), saved.next_execution);
+    assert_eq!(Some(first.fires_at), saved.blackout_end);
+    assert!(first.deferred);
+    assert!(
+        (2..=3).contains(&(preview.firings.len() - 1)),
+        "{preview:?}"
+    );
+    assert!(preview.firings[1..].iter().all(|firing| {
+        !firing.deferred && firing.fires_at == firing.slot && firing.fires_at <= preview.until
+    }));
+
+    // nothing was claimed or enqueued.
+    let stored = crate::repository::fetch_workflow_trigger(db.as_ref(), saved.id.unwrap())
         .await
-        .expect("ack_control must be permitted over the desktop-worker relay");
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.next_execution, saved.next_execution);
+    assert!(
+        crate::repository::fetch_workflow_runs_by_status(db.as_ref(), WorkflowStatus::Queued)
+            .await
+            .unwrap()
+            .is_empty()
+    );
 
-    server.abort();
     let _ = std::fs::remove_file(path);
 }
 
-// ---- capability resolver (authz::capabilities_for / require_capability) ----
-
-fn auth_ctx(is_admin: bool, org_role: Option<OrgRole>) -> AuthContext {
-    AuthContext {
-        principal_id: Some(Uuid::now_v7()),
-        is_admin,
-        kind: PrincipalKind::User,
-        org_id: org_role.map(|_| Uuid::now_v7()),
-        org_role,
-    }
-}
+#[tokio::test]
+async fn cron_firing_is_skipped_while_the_last_success_is_fresh() {
+    let (db, path) = test_db().await;
+    let mut fresh = workflow(None, "cache-refresh");
+    fresh.definition.metadata = json!({ "freshness_seconds": 3600 });
+    let workflow_id = crate::repository::upsert_workflow(&db, &fresh)
+        .await
+        .unwrap()
+        .id
+        .unwrap();
+    let succeeded = crate::repository::create_workflow_run(
+        &db,
+        workflow_id,
+        json!({}),
+        false,
+        None,
+        Default::default(),
+    )
+    .await
+    .unwrap();
+    crate::repository::update_workflow_run_status(
+        &db,
+        succeeded.id,
+        WorkflowStatus::Succeeded,
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    let now = chrono::Utc::now();
+    crate::repository::upsert_workflow_trigger(
+        &db,
+        &WorkflowTrigger {
+            kind: WorkflowTriggerKind::Cron,
+            configuration: json!({ "cron": "0 */5 * * * *" }),
+            next_execution: Some(now - chrono::Duration::seconds(5)),
+            ..trigger(None, workflow_id)
+        },
+    )
+    .await
+    .unwrap();
 
-#[test]
-fn org_visible_matches_ui_event_egress_policy() {
-    let org_a = Uuid::now_v7();
-    let org_b = Uuid::now_v7();
-    let admin = AuthContext {
-        principal_id: Some(Uuid::now_v7()),
-        is_admin: true,
-        kind: PrincipalKind::User,
-        org_id: Some(org_a),
-        org_role: Some(OrgRole::Admin),
-    };
-    let member_a = AuthContext {
-        principal_id: Some(Uuid::now_v7()),
-        is_admin: false,
-        kind: PrincipalKind::User,
-        org_id: Some(org_a),
-        org_role: Some(OrgRole::Member),
-    };
-    let member_b = AuthContext {
-        principal_id: Some(Uuid::now_v7()),
-        is_admin: false,
-        kind: PrincipalKind::User,
-        org_id: Some(org_b),
-        org_role: Some(OrgRole::Member),
-    };
+    let runs = crate::repository::claim_due_workflow_trigger_firings_at(
+        &db,
+        "scheduler-1".into(),
+        now,
+        10,
+    )
+    .await
+    .unwrap();
+    assert_eq!(runs.len(), 1);
+    assert_eq!(runs[0].status, WorkflowStatus::Canceled);
+    assert!(
+        runs[0]
+            .message
+            .as_deref()
+            .is_some_and(|message| message.contains("freshness_seconds")),
+        "{:?}",
+        runs[0].message
+    );
 
-    // platform admin sees every scoped and unscoped event.
-    assert!(crate::authz::org_visible(&admin, Some(org_a)));
-    assert!(crate::authz::org_visible(&admin, Some(org_b)));
-    assert!(crate::authz::org_visible(&admin, None));
-    // unscoped (rollout / global) tips stay visible to every client.
-    assert!(crate::authz::org_visible(&member_a, None));
-    assert!(crate::authz::org_visible(&member_b, None));
-    // scoped tips only reach the matching active org.
-    assert!(crate::authz::org_visible(&member_a, Some(org_a)));
-    assert!(!crate::authz::org_visible(&member_a, Some(org_b)));
-    assert!(!crate::authz::org_visible(&member_b, Some(org_a)));
+    let _ = std::fs::remove_file(path);
 }
 
-#[test]
-fn platform_admin_holds_every_capability() {
-    let ctx = auth_ctx(true, None);
-    let caps = crate::authz::capabilities_for(&ctx);
-    for cap in runinator_models::capabilities::Capability::ALL {
-        assert!(caps.contains(cap), "admin must hold {cap:?}");
-    }
-}
+#[tokio::test]
+async fn missed_run_checker_alerts_once_per_missed_deadline() {
+    use runinator_engine::missed_runs::{MISSED_RUN_CHANNEL, check_missed_runs};
 
-#[test]
-fn disabled_admin_holds_every_capability() {
-    let caps = crate::authz::capabilities_for(&AuthContext::disabled_admin());
-    assert_eq!(
-        caps.len(),
-        runinator_models::capabilities::Capability::ALL.len()
+    let (db, path) = test_db().await;
+    let publisher = runinator_engine::EnginePublisher::new(Arc::new(RecordingBroker::new()));
+    let mut invalid = workflow(None, "nightly-export");
+    invalid.definition.metadata = json!({ "expected_max_interval_seconds": 0 });
+    assert!(
+        crate::repository::upsert_workflow(&db, &invalid)
+            .await
+            .unwrap_err()
+            .to_string()
+            .contains("RUNI148")
     );
-}
 
-#[test]
-fn org_admin_holds_only_org_capabilities() {
-    use runinator_models::capabilities::Capability;
-    let ctx = auth_ctx(false, Some(OrgRole::Admin));
-    let caps = crate::authz::capabilities_for(&ctx);
-    assert!(caps.contains(&Capability::OrgMembersManage));
-    assert!(caps.contains(&Capability::OrgNodesScale));
-    assert!(!caps.contains(&Capability::UsersManage));
-    assert!(!caps.contains(&Capability::SecretsRead));
-    assert!(!caps.contains(&Capability::NodesScale));
-}
+    let mut expected = workflow(None, "nightly-export");
+    expected.definition.metadata = json!({ "expected_max_interval_seconds": 3600 });
+    let workflow_id = crate::repository::upsert_workflow(&db, &expected)
+        .await
+        .unwrap()
+        .id
+        .unwrap();
+    let mut disabled = workflow(None, "retired-export");
+    disabled.enabled = false;
+    disabled.definition.metadata = json!({ "expected_max_interval_seconds": 3600 });
+    crate::repository::upsert_workflow(&db, &disabled)
+        .await
+        .unwrap();
+    let now = chrono::Utc::now();
 
-#[test]
-fn org_member_holds_no_capabilities() {
-    let ctx = auth_ctx(false, Some(OrgRole::Member));
-    assert!(crate::authz::capabilities_for(&ctx).is_empty());
-}
+    // never succeeded: the deadline runs from when the workflow was saved.
+    assert_eq!(check_missed_runs(&db, &publisher, now).await.unwrap(), 0);
+    let later = now + chrono::Duration::hours(2);
+    assert_eq!(check_missed_runs(&db, &publisher, later).await.unwrap(), 1);
+    assert_eq!(check_missed_runs(&db, &publisher, later).await.unwrap(), 0);
+    let alerts = db
+        .fetch_notifications(false, 10)
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|notification| notification.channel == MISSED_RUN_CHANNEL)
+        .collect::<Vec<_>>();
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0].severity, "warning");
+    assert!(alerts[0].title.contains("nightly-export"));
+    assert_eq!(
+        alerts[0].metadata["workflow_id"],
+        json!(workflow_id.to_string())
+    );
 
-#[test]
-fn require_capability_gates_by_holder() {
-    use runinator_models::capabilities::Capability;
-    let admin = auth_ctx(true, None);
-    let member = auth_ctx(false, Some(OrgRole::Member));
-    let org_admin = auth_ctx(false, Some(OrgRole::Admin));
+    // a success moves the deadline on, and missing the new one alerts again. deadlines are kept to
+    // the second, so step past the one the save set before the run finishes.
+    tokio::time::sleep(Duration::from_millis(1100)).await;
+    let run = crate::repository::create_workflow_run(
+        &db,
+        workflow_id,
+        json!({}),
+        false,
+        None,
+        Default::default(),
+    )
+    .await
+    .unwrap();
+    crate::repository::update_workflow_run_status(
+        &db,
+        run.id,
+        WorkflowStatus::Succeeded,
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        check_missed_runs(&db, &publisher, now + chrono::Duration::minutes(30))
+            .await
+            .unwrap(),
+        0
+    );
+    assert_eq!(check_missed_runs(&db, &publisher, later).await.unwrap(), 1);
 
-    assert!(crate::authz::require_capability(&admin, Capability::UsersManage).is_ok());
-    assert!(crate::authz::require_capability(&member, Capability::UsersManage).is_err());
-    assert!(crate::authz::require_capability(&org_admin, Capability::UsersManage).is_err());
-    assert!(crate::authz::require_capability(&org_admin, Capability::OrgMembersManage).is_ok());
+    let _ = std::fs::remove_file(path);
 }
 
+// the startup report is admin-only and describes this replica even when no binary published one.
 #[tokio::test]
-async fn me_returns_resolved_capabilities() {
-    let (db, path) = test_db().await;
-    let db = Arc::new(db);
-    let user = db
-        .create_user("member".into(), None, false, None)
-        .await
-        .unwrap();
-    let user_id = user.id.expect("user id");
+async fn debug_info_requires_settings_manage() {
+    use crate::handlers::observability::get_debug_info;
 
-    // an org admin sees the org capabilities but none of the platform ones.
-    let ctx = AuthContext {
-        principal_id: Some(user_id),
+    let member = AuthContext {
+        principal_id: Some(Uuid::new_v4()),
         is_admin: false,
         kind: PrincipalKind::User,
-        org_id: Some(Uuid::now_v7()),
-        org_role: Some(OrgRole::Admin),
+        org_id: None,
+        org_role: None,
     };
-    let (status, Json(body)) =
-        crate::handlers::auth::me::<SqliteDb>(Extension(db.clone()), Extension(ctx)).await;
+    let (status, _) = get_debug_info(Extension(member)).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, Json(body)) = get_debug_info(Extension(AuthContext::disabled_admin())).await;
     assert_eq!(status, StatusCode::OK);
-    let crate::models::ApiResponse::JsonValue(value) = body else {
-        panic!("me response must be json");
+    let crate::models::ApiResponse::StartupReport(report) = body else {
+        panic!("expected a startup report");
     };
-    let capabilities: Vec<String> = value
-        .get("capabilities")
-        .and_then(|caps| caps.as_array())
-        .expect("capabilities array")
-        .iter()
-        .filter_map(|cap| cap.as_str().map(str::to_string))
-        .collect();
-    assert!(capabilities.contains(&"org:members:manage".to_string()));
-    assert!(!capabilities.contains(&"users:manage".to_string()));
+    assert_eq!(report.component, "Runinator Web Service");
+    assert_eq!(report.pid, std::process::id());
+}
 
-    let _ = std::fs::remove_file(path);
+// with reuse_port a replacement replica can bind the port a running one still holds; without it the
+// second bind is refused.
+#[cfg(unix)]
+#[tokio::test]
+async fn reuse_port_lets_a_second_listener_share_the_port() {
+    let loopback = std::net::SocketAddr::from(([127, 0, 0, 1], 0));
+    let first = crate::server::bind_listener(loopback, true).unwrap();
+    let addr = first.local_addr().unwrap();
+
+    let second = crate::server::bind_listener(addr, true).unwrap();
+    assert_eq!(second.local_addr().unwrap(), addr);
+    drop(second);
+
+    let exclusive = crate::server::bind_listener(loopback, false).unwrap();
+    assert!(crate::server::bind_listener(exclusive.local_addr().unwrap(), false).is_err());
 }